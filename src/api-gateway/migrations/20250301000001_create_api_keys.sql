@@ -0,0 +1,21 @@
+-- Long-lived API keys for programmatic clients, sent in the X-API-Key header
+CREATE TABLE IF NOT EXISTS api_keys (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id TEXT NOT NULL,
+    key_name VARCHAR(255) NOT NULL,
+    -- Hex-encoded SHA-256 of the key; the key itself is never stored
+    key_hash CHAR(64) NOT NULL UNIQUE,
+    -- Leading characters of the key so owners can tell their keys apart
+    key_prefix VARCHAR(16) NOT NULL,
+    -- Permissions granted to requests using the key, e.g. 'workflows:read'
+    scopes TEXT[] NOT NULL DEFAULT '{}',
+    subscription_tier VARCHAR(32) NOT NULL DEFAULT 'free',
+    -- Overrides the subscription tier's per-minute rate limit when set
+    rate_limit_per_minute INTEGER,
+    expires_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ,
+    last_used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_keys_user_id ON api_keys (user_id);
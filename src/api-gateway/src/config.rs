@@ -6,6 +6,8 @@
 
 use serde::Deserialize;
 use std::collections::HashMap;
+use std::net::IpAddr;
+use std::time::Duration;
 
 /// Main configuration for the application
 #[derive(Debug, Clone, Deserialize)]
@@ -20,6 +22,8 @@ pub struct Config {
     pub observability: ObservabilityConfig,
     #[serde(default)] // Use default if 'integrations' is missing
     pub integrations: IntegrationsConfig,
+    #[serde(default)] // Use default if 'input_inspection' is missing
+    pub input_inspection: InputInspectionConfig,
 }
 
 /// Server configuration
@@ -30,6 +34,32 @@ pub struct ServerConfig {
     pub workers: usize,
     pub max_connections: u32,
     pub timeout_seconds: u64,
+    /// Largest request body accepted, in bytes; larger requests get 413
+    #[serde(default = "default_max_body_bytes")]
+    pub max_body_bytes: usize,
+    /// Per-route overrides of `max_body_bytes`
+    #[serde(default)]
+    pub body_limits: Vec<RouteBodyLimit>,
+    /// Seconds in-flight requests get to finish after a shutdown signal
+    #[serde(default = "default_drain_timeout_seconds")]
+    pub drain_timeout_seconds: u64,
+    /// Service discovery base URL; the gateway registers itself there when set
+    #[serde(default)]
+    pub discovery_url: Option<String>,
+    /// Address advertised to service discovery, defaulting to `host`
+    #[serde(default)]
+    pub advertise_host: Option<String>,
+}
+
+/// Body size limit for requests to matching paths, optionally only for some methods
+#[derive(Debug, Clone, Deserialize)]
+pub struct RouteBodyLimit {
+    /// Path pattern, matched like `RouteRateLimit::path`
+    pub path: String,
+    /// Methods the limit applies to; empty means all methods
+    #[serde(default)]
+    pub methods: Vec<String>,
+    pub max_bytes: usize,
 }
 
 /// Database configuration
@@ -67,6 +97,26 @@ pub struct RateLimitConfig {
     pub redis_key_prefix: String,
     pub default_burst_size: u32,
     pub cleanup_interval_seconds: u64,
+    /// Per-route limits that replace the default; the most specific match wins
+    #[serde(default)]
+    pub routes: Vec<RouteRateLimit>,
+    /// Proxies whose `X-Forwarded-For` and `X-Real-IP` identify anonymous clients;
+    /// from anyone else those headers are ignored
+    #[serde(default)]
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+/// Per-minute limit for requests to matching paths, optionally only for some methods
+#[derive(Debug, Clone, Deserialize)]
+pub struct RouteRateLimit {
+    /// Path pattern such as `/v1/intent/parse`, `/v1/workflows/:id/execute` or
+    /// `/v1/analytics/**`. `*` and `:name` match one segment and a trailing `**`
+    /// matches any remainder.
+    pub path: String,
+    /// Methods the limit applies to; empty means all methods
+    #[serde(default)]
+    pub methods: Vec<String>,
+    pub per_minute: u32,
 }
 
 /// Service routing configuration
@@ -74,9 +124,51 @@ pub struct RateLimitConfig {
 pub struct RoutingConfig {
     pub services: HashMap<String, ServiceConfig>,
     pub circuit_breaker_enabled: bool,
+    /// Failure rate (0.0 - 1.0) at which an upstream's circuit opens
     pub circuit_breaker_failure_threshold: f64,
+    /// Seconds an open circuit fails fast before probing the upstream again
     pub circuit_breaker_timeout_seconds: u64,
     pub health_check_interval_seconds: u64,
+    /// Requests an upstream must see in a window before its failure rate counts
+    #[serde(default = "default_circuit_breaker_min_requests")]
+    pub circuit_breaker_min_requests: u32,
+    /// Length of the window failure rates are measured over
+    #[serde(default = "default_circuit_breaker_window_seconds")]
+    pub circuit_breaker_window_seconds: u64,
+    /// Probe requests that must succeed while half-open before the circuit closes
+    #[serde(default = "default_circuit_breaker_half_open_requests")]
+    pub circuit_breaker_half_open_requests: u32,
+    /// GET routes whose successful responses are cached; routes not listed are never cached
+    #[serde(default)]
+    pub cached_routes: Vec<CachedRoute>,
+    /// Writes that invalidate cached responses by tag
+    #[serde(default)]
+    pub cache_invalidations: Vec<CacheInvalidation>,
+    /// Most responses held in the response cache at once
+    #[serde(default = "default_response_cache_max_entries")]
+    pub response_cache_max_entries: usize,
+}
+
+/// A GET route whose successful responses are cached per caller
+#[derive(Debug, Clone, Deserialize)]
+pub struct CachedRoute {
+    /// Path pattern, matched like `RouteRateLimit::path`
+    pub path: String,
+    pub ttl_seconds: u64,
+    /// Tags cached responses are stored under, for invalidation
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Successful requests to matching paths drop cached responses with these tags
+#[derive(Debug, Clone, Deserialize)]
+pub struct CacheInvalidation {
+    /// Path pattern, matched like `RouteRateLimit::path`
+    pub path: String,
+    /// Methods that invalidate; empty means every method except GET, HEAD and OPTIONS
+    #[serde(default)]
+    pub methods: Vec<String>,
+    pub tags: Vec<String>,
 }
 
 /// Individual service configuration
@@ -87,6 +179,33 @@ pub struct ServiceConfig {
     pub timeout_seconds: u64,
     pub retries: u32,
     pub enabled: bool,
+    /// Gateway path prefixes served by this service, e.g. `/v1/intent`
+    #[serde(default)]
+    pub route_prefixes: Vec<String>,
+    /// Per-service overrides of the routing circuit breaker settings
+    #[serde(default)]
+    pub circuit_breaker: CircuitBreakerOverrides,
+}
+
+/// Circuit breaker settings a service can override; unset values use the
+/// `circuit_breaker_*` values from `RoutingConfig`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CircuitBreakerOverrides {
+    pub failure_threshold: Option<f64>,
+    pub timeout_seconds: Option<u64>,
+    pub min_requests: Option<u32>,
+    pub window_seconds: Option<u64>,
+    pub half_open_requests: Option<u32>,
+}
+
+/// Effective circuit breaker settings for one upstream service
+#[derive(Debug, Clone, PartialEq)]
+pub struct CircuitBreakerSettings {
+    pub failure_threshold: f64,
+    pub open_duration: Duration,
+    pub min_requests: u32,
+    pub window: Duration,
+    pub half_open_requests: u32,
 }
 
 /// Observability (metrics and tracing) configuration
@@ -113,6 +232,29 @@ pub struct ZapierConfig {
     pub secret_key: Option<String>,
 }
 
+/// What the gateway does with requests whose input looks like an injection or XSS payload
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum InputInspectionMode {
+    /// Skip inspection entirely
+    Off,
+    /// Log findings and let the request through
+    #[default]
+    Log,
+    /// Log findings and reject the request with 400 Bad Request
+    Block,
+}
+
+/// Request input inspection configuration
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct InputInspectionConfig {
+    pub mode: InputInspectionMode,
+    /// Bodies are inspected up to this size. Larger bodies are rejected in
+    /// block mode; in log mode only their first `max_body_bytes` are inspected.
+    pub max_body_bytes: usize,
+}
+
 impl Config {
     /// Load configuration from environment variables and config files
     pub fn from_env() -> Result<Self, config::ConfigError> {
@@ -150,6 +292,84 @@ impl Config {
     }
 }
 
+impl RoutingConfig {
+    /// Circuit breaker settings for a service, applying its overrides
+    pub fn circuit_breaker_settings(&self, service_name: &str) -> CircuitBreakerSettings {
+        let overrides = self
+            .services
+            .get(service_name)
+            .map(|service| service.circuit_breaker.clone())
+            .unwrap_or_default();
+
+        CircuitBreakerSettings {
+            failure_threshold: overrides
+                .failure_threshold
+                .unwrap_or(self.circuit_breaker_failure_threshold),
+            open_duration: Duration::from_secs(
+                overrides
+                    .timeout_seconds
+                    .unwrap_or(self.circuit_breaker_timeout_seconds),
+            ),
+            min_requests: overrides
+                .min_requests
+                .unwrap_or(self.circuit_breaker_min_requests)
+                .max(1),
+            window: Duration::from_secs(
+                overrides
+                    .window_seconds
+                    .unwrap_or(self.circuit_breaker_window_seconds),
+            ),
+            half_open_requests: overrides
+                .half_open_requests
+                .unwrap_or(self.circuit_breaker_half_open_requests)
+                .max(1),
+        }
+    }
+
+    /// Enabled service serving a gateway path, by longest matching route prefix
+    pub fn service_for_path(&self, path: &str) -> Option<&ServiceConfig> {
+        self.services
+            .values()
+            .filter(|service| service.enabled)
+            .flat_map(|service| {
+                service
+                    .route_prefixes
+                    .iter()
+                    .map(move |prefix| (prefix.trim_end_matches('/'), service))
+            })
+            .filter(|(prefix, _)| {
+                path.strip_prefix(prefix)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, service)| service)
+    }
+}
+
+fn default_response_cache_max_entries() -> usize {
+    10_000
+}
+
+fn default_drain_timeout_seconds() -> u64 {
+    30
+}
+
+fn default_max_body_bytes() -> usize {
+    10 * 1024 * 1024 // 10 MB
+}
+
+fn default_circuit_breaker_min_requests() -> u32 {
+    10
+}
+
+fn default_circuit_breaker_window_seconds() -> u64 {
+    60
+}
+
+fn default_circuit_breaker_half_open_requests() -> u32 {
+    3
+}
+
 // Default implementations for sub-configurations
 
 impl Default for ServerConfig {
@@ -160,6 +380,11 @@ impl Default for ServerConfig {
             workers: num_cpus::get(),
             max_connections: 1024,
             timeout_seconds: 30,
+            max_body_bytes: default_max_body_bytes(),
+            body_limits: Vec::new(),
+            drain_timeout_seconds: default_drain_timeout_seconds(),
+            discovery_url: None,
+            advertise_host: None,
         }
     }
 }
@@ -205,6 +430,8 @@ impl Default for RateLimitConfig {
             redis_key_prefix: "rate_limit:".to_string(),
             default_burst_size: 100,
             cleanup_interval_seconds: 300,
+            routes: Vec::new(),
+            trusted_proxies: Vec::new(),
         }
     }
 }
@@ -217,6 +444,12 @@ impl Default for RoutingConfig {
             circuit_breaker_failure_threshold: 0.5,
             circuit_breaker_timeout_seconds: 30,
             health_check_interval_seconds: 60,
+            circuit_breaker_min_requests: default_circuit_breaker_min_requests(),
+            circuit_breaker_window_seconds: default_circuit_breaker_window_seconds(),
+            circuit_breaker_half_open_requests: default_circuit_breaker_half_open_requests(),
+            cached_routes: Vec::new(),
+            cache_invalidations: Vec::new(),
+            response_cache_max_entries: default_response_cache_max_entries(),
         }
     }
 }
@@ -232,6 +465,15 @@ impl Default for ObservabilityConfig {
     }
 }
 
+impl Default for InputInspectionConfig {
+    fn default() -> Self {
+        Self {
+            mode: InputInspectionMode::Log,
+            max_body_bytes: 1024 * 1024, // 1 MB
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -277,5 +519,50 @@ mod tests {
 
         let integrations_config = IntegrationsConfig::default();
         assert!(integrations_config.zapier.secret_key.is_none());
+
+        let input_inspection_config = InputInspectionConfig::default();
+        assert_eq!(input_inspection_config.mode, InputInspectionMode::Log);
+    }
+
+    #[test]
+    fn test_routing_service_lookup_and_circuit_breaker_overrides() {
+        let service = |name: &str, prefixes: &[&str]| ServiceConfig {
+            name: name.to_string(),
+            url: format!("http://{}:8080", name),
+            timeout_seconds: 30,
+            retries: 0,
+            enabled: true,
+            route_prefixes: prefixes.iter().map(|p| p.to_string()).collect(),
+            circuit_breaker: CircuitBreakerOverrides::default(),
+        };
+
+        let mut routing = RoutingConfig::default();
+        let mut intent_parser = service("intent-parser", &["/v1/intent"]);
+        intent_parser.circuit_breaker.failure_threshold = Some(0.25);
+        routing
+            .services
+            .insert("intent-parser".to_string(), intent_parser);
+        routing.services.insert(
+            "federation".to_string(),
+            service("federation", &["/v1/federation", "/v1/federation/proxy/"]),
+        );
+
+        let name = |path: &str| routing.service_for_path(path).map(|s| s.name.as_str());
+        assert_eq!(name("/v1/intent"), Some("intent-parser"));
+        assert_eq!(name("/v1/intent/parse"), Some("intent-parser"));
+        assert_eq!(name("/v1/intentional"), None);
+        assert_eq!(name("/v1/federation/proxy"), Some("federation"));
+        assert_eq!(name("/health"), None);
+
+        let settings = routing.circuit_breaker_settings("intent-parser");
+        assert_eq!(settings.failure_threshold, 0.25);
+        assert_eq!(settings.min_requests, 10);
+        assert_eq!(settings.open_duration, Duration::from_secs(30));
+        assert_eq!(
+            routing
+                .circuit_breaker_settings("federation")
+                .failure_threshold,
+            0.5
+        );
     }
 }
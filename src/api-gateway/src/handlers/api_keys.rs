@@ -0,0 +1,270 @@
+//! Handlers for users managing their own API keys
+
+use axum::{
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    response::Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    error::{ApiError, Result},
+    middleware_layer::auth::{parse_permission, UserContext},
+    services::api_keys::{ApiKeyRecord, ApiKeyService, NewApiKey},
+    state::AppState,
+};
+
+/// Create API key request
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateApiKeyRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    pub name: String,
+
+    /// Permissions granted to the key; each must be one the owner holds
+    #[validate(length(min = 1, message = "At least one scope is required"))]
+    pub scopes: Vec<String>,
+
+    /// Per-minute rate limit, overriding the subscription tier's
+    #[validate(range(min = 1, message = "Rate limit must be positive"))]
+    pub rate_limit_per_minute: Option<i32>,
+
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// API key as shown to its owner, without the key itself
+#[derive(Debug, Serialize)]
+pub struct ApiKeyResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub key_prefix: String,
+    pub scopes: Vec<String>,
+    pub subscription_tier: String,
+    pub rate_limit_per_minute: Option<i32>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<ApiKeyRecord> for ApiKeyResponse {
+    fn from(record: ApiKeyRecord) -> Self {
+        Self {
+            id: record.id,
+            name: record.key_name,
+            key_prefix: record.key_prefix,
+            scopes: record.scopes,
+            subscription_tier: record.subscription_tier,
+            rate_limit_per_minute: record.rate_limit_per_minute,
+            expires_at: record.expires_at,
+            revoked_at: record.revoked_at,
+            created_at: record.created_at,
+        }
+    }
+}
+
+/// Newly created API key; the only response that includes the key
+#[derive(Debug, Serialize)]
+pub struct CreateApiKeyResponse {
+    pub api_key: String,
+    #[serde(flatten)]
+    pub key: ApiKeyResponse,
+}
+
+/// POST /auth/api-keys - Issue an API key for the current user
+pub async fn create_api_key(
+    State(state): State<AppState>,
+    Extension(user_context): Extension<UserContext>,
+    Json(payload): Json<CreateApiKeyRequest>,
+) -> Result<(StatusCode, Json<CreateApiKeyResponse>)> {
+    require_session(&user_context)?;
+
+    payload
+        .validate()
+        .map_err(|e| ApiError::validation("api_key", format!("Invalid API key request: {}", e)))?;
+
+    if payload
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= Utc::now())
+    {
+        return Err(ApiError::validation(
+            "expires_at",
+            "Expiry must be in the future",
+        ));
+    }
+
+    authorize_scopes(&user_context, &payload.scopes)?;
+
+    let (record, api_key) = api_key_service(&state)?
+        .create(NewApiKey {
+            user_id: user_context.user_id.clone(),
+            key_name: payload.name,
+            scopes: payload.scopes,
+            subscription_tier: user_context.subscription_tier.to_string(),
+            rate_limit_per_minute: payload.rate_limit_per_minute,
+            expires_at: payload.expires_at,
+        })
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateApiKeyResponse {
+            api_key,
+            key: record.into(),
+        }),
+    ))
+}
+
+/// GET /auth/api-keys - List the current user's API keys
+pub async fn list_api_keys(
+    State(state): State<AppState>,
+    Extension(user_context): Extension<UserContext>,
+) -> Result<Json<Vec<ApiKeyResponse>>> {
+    let records = api_key_service(&state)?
+        .list_for_user(&user_context.user_id)
+        .await?;
+
+    Ok(Json(records.into_iter().map(Into::into).collect()))
+}
+
+/// DELETE /auth/api-keys/{key_id} - Revoke one of the current user's API keys
+pub async fn revoke_api_key(
+    State(state): State<AppState>,
+    Extension(user_context): Extension<UserContext>,
+    Path(key_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>> {
+    require_session(&user_context)?;
+
+    api_key_service(&state)?
+        .revoke(&user_context.user_id, key_id)
+        .await?;
+
+    info!(
+        key_id = %key_id,
+        user_id = %user_context.user_id,
+        "API key revoked by owner"
+    );
+
+    Ok(Json(serde_json::json!({
+        "message": "API key revoked successfully"
+    })))
+}
+
+fn api_key_service(state: &AppState) -> Result<&ApiKeyService> {
+    state
+        .api_key_service
+        .as_deref()
+        .ok_or_else(|| ApiError::service_unavailable("api_keys"))
+}
+
+/// Keys are issued and revoked from a login session, so a leaked key cannot
+/// mint longer-lived keys or revoke its owner's others
+fn require_session(user_context: &UserContext) -> Result<()> {
+    if user_context.api_key.is_some() {
+        return Err(ApiError::authorization(
+            "API keys cannot be managed with an API key",
+        ));
+    }
+    Ok(())
+}
+
+/// Reject unknown scopes and scopes the owner does not hold
+fn authorize_scopes(user_context: &UserContext, scopes: &[String]) -> Result<()> {
+    for scope in scopes {
+        let permission = parse_permission(scope)
+            .ok_or_else(|| ApiError::validation("scopes", format!("Unknown scope: {}", scope)))?;
+
+        if !user_context.has_permission(&permission) {
+            return Err(ApiError::authorization(format!(
+                "Permission denied: cannot grant {} without holding it",
+                scope
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware_layer::auth::ApiKeyContext;
+    use ai_core_shared::types::core::{Permission, SubscriptionTier, TokenClaims};
+
+    fn user(permissions: &[Permission]) -> UserContext {
+        UserContext {
+            user_id: "demo-user-id".to_string(),
+            roles: vec!["user".to_string()],
+            permissions: permissions.iter().cloned().collect(),
+            subscription_tier: SubscriptionTier::Pro,
+            token_claims: TokenClaims {
+                sub: "demo-user-id".to_string(),
+                iss: "AI-PLATFORM".to_string(),
+                aud: "api-gateway".to_string(),
+                exp: 1234567890,
+                iat: 1234567890,
+                roles: vec!["user".to_string()],
+                permissions: Vec::new(),
+                subscription_tier: SubscriptionTier::Pro,
+            },
+            api_key: None,
+        }
+    }
+
+    fn scopes(scopes: &[&str]) -> Vec<String> {
+        scopes.iter().map(|scope| scope.to_string()).collect()
+    }
+
+    #[test]
+    fn test_key_scopes_are_capped_at_the_owners_permissions() {
+        let owner = user(&[Permission::WorkflowsRead, Permission::WorkflowsCreate]);
+
+        assert!(authorize_scopes(&owner, &scopes(&["workflows:read"])).is_ok());
+        assert!(authorize_scopes(&owner, &scopes(&["workflows:read", "workflows:create"])).is_ok());
+
+        let escalation = authorize_scopes(&owner, &scopes(&["workflows:read", "admin:system"]));
+        assert!(matches!(escalation, Err(ApiError::Authorization { .. })));
+
+        let unknown = authorize_scopes(&owner, &scopes(&["workflows:everything"]));
+        assert!(matches!(unknown, Err(ApiError::Validation { .. })));
+    }
+
+    #[test]
+    fn test_api_keys_cannot_manage_keys() {
+        let mut owner = user(&[Permission::WorkflowsRead]);
+        assert!(require_session(&owner).is_ok());
+
+        owner.api_key = Some(ApiKeyContext {
+            key_id: Uuid::new_v4().to_string(),
+            name: "CI pipeline".to_string(),
+            rate_limit_per_minute: None,
+        });
+        assert!(require_session(&owner).is_err());
+    }
+
+    #[test]
+    fn test_create_api_key_request_validation() {
+        let valid_request = CreateApiKeyRequest {
+            name: "CI pipeline".to_string(),
+            scopes: scopes(&["workflows:read"]),
+            rate_limit_per_minute: Some(60),
+            expires_at: None,
+        };
+        assert!(valid_request.validate().is_ok());
+
+        let no_scopes = CreateApiKeyRequest {
+            scopes: Vec::new(),
+            ..valid_request
+        };
+        assert!(no_scopes.validate().is_err());
+
+        let zero_rate_limit = CreateApiKeyRequest {
+            name: "CI pipeline".to_string(),
+            scopes: scopes(&["workflows:read"]),
+            rate_limit_per_minute: Some(0),
+            expires_at: None,
+        };
+        assert!(zero_rate_limit.validate().is_err());
+    }
+}
@@ -0,0 +1,40 @@
+//! Admin handlers for upstream circuit breakers
+
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use std::collections::HashMap;
+use tracing::info;
+
+use crate::{
+    error::Result,
+    middleware_layer::auth::{require_admin, UserContext},
+    services::circuit_breaker::CircuitBreakerStats,
+    state::AppState,
+};
+
+/// GET /admin/circuit-breakers - Current circuit state of every upstream service
+pub async fn list_circuit_breakers(
+    State(state): State<AppState>,
+    Extension(user_context): Extension<UserContext>,
+) -> Result<Json<HashMap<String, CircuitBreakerStats>>> {
+    require_admin()(user_context)?;
+    Ok(Json(state.circuit_breaker.get_stats()))
+}
+
+/// POST /admin/circuit-breakers/:service/reset - Close a service's circuit
+pub async fn reset_circuit_breaker(
+    State(state): State<AppState>,
+    Extension(user_context): Extension<UserContext>,
+    Path(service): Path<String>,
+) -> Result<Json<HashMap<String, CircuitBreakerStats>>> {
+    let user_context = require_admin()(user_context)?;
+    state.circuit_breaker.reset(&service)?;
+    info!(
+        service = %service,
+        user_id = %user_context.user_id,
+        "Circuit breaker reset by admin"
+    );
+    Ok(Json(state.circuit_breaker.get_stats()))
+}
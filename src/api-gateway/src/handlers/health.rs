@@ -1,12 +1,20 @@
 //! Health check handlers
 
-use axum::{extract::State, Json};
+use axum::{extract::State, http::StatusCode, Json};
 
-use crate::{error::Result, state::AppState};
+use crate::{
+    error::{ApiError, Result},
+    state::AppState,
+};
 use ai_core_shared::types::core::{ServiceHealth, SystemInfo};
 
 /// Get system health status
 pub async fn health_check(State(state): State<AppState>) -> Result<Json<Vec<ServiceHealth>>> {
+    // Draining instances report unhealthy so load balancers stop routing to them
+    if state.lifecycle.is_draining() {
+        return Err(ApiError::service_unavailable("api-gateway"));
+    }
+
     let health_status = state.health_service.check_all().await?;
     Ok(Json(health_status))
 }
@@ -26,11 +34,24 @@ pub async fn liveness() -> Result<Json<serde_json::Value>> {
 }
 
 /// Readiness probe
-pub async fn readiness(State(state): State<AppState>) -> Result<Json<serde_json::Value>> {
-    let is_ready = state.is_healthy().await;
+pub async fn readiness(
+    State(state): State<AppState>,
+) -> Result<(StatusCode, Json<serde_json::Value>)> {
+    let draining = state.lifecycle.is_draining();
+    let is_ready = !draining && state.is_healthy().await;
 
-    Ok(Json(serde_json::json!({
-        "status": if is_ready { "ready" } else { "not_ready" },
-        "timestamp": chrono::Utc::now()
-    })))
+    let status = if draining {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
+    };
+
+    Ok((
+        status,
+        Json(serde_json::json!({
+            "status": if is_ready { "ready" } else { "not_ready" },
+            "draining": draining,
+            "timestamp": chrono::Utc::now()
+        })),
+    ))
 }
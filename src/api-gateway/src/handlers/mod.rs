@@ -1,6 +1,8 @@
 //! Request handlers for the API Gateway
 
+pub mod api_keys;
 pub mod auth;
+pub mod circuit_breakers;
 pub mod workflows;
 
 pub mod health;
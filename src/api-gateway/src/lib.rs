@@ -13,8 +13,9 @@ pub mod state;
 
 // Re-export main types and functions for external use
 pub use config::{
-    AuthConfig, Config, DatabaseConfig, ObservabilityConfig, RateLimitConfig, RedisConfig,
-    RoutingConfig, ServerConfig, ServiceConfig,
+    AuthConfig, CacheInvalidation, CachedRoute, Config, DatabaseConfig, ObservabilityConfig,
+    RateLimitConfig, RedisConfig, RouteBodyLimit, RouteRateLimit, RoutingConfig, ServerConfig,
+    ServiceConfig,
 };
 pub use error::{ApiError, Result};
 pub use state::AppState;
@@ -23,7 +24,7 @@ use axum::Router;
 
 /// Build the main application router with all middleware and routes
 pub fn build_router(state: AppState) -> Router {
-    use axum::middleware;
+    use axum::{extract::DefaultBodyLimit, middleware};
     use tower::ServiceBuilder;
     use tower_http::{
         compression::CompressionLayer, cors::CorsLayer, request_id::SetRequestIdLayer,
@@ -33,11 +34,25 @@ pub fn build_router(state: AppState) -> Router {
     let api_routes = routes::api::router()
         .layer(middleware::from_fn_with_state(
             state.clone(),
-            middleware_layer::auth::auth_middleware,
+            middleware_layer::circuit_breaker::circuit_breaker_middleware,
         ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            middleware_layer::input_inspection::input_inspection_middleware,
+        ))
+        // Cached responses are per caller, so the cache runs after authentication
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            middleware_layer::response_cache::response_cache_middleware,
+        ))
+        // Rate limiting runs after authentication so limits are keyed by user or API key
         .layer(middleware::from_fn_with_state(
             state.clone(),
             middleware_layer::rate_limit::rate_limit_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            middleware_layer::auth::auth_middleware,
         ));
 
     let public_routes = routes::public::router();
@@ -56,9 +71,19 @@ pub fn build_router(state: AppState) -> Router {
                 .layer(middleware::from_fn(
                     middleware_layer::logging::logging_middleware,
                 ))
+                .layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    middleware_layer::in_flight::in_flight_middleware,
+                ))
                 .layer(middleware::from_fn_with_state(
                     state.clone(),
                     middleware_layer::error_handling::error_handling_middleware,
+                ))
+                // body_limit_middleware enforces the configured limits instead
+                .layer(DefaultBodyLimit::disable())
+                .layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    middleware_layer::body_limit::body_limit_middleware,
                 )),
         )
         .with_state(state)
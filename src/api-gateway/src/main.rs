@@ -3,9 +3,11 @@
 //! High-performance API Gateway service for the AI-PLATFORM Intelligent Automation Platform.
 //! Provides centralized authentication, rate limiting, routing, and observability.
 
+use std::future::IntoFuture;
 use std::net::SocketAddr;
+use std::time::Duration;
 
-use axum::{middleware, Router};
+use axum::{extract::DefaultBodyLimit, middleware, Router};
 use tower::ServiceBuilder;
 use tower_http::{
     compression::CompressionLayer, cors::CorsLayer, request_id::SetRequestIdLayer,
@@ -56,6 +58,11 @@ async fn main() -> Result<()> {
         }
     };
 
+    let lifecycle = state.lifecycle.clone();
+    let registration =
+        services::discovery::DiscoveryRegistration::register(&state.http_client, &config.server)
+            .await;
+
     // Build the application router
     let app = build_router(state);
 
@@ -67,8 +74,27 @@ async fn main() -> Result<()> {
     info!("Health check endpoint: http://{}/health", addr);
     info!("Metrics endpoint: http://{}/metrics", addr);
 
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
+    // Peer addresses let the rate limiter tell trusted proxies from clients
+    let server = axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown({
+        let lifecycle = lifecycle.clone();
+        async move {
+            shutdown_signal().await;
+            lifecycle.start_draining();
+            if let Some(registration) = &registration {
+                registration.deregister().await;
+            }
+        }
+    });
+
+    lifecycle
+        .run_until_drained(
+            server.into_future(),
+            Duration::from_secs(config.server.drain_timeout_seconds),
+        )
         .await?;
 
     info!("API Gateway shutdown complete");
@@ -80,11 +106,25 @@ fn build_router(state: AppState) -> Router {
     let api_routes = routes::api::router()
         .layer(middleware::from_fn_with_state(
             state.clone(),
-            middleware_layer::auth::auth_middleware,
+            middleware_layer::circuit_breaker::circuit_breaker_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            middleware_layer::input_inspection::input_inspection_middleware,
+        ))
+        // Cached responses are per caller, so the cache runs after authentication
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            middleware_layer::response_cache::response_cache_middleware,
         ))
+        // Rate limiting runs after authentication so limits are keyed by user or API key
         .layer(middleware::from_fn_with_state(
             state.clone(),
             middleware_layer::rate_limit::rate_limit_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            middleware_layer::auth::auth_middleware,
         ));
 
     let public_routes = routes::public::router();
@@ -103,9 +143,19 @@ fn build_router(state: AppState) -> Router {
                 .layer(middleware::from_fn(
                     middleware_layer::logging::logging_middleware,
                 ))
+                .layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    middleware_layer::in_flight::in_flight_middleware,
+                ))
                 .layer(middleware::from_fn_with_state(
                     state.clone(),
                     middleware_layer::error_handling::error_handling_middleware,
+                ))
+                // body_limit_middleware enforces the configured limits instead
+                .layer(DefaultBodyLimit::disable())
+                .layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    middleware_layer::body_limit::body_limit_middleware,
                 )),
         )
         .with_state(state)
@@ -1,4 +1,7 @@
 //! Authentication middleware for JWT token validation and user context extraction
+//!
+//! Requests authenticate with either a Bearer JWT or an `X-API-Key` header.
+//! Both produce the same `UserContext`, so handlers need not care which was used.
 
 use axum::{
     extract::{Request, State},
@@ -11,11 +14,12 @@ use tracing::{debug, warn};
 
 use crate::{
     error::{ApiError, Result},
+    services::api_keys::{ApiKeyRecord, API_KEY_HEADER},
     state::AppState,
 };
 use ai_core_shared::types::core::{Permission, SubscriptionTier, TokenClaims};
 
-/// User context extracted from JWT token
+/// User context extracted from a JWT token or API key
 #[derive(Debug, Clone)]
 pub struct UserContext {
     pub user_id: String,
@@ -23,6 +27,17 @@ pub struct UserContext {
     pub permissions: HashSet<Permission>,
     pub subscription_tier: SubscriptionTier,
     pub token_claims: TokenClaims,
+    /// Set when the request authenticated with an API key
+    pub api_key: Option<ApiKeyContext>,
+}
+
+/// API key a request authenticated with
+#[derive(Debug, Clone)]
+pub struct ApiKeyContext {
+    pub key_id: String,
+    pub name: String,
+    /// Per-minute rate limit for the key, overriding the subscription tier's
+    pub rate_limit_per_minute: Option<u32>,
 }
 
 impl UserContext {
@@ -70,37 +85,88 @@ impl UserContext {
     pub fn can_manage_federation(&self) -> bool {
         self.has_permission(&Permission::FederationManage)
     }
+
+    /// Build the context for a request authenticated with an API key. The key's
+    /// scopes become its permissions; API keys carry no roles.
+    pub fn from_api_key(record: ApiKeyRecord) -> Result<Self> {
+        let permissions = parse_permissions(&record.scopes)?;
+        let subscription_tier = record.subscription_tier.parse().unwrap_or_else(|e| {
+            warn!(key_id = %record.id, "{}, treating API key as free tier", e);
+            SubscriptionTier::Free
+        });
+
+        // Handlers that read token claims see the key's lifetime
+        let token_claims = TokenClaims {
+            sub: record.user_id.clone(),
+            iss: "AI-PLATFORM-platform".to_string(),
+            aud: "api-key".to_string(),
+            exp: record
+                .expires_at
+                .map(|expires_at| expires_at.timestamp())
+                .unwrap_or(i64::MAX),
+            iat: record.created_at.timestamp(),
+            roles: Vec::new(),
+            permissions: record.scopes,
+            subscription_tier: subscription_tier.clone(),
+        };
+
+        Ok(Self {
+            user_id: record.user_id,
+            roles: Vec::new(),
+            permissions,
+            subscription_tier,
+            token_claims,
+            api_key: Some(ApiKeyContext {
+                key_id: record.id.to_string(),
+                name: record.key_name,
+                rate_limit_per_minute: record
+                    .rate_limit_per_minute
+                    .and_then(|limit| u32::try_from(limit).ok()),
+            }),
+        })
+    }
 }
 
-/// Authentication middleware that validates JWT tokens and extracts user context
+/// Authentication middleware that validates JWT tokens or API keys and extracts user context
 pub async fn auth_middleware(
     State(state): State<AppState>,
     mut request: Request,
     next: Next,
 ) -> Result<Response> {
-    // Extract authorization header
-    let auth_header = request
-        .headers()
-        .get(AUTHORIZATION)
-        .and_then(|header| header.to_str().ok())
-        .ok_or_else(|| ApiError::authentication("Missing authorization header"))?;
-
-    // Validate Bearer token format
-    if !auth_header.starts_with("Bearer ") {
-        return Err(ApiError::authentication(
-            "Invalid authorization header format",
-        ));
-    }
+    let user_context = match request.headers().get(API_KEY_HEADER) {
+        Some(api_key) => {
+            let api_key = api_key
+                .to_str()
+                .map_err(|_| ApiError::authentication("Invalid API key"))?;
+            validate_api_key(&state, api_key).await?
+        }
+        None => {
+            // Extract authorization header
+            let auth_header = request
+                .headers()
+                .get(AUTHORIZATION)
+                .and_then(|header| header.to_str().ok())
+                .ok_or_else(|| ApiError::authentication("Missing authorization header"))?;
+
+            // Validate Bearer token format
+            if !auth_header.starts_with("Bearer ") {
+                return Err(ApiError::authentication(
+                    "Invalid authorization header format",
+                ));
+            }
 
-    let token = &auth_header[7..]; // Remove "Bearer " prefix
+            let token = &auth_header[7..]; // Remove "Bearer " prefix
 
-    // Validate and decode JWT token
-    let user_context = validate_jwt_token(&state, token).await?;
+            // Validate and decode JWT token
+            validate_jwt_token(&state, token).await?
+        }
+    };
 
     debug!(
         user_id = %user_context.user_id,
         roles = ?user_context.roles,
         subscription_tier = ?user_context.subscription_tier,
+        api_key_id = ?user_context.api_key.as_ref().map(|key| &key.key_id),
         "User authenticated successfully"
     );
 
@@ -119,8 +185,16 @@ pub async fn optional_auth_middleware(
     mut request: Request,
     next: Next,
 ) -> Result<Response> {
-    // Try to extract authorization header
-    if let Some(auth_header) = request
+    if let Some(api_key) = request
+        .headers()
+        .get(API_KEY_HEADER)
+        .and_then(|header| header.to_str().ok())
+    {
+        // Try to validate the API key, but continue even if it fails
+        if let Ok(user_context) = validate_api_key(&state, api_key).await {
+            request.extensions_mut().insert(user_context);
+        }
+    } else if let Some(auth_header) = request
         .headers()
         .get(AUTHORIZATION)
         .and_then(|header| header.to_str().ok())
@@ -165,40 +239,31 @@ async fn validate_jwt_token(state: &AppState, token: &str) -> Result<UserContext
         permissions,
         subscription_tier: token_claims.subscription_tier.clone(),
         token_claims,
+        api_key: None,
     };
 
     Ok(user_context)
 }
 
+/// Validate an API key and return user context
+async fn validate_api_key(state: &AppState, api_key: &str) -> Result<UserContext> {
+    let api_key_service = state
+        .api_key_service
+        .as_ref()
+        .ok_or_else(|| ApiError::service_unavailable("authentication"))?;
+
+    let record = api_key_service.authenticate(api_key).await?;
+    UserContext::from_api_key(record)
+}
+
 /// Parse string permissions into enum permissions
 fn parse_permissions(permission_strings: &[String]) -> Result<HashSet<Permission>> {
     let mut permissions = HashSet::new();
 
     for perm_str in permission_strings {
-        let permission = match perm_str.as_str() {
-            "workflows:read" => Permission::WorkflowsRead,
-            "workflows:create" => Permission::WorkflowsCreate,
-            "workflows:update" => Permission::WorkflowsUpdate,
-            "workflows:delete" => Permission::WorkflowsDelete,
-            "content:read" => Permission::ContentRead,
-            "content:create" => Permission::ContentCreate,
-            "content:update" => Permission::ContentUpdate,
-            "content:delete" => Permission::ContentDelete,
-            "campaigns:read" => Permission::CampaignsRead,
-            "campaigns:create" => Permission::CampaignsCreate,
-            "campaigns:update" => Permission::CampaignsUpdate,
-            "campaigns:delete" => Permission::CampaignsDelete,
-            "analytics:read" => Permission::AnalyticsRead,
-            "analytics:export" => Permission::AnalyticsExport,
-            "federation:proxy" => Permission::FederationProxy,
-            "federation:manage" => Permission::FederationManage,
-            "admin:users" => Permission::AdminUsers,
-            "admin:system" => Permission::AdminSystem,
-            "admin:billing" => Permission::AdminBilling,
-            _ => {
-                warn!("Unknown permission: {}", perm_str);
-                continue;
-            }
+        let Some(permission) = parse_permission(perm_str) else {
+            warn!("Unknown permission: {}", perm_str);
+            continue;
         };
         permissions.insert(permission);
     }
@@ -206,6 +271,34 @@ fn parse_permissions(permission_strings: &[String]) -> Result<HashSet<Permission
     Ok(permissions)
 }
 
+/// Parse a `resource:action` permission string
+pub(crate) fn parse_permission(permission: &str) -> Option<Permission> {
+    let permission = match permission {
+        "workflows:read" => Permission::WorkflowsRead,
+        "workflows:create" => Permission::WorkflowsCreate,
+        "workflows:update" => Permission::WorkflowsUpdate,
+        "workflows:delete" => Permission::WorkflowsDelete,
+        "content:read" => Permission::ContentRead,
+        "content:create" => Permission::ContentCreate,
+        "content:update" => Permission::ContentUpdate,
+        "content:delete" => Permission::ContentDelete,
+        "campaigns:read" => Permission::CampaignsRead,
+        "campaigns:create" => Permission::CampaignsCreate,
+        "campaigns:update" => Permission::CampaignsUpdate,
+        "campaigns:delete" => Permission::CampaignsDelete,
+        "analytics:read" => Permission::AnalyticsRead,
+        "analytics:export" => Permission::AnalyticsExport,
+        "federation:proxy" => Permission::FederationProxy,
+        "federation:manage" => Permission::FederationManage,
+        "admin:users" => Permission::AdminUsers,
+        "admin:system" => Permission::AdminSystem,
+        "admin:billing" => Permission::AdminBilling,
+        _ => return None,
+    };
+
+    Some(permission)
+}
+
 /// Middleware to require specific permissions
 pub fn require_permission(
     required_permission: Permission,
@@ -324,6 +417,7 @@ mod tests {
                 permissions: vec!["workflows:read".to_string()],
                 subscription_tier: SubscriptionTier::Pro,
             },
+            api_key: None,
         };
 
         // Test single permission
@@ -388,6 +482,7 @@ mod tests {
                 permissions: vec![],
                 subscription_tier: SubscriptionTier::Free,
             },
+            api_key: None,
         };
 
         let pro_user = UserContext {
@@ -401,4 +496,37 @@ mod tests {
         assert!(require_pro(pro_user).is_ok());
         assert!(require_pro(free_user).is_err());
     }
+
+    #[test]
+    fn test_api_key_context_uses_key_scopes_and_limits() {
+        use chrono::{Duration, Utc};
+
+        let expires_at = Utc::now() + Duration::days(30);
+        let record = ApiKeyRecord {
+            id: uuid::Uuid::new_v4(),
+            user_id: "demo-user-id".to_string(),
+            key_name: "CI pipeline".to_string(),
+            key_prefix: "ak_12345".to_string(),
+            scopes: vec!["workflows:read".to_string(), "workflows:create".to_string()],
+            subscription_tier: "enterprise".to_string(),
+            rate_limit_per_minute: Some(42),
+            expires_at: Some(expires_at),
+            revoked_at: None,
+            created_at: Utc::now(),
+        };
+        let key_id = record.id.to_string();
+
+        let user_context = UserContext::from_api_key(record).unwrap();
+
+        assert_eq!(user_context.user_id, "demo-user-id");
+        assert!(user_context.can_create_workflows());
+        assert!(!user_context.has_permission(&Permission::WorkflowsDelete));
+        assert!(!user_context.is_admin());
+        assert_eq!(user_context.subscription_tier, SubscriptionTier::Enterprise);
+        assert_eq!(user_context.token_claims.exp, expires_at.timestamp());
+
+        let api_key = user_context.api_key.unwrap();
+        assert_eq!(api_key.key_id, key_id);
+        assert_eq!(api_key.rate_limit_per_minute, Some(42));
+    }
 }
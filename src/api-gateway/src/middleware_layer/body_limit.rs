@@ -0,0 +1,175 @@
+//! Request body size limits
+//!
+//! Requests whose `Content-Length` is over the limit are rejected with 413
+//! before the handler runs. Bodies without a declared length are counted as
+//! they stream and fail as soon as they pass the limit, so an oversized body is
+//! never buffered in full. The limit comes from `ServerConfig::max_body_bytes`
+//! unless a `ServerConfig::body_limits` entry matches the route.
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{header::CONTENT_LENGTH, HeaderMap},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use futures::StreamExt;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use tracing::debug;
+
+use crate::{error::ApiError, middleware_layer::route_match, state::AppState};
+
+/// Error a limited body yields once it passes the limit
+#[derive(Debug, thiserror::Error)]
+#[error("request body exceeds {max_bytes} bytes")]
+struct BodyTooLarge {
+    max_bytes: usize,
+}
+
+/// Body size limit middleware
+pub async fn body_limit_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let server = &state.config.server;
+
+    let path = route_match::original_path(&request);
+
+    let max_bytes = route_match::most_specific(&server.body_limits, request.method(), path)
+        .map(|route| route.max_bytes)
+        .unwrap_or(server.max_body_bytes);
+
+    limit_body(max_bytes, request, next).await
+}
+
+/// Reject the request with 413 if its body is, or turns out to be, larger than `max_bytes`
+async fn limit_body(max_bytes: usize, request: Request, next: Next) -> Response {
+    if let Some(declared) = content_length(request.headers()).filter(|len| *len > max_bytes) {
+        debug!(
+            path = %request.uri().path(),
+            content_length = declared,
+            max_bytes = max_bytes,
+            "Rejecting request with oversized Content-Length"
+        );
+        return ApiError::request_too_large(max_bytes).into_response();
+    }
+
+    let exceeded = Arc::new(AtomicBool::new(false));
+    let (parts, body) = request.into_parts();
+
+    let body_exceeded = exceeded.clone();
+    let mut received = 0usize;
+    let body = Body::from_stream(body.into_data_stream().map(move |chunk| {
+        let chunk = chunk?;
+        received = received.saturating_add(chunk.len());
+        if received > max_bytes {
+            body_exceeded.store(true, Ordering::SeqCst);
+            return Err(axum::Error::new(BodyTooLarge { max_bytes }));
+        }
+        Ok(chunk)
+    }));
+
+    let response = next.run(Request::from_parts(parts, body)).await;
+
+    // Whatever the handler made of the failed read, the caller gets a 413
+    if exceeded.load(Ordering::SeqCst) {
+        debug!(
+            max_bytes = max_bytes,
+            "Request body exceeded limit while streaming"
+        );
+        return ApiError::request_too_large(max_bytes).into_response();
+    }
+
+    response
+}
+
+fn content_length(headers: &HeaderMap) -> Option<usize> {
+    headers
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Bytes, http::StatusCode, routing::post, Router};
+    use std::convert::Infallible;
+    use std::sync::atomic::AtomicUsize;
+    use tower::ServiceExt;
+
+    /// Echo the body length, counting how often the handler ran to completion
+    fn app(max_bytes: usize, handled: Arc<AtomicUsize>) -> Router {
+        Router::new()
+            .route(
+                "/v1/upload",
+                post(move |body: Bytes| {
+                    handled.fetch_add(1, Ordering::SeqCst);
+                    async move { body.len().to_string() }
+                }),
+            )
+            .layer(axum::middleware::from_fn(
+                move |request: Request, next: Next| limit_body(max_bytes, request, next),
+            ))
+    }
+
+    #[tokio::test]
+    async fn test_declared_oversized_body_is_rejected_before_the_handler() {
+        let handled = Arc::new(AtomicUsize::new(0));
+        let app = app(8, handled.clone());
+
+        let accepted = app
+            .clone()
+            .oneshot(
+                axum::http::Request::post("/v1/upload")
+                    .header(CONTENT_LENGTH, 8)
+                    .body(Body::from("12345678"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(accepted.status(), StatusCode::OK);
+
+        // The declared length alone is enough to reject, whatever the body holds
+        let rejected = app
+            .oneshot(
+                axum::http::Request::post("/v1/upload")
+                    .header(CONTENT_LENGTH, 1024)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(rejected.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(handled.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_streamed_body_is_rejected_once_it_passes_the_limit() {
+        let pulled = Arc::new(AtomicUsize::new(0));
+        let chunks_pulled = pulled.clone();
+        let chunks = futures::stream::iter(0..100).map(move |_| {
+            chunks_pulled.fetch_add(1, Ordering::SeqCst);
+            Ok::<_, Infallible>(Bytes::from_static(b"0123456789"))
+        });
+
+        let handled = Arc::new(AtomicUsize::new(0));
+        let response = app(25, handled.clone())
+            .oneshot(
+                axum::http::Request::post("/v1/upload")
+                    .body(Body::from_stream(chunks))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(handled.load(Ordering::SeqCst), 0);
+        // Reading stopped at the chunk that crossed the limit
+        assert_eq!(pulled.load(Ordering::SeqCst), 3);
+    }
+}
@@ -0,0 +1,185 @@
+//! Circuit breaker middleware for requests forwarded to upstream services
+//!
+//! Requests are matched to an upstream by the `route_prefixes` in its
+//! `ServiceConfig`. While that upstream's circuit is open they fail fast with
+//! 503 and a `Retry-After` header instead of waiting on a failing service.
+
+use axum::{
+    extract::{Request, State},
+    http::{header::RETRY_AFTER, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use tracing::debug;
+
+use crate::{
+    error::ApiError, middleware_layer::route_match,
+    services::circuit_breaker::CircuitBreakerService, state::AppState,
+};
+
+/// Circuit breaker middleware for upstream-backed routes
+pub async fn circuit_breaker_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !state.circuit_breaker.is_enabled() {
+        return next.run(request).await;
+    }
+
+    let Some(service_name) = state
+        .config
+        .routing
+        .service_for_path(route_match::original_path(&request))
+        .map(|service| service.name.clone())
+    else {
+        return next.run(request).await;
+    };
+
+    guard_upstream(&state.circuit_breaker, &service_name, request, next).await
+}
+
+/// Forward the request unless the service's circuit is open, then record the outcome
+async fn guard_upstream(
+    circuit_breaker: &CircuitBreakerService,
+    service_name: &str,
+    request: Request,
+    next: Next,
+) -> Response {
+    if let Err(open) = circuit_breaker.try_acquire(service_name) {
+        debug!(
+            service = service_name,
+            retry_after_ms = open.retry_after.as_millis() as u64,
+            "Circuit breaker open, failing fast"
+        );
+
+        let mut response = ApiError::circuit_breaker_open(service_name).into_response();
+        let retry_after = open.retry_after.as_secs_f64().ceil().max(1.0) as u64;
+        response
+            .headers_mut()
+            .insert(RETRY_AFTER, HeaderValue::from(retry_after));
+        return response;
+    }
+
+    let response = next.run(request).await;
+    if is_upstream_failure(response.status()) {
+        circuit_breaker.record_failure(service_name);
+    } else {
+        circuit_breaker.record_success(service_name);
+    }
+
+    response
+}
+
+/// Responses that count against the upstream: server errors and timeouts.
+/// Client errors are the caller's fault and count as successes.
+fn is_upstream_failure(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::REQUEST_TIMEOUT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RoutingConfig;
+    use axum::{body::Body, routing::get, Router};
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_half_open_probe_closes_circuit_once_upstream_recovers() {
+        let config = RoutingConfig {
+            circuit_breaker_min_requests: 2,
+            circuit_breaker_failure_threshold: 0.5,
+            circuit_breaker_timeout_seconds: 0,
+            circuit_breaker_half_open_requests: 1,
+            ..Default::default()
+        };
+        let circuit_breaker = Arc::new(CircuitBreakerService::new(config));
+
+        let healthy = Arc::new(AtomicBool::new(false));
+        let upstream_healthy = healthy.clone();
+        let app =
+            Router::new()
+                .route(
+                    "/v1/intent/parse",
+                    get(move || {
+                        let healthy = upstream_healthy.load(Ordering::SeqCst);
+                        async move {
+                            if healthy {
+                                StatusCode::OK
+                            } else {
+                                StatusCode::BAD_GATEWAY
+                            }
+                        }
+                    }),
+                )
+                .layer(axum::middleware::from_fn(
+                    move |request: Request, next: Next| {
+                        let circuit_breaker = circuit_breaker.clone();
+                        async move {
+                            guard_upstream(&circuit_breaker, "intent-parser", request, next).await
+                        }
+                    },
+                ));
+        let send = || {
+            app.clone().oneshot(
+                axum::http::Request::builder()
+                    .uri("/v1/intent/parse")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+        };
+
+        assert_eq!(send().await.unwrap().status(), StatusCode::BAD_GATEWAY);
+        assert_eq!(send().await.unwrap().status(), StatusCode::BAD_GATEWAY);
+
+        // Open: the half-open probe fails and the circuit reopens
+        assert_eq!(send().await.unwrap().status(), StatusCode::BAD_GATEWAY);
+
+        // Upstream recovers: the next probe succeeds and closes the circuit
+        healthy.store(true, Ordering::SeqCst);
+        assert_eq!(send().await.unwrap().status(), StatusCode::OK);
+        assert_eq!(send().await.unwrap().status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_open_circuit_returns_503_with_retry_after() {
+        let config = RoutingConfig {
+            circuit_breaker_min_requests: 1,
+            circuit_breaker_timeout_seconds: 30,
+            ..Default::default()
+        };
+        let circuit_breaker = Arc::new(CircuitBreakerService::new(config));
+
+        let app =
+            Router::new()
+                .route(
+                    "/v1/intent/parse",
+                    get(|| async { StatusCode::GATEWAY_TIMEOUT }),
+                )
+                .layer(axum::middleware::from_fn(
+                    move |request: Request, next: Next| {
+                        let circuit_breaker = circuit_breaker.clone();
+                        async move {
+                            guard_upstream(&circuit_breaker, "intent-parser", request, next).await
+                        }
+                    },
+                ));
+        let request = || {
+            axum::http::Request::builder()
+                .uri("/v1/intent/parse")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let failed = app.clone().oneshot(request()).await.unwrap();
+        assert_eq!(failed.status(), StatusCode::GATEWAY_TIMEOUT);
+
+        let rejected = app.clone().oneshot(request()).await.unwrap();
+        assert_eq!(rejected.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(rejected.headers()[RETRY_AFTER], "30");
+    }
+}
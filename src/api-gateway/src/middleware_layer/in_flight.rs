@@ -0,0 +1,22 @@
+//! In-flight request tracking
+//!
+//! Counts every request as in flight while its handler runs, so shutdown can
+//! report how many requests were abandoned when the drain timeout elapsed.
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::state::AppState;
+
+/// In-flight request tracking middleware
+pub async fn in_flight_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let _in_flight = state.lifecycle.track();
+    next.run(request).await
+}
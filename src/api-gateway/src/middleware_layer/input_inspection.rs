@@ -0,0 +1,246 @@
+//! Input inspection middleware for SQL/NoSQL injection and XSS payloads
+//!
+//! Runs the security crate's detectors over the query string and text-like
+//! request bodies, then logs or blocks flagged requests depending on the
+//! configured `InputInspectionMode`. Bodies over the inspection limit are
+//! rejected when blocking; when logging, only their start is inspected and
+//! the request is never rejected.
+
+use ai_core_security::{InputFinding, InputValidator};
+use axum::{
+    body::{Body, Bytes},
+    extract::{Request, State},
+    http::{header::CONTENT_LENGTH, header::CONTENT_TYPE, HeaderMap},
+    middleware::Next,
+    response::Response,
+};
+use bytes::BytesMut;
+use futures::{stream, StreamExt};
+use std::sync::OnceLock;
+use tracing::{debug, warn};
+
+use crate::{
+    config::InputInspectionMode,
+    error::{ApiError, Result},
+    middleware_layer::auth::extract_user_context,
+    state::AppState,
+};
+
+/// Longest slice of a matched payload written to the logs
+const MAX_LOGGED_MATCH: usize = 128;
+
+static VALIDATOR: OnceLock<InputValidator> = OnceLock::new();
+
+/// How a request body is decoded before inspection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BodyEncoding {
+    Text,
+    UrlEncoded,
+}
+
+/// Input inspection middleware that logs or rejects suspicious requests
+pub async fn input_inspection_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response> {
+    let config = &state.config.input_inspection;
+    if config.mode == InputInspectionMode::Off {
+        return Ok(next.run(request).await);
+    }
+
+    let validator = VALIDATOR.get_or_init(InputValidator::with_defaults);
+
+    let mut findings = request
+        .uri()
+        .query()
+        .map(|query| validator.inspect_url_encoded(query))
+        .unwrap_or_default();
+
+    let block = config.mode == InputInspectionMode::Block;
+    let (parts, body) = request.into_parts();
+    let request = match body_encoding(&parts.headers) {
+        // Blocking can only vouch for bodies it inspected in full
+        Some(_)
+            if block
+                && content_length(&parts.headers)
+                    .is_some_and(|len| len > config.max_body_bytes) =>
+        {
+            return Err(ApiError::request_too_large(config.max_body_bytes));
+        }
+        Some(encoding) => {
+            let buffered = buffer_body(body, config.max_body_bytes).await;
+            if buffered.truncated {
+                if block {
+                    return Err(ApiError::request_too_large(config.max_body_bytes));
+                }
+                debug!(
+                    path = %parts.uri.path(),
+                    max_body_bytes = config.max_body_bytes,
+                    "Request body too large, inspecting only its start"
+                );
+            }
+
+            let text = String::from_utf8_lossy(&buffered.inspected);
+            findings.extend(match encoding {
+                BodyEncoding::Text => validator.inspect(&text),
+                BodyEncoding::UrlEncoded => validator.inspect_url_encoded(&text),
+            });
+            Request::from_parts(parts, buffered.body)
+        }
+        None => Request::from_parts(parts, body),
+    };
+
+    if let Some(first) = findings.first() {
+        log_findings(&request, &findings);
+
+        if config.mode == InputInspectionMode::Block {
+            return Err(ApiError::bad_request(format!(
+                "Request rejected: potential {} detected",
+                first.kind
+            )));
+        }
+    }
+
+    Ok(next.run(request).await)
+}
+
+/// Bodies worth inspecting: JSON, form data, XML and plain text
+fn body_encoding(headers: &HeaderMap) -> Option<BodyEncoding> {
+    let content_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())?
+        .to_ascii_lowercase();
+
+    if content_type.starts_with("application/x-www-form-urlencoded") {
+        Some(BodyEncoding::UrlEncoded)
+    } else if content_type.starts_with("text/")
+        || content_type.contains("json")
+        || content_type.contains("xml")
+    {
+        Some(BodyEncoding::Text)
+    } else {
+        None
+    }
+}
+
+/// A request body read for inspection
+struct BufferedBody {
+    /// Up to the inspection limit from the start of the body
+    inspected: Bytes,
+    /// Whether the body continues past what was inspected
+    truncated: bool,
+    /// The complete body, to forward to the handler
+    body: Body,
+}
+
+/// Read a body up to `limit` bytes. Whatever is not read, including a read
+/// error, is left in the forwarded body for the handler to deal with.
+async fn buffer_body(body: Body, limit: usize) -> BufferedBody {
+    let mut data = body.into_data_stream();
+    let mut buffered = BytesMut::new();
+
+    let rest = loop {
+        match data.next().await {
+            Some(Ok(chunk)) => {
+                buffered.extend_from_slice(&chunk);
+                if buffered.len() > limit {
+                    break data.boxed();
+                }
+            }
+            Some(Err(e)) => break stream::once(async move { Err(e) }).boxed(),
+            None => {
+                let bytes = buffered.freeze();
+                return BufferedBody {
+                    inspected: bytes.clone(),
+                    truncated: false,
+                    body: Body::from(bytes),
+                };
+            }
+        }
+    };
+
+    let bytes = buffered.freeze();
+    BufferedBody {
+        inspected: bytes.slice(..bytes.len().min(limit)),
+        truncated: bytes.len() > limit,
+        body: Body::from_stream(stream::once(async move { Ok(bytes) }).chain(rest)),
+    }
+}
+
+fn content_length(headers: &HeaderMap) -> Option<usize> {
+    headers
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+}
+
+fn log_findings(request: &Request, findings: &[InputFinding]) {
+    let user_id = extract_user_context(request)
+        .map(|ctx| ctx.user_id.as_str())
+        .unwrap_or("anonymous");
+
+    for finding in findings {
+        let matched: String = finding.matched.chars().take(MAX_LOGGED_MATCH).collect();
+        warn!(
+            method = %request.method(),
+            path = %request.uri().path(),
+            user_id = user_id,
+            kind = %finding.kind,
+            rule = %finding.rule,
+            offset = finding.offset,
+            matched = %matched,
+            "Suspicious request input detected"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_body_encoding() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(body_encoding(&headers), None);
+
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/json; charset=utf-8"),
+        );
+        assert_eq!(body_encoding(&headers), Some(BodyEncoding::Text));
+
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/x-www-form-urlencoded"),
+        );
+        assert_eq!(body_encoding(&headers), Some(BodyEncoding::UrlEncoded));
+
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("image/png"));
+        assert_eq!(body_encoding(&headers), None);
+    }
+
+    #[tokio::test]
+    async fn test_buffer_body_within_limit() {
+        let buffered = buffer_body(Body::from("name=widget"), 64).await;
+
+        assert!(!buffered.truncated);
+        assert_eq!(buffered.inspected, "name=widget");
+        let forwarded = to_bytes(buffered.body, usize::MAX).await.unwrap();
+        assert_eq!(forwarded, "name=widget");
+    }
+
+    #[tokio::test]
+    async fn test_buffer_body_forwards_oversized_body_intact() {
+        let chunks: Vec<Result<&'static str, std::io::Error>> =
+            vec![Ok("<script>"), Ok("alert(1)"), Ok("</script>")];
+        let buffered = buffer_body(Body::from_stream(stream::iter(chunks)), 10).await;
+
+        assert!(buffered.truncated);
+        assert_eq!(buffered.inspected, "<script>al");
+        let forwarded = to_bytes(buffered.body, usize::MAX).await.unwrap();
+        assert_eq!(forwarded, "<script>alert(1)</script>");
+    }
+}
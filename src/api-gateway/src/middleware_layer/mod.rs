@@ -1,6 +1,12 @@
 //! Middleware modules for the API Gateway
 
 pub mod auth;
+pub mod body_limit;
+pub mod circuit_breaker;
 pub mod error_handling;
+pub mod in_flight;
+pub mod input_inspection;
 pub mod logging;
 pub mod rate_limit;
+pub mod response_cache;
+pub mod route_match;
@@ -2,53 +2,101 @@
 
 use axum::{
     body::Body,
-    extract::{Request, State},
-    http::{HeaderMap, HeaderName, HeaderValue},
+    extract::{ConnectInfo, Request, State},
+    http::{HeaderName, HeaderValue, Method},
     middleware::Next,
     response::Response,
 };
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
 use tracing::{debug, warn};
 
-use crate::services::rate_limiter::RateLimitResult;
 use crate::{
+    config::RouteRateLimit,
     error::{ApiError, Result},
-    middleware_layer::auth::{extract_user_context, UserContext},
+    middleware_layer::{
+        auth::{extract_user_context, UserContext},
+        route_match,
+    },
+    services::{metrics::MetricsService, rate_limiter::RateLimiterService},
     state::AppState,
 };
+use ai_core_shared::rate_limit::response::{apply_headers, client_key, too_many_requests};
 use ai_core_shared::types::core::SubscriptionTier;
 
+/// Names the limit applied to a response: the matching route pattern or `default`
+pub const HEADER_POLICY: &str = "x-ratelimit-policy";
+
 /// Rate limiting middleware using sliding window algorithm
 pub async fn rate_limit_middleware(
     State(state): State<AppState>,
     request: Request,
     next: Next,
 ) -> Result<Response> {
-    // Extract user context if available
-    let user_context = extract_user_context(&request);
-
-    // Determine rate limit key and limits
-    let (limit_key, limits) = get_rate_limit_info(&request, user_context)?;
-
-    // Check rate limit
     // Get rate limiter service
     let rate_limiter = state
         .rate_limiter
         .as_ref()
         .ok_or_else(|| ApiError::service_unavailable("rate_limiter"))?;
 
+    enforce_rate_limit(
+        rate_limiter,
+        &state.config.rate_limiting.routes,
+        &state.config.rate_limiting.trusted_proxies,
+        &state.metrics,
+        request,
+        next,
+    )
+    .await
+}
+
+/// Check the request against its route limit, or the default limit when no route
+/// override matches, and reject it with 429 once the limit is used up
+async fn enforce_rate_limit(
+    rate_limiter: &RateLimiterService,
+    routes: &[RouteRateLimit],
+    trusted_proxies: &[IpAddr],
+    metrics: &MetricsService,
+    request: Request,
+    next: Next,
+) -> Result<Response> {
+    // Extract user context if available
+    let user_context = extract_user_context(&request);
+
+    // Determine rate limit key and limits
+    let (limit_key, limits) = get_rate_limit_info(&request, user_context, trusted_proxies)?;
+
+    let path = route_match::original_path(&request);
+
+    // Route overrides count separately so a tight route limit leaves others available
+    let (limit_key, per_minute, policy) = match matching_route_limit(routes, request.method(), path)
+    {
+        Some(route) => (
+            format!("{}:route:{}", limit_key, route_key(route)),
+            route.per_minute,
+            route.path.as_str(),
+        ),
+        None => (limit_key, limits.per_minute, "default"),
+    };
+
+    // Check rate limit
     let rate_limit_result = rate_limiter
-        .check_rate_limit(&limit_key, limits.per_minute, Duration::from_secs(60))
+        .check_rate_limit(&limit_key, per_minute, Duration::from_secs(60))
         .await?;
 
     debug!(
         key = %limit_key,
+        policy = policy,
         allowed = rate_limit_result.allowed,
         remaining = rate_limit_result.remaining,
         limit = rate_limit_result.limit,
         "Rate limit check completed"
     );
 
+    let policy_header = HeaderValue::from_str(policy).ok();
+
     // Record metrics
     if !rate_limit_result.allowed {
         let user_id = user_context
@@ -58,32 +106,63 @@ pub async fn rate_limit_middleware(
             .map(|ctx| ctx.subscription_tier().to_string())
             .unwrap_or_else(|| "free".to_string());
 
-        state.metrics.record_rate_limit_hit(user_id, &tier);
+        metrics.record_rate_limit_hit(user_id, &tier);
 
         warn!(
             key = %limit_key,
+            policy = policy,
             user_id = user_id,
             tier = tier,
             "Rate limit exceeded"
         );
     }
 
-    // Continue with request if allowed
-    let mut response = if rate_limit_result.allowed {
-        next.run(request).await
-    } else {
-        return Err(ApiError::rate_limit(format!(
-            "Rate limit exceeded. Try again in {} seconds",
-            rate_limit_result.retry_after.unwrap_or(60)
-        )));
-    };
+    if !rate_limit_result.allowed {
+        let mut response = too_many_requests(
+            &rate_limit_result,
+            format!(
+                "Rate limit exceeded. Try again in {} seconds",
+                rate_limit_result.retry_after_seconds().unwrap_or(60)
+            ),
+        );
+        if let Some(policy) = policy_header {
+            response
+                .headers_mut()
+                .insert(HeaderName::from_static(HEADER_POLICY), policy);
+        }
+        return Ok(response);
+    }
 
-    // Add rate limit headers to response
-    add_rate_limit_headers(response.headers_mut(), &rate_limit_result)?;
+    // Continue with request and add rate limit headers to response
+    let mut response = next.run(request).await;
+    apply_headers(response.headers_mut(), &rate_limit_result);
+    if let Some(policy) = policy_header {
+        response
+            .headers_mut()
+            .insert(HeaderName::from_static(HEADER_POLICY), policy);
+    }
 
     Ok(response)
 }
 
+/// Most specific route limit for a request, see [`route_match::most_specific`]
+pub fn matching_route_limit<'a>(
+    routes: &'a [RouteRateLimit],
+    method: &Method,
+    path: &str,
+) -> Option<&'a RouteRateLimit> {
+    route_match::most_specific(routes, method, path)
+}
+
+/// Identifies a route limit in limiter keys
+fn route_key(route: &RouteRateLimit) -> String {
+    if route.methods.is_empty() {
+        route.path.clone()
+    } else {
+        format!("{}:{}", route.methods.join(",").to_uppercase(), route.path)
+    }
+}
+
 /// Rate limit configuration for different subscription tiers
 #[derive(Debug, Clone)]
 pub struct RateLimitConfig {
@@ -133,8 +212,22 @@ impl RateLimitConfig {
 fn get_rate_limit_info(
     request: &Request,
     user_context: Option<&UserContext>,
+    trusted_proxies: &[IpAddr],
 ) -> Result<(String, RateLimitConfig)> {
     match user_context {
+        Some(UserContext {
+            api_key: Some(api_key),
+            subscription_tier,
+            ..
+        }) => {
+            // API key - each key has its own budget, separate from the owner's other keys
+            let key = format!("rate_limit:api_key:{}", api_key.key_id);
+            let mut limits = RateLimitConfig::for_subscription_tier(subscription_tier);
+            if let Some(per_minute) = api_key.rate_limit_per_minute {
+                limits.per_minute = per_minute;
+            }
+            Ok((key, limits))
+        }
         Some(ctx) => {
             // Authenticated user - use user ID
             let key = format!("rate_limit:user:{}", ctx.user_id);
@@ -143,7 +236,7 @@ fn get_rate_limit_info(
         }
         None => {
             // Anonymous user - use IP address
-            let ip = extract_client_ip(request)?;
+            let ip = extract_client_ip(request, trusted_proxies)?;
             let key = format!("rate_limit:ip:{}", ip);
             let limits = RateLimitConfig::default();
             Ok((key, limits))
@@ -152,69 +245,29 @@ fn get_rate_limit_info(
 }
 
 /// Extract client IP address from request
-fn extract_client_ip(request: &Request<Body>) -> Result<String> {
-    // Check X-Forwarded-For header first (for proxy/load balancer)
-    if let Some(forwarded_for) = request.headers().get("X-Forwarded-For") {
-        if let Ok(forwarded_str) = forwarded_for.to_str() {
-            // Take the first IP in the chain
-            if let Some(first_ip) = forwarded_str.split(',').next() {
-                return Ok(first_ip.trim().to_string());
-            }
+///
+/// Proxy headers are only believed when the connection comes from one of
+/// `trusted_proxies`; otherwise the peer address is the client.
+fn extract_client_ip(request: &Request<Body>, trusted_proxies: &[IpAddr]) -> Result<String> {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let headers = request.headers();
+
+    // A single trusted proxy may report the client in X-Real-IP instead
+    let behind_trusted_proxy = peer.is_some_and(|peer| trusted_proxies.contains(&peer));
+    if behind_trusted_proxy && !headers.contains_key("X-Forwarded-For") {
+        if let Some(real_ip) = headers
+            .get("X-Real-IP")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<IpAddr>().ok())
+        {
+            return Ok(real_ip.to_string());
         }
     }
 
-    // Check X-Real-IP header
-    if let Some(real_ip) = request.headers().get("X-Real-IP") {
-        if let Ok(ip_str) = real_ip.to_str() {
-            return Ok(ip_str.to_string());
-        }
-    }
-
-    // Fallback to connection info (may not be available in middleware)
-    Ok("unknown".to_string())
-}
-
-/// Add rate limit headers to response
-fn add_rate_limit_headers(
-    headers: &mut HeaderMap,
-    rate_limit_result: &crate::services::rate_limiter::RateLimitResult,
-) -> Result<()> {
-    // X-RateLimit-Limit: Request limit per window
-    headers.insert(
-        HeaderName::from_static("x-ratelimit-limit"),
-        HeaderValue::from_str(&rate_limit_result.limit.to_string())
-            .map_err(|e| ApiError::internal(format!("Invalid header value: {}", e)))?,
-    );
-
-    // X-RateLimit-Remaining: Requests remaining in current window
-    headers.insert(
-        HeaderName::from_static("x-ratelimit-remaining"),
-        HeaderValue::from_str(&rate_limit_result.remaining.to_string())
-            .map_err(|e| ApiError::internal(format!("Invalid header value: {}", e)))?,
-    );
-
-    // X-RateLimit-Reset: Unix timestamp when window resets
-    let reset_timestamp = rate_limit_result
-        .reset_time
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or(Duration::from_secs(0))
-        .as_secs();
-    headers.insert(
-        HeaderName::from_static("x-ratelimit-reset"),
-        HeaderValue::from_str(&reset_timestamp.to_string())
-            .map_err(|e| ApiError::internal(format!("Invalid header value: {}", e)))?,
-    );
-
-    // Retry-After header if rate limited
-    if let Some(retry_after) = rate_limit_result.retry_after {
-        headers.insert(
-            HeaderName::from_static("retry-after"),
-            HeaderValue::from_str(&retry_after.to_string())
-                .map_err(|e| ApiError::internal(format!("Invalid header value: {}", e)))?,
-        );
-    }
-
-    Ok(())
+    Ok(client_key(headers, peer, trusted_proxies))
 }
 
 /// Endpoint-specific rate limiting middleware
@@ -238,7 +291,8 @@ pub fn endpoint_rate_limit_middleware(
             let limit_key = match user_context {
                 Some(ctx) => format!("rate_limit:endpoint:{}:user:{}", endpoint, ctx.user_id),
                 None => {
-                    let ip = extract_client_ip(&request)?;
+                    let ip =
+                        extract_client_ip(&request, &state.config.rate_limiting.trusted_proxies)?;
                     format!("rate_limit:endpoint:{}:ip:{}", endpoint, ip)
                 }
             };
@@ -255,16 +309,19 @@ pub fn endpoint_rate_limit_middleware(
                 .await?;
 
             if !rate_limit_result.allowed {
-                return Err(ApiError::rate_limit(format!(
-                    "Rate limit exceeded for endpoint {}. Try again in {} seconds",
-                    endpoint,
-                    rate_limit_result.retry_after.unwrap_or(60)
-                )));
+                return Ok(too_many_requests(
+                    &rate_limit_result,
+                    format!(
+                        "Rate limit exceeded for endpoint {}. Try again in {} seconds",
+                        endpoint,
+                        rate_limit_result.retry_after_seconds().unwrap_or(60)
+                    ),
+                ));
             }
 
             // Continue with request
             let mut response = next.run(request).await;
-            add_rate_limit_headers(response.headers_mut(), &rate_limit_result)?;
+            apply_headers(response.headers_mut(), &rate_limit_result);
 
             Ok(response)
         })
@@ -297,7 +354,8 @@ pub fn cost_based_rate_limit_middleware(
                     (key, limit)
                 }
                 None => {
-                    let ip = extract_client_ip(&request)?;
+                    let ip =
+                        extract_client_ip(&request, &state.config.rate_limiting.trusted_proxies)?;
                     let key = format!("cost_limit:ip:{}", ip);
                     (key, 10) // Very limited for anonymous users
                 }
@@ -315,15 +373,18 @@ pub fn cost_based_rate_limit_middleware(
                 .await?;
 
             if !rate_limit_result.allowed {
-                return Err(ApiError::rate_limit(format!(
-                    "Cost limit exceeded. Current operation costs {} units, {} remaining",
-                    cost, rate_limit_result.remaining
-                )));
+                return Ok(too_many_requests(
+                    &rate_limit_result,
+                    format!(
+                        "Cost limit exceeded. Current operation costs {} units, {} remaining",
+                        cost, rate_limit_result.remaining
+                    ),
+                ));
             }
 
             // Continue with request
             let mut response = next.run(request).await;
-            add_rate_limit_headers(response.headers_mut(), &rate_limit_result)?;
+            apply_headers(response.headers_mut(), &rate_limit_result);
 
             Ok(response)
         })
@@ -333,6 +394,7 @@ pub fn cost_based_rate_limit_middleware(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::middleware_layer::auth::ApiKeyContext;
     use ai_core_shared::types::core::{Permission, TokenClaims};
     use axum::body::Body;
     use std::collections::HashSet;
@@ -372,37 +434,187 @@ mod tests {
         assert_eq!(default_config.burst_multiplier, 1.0);
     }
 
+    fn route(path: &str, methods: &[&str], per_minute: u32) -> RouteRateLimit {
+        RouteRateLimit {
+            path: path.to_string(),
+            methods: methods.iter().map(|m| m.to_string()).collect(),
+            per_minute,
+        }
+    }
+
+    #[test]
+    fn test_matching_route_limit_prefers_most_specific() {
+        let routes = vec![
+            route("/v1/**", &[], 50),
+            route("/v1/workflows/:workflow_id", &[], 40),
+            route("/v1/workflows/*/execute", &[], 30),
+            route("/v1/workflows/:workflow_id/execute", &["post"], 20),
+            route("/v1/intent/parse", &["POST"], 10),
+        ];
+        let limit = |method: Method, path: &str| {
+            matching_route_limit(&routes, &method, path).map(|route| route.per_minute)
+        };
+
+        assert_eq!(limit(Method::POST, "/v1/intent/parse"), Some(10));
+        assert_eq!(limit(Method::GET, "/v1/intent/parse"), Some(50));
+        assert_eq!(limit(Method::POST, "/v1/workflows/abc/execute"), Some(20));
+        assert_eq!(limit(Method::GET, "/v1/workflows/abc/execute"), Some(30));
+        assert_eq!(limit(Method::GET, "/v1/workflows/abc"), Some(40));
+        assert_eq!(limit(Method::GET, "/v1/workflows/abc/executions"), Some(50));
+        assert_eq!(limit(Method::GET, "/health"), None);
+    }
+
     #[tokio::test]
-    async fn test_extract_client_ip() {
-        use axum::http::{HeaderValue, Request};
+    async fn test_route_limit_returns_429_while_other_routes_stay_available() {
+        use ai_core_shared::rate_limit::{HEADER_LIMIT, HEADER_REMAINING};
+        use axum::{
+            http::StatusCode,
+            routing::{get, post},
+            Router,
+        };
+        use std::sync::Arc;
+        use tower::ServiceExt;
+
+        let rate_limiter = Arc::new(RateLimiterService::with_quota(
+            ai_core_shared::config::RateLimitConfig::default(),
+        ));
+        let routes = Arc::new(vec![route("/v1/intent/parse", &["POST"], 2)]);
+        let metrics = Arc::new(MetricsService::new().unwrap());
+
+        let app = Router::new()
+            .route("/v1/intent/parse", post(|| async { "parsed" }))
+            .route("/v1/workflows", get(|| async { "workflows" }))
+            .layer(axum::middleware::from_fn(
+                move |request: Request, next: Next| {
+                    let (rate_limiter, routes, metrics) =
+                        (rate_limiter.clone(), routes.clone(), metrics.clone());
+                    async move {
+                        enforce_rate_limit(&rate_limiter, &routes, &[], &metrics, request, next)
+                            .await
+                    }
+                },
+            ));
+        let send = |method: &str, uri: &str| {
+            let request = axum::http::Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("X-Real-IP", "10.0.0.1")
+                .body(Body::empty())
+                .unwrap();
+            app.clone().oneshot(request)
+        };
 
-        // Test with X-Forwarded-For header
-        let mut request: Request<Body> =
-            Request::builder().uri("/test").body(Body::empty()).unwrap();
+        for remaining in ["1", "0"] {
+            let response = send("POST", "/v1/intent/parse").await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(response.headers()[HEADER_LIMIT], "2");
+            assert_eq!(response.headers()[HEADER_REMAINING], remaining);
+            assert_eq!(response.headers()[HEADER_POLICY], "/v1/intent/parse");
+        }
 
-        request.headers_mut().insert(
-            "X-Forwarded-For",
-            HeaderValue::from_static("192.168.1.1, 10.0.0.1"),
-        );
+        let limited = send("POST", "/v1/intent/parse").await.unwrap();
+        assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(limited.headers()[HEADER_POLICY], "/v1/intent/parse");
 
-        let ip = extract_client_ip(&request).unwrap();
-        assert_eq!(ip, "192.168.1.1");
+        let other = send("GET", "/v1/workflows").await.unwrap();
+        assert_eq!(other.status(), StatusCode::OK);
+        assert_eq!(other.headers()[HEADER_LIMIT], "5");
+        assert_eq!(other.headers()[HEADER_POLICY], "default");
+    }
 
-        // Test with X-Real-IP header
-        let mut request: Request<Body> =
-            Request::builder().uri("/test").body(Body::empty()).unwrap();
+    #[test]
+    fn test_api_key_requests_are_limited_per_key() {
+        let user = UserContext {
+            user_id: "demo-user-id".to_string(),
+            roles: vec![],
+            permissions: HashSet::from([Permission::WorkflowsRead]),
+            subscription_tier: SubscriptionTier::Pro,
+            token_claims: TokenClaims {
+                sub: "demo-user-id".to_string(),
+                iss: "AI-PLATFORM-platform".to_string(),
+                aud: "api-key".to_string(),
+                exp: i64::MAX,
+                iat: 0,
+                roles: vec![],
+                permissions: vec!["workflows:read".to_string()],
+                subscription_tier: SubscriptionTier::Pro,
+            },
+            api_key: None,
+        };
+        let with_key = |rate_limit_per_minute| UserContext {
+            api_key: Some(ApiKeyContext {
+                key_id: "key-1".to_string(),
+                name: "CI pipeline".to_string(),
+                rate_limit_per_minute,
+            }),
+            ..user.clone()
+        };
+        let request = Request::builder().uri("/test").body(Body::empty()).unwrap();
 
-        request
-            .headers_mut()
-            .insert("X-Real-IP", HeaderValue::from_static("10.0.0.1"));
+        let (key, limits) = get_rate_limit_info(&request, Some(&user), &[]).unwrap();
+        assert_eq!(key, "rate_limit:user:demo-user-id");
+        assert_eq!(limits.per_minute, 100);
 
-        let ip = extract_client_ip(&request).unwrap();
-        assert_eq!(ip, "10.0.0.1");
+        let (key, limits) = get_rate_limit_info(&request, Some(&with_key(None)), &[]).unwrap();
+        assert_eq!(key, "rate_limit:api_key:key-1");
+        assert_eq!(limits.per_minute, 100);
 
-        // Test fallback
-        let request: Request<Body> = Request::builder().uri("/test").body(Body::empty()).unwrap();
+        let (key, limits) = get_rate_limit_info(&request, Some(&with_key(Some(7))), &[]).unwrap();
+        assert_eq!(key, "rate_limit:api_key:key-1");
+        assert_eq!(limits.per_minute, 7);
+        assert_eq!(limits.per_hour, 2000);
+    }
+
+    #[tokio::test]
+    async fn test_extract_client_ip() {
+        let proxy: IpAddr = "10.0.0.1".parse().unwrap();
+        let request = |peer: Option<&str>, header: Option<(&'static str, &'static str)>| {
+            let mut builder = Request::builder().uri("/test");
+            if let Some((name, value)) = header {
+                builder = builder.header(name, value);
+            }
+            let mut request = builder.body(Body::empty()).unwrap();
+            if let Some(peer) = peer {
+                let addr = SocketAddr::new(peer.parse().unwrap(), 443);
+                request.extensions_mut().insert(ConnectInfo(addr));
+            }
+            request
+        };
 
-        let ip = extract_client_ip(&request).unwrap();
-        assert_eq!(ip, "unknown");
+        // Behind a trusted proxy the forwarded client is used
+        let forwarded = request(
+            Some("10.0.0.1"),
+            Some(("X-Forwarded-For", "192.168.1.1, 10.0.0.1")),
+        );
+        assert_eq!(
+            extract_client_ip(&forwarded, &[proxy]).unwrap(),
+            "192.168.1.1"
+        );
+
+        let real_ip = request(Some("10.0.0.1"), Some(("X-Real-IP", "192.168.1.2")));
+        assert_eq!(
+            extract_client_ip(&real_ip, &[proxy]).unwrap(),
+            "192.168.1.2"
+        );
+
+        // Anyone else gets limited by their own address whatever they send
+        let spoofed = request(
+            Some("203.0.113.5"),
+            Some(("X-Forwarded-For", "192.168.1.1")),
+        );
+        assert_eq!(
+            extract_client_ip(&spoofed, &[proxy]).unwrap(),
+            "203.0.113.5"
+        );
+
+        let spoofed = request(Some("203.0.113.5"), Some(("X-Real-IP", "192.168.1.2")));
+        assert_eq!(
+            extract_client_ip(&spoofed, &[proxy]).unwrap(),
+            "203.0.113.5"
+        );
+
+        // Test fallback
+        let unknown = request(None, Some(("X-Real-IP", "192.168.1.2")));
+        assert_eq!(extract_client_ip(&unknown, &[proxy]).unwrap(), "unknown");
     }
 }
@@ -0,0 +1,367 @@
+//! Response caching middleware for opted-in GET routes
+//!
+//! Successful GET responses to routes listed in `RoutingConfig::cached_routes`
+//! are cached per caller and served with an `ETag`; a request whose
+//! `If-None-Match` still matches gets 304 Not Modified. Successful writes that
+//! match a `RoutingConfig::cache_invalidations` entry drop cached responses by
+//! tag. Responses on cached routes carry `x-cache` (`HIT`, `MISS` or `BYPASS`)
+//! and `x-cache-ttl`, the seconds the cached copy stays fresh.
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{
+        header::{CACHE_CONTROL, ETAG, IF_NONE_MATCH},
+        HeaderMap, HeaderName, HeaderValue, Method, StatusCode,
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use crate::{
+    config::RoutingConfig,
+    error::ApiError,
+    middleware_layer::{auth::extract_user_context, route_match},
+    services::response_cache::{CachedResponse, ResponseCache},
+    state::AppState,
+};
+
+/// Whether the response came from the cache: `HIT`, `MISS` or `BYPASS`
+pub const HEADER_CACHE: &str = "x-cache";
+
+/// Seconds until the cached copy of the response expires
+pub const HEADER_CACHE_TTL: &str = "x-cache-ttl";
+
+/// Response caching middleware
+pub async fn response_cache_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    apply_response_cache(&state.response_cache, &state.config.routing, request, next).await
+}
+
+/// Serve cacheable GETs from the cache and invalidate tags after successful writes
+async fn apply_response_cache(
+    cache: &ResponseCache,
+    routing: &RoutingConfig,
+    request: Request,
+    next: Next,
+) -> Response {
+    let uri = route_match::original_uri(&request).clone();
+    let method = request.method().clone();
+
+    if method != Method::GET {
+        let response = next.run(request).await;
+        if !method.is_safe() && response.status().is_success() {
+            invalidate_for_write(cache, routing, &method, uri.path());
+        }
+        return response;
+    }
+
+    let Some(route) = route_match::most_specific(&routing.cached_routes, &method, uri.path())
+    else {
+        return next.run(request).await;
+    };
+
+    if bypasses_cache(request.headers()) {
+        let mut response = next.run(request).await;
+        set_header(
+            &mut response,
+            HEADER_CACHE,
+            HeaderValue::from_static("BYPASS"),
+        );
+        return response;
+    }
+
+    let if_none_match = request.headers().get(IF_NONE_MATCH).cloned();
+
+    // Responses depend on who is asking, so every caller gets their own entry
+    let principal = match extract_user_context(&request) {
+        Some(user) => match &user.api_key {
+            Some(api_key) => format!("api_key:{}", api_key.key_id),
+            None => format!("user:{}", user.user_id),
+        },
+        None => "anonymous".to_string(),
+    };
+    let key = format!("{} {} {}", method, uri, principal);
+
+    if let Some(cached) = cache.get(&key) {
+        debug!(key = %key, "Response cache hit");
+        return respond(&cached, if_none_match.as_ref(), "HIT");
+    }
+
+    let response = next.run(request).await;
+    if response.status() != StatusCode::OK || is_no_store(response.headers()) {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            warn!(key = %key, "Failed to read response for caching: {}", e);
+            return ApiError::internal("Failed to read upstream response").into_response();
+        }
+    };
+
+    let cached = CachedResponse::new(
+        parts.status,
+        parts.headers,
+        body,
+        Duration::from_secs(route.ttl_seconds),
+        route.tags.clone(),
+    );
+    let response = respond(&cached, if_none_match.as_ref(), "MISS");
+    cache.insert(key, cached);
+
+    response
+}
+
+/// Drop cached responses tagged by every invalidation rule the write matches
+fn invalidate_for_write(
+    cache: &ResponseCache,
+    routing: &RoutingConfig,
+    method: &Method,
+    path: &str,
+) {
+    let tags: Vec<String> = routing
+        .cache_invalidations
+        .iter()
+        .filter(|rule| route_match::matches(*rule, method, path))
+        .flat_map(|rule| rule.tags.iter().cloned())
+        .collect();
+
+    if !tags.is_empty() {
+        cache.invalidate_tags(&tags);
+    }
+}
+
+/// Build the response for a cached entry, or 304 if the caller's copy is current
+fn respond(
+    cached: &CachedResponse,
+    if_none_match: Option<&HeaderValue>,
+    outcome: &'static str,
+) -> Response {
+    let mut response = if if_none_match.is_some_and(|value| etag_matches(value, &cached.etag)) {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        let mut response = Response::new(Body::from(cached.body.clone()));
+        *response.status_mut() = cached.status;
+        *response.headers_mut() = cached.headers.clone();
+        response
+    };
+
+    if let Ok(etag) = HeaderValue::from_str(&cached.etag) {
+        response.headers_mut().insert(ETAG, etag);
+    }
+    set_header(
+        &mut response,
+        HEADER_CACHE,
+        HeaderValue::from_static(outcome),
+    );
+    set_header(
+        &mut response,
+        HEADER_CACHE_TTL,
+        HeaderValue::from(cached.remaining_ttl().as_secs_f64().ceil() as u64),
+    );
+
+    response
+}
+
+fn set_header(response: &mut Response, name: &'static str, value: HeaderValue) {
+    response
+        .headers_mut()
+        .insert(HeaderName::from_static(name), value);
+}
+
+/// Whether an `If-None-Match` value names the entity tag; weak tags compare equal
+fn etag_matches(if_none_match: &HeaderValue, etag: &str) -> bool {
+    let Ok(value) = if_none_match.to_str() else {
+        return false;
+    };
+    value.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate) == etag
+    })
+}
+
+/// Requests sending `Cache-Control: no-cache` or `no-store` skip the cache
+fn bypasses_cache(headers: &HeaderMap) -> bool {
+    cache_control_has(headers, &["no-cache", "no-store"])
+}
+
+/// Responses marked `no-store` are never cached
+fn is_no_store(headers: &HeaderMap) -> bool {
+    cache_control_has(headers, &["no-store"])
+}
+
+fn cache_control_has(headers: &HeaderMap, directives: &[&str]) -> bool {
+    headers
+        .get_all(CACHE_CONTROL)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|directive| {
+            directives
+                .iter()
+                .any(|wanted| directive.trim().eq_ignore_ascii_case(wanted))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{CacheInvalidation, CachedRoute};
+    use axum::{
+        routing::{get, post},
+        Router,
+    };
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+    use tower::ServiceExt;
+
+    /// Workflow list cached under the `workflows` tag, which creating a workflow
+    /// invalidates, plus an uncached profile route
+    fn app(listed: Arc<AtomicUsize>) -> Router {
+        let routing = Arc::new(RoutingConfig {
+            cached_routes: vec![CachedRoute {
+                path: "/v1/workflows".to_string(),
+                ttl_seconds: 60,
+                tags: vec!["workflows".to_string()],
+            }],
+            cache_invalidations: vec![CacheInvalidation {
+                path: "/v1/workflows/**".to_string(),
+                methods: vec![],
+                tags: vec!["workflows".to_string()],
+            }],
+            ..Default::default()
+        });
+        let cache = Arc::new(ResponseCache::new(100));
+
+        Router::new()
+            .route(
+                "/v1/workflows",
+                get(move || {
+                    let count = listed.fetch_add(1, Ordering::SeqCst) + 1;
+                    async move { format!("listed {} times", count) }
+                })
+                .post(|| async { StatusCode::CREATED }),
+            )
+            .route(
+                "/v1/workflows/invalid",
+                post(|| async { StatusCode::BAD_REQUEST }),
+            )
+            .route("/v1/auth/me", get(|| async { "me" }))
+            .layer(axum::middleware::from_fn(
+                move |request: Request, next: Next| {
+                    let (cache, routing) = (cache.clone(), routing.clone());
+                    async move { apply_response_cache(&cache, &routing, request, next).await }
+                },
+            ))
+    }
+
+    fn request(method: &str, uri: &str) -> axum::http::request::Builder {
+        axum::http::Request::builder().method(method).uri(uri)
+    }
+
+    async fn body_text(response: Response) -> String {
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_cached_get_serves_hits_and_not_modified() {
+        let listed = Arc::new(AtomicUsize::new(0));
+        let app = app(listed.clone());
+        let get_workflows = || request("GET", "/v1/workflows").body(Body::empty()).unwrap();
+
+        let miss = app.clone().oneshot(get_workflows()).await.unwrap();
+        assert_eq!(miss.status(), StatusCode::OK);
+        assert_eq!(miss.headers()[HEADER_CACHE], "MISS");
+        assert_eq!(miss.headers()[HEADER_CACHE_TTL], "60");
+        let etag = miss.headers()[ETAG].clone();
+        assert_eq!(body_text(miss).await, "listed 1 times");
+
+        let hit = app.clone().oneshot(get_workflows()).await.unwrap();
+        assert_eq!(hit.headers()[HEADER_CACHE], "HIT");
+        assert_eq!(hit.headers()[ETAG], etag);
+        assert_eq!(body_text(hit).await, "listed 1 times");
+
+        let not_modified = app
+            .clone()
+            .oneshot(
+                request("GET", "/v1/workflows")
+                    .header(IF_NONE_MATCH, etag)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(not_modified.status(), StatusCode::NOT_MODIFIED);
+        assert!(body_text(not_modified).await.is_empty());
+
+        let bypass = app
+            .clone()
+            .oneshot(
+                request("GET", "/v1/workflows")
+                    .header(CACHE_CONTROL, "no-cache")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(bypass.headers()[HEADER_CACHE], "BYPASS");
+        assert_eq!(body_text(bypass).await, "listed 2 times");
+
+        // Routes that have not opted in are never cached
+        let uncached = app
+            .oneshot(request("GET", "/v1/auth/me").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert!(uncached.headers().get(HEADER_CACHE).is_none());
+        assert!(uncached.headers().get(ETAG).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_successful_writes_invalidate_tagged_responses() {
+        let listed = Arc::new(AtomicUsize::new(0));
+        let app = app(listed.clone());
+        let get_workflows = || request("GET", "/v1/workflows").body(Body::empty()).unwrap();
+
+        app.clone().oneshot(get_workflows()).await.unwrap();
+
+        // A failed write leaves the cache alone
+        let rejected = app
+            .clone()
+            .oneshot(
+                request("POST", "/v1/workflows/invalid")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(rejected.status(), StatusCode::BAD_REQUEST);
+        let hit = app.clone().oneshot(get_workflows()).await.unwrap();
+        assert_eq!(hit.headers()[HEADER_CACHE], "HIT");
+
+        let created = app
+            .clone()
+            .oneshot(
+                request("POST", "/v1/workflows")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(created.status(), StatusCode::CREATED);
+
+        let refreshed = app.oneshot(get_workflows()).await.unwrap();
+        assert_eq!(refreshed.headers()[HEADER_CACHE], "MISS");
+        assert_eq!(body_text(refreshed).await, "listed 2 times");
+        assert_eq!(listed.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,140 @@
+//! Matching of configured per-route overrides against request paths
+//!
+//! Patterns look like `/v1/intent/parse`, `/v1/workflows/:id/execute` or
+//! `/v1/analytics/**`: `*` and `:name` match one segment and a trailing `**`
+//! matches any remainder.
+
+use axum::{
+    extract::{OriginalUri, Request},
+    http::{Method, Uri},
+};
+
+use crate::config::{CacheInvalidation, CachedRoute, RouteBodyLimit, RouteRateLimit};
+
+/// A configured override that applies to matching paths and methods
+pub trait RouteRule {
+    /// Path pattern the rule applies to
+    fn path_pattern(&self) -> &str;
+    /// Methods the rule applies to; empty means all methods
+    fn methods(&self) -> &[String];
+}
+
+impl RouteRule for RouteRateLimit {
+    fn path_pattern(&self) -> &str {
+        &self.path
+    }
+
+    fn methods(&self) -> &[String] {
+        &self.methods
+    }
+}
+
+impl RouteRule for RouteBodyLimit {
+    fn path_pattern(&self) -> &str {
+        &self.path
+    }
+
+    fn methods(&self) -> &[String] {
+        &self.methods
+    }
+}
+
+impl RouteRule for CachedRoute {
+    fn path_pattern(&self) -> &str {
+        &self.path
+    }
+
+    /// Only GET responses are cached
+    fn methods(&self) -> &[String] {
+        &[]
+    }
+}
+
+impl RouteRule for CacheInvalidation {
+    fn path_pattern(&self) -> &str {
+        &self.path
+    }
+
+    fn methods(&self) -> &[String] {
+        &self.methods
+    }
+}
+
+/// URI of a request as the client sent it
+///
+/// Nested routers see a stripped path, so rules match against the original one.
+pub fn original_uri(request: &Request) -> &Uri {
+    request
+        .extensions()
+        .get::<OriginalUri>()
+        .map(|uri| &uri.0)
+        .unwrap_or_else(|| request.uri())
+}
+
+/// Path of a request as the client sent it, see [`original_uri`]
+pub fn original_path(request: &Request) -> &str {
+    original_uri(request).path()
+}
+
+/// Most specific rule for a request. Rules with more literal segments win,
+/// then rules without a trailing `**`, then longer patterns, then rules naming
+/// the method. Earlier rules win ties.
+pub fn most_specific<'a, R: RouteRule>(
+    rules: &'a [R],
+    method: &Method,
+    path: &str,
+) -> Option<&'a R> {
+    rules
+        .iter()
+        .enumerate()
+        .filter(|(_, rule)| matches(*rule, method, path))
+        .max_by_key(|(index, rule)| {
+            let segments: Vec<&str> = segments(rule.path_pattern()).collect();
+            let literal = segments
+                .iter()
+                .filter(|s| !matches!(**s, "*" | "**") && !s.starts_with(':'))
+                .count();
+            let open_ended = segments.last() == Some(&"**");
+            (
+                literal,
+                !open_ended,
+                segments.len(),
+                !rule.methods().is_empty(),
+                std::cmp::Reverse(*index),
+            )
+        })
+        .map(|(_, rule)| rule)
+}
+
+/// Whether a rule applies to a request
+pub fn matches<R: RouteRule>(rule: &R, method: &Method, path: &str) -> bool {
+    (rule.methods().is_empty()
+        || rule
+            .methods()
+            .iter()
+            .any(|m| m.eq_ignore_ascii_case(method.as_str())))
+        && path_matches(rule.path_pattern(), path)
+}
+
+fn segments(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|s| !s.is_empty())
+}
+
+/// Whether a route pattern matches a request path
+fn path_matches(pattern: &str, path: &str) -> bool {
+    let mut pattern = segments(pattern);
+    let mut path = segments(path);
+
+    loop {
+        match (pattern.next(), path.next()) {
+            (Some("**"), _) => return true,
+            (None, None) => return true,
+            (Some(expected), Some(actual)) => {
+                if expected != "*" && !expected.starts_with(':') && expected != actual {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+    }
+}
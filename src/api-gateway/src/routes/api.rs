@@ -26,6 +26,13 @@ pub fn router() -> Router<AppState> {
             "/auth/sessions/:session_id",
             delete(handlers::auth::revoke_session),
         )
+        // API key management routes
+        .route("/auth/api-keys", post(handlers::api_keys::create_api_key))
+        .route("/auth/api-keys", get(handlers::api_keys::list_api_keys))
+        .route(
+            "/auth/api-keys/:key_id",
+            delete(handlers::api_keys::revoke_api_key),
+        )
         // Workflow management routes
         .route("/workflows", post(handlers::workflows::create_workflow))
         .route("/workflows", get(handlers::workflows::list_workflows))
@@ -97,6 +104,14 @@ pub fn router() -> Router<AppState> {
         .route("/admin/users/:user_id/suspend", post(suspend_user))
         .route("/admin/users/:user_id/activate", post(activate_user))
         // System management routes (admin only)
+        .route(
+            "/admin/circuit-breakers",
+            get(handlers::circuit_breakers::list_circuit_breakers),
+        )
+        .route(
+            "/admin/circuit-breakers/:service/reset",
+            post(handlers::circuit_breakers::reset_circuit_breaker),
+        )
         .route("/admin/system/config", get(get_system_config))
         .route("/admin/system/config", put(update_system_config))
         .route("/admin/system/maintenance", post(enter_maintenance_mode))
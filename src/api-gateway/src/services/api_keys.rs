@@ -0,0 +1,247 @@
+//! API key service for programmatic clients that cannot use the JWT flow
+//!
+//! Keys are sent in the `X-API-Key` header. Only a SHA-256 hash of each key is
+//! stored in the `api_keys` table, alongside its scopes, subscription tier,
+//! optional per-minute rate limit, expiry and revocation time.
+
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::error::{ApiError, Result};
+
+/// Header programmatic clients send their API key in
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Prefix of every issued key
+const API_KEY_PREFIX: &str = "ak_";
+
+/// Characters of a key kept in clear so owners can tell their keys apart
+const DISPLAY_PREFIX_LEN: usize = 8;
+
+const SELECT_API_KEY: &str = r#"
+    SELECT id, user_id, key_name, key_prefix, scopes, subscription_tier,
+           rate_limit_per_minute, expires_at, revoked_at, created_at
+    FROM api_keys
+"#;
+
+/// Stored API key, without its hash
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ApiKeyRecord {
+    pub id: Uuid,
+    pub user_id: String,
+    pub key_name: String,
+    pub key_prefix: String,
+    pub scopes: Vec<String>,
+    pub subscription_tier: String,
+    pub rate_limit_per_minute: Option<i32>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ApiKeyRecord {
+    /// Reject keys that have been revoked or have expired
+    pub fn ensure_usable(&self, now: DateTime<Utc>) -> Result<()> {
+        if self.revoked_at.is_some_and(|revoked_at| revoked_at <= now) {
+            return Err(ApiError::authentication("API key has been revoked"));
+        }
+
+        if self.expires_at.is_some_and(|expires_at| expires_at <= now) {
+            return Err(ApiError::authentication("API key has expired"));
+        }
+
+        Ok(())
+    }
+}
+
+/// Parameters for issuing a new key
+#[derive(Debug, Clone)]
+pub struct NewApiKey {
+    pub user_id: String,
+    pub key_name: String,
+    pub scopes: Vec<String>,
+    pub subscription_tier: String,
+    pub rate_limit_per_minute: Option<i32>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Hash stored for a key; keys carry enough entropy that an unsalted hash is safe
+pub fn hash_api_key(api_key: &str) -> String {
+    format!("{:x}", Sha256::digest(api_key.as_bytes()))
+}
+
+/// Generate a new random key
+fn generate_api_key() -> String {
+    format!(
+        "{}{}{}",
+        API_KEY_PREFIX,
+        Uuid::new_v4().simple(),
+        Uuid::new_v4().simple()
+    )
+}
+
+/// API key service backed by the `api_keys` table
+#[derive(Clone)]
+pub struct ApiKeyService {
+    db_pool: PgPool,
+}
+
+impl ApiKeyService {
+    /// Create new API key service
+    pub fn new(db_pool: PgPool) -> Self {
+        Self { db_pool }
+    }
+
+    /// Resolve a presented key to its record, rejecting unknown, revoked and expired keys
+    pub async fn authenticate(&self, api_key: &str) -> Result<ApiKeyRecord> {
+        let record =
+            sqlx::query_as::<_, ApiKeyRecord>(&format!("{} WHERE key_hash = $1", SELECT_API_KEY))
+                .bind(hash_api_key(api_key))
+                .fetch_optional(&self.db_pool)
+                .await?
+                .ok_or_else(|| ApiError::authentication("Invalid API key"))?;
+
+        record.ensure_usable(Utc::now())?;
+        debug!(key_id = %record.id, user_id = %record.user_id, "API key authenticated");
+
+        self.touch(record.id);
+        Ok(record)
+    }
+
+    /// Record when a key was last used without holding up the request
+    fn touch(&self, key_id: Uuid) {
+        let db_pool = self.db_pool.clone();
+        tokio::spawn(async move {
+            if let Err(e) = sqlx::query("UPDATE api_keys SET last_used_at = NOW() WHERE id = $1")
+                .bind(key_id)
+                .execute(&db_pool)
+                .await
+            {
+                warn!(key_id = %key_id, "Failed to record API key use: {}", e);
+            }
+        });
+    }
+
+    /// Issue a new key. The plaintext key is only ever returned here.
+    pub async fn create(&self, new_key: NewApiKey) -> Result<(ApiKeyRecord, String)> {
+        let api_key = generate_api_key();
+
+        let record = sqlx::query_as::<_, ApiKeyRecord>(
+            r#"
+            INSERT INTO api_keys (
+                user_id, key_name, key_hash, key_prefix, scopes,
+                subscription_tier, rate_limit_per_minute, expires_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id, user_id, key_name, key_prefix, scopes, subscription_tier,
+                      rate_limit_per_minute, expires_at, revoked_at, created_at
+            "#,
+        )
+        .bind(&new_key.user_id)
+        .bind(&new_key.key_name)
+        .bind(hash_api_key(&api_key))
+        .bind(&api_key[..DISPLAY_PREFIX_LEN])
+        .bind(&new_key.scopes)
+        .bind(&new_key.subscription_tier)
+        .bind(new_key.rate_limit_per_minute)
+        .bind(new_key.expires_at)
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        info!(key_id = %record.id, user_id = %record.user_id, "API key created");
+        Ok((record, api_key))
+    }
+
+    /// Keys owned by a user, newest first
+    pub async fn list_for_user(&self, user_id: &str) -> Result<Vec<ApiKeyRecord>> {
+        let records = sqlx::query_as::<_, ApiKeyRecord>(&format!(
+            "{} WHERE user_id = $1 ORDER BY created_at DESC",
+            SELECT_API_KEY
+        ))
+        .bind(user_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Revoke one of a user's keys; requests using it fail from then on
+    pub async fn revoke(&self, user_id: &str, key_id: Uuid) -> Result<()> {
+        let revoked = sqlx::query(
+            "UPDATE api_keys SET revoked_at = NOW() \
+             WHERE id = $1 AND user_id = $2 AND revoked_at IS NULL",
+        )
+        .bind(key_id)
+        .bind(user_id)
+        .execute(&self.db_pool)
+        .await?;
+
+        if revoked.rows_affected() == 0 {
+            return Err(ApiError::not_found("API key"));
+        }
+
+        info!(key_id = %key_id, user_id = user_id, "API key revoked");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn record() -> ApiKeyRecord {
+        ApiKeyRecord {
+            id: Uuid::new_v4(),
+            user_id: "demo-user-id".to_string(),
+            key_name: "CI pipeline".to_string(),
+            key_prefix: "ak_12345".to_string(),
+            scopes: vec!["workflows:read".to_string()],
+            subscription_tier: "pro".to_string(),
+            rate_limit_per_minute: None,
+            expires_at: None,
+            revoked_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_generated_keys_are_unique_and_hashed_stably() {
+        let first = generate_api_key();
+        let second = generate_api_key();
+
+        assert!(first.starts_with(API_KEY_PREFIX));
+        assert_eq!(first.len(), API_KEY_PREFIX.len() + 64);
+        assert_ne!(first, second);
+
+        assert_eq!(hash_api_key(&first), hash_api_key(&first));
+        assert_ne!(hash_api_key(&first), hash_api_key(&second));
+        assert_eq!(hash_api_key(&first).len(), 64);
+    }
+
+    #[test]
+    fn test_revoked_and_expired_keys_are_rejected() {
+        let now = Utc::now();
+        assert!(record().ensure_usable(now).is_ok());
+
+        let expiring = ApiKeyRecord {
+            expires_at: Some(now + Duration::days(1)),
+            ..record()
+        };
+        assert!(expiring.ensure_usable(now).is_ok());
+
+        let expired = ApiKeyRecord {
+            expires_at: Some(now - Duration::seconds(1)),
+            ..record()
+        };
+        assert!(expired.ensure_usable(now).is_err());
+
+        let revoked = ApiKeyRecord {
+            revoked_at: Some(now - Duration::seconds(1)),
+            ..record()
+        };
+        assert!(revoked.ensure_usable(now).is_err());
+    }
+}
@@ -1,28 +1,202 @@
 //! Circuit breaker service for fault tolerance
+//!
+//! Every upstream service has its own breaker. A closed breaker measures the
+//! failure rate over a fixed window and opens once the window has seen enough
+//! requests and the rate reaches the threshold. An open breaker fails fast
+//! until its timeout passes, then goes half-open and admits a few probe
+//! requests: if they all succeed the circuit closes, any failure reopens it.
 
-use ai_core_shared::config::RoutingConfig;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
+use tracing::{info, warn};
 
+use crate::config::{CircuitBreakerSettings, RoutingConfig};
 use crate::error::{ApiError, Result};
+use crate::services::metrics::{CircuitBreakerState, MetricsService};
 
 /// Circuit breaker states
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum CircuitState {
     Closed,   // Normal operation
     Open,     // Circuit open, requests fail fast
     HalfOpen, // Testing if service is back up
 }
 
+impl CircuitState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CircuitState::Closed => "closed",
+            CircuitState::Open => "open",
+            CircuitState::HalfOpen => "half_open",
+        }
+    }
+}
+
+impl From<CircuitState> for CircuitBreakerState {
+    fn from(state: CircuitState) -> Self {
+        match state {
+            CircuitState::Closed => CircuitBreakerState::Closed,
+            CircuitState::Open => CircuitBreakerState::Open,
+            CircuitState::HalfOpen => CircuitBreakerState::HalfOpen,
+        }
+    }
+}
+
 /// Circuit breaker for a service
 #[derive(Debug, Clone)]
 pub struct CircuitBreaker {
     pub state: CircuitState,
-    pub failure_count: u32,
+    pub settings: CircuitBreakerSettings,
+    /// Start of the window `requests` and `failures` are counted in
+    pub window_started: Instant,
+    pub requests: u32,
+    pub failures: u32,
     pub last_failure_time: Option<Instant>,
-    pub failure_threshold: u32,
-    pub timeout: Duration,
+    /// When the breaker entered its current state
+    pub state_changed_at: Instant,
+    /// Half-open probes admitted but not yet reported
+    pub probes_in_flight: u32,
+    /// Half-open probes that succeeded
+    pub probe_successes: u32,
+}
+
+/// A request refused because the service's circuit is open
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CircuitOpen {
+    /// Time until the breaker admits another request
+    pub retry_after: Duration,
+}
+
+type Transition = (CircuitState, CircuitState);
+
+impl CircuitBreaker {
+    fn new(settings: CircuitBreakerSettings) -> Self {
+        let now = Instant::now();
+        Self {
+            state: CircuitState::Closed,
+            settings,
+            window_started: now,
+            requests: 0,
+            failures: 0,
+            last_failure_time: None,
+            state_changed_at: now,
+            probes_in_flight: 0,
+            probe_successes: 0,
+        }
+    }
+
+    fn transition(&mut self, to: CircuitState) -> Option<Transition> {
+        let from = self.state;
+        if from == to {
+            return None;
+        }
+
+        let now = Instant::now();
+        self.state = to;
+        self.state_changed_at = now;
+        self.probes_in_flight = 0;
+        self.probe_successes = 0;
+        if to == CircuitState::Closed {
+            self.window_started = now;
+            self.requests = 0;
+            self.failures = 0;
+        }
+        Some((from, to))
+    }
+
+    /// Start a new measurement window once the current one has elapsed
+    fn roll_window(&mut self) {
+        if self.window_started.elapsed() >= self.settings.window {
+            self.window_started = Instant::now();
+            self.requests = 0;
+            self.failures = 0;
+        }
+    }
+
+    fn failure_rate(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            self.failures as f64 / self.requests as f64
+        }
+    }
+
+    fn try_acquire(&mut self) -> (std::result::Result<(), CircuitOpen>, Option<Transition>) {
+        let mut transition = None;
+
+        if self.state == CircuitState::Open {
+            let elapsed = self.state_changed_at.elapsed();
+            if elapsed < self.settings.open_duration {
+                let retry_after = self.settings.open_duration - elapsed;
+                return (Err(CircuitOpen { retry_after }), None);
+            }
+            transition = self.transition(CircuitState::HalfOpen);
+        }
+
+        if self.state == CircuitState::HalfOpen {
+            // Probes that never reported back within a window (e.g. cancelled
+            // requests) must not keep the circuit half-open forever
+            if self.probes_in_flight >= self.settings.half_open_requests
+                && self.state_changed_at.elapsed() >= self.settings.window
+            {
+                self.probes_in_flight = 0;
+                self.state_changed_at = Instant::now();
+            }
+
+            if self.probes_in_flight + self.probe_successes >= self.settings.half_open_requests {
+                let retry_after = Duration::from_secs(1);
+                return (Err(CircuitOpen { retry_after }), transition);
+            }
+            self.probes_in_flight += 1;
+        }
+
+        (Ok(()), transition)
+    }
+
+    fn record_success(&mut self) -> Option<Transition> {
+        match self.state {
+            CircuitState::Closed => {
+                self.roll_window();
+                self.requests += 1;
+                None
+            }
+            CircuitState::HalfOpen => {
+                self.probes_in_flight = self.probes_in_flight.saturating_sub(1);
+                self.probe_successes += 1;
+                if self.probe_successes >= self.settings.half_open_requests {
+                    self.transition(CircuitState::Closed)
+                } else {
+                    None
+                }
+            }
+            // Late result of a request admitted before the circuit opened
+            CircuitState::Open => None,
+        }
+    }
+
+    fn record_failure(&mut self) -> Option<Transition> {
+        self.last_failure_time = Some(Instant::now());
+
+        match self.state {
+            CircuitState::Closed => {
+                self.roll_window();
+                self.requests += 1;
+                self.failures += 1;
+                if self.requests >= self.settings.min_requests
+                    && self.failure_rate() >= self.settings.failure_threshold
+                {
+                    self.transition(CircuitState::Open)
+                } else {
+                    None
+                }
+            }
+            CircuitState::HalfOpen => self.transition(CircuitState::Open),
+            CircuitState::Open => None,
+        }
+    }
 }
 
 /// Circuit breaker service managing multiple service breakers
@@ -30,6 +204,7 @@ pub struct CircuitBreaker {
 pub struct CircuitBreakerService {
     breakers: Arc<Mutex<HashMap<String, CircuitBreaker>>>,
     config: RoutingConfig,
+    metrics: Option<Arc<MetricsService>>,
 }
 
 impl CircuitBreakerService {
@@ -38,169 +213,189 @@ impl CircuitBreakerService {
         Self {
             breakers: Arc::new(Mutex::new(HashMap::new())),
             config,
+            metrics: None,
         }
     }
 
-    /// Check if a request to service should be allowed
-    pub fn can_execute(&self, service_name: &str) -> bool {
-        if !self.config.circuit_breaker.enabled {
-            return true;
-        }
-
-        let mut breakers = self.breakers.lock().unwrap();
-        let breaker = breakers
-            .entry(service_name.to_string())
-            .or_insert_with(|| CircuitBreaker {
-                state: CircuitState::Closed,
-                failure_count: 0,
-                last_failure_time: None,
-                failure_threshold: self.config.circuit_breaker.failure_threshold,
-                timeout: Duration::from_secs(self.config.circuit_breaker.recovery_timeout_seconds),
+    /// Report state transitions to the metrics service
+    pub fn with_metrics(mut self, metrics: Arc<MetricsService>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Whether circuit breaking is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.config.circuit_breaker_enabled
+    }
+
+    /// Run `f` on the breaker for a service, creating it on first use, and
+    /// report any state transition it causes
+    fn with_breaker<T>(
+        &self,
+        service_name: &str,
+        f: impl FnOnce(&mut CircuitBreaker) -> (T, Option<Transition>),
+    ) -> T {
+        let (result, transition) = {
+            let mut breakers = self.breakers.lock().unwrap_or_else(|e| e.into_inner());
+            let breaker = breakers.entry(service_name.to_string()).or_insert_with(|| {
+                CircuitBreaker::new(self.config.circuit_breaker_settings(service_name))
             });
+            f(breaker)
+        };
 
-        self.update_breaker_state(breaker);
+        if let Some((from, to)) = transition {
+            self.report_transition(service_name, from, to);
+        }
+        result
+    }
+
+    fn report_transition(&self, service_name: &str, from: CircuitState, to: CircuitState) {
+        match to {
+            CircuitState::Open => warn!(
+                service = service_name,
+                from = from.as_str(),
+                "Circuit breaker opened"
+            ),
+            _ => info!(
+                service = service_name,
+                from = from.as_str(),
+                to = to.as_str(),
+                "Circuit breaker state changed"
+            ),
+        }
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_circuit_breaker_transition(service_name, from.into(), to.into());
+        }
+    }
 
-        match breaker.state {
-            CircuitState::Closed => true,
-            CircuitState::Open => false,
-            CircuitState::HalfOpen => true, // Allow one request to test
+    /// Admit a request to a service, or report how long its circuit stays open
+    pub fn try_acquire(&self, service_name: &str) -> std::result::Result<(), CircuitOpen> {
+        if !self.is_enabled() {
+            return Ok(());
         }
+        self.with_breaker(service_name, CircuitBreaker::try_acquire)
+    }
+
+    /// Check if a request to service should be allowed
+    pub fn can_execute(&self, service_name: &str) -> bool {
+        self.try_acquire(service_name).is_ok()
     }
 
     /// Record a successful request
     pub fn record_success(&self, service_name: &str) {
-        if !self.config.circuit_breaker.enabled {
+        if !self.is_enabled() {
             return;
         }
-
-        let mut breakers = self.breakers.lock().unwrap();
-        if let Some(breaker) = breakers.get_mut(service_name) {
-            breaker.failure_count = 0;
-            breaker.state = CircuitState::Closed;
-        }
+        self.with_breaker(service_name, |breaker| ((), breaker.record_success()));
     }
 
     /// Record a failed request
     pub fn record_failure(&self, service_name: &str) {
-        if !self.config.circuit_breaker.enabled {
+        if !self.is_enabled() {
             return;
         }
-
-        let mut breakers = self.breakers.lock().unwrap();
-        let breaker = breakers
-            .entry(service_name.to_string())
-            .or_insert_with(|| CircuitBreaker {
-                state: CircuitState::Closed,
-                failure_count: 0,
-                last_failure_time: None,
-                failure_threshold: self.config.circuit_breaker.failure_threshold,
-                timeout: Duration::from_secs(self.config.circuit_breaker.recovery_timeout_seconds),
-            });
-
-        breaker.failure_count += 1;
-        breaker.last_failure_time = Some(Instant::now());
-
-        if breaker.failure_count >= breaker.failure_threshold {
-            breaker.state = CircuitState::Open;
-        }
+        self.with_breaker(service_name, |breaker| ((), breaker.record_failure()));
     }
 
     /// Get current state of circuit breaker for service
     pub fn get_state(&self, service_name: &str) -> CircuitState {
-        let breakers = self.breakers.lock().unwrap();
+        let breakers = self.breakers.lock().unwrap_or_else(|e| e.into_inner());
         breakers
             .get(service_name)
-            .map(|b| b.state.clone())
+            .map(|b| b.state)
             .unwrap_or(CircuitState::Closed)
     }
 
-    /// Get failure count for service
+    /// Get failure count for service in the current window
     pub fn get_failure_count(&self, service_name: &str) -> u32 {
-        let breakers = self.breakers.lock().unwrap();
-        breakers
-            .get(service_name)
-            .map(|b| b.failure_count)
-            .unwrap_or(0)
-    }
-
-    /// Update breaker state based on time and current state
-    fn update_breaker_state(&self, breaker: &mut CircuitBreaker) {
-        if breaker.state == CircuitState::Open {
-            if let Some(last_failure) = breaker.last_failure_time {
-                if last_failure.elapsed() >= breaker.timeout {
-                    breaker.state = CircuitState::HalfOpen;
-                }
-            }
-        }
+        let breakers = self.breakers.lock().unwrap_or_else(|e| e.into_inner());
+        breakers.get(service_name).map(|b| b.failures).unwrap_or(0)
     }
 
     /// Reset circuit breaker for service (admin function)
     pub fn reset(&self, service_name: &str) -> Result<()> {
-        let mut breakers = self.breakers.lock().unwrap();
-        if let Some(breaker) = breakers.get_mut(service_name) {
-            breaker.state = CircuitState::Closed;
-            breaker.failure_count = 0;
-            breaker.last_failure_time = None;
+        let known = self.config.services.contains_key(service_name)
+            || self
+                .breakers
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .contains_key(service_name);
+        if !known {
+            return Err(ApiError::not_found(format!(
+                "Service '{}' not found",
+                service_name
+            )));
         }
+
+        self.with_breaker(service_name, |breaker| {
+            let transition = breaker.transition(CircuitState::Closed);
+            breaker.window_started = Instant::now();
+            breaker.requests = 0;
+            breaker.failures = 0;
+            breaker.last_failure_time = None;
+            ((), transition)
+        });
         Ok(())
     }
 
-    /// Get stats for all circuit breakers
+    /// Get stats for every configured service and every service seen so far
     pub fn get_stats(&self) -> HashMap<String, CircuitBreakerStats> {
-        let breakers = self.breakers.lock().unwrap();
-        breakers
-            .iter()
-            .map(|(name, breaker)| {
-                (
-                    name.clone(),
-                    CircuitBreakerStats {
-                        state: breaker.state.clone(),
-                        failure_count: breaker.failure_count,
-                        failure_threshold: breaker.failure_threshold,
-                        last_failure_time: breaker.last_failure_time,
-                    },
-                )
+        let breakers = self.breakers.lock().unwrap_or_else(|e| e.into_inner());
+        let mut stats: HashMap<String, CircuitBreakerStats> = self
+            .config
+            .services
+            .keys()
+            .map(|name| {
+                let breaker = CircuitBreaker::new(self.config.circuit_breaker_settings(name));
+                (name.clone(), CircuitBreakerStats::from(&breaker))
             })
-            .collect()
+            .collect();
+
+        stats.extend(
+            breakers
+                .iter()
+                .map(|(name, breaker)| (name.clone(), CircuitBreakerStats::from(breaker))),
+        );
+        stats
     }
 }
 
 /// Circuit breaker statistics
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CircuitBreakerStats {
     pub state: CircuitState,
+    /// Requests and failures in the current window
+    pub requests: u32,
     pub failure_count: u32,
-    pub failure_threshold: u32,
-    pub last_failure_time: Option<Instant>,
+    pub failure_rate: f64,
+    pub failure_threshold: f64,
+    pub min_requests: u32,
+    /// Seconds since the last failure
+    pub last_failure_seconds_ago: Option<u64>,
+    /// Seconds until an open circuit starts probing
+    pub retry_after_seconds: Option<u64>,
 }
 
-impl serde::Serialize for CircuitBreakerStats {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        use serde::ser::SerializeStruct;
-        let mut state = serializer.serialize_struct("CircuitBreakerStats", 4)?;
-        state.serialize_field("state", &self.state)?;
-        state.serialize_field("failure_count", &self.failure_count)?;
-        state.serialize_field("failure_threshold", &self.failure_threshold)?;
-        state.serialize_field(
-            "last_failure_time",
-            &self.last_failure_time.map(|t| t.elapsed().as_secs()),
-        )?;
-        state.end()
-    }
-}
+impl From<&CircuitBreaker> for CircuitBreakerStats {
+    fn from(breaker: &CircuitBreaker) -> Self {
+        let retry_after_seconds = (breaker.state == CircuitState::Open).then(|| {
+            breaker
+                .settings
+                .open_duration
+                .saturating_sub(breaker.state_changed_at.elapsed())
+                .as_secs()
+        });
 
-impl serde::Serialize for CircuitState {
-    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        match self {
-            CircuitState::Closed => serializer.serialize_str("closed"),
-            CircuitState::Open => serializer.serialize_str("open"),
-            CircuitState::HalfOpen => serializer.serialize_str("half_open"),
+        Self {
+            state: breaker.state,
+            requests: breaker.requests,
+            failure_count: breaker.failures,
+            failure_rate: breaker.failure_rate(),
+            failure_threshold: breaker.settings.failure_threshold,
+            min_requests: breaker.settings.min_requests,
+            last_failure_seconds_ago: breaker.last_failure_time.map(|t| t.elapsed().as_secs()),
+            retry_after_seconds,
         }
     }
 }
@@ -209,20 +404,29 @@ impl serde::Serialize for CircuitState {
 mod tests {
     use super::*;
 
+    fn service_with(configure: impl FnOnce(&mut RoutingConfig)) -> CircuitBreakerService {
+        let mut config = RoutingConfig {
+            circuit_breaker_enabled: true,
+            circuit_breaker_failure_threshold: 0.5,
+            circuit_breaker_min_requests: 4,
+            circuit_breaker_timeout_seconds: 60,
+            circuit_breaker_half_open_requests: 2,
+            ..Default::default()
+        };
+        configure(&mut config);
+        CircuitBreakerService::new(config)
+    }
+
     #[test]
     fn test_circuit_breaker_states() {
-        let mut config = RoutingConfig::default();
-        config.circuit_breaker_enabled = true;
-        config.circuit_breaker_failure_threshold = 3.0; // Set as float, will be cast to u32
-        config.circuit_breaker_timeout_seconds = 60;
-
-        let service = CircuitBreakerService::new(config);
+        let service = service_with(|_| {});
 
         // Initially closed
         assert!(service.can_execute("test-service"));
         assert_eq!(service.get_state("test-service"), CircuitState::Closed);
 
-        // Record failures
+        // Failures below the minimum request count never open the circuit
+        service.record_failure("test-service");
         service.record_failure("test-service");
         service.record_failure("test-service");
         assert!(service.can_execute("test-service")); // Still closed
@@ -230,10 +434,73 @@ mod tests {
         service.record_failure("test-service");
         assert!(!service.can_execute("test-service")); // Now open
         assert_eq!(service.get_state("test-service"), CircuitState::Open);
+        assert!(service.try_acquire("test-service").unwrap_err().retry_after > Duration::ZERO);
 
-        // Record success should close it
-        service.record_success("test-service");
+        // Other services are unaffected
+        assert!(service.can_execute("other-service"));
+
+        // Reset closes it
+        service.reset("test-service").unwrap();
         assert!(service.can_execute("test-service"));
         assert_eq!(service.get_state("test-service"), CircuitState::Closed);
+        assert!(service.reset("unknown-service").is_err());
+    }
+
+    #[test]
+    fn test_circuit_opens_on_failure_rate() {
+        let service = service_with(|_| {});
+
+        // 1 failure in 4 requests stays below the 50% threshold
+        for _ in 0..3 {
+            service.record_success("svc");
+        }
+        service.record_failure("svc");
+        assert_eq!(service.get_state("svc"), CircuitState::Closed);
+
+        // 3 failures in 6 requests reaches it
+        service.record_failure("svc");
+        service.record_failure("svc");
+        assert_eq!(service.get_state("svc"), CircuitState::Open);
+
+        let stats = service.get_stats();
+        assert_eq!(stats["svc"].state, CircuitState::Open);
+        assert_eq!(stats["svc"].failure_rate, 0.5);
+    }
+
+    #[test]
+    fn test_half_open_probes_close_or_reopen_circuit() {
+        let service = service_with(|config| config.circuit_breaker_timeout_seconds = 0);
+        for _ in 0..4 {
+            service.record_failure("svc");
+        }
+        assert_eq!(service.get_state("svc"), CircuitState::Open);
+
+        // Timeout elapsed: only `half_open_requests` probes are admitted
+        assert!(service.can_execute("svc"));
+        assert_eq!(service.get_state("svc"), CircuitState::HalfOpen);
+        assert!(service.can_execute("svc"));
+        assert!(!service.can_execute("svc"));
+
+        // Any probe failure reopens the circuit
+        service.record_failure("svc");
+        assert_eq!(service.get_state("svc"), CircuitState::Open);
+
+        // All probes succeeding closes it
+        assert!(service.can_execute("svc"));
+        assert!(service.can_execute("svc"));
+        service.record_success("svc");
+        assert_eq!(service.get_state("svc"), CircuitState::HalfOpen);
+        service.record_success("svc");
+        assert_eq!(service.get_state("svc"), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_disabled_circuit_breaker_always_allows() {
+        let service = service_with(|config| config.circuit_breaker_enabled = false);
+        for _ in 0..10 {
+            service.record_failure("svc");
+        }
+        assert!(service.can_execute("svc"));
+        assert_eq!(service.get_state("svc"), CircuitState::Closed);
     }
 }
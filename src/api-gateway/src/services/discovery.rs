@@ -0,0 +1,121 @@
+//! Registration of the gateway with the service-discovery service
+//!
+//! When `ServerConfig::discovery_url` is set the gateway registers itself at
+//! startup and deregisters as soon as it starts draining, so clients resolving
+//! it through discovery stop being sent to an instance that is going away.
+
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::config::ServerConfig;
+use crate::error::{ApiError, Result};
+
+/// Name the gateway registers under
+const SERVICE_NAME: &str = "api-gateway";
+
+/// How long registration calls may take before they are abandoned
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Deserialize)]
+struct DiscoveryResponse<T> {
+    data: Option<T>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Registration {
+    service_id: String,
+}
+
+/// The gateway's entry in service discovery
+pub struct DiscoveryRegistration {
+    http_client: Client,
+    base_url: String,
+    service_id: String,
+}
+
+impl DiscoveryRegistration {
+    /// Register the gateway if service discovery is configured. Failures are
+    /// logged and the gateway runs unregistered.
+    pub async fn register(http_client: &Client, server: &ServerConfig) -> Option<Self> {
+        let base_url = server.discovery_url.as_deref()?.trim_end_matches('/');
+        let address = server.advertise_host.as_deref().unwrap_or(&server.host);
+
+        let body = serde_json::json!({
+            "name": SERVICE_NAME,
+            "version": env!("CARGO_PKG_VERSION"),
+            "address": address,
+            "port": server.port,
+            "protocol": "http",
+            "metadata": { "health_endpoint": "/readiness" },
+        });
+
+        match Self::send_registration(http_client, base_url, &body).await {
+            Ok(service_id) => {
+                info!(service_id = %service_id, "Registered with service discovery at {}", base_url);
+                Some(Self {
+                    http_client: http_client.clone(),
+                    base_url: base_url.to_string(),
+                    service_id,
+                })
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to register with service discovery at {}: {}",
+                    base_url, e
+                );
+                None
+            }
+        }
+    }
+
+    async fn send_registration(
+        http_client: &Client,
+        base_url: &str,
+        body: &serde_json::Value,
+    ) -> Result<String> {
+        let response: DiscoveryResponse<Registration> = http_client
+            .post(format!("{}/api/v1/services", base_url))
+            .timeout(REQUEST_TIMEOUT)
+            .json(body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        match response.data {
+            Some(registration) => Ok(registration.service_id),
+            None => Err(ApiError::external_service(
+                "service-discovery",
+                response
+                    .error
+                    .unwrap_or_else(|| "registration returned no service id".to_string()),
+            )),
+        }
+    }
+
+    /// Remove the gateway from service discovery
+    pub async fn deregister(&self) {
+        let result = self
+            .http_client
+            .delete(format!(
+                "{}/api/v1/services/{}",
+                self.base_url, self.service_id
+            ))
+            .timeout(REQUEST_TIMEOUT)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+
+        match result {
+            Ok(_) => info!(service_id = %self.service_id, "Deregistered from service discovery"),
+            Err(e) => warn!(
+                service_id = %self.service_id,
+                "Failed to deregister from service discovery: {}", e
+            ),
+        }
+    }
+}
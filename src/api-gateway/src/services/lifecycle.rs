@@ -0,0 +1,166 @@
+//! Server lifecycle: in-flight request tracking and shutdown draining
+//!
+//! On a shutdown signal the gateway starts draining: readiness probes report
+//! not-ready so load balancers stop sending traffic, the server stops accepting
+//! connections, and in-flight requests get up to the drain timeout to finish.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+/// Shared draining flag and in-flight request count
+pub struct Lifecycle {
+    draining: watch::Sender<bool>,
+    in_flight: Arc<AtomicUsize>,
+}
+
+/// Counts a request as in flight until dropped
+pub struct InFlightGuard {
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl Default for Lifecycle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Lifecycle {
+    pub fn new() -> Self {
+        Self {
+            draining: watch::Sender::new(false),
+            in_flight: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Whether the gateway is shutting down and should no longer receive traffic
+    pub fn is_draining(&self) -> bool {
+        *self.draining.borrow()
+    }
+
+    /// Flip readiness to not-ready; requests already accepted carry on
+    pub fn start_draining(&self) {
+        if !self.draining.send_replace(true) {
+            info!(
+                in_flight = self.in_flight(),
+                "Draining: readiness now reports not ready"
+            );
+        }
+    }
+
+    /// Resolves once draining has started
+    pub async fn draining_started(&self) {
+        let mut draining = self.draining.subscribe();
+        // The sender lives as long as `self`, so this cannot fail
+        let _ = draining.wait_for(|draining| *draining).await;
+    }
+
+    /// Requests currently being handled
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Count a request as in flight for as long as the guard lives
+    pub fn track(&self) -> InFlightGuard {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        InFlightGuard {
+            in_flight: self.in_flight.clone(),
+        }
+    }
+
+    /// Run the server to completion, abandoning in-flight requests once
+    /// `drain_timeout` has passed since draining started
+    pub async fn run_until_drained<F>(
+        &self,
+        server: F,
+        drain_timeout: Duration,
+    ) -> std::io::Result<()>
+    where
+        F: Future<Output = std::io::Result<()>>,
+    {
+        let deadline = async {
+            self.draining_started().await;
+            tokio::time::sleep(drain_timeout).await;
+        };
+
+        tokio::select! {
+            result = server => result,
+            _ = deadline => {
+                warn!(
+                    in_flight = self.in_flight(),
+                    drain_timeout_seconds = drain_timeout.as_secs_f64(),
+                    "Drain timeout elapsed with {} requests still in flight",
+                    self.in_flight()
+                );
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_in_flight_requests_are_counted_until_done() {
+        let lifecycle = Lifecycle::new();
+
+        let first = lifecycle.track();
+        let second = lifecycle.track();
+        assert_eq!(lifecycle.in_flight(), 2);
+
+        drop(first);
+        assert_eq!(lifecycle.in_flight(), 1);
+        drop(second);
+        assert_eq!(lifecycle.in_flight(), 0);
+
+        assert!(!lifecycle.is_draining());
+        lifecycle.start_draining();
+        assert!(lifecycle.is_draining());
+        lifecycle.draining_started().await;
+    }
+
+    #[tokio::test]
+    async fn test_drain_timeout_abandons_stuck_requests() {
+        let lifecycle = Lifecycle::new();
+        let _stuck = lifecycle.track();
+
+        // A server that never finishes draining on its own
+        let server = std::future::pending::<std::io::Result<()>>();
+        lifecycle.start_draining();
+
+        let drained = tokio::time::timeout(
+            Duration::from_secs(5),
+            lifecycle.run_until_drained(server, Duration::from_millis(20)),
+        )
+        .await;
+        assert!(drained.is_ok_and(|result| result.is_ok()));
+        assert_eq!(lifecycle.in_flight(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_server_that_drains_in_time_finishes_normally() {
+        let lifecycle = Lifecycle::new();
+
+        let server = async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            Err(std::io::Error::other("listener closed"))
+        };
+
+        // Not draining yet, so only the server can finish the future
+        let result = lifecycle
+            .run_until_drained(server, Duration::from_millis(1))
+            .await;
+        assert!(result.is_err());
+    }
+}
@@ -41,6 +41,7 @@ pub struct MetricsService {
     // Service health metrics
     pub service_health_status: GaugeVec,
     pub circuit_breaker_state: GaugeVec,
+    pub circuit_breaker_transitions_total: CounterVec,
 
     // Custom metrics storage
     custom_counters: Arc<std::sync::RwLock<HashMap<String, Counter>>>,
@@ -267,6 +268,20 @@ impl MetricsService {
             ))
         })?;
 
+        let circuit_breaker_transitions_total = CounterVec::new(
+            Opts::new(
+                "circuit_breaker_transitions_total",
+                "Total number of circuit breaker state transitions",
+            ),
+            &["service_name", "from", "to"],
+        )
+        .map_err(|e| {
+            ApiError::internal(format!(
+                "Failed to create circuit_breaker_transitions_total metric: {}",
+                e
+            ))
+        })?;
+
         // Register all metrics
         registry.register(Box::new(http_requests_total.clone()))?;
         registry.register(Box::new(http_request_duration_seconds.clone()))?;
@@ -284,6 +299,7 @@ impl MetricsService {
         registry.register(Box::new(authentication_failures_total.clone()))?;
         registry.register(Box::new(service_health_status.clone()))?;
         registry.register(Box::new(circuit_breaker_state.clone()))?;
+        registry.register(Box::new(circuit_breaker_transitions_total.clone()))?;
 
         info!(
             "Metrics service initialized with {} collectors",
@@ -308,6 +324,7 @@ impl MetricsService {
             authentication_failures_total,
             service_health_status,
             circuit_breaker_state,
+            circuit_breaker_transitions_total,
             custom_counters: Arc::new(std::sync::RwLock::new(HashMap::new())),
             custom_gauges: Arc::new(std::sync::RwLock::new(HashMap::new())),
             custom_histograms: Arc::new(std::sync::RwLock::new(HashMap::new())),
@@ -532,6 +549,19 @@ impl MetricsService {
         debug!("Set circuit breaker state: {} = {:?}", service_name, state);
     }
 
+    /// Record a circuit breaker state transition and the new state
+    pub fn record_circuit_breaker_transition(
+        &self,
+        service_name: &str,
+        from: CircuitBreakerState,
+        to: CircuitBreakerState,
+    ) {
+        self.circuit_breaker_transitions_total
+            .with_label_values(&[service_name, from.as_str(), to.as_str()])
+            .inc();
+        self.set_circuit_breaker_state(service_name, to);
+    }
+
     /// Create and register a custom counter
     pub fn create_custom_counter(&self, name: &str, help: &str) -> Result<()> {
         let counter = Counter::new(name, help)
@@ -659,6 +689,16 @@ pub enum CircuitBreakerState {
     Open,
 }
 
+impl CircuitBreakerState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CircuitBreakerState::Closed => "closed",
+            CircuitBreakerState::HalfOpen => "half_open",
+            CircuitBreakerState::Open => "open",
+        }
+    }
+}
+
 /// Metrics summary for health checks
 #[derive(Debug, serde::Serialize)]
 pub struct MetricsSummary {
@@ -1,12 +1,16 @@
 //! Core services for the API Gateway
 
+pub mod api_keys;
 pub mod auth;
 pub mod circuit_breaker;
+pub mod discovery;
 pub mod health;
 pub mod intent_parser;
+pub mod lifecycle;
 pub mod metrics;
 pub mod orchestrator;
 pub mod rate_limiter;
+pub mod response_cache;
 pub mod router;
 pub mod secure_database;
 pub mod workflow;
@@ -1,45 +1,36 @@
-//! Rate limiting service using governor with in-memory and distributed state
+//! Rate limiting service backed by the shared sliding-window limiter
 
-use std::{
-    net::IpAddr,
-    time::{Duration, SystemTime},
-};
+use std::{net::IpAddr, sync::Arc, time::Duration};
 use tracing::{debug, warn};
 
 use crate::error::{ApiError, Result};
 use ai_core_shared::config::RateLimitConfig;
+use ai_core_shared::rate_limit::{per_second_quota, SlidingWindowLimiter};
 
 /// Result of a rate limit check
-#[derive(Debug, Clone)]
-pub struct RateLimitResult {
-    /// Whether the request is allowed
-    pub allowed: bool,
-    /// Number of requests remaining in the current window
-    pub remaining: u32,
-    /// Maximum number of requests allowed in the window
-    pub limit: u32,
-    /// When the rate limit window resets
-    pub reset_time: SystemTime,
-    /// How long to wait before retrying (in seconds)
-    pub retry_after: Option<u64>,
-}
+pub use ai_core_shared::rate_limit::RateLimitDecision as RateLimitResult;
 
 /// Rate limiting service that can use either in-memory or distributed state
 #[derive(Clone)]
 pub struct RateLimiterService {
     /// Configuration
     config: RateLimitConfig,
+    /// Sliding-window state per key
+    limiter: Arc<SlidingWindowLimiter>,
 }
 
 impl RateLimiterService {
     /// Create new rate limiter service with in-memory state
     pub fn new(config: RateLimitConfig, _redis_manager: redis::aio::ConnectionManager) -> Self {
-        Self { config }
+        Self::with_quota(config)
     }
 
     /// Create new rate limiter service with custom quota
     pub fn with_quota(config: RateLimitConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            limiter: Arc::new(SlidingWindowLimiter::new()),
+        }
     }
 
     /// Check rate limit with specified limits
@@ -51,13 +42,7 @@ impl RateLimiterService {
     ) -> Result<RateLimitResult> {
         if !self.config.enabled {
             debug!("Rate limiting disabled, allowing request for key: {}", key);
-            return Ok(RateLimitResult {
-                allowed: true,
-                remaining: limit,
-                limit,
-                reset_time: SystemTime::now() + window,
-                retry_after: None,
-            });
+            return Ok(RateLimitResult::unlimited(limit, window));
         }
 
         debug!(
@@ -65,14 +50,7 @@ impl RateLimiterService {
             key, limit, window
         );
 
-        // Simplified rate limiting - always allow for now
-        Ok(RateLimitResult {
-            allowed: true,
-            remaining: limit.saturating_sub(1),
-            limit,
-            reset_time: SystemTime::now() + window,
-            retry_after: None,
-        })
+        Ok(self.limiter.check(key, limit, window))
     }
 
     /// Check cost-based rate limit
@@ -85,23 +63,15 @@ impl RateLimiterService {
     ) -> Result<RateLimitResult> {
         if !self.config.enabled {
             debug!("Rate limiting disabled, allowing request for key: {}", key);
-            return Ok(RateLimitResult {
-                allowed: true,
-                remaining: limit,
-                limit,
-                reset_time: SystemTime::now() + window,
-                retry_after: None,
-            });
+            return Ok(RateLimitResult::unlimited(limit, window));
         }
 
-        // Simplified cost-based rate limiting - always allow for now
-        Ok(RateLimitResult {
-            allowed: true,
-            remaining: limit.saturating_sub(cost),
-            limit,
-            reset_time: SystemTime::now() + window,
-            retry_after: None,
-        })
+        Ok(self.limiter.check_cost(key, cost, limit, window))
+    }
+
+    /// Drop limiter state for idle keys
+    pub fn prune(&self) {
+        self.limiter.prune();
     }
 
     /// Check if a key is rate limited
@@ -112,8 +82,19 @@ impl RateLimiterService {
         }
 
         debug!("Checking rate limit for key: {}", key);
-        // Simplified - always allow for now
-        Ok(())
+        let (limit, window) =
+            per_second_quota(self.config.requests_per_second, self.config.burst_size);
+        let decision = self.limiter.check(key, limit, window);
+
+        if decision.allowed {
+            Ok(())
+        } else {
+            warn!("Rate limit exceeded for key: {}", key);
+            Err(ApiError::rate_limit(format!(
+                "Rate limit exceeded. Try again in {} seconds",
+                decision.retry_after_seconds().unwrap_or(1)
+            )))
+        }
     }
 
     /// Check rate limit for an IP address
@@ -186,9 +167,7 @@ pub struct RateLimiterStats {
 /// Default implementation for testing
 impl Default for RateLimiterService {
     fn default() -> Self {
-        Self {
-            config: RateLimitConfig::default(),
-        }
+        Self::with_quota(RateLimitConfig::default())
     }
 }
 
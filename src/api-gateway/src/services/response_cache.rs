@@ -0,0 +1,246 @@
+//! In-memory cache of successful GET responses
+//!
+//! Entries are keyed by method, path, query and caller, expire after their
+//! route's TTL and are indexed by tag so writes can drop every response they
+//! make stale. The cache is bounded: when full, expired entries are purged
+//! first, then the entry closest to expiry is evicted.
+
+use axum::{
+    body::Bytes,
+    http::{HeaderMap, StatusCode},
+};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// A stored response
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+    /// Strong validator derived from the body
+    pub etag: String,
+    pub expires_at: Instant,
+    pub tags: Vec<String>,
+}
+
+impl CachedResponse {
+    pub fn new(
+        status: StatusCode,
+        headers: HeaderMap,
+        body: Bytes,
+        ttl: Duration,
+        tags: Vec<String>,
+    ) -> Self {
+        Self {
+            status,
+            headers,
+            etag: etag_for(&body),
+            body,
+            expires_at: Instant::now() + ttl,
+            tags,
+        }
+    }
+
+    /// Time left before the entry expires
+    pub fn remaining_ttl(&self) -> Duration {
+        self.expires_at.saturating_duration_since(Instant::now())
+    }
+}
+
+/// Strong ETag for a response body
+pub fn etag_for(body: &[u8]) -> String {
+    let digest = Sha256::digest(body);
+    let hex: String = digest[..16].iter().map(|b| format!("{:02x}", b)).collect();
+    format!("\"{}\"", hex)
+}
+
+#[derive(Default)]
+struct CacheEntries {
+    responses: HashMap<String, CachedResponse>,
+    /// Cache keys stored under each tag
+    tags: HashMap<String, HashSet<String>>,
+}
+
+impl CacheEntries {
+    fn remove(&mut self, key: &str) -> Option<CachedResponse> {
+        let removed = self.responses.remove(key)?;
+        for tag in &removed.tags {
+            if let Some(keys) = self.tags.get_mut(tag) {
+                keys.remove(key);
+                if keys.is_empty() {
+                    self.tags.remove(tag);
+                }
+            }
+        }
+        Some(removed)
+    }
+
+    fn purge_expired(&mut self, now: Instant) {
+        let expired: Vec<String> = self
+            .responses
+            .iter()
+            .filter(|(_, response)| response.expires_at <= now)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in expired {
+            self.remove(&key);
+        }
+    }
+}
+
+/// Response cache shared by all requests
+#[derive(Clone)]
+pub struct ResponseCache {
+    entries: Arc<Mutex<CacheEntries>>,
+    max_entries: usize,
+}
+
+impl ResponseCache {
+    /// Create new response cache holding at most `max_entries` responses
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(CacheEntries::default())),
+            max_entries: max_entries.max(1),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheEntries> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Fresh response stored under `key`
+    pub fn get(&self, key: &str) -> Option<CachedResponse> {
+        let mut entries = self.lock();
+        match entries.responses.get(key) {
+            Some(response) if response.expires_at <= Instant::now() => {
+                entries.remove(key);
+                None
+            }
+            Some(response) => Some(response.clone()),
+            None => None,
+        }
+    }
+
+    /// Store a response, evicting others if the cache is full
+    pub fn insert(&self, key: String, response: CachedResponse) {
+        let mut entries = self.lock();
+        entries.remove(&key);
+
+        if entries.responses.len() >= self.max_entries {
+            entries.purge_expired(Instant::now());
+        }
+        if entries.responses.len() >= self.max_entries {
+            if let Some(oldest) = entries
+                .responses
+                .iter()
+                .min_by_key(|(_, response)| response.expires_at)
+                .map(|(key, _)| key.clone())
+            {
+                entries.remove(&oldest);
+            }
+        }
+
+        for tag in &response.tags {
+            entries
+                .tags
+                .entry(tag.clone())
+                .or_default()
+                .insert(key.clone());
+        }
+        entries.responses.insert(key, response);
+    }
+
+    /// Drop every response stored under any of `tags`, returning how many were dropped
+    pub fn invalidate_tags(&self, tags: &[String]) -> usize {
+        let mut entries = self.lock();
+        let keys: HashSet<String> = tags
+            .iter()
+            .filter_map(|tag| entries.tags.get(tag))
+            .flatten()
+            .cloned()
+            .collect();
+
+        let removed = keys
+            .iter()
+            .filter(|key| entries.remove(key).is_some())
+            .count();
+        if removed > 0 {
+            debug!(tags = ?tags, removed = removed, "Invalidated cached responses");
+        }
+        removed
+    }
+
+    /// Number of responses currently stored, including any not yet purged after expiry
+    pub fn len(&self) -> usize {
+        self.lock().responses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(body: &'static str, ttl: Duration, tags: &[&str]) -> CachedResponse {
+        CachedResponse::new(
+            StatusCode::OK,
+            HeaderMap::new(),
+            Bytes::from_static(body.as_bytes()),
+            ttl,
+            tags.iter().map(|tag| tag.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn test_entries_expire_and_are_invalidated_by_tag() {
+        let cache = ResponseCache::new(10);
+        let minute = Duration::from_secs(60);
+
+        cache.insert("list".to_string(), response("[]", minute, &["workflows"]));
+        cache.insert(
+            "one".to_string(),
+            response("{}", minute, &["workflows", "workflow"]),
+        );
+        cache.insert("me".to_string(), response("{}", minute, &["profile"]));
+        cache.insert("gone".to_string(), response("{}", Duration::ZERO, &[]));
+
+        assert!(cache.get("gone").is_none());
+        assert_eq!(cache.get("list").unwrap().etag, etag_for(b"[]"));
+
+        assert_eq!(cache.invalidate_tags(&["workflows".to_string()]), 2);
+        assert!(cache.get("list").is_none());
+        assert!(cache.get("one").is_none());
+        assert!(cache.get("me").is_some());
+        assert_eq!(cache.invalidate_tags(&["workflow".to_string()]), 0);
+    }
+
+    #[test]
+    fn test_cache_is_bounded() {
+        let cache = ResponseCache::new(2);
+
+        cache.insert(
+            "a".to_string(),
+            response("a", Duration::from_secs(10), &["t"]),
+        );
+        cache.insert(
+            "b".to_string(),
+            response("b", Duration::from_secs(20), &["t"]),
+        );
+        cache.insert(
+            "c".to_string(),
+            response("c", Duration::from_secs(30), &["t"]),
+        );
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("a").is_none());
+        assert_eq!(cache.invalidate_tags(&["t".to_string()]), 2);
+        assert!(cache.is_empty());
+    }
+}
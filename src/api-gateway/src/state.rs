@@ -14,10 +14,11 @@ use tracing::{info, warn};
 use crate::config::Config;
 use crate::error::{ApiError, Result};
 use crate::services::{
-    auth::AuthService, circuit_breaker::CircuitBreakerService, health::HealthService,
-    intent_parser::IntentParserService, metrics::MetricsService,
-    orchestrator::WorkflowOrchestratorService, rate_limiter::RateLimiterService,
-    router::ServiceRouter, workflow::WorkflowService,
+    api_keys::ApiKeyService, auth::AuthService, circuit_breaker::CircuitBreakerService,
+    health::HealthService, intent_parser::IntentParserService, lifecycle::Lifecycle,
+    metrics::MetricsService, orchestrator::WorkflowOrchestratorService,
+    rate_limiter::RateLimiterService, response_cache::ResponseCache, router::ServiceRouter,
+    workflow::WorkflowService,
 };
 use ai_core_shared::config::{
     CircuitBreakerConfig, HealthCheckConfig, LoadBalancingStrategy, RateLimitStrategy,
@@ -42,14 +43,17 @@ pub struct AppState {
     pub redis_manager: Option<ConnectionManager>,
     pub http_client: Client,
     pub auth_service: Option<Arc<AuthService>>,
+    pub api_key_service: Option<Arc<ApiKeyService>>,
     pub rate_limiter: Option<Arc<RateLimiterService>>,
     pub service_router: Arc<ServiceRouter>,
     pub circuit_breaker: Arc<CircuitBreakerService>,
+    pub response_cache: Arc<ResponseCache>,
     pub health_service: Arc<HealthService>,
     pub workflow_service: Option<Arc<WorkflowService>>,
     pub workflow_orchestrator: Option<Arc<WorkflowOrchestratorService>>,
     pub intent_parser: Arc<IntentParserService>,
     pub metrics: Arc<MetricsService>,
+    pub lifecycle: Arc<Lifecycle>,
 }
 
 impl AppState {
@@ -95,6 +99,8 @@ impl AppState {
             redis_manager.clone(),
         ));
 
+        let api_key_service = Arc::new(ApiKeyService::new(db_pool.clone()));
+
         let shared_rate_limit_config = ai_core_shared::config::RateLimitConfig {
             enabled: config.rate_limiting.enabled,
             requests_per_second: 100, // Default value
@@ -133,7 +139,13 @@ impl AppState {
             },
         };
 
-        let circuit_breaker = Arc::new(CircuitBreakerService::new(shared_routing_config.clone()));
+        let circuit_breaker = Arc::new(
+            CircuitBreakerService::new(config.routing.clone()).with_metrics(metrics.clone()),
+        );
+
+        let response_cache = Arc::new(ResponseCache::new(
+            config.routing.response_cache_max_entries,
+        ));
 
         let service_router = Arc::new(ServiceRouter::new(
             shared_routing_config.clone(),
@@ -163,14 +175,17 @@ impl AppState {
             redis_manager: Some(redis_manager),
             http_client,
             auth_service: Some(auth_service),
+            api_key_service: Some(api_key_service),
             rate_limiter: Some(rate_limiter),
             service_router,
             circuit_breaker,
+            response_cache,
             health_service,
             workflow_service: Some(workflow_service),
             workflow_orchestrator: Some(workflow_orchestrator),
             intent_parser,
             metrics,
+            lifecycle: Arc::new(Lifecycle::new()),
         })
     }
 
@@ -211,7 +226,13 @@ impl AppState {
             },
         };
 
-        let circuit_breaker = Arc::new(CircuitBreakerService::new(shared_routing_config.clone()));
+        let circuit_breaker = Arc::new(
+            CircuitBreakerService::new(config.routing.clone()).with_metrics(metrics.clone()),
+        );
+
+        let response_cache = Arc::new(ResponseCache::new(
+            config.routing.response_cache_max_entries,
+        ));
 
         let service_router = Arc::new(ServiceRouter::new(
             shared_routing_config.clone(),
@@ -237,14 +258,17 @@ impl AppState {
             redis_manager: None,
             http_client,
             auth_service: None,
+            api_key_service: None,
             rate_limiter: None,
             service_router,
             circuit_breaker,
+            response_cache,
             health_service,
             workflow_service: None,
             workflow_orchestrator: None,
             intent_parser,
             metrics,
+            lifecycle: Arc::new(Lifecycle::new()),
         })
     }
 
@@ -354,6 +378,8 @@ mod tests {
             rate_limiting: crate::config::RateLimitConfig::default(),
             routing: crate::config::RoutingConfig::default(),
             observability: crate::config::ObservabilityConfig::default(),
+            integrations: crate::config::IntegrationsConfig::default(),
+            input_inspection: crate::config::InputInspectionConfig::default(),
             environment: "test".to_string(),
         };
 
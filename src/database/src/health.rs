@@ -10,6 +10,7 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tracing::{debug, error, warn};
 
+use crate::connections::ConnectionHealth;
 use crate::DatabaseError;
 
 /// Health checker for PostgreSQL database
@@ -46,6 +47,10 @@ pub struct HealthStatus {
     pub postgres: PostgresHealth,
     #[cfg(feature = "redis")]
     pub redis: Option<RedisHealth>,
+    #[cfg(feature = "clickhouse")]
+    pub clickhouse: Option<BackendHealth>,
+    #[cfg(feature = "mongodb")]
+    pub mongodb: Option<BackendHealth>,
     pub last_check: chrono::DateTime<chrono::Utc>,
 }
 
@@ -81,6 +86,30 @@ pub struct RedisHealth {
     pub last_successful_connection: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+/// Health status of a probed database backend (ClickHouse, MongoDB)
+#[derive(Debug, Clone, Serialize)]
+pub struct BackendHealth {
+    pub healthy: bool,
+    pub response_time_ms: u64,
+    pub error_message: Option<String>,
+    pub last_successful_connection: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<ConnectionHealth> for BackendHealth {
+    fn from(health: ConnectionHealth) -> Self {
+        Self {
+            healthy: health.healthy,
+            response_time_ms: health.response_time_ms,
+            error_message: health.error_message,
+            last_successful_connection: if health.healthy {
+                Some(chrono::Utc::now())
+            } else {
+                None
+            },
+        }
+    }
+}
+
 impl HealthChecker {
     /// Create new health checker
     pub fn new(postgres_pool: Arc<PgPool>, config: HealthConfig) -> Self {
@@ -103,6 +132,10 @@ impl HealthChecker {
             postgres: postgres_health,
             #[cfg(feature = "redis")]
             redis: None,
+            #[cfg(feature = "clickhouse")]
+            clickhouse: None,
+            #[cfg(feature = "mongodb")]
+            mongodb: None,
             last_check: chrono::Utc::now(),
         };
 
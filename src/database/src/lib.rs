@@ -194,6 +194,9 @@ impl DatabaseManager {
     }
 
     /// Health check for database connections
+    ///
+    /// ClickHouse and MongoDB are probed with `SELECT 1` and `ping`, bounded by
+    /// their configured timeouts; any unhealthy backend fails `overall_healthy`.
     pub async fn health_check(&self) -> Result<health::HealthStatus> {
         let pg_health = self.check_postgres_health().await?;
         let mut overall_healthy = pg_health.healthy;
@@ -241,6 +244,40 @@ impl DatabaseManager {
             None
         };
 
+        // Check ClickHouse health if available
+        #[cfg(feature = "clickhouse")]
+        let clickhouse_health = if let Some(clickhouse) = &self.clickhouse {
+            let timeout = self
+                .config
+                .clickhouse
+                .as_ref()
+                .map_or(DEFAULT_HEALTH_CHECK_TIMEOUT, |config| {
+                    Duration::from_secs(config.timeout_seconds)
+                });
+            let health = probe_with_timeout("ClickHouse", timeout, clickhouse.health_check()).await;
+            overall_healthy = overall_healthy && health.healthy;
+            Some(health::BackendHealth::from(health))
+        } else {
+            None
+        };
+
+        // Check MongoDB health if available
+        #[cfg(feature = "mongodb")]
+        let mongodb_health = if let Some(mongodb) = &self.mongodb {
+            let timeout = self
+                .config
+                .mongodb
+                .as_ref()
+                .map_or(DEFAULT_HEALTH_CHECK_TIMEOUT, |config| {
+                    Duration::from_secs(config.server_selection_timeout_seconds)
+                });
+            let health = probe_with_timeout("MongoDB", timeout, mongodb.health_check()).await;
+            overall_healthy = overall_healthy && health.healthy;
+            Some(health::BackendHealth::from(health))
+        } else {
+            None
+        };
+
         Ok(health::HealthStatus {
             postgres: health::PostgresHealth {
                 healthy: pg_health.healthy,
@@ -262,6 +299,10 @@ impl DatabaseManager {
             },
             #[cfg(feature = "redis")]
             redis: redis_health,
+            #[cfg(feature = "clickhouse")]
+            clickhouse: clickhouse_health,
+            #[cfg(feature = "mongodb")]
+            mongodb: mongodb_health,
             overall_healthy,
             last_check: chrono::Utc::now(),
         })
@@ -309,6 +350,42 @@ impl DatabaseManager {
     }
 }
 
+/// Fallback timeout for backend health probes
+#[cfg(any(feature = "clickhouse", feature = "mongodb"))]
+const DEFAULT_HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Run a backend health probe, reporting errors and timeouts as unhealthy
+#[cfg(any(feature = "clickhouse", feature = "mongodb"))]
+async fn probe_with_timeout<F>(backend: &str, timeout: Duration, probe: F) -> ConnectionHealth
+where
+    F: std::future::Future<Output = std::result::Result<ConnectionHealth, DatabaseError>>,
+{
+    let start_time = std::time::Instant::now();
+    let result = tokio::time::timeout(timeout, probe).await;
+    let response_time_ms = start_time.elapsed().as_millis() as u64;
+
+    match result {
+        Ok(Ok(health)) => health,
+        Ok(Err(e)) => ConnectionHealth {
+            healthy: false,
+            response_time_ms,
+            error_message: Some(e.to_string()),
+        },
+        Err(_) => {
+            tracing::warn!("{} health check timed out after {:?}", backend, timeout);
+            ConnectionHealth {
+                healthy: false,
+                response_time_ms,
+                error_message: Some(format!(
+                    "{} health check timed out after {}ms",
+                    backend,
+                    timeout.as_millis()
+                )),
+            }
+        }
+    }
+}
+
 /// Overall health status
 #[derive(Debug, Clone, Serialize)]
 pub struct HealthStatus {
@@ -376,4 +453,36 @@ mod tests {
         assert_eq!(config.postgresql.min_connections, 5);
         assert!(config.monitoring.enabled);
     }
+
+    #[cfg(any(feature = "clickhouse", feature = "mongodb"))]
+    #[tokio::test]
+    async fn test_probe_timeout_is_unhealthy() {
+        let health = probe_with_timeout(
+            "MongoDB",
+            Duration::from_millis(10),
+            std::future::pending::<std::result::Result<ConnectionHealth, DatabaseError>>(),
+        )
+        .await;
+
+        assert!(!health.healthy);
+        assert_eq!(
+            health.error_message.as_deref(),
+            Some("MongoDB health check timed out after 10ms")
+        );
+    }
+
+    #[cfg(any(feature = "clickhouse", feature = "mongodb"))]
+    #[tokio::test]
+    async fn test_probe_error_is_unhealthy() {
+        let health = probe_with_timeout("ClickHouse", Duration::from_secs(1), async {
+            Err(DatabaseError::Connection("connection refused".to_string()))
+        })
+        .await;
+
+        assert!(!health.healthy);
+        assert_eq!(
+            health.error_message.as_deref(),
+            Some("Connection error: connection refused")
+        );
+    }
 }
@@ -3,6 +3,10 @@
 //! This module provides database migration functionality for PostgreSQL, MongoDB,
 //! ClickHouse, and Redis databases. It handles schema versioning, migration execution,
 //! and rollback operations.
+//!
+//! Before applying anything, `check` verifies that already-applied migrations still
+//! match their recorded checksums and returns the pending plan, so deploy pipelines
+//! can fail fast on drift without touching the schema.
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
@@ -10,8 +14,10 @@ use chrono::{DateTime, Utc};
 // use mongodb::Database as MongoDatabase;
 // use redis::aio::ConnectionManager;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use sqlx::{PgPool, Row};
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 
 use crate::DatabaseError;
@@ -87,8 +93,31 @@ impl MigrationManager {
         // Load available migrations
         let migrations = self.load_migrations().await?;
 
-        // Get executed migrations
-        let executed_versions = self.get_executed_versions().await?;
+        // Refuse to run on top of modified migrations
+        let applied = self.get_applied_migrations().await?;
+        verify_applied_checksums(&migrations, &applied)?;
+
+        if self.config.dry_run {
+            let pending = pending_migrations(&migrations, &applied);
+            for migration in &pending {
+                tracing::info!(
+                    "Dry run: would apply {} migration {} ({})",
+                    migration.database_type,
+                    migration.version,
+                    migration.name
+                );
+            }
+
+            return Ok(MigrationResult {
+                total_migrations: migrations.len() as u32,
+                successful_migrations: (migrations.len() - pending.len()) as u32,
+                failed_migrations: 0,
+                execution_time: 0,
+                database_results,
+            });
+        }
+
+        let executed_versions: Vec<String> = applied.iter().map(AppliedMigration::key).collect();
 
         // Execute pending migrations
         for migration in migrations {
@@ -155,6 +184,31 @@ impl MigrationManager {
         Ok(result)
     }
 
+    /// List pending migrations without executing them
+    pub async fn plan(&self) -> Result<Vec<Migration>, DatabaseError> {
+        let migrations = self.load_migrations().await?;
+        let applied = self.get_applied_migrations().await?;
+        Ok(pending_migrations(&migrations, &applied))
+    }
+
+    /// Verify that applied migrations have not been modified since they ran
+    pub async fn verify_checksums(&self) -> Result<(), DatabaseError> {
+        let migrations = self.load_migrations().await?;
+        let applied = self.get_applied_migrations().await?;
+        verify_applied_checksums(&migrations, &applied)
+    }
+
+    /// Verify checksums and return the pending plan, without executing anything
+    ///
+    /// Intended for deploy pipelines: an error means drift was detected and the
+    /// deploy should stop before any migration is applied.
+    pub async fn check(&self) -> Result<Vec<Migration>, DatabaseError> {
+        let migrations = self.load_migrations().await?;
+        let applied = self.get_applied_migrations().await?;
+        verify_applied_checksums(&migrations, &applied)?;
+        Ok(pending_migrations(&migrations, &applied))
+    }
+
     /// Load available migrations
     async fn load_migrations(&self) -> Result<Vec<Migration>, DatabaseError> {
         let mut migrations = Vec::new();
@@ -195,6 +249,13 @@ impl MigrationManager {
             checksum: calculate_checksum("initial_users_schema"),
         });
 
+        if let Some(dir) = &self.config.migrations_dir {
+            migrations.extend(load_migrations_from_dir(
+                Path::new(dir),
+                DatabaseType::PostgreSQL,
+            )?);
+        }
+
         Ok(migrations)
     }
 
@@ -319,24 +380,24 @@ impl MigrationManager {
         Ok(())
     }
 
-    /// Get list of executed migration versions
-    async fn get_executed_versions(&self) -> Result<Vec<String>, DatabaseError> {
+    /// Get successfully applied migrations with their recorded checksums
+    async fn get_applied_migrations(&self) -> Result<Vec<AppliedMigration>, DatabaseError> {
         let rows = sqlx::query(
-            "SELECT version, database_type FROM schema_migrations WHERE success = TRUE",
+            "SELECT version, database_type, checksum FROM schema_migrations WHERE success = TRUE",
         )
         .fetch_all(&*self.postgres)
         .await?;
 
-        let versions = rows
+        let applied = rows
             .iter()
-            .map(|row| {
-                let version: String = row.try_get("version").unwrap_or_default();
-                let db_type: String = row.try_get("database_type").unwrap_or_default();
-                format!("{}_{}", version, db_type)
+            .map(|row| AppliedMigration {
+                version: row.try_get("version").unwrap_or_default(),
+                database_type: row.try_get("database_type").unwrap_or_default(),
+                checksum: row.try_get("checksum").unwrap_or_default(),
             })
             .collect();
 
-        Ok(versions)
+        Ok(applied)
     }
 
     /// Rollback a specific migration
@@ -368,6 +429,10 @@ pub struct MigrationConfig {
     pub backup_before_migration: bool,
     pub migration_timeout_seconds: u64,
     pub dry_run: bool,
+    /// Directory of `<version>_<name>.sql` PostgreSQL migrations, with optional
+    /// `<version>_<name>.down.sql` rollbacks
+    #[serde(default)]
+    pub migrations_dir: Option<String>,
 }
 
 impl Default for MigrationConfig {
@@ -378,6 +443,7 @@ impl Default for MigrationConfig {
             backup_before_migration: true,
             migration_timeout_seconds: 300,
             dry_run: false,
+            migrations_dir: None,
         }
     }
 }
@@ -402,6 +468,26 @@ pub enum DatabaseType {
     Redis,
 }
 
+impl Migration {
+    fn key(&self) -> String {
+        format!("{}_{}", self.version, self.database_type)
+    }
+}
+
+/// Checksum of a migration as recorded when it was applied
+#[derive(Debug, Clone)]
+struct AppliedMigration {
+    version: String,
+    database_type: String,
+    checksum: String,
+}
+
+impl AppliedMigration {
+    fn key(&self) -> String {
+        format!("{}_{}", self.version, self.database_type)
+    }
+}
+
 impl DatabaseType {
     fn from_string(s: &str) -> Result<Self, DatabaseError> {
         match s.to_lowercase().as_str() {
@@ -470,14 +556,121 @@ pub struct MigrationRecord {
     pub applied_by: String,
 }
 
-/// Calculate checksum for migration content
+/// Migrations that have not been applied yet, in version order
+fn pending_migrations(migrations: &[Migration], applied: &[AppliedMigration]) -> Vec<Migration> {
+    let applied_keys: Vec<String> = applied.iter().map(AppliedMigration::key).collect();
+    migrations
+        .iter()
+        .filter(|migration| !applied_keys.contains(&migration.key()))
+        .cloned()
+        .collect()
+}
+
+/// Fail if any applied migration no longer matches its recorded checksum
+fn verify_applied_checksums(
+    migrations: &[Migration],
+    applied: &[AppliedMigration],
+) -> Result<(), DatabaseError> {
+    let available: HashMap<String, &Migration> = migrations
+        .iter()
+        .map(|migration| (migration.key(), migration))
+        .collect();
+
+    let mut modified = Vec::new();
+    for record in applied {
+        match available.get(&record.key()) {
+            Some(_) if is_legacy_checksum(&record.checksum) => tracing::warn!(
+                "Applied {} migration {} has a legacy checksum and cannot be verified",
+                record.database_type,
+                record.version
+            ),
+            Some(migration) if migration.checksum != record.checksum => {
+                modified.push(format!(
+                    "{} {} (applied checksum {}, current {})",
+                    record.database_type, record.version, record.checksum, migration.checksum
+                ));
+            }
+            Some(_) => {}
+            None => tracing::warn!(
+                "Applied {} migration {} is no longer available",
+                record.database_type,
+                record.version
+            ),
+        }
+    }
+
+    if modified.is_empty() {
+        Ok(())
+    } else {
+        Err(DatabaseError::Migration(format!(
+            "Applied migrations were modified: {}",
+            modified.join(", ")
+        )))
+    }
+}
+
+/// Load `<version>_<name>.sql` migrations from a directory
+fn load_migrations_from_dir(
+    dir: &Path,
+    database_type: DatabaseType,
+) -> Result<Vec<Migration>, DatabaseError> {
+    let read_error = |e: std::io::Error| {
+        DatabaseError::Migration(format!(
+            "Failed to read migrations from {}: {}",
+            dir.display(),
+            e
+        ))
+    };
+
+    let mut migrations = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(read_error)? {
+        let path = entry.map_err(read_error)?.path();
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        let stem = match file_name.strip_suffix(".sql") {
+            Some(stem) if !stem.ends_with(".down") => stem,
+            _ => continue,
+        };
+
+        let (version, name) = stem.split_once('_').ok_or_else(|| {
+            DatabaseError::Migration(format!(
+                "Migration file {} must be named <version>_<name>.sql",
+                file_name
+            ))
+        })?;
+
+        let up_sql = std::fs::read_to_string(&path).map_err(read_error)?;
+        let down_path = dir.join(format!("{}.down.sql", stem));
+        let down_sql = if down_path.exists() {
+            Some(std::fs::read_to_string(&down_path).map_err(read_error)?)
+        } else {
+            None
+        };
+
+        migrations.push(Migration {
+            version: version.to_string(),
+            name: name.replace('_', " "),
+            database_type: database_type.clone(),
+            checksum: calculate_checksum(&up_sql),
+            up_sql,
+            down_sql,
+        });
+    }
+
+    Ok(migrations)
+}
+
+/// Calculate the SHA-256 checksum of migration content, hex encoded
 fn calculate_checksum(content: &str) -> String {
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
+    hex::encode(Sha256::digest(content.as_bytes()))
+}
 
-    let mut hasher = DefaultHasher::new();
-    content.hash(&mut hasher);
-    format!("{:x}", hasher.finish())
+/// Checksums recorded before migrations were hashed with SHA-256 came from the
+/// std hasher, which is not stable across Rust releases and cannot be compared
+fn is_legacy_checksum(checksum: &str) -> bool {
+    checksum.len() != 64
 }
 
 #[cfg(test)]
@@ -492,6 +685,10 @@ mod tests {
 
         assert_eq!(checksum1, checksum2);
         assert_ne!(checksum1, checksum3);
+        assert_eq!(
+            calculate_checksum("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
     }
 
     #[test]
@@ -507,6 +704,98 @@ mod tests {
         assert!(DatabaseType::from_string("invalid").is_err());
     }
 
+    fn migration(version: &str, up_sql: &str) -> Migration {
+        Migration {
+            version: version.to_string(),
+            name: format!("migration {}", version),
+            database_type: DatabaseType::PostgreSQL,
+            up_sql: up_sql.to_string(),
+            down_sql: None,
+            checksum: calculate_checksum(up_sql),
+        }
+    }
+
+    fn applied(migration: &Migration, checksum: &str) -> AppliedMigration {
+        AppliedMigration {
+            version: migration.version.clone(),
+            database_type: migration.database_type.to_string(),
+            checksum: checksum.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_pending_migrations_skip_applied() {
+        let first = migration("20250101000001", "CREATE TABLE a ();");
+        let second = migration("20250101000002", "CREATE TABLE b ();");
+        let applied = vec![applied(&first, &first.checksum)];
+
+        let pending = pending_migrations(&[first, second], &applied);
+
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].version, "20250101000002");
+    }
+
+    #[test]
+    fn test_verify_checksums_detects_modified_migration() {
+        let first = migration("20250101000001", "CREATE TABLE a ();");
+        let second = migration("20250101000002", "CREATE TABLE b ();");
+        let applied = vec![
+            applied(&first, &first.checksum),
+            applied(&second, &calculate_checksum("CREATE TABLE old_b ();")),
+        ];
+
+        let result = verify_applied_checksums(&[first.clone(), second], &applied);
+        match result {
+            Err(DatabaseError::Migration(message)) => {
+                assert!(message.contains("20250101000002"));
+                assert!(!message.contains("20250101000001"));
+            }
+            other => panic!("expected checksum mismatch, got {:?}", other),
+        }
+
+        assert!(
+            verify_applied_checksums(&[first.clone()], &[applied(&first, &first.checksum)]).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_verify_checksums_skips_legacy_checksums() {
+        let first = migration("20250101000001", "CREATE TABLE a ();");
+        let applied = vec![applied(&first, "9f2c4e1a7b3d5068")];
+
+        assert!(verify_applied_checksums(&[first], &applied).is_ok());
+    }
+
+    #[test]
+    fn test_load_migrations_from_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("20250101000001_create_projects.sql"),
+            "CREATE TABLE projects ();",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("20250101000001_create_projects.down.sql"),
+            "DROP TABLE projects;",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("README.md"), "not a migration").unwrap();
+
+        let migrations = load_migrations_from_dir(dir.path(), DatabaseType::PostgreSQL).unwrap();
+
+        assert_eq!(migrations.len(), 1);
+        assert_eq!(migrations[0].version, "20250101000001");
+        assert_eq!(migrations[0].name, "create projects");
+        assert_eq!(
+            migrations[0].down_sql.as_deref(),
+            Some("DROP TABLE projects;")
+        );
+        assert_eq!(
+            migrations[0].checksum,
+            calculate_checksum("CREATE TABLE projects ();")
+        );
+    }
+
     #[test]
     fn test_database_type_display() {
         assert_eq!(DatabaseType::PostgreSQL.to_string(), "PostgreSQL");
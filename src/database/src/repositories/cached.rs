@@ -0,0 +1,438 @@
+//! Read-through cache layer for repositories
+//!
+//! `CachedRepository` wraps any `Repository` and serves `find_by_id` from a
+//! cache store (Redis in production), populating it on a miss and invalidating
+//! entries on `update` and `delete`. Cache failures are logged and fall back to
+//! the wrapped repository, so the cache never makes a read fail.
+
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+use super::{Repository, RepositoryConfig};
+use crate::DatabaseError;
+
+/// Key/value store used by `CachedRepository`
+#[async_trait]
+pub trait CacheStore: Send + Sync {
+    /// Get a cached value
+    async fn get_json(&self, key: &str) -> Result<Option<serde_json::Value>, DatabaseError>;
+
+    /// Store a value with a TTL
+    async fn set_json(
+        &self,
+        key: &str,
+        value: &serde_json::Value,
+        ttl_seconds: u64,
+    ) -> Result<(), DatabaseError>;
+
+    /// Remove a cached value
+    async fn delete(&self, key: &str) -> Result<bool, DatabaseError>;
+}
+
+#[cfg(feature = "redis")]
+#[async_trait]
+impl CacheStore for crate::connections::RedisConnection {
+    async fn get_json(&self, key: &str) -> Result<Option<serde_json::Value>, DatabaseError> {
+        self.get(key).await
+    }
+
+    async fn set_json(
+        &self,
+        key: &str,
+        value: &serde_json::Value,
+        ttl_seconds: u64,
+    ) -> Result<(), DatabaseError> {
+        self.set_with_ttl(key, value, ttl_seconds).await
+    }
+
+    async fn delete(&self, key: &str) -> Result<bool, DatabaseError> {
+        crate::connections::RedisConnection::delete(self, key).await
+    }
+}
+
+/// Cache settings for a `CachedRepository`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedRepositoryConfig {
+    /// Prefix for cache keys, e.g. `users` for keys like `users:<id>`
+    pub key_prefix: String,
+    /// Lifetime of cached entities
+    pub ttl_seconds: u64,
+}
+
+impl CachedRepositoryConfig {
+    /// Create a config with the default repository cache TTL
+    pub fn new(key_prefix: impl Into<String>) -> Self {
+        Self {
+            key_prefix: key_prefix.into(),
+            ttl_seconds: RepositoryConfig::default().cache_ttl_seconds,
+        }
+    }
+
+    /// Set the cache TTL
+    pub fn with_ttl(mut self, ttl_seconds: u64) -> Self {
+        self.ttl_seconds = ttl_seconds;
+        self
+    }
+}
+
+/// Per-call read options
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReadOptions {
+    /// Read straight from the wrapped repository, skipping the cache
+    pub cache_bypass: bool,
+}
+
+impl ReadOptions {
+    /// Options for consistency-sensitive reads that must not see stale data
+    pub fn bypass_cache() -> Self {
+        Self { cache_bypass: true }
+    }
+}
+
+/// Cache hit/miss counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// Repository wrapper that caches `find_by_id` results
+pub struct CachedRepository<R> {
+    inner: R,
+    cache: Arc<dyn CacheStore>,
+    config: CachedRepositoryConfig,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<R> CachedRepository<R> {
+    /// Wrap a repository with a cache
+    pub fn new(inner: R, cache: Arc<dyn CacheStore>, config: CachedRepositoryConfig) -> Self {
+        Self {
+            inner,
+            cache,
+            config,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Get the wrapped repository
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    /// Get the cache hit/miss counters
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    fn cache_key(&self, id: &impl Display) -> String {
+        format!("{}:{}", self.config.key_prefix, id)
+    }
+
+    async fn invalidate(&self, key: &str) {
+        if let Err(e) = self.cache.delete(key).await {
+            warn!("Failed to invalidate cache key '{}': {}", key, e);
+        }
+    }
+
+    /// Find entity by ID, optionally bypassing the cache
+    pub async fn find_by_id_with_options<T>(
+        &self,
+        id: <R as Repository<T>>::Id,
+        options: ReadOptions,
+    ) -> Result<Option<T>, DatabaseError>
+    where
+        R: Repository<T> + Send + Sync,
+        <R as Repository<T>>::Id: Display + Send,
+        T: Serialize + DeserializeOwned + Send,
+    {
+        if options.cache_bypass {
+            return self.inner.find_by_id(id).await;
+        }
+
+        let key = self.cache_key(&id);
+        match self.cache.get_json(&key).await {
+            Ok(Some(value)) => match serde_json::from_value(value) {
+                Ok(entity) => {
+                    self.hits.fetch_add(1, Ordering::Relaxed);
+                    debug!("Repository cache hit for '{}'", key);
+                    return Ok(Some(entity));
+                }
+                Err(e) => warn!("Discarding undecodable cache entry '{}': {}", key, e),
+            },
+            Ok(None) => {}
+            Err(e) => warn!("Cache read for '{}' failed: {}", key, e),
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let entity = self.inner.find_by_id(id).await?;
+
+        let serialized = entity.as_ref().map(serde_json::to_value).transpose();
+        match serialized {
+            Ok(Some(value)) => {
+                if let Err(e) = self
+                    .cache
+                    .set_json(&key, &value, self.config.ttl_seconds)
+                    .await
+                {
+                    warn!("Failed to populate cache key '{}': {}", key, e);
+                }
+            }
+            Ok(None) => {}
+            Err(e) => warn!("Failed to serialize cache entry '{}': {}", key, e),
+        }
+
+        Ok(entity)
+    }
+}
+
+#[async_trait]
+impl<T, R> Repository<T> for CachedRepository<R>
+where
+    R: Repository<T> + Send + Sync,
+    R::Id: Display + Send + 'static,
+    R::CreateInput: Send + 'static,
+    R::UpdateInput: Send + 'static,
+    R::QueryFilter: Send + 'static,
+    T: Serialize + DeserializeOwned + Send + 'static,
+{
+    type Id = R::Id;
+    type CreateInput = R::CreateInput;
+    type UpdateInput = R::UpdateInput;
+    type QueryFilter = R::QueryFilter;
+
+    async fn create(&self, input: Self::CreateInput) -> Result<T, DatabaseError> {
+        self.inner.create(input).await
+    }
+
+    async fn find_by_id(&self, id: Self::Id) -> Result<Option<T>, DatabaseError> {
+        self.find_by_id_with_options(id, ReadOptions::default())
+            .await
+    }
+
+    async fn update(&self, id: Self::Id, input: Self::UpdateInput) -> Result<T, DatabaseError> {
+        let key = self.cache_key(&id);
+        let result = self.inner.update(id, input).await;
+        self.invalidate(&key).await;
+        result
+    }
+
+    async fn delete(&self, id: Self::Id) -> Result<bool, DatabaseError> {
+        let key = self.cache_key(&id);
+        let result = self.inner.delete(id).await;
+        self.invalidate(&key).await;
+        result
+    }
+
+    async fn find_many(&self, filter: Self::QueryFilter) -> Result<Vec<T>, DatabaseError> {
+        self.inner.find_many(filter).await
+    }
+
+    async fn count(&self, filter: Self::QueryFilter) -> Result<u64, DatabaseError> {
+        self.inner.count(filter).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Item {
+        id: u32,
+        name: String,
+    }
+
+    /// In-memory stand-in for a Redis connection
+    #[derive(Default)]
+    struct MockCache {
+        entries: Mutex<HashMap<String, (serde_json::Value, u64)>>,
+        fail_reads: bool,
+    }
+
+    #[async_trait]
+    impl CacheStore for MockCache {
+        async fn get_json(&self, key: &str) -> Result<Option<serde_json::Value>, DatabaseError> {
+            if self.fail_reads {
+                return Err(DatabaseError::Connection("redis unavailable".to_string()));
+            }
+            Ok(self
+                .entries
+                .lock()
+                .unwrap()
+                .get(key)
+                .map(|(value, _)| value.clone()))
+        }
+
+        async fn set_json(
+            &self,
+            key: &str,
+            value: &serde_json::Value,
+            ttl_seconds: u64,
+        ) -> Result<(), DatabaseError> {
+            self.entries
+                .lock()
+                .unwrap()
+                .insert(key.to_string(), (value.clone(), ttl_seconds));
+            Ok(())
+        }
+
+        async fn delete(&self, key: &str) -> Result<bool, DatabaseError> {
+            Ok(self.entries.lock().unwrap().remove(key).is_some())
+        }
+    }
+
+    #[derive(Default)]
+    struct ItemRepository {
+        items: Mutex<HashMap<u32, Item>>,
+        reads: AtomicU64,
+    }
+
+    #[async_trait]
+    impl Repository<Item> for ItemRepository {
+        type Id = u32;
+        type CreateInput = Item;
+        type UpdateInput = String;
+        type QueryFilter = ();
+
+        async fn create(&self, input: Item) -> Result<Item, DatabaseError> {
+            self.items.lock().unwrap().insert(input.id, input.clone());
+            Ok(input)
+        }
+
+        async fn find_by_id(&self, id: u32) -> Result<Option<Item>, DatabaseError> {
+            self.reads.fetch_add(1, Ordering::Relaxed);
+            Ok(self.items.lock().unwrap().get(&id).cloned())
+        }
+
+        async fn update(&self, id: u32, name: String) -> Result<Item, DatabaseError> {
+            let mut items = self.items.lock().unwrap();
+            let item = items
+                .get_mut(&id)
+                .ok_or_else(|| DatabaseError::Validation(format!("Item {} not found", id)))?;
+            item.name = name;
+            Ok(item.clone())
+        }
+
+        async fn delete(&self, id: u32) -> Result<bool, DatabaseError> {
+            Ok(self.items.lock().unwrap().remove(&id).is_some())
+        }
+
+        async fn find_many(&self, _filter: ()) -> Result<Vec<Item>, DatabaseError> {
+            Ok(self.items.lock().unwrap().values().cloned().collect())
+        }
+
+        async fn count(&self, _filter: ()) -> Result<u64, DatabaseError> {
+            Ok(self.items.lock().unwrap().len() as u64)
+        }
+    }
+
+    async fn cached_repository(cache: Arc<MockCache>) -> CachedRepository<ItemRepository> {
+        let repository = CachedRepository::new(
+            ItemRepository::default(),
+            cache,
+            CachedRepositoryConfig::new("items").with_ttl(60),
+        );
+        Repository::<Item>::create(
+            &repository,
+            Item {
+                id: 1,
+                name: "first".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        repository
+    }
+
+    fn reads(repository: &CachedRepository<ItemRepository>) -> u64 {
+        repository.inner().reads.load(Ordering::Relaxed)
+    }
+
+    #[tokio::test]
+    async fn test_miss_populates_cache_and_hit_skips_repository() {
+        let cache = Arc::new(MockCache::default());
+        let repository = cached_repository(cache.clone()).await;
+
+        let first: Option<Item> = repository.find_by_id(1).await.unwrap();
+        let second: Option<Item> = repository.find_by_id(1).await.unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(reads(&repository), 1);
+        assert_eq!(repository.stats(), CacheStats { hits: 1, misses: 1 });
+        assert_eq!(cache.entries.lock().unwrap()["items:1"].1, 60);
+    }
+
+    #[tokio::test]
+    async fn test_missing_entities_are_not_cached() {
+        let cache = Arc::new(MockCache::default());
+        let repository = cached_repository(cache.clone()).await;
+
+        let missing: Option<Item> = repository.find_by_id(2).await.unwrap();
+
+        assert!(missing.is_none());
+        assert!(cache.entries.lock().unwrap().is_empty());
+        assert_eq!(repository.stats(), CacheStats { hits: 0, misses: 1 });
+    }
+
+    #[tokio::test]
+    async fn test_update_and_delete_invalidate_cache() {
+        let cache = Arc::new(MockCache::default());
+        let repository = cached_repository(cache.clone()).await;
+
+        let _: Option<Item> = repository.find_by_id(1).await.unwrap();
+        Repository::<Item>::update(&repository, 1, "renamed".to_string())
+            .await
+            .unwrap();
+        assert!(!cache.entries.lock().unwrap().contains_key("items:1"));
+
+        let renamed: Option<Item> = repository.find_by_id(1).await.unwrap();
+        assert_eq!(renamed.unwrap().name, "renamed");
+
+        assert!(Repository::<Item>::delete(&repository, 1).await.unwrap());
+        assert!(!cache.entries.lock().unwrap().contains_key("items:1"));
+        let deleted: Option<Item> = repository.find_by_id(1).await.unwrap();
+        assert!(deleted.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cache_bypass_reads_repository() {
+        let cache = Arc::new(MockCache::default());
+        let repository = cached_repository(cache.clone()).await;
+
+        let _: Option<Item> = repository.find_by_id(1).await.unwrap();
+        let fresh: Option<Item> = repository
+            .find_by_id_with_options(1, ReadOptions::bypass_cache())
+            .await
+            .unwrap();
+
+        assert!(fresh.is_some());
+        assert_eq!(reads(&repository), 2);
+        assert_eq!(repository.stats(), CacheStats { hits: 0, misses: 1 });
+    }
+
+    #[tokio::test]
+    async fn test_cache_errors_fall_back_to_repository() {
+        let cache = Arc::new(MockCache {
+            fail_reads: true,
+            ..Default::default()
+        });
+        let repository = cached_repository(cache).await;
+
+        let item: Option<Item> = repository.find_by_id(1).await.unwrap();
+
+        assert_eq!(item.unwrap().name, "first");
+        assert_eq!(repository.stats(), CacheStats { hits: 0, misses: 1 });
+    }
+}
@@ -11,6 +11,7 @@
 
 // pub mod users;
 // pub mod workflows;
+pub mod cached;
 pub mod postgresql;
 // pub mod content;
 // pub mod analytics;
@@ -28,6 +29,7 @@ use uuid::Uuid;
 
 // pub use users::*;
 // pub use workflows::*;
+pub use cached::*;
 pub use postgresql::*;
 // pub use content::*;
 // pub use analytics::*;
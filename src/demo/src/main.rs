@@ -13,7 +13,6 @@ use axum::{
 };
 use chrono::{DateTime, Utc};
 use futures::{SinkExt, StreamExt};
-use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, VecDeque},
@@ -30,7 +29,9 @@ pub struct AppState {
     pub config: DemoConfig,
     pub workflow_store: Arc<RwLock<HashMap<Uuid, WorkflowExecution>>>,
     pub demo_scenarios: Arc<Vec<DemoScenario>>,
-    pub real_time_clients: Arc<RwLock<HashMap<Uuid, tokio::sync::mpsc::UnboundedSender<String>>>>,
+    /// Senders for every WebSocket client watching each workflow
+    pub real_time_clients:
+        Arc<RwLock<HashMap<Uuid, Vec<tokio::sync::mpsc::UnboundedSender<String>>>>>,
 }
 
 #[derive(Debug, Clone)]
@@ -564,12 +565,30 @@ async fn handle_websocket(socket: WebSocket, workflow_id: Uuid, state: AppState)
     let (mut sender, mut receiver) = socket.split();
     let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
 
-    // Store client connection
+    // Send current progress straight away so late joiners don't sit at 0%
+    {
+        let store = state.workflow_store.read().await;
+        if let Some(workflow) = store.get(&workflow_id) {
+            let step_name = workflow
+                .execution_steps
+                .get(workflow.current_step)
+                .map(|step| step.name.as_str())
+                .unwrap_or_default();
+            let snapshot = progress_update(workflow, step_name, "Connected to workflow updates");
+            if let Ok(message) = serde_json::to_string(&snapshot) {
+                let _ = tx.send(message);
+            }
+        }
+    }
+
+    // Store client connection alongside any other viewers of the workflow
     state
         .real_time_clients
         .write()
         .await
-        .insert(workflow_id, tx);
+        .entry(workflow_id)
+        .or_default()
+        .push(tx.clone());
 
     // Handle incoming messages (if any)
     let receive_task = tokio::spawn(async move {
@@ -599,8 +618,14 @@ async fn handle_websocket(socket: WebSocket, workflow_id: Uuid, state: AppState)
         _ = send_task => {},
     }
 
-    // Clean up
-    state.real_time_clients.write().await.remove(&workflow_id);
+    // Clean up this connection only; other viewers keep receiving updates
+    let mut clients = state.real_time_clients.write().await;
+    if let Some(senders) = clients.get_mut(&workflow_id) {
+        senders.retain(|sender| !sender.same_channel(&tx) && !sender.is_closed());
+        if senders.is_empty() {
+            clients.remove(&workflow_id);
+        }
+    }
 }
 
 // Execute the complete demo workflow
@@ -649,13 +674,22 @@ async fn execute_demo_workflow(state: AppState, workflow_id: Uuid) {
                 };
                 workflow.progress_percentage = ((index + 1) as f32 / steps.len() as f32) * 100.0;
 
-                // Update cost tracking
-                let step_cost = rand::thread_rng().gen_range(0.05..0.50);
-                workflow.cost_tracking.total_cost_dollars += step_cost;
-                workflow
-                    .cost_tracking
-                    .breakdown
-                    .insert(step_name.to_string(), step_cost);
+                // Route the step to the cheapest adequate provider and charge its price
+                if let Some((decision, step_cost, tokens)) = route_step(step_name) {
+                    workflow.cost_tracking.total_cost_dollars += step_cost;
+                    workflow
+                        .cost_tracking
+                        .breakdown
+                        .insert(step_name.to_string(), step_cost);
+                    *workflow
+                        .cost_tracking
+                        .token_usage
+                        .entry(decision.selected_provider.clone())
+                        .or_insert(0) += tokens;
+                    if let Some(federation) = workflow.federation_info.as_mut() {
+                        federation.routing_decisions.push(decision);
+                    }
+                }
             }
         }
 
@@ -691,33 +725,42 @@ async fn send_progress_update(
     step_name: &str,
     description: &str,
 ) {
-    let (status, progress, cost) = {
+    let update = {
         let store = state.workflow_store.read().await;
-        if let Some(workflow) = store.get(&workflow_id) {
-            (
-                workflow.status.clone(),
-                workflow.progress_percentage,
-                workflow.cost_tracking.total_cost_dollars,
-            )
-        } else {
-            return;
+        match store.get(&workflow_id) {
+            Some(workflow) => progress_update(workflow, step_name, description),
+            None => return,
         }
     };
 
-    let update = ProgressUpdate {
-        workflow_id,
-        status,
-        progress_percentage: progress,
+    if let Ok(message) = serde_json::to_string(&update) {
+        broadcast(state, workflow_id, message).await;
+    }
+}
+
+fn progress_update(
+    workflow: &WorkflowExecution,
+    step_name: &str,
+    description: &str,
+) -> ProgressUpdate {
+    ProgressUpdate {
+        workflow_id: workflow.id,
+        status: workflow.status.clone(),
+        progress_percentage: workflow.progress_percentage,
         current_step: step_name.to_string(),
         message: description.to_string(),
         timestamp: Utc::now(),
-        cost_so_far: cost,
-    };
+        cost_so_far: workflow.cost_tracking.total_cost_dollars,
+    }
+}
 
-    if let Ok(message) = serde_json::to_string(&update) {
-        let clients = state.real_time_clients.read().await;
-        if let Some(client) = clients.get(&workflow_id) {
-            let _ = client.send(message);
+/// Send a message to every client watching a workflow, pruning disconnected ones
+async fn broadcast(state: &AppState, workflow_id: Uuid, message: String) {
+    let mut clients = state.real_time_clients.write().await;
+    if let Some(senders) = clients.get_mut(&workflow_id) {
+        senders.retain(|sender| sender.send(message.clone()).is_ok());
+        if senders.is_empty() {
+            clients.remove(&workflow_id);
         }
     }
 }
@@ -753,15 +796,34 @@ async fn simulate_content_generation(state: &AppState, workflow_id: Uuid) {
     {
         let mut store = state.workflow_store.write().await;
         if let Some(workflow) = store.get_mut(&workflow_id) {
+            // Attribute the content to the provider the step was routed to
+            let provider = workflow
+                .federation_info
+                .as_ref()
+                .and_then(|federation| {
+                    federation
+                        .routing_decisions
+                        .iter()
+                        .find(|decision| decision.step == "Content Generation")
+                })
+                .map(|decision| decision.selected_provider.clone())
+                .unwrap_or_else(|| "demo-provider".to_string());
+            let cost_dollars = workflow
+                .cost_tracking
+                .breakdown
+                .get("Content Generation")
+                .copied()
+                .unwrap_or_default();
+
             workflow.results.push(StepResult {
                 step_id: Uuid::new_v4(),
                 success: true,
                 output: content_response,
                 duration_ms: 2500,
-                cost_dollars: 0.25,
+                cost_dollars,
                 metadata: [
                     ("service".to_string(), "content-mcp".to_string()),
-                    ("provider".to_string(), "demo-provider".to_string()),
+                    ("provider".to_string(), provider),
                 ]
                 .into_iter()
                 .collect(),
@@ -890,6 +952,137 @@ async fn workflow_viewer(Path(workflow_id): Path<Uuid>) -> Html<String> {
     ))
 }
 
+/// Price a candidate provider charges for a workflow step
+struct ProviderQuote {
+    provider: &'static str,
+    cost_per_1k_tokens: f32,
+    quality_score: f32,
+}
+
+/// Lowest quality score a provider needs before its price is considered
+const MIN_QUALITY_SCORE: f32 = 0.8;
+
+/// Stubbed pricing table: estimated tokens and candidate providers for each
+/// step that calls out to a provider. Steps not listed run locally for free.
+fn step_pricing(step_name: &str) -> Option<(u32, &'static [ProviderQuote])> {
+    const LLM_PROVIDERS: &[ProviderQuote] = &[
+        ProviderQuote {
+            provider: "openai-gpt-4o",
+            cost_per_1k_tokens: 0.0100,
+            quality_score: 0.95,
+        },
+        ProviderQuote {
+            provider: "anthropic-claude-3-haiku",
+            cost_per_1k_tokens: 0.0025,
+            quality_score: 0.86,
+        },
+        ProviderQuote {
+            provider: "openai-gpt-3.5-turbo",
+            cost_per_1k_tokens: 0.0015,
+            quality_score: 0.74,
+        },
+    ];
+    const CONTENT_PROVIDERS: &[ProviderQuote] = &[
+        ProviderQuote {
+            provider: "openai-gpt-4o",
+            cost_per_1k_tokens: 0.0100,
+            quality_score: 0.95,
+        },
+        ProviderQuote {
+            provider: "anthropic-claude-3-sonnet",
+            cost_per_1k_tokens: 0.0090,
+            quality_score: 0.93,
+        },
+        ProviderQuote {
+            provider: "google-gemini-1.5-flash",
+            cost_per_1k_tokens: 0.0011,
+            quality_score: 0.78,
+        },
+    ];
+    const PUBLISHING_PROVIDERS: &[ProviderQuote] = &[
+        ProviderQuote {
+            provider: "client-b-premium-publisher",
+            cost_per_1k_tokens: 0.0200,
+            quality_score: 0.97,
+        },
+        ProviderQuote {
+            provider: "buffer-api",
+            cost_per_1k_tokens: 0.0080,
+            quality_score: 0.88,
+        },
+    ];
+
+    match step_name {
+        "Parsing Intent" => Some((1_500, LLM_PROVIDERS)),
+        "Planning Workflow" => Some((2_500, LLM_PROVIDERS)),
+        "Content Generation" => Some((12_000, CONTENT_PROVIDERS)),
+        "Publishing Content" => Some((3_000, PUBLISHING_PROVIDERS)),
+        "Quality Validation" => Some((4_000, LLM_PROVIDERS)),
+        _ => None,
+    }
+}
+
+/// Pick the cheapest provider meeting the quality floor for a step, returning
+/// the routing decision, the step's cost and the tokens it uses
+fn route_step(step_name: &str) -> Option<(RoutingDecision, f32, u32)> {
+    let (tokens, quotes) = step_pricing(step_name)?;
+    let cost_of = |quote: &ProviderQuote| quote.cost_per_1k_tokens * tokens as f32 / 1000.0;
+
+    let cost_comparison: HashMap<String, f32> = quotes
+        .iter()
+        .map(|quote| (quote.provider.to_string(), cost_of(quote)))
+        .collect();
+
+    let selected = quotes
+        .iter()
+        .filter(|quote| quote.quality_score >= MIN_QUALITY_SCORE)
+        .min_by(|a, b| a.cost_per_1k_tokens.total_cmp(&b.cost_per_1k_tokens))?;
+    let selected_cost = cost_of(selected);
+
+    let mut reason = format!(
+        "Cheapest provider meeting the {:.2} quality floor at ${:.4} (quality {:.2})",
+        MIN_QUALITY_SCORE, selected_cost, selected.quality_score
+    );
+    if let Some(priciest) = quotes
+        .iter()
+        .max_by(|a, b| a.cost_per_1k_tokens.total_cmp(&b.cost_per_1k_tokens))
+        .filter(|quote| quote.provider != selected.provider)
+    {
+        let savings = cost_of(priciest) - selected_cost;
+        reason.push_str(&format!(
+            ", saving ${:.4} ({:.0}%) over {}",
+            savings,
+            savings / cost_of(priciest) * 100.0,
+            priciest.provider
+        ));
+    }
+    let rejected: Vec<&str> = quotes
+        .iter()
+        .filter(|quote| {
+            quote.quality_score < MIN_QUALITY_SCORE
+                && quote.cost_per_1k_tokens < selected.cost_per_1k_tokens
+        })
+        .map(|quote| quote.provider)
+        .collect();
+    if !rejected.is_empty() {
+        reason.push_str(&format!(
+            "; {} cheaper but below the quality floor",
+            rejected.join(", ")
+        ));
+    }
+
+    Some((
+        RoutingDecision {
+            step: step_name.to_string(),
+            selected_provider: selected.provider.to_string(),
+            reason,
+            cost_comparison,
+        },
+        selected_cost,
+        tokens,
+    ))
+}
+
 // Initialize demo execution steps
 fn initialize_demo_steps() -> VecDeque<ExecutionStep> {
     [
@@ -961,3 +1154,49 @@ fn initialize_demo_scenarios() -> Vec<DemoScenario> {
         },
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_state() -> AppState {
+        AppState {
+            config: DemoConfig::default(),
+            workflow_store: Arc::new(RwLock::new(HashMap::new())),
+            demo_scenarios: Arc::new(initialize_demo_scenarios()),
+            real_time_clients: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_progress_fans_out_to_every_connected_client() {
+        let state = test_state();
+        let workflow_id = Uuid::new_v4();
+
+        let (first_tx, mut first_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (second_tx, mut second_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (closed_tx, closed_rx) = tokio::sync::mpsc::unbounded_channel();
+        drop(closed_rx);
+        state
+            .real_time_clients
+            .write()
+            .await
+            .insert(workflow_id, vec![first_tx, closed_tx, second_tx]);
+
+        broadcast(&state, workflow_id, "step 1".to_string()).await;
+
+        assert_eq!(first_rx.recv().await.as_deref(), Some("step 1"));
+        assert_eq!(second_rx.recv().await.as_deref(), Some("step 1"));
+        assert_eq!(state.real_time_clients.read().await[&workflow_id].len(), 2);
+
+        // Once every viewer has gone the workflow's entry is dropped
+        drop(first_rx);
+        drop(second_rx);
+        broadcast(&state, workflow_id, "step 2".to_string()).await;
+        assert!(!state
+            .real_time_clients
+            .read()
+            .await
+            .contains_key(&workflow_id));
+    }
+}
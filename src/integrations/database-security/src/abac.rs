@@ -0,0 +1,731 @@
+//! # ABAC Module
+//!
+//! This module provides attribute-based access control (ABAC) policies for
+//! database operations. A policy applies to a set of permissions and carries a
+//! condition over user and resource attributes, for example
+//! `resource.department == user.department AND NOT user.is_api_key == true`.
+//! Policies narrow what RBAC grants: every policy that applies to a permission
+//! must hold for access to be allowed.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Instant;
+
+use crate::{
+    error::SecureDatabaseError,
+    security_context::{SecurityContext, SecurityLevel},
+};
+
+/// Attribute-based access policy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AbacPolicy {
+    /// Policy name, reported when the policy denies access
+    pub name: String,
+    /// Permissions the policy applies to; `*` matches every permission and
+    /// `documents:*` matches every `documents` permission
+    pub permissions: Vec<String>,
+    /// Condition that must hold for access to be granted
+    pub condition: AbacExpr,
+}
+
+impl AbacPolicy {
+    /// Create a policy, parsing its condition
+    pub fn new(
+        name: impl Into<String>,
+        permissions: Vec<String>,
+        condition: &str,
+    ) -> Result<Self, SecureDatabaseError> {
+        Ok(Self {
+            name: name.into(),
+            permissions,
+            condition: condition.parse()?,
+        })
+    }
+
+    /// Check whether the policy applies to a permission
+    pub fn applies_to(&self, permission: &str) -> bool {
+        self.permissions.iter().any(|pattern| {
+            pattern == "*"
+                || pattern == permission
+                || pattern
+                    .strip_suffix('*')
+                    .is_some_and(|prefix| prefix.ends_with(':') && permission.starts_with(prefix))
+        })
+    }
+}
+
+/// Outcome of evaluating ABAC policies
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbacDecision {
+    /// Every applicable policy holds
+    Allow,
+    /// A policy failed; `clause` is the innermost part of its condition that
+    /// did not hold
+    Deny { policy: String, clause: String },
+}
+
+/// Attributes available to policy conditions
+#[derive(Debug, Clone, Default)]
+pub struct AbacAttributes {
+    /// `user.*` attributes
+    pub user: HashMap<String, Value>,
+    /// `resource.*` attributes
+    pub resource: HashMap<String, Value>,
+}
+
+impl AbacAttributes {
+    /// Collect user attributes from a security context
+    ///
+    /// Custom attributes in the context metadata are available under their own
+    /// names; built-in attributes take precedence over custom ones.
+    pub fn from_context(context: &SecurityContext, resource: HashMap<String, Value>) -> Self {
+        let metadata = &context.metadata;
+        let mut user: HashMap<String, Value> = metadata
+            .attributes
+            .iter()
+            .map(|(name, value)| (name.clone(), Value::String(value.clone())))
+            .collect();
+
+        user.insert("id".to_string(), Value::String(context.user_id.to_string()));
+        if let Some(organization_id) = metadata.organization_id {
+            user.insert(
+                "organization".to_string(),
+                Value::String(organization_id.to_string()),
+            );
+        }
+        if let Some(department_id) = &metadata.department_id {
+            user.insert(
+                "department".to_string(),
+                Value::String(department_id.clone()),
+            );
+        }
+        let security_level = match metadata.security_level {
+            SecurityLevel::Standard => "standard",
+            SecurityLevel::Elevated => "elevated",
+            SecurityLevel::Administrative => "administrative",
+            SecurityLevel::System => "system",
+        };
+        user.insert(
+            "security_level".to_string(),
+            Value::String(security_level.to_string()),
+        );
+        user.insert(
+            "mfa_verified".to_string(),
+            Value::Bool(metadata.mfa_verified),
+        );
+        user.insert("is_api_key".to_string(), Value::Bool(metadata.is_api_key));
+
+        Self { user, resource }
+    }
+}
+
+/// Evaluate every policy that applies to `permission`
+///
+/// Returns a timeout error if evaluation runs past `deadline`.
+pub fn evaluate_policies(
+    policies: &[AbacPolicy],
+    permission: &str,
+    attributes: &AbacAttributes,
+    deadline: Instant,
+) -> Result<AbacDecision, SecureDatabaseError> {
+    for policy in policies
+        .iter()
+        .filter(|policy| policy.applies_to(permission))
+    {
+        if let Some(clause) = policy.condition.evaluate(attributes, deadline)? {
+            return Ok(AbacDecision::Deny {
+                policy: policy.name.clone(),
+                clause: clause.to_string(),
+            });
+        }
+    }
+
+    Ok(AbacDecision::Allow)
+}
+
+/// Policy condition expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum AbacExpr {
+    /// Comparison between two operands
+    Compare {
+        left: Operand,
+        op: CompareOp,
+        right: Operand,
+    },
+    /// Both sides must hold
+    And(Box<AbacExpr>, Box<AbacExpr>),
+    /// Either side must hold
+    Or(Box<AbacExpr>, Box<AbacExpr>),
+    /// The inner expression must not hold
+    Not(Box<AbacExpr>),
+}
+
+/// Comparison operand
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand {
+    /// `user.<name>` attribute
+    User(String),
+    /// `resource.<name>` attribute
+    Resource(String),
+    /// String, number, boolean or null literal
+    Literal(Value),
+}
+
+/// Comparison operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl AbacExpr {
+    /// Evaluate the expression, returning the innermost clause that failed or
+    /// `None` if the expression holds
+    pub fn evaluate(
+        &self,
+        attributes: &AbacAttributes,
+        deadline: Instant,
+    ) -> Result<Option<&AbacExpr>, SecureDatabaseError> {
+        if Instant::now() >= deadline {
+            return Err(SecureDatabaseError::timeout(
+                "ABAC policy evaluation exceeded its time budget",
+            ));
+        }
+
+        match self {
+            Self::Compare { left, op, right } => {
+                let holds = match (left.resolve(attributes), right.resolve(attributes)) {
+                    (Some(left), Some(right)) => op.apply(left, right),
+                    // Missing attributes never satisfy a comparison
+                    _ => false,
+                };
+                Ok((!holds).then_some(self))
+            }
+            Self::And(left, right) => match left.evaluate(attributes, deadline)? {
+                Some(failed) => Ok(Some(failed)),
+                None => right.evaluate(attributes, deadline),
+            },
+            Self::Or(left, right) => {
+                if left.evaluate(attributes, deadline)?.is_none()
+                    || right.evaluate(attributes, deadline)?.is_none()
+                {
+                    Ok(None)
+                } else {
+                    Ok(Some(self))
+                }
+            }
+            Self::Not(inner) => match inner.evaluate(attributes, deadline)? {
+                Some(_) => Ok(None),
+                None => Ok(Some(self)),
+            },
+        }
+    }
+}
+
+impl Operand {
+    fn resolve<'a>(&'a self, attributes: &'a AbacAttributes) -> Option<&'a Value> {
+        match self {
+            Self::User(name) => attributes.user.get(name),
+            Self::Resource(name) => attributes.resource.get(name),
+            Self::Literal(value) => Some(value),
+        }
+    }
+}
+
+impl CompareOp {
+    fn apply(self, left: &Value, right: &Value) -> bool {
+        let ordering = match (left, right) {
+            (Value::Number(l), Value::Number(r)) => match (l.as_f64(), r.as_f64()) {
+                (Some(l), Some(r)) => l.partial_cmp(&r),
+                _ => None,
+            },
+            (Value::String(l), Value::String(r)) => Some(l.cmp(r)),
+            _ => None,
+        };
+
+        match self {
+            Self::Eq => ordering.map_or(left == right, |o| o.is_eq()),
+            Self::Ne => ordering.map_or(left != right, |o| o.is_ne()),
+            Self::Lt => ordering.is_some_and(|o| o.is_lt()),
+            Self::Le => ordering.is_some_and(|o| o.is_le()),
+            Self::Gt => ordering.is_some_and(|o| o.is_gt()),
+            Self::Ge => ordering.is_some_and(|o| o.is_ge()),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Eq => "==",
+            Self::Ne => "!=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+        }
+    }
+}
+
+impl fmt::Display for AbacExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Compare { left, op, right } => write!(f, "{} {} {}", left, op.as_str(), right),
+            Self::And(left, right) => {
+                write_operand_of(f, left, |e| matches!(e, Self::Or(..)))?;
+                f.write_str(" AND ")?;
+                write_operand_of(f, right, |e| matches!(e, Self::Or(..)))
+            }
+            Self::Or(left, right) => write!(f, "{} OR {}", left, right),
+            Self::Not(inner) => {
+                f.write_str("NOT ")?;
+                write_operand_of(f, inner, |e| !matches!(e, Self::Compare { .. }))
+            }
+        }
+    }
+}
+
+/// Write a sub-expression, parenthesized when `needs_parens` says so
+fn write_operand_of(
+    f: &mut fmt::Formatter<'_>,
+    expr: &AbacExpr,
+    needs_parens: impl Fn(&AbacExpr) -> bool,
+) -> fmt::Result {
+    if needs_parens(expr) {
+        write!(f, "({})", expr)
+    } else {
+        write!(f, "{}", expr)
+    }
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::User(name) => write!(f, "user.{}", name),
+            Self::Resource(name) => write!(f, "resource.{}", name),
+            Self::Literal(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl FromStr for AbacExpr {
+    type Err = SecureDatabaseError;
+
+    fn from_str(condition: &str) -> Result<Self, Self::Err> {
+        let invalid = |message: String| {
+            SecureDatabaseError::Configuration(format!(
+                "Invalid ABAC condition '{}': {}",
+                condition, message
+            ))
+        };
+
+        let tokens = tokenize(condition).map_err(invalid)?;
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+        };
+        let expr = parser.parse_or().map_err(invalid)?;
+        if let Some(token) = parser.peek() {
+            return Err(invalid(format!("unexpected {:?}", token)));
+        }
+
+        Ok(expr)
+    }
+}
+
+impl Serialize for AbacExpr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for AbacExpr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let condition = String::deserialize(deserializer)?;
+        condition.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Op(CompareOp),
+    Operand(Operand),
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            '=' | '!' | '<' | '>' if next == Some('=') => {
+                tokens.push(Token::Op(match c {
+                    '=' => CompareOp::Eq,
+                    '!' => CompareOp::Ne,
+                    '<' => CompareOp::Le,
+                    _ => CompareOp::Ge,
+                }));
+                i += 2;
+            }
+            '<' => {
+                tokens.push(Token::Op(CompareOp::Lt));
+                i += 1;
+            }
+            '>' => {
+                tokens.push(Token::Op(CompareOp::Gt));
+                i += 1;
+            }
+            '!' => {
+                tokens.push(Token::Not);
+                i += 1;
+            }
+            '&' if next == Some('&') => {
+                tokens.push(Token::And);
+                i += 2;
+            }
+            '|' if next == Some('|') => {
+                tokens.push(Token::Or);
+                i += 2;
+            }
+            '"' | '\'' => {
+                let mut literal = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return Err("unterminated string literal".to_string()),
+                        Some(&ch) if ch == c => break,
+                        Some(&'\\') => {
+                            let escaped = chars
+                                .get(i + 1)
+                                .ok_or_else(|| "unterminated string literal".to_string())?;
+                            literal.push(*escaped);
+                            i += 2;
+                        }
+                        Some(&ch) => {
+                            literal.push(ch);
+                            i += 1;
+                        }
+                    }
+                }
+                tokens.push(Token::Operand(Operand::Literal(Value::String(literal))));
+                i += 1;
+            }
+            c if c.is_ascii_digit() || (c == '-' && next.is_some_and(|n| n.is_ascii_digit())) => {
+                let start = i;
+                i += 1;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let number: serde_json::Number = text
+                    .parse()
+                    .map_err(|_| format!("invalid number '{}'", text))?;
+                tokens.push(Token::Operand(Operand::Literal(Value::Number(number))));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_ascii_alphanumeric() || chars[i] == '_' || chars[i] == '.')
+                {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                tokens.push(word_token(&word)?);
+            }
+            other => return Err(format!("unexpected character '{}'", other)),
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn word_token(word: &str) -> Result<Token, String> {
+    let token = match word.to_ascii_uppercase().as_str() {
+        "AND" => Token::And,
+        "OR" => Token::Or,
+        "NOT" => Token::Not,
+        "TRUE" => Token::Operand(Operand::Literal(Value::Bool(true))),
+        "FALSE" => Token::Operand(Operand::Literal(Value::Bool(false))),
+        "NULL" => Token::Operand(Operand::Literal(Value::Null)),
+        _ => {
+            let attribute = match word.split_once('.') {
+                Some(("user", name)) if !name.is_empty() => Operand::User(name.to_string()),
+                Some(("resource", name)) if !name.is_empty() => Operand::Resource(name.to_string()),
+                _ => {
+                    return Err(format!(
+                        "unknown attribute '{}', expected user.<name> or resource.<name>",
+                        word
+                    ))
+                }
+            };
+            Token::Operand(attribute)
+        }
+    };
+
+    Ok(token)
+}
+
+/// Recursive-descent parser; precedence from lowest to highest is OR, AND,
+/// NOT, comparison
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<AbacExpr, String> {
+        let mut expr = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.position += 1;
+            expr = AbacExpr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<AbacExpr, String> {
+        let mut expr = self.parse_unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.position += 1;
+            expr = AbacExpr::And(Box::new(expr), Box::new(self.parse_unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_unary(&mut self) -> Result<AbacExpr, String> {
+        match self.peek() {
+            Some(Token::Not) => {
+                self.position += 1;
+                Ok(AbacExpr::Not(Box::new(self.parse_unary()?)))
+            }
+            Some(Token::LParen) => {
+                self.position += 1;
+                let expr = self.parse_or()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(expr),
+                    _ => Err("missing closing parenthesis".to_string()),
+                }
+            }
+            _ => self.parse_comparison(),
+        }
+    }
+
+    fn parse_comparison(&mut self) -> Result<AbacExpr, String> {
+        let left = self.parse_operand()?;
+        let op = match self.next() {
+            Some(Token::Op(op)) => op,
+            other => return Err(format!("expected comparison operator, found {:?}", other)),
+        };
+        let right = self.parse_operand()?;
+
+        Ok(AbacExpr::Compare { left, op, right })
+    }
+
+    fn parse_operand(&mut self) -> Result<Operand, String> {
+        match self.next() {
+            Some(Token::Operand(operand)) => Ok(operand),
+            other => Err(format!("expected attribute or literal, found {:?}", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    fn create_context(department: &str) -> SecurityContext {
+        let mut context = SecurityContext::new(
+            Uuid::new_v4(),
+            None,
+            HashSet::from(["documents:read".to_string()]),
+            vec!["user".to_string()],
+        );
+        context.metadata.department_id = Some(department.to_string());
+        context
+            .metadata
+            .attributes
+            .insert("region".to_string(), "eu".to_string());
+        context
+    }
+
+    fn resource(department: &str, clearance: i64) -> HashMap<String, Value> {
+        HashMap::from([
+            ("department".to_string(), Value::from(department)),
+            ("clearance".to_string(), Value::from(clearance)),
+        ])
+    }
+
+    fn deadline() -> Instant {
+        Instant::now() + Duration::from_secs(1)
+    }
+
+    #[test]
+    fn test_parse_precedence_and_display() {
+        let expr: AbacExpr =
+            "user.region == 'eu' OR resource.public == true AND NOT resource.clearance > 3"
+                .parse()
+                .unwrap();
+
+        assert!(matches!(expr, AbacExpr::Or(_, ref right) if matches!(**right, AbacExpr::And(..))));
+        assert_eq!(
+            expr.to_string(),
+            r#"user.region == "eu" OR resource.public == true AND NOT resource.clearance > 3"#
+        );
+
+        let grouped: AbacExpr = "(user.a == 1 || user.b == 2) && !(user.c != 3)"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            grouped.to_string(),
+            "(user.a == 1 OR user.b == 2) AND NOT user.c != 3"
+        );
+        assert_eq!(grouped.to_string().parse::<AbacExpr>().unwrap(), grouped);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for condition in [
+            "",
+            "user.department ==",
+            "department == 'x'",
+            "(user.a == 1",
+            "user.a == 'open",
+            "user.a == 1 user.b == 2",
+        ] {
+            assert!(
+                condition.parse::<AbacExpr>().is_err(),
+                "expected parse error for {:?}",
+                condition
+            );
+        }
+    }
+
+    #[test]
+    fn test_department_policy() {
+        let policy = AbacPolicy::new(
+            "same-department",
+            vec!["documents:*".to_string()],
+            "resource.department == user.department AND resource.clearance <= 3",
+        )
+        .unwrap();
+        let context = create_context("finance");
+
+        let allowed = AbacAttributes::from_context(&context, resource("finance", 2));
+        assert_eq!(
+            evaluate_policies(&[policy.clone()], "documents:read", &allowed, deadline()).unwrap(),
+            AbacDecision::Allow
+        );
+
+        let other_department = AbacAttributes::from_context(&context, resource("legal", 2));
+        assert_eq!(
+            evaluate_policies(
+                &[policy.clone()],
+                "documents:read",
+                &other_department,
+                deadline()
+            )
+            .unwrap(),
+            AbacDecision::Deny {
+                policy: "same-department".to_string(),
+                clause: "resource.department == user.department".to_string(),
+            }
+        );
+
+        // Policies only apply to their own permissions
+        assert_eq!(
+            evaluate_policies(&[policy], "users:read", &other_department, deadline()).unwrap(),
+            AbacDecision::Allow
+        );
+    }
+
+    #[test]
+    fn test_or_not_and_missing_attributes() {
+        let context = create_context("finance");
+        let attributes = AbacAttributes::from_context(&context, resource("legal", 5));
+        let evaluate = |condition: &str| {
+            condition
+                .parse::<AbacExpr>()
+                .unwrap()
+                .evaluate(&attributes, deadline())
+                .unwrap()
+                .map(|clause| clause.to_string())
+        };
+
+        assert_eq!(evaluate("user.region == 'eu' OR user.region == 'us'"), None);
+        assert_eq!(evaluate("NOT user.is_api_key == true"), None);
+        assert_eq!(evaluate("user.security_level == 'standard'"), None);
+        assert_eq!(
+            evaluate("NOT resource.clearance >= 5"),
+            Some("NOT resource.clearance >= 5".to_string())
+        );
+        assert_eq!(
+            evaluate("resource.owner != 'someone'"),
+            Some(r#"resource.owner != "someone""#.to_string())
+        );
+    }
+
+    #[test]
+    fn test_evaluation_respects_deadline() {
+        let policy = AbacPolicy::new("any", vec!["*".to_string()], "user.region == 'eu'").unwrap();
+        let attributes = AbacAttributes::from_context(&create_context("finance"), HashMap::new());
+
+        let result = evaluate_policies(&[policy], "documents:read", &attributes, Instant::now());
+        assert!(matches!(result, Err(SecureDatabaseError::Timeout(_))));
+    }
+
+    #[test]
+    fn test_policy_serde_round_trip() {
+        let json = serde_json::json!({
+            "name": "mfa-for-exports",
+            "permissions": ["analytics:export"],
+            "condition": "user.mfa_verified == true"
+        });
+
+        let policy: AbacPolicy = serde_json::from_value(json.clone()).unwrap();
+        assert!(policy.applies_to("analytics:export"));
+        assert!(!policy.applies_to("analytics:read"));
+        assert_eq!(serde_json::to_value(&policy).unwrap(), json);
+
+        let invalid = serde_json::json!({
+            "name": "broken",
+            "permissions": ["*"],
+            "condition": "user.mfa_verified =="
+        });
+        assert!(serde_json::from_value::<AbacPolicy>(invalid).is_err());
+    }
+}
@@ -2,7 +2,8 @@
 //!
 //! This module provides database access control functionality that integrates
 //! with the security-agent's authorization services. It implements role-based
-//! and attribute-based access control for database operations.
+//! and attribute-based access control for database operations; see the `abac`
+//! module for the policy language.
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
@@ -15,6 +16,7 @@ use ai_core_security::RbacService;
 use uuid::Uuid;
 
 use crate::{
+    abac::{self, AbacAttributes, AbacDecision, AbacPolicy},
     error::SecureDatabaseError,
     security_context::{SecurityContext, SecurityLevel},
 };
@@ -36,6 +38,19 @@ pub struct AccessControlConfig {
     pub resource_rules: HashMap<String, ResourceAccessRule>,
     /// Default permissions for new resources
     pub default_permissions: Vec<String>,
+    /// Evaluate ABAC policies after RBAC grants a permission
+    #[serde(default)]
+    pub enable_abac: bool,
+    /// Attribute-based policies evaluated when `enable_abac` is set
+    #[serde(default)]
+    pub abac_policies: Vec<AbacPolicy>,
+    /// Time budget for evaluating ABAC policies on a single check
+    #[serde(default = "default_max_policy_evaluation_time_ms")]
+    pub max_policy_evaluation_time_ms: u64,
+}
+
+fn default_max_policy_evaluation_time_ms() -> u64 {
+    100
 }
 
 impl Default for AccessControlConfig {
@@ -90,6 +105,9 @@ impl Default for AccessControlConfig {
             audit_access_decisions: true,
             resource_rules,
             default_permissions: vec![],
+            enable_abac: false,
+            abac_policies: vec![],
+            max_policy_evaluation_time_ms: default_max_policy_evaluation_time_ms(),
         }
     }
 }
@@ -144,6 +162,8 @@ pub struct AccessControlMetrics {
     pub cache_misses: u64,
     pub mfa_required_checks: u64,
     pub elevation_required_checks: u64,
+    pub abac_evaluations: u64,
+    pub abac_denials: u64,
 }
 
 impl DatabaseAccessControl {
@@ -167,6 +187,20 @@ impl DatabaseAccessControl {
         &self,
         context: &SecurityContext,
         permission: &str,
+    ) -> Result<(), SecureDatabaseError> {
+        self.check_permission_with_attributes(context, permission, HashMap::new())
+            .await
+    }
+
+    /// Check a permission against a resource with the given attributes
+    ///
+    /// RBAC decides first; when `enable_abac` is set, every ABAC policy that
+    /// applies to the permission must also hold for the resource.
+    pub async fn check_permission_with_attributes(
+        &self,
+        context: &SecurityContext,
+        permission: &str,
+        resource_attributes: HashMap<String, serde_json::Value>,
     ) -> Result<(), SecureDatabaseError> {
         let start_time = std::time::Instant::now();
 
@@ -194,29 +228,42 @@ impl DatabaseAccessControl {
             ));
         }
 
-        // Check cache first if enabled
-        if self.config.enable_permission_caching {
-            if let Some(cached_result) = self.get_cached_permission(context, permission).await? {
-                if cached_result {
-                    self.update_allowed_metrics().await;
-                    return Ok(());
-                } else {
-                    self.update_denied_metrics().await;
-                    return Err(SecureDatabaseError::AccessDenied(format!(
-                        "Permission denied: {}",
-                        permission
-                    )));
+        // Check cache first if enabled; only the RBAC result is cached since
+        // ABAC decisions depend on the resource
+        let cached_result = if self.config.enable_permission_caching {
+            self.get_cached_permission(context, permission).await?
+        } else {
+            None
+        };
+
+        let allowed = match cached_result {
+            Some(allowed) => allowed,
+            None => {
+                // Perform permission check
+                let allowed = self.perform_permission_check(context, permission).await?;
+
+                // Cache the result if enabled
+                if self.config.enable_permission_caching {
+                    self.cache_permission_result(context, permission, allowed)
+                        .await;
                 }
+                allowed
             }
-        }
-
-        // Perform permission check
-        let allowed = self.perform_permission_check(context, permission).await?;
+        };
 
-        // Cache the result if enabled
-        if self.config.enable_permission_caching {
-            self.cache_permission_result(context, permission, allowed)
-                .await;
+        if allowed && self.config.enable_abac {
+            let abac_result = self.evaluate_abac(context, permission, resource_attributes);
+            {
+                let mut metrics = self.metrics.write().await;
+                metrics.abac_evaluations += 1;
+                if abac_result.is_err() {
+                    metrics.abac_denials += 1;
+                }
+            }
+            if let Err(e) = abac_result {
+                self.update_denied_metrics().await;
+                return Err(e);
+            }
         }
 
         if allowed {
@@ -243,6 +290,49 @@ impl DatabaseAccessControl {
         }
     }
 
+    /// Evaluate the ABAC policies that apply to a permission
+    fn evaluate_abac(
+        &self,
+        context: &SecurityContext,
+        permission: &str,
+        resource_attributes: HashMap<String, serde_json::Value>,
+    ) -> Result<(), SecureDatabaseError> {
+        let deadline = std::time::Instant::now()
+            + std::time::Duration::from_millis(self.config.max_policy_evaluation_time_ms);
+        let attributes = AbacAttributes::from_context(context, resource_attributes);
+
+        match abac::evaluate_policies(
+            &self.config.abac_policies,
+            permission,
+            &attributes,
+            deadline,
+        ) {
+            Ok(AbacDecision::Allow) => Ok(()),
+            Ok(AbacDecision::Deny { policy, clause }) => {
+                warn!(
+                    user_id = %context.user_id,
+                    permission = %permission,
+                    policy = %policy,
+                    clause = %clause,
+                    "Permission denied by ABAC policy"
+                );
+                Err(SecureDatabaseError::AccessDenied(format!(
+                    "Permission denied: {} (policy '{}' failed on `{}`)",
+                    permission, policy, clause
+                )))
+            }
+            Err(e) => {
+                warn!(
+                    user_id = %context.user_id,
+                    permission = %permission,
+                    error = %e,
+                    "ABAC policy evaluation failed"
+                );
+                Err(e)
+            }
+        }
+    }
+
     /// Check resource-specific access
     pub async fn check_resource_access(
         &self,
@@ -7,6 +7,8 @@
 use anyhow::{Context as _, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, instrument, warn};
@@ -158,6 +160,131 @@ pub struct AuditEvent {
     pub risk_score: Option<u8>,
     /// Whether this event triggered an alert
     pub alert_triggered: bool,
+    /// Position of this event in its tenant's hash chain
+    #[serde(default)]
+    pub sequence: u64,
+    /// Hash of the previous event in the tenant's chain
+    #[serde(default)]
+    pub prev_hash: String,
+    /// SHA-256 over the event fields, including `sequence` and `prev_hash`
+    #[serde(default)]
+    pub hash: String,
+}
+
+/// `prev_hash` of the first event in a chain
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+impl AuditEvent {
+    /// Tenant whose hash chain this event belongs to; events without an
+    /// organization share the platform-wide chain
+    pub fn tenant_id(&self) -> Option<Uuid> {
+        self.user_context.organization_id
+    }
+
+    /// Link this event to the tenant's chain head and compute its hash,
+    /// returning the new chain head
+    fn seal(&mut self, head: Option<&ChainHead>) -> Result<ChainHead> {
+        let (sequence, prev_hash) = match head {
+            Some(head) => (head.sequence + 1, head.hash.clone()),
+            None => (0, GENESIS_HASH.to_string()),
+        };
+        self.sequence = sequence;
+        self.prev_hash = prev_hash;
+
+        // Hash the JSON as it will be stored, so verification recomputes the
+        // hash from exactly the same values after a restart
+        let stored: serde_json::Value = serde_json::from_str(&serde_json::to_string(self)?)?;
+        self.hash = chain_hash(&stored);
+
+        Ok(ChainHead {
+            sequence,
+            hash: self.hash.clone(),
+            timestamp: self.timestamp,
+        })
+    }
+}
+
+/// Latest link of a tenant's hash chain
+#[derive(Debug, Clone)]
+struct ChainHead {
+    sequence: u64,
+    hash: String,
+    timestamp: DateTime<Utc>,
+}
+
+/// Result of walking the stored audit hash chains
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChainVerification {
+    /// Start of the verified window
+    pub from: DateTime<Utc>,
+    /// End of the verified window
+    pub to: DateTime<Utc>,
+    /// Number of tenant chains walked
+    pub tenants_checked: usize,
+    /// Number of events whose links were checked
+    pub events_checked: u64,
+    /// Earliest broken link, if any event was altered or deleted
+    pub first_broken_link: Option<BrokenLink>,
+}
+
+impl ChainVerification {
+    /// Whether every checked link is intact
+    pub fn is_intact(&self) -> bool {
+        self.first_broken_link.is_none()
+    }
+}
+
+/// A link in an audit hash chain that failed verification
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BrokenLink {
+    /// Tenant whose chain is broken
+    pub tenant_id: Option<Uuid>,
+    /// Sequence number of the offending link
+    pub sequence: u64,
+    /// Stored event at that position, if it still exists
+    pub event_id: Option<Uuid>,
+    /// Timestamp of the offending event
+    pub timestamp: DateTime<Utc>,
+    /// Why the link is broken
+    pub reason: ChainBreak,
+}
+
+/// Reason a chain link failed verification
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChainBreak {
+    /// The event's contents no longer match its hash
+    HashMismatch,
+    /// `prev_hash` does not match the hash of the preceding event
+    PrevHashMismatch,
+    /// The event at `sequence` has been deleted
+    MissingEvent,
+    /// More than one stored event claims this sequence number
+    DuplicateSequence,
+}
+
+/// Stored event together with the hash recomputed from its stored form
+#[derive(Debug, Clone)]
+struct ChainEntry {
+    event: AuditEvent,
+    computed_hash: String,
+}
+
+impl ChainEntry {
+    /// Parse a stored audit log line; events written before hash chaining
+    /// was introduced carry no hash and are skipped
+    fn parse(line: &str) -> Result<Option<Self>> {
+        let stored: serde_json::Value = serde_json::from_str(line)?;
+        let event: AuditEvent = serde_json::from_value(stored.clone())?;
+        if event.hash.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            computed_hash: chain_hash(&stored),
+            event,
+        }))
+    }
 }
 
 /// Audit logger implementation
@@ -170,6 +297,8 @@ pub struct AuditLogger {
     event_buffer: Arc<RwLock<Vec<AuditEvent>>>,
     /// Audit metrics
     metrics: Arc<RwLock<AuditMetrics>>,
+    /// Latest hash chain link per tenant
+    chain_heads: Arc<RwLock<HashMap<Option<Uuid>, ChainHead>>>,
 }
 
 /// Audit logging metrics
@@ -196,11 +325,19 @@ impl AuditLogger {
             info!("Audit logging is disabled");
         }
 
+        // Continue the hash chains where the stored log left off
+        let chain_heads = if config.store_in_files {
+            Self::restore_chain_heads(&config.file_storage_path).await?
+        } else {
+            HashMap::new()
+        };
+
         let logger = Self {
             database_manager,
             config,
             event_buffer: Arc::new(RwLock::new(Vec::new())),
             metrics: Arc::new(RwLock::new(AuditMetrics::default())),
+            chain_heads: Arc::new(RwLock::new(chain_heads)),
         };
 
         // Start background flush task if enabled
@@ -249,6 +386,9 @@ impl AuditLogger {
                 operation: operation.to_string(),
             }),
             alert_triggered: false,
+            sequence: 0,
+            prev_hash: String::new(),
+            hash: String::new(),
         };
 
         self.add_event(event).await;
@@ -303,6 +443,9 @@ impl AuditLogger {
             }),
             risk_score: self.calculate_risk_score(&event_type),
             alert_triggered: false,
+            sequence: 0,
+            prev_hash: String::new(),
+            hash: String::new(),
         };
 
         self.add_event(event).await;
@@ -358,6 +501,9 @@ impl AuditLogger {
             }),
             risk_score: self.calculate_risk_score(&audit_event_type),
             alert_triggered: !success, // Failed auth always triggers alerts
+            sequence: 0,
+            prev_hash: String::new(),
+            hash: String::new(),
         };
 
         self.add_event(event).await;
@@ -424,6 +570,9 @@ impl AuditLogger {
             }),
             risk_score: self.calculate_risk_score(&audit_event_type),
             alert_triggered: !granted && self.is_sensitive_permission(permission),
+            sequence: 0,
+            prev_hash: String::new(),
+            hash: String::new(),
         };
 
         self.add_event(event).await;
@@ -480,6 +629,9 @@ impl AuditLogger {
             }),
             risk_score: self.calculate_risk_score(&audit_event_type),
             alert_triggered: false,
+            sequence: 0,
+            prev_hash: String::new(),
+            hash: String::new(),
         };
 
         self.add_event(event).await;
@@ -528,6 +680,9 @@ impl AuditLogger {
             }),
             risk_score: self.calculate_risk_score(&audit_event_type),
             alert_triggered: severity == "high" || severity == "critical",
+            sequence: 0,
+            prev_hash: String::new(),
+            hash: String::new(),
         };
 
         self.add_event(event).await;
@@ -542,7 +697,21 @@ impl AuditLogger {
     }
 
     /// Add event to buffer
-    async fn add_event(&self, event: AuditEvent) {
+    async fn add_event(&self, mut event: AuditEvent) {
+        {
+            let mut chain_heads = self.chain_heads.write().await;
+            let tenant_id = event.tenant_id();
+            match event.seal(chain_heads.get(&tenant_id)) {
+                Ok(head) => {
+                    chain_heads.insert(tenant_id, head);
+                }
+                Err(e) => {
+                    error!(error = %e, event_id = %event.id, "Failed to hash audit event");
+                    self.increment_write_errors().await;
+                }
+            }
+        }
+
         let mut buffer = self.event_buffer.write().await;
         buffer.push(event);
 
@@ -683,6 +852,154 @@ impl AuditLogger {
         });
     }
 
+    /// Verify the stored hash chains of all tenants for events between
+    /// `from` and `to`, reporting the earliest broken link
+    pub async fn verify_chain(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<ChainVerification, SecureDatabaseError> {
+        self.verify_chains(None, from, to).await
+    }
+
+    /// Verify the stored hash chain of a single tenant for events between
+    /// `from` and `to`
+    pub async fn verify_tenant_chain(
+        &self,
+        tenant_id: Option<Uuid>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<ChainVerification, SecureDatabaseError> {
+        self.verify_chains(Some(tenant_id), from, to).await
+    }
+
+    async fn verify_chains(
+        &self,
+        tenant_filter: Option<Option<Uuid>>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<ChainVerification, SecureDatabaseError> {
+        if from > to {
+            return Err(SecureDatabaseError::InvalidInput(
+                "Chain verification window must not end before it starts".to_string(),
+            ));
+        }
+        if !self.config.store_in_files {
+            return Err(SecureDatabaseError::AuditError(
+                "Hash chain verification requires file storage to be enabled".to_string(),
+            ));
+        }
+
+        // Buffered events are part of the chain but not yet stored
+        self.flush_events().await;
+
+        let mut entries = Self::read_chain_entries(&self.config.file_storage_path).await?;
+        let mut heads: HashMap<Option<Uuid>, ChainHead> = self.chain_heads.read().await.clone();
+        if let Some(tenant_id) = tenant_filter {
+            entries.retain(|entry| entry.event.tenant_id() == tenant_id);
+            heads.retain(|id, _| *id == tenant_id);
+        }
+
+        let verification = verify_entries(entries, &heads, from, to);
+        match &verification.first_broken_link {
+            Some(link) => warn!(
+                tenant_id = ?link.tenant_id,
+                sequence = link.sequence,
+                reason = ?link.reason,
+                "Audit hash chain verification failed"
+            ),
+            None => debug!(
+                events_checked = verification.events_checked,
+                "Audit hash chains verified"
+            ),
+        }
+
+        Ok(verification)
+    }
+
+    /// Rebuild the per-tenant chain heads from the stored audit log
+    async fn restore_chain_heads(
+        storage_path: &str,
+    ) -> Result<HashMap<Option<Uuid>, ChainHead>, SecureDatabaseError> {
+        let mut heads: HashMap<Option<Uuid>, ChainHead> = HashMap::new();
+
+        for entry in Self::read_chain_entries(storage_path).await? {
+            let event = entry.event;
+            let is_newer = !matches!(
+                heads.get(&event.tenant_id()),
+                Some(head) if head.sequence >= event.sequence
+            );
+            if is_newer {
+                heads.insert(
+                    event.tenant_id(),
+                    ChainHead {
+                        sequence: event.sequence,
+                        hash: event.hash,
+                        timestamp: event.timestamp,
+                    },
+                );
+            }
+        }
+
+        debug!("Restored audit hash chains for {} tenants", heads.len());
+        Ok(heads)
+    }
+
+    /// Read every hash-chained event from the audit log files
+    async fn read_chain_entries(
+        storage_path: &str,
+    ) -> Result<Vec<ChainEntry>, SecureDatabaseError> {
+        let mut dir = match tokio::fs::read_dir(storage_path).await {
+            Ok(dir) => dir,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(SecureDatabaseError::AuditError(format!(
+                    "Failed to read audit log directory {}: {}",
+                    storage_path, e
+                )))
+            }
+        };
+
+        let mut files = Vec::new();
+        while let Some(file) = dir.next_entry().await.map_err(|e| {
+            SecureDatabaseError::AuditError(format!("Failed to list audit log files: {}", e))
+        })? {
+            let name = file.file_name().to_string_lossy().to_string();
+            if name.starts_with("audit_") && name.ends_with(".jsonl") {
+                files.push(file.path());
+            }
+        }
+        files.sort();
+
+        let mut entries = Vec::new();
+        for path in files {
+            let contents = tokio::fs::read_to_string(&path).await.map_err(|e| {
+                SecureDatabaseError::AuditError(format!(
+                    "Failed to read audit log file {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+
+            for (index, line) in contents.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let entry = ChainEntry::parse(line).map_err(|e| {
+                    SecureDatabaseError::AuditError(format!(
+                        "Malformed audit log entry at {}:{}: {}",
+                        path.display(),
+                        index + 1,
+                        e
+                    ))
+                })?;
+                entries.extend(entry);
+            }
+        }
+
+        Ok(entries)
+    }
+
     /// Calculate risk score for an event
     fn calculate_risk_score(&self, event_type: &AuditEventType) -> Option<u8> {
         match event_type {
@@ -787,7 +1104,170 @@ impl Clone for AuditLogger {
             config: self.config.clone(),
             event_buffer: self.event_buffer.clone(),
             metrics: self.metrics.clone(),
+            chain_heads: self.chain_heads.clone(),
+        }
+    }
+}
+
+/// Walk the stored entries of each tenant chain in sequence order and find
+/// the earliest link inside `from..=to` that was altered or deleted
+fn verify_entries(
+    entries: Vec<ChainEntry>,
+    heads: &HashMap<Option<Uuid>, ChainHead>,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> ChainVerification {
+    let in_window = |timestamp: DateTime<Utc>| timestamp >= from && timestamp <= to;
+    let mut chains: HashMap<Option<Uuid>, BTreeMap<u64, ChainEntry>> = HashMap::new();
+    let mut breaks: Vec<BrokenLink> = Vec::new();
+
+    for entry in entries {
+        let chain = chains.entry(entry.event.tenant_id()).or_default();
+        let sequence = entry.event.sequence;
+        if let Some(existing) = chain.insert(sequence, entry) {
+            if in_window(existing.event.timestamp) {
+                breaks.push(BrokenLink {
+                    tenant_id: existing.event.tenant_id(),
+                    sequence,
+                    event_id: Some(existing.event.id),
+                    timestamp: existing.event.timestamp,
+                    reason: ChainBreak::DuplicateSequence,
+                });
+            }
+        }
+    }
+
+    let mut tenants_checked = 0;
+    let mut events_checked = 0;
+
+    for (tenant_id, chain) in &chains {
+        tenants_checked += 1;
+
+        for (sequence, entry) in chain {
+            let event = &entry.event;
+            if !in_window(event.timestamp) {
+                continue;
+            }
+            events_checked += 1;
+
+            let expected_prev = if *sequence == 0 {
+                Some(GENESIS_HASH)
+            } else {
+                chain
+                    .get(&(sequence - 1))
+                    .map(|prev| prev.event.hash.as_str())
+            };
+            let reason = if entry.computed_hash != event.hash {
+                Some(ChainBreak::HashMismatch)
+            } else if expected_prev.is_none() {
+                Some(ChainBreak::MissingEvent)
+            } else if expected_prev != Some(event.prev_hash.as_str()) {
+                Some(ChainBreak::PrevHashMismatch)
+            } else {
+                None
+            };
+
+            if let Some(reason) = reason {
+                // A missing event is reported at the position that was removed
+                let sequence = match reason {
+                    ChainBreak::MissingEvent => sequence - 1,
+                    _ => *sequence,
+                };
+                breaks.push(BrokenLink {
+                    tenant_id: *tenant_id,
+                    sequence,
+                    event_id: (reason != ChainBreak::MissingEvent).then_some(event.id),
+                    timestamp: event.timestamp,
+                    reason,
+                });
+                break;
+            }
+        }
+    }
+
+    // Events removed from the end of a chain leave no successor behind, so
+    // compare against the head the logger last issued
+    for (tenant_id, head) in heads {
+        let stored_last = chains
+            .get(tenant_id)
+            .and_then(|chain| chain.keys().next_back().copied());
+        let truncated = !matches!(stored_last, Some(last) if last >= head.sequence);
+        if truncated && in_window(head.timestamp) {
+            breaks.push(BrokenLink {
+                tenant_id: *tenant_id,
+                sequence: head.sequence,
+                event_id: None,
+                timestamp: head.timestamp,
+                reason: ChainBreak::MissingEvent,
+            });
+        }
+    }
+
+    ChainVerification {
+        from,
+        to,
+        tenants_checked,
+        events_checked,
+        first_broken_link: breaks
+            .into_iter()
+            .min_by_key(|link| (link.timestamp, link.sequence)),
+    }
+}
+
+/// SHA-256 over the canonical JSON of a stored event, excluding its own `hash`
+fn chain_hash(stored: &serde_json::Value) -> String {
+    let mut canonical = String::new();
+    match stored {
+        serde_json::Value::Object(fields) => {
+            let fields: serde_json::Map<String, serde_json::Value> = fields
+                .iter()
+                .filter(|(key, _)| key.as_str() != "hash")
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect();
+            write_canonical_json(&serde_json::Value::Object(fields), &mut canonical);
+        }
+        other => write_canonical_json(other, &mut canonical),
+    }
+
+    let digest = ring::digest::digest(&ring::digest::SHA256, canonical.as_bytes());
+    digest
+        .as_ref()
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{:02x}", byte);
+            hex
+        })
+}
+
+/// Serialize JSON with object keys sorted so the output does not depend on
+/// map ordering
+fn write_canonical_json(value: &serde_json::Value, out: &mut String) {
+    match value {
+        serde_json::Value::Object(fields) => {
+            let mut keys: Vec<&String> = fields.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (index, key) in keys.into_iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical_json(&fields[key], out);
+            }
+            out.push('}');
+        }
+        serde_json::Value::Array(items) => {
+            out.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_canonical_json(item, out);
+            }
+            out.push(']');
         }
+        scalar => out.push_str(&scalar.to_string()),
     }
 }
 
@@ -837,6 +1317,9 @@ mod tests {
             metadata: serde_json::json!({"test": "data"}),
             risk_score: Some(10),
             alert_triggered: false,
+            sequence: 0,
+            prev_hash: String::new(),
+            hash: String::new(),
         };
 
         assert_eq!(event.level, AuditLevel::Info);
@@ -853,6 +1336,7 @@ mod tests {
             config,
             event_buffer: Arc::new(RwLock::new(Vec::new())),
             metrics: Arc::new(RwLock::new(AuditMetrics::default())),
+            chain_heads: Arc::new(RwLock::new(HashMap::new())),
         };
 
         let read_event = AuditEventType::DataAccess {
@@ -882,6 +1366,7 @@ mod tests {
             config,
             event_buffer: Arc::new(RwLock::new(Vec::new())),
             metrics: Arc::new(RwLock::new(AuditMetrics::default())),
+            chain_heads: Arc::new(RwLock::new(HashMap::new())),
         };
 
         assert!(logger.is_sensitive_permission("user:admin"));
@@ -890,4 +1375,153 @@ mod tests {
         assert!(!logger.is_sensitive_permission("user:read"));
         assert!(!logger.is_sensitive_permission("workflow:create"));
     }
+
+    fn create_tenant_event(tenant_id: Option<Uuid>, message: &str) -> AuditEvent {
+        let mut context = create_test_context();
+        context.metadata.organization_id = tenant_id;
+        AuditEvent {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            level: AuditLevel::Info,
+            event_type: AuditEventType::DataAccess {
+                table: "posts".to_string(),
+                record_id: "42".to_string(),
+                operation: "read".to_string(),
+            },
+            user_context: context.audit_context(),
+            message: message.to_string(),
+            metadata: serde_json::json!({"score": 0.1, "tags": ["a", "b"]}),
+            risk_score: Some(10),
+            alert_triggered: false,
+            sequence: 0,
+            prev_hash: String::new(),
+            hash: String::new(),
+        }
+    }
+
+    /// Seal `count` events into a chain and return them as stored log lines
+    fn create_chain(
+        tenant_id: Option<Uuid>,
+        count: usize,
+        heads: &mut HashMap<Option<Uuid>, ChainHead>,
+    ) -> Vec<String> {
+        (0..count)
+            .map(|index| {
+                let mut event = create_tenant_event(tenant_id, &format!("event {}", index));
+                let head = event.seal(heads.get(&tenant_id)).unwrap();
+                heads.insert(tenant_id, head);
+                serde_json::to_string(&event).unwrap()
+            })
+            .collect()
+    }
+
+    fn parse_lines(lines: &[String]) -> Vec<ChainEntry> {
+        lines
+            .iter()
+            .filter_map(|line| ChainEntry::parse(line).unwrap())
+            .collect()
+    }
+
+    fn full_window() -> (DateTime<Utc>, DateTime<Utc>) {
+        (
+            Utc::now() - chrono::Duration::hours(1),
+            Utc::now() + chrono::Duration::hours(1),
+        )
+    }
+
+    #[test]
+    fn test_chains_are_linked_per_tenant() {
+        let tenant_a = Some(Uuid::new_v4());
+        let tenant_b = Some(Uuid::new_v4());
+        let mut heads = HashMap::new();
+        let chain_a = create_chain(tenant_a, 3, &mut heads);
+        let chain_b = create_chain(tenant_b, 2, &mut heads);
+
+        let entries = parse_lines(&chain_a);
+        assert_eq!(entries[0].event.prev_hash, GENESIS_HASH);
+        assert_eq!(entries[1].event.prev_hash, entries[0].event.hash);
+        assert_eq!(entries[2].event.sequence, 2);
+        assert!(entries
+            .iter()
+            .all(|entry| entry.computed_hash == entry.event.hash));
+        assert_eq!(parse_lines(&chain_b)[0].event.prev_hash, GENESIS_HASH);
+
+        let (from, to) = full_window();
+        let all_lines: Vec<String> = chain_a.into_iter().chain(chain_b).collect();
+        let verification = verify_entries(parse_lines(&all_lines), &heads, from, to);
+        assert!(verification.is_intact());
+        assert_eq!(verification.tenants_checked, 2);
+        assert_eq!(verification.events_checked, 5);
+    }
+
+    #[test]
+    fn test_verify_detects_altered_event() {
+        let tenant_id = Some(Uuid::new_v4());
+        let mut heads = HashMap::new();
+        let mut lines = create_chain(tenant_id, 4, &mut heads);
+        lines[2] = lines[2].replace("event 2", "event two");
+
+        let (from, to) = full_window();
+        let verification = verify_entries(parse_lines(&lines), &heads, from, to);
+        let broken = verification.first_broken_link.unwrap();
+        assert_eq!(broken.tenant_id, tenant_id);
+        assert_eq!(broken.sequence, 2);
+        assert_eq!(broken.reason, ChainBreak::HashMismatch);
+    }
+
+    #[test]
+    fn test_verify_detects_deleted_events() {
+        let tenant_id = Some(Uuid::new_v4());
+        let mut heads = HashMap::new();
+        let mut lines = create_chain(tenant_id, 4, &mut heads);
+        lines.remove(1);
+
+        let (from, to) = full_window();
+        let broken = verify_entries(parse_lines(&lines), &heads, from, to)
+            .first_broken_link
+            .unwrap();
+        assert_eq!(broken.sequence, 1);
+        assert_eq!(broken.reason, ChainBreak::MissingEvent);
+
+        // Truncating the tail is caught against the issued chain head
+        let mut heads = HashMap::new();
+        let lines = create_chain(tenant_id, 3, &mut heads);
+        let broken = verify_entries(parse_lines(&lines[..2]), &heads, from, to)
+            .first_broken_link
+            .unwrap();
+        assert_eq!(broken.sequence, 2);
+        assert_eq!(broken.reason, ChainBreak::MissingEvent);
+        assert_eq!(broken.event_id, None);
+    }
+
+    #[test]
+    fn test_tenant_chains_verify_independently() {
+        let tenant_a = Some(Uuid::new_v4());
+        let tenant_b = Some(Uuid::new_v4());
+        let mut heads = HashMap::new();
+        let mut chain_a = create_chain(tenant_a, 3, &mut heads);
+        let chain_b = create_chain(tenant_b, 3, &mut heads);
+        chain_a[1] = chain_a[1].replace("\"risk_score\":10", "\"risk_score\":0");
+
+        let (from, to) = full_window();
+        let all_lines: Vec<String> = chain_a.into_iter().chain(chain_b.clone()).collect();
+        let broken = verify_entries(parse_lines(&all_lines), &heads, from, to)
+            .first_broken_link
+            .unwrap();
+        assert_eq!(broken.tenant_id, tenant_a);
+
+        let mut b_heads = heads.clone();
+        b_heads.retain(|id, _| *id == tenant_b);
+        assert!(verify_entries(parse_lines(&chain_b), &b_heads, from, to).is_intact());
+    }
+
+    #[test]
+    fn test_chain_hash_ignores_key_order() {
+        let ordered = serde_json::json!({"a": 1, "b": {"c": [1, 2], "d": "x"}, "hash": "ignored"});
+        let reordered: serde_json::Value =
+            serde_json::from_str(r#"{"b": {"d": "x", "c": [1, 2]}, "a": 1}"#).unwrap();
+
+        assert_eq!(chain_hash(&ordered), chain_hash(&reordered));
+        assert_eq!(chain_hash(&ordered).len(), 64);
+    }
 }
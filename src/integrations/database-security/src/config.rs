@@ -770,6 +770,14 @@ impl SecureDatabaseConfig {
             ));
         }
 
+        // Validate access control configurations
+        if self.access_control.enable_abac && self.access_control.max_policy_evaluation_time_ms == 0
+        {
+            return Err(SecureDatabaseError::Configuration(
+                "ABAC policy evaluation time must be greater than 0".to_string(),
+            ));
+        }
+
         info!("Configuration validation passed");
         Ok(())
     }
@@ -3,17 +3,31 @@
 //! This module provides encryption integration functionality that bridges
 //! the security-agent's encryption services with database operations for
 //! transparent data encryption and decryption.
+//!
+//! Tenant data can additionally be protected with envelope encryption: each
+//! tenant gets its own data encryption key (DEK), stored wrapped by the master
+//! key, so one tenant's key never decrypts another tenant's data.
 
 use anyhow::Result;
+use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine as _};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 use tracing::{debug, info, instrument};
+use uuid::Uuid;
 
+use ai_core_security::encryption::{EncryptedData, EncryptionAlgorithm, EncryptionKey, KeyPurpose};
 use ai_core_security::EncryptionService;
 
 use crate::error::SecureDatabaseError;
+use crate::tenant_keys::{InMemoryTenantKeyStore, TenantKeyStore, WrappedDataKey};
+
+/// Prefix of tenant ciphertexts, followed by the key version: `tdk:v<version>:<payload>`
+const TENANT_CIPHERTEXT_PREFIX: &str = "tdk:v";
+
+/// Size of a tenant data encryption key in bytes (AES-256)
+const DATA_KEY_SIZE: usize = 32;
 
 /// Data encryption configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,6 +50,13 @@ pub struct DataEncryptionConfig {
     pub cache_ttl_seconds: u64,
     /// Maximum cache size
     pub max_cache_size: usize,
+    /// How long unwrapped tenant data keys are kept in memory, in seconds
+    #[serde(default = "default_data_key_cache_ttl_seconds")]
+    pub data_key_cache_ttl_seconds: u64,
+}
+
+fn default_data_key_cache_ttl_seconds() -> u64 {
+    300
 }
 
 impl Default for DataEncryptionConfig {
@@ -63,6 +84,7 @@ impl Default for DataEncryptionConfig {
             enable_caching: true,
             cache_ttl_seconds: 300, // 5 minutes
             max_cache_size: 10000,
+            data_key_cache_ttl_seconds: default_data_key_cache_ttl_seconds(),
         }
     }
 }
@@ -76,6 +98,13 @@ struct EncryptionCacheEntry {
     cached_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// Unwrapped tenant data key held in memory
+#[derive(Debug, Clone)]
+struct CachedDataKey {
+    key: EncryptionKey,
+    cached_at: chrono::DateTime<chrono::Utc>,
+}
+
 /// Data encryption manager
 pub struct DataEncryption {
     /// Encryption service from security-agent
@@ -86,6 +115,12 @@ pub struct DataEncryption {
     encryption_cache: Arc<RwLock<HashMap<String, EncryptionCacheEntry>>>,
     /// Encryption metrics
     metrics: Arc<RwLock<EncryptionMetrics>>,
+    /// Store of wrapped tenant data keys
+    key_store: Arc<dyn TenantKeyStore>,
+    /// Unwrapped tenant data keys by tenant and version
+    data_key_cache: Arc<RwLock<HashMap<(Uuid, u32), CachedDataKey>>>,
+    /// Serializes tenant key creation and rotation
+    tenant_key_lock: Arc<Mutex<()>>,
 }
 
 /// Encryption operation metrics
@@ -101,6 +136,10 @@ pub struct EncryptionMetrics {
     pub avg_decryption_time_ms: f64,
     pub keys_rotated: u64,
     pub last_key_rotation: Option<chrono::DateTime<chrono::Utc>>,
+    pub tenant_keys_created: u64,
+    pub tenant_keys_rotated: u64,
+    pub data_key_cache_hits: u64,
+    pub data_key_cache_misses: u64,
 }
 
 impl DataEncryption {
@@ -116,9 +155,18 @@ impl DataEncryption {
             config,
             encryption_cache: Arc::new(RwLock::new(HashMap::new())),
             metrics: Arc::new(RwLock::new(EncryptionMetrics::default())),
+            key_store: Arc::new(InMemoryTenantKeyStore::new()),
+            data_key_cache: Arc::new(RwLock::new(HashMap::new())),
+            tenant_key_lock: Arc::new(Mutex::new(())),
         })
     }
 
+    /// Use a different store for wrapped tenant data keys
+    pub fn with_key_store(mut self, key_store: Arc<dyn TenantKeyStore>) -> Self {
+        self.key_store = key_store;
+        self
+    }
+
     /// Encrypt a string value
     #[instrument(skip(self, plaintext), fields(len = plaintext.len()))]
     pub async fn encrypt_string(&self, plaintext: &str) -> Result<String, SecureDatabaseError> {
@@ -422,6 +470,301 @@ impl DataEncryption {
         Ok(())
     }
 
+    /// Encrypt a value with the tenant's current data key, creating the key on
+    /// first use
+    #[instrument(skip(self, plaintext), fields(tenant_id = %tenant_id, len = plaintext.len()))]
+    pub async fn encrypt_for_tenant(
+        &self,
+        tenant_id: Uuid,
+        plaintext: &str,
+    ) -> Result<String, SecureDatabaseError> {
+        if !self.config.enabled {
+            return Ok(plaintext.to_string());
+        }
+
+        let start_time = std::time::Instant::now();
+
+        let (version, data_key) = self.current_data_key(tenant_id).await?;
+        let encrypted = self
+            .encryption_service
+            .encrypt_with_key(
+                plaintext.as_bytes(),
+                &data_key,
+                Some(tenant_id.as_bytes().as_slice()),
+            )
+            .await
+            .map_err(|e| SecureDatabaseError::EncryptionError(e.to_string()))?;
+
+        let payload = serde_json::to_vec(&encrypted)
+            .map_err(|e| SecureDatabaseError::SerializationError(e.to_string()))?;
+        let ciphertext = format!(
+            "{}{}:{}",
+            TENANT_CIPHERTEXT_PREFIX,
+            version,
+            BASE64_STANDARD.encode(payload)
+        );
+
+        let duration = start_time.elapsed();
+        self.update_encryption_metrics(duration).await;
+
+        debug!(
+            tenant_id = %tenant_id,
+            key_version = version,
+            duration_ms = duration.as_millis(),
+            "Tenant value encrypted"
+        );
+
+        Ok(ciphertext)
+    }
+
+    /// Decrypt a value produced by `encrypt_for_tenant`, using the key version
+    /// embedded in the ciphertext
+    #[instrument(skip(self, ciphertext), fields(tenant_id = %tenant_id, len = ciphertext.len()))]
+    pub async fn decrypt_for_tenant(
+        &self,
+        tenant_id: Uuid,
+        ciphertext: &str,
+    ) -> Result<String, SecureDatabaseError> {
+        if !self.config.enabled {
+            return Ok(ciphertext.to_string());
+        }
+
+        let start_time = std::time::Instant::now();
+
+        let (version, encrypted) = Self::parse_tenant_ciphertext(ciphertext)?;
+        // The tenant id is bound as associated data, so ciphertext copied
+        // between tenants is rejected even if the key version exists for both
+        if encrypted.associated_data.as_deref()
+            != Some(BASE64_STANDARD.encode(tenant_id.as_bytes()).as_str())
+        {
+            return Err(SecureDatabaseError::DecryptionError(format!(
+                "Ciphertext does not belong to tenant {}",
+                tenant_id
+            )));
+        }
+
+        let data_key = self.data_key(tenant_id, version).await?;
+        let decrypted_bytes = self
+            .encryption_service
+            .decrypt_with_key(&encrypted, &data_key)
+            .await
+            .map_err(|e| SecureDatabaseError::DecryptionError(e.to_string()))?;
+
+        let decrypted_string = String::from_utf8(decrypted_bytes).map_err(|e| {
+            SecureDatabaseError::DecryptionError(format!("UTF-8 decode error: {}", e))
+        })?;
+
+        let duration = start_time.elapsed();
+        self.update_decryption_metrics(duration).await;
+
+        debug!(
+            tenant_id = %tenant_id,
+            key_version = version,
+            duration_ms = duration.as_millis(),
+            "Tenant value decrypted"
+        );
+
+        Ok(decrypted_string)
+    }
+
+    /// Create a new data key version for a tenant and return it; data
+    /// encrypted under earlier versions stays decryptable
+    #[instrument(skip(self), fields(tenant_id = %tenant_id))]
+    pub async fn rotate_tenant_key(&self, tenant_id: Uuid) -> Result<u32, SecureDatabaseError> {
+        let _guard = self.tenant_key_lock.lock().await;
+
+        let current_version = self
+            .key_store
+            .get_current(tenant_id)
+            .await?
+            .map_or(0, |key| key.version);
+        let (version, _) = self.create_data_key(tenant_id, current_version + 1).await?;
+
+        {
+            let mut metrics = self.metrics.write().await;
+            metrics.tenant_keys_rotated += 1;
+        }
+
+        info!(tenant_id = %tenant_id, key_version = version, "Tenant data key rotated");
+        Ok(version)
+    }
+
+    /// Get the tenant's newest data key, creating version 1 if none exists
+    async fn current_data_key(
+        &self,
+        tenant_id: Uuid,
+    ) -> Result<(u32, EncryptionKey), SecureDatabaseError> {
+        if let Some(wrapped) = self.key_store.get_current(tenant_id).await? {
+            let key = self.data_key(tenant_id, wrapped.version).await?;
+            return Ok((wrapped.version, key));
+        }
+
+        let _guard = self.tenant_key_lock.lock().await;
+
+        // Another task may have created the key while we waited for the lock
+        if let Some(wrapped) = self.key_store.get_current(tenant_id).await? {
+            let key = self.data_key(tenant_id, wrapped.version).await?;
+            return Ok((wrapped.version, key));
+        }
+
+        self.create_data_key(tenant_id, 1).await
+    }
+
+    /// Get a specific data key version, unwrapping it from the key store on a
+    /// cache miss
+    async fn data_key(
+        &self,
+        tenant_id: Uuid,
+        version: u32,
+    ) -> Result<EncryptionKey, SecureDatabaseError> {
+        {
+            let cache = self.data_key_cache.read().await;
+            if let Some(entry) = cache.get(&(tenant_id, version)) {
+                let cache_age = chrono::Utc::now() - entry.cached_at;
+                if cache_age.num_seconds() < self.config.data_key_cache_ttl_seconds as i64 {
+                    self.metrics.write().await.data_key_cache_hits += 1;
+                    return Ok(entry.key.clone());
+                }
+            }
+        }
+        self.metrics.write().await.data_key_cache_misses += 1;
+
+        let wrapped = self
+            .key_store
+            .get_version(tenant_id, version)
+            .await?
+            .ok_or_else(|| {
+                SecureDatabaseError::KeyManagementError(format!(
+                    "Data key version {} not found for tenant {}",
+                    version, tenant_id
+                ))
+            })?;
+
+        let key = self.unwrap_data_key(&wrapped).await?;
+        self.cache_data_key(tenant_id, version, &key).await;
+        Ok(key)
+    }
+
+    /// Generate a data key, store it wrapped by the master key and cache it
+    async fn create_data_key(
+        &self,
+        tenant_id: Uuid,
+        version: u32,
+    ) -> Result<(u32, EncryptionKey), SecureDatabaseError> {
+        let master_key = self
+            .encryption_service
+            .key_manager
+            .get_default_key()
+            .await
+            .map_err(|e| SecureDatabaseError::KeyManagementError(e.to_string()))?;
+
+        let key = Self::data_encryption_key(
+            tenant_id,
+            version,
+            self.encryption_service.generate_random_bytes(DATA_KEY_SIZE),
+        );
+        let wrapped_key = self
+            .encryption_service
+            .encrypt_with_key(&key.key, &master_key, Some(tenant_id.as_bytes().as_slice()))
+            .await
+            .map_err(|e| SecureDatabaseError::KeyManagementError(e.to_string()))?;
+
+        self.key_store
+            .insert(WrappedDataKey {
+                tenant_id,
+                version,
+                wrapped_key,
+                created_at: chrono::Utc::now(),
+            })
+            .await?;
+        self.cache_data_key(tenant_id, version, &key).await;
+
+        {
+            let mut metrics = self.metrics.write().await;
+            metrics.tenant_keys_created += 1;
+        }
+
+        debug!(tenant_id = %tenant_id, key_version = version, "Tenant data key created");
+        Ok((version, key))
+    }
+
+    /// Decrypt a wrapped data key with the master key it was wrapped under
+    async fn unwrap_data_key(
+        &self,
+        wrapped: &WrappedDataKey,
+    ) -> Result<EncryptionKey, SecureDatabaseError> {
+        let key_material = self
+            .encryption_service
+            .decrypt(&wrapped.wrapped_key)
+            .await
+            .map_err(|e| {
+                SecureDatabaseError::KeyManagementError(format!(
+                    "Failed to unwrap data key version {} for tenant {}: {}",
+                    wrapped.version, wrapped.tenant_id, e
+                ))
+            })?;
+
+        if key_material.len() != DATA_KEY_SIZE {
+            return Err(SecureDatabaseError::KeyManagementError(format!(
+                "Data key version {} for tenant {} has an invalid length",
+                wrapped.version, wrapped.tenant_id
+            )));
+        }
+
+        Ok(Self::data_encryption_key(
+            wrapped.tenant_id,
+            wrapped.version,
+            key_material,
+        ))
+    }
+
+    async fn cache_data_key(&self, tenant_id: Uuid, version: u32, key: &EncryptionKey) {
+        let mut cache = self.data_key_cache.write().await;
+        let now = chrono::Utc::now();
+        let ttl = self.config.data_key_cache_ttl_seconds as i64;
+        cache.retain(|_, entry| (now - entry.cached_at).num_seconds() < ttl);
+        cache.insert(
+            (tenant_id, version),
+            CachedDataKey {
+                key: key.clone(),
+                cached_at: now,
+            },
+        );
+    }
+
+    fn data_encryption_key(tenant_id: Uuid, version: u32, key: Vec<u8>) -> EncryptionKey {
+        EncryptionKey {
+            id: format!("tenant:{}:v{}", tenant_id, version),
+            key,
+            algorithm: EncryptionAlgorithm::Aes256Gcm,
+            created_at: chrono::Utc::now(),
+            expires_at: None,
+            generation: version,
+            purpose: KeyPurpose::DataEncryption,
+            derivation: None,
+        }
+    }
+
+    /// Split a tenant ciphertext into its key version and encrypted payload
+    fn parse_tenant_ciphertext(
+        ciphertext: &str,
+    ) -> Result<(u32, EncryptedData), SecureDatabaseError> {
+        let invalid =
+            || SecureDatabaseError::DecryptionError("Invalid tenant ciphertext format".to_string());
+
+        let (version, payload) = ciphertext
+            .strip_prefix(TENANT_CIPHERTEXT_PREFIX)
+            .and_then(|rest| rest.split_once(':'))
+            .ok_or_else(invalid)?;
+        let version: u32 = version.parse().map_err(|_| invalid())?;
+
+        let payload = BASE64_STANDARD.decode(payload).map_err(|_| invalid())?;
+        let encrypted: EncryptedData = serde_json::from_slice(&payload)
+            .map_err(|e| SecureDatabaseError::DeserializationError(e.to_string()))?;
+
+        Ok((version, encrypted))
+    }
+
     /// Check if key rotation is needed
     pub async fn needs_key_rotation(&self) -> bool {
         let metrics = self.metrics.read().await;
@@ -583,6 +926,9 @@ impl Clone for DataEncryption {
             config: self.config.clone(),
             encryption_cache: self.encryption_cache.clone(),
             metrics: self.metrics.clone(),
+            key_store: self.key_store.clone(),
+            data_key_cache: self.data_key_cache.clone(),
+            tenant_key_lock: self.tenant_key_lock.clone(),
         }
     }
 }
@@ -669,4 +1015,110 @@ mod tests {
         // Should need rotation initially (never rotated)
         assert!(data_encryption.needs_key_rotation().await);
     }
+
+    async fn create_tenant_encryption(data_key_cache_ttl_seconds: u64) -> DataEncryption {
+        let key_manager =
+            ai_core_security::encryption::InMemoryKeyManager::new(chrono::Duration::days(90));
+        key_manager.initialize_with_defaults().await.unwrap();
+        let encryption_service = Arc::new(EncryptionService::new(key_manager).await.unwrap());
+        let config = DataEncryptionConfig {
+            data_key_cache_ttl_seconds,
+            ..Default::default()
+        };
+
+        DataEncryption::new(encryption_service, config).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_tenant_encryption_round_trip() {
+        let data_encryption = create_tenant_encryption(300).await;
+        let tenant_id = Uuid::new_v4();
+
+        let ciphertext = data_encryption
+            .encrypt_for_tenant(tenant_id, "alice@example.com")
+            .await
+            .unwrap();
+        assert!(ciphertext.starts_with("tdk:v1:"));
+        assert!(!ciphertext.contains("alice"));
+
+        let plaintext = data_encryption
+            .decrypt_for_tenant(tenant_id, &ciphertext)
+            .await
+            .unwrap();
+        assert_eq!(plaintext, "alice@example.com");
+
+        let metrics = data_encryption.get_metrics().await;
+        assert_eq!(metrics.tenant_keys_created, 1);
+        assert_eq!(metrics.data_key_cache_hits, 1);
+    }
+
+    #[tokio::test]
+    async fn test_tenant_ciphertext_is_isolated() {
+        let data_encryption = create_tenant_encryption(300).await;
+        let tenant_a = Uuid::new_v4();
+        let tenant_b = Uuid::new_v4();
+
+        let ciphertext = data_encryption
+            .encrypt_for_tenant(tenant_a, "secret")
+            .await
+            .unwrap();
+        data_encryption
+            .encrypt_for_tenant(tenant_b, "other")
+            .await
+            .unwrap();
+
+        let result = data_encryption
+            .decrypt_for_tenant(tenant_b, &ciphertext)
+            .await;
+        assert!(matches!(
+            result,
+            Err(SecureDatabaseError::DecryptionError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rotated_tenant_key_keeps_old_data_readable() {
+        let data_encryption = create_tenant_encryption(0).await;
+        let tenant_id = Uuid::new_v4();
+
+        let old_ciphertext = data_encryption
+            .encrypt_for_tenant(tenant_id, "before rotation")
+            .await
+            .unwrap();
+        let version = data_encryption.rotate_tenant_key(tenant_id).await.unwrap();
+        assert_eq!(version, 2);
+
+        let new_ciphertext = data_encryption
+            .encrypt_for_tenant(tenant_id, "after rotation")
+            .await
+            .unwrap();
+        assert!(new_ciphertext.starts_with("tdk:v2:"));
+
+        // A zero TTL forces the old key to be unwrapped from the key store
+        assert_eq!(
+            data_encryption
+                .decrypt_for_tenant(tenant_id, &old_ciphertext)
+                .await
+                .unwrap(),
+            "before rotation"
+        );
+        assert_eq!(
+            data_encryption
+                .decrypt_for_tenant(tenant_id, &new_ciphertext)
+                .await
+                .unwrap(),
+            "after rotation"
+        );
+
+        let metrics = data_encryption.get_metrics().await;
+        assert_eq!(metrics.tenant_keys_rotated, 1);
+        assert_eq!(metrics.data_key_cache_hits, 0);
+    }
+
+    #[test]
+    fn test_tenant_ciphertext_parsing_rejects_garbage() {
+        for ciphertext in ["plain", "tdk:vX:abc", "tdk:v1:not base64!"] {
+            assert!(DataEncryption::parse_tenant_ciphertext(ciphertext).is_err());
+        }
+    }
 }
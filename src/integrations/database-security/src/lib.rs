@@ -56,6 +56,7 @@ pub use ai_core_security::{SecurityConfig, SecurityService};
 pub use ai_core_shared::types::{Permission, TokenClaims, User};
 
 // Core integration modules
+pub mod abac;
 pub mod access_control;
 pub mod audit;
 pub mod config;
@@ -64,6 +65,7 @@ pub mod error;
 pub mod metrics;
 pub mod secure_repositories;
 pub mod security_context;
+pub mod tenant_keys;
 
 // Re-export key types
 pub use access_control::DatabaseAccessControl;
@@ -73,6 +75,7 @@ pub use encryption_integration::DataEncryption;
 pub use error::SecureDatabaseError;
 pub use metrics::SecureDatabaseMetrics;
 pub use security_context::SecurityContext;
+pub use tenant_keys::{InMemoryTenantKeyStore, TenantKeyStore};
 
 /// Main secure database manager that integrates security and database services
 #[derive(Clone)]
@@ -154,11 +157,14 @@ impl SecureDatabaseManager {
             permission_cache,
             ai_core_security::rbac::RbacConfig {
                 enable_rbac: true,
+                // No policies are loaded into the RBAC service, and its ABAC
+                // fallback allows everything in permissive mode; attribute
+                // policies are evaluated by DatabaseAccessControl instead
                 enable_abac: false,
                 cache_ttl: chrono::Duration::minutes(30),
                 admin_override: true,
                 evaluation_mode: ai_core_security::rbac::PermissionEvaluationMode::Permissive,
-                max_policy_evaluation_time_ms: 100,
+                max_policy_evaluation_time_ms: config.access_control.max_policy_evaluation_time_ms,
             },
         ));
 
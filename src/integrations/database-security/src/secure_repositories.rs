@@ -4,7 +4,8 @@
 //! with the security-agent's authorization and encryption services.
 
 use anyhow::Result;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sqlx::Row;
 use std::sync::Arc;
 use tracing::debug;
 
@@ -22,6 +23,9 @@ use crate::{
     metrics::SecureDatabaseMetrics, security_context::SecurityContext,
 };
 
+/// Largest page `list_owned` will return
+const MAX_PAGE_SIZE: u32 = 500;
+
 /// Secure PostgreSQL repository with integrated security
 pub struct SecurePostgresRepository {
     postgres: Arc<PostgresRepository>,
@@ -139,6 +143,132 @@ impl SecurePostgresRepository {
         })
     }
 
+    /// List one page of rows the caller owns, ordered by `id`.
+    ///
+    /// Table access is checked with `DatabaseAccessControl` before any query
+    /// runs. Rows are filtered on `owner_id = ctx.user_id` unless the context
+    /// holds the table's admin permission, and encrypted columns are decrypted
+    /// before deserializing into `T`. `page` is zero-based.
+    pub async fn list_owned<T: DeserializeOwned>(
+        &self,
+        context: &SecurityContext,
+        table: &str,
+        page: u32,
+        page_size: u32,
+    ) -> Result<Page<T>, SecureDatabaseError> {
+        if !is_valid_table_name(table) {
+            return Err(SecureDatabaseError::InvalidInput(format!(
+                "Invalid table name: {}",
+                table
+            )));
+        }
+        if page_size == 0 || page_size > MAX_PAGE_SIZE {
+            return Err(SecureDatabaseError::InvalidInput(format!(
+                "Page size must be between 1 and {}",
+                MAX_PAGE_SIZE
+            )));
+        }
+
+        // Deny unauthorized table access before touching the database
+        self.access_control
+            .check_resource_access(context, table, "*", "list")
+            .await?;
+        let admin_override = self
+            .access_control
+            .check_resource_access(context, table, "*", "admin")
+            .await
+            .is_ok();
+
+        let start_time = std::time::Instant::now();
+        let result = self
+            .query_page(context, table, page, page_size, admin_override)
+            .await;
+
+        self.metrics
+            .record_operation("postgresql", "list", start_time.elapsed(), result.is_ok())
+            .await;
+
+        let (records, total_count) = result?;
+
+        self.audit_logger
+            .log_data_access(
+                context,
+                table,
+                &format!("page:{}", page),
+                "list",
+                if admin_override {
+                    "Rows listed with admin override"
+                } else {
+                    "Owned rows listed"
+                },
+            )
+            .await;
+
+        let mut items = Vec::with_capacity(records.len());
+        for mut record in records {
+            self.data_encryption
+                .decrypt_record(table, &mut record)
+                .await?;
+            items.push(
+                serde_json::from_value(record)
+                    .map_err(|e| SecureDatabaseError::DeserializationError(e.to_string()))?,
+            );
+        }
+
+        Ok(Page::new(items, total_count, page, page_size))
+    }
+
+    async fn query_page(
+        &self,
+        context: &SecurityContext,
+        table: &str,
+        page: u32,
+        page_size: u32,
+        admin_override: bool,
+    ) -> Result<(Vec<serde_json::Value>, u64), SecureDatabaseError> {
+        let pool = self.postgres.pool();
+        let query = OwnedListQuery::new(table, !admin_override);
+        let offset = i64::from(page) * i64::from(page_size);
+
+        let mut count = sqlx::query(&query.count_sql);
+        let mut select = sqlx::query(&query.select_sql);
+        if query.owner_filtered {
+            count = count.bind(context.user_id);
+            select = select.bind(context.user_id);
+        }
+
+        // Count and page from one snapshot so `total_count` matches the rows
+        let db_error = |e: sqlx::Error| SecureDatabaseError::DatabaseOperation(e.to_string());
+        let mut tx = pool.begin().await.map_err(db_error)?;
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ READ ONLY")
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+
+        let total_count: i64 = count
+            .fetch_one(&mut *tx)
+            .await
+            .and_then(|row| row.try_get(0))
+            .map_err(db_error)?;
+
+        let rows = select
+            .bind(i64::from(page_size))
+            .bind(offset)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(db_error)?;
+
+        tx.commit().await.map_err(db_error)?;
+
+        let records = rows
+            .iter()
+            .map(|row| row.try_get::<serde_json::Value, _>("record"))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| SecureDatabaseError::DatabaseOperation(e.to_string()))?;
+
+        Ok((records, total_count.max(0) as u64))
+    }
+
     /// Health check with security context
     pub async fn health_check(
         &self,
@@ -409,8 +539,87 @@ impl SecureRedisRepository {
     }
 }
 
+/// SQL for one page of `list_owned`
+#[derive(Debug, Clone, PartialEq)]
+struct OwnedListQuery {
+    count_sql: String,
+    select_sql: String,
+    owner_filtered: bool,
+}
+
+impl OwnedListQuery {
+    /// Build the count and page queries. `table` must already be validated.
+    /// Owner-filtered queries bind the owner as `$1`; the page query binds
+    /// `LIMIT` and `OFFSET` after it.
+    fn new(table: &str, owner_filtered: bool) -> Self {
+        let quoted = table
+            .split('.')
+            .map(|part| format!("\"{}\"", part))
+            .collect::<Vec<_>>()
+            .join(".");
+
+        let (filter, limit, offset) = if owner_filtered {
+            (" WHERE t.owner_id = $1", "$2", "$3")
+        } else {
+            ("", "$1", "$2")
+        };
+
+        Self {
+            count_sql: format!("SELECT COUNT(*) FROM {} t{}", quoted, filter),
+            select_sql: format!(
+                "SELECT to_jsonb(t) AS record FROM {} t{} ORDER BY t.id LIMIT {} OFFSET {}",
+                quoted, filter, limit, offset
+            ),
+            owner_filtered,
+        }
+    }
+}
+
+/// Table names are identifiers, optionally schema-qualified
+fn is_valid_table_name(table: &str) -> bool {
+    let parts: Vec<&str> = table.split('.').collect();
+    parts.len() <= 2
+        && parts.iter().all(|part| {
+            let mut chars = part.chars();
+            matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+                && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+}
+
 // Data structures for secure operations
 
+/// One page of query results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Total matching rows across all pages
+    pub total_count: u64,
+    /// Zero-based page index
+    pub page: u32,
+    pub page_size: u32,
+    /// Cursor for the next page; `None` on the last page
+    pub next_page: Option<u32>,
+}
+
+impl<T> Page<T> {
+    pub fn new(items: Vec<T>, total_count: u64, page: u32, page_size: u32) -> Self {
+        let seen = (u64::from(page) + 1) * u64::from(page_size);
+        let next_page = if seen < total_count {
+            page.checked_add(1)
+        } else {
+            None
+        };
+
+        Self {
+            items,
+            total_count,
+            page,
+            page_size,
+            next_page,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SecureUserData {
     pub id: uuid::Uuid,
@@ -520,6 +729,55 @@ mod tests {
         SecurityContext::new(user_id, None, permissions, roles)
     }
 
+    #[test]
+    fn test_owned_list_query_filters_by_owner() {
+        let query = OwnedListQuery::new("documents", true);
+
+        assert_eq!(
+            query.count_sql,
+            "SELECT COUNT(*) FROM \"documents\" t WHERE t.owner_id = $1"
+        );
+        assert_eq!(
+            query.select_sql,
+            "SELECT to_jsonb(t) AS record FROM \"documents\" t WHERE t.owner_id = $1 \
+             ORDER BY t.id LIMIT $2 OFFSET $3"
+        );
+    }
+
+    #[test]
+    fn test_owned_list_query_admin_override() {
+        let query = OwnedListQuery::new("app.documents", false);
+
+        assert!(!query.owner_filtered);
+        assert_eq!(
+            query.select_sql,
+            "SELECT to_jsonb(t) AS record FROM \"app\".\"documents\" t ORDER BY t.id LIMIT $1 OFFSET $2"
+        );
+    }
+
+    #[test]
+    fn test_table_name_validation() {
+        assert!(is_valid_table_name("documents"));
+        assert!(is_valid_table_name("app.user_files"));
+        assert!(!is_valid_table_name(""));
+        assert!(!is_valid_table_name("1documents"));
+        assert!(!is_valid_table_name("documents; DROP TABLE users"));
+        assert!(!is_valid_table_name("a.b.c"));
+        assert!(!is_valid_table_name("docs\""));
+    }
+
+    #[test]
+    fn test_page_next_cursor() {
+        let first: Page<u32> = Page::new(vec![1, 2], 5, 0, 2);
+        assert_eq!(first.next_page, Some(1));
+
+        let last: Page<u32> = Page::new(vec![5], 5, 2, 2);
+        assert_eq!(last.next_page, None);
+
+        let exact: Page<u32> = Page::new(vec![3, 4], 4, 1, 2);
+        assert_eq!(exact.next_page, None);
+    }
+
     #[tokio::test]
     async fn test_secure_user_operations() {
         // This test would require proper mocking of dependencies
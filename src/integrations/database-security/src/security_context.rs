@@ -7,7 +7,7 @@
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 /// Security context for database operations
@@ -51,6 +51,9 @@ pub struct SecurityContextMetadata {
     pub is_api_key: bool,
     /// Multi-factor authentication status
     pub mfa_verified: bool,
+    /// Custom attributes available to ABAC policies as `user.<name>`
+    #[serde(default)]
+    pub attributes: HashMap<String, String>,
 }
 
 /// Security level enumeration
@@ -77,6 +80,7 @@ impl Default for SecurityContextMetadata {
             security_level: SecurityLevel::Standard,
             is_api_key: false,
             mfa_verified: false,
+            attributes: HashMap::new(),
         }
     }
 }
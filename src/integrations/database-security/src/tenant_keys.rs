@@ -0,0 +1,105 @@
+//! # Tenant Key Store Module
+//!
+//! This module provides storage for the per-tenant data encryption keys (DEKs)
+//! used by envelope encryption. Keys are only ever stored wrapped by the master
+//! key, so a key store never holds usable key material on its own.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use ai_core_security::encryption::EncryptedData;
+
+use crate::error::SecureDatabaseError;
+
+/// A tenant data encryption key, wrapped by the master key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WrappedDataKey {
+    /// Tenant that owns the key
+    pub tenant_id: Uuid,
+    /// Key version, starting at 1 and incremented on every rotation
+    pub version: u32,
+    /// DEK encrypted with the master key; `key_id` names the master key used
+    pub wrapped_key: EncryptedData,
+    /// Key creation timestamp
+    pub created_at: DateTime<Utc>,
+}
+
+/// Persistent storage for wrapped tenant keys
+#[async_trait]
+pub trait TenantKeyStore: Send + Sync {
+    /// Get the newest key version for a tenant
+    async fn get_current(
+        &self,
+        tenant_id: Uuid,
+    ) -> Result<Option<WrappedDataKey>, SecureDatabaseError>;
+
+    /// Get a specific key version for a tenant
+    async fn get_version(
+        &self,
+        tenant_id: Uuid,
+        version: u32,
+    ) -> Result<Option<WrappedDataKey>, SecureDatabaseError>;
+
+    /// Store a new key version, failing if that version already exists
+    async fn insert(&self, key: WrappedDataKey) -> Result<(), SecureDatabaseError>;
+}
+
+/// In-memory tenant key store
+///
+/// Keys do not survive a restart, so data encrypted with them becomes
+/// unreadable; production deployments should provide a persistent store.
+#[derive(Debug, Default)]
+pub struct InMemoryTenantKeyStore {
+    keys: RwLock<HashMap<Uuid, BTreeMap<u32, WrappedDataKey>>>,
+}
+
+impl InMemoryTenantKeyStore {
+    /// Create an empty key store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl TenantKeyStore for InMemoryTenantKeyStore {
+    async fn get_current(
+        &self,
+        tenant_id: Uuid,
+    ) -> Result<Option<WrappedDataKey>, SecureDatabaseError> {
+        let keys = self.keys.read().await;
+        Ok(keys
+            .get(&tenant_id)
+            .and_then(|versions| versions.values().next_back())
+            .cloned())
+    }
+
+    async fn get_version(
+        &self,
+        tenant_id: Uuid,
+        version: u32,
+    ) -> Result<Option<WrappedDataKey>, SecureDatabaseError> {
+        let keys = self.keys.read().await;
+        Ok(keys
+            .get(&tenant_id)
+            .and_then(|versions| versions.get(&version))
+            .cloned())
+    }
+
+    async fn insert(&self, key: WrappedDataKey) -> Result<(), SecureDatabaseError> {
+        let mut keys = self.keys.write().await;
+        let versions = keys.entry(key.tenant_id).or_default();
+        if versions.contains_key(&key.version) {
+            return Err(SecureDatabaseError::conflict(format!(
+                "Key version {} already exists for tenant {}",
+                key.version, key.tenant_id
+            )));
+        }
+
+        versions.insert(key.version, key);
+        Ok(())
+    }
+}
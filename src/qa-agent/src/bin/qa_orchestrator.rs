@@ -6,7 +6,10 @@
 use anyhow::Result;
 use chrono::Utc;
 use clap::{Arg, ArgAction, Command};
-use qa_agent::{PerformanceTester, QAAgent, QAConfig, SecurityTester, TestOrchestrator};
+use qa_agent::{
+    PerformanceTester, QAAgent, QAConfig, ReportFormat, ReportGenerator, SecurityTester,
+    TestOrchestrator,
+};
 use std::path::PathBuf;
 use tokio;
 use tracing::{error, info, warn};
@@ -228,7 +231,7 @@ async fn main() -> Result<()> {
         generate_reports(&qa_agent, &config, &result).await?;
 
         // Exit with appropriate code
-        std::process::exit(if result.status == qa_agent::testing::TestStatus::Passed {
+        std::process::exit(if result.is_successful(&config.test.retry_policy) {
             0
         } else {
             1
@@ -609,6 +612,7 @@ async fn print_test_summary(result: &qa_agent::orchestrator::TestSuiteResult) ->
     println!("Passed: {}", result.passed_tests);
     println!("Failed: {}", result.failed_tests);
     println!("Skipped: {}", result.skipped_tests);
+    println!("Flaky: {}", result.flaky_tests);
 
     if let Some(coverage) = result.coverage_percentage {
         println!("Coverage: {:.1}%", coverage);
@@ -645,6 +649,15 @@ async fn print_workflow_summary(result: &qa_agent::QAWorkflowResult) -> Result<(
     println!("  Total Tests: {}", result.test_result.total_tests);
     println!("  Passed: {}", result.test_result.passed_tests);
     println!("  Failed: {}", result.test_result.failed_tests);
+    println!("  Flaky: {}", result.test_result.flaky_tests);
+    for flaky in &result.report.flaky_tests {
+        println!(
+            "    - {} / {} ({:.0}% flake rate)",
+            flaky.suite_name,
+            flaky.test_name,
+            flaky.flake_rate * 100.0
+        );
+    }
 
     println!("\nPerformance Results:");
     println!("  Status: {:?}", result.performance_result.status);
@@ -714,6 +727,13 @@ async fn generate_reports(
     std::fs::write(&json_report_path, json_content)?;
     info!("JSON report generated: {}", json_report_path.display());
 
+    // Generate JUnit XML report for CI test views
+    if config.reporting.formats.contains(&ReportFormat::JUnitXml) {
+        let junit_report_path = config.reporting.output_dir.join("junit.xml");
+        std::fs::write(&junit_report_path, qa_agent::reporting::junit_xml(result))?;
+        info!("JUnit report generated: {}", junit_report_path.display());
+    }
+
     Ok(())
 }
 
@@ -750,6 +770,11 @@ async fn generate_workflow_reports(
         json_report_path.display()
     );
 
+    // Generate quality report, JUnit XML and SARIF outputs as configured
+    ReportGenerator::new(config.reporting.clone())
+        .write_reports(result)
+        .await?;
+
     Ok(())
 }
 
@@ -6,7 +6,7 @@
 use anyhow::Result;
 use axum::{
     extract::{Query, State},
-    http::{header, StatusCode},
+    http::{header, HeaderMap, StatusCode},
     response::{Html, IntoResponse, Json},
     routing::{get, post},
     Router,
@@ -14,6 +14,9 @@ use axum::{
 use clap::{Arg, ArgMatches, Command};
 use qa_agent::config::{DashboardConfig, QAConfig};
 use qa_agent::dashboard::{DashboardStatus, QualityDashboard};
+use qa_agent::github_checks::{
+    repository_allowed, trigger_authorized, GitHubChecksClient, PullRequestTrigger,
+};
 use qa_agent::metrics::{MetricsCollector, QualityScore, QualityTrends};
 use serde::{Deserialize, Serialize};
 use serde_json;
@@ -214,6 +217,7 @@ async fn build_router(state: Arc<DashboardAppState>, static_dir: &str) -> Result
             get(get_security_vulnerabilities),
         )
         .route("/api/reports/generate", post(generate_report))
+        .route("/api/qa/pull-requests", post(run_pull_request_qa))
         .route("/api/alerts", get(get_alerts))
         .route("/api/system/status", get(get_system_status))
         // WebSocket for real-time updates
@@ -236,6 +240,90 @@ async fn build_router(state: Arc<DashboardAppState>, static_dir: &str) -> Result
     Ok(app)
 }
 
+// Run the QA workflow for a pull request and report it as a GitHub check run
+async fn run_pull_request_qa(
+    State(state): State<Arc<DashboardAppState>>,
+    headers: HeaderMap,
+    Json(pull_request): Json<PullRequestTrigger>,
+) -> impl IntoResponse {
+    let github_checks = &state.config.github_checks;
+    if !github_checks.enabled || github_checks.trigger_secret.is_none() {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiResponse::<()>::error(
+                "GitHub check run reporting is disabled".to_string(),
+            )),
+        )
+            .into_response();
+    }
+
+    let authorization = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok());
+    if !trigger_authorized(github_checks, authorization) {
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(ApiResponse::<()>::error(
+                "Invalid trigger secret".to_string(),
+            )),
+        )
+            .into_response();
+    }
+
+    if let Err(e) = pull_request.validate() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::<()>::error(e.to_string())),
+        )
+            .into_response();
+    }
+    if !repository_allowed(github_checks, &pull_request) {
+        warn!(
+            "Rejected QA workflow for repository {} not on the allowlist",
+            pull_request.repository()
+        );
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::<()>::error(format!(
+                "Repository {} is not allowed",
+                pull_request.repository()
+            ))),
+        )
+            .into_response();
+    }
+
+    let checks = match GitHubChecksClient::new(state.config.github_checks.clone()) {
+        Ok(checks) => checks,
+        Err(e) => {
+            error!("Failed to create GitHub checks client: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<()>::error(e.to_string())),
+            )
+                .into_response();
+        }
+    };
+
+    info!(
+        "Queued QA workflow for {}/{}#{} at {}",
+        pull_request.owner, pull_request.repo, pull_request.number, pull_request.head_sha
+    );
+
+    // The workflow takes minutes; progress is reported through the check run
+    let config = state.config.clone();
+    let accepted = pull_request.clone();
+    tokio::spawn(async move {
+        if let Err(e) = checks.run_pull_request_qa(&config, &pull_request).await {
+            error!(
+                "QA workflow for {}/{}#{} failed: {}",
+                pull_request.owner, pull_request.repo, pull_request.number, e
+            );
+        }
+    });
+
+    (StatusCode::ACCEPTED, Json(ApiResponse::success(accepted))).into_response()
+}
+
 // Dashboard HTML page
 async fn dashboard_home() -> impl IntoResponse {
     let html = include_str!("../../dashboard/templates/index.html");
@@ -29,6 +29,9 @@ pub struct QAConfig {
     pub reporting: ReportingConfig,
     /// General QA settings
     pub general: GeneralConfig,
+    /// Pull request check run reporting
+    #[serde(default)]
+    pub github_checks: GitHubChecksConfig,
 }
 
 /// Test orchestration configuration
@@ -40,8 +43,9 @@ pub struct TestConfig {
     pub max_workers: usize,
     /// Test timeout in seconds
     pub timeout_seconds: u64,
-    /// Test retry attempts for flaky tests
-    pub retry_attempts: u32,
+    /// Retry policy for detecting flaky tests
+    #[serde(default)]
+    pub retry_policy: RetryPolicy,
     /// Test environment configuration
     pub environment: TestEnvironmentConfig,
     /// Test suites to execute
@@ -54,6 +58,18 @@ pub struct TestConfig {
     pub collect_coverage: bool,
     /// Minimum coverage threshold (percentage)
     pub min_coverage_threshold: f64,
+    /// Directory test commands run in; defaults to the agent's working directory
+    #[serde(default)]
+    pub working_dir: Option<PathBuf>,
+}
+
+/// Retry policy for failed test cases
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Number of times a suite with failing test cases is re-run (0 disables retries)
+    pub max_retries: u32,
+    /// Count flaky tests as failures when determining the overall status
+    pub fail_on_flaky: bool,
 }
 
 /// Test environment configuration
@@ -129,6 +145,9 @@ pub struct PerformanceConfig {
     pub benchmarking: BenchmarkConfig,
     /// Performance monitoring during tests
     pub monitoring: PerformanceMonitoringConfig,
+    /// Regression detection against a stored baseline
+    #[serde(default)]
+    pub regression: RegressionConfig,
 }
 
 /// Performance test scenario
@@ -214,6 +233,19 @@ pub enum BenchmarkOutputFormat {
     Pretty,
 }
 
+/// Performance regression detection configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegressionConfig {
+    /// Compare each run against the stored baseline
+    pub enabled: bool,
+    /// Baseline file written by `promote_baseline`
+    pub baseline_path: PathBuf,
+    /// Maximum allowed P95 latency increase (percentage)
+    pub max_p95_increase_percent: f64,
+    /// Maximum allowed throughput decrease (percentage)
+    pub max_throughput_decrease_percent: f64,
+}
+
 /// Performance monitoring during tests
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PerformanceMonitoringConfig {
@@ -527,6 +559,28 @@ pub struct MonitoringConfig {
     pub health_endpoints: Vec<String>,
 }
 
+/// GitHub Checks API configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubChecksConfig {
+    /// Report pull request QA workflows as check runs
+    pub enabled: bool,
+    /// GitHub API base URL (for GitHub Enterprise)
+    pub api_base_url: String,
+    /// Token with `checks:write` permission
+    pub token: Option<String>,
+    /// Name the check run is listed under on the pull request
+    pub check_name: String,
+    /// Base URL pull requests are fetched from
+    pub git_base_url: String,
+    /// Directory pull request checkouts are created in
+    pub workspace_dir: PathBuf,
+    /// Bearer token required to trigger pull request QA runs; runs are
+    /// rejected while unset
+    pub trigger_secret: Option<String>,
+    /// Repositories (`owner/repo`) pull request QA runs may be triggered for
+    pub allowed_repositories: Vec<String>,
+}
+
 /// Reporting configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReportingConfig {
@@ -543,13 +597,17 @@ pub struct ReportingConfig {
 }
 
 /// Report formats
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ReportFormat {
     Html,
     Pdf,
     Json,
     Xml,
     Markdown,
+    /// JUnit XML test results for CI test views
+    JUnitXml,
+    /// SARIF 2.1.0 security findings for code scanning
+    Sarif,
 }
 
 /// Email notification configuration
@@ -608,6 +666,7 @@ impl Default for QAConfig {
             monitoring: MonitoringConfig::default(),
             reporting: ReportingConfig::default(),
             general: GeneralConfig::default(),
+            github_checks: GitHubChecksConfig::default(),
         }
     }
 }
@@ -618,7 +677,7 @@ impl Default for TestConfig {
             parallel_execution: true,
             max_workers: num_cpus::get(),
             timeout_seconds: 300,
-            retry_attempts: 3,
+            retry_policy: RetryPolicy::default(),
             environment: TestEnvironmentConfig::default(),
             suites: vec![
                 TestSuiteConfig {
@@ -644,6 +703,16 @@ impl Default for TestConfig {
             results_dir: PathBuf::from("target/qa-results"),
             collect_coverage: true,
             min_coverage_threshold: 80.0,
+            working_dir: None,
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            fail_on_flaky: false,
         }
     }
 }
@@ -679,6 +748,7 @@ impl Default for PerformanceConfig {
             load_testing: LoadTestingConfig::default(),
             benchmarking: BenchmarkConfig::default(),
             monitoring: PerformanceMonitoringConfig::default(),
+            regression: RegressionConfig::default(),
         }
     }
 }
@@ -719,6 +789,17 @@ impl Default for BenchmarkConfig {
     }
 }
 
+impl Default for RegressionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            baseline_path: PathBuf::from("target/qa-results/performance-baseline.json"),
+            max_p95_increase_percent: 10.0,
+            max_throughput_decrease_percent: 10.0,
+        }
+    }
+}
+
 impl Default for PerformanceMonitoringConfig {
     fn default() -> Self {
         Self {
@@ -901,6 +982,21 @@ impl Default for MonitoringConfig {
     }
 }
 
+impl Default for GitHubChecksConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            api_base_url: "https://api.github.com".to_string(),
+            token: None,
+            check_name: "AI-CORE QA".to_string(),
+            git_base_url: "https://github.com".to_string(),
+            workspace_dir: std::env::temp_dir().join("qa-pull-requests"),
+            trigger_secret: None,
+            allowed_repositories: vec![],
+        }
+    }
+}
+
 impl Default for ReportingConfig {
     fn default() -> Self {
         Self {
@@ -964,6 +1060,12 @@ impl QAConfig {
         if let Ok(dashboard_port) = std::env::var("QA_DASHBOARD_PORT") {
             config.dashboard.port = dashboard_port.parse()?;
         }
+        if let Ok(github_token) = std::env::var("QA_GITHUB_TOKEN") {
+            config.github_checks.token = Some(github_token);
+        }
+        if let Ok(trigger_secret) = std::env::var("QA_GITHUB_TRIGGER_SECRET") {
+            config.github_checks.trigger_secret = Some(trigger_secret);
+        }
 
         Ok(config)
     }
@@ -991,6 +1093,12 @@ impl QAConfig {
             anyhow::bail!("Error rate threshold must be between 0 and 100");
         }
 
+        if self.performance.regression.max_p95_increase_percent < 0.0
+            || self.performance.regression.max_throughput_decrease_percent < 0.0
+        {
+            anyhow::bail!("Regression thresholds must not be negative");
+        }
+
         Ok(())
     }
 
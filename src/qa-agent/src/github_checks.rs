@@ -0,0 +1,878 @@
+//! # GitHub Checks Module
+//!
+//! Reports QA workflow results on pull requests as GitHub check runs. The check
+//! run is created `in_progress` when a pull request's QA workflow starts and is
+//! completed with the workflow's outcome, a quality score summary, and
+//! annotations for failed tests and security findings.
+//!
+//! The workflow runs against a scratch checkout of the pull request's head
+//! commit, so the result reflects the code under review rather than whatever
+//! the agent's own working directory holds.
+
+use crate::config::{GitHubChecksConfig, QAConfig};
+use crate::metrics::QualityScore;
+use crate::orchestrator::TestSuiteResult;
+use crate::reporting::{leaf_suites, scan_location, vulnerability_status, TestSummary};
+use crate::security::{SecuritySeverity, SecurityTestResult, VulnerabilityStatus};
+use crate::testing::TestStatus;
+use crate::{QAAgent, QAStatus, QAWorkflowResult};
+use anyhow::{bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use tokio::process::Command as AsyncCommand;
+use tracing::{info, warn};
+
+/// GitHub accepts at most this many annotations per check run request
+const MAX_ANNOTATIONS_PER_REQUEST: usize = 50;
+
+/// Pull request commit to run the QA workflow against
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PullRequestTrigger {
+    pub owner: String,
+    pub repo: String,
+    pub number: u64,
+    pub head_sha: String,
+}
+
+impl PullRequestTrigger {
+    /// Reject names and commits that could escape the checkout directory or
+    /// be read as git options
+    pub fn validate(&self) -> Result<()> {
+        for name in [&self.owner, &self.repo] {
+            let valid = !name.is_empty()
+                && !name.starts_with(['.', '-'])
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+            if !valid {
+                bail!("invalid repository name: {:?}", name);
+            }
+        }
+        if self.head_sha.len() != 40 || !self.head_sha.chars().all(|c| c.is_ascii_hexdigit()) {
+            bail!("head_sha must be a full 40 character commit SHA");
+        }
+        Ok(())
+    }
+
+    /// `owner/repo`
+    pub fn repository(&self) -> String {
+        format!("{}/{}", self.owner, self.repo)
+    }
+}
+
+/// Whether QA runs may be triggered for the pull request's repository
+pub fn repository_allowed(config: &GitHubChecksConfig, pull_request: &PullRequestTrigger) -> bool {
+    let repository = pull_request.repository();
+    config
+        .allowed_repositories
+        .iter()
+        .any(|allowed| allowed.eq_ignore_ascii_case(&repository))
+}
+
+/// Whether an `Authorization` header carries the configured trigger secret.
+/// Always false while no secret is configured.
+pub fn trigger_authorized(config: &GitHubChecksConfig, authorization: Option<&str>) -> bool {
+    let (Some(secret), Some(presented)) = (
+        config.trigger_secret.as_deref().filter(|s| !s.is_empty()),
+        authorization.and_then(|value| value.strip_prefix("Bearer ")),
+    ) else {
+        return false;
+    };
+
+    // Compare in constant time for equal lengths
+    secret.len() == presented.len()
+        && secret
+            .bytes()
+            .zip(presented.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+/// Check run annotation level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnotationLevel {
+    Notice,
+    Warning,
+    Failure,
+}
+
+/// Annotation shown inline on the pull request diff
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckAnnotation {
+    pub path: String,
+    pub start_line: u32,
+    pub end_line: u32,
+    pub annotation_level: AnnotationLevel,
+    pub title: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_details: Option<String>,
+}
+
+impl CheckAnnotation {
+    /// Tests and scans carry no line numbers, so annotations mark the file's
+    /// first line
+    fn file_level(
+        path: &str,
+        annotation_level: AnnotationLevel,
+        title: String,
+        message: String,
+        raw_details: Option<String>,
+    ) -> Self {
+        Self {
+            path: path.to_string(),
+            start_line: 1,
+            end_line: 1,
+            annotation_level,
+            title,
+            message,
+            raw_details,
+        }
+    }
+}
+
+/// Client for the GitHub Checks API
+#[derive(Debug, Clone)]
+pub struct GitHubChecksClient {
+    config: GitHubChecksConfig,
+    token: String,
+    http_client: reqwest::Client,
+}
+
+impl GitHubChecksClient {
+    /// Create a client; requires a token with `checks:write` permission
+    pub fn new(config: GitHubChecksConfig) -> Result<Self> {
+        let token = config
+            .token
+            .clone()
+            .context("GitHub checks token not configured")?;
+
+        let http_client = reqwest::Client::builder()
+            .user_agent("ai-core-qa-agent")
+            .timeout(std::time::Duration::from_secs(30))
+            .build()?;
+
+        Ok(Self {
+            config,
+            token,
+            http_client,
+        })
+    }
+
+    /// Run the QA workflow for a pull request against a checkout of its head
+    /// commit, reporting its progress and result as a check run on that commit
+    pub async fn run_pull_request_qa(
+        &self,
+        config: &QAConfig,
+        pull_request: &PullRequestTrigger,
+    ) -> Result<QAWorkflowResult> {
+        pull_request.validate()?;
+        let check_run_id = self.start_check_run(pull_request).await?;
+
+        match self.run_in_checkout(config, pull_request).await {
+            Ok(result) => {
+                self.complete_check_run(pull_request, check_run_id, &result)
+                    .await?;
+                Ok(result)
+            }
+            Err(e) => {
+                // Leave no check run stuck in progress
+                if let Err(update_error) = self
+                    .fail_check_run(pull_request, check_run_id, &e.to_string())
+                    .await
+                {
+                    warn!(
+                        check_run_id,
+                        "Failed to mark check run as failed: {}", update_error
+                    );
+                }
+                Err(e)
+            }
+        }
+    }
+
+    async fn run_in_checkout(
+        &self,
+        config: &QAConfig,
+        pull_request: &PullRequestTrigger,
+    ) -> Result<QAWorkflowResult> {
+        let checkout = self.checkout(pull_request).await?;
+
+        let mut config = config.clone();
+        config.test.working_dir = Some(checkout.clone());
+        let result = match QAAgent::new(config).await {
+            Ok(agent) => agent.run_qa_workflow().await,
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = tokio::fs::remove_dir_all(&checkout).await {
+            warn!("Failed to remove checkout {}: {}", checkout.display(), e);
+        }
+        result
+    }
+
+    /// Fetch the pull request's head commit into a fresh scratch checkout
+    pub async fn checkout(&self, pull_request: &PullRequestTrigger) -> Result<PathBuf> {
+        pull_request.validate()?;
+        let dir = self.config.workspace_dir.join(format!(
+            "{}-{}-{}",
+            pull_request.owner, pull_request.repo, pull_request.head_sha
+        ));
+        if tokio::fs::try_exists(&dir).await? {
+            tokio::fs::remove_dir_all(&dir).await?;
+        }
+        tokio::fs::create_dir_all(&dir).await?;
+
+        let remote = format!(
+            "{}/{}/{}.git",
+            self.config.git_base_url.trim_end_matches('/'),
+            pull_request.owner,
+            pull_request.repo
+        );
+        self.git(&dir, &["init", "--quiet"]).await?;
+        self.git(
+            &dir,
+            &[
+                "fetch",
+                "--quiet",
+                "--depth",
+                "1",
+                &remote,
+                &pull_request.head_sha,
+            ],
+        )
+        .await?;
+        self.git(&dir, &["checkout", "--quiet", "--detach", "FETCH_HEAD"])
+            .await?;
+
+        info!(
+            "Checked out {} at {} into {}",
+            pull_request.repository(),
+            pull_request.head_sha,
+            dir.display()
+        );
+        Ok(dir)
+    }
+
+    async fn git(&self, dir: &Path, args: &[&str]) -> Result<()> {
+        // The token goes through the environment so it never shows up in the
+        // process list or the checkout's git config
+        let credentials = STANDARD.encode(format!("x-access-token:{}", self.token));
+        let output = AsyncCommand::new("git")
+            .current_dir(dir)
+            .args(args)
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .env("GIT_CONFIG_COUNT", "1")
+            .env("GIT_CONFIG_KEY_0", "http.extraHeader")
+            .env(
+                "GIT_CONFIG_VALUE_0",
+                format!("Authorization: Basic {}", credentials),
+            )
+            .output()
+            .await
+            .context("Failed to run git")?;
+
+        if !output.status.success() {
+            bail!(
+                "git {} failed: {}",
+                args.first().unwrap_or(&""),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(())
+    }
+
+    /// Create an `in_progress` check run for the pull request's head commit
+    pub async fn start_check_run(&self, pull_request: &PullRequestTrigger) -> Result<u64> {
+        let response: serde_json::Value = self
+            .http_client
+            .post(self.check_runs_url(pull_request))
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .json(&json!({
+                "name": self.config.check_name,
+                "head_sha": pull_request.head_sha,
+                "status": "in_progress",
+                "started_at": Utc::now(),
+                "output": {
+                    "title": "QA workflow running",
+                    "summary": "Running tests, performance and security checks.",
+                },
+            }))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let check_run_id = response["id"]
+            .as_u64()
+            .context("GitHub did not return a check run id")?;
+
+        info!(
+            check_run_id,
+            "Started check run for {}/{}#{}",
+            pull_request.owner,
+            pull_request.repo,
+            pull_request.number
+        );
+        Ok(check_run_id)
+    }
+
+    /// Complete a check run with the QA workflow's conclusion, summary and
+    /// annotations
+    pub async fn complete_check_run(
+        &self,
+        pull_request: &PullRequestTrigger,
+        check_run_id: u64,
+        result: &QAWorkflowResult,
+    ) -> Result<()> {
+        let title = format!(
+            "QA {}: quality score {:.1}",
+            result.overall_status, result.metrics_result.quality_score.overall_score
+        );
+        let summary = quality_summary(
+            &result.metrics_result.quality_score,
+            &result.report.test_summary,
+            result.report.vulnerability_count,
+        );
+        let annotations = check_run_annotations(&result.test_result, &result.security_result);
+
+        // Extra annotations go in earlier updates; the last one completes the run
+        let batches: Vec<&[CheckAnnotation]> = if annotations.is_empty() {
+            vec![&[]]
+        } else {
+            annotations.chunks(MAX_ANNOTATIONS_PER_REQUEST).collect()
+        };
+        let last = batches.len() - 1;
+
+        for (index, batch) in batches.into_iter().enumerate() {
+            let mut body = json!({
+                "output": {
+                    "title": title,
+                    "summary": summary,
+                    "annotations": batch,
+                },
+            });
+            if index == last {
+                body["status"] = json!("completed");
+                body["conclusion"] = json!(check_run_conclusion(&result.overall_status));
+                body["completed_at"] = json!(Utc::now());
+            }
+            self.update_check_run(pull_request, check_run_id, &body)
+                .await?;
+        }
+
+        info!(
+            check_run_id,
+            annotations = annotations.len(),
+            "Completed check run: {}",
+            result.overall_status
+        );
+        Ok(())
+    }
+
+    /// Complete a check run as failed when the QA workflow could not run
+    pub async fn fail_check_run(
+        &self,
+        pull_request: &PullRequestTrigger,
+        check_run_id: u64,
+        error: &str,
+    ) -> Result<()> {
+        let body = json!({
+            "status": "completed",
+            "conclusion": "failure",
+            "completed_at": Utc::now(),
+            "output": {
+                "title": "QA workflow failed to run",
+                "summary": format!("The QA workflow stopped with an error:\n\n```\n{}\n```", error),
+            },
+        });
+        self.update_check_run(pull_request, check_run_id, &body)
+            .await
+    }
+
+    async fn update_check_run(
+        &self,
+        pull_request: &PullRequestTrigger,
+        check_run_id: u64,
+        body: &serde_json::Value,
+    ) -> Result<()> {
+        self.http_client
+            .patch(format!(
+                "{}/{}",
+                self.check_runs_url(pull_request),
+                check_run_id
+            ))
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .json(body)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    fn check_runs_url(&self, pull_request: &PullRequestTrigger) -> String {
+        format!(
+            "{}/repos/{}/{}/check-runs",
+            self.config.api_base_url.trim_end_matches('/'),
+            pull_request.owner,
+            pull_request.repo
+        )
+    }
+}
+
+/// Check run conclusion for a QA workflow outcome
+pub fn check_run_conclusion(status: &QAStatus) -> &'static str {
+    match status {
+        QAStatus::Passed => "success",
+        _ => "failure",
+    }
+}
+
+/// Markdown summary of the quality score and test and security totals
+pub fn quality_summary(
+    score: &QualityScore,
+    tests: &TestSummary,
+    vulnerability_count: u32,
+) -> String {
+    let components = &score.component_scores;
+    let mut summary = format!(
+        "## Quality score: {:.1} (grade {:?})\n\n\
+         | Component | Score |\n\
+         | --- | --- |\n\
+         | Tests | {:.1} |\n\
+         | Performance | {:.1} |\n\
+         | Security | {:.1} |\n\
+         | Code quality | {:.1} |\n\
+         | Documentation | {:.1} |\n\n",
+        score.overall_score,
+        score.grade,
+        components.test_score,
+        components.performance_score,
+        components.security_score,
+        components.code_quality_score,
+        components.documentation_score,
+    );
+
+    summary.push_str(&format!(
+        "**Tests:** {} passed, {} failed, {} skipped",
+        tests.passed_tests, tests.failed_tests, tests.skipped_tests
+    ));
+    if tests.flaky_tests > 0 {
+        summary.push_str(&format!(", {} flaky", tests.flaky_tests));
+    }
+    if let Some(coverage) = tests.coverage_percentage {
+        summary.push_str(&format!(" ({:.1}% coverage)", coverage));
+    }
+    summary.push_str(&format!(
+        "\n\n**Security:** {} vulnerabilities\n",
+        vulnerability_count
+    ));
+
+    summary
+}
+
+/// Annotations for failed tests and unresolved security findings
+pub fn check_run_annotations(
+    test_result: &TestSuiteResult,
+    security_result: &SecurityTestResult,
+) -> Vec<CheckAnnotation> {
+    let mut annotations = Vec::new();
+
+    for suite in leaf_suites(test_result) {
+        let path = suite
+            .metadata
+            .get("location")
+            .map(String::as_str)
+            .unwrap_or("Cargo.toml");
+
+        for case in &suite.test_cases {
+            if !matches!(
+                case.status,
+                TestStatus::Failed | TestStatus::Timeout | TestStatus::Error
+            ) {
+                continue;
+            }
+
+            annotations.push(CheckAnnotation::file_level(
+                path,
+                AnnotationLevel::Failure,
+                format!("{} › {} ({:?})", suite.suite_name, case.name, case.status),
+                case.error_message
+                    .clone()
+                    .unwrap_or_else(|| format!("Test {:?}", case.status)),
+                case.output.clone(),
+            ));
+        }
+    }
+
+    for scan in &security_result.scans {
+        for finding in &scan.findings {
+            let status = vulnerability_status(security_result, scan, finding);
+            let Some(level) = finding_annotation_level(&finding.severity, status) else {
+                continue;
+            };
+
+            let mut title = match &finding.cve_id {
+                Some(cve_id) => format!("{}: {}", cve_id, finding.title),
+                None => finding.title.clone(),
+            };
+            if let Some(status) = status {
+                if !matches!(status, VulnerabilityStatus::Open) {
+                    title.push_str(&format!(" ({:?})", status));
+                }
+            }
+
+            annotations.push(CheckAnnotation::file_level(
+                scan_location(scan),
+                level,
+                title,
+                finding.description.clone(),
+                finding.remediation.clone(),
+            ));
+        }
+    }
+
+    annotations
+}
+
+/// Annotation level for a security finding. Open findings are graded by
+/// severity, triaged ones are downgraded, and resolved ones are not annotated.
+fn finding_annotation_level(
+    severity: &SecuritySeverity,
+    status: Option<&VulnerabilityStatus>,
+) -> Option<AnnotationLevel> {
+    match status {
+        Some(VulnerabilityStatus::Resolved) => None,
+        Some(VulnerabilityStatus::Accepted | VulnerabilityStatus::FalsePositive) => {
+            Some(AnnotationLevel::Notice)
+        }
+        Some(VulnerabilityStatus::InProgress) => Some(AnnotationLevel::Warning),
+        Some(VulnerabilityStatus::Open) | None => Some(match severity {
+            SecuritySeverity::Critical | SecuritySeverity::High => AnnotationLevel::Failure,
+            SecuritySeverity::Medium => AnnotationLevel::Warning,
+            SecuritySeverity::Low | SecuritySeverity::Info => AnnotationLevel::Notice,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TestSuiteType;
+    use crate::metrics::{ComponentScores, QualityGrade};
+    use crate::orchestrator::TestCaseResult;
+    use crate::security::{
+        ComplianceStatus, SecurityCategory, SecurityFinding, SecurityScan, SecurityScanType,
+        SecurityStatus, SecurityVulnerability,
+    };
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn test_case(name: &str, status: TestStatus, error_message: Option<&str>) -> TestCaseResult {
+        TestCaseResult {
+            name: name.to_string(),
+            status,
+            duration: 10,
+            error_message: error_message.map(str::to_string),
+            assertions: 1,
+            output: None,
+            attempts: Vec::new(),
+        }
+    }
+
+    fn finding(severity: SecuritySeverity, title: &str, cve_id: Option<&str>) -> SecurityFinding {
+        SecurityFinding {
+            id: Uuid::nil(),
+            severity,
+            title: title.to_string(),
+            description: format!("{} description", title),
+            category: SecurityCategory::DependencyVulnerability,
+            cve_id: cve_id.map(str::to_string),
+            remediation: None,
+        }
+    }
+
+    fn security_result(
+        findings: Vec<(SecurityFinding, VulnerabilityStatus)>,
+    ) -> SecurityTestResult {
+        let scan_id = Uuid::from_u128(1);
+        let vulnerabilities = findings
+            .iter()
+            .map(|(finding, status)| SecurityVulnerability {
+                id: Uuid::new_v4(),
+                title: finding.title.clone(),
+                severity: finding.severity.clone(),
+                description: finding.description.clone(),
+                source_scan: scan_id,
+                cve_id: finding.cve_id.clone(),
+                remediation: None,
+                status: status.clone(),
+            })
+            .collect();
+
+        SecurityTestResult {
+            test_id: Uuid::new_v4(),
+            start_time: Utc::now(),
+            end_time: Utc::now(),
+            duration: 0,
+            status: SecurityStatus::VulnerabilityFound,
+            scans: vec![SecurityScan {
+                scan_id,
+                name: "Dependency Vulnerability Scan".to_string(),
+                scan_type: SecurityScanType::DependencyCheck,
+                status: SecurityStatus::VulnerabilityFound,
+                start_time: Utc::now(),
+                end_time: Utc::now(),
+                duration: 0,
+                findings: findings.into_iter().map(|(finding, _)| finding).collect(),
+                metadata: HashMap::new(),
+            }],
+            vulnerabilities,
+            compliance_status: ComplianceStatus {
+                overall_status: SecurityStatus::Passed,
+                compliance_percentage: 100.0,
+                frameworks_checked: vec![],
+                violations: vec![],
+            },
+        }
+    }
+
+    #[test]
+    fn test_annotations_cover_failed_tests_and_unresolved_findings() {
+        let mut unit =
+            TestSuiteResult::failed_suite("unit".to_string(), TestSuiteType::Unit, String::new());
+        unit.metadata
+            .insert("location".to_string(), "src/lib.rs".to_string());
+        unit.test_cases = vec![
+            test_case("parses_config", TestStatus::Passed, None),
+            test_case(
+                "rejects_empty",
+                TestStatus::Failed,
+                Some("assertion failed"),
+            ),
+            test_case("slow_path", TestStatus::Timeout, None),
+        ];
+        let mut all = TestSuiteResult::failed_suite(
+            "All Test Suites".to_string(),
+            TestSuiteType::Unit,
+            String::new(),
+        );
+        all.suite_results = Some(vec![unit]);
+
+        let security = security_result(vec![
+            (
+                finding(
+                    SecuritySeverity::Critical,
+                    "Vulnerable TLS",
+                    Some("CVE-2024-0001"),
+                ),
+                VulnerabilityStatus::Open,
+            ),
+            (
+                finding(SecuritySeverity::Critical, "Accepted risk", None),
+                VulnerabilityStatus::Accepted,
+            ),
+            (
+                finding(SecuritySeverity::Medium, "Being patched", None),
+                VulnerabilityStatus::InProgress,
+            ),
+            (
+                finding(SecuritySeverity::High, "Already fixed", None),
+                VulnerabilityStatus::Resolved,
+            ),
+        ]);
+
+        let annotations = check_run_annotations(&all, &security);
+        let summary: Vec<_> = annotations
+            .iter()
+            .map(|a| (a.path.as_str(), a.annotation_level, a.title.as_str()))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "src/lib.rs",
+                    AnnotationLevel::Failure,
+                    "unit › rejects_empty (Failed)"
+                ),
+                (
+                    "src/lib.rs",
+                    AnnotationLevel::Failure,
+                    "unit › slow_path (Timeout)"
+                ),
+                (
+                    "Cargo.lock",
+                    AnnotationLevel::Failure,
+                    "CVE-2024-0001: Vulnerable TLS"
+                ),
+                (
+                    "Cargo.lock",
+                    AnnotationLevel::Notice,
+                    "Accepted risk (Accepted)"
+                ),
+                (
+                    "Cargo.lock",
+                    AnnotationLevel::Warning,
+                    "Being patched (InProgress)"
+                ),
+            ]
+        );
+        assert_eq!(annotations[0].message, "assertion failed");
+
+        let json = serde_json::to_value(&annotations[2]).unwrap();
+        assert_eq!(json["annotation_level"], "failure");
+        assert_eq!(json["start_line"], 1);
+        assert!(json.get("raw_details").is_none());
+    }
+
+    #[test]
+    fn test_untracked_findings_are_graded_by_severity() {
+        assert_eq!(
+            finding_annotation_level(&SecuritySeverity::High, None),
+            Some(AnnotationLevel::Failure)
+        );
+        assert_eq!(
+            finding_annotation_level(&SecuritySeverity::Medium, None),
+            Some(AnnotationLevel::Warning)
+        );
+        assert_eq!(
+            finding_annotation_level(&SecuritySeverity::Info, None),
+            Some(AnnotationLevel::Notice)
+        );
+        assert_eq!(
+            finding_annotation_level(
+                &SecuritySeverity::Critical,
+                Some(&VulnerabilityStatus::FalsePositive)
+            ),
+            Some(AnnotationLevel::Notice)
+        );
+    }
+
+    #[test]
+    fn test_conclusion_and_summary() {
+        assert_eq!(check_run_conclusion(&QAStatus::Passed), "success");
+        assert_eq!(check_run_conclusion(&QAStatus::SecurityFailed), "failure");
+
+        let score = QualityScore {
+            overall_score: 87.5,
+            grade: QualityGrade::B,
+            component_scores: ComponentScores {
+                test_score: 90.0,
+                performance_score: 85.0,
+                security_score: 80.0,
+                code_quality_score: 92.5,
+                documentation_score: 70.0,
+            },
+            score_breakdown: Vec::new(),
+        };
+        let tests = TestSummary {
+            total_tests: 12,
+            passed_tests: 10,
+            failed_tests: 1,
+            skipped_tests: 1,
+            flaky_tests: 2,
+            coverage_percentage: Some(81.5),
+        };
+
+        let summary = quality_summary(&score, &tests, 3);
+        assert!(summary.starts_with("## Quality score: 87.5 (grade B)"));
+        assert!(summary.contains("| Code quality | 92.5 |"));
+        assert!(
+            summary.contains("**Tests:** 10 passed, 1 failed, 1 skipped, 2 flaky (81.5% coverage)")
+        );
+        assert!(summary.contains("**Security:** 3 vulnerabilities"));
+    }
+
+    fn trigger(owner: &str, repo: &str, head_sha: &str) -> PullRequestTrigger {
+        PullRequestTrigger {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            number: 7,
+            head_sha: head_sha.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_trigger_requires_secret_and_allowed_repository() {
+        let sha = "0123456789abcdef0123456789abcdef01234567";
+        let mut config = GitHubChecksConfig::default();
+        assert!(!trigger_authorized(&config, Some("Bearer ")));
+
+        config.trigger_secret = Some("s3cret".to_string());
+        config.allowed_repositories = vec!["netadx1ai/ai-core".to_string()];
+        assert!(trigger_authorized(&config, Some("Bearer s3cret")));
+        assert!(!trigger_authorized(&config, Some("Bearer s3cre")));
+        assert!(!trigger_authorized(&config, Some("s3cret")));
+        assert!(!trigger_authorized(&config, None));
+
+        assert!(repository_allowed(
+            &config,
+            &trigger("netadx1ai", "ai-core", sha)
+        ));
+        assert!(!repository_allowed(
+            &config,
+            &trigger("someone", "ai-core", sha)
+        ));
+
+        assert!(trigger("netadx1ai", "ai-core", sha).validate().is_ok());
+        assert!(trigger("..", "ai-core", sha).validate().is_err());
+        assert!(trigger("netadx1ai", "ai-core/../x", sha)
+            .validate()
+            .is_err());
+        assert!(trigger("netadx1ai", "ai-core", "--upload-pack=x")
+            .validate()
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_checkout_fetches_head_sha() {
+        fn git(dir: &Path, args: &[&str]) -> String {
+            let output = std::process::Command::new("git")
+                .current_dir(dir)
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {:?} failed", args);
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        }
+
+        let remotes = tempfile::tempdir().unwrap();
+        let upstream = remotes.path().join("netadx1ai").join("ai-core.git");
+        std::fs::create_dir_all(&upstream).unwrap();
+        git(&upstream, &["init", "--quiet"]);
+        git(&upstream, &["config", "user.email", "qa@example.com"]);
+        git(&upstream, &["config", "user.name", "QA"]);
+        std::fs::write(upstream.join("README.md"), "head\n").unwrap();
+        git(&upstream, &["add", "README.md"]);
+        git(&upstream, &["commit", "--quiet", "-m", "head"]);
+        let head_sha = git(&upstream, &["rev-parse", "HEAD"]);
+
+        let workspace = tempfile::tempdir().unwrap();
+        let client = GitHubChecksClient::new(GitHubChecksConfig {
+            token: Some("token".to_string()),
+            git_base_url: format!("file://{}", remotes.path().display()),
+            workspace_dir: workspace.path().to_path_buf(),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let checkout = client
+            .checkout(&trigger("netadx1ai", "ai-core", &head_sha))
+            .await
+            .unwrap();
+        assert!(checkout.starts_with(workspace.path()));
+        assert_eq!(git(&checkout, &["rev-parse", "HEAD"]), head_sha);
+        assert_eq!(
+            std::fs::read_to_string(checkout.join("README.md")).unwrap(),
+            "head\n"
+        );
+    }
+}
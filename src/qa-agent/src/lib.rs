@@ -39,6 +39,7 @@
 
 pub mod config;
 pub mod dashboard;
+pub mod github_checks;
 pub mod metrics;
 pub mod orchestrator;
 pub mod performance;
@@ -48,8 +49,9 @@ pub mod testing;
 pub mod utils;
 
 // Re-export key types and traits
-pub use config::{PerformanceConfig, QAConfig, SecurityConfig, TestConfig};
+pub use config::{PerformanceConfig, QAConfig, RetryPolicy, SecurityConfig, TestConfig};
 pub use dashboard::{DashboardService, QualityDashboard};
+pub use github_checks::{GitHubChecksClient, PullRequestTrigger};
 pub use metrics::{MetricsCollector, QualityMetricsResult, QualityScore};
 pub use orchestrator::{TestOrchestrator, TestSuite, TestSuiteResult};
 pub use performance::{PerformanceBenchmark, PerformanceTester};
@@ -213,7 +215,8 @@ impl QAAgent {
         performance_result: &performance::PerformanceTestResult,
         security_result: &security::SecurityTestResult,
     ) -> QAStatus {
-        let test_passed = test_result.status == testing::TestStatus::Passed;
+        // Flaky tests only fail the run when the retry policy says so
+        let test_passed = test_result.is_successful(&self.config.test.retry_policy);
         let performance_passed =
             performance_result.status == performance::PerformanceStatus::Passed;
         let security_passed = security_result.status == security::SecurityStatus::Passed;
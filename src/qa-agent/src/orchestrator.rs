@@ -3,7 +3,7 @@
 //! Coordinates and manages execution of all test suites across the AI-CORE platform.
 //! Provides centralized test execution, result aggregation, and reporting.
 
-use crate::config::{TestConfig, TestSuiteConfig, TestSuiteType};
+use crate::config::{RetryPolicy, TestConfig, TestSuiteConfig, TestSuiteType};
 use crate::testing::{TestCase, TestRunner, TestStatus};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
@@ -92,6 +92,9 @@ impl TestOrchestrator {
                     overall_status = TestStatus::Failed;
                     break;
                 }
+                TestStatus::Flaky => {
+                    overall_status = TestStatus::Flaky;
+                }
                 TestStatus::Skipped => {
                     if overall_status == TestStatus::Passed {
                         overall_status = TestStatus::Skipped;
@@ -109,6 +112,7 @@ impl TestOrchestrator {
         let passed_tests = suite_results.iter().map(|r| r.passed_tests).sum();
         let failed_tests = suite_results.iter().map(|r| r.failed_tests).sum();
         let skipped_tests = suite_results.iter().map(|r| r.skipped_tests).sum();
+        let flaky_tests = suite_results.iter().map(|r| r.flaky_tests).sum();
 
         let coverage_percentage = self.calculate_overall_coverage(&suite_results).await?;
 
@@ -124,6 +128,7 @@ impl TestOrchestrator {
             passed_tests,
             failed_tests,
             skipped_tests,
+            flaky_tests,
             suite_results: Some(suite_results),
             test_cases: vec![], // Individual test cases are in suite_results
             coverage_percentage: Some(coverage_percentage),
@@ -143,6 +148,7 @@ impl TestOrchestrator {
             total_tests = total_tests,
             passed_tests = passed_tests,
             failed_tests = failed_tests,
+            flaky_tests = flaky_tests,
             status = ?overall_status,
             "Test execution completed"
         );
@@ -167,7 +173,7 @@ impl TestOrchestrator {
 
                 let handle = tokio::spawn(async move {
                     let _permit = semaphore.acquire().await.unwrap();
-                    runner.run_test_suite(&suite_config, &context).await
+                    run_with_retries(&runner, &suite_config, &context).await
                 });
 
                 handles.push(handle);
@@ -208,7 +214,7 @@ impl TestOrchestrator {
             if let Some(runner) = self.test_runners.get(&suite_config.suite_type) {
                 info!("Running test suite: {}", suite_config.name);
 
-                match runner.run_test_suite(suite_config, context).await {
+                match run_with_retries(runner, suite_config, context).await {
                     Ok(result) => {
                         debug!(
                             suite = %suite_config.name,
@@ -264,9 +270,7 @@ impl TestOrchestrator {
         };
 
         info!("Running specific test suite: {}", suite_name);
-        let result = runner
-            .run_test_suite(suite_config, &execution_context)
-            .await?;
+        let result = run_with_retries(runner, suite_config, &execution_context).await?;
 
         // Store result
         {
@@ -337,8 +341,12 @@ impl TestOrchestrator {
             self.config.timeout_seconds.to_string(),
         );
         metadata.insert(
-            "retry_attempts".to_string(),
-            self.config.retry_attempts.to_string(),
+            "max_retries".to_string(),
+            self.config.retry_policy.max_retries.to_string(),
+        );
+        metadata.insert(
+            "fail_on_flaky".to_string(),
+            self.config.retry_policy.fail_on_flaky.to_string(),
         );
         metadata
     }
@@ -419,6 +427,50 @@ impl TestOrchestrator {
     }
 }
 
+/// Run a test suite, re-running it while test cases keep failing so that
+/// tests which pass on a later attempt are reported as flaky
+async fn run_with_retries(
+    runner: &TestRunner,
+    suite_config: &TestSuiteConfig,
+    context: &TestExecutionContext,
+) -> Result<TestSuiteResult> {
+    let mut result = runner.run_test_suite(suite_config, context).await?;
+    result.record_initial_attempts();
+
+    let max_retries = context.config.retry_policy.max_retries;
+    let mut retry = 0;
+    while retry < max_retries && result.has_failed_tests() {
+        retry += 1;
+        debug!(
+            suite = %suite_config.name,
+            retry = retry,
+            failed_tests = result.failed_tests,
+            "Re-running suite with failed test cases"
+        );
+
+        match runner.run_test_suite(suite_config, context).await {
+            Ok(rerun) => result.record_retry(&rerun),
+            Err(e) => {
+                warn!(
+                    "Retry {} of test suite '{}' failed: {}",
+                    retry, suite_config.name, e
+                );
+                break;
+            }
+        }
+    }
+
+    if result.flaky_tests > 0 {
+        warn!(
+            suite = %suite_config.name,
+            flaky_tests = result.flaky_tests,
+            "Flaky tests detected"
+        );
+    }
+
+    Ok(result)
+}
+
 /// Test execution context
 #[derive(Debug, Clone)]
 pub struct TestExecutionContext {
@@ -441,6 +493,8 @@ pub struct TestSuiteResult {
     pub passed_tests: u32,
     pub failed_tests: u32,
     pub skipped_tests: u32,
+    #[serde(default)]
+    pub flaky_tests: u32,
     pub suite_results: Option<Vec<TestSuiteResult>>, // For composite results
     pub test_cases: Vec<TestCaseResult>,
     pub coverage_percentage: Option<f64>,
@@ -464,6 +518,7 @@ impl TestSuiteResult {
             passed_tests: 0,
             failed_tests: 1,
             skipped_tests: 0,
+            flaky_tests: 0,
             suite_results: None,
             test_cases: vec![TestCaseResult {
                 name: "Suite Execution".to_string(),
@@ -472,12 +527,65 @@ impl TestSuiteResult {
                 error_message: Some(error_message),
                 assertions: 0,
                 output: None,
+                attempts: Vec::new(),
             }],
             coverage_percentage: None,
             artifacts: TestArtifacts::default(),
             metadata: HashMap::new(),
         }
     }
+
+    /// Whether the suite counts as passing under the given retry policy
+    pub fn is_successful(&self, retry_policy: &RetryPolicy) -> bool {
+        match self.status {
+            TestStatus::Passed => true,
+            TestStatus::Flaky => !retry_policy.fail_on_flaky,
+            _ => false,
+        }
+    }
+
+    /// Whether any test case is still failing
+    pub fn has_failed_tests(&self) -> bool {
+        self.test_cases
+            .iter()
+            .any(|case| case.status == TestStatus::Failed)
+    }
+
+    /// Start each test case's attempt history with its first result
+    pub fn record_initial_attempts(&mut self) {
+        for case in &mut self.test_cases {
+            if case.attempts.is_empty() {
+                case.attempts.push(case.status.clone());
+            }
+        }
+    }
+
+    /// Merge a re-run of this suite: failing test cases record the new
+    /// attempt, and those that now pass are marked flaky
+    pub fn record_retry(&mut self, rerun: &TestSuiteResult) {
+        let mut newly_flaky = 0;
+        for case in self
+            .test_cases
+            .iter_mut()
+            .filter(|case| case.status == TestStatus::Failed)
+        {
+            let Some(retried) = rerun.test_cases.iter().find(|r| r.name == case.name) else {
+                continue;
+            };
+
+            case.attempts.push(retried.status.clone());
+            if retried.status == TestStatus::Passed {
+                case.status = TestStatus::Flaky;
+                newly_flaky += 1;
+            }
+        }
+
+        self.failed_tests = self.failed_tests.saturating_sub(newly_flaky);
+        self.flaky_tests += newly_flaky;
+        if self.status == TestStatus::Failed && newly_flaky > 0 && !self.has_failed_tests() {
+            self.status = TestStatus::Flaky;
+        }
+    }
 }
 
 /// Individual test case result
@@ -489,6 +597,25 @@ pub struct TestCaseResult {
     pub error_message: Option<String>,
     pub assertions: u32,
     pub output: Option<String>,
+    /// Status of every attempt, in order
+    #[serde(default)]
+    pub attempts: Vec<TestStatus>,
+}
+
+impl TestCaseResult {
+    /// Fraction of attempts that failed
+    pub fn flake_rate(&self) -> f64 {
+        if self.attempts.is_empty() {
+            return 0.0;
+        }
+
+        let failures = self
+            .attempts
+            .iter()
+            .filter(|status| matches!(status, TestStatus::Failed))
+            .count();
+        failures as f64 / self.attempts.len() as f64
+    }
 }
 
 /// Test artifacts (logs, screenshots, etc.)
@@ -579,4 +706,82 @@ mod tests {
         assert!(artifacts.screenshots.is_empty());
         assert!(artifacts.coverage_reports.is_empty());
     }
+
+    fn suite_with_cases(cases: &[(&str, TestStatus)]) -> TestSuiteResult {
+        let mut result = TestSuiteResult::failed_suite(
+            "Test Suite".to_string(),
+            TestSuiteType::Unit,
+            "Test error".to_string(),
+        );
+        result.test_cases = cases
+            .iter()
+            .map(|(name, status)| TestCaseResult {
+                name: name.to_string(),
+                status: status.clone(),
+                duration: 10,
+                error_message: None,
+                assertions: 1,
+                output: None,
+                attempts: Vec::new(),
+            })
+            .collect();
+        result.failed_tests = cases
+            .iter()
+            .filter(|(_, status)| *status == TestStatus::Failed)
+            .count() as u32;
+        result
+    }
+
+    #[test]
+    fn test_retry_marks_passing_cases_flaky() {
+        let mut result = suite_with_cases(&[
+            ("stable", TestStatus::Passed),
+            ("flaky", TestStatus::Failed),
+            ("broken", TestStatus::Failed),
+        ]);
+        result.record_initial_attempts();
+
+        let rerun = suite_with_cases(&[
+            ("stable", TestStatus::Passed),
+            ("flaky", TestStatus::Passed),
+            ("broken", TestStatus::Failed),
+        ]);
+        result.record_retry(&rerun);
+
+        assert_eq!(result.status, TestStatus::Failed);
+        assert_eq!(result.failed_tests, 1);
+        assert_eq!(result.flaky_tests, 1);
+        assert_eq!(result.test_cases[0].attempts, vec![TestStatus::Passed]);
+        assert_eq!(result.test_cases[1].status, TestStatus::Flaky);
+        assert_eq!(
+            result.test_cases[1].attempts,
+            vec![TestStatus::Failed, TestStatus::Passed]
+        );
+        assert_eq!(
+            result.test_cases[2].attempts,
+            vec![TestStatus::Failed, TestStatus::Failed]
+        );
+
+        // A second retry only re-records the case that is still failing
+        result.record_retry(&rerun);
+        assert_eq!(result.test_cases[1].attempts.len(), 2);
+        assert_eq!(result.test_cases[2].attempts.len(), 3);
+    }
+
+    #[test]
+    fn test_suite_becomes_flaky_when_all_failures_pass_on_retry() {
+        let mut result = suite_with_cases(&[("flaky", TestStatus::Failed)]);
+        result.record_initial_attempts();
+        result.record_retry(&suite_with_cases(&[("flaky", TestStatus::Failed)]));
+        result.record_retry(&suite_with_cases(&[("flaky", TestStatus::Passed)]));
+
+        assert_eq!(result.status, TestStatus::Flaky);
+        assert_eq!(result.failed_tests, 0);
+        assert!((result.test_cases[0].flake_rate() - 2.0 / 3.0).abs() < f64::EPSILON);
+
+        let mut policy = RetryPolicy::default();
+        assert!(result.is_successful(&policy));
+        policy.fail_on_flaky = true;
+        assert!(!result.is_successful(&policy));
+    }
 }
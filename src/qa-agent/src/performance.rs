@@ -3,11 +3,14 @@
 //! Comprehensive performance testing framework for the AI-CORE platform.
 //! Provides load testing, benchmark execution, SLA validation, and performance monitoring.
 
-use crate::config::{BenchmarkConfig, LoadTestingConfig, PerformanceConfig, SLAThresholds};
+use crate::config::{
+    BenchmarkConfig, LoadTestingConfig, PerformanceConfig, RegressionConfig, SLAThresholds,
+};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, Semaphore};
@@ -78,11 +81,26 @@ impl PerformanceTester {
         // Validate SLA compliance
         let sla_result = self.validate_sla_compliance(&scenarios).await?;
 
+        // Compare against the promoted baseline
+        let regressions = if self.config.regression.enabled {
+            match self.load_baseline().await? {
+                Some(baseline) => baseline.detect_regressions(&scenarios, &self.config.regression),
+                None => {
+                    debug!("No performance baseline found, skipping regression detection");
+                    Vec::new()
+                }
+            }
+        } else {
+            Vec::new()
+        };
+
         let end_time = Utc::now();
         let duration = end_time - start_time;
 
         // Determine overall status
-        let overall_status = if sla_result.violations.is_empty() {
+        let overall_status = if !regressions.is_empty() {
+            PerformanceStatus::Failed
+        } else if sla_result.violations.is_empty() {
             PerformanceStatus::Passed
         } else {
             PerformanceStatus::SlaViolation
@@ -100,6 +118,7 @@ impl PerformanceTester {
             recommendations: self
                 .generate_recommendations(&scenarios, &sla_result)
                 .await?,
+            regressions,
         };
 
         // Store result
@@ -114,12 +133,43 @@ impl PerformanceTester {
             status = ?overall_status,
             scenarios = scenarios.len(),
             sla_violations = sla_result.violations.len(),
+            regressions = result.regressions.len(),
             "Performance test suite completed"
         );
 
         Ok(result)
     }
 
+    /// Load the promoted performance baseline, if one exists
+    pub async fn load_baseline(&self) -> Result<Option<PerformanceBaseline>> {
+        PerformanceBaseline::load(&self.config.regression.baseline_path).await
+    }
+
+    /// Make a known-good run the reference for future regression checks
+    pub async fn promote_baseline(
+        &self,
+        result: &PerformanceTestResult,
+    ) -> Result<PerformanceBaseline> {
+        if result.status != PerformanceStatus::Passed {
+            anyhow::bail!(
+                "Only passing performance runs can become the baseline (status: {:?})",
+                result.status
+            );
+        }
+
+        let baseline = PerformanceBaseline::from_result(result);
+        baseline.save(&self.config.regression.baseline_path).await?;
+
+        info!(
+            test_id = %result.test_id,
+            benchmarks = baseline.benchmarks.len(),
+            path = %self.config.regression.baseline_path.display(),
+            "Promoted performance baseline"
+        );
+
+        Ok(baseline)
+    }
+
     /// Run API performance tests
     async fn run_api_performance_tests(&self) -> Result<PerformanceScenario> {
         info!("Running API performance tests");
@@ -1299,6 +1349,7 @@ steps:
                 gc_collections: 0,
             },
             recommendations: vec![],
+            regressions: vec![],
         };
 
         // Store result
@@ -1413,6 +1464,8 @@ pub struct PerformanceTestResult {
     pub sla_validation: SLAValidationResult,
     pub system_metrics: SystemMetrics,
     pub recommendations: Vec<PerformanceRecommendation>,
+    #[serde(default)]
+    pub regressions: Vec<RegressionDetail>,
 }
 
 /// Performance test scenario
@@ -1542,6 +1595,143 @@ pub enum RecommendationPriority {
     Critical,
 }
 
+/// Reference metrics from a known-good performance run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerformanceBaseline {
+    pub source_test_id: Uuid,
+    pub promoted_at: DateTime<Utc>,
+    /// Metrics keyed by `scenario/test case`
+    pub benchmarks: HashMap<String, BaselineMetrics>,
+}
+
+/// Baseline metrics for a single benchmark
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaselineMetrics {
+    pub p95_response_time_ms: u64,
+    pub requests_per_second: f64,
+}
+
+/// A benchmark metric that degraded beyond its allowed threshold
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegressionDetail {
+    pub benchmark: String,
+    pub metric: RegressionMetric,
+    pub baseline_value: f64,
+    pub current_value: f64,
+    /// How much worse the current run is, as a percentage of the baseline
+    pub degradation_percent: f64,
+    pub threshold_percent: f64,
+}
+
+/// Metrics checked for regressions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RegressionMetric {
+    P95Latency,
+    Throughput,
+}
+
+impl PerformanceBaseline {
+    /// Build a baseline from the test cases of a performance run
+    pub fn from_result(result: &PerformanceTestResult) -> Self {
+        let benchmarks = result
+            .scenarios
+            .iter()
+            .flat_map(|scenario| {
+                scenario.test_cases.iter().map(move |case| {
+                    (
+                        benchmark_key(scenario, case),
+                        BaselineMetrics {
+                            p95_response_time_ms: case.metrics.p95_response_time_ms,
+                            requests_per_second: case.metrics.requests_per_second,
+                        },
+                    )
+                })
+            })
+            .collect();
+
+        Self {
+            source_test_id: result.test_id,
+            promoted_at: Utc::now(),
+            benchmarks,
+        }
+    }
+
+    /// Load a baseline file, returning `None` if it does not exist
+    pub async fn load(path: &Path) -> Result<Option<Self>> {
+        match tokio::fs::read_to_string(path).await {
+            Ok(content) => Ok(Some(serde_json::from_str(&content)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Write the baseline to a file, creating parent directories as needed
+    pub async fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(path, serde_json::to_string_pretty(self)?).await?;
+        Ok(())
+    }
+
+    /// Compare scenarios against the baseline and return every metric that
+    /// degraded beyond the configured thresholds
+    pub fn detect_regressions(
+        &self,
+        scenarios: &[PerformanceScenario],
+        config: &RegressionConfig,
+    ) -> Vec<RegressionDetail> {
+        let mut regressions = Vec::new();
+
+        for scenario in scenarios {
+            for case in &scenario.test_cases {
+                let key = benchmark_key(scenario, case);
+                let Some(baseline) = self.benchmarks.get(&key) else {
+                    continue;
+                };
+
+                if baseline.p95_response_time_ms > 0 {
+                    let baseline_value = baseline.p95_response_time_ms as f64;
+                    let current_value = case.metrics.p95_response_time_ms as f64;
+                    let increase = (current_value - baseline_value) / baseline_value * 100.0;
+                    if increase > config.max_p95_increase_percent {
+                        regressions.push(RegressionDetail {
+                            benchmark: key.clone(),
+                            metric: RegressionMetric::P95Latency,
+                            baseline_value,
+                            current_value,
+                            degradation_percent: increase,
+                            threshold_percent: config.max_p95_increase_percent,
+                        });
+                    }
+                }
+
+                if baseline.requests_per_second > 0.0 {
+                    let baseline_value = baseline.requests_per_second;
+                    let current_value = case.metrics.requests_per_second;
+                    let decrease = (baseline_value - current_value) / baseline_value * 100.0;
+                    if decrease > config.max_throughput_decrease_percent {
+                        regressions.push(RegressionDetail {
+                            benchmark: key,
+                            metric: RegressionMetric::Throughput,
+                            baseline_value,
+                            current_value,
+                            degradation_percent: decrease,
+                            threshold_percent: config.max_throughput_decrease_percent,
+                        });
+                    }
+                }
+            }
+        }
+
+        regressions
+    }
+}
+
+fn benchmark_key(scenario: &PerformanceScenario, case: &PerformanceTestCase) -> String {
+    format!("{}/{}", scenario.name, case.name)
+}
+
 /// Performance benchmark definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PerformanceBenchmark {
@@ -1620,4 +1810,116 @@ mod tests {
         assert_eq!(violation.metric, "Response Time");
         assert_eq!(violation.severity, ViolationSeverity::High);
     }
+
+    fn scenario_with_case(p95_ms: u64, rps: f64) -> PerformanceScenario {
+        let metrics = PerformanceMetrics {
+            total_requests: 100,
+            successful_requests: 100,
+            failed_requests: 0,
+            average_response_time_ms: p95_ms / 2,
+            p95_response_time_ms: p95_ms,
+            p99_response_time_ms: p95_ms * 2,
+            requests_per_second: rps,
+            error_rate_percent: 0.0,
+        };
+
+        PerformanceScenario {
+            scenario_id: Uuid::new_v4(),
+            name: "API Performance".to_string(),
+            scenario_type: PerformanceScenarioType::ApiTesting,
+            status: PerformanceStatus::Passed,
+            start_time: Utc::now(),
+            end_time: Utc::now(),
+            duration: 1,
+            test_cases: vec![PerformanceTestCase {
+                test_id: Uuid::new_v4(),
+                name: "GET /health".to_string(),
+                test_type: PerformanceTestType::HttpEndpoint,
+                status: PerformanceStatus::Passed,
+                start_time: Utc::now(),
+                end_time: Utc::now(),
+                duration: 1,
+                metrics: metrics.clone(),
+                details: None,
+            }],
+            metrics,
+        }
+    }
+
+    fn result_with_scenarios(scenarios: Vec<PerformanceScenario>) -> PerformanceTestResult {
+        PerformanceTestResult {
+            test_id: Uuid::new_v4(),
+            start_time: Utc::now(),
+            end_time: Utc::now(),
+            duration: 1,
+            status: PerformanceStatus::Passed,
+            scenarios,
+            sla_validation: SLAValidationResult {
+                overall_status: SLAStatus::Pass,
+                response_time_sla_met: true,
+                error_rate_sla_met: true,
+                throughput_sla_met: true,
+                availability_sla_met: true,
+                validation_timestamp: Utc::now(),
+                details: HashMap::new(),
+                compliance_percentage: 100.0,
+                violations: Vec::new(),
+            },
+            system_metrics: SystemMetrics {
+                cpu_usage_percent: 0.0,
+                memory_usage_mb: 0,
+                disk_io_ops_per_sec: 0,
+                network_throughput_mbps: 0.0,
+                active_connections: 0,
+                gc_collections: 0,
+            },
+            recommendations: vec![],
+            regressions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_regressions_beyond_threshold_are_detected() {
+        let baseline =
+            PerformanceBaseline::from_result(&result_with_scenarios(vec![scenario_with_case(
+                40, 1000.0,
+            )]));
+        let config = RegressionConfig::default();
+
+        // Within the 10% thresholds
+        let regressions = baseline.detect_regressions(&[scenario_with_case(43, 950.0)], &config);
+        assert!(regressions.is_empty());
+
+        let regressions = baseline.detect_regressions(&[scenario_with_case(50, 800.0)], &config);
+        assert_eq!(regressions.len(), 2);
+        assert_eq!(regressions[0].benchmark, "API Performance/GET /health");
+        assert_eq!(regressions[0].metric, RegressionMetric::P95Latency);
+        assert_eq!(regressions[0].degradation_percent, 25.0);
+        assert_eq!(regressions[1].metric, RegressionMetric::Throughput);
+        assert_eq!(regressions[1].degradation_percent, 20.0);
+    }
+
+    #[tokio::test]
+    async fn test_promoted_baseline_is_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = PerformanceConfig::default();
+        config.regression.baseline_path = dir.path().join("baselines/performance.json");
+        let tester = PerformanceTester::new(config).await.unwrap();
+
+        assert!(tester.load_baseline().await.unwrap().is_none());
+
+        let mut result = result_with_scenarios(vec![scenario_with_case(40, 1000.0)]);
+        result.status = PerformanceStatus::SlaViolation;
+        assert!(tester.promote_baseline(&result).await.is_err());
+
+        result.status = PerformanceStatus::Passed;
+        tester.promote_baseline(&result).await.unwrap();
+
+        let baseline = tester.load_baseline().await.unwrap().unwrap();
+        assert_eq!(baseline.source_test_id, result.test_id);
+        assert_eq!(
+            baseline.benchmarks["API Performance/GET /health"].p95_response_time_ms,
+            40
+        );
+    }
 }
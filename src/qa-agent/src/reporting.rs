@@ -0,0 +1,823 @@
+//! # Reporting Module
+//!
+//! Quality report generation for the AI-CORE QA Agent.
+//! Combines test, performance, security, and metrics results into a single report.
+
+pub use crate::config::ReportFormat;
+
+use crate::config::ReportingConfig;
+use crate::metrics::QualityMetricsResult;
+use crate::orchestrator::{TestCaseResult, TestSuiteResult};
+use crate::performance::{PerformanceStatus, PerformanceTestResult};
+use crate::security::{
+    SecurityFinding, SecurityScan, SecurityScanType, SecuritySeverity, SecurityStatus,
+    SecurityTestResult, VulnerabilityStatus,
+};
+use crate::testing::TestStatus;
+use crate::QAWorkflowResult;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::path::PathBuf;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Report generator for QA workflow results
+#[derive(Debug, Clone)]
+pub struct ReportGenerator {
+    config: ReportingConfig,
+}
+
+impl ReportGenerator {
+    /// Create a new report generator
+    pub fn new(config: ReportingConfig) -> Self {
+        Self { config }
+    }
+
+    /// Build a quality report from the results of a QA workflow
+    pub async fn generate_comprehensive_report(
+        &self,
+        test_result: &TestSuiteResult,
+        performance_result: &PerformanceTestResult,
+        security_result: &SecurityTestResult,
+        metrics_result: &QualityMetricsResult,
+    ) -> Result<QualityReport> {
+        let flaky_tests = collect_flaky_tests(test_result);
+
+        Ok(QualityReport {
+            report_id: Uuid::new_v4(),
+            generated_at: Utc::now(),
+            quality_score: metrics_result.quality_score.overall_score,
+            test_status: test_result.status.clone(),
+            performance_status: performance_result.status.clone(),
+            security_status: security_result.status.clone(),
+            test_summary: TestSummary {
+                total_tests: test_result.total_tests,
+                passed_tests: test_result.passed_tests,
+                failed_tests: test_result.failed_tests,
+                skipped_tests: test_result.skipped_tests,
+                flaky_tests: test_result.flaky_tests,
+                coverage_percentage: test_result.coverage_percentage,
+            },
+            flaky_tests,
+            vulnerability_count: security_result.vulnerabilities.len() as u32,
+        })
+    }
+
+    /// Write the reports of a QA workflow to the output directory in every
+    /// configured format
+    pub async fn write_reports(&self, workflow: &QAWorkflowResult) -> Result<Vec<PathBuf>> {
+        if !self.config.enabled {
+            return Ok(Vec::new());
+        }
+
+        std::fs::create_dir_all(&self.config.output_dir)?;
+
+        let mut written = Vec::new();
+        for format in &self.config.formats {
+            let (file_name, content) = match format {
+                ReportFormat::Json => (
+                    "quality-report.json",
+                    serde_json::to_string_pretty(&workflow.report)?,
+                ),
+                ReportFormat::JUnitXml => ("junit.xml", junit_xml(&workflow.test_result)),
+                ReportFormat::Sarif => (
+                    "security.sarif",
+                    serde_json::to_string_pretty(&sarif_log(&workflow.security_result))?,
+                ),
+                other => {
+                    warn!("Report format {:?} is not supported yet", other);
+                    continue;
+                }
+            };
+
+            let path = self.config.output_dir.join(file_name);
+            std::fs::write(&path, content)?;
+            info!("{:?} report written: {}", format, path.display());
+            written.push(path);
+        }
+
+        Ok(written)
+    }
+}
+
+/// Combined quality report for a QA workflow run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityReport {
+    pub report_id: Uuid,
+    pub generated_at: DateTime<Utc>,
+    pub quality_score: f64,
+    pub test_status: TestStatus,
+    pub performance_status: PerformanceStatus,
+    pub security_status: SecurityStatus,
+    pub test_summary: TestSummary,
+    /// Flaky tests, most unreliable first
+    pub flaky_tests: Vec<FlakyTestReport>,
+    pub vulnerability_count: u32,
+}
+
+/// Test counts for a report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestSummary {
+    pub total_tests: u32,
+    pub passed_tests: u32,
+    pub failed_tests: u32,
+    pub skipped_tests: u32,
+    pub flaky_tests: u32,
+    pub coverage_percentage: Option<f64>,
+}
+
+/// A test that failed and then passed on retry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlakyTestReport {
+    pub suite_name: String,
+    pub test_name: String,
+    pub attempts: u32,
+    pub failures: u32,
+    /// Fraction of attempts that failed
+    pub flake_rate: f64,
+}
+
+/// Collect the flaky tests of a result and its nested suites, sorted by
+/// descending flake rate so the least reliable tests come first
+pub fn collect_flaky_tests(result: &TestSuiteResult) -> Vec<FlakyTestReport> {
+    let mut flaky_tests = Vec::new();
+    push_flaky_tests(result, &mut flaky_tests);
+    flaky_tests.sort_by(|a, b| {
+        b.flake_rate
+            .total_cmp(&a.flake_rate)
+            .then_with(|| a.suite_name.cmp(&b.suite_name))
+            .then_with(|| a.test_name.cmp(&b.test_name))
+    });
+    flaky_tests
+}
+
+fn push_flaky_tests(result: &TestSuiteResult, flaky_tests: &mut Vec<FlakyTestReport>) {
+    for case in &result.test_cases {
+        if case.status == TestStatus::Flaky {
+            flaky_tests.push(FlakyTestReport {
+                suite_name: result.suite_name.clone(),
+                test_name: case.name.clone(),
+                attempts: case.attempts.len() as u32,
+                failures: case
+                    .attempts
+                    .iter()
+                    .filter(|status| **status == TestStatus::Failed)
+                    .count() as u32,
+                flake_rate: case.flake_rate(),
+            });
+        }
+    }
+
+    for suite in result.suite_results.iter().flatten() {
+        push_flaky_tests(suite, flaky_tests);
+    }
+}
+
+/// Render test results as JUnit XML
+///
+/// Each leaf suite becomes a `<testsuite>`. Flaky tests pass and carry a
+/// Surefire-style `<flakyFailure>` for every failed attempt.
+pub fn junit_xml(result: &TestSuiteResult) -> String {
+    let mut totals = JUnitCounts::default();
+    let mut suites_xml = String::new();
+
+    for suite in leaf_suites(result) {
+        let counts = JUnitCounts::from_cases(&suite.test_cases);
+        totals.add(&counts);
+
+        suites_xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.3}\" timestamp=\"{}\">\n",
+            xml_escape(&suite.suite_name),
+            counts.tests,
+            counts.failures,
+            counts.errors,
+            counts.skipped,
+            suite.duration as f64,
+            suite.start_time.format("%Y-%m-%dT%H:%M:%S"),
+        ));
+        for case in &suite.test_cases {
+            suites_xml.push_str(&junit_test_case(&suite.suite_name, case));
+        }
+        suites_xml.push_str("  </testsuite>\n");
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.3}\">\n{}</testsuites>\n",
+        xml_escape(&result.suite_name),
+        totals.tests,
+        totals.failures,
+        totals.errors,
+        totals.skipped,
+        result.duration as f64,
+        suites_xml,
+    )
+}
+
+/// Test case counts in JUnit terms
+#[derive(Debug, Default)]
+struct JUnitCounts {
+    tests: usize,
+    failures: usize,
+    errors: usize,
+    skipped: usize,
+}
+
+impl JUnitCounts {
+    fn from_cases(cases: &[TestCaseResult]) -> Self {
+        let mut counts = Self {
+            tests: cases.len(),
+            ..Self::default()
+        };
+        for case in cases {
+            match case.status {
+                TestStatus::Failed | TestStatus::Timeout => counts.failures += 1,
+                TestStatus::Error => counts.errors += 1,
+                TestStatus::Skipped | TestStatus::Pending | TestStatus::Running => {
+                    counts.skipped += 1
+                }
+                TestStatus::Passed | TestStatus::Flaky => {}
+            }
+        }
+        counts
+    }
+
+    fn add(&mut self, other: &JUnitCounts) {
+        self.tests += other.tests;
+        self.failures += other.failures;
+        self.errors += other.errors;
+        self.skipped += other.skipped;
+    }
+}
+
+/// Suites that hold test cases, flattening composite results
+pub(crate) fn leaf_suites(result: &TestSuiteResult) -> Vec<&TestSuiteResult> {
+    match &result.suite_results {
+        Some(suites) => suites.iter().flat_map(leaf_suites).collect(),
+        None => vec![result],
+    }
+}
+
+fn junit_test_case(class_name: &str, case: &TestCaseResult) -> String {
+    let mut children = String::new();
+    let message = case
+        .error_message
+        .as_deref()
+        .and_then(|error| error.lines().next());
+
+    match case.status {
+        TestStatus::Failed => {
+            children.push_str(&junit_problem(
+                "failure",
+                "failure",
+                message.unwrap_or("Test failed"),
+                case.error_message.as_deref(),
+            ));
+        }
+        TestStatus::Timeout => {
+            children.push_str(&junit_problem(
+                "failure",
+                "timeout",
+                message.unwrap_or("Test timed out"),
+                case.error_message.as_deref(),
+            ));
+        }
+        TestStatus::Error => {
+            children.push_str(&junit_problem(
+                "error",
+                "error",
+                message.unwrap_or("Test error"),
+                case.error_message.as_deref(),
+            ));
+        }
+        TestStatus::Flaky => {
+            let failed_attempts = case
+                .attempts
+                .iter()
+                .filter(|status| **status == TestStatus::Failed)
+                .count();
+            for _ in 0..failed_attempts {
+                children.push_str(&junit_problem(
+                    "flakyFailure",
+                    "failure",
+                    message.unwrap_or("Failed before passing on retry"),
+                    None,
+                ));
+            }
+        }
+        TestStatus::Skipped | TestStatus::Pending | TestStatus::Running => {
+            children.push_str("      <skipped/>\n");
+        }
+        TestStatus::Passed => {}
+    }
+
+    if let Some(output) = &case.output {
+        children.push_str(&format!(
+            "      <system-out>{}</system-out>\n",
+            xml_escape(output)
+        ));
+    }
+
+    let open = format!(
+        "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\"",
+        xml_escape(&case.name),
+        xml_escape(class_name),
+        case.duration as f64 / 1000.0,
+    );
+    if children.is_empty() {
+        format!("{}/>\n", open)
+    } else {
+        format!("{}>\n{}    </testcase>\n", open, children)
+    }
+}
+
+fn junit_problem(element: &str, kind: &str, message: &str, details: Option<&str>) -> String {
+    match details {
+        Some(details) => format!(
+            "      <{element} message=\"{}\" type=\"{kind}\">{}</{element}>\n",
+            xml_escape(message),
+            xml_escape(details),
+        ),
+        None => format!(
+            "      <{element} message=\"{}\" type=\"{kind}\"/>\n",
+            xml_escape(message),
+        ),
+    }
+}
+
+/// Escape text for XML, dropping control characters XML 1.0 cannot represent
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// SARIF schema location used by GitHub code scanning
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Render security findings as a SARIF 2.1.0 log
+///
+/// Findings become results of rules keyed by CVE, or by the finding title
+/// when there is none. Accepted, false-positive, and in-progress
+/// vulnerabilities are reported as suppressed; resolved ones are left out.
+pub fn sarif_log(result: &SecurityTestResult) -> serde_json::Value {
+    let mut rule_ids: Vec<String> = Vec::new();
+    let mut rules = Vec::new();
+    let mut results = Vec::new();
+
+    for scan in &result.scans {
+        for finding in &scan.findings {
+            let status = vulnerability_status(result, scan, finding);
+            if matches!(status, Some(VulnerabilityStatus::Resolved)) {
+                continue;
+            }
+
+            let rule_id = sarif_rule_id(finding);
+            if !rule_ids.contains(&rule_id) {
+                rules.push(sarif_rule(&rule_id, finding));
+                rule_ids.push(rule_id.clone());
+            }
+
+            let mut sarif_result = json!({
+                "ruleId": rule_id,
+                "level": sarif_level(&finding.severity),
+                "message": { "text": finding.description },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": scan_location(scan) }
+                    }
+                }],
+                "properties": {
+                    "scan": scan.name,
+                    "severity": format!("{:?}", finding.severity),
+                },
+            });
+            if let Some(suppression) = status.and_then(sarif_suppression) {
+                sarif_result["suppressions"] = json!([suppression]);
+            }
+            results.push(sarif_result);
+        }
+    }
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "ai-core-qa-agent",
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    })
+}
+
+/// Tracked status of the vulnerability raised from a finding, if any
+pub(crate) fn vulnerability_status<'a>(
+    result: &'a SecurityTestResult,
+    scan: &SecurityScan,
+    finding: &SecurityFinding,
+) -> Option<&'a VulnerabilityStatus> {
+    result
+        .vulnerabilities
+        .iter()
+        .find(|vulnerability| {
+            vulnerability.source_scan == scan.scan_id
+                && vulnerability.title == finding.title
+                && vulnerability.cve_id == finding.cve_id
+        })
+        .map(|vulnerability| &vulnerability.status)
+}
+
+fn sarif_rule_id(finding: &SecurityFinding) -> String {
+    if let Some(cve_id) = &finding.cve_id {
+        return cve_id.clone();
+    }
+
+    let mut slug = String::new();
+    for c in finding.title.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+fn sarif_rule(rule_id: &str, finding: &SecurityFinding) -> serde_json::Value {
+    let category = format!("{:?}", finding.category);
+    let mut rule = json!({
+        "id": rule_id,
+        "name": category,
+        "shortDescription": { "text": finding.title },
+        "fullDescription": { "text": finding.description },
+        "properties": {
+            "security-severity": security_severity_score(&finding.severity),
+            "tags": ["security", category],
+        },
+    });
+    if let Some(remediation) = &finding.remediation {
+        rule["help"] = json!({ "text": remediation });
+    }
+    rule
+}
+
+fn sarif_level(severity: &SecuritySeverity) -> &'static str {
+    match severity {
+        SecuritySeverity::Critical | SecuritySeverity::High => "error",
+        SecuritySeverity::Medium => "warning",
+        SecuritySeverity::Low | SecuritySeverity::Info => "note",
+    }
+}
+
+/// CVSS-style score GitHub code scanning uses to bucket severities
+fn security_severity_score(severity: &SecuritySeverity) -> &'static str {
+    match severity {
+        SecuritySeverity::Critical => "9.5",
+        SecuritySeverity::High => "8.0",
+        SecuritySeverity::Medium => "5.5",
+        SecuritySeverity::Low => "2.0",
+        SecuritySeverity::Info => "0.0",
+    }
+}
+
+/// Code scanning and check run annotations require a location, so findings
+/// are attached to the scan's `location` metadata or to the file the scan type
+/// inspects
+pub(crate) fn scan_location(scan: &SecurityScan) -> &str {
+    if let Some(location) = scan.metadata.get("location") {
+        return location;
+    }
+
+    match scan.scan_type {
+        SecurityScanType::DependencyCheck => "Cargo.lock",
+        SecurityScanType::ContainerScan => "Dockerfile",
+        _ => "Cargo.toml",
+    }
+}
+
+fn sarif_suppression(status: &VulnerabilityStatus) -> Option<serde_json::Value> {
+    let (status, justification) = match status {
+        VulnerabilityStatus::Open | VulnerabilityStatus::Resolved => return None,
+        VulnerabilityStatus::InProgress => ("underReview", "Remediation in progress"),
+        VulnerabilityStatus::Accepted => ("accepted", "Risk accepted"),
+        VulnerabilityStatus::FalsePositive => ("accepted", "False positive"),
+    };
+
+    Some(json!({
+        "kind": "external",
+        "status": status,
+        "justification": justification,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TestSuiteType;
+    use crate::security::{ComplianceStatus, SecurityCategory, SecurityVulnerability};
+    use std::collections::HashMap;
+
+    fn flaky_case(name: &str, attempts: Vec<TestStatus>) -> TestCaseResult {
+        TestCaseResult {
+            name: name.to_string(),
+            status: TestStatus::Flaky,
+            duration: 10,
+            error_message: None,
+            assertions: 1,
+            output: None,
+            attempts,
+        }
+    }
+
+    #[test]
+    fn test_flaky_tests_are_collected_from_nested_suites() {
+        let mut unit = TestSuiteResult::failed_suite(
+            "unit".to_string(),
+            TestSuiteType::Unit,
+            "error".to_string(),
+        );
+        unit.test_cases.push(flaky_case(
+            "mostly_passes",
+            vec![TestStatus::Failed, TestStatus::Passed],
+        ));
+
+        let mut integration = TestSuiteResult::failed_suite(
+            "integration".to_string(),
+            TestSuiteType::Integration,
+            "error".to_string(),
+        );
+        integration.test_cases.push(flaky_case(
+            "mostly_fails",
+            vec![TestStatus::Failed, TestStatus::Failed, TestStatus::Passed],
+        ));
+
+        let mut all = TestSuiteResult::failed_suite(
+            "All Test Suites".to_string(),
+            TestSuiteType::Integration,
+            "error".to_string(),
+        );
+        all.test_cases.clear();
+        all.suite_results = Some(vec![unit, integration]);
+
+        let flaky_tests = collect_flaky_tests(&all);
+
+        assert_eq!(flaky_tests.len(), 2);
+        assert_eq!(flaky_tests[0].suite_name, "integration");
+        assert_eq!(flaky_tests[0].test_name, "mostly_fails");
+        assert_eq!(flaky_tests[0].attempts, 3);
+        assert_eq!(flaky_tests[0].failures, 2);
+        assert_eq!(flaky_tests[1].test_name, "mostly_passes");
+        assert_eq!(flaky_tests[1].flake_rate, 0.5);
+    }
+
+    fn test_case(
+        name: &str,
+        status: TestStatus,
+        duration: i64,
+        error_message: Option<&str>,
+    ) -> TestCaseResult {
+        TestCaseResult {
+            name: name.to_string(),
+            status,
+            duration,
+            error_message: error_message.map(str::to_string),
+            assertions: 1,
+            output: None,
+            attempts: Vec::new(),
+        }
+    }
+
+    fn suite(name: &str, duration: i64, test_cases: Vec<TestCaseResult>) -> TestSuiteResult {
+        let mut suite =
+            TestSuiteResult::failed_suite(name.to_string(), TestSuiteType::Unit, String::new());
+        suite.start_time = "2026-01-01T00:00:00Z".parse().unwrap();
+        suite.duration = duration;
+        suite.test_cases = test_cases;
+        suite
+    }
+
+    #[test]
+    fn test_junit_xml_matches_golden_file() {
+        let mut passed = test_case("parses_config", TestStatus::Passed, 1500, None);
+        passed.output = Some("ok\u{1b}[0m".to_string());
+        let mut flaky = test_case(
+            "flaky_network",
+            TestStatus::Flaky,
+            250,
+            Some("connection reset"),
+        );
+        flaky.attempts = vec![TestStatus::Failed, TestStatus::Passed];
+
+        let unit = suite(
+            "unit",
+            2,
+            vec![
+                passed,
+                test_case(
+                    "handles \"quoted\" & <angled> names",
+                    TestStatus::Failed,
+                    20,
+                    Some("assertion failed: left == right\n  left: 1\n right: 2"),
+                ),
+                flaky,
+                test_case("needs_docker", TestStatus::Skipped, 0, None),
+            ],
+        );
+        let integration = suite(
+            "integration",
+            1,
+            vec![test_case(
+                "database_roundtrip",
+                TestStatus::Error,
+                5,
+                Some("pool timed out"),
+            )],
+        );
+        let mut all = suite("All Test Suites", 3, Vec::new());
+        all.suite_results = Some(vec![unit, integration]);
+
+        assert_eq!(junit_xml(&all), include_str!("../tests/golden/junit.xml"));
+    }
+
+    fn finding(
+        severity: SecuritySeverity,
+        title: &str,
+        description: &str,
+        category: SecurityCategory,
+        cve_id: Option<&str>,
+        remediation: Option<&str>,
+    ) -> SecurityFinding {
+        SecurityFinding {
+            id: Uuid::nil(),
+            severity,
+            title: title.to_string(),
+            description: description.to_string(),
+            category,
+            cve_id: cve_id.map(str::to_string),
+            remediation: remediation.map(str::to_string),
+        }
+    }
+
+    fn scan(
+        scan_id: Uuid,
+        name: &str,
+        scan_type: SecurityScanType,
+        findings: Vec<SecurityFinding>,
+    ) -> SecurityScan {
+        SecurityScan {
+            scan_id,
+            name: name.to_string(),
+            scan_type,
+            status: SecurityStatus::VulnerabilityFound,
+            start_time: Utc::now(),
+            end_time: Utc::now(),
+            duration: 0,
+            findings,
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn vulnerability(
+        source_scan: Uuid,
+        finding: &SecurityFinding,
+        status: VulnerabilityStatus,
+    ) -> SecurityVulnerability {
+        SecurityVulnerability {
+            id: Uuid::new_v4(),
+            title: finding.title.clone(),
+            severity: finding.severity.clone(),
+            description: finding.description.clone(),
+            source_scan,
+            cve_id: finding.cve_id.clone(),
+            remediation: finding.remediation.clone(),
+            status,
+        }
+    }
+
+    fn security_result() -> SecurityTestResult {
+        let dependency_scan_id = Uuid::from_u128(1);
+        let container_scan_id = Uuid::from_u128(2);
+
+        let outdated = finding(
+            SecuritySeverity::Medium,
+            "Outdated dependency detected",
+            "Package 'example-lib' version 1.2.3 has known vulnerabilities",
+            SecurityCategory::DependencyVulnerability,
+            Some("CVE-2023-1234"),
+            Some("Update to version 1.2.4 or later"),
+        );
+        let tls = finding(
+            SecuritySeverity::Critical,
+            "Vulnerable TLS library",
+            "openssl 1.1.1 is affected by CVE-2024-0001",
+            SecurityCategory::DependencyVulnerability,
+            Some("CVE-2024-0001"),
+            None,
+        );
+        let fixed = finding(
+            SecuritySeverity::High,
+            "Prototype pollution",
+            "lodash 4.17.15 is affected by CVE-2020-8203",
+            SecurityCategory::DependencyVulnerability,
+            Some("CVE-2020-8203"),
+            None,
+        );
+        let root = finding(
+            SecuritySeverity::Low,
+            "Container running as root",
+            "Container is running with root privileges",
+            SecurityCategory::ContainerSecurity,
+            None,
+            Some("Configure container to run as non-root user"),
+        );
+
+        let vulnerabilities = vec![
+            vulnerability(dependency_scan_id, &outdated, VulnerabilityStatus::Open),
+            vulnerability(dependency_scan_id, &tls, VulnerabilityStatus::Accepted),
+            vulnerability(dependency_scan_id, &fixed, VulnerabilityStatus::Resolved),
+            vulnerability(container_scan_id, &root, VulnerabilityStatus::FalsePositive),
+        ];
+
+        let mut container_scan = scan(
+            container_scan_id,
+            "Container Security Scan",
+            SecurityScanType::ContainerScan,
+            vec![root],
+        );
+        container_scan
+            .metadata
+            .insert("location".to_string(), "docker/Dockerfile".to_string());
+
+        SecurityTestResult {
+            test_id: Uuid::new_v4(),
+            start_time: Utc::now(),
+            end_time: Utc::now(),
+            duration: 0,
+            status: SecurityStatus::VulnerabilityFound,
+            scans: vec![
+                scan(
+                    dependency_scan_id,
+                    "Dependency Vulnerability Scan",
+                    SecurityScanType::DependencyCheck,
+                    vec![outdated, tls, fixed],
+                ),
+                container_scan,
+            ],
+            vulnerabilities,
+            compliance_status: ComplianceStatus {
+                overall_status: SecurityStatus::Passed,
+                compliance_percentage: 100.0,
+                frameworks_checked: vec![],
+                violations: vec![],
+            },
+        }
+    }
+
+    #[test]
+    fn test_sarif_log_matches_golden_file() {
+        let golden: serde_json::Value =
+            serde_json::from_str(include_str!("../tests/golden/security.sarif")).unwrap();
+
+        assert_eq!(sarif_log(&security_result()), golden);
+    }
+
+    #[test]
+    fn test_sarif_results_satisfy_schema_requirements() {
+        let sarif = sarif_log(&security_result());
+        assert_eq!(sarif["version"], "2.1.0");
+
+        let run = &sarif["runs"][0];
+        assert!(run["tool"]["driver"]["name"].is_string());
+
+        let rule_ids: Vec<&str> = run["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|rule| rule["id"].as_str().unwrap())
+            .collect();
+        for result in run["results"].as_array().unwrap() {
+            assert!(rule_ids.contains(&result["ruleId"].as_str().unwrap()));
+            assert!(
+                ["none", "note", "warning", "error"].contains(&result["level"].as_str().unwrap())
+            );
+            assert!(result["message"]["text"].is_string());
+            assert!(!result["locations"].as_array().unwrap().is_empty());
+        }
+    }
+}
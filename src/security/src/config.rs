@@ -96,6 +96,9 @@ pub struct RateLimitingConfig {
     pub endpoint_limits: HashMap<String, EndpointRateLimit>,
     /// Rate limit by user tier
     pub tier_limits: HashMap<String, TierRateLimit>,
+    /// Behaviour of distributed limiting when its storage is unreachable
+    #[serde(default)]
+    pub failure_policy: RateLimitFailurePolicy,
 }
 
 /// Authorization (RBAC/ABAC) configuration
@@ -250,6 +253,17 @@ pub enum RateLimitStorage {
     Redis,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RateLimitFailurePolicy {
+    /// Admit requests unchecked, favouring availability
+    #[default]
+    #[serde(rename = "fail_open")]
+    FailOpen,
+    /// Reject requests, favouring protection of downstream services
+    #[serde(rename = "fail_closed")]
+    FailClosed,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EndpointRateLimit {
     pub requests_per_minute: u32,
@@ -376,6 +390,7 @@ impl Default for RateLimitingConfig {
             storage_backend: RateLimitStorage::Redis,
             endpoint_limits: HashMap::new(),
             tier_limits: HashMap::new(),
+            failure_policy: RateLimitFailurePolicy::FailOpen,
         }
     }
 }
@@ -499,6 +514,20 @@ impl SecurityConfig {
             config.database.redis_url = redis_url;
         }
 
+        // Rate limiting configuration from environment
+        if let Ok(policy) = std::env::var("RATE_LIMIT_FAILURE_POLICY") {
+            config.rate_limiting.failure_policy = match policy.as_str() {
+                "fail_open" => RateLimitFailurePolicy::FailOpen,
+                "fail_closed" => RateLimitFailurePolicy::FailClosed,
+                other => {
+                    return Err(SecurityError::Configuration(format!(
+                        "Invalid RATE_LIMIT_FAILURE_POLICY: {} (expected fail_open or fail_closed)",
+                        other
+                    )))
+                }
+            };
+        }
+
         // Encryption configuration from environment
         if let Ok(master_key) = std::env::var("ENCRYPTION_MASTER_KEY") {
             config.encryption.master_key = master_key;
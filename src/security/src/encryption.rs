@@ -3,8 +3,9 @@
 //! Provides comprehensive encryption services for the AI-CORE security framework.
 //! Supports AES-256-GCM, ChaCha20-Poly1305, key management, and password hashing.
 
-use crate::constants::{AES_KEY_SIZE, CHACHA20_KEY_SIZE};
+use crate::constants::{AES_KEY_SIZE, CHACHA20_KEY_SIZE, MAX_PASSWORD_LENGTH, MIN_PASSWORD_LENGTH};
 use crate::errors::{SecurityError, SecurityResult};
+use crate::password_strength::{self, PasswordStrength};
 
 use aes_gcm::{
     aead::{Aead, AeadCore, KeyInit, OsRng},
@@ -127,6 +128,8 @@ pub struct PasswordConfig {
     pub time_cost: u32,
     pub parallelism: u32,
     pub hash_length: u32,
+    /// Lowest `PasswordStrength` score (0-4) accepted for new passwords
+    pub min_strength_score: u8,
 }
 
 impl Default for PasswordConfig {
@@ -136,6 +139,7 @@ impl Default for PasswordConfig {
             time_cost: 3,
             parallelism: 1,
             hash_length: 32,
+            min_strength_score: 3,
         }
     }
 }
@@ -466,6 +470,41 @@ impl PasswordService {
             .is_ok())
     }
 
+    /// Estimate how hard a password is to guess, with feedback for the user
+    pub fn estimate_strength(&self, password: &str) -> PasswordStrength {
+        password_strength::estimate(password)
+    }
+
+    /// Check a password chosen at registration or on a password change
+    /// against the length limits and the configured minimum strength
+    pub fn validate_new_password(&self, password: &str) -> SecurityResult<PasswordStrength> {
+        let length = password.chars().count();
+        if length < MIN_PASSWORD_LENGTH {
+            return Err(SecurityError::PasswordPolicy(format!(
+                "Password must be at least {} characters",
+                MIN_PASSWORD_LENGTH
+            )));
+        }
+        if length > MAX_PASSWORD_LENGTH {
+            return Err(SecurityError::PasswordPolicy(format!(
+                "Password must be at most {} characters",
+                MAX_PASSWORD_LENGTH
+            )));
+        }
+
+        let strength = self.estimate_strength(password);
+        if strength.score < self.config.min_strength_score {
+            let reasons = if strength.warnings.is_empty() {
+                &strength.suggestions
+            } else {
+                &strength.warnings
+            };
+            return Err(SecurityError::WeakPassword(reasons.join("; ")));
+        }
+
+        Ok(strength)
+    }
+
     /// Check password strength
     pub fn check_password_strength(&self, password: &str) -> PasswordStrengthLevel {
         let length = password.len();
@@ -586,7 +625,15 @@ impl EncryptionService {
     /// Decrypt data
     pub async fn decrypt(&self, encrypted: &EncryptedData) -> SecurityResult<Vec<u8>> {
         let key = self.key_manager.get_key(&encrypted.key_id).await?;
+        self.decrypt_with_key(encrypted, &key).await
+    }
 
+    /// Decrypt data with a specific key
+    pub async fn decrypt_with_key(
+        &self,
+        encrypted: &EncryptedData,
+        key: &EncryptionKey,
+    ) -> SecurityResult<Vec<u8>> {
         let ciphertext = BASE64_STANDARD
             .decode(&encrypted.ciphertext)
             .map_err(|e| SecurityError::InvalidInputFormat(e.to_string()))?;
@@ -862,4 +909,34 @@ mod tests {
             PasswordStrengthLevel::VeryStrong
         );
     }
+
+    #[test]
+    fn test_validate_new_password() {
+        let password_service = PasswordService::new();
+
+        assert!(matches!(
+            password_service.validate_new_password("Sh0rt!"),
+            Err(SecurityError::PasswordPolicy(_))
+        ));
+
+        // Long enough and mixes every character class, but common
+        match password_service.validate_new_password("Password123456!") {
+            Err(SecurityError::WeakPassword(reasons)) => {
+                assert!(reasons.contains("commonly used password"))
+            }
+            other => panic!("expected WeakPassword, got {:?}", other),
+        }
+
+        let strength = password_service
+            .validate_new_password("granite Orbit pickle 42 lantern")
+            .unwrap();
+        assert!(strength.score >= 3);
+
+        // The threshold is configurable
+        let lenient = PasswordService::new_with_config(PasswordConfig {
+            min_strength_score: 0,
+            ..PasswordConfig::default()
+        });
+        assert!(lenient.validate_new_password("Password123456!").is_ok());
+    }
 }
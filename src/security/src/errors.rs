@@ -25,6 +25,9 @@ pub enum SecurityError {
     #[error("Token blacklisted: {0}")]
     TokenBlacklisted(String),
 
+    #[error("Token revoked: {0}")]
+    TokenRevoked(String),
+
     #[error("Invalid credentials")]
     InvalidCredentials,
 
@@ -380,6 +383,7 @@ impl SecurityError {
 
             // High severity errors
             SecurityError::Unauthorized
+            | SecurityError::TokenRevoked(_)
             | SecurityError::InsufficientPermissions { .. }
             | SecurityError::AccountLocked(_)
             | SecurityError::IpBlocked(_)
@@ -496,6 +500,7 @@ impl SecurityError {
             SecurityError::InvalidSignature(_) => "SEC069",
             SecurityError::KeyDerivation(_) => "SEC070",
             SecurityError::UnsupportedOperation(_) => "SEC071",
+            SecurityError::TokenRevoked(_) => "SEC072",
             SecurityError::Custom(_) => "SEC999",
         }
     }
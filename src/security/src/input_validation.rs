@@ -1,10 +1,14 @@
 //! Input Validation Module
 //!
-//! Provides input sanitization and validation capabilities for security.
+//! Provides input sanitization and validation capabilities for security,
+//! including pattern-based detection of SQL/NoSQL injection and XSS payloads.
 
 use crate::errors::{SecurityError, SecurityResult};
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt::{self, Write};
 
 /// Input validation configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +25,10 @@ pub struct SanitizationConfig {
     pub blocked_user_agents: Vec<String>,
     /// Allowed file types
     pub allowed_file_types: Vec<String>,
+    /// HTML tags kept by `sanitize_html`; all other tags are stripped
+    pub allowed_html_tags: Vec<String>,
+    /// HTML attributes kept on allowed tags; URL attributes must also use a safe scheme
+    pub allowed_html_attributes: Vec<String>,
 }
 
 impl Default for SanitizationConfig {
@@ -43,10 +51,191 @@ impl Default for SanitizationConfig {
                 "pdf".to_string(),
                 "txt".to_string(),
             ],
+            allowed_html_tags: [
+                "a",
+                "b",
+                "blockquote",
+                "br",
+                "code",
+                "em",
+                "i",
+                "li",
+                "ol",
+                "p",
+                "pre",
+                "strong",
+                "ul",
+            ]
+            .iter()
+            .map(|tag| tag.to_string())
+            .collect(),
+            allowed_html_attributes: vec!["href".to_string(), "title".to_string()],
         }
     }
 }
 
+/// Class of attack an input finding points to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InjectionKind {
+    SqlInjection,
+    NoSqlInjection,
+    Xss,
+}
+
+impl fmt::Display for InjectionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InjectionKind::SqlInjection => write!(f, "SQL injection"),
+            InjectionKind::NoSqlInjection => write!(f, "NoSQL injection"),
+            InjectionKind::Xss => write!(f, "cross-site scripting"),
+        }
+    }
+}
+
+/// A suspicious pattern found in an input
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputFinding {
+    pub kind: InjectionKind,
+    /// Name of the detection rule that matched
+    pub rule: String,
+    /// The text the rule matched
+    pub matched: String,
+    /// Byte offset of the match in the inspected input
+    pub offset: usize,
+}
+
+struct DetectionRule {
+    name: &'static str,
+    pattern: Regex,
+}
+
+fn detection_rules(rules: &[(&'static str, &str)]) -> Vec<DetectionRule> {
+    rules
+        .iter()
+        .map(|(name, pattern)| DetectionRule {
+            name,
+            pattern: Regex::new(pattern).expect("detection patterns are valid regexes"),
+        })
+        .collect()
+}
+
+// Rules look for injection *context* (quote breakouts, stacked statements,
+// operators used as keys, markup) rather than bare keywords, so ordinary prose
+// that mentions "select" or "drop" is not flagged.
+static SQL_INJECTION_RULES: Lazy<Vec<DetectionRule>> = Lazy::new(|| {
+    detection_rules(&[
+        (
+            "tautology",
+            r#"(?i)['")]\s*(?:or|and)\s+['"(]?\w+['"]?\s*(?:=|<>|!=|<|>|\blike\b)\s*['"(]?\w+"#,
+        ),
+        ("numeric_tautology", r"(?i)\b(?:or|and)\s+\d+\s*=\s*\d+\b"),
+        (
+            "stacked_query",
+            r"(?i);\s*(?:drop\s+(?:table|database|schema|view|index|user)\b|truncate\s+table\b|alter\s+(?:table|database|user)\b|delete\s+from\b|insert\s+into\b|update\s+\w+\s+set\b|create\s+(?:table|database|user)\b|exec(?:ute)?\s*(?:\(|xp_|sp_)|shutdown\b)",
+        ),
+        (
+            "union_select",
+            r"(?i)\bunion(?:\s+all|\s+distinct)?\s+select\b",
+        ),
+        // A quote closing a value, then a comment that ends it (`admin'--`).
+        // Quotes opening a string (`"#ff0000"`, `"--verbose"`) don't count.
+        (
+            "quote_comment",
+            r#"\w\\?['"]\)?\s*;?\s*(?:(?:--|#)\s*(?:$|["'}\],&])|/\*)"#,
+        ),
+        (
+            "time_delay",
+            r"(?i)\b(?:sleep|pg_sleep|benchmark)\s*\(\s*\d|\bwaitfor\s+delay\s+'",
+        ),
+        (
+            "schema_probe",
+            r"(?i)\binformation_schema\b|\bpg_catalog\b|\bsys(?:objects|columns)\b|\bxp_cmdshell\b|\bload_file\s*\(|\binto\s+(?:out|dump)file\b",
+        ),
+    ])
+});
+
+static NOSQL_INJECTION_RULES: Lazy<Vec<DetectionRule>> = Lazy::new(|| {
+    detection_rules(&[
+        ("where_operator", r"(?i)\$where\b"),
+        (
+            "query_operator",
+            r#"(?i)\$(?:ne|eq|gt|gte|lt|lte|in|nin|regex|exists|expr|not|nor|or|and|elemmatch|function|accumulator)\b["'\]]?\s*[:=]"#,
+        ),
+        (
+            "js_tautology",
+            r#"['"]\s*\|\|\s*['"]?\w+['"]?\s*={2,3}\s*['"]?\w+"#,
+        ),
+        ("js_return", r"(?i);\s*return\s+(?:true|1)\b"),
+        (
+            "shell_command",
+            r"(?i)\bdb\.(?:\w+\.)?(?:find\w*|drop\w*|remove|delete\w*|insert\w*|update\w*|aggregate|eval|getcollection)\s*\(",
+        ),
+    ])
+});
+
+static XSS_RULES: Lazy<Vec<DetectionRule>> = Lazy::new(|| {
+    detection_rules(&[
+        ("script_tag", r"(?i)<\s*/?\s*script\b"),
+        (
+            "event_handler",
+            r#"(?i)(?:<[^>]{0,256}?[\s/"']|["']\s*)on(?:abort|animation\w+|beforeunload|blur|change|click|contextmenu|copy|cut|dblclick|drag\w*|drop|error|focus\w*|hashchange|input|invalid|key(?:down|press|up)|load|message|mouse\w+|pageshow|paste|pointer\w+|popstate|reset|resize|scroll|select|show|submit|toggle|touch\w+|transition\w+|unload|wheel)\s*="#,
+        ),
+        (
+            "script_uri",
+            r#"(?i)(?:\b(?:href|src|action|formaction|data|background|poster)\s*=\s*["']?\s*|^\s*)(?:javascript|vbscript|livescript|data\s*:\s*text/html)\s*[:;,]"#,
+        ),
+        (
+            "dangerous_tag",
+            r"(?i)<\s*(?:iframe|frame|frameset|object|embed|applet|meta|base|link|svg|math|form|style)\b",
+        ),
+        ("srcdoc", r"(?i)\bsrcdoc\s*="),
+        (
+            "css_expression",
+            r#"(?i)\bstyle\s*=\s*["']?[^"'>]*expression\s*\("#,
+        ),
+        (
+            "dom_access",
+            r"(?i)\bdocument\s*\.\s*(?:cookie|domain)\b|\bdocument\s*\.\s*write(?:ln)?\s*\(|\bString\s*\.\s*fromCharCode\s*\(",
+        ),
+    ])
+});
+
+fn scan(kind: InjectionKind, rules: &[DetectionRule], input: &str) -> Vec<InputFinding> {
+    let mut findings: Vec<InputFinding> = rules
+        .iter()
+        .flat_map(|rule| {
+            rule.pattern.find_iter(input).map(move |m| InputFinding {
+                kind,
+                rule: rule.name.to_string(),
+                matched: m.as_str().to_string(),
+                offset: m.start(),
+            })
+        })
+        .collect();
+    findings.sort_by_key(|finding| finding.offset);
+    findings
+}
+
+/// Tags whose content is dropped along with the tag when they are not allowed
+const DROP_CONTENT_TAGS: &[&str] = &[
+    "script", "style", "iframe", "object", "noscript", "template",
+];
+
+/// Attributes whose values are URLs and must use a safe scheme
+const URL_ATTRIBUTES: &[&str] = &[
+    "href",
+    "src",
+    "action",
+    "formaction",
+    "cite",
+    "poster",
+    "background",
+    "xlink:href",
+];
+
+const SAFE_URL_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
 /// Input validator service
 pub struct InputValidator {
     config: SanitizationConfig,
@@ -153,6 +342,135 @@ impl InputValidator {
         Ok(threats)
     }
 
+    /// Detect SQL injection payloads such as `' OR '1'='1`, `'; DROP TABLE` and `UNION SELECT`
+    pub fn detect_sql_injection(&self, input: &str) -> Vec<InputFinding> {
+        scan(InjectionKind::SqlInjection, &SQL_INJECTION_RULES, input)
+    }
+
+    /// Detect NoSQL injection payloads such as `$where` clauses and `{"$ne": ...}` operators
+    pub fn detect_nosql_injection(&self, input: &str) -> Vec<InputFinding> {
+        scan(InjectionKind::NoSqlInjection, &NOSQL_INJECTION_RULES, input)
+    }
+
+    /// Detect XSS payloads such as `<script>` tags, event-handler attributes and `javascript:` URLs
+    pub fn detect_xss(&self, input: &str) -> Vec<InputFinding> {
+        scan(InjectionKind::Xss, &XSS_RULES, input)
+    }
+
+    /// Run every detector over the input, returning findings ordered by offset
+    pub fn inspect(&self, input: &str) -> Vec<InputFinding> {
+        let mut findings = self.detect_sql_injection(input);
+        findings.extend(self.detect_nosql_injection(input));
+        findings.extend(self.detect_xss(input));
+        findings.sort_by_key(|finding| finding.offset);
+        findings
+    }
+
+    /// Inspect a URL-encoded query string or form body. Offsets refer to the
+    /// decoded text, with `&` separators kept in place.
+    pub fn inspect_url_encoded(&self, input: &str) -> Vec<InputFinding> {
+        let plus_decoded = input.replace('+', " ");
+        let decoded = urlencoding::decode_binary(plus_decoded.as_bytes());
+        self.inspect(&String::from_utf8_lossy(&decoded))
+    }
+
+    /// Strip HTML tags and attributes that are not allowed by the configuration.
+    /// Event handlers and URLs with unsafe schemes are always removed, and the
+    /// content of script-like elements is dropped with them.
+    pub fn sanitize_html(&self, html: &str) -> SecurityResult<String> {
+        if html.len() > self.config.max_input_length {
+            return Err(SecurityError::InputTooLong {
+                max: self.config.max_input_length,
+                actual: html.len(),
+            });
+        }
+
+        let mut output = String::with_capacity(html.len());
+        let mut rest = html;
+        // Closing tag that ends content being dropped
+        let mut dropping_until: Option<String> = None;
+
+        while let Some(start) = rest.find('<') {
+            if dropping_until.is_none() {
+                push_html_text(&mut output, &rest[..start]);
+            }
+            let markup = &rest[start..];
+
+            if let Some(comment) = markup.strip_prefix("<!--") {
+                rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+                continue;
+            }
+
+            let Some((tag, length)) = parse_html_tag(markup) else {
+                if dropping_until.is_none() {
+                    output.push_str("&lt;");
+                }
+                rest = &markup[1..];
+                continue;
+            };
+            rest = &markup[length..];
+
+            if let Some(until) = &dropping_until {
+                if tag.closing && &tag.name == until {
+                    dropping_until = None;
+                }
+            } else if self.is_allowed_tag(&tag.name) {
+                output.push_str(&self.render_html_tag(&tag));
+            } else if !tag.closing && !tag.self_closing && DROP_CONTENT_TAGS.contains(&&*tag.name) {
+                dropping_until = Some(tag.name);
+            }
+        }
+
+        if dropping_until.is_none() {
+            push_html_text(&mut output, rest);
+        }
+
+        Ok(output)
+    }
+
+    fn is_allowed_tag(&self, name: &str) -> bool {
+        self.config
+            .allowed_html_tags
+            .iter()
+            .any(|tag| tag.eq_ignore_ascii_case(name))
+    }
+
+    fn is_allowed_attribute(&self, name: &str, value: Option<&str>) -> bool {
+        if name.starts_with("on")
+            || !self
+                .config
+                .allowed_html_attributes
+                .iter()
+                .any(|attribute| attribute.eq_ignore_ascii_case(name))
+        {
+            return false;
+        }
+
+        !URL_ATTRIBUTES.contains(&name) || value.is_some_and(is_safe_url)
+    }
+
+    fn render_html_tag(&self, tag: &HtmlTag) -> String {
+        if tag.closing {
+            return format!("</{}>", tag.name);
+        }
+
+        let mut rendered = format!("<{}", tag.name);
+        for (name, value) in &tag.attributes {
+            if !self.is_allowed_attribute(name, value.as_deref()) {
+                continue;
+            }
+            let _ = match value {
+                Some(value) => write!(rendered, " {}=\"{}\"", name, escape_attribute(value)),
+                None => write!(rendered, " {}", name),
+            };
+        }
+        if tag.self_closing {
+            rendered.push_str(" /");
+        }
+        rendered.push('>');
+        rendered
+    }
+
     /// Validate file upload
     pub fn validate_file_upload(
         &self,
@@ -241,6 +559,125 @@ impl InputValidator {
     }
 }
 
+/// A parsed start or end tag
+struct HtmlTag {
+    name: String,
+    closing: bool,
+    self_closing: bool,
+    attributes: Vec<(String, Option<String>)>,
+}
+
+/// Parse the tag at the start of `input`, which begins with `<`, returning it
+/// and its length. Returns `None` when the `<` does not open a well-formed tag.
+fn parse_html_tag(input: &str) -> Option<(HtmlTag, usize)> {
+    let bytes = input.as_bytes();
+    let is_space = |pos: usize| bytes[pos].is_ascii_whitespace();
+
+    let mut pos = 1;
+    let closing = bytes.get(pos) == Some(&b'/');
+    if closing {
+        pos += 1;
+    }
+
+    let name_start = pos;
+    while pos < bytes.len() && (bytes[pos].is_ascii_alphanumeric() || bytes[pos] == b'-') {
+        pos += 1;
+    }
+    if pos == name_start || !bytes[name_start].is_ascii_alphabetic() {
+        return None;
+    }
+
+    let mut tag = HtmlTag {
+        name: input[name_start..pos].to_ascii_lowercase(),
+        closing,
+        self_closing: false,
+        attributes: Vec::new(),
+    };
+
+    loop {
+        tag.self_closing = false;
+        while pos < bytes.len() && (is_space(pos) || bytes[pos] == b'/') {
+            tag.self_closing = bytes[pos] == b'/';
+            pos += 1;
+        }
+        if *bytes.get(pos)? == b'>' {
+            return Some((tag, pos + 1));
+        }
+
+        let attribute_start = pos;
+        while pos < bytes.len() && !is_space(pos) && !matches!(bytes[pos], b'=' | b'>' | b'/') {
+            pos += 1;
+        }
+        if pos == attribute_start {
+            // A stray `=`; skip it
+            pos += 1;
+            continue;
+        }
+        let name = input[attribute_start..pos].to_ascii_lowercase();
+
+        while pos < bytes.len() && is_space(pos) {
+            pos += 1;
+        }
+        let mut value = None;
+        if bytes.get(pos) == Some(&b'=') {
+            pos += 1;
+            while pos < bytes.len() && is_space(pos) {
+                pos += 1;
+            }
+            match *bytes.get(pos)? {
+                quote @ (b'"' | b'\'') => {
+                    let end = pos + 1 + input[pos + 1..].find(quote as char)?;
+                    value = Some(input[pos + 1..end].to_string());
+                    pos = end + 1;
+                }
+                _ => {
+                    let value_start = pos;
+                    while pos < bytes.len() && !is_space(pos) && bytes[pos] != b'>' {
+                        pos += 1;
+                    }
+                    value = Some(input[value_start..pos].to_string());
+                }
+            }
+        }
+
+        tag.attributes.push((name, value));
+    }
+}
+
+/// Whether a URL attribute value is relative or uses an allowed scheme
+fn is_safe_url(value: &str) -> bool {
+    let url: String = value
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect::<String>()
+        .to_ascii_lowercase();
+
+    // Encoded characters could hide a scheme such as `javascript&#58;`
+    if url.contains("&#") || url.contains("&colon") {
+        return false;
+    }
+
+    match url.find(':') {
+        None => true,
+        Some(colon) => {
+            let scheme = &url[..colon];
+            // A colon after a path, query or fragment delimiter is not a scheme
+            scheme.contains(['/', '?', '#']) || SAFE_URL_SCHEMES.contains(&scheme)
+        }
+    }
+}
+
+fn push_html_text(output: &mut String, text: &str) {
+    output.push_str(&text.replace('>', "&gt;"));
+}
+
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -309,4 +746,191 @@ mod tests {
             .validate_file_upload("image.jpg", "text/plain", 1024)
             .is_err());
     }
+
+    #[test]
+    fn test_detect_sql_injection() {
+        let validator = InputValidator::with_defaults();
+
+        let malicious = [
+            "admin' OR '1'='1",
+            "1 OR 1=1",
+            "name'; DROP TABLE users;--",
+            "admin'--",
+            "admin'#",
+            "admin') /* bypass */",
+            r#"{"username": "admin'--", "password": "x"}"#,
+            "1 UNION ALL SELECT username, password FROM users",
+            "1; WAITFOR DELAY '0:0:5'",
+            "1 AND SLEEP(5)",
+            "1' AND 1=(SELECT COUNT(*) FROM information_schema.tables)--",
+        ];
+        for sample in malicious {
+            let findings = validator.detect_sql_injection(sample);
+            assert!(!findings.is_empty(), "missed: {}", sample);
+            assert!(findings
+                .iter()
+                .all(|f| f.kind == InjectionKind::SqlInjection));
+        }
+
+        let findings = validator.detect_sql_injection("name'; DROP TABLE users;--");
+        let stacked = findings.iter().find(|f| f.rule == "stacked_query").unwrap();
+        assert_eq!(stacked.offset, 5);
+        assert_eq!(stacked.matched, "; DROP TABLE");
+    }
+
+    #[test]
+    fn test_detect_nosql_injection() {
+        let validator = InputValidator::with_defaults();
+
+        let malicious = [
+            r#"{"username": "admin", "password": {"$ne": null}}"#,
+            "username=admin&password[$ne]=x",
+            r#"{"$where": "this.password.length > 0"}"#,
+            r#"{"age": {"$gt": ""}}"#,
+            "' || '1'=='1",
+            "x'; return true; var y='",
+            "db.users.find({})",
+        ];
+        for sample in malicious {
+            let findings = validator.detect_nosql_injection(sample);
+            assert!(!findings.is_empty(), "missed: {}", sample);
+        }
+
+        let findings = validator.detect_nosql_injection(r#"{"$where": "sleep(100)"}"#);
+        assert_eq!(findings[0].rule, "where_operator");
+        assert_eq!(findings[0].matched, "$where");
+        assert_eq!(findings[0].offset, 2);
+    }
+
+    #[test]
+    fn test_detect_xss() {
+        let validator = InputValidator::with_defaults();
+
+        let malicious = [
+            "<script>alert(1)</script>",
+            "<img src=x onerror=alert(1)>",
+            "\" onmouseover=\"alert(1)",
+            "<a href=\"javascript:alert(1)\">click</a>",
+            "javascript:alert(document.cookie)",
+            "<svg/onload=alert(1)>",
+            "<iframe src=\"https://evil.example\"></iframe>",
+            "<div style=\"width: expression(alert(1))\">",
+            "<body onload=alert(1)>",
+        ];
+        for sample in malicious {
+            let findings = validator.detect_xss(sample);
+            assert!(!findings.is_empty(), "missed: {}", sample);
+            assert!(findings.iter().all(|f| f.kind == InjectionKind::Xss));
+        }
+
+        let findings = validator.detect_xss("Hi <script>alert(1)</script>");
+        assert_eq!(findings[0].rule, "script_tag");
+        assert_eq!(findings[0].offset, 3);
+    }
+
+    #[test]
+    fn test_inspect_benign_input_has_no_findings() {
+        let validator = InputValidator::with_defaults();
+
+        let benign = [
+            "Hello, world!",
+            "O'Brien",
+            "Rock 'n' roll",
+            "Let's meet at 5; bring the drafts",
+            "Select your favourite colour from the list",
+            "SELECT statements are covered in chapter 3",
+            "Please update the README and drop me a line",
+            "The union voted to select a new leader",
+            "I'd like 1 or 2 tickets",
+            "Price: $20 or less, $5 shipping",
+            "It's great -- really",
+            "Email me at jane.doe@example.com",
+            "Use the onboarding guide, then click Save",
+            "Senior JavaScript developer wanted: React, Node",
+            "<b>bold</b> and <em>emphasis</em>",
+            "2 < 3 and 5 > 4",
+            "Order #1234 shipped to 10 Downing St.",
+            "C:\\Users\\jane\\Documents",
+            r#"{"name": "Widget", "price": 9.99, "tags": ["sale", "new"]}"#,
+            r#"{"query": "best running shoes", "page": 2, "in_stock": true}"#,
+            "Q&A: what does 'or' mean in logic?",
+            "The expression (x + 1) evaluates to 3",
+            r##"{"color":"#ff0000"}"##,
+            r###"{"title": "# Title", "body": "## Intro"}"###,
+            r#"{"args": ["ls", "--verbose"]}"#,
+            r#"{"flag":"--dry-run","comment":"/* todo */"}"#,
+        ];
+        for sample in benign {
+            let findings = validator.inspect(sample);
+            assert!(
+                findings.is_empty(),
+                "false positive on {}: {:?}",
+                sample,
+                findings
+            );
+        }
+    }
+
+    #[test]
+    fn test_inspect_url_encoded() {
+        let validator = InputValidator::with_defaults();
+
+        let findings =
+            validator.inspect_url_encoded("q=%3Cscript%3Ealert(1)%3C%2Fscript%3E&page=1");
+        assert!(findings.iter().any(|f| f.kind == InjectionKind::Xss));
+
+        let findings = validator.inspect_url_encoded("name=admin%27+OR+%271%27%3D%271");
+        assert!(findings
+            .iter()
+            .any(|f| f.kind == InjectionKind::SqlInjection));
+
+        assert!(validator
+            .inspect_url_encoded("q=running+shoes&sort=price%3Aasc")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_sanitize_html() {
+        let validator = InputValidator::with_defaults();
+
+        let sanitized = validator
+            .sanitize_html(
+                "<p onclick=\"steal()\">Hello <b>world</b><script>alert(1)</script></p>\
+                 <img src=x onerror=alert(1)><a href=\"javascript:alert(1)\" title='x\"y'>link</a>\
+                 <a href=\"https://example.com/a?b=c\">ok</a><!-- hidden -->",
+            )
+            .unwrap();
+        assert_eq!(
+            sanitized,
+            "<p>Hello <b>world</b></p><a title=\"x&quot;y\">link</a>\
+             <a href=\"https://example.com/a?b=c\">ok</a>"
+        );
+
+        // Unknown tags are removed but their text is kept
+        assert_eq!(
+            validator
+                .sanitize_html("<div><span>text</span></div> 2 < 3")
+                .unwrap(),
+            "text 2 &lt; 3"
+        );
+        assert_eq!(
+            validator
+                .sanitize_html("<a href=\"JaVaScRiPt&#58;alert(1)\">x</a><br/>")
+                .unwrap(),
+            "<a>x</a><br />"
+        );
+
+        let config = SanitizationConfig {
+            allowed_html_tags: vec!["img".to_string()],
+            allowed_html_attributes: vec!["src".to_string(), "alt".to_string()],
+            ..SanitizationConfig::default()
+        };
+        let validator = InputValidator::new(config);
+        assert_eq!(
+            validator
+                .sanitize_html("<img src=\"/logo.png\" alt=\"Logo\" onerror=\"x()\"><p>caption</p>")
+                .unwrap(),
+            "<img src=\"/logo.png\" alt=\"Logo\">caption"
+        );
+    }
 }
@@ -51,6 +51,7 @@ pub mod jwt;
 // Temporarily disabled due to Send trait issues
 // pub mod middleware;
 pub mod middleware_simple;
+pub mod password_strength;
 pub mod rate_limiting;
 pub mod rbac;
 pub mod threat_detection;
@@ -71,14 +72,18 @@ pub use service::SecurityService;
 // Re-export commonly used types and traits
 pub use audit::{AuditLevel, AuditLogger, SecurityEvent};
 pub use encryption::{EncryptionService, KeyManager, PasswordService};
-pub use input_validation::{InputValidator, SanitizationConfig};
-pub use jwt::{AccessToken, JwtClaims, JwtService, RefreshToken};
+pub use input_validation::{InjectionKind, InputFinding, InputValidator, SanitizationConfig};
+pub use jwt::{AccessToken, JwkSet, JwtClaims, JwtService, RefreshToken, SigningKeyMaterial};
 // Temporarily disabled due to Send trait issues
 // pub use middleware::{AuthenticationLayer, AuthorizationLayer, SecurityMiddleware};
 pub use middleware_simple::SimpleSecurityMiddleware;
-pub use rate_limiting::{RateLimitConfig, RateLimitResult, RateLimiter};
+pub use password_strength::PasswordStrength;
+pub use rate_limiting::{
+    DistributedRateLimitConfig, RateLimitConfig, RateLimitKey, RateLimitResult, RateLimiter,
+    RedisRateLimiter, TokenBucketLimit,
+};
 pub use rbac::{PermissionCache, RbacService, RoleRepository};
-pub use threat_detection::{SecurityAlert, ThreatDetector, ThreatLevel};
+pub use threat_detection::{LoginIdentifier, SecurityAlert, ThreatDetector, ThreatLevel};
 
 // Security constants
 pub mod constants {
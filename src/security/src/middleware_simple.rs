@@ -203,6 +203,7 @@ impl SimpleSecurityMiddleware {
             refresh_token_ttl: chrono::TimeDelta::from_std(security_config.jwt.refresh_token_ttl)
                 .unwrap(),
             algorithm: jsonwebtoken::Algorithm::HS256,
+            private_key_pem: None,
             enable_blacklist: security_config.jwt.enable_blacklist,
             max_tokens_per_user: security_config.jwt.max_tokens_per_user,
         };
@@ -0,0 +1,705 @@
+//! Password Strength Estimation
+//!
+//! Estimates how guessable a password is rather than checking character classes.
+//! The password is split into the cheapest tokens an attacker would try: common
+//! passwords (including `@`-for-`a` style substitutions), sequences, repeated
+//! characters and keyboard walks each cost a few bits, and only the remaining
+//! characters are priced by the size of their character set.
+
+use crate::constants::{MAX_PASSWORD_LENGTH, MIN_PASSWORD_LENGTH};
+use crate::encryption::PasswordStrengthLevel;
+use serde::{Deserialize, Serialize};
+
+/// Entropy (bits) needed for scores 1 to 4
+const SCORE_THRESHOLDS: [f64; 4] = [30.0, 45.0, 60.0, 75.0];
+
+/// Shortest run treated as a sequence or repeat, and as a keyboard walk
+const MIN_RUN_LENGTH: usize = 3;
+const MIN_KEYBOARD_RUN_LENGTH: usize = 4;
+
+/// Shortest and longest common-password match looked up inside a password
+const MIN_COMMON_LENGTH: usize = 4;
+const MAX_COMMON_LENGTH: usize = 16;
+
+/// Rows and columns of a US keyboard; neighbours on a line are adjacent keys
+const KEYBOARD_LINES: &[&str] = &[
+    "`1234567890-=",
+    "qwertyuiop[]\\",
+    "asdfghjkl;'",
+    "zxcvbnm,./",
+    "1qaz",
+    "2wsx",
+    "3edc",
+    "4rfv",
+    "5tgb",
+    "6yhn",
+    "7ujm",
+    "8ik,",
+    "9ol.",
+    "0p;/",
+];
+
+/// Most common passwords, lowercase and sorted for binary search
+const COMMON_PASSWORDS: &[&str] = &[
+    "000000",
+    "0987654321",
+    "101010",
+    "1111",
+    "111111",
+    "11111111",
+    "112211",
+    "112233",
+    "11223344",
+    "1212",
+    "121212",
+    "121314",
+    "123123",
+    "123321",
+    "1234",
+    "12341234",
+    "12344321",
+    "12345",
+    "123456",
+    "1234567",
+    "12345678",
+    "123456789",
+    "1234567890",
+    "123654",
+    "123abc",
+    "123qwe",
+    "131313",
+    "147258",
+    "147258369",
+    "159357",
+    "159753",
+    "1q2w3e",
+    "1q2w3e4r",
+    "1q2w3e4r5t",
+    "1qaz2wsx",
+    "1qazxsw2",
+    "2000",
+    "2020",
+    "202020",
+    "2021",
+    "2022",
+    "2023",
+    "2024",
+    "2025",
+    "456789",
+    "555555",
+    "654321",
+    "666666",
+    "6969",
+    "696969",
+    "741852963",
+    "777777",
+    "7777777",
+    "789456",
+    "789456123",
+    "888888",
+    "98765",
+    "987654",
+    "987654321",
+    "999999",
+    "a1b2c3",
+    "aaaaaa",
+    "aaaaaa1",
+    "abc123",
+    "abc123456",
+    "abcabc",
+    "abcd1234",
+    "abcdef",
+    "access",
+    "admin",
+    "admin1",
+    "admin123",
+    "admin1234",
+    "administrator",
+    "amanda",
+    "amber",
+    "andrew",
+    "andrew1",
+    "angel",
+    "angel1",
+    "apple",
+    "arsenal",
+    "asd123",
+    "asdf",
+    "asdf1234",
+    "asdfasdf",
+    "asdfgh",
+    "asdfghjkl",
+    "ashley",
+    "ashley1",
+    "austin",
+    "autumn2024",
+    "azerty",
+    "babygirl",
+    "banana",
+    "barcelona",
+    "baseball",
+    "baseball1",
+    "batman",
+    "batman1",
+    "beautiful",
+    "biteme",
+    "blank",
+    "blessed",
+    "buster",
+    "butterfly",
+    "changeme",
+    "charlie",
+    "charlie1",
+    "cheese",
+    "cheese1",
+    "chelsea",
+    "chelsea1",
+    "chicken",
+    "chocolate",
+    "christ",
+    "coffee",
+    "computer",
+    "computer1",
+    "cookie",
+    "cowboys",
+    "dallas",
+    "daniel",
+    "daniel1",
+    "database",
+    "december",
+    "default",
+    "demo",
+    "diamond",
+    "dolphin",
+    "dragon",
+    "dragon1",
+    "dragons",
+    "eagles",
+    "emily",
+    "faith",
+    "family",
+    "february",
+    "flower",
+    "football",
+    "football1",
+    "forever",
+    "fortnite",
+    "freedom",
+    "friday",
+    "friends",
+    "george",
+    "ginger",
+    "golden",
+    "google",
+    "guest",
+    "hannah",
+    "harley",
+    "heaven",
+    "hello",
+    "hello123",
+    "helloworld",
+    "hockey",
+    "hunter",
+    "hunter2",
+    "iloveu",
+    "iloveyou",
+    "iloveyou1",
+    "internet",
+    "ironman",
+    "january",
+    "jasmine",
+    "jennifer",
+    "jessica",
+    "jessica1",
+    "jesus",
+    "jordan",
+    "jordan23",
+    "joshua",
+    "joshua1",
+    "justin",
+    "justin1",
+    "juventus",
+    "killer",
+    "klaster",
+    "lakers",
+    "lauren",
+    "letmein",
+    "letmein1",
+    "letmein123",
+    "linux",
+    "lion",
+    "liverpool",
+    "lkjhgfdsa",
+    "login",
+    "love",
+    "lovely",
+    "loveme",
+    "loveyou",
+    "maggie",
+    "manchester",
+    "master",
+    "master1",
+    "master123",
+    "matrix",
+    "matthew",
+    "michael",
+    "michael1",
+    "michelle",
+    "michelle1",
+    "microsoft",
+    "minecraft",
+    "mnbvcxz",
+    "mobilemail",
+    "monday",
+    "monitor",
+    "monitoring",
+    "monkey",
+    "monkey1",
+    "monkeys",
+    "montana",
+    "moon",
+    "moscow",
+    "mustang",
+    "mypass",
+    "mypassword",
+    "mysql",
+    "naruto",
+    "newpass",
+    "newpassword",
+    "nicole",
+    "nicole1",
+    "nopass",
+    "nothing",
+    "office",
+    "oldpassword",
+    "olivia",
+    "oracle",
+    "orange",
+    "p@ssw0rd",
+    "packers",
+    "pass",
+    "pass123",
+    "pass1234",
+    "passpass",
+    "passw0rd",
+    "passw0rd1",
+    "password",
+    "password1",
+    "password12",
+    "password123",
+    "password1234",
+    "patriots",
+    "pepper",
+    "pepper1",
+    "pizza",
+    "poiuytrewq",
+    "pokemon",
+    "postgres",
+    "princess",
+    "princess1",
+    "purple",
+    "q1w2e3r4",
+    "qazwsx",
+    "qazwsxedc",
+    "qqqqqq",
+    "qwe123",
+    "qweasd",
+    "qweasdzxc",
+    "qwerty",
+    "qwerty1",
+    "qwerty123",
+    "qwertyu",
+    "qwertyuiop",
+    "qwertz",
+    "rainbow",
+    "ranger",
+    "realmadrid",
+    "robert",
+    "root",
+    "root123",
+    "samantha",
+    "samsung",
+    "secret",
+    "secret1",
+    "secret123",
+    "server",
+    "shadow",
+    "shadow1",
+    "silver",
+    "soccer",
+    "sophie",
+    "spiderman",
+    "spring2024",
+    "starwars",
+    "starwars1",
+    "steelers",
+    "summer",
+    "summer2023",
+    "summer2024",
+    "sunday",
+    "sunshine",
+    "sunshine1",
+    "superman",
+    "superman1",
+    "taylor",
+    "temp",
+    "temp123",
+    "temppass",
+    "test",
+    "test123",
+    "tester",
+    "testing",
+    "thomas",
+    "thunder",
+    "tiffany",
+    "tiger",
+    "tigger",
+    "toor",
+    "trustno1",
+    "united",
+    "user",
+    "user123",
+    "welcome",
+    "welcome1",
+    "welcome123",
+    "whatever",
+    "whatever1",
+    "william",
+    "william1",
+    "windows",
+    "winter2024",
+    "xxxxxx",
+    "yankees",
+    "yankees1",
+    "yellow",
+    "yourpassword",
+    "zaq12wsx",
+    "zaqxsw",
+    "zxc123",
+    "zxcvbn",
+    "zxcvbnm",
+    "zzzzzz",
+];
+
+/// Estimated strength of a password with feedback for the user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasswordStrength {
+    /// 0 (trivially guessable) to 4 (very hard to guess)
+    pub score: u8,
+    /// Estimated entropy after discounting guessable patterns
+    pub entropy_bits: f64,
+    /// What makes the password guessable
+    pub warnings: Vec<String>,
+    /// How to make it stronger
+    pub suggestions: Vec<String>,
+}
+
+impl PasswordStrength {
+    /// The strength level matching the score
+    pub fn level(&self) -> PasswordStrengthLevel {
+        match self.score {
+            0 => PasswordStrengthLevel::VeryWeak,
+            1 => PasswordStrengthLevel::Weak,
+            2 => PasswordStrengthLevel::Medium,
+            3 => PasswordStrengthLevel::Strong,
+            _ => PasswordStrengthLevel::VeryStrong,
+        }
+    }
+}
+
+/// Guessable pattern found in a password
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pattern {
+    Common,
+    Sequence,
+    Repeat,
+    Keyboard,
+}
+
+/// Estimate how hard `password` is to guess
+pub fn estimate(password: &str) -> PasswordStrength {
+    let chars: Vec<char> = password.chars().collect();
+    let lowercase: Vec<char> = chars.iter().flat_map(|c| c.to_lowercase()).collect();
+    // Lowercasing can change the length of non-ASCII text; only match patterns
+    // when positions still line up
+    let lowercase = if lowercase.len() == chars.len() {
+        lowercase
+    } else {
+        chars.clone()
+    };
+    let unleeted: Vec<char> = lowercase.iter().map(|c| unleet(*c)).collect();
+    let char_bits = charset_size(&chars).log2();
+
+    let mut entropy_bits = 0.0;
+    let mut patterns = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        // On equal lengths the later, more specific pattern wins
+        let found = [
+            keyboard_length(&lowercase, i).map(|len| (Pattern::Keyboard, len)),
+            repeat_length(&lowercase, i).map(|len| (Pattern::Repeat, len)),
+            sequence_length(&lowercase, i).map(|len| (Pattern::Sequence, len)),
+            common_match(&lowercase, &unleeted, i).map(|len| (Pattern::Common, len)),
+        ]
+        .into_iter()
+        .flatten()
+        .max_by_key(|(_, len)| *len);
+
+        match found {
+            Some((pattern, len)) => {
+                entropy_bits += match pattern {
+                    // Which list entry, plus whether it was capitalized or substituted
+                    Pattern::Common => {
+                        (COMMON_PASSWORDS.len() as f64).log2()
+                            + f64::from(u8::from(chars[i..i + len] != unleeted[i..i + len]))
+                    }
+                    // Where it starts, how long it runs and which direction
+                    _ => char_bits + (len as f64).log2() + 1.0,
+                };
+                if !patterns.contains(&pattern) {
+                    patterns.push(pattern);
+                }
+                i += len;
+            }
+            None => {
+                entropy_bits += char_bits;
+                i += 1;
+            }
+        }
+    }
+
+    let score = SCORE_THRESHOLDS
+        .iter()
+        .filter(|threshold| entropy_bits >= **threshold)
+        .count() as u8;
+
+    let (warnings, suggestions) = feedback(chars.len(), score, &patterns);
+    PasswordStrength {
+        score,
+        entropy_bits,
+        warnings,
+        suggestions,
+    }
+}
+
+fn feedback(length: usize, score: u8, patterns: &[Pattern]) -> (Vec<String>, Vec<String>) {
+    let mut warnings = Vec::new();
+    let mut suggestions = Vec::new();
+
+    if length < MIN_PASSWORD_LENGTH {
+        warnings.push(format!(
+            "Password is shorter than {} characters",
+            MIN_PASSWORD_LENGTH
+        ));
+    }
+    if length > MAX_PASSWORD_LENGTH {
+        warnings.push(format!(
+            "Password is longer than {} characters",
+            MAX_PASSWORD_LENGTH
+        ));
+    }
+
+    for pattern in patterns {
+        let (warning, suggestion) = match pattern {
+            Pattern::Common => (
+                "Contains a commonly used password",
+                "Avoid common passwords, even with substitutions like '@' for 'a'",
+            ),
+            Pattern::Sequence => (
+                "Contains a sequence like 'abc' or '6543'",
+                "Avoid sequences of letters or numbers",
+            ),
+            Pattern::Repeat => (
+                "Contains repeated characters like 'aaa'",
+                "Avoid repeating the same character",
+            ),
+            Pattern::Keyboard => (
+                "Contains a keyboard pattern like 'qwerty' or '1qaz'",
+                "Avoid runs of neighbouring keys",
+            ),
+        };
+        warnings.push(warning.to_string());
+        suggestions.push(suggestion.to_string());
+    }
+
+    if score < 3 {
+        suggestions.push(
+            "Use a longer password; several uncommon words together are strong and memorable"
+                .to_string(),
+        );
+    }
+
+    (warnings, suggestions)
+}
+
+/// Size of the alphabet an attacker would brute-force the password over
+fn charset_size(chars: &[char]) -> f64 {
+    let mut size = 0.0;
+    if chars.iter().any(|c| c.is_ascii_lowercase()) {
+        size += 26.0;
+    }
+    if chars.iter().any(|c| c.is_ascii_uppercase()) {
+        size += 26.0;
+    }
+    if chars.iter().any(|c| c.is_ascii_digit()) {
+        size += 10.0;
+    }
+    if chars
+        .iter()
+        .any(|c| c.is_ascii() && !c.is_ascii_alphanumeric())
+    {
+        size += 33.0;
+    }
+    if chars.iter().any(|c| !c.is_ascii()) {
+        size += 100.0;
+    }
+    f64::max(size, 2.0)
+}
+
+/// The letter a common substitution stands for
+fn unleet(c: char) -> char {
+    match c {
+        '@' | '4' => 'a',
+        '8' => 'b',
+        '(' => 'c',
+        '3' => 'e',
+        '6' | '9' => 'g',
+        '1' | '!' | '|' => 'i',
+        '0' => 'o',
+        '$' | '5' => 's',
+        '7' | '+' => 't',
+        '2' => 'z',
+        other => other,
+    }
+}
+
+fn is_common(candidate: &[char]) -> bool {
+    let candidate: String = candidate.iter().collect();
+    COMMON_PASSWORDS.binary_search(&candidate.as_str()).is_ok()
+}
+
+/// Length of the longest common password starting at `start`, as typed or
+/// with substitutions undone
+fn common_match(lowercase: &[char], unleeted: &[char], start: usize) -> Option<usize> {
+    let longest = (lowercase.len() - start).min(MAX_COMMON_LENGTH);
+    (MIN_COMMON_LENGTH..=longest).rev().find(|len| {
+        is_common(&lowercase[start..start + len]) || is_common(&unleeted[start..start + len])
+    })
+}
+
+/// Length of an ascending or descending run like "abcd" or "9876"
+fn sequence_length(chars: &[char], start: usize) -> Option<usize> {
+    let step = |a: char, b: char| b as i64 - a as i64;
+    let first = *chars.get(start)?;
+    let second = *chars.get(start + 1)?;
+    let direction = step(first, second);
+    if direction.abs() != 1 || !first.is_alphanumeric() || !second.is_alphanumeric() {
+        return None;
+    }
+
+    let len = 2 + chars[start + 1..]
+        .windows(2)
+        .take_while(|pair| pair[1].is_alphanumeric() && step(pair[0], pair[1]) == direction)
+        .count();
+    (len >= MIN_RUN_LENGTH).then_some(len)
+}
+
+/// Length of a run of the same character
+fn repeat_length(chars: &[char], start: usize) -> Option<usize> {
+    let first = chars[start];
+    let len = chars[start..].iter().take_while(|c| **c == first).count();
+    (len >= MIN_RUN_LENGTH).then_some(len)
+}
+
+fn keyboard_adjacent(a: char, b: char) -> bool {
+    KEYBOARD_LINES.iter().any(|line| {
+        line.chars()
+            .zip(line.chars().skip(1))
+            .any(|(x, y)| (x, y) == (a, b) || (y, x) == (a, b))
+    })
+}
+
+/// Length of a walk over neighbouring keys like "qwerty" or "1qaz2wsx"
+fn keyboard_length(chars: &[char], start: usize) -> Option<usize> {
+    // "1qaz2wsx" jumps between columns, so allow any neighbour along any line
+    let len = 1 + chars[start..]
+        .windows(2)
+        .take_while(|pair| keyboard_adjacent(pair[0], pair[1]) || column_jump(pair[0], pair[1]))
+        .count();
+    (len >= MIN_KEYBOARD_RUN_LENGTH).then_some(len)
+}
+
+/// From the bottom of one keyboard column to the top of the next, as in "1qaz2wsx"
+fn column_jump(a: char, b: char) -> bool {
+    let columns = &KEYBOARD_LINES[4..];
+    columns.windows(2).any(|pair| {
+        (pair[0].ends_with(a) && pair[1].starts_with(b))
+            || (pair[1].ends_with(a) && pair[0].starts_with(b))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_common_password_list_is_sorted() {
+        assert!(COMMON_PASSWORDS.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(COMMON_PASSWORDS
+            .iter()
+            .all(|password| *password == password.to_lowercase()));
+    }
+
+    #[test]
+    fn test_common_passwords_are_weak_despite_length_and_classes() {
+        for password in ["Password123!", "P@ssw0rd2024", "Welcome123456"] {
+            let strength = estimate(password);
+            assert!(
+                strength.score <= 1,
+                "{} scored {}",
+                password,
+                strength.score
+            );
+            assert!(strength
+                .warnings
+                .contains(&"Contains a commonly used password".to_string()));
+        }
+    }
+
+    #[test]
+    fn test_detects_sequences_repeats_and_keyboard_walks() {
+        let has_warning = |password: &str, fragment: &str| {
+            estimate(password)
+                .warnings
+                .iter()
+                .any(|warning| warning.contains(fragment))
+        };
+
+        assert!(has_warning("xbcdefghijk9", "sequence"));
+        assert!(has_warning("Zq98765432Lm", "sequence"));
+        assert!(has_warning("aaaaaaaaaaaaaaa", "repeated"));
+        assert!(has_warning("Zqwsxcderfvm", "keyboard"));
+        assert!(has_warning("1qaz2wsx3edc", "keyboard"));
+        assert!(estimate("abcdefghijklmnop").score == 0);
+    }
+
+    #[test]
+    fn test_random_and_passphrase_passwords_are_strong() {
+        let strength = estimate("vT9#mQ2!xLp7$wRz");
+        assert_eq!(strength.score, 4);
+        assert!(strength.warnings.is_empty());
+
+        let strength = estimate("correct horse battery staple");
+        assert!(matches!(
+            strength.level(),
+            PasswordStrengthLevel::Strong | PasswordStrengthLevel::VeryStrong
+        ));
+    }
+
+    #[test]
+    fn test_short_passwords_get_length_feedback() {
+        let strength = estimate("x7#Lq");
+        assert!(strength.warnings[0].contains("shorter than"));
+        assert!(!strength.suggestions.is_empty());
+    }
+}
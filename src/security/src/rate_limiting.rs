@@ -1,14 +1,24 @@
 //! Rate Limiting Module
 //!
-//! Provides rate limiting capabilities for API endpoints and users.
-
-use crate::errors::SecurityResult;
+//! Provides rate limiting capabilities for API endpoints and users. `RateLimiter`
+//! keeps state in process memory; `RedisRateLimiter` keeps token buckets in Redis
+//! so limits hold across every gateway instance.
+
+use crate::config::{RateLimitFailurePolicy, RateLimitingConfig};
+use crate::errors::{SecurityError, SecurityResult};
+use ai_core_shared::rate_limit::{
+    HEADER_LIMIT, HEADER_REMAINING, HEADER_RESET, HEADER_RETRY_AFTER,
+};
+use redis::aio::MultiplexedConnection;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::fmt;
 use std::net::IpAddr;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
-use tokio::sync::RwLock;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::{Mutex, RwLock};
+use tracing::warn;
 
 /// Rate limit configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,16 +54,78 @@ impl Default for RateLimitConfig {
 #[derive(Debug, Clone)]
 pub enum RateLimitResult {
     /// Request allowed
-    Allowed,
+    Allowed {
+        /// Requests allowed per window (bucket capacity for token buckets)
+        limit: u32,
+        /// Requests left before the limit is hit
+        remaining: u32,
+        /// Time until the limit is fully replenished
+        reset_after: Duration,
+    },
     /// Rate limit exceeded
     Exceeded {
         /// Time until reset
         retry_after: Duration,
         /// Limit type that was exceeded
         limit_type: String,
+        /// Requests allowed per window (bucket capacity for token buckets)
+        limit: u32,
     },
 }
 
+impl RateLimitResult {
+    /// Whether the request may proceed
+    pub fn is_allowed(&self) -> bool {
+        matches!(self, RateLimitResult::Allowed { .. })
+    }
+
+    /// Requests left before the limit is hit
+    pub fn remaining(&self) -> u32 {
+        match self {
+            RateLimitResult::Allowed { remaining, .. } => *remaining,
+            RateLimitResult::Exceeded { .. } => 0,
+        }
+    }
+
+    /// Time until the limit resets, or until a rejected request may retry
+    pub fn reset_after(&self) -> Duration {
+        match self {
+            RateLimitResult::Allowed { reset_after, .. } => *reset_after,
+            RateLimitResult::Exceeded { retry_after, .. } => *retry_after,
+        }
+    }
+
+    /// `X-RateLimit-*` response headers, plus `Retry-After` when rejected
+    pub fn headers(&self) -> Vec<(&'static str, String)> {
+        let (limit, retry_after) = match self {
+            RateLimitResult::Allowed { limit, .. } => (*limit, None),
+            RateLimitResult::Exceeded {
+                limit, retry_after, ..
+            } => (*limit, Some(*retry_after)),
+        };
+        let reset_at = (SystemTime::now() + self.reset_after())
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let mut headers = vec![
+            (HEADER_LIMIT, limit.to_string()),
+            (HEADER_REMAINING, self.remaining().to_string()),
+            (HEADER_RESET, reset_at.to_string()),
+        ];
+        if let Some(retry_after) = retry_after {
+            headers.push((HEADER_RETRY_AFTER, ceil_seconds(retry_after).to_string()));
+        }
+        headers
+    }
+}
+
+/// Whole seconds, rounded up and never zero
+fn ceil_seconds(duration: Duration) -> u64 {
+    let seconds = duration.as_secs() + u64::from(duration.subsec_nanos() > 0);
+    seconds.max(1)
+}
+
 /// Rate limit entry
 #[derive(Debug, Clone)]
 struct RateLimitEntry {
@@ -109,6 +181,7 @@ impl RateLimitEntry {
             return RateLimitResult::Exceeded {
                 retry_after,
                 limit_type: "requests_per_minute".to_string(),
+                limit: burst_limit_minute,
             };
         }
 
@@ -118,10 +191,23 @@ impl RateLimitEntry {
             return RateLimitResult::Exceeded {
                 retry_after,
                 limit_type: "requests_per_hour".to_string(),
+                limit: config.requests_per_hour,
             };
         }
 
-        RateLimitResult::Allowed
+        // Counts exclude the request being admitted, which `update` records
+        RateLimitResult::Allowed {
+            limit: burst_limit_minute,
+            remaining: burst_limit_minute
+                .saturating_sub(self.requests_this_minute + 1)
+                .min(
+                    config
+                        .requests_per_hour
+                        .saturating_sub(self.requests_this_hour + 1),
+                ),
+            reset_after: Duration::from_secs(60)
+                .saturating_sub(Instant::now().duration_since(self.minute_window_start)),
+        }
     }
 
     fn is_expired(&self, cleanup_threshold: Duration) -> bool {
@@ -153,10 +239,19 @@ impl RateLimiter {
         Self::new(RateLimitConfig::default())
     }
 
+    /// Result for checks whose limiting is disabled
+    fn unlimited(&self) -> RateLimitResult {
+        RateLimitResult::Allowed {
+            limit: self.config.requests_per_minute,
+            remaining: self.config.requests_per_minute,
+            reset_after: Duration::from_secs(60),
+        }
+    }
+
     /// Check rate limit for user
     pub async fn check_user_limit(&self, user_id: &str) -> SecurityResult<RateLimitResult> {
         if !self.config.per_user_limiting {
-            return Ok(RateLimitResult::Allowed);
+            return Ok(self.unlimited());
         }
 
         let mut limits = self.user_limits.write().await;
@@ -167,19 +262,16 @@ impl RateLimiter {
         let now = Instant::now();
         let result = entry.check_limits(&self.config);
 
-        match result {
-            RateLimitResult::Allowed => {
-                entry.update(now);
-                Ok(RateLimitResult::Allowed)
-            }
-            exceeded => Ok(exceeded),
+        if result.is_allowed() {
+            entry.update(now);
         }
+        Ok(result)
     }
 
     /// Check rate limit for IP address
     pub async fn check_ip_limit(&self, ip: IpAddr) -> SecurityResult<RateLimitResult> {
         if !self.config.per_ip_limiting {
-            return Ok(RateLimitResult::Allowed);
+            return Ok(self.unlimited());
         }
 
         let mut limits = self.ip_limits.write().await;
@@ -188,13 +280,10 @@ impl RateLimiter {
         let now = Instant::now();
         let result = entry.check_limits(&self.config);
 
-        match result {
-            RateLimitResult::Allowed => {
-                entry.update(now);
-                Ok(RateLimitResult::Allowed)
-            }
-            exceeded => Ok(exceeded),
+        if result.is_allowed() {
+            entry.update(now);
         }
+        Ok(result)
     }
 
     /// Check rate limit for endpoint
@@ -207,13 +296,10 @@ impl RateLimiter {
         let now = Instant::now();
         let result = entry.check_limits(&self.config);
 
-        match result {
-            RateLimitResult::Allowed => {
-                entry.update(now);
-                Ok(RateLimitResult::Allowed)
-            }
-            exceeded => Ok(exceeded),
+        if result.is_allowed() {
+            entry.update(now);
         }
+        Ok(result)
     }
 
     /// Get current stats for user
@@ -281,6 +367,302 @@ impl RateLimiter {
     }
 }
 
+/// Token bucket parameters: bursts of up to `capacity` requests, refilled
+/// continuously at `refill_per_second`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TokenBucketLimit {
+    pub capacity: u32,
+    pub refill_per_second: f64,
+}
+
+impl TokenBucketLimit {
+    /// Bucket sustaining `requests_per_minute` with bursts scaled by `burst_multiplier`
+    pub fn per_minute(requests_per_minute: u32, burst_multiplier: f64) -> Self {
+        Self {
+            capacity: ((requests_per_minute as f64 * burst_multiplier) as u32).max(1),
+            refill_per_second: requests_per_minute as f64 / 60.0,
+        }
+    }
+}
+
+/// Identity a distributed limit is tracked under
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RateLimitKey {
+    Ip(IpAddr),
+    User(String),
+    ApiKey(String),
+}
+
+impl RateLimitKey {
+    fn scope(&self) -> &'static str {
+        match self {
+            RateLimitKey::Ip(_) => "ip",
+            RateLimitKey::User(_) => "user",
+            RateLimitKey::ApiKey(_) => "api_key",
+        }
+    }
+
+    /// Identifier used in Redis keys. API keys are hashed so credentials are
+    /// never written to Redis.
+    fn storage_id(&self) -> String {
+        match self {
+            RateLimitKey::Ip(ip) => ip.to_string(),
+            RateLimitKey::User(user_id) => user_id.clone(),
+            RateLimitKey::ApiKey(api_key) => format!("{:x}", Sha256::digest(api_key.as_bytes())),
+        }
+    }
+}
+
+impl fmt::Display for RateLimitKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RateLimitKey::Ip(ip) => write!(f, "ip:{}", ip),
+            RateLimitKey::User(user_id) => write!(f, "user:{}", user_id),
+            RateLimitKey::ApiKey(api_key) => write!(f, "api_key:{}", api_key),
+        }
+    }
+}
+
+/// Distributed rate limit configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DistributedRateLimitConfig {
+    /// Prefix for every bucket key in Redis
+    pub key_prefix: String,
+    /// Limit applied per client IP
+    pub ip_limit: TokenBucketLimit,
+    /// Limit applied per authenticated user
+    pub user_limit: TokenBucketLimit,
+    /// Limit applied per API key
+    pub api_key_limit: TokenBucketLimit,
+    /// Limits for individual keys, keyed by `ip:<addr>`, `user:<id>` or `api_key:<key>`
+    pub key_overrides: HashMap<String, TokenBucketLimit>,
+    /// Behaviour when Redis is unreachable or slow
+    pub failure_policy: RateLimitFailurePolicy,
+    /// Longest a check may wait on Redis before the failure policy applies
+    pub redis_timeout: Duration,
+}
+
+impl Default for DistributedRateLimitConfig {
+    fn default() -> Self {
+        Self::from(&RateLimitingConfig::default())
+    }
+}
+
+impl From<&RateLimitingConfig> for DistributedRateLimitConfig {
+    fn from(config: &RateLimitingConfig) -> Self {
+        let limit =
+            TokenBucketLimit::per_minute(config.requests_per_minute, config.burst_multiplier);
+        Self {
+            key_prefix: "rate_limit:bucket".to_string(),
+            ip_limit: limit,
+            user_limit: limit,
+            api_key_limit: limit,
+            key_overrides: HashMap::new(),
+            failure_policy: config.failure_policy,
+            redis_timeout: Duration::from_millis(250),
+        }
+    }
+}
+
+/// Refills the bucket for the time elapsed since it was last touched, then takes
+/// `cost` tokens if enough are available. Runs atomically in Redis and reads the
+/// clock from Redis, so every instance sees the same bucket and time.
+///
+/// KEYS[1] bucket key; ARGV[1] capacity, ARGV[2] refill per millisecond, ARGV[3] cost.
+/// Returns {allowed, remaining tokens, retry after ms, reset after ms}.
+const TOKEN_BUCKET_SCRIPT: &str = r#"
+redis.replicate_commands()
+
+local capacity = tonumber(ARGV[1])
+local refill_per_ms = tonumber(ARGV[2])
+local cost = tonumber(ARGV[3])
+
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+
+local bucket = redis.call('HMGET', KEYS[1], 'tokens', 'updated_at')
+local tokens = tonumber(bucket[1])
+local updated_at = tonumber(bucket[2])
+if tokens == nil or updated_at == nil then
+    tokens = capacity
+    updated_at = now
+end
+
+tokens = math.min(capacity, tokens + math.max(0, now - updated_at) * refill_per_ms)
+
+local allowed = 0
+local retry_after = 0
+if tokens >= cost then
+    tokens = tokens - cost
+    allowed = 1
+else
+    retry_after = math.ceil((cost - tokens) / refill_per_ms)
+end
+
+local reset_after = math.ceil((capacity - tokens) / refill_per_ms)
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'updated_at', now)
+redis.call('PEXPIRE', KEYS[1], math.max(reset_after, 1000))
+
+return {allowed, math.floor(tokens), retry_after, reset_after}
+"#;
+
+/// Token-bucket rate limiter with state in Redis, shared by every instance
+pub struct RedisRateLimiter {
+    client: Arc<redis::Client>,
+    connection: Mutex<Option<MultiplexedConnection>>,
+    script: redis::Script,
+    config: DistributedRateLimitConfig,
+}
+
+impl RedisRateLimiter {
+    /// Create a limiter; Redis is connected lazily on the first check
+    pub fn new(client: Arc<redis::Client>, config: DistributedRateLimitConfig) -> Self {
+        Self {
+            client,
+            connection: Mutex::new(None),
+            script: redis::Script::new(TOKEN_BUCKET_SCRIPT),
+            config,
+        }
+    }
+
+    /// Limit that applies to `key`
+    pub fn limit_for(&self, key: &RateLimitKey) -> TokenBucketLimit {
+        if let Some(limit) = self.config.key_overrides.get(&key.to_string()) {
+            return *limit;
+        }
+
+        match key {
+            RateLimitKey::Ip(_) => self.config.ip_limit,
+            RateLimitKey::User(_) => self.config.user_limit,
+            RateLimitKey::ApiKey(_) => self.config.api_key_limit,
+        }
+    }
+
+    /// Take one token for `key`
+    pub async fn check(&self, key: &RateLimitKey) -> SecurityResult<RateLimitResult> {
+        self.check_cost(key, 1).await
+    }
+
+    /// Take `cost` tokens for `key`, applying the failure policy if Redis is
+    /// unavailable
+    pub async fn check_cost(
+        &self,
+        key: &RateLimitKey,
+        cost: u32,
+    ) -> SecurityResult<RateLimitResult> {
+        let limit = self.limit_for(key);
+        if limit.capacity == 0 || limit.refill_per_second <= 0.0 {
+            return Err(SecurityError::RateLimitConfig(format!(
+                "Invalid token bucket for {}: capacity {}, refill {}/s",
+                key.scope(),
+                limit.capacity,
+                limit.refill_per_second
+            )));
+        }
+
+        let outcome = tokio::time::timeout(
+            self.config.redis_timeout,
+            self.take_tokens(key, limit, cost),
+        )
+        .await;
+
+        let error = match outcome {
+            Ok(Ok(result)) => return Ok(result),
+            Ok(Err(e)) => e.to_string(),
+            Err(_) => format!("timed out after {:?}", self.config.redis_timeout),
+        };
+
+        // Reconnect on the next check rather than reuse a broken connection
+        *self.connection.lock().await = None;
+        warn!(
+            "Rate limit storage unavailable for {} ({}), applying {:?}",
+            key.scope(),
+            error,
+            self.config.failure_policy
+        );
+
+        Ok(match self.config.failure_policy {
+            RateLimitFailurePolicy::FailOpen => RateLimitResult::Allowed {
+                limit: limit.capacity,
+                remaining: limit.capacity,
+                reset_after: Duration::ZERO,
+            },
+            RateLimitFailurePolicy::FailClosed => RateLimitResult::Exceeded {
+                retry_after: Duration::from_secs(1),
+                limit_type: "rate_limit_storage_unavailable".to_string(),
+                limit: limit.capacity,
+            },
+        })
+    }
+
+    /// Refill `key`'s bucket to capacity (for testing or admin purposes)
+    pub async fn reset(&self, key: &RateLimitKey) -> SecurityResult<()> {
+        let mut connection = self.connection().await?;
+        redis::cmd("DEL")
+            .arg(self.redis_key(key))
+            .query_async::<_, ()>(&mut connection)
+            .await
+            .map_err(|e| SecurityError::RateLimitStorage(e.to_string()))
+    }
+
+    async fn take_tokens(
+        &self,
+        key: &RateLimitKey,
+        limit: TokenBucketLimit,
+        cost: u32,
+    ) -> SecurityResult<RateLimitResult> {
+        let mut connection = self.connection().await?;
+        let (allowed, remaining, retry_after_ms, reset_after_ms): (i64, i64, i64, i64) = self
+            .script
+            .key(self.redis_key(key))
+            .arg(limit.capacity)
+            .arg(limit.refill_per_second / 1000.0)
+            .arg(cost)
+            .invoke_async(&mut connection)
+            .await
+            .map_err(|e| SecurityError::RateLimitStorage(e.to_string()))?;
+
+        let reset_after = Duration::from_millis(reset_after_ms.max(0) as u64);
+        Ok(if allowed == 1 {
+            RateLimitResult::Allowed {
+                limit: limit.capacity,
+                remaining: remaining.clamp(0, i64::from(limit.capacity)) as u32,
+                reset_after,
+            }
+        } else {
+            RateLimitResult::Exceeded {
+                retry_after: Duration::from_millis(retry_after_ms.max(0) as u64),
+                limit_type: format!("{}_token_bucket", key.scope()),
+                limit: limit.capacity,
+            }
+        })
+    }
+
+    async fn connection(&self) -> SecurityResult<MultiplexedConnection> {
+        let mut connection = self.connection.lock().await;
+        if let Some(connection) = connection.as_ref() {
+            return Ok(connection.clone());
+        }
+
+        let fresh = self
+            .client
+            .get_multiplexed_tokio_connection()
+            .await
+            .map_err(|e| SecurityError::CacheConnection(e.to_string()))?;
+        *connection = Some(fresh.clone());
+        Ok(fresh)
+    }
+
+    fn redis_key(&self, key: &RateLimitKey) -> String {
+        format!(
+            "{}:{}:{}",
+            self.config.key_prefix,
+            key.scope(),
+            key.storage_id()
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -298,11 +680,11 @@ mod tests {
 
         // First request should be allowed
         let result = limiter.check_user_limit(user_id).await.unwrap();
-        assert!(matches!(result, RateLimitResult::Allowed));
+        assert!(matches!(result, RateLimitResult::Allowed { .. }));
 
         // Second request should be allowed
         let result = limiter.check_user_limit(user_id).await.unwrap();
-        assert!(matches!(result, RateLimitResult::Allowed));
+        assert!(matches!(result, RateLimitResult::Allowed { .. }));
 
         // Third request should exceed limit
         let result = limiter.check_user_limit(user_id).await.unwrap();
@@ -319,11 +701,11 @@ mod tests {
 
         // First request should be allowed
         let result = limiter.check_ip_limit(ip).await.unwrap();
-        assert!(matches!(result, RateLimitResult::Allowed));
+        assert!(matches!(result, RateLimitResult::Allowed { .. }));
 
         // Second request should be allowed
         let result = limiter.check_ip_limit(ip).await.unwrap();
-        assert!(matches!(result, RateLimitResult::Allowed));
+        assert!(matches!(result, RateLimitResult::Allowed { .. }));
 
         // Third request should exceed limit
         let result = limiter.check_ip_limit(ip).await.unwrap();
@@ -391,4 +773,137 @@ mod tests {
         let stats = limiter.get_ip_stats(ip).await;
         assert!(stats.is_none());
     }
+
+    #[test]
+    fn test_rate_limit_result_headers() {
+        let allowed = RateLimitResult::Allowed {
+            limit: 10,
+            remaining: 7,
+            reset_after: Duration::from_secs(3),
+        };
+        let headers = allowed.headers();
+        assert_eq!(headers.len(), 3);
+        assert!(headers.contains(&(HEADER_LIMIT, "10".to_string())));
+        assert!(headers.contains(&(HEADER_REMAINING, "7".to_string())));
+
+        let exceeded = RateLimitResult::Exceeded {
+            retry_after: Duration::from_millis(1500),
+            limit_type: "user_token_bucket".to_string(),
+            limit: 10,
+        };
+        assert_eq!(exceeded.remaining(), 0);
+        assert!(exceeded
+            .headers()
+            .contains(&(HEADER_RETRY_AFTER, "2".to_string())));
+    }
+
+    #[test]
+    fn test_redis_key_hashes_api_keys() {
+        let client = Arc::new(redis::Client::open("redis://127.0.0.1:6379/").unwrap());
+        let limiter = RedisRateLimiter::new(client, DistributedRateLimitConfig::default());
+
+        let key = limiter.redis_key(&RateLimitKey::ApiKey("sk_live_secret".to_string()));
+        assert!(key.starts_with("rate_limit:bucket:api_key:"));
+        assert!(!key.contains("sk_live_secret"));
+    }
+
+    #[test]
+    fn test_key_overrides() {
+        let client = Arc::new(redis::Client::open("redis://127.0.0.1:6379/").unwrap());
+        let mut config = DistributedRateLimitConfig::default();
+        let partner = TokenBucketLimit {
+            capacity: 1000,
+            refill_per_second: 50.0,
+        };
+        config
+            .key_overrides
+            .insert("user:partner".to_string(), partner);
+        let limiter = RedisRateLimiter::new(client, config.clone());
+
+        assert_eq!(
+            limiter.limit_for(&RateLimitKey::User("partner".to_string())),
+            partner
+        );
+        assert_eq!(
+            limiter.limit_for(&RateLimitKey::User("someone".to_string())),
+            config.user_limit
+        );
+    }
+
+    #[tokio::test]
+    async fn test_redis_unavailable_applies_failure_policy() {
+        // Nothing listens on port 1, so every check fails to connect
+        let client = Arc::new(redis::Client::open("redis://127.0.0.1:1/").unwrap());
+        let key = RateLimitKey::Ip(IpAddr::from_str("192.168.1.1").unwrap());
+
+        let open = RedisRateLimiter::new(client.clone(), DistributedRateLimitConfig::default());
+        assert!(open.check(&key).await.unwrap().is_allowed());
+
+        let closed = RedisRateLimiter::new(
+            client,
+            DistributedRateLimitConfig {
+                failure_policy: RateLimitFailurePolicy::FailClosed,
+                ..DistributedRateLimitConfig::default()
+            },
+        );
+        let result = closed.check(&key).await.unwrap();
+        assert!(matches!(
+            result,
+            RateLimitResult::Exceeded { ref limit_type, .. }
+                if limit_type == "rate_limit_storage_unavailable"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_distributed_limit_holds_under_concurrency() {
+        let redis_url =
+            std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379/".to_string());
+        let probe = redis::Client::open(redis_url.as_str()).unwrap();
+        if probe.get_multiplexed_tokio_connection().await.is_err() {
+            eprintln!(
+                "Skipping distributed rate limit test: Redis unavailable at {}",
+                redis_url
+            );
+            return;
+        }
+
+        let capacity = 10;
+        let config = DistributedRateLimitConfig {
+            key_prefix: format!("rate_limit:test:{}", uuid::Uuid::new_v4()),
+            user_limit: TokenBucketLimit {
+                capacity,
+                refill_per_second: 0.001,
+            },
+            failure_policy: RateLimitFailurePolicy::FailClosed,
+            redis_timeout: Duration::from_secs(5),
+            ..DistributedRateLimitConfig::default()
+        };
+
+        // Separate limiters and connections stand in for separate gateway instances
+        let limiters: Vec<Arc<RedisRateLimiter>> = (0..4)
+            .map(|_| {
+                let client = Arc::new(redis::Client::open(redis_url.as_str()).unwrap());
+                Arc::new(RedisRateLimiter::new(client, config.clone()))
+            })
+            .collect();
+
+        let key = RateLimitKey::User("concurrent_user".to_string());
+        let tasks: Vec<_> = (0..100)
+            .map(|i| {
+                let limiter = limiters[i % limiters.len()].clone();
+                let key = key.clone();
+                tokio::spawn(async move { limiter.check(&key).await.unwrap().is_allowed() })
+            })
+            .collect();
+
+        let mut allowed = 0;
+        for task in tasks {
+            if task.await.unwrap() {
+                allowed += 1;
+            }
+        }
+
+        assert_eq!(allowed, capacity);
+        limiters[0].reset(&key).await.unwrap();
+    }
 }
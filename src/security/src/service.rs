@@ -51,6 +51,7 @@ impl SecurityService {
                 refresh_token_ttl: chrono::Duration::from_std(config.jwt.refresh_token_ttl)
                     .map_err(|e| SecurityError::Configuration(e.to_string()))?,
                 algorithm: jsonwebtoken::Algorithm::HS256,
+                private_key_pem: None,
                 enable_blacklist: config.jwt.enable_blacklist,
                 max_tokens_per_user: config.jwt.max_tokens_per_user,
             },
@@ -147,7 +148,8 @@ impl SecurityService {
             .await
     }
 
-    /// Validate an access token and return user information
+    /// Validate an access token and return user information. Revoked tokens
+    /// are rejected with `TokenBlacklisted` or `TokenRevoked`.
     pub async fn validate_token(&self, token: &str) -> SecurityResult<ValidationResult> {
         self.jwt_service.validate_access_token(token).await
     }
@@ -157,15 +159,27 @@ impl SecurityService {
         self.jwt_service.refresh_token(refresh_token).await
     }
 
-    /// Revoke a specific token
-    pub async fn revoke_token(&self, token_id: &str, reason: &str) -> SecurityResult<()> {
-        self.jwt_service.revoke_token(token_id, reason).await
+    /// Log out by revoking the presented token for the rest of its lifetime
+    pub async fn logout(&self, token: &str) -> SecurityResult<()> {
+        self.jwt_service
+            .revoke_presented_token(token, "logout")
+            .await
+    }
+
+    /// Revoke a specific token by its `jti`
+    pub async fn revoke_token(&self, jti: &str) -> SecurityResult<()> {
+        self.jwt_service.revoke_token(jti, "revoked").await
     }
 
-    /// Revoke all tokens for a user
-    pub async fn revoke_all_user_tokens(&self, user_id: Uuid, reason: &str) -> SecurityResult<()> {
+    /// Forcibly end a session, revoking its access and refresh tokens
+    pub async fn terminate_session(&self, session_id: &str) -> SecurityResult<()> {
+        self.jwt_service.revoke_session(session_id).await
+    }
+
+    /// Revoke every token issued to a user so far, on any instance
+    pub async fn revoke_all_for_user(&self, user_id: Uuid) -> SecurityResult<()> {
         self.jwt_service
-            .revoke_all_user_tokens(user_id, reason)
+            .revoke_all_user_tokens(user_id, "user_tokens_revoked")
             .await
     }
 
@@ -203,6 +217,16 @@ impl SecurityService {
         self.password_service.verify_password(password, hash_result)
     }
 
+    /// Validate a password chosen at registration or on a password change.
+    /// Rejects it with `WeakPassword` listing the reasons when it is too easy
+    /// to guess.
+    pub fn validate_new_password(
+        &self,
+        password: &str,
+    ) -> SecurityResult<crate::password_strength::PasswordStrength> {
+        self.password_service.validate_new_password(password)
+    }
+
     /// Check password strength
     pub fn check_password_strength(
         &self,
@@ -434,6 +458,101 @@ mod tests {
         assert_eq!(validation_result.user_id.to_string(), user.id);
     }
 
+    #[tokio::test]
+    async fn test_revoked_token_is_rejected() {
+        let service = SecurityService::with_defaults().await.unwrap();
+        let user = create_test_user();
+
+        let token_pair = service
+            .authenticate_user(&user, None, None, None)
+            .await
+            .unwrap();
+        let token = &token_pair.access_token.token;
+
+        // Validate first so the revocation must also beat the validation cache
+        let validation_result = service.validate_token(token).await.unwrap();
+        service
+            .revoke_token(&validation_result.claims.jti)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            service.validate_token(token).await,
+            Err(SecurityError::TokenBlacklisted(jti)) if jti == validation_result.claims.jti
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_logout_revokes_token() {
+        let service = SecurityService::with_defaults().await.unwrap();
+        let user = create_test_user();
+
+        let token_pair = service
+            .authenticate_user(&user, None, None, None)
+            .await
+            .unwrap();
+
+        service
+            .logout(&token_pair.access_token.token)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            service.validate_token(&token_pair.access_token.token).await,
+            Err(SecurityError::TokenBlacklisted(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_revoke_all_for_user() {
+        let service = SecurityService::with_defaults().await.unwrap();
+        let user = create_test_user();
+        let user_id: Uuid = user.id.parse().unwrap();
+
+        let first = service
+            .authenticate_user(&user, None, None, None)
+            .await
+            .unwrap();
+        let second = service
+            .authenticate_user(&user, None, None, None)
+            .await
+            .unwrap();
+
+        service.revoke_all_for_user(user_id).await.unwrap();
+
+        for token_pair in [&first, &second] {
+            assert!(matches!(
+                service.validate_token(&token_pair.access_token.token).await,
+                Err(SecurityError::TokenRevoked(_))
+            ));
+            assert!(matches!(
+                service.refresh_token(&token_pair.refresh_token.token).await,
+                Err(SecurityError::TokenRevoked(_))
+            ));
+        }
+
+        // A token from another instance that never tracked the sessions is
+        // rejected through the shared cutoff
+        let other_instance = SecurityService::with_defaults().await.unwrap();
+        assert!(matches!(
+            other_instance
+                .validate_token(&first.access_token.token)
+                .await,
+            Err(SecurityError::TokenRevoked(_))
+        ));
+
+        // Cutoffs have one-second resolution; logins after it are unaffected
+        tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+        let fresh = service
+            .authenticate_user(&user, None, None, None)
+            .await
+            .unwrap();
+        assert!(service
+            .validate_token(&fresh.access_token.token)
+            .await
+            .is_ok());
+    }
+
     #[tokio::test]
     async fn test_password_operations() {
         let service = SecurityService::with_defaults().await.unwrap();
@@ -1,14 +1,24 @@
 //! Threat Detection Module
 //!
 //! Provides threat detection and security monitoring capabilities.
-
-use crate::errors::SecurityResult;
+//!
+//! Failed authentications are counted per account and per IP. Reaching
+//! `max_login_attempts` within the window locks the identifier out, and each
+//! repeated lockout doubles the cooldown up to `max_lockout_duration`. With
+//! `ThreatDetector::with_redis` the counters live in Redis, so a lockout holds
+//! on every instance.
+
+use crate::errors::{SecurityError, SecurityResult};
+use redis::aio::MultiplexedConnection;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::fmt;
 use std::net::IpAddr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
+use tracing::warn;
 
 /// Threat level classification
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -48,6 +58,72 @@ pub enum SecurityAlert {
     CrossSiteScripting { ip: IpAddr, pattern: String },
     /// Unusual access patterns
     AnomalousAccess { user_id: String, pattern: String },
+    /// Account or IP locked out after repeated failed logins
+    LoginLockout {
+        identifier: LoginIdentifier,
+        attempts: u32,
+        lockout_duration: Duration,
+        /// Lockouts of this identifier still remembered, including this one
+        lockout_count: u32,
+    },
+}
+
+impl SecurityAlert {
+    /// How serious the alert is
+    pub fn threat_level(&self) -> ThreatLevel {
+        match self {
+            SecurityAlert::SqlInjection { .. } | SecurityAlert::CrossSiteScripting { .. } => {
+                ThreatLevel::Critical
+            }
+            SecurityAlert::LoginLockout { lockout_count, .. } if *lockout_count > 1 => {
+                ThreatLevel::Critical
+            }
+            SecurityAlert::BruteForce { .. }
+            | SecurityAlert::LoginLockout { .. }
+            | SecurityAlert::RateLimitAbuse { .. } => ThreatLevel::High,
+            SecurityAlert::SuspiciousUserAgent { .. }
+            | SecurityAlert::GeographicAnomaly { .. }
+            | SecurityAlert::AnomalousAccess { .. } => ThreatLevel::Medium,
+        }
+    }
+}
+
+/// What failed logins are counted and locked out by
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LoginIdentifier {
+    /// Username or email, compared case-insensitively
+    Account(String),
+    Ip(IpAddr),
+}
+
+impl LoginIdentifier {
+    fn scope(&self) -> &'static str {
+        match self {
+            LoginIdentifier::Account(_) => "account",
+            LoginIdentifier::Ip(_) => "ip",
+        }
+    }
+
+    /// Identifier used in storage keys. Accounts are hashed so usernames and
+    /// emails are never written to Redis.
+    fn storage_id(&self) -> String {
+        match self {
+            LoginIdentifier::Account(account) => format!(
+                "{:x}",
+                Sha256::digest(account.trim().to_lowercase().as_bytes())
+            ),
+            LoginIdentifier::Ip(ip) => ip.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for LoginIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoginIdentifier::Account(account) => write!(f, "account:{}", account),
+            LoginIdentifier::Ip(ip) => write!(f, "ip:{}", ip),
+        }
+    }
 }
 
 /// Threat detection configuration
@@ -65,6 +141,11 @@ pub struct ThreatDetectionConfig {
     pub enable_geo_detection: bool,
     /// Enable pattern-based detection
     pub enable_pattern_detection: bool,
+    /// Cooldown after the first lockout; doubles with each repeated lockout
+    pub lockout_duration: Duration,
+    /// Longest cooldown repeated lockouts can grow to. Lockouts are remembered
+    /// for this long when deciding how much to escalate.
+    pub max_lockout_duration: Duration,
 }
 
 impl Default for ThreatDetectionConfig {
@@ -76,10 +157,110 @@ impl Default for ThreatDetectionConfig {
             suspicious_activity_threshold: 10,
             enable_geo_detection: true,
             enable_pattern_detection: true,
+            lockout_duration: Duration::from_secs(300), // 5 minutes
+            max_lockout_duration: Duration::from_secs(86400), // 24 hours
+        }
+    }
+}
+
+impl From<&crate::config::ThreatDetectionConfig> for ThreatDetectionConfig {
+    fn from(config: &crate::config::ThreatDetectionConfig) -> Self {
+        let defaults = Self::default();
+        Self {
+            max_login_attempts: config.max_login_attempts,
+            login_attempt_window: config.login_attempt_window,
+            suspicious_activity_threshold: config.suspicious_activity_threshold,
+            lockout_duration: config.lockout_duration,
+            max_lockout_duration: defaults.max_lockout_duration.max(config.lockout_duration),
+            ..defaults
         }
     }
 }
 
+/// Counts a failed login and locks the identifier out once it reaches the
+/// limit. Runs atomically in Redis so concurrent attempts on different
+/// instances are all counted.
+///
+/// KEYS[1] failure counter, KEYS[2] lockout history, KEYS[3] active lockout.
+/// ARGV[1] max attempts, ARGV[2] window ms, ARGV[3] base lockout ms,
+/// ARGV[4] max lockout ms.
+/// Returns {status, lockout ms remaining, failures, lockouts} where status is
+/// 0 while counting, 1 when this attempt caused a lockout and 2 when the
+/// identifier was already locked out.
+const RECORD_FAILURE_SCRIPT: &str = r#"
+local remaining = redis.call('PTTL', KEYS[3])
+if remaining > 0 then
+    return {2, remaining, 0, 0}
+end
+
+local failures = redis.call('INCR', KEYS[1])
+if failures == 1 then
+    redis.call('PEXPIRE', KEYS[1], ARGV[2])
+end
+if failures < tonumber(ARGV[1]) then
+    return {0, 0, failures, 0}
+end
+
+redis.call('DEL', KEYS[1])
+local lockouts = redis.call('INCR', KEYS[2])
+redis.call('PEXPIRE', KEYS[2], ARGV[4])
+local duration = math.floor(math.min(tonumber(ARGV[3]) * 2 ^ (lockouts - 1), tonumber(ARGV[4])))
+duration = math.max(duration, 1)
+redis.call('SET', KEYS[3], lockouts, 'PX', duration)
+return {1, duration, failures, lockouts}
+"#;
+
+/// Result of counting a failed login against one identifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FailureOutcome {
+    Counted,
+    LockedOut {
+        attempts: u32,
+        lockout_duration: Duration,
+        lockout_count: u32,
+    },
+    AlreadyLocked,
+}
+
+/// Cooldown for the `lockout_count`-th lockout still remembered
+fn lockout_duration_for(config: &ThreatDetectionConfig, lockout_count: u32) -> Duration {
+    let factor = 2u32.saturating_pow(lockout_count.saturating_sub(1));
+    config
+        .lockout_duration
+        .saturating_mul(factor)
+        .min(config.max_lockout_duration)
+}
+
+fn lockout_key(key_prefix: &str, identifier: &LoginIdentifier, kind: &str) -> String {
+    format!(
+        "{}:{}:{}:{}",
+        key_prefix,
+        kind,
+        identifier.scope(),
+        identifier.storage_id()
+    )
+}
+
+/// Failed login state for one identifier, when kept in process memory
+#[derive(Debug, Clone, Default)]
+struct LocalLockout {
+    failures: Vec<Instant>,
+    lockout_count: u32,
+    last_lockout: Option<Instant>,
+    locked_until: Option<Instant>,
+}
+
+/// Where failed login counters and lockouts are kept
+enum LockoutStore {
+    Memory(RwLock<HashMap<LoginIdentifier, LocalLockout>>),
+    Redis {
+        client: Arc<redis::Client>,
+        connection: Mutex<Option<MultiplexedConnection>>,
+        script: redis::Script,
+        key_prefix: String,
+    },
+}
+
 /// Login attempt tracking
 #[derive(Debug, Clone)]
 struct LoginAttempt {
@@ -168,11 +349,30 @@ pub struct ThreatDetector {
     ip_threats: Arc<RwLock<HashMap<IpAddr, IpThreatInfo>>>,
     user_activities: Arc<RwLock<HashMap<String, Vec<String>>>>,
     suspicious_patterns: Vec<String>,
+    lockouts: LockoutStore,
 }
 
 impl ThreatDetector {
-    /// Create new threat detector
+    /// Create new threat detector keeping lockouts in process memory
     pub fn new(config: ThreatDetectionConfig) -> Self {
+        Self::with_lockout_store(config, LockoutStore::Memory(RwLock::new(HashMap::new())))
+    }
+
+    /// Create a threat detector keeping failed login counters and lockouts in
+    /// Redis, shared by every instance. Redis is connected lazily.
+    pub fn with_redis(config: ThreatDetectionConfig, client: Arc<redis::Client>) -> Self {
+        Self::with_lockout_store(
+            config,
+            LockoutStore::Redis {
+                client,
+                connection: Mutex::new(None),
+                script: redis::Script::new(RECORD_FAILURE_SCRIPT),
+                key_prefix: "threat:login".to_string(),
+            },
+        )
+    }
+
+    fn with_lockout_store(config: ThreatDetectionConfig, lockouts: LockoutStore) -> Self {
         let suspicious_patterns = vec![
             // SQL injection patterns
             "union select".to_string(),
@@ -195,6 +395,7 @@ impl ThreatDetector {
             ip_threats: Arc::new(RwLock::new(HashMap::new())),
             user_activities: Arc::new(RwLock::new(HashMap::new())),
             suspicious_patterns,
+            lockouts,
         }
     }
 
@@ -242,6 +443,207 @@ impl ThreatDetector {
         Ok(None)
     }
 
+    /// Record the outcome of authenticating `account` from `ip`. Failures count
+    /// against both and may lock either out; a success resets the account's
+    /// failure count. The IP's count is kept, so one valid login does not hide
+    /// an IP trying many accounts.
+    pub async fn record_authentication(
+        &self,
+        account: &str,
+        ip: IpAddr,
+        success: bool,
+    ) -> SecurityResult<Vec<SecurityAlert>> {
+        let account = LoginIdentifier::Account(account.to_string());
+
+        if success {
+            self.reset_failures(&account).await?;
+            return Ok(Vec::new());
+        }
+
+        let mut alerts = Vec::new();
+        for identifier in [account, LoginIdentifier::Ip(ip)] {
+            if let FailureOutcome::LockedOut {
+                attempts,
+                lockout_duration,
+                lockout_count,
+            } = self.record_failure(&identifier).await?
+            {
+                warn!(
+                    "Locked out {} for {:?} after {} failed logins (lockout #{})",
+                    identifier, lockout_duration, attempts, lockout_count
+                );
+                alerts.push(SecurityAlert::LoginLockout {
+                    identifier,
+                    attempts,
+                    lockout_duration,
+                    lockout_count,
+                });
+            }
+        }
+
+        Ok(alerts)
+    }
+
+    /// Whether `identifier` is currently locked out
+    pub async fn is_locked(&self, identifier: &LoginIdentifier) -> SecurityResult<bool> {
+        Ok(self.lockout_remaining(identifier).await?.is_some())
+    }
+
+    /// Time left on the lockout of `identifier`, suitable for `Retry-After`
+    pub async fn lockout_remaining(
+        &self,
+        identifier: &LoginIdentifier,
+    ) -> SecurityResult<Option<Duration>> {
+        match &self.lockouts {
+            LockoutStore::Memory(state) => {
+                let now = Instant::now();
+                Ok(state
+                    .read()
+                    .await
+                    .get(identifier)
+                    .and_then(|lockout| lockout.locked_until)
+                    .filter(|locked_until| *locked_until > now)
+                    .map(|locked_until| locked_until - now))
+            }
+            LockoutStore::Redis { key_prefix, .. } => {
+                let mut connection = self.redis_connection().await?;
+                let remaining_ms: i64 = redis::cmd("PTTL")
+                    .arg(lockout_key(key_prefix, identifier, "locked"))
+                    .query_async(&mut connection)
+                    .await
+                    .map_err(|e| SecurityError::CacheOperation(e.to_string()))?;
+                Ok((remaining_ms > 0).then(|| Duration::from_millis(remaining_ms as u64)))
+            }
+        }
+    }
+
+    /// Longest lockout remaining on the account or the IP, if either is locked
+    pub async fn login_lockout(
+        &self,
+        account: &str,
+        ip: IpAddr,
+    ) -> SecurityResult<Option<Duration>> {
+        let account = self
+            .lockout_remaining(&LoginIdentifier::Account(account.to_string()))
+            .await?;
+        let ip = self.lockout_remaining(&LoginIdentifier::Ip(ip)).await?;
+        Ok(account.max(ip))
+    }
+
+    async fn record_failure(&self, identifier: &LoginIdentifier) -> SecurityResult<FailureOutcome> {
+        match &self.lockouts {
+            LockoutStore::Memory(state) => {
+                let now = Instant::now();
+                let mut state = state.write().await;
+                let lockout = state.entry(identifier.clone()).or_default();
+
+                if lockout.locked_until.is_some_and(|until| until > now) {
+                    return Ok(FailureOutcome::AlreadyLocked);
+                }
+
+                let window = self.config.login_attempt_window;
+                lockout
+                    .failures
+                    .retain(|failed_at| now.duration_since(*failed_at) < window);
+                lockout.failures.push(now);
+                let attempts = lockout.failures.len() as u32;
+                if attempts < self.config.max_login_attempts {
+                    return Ok(FailureOutcome::Counted);
+                }
+
+                // Lockouts are forgotten once none has happened for the longest cooldown
+                let history = self.config.max_lockout_duration;
+                if lockout
+                    .last_lockout
+                    .is_some_and(|locked_at| now.duration_since(locked_at) >= history)
+                {
+                    lockout.lockout_count = 0;
+                }
+                lockout.lockout_count += 1;
+                lockout.last_lockout = Some(now);
+                lockout.failures.clear();
+
+                let lockout_count = lockout.lockout_count;
+                let lockout_duration = lockout_duration_for(&self.config, lockout_count);
+                lockout.locked_until = Some(now + lockout_duration);
+
+                Ok(FailureOutcome::LockedOut {
+                    attempts,
+                    lockout_duration,
+                    lockout_count,
+                })
+            }
+            LockoutStore::Redis {
+                script, key_prefix, ..
+            } => {
+                let mut connection = self.redis_connection().await?;
+                let (status, remaining_ms, attempts, lockout_count): (i64, i64, i64, i64) = script
+                    .key(lockout_key(key_prefix, identifier, "failures"))
+                    .key(lockout_key(key_prefix, identifier, "lockouts"))
+                    .key(lockout_key(key_prefix, identifier, "locked"))
+                    .arg(self.config.max_login_attempts.max(1))
+                    .arg(self.config.login_attempt_window.as_millis() as u64)
+                    .arg(self.config.lockout_duration.as_millis() as u64)
+                    .arg(self.config.max_lockout_duration.as_millis() as u64)
+                    .invoke_async(&mut connection)
+                    .await
+                    .map_err(|e| SecurityError::CacheOperation(e.to_string()))?;
+
+                Ok(match status {
+                    1 => FailureOutcome::LockedOut {
+                        attempts: attempts.max(0) as u32,
+                        lockout_duration: Duration::from_millis(remaining_ms.max(0) as u64),
+                        lockout_count: lockout_count.max(1) as u32,
+                    },
+                    2 => FailureOutcome::AlreadyLocked,
+                    _ => FailureOutcome::Counted,
+                })
+            }
+        }
+    }
+
+    async fn reset_failures(&self, identifier: &LoginIdentifier) -> SecurityResult<()> {
+        match &self.lockouts {
+            LockoutStore::Memory(state) => {
+                if let Some(lockout) = state.write().await.get_mut(identifier) {
+                    lockout.failures.clear();
+                }
+                Ok(())
+            }
+            LockoutStore::Redis { key_prefix, .. } => {
+                let mut connection = self.redis_connection().await?;
+                redis::cmd("DEL")
+                    .arg(lockout_key(key_prefix, identifier, "failures"))
+                    .query_async::<_, ()>(&mut connection)
+                    .await
+                    .map_err(|e| SecurityError::CacheOperation(e.to_string()))
+            }
+        }
+    }
+
+    async fn redis_connection(&self) -> SecurityResult<MultiplexedConnection> {
+        let LockoutStore::Redis {
+            client, connection, ..
+        } = &self.lockouts
+        else {
+            return Err(SecurityError::Internal(
+                "Lockouts are not stored in Redis".to_string(),
+            ));
+        };
+
+        let mut connection = connection.lock().await;
+        if let Some(connection) = connection.as_ref() {
+            return Ok(connection.clone());
+        }
+
+        let fresh = client
+            .get_multiplexed_tokio_connection()
+            .await
+            .map_err(|e| SecurityError::CacheConnection(e.to_string()))?;
+        *connection = Some(fresh.clone());
+        Ok(fresh)
+    }
+
     /// Check if IP is blacklisted
     pub async fn is_ip_blacklisted(&self, ip: IpAddr) -> bool {
         let ip_threats = self.ip_threats.read().await;
@@ -363,6 +765,27 @@ impl ThreatDetector {
 
         removed_count += (initial_count - ip_threats.len()) as u32;
 
+        if let LockoutStore::Memory(state) = &self.lockouts {
+            let now = Instant::now();
+            let window = self.config.login_attempt_window;
+            let history = self.config.max_lockout_duration;
+            let mut state = state.write().await;
+            let initial_count = state.len();
+
+            state.retain(|_, lockout| {
+                lockout.locked_until.is_some_and(|until| until > now)
+                    || lockout
+                        .failures
+                        .iter()
+                        .any(|failed_at| now.duration_since(*failed_at) < window)
+                    || lockout
+                        .last_lockout
+                        .is_some_and(|locked_at| now.duration_since(locked_at) < history)
+            });
+
+            removed_count += (initial_count - state.len()) as u32;
+        }
+
         Ok(removed_count)
     }
 
@@ -537,6 +960,189 @@ mod tests {
         assert_eq!(removed, 0);
     }
 
+    #[tokio::test]
+    async fn test_progressive_login_lockout() {
+        let detector = ThreatDetector::new(ThreatDetectionConfig {
+            max_login_attempts: 3,
+            lockout_duration: Duration::from_millis(100),
+            max_lockout_duration: Duration::from_secs(60),
+            ..ThreatDetectionConfig::default()
+        });
+        let ip = IpAddr::from_str("192.168.1.1").unwrap();
+        let account = LoginIdentifier::Account("alice@example.com".to_string());
+
+        for _ in 0..2 {
+            let alerts = detector
+                .record_authentication("alice@example.com", ip, false)
+                .await
+                .unwrap();
+            assert!(alerts.is_empty());
+        }
+        assert!(!detector.is_locked(&account).await.unwrap());
+
+        // Third failure locks out both the account and the IP
+        let alerts = detector
+            .record_authentication("alice@example.com", ip, false)
+            .await
+            .unwrap();
+        assert_eq!(alerts.len(), 2);
+        assert!(matches!(
+            &alerts[0],
+            SecurityAlert::LoginLockout {
+                identifier,
+                attempts: 3,
+                lockout_duration,
+                lockout_count: 1,
+            } if identifier == &account && *lockout_duration == Duration::from_millis(100)
+        ));
+        assert_eq!(alerts[0].threat_level(), ThreatLevel::High);
+        assert!(detector.is_locked(&account).await.unwrap());
+        assert!(detector.is_locked(&LoginIdentifier::Ip(ip)).await.unwrap());
+        let remaining = detector
+            .login_lockout("ALICE@example.com", ip)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(remaining <= Duration::from_millis(100));
+
+        // Attempts during the lockout are not counted towards the next one
+        assert!(detector
+            .record_authentication("alice@example.com", ip, false)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // The next lockout lasts twice as long
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        assert!(!detector.is_locked(&account).await.unwrap());
+        let mut alerts = Vec::new();
+        for _ in 0..3 {
+            alerts = detector
+                .record_authentication("alice@example.com", ip, false)
+                .await
+                .unwrap();
+        }
+        assert!(matches!(
+            &alerts[0],
+            SecurityAlert::LoginLockout {
+                lockout_duration,
+                lockout_count: 2,
+                ..
+            } if *lockout_duration == Duration::from_millis(200)
+        ));
+        assert_eq!(alerts[0].threat_level(), ThreatLevel::Critical);
+    }
+
+    #[tokio::test]
+    async fn test_successful_login_resets_account_failures() {
+        let detector = ThreatDetector::new(ThreatDetectionConfig {
+            max_login_attempts: 3,
+            ..ThreatDetectionConfig::default()
+        });
+        let ip = IpAddr::from_str("192.168.1.1").unwrap();
+        let other_ip = IpAddr::from_str("192.168.1.2").unwrap();
+
+        for _ in 0..2 {
+            detector
+                .record_authentication("bob", ip, false)
+                .await
+                .unwrap();
+        }
+        detector
+            .record_authentication("bob", ip, true)
+            .await
+            .unwrap();
+
+        // The account starts counting again; the first IP does not
+        let alerts = detector
+            .record_authentication("bob", other_ip, false)
+            .await
+            .unwrap();
+        assert!(alerts.is_empty());
+        let alerts = detector
+            .record_authentication("bob", ip, false)
+            .await
+            .unwrap();
+        assert!(matches!(
+            &alerts[..],
+            [SecurityAlert::LoginLockout {
+                identifier: LoginIdentifier::Ip(_),
+                ..
+            }]
+        ));
+        assert!(!detector
+            .is_locked(&LoginIdentifier::Account("bob".to_string()))
+            .await
+            .unwrap());
+    }
+
+    #[test]
+    fn test_lockout_keys_hash_accounts() {
+        let key = lockout_key(
+            "threat:login",
+            &LoginIdentifier::Account("Alice@Example.com ".to_string()),
+            "failures",
+        );
+        assert!(key.starts_with("threat:login:failures:account:"));
+        assert!(!key.to_lowercase().contains("alice"));
+        assert_eq!(
+            key,
+            lockout_key(
+                "threat:login",
+                &LoginIdentifier::Account("alice@example.com".to_string()),
+                "failures",
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lockout_is_shared_through_redis() {
+        let redis_url =
+            std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379/".to_string());
+        let probe = redis::Client::open(redis_url.as_str()).unwrap();
+        if probe.get_multiplexed_tokio_connection().await.is_err() {
+            eprintln!(
+                "Skipping distributed lockout test: Redis unavailable at {}",
+                redis_url
+            );
+            return;
+        }
+
+        let config = ThreatDetectionConfig {
+            max_login_attempts: 4,
+            ..ThreatDetectionConfig::default()
+        };
+        // Separate detectors stand in for separate gateway instances
+        let instances: Vec<ThreatDetector> = (0..2)
+            .map(|_| {
+                let client = Arc::new(redis::Client::open(redis_url.as_str()).unwrap());
+                ThreatDetector::with_redis(config.clone(), client)
+            })
+            .collect();
+
+        let account = format!("user-{}@example.com", uuid::Uuid::new_v4());
+        let ip = IpAddr::from(std::net::Ipv6Addr::from(uuid::Uuid::new_v4().as_u128()));
+        let mut alerts = Vec::new();
+        for attempt in 0..4 {
+            alerts = instances[attempt % 2]
+                .record_authentication(&account, ip, false)
+                .await
+                .unwrap();
+        }
+
+        assert!(alerts.iter().any(|alert| matches!(
+            alert,
+            SecurityAlert::LoginLockout {
+                identifier: LoginIdentifier::Account(_),
+                ..
+            }
+        )));
+        for instance in &instances {
+            let remaining = instance.login_lockout(&account, ip).await.unwrap();
+            assert!(remaining.is_some_and(|remaining| remaining <= config.lockout_duration));
+        }
+    }
+
     #[tokio::test]
     async fn test_unusual_activity_detection() {
         let detector = ThreatDetector::with_defaults();
@@ -27,8 +27,9 @@ use crate::{
     config::{BatchConfig, Config},
     error::{BatchProcessingError, DataProcessingError, Result},
     metrics::MetricsCollector,
+    output::BatchOutputWriter,
     types::{
-        BatchJob, BatchJobStatus, BatchJobType, HealthStatus, JobMetrics, JobState,
+        BatchJob, BatchJobStatus, BatchJobType, HealthStatus, JobMetrics, JobState, OutputFile,
         ProcessingError, ProcessingStatus, ProcessingWarning, ResourceRequirements,
     },
 };
@@ -385,6 +386,8 @@ impl BatchProcessor {
                 errors: Vec::new(),
                 warnings: Vec::new(),
                 logs_url: None,
+                output_files: Vec::new(),
+                bytes_written: 0,
             });
         }
 
@@ -446,6 +449,59 @@ impl BatchProcessor {
         Ok(jobs)
     }
 
+    /// Write result rows of a running job
+    ///
+    /// Files are written in the job's output format, one per partition, and
+    /// recorded in the job status so downstream jobs can locate them.
+    pub async fn write_job_output(
+        &self,
+        job_id: &str,
+        rows: Vec<serde_json::Value>,
+    ) -> Result<Vec<OutputFile>> {
+        let job = self
+            .active_jobs
+            .get(job_id)
+            .map(|active_job| active_job.job.clone())
+            .ok_or_else(|| {
+                DataProcessingError::validation("job_id", format!("Job {} is not running", job_id))
+            })?;
+
+        let writer = BatchOutputWriter::new(&job, self.output_directory(&job))?;
+        let files = tokio::task::spawn_blocking(move || writer.write(&rows))
+            .await
+            .map_err(|e| {
+                DataProcessingError::internal(format!("Output writer task failed: {}", e))
+            })??;
+
+        let bytes_written: u64 = files.iter().map(|file| file.bytes).sum();
+        if let Some(mut active_job) = self.active_jobs.get_mut(job_id) {
+            let status = &mut active_job.status;
+            status.output_files.extend(files.iter().cloned());
+            status.bytes_written += bytes_written;
+            status.metrics.disk_io_mb = status.bytes_written / (1024 * 1024);
+        }
+
+        debug!(
+            "Job {} wrote {} output files ({} bytes)",
+            job_id,
+            files.len(),
+            bytes_written
+        );
+        Ok(files)
+    }
+
+    /// Directory a job's output files are written to
+    ///
+    /// Uses the `path` destination option, falling back to a directory per job
+    /// under the batch temp directory.
+    fn output_directory(&self, job: &BatchJob) -> PathBuf {
+        job.output_config
+            .destination_config
+            .get("path")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| self.config.temp_dir.join("output").join(job.id.to_string()))
+    }
+
     /// Validate a batch job before submission
     fn validate_job(&self, job: &BatchJob) -> Result<()> {
         // Check timeout
@@ -471,6 +527,9 @@ impl BatchProcessor {
             ));
         }
 
+        // Check output settings
+        BatchOutputWriter::validate(job)?;
+
         // Check if resources are available
         if !self.resource_manager.can_allocate(&job.resources) {
             return Err(DataProcessingError::resource_exhausted(
@@ -559,6 +618,8 @@ impl BatchProcessor {
                         errors: Vec::new(),
                         warnings: Vec::new(),
                         logs_url: None,
+                        output_files: Vec::new(),
+                        bytes_written: 0,
                     };
 
                     let active_context = ActiveJobContext {
@@ -1039,6 +1100,72 @@ mod tests {
         assert!(manager.can_allocate(&requirements));
     }
 
+    #[tokio::test]
+    async fn test_job_output_is_reported_in_status() {
+        let config = Config::default();
+        let metrics = Arc::new(MetricsCollector::new(&config).unwrap());
+        let processor = BatchProcessor::new(&config, metrics).await.unwrap();
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut job = BatchJob::default();
+        job.output_config.destination_config.insert(
+            "path".to_string(),
+            dir.path().to_string_lossy().into_owned(),
+        );
+        let job_id = job.id.to_string();
+        let status = BatchJobStatus {
+            job_id: job.id,
+            state: JobState::Running,
+            progress: 0.0,
+            started_at: Some(Utc::now()),
+            completed_at: None,
+            current_stage: None,
+            records_processed: 0,
+            total_records: None,
+            metrics: JobMetrics {
+                duration_secs: None,
+                cpu_time_secs: 0.0,
+                peak_memory_mb: 0,
+                disk_io_mb: 0,
+                network_io_mb: 0,
+                throughput_rps: 0.0,
+                error_rate: 0.0,
+                custom_metrics: HashMap::new(),
+            },
+            errors: Vec::new(),
+            warnings: Vec::new(),
+            logs_url: None,
+            output_files: Vec::new(),
+            bytes_written: 0,
+        };
+        let resources = processor
+            .resource_manager
+            .try_allocate(&job.resources)
+            .await
+            .unwrap();
+        processor.active_jobs.insert(
+            job_id.clone(),
+            ActiveJobContext {
+                job,
+                status,
+                worker_id: None,
+                resources,
+                handle: None,
+            },
+        );
+
+        let rows = vec![serde_json::json!({"id": 1}), serde_json::json!({"id": 2})];
+        let files = processor.write_job_output(&job_id, rows).await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].records, 2);
+        assert!(files[0].path.starts_with(&*dir.path().to_string_lossy()));
+
+        let status = processor.get_job_status(&job_id).await.unwrap();
+        assert_eq!(status.output_files, files);
+        assert_eq!(status.bytes_written, files[0].bytes);
+        assert!(status.bytes_written > 0);
+    }
+
     #[tokio::test]
     async fn test_job_validation() {
         let config = Config::default();
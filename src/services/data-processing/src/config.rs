@@ -86,6 +86,48 @@ pub struct KafkaConfig {
     pub sasl: Option<SaslConfig>,
     /// SSL configuration
     pub ssl: Option<SslConfig>,
+    /// Transaction settings used when exactly-once stream processing is enabled
+    #[serde(default)]
+    pub transactions: KafkaTransactionConfig,
+}
+
+/// Kafka transaction configuration
+///
+/// With `stream.exactly_once` enabled, the stream processor consumes in batches
+/// and commits each batch's output records and consumed offsets in a single
+/// Kafka transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KafkaTransactionConfig {
+    /// Transactional ID, must stay stable across restarts of the same instance
+    pub transactional_id: String,
+    /// Isolation level of the consumer
+    pub isolation_level: IsolationLevel,
+    /// Transaction timeout in milliseconds
+    pub transaction_timeout_ms: u64,
+    /// Maximum consumed messages per transaction
+    pub batch_size: usize,
+    /// Maximum time to wait for a batch to fill in milliseconds
+    pub batch_linger_ms: u64,
+}
+
+/// Consumer isolation level
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IsolationLevel {
+    /// Only read messages from committed transactions
+    ReadCommitted,
+    /// Read all messages, including those of aborted transactions
+    ReadUncommitted,
+}
+
+impl IsolationLevel {
+    /// Value of the `isolation.level` client property
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IsolationLevel::ReadCommitted => "read_committed",
+            IsolationLevel::ReadUncommitted => "read_uncommitted",
+        }
+    }
 }
 
 /// SASL configuration for Kafka
@@ -160,7 +202,7 @@ pub struct StreamConfig {
     pub retry_backoff_base_ms: u64,
     /// Maximum retry backoff in milliseconds
     pub retry_backoff_max_ms: u64,
-    /// Enable exactly-once processing
+    /// Enable exactly-once processing through Kafka transactions
     pub exactly_once: bool,
     /// Checkpoint interval in milliseconds
     pub checkpoint_interval_ms: u64,
@@ -174,6 +216,52 @@ pub struct StreamConfig {
     pub output_topics: Vec<String>,
     /// Dead letter topic for failed messages
     pub dead_letter_topic: String,
+    /// Backpressure watermarks for the stream task queue
+    #[serde(default)]
+    pub backpressure: BackpressureConfig,
+}
+
+/// Backpressure configuration for stream ingestion
+///
+/// With the `block` strategy, ingestion pauses once the number of queued
+/// stream tasks reaches `high_watermark` and resumes after it drains to
+/// `low_watermark`. The drop strategies never pause; they cap the queue at
+/// `high_watermark` tasks and discard records instead.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackpressureConfig {
+    /// Queue depth at which producers are told to back off
+    pub high_watermark: usize,
+    /// Queue depth at which ingestion resumes
+    pub low_watermark: usize,
+    /// Retry-After hint returned to HTTP producers in seconds
+    pub retry_after_secs: u64,
+    /// What to do with new records once the queue is full
+    #[serde(default)]
+    pub strategy: BackpressureStrategy,
+}
+
+/// Handling of records arriving while the stream task queue is full
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BackpressureStrategy {
+    /// Pause ingestion until the queue drains
+    #[default]
+    Block,
+    /// Discard the oldest queued record to make room
+    DropOldest,
+    /// Discard the incoming record
+    DropNewest,
+}
+
+impl BackpressureStrategy {
+    /// Metric label for the strategy
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BackpressureStrategy::Block => "block",
+            BackpressureStrategy::DropOldest => "drop_oldest",
+            BackpressureStrategy::DropNewest => "drop_newest",
+        }
+    }
 }
 
 /// Batch processing configuration
@@ -364,6 +452,19 @@ impl Default for KafkaConfig {
             compression_type: "lz4".to_string(),
             sasl: None,
             ssl: None,
+            transactions: KafkaTransactionConfig::default(),
+        }
+    }
+}
+
+impl Default for KafkaTransactionConfig {
+    fn default() -> Self {
+        Self {
+            transactional_id: "data-processing-service".to_string(),
+            isolation_level: IsolationLevel::ReadCommitted,
+            transaction_timeout_ms: 60000,
+            batch_size: 100,
+            batch_linger_ms: 100,
         }
     }
 }
@@ -405,6 +506,18 @@ impl Default for StreamConfig {
             input_topics: vec!["events".to_string()],
             output_topics: vec!["processed-events".to_string()],
             dead_letter_topic: "failed-events".to_string(),
+            backpressure: BackpressureConfig::default(),
+        }
+    }
+}
+
+impl Default for BackpressureConfig {
+    fn default() -> Self {
+        Self {
+            high_watermark: 10000,
+            low_watermark: 5000,
+            retry_after_secs: 5,
+            strategy: BackpressureStrategy::Block,
         }
     }
 }
@@ -536,6 +649,23 @@ impl Config {
             return Err("Kafka bootstrap servers cannot be empty".to_string());
         }
 
+        if self.stream.exactly_once {
+            if self.kafka.transactions.transactional_id.is_empty() {
+                return Err("Kafka transactional ID cannot be empty".to_string());
+            }
+
+            if self.kafka.transactions.batch_size == 0 {
+                return Err("Kafka transaction batch size must be greater than 0".to_string());
+            }
+
+            // Offsets are committed through the transaction instead
+            if self.kafka.enable_auto_commit {
+                return Err(
+                    "Kafka auto commit must be disabled for exactly-once processing".to_string(),
+                );
+            }
+        }
+
         // Validate ClickHouse config
         if self.clickhouse.url.is_empty() {
             return Err("ClickHouse URL cannot be empty".to_string());
@@ -546,6 +676,14 @@ impl Config {
             return Err("Stream worker threads must be greater than 0".to_string());
         }
 
+        if self.stream.backpressure.high_watermark == 0 {
+            return Err("Backpressure high watermark must be greater than 0".to_string());
+        }
+
+        if self.stream.backpressure.low_watermark >= self.stream.backpressure.high_watermark {
+            return Err("Backpressure low watermark must be below the high watermark".to_string());
+        }
+
         // Validate batch config
         if self.batch.worker_threads == 0 {
             return Err("Batch worker threads must be greater than 0".to_string());
@@ -588,5 +726,20 @@ mod tests {
         config.performance.min_workers = 10;
         config.performance.max_workers = 5;
         assert!(config.validate().is_err());
+
+        // Reset and test backpressure watermark ordering
+        config = Config::default();
+        config.stream.backpressure.low_watermark = config.stream.backpressure.high_watermark;
+        assert!(config.validate().is_err());
+
+        // Reset and test transaction settings
+        config = Config::default();
+        config.stream.exactly_once = true;
+        config.kafka.transactions.batch_size = 0;
+        assert!(config.validate().is_err());
+
+        config.kafka.transactions.batch_size = 100;
+        config.kafka.enable_auto_commit = true;
+        assert!(config.validate().is_err());
     }
 }
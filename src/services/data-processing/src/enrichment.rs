@@ -8,15 +8,22 @@
 //! - User profile and behavioral data enrichment
 
 use std::collections::HashMap;
+use std::num::NonZeroUsize;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use chrono::{DateTime, Utc};
+use flume::{Receiver, Sender};
+use lru::LruCache;
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use tokio::sync::RwLock;
+use tracing::{debug, warn};
 
 use crate::{
     error::{DataProcessingError, Result},
+    metrics::MetricsCollector,
     types::DataRecord,
 };
 
@@ -80,6 +87,85 @@ pub struct UserProfileEnricher {
     cache: Arc<EnrichmentCache>,
 }
 
+/// Source of key-value lookups for enrichment
+#[async_trait::async_trait]
+pub trait LookupSource {
+    /// Look up the value stored under a key
+    async fn lookup(&self, key: &str) -> Result<Option<Value>>;
+}
+
+/// Redis lookup enricher configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedisLookupConfig {
+    /// Enricher name
+    pub name: String,
+    /// Redis connection URL
+    pub redis_url: String,
+    /// Record data field holding the lookup key, e.g. `user_id`
+    pub key_field: String,
+    /// Redis key template, `{}` is replaced with the lookup key
+    pub key_template: String,
+    /// How looked-up values are stored in Redis
+    pub value_format: LookupValueFormat,
+    /// Data field the looked-up value is written to; the value's fields are
+    /// merged into the record data when unset
+    pub target_field: Option<String>,
+    /// Handling of records whose key is missing or not found
+    pub on_miss: MissPolicy,
+    /// Maximum number of cached lookups
+    pub cache_capacity: usize,
+    /// Lifetime of cached lookups, including misses, in seconds
+    pub cache_ttl_secs: u64,
+    /// Capacity of the side output for missed records
+    pub side_output_capacity: usize,
+}
+
+/// Storage format of looked-up values
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LookupValueFormat {
+    /// A JSON document stored as a string
+    Json,
+    /// A Redis hash of string fields
+    Hash,
+}
+
+/// Handling of records without a lookup result
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MissPolicy {
+    /// Leave the record unchanged
+    PassThrough,
+    /// Send a copy of the record to the enricher's side output
+    SideOutput,
+}
+
+/// Redis-backed lookup source
+pub struct RedisLookupSource {
+    connection: redis::aio::ConnectionManager,
+    value_format: LookupValueFormat,
+}
+
+/// Enricher that merges supplementary fields looked up in Redis by a record key
+///
+/// Lookups, including misses, are cached in an in-process LRU so repeated keys
+/// do not reach Redis until their cache entry expires.
+pub struct RedisLookupEnricher {
+    config: RedisLookupConfig,
+    source: Arc<dyn LookupSource + Send + Sync>,
+    cache: Mutex<LruCache<String, CachedLookup>>,
+    cache_ttl: Duration,
+    side_output: (Sender<DataRecord>, Receiver<DataRecord>),
+    metrics: Arc<MetricsCollector>,
+}
+
+/// Cached lookup result
+#[derive(Debug, Clone)]
+struct CachedLookup {
+    value: Option<Value>,
+    expires_at: Instant,
+}
+
 impl EnrichmentEngine {
     pub fn new() -> Self {
         Self {
@@ -203,6 +289,284 @@ impl DataEnricher for UserProfileEnricher {
     }
 }
 
+impl RedisLookupSource {
+    /// Connect to Redis
+    pub async fn connect(redis_url: &str, value_format: LookupValueFormat) -> Result<Self> {
+        let client = redis::Client::open(redis_url)
+            .map_err(|e| DataProcessingError::configuration(format!("Invalid Redis URL: {}", e)))?;
+        let connection = redis::aio::ConnectionManager::new(client)
+            .await
+            .map_err(|e| DataProcessingError::external_service("redis", e.to_string()))?;
+
+        Ok(Self {
+            connection,
+            value_format,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl LookupSource for RedisLookupSource {
+    async fn lookup(&self, key: &str) -> Result<Option<Value>> {
+        let mut connection = self.connection.clone();
+        let redis_error = |e: redis::RedisError| {
+            DataProcessingError::external_service("redis", format!("Lookup of {}: {}", key, e))
+        };
+
+        match self.value_format {
+            LookupValueFormat::Json => {
+                let raw: Option<String> = redis::cmd("GET")
+                    .arg(key)
+                    .query_async(&mut connection)
+                    .await
+                    .map_err(redis_error)?;
+                raw.map(|raw| {
+                    serde_json::from_str(&raw).map_err(|e| {
+                        DataProcessingError::serialization(format!(
+                            "Invalid JSON stored under {}: {}",
+                            key, e
+                        ))
+                    })
+                })
+                .transpose()
+            }
+            LookupValueFormat::Hash => {
+                let fields: HashMap<String, String> = redis::cmd("HGETALL")
+                    .arg(key)
+                    .query_async(&mut connection)
+                    .await
+                    .map_err(redis_error)?;
+                if fields.is_empty() {
+                    return Ok(None);
+                }
+                Ok(Some(Value::Object(
+                    fields
+                        .into_iter()
+                        .map(|(field, value)| (field, Value::String(value)))
+                        .collect(),
+                )))
+            }
+        }
+    }
+}
+
+impl RedisLookupEnricher {
+    /// Create an enricher that looks up values in Redis
+    pub async fn new(config: RedisLookupConfig, metrics: Arc<MetricsCollector>) -> Result<Self> {
+        let source = RedisLookupSource::connect(&config.redis_url, config.value_format).await?;
+        Self::with_source(config, Arc::new(source), metrics)
+    }
+
+    /// Create an enricher over a custom lookup source
+    pub fn with_source(
+        config: RedisLookupConfig,
+        source: Arc<dyn LookupSource + Send + Sync>,
+        metrics: Arc<MetricsCollector>,
+    ) -> Result<Self> {
+        let capacity = NonZeroUsize::new(config.cache_capacity).ok_or_else(|| {
+            DataProcessingError::validation(
+                "cache_capacity",
+                "Cache capacity must be greater than 0",
+            )
+        })?;
+        if !config.key_template.contains("{}") {
+            return Err(DataProcessingError::validation(
+                "key_template",
+                "Key template must contain a {} placeholder",
+            ));
+        }
+
+        Ok(Self {
+            cache: Mutex::new(LruCache::new(capacity)),
+            cache_ttl: Duration::from_secs(config.cache_ttl_secs),
+            side_output: flume::bounded(config.side_output_capacity),
+            config,
+            source,
+            metrics,
+        })
+    }
+
+    /// Receiver for records routed to the side output on a miss
+    pub fn side_output(&self) -> Receiver<DataRecord> {
+        self.side_output.1.clone()
+    }
+
+    /// Lookup key of a record, if its key field holds a string or number
+    fn lookup_key(&self, record: &DataRecord) -> Option<String> {
+        match record.data.get(&self.config.key_field)? {
+            Value::String(key) => Some(key.clone()),
+            Value::Number(key) => Some(key.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Look up a key, returning the value and whether it came from the cache
+    async fn lookup(&self, key: &str) -> Result<(Option<Value>, bool)> {
+        let redis_key = self.config.key_template.replace("{}", key);
+        let start_time = Instant::now();
+
+        if let Some(value) = self.cached(&redis_key) {
+            self.record_lookup("cache", value.is_some(), start_time);
+            return Ok((value, true));
+        }
+
+        let value = match self.source.lookup(&redis_key).await {
+            Ok(value) => value,
+            Err(e) => {
+                self.metrics.increment_counter(
+                    "enrichment_lookups_total",
+                    &[
+                        ("enricher", &self.config.name),
+                        ("source", "redis"),
+                        ("result", "error"),
+                    ],
+                );
+                return Err(e);
+            }
+        };
+        self.record_lookup("redis", value.is_some(), start_time);
+
+        self.cache.lock().put(
+            redis_key,
+            CachedLookup {
+                value: value.clone(),
+                expires_at: Instant::now() + self.cache_ttl,
+            },
+        );
+
+        Ok((value, false))
+    }
+
+    /// Unexpired cached lookup for a key
+    fn cached(&self, key: &str) -> Option<Option<Value>> {
+        let mut cache = self.cache.lock();
+        match cache.get(key) {
+            Some(entry) if entry.expires_at > Instant::now() => Some(entry.value.clone()),
+            Some(_) => {
+                cache.pop(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn record_lookup(&self, source: &str, hit: bool, start_time: Instant) {
+        let result = if hit { "hit" } else { "miss" };
+        self.metrics.increment_counter(
+            "enrichment_lookups_total",
+            &[
+                ("enricher", &self.config.name),
+                ("source", source),
+                ("result", result),
+            ],
+        );
+        self.metrics.record_histogram(
+            "enrichment_lookup_duration_seconds",
+            start_time.elapsed().as_secs_f64(),
+            &[("enricher", &self.config.name), ("source", source)],
+        );
+    }
+
+    /// Merge a looked-up value into the record, returning the enriched fields
+    fn merge(&self, record: &mut DataRecord, value: Value) -> Vec<String> {
+        if record.data.is_null() {
+            record.data = Value::Object(serde_json::Map::new());
+        }
+        let Value::Object(data) = &mut record.data else {
+            warn!(
+                "Record {} data is not an object, skipping {} enrichment",
+                record.id, self.config.name
+            );
+            return Vec::new();
+        };
+
+        match (&self.config.target_field, value) {
+            (Some(field), value) => {
+                data.insert(field.clone(), value);
+                vec![field.clone()]
+            }
+            (None, Value::Object(fields)) => {
+                let names = fields.keys().cloned().collect();
+                data.extend(fields);
+                names
+            }
+            (None, _) => {
+                warn!(
+                    "{} lookup value is not an object and no target field is set",
+                    self.config.name
+                );
+                Vec::new()
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl DataEnricher for RedisLookupEnricher {
+    async fn enrich(&self, record: &mut DataRecord) -> Result<EnrichmentResult> {
+        let start_time = Instant::now();
+
+        let (value, cache_hits, cache_misses) = match self.lookup_key(record) {
+            Some(key) => {
+                let (value, cached) = self.lookup(&key).await?;
+                (value, u32::from(cached), u32::from(!cached))
+            }
+            None => {
+                debug!(
+                    "Record {} has no {} field for {} lookup",
+                    record.id, self.config.key_field, self.config.name
+                );
+                (None, 0, 0)
+            }
+        };
+
+        let found = value.is_some();
+        let enriched_fields = match value {
+            Some(value) => self.merge(record, value),
+            None => {
+                if self.config.on_miss == MissPolicy::SideOutput {
+                    if let Err(e) = self.side_output.0.try_send(record.clone()) {
+                        warn!(
+                            "Failed to route record {} to {} side output: {}",
+                            record.id, self.config.name, e
+                        );
+                    }
+                }
+                Vec::new()
+            }
+        };
+
+        Ok(EnrichmentResult {
+            success: found || self.config.on_miss == MissPolicy::PassThrough,
+            enriched_fields,
+            cache_hits,
+            cache_misses,
+            duration_ms: start_time.elapsed().as_millis() as u64,
+        })
+    }
+
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+}
+
+impl Default for RedisLookupConfig {
+    fn default() -> Self {
+        Self {
+            name: "redis_lookup".to_string(),
+            redis_url: "redis://localhost:6379".to_string(),
+            key_field: "user_id".to_string(),
+            key_template: "user:{}".to_string(),
+            value_format: LookupValueFormat::Json,
+            target_field: Some("user_profile".to_string()),
+            on_miss: MissPolicy::PassThrough,
+            cache_capacity: 10_000,
+            cache_ttl_secs: 300,
+            side_output_capacity: 1000,
+        }
+    }
+}
+
 impl Default for EnrichmentEngine {
     fn default() -> Self {
         Self::new()
@@ -212,6 +576,112 @@ impl Default for EnrichmentEngine {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::Config;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// In-memory lookup source counting backend calls
+    struct StaticLookup {
+        values: HashMap<String, Value>,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl LookupSource for StaticLookup {
+        async fn lookup(&self, key: &str) -> Result<Option<Value>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(self.values.get(key).cloned())
+        }
+    }
+
+    fn lookup_enricher(config: RedisLookupConfig) -> (RedisLookupEnricher, Arc<StaticLookup>) {
+        let source = Arc::new(StaticLookup {
+            values: HashMap::from([
+                (
+                    "user:42".to_string(),
+                    json!({"name": "Ada", "tier": "gold"}),
+                ),
+                (
+                    "user:7".to_string(),
+                    json!({"name": "Grace", "tier": "silver"}),
+                ),
+            ]),
+            calls: AtomicUsize::new(0),
+        });
+        let metrics = Arc::new(MetricsCollector::new(&Config::default()).unwrap());
+        let enricher = RedisLookupEnricher::with_source(config, source.clone(), metrics).unwrap();
+        (enricher, source)
+    }
+
+    fn record_for(user_id: Value) -> DataRecord {
+        DataRecord {
+            data: json!({ "user_id": user_id, "action": "login" }),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_redis_lookup_merges_and_caches() {
+        let (enricher, source) = lookup_enricher(RedisLookupConfig::default());
+
+        let mut record = record_for(json!(42));
+        let result = enricher.enrich(&mut record).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.enriched_fields, vec!["user_profile".to_string()]);
+        assert_eq!(result.cache_misses, 1);
+        assert_eq!(record.data["user_profile"]["tier"], "gold");
+        assert_eq!(record.data["action"], "login");
+
+        let mut record = record_for(json!("42"));
+        let result = enricher.enrich(&mut record).await.unwrap();
+        assert_eq!(result.cache_hits, 1);
+        assert_eq!(record.data["user_profile"]["name"], "Ada");
+        assert_eq!(source.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_redis_lookup_routes_misses_to_side_output() {
+        let (enricher, source) = lookup_enricher(RedisLookupConfig {
+            target_field: None,
+            on_miss: MissPolicy::SideOutput,
+            ..Default::default()
+        });
+        let side_output = enricher.side_output();
+
+        let mut record = record_for(json!(99));
+        let original = record.data.clone();
+        let result = enricher.enrich(&mut record).await.unwrap();
+        assert!(!result.success);
+        assert!(result.enriched_fields.is_empty());
+        assert_eq!(record.data, original);
+        assert_eq!(side_output.try_recv().unwrap().id, record.id);
+
+        // Misses are cached as well
+        enricher.enrich(&mut record_for(json!(99))).await.unwrap();
+        assert_eq!(source.calls.load(Ordering::SeqCst), 1);
+
+        // Without a target field the value's fields are merged into the data
+        let mut record = record_for(json!(7));
+        let result = enricher.enrich(&mut record).await.unwrap();
+        assert!(result.success);
+        assert_eq!(record.data["tier"], "silver");
+    }
+
+    #[tokio::test]
+    async fn test_redis_lookup_cache_evicts_least_recent() {
+        let (enricher, source) = lookup_enricher(RedisLookupConfig {
+            cache_capacity: 1,
+            ..Default::default()
+        });
+
+        for user_id in [42, 7, 42] {
+            enricher
+                .enrich(&mut record_for(json!(user_id)))
+                .await
+                .unwrap();
+        }
+        assert_eq!(source.calls.load(Ordering::SeqCst), 3);
+    }
 
     #[tokio::test]
     async fn test_enrichment_engine() {
@@ -9,6 +9,7 @@
 //! - Exactly-once processing support
 
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -19,8 +20,8 @@ use rdkafka::{
     config::{ClientConfig, RDKafkaLogLevel},
     consumer::{Consumer, StreamConsumer},
     message::{Headers, Message, OwnedHeaders},
-    producer::{FutureProducer, FutureRecord},
-    ClientContext, TopicPartitionList,
+    producer::{FutureProducer, FutureRecord, Producer},
+    ClientContext, Offset, TopicPartitionList,
 };
 use serde::{Deserialize, Serialize};
 use tokio::sync::{mpsc, RwLock};
@@ -39,6 +40,8 @@ use crate::{
 pub struct KafkaManager {
     config: Arc<KafkaConfig>,
     producer: Arc<FutureProducer>,
+    /// Producer for exactly-once processing, present when `stream.exactly_once` is set
+    transactional_producer: Option<Arc<FutureProducer>>,
     consumer: Arc<StreamConsumer>,
     admin_client: Arc<AdminClient<DefaultClientContext>>,
     metrics: Arc<MetricsCollector>,
@@ -61,6 +64,25 @@ pub struct KafkaMessage {
     pub headers: HashMap<String, Vec<u8>>,
 }
 
+/// Record produced inside a Kafka transaction
+#[derive(Debug, Clone)]
+pub struct TransactionalRecord {
+    pub topic: String,
+    pub key: Option<Vec<u8>>,
+    pub payload: Vec<u8>,
+}
+
+/// Open Kafka transaction
+///
+/// A transaction that is dropped without being committed is aborted, so output
+/// of an early return or a panic never becomes visible to read_committed readers.
+pub struct KafkaTransaction {
+    producer: Arc<FutureProducer>,
+    consumer: Arc<StreamConsumer>,
+    timeout: Duration,
+    finished: bool,
+}
+
 /// Producer configuration
 #[derive(Debug, Clone)]
 pub struct ProducerConfig {
@@ -161,6 +183,15 @@ impl KafkaManager {
         // Create producer
         let producer = Self::create_producer(kafka_config).await?;
 
+        // Create transactional producer for exactly-once processing
+        let transactional_producer = if config.stream.exactly_once {
+            Some(Arc::new(
+                Self::create_transactional_producer(kafka_config).await?,
+            ))
+        } else {
+            None
+        };
+
         // Create consumer
         let consumer = Self::create_consumer(kafka_config).await?;
 
@@ -170,6 +201,7 @@ impl KafkaManager {
         let manager = Self {
             config: Arc::new(kafka_config.clone()),
             producer: Arc::new(producer),
+            transactional_producer,
             consumer: Arc::new(consumer),
             admin_client: Arc::new(admin_client),
             metrics,
@@ -186,6 +218,57 @@ impl KafkaManager {
 
     /// Create Kafka producer
     async fn create_producer(config: &KafkaConfig) -> Result<FutureProducer> {
+        let producer: FutureProducer =
+            Self::producer_client_config(config)
+                .create()
+                .map_err(|e| KafkaError::Connection {
+                    message: format!("Failed to create producer: {}", e),
+                })?;
+
+        Ok(producer)
+    }
+
+    /// Create a transactional Kafka producer
+    ///
+    /// Initializing transactions fences any earlier producer with the same
+    /// transactional ID and aborts the transaction it left open.
+    async fn create_transactional_producer(config: &KafkaConfig) -> Result<FutureProducer> {
+        let transactions = &config.transactions;
+        let mut client_config = Self::producer_client_config(config);
+        client_config
+            .set(
+                "client.id",
+                &format!("{}-transactional", config.producer_client_id),
+            )
+            .set("transactional.id", &transactions.transactional_id)
+            .set(
+                "transaction.timeout.ms",
+                &transactions.transaction_timeout_ms.to_string(),
+            );
+
+        let producer: FutureProducer =
+            client_config.create().map_err(|e| KafkaError::Connection {
+                message: format!("Failed to create transactional producer: {}", e),
+            })?;
+
+        producer
+            .init_transactions(Duration::from_millis(transactions.transaction_timeout_ms))
+            .map_err(|e| KafkaError::Producer {
+                message: format!(
+                    "Failed to initialize transactions for {}: {}",
+                    transactions.transactional_id, e
+                ),
+            })?;
+
+        info!(
+            "Initialized transactional producer {}",
+            transactions.transactional_id
+        );
+        Ok(producer)
+    }
+
+    /// Client configuration shared by all producers
+    fn producer_client_config(config: &KafkaConfig) -> ClientConfig {
         let mut client_config = ClientConfig::new();
 
         client_config
@@ -228,13 +311,7 @@ impl KafkaManager {
         }
 
         client_config.set_log_level(RDKafkaLogLevel::Info);
-
-        let producer: FutureProducer =
-            client_config.create().map_err(|e| KafkaError::Connection {
-                message: format!("Failed to create producer: {}", e),
-            })?;
-
-        Ok(producer)
+        client_config
     }
 
     /// Create Kafka consumer
@@ -250,6 +327,10 @@ impl KafkaManager {
             )
             .set("auto.offset.reset", &config.auto_offset_reset)
             .set("enable.auto.commit", &config.enable_auto_commit.to_string())
+            .set(
+                "isolation.level",
+                config.transactions.isolation_level.as_str(),
+            )
             .set(
                 "auto.commit.interval.ms",
                 &config.auto_commit_interval_ms.to_string(),
@@ -463,15 +544,7 @@ impl KafkaManager {
                     Ok(message) => {
                         let start_time = Instant::now();
 
-                        let kafka_message = KafkaMessage {
-                            topic: message.topic().to_string(),
-                            partition: message.partition(),
-                            offset: message.offset(),
-                            key: message.key().map(|k| k.to_vec()),
-                            payload: message.payload().unwrap_or(&[]).to_vec(),
-                            timestamp: message.timestamp().to_millis(),
-                            headers: Self::extract_headers(&message),
-                        };
+                        let kafka_message = Self::to_kafka_message(&message);
 
                         // Update metrics
                         metrics.increment_counter(
@@ -522,6 +595,157 @@ impl KafkaManager {
         Ok(rx)
     }
 
+    /// Consume topics with exactly-once semantics
+    ///
+    /// Messages are processed in batches of up to `transactions.batch_size`. The
+    /// records returned by `process` and the consumed offsets of the batch are
+    /// committed in one transaction. When processing or the commit fails the
+    /// transaction is aborted and the consumer rewinds to the start of the batch,
+    /// so the batch is retried without exposing output of the failed attempt.
+    /// Only returns on errors the transaction cannot recover from.
+    pub async fn run_transactional<F, Fut>(&self, topics: &[String], mut process: F) -> Result<()>
+    where
+        F: FnMut(KafkaMessage) -> Fut,
+        Fut: Future<Output = Result<Vec<TransactionalRecord>>>,
+    {
+        info!("Subscribing to topics with transactions: {:?}", topics);
+
+        self.consumer
+            .subscribe(&topics.iter().map(|s| s.as_str()).collect::<Vec<_>>())
+            .map_err(|e| KafkaError::Consumer {
+                message: format!("Failed to subscribe to topics: {}", e),
+            })?;
+
+        loop {
+            let batch = self.next_batch().await;
+            let mut transaction = self.begin_transaction()?;
+
+            let mut outcome = Ok(());
+            for message in &batch {
+                outcome = match process(message.clone()).await {
+                    Ok(records) => transaction.send_all(&records).await,
+                    Err(e) => Err(e),
+                };
+                if outcome.is_err() {
+                    break;
+                }
+            }
+
+            let outcome = match outcome {
+                Ok(()) => transaction.commit(&batch),
+                Err(e) => {
+                    transaction.abort()?;
+                    Err(e)
+                }
+            };
+
+            match outcome {
+                Ok(()) => {
+                    debug!("Committed transaction for {} messages", batch.len());
+                }
+                Err(e) => {
+                    warn!(
+                        "Transaction for {} messages aborted, retrying batch: {}",
+                        batch.len(),
+                        e
+                    );
+                    self.rewind(&batch)?;
+                }
+            }
+        }
+    }
+
+    /// Begin a transaction on the transactional producer
+    pub fn begin_transaction(&self) -> Result<KafkaTransaction> {
+        let producer =
+            self.transactional_producer
+                .clone()
+                .ok_or_else(|| KafkaError::Configuration {
+                    parameter: "stream.exactly_once".to_string(),
+                    message: "Transactions require exactly-once processing to be enabled"
+                        .to_string(),
+                })?;
+
+        producer
+            .begin_transaction()
+            .map_err(|e| KafkaError::Producer {
+                message: format!("Failed to begin transaction: {}", e),
+            })?;
+
+        Ok(KafkaTransaction {
+            producer,
+            consumer: self.consumer.clone(),
+            timeout: Duration::from_millis(self.config.transactions.transaction_timeout_ms),
+            finished: false,
+        })
+    }
+
+    /// Wait for the next batch of messages
+    ///
+    /// Blocks until a message arrives, then collects more until the batch is full
+    /// or `transactions.batch_linger_ms` has passed.
+    async fn next_batch(&self) -> Vec<KafkaMessage> {
+        let settings = &self.config.transactions;
+        let mut batch = Vec::with_capacity(settings.batch_size);
+
+        while batch.is_empty() {
+            match self.consumer.recv().await {
+                Ok(message) => batch.push(Self::to_kafka_message(&message)),
+                Err(e) => {
+                    error!("Error receiving message: {}", e);
+                    self.metrics
+                        .increment_counter("kafka_consume_errors_total", &[]);
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+            }
+        }
+
+        let deadline =
+            tokio::time::Instant::now() + Duration::from_millis(settings.batch_linger_ms);
+        while batch.len() < settings.batch_size {
+            match tokio::time::timeout_at(deadline, self.consumer.recv()).await {
+                Ok(Ok(message)) => batch.push(Self::to_kafka_message(&message)),
+                Ok(Err(e)) => {
+                    error!("Error receiving message: {}", e);
+                    self.metrics
+                        .increment_counter("kafka_consume_errors_total", &[]);
+                    break;
+                }
+                Err(_) => break,
+            }
+        }
+
+        for message in &batch {
+            self.metrics.increment_counter(
+                "kafka_messages_consumed_total",
+                &[("topic", &message.topic)],
+            );
+        }
+
+        batch
+    }
+
+    /// Seek the consumer back to the first message of a batch
+    fn rewind(&self, batch: &[KafkaMessage]) -> Result<()> {
+        for ((topic, partition), offset) in batch_start_offsets(batch) {
+            self.consumer
+                .seek(
+                    &topic,
+                    partition,
+                    Offset::Offset(offset),
+                    Duration::from_secs(10),
+                )
+                .map_err(|e| KafkaError::Offset {
+                    message: format!(
+                        "Failed to rewind {} partition {} to offset {}: {}",
+                        topic, partition, offset, e
+                    ),
+                })?;
+        }
+
+        Ok(())
+    }
+
     /// Register a message handler for a specific topic
     pub async fn register_handler<F>(&self, topic: String, handler: F)
     where
@@ -594,6 +818,44 @@ impl KafkaManager {
         Ok(())
     }
 
+    /// Pause fetching on all assigned partitions
+    pub fn pause_consumption(&self) -> Result<()> {
+        let assignment = self
+            .consumer
+            .assignment()
+            .map_err(|e| KafkaError::Consumer {
+                message: format!("Failed to read partition assignment: {}", e),
+            })?;
+
+        self.consumer
+            .pause(&assignment)
+            .map_err(|e| KafkaError::Consumer {
+                message: format!("Failed to pause consumption: {}", e),
+            })?;
+
+        info!("Paused consumption on {} partitions", assignment.count());
+        Ok(())
+    }
+
+    /// Resume fetching on all assigned partitions
+    pub fn resume_consumption(&self) -> Result<()> {
+        let assignment = self
+            .consumer
+            .assignment()
+            .map_err(|e| KafkaError::Consumer {
+                message: format!("Failed to read partition assignment: {}", e),
+            })?;
+
+        self.consumer
+            .resume(&assignment)
+            .map_err(|e| KafkaError::Consumer {
+                message: format!("Failed to resume consumption: {}", e),
+            })?;
+
+        info!("Resumed consumption on {} partitions", assignment.count());
+        Ok(())
+    }
+
     /// Get current health status
     pub async fn get_health(&self) -> HealthStatus {
         self.health_status.read().await.clone()
@@ -649,6 +911,19 @@ impl KafkaManager {
         });
     }
 
+    /// Convert a consumed message into a `KafkaMessage`
+    fn to_kafka_message<M: Message>(message: &M) -> KafkaMessage {
+        KafkaMessage {
+            topic: message.topic().to_string(),
+            partition: message.partition(),
+            offset: message.offset(),
+            key: message.key().map(|k| k.to_vec()),
+            payload: message.payload().unwrap_or(&[]).to_vec(),
+            timestamp: message.timestamp().to_millis(),
+            headers: Self::extract_headers(message),
+        }
+    }
+
     /// Extract headers from Kafka message
     fn extract_headers<M: Message>(message: &M) -> HashMap<String, Vec<u8>> {
         let mut headers = HashMap::new();
@@ -681,6 +956,115 @@ impl KafkaManager {
     }
 }
 
+impl KafkaTransaction {
+    /// Produce records as part of the transaction
+    pub async fn send_all(&mut self, records: &[TransactionalRecord]) -> Result<()> {
+        for record in records {
+            let mut future_record =
+                FutureRecord::<[u8], [u8]>::to(&record.topic).payload(&record.payload);
+            if let Some(key) = &record.key {
+                future_record = future_record.key(key.as_slice());
+            }
+
+            self.producer
+                .send(future_record, self.timeout)
+                .await
+                .map_err(|(e, _)| KafkaError::Producer {
+                    message: format!(
+                        "Failed to send transactional message to {}: {}",
+                        record.topic, e
+                    ),
+                })?;
+        }
+
+        Ok(())
+    }
+
+    /// Commit produced records together with the consumed offsets of a batch
+    pub fn commit(mut self, consumed: &[KafkaMessage]) -> Result<()> {
+        let mut offsets = TopicPartitionList::new();
+        for ((topic, partition), offset) in next_offsets(consumed) {
+            offsets
+                .add_partition_offset(&topic, partition, Offset::Offset(offset))
+                .map_err(|e| KafkaError::Offset {
+                    message: format!(
+                        "Invalid offset for {} partition {}: {}",
+                        topic, partition, e
+                    ),
+                })?;
+        }
+
+        let group_metadata = self
+            .consumer
+            .group_metadata()
+            .ok_or_else(|| KafkaError::Offset {
+                message: "Consumer group metadata is unavailable".to_string(),
+            })?;
+
+        self.producer
+            .send_offsets_to_transaction(&offsets, &group_metadata, self.timeout)
+            .map_err(|e| KafkaError::Offset {
+                message: format!("Failed to add offsets to transaction: {}", e),
+            })?;
+
+        self.producer
+            .commit_transaction(self.timeout)
+            .map_err(|e| KafkaError::Producer {
+                message: format!("Failed to commit transaction: {}", e),
+            })?;
+
+        self.finished = true;
+        Ok(())
+    }
+
+    /// Abort the transaction, discarding its records and offsets
+    pub fn abort(mut self) -> Result<()> {
+        self.finished = true;
+        self.producer
+            .abort_transaction(self.timeout)
+            .map_err(|e| KafkaError::Producer {
+                message: format!("Failed to abort transaction: {}", e),
+            })?;
+
+        Ok(())
+    }
+}
+
+impl Drop for KafkaTransaction {
+    fn drop(&mut self) {
+        if !self.finished {
+            warn!("Aborting unfinished Kafka transaction");
+            if let Err(e) = self.producer.abort_transaction(self.timeout) {
+                error!("Failed to abort transaction: {}", e);
+            }
+        }
+    }
+}
+
+/// Offset of the next message to consume for each partition in a batch
+fn next_offsets(batch: &[KafkaMessage]) -> HashMap<(String, i32), i64> {
+    let mut offsets = HashMap::new();
+    for message in batch {
+        let next = offsets
+            .entry((message.topic.clone(), message.partition))
+            .or_insert(message.offset + 1);
+        *next = (*next).max(message.offset + 1);
+    }
+    offsets
+}
+
+/// Offset of the first message of each partition in a batch
+fn batch_start_offsets(batch: &[KafkaMessage]) -> HashMap<(String, i32), i64> {
+    let mut offsets = HashMap::new();
+    for message in batch {
+        let start = offsets
+            .entry((message.topic.clone(), message.partition))
+            .or_insert(message.offset);
+        *start = (*start).min(message.offset);
+    }
+    offsets
+}
+
 impl Default for PublishOptions {
     fn default() -> Self {
         Self {
@@ -737,6 +1121,40 @@ mod tests {
         assert!(options.headers.is_empty());
     }
 
+    fn message(topic: &str, partition: i32, offset: i64) -> KafkaMessage {
+        KafkaMessage {
+            topic: topic.to_string(),
+            partition,
+            offset,
+            key: None,
+            payload: Vec::new(),
+            timestamp: None,
+            headers: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_transaction_batch_offsets() {
+        let batch = vec![
+            message("events", 0, 7),
+            message("events", 1, 3),
+            message("events", 0, 5),
+            message("events", 0, 6),
+            message("audit", 0, 42),
+        ];
+
+        let next = next_offsets(&batch);
+        assert_eq!(next.len(), 3);
+        assert_eq!(next[&("events".to_string(), 0)], 8);
+        assert_eq!(next[&("events".to_string(), 1)], 4);
+        assert_eq!(next[&("audit".to_string(), 0)], 43);
+
+        let start = batch_start_offsets(&batch);
+        assert_eq!(start[&("events".to_string(), 0)], 5);
+        assert_eq!(start[&("events".to_string(), 1)], 3);
+        assert_eq!(start[&("audit".to_string(), 0)], 42);
+    }
+
     #[test]
     fn test_subscription_options_default() {
         let options = SubscriptionOptions::default();
@@ -17,6 +17,7 @@
 //!
 //! ### Analytics Processing
 //! - Batch processing for large-scale analytics
+//! - Batch job output as Parquet, CSV or JSON Lines, optionally partitioned
 //! - Integration with ClickHouse for columnar analytics
 //! - Apache Arrow for efficient columnar operations
 //! - DataFusion SQL engine for complex queries
@@ -58,6 +59,7 @@ pub mod error;
 pub mod health;
 pub mod kafka;
 pub mod metrics;
+pub mod output;
 pub mod server;
 pub mod stream;
 pub mod transformations;
@@ -204,6 +206,11 @@ impl DataProcessingService {
         self.stream_processor.process_record(record).await
     }
 
+    /// Get the backpressure state of stream ingestion
+    pub fn stream_backpressure(&self) -> stream::BackpressureStatus {
+        self.stream_processor.backpressure_status()
+    }
+
     /// Submit a batch processing job
     pub async fn submit_batch_job(&self, job: BatchJob) -> Result<String> {
         self.batch_processor.submit_job(job).await
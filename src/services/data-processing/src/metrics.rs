@@ -42,9 +42,11 @@ pub struct MetricsCollector {
     batch_jobs_completed_total: IntCounterVec,
     batch_jobs_failed_total: IntCounterVec,
     stream_records_processed_total: IntCounter,
+    stream_records_dropped_total: IntCounterVec,
     worker_tasks_processed_total: IntCounterVec,
     checkpoints_created_total: IntCounter,
     watermarks_updated_total: IntCounter,
+    enrichment_lookups_total: IntCounterVec,
 
     // Gauge metrics
     active_batch_jobs: IntGauge,
@@ -66,6 +68,7 @@ pub struct MetricsCollector {
     worker_task_duration_seconds: HistogramVec,
     database_query_duration_seconds: HistogramVec,
     http_request_duration_seconds: HistogramVec,
+    enrichment_lookup_duration_seconds: HistogramVec,
 
     // Custom metrics registry
     custom_counters: Arc<RwLock<HashMap<String, Counter>>>,
@@ -200,6 +203,14 @@ impl MetricsCollector {
             "Total stream records processed",
         )?;
 
+        let stream_records_dropped_total = IntCounterVec::new(
+            Opts::new(
+                "stream_records_dropped_total",
+                "Total stream records dropped by backpressure",
+            ),
+            &["strategy"],
+        )?;
+
         let worker_tasks_processed_total = IntCounterVec::new(
             Opts::new(
                 "worker_tasks_processed_total",
@@ -214,6 +225,11 @@ impl MetricsCollector {
         let watermarks_updated_total =
             IntCounter::new("watermarks_updated_total", "Total watermarks updated")?;
 
+        let enrichment_lookups_total = IntCounterVec::new(
+            Opts::new("enrichment_lookups_total", "Total enrichment lookups"),
+            &["enricher", "source", "result"],
+        )?;
+
         // Initialize gauge metrics
         let active_batch_jobs =
             IntGauge::new("active_batch_jobs", "Number of currently active batch jobs")?;
@@ -319,6 +335,15 @@ impl MetricsCollector {
             &["method", "endpoint", "status"],
         )?;
 
+        let enrichment_lookup_duration_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "enrichment_lookup_duration_seconds",
+                "Enrichment lookup duration in seconds",
+            )
+            .buckets(vec![0.0001, 0.0005, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5]),
+            &["enricher", "source"],
+        )?;
+
         // Register all metrics with Prometheus registry
         registry.register(Box::new(records_processed_total.clone()))?;
         registry.register(Box::new(records_failed_total.clone()))?;
@@ -330,9 +355,11 @@ impl MetricsCollector {
         registry.register(Box::new(batch_jobs_completed_total.clone()))?;
         registry.register(Box::new(batch_jobs_failed_total.clone()))?;
         registry.register(Box::new(stream_records_processed_total.clone()))?;
+        registry.register(Box::new(stream_records_dropped_total.clone()))?;
         registry.register(Box::new(worker_tasks_processed_total.clone()))?;
         registry.register(Box::new(checkpoints_created_total.clone()))?;
         registry.register(Box::new(watermarks_updated_total.clone()))?;
+        registry.register(Box::new(enrichment_lookups_total.clone()))?;
 
         registry.register(Box::new(active_batch_jobs.clone()))?;
         registry.register(Box::new(active_stream_workers.clone()))?;
@@ -352,6 +379,7 @@ impl MetricsCollector {
         registry.register(Box::new(worker_task_duration_seconds.clone()))?;
         registry.register(Box::new(database_query_duration_seconds.clone()))?;
         registry.register(Box::new(http_request_duration_seconds.clone()))?;
+        registry.register(Box::new(enrichment_lookup_duration_seconds.clone()))?;
 
         let collector = Self {
             registry,
@@ -366,9 +394,11 @@ impl MetricsCollector {
             batch_jobs_completed_total,
             batch_jobs_failed_total,
             stream_records_processed_total,
+            stream_records_dropped_total,
             worker_tasks_processed_total,
             checkpoints_created_total,
             watermarks_updated_total,
+            enrichment_lookups_total,
             active_batch_jobs,
             active_stream_workers,
             kafka_consumer_lag,
@@ -386,6 +416,7 @@ impl MetricsCollector {
             worker_task_duration_seconds,
             database_query_duration_seconds,
             http_request_duration_seconds,
+            enrichment_lookup_duration_seconds,
             custom_counters: Arc::new(RwLock::new(HashMap::new())),
             custom_gauges: Arc::new(RwLock::new(HashMap::new())),
             custom_histograms: Arc::new(RwLock::new(HashMap::new())),
@@ -444,6 +475,16 @@ impl MetricsCollector {
             "stream_records_processed_total" => {
                 self.stream_records_processed_total.inc();
             }
+            "stream_records_dropped_total" => {
+                let strategy = labels
+                    .iter()
+                    .find(|(k, _)| *k == "strategy")
+                    .map(|(_, v)| *v)
+                    .unwrap_or("unknown");
+                self.stream_records_dropped_total
+                    .with_label_values(&[strategy])
+                    .inc();
+            }
             "worker_tasks_processed_total" => {
                 if let Some(worker) = labels.iter().find(|(k, _)| *k == "worker").map(|(_, v)| *v) {
                     self.worker_tasks_processed_total
@@ -457,6 +498,18 @@ impl MetricsCollector {
             "watermarks_updated_total" => {
                 self.watermarks_updated_total.inc();
             }
+            "enrichment_lookups_total" => {
+                let label = |name: &str| {
+                    labels
+                        .iter()
+                        .find(|(k, _)| *k == name)
+                        .map(|(_, v)| *v)
+                        .unwrap_or("unknown")
+                };
+                self.enrichment_lookups_total
+                    .with_label_values(&[label("enricher"), label("source"), label("result")])
+                    .inc();
+            }
             _ => {
                 debug!("Unknown counter metric: {}", name);
             }
@@ -566,6 +619,18 @@ impl MetricsCollector {
                     .with_label_values(&[method, endpoint, status])
                     .observe(value);
             }
+            "enrichment_lookup_duration_seconds" => {
+                let label = |name: &str| {
+                    labels
+                        .iter()
+                        .find(|(k, _)| *k == name)
+                        .map(|(_, v)| *v)
+                        .unwrap_or("unknown")
+                };
+                self.enrichment_lookup_duration_seconds
+                    .with_label_values(&[label("enricher"), label("source")])
+                    .observe(value);
+            }
             _ => {
                 debug!("Unknown histogram metric: {}", name);
             }
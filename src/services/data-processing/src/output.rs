@@ -0,0 +1,532 @@
+//! Batch output module for the Data Processing Service
+//!
+//! Writes batch job results as Parquet, CSV or JSON Lines files. Rows are JSON
+//! objects. When the job configures partitioning, rows are grouped by partition
+//! and each partition is written to its own Hive-style directory
+//! (`column=value`), one file per partition.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashSet};
+use std::fs::File;
+use std::hash::{Hash, Hasher};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{
+    error::{BatchProcessingError, DataProcessingError, Result},
+    types::{
+        BatchJob, CompressionAlgorithm, OutputFile, OutputFormat, PartitioningConfig,
+        PartitioningStrategy, TimeUnit,
+    },
+};
+
+/// Partition name for rows without a value in the partition column
+const DEFAULT_PARTITION: &str = "__default__";
+
+/// Writer for the output files of a batch job
+#[derive(Debug, Clone)]
+pub struct BatchOutputWriter {
+    directory: PathBuf,
+    format: OutputFormat,
+    compression: CompressionAlgorithm,
+    compression_level: Option<u8>,
+    row_group_size: usize,
+    partitioning: Option<PartitioningConfig>,
+}
+
+impl BatchOutputWriter {
+    /// Create a writer for a job's output settings
+    pub fn new(job: &BatchJob, directory: impl Into<PathBuf>) -> Result<Self> {
+        Self::validate(job)?;
+
+        let compression = job.output_config.compression.as_ref();
+        Ok(Self {
+            directory: directory.into(),
+            format: job.output_format,
+            compression: compression
+                .map(|c| c.algorithm.clone())
+                .unwrap_or(CompressionAlgorithm::None),
+            compression_level: compression.and_then(|c| c.level),
+            row_group_size: job.output_config.parquet.row_group_size,
+            partitioning: job.output_config.partitioning.clone(),
+        })
+    }
+
+    /// Check that a job's output settings can be written
+    pub fn validate(job: &BatchJob) -> Result<()> {
+        let output = &job.output_config;
+
+        if let Some(compression) = &output.compression {
+            match (job.output_format, &compression.algorithm) {
+                (_, CompressionAlgorithm::None) => {}
+                (OutputFormat::Parquet, CompressionAlgorithm::Snappy)
+                | (OutputFormat::Parquet, CompressionAlgorithm::Zstd) => {}
+                (OutputFormat::Parquet, algorithm) => {
+                    return Err(DataProcessingError::validation(
+                        "output_config.compression",
+                        format!("{:?} is not supported for Parquet output", algorithm),
+                    ));
+                }
+                (format, _) => {
+                    return Err(DataProcessingError::validation(
+                        "output_config.compression",
+                        format!("Compression is not supported for {:?} output", format),
+                    ));
+                }
+            }
+        }
+
+        if job.output_format == OutputFormat::Parquet {
+            if !cfg!(feature = "parquet") {
+                return Err(DataProcessingError::configuration(
+                    "Parquet output requires the `parquet` feature",
+                ));
+            }
+
+            if output.parquet.row_group_size == 0 {
+                return Err(DataProcessingError::validation(
+                    "output_config.parquet.row_group_size",
+                    "Row group size must be greater than 0",
+                ));
+            }
+        }
+
+        if let Some(partitioning) = &output.partitioning {
+            if partitioning.columns.is_empty() {
+                return Err(DataProcessingError::validation(
+                    "output_config.partitioning.columns",
+                    "At least one partition column is required",
+                ));
+            }
+
+            match &partitioning.strategy {
+                PartitioningStrategy::Time { .. } if partitioning.columns.len() > 1 => {
+                    return Err(DataProcessingError::validation(
+                        "output_config.partitioning.columns",
+                        "Time partitioning uses a single timestamp column",
+                    ));
+                }
+                PartitioningStrategy::Hash if partitioning.partition_count.unwrap_or(0) == 0 => {
+                    return Err(DataProcessingError::validation(
+                        "output_config.partitioning.partition_count",
+                        "Hash partitioning requires a partition count greater than 0",
+                    ));
+                }
+                PartitioningStrategy::Range | PartitioningStrategy::Custom { .. } => {
+                    return Err(DataProcessingError::validation(
+                        "output_config.partitioning.strategy",
+                        format!(
+                            "{:?} partitioning is not supported for file output",
+                            partitioning.strategy
+                        ),
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Directory output files are written to
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Write rows, creating one file per partition
+    pub fn write(&self, rows: &[Value]) -> Result<Vec<OutputFile>> {
+        if rows.iter().any(|row| !row.is_object()) {
+            return Err(DataProcessingError::serialization(
+                "Batch output rows must be JSON objects",
+            ));
+        }
+
+        let mut partitions: BTreeMap<Option<String>, Vec<&Value>> = BTreeMap::new();
+        for row in rows {
+            partitions
+                .entry(self.partition_of(row))
+                .or_default()
+                .push(row);
+        }
+
+        let mut files = Vec::with_capacity(partitions.len());
+        for (partition, rows) in partitions {
+            let directory = match &partition {
+                Some(partition) => self.directory.join(partition),
+                None => self.directory.clone(),
+            };
+            std::fs::create_dir_all(&directory)
+                .map_err(|e| file_system_error("create_dir", &directory, e))?;
+
+            let path = directory.join(format!(
+                "part-{}.{}",
+                Uuid::new_v4().simple(),
+                self.format.extension()
+            ));
+            let file = File::create(&path).map_err(|e| file_system_error("create", &path, e))?;
+
+            match self.format {
+                OutputFormat::Parquet => self.write_parquet(file, &rows)?,
+                OutputFormat::Csv => write_csv(file, &rows, &path)?,
+                OutputFormat::JsonLines => write_json_lines(file, &rows, &path)?,
+            }
+
+            let bytes = std::fs::metadata(&path)
+                .map_err(|e| file_system_error("stat", &path, e))?
+                .len();
+
+            files.push(OutputFile {
+                path: path.to_string_lossy().into_owned(),
+                partition,
+                format: self.format,
+                records: rows.len() as u64,
+                bytes,
+            });
+        }
+
+        Ok(files)
+    }
+
+    /// Partition directory of a row, relative to the output directory
+    fn partition_of(&self, row: &Value) -> Option<String> {
+        let partitioning = self.partitioning.as_ref()?;
+        let columns = &partitioning.columns;
+
+        let partition = match &partitioning.strategy {
+            PartitioningStrategy::Time { unit } => {
+                let bucket = row
+                    .get(&columns[0])
+                    .and_then(parse_timestamp)
+                    .map(|timestamp| time_bucket(timestamp, unit))
+                    .unwrap_or_else(|| DEFAULT_PARTITION.to_string());
+                format!("{}={}", columns[0], bucket)
+            }
+            PartitioningStrategy::Hash => {
+                let mut hasher = DefaultHasher::new();
+                for column in columns {
+                    row.get(column)
+                        .unwrap_or(&Value::Null)
+                        .to_string()
+                        .hash(&mut hasher);
+                }
+                let count = partitioning.partition_count.unwrap_or(1) as u64;
+                format!("bucket={}", hasher.finish() % count)
+            }
+            _ => columns
+                .iter()
+                .map(|column| {
+                    let value = match row.get(column) {
+                        None | Some(Value::Null) => DEFAULT_PARTITION.to_string(),
+                        Some(Value::String(value)) => escape_partition_value(value),
+                        Some(value) => escape_partition_value(&value.to_string()),
+                    };
+                    format!("{}={}", column, value)
+                })
+                .collect::<Vec<_>>()
+                .join("/"),
+        };
+
+        Some(partition)
+    }
+
+    #[cfg(feature = "parquet")]
+    fn write_parquet(&self, file: File, rows: &[&Value]) -> Result<()> {
+        use arrow::json::reader::{infer_json_schema_from_iterator, ReaderBuilder};
+        use parquet::arrow::ArrowWriter;
+        use parquet::basic::{Compression, ZstdLevel};
+        use parquet::file::properties::WriterProperties;
+        use std::sync::Arc;
+
+        let encode_error = |e: &dyn std::fmt::Display| {
+            DataProcessingError::serialization(format!("Failed to encode Parquet output: {}", e))
+        };
+
+        let schema = Arc::new(
+            infer_json_schema_from_iterator(rows.iter().map(|row| Ok(*row)))
+                .map_err(|e| encode_error(&e))?,
+        );
+
+        let compression = match self.compression {
+            CompressionAlgorithm::Snappy => Compression::SNAPPY,
+            CompressionAlgorithm::Zstd => Compression::ZSTD(match self.compression_level {
+                Some(level) => ZstdLevel::try_new(level as i32).map_err(|e| encode_error(&e))?,
+                None => ZstdLevel::default(),
+            }),
+            _ => Compression::UNCOMPRESSED,
+        };
+        let properties = WriterProperties::builder()
+            .set_compression(compression)
+            .set_max_row_group_size(self.row_group_size)
+            .build();
+
+        let mut decoder = ReaderBuilder::new(schema.clone())
+            .with_batch_size(self.row_group_size)
+            .build_decoder()
+            .map_err(|e| encode_error(&e))?;
+        let mut writer =
+            ArrowWriter::try_new(file, schema, Some(properties)).map_err(|e| encode_error(&e))?;
+
+        for chunk in rows.chunks(self.row_group_size) {
+            decoder.serialize(chunk).map_err(|e| encode_error(&e))?;
+            if let Some(batch) = decoder.flush().map_err(|e| encode_error(&e))? {
+                writer.write(&batch).map_err(|e| encode_error(&e))?;
+            }
+        }
+
+        writer.close().map_err(|e| encode_error(&e))?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "parquet"))]
+    fn write_parquet(&self, _file: File, _rows: &[&Value]) -> Result<()> {
+        Err(DataProcessingError::configuration(
+            "Parquet output requires the `parquet` feature",
+        ))
+    }
+}
+
+/// Write rows as CSV with a header of every column seen, in first-seen order
+fn write_csv(file: File, rows: &[&Value], path: &Path) -> Result<()> {
+    let mut columns: Vec<&str> = Vec::new();
+    let mut seen = HashSet::new();
+    for row in rows {
+        if let Value::Object(fields) = row {
+            for column in fields.keys() {
+                if seen.insert(column.as_str()) {
+                    columns.push(column);
+                }
+            }
+        }
+    }
+
+    let mut writer = csv::Writer::from_writer(file);
+    let csv_error = |e: csv::Error| file_system_error("write", path, e);
+
+    writer.write_record(&columns).map_err(csv_error)?;
+    for row in rows {
+        writer
+            .write_record(columns.iter().map(|column| match row.get(*column) {
+                None | Some(Value::Null) => String::new(),
+                Some(Value::String(value)) => value.clone(),
+                Some(value) => value.to_string(),
+            }))
+            .map_err(csv_error)?;
+    }
+
+    writer
+        .flush()
+        .map_err(|e| file_system_error("write", path, e))?;
+    Ok(())
+}
+
+/// Write rows as newline-delimited JSON
+fn write_json_lines(file: File, rows: &[&Value], path: &Path) -> Result<()> {
+    let mut writer = BufWriter::new(file);
+    for row in rows {
+        serde_json::to_writer(&mut writer, row).map_err(|e| file_system_error("write", path, e))?;
+        writer
+            .write_all(b"\n")
+            .map_err(|e| file_system_error("write", path, e))?;
+    }
+
+    writer
+        .flush()
+        .map_err(|e| file_system_error("write", path, e))?;
+    Ok(())
+}
+
+/// Parse an RFC 3339 string or epoch milliseconds
+fn parse_timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::String(value) => DateTime::parse_from_rfc3339(value)
+            .ok()
+            .map(|timestamp| timestamp.with_timezone(&Utc)),
+        Value::Number(millis) => Utc.timestamp_millis_opt(millis.as_i64()?).single(),
+        _ => None,
+    }
+}
+
+/// Partition value of a timestamp for a time unit
+fn time_bucket(timestamp: DateTime<Utc>, unit: &TimeUnit) -> String {
+    let format = match unit {
+        TimeUnit::Hour => "%Y-%m-%dT%H",
+        TimeUnit::Day => "%Y-%m-%d",
+        TimeUnit::Week => "%G-W%V",
+        TimeUnit::Month => "%Y-%m",
+        TimeUnit::Year => "%Y",
+    };
+    timestamp.format(format).to_string()
+}
+
+/// Percent-encode characters that would change the directory structure
+fn escape_partition_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '/' | '\\' | '=' | '%' | ':' => escaped.push_str(&format!("%{:02X}", c as u32)),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn file_system_error(
+    operation: &str,
+    path: &Path,
+    error: impl std::fmt::Display,
+) -> DataProcessingError {
+    BatchProcessingError::FileSystem {
+        operation: operation.to_string(),
+        path: path.display().to_string(),
+        message: error.to_string(),
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CompressionConfig;
+    use serde_json::json;
+
+    fn job(format: OutputFormat, partitioning: Option<PartitioningConfig>) -> BatchJob {
+        let mut job = BatchJob {
+            output_format: format,
+            ..Default::default()
+        };
+        job.output_config.partitioning = partitioning;
+        job
+    }
+
+    fn rows() -> Vec<Value> {
+        vec![
+            json!({"amount": 10, "event_time": "2024-01-15T10:00:00Z", "user": "alice"}),
+            json!({"amount": 20, "event_time": "2024-01-15T23:59:59Z", "user": "bob"}),
+            json!({"amount": 30, "event_time": "2024-01-16T00:00:00Z", "user": "alice"}),
+            json!({"amount": 40, "note": "late", "user": "carol"}),
+        ]
+    }
+
+    #[test]
+    fn test_time_partitioned_json_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let partitioning = PartitioningConfig {
+            strategy: PartitioningStrategy::Time {
+                unit: TimeUnit::Day,
+            },
+            columns: vec!["event_time".to_string()],
+            partition_count: None,
+        };
+        let writer = BatchOutputWriter::new(
+            &job(OutputFormat::JsonLines, Some(partitioning)),
+            dir.path(),
+        )
+        .unwrap();
+
+        let files = writer.write(&rows()).unwrap();
+
+        let partitions: Vec<_> = files
+            .iter()
+            .map(|f| (f.partition.clone().unwrap(), f.records))
+            .collect();
+        assert_eq!(
+            partitions,
+            vec![
+                ("event_time=2024-01-15".to_string(), 2),
+                ("event_time=2024-01-16".to_string(), 1),
+                ("event_time=__default__".to_string(), 1),
+            ]
+        );
+
+        for file in &files {
+            let path = Path::new(&file.path);
+            assert!(path.starts_with(dir.path().join(file.partition.as_ref().unwrap())));
+            assert_eq!(file.bytes, std::fs::metadata(path).unwrap().len());
+        }
+
+        let content = std::fs::read_to_string(&files[0].path).unwrap();
+        let lines: Vec<Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines, rows()[..2].to_vec());
+    }
+
+    #[test]
+    fn test_csv_header_covers_all_columns() {
+        let dir = tempfile::tempdir().unwrap();
+        let writer = BatchOutputWriter::new(&job(OutputFormat::Csv, None), dir.path()).unwrap();
+
+        let files = writer.write(&rows()[2..]).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].partition, None);
+
+        let content = std::fs::read_to_string(&files[0].path).unwrap();
+        assert_eq!(
+            content,
+            "amount,event_time,user,note\n\
+             30,2024-01-16T00:00:00Z,alice,\n\
+             40,,carol,late\n"
+        );
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_parquet_compression_and_row_groups() {
+        use parquet::basic::Compression;
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut job = job(OutputFormat::Parquet, None);
+        job.output_config.compression = Some(CompressionConfig {
+            algorithm: CompressionAlgorithm::Zstd,
+            level: Some(3),
+            block_size: None,
+        });
+        job.output_config.parquet.row_group_size = 2;
+        let writer = BatchOutputWriter::new(&job, dir.path()).unwrap();
+
+        let rows: Vec<Value> = (0..5)
+            .map(|i| json!({"id": i, "name": format!("row-{}", i)}))
+            .collect();
+        let files = writer.write(&rows).unwrap();
+
+        let reader = SerializedFileReader::new(File::open(&files[0].path).unwrap()).unwrap();
+        let metadata = reader.metadata();
+        assert_eq!(metadata.file_metadata().num_rows(), 5);
+        assert_eq!(metadata.num_row_groups(), 3);
+        assert!(matches!(
+            metadata.row_group(0).column(0).compression(),
+            Compression::ZSTD(_)
+        ));
+    }
+
+    #[test]
+    fn test_unsupported_output_settings_are_rejected() {
+        let mut gzip_parquet = job(OutputFormat::Parquet, None);
+        gzip_parquet.output_config.compression = Some(CompressionConfig {
+            algorithm: CompressionAlgorithm::Gzip,
+            level: None,
+            block_size: None,
+        });
+        assert!(BatchOutputWriter::validate(&gzip_parquet).is_err());
+
+        let range = PartitioningConfig {
+            strategy: PartitioningStrategy::Range,
+            columns: vec!["amount".to_string()],
+            partition_count: None,
+        };
+        assert!(BatchOutputWriter::validate(&job(OutputFormat::Csv, Some(range))).is_err());
+
+        let hash = PartitioningConfig {
+            strategy: PartitioningStrategy::Hash,
+            columns: vec!["user".to_string()],
+            partition_count: Some(4),
+        };
+        assert!(BatchOutputWriter::validate(&job(OutputFormat::JsonLines, Some(hash))).is_ok());
+    }
+}
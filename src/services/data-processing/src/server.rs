@@ -9,8 +9,8 @@ use std::time::Duration;
 
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::Json,
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
     routing::{delete, get, post, put},
     Router,
 };
@@ -23,11 +23,11 @@ use tower_http::{
     timeout::TimeoutLayer,
     trace::TraceLayer,
 };
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 use crate::{
     config::Config,
-    error::{DataProcessingError, Result},
+    error::{DataProcessingError, Result, StreamProcessingError},
     types::{BatchJob, BatchJobStatus, DataRecord, ProcessingResult, ServiceHealth},
     DataProcessingService,
 };
@@ -281,10 +281,13 @@ async fn get_prometheus_metrics(State(service): State<Arc<DataProcessingService>
 }
 
 /// Process record endpoint
+///
+/// Responds with `503 Service Unavailable` and a `Retry-After` header while
+/// stream ingestion is applying backpressure.
 async fn process_record(
     State(service): State<Arc<DataProcessingService>>,
     Json(request): Json<ProcessRecordRequest>,
-) -> std::result::Result<Json<ApiResponse<ProcessRecordResponse>>, (StatusCode, Json<ApiError>)> {
+) -> std::result::Result<Json<ApiResponse<ProcessRecordResponse>>, Response> {
     match service.process_record(request.record).await {
         Ok(result) => {
             let response = ProcessRecordResponse { result };
@@ -296,6 +299,23 @@ async fn process_record(
                 timestamp: chrono::Utc::now().timestamp(),
             }))
         }
+        Err(DataProcessingError::StreamProcessing {
+            source: e @ StreamProcessingError::Backpressure { .. },
+        }) => {
+            warn!("Rejecting record under backpressure: {}", e);
+            let retry_after = service.stream_backpressure().retry_after_secs;
+            Err((
+                StatusCode::SERVICE_UNAVAILABLE,
+                [(header::RETRY_AFTER, retry_after.to_string())],
+                Json(ApiError {
+                    success: false,
+                    error: e.to_string(),
+                    error_code: "BACKPRESSURE".to_string(),
+                    timestamp: chrono::Utc::now().timestamp(),
+                }),
+            )
+                .into_response())
+        }
         Err(e) => {
             error!("Failed to process record: {}", e);
             Err((
@@ -306,7 +326,8 @@ async fn process_record(
                     error_code: "PROCESSING_ERROR".to_string(),
                     timestamp: chrono::Utc::now().timestamp(),
                 }),
-            ))
+            )
+                .into_response())
         }
     }
 }
@@ -321,7 +342,8 @@ async fn get_stream_status(
         success: true,
         data: Some(serde_json::json!({
             "status": format!("{:?}", health.status),
-            "components": health.components
+            "components": health.components,
+            "backpressure": service.stream_backpressure()
         })),
         message: "Stream status retrieved successfully".to_string(),
         timestamp: chrono::Utc::now().timestamp(),
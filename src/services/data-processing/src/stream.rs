@@ -10,28 +10,30 @@
 
 use chrono::DurationRound;
 use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
 use flume::{Receiver, Sender};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
-use tokio::sync::{mpsc, Mutex, RwLock as TokioRwLock};
+use tokio::sync::{watch, RwLock as TokioRwLock};
 use tokio_stream::{Stream, StreamExt};
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use crate::{
-    config::{Config, StreamConfig},
+    config::{BackpressureConfig, BackpressureStrategy, Config, StreamConfig},
     error::{DataProcessingError, Result, StreamProcessingError},
-    kafka::KafkaManager,
+    kafka::{KafkaManager, KafkaMessage, TransactionalRecord},
     metrics::MetricsCollector,
     types::{
         DataRecord, HealthStatus, ProcessingContext, ProcessingMetrics, ProcessingResult,
         ProcessingStatus, Watermark, WatermarkType, WindowType,
     },
+    windowing::{WindowAggregationConfig, WindowAggregator, WindowedAggregate},
 };
 
 /// Stream processor that handles real-time data processing
@@ -46,16 +48,50 @@ pub struct StreamProcessor {
     watermark_manager: Arc<WatermarkManager>,
     health_status: Arc<TokioRwLock<HealthStatus>>,
     worker_pool: Arc<WorkerPool>,
+    window_aggregators: Arc<RwLock<Vec<WindowAggregator>>>,
+    aggregate_sender: Sender<WindowedAggregate>,
+    aggregate_receiver: Receiver<WindowedAggregate>,
 }
 
 /// Stream processing worker pool
 pub struct WorkerPool {
     workers: Vec<StreamWorker>,
-    task_sender: mpsc::UnboundedSender<StreamTask>,
-    task_receiver: Arc<Mutex<mpsc::UnboundedReceiver<StreamTask>>>,
+    task_sender: Sender<StreamTask>,
+    task_receiver: Receiver<StreamTask>,
+    /// Serializes the capacity check and send under the drop strategies
+    admission: Mutex<()>,
+    backpressure: Arc<BackpressureController>,
     metrics: Arc<MetricsCollector>,
 }
 
+/// Watermark-based backpressure signal for the stream task queue
+///
+/// Tracks queued and in-flight tasks. Under the `Block` strategy ingestion is
+/// paused once the depth reaches the high watermark, and it stays paused until
+/// the depth drains to the low watermark. The drop strategies never pause.
+pub struct BackpressureController {
+    strategy: BackpressureStrategy,
+    high_watermark: usize,
+    low_watermark: usize,
+    retry_after: Duration,
+    queue_depth: AtomicUsize,
+    dropped_records: AtomicU64,
+    paused: watch::Sender<bool>,
+    metrics: Arc<MetricsCollector>,
+}
+
+/// Snapshot of the stream backpressure state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackpressureStatus {
+    pub strategy: BackpressureStrategy,
+    pub paused: bool,
+    pub queue_depth: usize,
+    pub high_watermark: usize,
+    pub low_watermark: usize,
+    pub retry_after_secs: u64,
+    pub dropped_records: u64,
+}
+
 /// Individual stream processing worker
 pub struct StreamWorker {
     id: String,
@@ -264,6 +300,9 @@ impl StreamProcessor {
         // Create worker pool
         let worker_pool = Arc::new(WorkerPool::new(stream_config.clone(), metrics.clone()).await?);
 
+        // Windowed aggregates wait here until consumed
+        let (aggregate_sender, aggregate_receiver) = flume::bounded(stream_config.buffer_size);
+
         Ok(Self {
             config: stream_config,
             kafka_manager,
@@ -274,6 +313,9 @@ impl StreamProcessor {
             watermark_manager,
             health_status: Arc::new(TokioRwLock::new(HealthStatus::Unknown)),
             worker_pool,
+            window_aggregators: Arc::new(RwLock::new(Vec::new())),
+            aggregate_sender,
+            aggregate_receiver,
         })
     }
 
@@ -321,7 +363,46 @@ impl StreamProcessor {
     }
 
     /// Process a single data record
+    ///
+    /// Fails with a backpressure error while the task queue is above its high
+    /// watermark so that producers can back off and retry.
     pub async fn process_record(&self, record: DataRecord) -> Result<ProcessingResult> {
+        let backpressure = &self.worker_pool.backpressure;
+        if backpressure.is_paused() {
+            let status = backpressure.status();
+            return Err(StreamProcessingError::Backpressure {
+                component: "stream_task_queue".to_string(),
+                message: format!(
+                    "queue depth {} exceeds high watermark {}, retry after {}s",
+                    status.queue_depth, status.high_watermark, status.retry_after_secs
+                ),
+            }
+            .into());
+        }
+
+        self.enqueue_record(record).await
+    }
+
+    /// Register a keyed windowed aggregation over processed records
+    pub fn add_window_aggregation(&self, config: WindowAggregationConfig) -> Result<()> {
+        let aggregator = WindowAggregator::new(config)?;
+        info!("Registered window aggregation {}", aggregator.name());
+        self.window_aggregators.write().push(aggregator);
+        Ok(())
+    }
+
+    /// Receiver for aggregates emitted when windows close or are updated by late records
+    pub fn windowed_aggregates(&self) -> Receiver<WindowedAggregate> {
+        self.aggregate_receiver.clone()
+    }
+
+    /// Get the current backpressure state of the stream task queue
+    pub fn backpressure_status(&self) -> BackpressureStatus {
+        self.worker_pool.backpressure.status()
+    }
+
+    /// Submit a record to the worker pool without checking backpressure
+    async fn enqueue_record(&self, record: DataRecord) -> Result<ProcessingResult> {
         let start_time = Instant::now();
 
         debug!("Processing record: {}", record.id);
@@ -336,7 +417,30 @@ impl StreamProcessor {
         };
 
         // Submit task to worker pool
-        self.worker_pool.submit_task(task).await?;
+        if !self.worker_pool.submit_task(task).await? {
+            debug!("Record {} dropped by backpressure", record.id);
+            return Ok(ProcessingResult {
+                record_id: record.id,
+                status: ProcessingStatus::Skipped,
+                processed_data: None,
+                metrics: ProcessingMetrics {
+                    start_time: record.timestamp,
+                    end_time: Utc::now(),
+                    duration_ms: start_time.elapsed().as_millis() as u64,
+                    memory_bytes: 0,
+                    cpu_time_ms: 0,
+                    transformations_count: 0,
+                    input_size_bytes: 0,
+                    output_size_bytes: 0,
+                    custom_metrics: HashMap::new(),
+                },
+                errors: Vec::new(),
+                warnings: Vec::new(),
+                outputs: Vec::new(),
+            });
+        }
+
+        self.aggregate_record(&record);
 
         // Create processing result
         let processing_time = start_time.elapsed();
@@ -373,8 +477,37 @@ impl StreamProcessor {
         Ok(result)
     }
 
+    /// Feed a record to the registered window aggregations
+    fn aggregate_record(&self, record: &DataRecord) {
+        let mut emitted = Vec::new();
+        for aggregator in self.window_aggregators.write().iter_mut() {
+            match aggregator.process(record) {
+                Ok(aggregates) => emitted.extend(aggregates),
+                Err(e) => warn!(
+                    "Window aggregation {} rejected record {}: {}",
+                    aggregator.name(),
+                    record.id,
+                    e
+                ),
+            }
+        }
+
+        for aggregate in emitted {
+            if let Err(e) = self.aggregate_sender.try_send(aggregate) {
+                warn!(
+                    "Windowed aggregate buffer is full, dropping aggregate for {}",
+                    e.into_inner().key
+                );
+            }
+        }
+    }
+
     /// Start Kafka consumption
     async fn start_kafka_consumption(&self) -> Result<()> {
+        if self.config.exactly_once {
+            return self.start_transactional_consumption();
+        }
+
         let subscription_options = crate::kafka::SubscriptionOptions {
             topics: self.config.input_topics.clone(),
             assignment_strategy: crate::kafka::AssignmentStrategy::RoundRobin,
@@ -387,10 +520,22 @@ impl StreamProcessor {
         let processor = self.clone();
         tokio::spawn(async move {
             while let Some(kafka_message) = message_stream.recv().await {
+                // Stop fetching until the task queue drains below the low watermark
+                let backpressure = &processor.worker_pool.backpressure;
+                if backpressure.is_paused() {
+                    if let Err(e) = processor.kafka_manager.pause_consumption() {
+                        warn!("Failed to pause Kafka consumption: {}", e);
+                    }
+                    backpressure.wait_for_resume().await;
+                    if let Err(e) = processor.kafka_manager.resume_consumption() {
+                        warn!("Failed to resume Kafka consumption: {}", e);
+                    }
+                }
+
                 // Deserialize Kafka message to DataRecord
                 match serde_json::from_slice::<DataRecord>(&kafka_message.payload) {
                     Ok(record) => {
-                        if let Err(e) = processor.process_record(record).await {
+                        if let Err(e) = processor.enqueue_record(record).await {
                             error!("Failed to process record: {}", e);
                         }
                     }
@@ -404,6 +549,69 @@ impl StreamProcessor {
         Ok(())
     }
 
+    /// Consume input topics inside Kafka transactions
+    fn start_transactional_consumption(&self) -> Result<()> {
+        let processor = self.clone();
+        tokio::spawn(async move {
+            let topics = processor.config.input_topics.clone();
+            let result = processor
+                .kafka_manager
+                .run_transactional(&topics, |message| {
+                    let processor = processor.clone();
+                    async move { processor.process_transactional_message(message).await }
+                })
+                .await;
+
+            if let Err(e) = result {
+                error!("Exactly-once Kafka consumption stopped: {}", e);
+                *processor.health_status.write().await = HealthStatus::Unhealthy;
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Process a message consumed inside a transaction, returning the records to produce
+    async fn process_transactional_message(
+        &self,
+        message: KafkaMessage,
+    ) -> Result<Vec<TransactionalRecord>> {
+        let record = match serde_json::from_slice::<DataRecord>(&message.payload) {
+            Ok(record) => record,
+            Err(e) => {
+                // Dead-lettered in the same transaction so the batch can still commit
+                warn!("Failed to deserialize message, dead-lettering: {}", e);
+                return Ok(vec![TransactionalRecord {
+                    topic: self.config.dead_letter_topic.clone(),
+                    key: message.key,
+                    payload: message.payload,
+                }]);
+            }
+        };
+
+        let backpressure = &self.worker_pool.backpressure;
+        if backpressure.is_paused() {
+            backpressure.wait_for_resume().await;
+        }
+
+        let key = record.id.to_string().into_bytes();
+        let payload = serde_json::to_vec(&record).map_err(|e| {
+            DataProcessingError::serialization(format!("Failed to serialize record: {}", e))
+        })?;
+        self.enqueue_record(record).await?;
+
+        Ok(self
+            .config
+            .output_topics
+            .iter()
+            .map(|topic| TransactionalRecord {
+                topic: topic.clone(),
+                key: Some(key.clone()),
+                payload: payload.clone(),
+            })
+            .collect())
+    }
+
     /// Get current health status
     pub async fn get_health(&self) -> HealthStatus {
         self.health_status.read().await.clone()
@@ -413,8 +621,11 @@ impl StreamProcessor {
 impl WorkerPool {
     /// Create a new worker pool
     async fn new(config: Arc<StreamConfig>, metrics: Arc<MetricsCollector>) -> Result<Self> {
-        let (task_sender, task_receiver) = mpsc::unbounded_channel();
-        let task_receiver = Arc::new(Mutex::new(task_receiver));
+        let (task_sender, task_receiver) = flume::unbounded();
+        let backpressure = Arc::new(BackpressureController::new(
+            &config.backpressure,
+            metrics.clone(),
+        ));
 
         let mut workers = Vec::new();
         for i in 0..config.worker_threads {
@@ -427,6 +638,8 @@ impl WorkerPool {
             workers,
             task_sender,
             task_receiver,
+            admission: Mutex::new(()),
+            backpressure,
             metrics,
         })
     }
@@ -434,7 +647,9 @@ impl WorkerPool {
     /// Start all workers
     async fn start(&self) -> Result<()> {
         for worker in &self.workers {
-            worker.start(self.task_receiver.clone()).await?;
+            worker
+                .start(self.task_receiver.clone(), self.backpressure.clone())
+                .await?;
         }
         Ok(())
     }
@@ -448,14 +663,153 @@ impl WorkerPool {
     }
 
     /// Submit a task to the worker pool
-    async fn submit_task(&self, task: StreamTask) -> Result<()> {
+    ///
+    /// Under the drop strategies the queue holds at most `high_watermark`
+    /// tasks. Returns `false` when the submitted task itself was dropped.
+    async fn submit_task(&self, task: StreamTask) -> Result<bool> {
+        let backpressure = &self.backpressure;
+        let _admission = self.admission.lock();
+
+        match backpressure.strategy {
+            BackpressureStrategy::Block => {}
+            BackpressureStrategy::DropNewest => {
+                if self.task_sender.len() >= backpressure.high_watermark {
+                    backpressure.record_dropped();
+                    return Ok(false);
+                }
+            }
+            BackpressureStrategy::DropOldest => {
+                while self.task_sender.len() >= backpressure.high_watermark {
+                    match self.task_receiver.try_recv() {
+                        Ok(evicted) => {
+                            debug!("Dropping queued stream task {}", evicted.id);
+                            backpressure.record_dropped();
+                            backpressure.record_dequeued();
+                        }
+                        // Workers emptied the queue in the meantime
+                        Err(_) => break,
+                    }
+                }
+            }
+        }
+
         self.task_sender
             .send(task)
             .map_err(|_| StreamProcessingError::Worker {
                 worker_id: "pool".to_string(),
                 message: "Failed to submit task to worker pool".to_string(),
             })?;
-        Ok(())
+        backpressure.record_enqueued();
+        Ok(true)
+    }
+}
+
+impl BackpressureController {
+    /// Create a new backpressure controller
+    pub fn new(config: &BackpressureConfig, metrics: Arc<MetricsCollector>) -> Self {
+        let (paused, _) = watch::channel(false);
+        Self {
+            strategy: config.strategy,
+            high_watermark: config.high_watermark,
+            low_watermark: config.low_watermark,
+            retry_after: Duration::from_secs(config.retry_after_secs),
+            queue_depth: AtomicUsize::new(0),
+            dropped_records: AtomicU64::new(0),
+            paused,
+            metrics,
+        }
+    }
+
+    /// Whether producers should currently back off
+    pub fn is_paused(&self) -> bool {
+        *self.paused.borrow()
+    }
+
+    /// Suggested delay before a rejected producer retries
+    pub fn retry_after(&self) -> Duration {
+        self.retry_after
+    }
+
+    /// Wait until ingestion is resumed
+    pub async fn wait_for_resume(&self) {
+        let mut receiver = self.paused.subscribe();
+        let _ = receiver.wait_for(|paused| !*paused).await;
+    }
+
+    /// Get a snapshot of the current state
+    pub fn status(&self) -> BackpressureStatus {
+        BackpressureStatus {
+            strategy: self.strategy,
+            paused: self.is_paused(),
+            queue_depth: self.queue_depth.load(Ordering::SeqCst),
+            high_watermark: self.high_watermark,
+            low_watermark: self.low_watermark,
+            retry_after_secs: self.retry_after.as_secs(),
+            dropped_records: self.dropped_records.load(Ordering::SeqCst),
+        }
+    }
+
+    /// Record a record discarded by a drop strategy
+    fn record_dropped(&self) {
+        self.dropped_records.fetch_add(1, Ordering::SeqCst);
+        self.metrics.increment_counter(
+            "stream_records_dropped_total",
+            &[("strategy", self.strategy.as_str())],
+        );
+    }
+
+    /// Record a task entering the queue
+    fn record_enqueued(&self) {
+        self.queue_depth.fetch_add(1, Ordering::SeqCst);
+        self.refresh();
+    }
+
+    /// Record a task leaving the queue after processing
+    fn record_dequeued(&self) {
+        let _ = self
+            .queue_depth
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |depth| {
+                Some(depth.saturating_sub(1))
+            });
+        self.refresh();
+    }
+
+    /// Re-evaluate the watermarks against the current queue depth
+    fn refresh(&self) {
+        let mut depth = 0;
+        let changed = self.paused.send_if_modified(|paused| {
+            depth = self.queue_depth.load(Ordering::SeqCst);
+            let blocking = self.strategy == BackpressureStrategy::Block;
+            if !*paused && blocking && depth >= self.high_watermark {
+                *paused = true;
+                true
+            } else if *paused && depth <= self.low_watermark {
+                *paused = false;
+                true
+            } else {
+                false
+            }
+        });
+
+        if changed {
+            if self.is_paused() {
+                warn!(
+                    "Stream task queue reached high watermark ({} >= {}), applying backpressure",
+                    depth, self.high_watermark
+                );
+            } else {
+                info!(
+                    "Stream task queue drained to low watermark ({} <= {}), resuming ingestion",
+                    depth, self.low_watermark
+                );
+            }
+        }
+
+        self.metrics.set_gauge(
+            "queue_size",
+            depth as f64,
+            &[("queue_name", "stream_tasks")],
+        );
     }
 }
 
@@ -473,7 +827,8 @@ impl StreamWorker {
     /// Start the worker
     async fn start(
         &self,
-        task_receiver: Arc<Mutex<mpsc::UnboundedReceiver<StreamTask>>>,
+        task_receiver: Receiver<StreamTask>,
+        backpressure: Arc<BackpressureController>,
     ) -> Result<()> {
         {
             let mut running = self.is_running.write().await;
@@ -489,15 +844,13 @@ impl StreamWorker {
             info!("Starting stream worker: {}", worker_id);
 
             while *is_running.read().await {
-                let mut receiver = task_receiver.lock().await;
-                match receiver.recv().await {
-                    Some(task) => {
-                        drop(receiver); // Release lock early
-
+                match task_receiver.recv_async().await {
+                    Ok(task) => {
                         let start_time = Instant::now();
                         if let Err(e) = Self::process_task(task, &config, &metrics).await {
                             error!("Worker {} failed to process task: {}", worker_id, e);
                         }
+                        backpressure.record_dequeued();
                         let processing_time = start_time.elapsed();
                         metrics.record_histogram(
                             "worker_task_duration_seconds",
@@ -505,7 +858,7 @@ impl StreamWorker {
                             &[("worker", &worker_id)],
                         );
                     }
-                    None => {
+                    Err(_) => {
                         warn!("Worker {} task channel closed", worker_id);
                         break;
                     }
@@ -759,5 +1112,141 @@ mod tests {
         // This should not fail
         let result = worker_pool.submit_task(task).await;
         assert!(result.is_ok());
+        assert_eq!(worker_pool.backpressure.status().queue_depth, 1);
+    }
+
+    #[test]
+    fn test_backpressure_watermarks() {
+        let metrics = Arc::new(MetricsCollector::new(&Config::default()).unwrap());
+        let config = BackpressureConfig {
+            high_watermark: 4,
+            low_watermark: 2,
+            retry_after_secs: 3,
+            strategy: BackpressureStrategy::Block,
+        };
+        let controller = BackpressureController::new(&config, metrics);
+
+        for _ in 0..3 {
+            controller.record_enqueued();
+        }
+        assert!(!controller.is_paused());
+
+        // Reaching the high watermark pauses ingestion
+        controller.record_enqueued();
+        assert!(controller.is_paused());
+        assert_eq!(controller.retry_after(), Duration::from_secs(3));
+
+        // Stays paused until the queue drains to the low watermark
+        controller.record_dequeued();
+        assert!(controller.is_paused());
+        controller.record_dequeued();
+        assert!(!controller.is_paused());
+        assert_eq!(controller.status().queue_depth, 2);
+    }
+
+    async fn flooded_pool(strategy: BackpressureStrategy) -> WorkerPool {
+        let config = Arc::new(StreamConfig {
+            worker_threads: 2,
+            backpressure: BackpressureConfig {
+                high_watermark: 10,
+                low_watermark: 5,
+                retry_after_secs: 1,
+                strategy,
+            },
+            ..Default::default()
+        });
+        let metrics = Arc::new(MetricsCollector::new(&Config::default()).unwrap());
+        WorkerPool::new(config, metrics).await.unwrap()
+    }
+
+    fn sequenced_task(sequence: u64) -> StreamTask {
+        StreamTask {
+            id: Uuid::new_v4(),
+            record: DataRecord {
+                data: serde_json::json!({ "sequence": sequence }),
+                ..Default::default()
+            },
+            window_assignment: None,
+            processing_time: Utc::now(),
+            watermark: None,
+        }
+    }
+
+    fn queued_sequences(worker_pool: &WorkerPool) -> Vec<u64> {
+        worker_pool
+            .task_receiver
+            .drain()
+            .map(|task| task.record.data["sequence"].as_u64().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_drop_oldest_keeps_newest_records() {
+        let worker_pool = flooded_pool(BackpressureStrategy::DropOldest).await;
+
+        for sequence in 0..100 {
+            assert!(worker_pool
+                .submit_task(sequenced_task(sequence))
+                .await
+                .unwrap());
+            assert!(worker_pool.task_sender.len() <= 10);
+        }
+
+        let status = worker_pool.backpressure.status();
+        assert!(!status.paused);
+        assert_eq!(status.dropped_records, 90);
+        assert_eq!(status.queue_depth, 10);
+        assert_eq!(
+            queued_sequences(&worker_pool),
+            (90..100).collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_drop_newest_keeps_oldest_records() {
+        let worker_pool = flooded_pool(BackpressureStrategy::DropNewest).await;
+
+        for sequence in 0..100 {
+            let accepted = worker_pool
+                .submit_task(sequenced_task(sequence))
+                .await
+                .unwrap();
+            assert_eq!(accepted, sequence < 10);
+        }
+
+        let status = worker_pool.backpressure.status();
+        assert!(!status.paused);
+        assert_eq!(status.dropped_records, 90);
+        assert_eq!(queued_sequences(&worker_pool), (0..10).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_block_bounds_queue_without_dropping() {
+        let worker_pool = flooded_pool(BackpressureStrategy::Block).await;
+        worker_pool.start().await.unwrap();
+
+        // Producer behaves like the Kafka consumption loop
+        let backpressure = worker_pool.backpressure.clone();
+        let mut max_depth = 0;
+        for sequence in 0..100 {
+            if backpressure.is_paused() {
+                backpressure.wait_for_resume().await;
+            }
+            assert!(worker_pool
+                .submit_task(sequenced_task(sequence))
+                .await
+                .unwrap());
+            max_depth = max_depth.max(backpressure.status().queue_depth);
+        }
+
+        assert!(max_depth <= 10, "queue grew to {}", max_depth);
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while backpressure.status().queue_depth > 0 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("queue should drain");
+        assert_eq!(backpressure.status().dropped_records, 0);
     }
 }
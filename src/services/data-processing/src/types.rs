@@ -200,6 +200,9 @@ pub struct BatchJob {
     pub input_config: BatchInputConfig,
     /// Output data configuration
     pub output_config: BatchOutputConfig,
+    /// File format results are written in
+    #[serde(default)]
+    pub output_format: OutputFormat,
     /// Processing configuration
     pub processing_config: BatchProcessingConfig,
     /// Job schedule if recurring
@@ -271,6 +274,39 @@ pub struct BatchOutputConfig {
     pub partitioning: Option<PartitioningConfig>,
     /// Output mode (overwrite, append, merge)
     pub mode: OutputMode,
+    /// Parquet writer settings
+    #[serde(default)]
+    pub parquet: ParquetOptions,
+}
+
+/// File formats for batch job output
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Apache Parquet columnar files
+    Parquet,
+    /// Comma-separated values with a header row
+    Csv,
+    /// One JSON object per line
+    #[default]
+    JsonLines,
+}
+
+impl OutputFormat {
+    /// File extension for the format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Parquet => "parquet",
+            Self::Csv => "csv",
+            Self::JsonLines => "jsonl",
+        }
+    }
+}
+
+/// Parquet writer settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParquetOptions {
+    /// Maximum rows per row group
+    pub row_group_size: usize,
 }
 
 /// Batch job processing configuration
@@ -387,6 +423,8 @@ pub enum PartitioningStrategy {
     Range,
     /// Time-based partitioning
     Time { unit: TimeUnit },
+    /// One partition per distinct value of the partition columns
+    Value,
     /// Custom partitioning
     Custom { expression: String },
 }
@@ -658,6 +696,27 @@ pub struct BatchJobStatus {
     pub warnings: Vec<ProcessingWarning>,
     /// Job logs URL
     pub logs_url: Option<String>,
+    /// Files written by the job
+    #[serde(default)]
+    pub output_files: Vec<OutputFile>,
+    /// Total bytes written to output files
+    #[serde(default)]
+    pub bytes_written: u64,
+}
+
+/// File written by a batch job
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OutputFile {
+    /// Path of the file
+    pub path: String,
+    /// Partition directory, relative to the output location
+    pub partition: Option<String>,
+    /// File format
+    pub format: OutputFormat,
+    /// Number of records in the file
+    pub records: u64,
+    /// File size in bytes
+    pub bytes: u64,
 }
 
 /// Job execution states
@@ -821,6 +880,7 @@ impl Default for BatchJob {
             job_type: BatchJobType::Etl,
             input_config: BatchInputConfig::default(),
             output_config: BatchOutputConfig::default(),
+            output_format: OutputFormat::default(),
             processing_config: BatchProcessingConfig::default(),
             schedule: None,
             priority: 5,
@@ -856,6 +916,15 @@ impl Default for BatchOutputConfig {
             compression: None,
             partitioning: None,
             mode: OutputMode::Append,
+            parquet: ParquetOptions::default(),
+        }
+    }
+}
+
+impl Default for ParquetOptions {
+    fn default() -> Self {
+        Self {
+            row_group_size: 128 * 1024,
         }
     }
 }
@@ -6,8 +6,9 @@
 //! - Session windows (dynamic windows based on inactivity gaps)
 //! - Global windows (single window for all data)
 //! - Custom windowing strategies
+//! - Keyed tumbling and sliding window aggregations with allowed lateness
 
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -491,6 +492,315 @@ impl Default for WindowManager {
     }
 }
 
+/// Window specification for keyed aggregations
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum WindowSpec {
+    /// Fixed-size, non-overlapping windows aligned to the epoch
+    Tumbling { size: Duration },
+    /// Fixed-size windows starting every `slide`, aligned to the epoch
+    Sliding { size: Duration, slide: Duration },
+}
+
+/// Configuration of a windowed aggregation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowAggregationConfig {
+    pub name: String,
+    pub window: WindowSpec,
+    /// Field whose value partitions records into separate aggregates
+    pub group_by: String,
+    /// Numeric field that is aggregated
+    pub value_field: String,
+    /// How the event time of a record is determined
+    pub timestamp_extractor: TimestampExtractor,
+    /// How far the watermark trails the highest event time seen
+    pub max_out_of_orderness: Duration,
+    /// How long after a window closes late records still update it
+    pub allowed_lateness: Duration,
+}
+
+/// Whether an aggregate is the first emission for its window or a late update
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AggregateKind {
+    /// Emitted when the watermark passes the end of the window
+    Final,
+    /// Re-emitted after a late record updated an already closed window
+    Update,
+}
+
+/// Aggregate of a numeric field over one window for one key
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WindowedAggregate {
+    pub aggregation: String,
+    pub key: String,
+    pub window_start: DateTime<Utc>,
+    pub window_end: DateTime<Utc>,
+    pub kind: AggregateKind,
+    /// Number of times this window has been emitted before
+    pub revision: u32,
+    pub count: u64,
+    pub sum: f64,
+    pub min: f64,
+    pub max: f64,
+    pub avg: f64,
+}
+
+/// Running statistics of an open or recently closed window
+#[derive(Debug, Clone)]
+struct WindowAccumulator {
+    count: u64,
+    sum: f64,
+    min: f64,
+    max: f64,
+    emissions: u32,
+}
+
+impl WindowAccumulator {
+    fn new() -> Self {
+        Self {
+            count: 0,
+            sum: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            emissions: 0,
+        }
+    }
+
+    fn add(&mut self, value: f64) {
+        self.count += 1;
+        self.sum += value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+}
+
+/// Keyed tumbling/sliding window aggregation driven by event-time watermarks
+///
+/// A window is emitted once the watermark passes its end. Records for a closed
+/// window are still applied until the watermark passes the window end plus the
+/// allowed lateness, each producing an [`AggregateKind::Update`] record; after
+/// that the window is discarded and further records for it are dropped.
+pub struct WindowAggregator {
+    config: WindowAggregationConfig,
+    /// Windows by (end, start, key) so closing follows event-time order
+    windows: BTreeMap<(DateTime<Utc>, DateTime<Utc>, String), WindowAccumulator>,
+    max_event_time: Option<DateTime<Utc>>,
+    dropped_records: u64,
+}
+
+impl WindowAggregator {
+    /// Create an aggregator, validating the window specification
+    pub fn new(config: WindowAggregationConfig) -> Result<Self> {
+        let (size, slide) = match config.window {
+            WindowSpec::Tumbling { size } => (size, size),
+            WindowSpec::Sliding { size, slide } => (size, slide),
+        };
+        if size.as_millis() == 0 || slide.as_millis() == 0 {
+            return Err(StreamProcessingError::Window {
+                window_type: config.name.clone(),
+                message: "window size and slide must be at least one millisecond".to_string(),
+            }
+            .into());
+        }
+
+        Ok(Self {
+            config,
+            windows: BTreeMap::new(),
+            max_event_time: None,
+            dropped_records: 0,
+        })
+    }
+
+    /// Name of the aggregation
+    pub fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    /// Current event-time watermark
+    pub fn watermark(&self) -> Option<DateTime<Utc>> {
+        let max_out_of_orderness = chrono::Duration::from_std(self.config.max_out_of_orderness)
+            .unwrap_or_else(|_| chrono::Duration::zero());
+        self.max_event_time
+            .map(|max_event_time| max_event_time - max_out_of_orderness)
+    }
+
+    /// Records dropped because they arrived after the allowed lateness
+    pub fn dropped_records(&self) -> u64 {
+        self.dropped_records
+    }
+
+    /// Apply a record, returning the aggregates emitted as a result
+    pub fn process(&mut self, record: &DataRecord) -> Result<Vec<WindowedAggregate>> {
+        let timestamp = self.extract_timestamp(record)?;
+        let Some(value) = record
+            .data
+            .get(&self.config.value_field)
+            .and_then(|value| value.as_f64())
+        else {
+            debug!(
+                "Record {} has no numeric {} field, skipping aggregation {}",
+                record.id, self.config.value_field, self.config.name
+            );
+            return Ok(Vec::new());
+        };
+        let key = match record.data.get(&self.config.group_by) {
+            Some(serde_json::Value::String(key)) => key.clone(),
+            Some(key) => key.to_string(),
+            None => "default".to_string(),
+        };
+
+        let mut emitted = Vec::new();
+        let watermark = self.watermark();
+        let lateness = self.allowed_lateness();
+
+        for (start, end) in self.window_bounds(timestamp) {
+            let is_closed = watermark.is_some_and(|watermark| end <= watermark);
+            if is_closed && watermark.is_some_and(|watermark| end + lateness <= watermark) {
+                self.dropped_records += 1;
+                debug!(
+                    "Dropping late record {} for window {} - {} of aggregation {}",
+                    record.id, start, end, self.config.name
+                );
+                continue;
+            }
+
+            let window_key = (end, start, key.clone());
+            let accumulator = self
+                .windows
+                .entry(window_key.clone())
+                .or_insert_with(WindowAccumulator::new);
+            accumulator.add(value);
+
+            if is_closed {
+                emitted.push(self.emit(&window_key));
+            }
+        }
+
+        let advances_watermark = match self.max_event_time {
+            Some(max_event_time) => timestamp > max_event_time,
+            None => true,
+        };
+        if advances_watermark {
+            self.max_event_time = Some(timestamp);
+            emitted.extend(self.close_windows());
+        }
+
+        Ok(emitted)
+    }
+
+    /// Move the watermark forward without a record, e.g. on idle sources
+    pub fn advance_to(&mut self, event_time: DateTime<Utc>) -> Vec<WindowedAggregate> {
+        if self.max_event_time.is_some_and(|max| event_time <= max) {
+            return Vec::new();
+        }
+        self.max_event_time = Some(event_time);
+        self.close_windows()
+    }
+
+    /// Emit windows the watermark has passed and discard expired ones
+    fn close_windows(&mut self) -> Vec<WindowedAggregate> {
+        let Some(watermark) = self.watermark() else {
+            return Vec::new();
+        };
+        let lateness = self.allowed_lateness();
+
+        let closing: Vec<_> = self
+            .windows
+            .iter()
+            .take_while(|((end, _, _), _)| *end <= watermark)
+            .filter(|(_, accumulator)| accumulator.emissions == 0)
+            .map(|(window_key, _)| window_key.clone())
+            .collect();
+        let emitted = closing
+            .iter()
+            .map(|window_key| self.emit(window_key))
+            .collect();
+
+        self.windows
+            .retain(|(end, _, _), _| *end + lateness > watermark);
+
+        emitted
+    }
+
+    fn emit(&mut self, window_key: &(DateTime<Utc>, DateTime<Utc>, String)) -> WindowedAggregate {
+        let accumulator = self
+            .windows
+            .get_mut(window_key)
+            .expect("emitted window must exist");
+        let revision = accumulator.emissions;
+        accumulator.emissions += 1;
+
+        let (window_end, window_start, key) = window_key.clone();
+        WindowedAggregate {
+            aggregation: self.config.name.clone(),
+            key,
+            window_start,
+            window_end,
+            kind: if revision == 0 {
+                AggregateKind::Final
+            } else {
+                AggregateKind::Update
+            },
+            revision,
+            count: accumulator.count,
+            sum: accumulator.sum,
+            min: accumulator.min,
+            max: accumulator.max,
+            avg: accumulator.sum / accumulator.count as f64,
+        }
+    }
+
+    /// Start and end of every window containing the timestamp
+    fn window_bounds(&self, timestamp: DateTime<Utc>) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+        let (size, slide) = match self.config.window {
+            WindowSpec::Tumbling { size } => (size, size),
+            WindowSpec::Sliding { size, slide } => (size, slide),
+        };
+        let size = size.as_millis() as i64;
+        let slide = slide.as_millis() as i64;
+        let millis = timestamp.timestamp_millis();
+
+        let mut bounds = Vec::new();
+        let mut start = millis - millis.rem_euclid(slide);
+        while start + size > millis {
+            if let (Some(window_start), Some(window_end)) = (
+                DateTime::<Utc>::from_timestamp_millis(start),
+                DateTime::<Utc>::from_timestamp_millis(start + size),
+            ) {
+                bounds.push((window_start, window_end));
+            }
+            start -= slide;
+        }
+
+        bounds.reverse();
+        bounds
+    }
+
+    fn allowed_lateness(&self) -> chrono::Duration {
+        chrono::Duration::from_std(self.config.allowed_lateness)
+            .unwrap_or_else(|_| chrono::Duration::zero())
+    }
+
+    fn extract_timestamp(&self, record: &DataRecord) -> Result<DateTime<Utc>> {
+        match &self.config.timestamp_extractor {
+            TimestampExtractor::EventTime(field_name) => {
+                match record.data.get(field_name).and_then(|value| value.as_str()) {
+                    Some(timestamp) => chrono::DateTime::parse_from_rfc3339(timestamp)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .map_err(|e| {
+                            DataProcessingError::validation(
+                                field_name,
+                                format!("Invalid timestamp: {}", e),
+                            )
+                        }),
+                    None => Ok(record.timestamp),
+                }
+            }
+            TimestampExtractor::ProcessingTime => Ok(Utc::now()),
+            TimestampExtractor::IngestionTime => Ok(record.timestamp),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -605,4 +915,140 @@ mod tests {
         let cleaned_count = manager.cleanup_expired_windows().await.unwrap();
         assert_eq!(cleaned_count, 1);
     }
+
+    fn aggregation_config(window: WindowSpec) -> WindowAggregationConfig {
+        WindowAggregationConfig {
+            name: "amounts".to_string(),
+            window,
+            group_by: "user_id".to_string(),
+            value_field: "amount".to_string(),
+            timestamp_extractor: TimestampExtractor::EventTime("timestamp".to_string()),
+            max_out_of_orderness: Duration::from_secs(5),
+            allowed_lateness: Duration::from_secs(30),
+        }
+    }
+
+    fn event(user_id: &str, timestamp: &str, amount: f64) -> DataRecord {
+        DataRecord {
+            data: json!({
+                "user_id": user_id,
+                "timestamp": timestamp,
+                "amount": amount
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn at(timestamp: &str) -> DateTime<Utc> {
+        timestamp.parse().unwrap()
+    }
+
+    #[test]
+    fn test_tumbling_aggregation_with_out_of_order_and_late_events() {
+        let mut aggregator = WindowAggregator::new(aggregation_config(WindowSpec::Tumbling {
+            size: Duration::from_secs(60),
+        }))
+        .unwrap();
+
+        // Out of order, but within the out-of-orderness bound
+        let stream = [
+            event("alice", "2024-01-01T10:00:30Z", 5.0),
+            event("bob", "2024-01-01T10:00:20Z", 7.0),
+            event("alice", "2024-01-01T10:00:10Z", 1.0),
+            event("alice", "2024-01-01T10:01:02Z", 100.0),
+            event("alice", "2024-01-01T10:00:50Z", 3.0),
+        ];
+        for record in &stream {
+            assert!(aggregator.process(record).unwrap().is_empty());
+        }
+
+        // Watermark passes 10:01:00 and closes the first window for both keys
+        let emitted = aggregator
+            .process(&event("bob", "2024-01-01T10:01:06Z", 2.0))
+            .unwrap();
+        assert_eq!(emitted.len(), 2);
+
+        let alice = emitted.iter().find(|a| a.key == "alice").unwrap();
+        assert_eq!(alice.kind, AggregateKind::Final);
+        assert_eq!(alice.revision, 0);
+        assert_eq!(alice.window_start, at("2024-01-01T10:00:00Z"));
+        assert_eq!(alice.window_end, at("2024-01-01T10:01:00Z"));
+        assert_eq!(alice.count, 3);
+        assert_eq!(alice.sum, 9.0);
+        assert_eq!(alice.min, 1.0);
+        assert_eq!(alice.max, 5.0);
+        assert_eq!(alice.avg, 3.0);
+
+        let bob = emitted.iter().find(|a| a.key == "bob").unwrap();
+        assert_eq!((bob.count, bob.sum), (1, 7.0));
+
+        // Late, but within the allowed lateness: the closed window is updated
+        let updated = aggregator
+            .process(&event("alice", "2024-01-01T10:00:40Z", 11.0))
+            .unwrap();
+        assert_eq!(updated.len(), 1);
+        assert_eq!(updated[0].kind, AggregateKind::Update);
+        assert_eq!(updated[0].revision, 1);
+        assert_eq!(updated[0].count, 4);
+        assert_eq!(updated[0].sum, 20.0);
+        assert_eq!(updated[0].max, 11.0);
+        assert_eq!(updated[0].avg, 5.0);
+
+        // Past the allowed lateness the window is gone and late records are dropped
+        let emitted = aggregator.advance_to(at("2024-01-01T10:02:10Z"));
+        assert_eq!(emitted.len(), 2);
+        assert!(emitted
+            .iter()
+            .all(|a| a.window_start == at("2024-01-01T10:01:00Z")));
+
+        let dropped = aggregator
+            .process(&event("alice", "2024-01-01T10:00:45Z", 50.0))
+            .unwrap();
+        assert!(dropped.is_empty());
+        assert_eq!(aggregator.dropped_records(), 1);
+    }
+
+    #[test]
+    fn test_sliding_aggregation_assigns_overlapping_windows() {
+        let mut aggregator = WindowAggregator::new(aggregation_config(WindowSpec::Sliding {
+            size: Duration::from_secs(60),
+            slide: Duration::from_secs(30),
+        }))
+        .unwrap();
+
+        let stream = [
+            event("alice", "2024-01-01T10:00:45Z", 4.0),
+            event("alice", "2024-01-01T10:00:15Z", 2.0),
+            event("alice", "2024-01-01T10:01:10Z", 6.0),
+        ];
+        let mut emitted = Vec::new();
+        for record in &stream {
+            emitted.extend(aggregator.process(record).unwrap());
+        }
+        emitted.extend(aggregator.advance_to(at("2024-01-01T10:03:00Z")));
+
+        let windows: Vec<_> = emitted
+            .iter()
+            .map(|a| (a.window_start, a.count, a.sum))
+            .collect();
+        assert_eq!(
+            windows,
+            vec![
+                (at("2024-01-01T09:59:30Z"), 1, 2.0),
+                (at("2024-01-01T10:00:00Z"), 2, 6.0),
+                (at("2024-01-01T10:00:30Z"), 2, 10.0),
+                (at("2024-01-01T10:01:00Z"), 1, 6.0),
+            ]
+        );
+        assert!(emitted.iter().all(|a| a.kind == AggregateKind::Final));
+    }
+
+    #[test]
+    fn test_window_aggregation_rejects_empty_windows() {
+        let config = aggregation_config(WindowSpec::Sliding {
+            size: Duration::from_secs(60),
+            slide: Duration::ZERO,
+        });
+        assert!(WindowAggregator::new(config).is_err());
+    }
 }
@@ -0,0 +1,166 @@
+//! Exactly-once Kafka processing tests
+//!
+//! These tests need a Kafka broker with transactions enabled. Single-node brokers
+//! must set `transaction.state.log.replication.factor=1` and
+//! `transaction.state.log.min.isr=1`. Run them with:
+//!
+//! ```text
+//! KAFKA_BOOTSTRAP_SERVERS=localhost:9092 \
+//!     cargo test --features kafka-integration-tests --test kafka_exactly_once
+//! ```
+
+#![cfg(feature = "kafka-integration-tests")]
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use rdkafka::{
+    config::ClientConfig,
+    consumer::{Consumer, StreamConsumer},
+    Message,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use data_processing_service::{
+    config::{Config, IsolationLevel},
+    kafka::{KafkaManager, KafkaMessage, PublishOptions, TransactionalRecord},
+    MetricsCollector,
+};
+
+const MESSAGES: u64 = 20;
+const PANIC_AT: u64 = 12;
+
+fn bootstrap_servers() -> String {
+    std::env::var("KAFKA_BOOTSTRAP_SERVERS").unwrap_or_else(|_| "localhost:9092".to_string())
+}
+
+fn test_config(run_id: &str) -> Config {
+    let mut config = Config::default();
+    config.kafka.bootstrap_servers = bootstrap_servers();
+    config.kafka.consumer_group_id = format!("exactly-once-{}", run_id);
+    config.kafka.auto_offset_reset = "earliest".to_string();
+    config.kafka.enable_auto_commit = false;
+    config.kafka.transactions.transactional_id = format!("exactly-once-{}", run_id);
+    config.kafka.transactions.isolation_level = IsolationLevel::ReadCommitted;
+    config.kafka.transactions.batch_size = 5;
+    config.stream.exactly_once = true;
+    config
+}
+
+fn sequence(payload: &[u8]) -> u64 {
+    let value: Value = serde_json::from_slice(payload).expect("payload should be JSON");
+    value["sequence"]
+        .as_u64()
+        .expect("payload should have a sequence")
+}
+
+fn forward(message: KafkaMessage, output_topic: &str) -> Vec<TransactionalRecord> {
+    vec![TransactionalRecord {
+        topic: output_topic.to_string(),
+        key: message.key,
+        payload: message.payload,
+    }]
+}
+
+/// Read committed output until `MESSAGES` records arrive, then drain any extras
+async fn read_committed_sequences(run_id: &str, topic: &str) -> Vec<u64> {
+    let consumer: StreamConsumer = ClientConfig::new()
+        .set("bootstrap.servers", bootstrap_servers())
+        .set("group.id", format!("exactly-once-verify-{}", run_id))
+        .set("auto.offset.reset", "earliest")
+        .set("isolation.level", "read_committed")
+        .create()
+        .expect("Failed to create verification consumer");
+    consumer.subscribe(&[topic]).unwrap();
+
+    let mut sequences = Vec::new();
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(90);
+    while (sequences.len() as u64) < MESSAGES {
+        match tokio::time::timeout_at(deadline, consumer.recv()).await {
+            Ok(Ok(message)) => sequences.push(sequence(message.payload().unwrap_or(&[]))),
+            Ok(Err(e)) => panic!("Failed to read output: {}", e),
+            Err(_) => break,
+        }
+    }
+
+    // Anything received from here on would be a duplicate
+    while let Ok(Ok(message)) = tokio::time::timeout(Duration::from_secs(5), consumer.recv()).await
+    {
+        sequences.push(sequence(message.payload().unwrap_or(&[])));
+    }
+
+    sequences
+}
+
+/// Test that a panic in the middle of a batch produces no duplicate output
+#[tokio::test(flavor = "multi_thread")]
+async fn test_no_duplicate_outputs_after_mid_batch_panic() {
+    let run_id = Uuid::new_v4().simple().to_string();
+    let input_topic = format!("exactly-once-input-{}", run_id);
+    let output_topic = format!("exactly-once-output-{}", run_id);
+    let config = test_config(&run_id);
+    let metrics = Arc::new(MetricsCollector::new(&config).unwrap());
+
+    let manager = KafkaManager::new(&config, metrics.clone())
+        .await
+        .expect("Kafka broker should be reachable");
+    manager.create_topic(&input_topic, 1, 1).await.unwrap();
+    manager.create_topic(&output_topic, 1, 1).await.unwrap();
+    for i in 0..MESSAGES {
+        manager
+            .publish(
+                &input_topic,
+                &json!({ "sequence": i }),
+                PublishOptions::default(),
+            )
+            .await
+            .unwrap();
+    }
+
+    // First run crashes part way through the batch holding PANIC_AT
+    let crashed = {
+        let manager = manager.clone();
+        let topics = vec![input_topic.clone()];
+        let output_topic = output_topic.clone();
+        tokio::spawn(async move {
+            manager
+                .run_transactional(&topics, |message| {
+                    let output_topic = output_topic.clone();
+                    async move {
+                        if sequence(&message.payload) == PANIC_AT {
+                            panic!("simulated crash while processing {}", PANIC_AT);
+                        }
+                        Ok(forward(message, &output_topic))
+                    }
+                })
+                .await
+        })
+    };
+    assert!(crashed.await.unwrap_err().is_panic());
+    drop(manager);
+
+    // Restarting with the same transactional ID fences the crashed producer
+    // and resumes from the last committed offsets
+    let manager = KafkaManager::new(&config, metrics)
+        .await
+        .expect("Kafka broker should be reachable");
+    let restarted = {
+        let topics = vec![input_topic.clone()];
+        let output_topic = output_topic.clone();
+        tokio::spawn(async move {
+            manager
+                .run_transactional(&topics, |message| {
+                    let output_topic = output_topic.clone();
+                    async move { Ok(forward(message, &output_topic)) }
+                })
+                .await
+        })
+    };
+
+    let mut sequences = read_committed_sequences(&run_id, &output_topic).await;
+    restarted.abort();
+
+    sequences.sort_unstable();
+    assert_eq!(sequences, (0..MESSAGES).collect::<Vec<_>>());
+}
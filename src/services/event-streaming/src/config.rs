@@ -7,9 +7,11 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::Duration;
 
+use crate::routing::RoutingRule;
+use crate::schema::EventSchema;
 use crate::types::{
-    BackoffStrategy, CompressionType, DeadLetterConfig, EventFilter, EventTransformation,
-    ReplayConfig, RetentionConfig, RetryConfig, StreamConfig,
+    BackoffStrategy, CompressionType, DeadLetterConfig, EventDestination, EventFilter,
+    EventTransformation, ReplayConfig, RetentionConfig, RetryConfig, StreamConfig,
 };
 
 /// Main configuration structure for the event streaming service
@@ -27,6 +29,14 @@ pub struct Config {
     /// Processing pipeline configuration
     pub processing: ProcessingConfig,
 
+    /// Event routing configuration
+    #[serde(default)]
+    pub routing: RoutingConfig,
+
+    /// Event schema validation configuration
+    #[serde(default)]
+    pub schemas: SchemaConfig,
+
     /// Storage configuration for event persistence
     pub storage: StorageConfig,
 
@@ -91,6 +101,8 @@ impl Default for Config {
             redis: RedisConfig::default(),
 
             processing: ProcessingConfig::default(),
+            routing: RoutingConfig::default(),
+            schemas: SchemaConfig::default(),
             storage: StorageConfig::default(),
             monitoring: MonitoringConfig::default(),
             security: SecurityConfig::default(),
@@ -523,6 +535,53 @@ impl Default for ProcessingConfig {
     }
 }
 
+/// Event routing configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoutingConfig {
+    /// Routing rules; the built-in rules are used when not set
+    pub rules: Option<Vec<RoutingRule>>,
+
+    /// JSON file of routing rules, read at startup and on every reload.
+    /// Takes precedence over `rules`.
+    pub rules_file: Option<String>,
+
+    /// Destination for events that no rule matches
+    pub default_destination: EventDestination,
+}
+
+impl Default for RoutingConfig {
+    fn default() -> Self {
+        Self {
+            rules: None,
+            rules_file: None,
+            default_destination: EventDestination {
+                target: "kafka:dead-letter".to_string(),
+                routing_key: Some("unrouted".to_string()),
+                config: HashMap::new(),
+            },
+        }
+    }
+}
+
+/// Event schema validation configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaConfig {
+    /// Validate events against registered schemas before publishing
+    pub enabled: bool,
+
+    /// Schemas registered at startup
+    pub definitions: Vec<EventSchema>,
+}
+
+impl Default for SchemaConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            definitions: Vec::new(),
+        }
+    }
+}
+
 /// Storage configuration for event persistence
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StorageConfig {
@@ -0,0 +1,428 @@
+//! # Dead Letter Queue Module
+//!
+//! Events that still fail after the configured retries are moved to a dead letter
+//! queue together with the failure reason. The original event, metadata included,
+//! is kept intact so it can later be replayed into the main pipeline. Replayed
+//! events carry a replay marker in their metadata so consumers can dedupe them.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::{
+    events::{Event, EventType},
+    types::{EventCategory, EventStatus},
+};
+
+/// Metadata property holding the reason an event was dead-lettered
+pub const DEAD_LETTER_REASON_PROPERTY: &str = "dead_letter.reason";
+
+/// Metadata property holding the stream the failed event was consumed from
+pub const DEAD_LETTER_SOURCE_PROPERTY: &str = "dead_letter.source_stream";
+
+/// Metadata property holding when the event was dead-lettered (RFC 3339)
+pub const DEAD_LETTER_AT_PROPERTY: &str = "dead_letter.failed_at";
+
+/// Metadata property holding the ID of the replay that re-injected the event
+pub const REPLAY_ID_PROPERTY: &str = "replay.id";
+
+/// Metadata property counting how many times the event has been replayed
+pub const REPLAY_COUNT_PROPERTY: &str = "replay.count";
+
+/// An event parked in the dead letter queue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetterEntry {
+    /// The failed event, with its original metadata and the last error
+    pub event: Event,
+
+    /// Why processing gave up on the event
+    pub reason: String,
+
+    /// Stream or topic the event was consumed from
+    pub source_stream: String,
+
+    /// Processing attempts made before the event was dead-lettered
+    pub attempts: u32,
+
+    /// When the event was moved to the dead letter queue
+    pub dead_lettered_at: DateTime<Utc>,
+}
+
+impl DeadLetterEntry {
+    /// Wrap a failed event, marking it as dead-lettered and attaching the
+    /// failure details to its metadata
+    pub fn new(
+        mut event: Event,
+        source_stream: impl Into<String>,
+        reason: impl Into<String>,
+    ) -> Self {
+        let source_stream = source_stream.into();
+        let reason = reason.into();
+        let dead_lettered_at = Utc::now();
+
+        let properties = &mut event.metadata.properties;
+        properties.insert(DEAD_LETTER_REASON_PROPERTY.to_string(), reason.clone());
+        properties.insert(
+            DEAD_LETTER_SOURCE_PROPERTY.to_string(),
+            source_stream.clone(),
+        );
+        properties.insert(
+            DEAD_LETTER_AT_PROPERTY.to_string(),
+            dead_lettered_at.to_rfc3339(),
+        );
+        event.update_status(EventStatus::DeadLetter, Some(reason.clone()));
+
+        Self {
+            attempts: event.attempt_count,
+            event,
+            reason,
+            source_stream,
+            dead_lettered_at,
+        }
+    }
+
+    /// Rebuild an entry from an event read back from the dead letter stream,
+    /// using the failure details attached to its metadata. Returns `None` for
+    /// events that were not dead-lettered.
+    pub fn from_event(event: Event) -> Option<Self> {
+        let properties = &event.metadata.properties;
+        let reason = properties.get(DEAD_LETTER_REASON_PROPERTY)?.clone();
+        let source_stream = properties.get(DEAD_LETTER_SOURCE_PROPERTY)?.clone();
+        let dead_lettered_at =
+            DateTime::parse_from_rfc3339(properties.get(DEAD_LETTER_AT_PROPERTY)?)
+                .ok()?
+                .with_timezone(&Utc);
+
+        Some(Self {
+            attempts: event.attempt_count,
+            event,
+            reason,
+            source_stream,
+            dead_lettered_at,
+        })
+    }
+
+    /// The event to re-inject for a replay: reset for processing and marked
+    /// with the replay ID. The event ID is kept so consumers can dedupe on it.
+    pub fn replay_event(&self, replay_id: Uuid) -> Event {
+        let mut event = self.event.clone();
+
+        let properties = &mut event.metadata.properties;
+        let replay_count = properties
+            .get(REPLAY_COUNT_PROPERTY)
+            .and_then(|count| count.parse::<u32>().ok())
+            .unwrap_or(0)
+            + 1;
+        properties.insert(REPLAY_ID_PROPERTY.to_string(), replay_id.to_string());
+        properties.insert(REPLAY_COUNT_PROPERTY.to_string(), replay_count.to_string());
+
+        event.attempt_count = 0;
+        event.error = None;
+        event.update_status(
+            EventStatus::Retried,
+            Some(format!("Replayed from dead letter queue ({})", replay_id)),
+        );
+        event
+    }
+
+    fn key(&self) -> (DateTime<Utc>, Uuid) {
+        (self.dead_lettered_at, self.event.id)
+    }
+}
+
+/// Selects dead-lettered events for a replay
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeadLetterFilter {
+    /// Only replay events of these types
+    pub event_types: Option<Vec<EventType>>,
+
+    /// Only replay events in these categories
+    pub categories: Option<Vec<EventCategory>>,
+}
+
+impl DeadLetterFilter {
+    /// Check whether an entry passes the filter
+    pub fn matches(&self, entry: &DeadLetterEntry) -> bool {
+        let type_match = self.event_types.as_ref().is_none_or(|types| {
+            types
+                .iter()
+                .any(|event_type| event_type.as_str() == entry.event.event_type)
+        });
+        let category_match = self
+            .categories
+            .as_ref()
+            .is_none_or(|categories| categories.contains(&entry.event.category));
+
+        type_match && category_match
+    }
+}
+
+/// Outcome of a dead letter replay
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeadLetterReplay {
+    /// Replay marker attached to every re-injected event
+    pub replay_id: Uuid,
+
+    /// Entries that matched the filter and time range
+    pub matched: u64,
+
+    /// Entries re-injected and removed from the queue
+    pub replayed: u64,
+
+    /// Entries that could not be re-injected and stay queued
+    pub failed: u64,
+}
+
+/// Index of dead-lettered events, ordered by when they were dead-lettered.
+/// A Redis dead letter stream is read back directly; the index stands in for
+/// Kafka dead letter topics, which cannot be read by time range.
+#[derive(Debug)]
+pub struct DeadLetterQueue {
+    entries: RwLock<BTreeMap<(DateTime<Utc>, Uuid), DeadLetterEntry>>,
+    retention: chrono::Duration,
+}
+
+impl DeadLetterQueue {
+    /// Create a queue that keeps entries for `retention_seconds`
+    pub fn new(retention_seconds: u64) -> Self {
+        Self {
+            entries: RwLock::new(BTreeMap::new()),
+            retention: chrono::Duration::from_std(std::time::Duration::from_secs(
+                retention_seconds,
+            ))
+            .unwrap_or(chrono::Duration::MAX),
+        }
+    }
+
+    /// Add an entry, returning the new queue depth
+    pub async fn push(&self, entry: DeadLetterEntry) -> usize {
+        let mut entries = self.entries.write().await;
+        entries.insert(entry.key(), entry);
+        entries.len()
+    }
+
+    /// Remove an entry, returning whether it was queued
+    pub async fn remove(&self, entry: &DeadLetterEntry) -> bool {
+        self.entries.write().await.remove(&entry.key()).is_some()
+    }
+
+    /// Number of queued entries
+    pub async fn depth(&self) -> usize {
+        self.entries.read().await.len()
+    }
+
+    /// Entries dead-lettered between `from` and `to` (inclusive) that match
+    /// the filter, oldest first
+    pub async fn list(
+        &self,
+        filter: &DeadLetterFilter,
+        from: DateTime<Utc>,
+        to: Option<DateTime<Utc>>,
+    ) -> Vec<DeadLetterEntry> {
+        let entries = self.entries.read().await;
+        entries
+            .range((from, Uuid::nil())..)
+            .take_while(|((dead_lettered_at, _), _)| to.is_none_or(|to| *dead_lettered_at <= to))
+            .map(|(_, entry)| entry)
+            .filter(|entry| filter.matches(entry))
+            .cloned()
+            .collect()
+    }
+
+    /// Entries dead-lettered before this time are past the retention period
+    pub fn retention_cutoff(&self) -> Option<DateTime<Utc>> {
+        Utc::now().checked_sub_signed(self.retention)
+    }
+
+    /// Drop entries older than the retention period, returning how many were removed
+    pub async fn prune_expired(&self) -> usize {
+        let Some(cutoff) = self.retention_cutoff() else {
+            return 0;
+        };
+        let mut entries = self.entries.write().await;
+        let retained = entries.split_off(&(cutoff, Uuid::nil()));
+        let removed = entries.len();
+        *entries = retained;
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventPayload;
+    use crate::types::EventSource;
+
+    fn event(event_type: &str, category: EventCategory) -> Event {
+        let source = EventSource {
+            service: "test-service".to_string(),
+            version: "1.0.0".to_string(),
+            instance_id: None,
+            hostname: None,
+            metadata: std::collections::HashMap::new(),
+        };
+        let mut event = Event::new(
+            event_type,
+            category,
+            source,
+            EventPayload::Custom(serde_json::json!({"test": "data"})),
+        );
+        event
+            .metadata
+            .properties
+            .insert("tenant_region".to_string(), "eu".to_string());
+        event
+    }
+
+    #[test]
+    fn test_entry_keeps_original_metadata() {
+        let entry = DeadLetterEntry::new(
+            event("workflow.failed", EventCategory::Workflow),
+            "workflow-events",
+            "handler timed out",
+        );
+
+        assert_eq!(entry.event.status, EventStatus::DeadLetter);
+        let properties = &entry.event.metadata.properties;
+        assert_eq!(properties["tenant_region"], "eu");
+        assert_eq!(properties[DEAD_LETTER_REASON_PROPERTY], "handler timed out");
+        assert_eq!(properties[DEAD_LETTER_SOURCE_PROPERTY], "workflow-events");
+    }
+
+    #[test]
+    fn test_entry_rebuilt_from_dead_letter_stream_event() {
+        let entry = DeadLetterEntry::new(
+            event("workflow.failed", EventCategory::Workflow),
+            "workflow-events",
+            "handler timed out",
+        );
+
+        // Round-trip through the payload published to the dead letter stream
+        let published: Event =
+            serde_json::from_str(&serde_json::to_string(&entry.event).unwrap()).unwrap();
+        let rebuilt = DeadLetterEntry::from_event(published).unwrap();
+
+        assert_eq!(rebuilt.event.id, entry.event.id);
+        assert_eq!(rebuilt.reason, "handler timed out");
+        assert_eq!(rebuilt.source_stream, "workflow-events");
+        assert_eq!(rebuilt.attempts, entry.attempts);
+        assert_eq!(rebuilt.dead_lettered_at, entry.dead_lettered_at);
+
+        // Events that never went through the dead letter queue are skipped
+        assert!(
+            DeadLetterEntry::from_event(event("workflow.failed", EventCategory::Workflow))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_replay_event_carries_marker() {
+        let entry = DeadLetterEntry::new(
+            event("workflow.failed", EventCategory::Workflow),
+            "workflow-events",
+            "handler timed out",
+        );
+        let first_replay = Uuid::new_v4();
+        let replayed = entry.replay_event(first_replay);
+
+        assert_eq!(replayed.id, entry.event.id);
+        assert_eq!(replayed.status, EventStatus::Retried);
+        assert_eq!(replayed.attempt_count, 0);
+        assert!(replayed.error.is_none());
+        assert_eq!(
+            replayed.metadata.properties[REPLAY_ID_PROPERTY],
+            first_replay.to_string()
+        );
+        assert_eq!(replayed.metadata.properties[REPLAY_COUNT_PROPERTY], "1");
+
+        // Failing again and being replayed a second time bumps the count
+        let entry = DeadLetterEntry::new(replayed, "workflow-events", "still failing");
+        let replayed = entry.replay_event(Uuid::new_v4());
+        assert_eq!(replayed.metadata.properties[REPLAY_COUNT_PROPERTY], "2");
+    }
+
+    #[tokio::test]
+    async fn test_list_filters_by_type_and_time() {
+        let queue = DeadLetterQueue::new(3600);
+        let before = Utc::now();
+
+        queue
+            .push(DeadLetterEntry::new(
+                event(EventType::WorkflowFailed.as_str(), EventCategory::Workflow),
+                "workflow-events",
+                "boom",
+            ))
+            .await;
+        queue
+            .push(DeadLetterEntry::new(
+                event(
+                    EventType::UserLoggedIn.as_str(),
+                    EventCategory::UserActivity,
+                ),
+                "user-events",
+                "boom",
+            ))
+            .await;
+        let depth = queue
+            .push(DeadLetterEntry::new(
+                event(EventType::WorkflowFailed.as_str(), EventCategory::Workflow),
+                "workflow-events",
+                "boom",
+            ))
+            .await;
+        assert_eq!(depth, 3);
+
+        let workflow_failures = DeadLetterFilter {
+            event_types: Some(vec![EventType::WorkflowFailed]),
+            categories: None,
+        };
+        let matched = queue.list(&workflow_failures, before, None).await;
+        assert_eq!(matched.len(), 2);
+        assert!(matched[0].dead_lettered_at <= matched[1].dead_lettered_at);
+
+        let user_activity = DeadLetterFilter {
+            event_types: None,
+            categories: Some(vec![EventCategory::UserActivity]),
+        };
+        assert_eq!(queue.list(&user_activity, before, None).await.len(), 1);
+
+        // Nothing was dead-lettered before `before`
+        let window_end = before - chrono::Duration::seconds(1);
+        assert!(queue
+            .list(
+                &DeadLetterFilter::default(),
+                before - chrono::Duration::hours(1),
+                Some(window_end)
+            )
+            .await
+            .is_empty());
+
+        assert!(queue.remove(&matched[0]).await);
+        assert_eq!(queue.depth().await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_prune_expired() {
+        let queue = DeadLetterQueue::new(60);
+        let mut stale = DeadLetterEntry::new(
+            event("workflow.failed", EventCategory::Workflow),
+            "workflow-events",
+            "boom",
+        );
+        stale.dead_lettered_at = Utc::now() - chrono::Duration::minutes(5);
+        queue.push(stale).await;
+        queue
+            .push(DeadLetterEntry::new(
+                event("workflow.failed", EventCategory::Workflow),
+                "workflow-events",
+                "boom",
+            ))
+            .await;
+
+        assert_eq!(queue.prune_expired().await, 1);
+        assert_eq!(queue.depth().await, 1);
+    }
+}
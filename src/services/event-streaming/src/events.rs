@@ -214,6 +214,16 @@ impl Event {
             .find(|h| matches!(h.status, EventStatus::Completed))
             .and_then(|h| h.duration_ms)
     }
+
+    /// Payload data as JSON, without the payload type tag
+    pub fn payload_data(&self) -> serde_json::Value {
+        match serde_json::to_value(&self.payload) {
+            Ok(serde_json::Value::Object(mut payload)) => {
+                payload.remove("data").unwrap_or(serde_json::Value::Null)
+            }
+            _ => serde_json::Value::Null,
+        }
+    }
 }
 
 /// Event payload that can contain different types of event data
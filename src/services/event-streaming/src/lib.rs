@@ -11,12 +11,14 @@ use std::sync::Arc;
 use thiserror::Error;
 
 pub mod config;
+pub mod dead_letter;
 pub mod error;
 pub mod events;
 pub mod handlers;
 pub mod metrics;
 pub mod processing;
 pub mod routing;
+pub mod schema;
 pub mod server;
 pub mod storage;
 pub mod types;
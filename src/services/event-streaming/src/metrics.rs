@@ -77,6 +77,15 @@ pub struct MetricsCollector {
     replay_jobs_active: IntGauge,
     replay_events_processed_total: IntCounter,
 
+    // Dead letter metrics
+    dead_letter_queue_depth: IntGauge,
+    dead_letter_replays_total: IntCounter,
+    dead_letter_events_replayed_total: IntCounter,
+    dead_letter_replay_failures_total: IntCounter,
+
+    // Schema validation metrics
+    events_rejected_by_schema_total: IntCounter,
+
     // Custom metrics storage
     custom_metrics: Arc<RwLock<HashMap<String, CustomMetric>>>,
 }
@@ -369,6 +378,53 @@ impl MetricsCollector {
         )
         .map_err(|e| EventStreamingError::internal(format!("Failed to register metric: {}", e)))?;
 
+        // Create dead letter metrics
+        let dead_letter_queue_depth = register_int_gauge_with_registry!(
+            opts!(
+                "dead_letter_queue_depth",
+                "Number of events waiting in the dead letter queue"
+            ),
+            &registry
+        )
+        .map_err(|e| EventStreamingError::internal(format!("Failed to register metric: {}", e)))?;
+
+        let dead_letter_replays_total = register_int_counter_with_registry!(
+            opts!(
+                "dead_letter_replays_total",
+                "Total number of dead letter replays run"
+            ),
+            &registry
+        )
+        .map_err(|e| EventStreamingError::internal(format!("Failed to register metric: {}", e)))?;
+
+        let dead_letter_events_replayed_total = register_int_counter_with_registry!(
+            opts!(
+                "dead_letter_events_replayed_total",
+                "Total number of dead letter events re-injected into the pipeline"
+            ),
+            &registry
+        )
+        .map_err(|e| EventStreamingError::internal(format!("Failed to register metric: {}", e)))?;
+
+        let dead_letter_replay_failures_total = register_int_counter_with_registry!(
+            opts!(
+                "dead_letter_replay_failures_total",
+                "Total number of dead letter events that failed to replay"
+            ),
+            &registry
+        )
+        .map_err(|e| EventStreamingError::internal(format!("Failed to register metric: {}", e)))?;
+
+        // Create schema validation metrics
+        let events_rejected_by_schema_total = register_int_counter_with_registry!(
+            opts!(
+                "events_rejected_by_schema_total",
+                "Total number of events rejected for not matching their schema"
+            ),
+            &registry
+        )
+        .map_err(|e| EventStreamingError::internal(format!("Failed to register metric: {}", e)))?;
+
         Ok(Self {
             config: Arc::new(config.clone()),
             registry: Arc::new(registry),
@@ -403,6 +459,11 @@ impl MetricsCollector {
             replay_jobs_total,
             replay_jobs_active,
             replay_events_processed_total,
+            dead_letter_queue_depth,
+            dead_letter_replays_total,
+            dead_letter_events_replayed_total,
+            dead_letter_replay_failures_total,
+            events_rejected_by_schema_total,
             custom_metrics: Arc::new(RwLock::new(HashMap::new())),
         })
     }
@@ -517,6 +578,37 @@ impl MetricsCollector {
         Ok(())
     }
 
+    /// Record an event moved to the dead letter queue
+    pub async fn record_dead_letter(&self, queue_depth: usize) -> Result<()> {
+        self.dead_letter_queue_depth.set(queue_depth as i64);
+        Ok(())
+    }
+
+    /// Record a dead letter replay
+    pub async fn record_dead_letter_replay(
+        &self,
+        replayed: u64,
+        failed: u64,
+        queue_depth: usize,
+    ) -> Result<()> {
+        self.dead_letter_replays_total.inc();
+        self.dead_letter_events_replayed_total.inc_by(replayed);
+        self.dead_letter_replay_failures_total.inc_by(failed);
+        self.dead_letter_queue_depth.set(queue_depth as i64);
+        Ok(())
+    }
+
+    /// Record an event rejected by schema validation
+    pub async fn record_schema_rejection(&self) -> Result<()> {
+        self.events_rejected_by_schema_total.inc();
+        Ok(())
+    }
+
+    /// Set the dead letter queue depth
+    pub fn set_dead_letter_queue_depth(&self, depth: i64) {
+        self.dead_letter_queue_depth.set(depth);
+    }
+
     /// Get metrics snapshot
     pub async fn get_snapshot(&self) -> Result<MetricsSnapshot> {
         Ok(MetricsSnapshot {
@@ -17,16 +17,24 @@ use uuid::Uuid;
 
 use crate::{
     config::Config,
+    dead_letter::{DeadLetterEntry, DeadLetterFilter, DeadLetterQueue, DeadLetterReplay},
     error::{EventStreamingError, Result},
-    events::Event,
+    events::{Event, EventError},
     kafka::KafkaManager,
     metrics::MetricsCollector,
     redis_streams::RedisStreamManager,
     routing::EventRouter,
+    schema::SchemaRegistry,
     storage::EventStorage,
-    types::{ComponentHealth, EventCategory, EventStatus, HealthStatus, ProcessingStats},
+    types::{
+        BackoffStrategy, ComponentHealth, EventCategory, EventStatus, HealthStatus,
+        ProcessingStats, RetryConfig,
+    },
 };
 
+/// Entries fetched per read when replaying from the dead letter stream
+const DEAD_LETTER_READ_BATCH: usize = 500;
+
 /// Main event processing pipeline
 #[derive(Clone)]
 pub struct ProcessingPipeline {
@@ -35,6 +43,7 @@ pub struct ProcessingPipeline {
     redis_manager: Arc<RedisStreamManager>,
     event_storage: Arc<EventStorage>,
     event_router: Arc<EventRouter>,
+    schema_registry: Arc<SchemaRegistry>,
     metrics_collector: Arc<MetricsCollector>,
     processing_semaphore: Arc<Semaphore>,
     worker_handles: Arc<RwLock<Vec<JoinHandle<()>>>>,
@@ -42,6 +51,7 @@ pub struct ProcessingPipeline {
     health_status: Arc<RwLock<HealthStatus>>,
     processing_stats: Arc<RwLock<ProcessingStats>>,
     replay_jobs: Arc<RwLock<HashMap<Uuid, ReplayJob>>>,
+    dead_letter_queue: Arc<DeadLetterQueue>,
 }
 
 /// Event processing context
@@ -92,6 +102,7 @@ impl ProcessingPipeline {
         redis_manager: Arc<RedisStreamManager>,
         event_storage: Arc<EventStorage>,
         event_router: Arc<EventRouter>,
+        schema_registry: Arc<SchemaRegistry>,
         metrics_collector: Arc<MetricsCollector>,
     ) -> Result<Self> {
         info!("Initializing Processing Pipeline");
@@ -105,6 +116,7 @@ impl ProcessingPipeline {
             redis_manager,
             event_storage,
             event_router,
+            schema_registry,
             metrics_collector,
             processing_semaphore,
             worker_handles: Arc::new(RwLock::new(Vec::new())),
@@ -120,6 +132,9 @@ impl ProcessingPipeline {
                 by_priority: HashMap::new(),
             })),
             replay_jobs: Arc::new(RwLock::new(HashMap::new())),
+            dead_letter_queue: Arc::new(DeadLetterQueue::new(
+                config.processing.dead_letter.retention_seconds,
+            )),
         })
     }
 
@@ -171,6 +186,13 @@ impl ProcessingPipeline {
 
         debug!("Publishing event {} to processing pipeline", event.id);
 
+        // Reject events that do not match their registered schema
+        if let Err(e) = self.schema_registry.validate(&event).await {
+            warn!("Rejected event {}: {}", event.id, e);
+            self.metrics_collector.record_schema_rejection().await?;
+            return Err(e);
+        }
+
         // Update stats
         {
             let mut stats = self.processing_stats.write().await;
@@ -184,21 +206,7 @@ impl ProcessingPipeline {
 
         // Publish to each destination
         for destination in &destinations {
-            match destination.target.as_str() {
-                target if target.starts_with("kafka:") => {
-                    let topic = target.strip_prefix("kafka:").unwrap();
-                    self.kafka_manager
-                        .publish_event(topic, &event, None)
-                        .await?;
-                }
-                target if target.starts_with("redis:") => {
-                    let stream = target.strip_prefix("redis:").unwrap();
-                    self.redis_manager.publish_event(stream, &event).await?;
-                }
-                _ => {
-                    warn!("Unknown destination target: {}", destination.target);
-                }
-            }
+            self.publish_to_target(&destination.target, &event).await?;
         }
 
         // Store event for audit and replay
@@ -221,6 +229,125 @@ impl ProcessingPipeline {
         Ok(())
     }
 
+    /// Re-inject dead-lettered events into the pipeline. Only entries that match
+    /// `filter` and were dead-lettered between `from` and `to` are replayed; each
+    /// replayed event carries the replay ID so consumers can dedupe. Entries that
+    /// fail to publish stay in the dead letter queue.
+    pub async fn replay(
+        &self,
+        filter: &DeadLetterFilter,
+        from: DateTime<Utc>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<DeadLetterReplay> {
+        let entries = self.dead_lettered(filter, from, to).await?;
+        let mut replay = DeadLetterReplay {
+            replay_id: Uuid::new_v4(),
+            matched: entries.len() as u64,
+            replayed: 0,
+            failed: 0,
+        };
+
+        info!(
+            "Replaying {} dead-lettered events (replay {})",
+            replay.matched, replay.replay_id
+        );
+
+        for (stream_entry_id, entry) in entries {
+            match self
+                .publish_event(entry.replay_event(replay.replay_id))
+                .await
+            {
+                Ok(()) => {
+                    match (self.dead_letter_stream(), stream_entry_id) {
+                        (Some(stream), Some(id)) => {
+                            self.redis_manager.delete_entries(stream, &[id]).await?;
+                        }
+                        _ => {
+                            self.dead_letter_queue.remove(&entry).await;
+                        }
+                    }
+                    replay.replayed += 1;
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to replay dead-lettered event {}: {}",
+                        entry.event.id, e
+                    );
+                    replay.failed += 1;
+                }
+            }
+        }
+
+        self.metrics_collector
+            .record_dead_letter_replay(
+                replay.replayed,
+                replay.failed,
+                self.dead_letter_depth().await?,
+            )
+            .await?;
+
+        info!(
+            "Replay {} finished: {} replayed, {} failed",
+            replay.replay_id, replay.replayed, replay.failed
+        );
+
+        Ok(replay)
+    }
+
+    /// Number of events waiting in the dead letter queue
+    pub async fn dead_letter_depth(&self) -> Result<usize> {
+        match self.dead_letter_stream() {
+            Some(stream) => Ok(self.redis_manager.stream_length(stream).await? as usize),
+            None => Ok(self.dead_letter_queue.depth().await),
+        }
+    }
+
+    /// Dead-lettered entries matching a replay, oldest first, each with its
+    /// dead letter stream entry ID when the queue is a Redis stream
+    async fn dead_lettered(
+        &self,
+        filter: &DeadLetterFilter,
+        from: DateTime<Utc>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<(Option<String>, DeadLetterEntry)>> {
+        let Some(stream) = self.dead_letter_stream() else {
+            return Ok(self
+                .dead_letter_queue
+                .list(filter, from, to)
+                .await
+                .into_iter()
+                .map(|entry| (None, entry))
+                .collect());
+        };
+
+        // Entries are appended after they are stamped, so none dead-lettered
+        // at or after `from` sits before it in the stream
+        let entries = self
+            .redis_manager
+            .read_range(stream, from, DEAD_LETTER_READ_BATCH)
+            .await?
+            .into_iter()
+            .filter_map(|(id, event)| {
+                let event_id = event.id;
+                let entry = DeadLetterEntry::from_event(event);
+                if entry.is_none() {
+                    warn!(
+                        "Skipping event {} in dead letter stream {} without dead letter details",
+                        event_id, stream
+                    );
+                }
+                entry.map(|entry| (Some(id), entry))
+            })
+            .filter(|(_, entry)| {
+                entry.dead_lettered_at >= from
+                    && to.is_none_or(|to| entry.dead_lettered_at <= to)
+                    && filter.matches(entry)
+            })
+            .collect();
+
+        Ok(entries)
+    }
+
     /// Start event replay
     pub async fn start_replay(
         &self,
@@ -464,8 +591,10 @@ impl ProcessingPipeline {
         // Transform event if needed
         let transformed_event = self.transform_event(context.event.clone()).await?;
 
-        // Process the event (business logic would go here)
-        let processing_result = self.execute_event_processing(transformed_event).await;
+        // Process the event, retrying failures before giving up on it
+        let processing_result = self
+            .execute_with_retries(transformed_event, context.processing_attempt)
+            .await;
 
         // Update processing stats
         {
@@ -480,6 +609,19 @@ impl ProcessingPipeline {
             }
         }
 
+        let processing_result = match processing_result {
+            Ok(()) => Ok(()),
+            Err((failed_event, e)) => {
+                if let Err(dlq_error) = self
+                    .dead_letter(failed_event, &context.source_stream, &e)
+                    .await
+                {
+                    error!("Failed to dead-letter event {}: {}", event_id, dlq_error);
+                }
+                Err(e)
+            }
+        };
+
         let duration = start_time.elapsed();
 
         // Record metrics
@@ -490,6 +632,118 @@ impl ProcessingPipeline {
         processing_result
     }
 
+    /// Run the processing for an event until it succeeds, fails with a
+    /// non-retryable error or runs out of attempts. On failure the event is
+    /// returned with its last error recorded.
+    async fn execute_with_retries(
+        &self,
+        mut event: Event,
+        attempts_made: u32,
+    ) -> std::result::Result<(), (Event, EventStreamingError)> {
+        let retry = &self.config.processing.retry;
+        event.attempt_count = attempts_made;
+
+        loop {
+            let e = match self.execute_event_processing(event.clone()).await {
+                Ok(()) => return Ok(()),
+                Err(e) => e,
+            };
+
+            event.mark_failed(EventError {
+                error_type: e.category().to_string(),
+                message: e.to_string(),
+                code: None,
+                retry_after: None,
+                retryable: e.is_retryable(),
+                occurred_at: Utc::now(),
+            });
+
+            if !e.is_retryable() || !event.should_retry(retry.max_attempts) {
+                return Err((event, e));
+            }
+
+            let delay = retry_delay(retry, event.attempt_count);
+            debug!(
+                "Event {} failed attempt {}, retrying in {:?}: {}",
+                event.id, event.attempt_count, delay, e
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Move an event that exhausted its retries to the dead letter queue
+    async fn dead_letter(
+        &self,
+        event: Event,
+        source_stream: &str,
+        error: &EventStreamingError,
+    ) -> Result<()> {
+        let entry = DeadLetterEntry::new(event, source_stream, error.to_string());
+
+        warn!(
+            "Event {} dead-lettered after {} attempts: {}",
+            entry.event.id, entry.attempts, entry.reason
+        );
+
+        self.publish_to_target(&self.dead_letter_target(), &entry.event)
+            .await?;
+        self.event_storage.update_event_status(&entry.event).await?;
+
+        // A Redis dead letter stream is read back directly; only Kafka topics need the index
+        let depth = if self.dead_letter_stream().is_some() {
+            self.dead_letter_depth().await?
+        } else {
+            self.dead_letter_queue.push(entry).await
+        };
+
+        {
+            let mut stats = self.processing_stats.write().await;
+            stats.total_dead_letter += 1;
+        }
+
+        self.metrics_collector.record_dead_letter(depth).await?;
+
+        Ok(())
+    }
+
+    /// Dead letter destination; a queue name without a transport prefix is a Redis stream
+    fn dead_letter_target(&self) -> String {
+        let queue_name = &self.config.processing.dead_letter.queue_name;
+        if queue_name.starts_with("kafka:") || queue_name.starts_with("redis:") {
+            queue_name.clone()
+        } else {
+            format!("redis:{}", queue_name)
+        }
+    }
+
+    /// Name of the dead letter stream when the queue is a Redis stream
+    fn dead_letter_stream(&self) -> Option<&str> {
+        let queue_name = &self.config.processing.dead_letter.queue_name;
+        if queue_name.starts_with("kafka:") {
+            None
+        } else {
+            Some(queue_name.strip_prefix("redis:").unwrap_or(queue_name))
+        }
+    }
+
+    /// Publish an event to a single `kafka:` or `redis:` target
+    async fn publish_to_target(&self, target: &str, event: &Event) -> Result<()> {
+        match target {
+            target if target.starts_with("kafka:") => {
+                let topic = target.strip_prefix("kafka:").unwrap();
+                self.kafka_manager.publish_event(topic, event, None).await?;
+            }
+            target if target.starts_with("redis:") => {
+                let stream = target.strip_prefix("redis:").unwrap();
+                self.redis_manager.publish_event(stream, event).await?;
+            }
+            _ => {
+                warn!("Unknown destination target: {}", target);
+            }
+        }
+        Ok(())
+    }
+
     /// Check if event should be processed
     async fn should_process_event(&self, event: &Event) -> Result<bool> {
         for filter in &self.config.processing.filters {
@@ -728,10 +982,50 @@ impl ProcessingPipeline {
             .record_replay_jobs(jobs.len(), active_jobs)
             .await?;
 
+        // Drop expired dead letter entries and report the queue depth
+        let expired = match (
+            self.dead_letter_stream(),
+            self.dead_letter_queue.retention_cutoff(),
+        ) {
+            (Some(stream), Some(cutoff)) => {
+                self.redis_manager.trim_before(stream, cutoff).await? as usize
+            }
+            (Some(_), None) => 0,
+            (None, _) => self.dead_letter_queue.prune_expired().await,
+        };
+        if expired > 0 {
+            debug!("Pruned {} expired dead letter entries", expired);
+        }
+        self.metrics_collector
+            .set_dead_letter_queue_depth(self.dead_letter_depth().await? as i64);
+
         Ok(())
     }
 }
 
+/// Delay before the next attempt after `attempt` failed attempts
+fn retry_delay(retry: &RetryConfig, attempt: u32) -> Duration {
+    let attempt = attempt.max(1);
+    let initial = retry.initial_delay_ms as f64;
+    let exponential = initial * retry.backoff_multiplier.powi(attempt as i32 - 1);
+
+    let delay_ms = match retry.backoff_strategy {
+        BackoffStrategy::Fixed => initial,
+        BackoffStrategy::Linear => initial * attempt as f64,
+        BackoffStrategy::Exponential => exponential,
+        BackoffStrategy::ExponentialWithJitter => {
+            // Up to 50% jitter so failed events do not retry in lockstep
+            let nanos = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|elapsed| elapsed.subsec_nanos())
+                .unwrap_or(0);
+            exponential * (0.5 + (nanos % 1000) as f64 / 2000.0)
+        }
+    };
+
+    Duration::from_millis(delay_ms.min(retry.max_delay_ms as f64) as u64)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -753,8 +1047,10 @@ mod tests {
         );
         let storage = Arc::new(EventStorage::new(&config).await.unwrap());
         let router = Arc::new(EventRouter::new(&config).await.unwrap());
+        let schemas = Arc::new(SchemaRegistry::new(&config).await.unwrap());
 
-        let result = ProcessingPipeline::new(&config, kafka, redis, storage, router, metrics).await;
+        let result =
+            ProcessingPipeline::new(&config, kafka, redis, storage, router, schemas, metrics).await;
 
         assert!(result.is_ok());
     }
@@ -773,10 +1069,12 @@ mod tests {
         );
         let storage = Arc::new(EventStorage::new(&config).await.unwrap());
         let router = Arc::new(EventRouter::new(&config).await.unwrap());
+        let schemas = Arc::new(SchemaRegistry::new(&config).await.unwrap());
 
-        let pipeline = ProcessingPipeline::new(&config, kafka, redis, storage, router, metrics)
-            .await
-            .unwrap();
+        let pipeline =
+            ProcessingPipeline::new(&config, kafka, redis, storage, router, schemas, metrics)
+                .await
+                .unwrap();
 
         let from_timestamp = Utc::now() - chrono::Duration::hours(1);
         let result = pipeline
@@ -796,6 +1094,30 @@ mod tests {
         assert_eq!(deserialized, ReplayStatus::Running);
     }
 
+    #[test]
+    fn test_retry_delay() {
+        let mut retry = RetryConfig {
+            max_attempts: 5,
+            backoff_strategy: BackoffStrategy::Exponential,
+            initial_delay_ms: 100,
+            max_delay_ms: 1000,
+            backoff_multiplier: 2.0,
+        };
+        assert_eq!(retry_delay(&retry, 1), Duration::from_millis(100));
+        assert_eq!(retry_delay(&retry, 3), Duration::from_millis(400));
+        assert_eq!(retry_delay(&retry, 10), Duration::from_millis(1000));
+
+        retry.backoff_strategy = BackoffStrategy::Linear;
+        assert_eq!(retry_delay(&retry, 3), Duration::from_millis(300));
+
+        retry.backoff_strategy = BackoffStrategy::Fixed;
+        assert_eq!(retry_delay(&retry, 3), Duration::from_millis(100));
+
+        retry.backoff_strategy = BackoffStrategy::ExponentialWithJitter;
+        let delay = retry_delay(&retry, 3);
+        assert!(delay >= Duration::from_millis(200) && delay <= Duration::from_millis(400));
+    }
+
     #[tokio::test]
     async fn test_event_filtering() {
         let config = Config::default();
@@ -810,10 +1132,12 @@ mod tests {
         );
         let storage = Arc::new(EventStorage::new(&config).await.unwrap());
         let router = Arc::new(EventRouter::new(&config).await.unwrap());
+        let schemas = Arc::new(SchemaRegistry::new(&config).await.unwrap());
 
-        let pipeline = ProcessingPipeline::new(&config, kafka, redis, storage, router, metrics)
-            .await
-            .unwrap();
+        let pipeline =
+            ProcessingPipeline::new(&config, kafka, redis, storage, router, schemas, metrics)
+                .await
+                .unwrap();
 
         // Create test event
         let source = EventSource {
@@ -7,8 +7,9 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use chrono::{DateTime, Utc};
 use redis::{
-    streams::{StreamId, StreamReadOptions, StreamReadReply},
+    streams::{StreamId, StreamRangeReply, StreamReadOptions, StreamReadReply},
     AsyncCommands, Client, Connection, RedisResult,
 };
 use serde_json;
@@ -351,6 +352,84 @@ impl RedisStreamManager {
         Ok(serde_json::Value::Object(json_info))
     }
 
+    /// Read the entries added to a stream at or after `from`, oldest first,
+    /// returning each event with its stream entry ID
+    pub async fn read_range(
+        &self,
+        stream: &str,
+        from: DateTime<Utc>,
+        batch_size: usize,
+    ) -> Result<Vec<(String, Event)>> {
+        let mut conn = self.get_connection().await?;
+
+        let mut entries = Vec::new();
+        let mut start = format!("{}-0", from.timestamp_millis().max(0));
+
+        loop {
+            let reply: StreamRangeReply = conn
+                .xrange_count(stream, &start, "+", batch_size)
+                .await
+                .map_err(|e| EventStreamingError::redis(format!("Failed to read range from stream {}: {}", stream, e)))?;
+
+            let Some(last) = reply.ids.last() else {
+                break;
+            };
+            // Exclusive start so the next batch begins after the last entry read
+            start = format!("({}", last.id);
+            let exhausted = reply.ids.len() < batch_size;
+
+            for stream_id in &reply.ids {
+                match self.parse_stream_entry(stream_id).await {
+                    Ok(event) => entries.push((stream_id.id.clone(), event)),
+                    Err(e) => {
+                        warn!("Failed to parse stream entry {}: {}", stream_id.id, e);
+                    }
+                }
+            }
+
+            if exhausted {
+                break;
+            }
+        }
+
+        Ok(entries)
+    }
+
+    /// Delete entries from a stream by ID, returning how many were removed
+    pub async fn delete_entries(&self, stream: &str, ids: &[String]) -> Result<u64> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        let mut conn = self.get_connection().await?;
+
+        conn.xdel(stream, ids)
+            .await
+            .map_err(|e| EventStreamingError::redis(format!("Failed to delete entries from stream {}: {}", stream, e)))
+    }
+
+    /// Number of entries in a stream; a missing stream has none
+    pub async fn stream_length(&self, stream: &str) -> Result<u64> {
+        let mut conn = self.get_connection().await?;
+
+        conn.xlen(stream)
+            .await
+            .map_err(|e| EventStreamingError::redis(format!("Failed to get length of stream {}: {}", stream, e)))
+    }
+
+    /// Drop the entries added to a stream before `cutoff`, returning how many were removed
+    pub async fn trim_before(&self, stream: &str, cutoff: DateTime<Utc>) -> Result<u64> {
+        let mut conn = self.get_connection().await?;
+
+        redis::cmd("XTRIM")
+            .arg(stream)
+            .arg("MINID")
+            .arg(format!("{}-0", cutoff.timestamp_millis().max(0)))
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| EventStreamingError::redis(format!("Failed to trim stream {}: {}", stream, e)))
+    }
+
     /// List all streams
     pub async fn list_streams(&self) -> Result<Vec<String>> {
         // Get connection
@@ -2,11 +2,20 @@
 //!
 //! This module provides event routing functionality for the event streaming service.
 //! It handles routing events to appropriate destinations based on rules and patterns.
-
-use std::collections::HashMap;
+//!
+//! Rules are declarative: each matches on event type, category, source, tenant,
+//! metadata properties and JSON-path conditions over the payload. Matching rules
+//! are evaluated in priority order and every match contributes its destination,
+//! unless a rule stops evaluation. Events no rule matches go to the configured
+//! default destination. Rules can be replaced or reloaded from the rules file at
+//! runtime.
+
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::RwLock;
 use tracing::{debug, info};
 
 use crate::{
@@ -20,26 +29,81 @@ use crate::{
 #[derive(Clone)]
 pub struct EventRouter {
     config: Arc<Config>,
-    routing_rules: Arc<Vec<RoutingRule>>,
+    routing_rules: Arc<RwLock<Vec<RoutingRule>>>,
 }
 
 /// Routing rule for determining event destinations
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RoutingRule {
     pub name: String,
     pub condition: RoutingCondition,
     pub destination: EventDestination,
     pub priority: u32,
     pub enabled: bool,
+    /// Skip lower-priority rules once this rule matches
+    #[serde(default)]
+    pub stop_on_match: bool,
 }
 
-/// Routing condition for matching events
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Routing condition for matching events. Every condition that is set must
+/// match; a condition with nothing set matches all events.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct RoutingCondition {
+    /// Event type patterns, e.g. `workflow.*` or `*.failed`
     pub event_types: Option<Vec<String>>,
     pub categories: Option<Vec<String>>,
     pub sources: Option<Vec<String>>,
     pub tenant_ids: Option<Vec<String>>,
+    /// Metadata property patterns, keyed by property name
+    #[serde(default)]
+    pub metadata: Option<HashMap<String, String>>,
+    /// Conditions on the payload data
+    #[serde(default)]
+    pub payload: Option<Vec<PayloadCondition>>,
+}
+
+/// Condition on a value in the event payload data, addressed by a JSON path
+/// such as `$.order.total` or `$.items[0].sku`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PayloadCondition {
+    pub path: String,
+    pub operator: PayloadOperator,
+    #[serde(default)]
+    pub value: Option<Value>,
+}
+
+/// Comparison applied by a payload condition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadOperator {
+    /// The path resolves to a non-null value
+    Exists,
+    Equals,
+    NotEquals,
+    GreaterThan,
+    LessThan,
+    /// Substring of a string, or element of an array
+    Contains,
+    /// Wildcard pattern match on a string
+    Matches,
+}
+
+/// A rule that matched during routing
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleMatch {
+    pub rule: String,
+    pub priority: u32,
+    pub destination: EventDestination,
+}
+
+/// Where an event would be routed and why
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoutingDecision {
+    /// Matching rules, in evaluation order
+    pub matched_rules: Vec<RuleMatch>,
+    pub destinations: Vec<EventDestination>,
+    /// Whether no rule matched and the default destination was used
+    pub used_default: bool,
 }
 
 impl EventRouter {
@@ -47,15 +111,29 @@ impl EventRouter {
     pub async fn new(config: &Config) -> Result<Self> {
         info!("Initializing Event Router");
 
-        // Default routing rules
-        let routing_rules = vec![
+        let routing_rules = match (&config.routing.rules_file, &config.routing.rules) {
+            (Some(path), _) => Self::read_rules_file(path).await?,
+            (None, Some(rules)) => rules.clone(),
+            (None, None) => Self::default_rules(),
+        };
+        let routing_rules = Self::prepare_rules(routing_rules)?;
+
+        info!("Event Router loaded {} routing rules", routing_rules.len());
+
+        Ok(Self {
+            config: Arc::new(config.clone()),
+            routing_rules: Arc::new(RwLock::new(routing_rules)),
+        })
+    }
+
+    /// Built-in rules used when no rules are configured
+    fn default_rules() -> Vec<RoutingRule> {
+        vec![
             RoutingRule {
                 name: "workflow-events".to_string(),
                 condition: RoutingCondition {
                     event_types: Some(vec!["workflow.*".to_string()]),
-                    categories: None,
-                    sources: None,
-                    tenant_ids: None,
+                    ..RoutingCondition::default()
                 },
                 destination: EventDestination {
                     target: "kafka:workflow-events".to_string(),
@@ -64,14 +142,13 @@ impl EventRouter {
                 },
                 priority: 100,
                 enabled: true,
+                stop_on_match: false,
             },
             RoutingRule {
                 name: "system-events".to_string(),
                 condition: RoutingCondition {
                     event_types: Some(vec!["system.*".to_string()]),
-                    categories: None,
-                    sources: None,
-                    tenant_ids: None,
+                    ..RoutingCondition::default()
                 },
                 destination: EventDestination {
                     target: "redis:system-events".to_string(),
@@ -80,15 +157,11 @@ impl EventRouter {
                 },
                 priority: 90,
                 enabled: true,
+                stop_on_match: false,
             },
             RoutingRule {
                 name: "default-fallback".to_string(),
-                condition: RoutingCondition {
-                    event_types: None,
-                    categories: None,
-                    sources: None,
-                    tenant_ids: None,
-                },
+                condition: RoutingCondition::default(),
                 destination: EventDestination {
                     target: "kafka:all-events".to_string(),
                     routing_key: None,
@@ -96,60 +169,100 @@ impl EventRouter {
                 },
                 priority: 1,
                 enabled: true,
+                stop_on_match: false,
             },
-        ];
-
-        Ok(Self {
-            config: Arc::new(config.clone()),
-            routing_rules: Arc::new(routing_rules),
-        })
+        ]
     }
 
     /// Route an event to appropriate destinations
     pub async fn route_event(&self, event: &Event) -> Result<Vec<EventDestination>> {
+        let decision = self.dry_run(event).await?;
+
+        debug!(
+            "Event {} routed to {} destinations",
+            event.id,
+            decision.destinations.len()
+        );
+
+        Ok(decision.destinations)
+    }
+
+    /// Evaluate the routing rules for an event without publishing it
+    pub async fn dry_run(&self, event: &Event) -> Result<RoutingDecision> {
         debug!("Routing event {} of type {}", event.id, event.event_type);
 
-        let mut destinations = Vec::new();
+        let rules = self.routing_rules.read().await;
         let mut matched_rules = Vec::new();
 
-        // Find matching rules
-        for rule in self.routing_rules.iter() {
-            if rule.enabled && self.matches_condition(&rule.condition, event) {
-                matched_rules.push(rule.clone());
+        // Rules are kept sorted by priority (higher priority first)
+        for rule in rules.iter() {
+            if !rule.enabled || !self.matches_condition(&rule.condition, event) {
+                continue;
             }
-        }
 
-        // Sort by priority (higher priority first)
-        matched_rules.sort_by(|a, b| b.priority.cmp(&a.priority));
+            matched_rules.push(RuleMatch {
+                rule: rule.name.clone(),
+                priority: rule.priority,
+                destination: rule.destination.clone(),
+            });
+
+            if rule.stop_on_match {
+                break;
+            }
+        }
 
-        // Add destinations from matching rules
-        for rule in matched_rules {
-            destinations.push(rule.destination);
+        let mut destinations: Vec<EventDestination> = Vec::new();
+        for rule_match in &matched_rules {
+            if !destinations.contains(&rule_match.destination) {
+                destinations.push(rule_match.destination.clone());
+            }
         }
 
         // Ensure at least one destination (fallback)
-        if destinations.is_empty() {
-            destinations.push(EventDestination {
-                target: "kafka:dead-letter".to_string(),
-                routing_key: Some("unrouted".to_string()),
-                config: HashMap::new(),
-            });
+        let used_default = destinations.is_empty();
+        if used_default {
+            destinations.push(self.config.routing.default_destination.clone());
         }
 
-        debug!(
-            "Event {} routed to {} destinations",
-            event.id,
-            destinations.len()
-        );
+        Ok(RoutingDecision {
+            matched_rules,
+            destinations,
+            used_default,
+        })
+    }
 
-        Ok(destinations)
+    /// Current routing rules, in evaluation order
+    pub async fn rules(&self) -> Vec<RoutingRule> {
+        self.routing_rules.read().await.clone()
+    }
+
+    /// Replace the routing rules. The rules are validated first; on error the
+    /// current rules stay in place.
+    pub async fn replace_rules(&self, rules: Vec<RoutingRule>) -> Result<usize> {
+        let rules = Self::prepare_rules(rules)?;
+        let count = rules.len();
+
+        *self.routing_rules.write().await = rules;
+
+        info!("Replaced routing rules ({} rules)", count);
+        Ok(count)
+    }
+
+    /// Reload the routing rules from the configured rules file
+    pub async fn reload(&self) -> Result<usize> {
+        let path = self.config.routing.rules_file.as_deref().ok_or_else(|| {
+            EventStreamingError::configuration("No routing rules file configured")
+        })?;
+
+        let rules = Self::read_rules_file(path).await?;
+        self.replace_rules(rules).await
     }
 
     /// List all available streams
     pub async fn list_streams(&self) -> Result<Vec<String>> {
         let mut streams = Vec::new();
 
-        for rule in self.routing_rules.iter() {
+        for rule in self.routing_rules.read().await.iter() {
             if let Some(stream) = self.extract_stream_name(&rule.destination.target) {
                 if !streams.contains(&stream) {
                     streams.push(stream);
@@ -165,7 +278,7 @@ impl EventRouter {
     pub async fn get_stream_info(&self, stream_name: &str) -> Result<Option<serde_json::Value>> {
         let mut rules_for_stream = Vec::new();
 
-        for rule in self.routing_rules.iter() {
+        for rule in self.routing_rules.read().await.iter() {
             if let Some(stream) = self.extract_stream_name(&rule.destination.target) {
                 if stream == stream_name {
                     rules_for_stream.push(rule.clone());
@@ -184,6 +297,67 @@ impl EventRouter {
         })))
     }
 
+    /// Read routing rules from a JSON file
+    async fn read_rules_file(path: &str) -> Result<Vec<RoutingRule>> {
+        let contents = tokio::fs::read_to_string(path).await.map_err(|e| {
+            EventStreamingError::configuration(format!(
+                "Failed to read routing rules file {}: {}",
+                path, e
+            ))
+        })?;
+
+        serde_json::from_str(&contents).map_err(|e| {
+            EventStreamingError::configuration(format!(
+                "Invalid routing rules file {}: {}",
+                path, e
+            ))
+        })
+    }
+
+    /// Validate rules and sort them by priority, keeping the given order for ties
+    fn prepare_rules(mut rules: Vec<RoutingRule>) -> Result<Vec<RoutingRule>> {
+        let mut names = HashSet::new();
+
+        for rule in &rules {
+            if rule.name.is_empty() {
+                return Err(EventStreamingError::validation(
+                    "Routing rule name must not be empty",
+                ));
+            }
+            if !names.insert(rule.name.as_str()) {
+                return Err(EventStreamingError::validation(format!(
+                    "Duplicate routing rule name: {}",
+                    rule.name
+                )));
+            }
+            if !rule.destination.target.starts_with("kafka:")
+                && !rule.destination.target.starts_with("redis:")
+            {
+                return Err(EventStreamingError::validation(format!(
+                    "Routing rule {} has unsupported destination {}; expected kafka:<topic> or redis:<stream>",
+                    rule.name, rule.destination.target
+                )));
+            }
+            for condition in rule.condition.payload.iter().flatten() {
+                if parse_path(&condition.path).is_none() {
+                    return Err(EventStreamingError::validation(format!(
+                        "Routing rule {} has invalid payload path {}",
+                        rule.name, condition.path
+                    )));
+                }
+                if condition.operator != PayloadOperator::Exists && condition.value.is_none() {
+                    return Err(EventStreamingError::validation(format!(
+                        "Routing rule {} needs a value for its {:?} condition on {}",
+                        rule.name, condition.operator, condition.path
+                    )));
+                }
+            }
+        }
+
+        rules.sort_by_key(|rule| std::cmp::Reverse(rule.priority));
+        Ok(rules)
+    }
+
     /// Check if event matches routing condition
     fn matches_condition(&self, condition: &RoutingCondition, event: &Event) -> bool {
         // Check event types
@@ -202,8 +376,11 @@ impl EventRouter {
 
         // Check categories
         if let Some(categories) = &condition.categories {
-            let category_str = serde_json::to_string(&event.category).unwrap_or_default();
-            if !categories.contains(&category_str) {
+            let category = serde_json::to_value(&event.category).unwrap_or_default();
+            let matches = category
+                .as_str()
+                .is_some_and(|category| categories.iter().any(|c| c == category));
+            if !matches {
                 return false;
             }
         }
@@ -227,9 +404,66 @@ impl EventRouter {
             }
         }
 
+        // Check metadata properties
+        if let Some(metadata) = &condition.metadata {
+            for (key, pattern) in metadata {
+                match event.metadata.properties.get(key) {
+                    Some(value) if self.matches_pattern(pattern, value) => {}
+                    _ => return false,
+                }
+            }
+        }
+
+        // Check payload conditions
+        if let Some(conditions) = &condition.payload {
+            let payload = event.payload_data();
+            if !conditions
+                .iter()
+                .all(|condition| self.matches_payload_condition(condition, &payload))
+            {
+                return false;
+            }
+        }
+
         true
     }
 
+    /// Check a payload condition against the payload data
+    fn matches_payload_condition(&self, condition: &PayloadCondition, payload: &Value) -> bool {
+        let actual = resolve_path(payload, &condition.path).filter(|value| !value.is_null());
+        if condition.operator == PayloadOperator::Exists {
+            return actual.is_some();
+        }
+
+        let (Some(actual), Some(expected)) = (actual, &condition.value) else {
+            // A missing value only satisfies "not equals"
+            return condition.operator == PayloadOperator::NotEquals;
+        };
+
+        match condition.operator {
+            PayloadOperator::Exists => true,
+            PayloadOperator::Equals => values_equal(actual, expected),
+            PayloadOperator::NotEquals => !values_equal(actual, expected),
+            PayloadOperator::GreaterThan => compare_numbers(actual, expected)
+                .is_some_and(|ordering| ordering == std::cmp::Ordering::Greater),
+            PayloadOperator::LessThan => compare_numbers(actual, expected)
+                .is_some_and(|ordering| ordering == std::cmp::Ordering::Less),
+            PayloadOperator::Contains => match (actual, expected) {
+                (Value::String(actual), Value::String(expected)) => actual.contains(expected),
+                (Value::Array(items), expected) => {
+                    items.iter().any(|item| values_equal(item, expected))
+                }
+                _ => false,
+            },
+            PayloadOperator::Matches => match (actual, expected) {
+                (Value::String(actual), Value::String(pattern)) => {
+                    self.matches_pattern(pattern, actual)
+                }
+                _ => false,
+            },
+        }
+    }
+
     /// Check if a string matches a pattern (supports wildcards)
     fn matches_pattern(&self, pattern: &str, value: &str) -> bool {
         if pattern == "*" {
@@ -261,6 +495,73 @@ impl EventRouter {
     }
 }
 
+/// A segment of a payload JSON path
+#[derive(Debug, PartialEq)]
+enum PathSegment<'a> {
+    Field(&'a str),
+    Index(usize),
+}
+
+/// Parse a JSON path of the form `$.field.nested[0]`. The leading `$` is
+/// optional and refers to the payload data.
+fn parse_path(path: &str) -> Option<Vec<PathSegment<'_>>> {
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let path = path.strip_prefix('.').unwrap_or(path);
+    let mut segments = Vec::new();
+
+    if path.is_empty() {
+        return Some(segments);
+    }
+
+    for part in path.split('.') {
+        let (field, mut indexes) = match part.find('[') {
+            Some(start) => (&part[..start], &part[start..]),
+            None => (part, ""),
+        };
+
+        if field.is_empty() && indexes.is_empty() {
+            return None;
+        }
+        if !field.is_empty() {
+            segments.push(PathSegment::Field(field));
+        }
+
+        while !indexes.is_empty() {
+            let end = indexes.find(']')?;
+            let index = indexes.get(1..end)?.parse().ok()?;
+            segments.push(PathSegment::Index(index));
+            indexes = &indexes[end + 1..];
+            if !indexes.is_empty() && !indexes.starts_with('[') {
+                return None;
+            }
+        }
+    }
+
+    Some(segments)
+}
+
+/// Resolve a JSON path against a value
+fn resolve_path<'v>(value: &'v Value, path: &str) -> Option<&'v Value> {
+    parse_path(path)?
+        .into_iter()
+        .try_fold(value, |value, segment| match segment {
+            PathSegment::Field(field) => value.get(field),
+            PathSegment::Index(index) => value.get(index),
+        })
+}
+
+/// Compare values, treating numbers of different representations as equal
+fn values_equal(actual: &Value, expected: &Value) -> bool {
+    match (actual.as_f64(), expected.as_f64()) {
+        (Some(actual), Some(expected)) => actual == expected,
+        _ => actual == expected,
+    }
+}
+
+fn compare_numbers(actual: &Value, expected: &Value) -> Option<std::cmp::Ordering> {
+    actual.as_f64()?.partial_cmp(&expected.as_f64()?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -268,6 +569,52 @@ mod tests {
     use crate::events::{Event, EventPayload};
     use crate::types::{EventCategory, EventSource};
 
+    fn event(event_type: &str, payload: Value) -> Event {
+        let source = EventSource {
+            service: "test-service".to_string(),
+            version: "1.0.0".to_string(),
+            instance_id: None,
+            hostname: None,
+            metadata: std::collections::HashMap::new(),
+        };
+
+        Event::new(
+            event_type,
+            EventCategory::Workflow,
+            source,
+            EventPayload::Custom(payload),
+        )
+    }
+
+    fn rule(name: &str, priority: u32, target: &str, condition: RoutingCondition) -> RoutingRule {
+        RoutingRule {
+            name: name.to_string(),
+            condition,
+            destination: EventDestination {
+                target: target.to_string(),
+                routing_key: None,
+                config: HashMap::new(),
+            },
+            priority,
+            enabled: true,
+            stop_on_match: false,
+        }
+    }
+
+    async fn router(rules: Vec<RoutingRule>) -> EventRouter {
+        let mut config = Config::default();
+        config.routing.rules = Some(rules);
+        EventRouter::new(&config).await.unwrap()
+    }
+
+    fn targets(decision: &RoutingDecision) -> Vec<&str> {
+        decision
+            .destinations
+            .iter()
+            .map(|destination| destination.target.as_str())
+            .collect()
+    }
+
     #[tokio::test]
     async fn test_event_router_creation() {
         let config = Config::default();
@@ -295,8 +642,8 @@ mod tests {
         assert!(!destinations.is_empty());
     }
 
-    #[test]
-    fn test_pattern_matching() {
+    #[tokio::test]
+    async fn test_pattern_matching() {
         let config = Config::default();
         let router = EventRouter::new(&config).await.unwrap();
 
@@ -314,4 +661,210 @@ mod tests {
         let streams = router.list_streams().await.unwrap();
         assert!(!streams.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_overlapping_rules_in_priority_order() {
+        let router = router(vec![
+            rule(
+                "all-workflows",
+                10,
+                "kafka:workflow-events",
+                RoutingCondition {
+                    event_types: Some(vec!["workflow.*".to_string()]),
+                    ..RoutingCondition::default()
+                },
+            ),
+            rule(
+                "large-orders",
+                50,
+                "redis:large-orders",
+                RoutingCondition {
+                    categories: Some(vec!["workflow".to_string()]),
+                    payload: Some(vec![PayloadCondition {
+                        path: "$.order.total".to_string(),
+                        operator: PayloadOperator::GreaterThan,
+                        value: Some(serde_json::json!(1000)),
+                    }]),
+                    ..RoutingCondition::default()
+                },
+            ),
+        ])
+        .await;
+
+        let large = event(
+            "workflow.completed",
+            serde_json::json!({"order": {"total": 2500.0}}),
+        );
+        let decision = router.dry_run(&large).await.unwrap();
+        assert_eq!(
+            targets(&decision),
+            vec!["redis:large-orders", "kafka:workflow-events"]
+        );
+        assert_eq!(decision.matched_rules[0].rule, "large-orders");
+        assert!(!decision.used_default);
+
+        let small = event(
+            "workflow.completed",
+            serde_json::json!({"order": {"total": 20}}),
+        );
+        let decision = router.dry_run(&small).await.unwrap();
+        assert_eq!(targets(&decision), vec!["kafka:workflow-events"]);
+    }
+
+    #[tokio::test]
+    async fn test_stop_on_match_skips_lower_priority_rules() {
+        let mut audit = rule(
+            "audit",
+            100,
+            "kafka:audit",
+            RoutingCondition {
+                metadata: Some(HashMap::from([(
+                    "compliance".to_string(),
+                    "sox*".to_string(),
+                )])),
+                ..RoutingCondition::default()
+            },
+        );
+        audit.stop_on_match = true;
+        let router = router(vec![
+            audit,
+            rule(
+                "everything",
+                1,
+                "kafka:all-events",
+                RoutingCondition::default(),
+            ),
+        ])
+        .await;
+
+        let mut flagged = event("workflow.completed", serde_json::json!({}));
+        flagged
+            .metadata
+            .properties
+            .insert("compliance".to_string(), "sox-2024".to_string());
+        let decision = router.dry_run(&flagged).await.unwrap();
+        assert_eq!(targets(&decision), vec!["kafka:audit"]);
+
+        let plain = event("workflow.completed", serde_json::json!({}));
+        let decision = router.dry_run(&plain).await.unwrap();
+        assert_eq!(targets(&decision), vec!["kafka:all-events"]);
+    }
+
+    #[tokio::test]
+    async fn test_unmatched_events_fall_through_to_default() {
+        let router = router(vec![rule(
+            "eu-customers",
+            10,
+            "kafka:eu-events",
+            RoutingCondition {
+                payload: Some(vec![PayloadCondition {
+                    path: "$.customer.regions[0]".to_string(),
+                    operator: PayloadOperator::Equals,
+                    value: Some(serde_json::json!("eu")),
+                }]),
+                ..RoutingCondition::default()
+            },
+        )])
+        .await;
+
+        let matched = event(
+            "user.signed_up",
+            serde_json::json!({"customer": {"regions": ["eu", "us"]}}),
+        );
+        let decision = router.dry_run(&matched).await.unwrap();
+        assert_eq!(targets(&decision), vec!["kafka:eu-events"]);
+
+        let unmatched = event(
+            "user.signed_up",
+            serde_json::json!({"customer": {"regions": ["us"]}}),
+        );
+        let decision = router.dry_run(&unmatched).await.unwrap();
+        assert!(decision.used_default);
+        assert!(decision.matched_rules.is_empty());
+        assert_eq!(targets(&decision), vec!["kafka:dead-letter"]);
+    }
+
+    #[tokio::test]
+    async fn test_replace_rules_at_runtime() {
+        let router = router(vec![rule(
+            "everything",
+            1,
+            "kafka:all-events",
+            RoutingCondition::default(),
+        )])
+        .await;
+        let event = event("system.started", serde_json::json!({}));
+
+        router
+            .replace_rules(vec![rule(
+                "system",
+                5,
+                "redis:system-events",
+                RoutingCondition {
+                    event_types: Some(vec!["system.*".to_string()]),
+                    ..RoutingCondition::default()
+                },
+            )])
+            .await
+            .unwrap();
+        let decision = router.dry_run(&event).await.unwrap();
+        assert_eq!(targets(&decision), vec!["redis:system-events"]);
+
+        // Invalid rules are rejected and the current rules stay in place
+        let result = router
+            .replace_rules(vec![rule(
+                "broken",
+                5,
+                "http://example.com",
+                RoutingCondition::default(),
+            )])
+            .await;
+        assert!(result.is_err());
+        assert_eq!(router.rules().await[0].name, "system");
+    }
+
+    #[tokio::test]
+    async fn test_reload_from_rules_file() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let write_rules = |target: &str| {
+            let rules = vec![rule("file-rule", 1, target, RoutingCondition::default())];
+            std::fs::write(file.path(), serde_json::to_string(&rules).unwrap()).unwrap();
+        };
+
+        write_rules("kafka:first");
+        let mut config = Config::default();
+        config.routing.rules_file = Some(file.path().to_string_lossy().into_owned());
+        let router = EventRouter::new(&config).await.unwrap();
+
+        let event = event("workflow.started", serde_json::json!({}));
+        let decision = router.dry_run(&event).await.unwrap();
+        assert_eq!(targets(&decision), vec!["kafka:first"]);
+
+        write_rules("redis:second");
+        assert_eq!(router.reload().await.unwrap(), 1);
+        let decision = router.dry_run(&event).await.unwrap();
+        assert_eq!(targets(&decision), vec!["redis:second"]);
+    }
+
+    #[test]
+    fn test_resolve_path() {
+        let value = serde_json::json!({"a": {"b": [{"c": 1}, {"c": 2}]}, "d": [[3]]});
+
+        assert_eq!(
+            resolve_path(&value, "$.a.b[1].c"),
+            Some(&serde_json::json!(2))
+        );
+        assert_eq!(
+            resolve_path(&value, "a.b[0].c"),
+            Some(&serde_json::json!(1))
+        );
+        assert_eq!(
+            resolve_path(&value, "$.d[0][0]"),
+            Some(&serde_json::json!(3))
+        );
+        assert_eq!(resolve_path(&value, "$"), Some(&value));
+        assert_eq!(resolve_path(&value, "$.a.missing"), None);
+        assert!(parse_path("$.a..b").is_none());
+        assert!(parse_path("$.a[x]").is_none());
+    }
 }
@@ -0,0 +1,357 @@
+//! # Event Schema Module
+//!
+//! JSON Schema validation for event payloads. Schemas are registered per event
+//! type and schema version, compiled once at registration and matched against
+//! an event's `metadata.schema_version`. Event types without a registered
+//! schema are not validated.
+
+use std::collections::{BTreeMap, HashMap};
+
+use jsonschema::JSONSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::RwLock;
+use tracing::{debug, info};
+
+use crate::{
+    config::Config,
+    error::{EventStreamingError, Result},
+    events::Event,
+};
+
+/// Maximum number of schema violations listed in a rejection message
+const MAX_REPORTED_ERRORS: usize = 10;
+
+/// A JSON Schema for the payload data of one event type version
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventSchema {
+    pub event_type: String,
+    pub version: String,
+    pub schema: Value,
+}
+
+/// A registered schema together with its compiled form
+struct CompiledSchema {
+    schema: Value,
+    compiled: JSONSchema,
+}
+
+/// Registry of event schemas, keyed by event type and then schema version
+pub struct SchemaRegistry {
+    enabled: bool,
+    schemas: RwLock<HashMap<String, BTreeMap<String, CompiledSchema>>>,
+}
+
+impl SchemaRegistry {
+    /// Create a registry holding the schemas defined in the configuration
+    pub async fn new(config: &Config) -> Result<Self> {
+        info!("Initializing Schema Registry");
+
+        let registry = Self {
+            enabled: config.schemas.enabled,
+            schemas: RwLock::new(HashMap::new()),
+        };
+
+        for schema in &config.schemas.definitions {
+            registry.register(schema.clone()).await?;
+        }
+
+        Ok(registry)
+    }
+
+    /// Register a schema, replacing any schema already registered for the same
+    /// event type and version. Returns whether a schema was replaced.
+    pub async fn register(&self, schema: EventSchema) -> Result<bool> {
+        let compiled = JSONSchema::compile(&schema.schema).map_err(|e| {
+            EventStreamingError::validation(format!(
+                "Invalid schema for {} version {}: {}",
+                schema.event_type, schema.version, e
+            ))
+        })?;
+
+        let replaced = self
+            .schemas
+            .write()
+            .await
+            .entry(schema.event_type.clone())
+            .or_default()
+            .insert(
+                schema.version.clone(),
+                CompiledSchema {
+                    schema: schema.schema,
+                    compiled,
+                },
+            )
+            .is_some();
+
+        info!(
+            "Registered schema for {} version {}",
+            schema.event_type, schema.version
+        );
+
+        Ok(replaced)
+    }
+
+    /// Remove a schema version, returning whether it was registered
+    pub async fn remove(&self, event_type: &str, version: &str) -> bool {
+        let mut schemas = self.schemas.write().await;
+        let Some(versions) = schemas.get_mut(event_type) else {
+            return false;
+        };
+
+        let removed = versions.remove(version).is_some();
+        if versions.is_empty() {
+            schemas.remove(event_type);
+        }
+        removed
+    }
+
+    /// Get a registered schema
+    pub async fn get(&self, event_type: &str, version: &str) -> Option<EventSchema> {
+        let schemas = self.schemas.read().await;
+        let schema = schemas.get(event_type)?.get(version)?;
+
+        Some(EventSchema {
+            event_type: event_type.to_string(),
+            version: version.to_string(),
+            schema: schema.schema.clone(),
+        })
+    }
+
+    /// List registered schemas, ordered by event type and version
+    pub async fn list(&self) -> Vec<EventSchema> {
+        let schemas = self.schemas.read().await;
+        let mut list: Vec<EventSchema> = schemas
+            .iter()
+            .flat_map(|(event_type, versions)| {
+                versions.iter().map(|(version, schema)| EventSchema {
+                    event_type: event_type.clone(),
+                    version: version.clone(),
+                    schema: schema.schema.clone(),
+                })
+            })
+            .collect();
+
+        list.sort_by(|a, b| (&a.event_type, &a.version).cmp(&(&b.event_type, &b.version)));
+        list
+    }
+
+    /// Validate an event's payload against the schema registered for its type
+    /// and schema version. Events of unschematized types always pass.
+    pub async fn validate(&self, event: &Event) -> Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        let schemas = self.schemas.read().await;
+        let Some(versions) = schemas.get(&event.event_type) else {
+            return Ok(());
+        };
+
+        let version = &event.metadata.schema_version;
+        let Some(schema) = versions.get(version) else {
+            return Err(EventStreamingError::Validation {
+                message: format!(
+                    "Event {} of type {} has unknown schema version {} (registered: {})",
+                    event.id,
+                    event.event_type,
+                    version,
+                    versions.keys().cloned().collect::<Vec<_>>().join(", ")
+                ),
+                field: Some("metadata.schema_version".to_string()),
+                value: Some(version.clone()),
+                event_id: Some(event.id),
+            });
+        };
+
+        let payload = event.payload_data();
+        if let Err(errors) = schema.compiled.validate(&payload) {
+            let violations: Vec<String> = errors
+                .take(MAX_REPORTED_ERRORS)
+                .map(|error| {
+                    let path = error.instance_path.to_string();
+                    if path.is_empty() {
+                        error.to_string()
+                    } else {
+                        format!("{}: {}", path, error)
+                    }
+                })
+                .collect();
+
+            return Err(EventStreamingError::Validation {
+                message: format!(
+                    "Event {} does not match schema {} version {}: {}",
+                    event.id,
+                    event.event_type,
+                    version,
+                    violations.join("; ")
+                ),
+                field: Some("payload".to_string()),
+                value: None,
+                event_id: Some(event.id),
+            });
+        }
+
+        debug!(
+            "Event {} matches schema {} version {}",
+            event.id, event.event_type, version
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventPayload;
+    use crate::types::{EventCategory, EventSource};
+
+    fn event(event_type: &str, schema_version: &str, payload: Value) -> Event {
+        let source = EventSource {
+            service: "test-service".to_string(),
+            version: "1.0.0".to_string(),
+            instance_id: None,
+            hostname: None,
+            metadata: HashMap::new(),
+        };
+        let mut event = Event::new(
+            event_type,
+            EventCategory::Integration,
+            source,
+            EventPayload::Custom(payload),
+        );
+        event.metadata.schema_version = schema_version.to_string();
+        event
+    }
+
+    fn order_schema(version: &str, required: &[&str]) -> EventSchema {
+        EventSchema {
+            event_type: "order.created".to_string(),
+            version: version.to_string(),
+            schema: serde_json::json!({
+                "type": "object",
+                "required": required,
+                "properties": {
+                    "order_id": { "type": "string" },
+                    "total": { "type": "number", "minimum": 0 },
+                },
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validate_against_schema_version() {
+        let registry = SchemaRegistry::new(&Config::default()).await.unwrap();
+        registry
+            .register(order_schema("1.0.0", &["order_id"]))
+            .await
+            .unwrap();
+        registry
+            .register(order_schema("2.0.0", &["order_id", "total"]))
+            .await
+            .unwrap();
+
+        let payload = serde_json::json!({ "order_id": "A-1" });
+        assert!(registry
+            .validate(&event("order.created", "1.0.0", payload.clone()))
+            .await
+            .is_ok());
+
+        let error = registry
+            .validate(&event("order.created", "2.0.0", payload))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("total"));
+
+        let negative = serde_json::json!({ "order_id": "A-1", "total": -5 });
+        let error = registry
+            .validate(&event("order.created", "2.0.0", negative))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("/total"));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_version_is_rejected() {
+        let registry = SchemaRegistry::new(&Config::default()).await.unwrap();
+        registry
+            .register(order_schema("1.0.0", &["order_id"]))
+            .await
+            .unwrap();
+
+        let error = registry
+            .validate(&event(
+                "order.created",
+                "3.0.0",
+                serde_json::json!({ "order_id": "A-1" }),
+            ))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            EventStreamingError::Validation { ref value, .. } if value.as_deref() == Some("3.0.0")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unschematized_types_pass() {
+        let registry = SchemaRegistry::new(&Config::default()).await.unwrap();
+        registry
+            .register(order_schema("1.0.0", &["order_id"]))
+            .await
+            .unwrap();
+
+        assert!(registry
+            .validate(&event("user.login", "1.0.0", serde_json::json!(42)))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_register_update_and_remove() {
+        let registry = SchemaRegistry::new(&Config::default()).await.unwrap();
+        let payload = serde_json::json!({ "order_id": "A-1" });
+
+        assert!(!registry
+            .register(order_schema("1.0.0", &["order_id", "total"]))
+            .await
+            .unwrap());
+        assert!(registry
+            .validate(&event("order.created", "1.0.0", payload.clone()))
+            .await
+            .is_err());
+
+        // Updating the version takes effect immediately
+        assert!(registry
+            .register(order_schema("1.0.0", &["order_id"]))
+            .await
+            .unwrap());
+        assert!(registry
+            .validate(&event("order.created", "1.0.0", payload.clone()))
+            .await
+            .is_ok());
+        assert_eq!(registry.list().await.len(), 1);
+
+        assert!(registry.remove("order.created", "1.0.0").await);
+        assert!(registry.get("order.created", "1.0.0").await.is_none());
+        assert!(registry
+            .validate(&event("order.created", "1.0.0", serde_json::json!(null)))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_schema_is_rejected() {
+        let registry = SchemaRegistry::new(&Config::default()).await.unwrap();
+        let result = registry
+            .register(EventSchema {
+                event_type: "order.created".to_string(),
+                version: "1.0.0".to_string(),
+                schema: serde_json::json!({ "type": "not-a-type" }),
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert!(registry.list().await.is_empty());
+    }
+}
@@ -13,7 +13,7 @@ use axum::{
     http::StatusCode,
     middleware,
     response::Json,
-    routing::{get, post},
+    routing::{delete, get, post, put},
     Router,
 };
 use serde::{Deserialize, Serialize};
@@ -28,13 +28,15 @@ use uuid::Uuid;
 
 use crate::{
     config::Config,
+    dead_letter::{DeadLetterFilter, DeadLetterReplay},
     error::{EventStreamingError, Result},
-    events::Event,
+    events::{Event, EventType},
     kafka::KafkaManager,
     metrics::MetricsCollector,
     processing::ProcessingPipeline,
     redis_streams::RedisStreamManager,
-    routing::EventRouter,
+    routing::{EventRouter, RoutingDecision, RoutingRule},
+    schema::{EventSchema, SchemaRegistry},
     storage::EventStorage,
     types::{ComponentHealth, EventCategory, HealthStatus},
 };
@@ -47,6 +49,7 @@ pub struct EventStreamingService {
     redis_manager: Arc<RedisStreamManager>,
     processing_pipeline: Arc<ProcessingPipeline>,
     event_router: Arc<EventRouter>,
+    schema_registry: Arc<SchemaRegistry>,
     event_storage: Arc<EventStorage>,
     metrics_collector: Arc<MetricsCollector>,
     shutdown_tx: Arc<RwLock<Option<broadcast::Sender<()>>>>,
@@ -81,6 +84,9 @@ impl EventStreamingService {
         // Initialize event router
         let event_router = Arc::new(EventRouter::new(&config).await?);
 
+        // Initialize schema registry
+        let schema_registry = Arc::new(SchemaRegistry::new(&config).await?);
+
         // Initialize processing pipeline
         let processing_pipeline = Arc::new(
             ProcessingPipeline::new(
@@ -89,6 +95,7 @@ impl EventStreamingService {
                 redis_manager.clone(),
                 event_storage.clone(),
                 event_router.clone(),
+                schema_registry.clone(),
                 metrics_collector.clone(),
             )
             .await?,
@@ -102,6 +109,7 @@ impl EventStreamingService {
             redis_manager,
             processing_pipeline,
             event_router,
+            schema_registry,
             event_storage,
             metrics_collector,
             shutdown_tx: Arc::new(RwLock::new(Some(shutdown_tx))),
@@ -195,14 +203,29 @@ impl EventStreamingService {
             // Stream management endpoints
             .route("/streams", get(list_streams_handler))
             .route("/streams/:name/info", get(get_stream_info_handler))
+            // Schema endpoints
+            .route("/schemas", get(list_schemas_handler))
+            .route(
+                "/schemas/:event_type/:version",
+                get(get_schema_handler)
+                    .put(register_schema_handler)
+                    .delete(remove_schema_handler),
+            )
             // Replay endpoints
             .route("/replay/events", post(replay_events_handler))
             .route("/replay/status/:job_id", get(get_replay_status_handler))
+            .route("/replay/dead-letter", post(replay_dead_letter_handler))
             // Metrics endpoint
             .route("/metrics", get(metrics_handler))
             // Administrative endpoints
             .route("/admin/config", get(get_config_handler))
             .route("/admin/stats", get(get_stats_handler))
+            .route(
+                "/admin/routing/rules",
+                get(get_routing_rules_handler).put(replace_routing_rules_handler),
+            )
+            .route("/admin/routing/reload", post(reload_routing_rules_handler))
+            .route("/admin/routing/dry-run", post(routing_dry_run_handler))
             .with_state(self.clone())
             .layer(
                 ServiceBuilder::new()
@@ -481,7 +504,7 @@ struct PublishEventResponse {
 async fn publish_event_handler(
     State(service): State<EventStreamingService>,
     Json(request): Json<PublishEventRequest>,
-) -> std::result::Result<Json<PublishEventResponse>, StatusCode> {
+) -> std::result::Result<Json<PublishEventResponse>, (StatusCode, Json<PublishEventResponse>)> {
     match service
         .processing_pipeline
         .publish_event(request.event.clone())
@@ -492,9 +515,24 @@ async fn publish_event_handler(
             status: "accepted".to_string(),
             message: "Event queued for processing".to_string(),
         })),
+        Err(e @ EventStreamingError::Validation { .. }) => Err((
+            StatusCode::BAD_REQUEST,
+            Json(PublishEventResponse {
+                event_id: request.event.id,
+                status: "rejected".to_string(),
+                message: e.to_string(),
+            }),
+        )),
         Err(e) => {
             error!("Failed to publish event: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(PublishEventResponse {
+                    event_id: request.event.id,
+                    status: "failed".to_string(),
+                    message: "Failed to publish event".to_string(),
+                }),
+            ))
         }
     }
 }
@@ -571,6 +609,108 @@ async fn get_stream_info_handler(
     }
 }
 
+/// List schemas handler
+async fn list_schemas_handler(
+    State(service): State<EventStreamingService>,
+) -> Json<Vec<EventSchema>> {
+    Json(service.schema_registry.list().await)
+}
+
+/// Get schema handler
+async fn get_schema_handler(
+    State(service): State<EventStreamingService>,
+    Path((event_type, version)): Path<(String, String)>,
+) -> std::result::Result<Json<EventSchema>, StatusCode> {
+    service
+        .schema_registry
+        .get(&event_type, &version)
+        .await
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// Register or update schema handler
+async fn register_schema_handler(
+    State(service): State<EventStreamingService>,
+    Path((event_type, version)): Path<(String, String)>,
+    Json(schema): Json<serde_json::Value>,
+) -> std::result::Result<StatusCode, (StatusCode, String)> {
+    let schema = EventSchema {
+        event_type,
+        version,
+        schema,
+    };
+
+    match service.schema_registry.register(schema).await {
+        Ok(true) => Ok(StatusCode::OK),
+        Ok(false) => Ok(StatusCode::CREATED),
+        Err(e) => {
+            warn!("Rejected schema: {}", e);
+            Err((StatusCode::BAD_REQUEST, e.to_string()))
+        }
+    }
+}
+
+/// Remove schema handler
+async fn remove_schema_handler(
+    State(service): State<EventStreamingService>,
+    Path((event_type, version)): Path<(String, String)>,
+) -> StatusCode {
+    if service.schema_registry.remove(&event_type, &version).await {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}
+
+/// Get routing rules handler
+async fn get_routing_rules_handler(
+    State(service): State<EventStreamingService>,
+) -> Json<Vec<RoutingRule>> {
+    Json(service.event_router.rules().await)
+}
+
+/// Replace routing rules handler
+async fn replace_routing_rules_handler(
+    State(service): State<EventStreamingService>,
+    Json(rules): Json<Vec<RoutingRule>>,
+) -> std::result::Result<Json<serde_json::Value>, StatusCode> {
+    match service.event_router.replace_rules(rules).await {
+        Ok(count) => Ok(Json(serde_json::json!({ "rule_count": count }))),
+        Err(e) => {
+            warn!("Rejected routing rules: {}", e);
+            Err(StatusCode::BAD_REQUEST)
+        }
+    }
+}
+
+/// Reload routing rules from the rules file handler
+async fn reload_routing_rules_handler(
+    State(service): State<EventStreamingService>,
+) -> std::result::Result<Json<serde_json::Value>, StatusCode> {
+    match service.event_router.reload().await {
+        Ok(count) => Ok(Json(serde_json::json!({ "rule_count": count }))),
+        Err(e) => {
+            error!("Failed to reload routing rules: {}", e);
+            Err(StatusCode::UNPROCESSABLE_ENTITY)
+        }
+    }
+}
+
+/// Routing dry run handler
+async fn routing_dry_run_handler(
+    State(service): State<EventStreamingService>,
+    Json(event): Json<Event>,
+) -> std::result::Result<Json<RoutingDecision>, StatusCode> {
+    match service.event_router.dry_run(&event).await {
+        Ok(decision) => Ok(Json(decision)),
+        Err(e) => {
+            error!("Failed to evaluate routing for event {}: {}", event.id, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
 /// Replay events handler
 #[derive(Debug, Deserialize)]
 struct ReplayEventsRequest {
@@ -616,6 +756,37 @@ async fn get_replay_status_handler(
     })))
 }
 
+/// Replay dead-lettered events handler
+#[derive(Debug, Deserialize)]
+struct ReplayDeadLetterRequest {
+    from_timestamp: chrono::DateTime<chrono::Utc>,
+    to_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    event_types: Option<Vec<EventType>>,
+    categories: Option<Vec<EventCategory>>,
+}
+
+async fn replay_dead_letter_handler(
+    State(service): State<EventStreamingService>,
+    Json(request): Json<ReplayDeadLetterRequest>,
+) -> std::result::Result<Json<DeadLetterReplay>, StatusCode> {
+    let filter = DeadLetterFilter {
+        event_types: request.event_types,
+        categories: request.categories,
+    };
+
+    match service
+        .processing_pipeline
+        .replay(&filter, request.from_timestamp, request.to_timestamp)
+        .await
+    {
+        Ok(replay) => Ok(Json(replay)),
+        Err(e) => {
+            error!("Failed to replay dead-lettered events: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
 /// Metrics handler
 async fn metrics_handler(
     State(service): State<EventStreamingService>,
@@ -0,0 +1,506 @@
+//! Blog workflow templates
+//!
+//! Reusable, parameterized blog workflows such as how-to posts, product
+//! announcements and case studies. Each template declares a JSON schema for
+//! its parameters, the blog preferences its posts start from, and skeletons
+//! for the topic and generation prompt that parameters are rendered into.
+
+use crate::saas_client_auth::{
+    BlogAutomationPreferences, ImagePreferences, ImageResolution, ImageStyle, SeoPreferences,
+    WordCountRange,
+};
+use jsonschema::{error::ValidationErrorKind, JSONSchema};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Template for a family of blog posts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlogWorkflowTemplate {
+    /// Unique template identifier, e.g. `how-to-post`
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// What the template is for
+    pub description: String,
+    /// JSON schema the template parameters must satisfy
+    pub parameter_schema: Value,
+    /// Blog preferences posts from this template are generated with
+    pub default_preferences: BlogAutomationPreferences,
+    /// Post topic, with `{{parameter}}` placeholders
+    pub topic_skeleton: String,
+    /// Instructions for the content generator, with `{{parameter}}` placeholders
+    pub prompt_skeleton: String,
+}
+
+impl BlogWorkflowTemplate {
+    /// Render a skeleton with `params`, falling back to the defaults declared
+    /// in the parameter schema; unknown placeholders render empty
+    pub fn render(&self, skeleton: &str, params: &Value) -> String {
+        let mut rendered = String::with_capacity(skeleton.len());
+        let mut rest = skeleton;
+
+        while let Some(start) = rest.find("{{") {
+            let Some(end) = rest[start + 2..].find("}}") else {
+                break;
+            };
+            rendered.push_str(&rest[..start]);
+            let name = rest[start + 2..start + 2 + end].trim();
+            if let Some(value) = self.parameter(params, name) {
+                rendered.push_str(&display_value(value));
+            }
+            rest = &rest[start + 2 + end + 2..];
+        }
+
+        rendered.push_str(rest);
+        rendered
+    }
+
+    /// Parameter value from `params`, or the schema default when it is absent
+    pub fn parameter<'a>(&'a self, params: &'a Value, name: &str) -> Option<&'a Value> {
+        params
+            .get(name)
+            .filter(|value| !value.is_null())
+            .or_else(|| {
+                self.parameter_schema
+                    .pointer(&format!("/properties/{}/default", name))
+            })
+    }
+}
+
+/// Parameter that failed its template's schema
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParameterError {
+    /// Dotted path of the offending parameter, e.g. `key_features.0`
+    pub field: String,
+    /// Why the value was rejected
+    pub message: String,
+}
+
+impl fmt::Display for ParameterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.field.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.field, self.message)
+        }
+    }
+}
+
+/// Template lookup and validation errors
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum TemplateError {
+    #[error("Workflow template not found: {0}")]
+    NotFound(String),
+
+    #[error("Invalid parameter schema for template {template_id}: {message}")]
+    InvalidSchema {
+        template_id: String,
+        message: String,
+    },
+
+    #[error("Invalid parameters for template {template_id}: {}", join_errors(.errors))]
+    InvalidParameters {
+        template_id: String,
+        errors: Vec<ParameterError>,
+    },
+}
+
+fn join_errors(errors: &[ParameterError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Template with its compiled parameter schema
+#[derive(Debug, Clone)]
+struct RegisteredTemplate {
+    template: Arc<BlogWorkflowTemplate>,
+    schema: Arc<JSONSchema>,
+}
+
+/// Registry of blog workflow templates available to clients
+#[derive(Debug, Clone)]
+pub struct BlogTemplateRegistry {
+    templates: HashMap<String, RegisteredTemplate>,
+}
+
+impl Default for BlogTemplateRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        for template in builtin_templates() {
+            registry
+                .register(template)
+                .expect("built-in template schemas are valid");
+        }
+        registry
+    }
+}
+
+impl BlogTemplateRegistry {
+    /// Registry without the built-in templates
+    pub fn empty() -> Self {
+        Self {
+            templates: HashMap::new(),
+        }
+    }
+
+    /// Add a template, replacing any template with the same ID
+    pub fn register(&mut self, template: BlogWorkflowTemplate) -> Result<(), TemplateError> {
+        let schema = JSONSchema::compile(&template.parameter_schema).map_err(|e| {
+            TemplateError::InvalidSchema {
+                template_id: template.id.clone(),
+                message: e.to_string(),
+            }
+        })?;
+
+        self.templates.insert(
+            template.id.clone(),
+            RegisteredTemplate {
+                template: Arc::new(template),
+                schema: Arc::new(schema),
+            },
+        );
+        Ok(())
+    }
+
+    /// Look up a template by ID
+    pub fn get(&self, template_id: &str) -> Result<Arc<BlogWorkflowTemplate>, TemplateError> {
+        self.templates
+            .get(template_id)
+            .map(|registered| registered.template.clone())
+            .ok_or_else(|| TemplateError::NotFound(template_id.to_string()))
+    }
+
+    /// All registered templates, ordered by ID
+    pub fn list(&self) -> Vec<Arc<BlogWorkflowTemplate>> {
+        let mut templates: Vec<_> = self
+            .templates
+            .values()
+            .map(|registered| registered.template.clone())
+            .collect();
+        templates.sort_by(|a, b| a.id.cmp(&b.id));
+        templates
+    }
+
+    /// Check `params` against the template's schema, reporting every offending field
+    pub fn validate(
+        &self,
+        template_id: &str,
+        params: &Value,
+    ) -> Result<Arc<BlogWorkflowTemplate>, TemplateError> {
+        let registered = self
+            .templates
+            .get(template_id)
+            .ok_or_else(|| TemplateError::NotFound(template_id.to_string()))?;
+
+        if let Err(validation_errors) = registered.schema.validate(params) {
+            let mut errors = Vec::new();
+            for error in validation_errors {
+                let path = error.instance_path.clone().into_vec().join(".");
+                match &error.kind {
+                    ValidationErrorKind::Required { property } => errors.push(ParameterError {
+                        field: child_path(&path, property.as_str().unwrap_or_default()),
+                        message: "is required".to_string(),
+                    }),
+                    ValidationErrorKind::AdditionalProperties { unexpected } => {
+                        errors.extend(unexpected.iter().map(|name| ParameterError {
+                            field: child_path(&path, name),
+                            message: "is not a parameter of this template".to_string(),
+                        }))
+                    }
+                    _ => errors.push(ParameterError {
+                        field: path,
+                        message: error.to_string(),
+                    }),
+                }
+            }
+
+            return Err(TemplateError::InvalidParameters {
+                template_id: template_id.to_string(),
+                errors,
+            });
+        }
+
+        Ok(registered.template.clone())
+    }
+}
+
+fn child_path(parent: &str, name: &str) -> String {
+    if parent.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", parent, name)
+    }
+}
+
+/// Text a parameter value renders as in a skeleton
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items
+            .iter()
+            .map(display_value)
+            .collect::<Vec<_>>()
+            .join(", "),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn preferences(
+    word_count: WordCountRange,
+    tone: &str,
+    image_style: ImageStyle,
+) -> BlogAutomationPreferences {
+    BlogAutomationPreferences {
+        default_word_count: word_count,
+        default_tone: tone.to_string(),
+        target_audience: None,
+        seo_preferences: SeoPreferences {
+            target_keywords: Vec::new(),
+            meta_description: true,
+            header_structure: true,
+            internal_links: true,
+            image_alt_text: true,
+        },
+        image_preferences: ImagePreferences {
+            style: image_style,
+            aspect_ratio: "16:9".to_string(),
+            resolution: ImageResolution::Medium,
+            brand_consistent: true,
+            custom_prompts: Vec::new(),
+        },
+        validation_rules: Vec::new(),
+    }
+}
+
+/// Parameters every template accepts on top of its own
+fn common_properties() -> Value {
+    json!({
+        "audience": { "type": "string", "minLength": 1 },
+        "tone": { "type": "string", "minLength": 1 },
+        "word_count": { "type": "integer", "minimum": 300, "maximum": 5000 },
+        "keywords": {
+            "type": "array",
+            "items": { "type": "string", "minLength": 1 },
+            "maxItems": 20
+        }
+    })
+}
+
+fn parameter_schema(properties: Value, required: &[&str]) -> Value {
+    let mut all_properties = common_properties();
+    if let (Some(all), Some(own)) = (all_properties.as_object_mut(), properties.as_object()) {
+        all.extend(own.clone());
+    }
+
+    json!({
+        "type": "object",
+        "properties": all_properties,
+        "required": required,
+        "additionalProperties": false
+    })
+}
+
+/// Templates every registry starts with
+pub fn builtin_templates() -> Vec<BlogWorkflowTemplate> {
+    vec![
+        BlogWorkflowTemplate {
+            id: "how-to-post".to_string(),
+            name: "How-to post".to_string(),
+            description: "Step-by-step guide that walks readers through a task".to_string(),
+            parameter_schema: parameter_schema(
+                json!({
+                    "task": { "type": "string", "minLength": 3 },
+                    "skill_level": {
+                        "type": "string",
+                        "enum": ["beginner", "intermediate", "advanced"],
+                        "default": "beginner"
+                    },
+                    "step_count": { "type": "integer", "minimum": 3, "maximum": 15, "default": 5 }
+                }),
+                &["task"],
+            ),
+            default_preferences: preferences(
+                WordCountRange {
+                    min: 800,
+                    max: 2000,
+                    target: 1200,
+                },
+                "instructional",
+                ImageStyle::Illustration,
+            ),
+            topic_skeleton: "How to {{task}}".to_string(),
+            prompt_skeleton:
+                "Write a step-by-step guide on how to {{task}} for {{skill_level}} readers. \
+                List prerequisites first, then walk through {{step_count}} numbered steps, \
+                and close with common mistakes and how to fix them."
+                    .to_string(),
+        },
+        BlogWorkflowTemplate {
+            id: "product-announcement".to_string(),
+            name: "Product announcement".to_string(),
+            description: "Launch post introducing a product or feature".to_string(),
+            parameter_schema: parameter_schema(
+                json!({
+                    "product_name": { "type": "string", "minLength": 1 },
+                    "key_features": {
+                        "type": "array",
+                        "items": { "type": "string", "minLength": 1 },
+                        "minItems": 1,
+                        "maxItems": 10
+                    },
+                    "availability": { "type": "string", "default": "available today" },
+                    "call_to_action": { "type": "string", "default": "Try it today" }
+                }),
+                &["product_name", "key_features"],
+            ),
+            default_preferences: preferences(
+                WordCountRange {
+                    min: 400,
+                    max: 1000,
+                    target: 600,
+                },
+                "enthusiastic",
+                ImageStyle::Corporate,
+            ),
+            topic_skeleton: "Introducing {{product_name}}".to_string(),
+            prompt_skeleton: "Announce {{product_name}}, {{availability}}. \
+                Lead with the problem it solves, then cover these features: {{key_features}}. \
+                End with the call to action \"{{call_to_action}}\"."
+                .to_string(),
+        },
+        BlogWorkflowTemplate {
+            id: "case-study".to_string(),
+            name: "Case study".to_string(),
+            description: "Customer story covering a challenge, solution and results".to_string(),
+            parameter_schema: parameter_schema(
+                json!({
+                    "customer_name": { "type": "string", "minLength": 1 },
+                    "industry": { "type": "string" },
+                    "challenge": { "type": "string", "minLength": 10 },
+                    "solution": { "type": "string", "minLength": 10 },
+                    "results": {
+                        "type": "array",
+                        "items": { "type": "string", "minLength": 1 },
+                        "minItems": 1
+                    }
+                }),
+                &["customer_name", "challenge", "solution", "results"],
+            ),
+            default_preferences: preferences(
+                WordCountRange {
+                    min: 1000,
+                    max: 2500,
+                    target: 1500,
+                },
+                "professional",
+                ImageStyle::Photographic,
+            ),
+            topic_skeleton: "How {{customer_name}} solved {{challenge}}".to_string(),
+            prompt_skeleton: "Write a case study about {{customer_name}} ({{industry}}). \
+                Describe the challenge: {{challenge}}. Explain the solution: {{solution}}. \
+                Quantify the results: {{results}}."
+                .to_string(),
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_templates_are_registered() {
+        let registry = BlogTemplateRegistry::default();
+        let ids: Vec<String> = registry.list().iter().map(|t| t.id.clone()).collect();
+        assert_eq!(ids, ["case-study", "how-to-post", "product-announcement"]);
+
+        assert_eq!(
+            registry.get("press-release").unwrap_err(),
+            TemplateError::NotFound("press-release".to_string())
+        );
+    }
+
+    #[test]
+    fn test_skeletons_render_params_and_schema_defaults() {
+        let registry = BlogTemplateRegistry::default();
+        let params = json!({ "task": "rotate API keys", "step_count": 4 });
+        let template = registry.validate("how-to-post", &params).unwrap();
+
+        assert_eq!(
+            template.render(&template.topic_skeleton, &params),
+            "How to rotate API keys"
+        );
+        let prompt = template.render(&template.prompt_skeleton, &params);
+        assert!(prompt.contains("for beginner readers"));
+        assert!(prompt.contains("walk through 4 numbered steps"));
+
+        let template = registry.get("product-announcement").unwrap();
+        let params = json!({ "product_name": "Relay", "key_features": ["Webhooks", "Retries"] });
+        let prompt = template.render(&template.prompt_skeleton, &params);
+        assert!(prompt.contains("these features: Webhooks, Retries."));
+    }
+
+    #[test]
+    fn test_invalid_params_list_each_offending_field() {
+        let registry = BlogTemplateRegistry::default();
+        let params = json!({
+            "product_name": "",
+            "key_features": ["Webhooks", 42],
+            "word_count": 100,
+            "launch_party": true
+        });
+
+        let Err(TemplateError::InvalidParameters {
+            template_id,
+            mut errors,
+        }) = registry.validate("product-announcement", &params)
+        else {
+            panic!("expected invalid parameters");
+        };
+        assert_eq!(template_id, "product-announcement");
+
+        errors.sort_by(|a, b| a.field.cmp(&b.field));
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            [
+                "key_features.1",
+                "launch_party",
+                "product_name",
+                "word_count"
+            ]
+        );
+
+        let params = json!({ "challenge": "Slow onboarding for new hires" });
+        let Err(TemplateError::InvalidParameters { errors, .. }) =
+            registry.validate("case-study", &params)
+        else {
+            panic!("expected invalid parameters");
+        };
+        let mut missing: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+        missing.sort();
+        assert_eq!(missing, ["customer_name", "results", "solution"]);
+        assert!(errors.iter().all(|e| e.message == "is required"));
+    }
+
+    #[test]
+    fn test_register_rejects_invalid_schema() {
+        let mut registry = BlogTemplateRegistry::empty();
+        let mut template = builtin_templates().remove(0);
+        template.parameter_schema = json!({ "type": "not-a-type" });
+
+        assert!(matches!(
+            registry.register(template),
+            Err(TemplateError::InvalidSchema { .. })
+        ));
+        assert!(registry.list().is_empty());
+    }
+}
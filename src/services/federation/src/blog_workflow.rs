@@ -3,8 +3,12 @@
 //! This module provides the end-to-end workflow execution service for blog post automation,
 //! orchestrating content generation, image creation, quality validation, and final assembly.
 
+use crate::blog_template::{BlogTemplateRegistry, TemplateError};
 use crate::models::{FederationError, WorkflowExecution, WorkflowStatus};
-use crate::saas_client_auth::SaasClientProfile;
+use crate::provider::ProviderManager;
+use crate::saas_client_auth::{BrandProfile, SaasClientProfile};
+use crate::telemetry;
+use ai_core_shared::moderation::{self, ModerationOutcome, ModerationPolicy};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -13,6 +17,10 @@ use thiserror::Error;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
+pub use ai_core_shared::moderation::{
+    ContentModerator, HttpContentModerator, KeywordModerator, ModerationCategory,
+};
+
 /// Blog post workflow service
 pub struct BlogWorkflowService {
     /// MCP orchestrator for service communication
@@ -23,6 +31,12 @@ pub struct BlogWorkflowService {
     image_generator: Arc<dyn ImageGenerator + Send + Sync>,
     /// Quality validator service
     quality_validator: Arc<dyn QualityValidator + Send + Sync>,
+    /// Content moderator run on generated content before publishing
+    content_moderator: Option<Arc<dyn ContentModerator>>,
+    /// Receives validation scores for the provider that generated the content
+    quality_feedback: Option<Arc<dyn ProviderQualityFeedback>>,
+    /// Templates requests can be created from
+    templates: Arc<BlogTemplateRegistry>,
     /// Workflow state manager
     workflow_manager: Arc<RwLock<WorkflowManager>>,
     /// Performance monitor
@@ -38,6 +52,9 @@ impl Clone for BlogWorkflowService {
             content_generator: self.content_generator.clone(),
             image_generator: self.image_generator.clone(),
             quality_validator: self.quality_validator.clone(),
+            content_moderator: self.content_moderator.clone(),
+            quality_feedback: self.quality_feedback.clone(),
+            templates: self.templates.clone(),
             workflow_manager: self.workflow_manager.clone(),
             performance_monitor: self.performance_monitor.clone(),
             config: self.config.clone(),
@@ -153,6 +170,7 @@ pub enum WorkflowEvent {
     WorkflowCompleted,
     WorkflowFailed,
     QualityCheckFailed,
+    ModerationBlocked,
     TimeoutOccurred,
 }
 
@@ -182,6 +200,8 @@ pub struct BlogWorkflowResponse {
     pub metrics: ExecutionMetrics,
     /// Quality scores
     pub quality_scores: QualityScores,
+    /// Moderation outcome for the generated content (if moderated)
+    pub moderation: Option<ModerationOutcome>,
     /// Error information (if failed)
     pub error: Option<WorkflowErrorResult>,
     /// Execution timeline
@@ -201,6 +221,7 @@ pub enum WorkflowExecutionStatus {
     Failed,
     Cancelled,
     TimedOut,
+    ModerationBlocked,
 }
 
 /// Generated blog post
@@ -416,6 +437,7 @@ pub enum ErrorCategory {
     ContentGeneration,
     ImageGeneration,
     QualityValidation,
+    Moderation,
     Timeout,
     RateLimit,
     Authentication,
@@ -589,6 +611,8 @@ pub struct BlogWorkflowConfig {
     pub performance_monitoring_enabled: bool,
     /// Webhook timeout
     pub webhook_timeout_seconds: u32,
+    /// Moderation thresholds applied to generated content
+    pub moderation_policy: ModerationPolicy,
 }
 
 /// Workflow service errors
@@ -624,6 +648,9 @@ pub enum WorkflowServiceError {
     #[error("External service error: {0}")]
     ExternalServiceError(String),
 
+    #[error("Content moderation failed: {0}")]
+    ModerationFailed(String),
+
     #[error("Internal error: {0}")]
     InternalError(String),
 }
@@ -679,6 +706,27 @@ pub trait QualityValidator: Send + Sync {
     ) -> Result<ImageQualityResult, Box<dyn std::error::Error>>;
 }
 
+/// Sink for provider output quality, normalized to 0.0-1.0
+#[async_trait::async_trait]
+pub trait ProviderQualityFeedback: Send + Sync {
+    async fn record_quality_feedback(
+        &self,
+        provider_id: &Uuid,
+        score: f64,
+    ) -> Result<f64, FederationError>;
+}
+
+#[async_trait::async_trait]
+impl ProviderQualityFeedback for ProviderManager {
+    async fn record_quality_feedback(
+        &self,
+        provider_id: &Uuid,
+        score: f64,
+    ) -> Result<f64, FederationError> {
+        ProviderManager::record_quality_feedback(self, provider_id, score).await
+    }
+}
+
 /// Quality validation requirements
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QualityValidationRequirements {
@@ -687,6 +735,9 @@ pub struct QualityValidationRequirements {
     pub plagiarism_check: bool,
     pub fact_check: bool,
     pub readability_check: bool,
+    /// Brand voice to score content against
+    #[serde(default)]
+    pub brand_profile: Option<BrandProfile>,
 }
 
 impl Default for QualityValidationRequirements {
@@ -697,6 +748,7 @@ impl Default for QualityValidationRequirements {
             plagiarism_check: true,
             fact_check: false,
             readability_check: true,
+            brand_profile: None,
         }
     }
 }
@@ -721,6 +773,9 @@ pub struct GeneratedContent {
     pub word_count: u32,
     pub reading_time: u32,
     pub structure_analysis: ContentStructureAnalysis,
+    /// Federation provider that generated the content, if known
+    #[serde(default)]
+    pub provider_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -756,6 +811,14 @@ pub struct QualityValidationResult {
     pub improvement_suggestions: Vec<String>,
 }
 
+impl QualityValidationResult {
+    /// Overall score normalized from the 0-5 validation scale to 0.0-1.0,
+    /// the scale used for provider quality feedback
+    pub fn normalized_score(&self) -> f64 {
+        (self.overall_score as f64 / 5.0).clamp(0.0, 1.0)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QualityIssue {
     pub issue_type: String,
@@ -844,12 +907,89 @@ impl BlogWorkflowService {
             content_generator,
             image_generator,
             quality_validator,
+            content_moderator: None,
+            quality_feedback: None,
+            templates: Arc::new(BlogTemplateRegistry::default()),
             workflow_manager: Arc::new(RwLock::new(WorkflowManager::new())),
             performance_monitor: Arc::new(PerformanceMonitor::new()),
             config,
         }
     }
 
+    /// Moderate generated content with `moderator` before it can be published
+    pub fn with_content_moderator(mut self, moderator: Arc<dyn ContentModerator>) -> Self {
+        self.content_moderator = Some(moderator);
+        self
+    }
+
+    /// Report every content validation score to `feedback` as quality
+    /// feedback for the provider that generated the content
+    pub fn with_quality_feedback(mut self, feedback: Arc<dyn ProviderQualityFeedback>) -> Self {
+        self.quality_feedback = Some(feedback);
+        self
+    }
+
+    /// Create requests from `templates` instead of the built-in templates
+    pub fn with_template_registry(mut self, templates: BlogTemplateRegistry) -> Self {
+        self.templates = Arc::new(templates);
+        self
+    }
+
+    /// Templates requests can be created from
+    pub fn templates(&self) -> &BlogTemplateRegistry {
+        &self.templates
+    }
+
+    /// Build a workflow request for `client` from a template
+    ///
+    /// `params` are validated against the template's parameter schema first;
+    /// the post is generated with the template's blog preferences, and
+    /// parameters the request leaves out fall back to those preferences.
+    pub fn create_from_template(
+        &self,
+        template_id: &str,
+        client: SaasClientProfile,
+        params: &serde_json::Value,
+    ) -> Result<BlogWorkflowRequest, TemplateError> {
+        let template = self.templates.validate(template_id, params)?;
+        let preferences = &template.default_preferences;
+        let param_str = |name: &str| params.get(name).and_then(|v| v.as_str()).map(String::from);
+
+        let parameters = BlogParameters {
+            audience: param_str("audience").or_else(|| preferences.target_audience.clone()),
+            tone: param_str("tone").or_else(|| Some(preferences.default_tone.clone())),
+            word_count: params
+                .get("word_count")
+                .and_then(|v| v.as_u64())
+                .map(|count| count as u32)
+                .or(Some(preferences.default_word_count.target)),
+            keywords: params
+                .get("keywords")
+                .and_then(|v| serde_json::from_value(v.clone()).ok())
+                .unwrap_or_else(|| preferences.seo_preferences.target_keywords.clone()),
+            custom_instructions: Some(template.render(&template.prompt_skeleton, params)),
+            brand_voice_override: None,
+        };
+
+        let mut client = client;
+        client.blog_preferences = preferences.clone();
+
+        Ok(BlogWorkflowRequest {
+            client,
+            topic: template.render(&template.topic_skeleton, params),
+            parameters,
+            execution_options: ExecutionOptions {
+                parallel_processing: self.config.default_parallel_processing,
+                priority: WorkflowPriority::Normal,
+                max_execution_time: self.config.default_timeout_seconds,
+                quality_threshold: self.config.default_quality_threshold,
+                real_time_updates: false,
+                retry_config: Some(self.config.default_retry_config.clone()),
+            },
+            callback_config: None,
+        })
+    }
+
     /// Execute a blog post generation workflow
     pub async fn execute_workflow(
         &self,
@@ -879,9 +1019,14 @@ impl BlogWorkflowService {
         }
 
         // Execute workflow with timeout
+        let correlation_id = telemetry::new_correlation_id();
         let execution_result = tokio::time::timeout(
             std::time::Duration::from_secs(request.execution_options.max_execution_time as u64),
-            self.execute_workflow_internal(workflow_id, &request),
+            telemetry::with_correlation_id(
+                correlation_id,
+                workflow_id,
+                self.execute_workflow_internal(workflow_id, &request),
+            ),
         )
         .await;
 
@@ -959,6 +1104,64 @@ impl BlogWorkflowService {
 
         timeline.content_generation_completed_at = Some(Utc::now());
 
+        // Moderation gate: blocked content halts the workflow before any further spend
+        let moderation = self.moderate_content(&generated_content).await?;
+        if let Some(outcome) = moderation.as_ref().filter(|o| o.is_blocked()) {
+            let reason = outcome
+                .reason
+                .clone()
+                .unwrap_or_else(|| "Content blocked by moderation".to_string());
+
+            self.record_intermediate_result(workflow_id, "moderation", outcome)
+                .await;
+            self.update_workflow_status(workflow_id, WorkflowExecutionStatus::ModerationBlocked)
+                .await;
+
+            timeline.completed_at = Some(Utc::now());
+            timeline.step_timeline.push(StepTimelineEntry {
+                step_name: "content_moderation".to_string(),
+                started_at: timeline
+                    .content_generation_completed_at
+                    .unwrap_or(timeline.started_at),
+                completed_at: timeline.completed_at,
+                status: StepStatus::Failed,
+                duration_ms: None,
+                metrics: Some(HashMap::from([(
+                    "reason".to_string(),
+                    serde_json::Value::String(reason.clone()),
+                )])),
+            });
+
+            return Ok(BlogWorkflowResponse {
+                workflow_id,
+                status: WorkflowExecutionStatus::ModerationBlocked,
+                blog_post: None,
+                metrics,
+                quality_scores: QualityScores::default(),
+                moderation,
+                error: Some(WorkflowErrorResult {
+                    error_code: "MODERATION_BLOCKED".to_string(),
+                    error_message: reason,
+                    error_category: ErrorCategory::Moderation,
+                    failed_step: Some("content_moderation".to_string()),
+                    retry_attempts: 0,
+                    stack_trace: None,
+                    occurred_at: Utc::now(),
+                }),
+                timeline,
+            });
+        }
+
+        if let Some(outcome) = moderation.as_ref().filter(|o| o.is_flagged()) {
+            tracing::warn!(
+                "Workflow {} content flagged by moderation: {}",
+                workflow_id,
+                outcome.reason.as_deref().unwrap_or_default()
+            );
+            self.record_intermediate_result(workflow_id, "moderation", outcome)
+                .await;
+        }
+
         // Step 2: Image Generation (parallel if enabled)
         timeline.image_generation_started_at = Some(Utc::now());
         self.update_workflow_status(workflow_id, WorkflowExecutionStatus::ImageGeneration)
@@ -1023,11 +1226,52 @@ impl BlogWorkflowService {
             blog_post: Some(blog_post),
             metrics,
             quality_scores,
+            moderation,
             error: None,
             timeline,
         })
     }
 
+    /// Run the configured moderator over generated content
+    ///
+    /// Returns `None` when no moderator is configured. Classifier failures fail
+    /// the workflow rather than letting unmoderated content through.
+    async fn moderate_content(
+        &self,
+        content: &GeneratedContent,
+    ) -> Result<Option<ModerationOutcome>, WorkflowServiceError> {
+        let Some(moderator) = &self.content_moderator else {
+            return Ok(None);
+        };
+
+        let text = format!(
+            "{}\n\n{}\n\n{}",
+            content.title, content.meta_description, content.content
+        );
+
+        moderation::moderate(moderator.as_ref(), &self.config.moderation_policy, &text)
+            .await
+            .map(Some)
+            .map_err(|e| {
+                WorkflowServiceError::ModerationFailed(format!("{}: {}", moderator.name(), e))
+            })
+    }
+
+    /// Store a step result on the active workflow
+    async fn record_intermediate_result<T: Serialize>(
+        &self,
+        workflow_id: Uuid,
+        key: &str,
+        value: &T,
+    ) {
+        let mut manager = self.workflow_manager.write().await;
+        if let Some(workflow) = manager.active_workflows.get_mut(&workflow_id) {
+            if let Ok(value) = serde_json::to_value(value) {
+                workflow.intermediate_results.insert(key.to_string(), value);
+            }
+        }
+    }
+
     /// Generate content step
     async fn generate_content(
         &self,
@@ -1110,6 +1354,35 @@ impl BlogWorkflowService {
         self.generate_images_sequential(request, content).await
     }
 
+    /// Run the content validator and report its score as quality feedback
+    /// for the provider that generated the content
+    ///
+    /// Feedback is best effort: failures are logged and never fail the workflow.
+    async fn validate_content(
+        &self,
+        content: &GeneratedContent,
+        requirements: &QualityValidationRequirements,
+    ) -> Result<QualityValidationResult, WorkflowServiceError> {
+        let validation = self
+            .quality_validator
+            .validate_content(&content.content, requirements)
+            .await
+            .map_err(|e| WorkflowServiceError::QualityValidationFailed(e.to_string()))?;
+
+        if let (Some(feedback), Some(provider_id)) = (&self.quality_feedback, content.provider_id) {
+            let score = validation.normalized_score();
+            if let Err(e) = feedback.record_quality_feedback(&provider_id, score).await {
+                tracing::warn!(
+                    "Failed to record quality feedback for provider {}: {}",
+                    provider_id,
+                    e
+                );
+            }
+        }
+
+        Ok(validation)
+    }
+
     /// Validate quality of generated content
     async fn validate_quality(
         &self,
@@ -1118,12 +1391,29 @@ impl BlogWorkflowService {
         images: &[GeneratedImage],
     ) -> Result<QualityScores, WorkflowServiceError> {
         let _quality_requirements = &request.client.blog_preferences.validation_rules;
+        let requirements = QualityValidationRequirements {
+            brand_profile: request.client.brand_profile.clone(),
+            ..QualityValidationRequirements::default()
+        };
 
-        let content_validation = self
-            .quality_validator
-            .validate_content(&content.content, &QualityValidationRequirements::default())
-            .await
-            .map_err(|e| WorkflowServiceError::QualityValidationFailed(e.to_string()))?;
+        let content_validation = self.validate_content(content, &requirements).await?;
+
+        // Hard failures such as banned phrases fail regardless of the score
+        if !content_validation.validation_passed {
+            let reasons: Vec<&str> = content_validation
+                .issues_found
+                .iter()
+                .filter(|issue| issue.severity == "critical")
+                .map(|issue| issue.description.as_str())
+                .collect();
+            return Err(WorkflowServiceError::QualityValidationFailed(
+                if reasons.is_empty() {
+                    "Content failed quality validation".to_string()
+                } else {
+                    reasons.join("; ")
+                },
+            ));
+        }
 
         // Calculate overall quality scores
         let overall_score = content_validation.overall_score;
@@ -1342,6 +1632,7 @@ impl Default for BlogWorkflowConfig {
             },
             performance_monitoring_enabled: true,
             webhook_timeout_seconds: 30,
+            moderation_policy: ModerationPolicy::default(),
         }
     }
 }
@@ -1388,3 +1679,143 @@ impl Default for QualityScores {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::mcp_orchestrator::HttpMcpOrchestrator;
+    use crate::models::{
+        AuthMethod, CostInfo, Provider, ProviderConfig, ProviderStatus, ProviderType,
+        QualityMetrics, RateLimits,
+    };
+    use crate::{MockContentGenerator, MockImageGenerator, MockQualityValidator};
+
+    /// Scores every post 1.0 out of 5
+    struct LowScoreValidator;
+
+    #[async_trait::async_trait]
+    impl QualityValidator for LowScoreValidator {
+        async fn validate_content(
+            &self,
+            content: &str,
+            requirements: &QualityValidationRequirements,
+        ) -> Result<QualityValidationResult, Box<dyn std::error::Error>> {
+            let mut result = MockQualityValidator
+                .validate_content(content, requirements)
+                .await?;
+            result.overall_score = 1.0;
+            Ok(result)
+        }
+
+        async fn validate_image(
+            &self,
+            image_url: &str,
+            requirements: &ImageQualityRequirements,
+        ) -> Result<ImageQualityResult, Box<dyn std::error::Error>> {
+            MockQualityValidator
+                .validate_image(image_url, requirements)
+                .await
+        }
+    }
+
+    fn test_provider() -> Provider {
+        Provider {
+            id: Uuid::new_v4(),
+            name: "Blog LLM".to_string(),
+            provider_type: ProviderType::Llm,
+            config: ProviderConfig {
+                endpoint: "http://example.com".to_string(),
+                auth_method: AuthMethod::None,
+                timeout: 30000,
+                rate_limits: RateLimits {
+                    requests_per_second: None,
+                    requests_per_minute: None,
+                    requests_per_hour: None,
+                    concurrent_requests: None,
+                },
+                headers: HashMap::new(),
+            },
+            cost_info: CostInfo {
+                cost_per_request: 0.5,
+                cost_per_token: None,
+                cost_per_gb: None,
+                cost_per_compute_hour: None,
+                minimum_cost: 0.0,
+                currency: "USD".to_string(),
+            },
+            quality_metrics: QualityMetrics {
+                avg_response_time: 100.0,
+                success_rate: 0.99,
+                availability: 0.99,
+                quality_score: 0.95,
+                last_updated: Utc::now(),
+            },
+            status: ProviderStatus::Active,
+            capabilities: vec!["text-generation".to_string()],
+            health_endpoint: None,
+            region: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn test_content(provider_id: Uuid) -> GeneratedContent {
+        GeneratedContent {
+            title: "Test Post".to_string(),
+            content: "<h1>Test Post</h1><p>Body.</p>".to_string(),
+            meta_description: "Test".to_string(),
+            word_count: 800,
+            reading_time: 3,
+            structure_analysis: ContentStructureAnalysis {
+                section_count: 3,
+                paragraph_count: 8,
+                header_analysis: vec![],
+                readability_metrics: ReadabilityMetrics {
+                    flesch_reading_ease: 65.0,
+                    flesch_kincaid_grade: 8.0,
+                    avg_sentence_length: 15.0,
+                    avg_syllables_per_word: 1.5,
+                },
+            },
+            provider_id: Some(provider_id),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_low_blog_score_lowers_provider_quality() {
+        let db_pool = sqlx::PgPool::connect_lazy("postgres://localhost/federation_test").unwrap();
+        let redis_client = redis::Client::open("redis://localhost").unwrap();
+        let provider_manager = Arc::new(ProviderManager::new(db_pool, redis_client).await.unwrap());
+        let provider = provider_manager
+            .register_provider(test_provider())
+            .await
+            .unwrap();
+
+        let orchestrator = HttpMcpOrchestrator::new(&Config::default().mcp_orchestrator).unwrap();
+        let service = BlogWorkflowService::new(
+            Arc::new(orchestrator),
+            Arc::new(MockContentGenerator),
+            Arc::new(MockImageGenerator),
+            Arc::new(LowScoreValidator),
+            BlogWorkflowConfig::default(),
+        )
+        .with_quality_feedback(provider_manager.clone());
+
+        let validation = service
+            .validate_content(
+                &test_content(provider.id),
+                &QualityValidationRequirements::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(validation.normalized_score(), 0.2);
+
+        let observed = provider_manager.get_observed_quality(&provider.id).unwrap();
+        assert_eq!(observed.sample_count(), 1);
+        assert_eq!(observed.rolling_average(), Some(0.2));
+
+        let adjusted = provider_manager.with_observed_quality(&[Arc::new(provider)]);
+        assert!(adjusted[0].quality_metrics.quality_score < 0.95);
+    }
+}
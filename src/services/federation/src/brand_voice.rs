@@ -0,0 +1,499 @@
+//! Brand voice consistency scoring
+//!
+//! This module scores generated content against a client's `BrandProfile`:
+//! banned phrases, preferred terminology, tone of voice and reading level.
+//! The result is a 0-5 sub-score plus concrete, positioned violations that
+//! quality validators fold into their `QualityValidationResult`.
+
+use crate::blog_workflow::{QualityIssue, QualityValidationResult};
+use crate::saas_client_auth::{BrandProfile, BrandTone};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Key of the brand voice sub-score in `QualityValidationResult::detailed_scores`
+pub const BRAND_VOICE_SCORE_KEY: &str = "brand_voice";
+
+/// Highest score on the validation scale
+const MAX_SCORE: f32 = 5.0;
+/// Penalty per banned phrase occurrence
+const BANNED_PHRASE_PENALTY: f32 = 1.5;
+/// Penalty per discouraged term occurrence
+const NON_PREFERRED_TERM_PENALTY: f32 = 0.5;
+/// Penalty per tone violation
+const TONE_PENALTY: f32 = 0.25;
+/// Cap on the combined tone penalty
+const MAX_TONE_PENALTY: f32 = 1.5;
+/// Grade levels either side of the target that go unpenalised
+const READING_LEVEL_TOLERANCE: f32 = 2.0;
+/// Penalty per grade level beyond the tolerance
+const READING_LEVEL_PENALTY: f32 = 0.5;
+/// Cap on the reading level penalty
+const MAX_READING_LEVEL_PENALTY: f32 = 1.5;
+
+/// Stock phrases that break a conversational tone
+const FORMAL_STOCK_PHRASES: &[&str] = &[
+    "hereby",
+    "heretofore",
+    "aforementioned",
+    "pursuant to",
+    "notwithstanding",
+    "henceforth",
+];
+
+/// Kind of brand voice violation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BrandVoiceViolationKind {
+    /// A banned phrase appears in the content
+    BannedPhrase,
+    /// A discouraged term was used instead of the preferred one
+    NonPreferredTerm,
+    /// Wording that does not match the brand tone
+    Tone,
+    /// Reading level is outside the target range
+    ReadingLevel,
+}
+
+impl BrandVoiceViolationKind {
+    /// Name used in issue types
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::BannedPhrase => "banned_phrase",
+            Self::NonPreferredTerm => "non_preferred_term",
+            Self::Tone => "tone",
+            Self::ReadingLevel => "reading_level",
+        }
+    }
+
+    fn severity(self) -> &'static str {
+        match self {
+            Self::BannedPhrase => "critical",
+            Self::NonPreferredTerm | Self::ReadingLevel => "major",
+            Self::Tone => "minor",
+        }
+    }
+}
+
+/// A single brand voice violation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrandVoiceViolation {
+    /// Violation kind
+    pub kind: BrandVoiceViolationKind,
+    /// Human-readable description
+    pub description: String,
+    /// Character offset into the content, when the violation has one
+    pub position: Option<usize>,
+    /// Suggested fix
+    pub suggested_fix: Option<String>,
+}
+
+/// Brand voice score and violations for a piece of content
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrandVoiceAssessment {
+    /// Brand alignment sub-score (0.0-5.0)
+    pub score: f32,
+    /// Flesch-Kincaid grade level of the content
+    pub reading_level: f32,
+    /// Violations, in content order within each check
+    pub violations: Vec<BrandVoiceViolation>,
+}
+
+impl BrandVoiceAssessment {
+    /// Score `content` against `profile`
+    pub fn evaluate(content: &str, profile: &BrandProfile) -> Self {
+        let mut violations = Vec::new();
+
+        for phrase in profile
+            .banned_phrases
+            .iter()
+            .filter(|p| !p.trim().is_empty())
+        {
+            for position in find_phrase(content, phrase) {
+                violations.push(BrandVoiceViolation {
+                    kind: BrandVoiceViolationKind::BannedPhrase,
+                    description: format!(
+                        "used banned phrase '{}' at position {}",
+                        phrase, position
+                    ),
+                    position: Some(position),
+                    suggested_fix: Some(format!("Remove '{}'", phrase)),
+                });
+            }
+        }
+
+        let mut discouraged: Vec<_> = profile.preferred_terms.iter().collect();
+        discouraged.sort();
+        for (term, preferred) in discouraged
+            .into_iter()
+            .filter(|(t, _)| !t.trim().is_empty())
+        {
+            for position in find_phrase(content, term) {
+                violations.push(BrandVoiceViolation {
+                    kind: BrandVoiceViolationKind::NonPreferredTerm,
+                    description: format!(
+                        "used '{}' instead of preferred term '{}' at position {}",
+                        term, preferred, position
+                    ),
+                    position: Some(position),
+                    suggested_fix: Some(format!("Replace '{}' with '{}'", term, preferred)),
+                });
+            }
+        }
+
+        if let Some(tone) = profile.tone {
+            violations.extend(tone_violations(content, tone));
+        }
+
+        let reading_level = flesch_kincaid_grade(&strip_markup(content));
+        if let Some(target) = profile.target_reading_level {
+            let deviation = (reading_level - target).abs();
+            if deviation > READING_LEVEL_TOLERANCE {
+                violations.push(BrandVoiceViolation {
+                    kind: BrandVoiceViolationKind::ReadingLevel,
+                    description: format!(
+                        "reading level {:.1} is {:.1} grades from target {:.1}",
+                        reading_level, deviation, target
+                    ),
+                    position: None,
+                    suggested_fix: Some(if reading_level > target {
+                        "Use shorter sentences and simpler words".to_string()
+                    } else {
+                        "Use more precise, domain-appropriate language".to_string()
+                    }),
+                });
+            }
+        }
+
+        let score = score_violations(&violations, reading_level, profile.target_reading_level);
+        Self {
+            score,
+            reading_level,
+            violations,
+        }
+    }
+
+    /// Whether any banned phrase appears in the content
+    pub fn has_banned_phrase(&self) -> bool {
+        self.violations
+            .iter()
+            .any(|v| v.kind == BrandVoiceViolationKind::BannedPhrase)
+    }
+
+    /// Record the sub-score and violations on a validation result. Banned
+    /// phrases fail validation regardless of the overall score.
+    pub fn apply_to(&self, result: &mut QualityValidationResult) {
+        result
+            .detailed_scores
+            .insert(BRAND_VOICE_SCORE_KEY.to_string(), self.score);
+
+        for violation in &self.violations {
+            result.issues_found.push(QualityIssue {
+                issue_type: format!("brand_voice.{}", violation.kind.as_str()),
+                severity: violation.kind.severity().to_string(),
+                description: violation.description.clone(),
+                location: violation.position.map(|p| format!("position {}", p)),
+                suggested_fix: violation.suggested_fix.clone(),
+            });
+        }
+
+        if self.has_banned_phrase() {
+            result.validation_passed = false;
+            result
+                .improvement_suggestions
+                .push("Remove banned phrases before publishing".to_string());
+        }
+    }
+}
+
+fn score_violations(
+    violations: &[BrandVoiceViolation],
+    reading_level: f32,
+    target_reading_level: Option<f32>,
+) -> f32 {
+    let mut tone_penalty = 0.0;
+    let mut penalty = 0.0;
+
+    for violation in violations {
+        match violation.kind {
+            BrandVoiceViolationKind::BannedPhrase => penalty += BANNED_PHRASE_PENALTY,
+            BrandVoiceViolationKind::NonPreferredTerm => penalty += NON_PREFERRED_TERM_PENALTY,
+            BrandVoiceViolationKind::Tone => tone_penalty += TONE_PENALTY,
+            BrandVoiceViolationKind::ReadingLevel => {}
+        }
+    }
+    penalty += f32::min(tone_penalty, MAX_TONE_PENALTY);
+
+    if let Some(target) = target_reading_level {
+        let excess = (reading_level - target).abs() - READING_LEVEL_TOLERANCE;
+        if excess > 0.0 {
+            penalty += f32::min(excess * READING_LEVEL_PENALTY, MAX_READING_LEVEL_PENALTY);
+        }
+    }
+
+    (MAX_SCORE - penalty).clamp(0.0, MAX_SCORE)
+}
+
+/// Character offsets of case-insensitive, whole-word matches of `phrase`
+fn find_phrase(content: &str, phrase: &str) -> Vec<usize> {
+    let pattern = format!(r"(?i)\b{}\b", regex::escape(phrase.trim()));
+    let Ok(regex) = Regex::new(&pattern) else {
+        return Vec::new();
+    };
+
+    regex
+        .find_iter(content)
+        .map(|m| content[..m.start()].chars().count())
+        .collect()
+}
+
+fn tone_violations(content: &str, tone: BrandTone) -> Vec<BrandVoiceViolation> {
+    let mut violations = Vec::new();
+
+    match tone {
+        BrandTone::Formal => {
+            let contraction =
+                Regex::new(r"(?i)\b\w+(?:n't|'re|'ll|'ve|'m|'d)\b").expect("valid pattern");
+            for m in contraction.find_iter(content) {
+                let position = content[..m.start()].chars().count();
+                violations.push(BrandVoiceViolation {
+                    kind: BrandVoiceViolationKind::Tone,
+                    description: format!(
+                        "contraction '{}' at position {} breaks formal tone",
+                        m.as_str(),
+                        position
+                    ),
+                    position: Some(position),
+                    suggested_fix: Some("Write the words out in full".to_string()),
+                });
+            }
+            for (position, ch) in content.chars().enumerate() {
+                if ch == '!' {
+                    violations.push(BrandVoiceViolation {
+                        kind: BrandVoiceViolationKind::Tone,
+                        description: format!(
+                            "exclamation mark at position {} breaks formal tone",
+                            position
+                        ),
+                        position: Some(position),
+                        suggested_fix: Some("End the sentence with a full stop".to_string()),
+                    });
+                }
+            }
+        }
+        BrandTone::Conversational => {
+            for phrase in FORMAL_STOCK_PHRASES {
+                for position in find_phrase(content, phrase) {
+                    violations.push(BrandVoiceViolation {
+                        kind: BrandVoiceViolationKind::Tone,
+                        description: format!(
+                            "'{}' at position {} breaks conversational tone",
+                            phrase, position
+                        ),
+                        position: Some(position),
+                        suggested_fix: Some("Use plain language".to_string()),
+                    });
+                }
+            }
+        }
+    }
+
+    violations
+}
+
+/// Drop HTML tags so they do not count as words
+fn strip_markup(content: &str) -> String {
+    let mut text = String::with_capacity(content.len());
+    let mut in_tag = false;
+
+    for ch in content.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(ch),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+/// Flesch-Kincaid grade level; 0.0 for content without words
+fn flesch_kincaid_grade(text: &str) -> f32 {
+    let words: Vec<&str> = text
+        .split_whitespace()
+        .filter(|w| w.chars().any(char::is_alphabetic))
+        .collect();
+    if words.is_empty() {
+        return 0.0;
+    }
+
+    let sentences = text
+        .split(['.', '!', '?'])
+        .filter(|s| s.chars().any(char::is_alphabetic))
+        .count()
+        .max(1);
+    let syllables: usize = words.iter().map(|w| count_syllables(w)).sum();
+
+    let words_per_sentence = words.len() as f32 / sentences as f32;
+    let syllables_per_word = syllables as f32 / words.len() as f32;
+    (0.39 * words_per_sentence + 11.8 * syllables_per_word - 15.59).max(0.0)
+}
+
+/// Vowel-group syllable estimate with a silent trailing 'e'
+fn count_syllables(word: &str) -> usize {
+    let letters: Vec<char> = word
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    let is_vowel = |c: char| matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y');
+
+    let mut count = 0;
+    let mut previous_vowel = false;
+    for &c in &letters {
+        let vowel = is_vowel(c);
+        if vowel && !previous_vowel {
+            count += 1;
+        }
+        previous_vowel = vowel;
+    }
+
+    let silent_e = letters.len() > 2
+        && letters.last() == Some(&'e')
+        && !is_vowel(letters[letters.len() - 2])
+        && !letters.ends_with(&['l', 'e']);
+    if silent_e && count > 1 {
+        count -= 1;
+    }
+
+    count.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn profile() -> BrandProfile {
+        BrandProfile {
+            brand_name: "Acme".to_string(),
+            brand_voice: "Clear and confident".to_string(),
+            brand_values: vec![],
+            color_palette: vec![],
+            visual_assets: vec![],
+            guidelines_url: None,
+            content_templates: vec![],
+            tone: None,
+            banned_phrases: vec![],
+            preferred_terms: HashMap::new(),
+            target_reading_level: None,
+        }
+    }
+
+    fn empty_result() -> QualityValidationResult {
+        QualityValidationResult {
+            overall_score: 4.8,
+            detailed_scores: HashMap::new(),
+            validation_passed: true,
+            issues_found: vec![],
+            improvement_suggestions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_clean_content_scores_full_marks() {
+        let assessment =
+            BrandVoiceAssessment::evaluate("Our team ships reliable tools.", &profile());
+
+        assert_eq!(assessment.score, MAX_SCORE);
+        assert!(assessment.violations.is_empty());
+    }
+
+    #[test]
+    fn test_banned_phrase_fails_validation_with_position() {
+        let mut profile = profile();
+        profile.banned_phrases = vec!["synergy".to_string()];
+        let content = "We value teamwork. Real Synergy matters.";
+
+        let assessment = BrandVoiceAssessment::evaluate(content, &profile);
+        assert!(assessment.has_banned_phrase());
+        assert_eq!(assessment.violations[0].position, Some(24));
+        assert_eq!(
+            assessment.violations[0].description,
+            "used banned phrase 'synergy' at position 24"
+        );
+
+        let mut result = empty_result();
+        assessment.apply_to(&mut result);
+        assert!(!result.validation_passed);
+        assert_eq!(result.detailed_scores[BRAND_VOICE_SCORE_KEY], 3.5);
+        assert_eq!(
+            result.issues_found[0].issue_type,
+            "brand_voice.banned_phrase"
+        );
+        assert_eq!(result.issues_found[0].severity, "critical");
+    }
+
+    #[test]
+    fn test_banned_phrase_matches_whole_words_only() {
+        let mut profile = profile();
+        profile.banned_phrases = vec!["leverage".to_string()];
+
+        let assessment = BrandVoiceAssessment::evaluate("Leveraged buyouts are risky.", &profile);
+        assert!(!assessment.has_banned_phrase());
+    }
+
+    #[test]
+    fn test_preferred_terms_and_formal_tone() {
+        let mut profile = profile();
+        profile.tone = Some(BrandTone::Formal);
+        profile
+            .preferred_terms
+            .insert("utilize".to_string(), "use".to_string());
+
+        let assessment = BrandVoiceAssessment::evaluate("Don't utilize the old API!", &profile);
+        let kinds: Vec<_> = assessment.violations.iter().map(|v| v.kind).collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                BrandVoiceViolationKind::NonPreferredTerm,
+                BrandVoiceViolationKind::Tone,
+                BrandVoiceViolationKind::Tone
+            ]
+        );
+        assert_eq!(assessment.score, MAX_SCORE - 0.5 - 0.5);
+
+        let mut result = empty_result();
+        assessment.apply_to(&mut result);
+        assert!(result.validation_passed);
+        assert_eq!(result.issues_found.len(), 3);
+    }
+
+    #[test]
+    fn test_reading_level_outside_target() {
+        let mut profile = profile();
+        profile.target_reading_level = Some(14.0);
+
+        let assessment =
+            BrandVoiceAssessment::evaluate("<p>The cat sat. The dog ran.</p>", &profile);
+
+        assert!(assessment.reading_level < 2.0);
+        assert_eq!(
+            assessment.violations[0].kind,
+            BrandVoiceViolationKind::ReadingLevel
+        );
+        assert!(assessment.score < MAX_SCORE);
+    }
+
+    #[test]
+    fn test_count_syllables() {
+        assert_eq!(count_syllables("cat"), 1);
+        assert_eq!(count_syllables("make"), 1);
+        assert_eq!(count_syllables("table"), 2);
+        assert_eq!(count_syllables("banana"), 3);
+    }
+}
@@ -8,6 +8,7 @@ use crate::models::{
     Client, ClientConfig, ClientCredentials, ClientRegistrationRequest, ClientRegistrationResponse,
     ClientStatus, ClientTier, FederationError, ResourceLimits,
 };
+use crate::telemetry;
 use crate::utils::{cache::CacheManager, database::DatabaseManager};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
@@ -118,10 +119,16 @@ impl ClientManager {
     }
 
     /// Register a new client
+    #[tracing::instrument(
+        name = "client_manager.register_client",
+        skip_all,
+        fields(correlation_id = tracing::field::Empty)
+    )]
     pub async fn register_client(
         &self,
         request: ClientRegistrationRequest,
     ) -> Result<ClientRegistrationResponse, FederationError> {
+        telemetry::record_correlation_id();
         info!("Registering new client: {}", request.name);
 
         // Validate the registration request
@@ -187,7 +194,13 @@ impl ClientManager {
     }
 
     /// Get client by ID
+    #[tracing::instrument(
+        name = "client_manager.get_client",
+        skip(self),
+        fields(correlation_id = tracing::field::Empty)
+    )]
     pub async fn get_client(&self, client_id: &Uuid) -> Result<Option<Client>, FederationError> {
+        telemetry::record_correlation_id();
         // Try in-memory registry first
         if let Some(client) = self.client_registry.get_client_by_id(client_id).await {
             self.record_activity(client_id).await;
@@ -355,7 +368,13 @@ impl ClientManager {
     }
 
     /// Authenticate client by API key
+    #[tracing::instrument(
+        name = "client_manager.authenticate_client",
+        skip_all,
+        fields(correlation_id = tracing::field::Empty)
+    )]
     pub async fn authenticate_client(&self, api_key: &str) -> Result<Client, FederationError> {
+        telemetry::record_correlation_id();
         let client = self.get_client_by_api_key(api_key).await?.ok_or_else(|| {
             FederationError::AuthenticationFailed {
                 reason: "Invalid API key".to_string(),
@@ -808,6 +827,7 @@ mod tests {
                 monthly_budget_limit: None,
                 prefer_cheaper_providers: false,
                 quality_cost_ratio: 0.5,
+                budget_caps: None,
             },
             schema_preferences: SchemaPreferences {
                 preferred_version: "1.0".to_string(),
@@ -24,6 +24,9 @@ pub struct Config {
     pub temporal: TemporalConfig,
     /// Proxy configuration
     pub proxy: ProxyConfig,
+    /// MCP orchestrator used by blog workflows
+    #[serde(default)]
+    pub mcp_orchestrator: McpOrchestratorConfig,
     /// Authentication configuration
     pub auth: AuthConfig,
     /// Cost optimization configuration
@@ -190,6 +193,64 @@ pub struct ProxyConfig {
     pub retry: RetryConfig,
     /// Circuit breaker configuration
     pub circuit_breaker: CircuitBreakerConfig,
+    /// Connection limits for each proxied MCP server
+    #[serde(default)]
+    pub target_pool: TargetPoolConfig,
+}
+
+/// Per-target connection pool configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TargetPoolConfig {
+    /// Maximum concurrent connections to a single MCP server
+    pub max_connections_per_target: u32,
+    /// Seconds a target's connections may sit unused before they are evicted
+    pub idle_timeout: u64,
+    /// What to do with requests to a target whose pool is saturated
+    pub saturation_policy: SaturationPolicy,
+    /// Seconds a queued request waits for a free connection before failing
+    pub queue_timeout: u64,
+    /// Seconds between health checks of pooled targets
+    pub health_check_interval: u64,
+    /// Seconds a target evicted for failing a health check waits before it
+    /// is checked again; doubles with each further failed check
+    #[serde(default = "default_eviction_backoff")]
+    pub eviction_backoff: u64,
+    /// Upper bound on the eviction backoff, in seconds
+    #[serde(default = "default_max_eviction_backoff")]
+    pub max_eviction_backoff: u64,
+}
+
+fn default_eviction_backoff() -> u64 {
+    30
+}
+
+fn default_max_eviction_backoff() -> u64 {
+    600
+}
+
+impl Default for TargetPoolConfig {
+    fn default() -> Self {
+        Self {
+            max_connections_per_target: 10,
+            idle_timeout: 90,
+            saturation_policy: SaturationPolicy::Queue,
+            queue_timeout: 5,
+            health_check_interval: 30,
+            eviction_backoff: default_eviction_backoff(),
+            max_eviction_backoff: default_max_eviction_backoff(),
+        }
+    }
+}
+
+/// Handling of requests to a saturated target pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SaturationPolicy {
+    /// Wait up to `queue_timeout` for a connection to free up
+    Queue,
+    /// Fail immediately
+    Reject,
 }
 
 /// Keep-alive configuration
@@ -236,6 +297,37 @@ pub struct CircuitBreakerConfig {
     pub half_open_max_calls: u32,
 }
 
+/// MCP orchestrator client configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpOrchestratorConfig {
+    /// Base URL of the mcp-orchestrator service
+    pub url: String,
+    /// Per-request timeout in seconds
+    pub request_timeout: u64,
+    /// How long the available MCP service list is cached, in seconds
+    pub services_cache_ttl: u64,
+    /// Retry configuration for failed calls
+    pub retry: RetryConfig,
+}
+
+impl Default for McpOrchestratorConfig {
+    fn default() -> Self {
+        Self {
+            url: "http://localhost:8807".to_string(),
+            request_timeout: 30,
+            services_cache_ttl: 30,
+            retry: RetryConfig {
+                max_attempts: 3,
+                base_delay: 250,
+                max_delay: 5000,
+                backoff_multiplier: 2.0,
+                enable_jitter: true,
+            },
+        }
+    }
+}
+
 /// Authentication configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -362,6 +454,9 @@ pub struct CostOptimizationConfig {
     pub budget_alerts: BudgetAlertsConfig,
     /// Provider scoring weights
     pub scoring_weights: ScoringWeights,
+    /// Similarity-keyed cache of routing decisions
+    #[serde(default)]
+    pub routing_cache: RoutingCacheConfig,
 }
 
 /// Optimization strategy
@@ -448,6 +543,31 @@ pub struct ScoringWeights {
     pub reliability: f64,
 }
 
+/// Routing decision cache configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutingCacheConfig {
+    /// Enable the routing cache
+    pub enabled: bool,
+    /// Minimum cosine similarity (0.0 - 1.0) for a cached decision to be reused
+    pub similarity_threshold: f64,
+    /// Cached decision lifetime in seconds
+    pub ttl_seconds: u64,
+    /// Maximum cached decisions per client and service type
+    pub max_entries_per_client: usize,
+}
+
+impl Default for RoutingCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            similarity_threshold: 0.95,
+            ttl_seconds: 300,
+            max_entries_per_client: 256,
+        }
+    }
+}
+
 /// Telemetry configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -690,7 +810,9 @@ impl Default for Config {
                     timeout: 60,
                     half_open_max_calls: 3,
                 },
+                target_pool: TargetPoolConfig::default(),
             },
+            mcp_orchestrator: McpOrchestratorConfig::default(),
             auth: AuthConfig {
                 jwt: JwtConfig {
                     secret: "your-jwt-secret-key".to_string(),
@@ -745,6 +867,7 @@ impl Default for Config {
                     performance: 0.2,
                     reliability: 0.2,
                 },
+                routing_cache: RoutingCacheConfig::default(),
             },
             telemetry: TelemetryConfig {
                 logging: LoggingConfig {
@@ -844,6 +967,10 @@ impl Config {
             config.temporal.server_url = temporal_url;
         }
 
+        if let Ok(orchestrator_url) = std::env::var("MCP_ORCHESTRATOR_URL") {
+            config.mcp_orchestrator.url = orchestrator_url;
+        }
+
         if let Ok(jwt_secret) = std::env::var("JWT_SECRET") {
             config.auth.jwt.secret = jwt_secret;
         }
@@ -909,6 +1036,17 @@ impl Config {
             return Err(anyhow::anyhow!("Redis URL is required"));
         }
 
+        // Validate MCP orchestrator configuration
+        if self.mcp_orchestrator.url.is_empty() {
+            return Err(anyhow::anyhow!("MCP orchestrator URL is required"));
+        }
+
+        if self.mcp_orchestrator.request_timeout == 0 {
+            return Err(anyhow::anyhow!(
+                "MCP orchestrator request timeout must be greater than 0"
+            ));
+        }
+
         // Validate JWT configuration
         if self.auth.jwt.secret.len() < 16 {
             return Err(anyhow::anyhow!(
@@ -929,6 +1067,14 @@ impl Config {
             ));
         }
 
+        let similarity_threshold = self.cost_optimization.routing_cache.similarity_threshold;
+        if !(0.0..=1.0).contains(&similarity_threshold) {
+            return Err(anyhow::anyhow!(
+                "Routing cache similarity threshold must be between 0.0 and 1.0, got {}",
+                similarity_threshold
+            ));
+        }
+
         Ok(())
     }
 
@@ -5,15 +5,22 @@
 //! minimize costs while maintaining quality requirements through advanced algorithms.
 
 use crate::client::ClientManager;
+use crate::config::RoutingCacheConfig;
 use crate::models::{
-    CostConstraints, FederationError, Provider, ProviderSelectionRequest, QualityRequirements,
+    BalancedWeights, BudgetCaps, CostConstraints, FederationError, Provider, ProviderAlternative,
+    ProviderScoreBreakdown, ProviderSelectionRequest, ProviderSelectionResponse,
+    QualityRequirements,
 };
-use crate::provider::ProviderManager;
+use crate::provider::{apply_residency_constraint, ProviderManager};
+use crate::routing_cache::{RoutingCache, RoutingCacheLookup};
+use crate::workflow::BudgetLedger;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
+use redis::Client as RedisClient;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
@@ -34,6 +41,8 @@ pub struct CostOptimizer {
     budget_manager: Arc<BudgetManager>,
     /// Optimization history
     optimization_history: Arc<DashMap<Uuid, Vec<OptimizationRecord>>>,
+    /// Cache of recent routing decisions keyed by request similarity
+    routing_cache: Arc<RoutingCache>,
 }
 
 /// Cost tracking system
@@ -58,8 +67,102 @@ pub struct BudgetManager {
     alert_tracker: Arc<DashMap<Uuid, Vec<BudgetAlert>>>,
     /// Budget enforcement policies
     enforcement_policies: Arc<DashMap<Uuid, BudgetPolicy>>,
+    /// Redis client holding per-client spend for the billing window
+    redis_client: Arc<RedisClient>,
+    /// Checks that crossed a soft cap
+    soft_cap_exceeded: AtomicU64,
+    /// Selections rejected at a hard cap
+    hard_cap_rejections: AtomicU64,
 }
 
+/// Outcome of checking an estimated cost against a client's budget caps
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "decision", rename_all = "snake_case")]
+pub enum BudgetDecision {
+    /// Spend stays within the soft cap
+    Allow,
+    /// Spend crosses the soft cap; the request proceeds with a warning
+    SoftCapExceeded { projected_spend: f64, soft_cap: f64 },
+    /// Spend crosses the hard cap; the request is rejected
+    HardCapExceeded { projected_spend: f64, hard_cap: f64 },
+}
+
+impl BudgetDecision {
+    /// Decide whether `estimated_cost` may be added to `current_spend`
+    pub fn evaluate(caps: &BudgetCaps, current_spend: f64, estimated_cost: f64) -> Self {
+        let projected_spend = current_spend + estimated_cost;
+
+        if projected_spend > caps.hard_cap {
+            Self::HardCapExceeded {
+                projected_spend,
+                hard_cap: caps.hard_cap,
+            }
+        } else if projected_spend > caps.soft_cap {
+            Self::SoftCapExceeded {
+                projected_spend,
+                soft_cap: caps.soft_cap,
+            }
+        } else {
+            Self::Allow
+        }
+    }
+}
+
+/// Result of atomically reserving spend against the hard cap
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Reservation {
+    /// The amount was added; `spend` includes it
+    Reserved { spend: f64 },
+    /// The amount would cross the hard cap; `spend` is unchanged
+    Rejected { spend: f64 },
+}
+
+/// Redis key prefix for per-client billing window spend
+const BUDGET_KEY_PREFIX: &str = "federation:budget";
+
+/// Add to a client's window spend, starting a new window first if the
+/// current one has elapsed. Spend never drops below zero, so releasing a
+/// reservation made in an earlier window cannot free budget in the new one.
+/// Returns the spend after the increment.
+///
+/// KEYS[1] = budget hash, ARGV = amount, now (unix seconds), window seconds
+const RECORD_SPEND_SCRIPT: &str = r#"
+local start = tonumber(redis.call('HGET', KEYS[1], 'window_start'))
+local now = tonumber(ARGV[2])
+local window = tonumber(ARGV[3])
+if (not start) or (now - start >= window) then
+    redis.call('HSET', KEYS[1], 'window_start', now, 'spend', 0)
+end
+local spend = redis.call('HINCRBYFLOAT', KEYS[1], 'spend', ARGV[1])
+if tonumber(spend) < 0 then
+    redis.call('HSET', KEYS[1], 'spend', 0)
+    spend = '0'
+end
+redis.call('EXPIRE', KEYS[1], window * 2)
+return spend
+"#;
+
+/// Add to a client's window spend only if the result stays within the hard
+/// cap, so concurrent reservations cannot overshoot it. Returns
+/// `{1, new spend}` when reserved and `{0, current spend}` when rejected.
+///
+/// KEYS[1] = budget hash, ARGV = amount, now (unix seconds), window seconds,
+/// hard cap
+const RESERVE_SPEND_SCRIPT: &str = r#"
+local start = tonumber(redis.call('HGET', KEYS[1], 'window_start'))
+local now = tonumber(ARGV[2])
+local window = tonumber(ARGV[3])
+if (not start) or (now - start >= window) then
+    redis.call('HSET', KEYS[1], 'window_start', now, 'spend', 0)
+end
+redis.call('EXPIRE', KEYS[1], window * 2)
+local spend = tonumber(redis.call('HGET', KEYS[1], 'spend')) or 0
+if spend + tonumber(ARGV[1]) > tonumber(ARGV[4]) then
+    return {0, tostring(spend)}
+end
+return {1, redis.call('HINCRBYFLOAT', KEYS[1], 'spend', ARGV[1])}
+"#;
+
 /// Optimization strategy trait
 pub trait OptimizationStrategy: std::fmt::Debug {
     /// Select the most cost-effective provider
@@ -78,6 +181,60 @@ pub trait OptimizationStrategy: std::fmt::Debug {
     fn description(&self) -> &str;
 }
 
+/// Observed inputs to balanced provider scoring
+#[derive(Debug, Clone)]
+pub struct ProviderSignals {
+    /// Candidate provider
+    pub provider: Arc<Provider>,
+    /// Recent average cost per request
+    pub recent_cost: f64,
+    /// 95th percentile latency (milliseconds)
+    pub p95_latency_ms: f64,
+    /// Quality score (0.0 - 1.0)
+    pub quality: f64,
+}
+
+impl ProviderSignals {
+    /// Whether the candidate satisfies the request's cost and quality limits
+    pub fn meets(
+        &self,
+        cost_constraints: Option<&CostConstraints>,
+        quality_requirements: Option<&QualityRequirements>,
+    ) -> bool {
+        if let Some(max) = cost_constraints.and_then(|cost| cost.max_cost_per_request) {
+            if self.recent_cost > max {
+                return false;
+            }
+        }
+
+        let Some(quality) = quality_requirements else {
+            return true;
+        };
+        let metrics = &self.provider.quality_metrics;
+        if let Some(min) = quality.min_success_rate {
+            if metrics.success_rate < min {
+                return false;
+            }
+        }
+        if let Some(max) = quality.max_response_time {
+            if self.p95_latency_ms > max {
+                return false;
+            }
+        }
+        if let Some(min) = quality.min_availability {
+            if metrics.availability < min {
+                return false;
+            }
+        }
+        if let Some(min) = quality.min_quality_score {
+            if self.quality < min {
+                return false;
+            }
+        }
+        true
+    }
+}
+
 /// Daily cost tracking data
 #[derive(Debug, Clone, Default)]
 pub struct DailyCostData {
@@ -281,14 +438,39 @@ pub struct OptimizationRecord {
     pub effectiveness_score: Option<f64>,
 }
 
+/// Outcome of a provider selection run
+#[derive(Debug, Clone)]
+struct Selection {
+    /// Chosen provider
+    provider: Arc<Provider>,
+    /// Score components, for balanced requests
+    score_breakdown: Option<ProviderScoreBreakdown>,
+    /// Candidates that were not chosen, best scoring or cheapest first
+    alternatives: Vec<ProviderAlternative>,
+    /// Strategy that made the choice
+    strategy: String,
+    /// Request embedding to cache the decision under, after a cache miss
+    embedding: Option<Vec<f32>>,
+}
+
+impl Selection {
+    /// Cost charged against the client's budget for this selection
+    fn estimated_cost(&self) -> f64 {
+        self.score_breakdown
+            .as_ref()
+            .map_or(self.provider.cost_info.cost_per_request, |b| b.recent_cost)
+    }
+}
+
 impl CostOptimizer {
     /// Create a new cost optimizer
     pub async fn new(
         provider_manager: Arc<ProviderManager>,
         client_manager: Arc<ClientManager>,
+        redis_client: RedisClient,
     ) -> Result<Self, FederationError> {
         let cost_tracker = Arc::new(CostTracker::new().await?);
-        let budget_manager = Arc::new(BudgetManager::new().await?);
+        let budget_manager = Arc::new(BudgetManager::new(Arc::new(redis_client)).await?);
         let strategies = Arc::new(DashMap::new());
 
         // Initialize default optimization strategies
@@ -312,20 +494,135 @@ impl CostOptimizer {
             cost_tracker,
             budget_manager,
             optimization_history: Arc::new(DashMap::new()),
+            routing_cache: Arc::new(RoutingCache::new(RoutingCacheConfig::default())),
         })
     }
 
+    /// Replace the routing cache with one using `config`
+    pub fn with_routing_cache(mut self, config: RoutingCacheConfig) -> Self {
+        self.routing_cache = Arc::new(RoutingCache::new(config));
+        self
+    }
+
     /// Optimize provider selection for cost
     pub async fn optimize_provider_selection(
         &self,
         request: &ProviderSelectionRequest,
         providers: &[Arc<Provider>],
     ) -> Result<Option<Arc<Provider>>, FederationError> {
+        Ok(self
+            .run_selection(request, providers)
+            .await?
+            .map(|selection| selection.provider))
+    }
+
+    /// Select a provider and describe the selection
+    ///
+    /// Requests with `balanced_weights` include the winning provider's score
+    /// breakdown in the response. Dry runs return the same description but
+    /// reserve no budget and leave no record of the selection.
+    pub async fn select_provider_with_scores(
+        &self,
+        request: &ProviderSelectionRequest,
+        providers: &[Arc<Provider>],
+    ) -> Result<Option<ProviderSelectionResponse>, FederationError> {
+        let Some(Selection {
+            provider,
+            score_breakdown,
+            alternatives,
+            ..
+        }) = self.run_selection(request, providers).await?
+        else {
+            return Ok(None);
+        };
+
+        let (reasoning, estimated_cost) = match &score_breakdown {
+            Some(breakdown) => (
+                format!(
+                    "Selected {} with balanced score {:.3}: cost {:.3} x {:.2}, latency {:.3} x {:.2}, quality {:.3} x {:.2}",
+                    provider.name,
+                    breakdown.composite_score,
+                    breakdown.cost_score,
+                    breakdown.weights.cost_weight,
+                    breakdown.latency_score,
+                    breakdown.weights.latency_weight,
+                    breakdown.quality_score,
+                    breakdown.weights.quality_weight
+                ),
+                breakdown.recent_cost,
+            ),
+            None => (
+                format!(
+                    "Selected {} based on optimal cost-quality balance",
+                    provider.name
+                ),
+                provider.cost_info.cost_per_request,
+            ),
+        };
+
+        Ok(Some(ProviderSelectionResponse {
+            provider: (*provider).clone(),
+            reasoning,
+            estimated_cost,
+            expected_quality: provider.quality_metrics.clone(),
+            score_breakdown,
+            alternatives,
+            dry_run: request.dry_run,
+        }))
+    }
+
+    /// Run provider selection, returning the chosen provider, its score
+    /// breakdown for balanced requests, and the candidates passed over
+    ///
+    /// The selection's estimated cost is checked against the client's budget
+    /// caps and, when allowed, added to the billing window spend. Dry runs
+    /// are held to the hard cap too, but reserve nothing and only log the
+    /// decision.
+    async fn run_selection(
+        &self,
+        request: &ProviderSelectionRequest,
+        providers: &[Arc<Provider>],
+    ) -> Result<Option<Selection>, FederationError> {
+        let Some(selection) = self.choose_provider(request, providers).await? else {
+            return Ok(None);
+        };
+
+        settle_selection(self, &self.optimization_history, request, &selection).await?;
+
+        if request.dry_run {
+            info!(
+                "Simulated provider selection for client {}: {} at {:.4} estimated cost, {} alternatives",
+                request.client_id,
+                selection.provider.name,
+                selection.estimated_cost(),
+                selection.alternatives.len()
+            );
+        } else if let Some(embedding) = &selection.embedding {
+            self.routing_cache.insert(
+                request,
+                embedding.clone(),
+                selection.provider.id,
+                &selection.strategy,
+            );
+        }
+
+        Ok(Some(selection))
+    }
+
+    async fn choose_provider(
+        &self,
+        request: &ProviderSelectionRequest,
+        providers: &[Arc<Provider>],
+    ) -> Result<Option<Selection>, FederationError> {
         debug!(
             "Optimizing provider selection for client: {}",
             request.client_id
         );
 
+        if let Some(weights) = &request.balanced_weights {
+            weights.validate()?;
+        }
+
         // Get client budget information
         let client_budget = self
             .budget_manager
@@ -343,6 +640,72 @@ impl CostOptimizer {
             });
         }
 
+        // Residency is a hard constraint: non-compliant providers are never scored
+        let providers = apply_residency_constraint(providers.to_vec(), request.residency.as_ref())?;
+
+        // Weighted scores depend on live latency, so balanced requests are not cached
+        if let Some(weights) = &request.balanced_weights {
+            let providers = self.provider_manager.with_observed_quality(&providers);
+            // Limits are hard constraints; weights only rank the providers that meet them
+            let signals: Vec<ProviderSignals> = self
+                .provider_signals(&providers)
+                .into_iter()
+                .filter(|candidate| {
+                    candidate.meets(
+                        request.cost_constraints.as_ref(),
+                        request.quality_requirements.as_ref(),
+                    )
+                })
+                .collect();
+
+            let mut scored = score_balanced(weights, &signals);
+            scored.sort_by(|(_, a), (_, b)| {
+                b.composite_score
+                    .partial_cmp(&a.composite_score)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+            if scored.is_empty() {
+                return Ok(None);
+            }
+
+            let (provider, breakdown) = scored.remove(0);
+
+            let alternatives = scored
+                .into_iter()
+                .map(|(alternative, breakdown)| ProviderAlternative {
+                    estimated_cost: breakdown.recent_cost,
+                    composite_score: Some(breakdown.composite_score),
+                    ..ProviderAlternative::from_provider(&alternative)
+                })
+                .collect();
+
+            return Ok(Some(Selection {
+                provider,
+                score_breakdown: Some(breakdown),
+                alternatives,
+                strategy: "balanced_optimizer".to_string(),
+                embedding: None,
+            }));
+        }
+
+        // Reuse the decision for a near-identical request if its provider still qualifies
+        let embedding = match self.routing_cache.lookup(request, &providers) {
+            RoutingCacheLookup::Hit {
+                provider, strategy, ..
+            } => {
+                let alternatives = unscored_alternatives(&provider, &providers);
+                return Ok(Some(Selection {
+                    provider,
+                    score_breakdown: None,
+                    alternatives,
+                    strategy,
+                    embedding: None,
+                }));
+            }
+            RoutingCacheLookup::Miss { embedding } => Some(embedding),
+            RoutingCacheLookup::Bypassed => None,
+        };
+
         // Select optimization strategy based on client preferences and constraints
         let strategy_name = self
             .select_optimization_strategy(request, &client_budget)
@@ -354,21 +717,190 @@ impl CostOptimizer {
                     message: format!("Optimization strategy not found: {}", strategy_name),
                 })?;
 
+        // Score against observed output quality rather than the configured value
+        let providers = self.provider_manager.with_observed_quality(&providers);
+
         // Apply optimization
         let selected_provider = strategy.optimize_selection(
-            providers,
+            &providers,
             request,
             request.cost_constraints.as_ref(),
             request.quality_requirements.as_ref(),
         )?;
 
-        // Record optimization for learning
-        if let Some(ref provider) = selected_provider {
-            self.record_optimization(request, provider, &strategy_name)
-                .await?;
+        Ok(selected_provider.map(|provider| {
+            let alternatives = unscored_alternatives(&provider, &providers);
+            Selection {
+                provider,
+                score_breakdown: None,
+                alternatives,
+                strategy: strategy_name,
+                embedding,
+            }
+        }))
+    }
+
+    /// Check an estimated cost against the client's budget caps
+    ///
+    /// Caps come from the client's cost optimization config, falling back to
+    /// the defaults for its tier. Spend is accumulated in Redis over the
+    /// billing window.
+    pub async fn check_budget(
+        &self,
+        client_id: &Uuid,
+        estimated_cost: f64,
+    ) -> Result<BudgetDecision, FederationError> {
+        let caps = self.budget_caps(client_id).await?;
+        let current_spend = self
+            .budget_manager
+            .current_spend(client_id, caps.window_days)
+            .await?;
+        let decision = BudgetDecision::evaluate(&caps, current_spend, estimated_cost);
+        self.note_budget_decision(client_id, &decision);
+
+        Ok(decision)
+    }
+
+    /// The client's configured budget caps, or its tier's defaults
+    async fn budget_caps(&self, client_id: &Uuid) -> Result<BudgetCaps, FederationError> {
+        let client = self
+            .client_manager
+            .get_client(client_id)
+            .await?
+            .ok_or(FederationError::ClientNotFound { id: *client_id })?;
+
+        let caps = client
+            .config
+            .cost_optimization
+            .budget_caps
+            .clone()
+            .unwrap_or_else(|| BudgetCaps::for_tier(&client.tier));
+        caps.validate()?;
+        Ok(caps)
+    }
+
+    /// Log and count soft and hard cap crossings
+    fn note_budget_decision(&self, client_id: &Uuid, decision: &BudgetDecision) {
+        match decision {
+            BudgetDecision::Allow => {}
+            BudgetDecision::SoftCapExceeded {
+                projected_spend,
+                soft_cap,
+            } => {
+                warn!(
+                    "Client {} is over its soft budget cap: projected spend {:.2} exceeds {:.2}",
+                    client_id, projected_spend, soft_cap
+                );
+                self.budget_manager
+                    .soft_cap_exceeded
+                    .fetch_add(1, Ordering::Relaxed);
+                metrics::counter!("federation_budget_soft_cap_exceeded_total").increment(1);
+            }
+            BudgetDecision::HardCapExceeded {
+                projected_spend,
+                hard_cap,
+            } => {
+                warn!(
+                    "Client {} hit its hard budget cap: projected spend {:.2} exceeds {:.2}",
+                    client_id, projected_spend, hard_cap
+                );
+                self.budget_manager
+                    .hard_cap_rejections
+                    .fetch_add(1, Ordering::Relaxed);
+                metrics::counter!("federation_budget_hard_cap_exceeded_total").increment(1);
+            }
         }
+    }
 
-        Ok(selected_provider)
+    /// Fail with `BudgetExceeded` if `estimated_cost` would cross the client's
+    /// hard cap
+    pub async fn enforce_budget(
+        &self,
+        client_id: &Uuid,
+        estimated_cost: f64,
+    ) -> Result<(), FederationError> {
+        match self.check_budget(client_id, estimated_cost).await? {
+            BudgetDecision::HardCapExceeded {
+                projected_spend,
+                hard_cap,
+            } => Err(FederationError::BudgetExceeded {
+                client_id: *client_id,
+                projected_spend,
+                hard_cap,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Add `estimated_cost` to the client's billing window spend, failing with
+    /// `BudgetExceeded` instead if it would cross the hard cap. The check and
+    /// the increment are one atomic Redis operation.
+    pub async fn reserve_budget(
+        &self,
+        client_id: &Uuid,
+        estimated_cost: f64,
+    ) -> Result<(), FederationError> {
+        let caps = self.budget_caps(client_id).await?;
+        let decision = match self
+            .budget_manager
+            .reserve(client_id, estimated_cost, &caps)
+            .await?
+        {
+            Reservation::Reserved { spend } => {
+                BudgetDecision::evaluate(&caps, spend - estimated_cost, estimated_cost)
+            }
+            Reservation::Rejected { spend } => BudgetDecision::HardCapExceeded {
+                projected_spend: spend + estimated_cost,
+                hard_cap: caps.hard_cap,
+            },
+        };
+        self.note_budget_decision(client_id, &decision);
+
+        match decision {
+            BudgetDecision::HardCapExceeded {
+                projected_spend,
+                hard_cap,
+            } => Err(FederationError::BudgetExceeded {
+                client_id: *client_id,
+                projected_spend,
+                hard_cap,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Return a reservation, or the unspent part of one, to the client's
+    /// billing window
+    pub async fn release_budget(
+        &self,
+        client_id: &Uuid,
+        amount: f64,
+    ) -> Result<(), FederationError> {
+        if amount > 0.0 {
+            self.record_spend(client_id, -amount).await?;
+        }
+        Ok(())
+    }
+
+    /// Add spend to the client's current billing window, returning the new total
+    pub async fn record_spend(
+        &self,
+        client_id: &Uuid,
+        amount: f64,
+    ) -> Result<f64, FederationError> {
+        let window_days = self.budget_window_days(client_id).await?;
+        self.budget_manager
+            .record_spend(client_id, amount, window_days)
+            .await
+    }
+
+    /// Start a new billing window for the client with zero spend. Call at the
+    /// start of each billing cycle; windows also roll over on their own once
+    /// `window_days` have elapsed.
+    pub async fn rollover_budget_window(&self, client_id: &Uuid) -> Result<(), FederationError> {
+        let window_days = self.budget_window_days(client_id).await?;
+        info!("Rolling over budget window for client {}", client_id);
+        self.budget_manager.rollover(client_id, window_days).await
     }
 
     /// Start optimization loop for continuous improvement
@@ -410,12 +942,49 @@ impl CostOptimizer {
             "cost_savings_achieved": stats.cost_savings,
             "optimization_strategies": self.strategies.len(),
             "active_client_budgets": self.budget_manager.client_budgets.len(),
-            "optimization_records": self.optimization_history.len()
+            "optimization_records": self.optimization_history.len(),
+            "routing_cache": self.routing_cache.stats(),
+            "budget_soft_cap_exceeded": self.budget_manager.soft_cap_exceeded.load(Ordering::Relaxed),
+            "budget_hard_cap_rejections": self.budget_manager.hard_cap_rejections.load(Ordering::Relaxed)
         }))
     }
 
     // Private helper methods
 
+    async fn budget_window_days(&self, client_id: &Uuid) -> Result<u32, FederationError> {
+        Ok(self.budget_caps(client_id).await?.window_days)
+    }
+
+    /// Gather recent cost, p95 latency and quality for each candidate.
+    /// Falls back to the configured cost and average response time for
+    /// providers without tracked costs or health samples.
+    fn provider_signals(&self, providers: &[Arc<Provider>]) -> Vec<ProviderSignals> {
+        providers
+            .iter()
+            .map(|provider| {
+                let recent_cost = self
+                    .cost_tracker
+                    .provider_costs
+                    .get(&provider.id)
+                    .filter(|costs| costs.total_requests > 0)
+                    .map(|costs| costs.avg_cost_per_request)
+                    .unwrap_or(provider.cost_info.cost_per_request);
+
+                let p95_latency_ms = self
+                    .provider_manager
+                    .p95_latency_ms(&provider.id)
+                    .unwrap_or(provider.quality_metrics.avg_response_time);
+
+                ProviderSignals {
+                    provider: provider.clone(),
+                    recent_cost,
+                    p95_latency_ms,
+                    quality: provider.quality_metrics.quality_score,
+                }
+            })
+            .collect()
+    }
+
     async fn check_budget_compliance(
         &self,
         client_id: &Uuid,
@@ -453,32 +1022,40 @@ impl CostOptimizer {
             Ok("balanced_optimizer".to_string())
         }
     }
+}
 
-    async fn record_optimization(
-        &self,
-        request: &ProviderSelectionRequest,
-        provider: &Arc<Provider>,
-        strategy: &str,
-    ) -> Result<(), FederationError> {
-        let record = OptimizationRecord {
+/// Settle a selection's budget, then record it for learning
+///
+/// Dry runs are checked against the hard cap like any other selection, but
+/// reserve no spend and leave no optimization record.
+async fn settle_selection(
+    ledger: &dyn BudgetLedger,
+    history: &DashMap<Uuid, Vec<OptimizationRecord>>,
+    request: &ProviderSelectionRequest,
+    selection: &Selection,
+) -> Result<(), FederationError> {
+    let estimated_cost = selection.estimated_cost();
+    if request.dry_run {
+        return ledger.check(&request.client_id, estimated_cost).await;
+    }
+
+    ledger.reserve(&request.client_id, estimated_cost).await?;
+    history
+        .entry(request.client_id)
+        .or_default()
+        .push(OptimizationRecord {
             timestamp: Utc::now(),
             client_id: request.client_id,
-            request: (*request).clone(),
-            selected_provider: provider.id,
-            strategy: strategy.to_string(),
-            predicted_cost: provider.cost_info.cost_per_request,
+            request: request.clone(),
+            selected_provider: selection.provider.id,
+            strategy: selection.strategy.clone(),
+            predicted_cost: selection.provider.cost_info.cost_per_request,
             actual_cost: None,
             quality_achieved: None,
             effectiveness_score: None,
-        };
-
-        self.optimization_history
-            .entry(request.client_id)
-            .or_insert_with(Vec::new)
-            .push(record);
+        });
 
-        Ok(())
-    }
+    Ok(())
 }
 
 impl CostTracker {
@@ -497,14 +1074,135 @@ impl CostTracker {
 }
 
 impl BudgetManager {
-    async fn new() -> Result<Self, FederationError> {
+    async fn new(redis_client: Arc<RedisClient>) -> Result<Self, FederationError> {
         Ok(Self {
             client_budgets: Arc::new(DashMap::new()),
             alert_tracker: Arc::new(DashMap::new()),
             enforcement_policies: Arc::new(DashMap::new()),
+            redis_client,
+            soft_cap_exceeded: AtomicU64::new(0),
+            hard_cap_rejections: AtomicU64::new(0),
+        })
+    }
+
+    fn budget_key(client_id: &Uuid) -> String {
+        format!("{}:{}", BUDGET_KEY_PREFIX, client_id)
+    }
+
+    fn window_secs(window_days: u32) -> i64 {
+        i64::from(window_days.max(1)) * 24 * 60 * 60
+    }
+
+    async fn connection(&self) -> Result<redis::aio::Connection, FederationError> {
+        self.redis_client
+            .get_async_connection()
+            .await
+            .map_err(|e| FederationError::CacheError {
+                message: format!("Failed to connect to Redis: {}", e),
+            })
+    }
+
+    /// Spend in the client's current window; zero once the window has elapsed
+    async fn current_spend(
+        &self,
+        client_id: &Uuid,
+        window_days: u32,
+    ) -> Result<f64, FederationError> {
+        let mut conn = self.connection().await?;
+        let (window_start, spend): (Option<i64>, Option<f64>) = redis::cmd("HMGET")
+            .arg(Self::budget_key(client_id))
+            .arg("window_start")
+            .arg("spend")
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| FederationError::CacheError {
+                message: format!("Failed to read budget spend: {}", e),
+            })?;
+
+        let window_open = window_start.map_or(false, |start| {
+            Utc::now().timestamp() - start < Self::window_secs(window_days)
+        });
+
+        Ok(if window_open {
+            spend.unwrap_or(0.0)
+        } else {
+            0.0
+        })
+    }
+
+    async fn record_spend(
+        &self,
+        client_id: &Uuid,
+        amount: f64,
+        window_days: u32,
+    ) -> Result<f64, FederationError> {
+        let mut conn = self.connection().await?;
+        let spend: f64 = redis::Script::new(RECORD_SPEND_SCRIPT)
+            .key(Self::budget_key(client_id))
+            .arg(amount)
+            .arg(Utc::now().timestamp())
+            .arg(Self::window_secs(window_days))
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| FederationError::CacheError {
+                message: format!("Failed to record budget spend: {}", e),
+            })?;
+
+        Ok(spend)
+    }
+
+    /// Atomically add `amount` to the window spend unless it would cross the
+    /// hard cap
+    async fn reserve(
+        &self,
+        client_id: &Uuid,
+        amount: f64,
+        caps: &BudgetCaps,
+    ) -> Result<Reservation, FederationError> {
+        let mut conn = self.connection().await?;
+        let (reserved, spend): (i64, f64) = redis::Script::new(RESERVE_SPEND_SCRIPT)
+            .key(Self::budget_key(client_id))
+            .arg(amount)
+            .arg(Utc::now().timestamp())
+            .arg(Self::window_secs(caps.window_days))
+            .arg(caps.hard_cap)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| FederationError::CacheError {
+                message: format!("Failed to reserve budget spend: {}", e),
+            })?;
+
+        Ok(if reserved == 1 {
+            Reservation::Reserved { spend }
+        } else {
+            Reservation::Rejected { spend }
         })
     }
 
+    async fn rollover(&self, client_id: &Uuid, window_days: u32) -> Result<(), FederationError> {
+        let key = Self::budget_key(client_id);
+        let mut conn = self.connection().await?;
+
+        redis::pipe()
+            .atomic()
+            .cmd("HSET")
+            .arg(&key)
+            .arg("window_start")
+            .arg(Utc::now().timestamp())
+            .arg("spend")
+            .arg(0)
+            .ignore()
+            .cmd("EXPIRE")
+            .arg(&key)
+            .arg(Self::window_secs(window_days) * 2)
+            .ignore()
+            .query_async::<_, ()>(&mut conn)
+            .await
+            .map_err(|e| FederationError::CacheError {
+                message: format!("Failed to roll over budget window: {}", e),
+            })
+    }
+
     async fn get_client_budget(
         &self,
         client_id: &Uuid,
@@ -513,6 +1211,81 @@ impl BudgetManager {
     }
 }
 
+/// Score candidates by the weighted composite of their normalized signals
+///
+/// Cost and latency are min-max normalized across the candidates so the
+/// cheapest and fastest score 1.0 and the most expensive and slowest score
+/// 0.0; when every candidate is equal they all score 1.0. Quality is clamped
+/// to 0.0-1.0.
+pub fn score_balanced(
+    weights: &BalancedWeights,
+    candidates: &[ProviderSignals],
+) -> Vec<(Arc<Provider>, ProviderScoreBreakdown)> {
+    let (min_cost, max_cost) = value_range(candidates.iter().map(|c| c.recent_cost));
+    let (min_latency, max_latency) = value_range(candidates.iter().map(|c| c.p95_latency_ms));
+
+    candidates
+        .iter()
+        .map(|candidate| {
+            let cost_score = inverted_score(candidate.recent_cost, min_cost, max_cost);
+            let latency_score = inverted_score(candidate.p95_latency_ms, min_latency, max_latency);
+            let quality_score = candidate.quality.clamp(0.0, 1.0);
+            let composite_score = cost_score * weights.cost_weight
+                + latency_score * weights.latency_weight
+                + quality_score * weights.quality_weight;
+
+            (
+                candidate.provider.clone(),
+                ProviderScoreBreakdown {
+                    provider_id: candidate.provider.id,
+                    recent_cost: candidate.recent_cost,
+                    p95_latency_ms: candidate.p95_latency_ms,
+                    quality: candidate.quality,
+                    cost_score,
+                    latency_score,
+                    quality_score,
+                    weights: *weights,
+                    composite_score,
+                },
+            )
+        })
+        .collect()
+}
+
+/// Every candidate other than `selected`, cheapest first
+fn unscored_alternatives(
+    selected: &Provider,
+    providers: &[Arc<Provider>],
+) -> Vec<ProviderAlternative> {
+    let mut alternatives: Vec<ProviderAlternative> = providers
+        .iter()
+        .filter(|provider| provider.id != selected.id)
+        .map(|provider| ProviderAlternative::from_provider(provider))
+        .collect();
+    alternatives.sort_by(|a, b| {
+        a.estimated_cost
+            .partial_cmp(&b.estimated_cost)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    alternatives
+}
+
+fn value_range(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+        (min.min(v), max.max(v))
+    })
+}
+
+/// Map `value` to 0.0-1.0 where `min` scores 1.0 and `max` scores 0.0
+fn inverted_score(value: f64, min: f64, max: f64) -> f64 {
+    let spread = max - min;
+    if !spread.is_finite() || spread <= f64::EPSILON {
+        1.0
+    } else {
+        ((max - value) / spread).clamp(0.0, 1.0)
+    }
+}
+
 // Optimization strategy implementations
 
 #[derive(Debug)]
@@ -643,6 +1416,388 @@ impl OptimizationStrategy for QualityPreserverStrategy {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::{
+        AuthMethod, CostInfo, ProviderConfig, ProviderStatus, ProviderType, QualityMetrics,
+        RateLimits,
+    };
+
+    fn signals(name: &str, recent_cost: f64, p95_latency_ms: f64, quality: f64) -> ProviderSignals {
+        let provider = Arc::new(Provider {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            provider_type: ProviderType::Llm,
+            config: ProviderConfig {
+                endpoint: "http://example.com".to_string(),
+                auth_method: AuthMethod::None,
+                timeout: 30000,
+                rate_limits: RateLimits {
+                    requests_per_second: None,
+                    requests_per_minute: None,
+                    requests_per_hour: None,
+                    concurrent_requests: None,
+                },
+                headers: HashMap::new(),
+            },
+            cost_info: CostInfo {
+                cost_per_request: recent_cost,
+                cost_per_token: None,
+                cost_per_gb: None,
+                cost_per_compute_hour: None,
+                minimum_cost: 0.0,
+                currency: "USD".to_string(),
+            },
+            quality_metrics: QualityMetrics {
+                avg_response_time: p95_latency_ms,
+                success_rate: 0.99,
+                availability: 0.99,
+                quality_score: quality,
+                last_updated: Utc::now(),
+            },
+            status: ProviderStatus::Active,
+            capabilities: vec!["chat".to_string()],
+            health_endpoint: None,
+            region: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        });
+
+        ProviderSignals {
+            provider,
+            recent_cost,
+            p95_latency_ms,
+            quality,
+        }
+    }
+
+    fn best(weights: &BalancedWeights, candidates: &[ProviderSignals]) -> String {
+        score_balanced(weights, candidates)
+            .into_iter()
+            .max_by(|(_, a), (_, b)| a.composite_score.partial_cmp(&b.composite_score).unwrap())
+            .map(|(provider, _)| provider.name.clone())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_budget_decision_thresholds() {
+        let caps = BudgetCaps {
+            soft_cap: 80.0,
+            hard_cap: 100.0,
+            window_days: 30,
+        };
+
+        assert_eq!(
+            BudgetDecision::evaluate(&caps, 50.0, 10.0),
+            BudgetDecision::Allow
+        );
+        assert_eq!(
+            BudgetDecision::evaluate(&caps, 75.0, 5.0),
+            BudgetDecision::Allow
+        );
+        assert_eq!(
+            BudgetDecision::evaluate(&caps, 75.0, 10.0),
+            BudgetDecision::SoftCapExceeded {
+                projected_spend: 85.0,
+                soft_cap: 80.0
+            }
+        );
+        assert_eq!(
+            BudgetDecision::evaluate(&caps, 95.0, 10.0),
+            BudgetDecision::HardCapExceeded {
+                projected_spend: 105.0,
+                hard_cap: 100.0
+            }
+        );
+    }
+
+    #[test]
+    fn test_budget_caps_tier_defaults() {
+        use crate::models::ClientTier;
+
+        let free = BudgetCaps::for_tier(&ClientTier::Free);
+        let pro = BudgetCaps::for_tier(&ClientTier::Professional);
+        let enterprise = BudgetCaps::for_tier(&ClientTier::Enterprise);
+
+        for caps in [&free, &pro, &enterprise] {
+            assert!(caps.validate().is_ok());
+            assert!(caps.soft_cap < caps.hard_cap);
+        }
+        assert!(free.hard_cap < pro.hard_cap);
+        assert!(pro.hard_cap < enterprise.hard_cap);
+
+        let inverted = BudgetCaps {
+            soft_cap: 20.0,
+            hard_cap: 10.0,
+            window_days: 30,
+        };
+        assert!(matches!(
+            inverted.validate(),
+            Err(FederationError::ConfigurationError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_balanced_weights_must_sum_to_one() {
+        let valid = BalancedWeights {
+            cost_weight: 0.2,
+            latency_weight: 0.5,
+            quality_weight: 0.3,
+        };
+        assert!(valid.validate().is_ok());
+
+        let short = BalancedWeights {
+            quality_weight: 0.2,
+            ..valid
+        };
+        assert!(matches!(
+            short.validate(),
+            Err(FederationError::ConfigurationError { .. })
+        ));
+
+        let negative = BalancedWeights {
+            cost_weight: -0.5,
+            latency_weight: 1.0,
+            quality_weight: 0.5,
+        };
+        assert!(matches!(
+            negative.validate(),
+            Err(FederationError::ConfigurationError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_balanced_scoring_weighs_latency() {
+        let candidates = vec![
+            signals("cheap-slow", 0.01, 2400.0, 0.9),
+            signals("pricey-fast", 0.05, 300.0, 0.9),
+        ];
+
+        let cost_heavy = BalancedWeights {
+            cost_weight: 0.8,
+            latency_weight: 0.1,
+            quality_weight: 0.1,
+        };
+        assert_eq!(best(&cost_heavy, &candidates), "cheap-slow");
+
+        let latency_heavy = BalancedWeights {
+            cost_weight: 0.1,
+            latency_weight: 0.8,
+            quality_weight: 0.1,
+        };
+        assert_eq!(best(&latency_heavy, &candidates), "pricey-fast");
+    }
+
+    #[test]
+    fn test_balanced_candidates_must_meet_limits() {
+        let weights = BalancedWeights {
+            cost_weight: 0.1,
+            latency_weight: 0.8,
+            quality_weight: 0.1,
+        };
+        let candidates = [
+            signals("cheap-slow", 0.01, 2400.0, 0.9),
+            signals("pricey-fast", 0.05, 300.0, 0.9),
+            signals("cheap-sloppy", 0.01, 300.0, 0.4),
+        ];
+        let cost = CostConstraints {
+            max_cost_per_request: Some(0.02),
+            max_total_cost: None,
+            prefer_cheaper: false,
+        };
+        let quality = QualityRequirements {
+            min_success_rate: None,
+            max_response_time: None,
+            min_availability: None,
+            min_quality_score: Some(0.8),
+        };
+
+        let eligible: Vec<ProviderSignals> = candidates
+            .iter()
+            .filter(|candidate| candidate.meets(Some(&cost), Some(&quality)))
+            .cloned()
+            .collect();
+        assert_eq!(best(&weights, &eligible), "cheap-slow");
+
+        let fast = QualityRequirements {
+            max_response_time: Some(1000.0),
+            ..quality
+        };
+        assert!(candidates
+            .iter()
+            .all(|candidate| !candidate.meets(Some(&cost), Some(&fast))));
+        assert!(candidates
+            .iter()
+            .all(|candidate| candidate.meets(None, None)));
+    }
+
+    #[test]
+    fn test_balanced_score_breakdown() {
+        let weights = BalancedWeights {
+            cost_weight: 0.5,
+            latency_weight: 0.25,
+            quality_weight: 0.25,
+        };
+        let candidates = vec![
+            signals("a", 0.02, 100.0, 0.8),
+            signals("b", 0.04, 500.0, 0.6),
+            signals("c", 0.03, 300.0, 1.4),
+        ];
+
+        let scored = score_balanced(&weights, &candidates);
+        let breakdown = |name: &str| {
+            scored
+                .iter()
+                .find(|(provider, _)| provider.name == name)
+                .map(|(_, breakdown)| breakdown.clone())
+                .unwrap()
+        };
+
+        let a = breakdown("a");
+        assert_eq!(a.cost_score, 1.0);
+        assert_eq!(a.latency_score, 1.0);
+        assert!((a.composite_score - (0.5 + 0.25 + 0.8 * 0.25)).abs() < 1e-9);
+
+        let b = breakdown("b");
+        assert_eq!(b.cost_score, 0.0);
+        assert_eq!(b.latency_score, 0.0);
+
+        let c = breakdown("c");
+        assert!((c.cost_score - 0.5).abs() < 1e-9);
+        assert!((c.latency_score - 0.5).abs() < 1e-9);
+        assert_eq!(c.quality_score, 1.0);
+        assert_eq!(c.weights, weights);
+
+        // Identical candidates all score fully on cost and latency
+        let tied = score_balanced(&weights, &[signals("solo", 0.02, 100.0, 0.5)]);
+        assert_eq!(tied[0].1.cost_score, 1.0);
+        assert_eq!(tied[0].1.latency_score, 1.0);
+    }
+
+    #[test]
+    fn test_unscored_alternatives_exclude_selected() {
+        let providers: Vec<Arc<Provider>> = [("mid", 0.03), ("chosen", 0.01), ("cheap", 0.02)]
+            .iter()
+            .map(|(name, cost)| signals(name, *cost, 100.0, 0.9).provider)
+            .collect();
+
+        let alternatives = unscored_alternatives(&providers[1], &providers);
+        let names: Vec<&str> = alternatives.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["cheap", "mid"]);
+        assert_eq!(alternatives[0].estimated_cost, 0.02);
+        assert!(alternatives.iter().all(|a| a.composite_score.is_none()));
+    }
+
+    /// Ledger holding spend in memory against a fixed hard cap
+    #[derive(Debug)]
+    struct CappedLedger {
+        spend: std::sync::Mutex<f64>,
+        hard_cap: f64,
+    }
+
+    impl CappedLedger {
+        fn new(spend: f64, hard_cap: f64) -> Self {
+            Self {
+                spend: std::sync::Mutex::new(spend),
+                hard_cap,
+            }
+        }
+
+        fn spend(&self) -> f64 {
+            *self.spend.lock().unwrap()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl BudgetLedger for CappedLedger {
+        async fn reserve(&self, client_id: &Uuid, amount: f64) -> Result<(), FederationError> {
+            self.check(client_id, amount).await?;
+            *self.spend.lock().unwrap() += amount;
+            Ok(())
+        }
+
+        async fn check(&self, client_id: &Uuid, amount: f64) -> Result<(), FederationError> {
+            let projected_spend = self.spend() + amount;
+            if projected_spend > self.hard_cap {
+                return Err(FederationError::BudgetExceeded {
+                    client_id: *client_id,
+                    projected_spend,
+                    hard_cap: self.hard_cap,
+                });
+            }
+            Ok(())
+        }
+
+        async fn release(&self, _client_id: &Uuid, amount: f64) -> Result<(), FederationError> {
+            *self.spend.lock().unwrap() -= amount;
+            Ok(())
+        }
+    }
+
+    fn selection_request(dry_run: bool) -> ProviderSelectionRequest {
+        ProviderSelectionRequest {
+            client_id: Uuid::new_v4(),
+            service_type: ProviderType::Llm,
+            required_capabilities: vec!["chat".to_string()],
+            cost_constraints: None,
+            quality_requirements: None,
+            residency: None,
+            bypass_routing_cache: false,
+            balanced_weights: None,
+            dry_run,
+        }
+    }
+
+    fn selection(cost: f64) -> Selection {
+        Selection {
+            provider: signals("chosen", cost, 100.0, 0.9).provider,
+            score_breakdown: None,
+            alternatives: Vec::new(),
+            strategy: "cost_minimizer".to_string(),
+            embedding: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_is_capped_but_spends_and_records_nothing() {
+        let ledger = CappedLedger::new(90.0, 100.0);
+        let history = DashMap::new();
+
+        let request = selection_request(true);
+        settle_selection(&ledger, &history, &request, &selection(5.0))
+            .await
+            .unwrap();
+        assert_eq!(ledger.spend(), 90.0);
+        assert!(history.is_empty());
+
+        let result = settle_selection(&ledger, &history, &request, &selection(20.0)).await;
+        assert!(matches!(
+            result,
+            Err(FederationError::BudgetExceeded { hard_cap, .. }) if hard_cap == 100.0
+        ));
+        assert_eq!(ledger.spend(), 90.0);
+        assert!(history.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_selection_records_only_after_reserving() {
+        let ledger = CappedLedger::new(90.0, 100.0);
+        let history = DashMap::new();
+
+        let request = selection_request(false);
+        let result = settle_selection(&ledger, &history, &request, &selection(20.0)).await;
+        assert!(matches!(
+            result,
+            Err(FederationError::BudgetExceeded { .. })
+        ));
+        assert!(history.is_empty());
+
+        settle_selection(&ledger, &history, &request, &selection(5.0))
+            .await
+            .unwrap();
+        assert_eq!(ledger.spend(), 95.0);
+        let records = history.get(&request.client_id).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].strategy, "cost_minimizer");
+    }
 
     #[test]
     fn test_cost_minimizer_strategy() {
@@ -9,7 +9,8 @@ use crate::blog_workflow::{
 };
 use crate::handlers::{success_response, ApiResponse};
 use crate::saas_client_auth::{
-    BrandProfile, SaasClientAuthService, SaasClientProfile, SaasClientRegistrationRequest,
+    BrandProfile, BrandTone, SaasClientAuthService, SaasClientProfile,
+    SaasClientRegistrationRequest,
 };
 use crate::server::ServerState;
 use axum::{
@@ -207,6 +208,18 @@ pub struct BrandProfileRequest {
     pub color_palette: Vec<String>,
     /// Industry context
     pub industry_context: Option<String>,
+    /// Expected tone of voice
+    #[serde(default)]
+    pub tone: Option<BrandTone>,
+    /// Phrases that must never appear in content
+    #[serde(default)]
+    pub banned_phrases: Vec<String>,
+    /// Preferred terminology, keyed by the discouraged term
+    #[serde(default)]
+    pub preferred_terms: HashMap<String, String>,
+    /// Target Flesch-Kincaid grade level
+    #[serde(default)]
+    pub target_reading_level: Option<f32>,
 }
 
 /// Blog preferences request
@@ -605,6 +618,10 @@ pub async fn register_client(
         visual_assets: vec![],
         guidelines_url: None,
         content_templates: vec![],
+        tone: bp.tone,
+        banned_phrases: bp.banned_phrases,
+        preferred_terms: bp.preferred_terms,
+        target_reading_level: bp.target_reading_level,
     });
 
     // This would create a full SaasClientRegistrationRequest
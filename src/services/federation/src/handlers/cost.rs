@@ -4,8 +4,14 @@
 //! including cost analysis, budget management, optimization strategies,
 //! and cost reporting within the federation service.
 
-use crate::handlers::{success_response, ApiResponse, IdPath, ListResponse, PaginationParams};
-use crate::models::{CostConstraints, ProviderSelectionRequest, QualityRequirements};
+use crate::handlers::{
+    error_response, not_found_response, success_response, ApiResponse, IdPath, ListResponse,
+    PaginationParams,
+};
+use crate::models::{
+    BalancedWeights, CostConstraints, DataResidencyConstraint, ProviderAlternative,
+    ProviderScoreBreakdown, ProviderSelectionRequest, QualityRequirements,
+};
 use crate::server::ServerState;
 use axum::{
     extract::{Path, Query, State},
@@ -31,6 +37,10 @@ pub async fn optimize_selection(
         required_capabilities: request.required_capabilities,
         cost_constraints: request.cost_constraints,
         quality_requirements: request.quality_requirements,
+        residency: request.residency,
+        bypass_routing_cache: request.bypass_routing_cache,
+        balanced_weights: request.balanced_weights,
+        dry_run: request.dry_run,
     };
 
     // Get available providers
@@ -56,19 +66,19 @@ pub async fn optimize_selection(
     // Use cost optimizer to select best provider
     match state
         .cost_optimizer
-        .optimize_provider_selection(&selection_request, &providers)
+        .select_provider_with_scores(&selection_request, &providers)
         .await
     {
-        Ok(Some(selected_provider)) => {
+        Ok(Some(selection)) => {
             let response = CostOptimizationResponse {
-                selected_provider: (*selected_provider).clone(),
-                estimated_cost: selected_provider.cost_info.cost_per_request,
+                estimated_cost: selection.estimated_cost,
                 optimization_strategy: "balanced".to_string(),
                 cost_savings: 0.0, // This would be calculated
-                reasoning: format!(
-                    "Selected {} based on optimal cost-quality balance",
-                    selected_provider.name
-                ),
+                reasoning: selection.reasoning,
+                score_breakdown: selection.score_breakdown,
+                alternatives: selection.alternatives,
+                dry_run: selection.dry_run,
+                selected_provider: selection.provider,
             };
             success_response(response)
         }
@@ -87,6 +97,24 @@ pub async fn optimize_selection(
     }
 }
 
+/// Start a new budget window for a client at the beginning of its billing cycle
+pub async fn rollover_budget_window(
+    State(state): State<ServerState>,
+    Path(id_path): Path<IdPath>,
+) -> Result<Json<ApiResponse<()>>, (StatusCode, Json<ApiResponse<()>>)> {
+    match state
+        .cost_optimizer
+        .rollover_budget_window(&id_path.id)
+        .await
+    {
+        Ok(()) => Ok(Json(ApiResponse::success(()))),
+        Err(crate::models::FederationError::ClientNotFound { .. }) => {
+            Err(not_found_response("Client", id_path.id))
+        }
+        Err(e) => Err(error_response(e.to_string())),
+    }
+}
+
 /// Get cost reports with filtering and pagination
 pub async fn get_cost_reports(
     State(state): State<ServerState>,
@@ -137,6 +165,18 @@ pub struct CostOptimizationRequest {
     pub cost_constraints: Option<CostConstraints>,
     /// Quality requirements
     pub quality_requirements: Option<QualityRequirements>,
+    /// Data residency constraint
+    #[serde(default)]
+    pub residency: Option<DataResidencyConstraint>,
+    /// Skip the routing cache and always run a fresh selection
+    #[serde(default)]
+    pub bypass_routing_cache: bool,
+    /// Rank providers by a weighted cost, latency and quality composite
+    #[serde(default)]
+    pub balanced_weights: Option<BalancedWeights>,
+    /// Preview the selection without reserving budget or recording it
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 /// Cost optimization response
@@ -152,6 +192,13 @@ pub struct CostOptimizationResponse {
     pub cost_savings: f64,
     /// Reasoning for selection
     pub reasoning: String,
+    /// Score components behind a balanced selection
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score_breakdown: Option<ProviderScoreBreakdown>,
+    /// Other candidates that were considered, best scoring or cheapest first
+    pub alternatives: Vec<ProviderAlternative>,
+    /// Whether this was a preview that reserved no budget
+    pub dry_run: bool,
 }
 
 /// Cost report filter parameters
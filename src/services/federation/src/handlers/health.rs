@@ -5,7 +5,8 @@
 
 use crate::handlers::{success_response, ApiResponse};
 use crate::server::ServerState;
-use axum::{extract::State, response::Json, response::Result as AxumResult};
+use crate::telemetry;
+use axum::{extract::State, http::HeaderMap, response::Json, response::Result as AxumResult};
 use serde_json;
 
 /// Basic health check endpoint
@@ -25,25 +26,34 @@ pub async fn health_check(
 /// Detailed health check with component status
 pub async fn detailed_health(
     State(state): State<ServerState>,
+    headers: HeaderMap,
 ) -> AxumResult<Json<ApiResponse<serde_json::Value>>> {
-    let client_health = state.client_manager.health().await.unwrap_or_else(|_| {
+    let correlation_id = telemetry::correlation_id_from_headers(&headers);
+
+    let client_health = state.client_manager.health().await.unwrap_or_else(|e| {
+        tracing::error!(%correlation_id, "Failed to get client manager health: {}", e);
         serde_json::json!({
             "status": "unhealthy",
-            "error": "Failed to get client manager health"
+            "error": "Failed to get client manager health",
+            "correlation_id": correlation_id
         })
     });
 
-    let provider_health = state.provider_manager.health().await.unwrap_or_else(|_| {
+    let provider_health = state.provider_manager.health().await.unwrap_or_else(|e| {
+        tracing::error!(%correlation_id, "Failed to get provider manager health: {}", e);
         serde_json::json!({
             "status": "unhealthy",
-            "error": "Failed to get provider manager health"
+            "error": "Failed to get provider manager health",
+            "correlation_id": correlation_id
         })
     });
 
-    let workflow_health = state.workflow_engine.health().await.unwrap_or_else(|_| {
+    let workflow_health = state.workflow_engine.health().await.unwrap_or_else(|e| {
+        tracing::error!(%correlation_id, "Failed to get workflow engine health: {}", e);
         serde_json::json!({
             "status": "unhealthy",
-            "error": "Failed to get workflow engine health"
+            "error": "Failed to get workflow engine health",
+            "correlation_id": correlation_id
         })
     });
 
@@ -5,34 +5,46 @@
 
 use crate::handlers::{success_response, ApiResponse};
 use crate::server::ServerState;
-use axum::{extract::State, response::Json, response::Result as AxumResult};
+use crate::telemetry;
+use axum::{extract::State, http::HeaderMap, response::Json, response::Result as AxumResult};
 use serde_json;
 
 /// Prometheus metrics endpoint
 pub async fn prometheus_metrics(
     State(state): State<ServerState>,
+    headers: HeaderMap,
 ) -> AxumResult<Json<ApiResponse<serde_json::Value>>> {
-    let client_metrics = state.client_manager.metrics().await.unwrap_or_else(|_| {
+    let correlation_id = telemetry::correlation_id_from_headers(&headers);
+
+    let client_metrics = state.client_manager.metrics().await.unwrap_or_else(|e| {
+        tracing::error!(%correlation_id, "Failed to get client metrics: {}", e);
         serde_json::json!({
-            "error": "Failed to get client metrics"
+            "error": "Failed to get client metrics",
+            "correlation_id": correlation_id
         })
     });
 
-    let provider_metrics = state.provider_manager.metrics().await.unwrap_or_else(|_| {
+    let provider_metrics = state.provider_manager.metrics().await.unwrap_or_else(|e| {
+        tracing::error!(%correlation_id, "Failed to get provider metrics: {}", e);
         serde_json::json!({
-            "error": "Failed to get provider metrics"
+            "error": "Failed to get provider metrics",
+            "correlation_id": correlation_id
         })
     });
 
-    let workflow_metrics = state.workflow_engine.metrics().await.unwrap_or_else(|_| {
+    let workflow_metrics = state.workflow_engine.metrics().await.unwrap_or_else(|e| {
+        tracing::error!(%correlation_id, "Failed to get workflow metrics: {}", e);
         serde_json::json!({
-            "error": "Failed to get workflow metrics"
+            "error": "Failed to get workflow metrics",
+            "correlation_id": correlation_id
         })
     });
 
-    let cost_metrics = state.cost_optimizer.metrics().await.unwrap_or_else(|_| {
+    let cost_metrics = state.cost_optimizer.metrics().await.unwrap_or_else(|e| {
+        tracing::error!(%correlation_id, "Failed to get cost metrics: {}", e);
         serde_json::json!({
-            "error": "Failed to get cost metrics"
+            "error": "Failed to get cost metrics",
+            "correlation_id": correlation_id
         })
     });
 
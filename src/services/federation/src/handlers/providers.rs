@@ -4,8 +4,8 @@
 //! selection, and lifecycle operations within the federation service.
 
 use crate::handlers::{
-    error_response, not_found_response, success_response, ApiResponse, IdPath, ListResponse,
-    PaginationParams,
+    error_response, not_found_response, success_response, validation_error_response, ApiResponse,
+    IdPath, ListResponse, PaginationParams,
 };
 use crate::models::{Provider, ProviderSelectionRequest, ProviderSelectionResponse};
 use crate::server::ServerState;
@@ -15,7 +15,7 @@ use axum::{
     response::Json,
     response::Result as AxumResult,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Register a new provider
 pub async fn register_provider(
@@ -69,6 +69,7 @@ pub async fn update_provider(
         status: update_request.status,
         capabilities: update_request.capabilities,
         health_endpoint: update_request.health_endpoint,
+        region: update_request.region,
     };
 
     match state
@@ -103,7 +104,15 @@ pub async fn select_provider(
     State(state): State<ServerState>,
     Json(request): Json<ProviderSelectionRequest>,
 ) -> AxumResult<Json<ApiResponse<ProviderSelectionResponse>>> {
-    match state.provider_manager.select_provider(request).await {
+    // Weighted requests are scored by the cost optimizer so the response
+    // carries the score breakdown
+    let result = if request.balanced_weights.is_some() {
+        select_balanced_provider(&state, &request).await
+    } else {
+        select_budgeted_provider(&state, request).await
+    };
+
+    match result {
         Ok(response) => success_response(response),
         Err(e) => Ok(Json(ApiResponse {
             success: false,
@@ -114,6 +123,97 @@ pub async fn select_provider(
     }
 }
 
+/// Select through the provider manager, rejecting selections over the
+/// client's hard budget cap. Dry runs are checked but reserve nothing.
+async fn select_budgeted_provider(
+    state: &ServerState,
+    request: ProviderSelectionRequest,
+) -> Result<ProviderSelectionResponse, crate::models::FederationError> {
+    let client_id = request.client_id;
+    let response = state.provider_manager.select_provider(request).await?;
+
+    if response.dry_run {
+        state
+            .cost_optimizer
+            .enforce_budget(&client_id, response.estimated_cost)
+            .await?;
+    } else {
+        state
+            .cost_optimizer
+            .reserve_budget(&client_id, response.estimated_cost)
+            .await?;
+    }
+
+    Ok(response)
+}
+
+async fn select_balanced_provider(
+    state: &ServerState,
+    request: &ProviderSelectionRequest,
+) -> Result<ProviderSelectionResponse, crate::models::FederationError> {
+    let providers = state
+        .provider_manager
+        .get_available_providers(request)
+        .await?;
+
+    state
+        .cost_optimizer
+        .select_provider_with_scores(request, &providers)
+        .await?
+        .ok_or_else(|| crate::models::FederationError::ProviderSelectionFailed {
+            reason: "No provider scored for the balanced selection".to_string(),
+        })
+}
+
+/// Record downstream quality feedback for a provider's output
+pub async fn record_quality_feedback(
+    State(state): State<ServerState>,
+    Path(id_path): Path<IdPath>,
+    Json(feedback): Json<QualityFeedbackPayload>,
+) -> Result<Json<ApiResponse<QualityFeedbackResponse>>, (StatusCode, Json<ApiResponse<()>>)> {
+    match state
+        .provider_manager
+        .record_quality_feedback(&id_path.id, feedback.score)
+        .await
+    {
+        Ok(rolling_quality_score) => {
+            let sample_count = state
+                .provider_manager
+                .get_observed_quality(&id_path.id)
+                .map(|observed| observed.sample_count())
+                .unwrap_or_default();
+
+            Ok(Json(ApiResponse::success(QualityFeedbackResponse {
+                provider_id: id_path.id,
+                rolling_quality_score,
+                sample_count,
+            })))
+        }
+        Err(crate::models::FederationError::ProviderNotFound { .. }) => {
+            Err(not_found_response("Provider", id_path.id))
+        }
+        Err(crate::models::FederationError::ValidationError { field, message }) => {
+            Err(validation_error_response(&field, &message))
+        }
+        Err(e) => Err(error_response(e.to_string())),
+    }
+}
+
+/// Quality feedback payload
+#[derive(Debug, Deserialize)]
+pub struct QualityFeedbackPayload {
+    /// Normalized quality score (0.0-1.0)
+    pub score: f64,
+}
+
+/// Quality feedback result
+#[derive(Debug, Serialize)]
+pub struct QualityFeedbackResponse {
+    pub provider_id: uuid::Uuid,
+    pub rolling_quality_score: f64,
+    pub sample_count: usize,
+}
+
 /// Provider update request payload
 #[derive(Debug, Deserialize)]
 pub struct ProviderUpdateRequestPayload {
@@ -123,6 +223,7 @@ pub struct ProviderUpdateRequestPayload {
     pub status: Option<crate::models::ProviderStatus>,
     pub capabilities: Option<Vec<String>>,
     pub health_endpoint: Option<Option<String>>,
+    pub region: Option<Option<String>>,
 }
 
 #[cfg(test)]
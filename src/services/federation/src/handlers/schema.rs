@@ -70,6 +70,18 @@ pub async fn get_translation(
     }
 }
 
+/// Invalidate the cached translation plan for a schema hash after a provider
+/// updates its schema; responds with whether a plan was cached
+pub async fn invalidate_translation_plan(
+    State(state): State<ServerState>,
+    Path(schema_hash): Path<String>,
+) -> Result<Json<ApiResponse<bool>>, (StatusCode, Json<ApiResponse<()>>)> {
+    match state.schema_translator.invalidate_plan(&schema_hash).await {
+        Ok(removed) => Ok(Json(ApiResponse::success(removed))),
+        Err(e) => Err(error_response(e.to_string())),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
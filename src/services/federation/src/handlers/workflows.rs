@@ -7,7 +7,9 @@ use crate::handlers::{
     error_response, not_found_response, success_response, ApiResponse, IdPath, ListResponse,
     PaginationParams,
 };
-use crate::models::{FederatedWorkflow, WorkflowExecution, WorkflowStatus};
+use crate::models::{
+    FederatedWorkflow, FederationError, RetryFrom, WorkflowExecution, WorkflowStatus,
+};
 use crate::server::ServerState;
 use axum::{
     extract::{Path, Query, State},
@@ -118,6 +120,69 @@ pub async fn cancel_workflow(
     }
 }
 
+/// Get a workflow execution with per-step status
+pub async fn get_workflow_execution(
+    State(state): State<ServerState>,
+    Path(id_path): Path<IdPath>,
+) -> Result<Json<ApiResponse<WorkflowExecution>>, (StatusCode, Json<ApiResponse<()>>)> {
+    match state.workflow_engine.get_execution(&id_path.id).await {
+        Ok(execution) => Ok(Json(ApiResponse::success(execution))),
+        Err(FederationError::WorkflowExecutionFailed { .. }) => {
+            Err(not_found_response("Workflow execution", id_path.id))
+        }
+        Err(e) => Err(error_response(e.to_string())),
+    }
+}
+
+/// Cancel a workflow execution, interrupting any running steps
+pub async fn cancel_workflow_execution(
+    State(state): State<ServerState>,
+    Path(id_path): Path<IdPath>,
+) -> Result<Json<ApiResponse<()>>, (StatusCode, Json<ApiResponse<()>>)> {
+    match state.workflow_engine.cancel(&id_path.id).await {
+        Ok(()) => Ok(Json(ApiResponse::success(()))),
+        Err(FederationError::WorkflowExecutionFailed { .. }) => {
+            Err(not_found_response("Workflow execution", id_path.id))
+        }
+        Err(e @ FederationError::InvalidWorkflowState { .. }) => Err((
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(e.to_string())),
+        )),
+        Err(e) => Err(error_response(e.to_string())),
+    }
+}
+
+/// Retry a failed workflow execution, by default from its failed steps
+pub async fn retry_workflow_execution(
+    State(state): State<ServerState>,
+    Path(id_path): Path<IdPath>,
+    Query(params): Query<RetryParams>,
+) -> Result<Json<ApiResponse<WorkflowExecution>>, (StatusCode, Json<ApiResponse<()>>)> {
+    match state
+        .workflow_engine
+        .retry_workflow(&id_path.id, params.from)
+        .await
+    {
+        Ok(execution) => Ok(Json(ApiResponse::success(execution))),
+        Err(FederationError::WorkflowExecutionFailed { .. }) => {
+            Err(not_found_response("Workflow execution", id_path.id))
+        }
+        Err(e @ FederationError::InvalidWorkflowState { .. }) => Err((
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(e.to_string())),
+        )),
+        Err(e) => Err(error_response(e.to_string())),
+    }
+}
+
+/// Query parameters for retrying an execution
+#[derive(Debug, Deserialize)]
+pub struct RetryParams {
+    /// `beginning` or `last_failed_step` (the default)
+    #[serde(default)]
+    pub from: RetryFrom,
+}
+
 /// Workflow update request payload
 #[derive(Debug, Deserialize)]
 pub struct WorkflowUpdateRequestPayload {
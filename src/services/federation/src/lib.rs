@@ -42,15 +42,19 @@
 //! }
 //! ```
 
+pub mod blog_template;
 pub mod blog_workflow;
+pub mod brand_voice;
 pub mod client;
 pub mod config;
 pub mod cost_optimizer;
 pub mod handlers;
+pub mod mcp_orchestrator;
 pub mod middleware;
 pub mod models;
 pub mod provider;
 pub mod proxy;
+pub mod routing_cache;
 pub mod saas_client_auth;
 pub mod schema_translator;
 pub mod server;
@@ -59,17 +63,23 @@ pub mod utils;
 pub mod workflow;
 
 // Re-export commonly used types
+pub use blog_template::{
+    BlogTemplateRegistry, BlogWorkflowTemplate, ParameterError, TemplateError,
+};
 pub use blog_workflow::{
     BlogWorkflowRequest, BlogWorkflowResponse, BlogWorkflowService, ExecutionMetrics,
     GeneratedBlogPost, QualityScores,
 };
+pub use brand_voice::{BrandVoiceAssessment, BrandVoiceViolation};
 pub use client::{ClientManager, ClientRegistry};
 pub use config::{Config, DatabaseConfig, RedisConfig};
-pub use cost_optimizer::{CostOptimizer, OptimizationStrategy};
+pub use cost_optimizer::{BudgetDecision, CostOptimizer, OptimizationStrategy};
+pub use mcp_orchestrator::HttpMcpOrchestrator;
 pub use models::{
-    Client, ClientConfig, ClientRegistrationRequest, ClientRegistrationResponse, ClientStatus,
-    ClientTier, FederationError, Provider, ProviderSelectionRequest, ProviderSelectionResponse,
-    ProviderStatus, ProviderType, SchemaTranslationRequest, SchemaTranslationResponse,
+    BalancedWeights, BudgetCaps, Client, ClientConfig, ClientRegistrationRequest,
+    ClientRegistrationResponse, ClientStatus, ClientTier, FederationError, Provider,
+    ProviderScoreBreakdown, ProviderSelectionRequest, ProviderSelectionResponse, ProviderStatus,
+    ProviderType, RetryFrom, SchemaTranslationRequest, SchemaTranslationResponse,
     WorkflowExecution, WorkflowStatus,
 };
 pub use provider::{ProviderManager, ProviderRegistry};
@@ -108,6 +118,10 @@ pub struct FederationService {
     pub saas_auth_service: Arc<SaasClientAuthService>,
     /// Blog workflow service
     pub blog_workflow_service: Arc<BlogWorkflowService>,
+    /// Wall-clock time the service was created
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    /// Monotonic start time used for uptime
+    start_time: std::time::Instant,
 }
 
 impl FederationService {
@@ -136,24 +150,35 @@ impl FederationService {
         let schema_translator =
             Arc::new(SchemaTranslationService::new(db_pool.clone(), redis_client.clone()).await?);
 
-        let workflow_engine =
-            Arc::new(WorkflowEngine::new(config.clone(), Arc::new(db_pool.clone())).await?);
-
         let mcp_proxy = Arc::new(McpProxy::new(config.proxy.clone()).await?);
 
-        let cost_optimizer =
-            Arc::new(CostOptimizer::new(provider_manager.clone(), client_manager.clone()).await?);
+        let cost_optimizer = Arc::new(
+            CostOptimizer::new(
+                provider_manager.clone(),
+                client_manager.clone(),
+                redis_client.clone(),
+            )
+            .await?
+            .with_routing_cache(config.cost_optimization.routing_cache.clone()),
+        );
+
+        let workflow_engine = Arc::new(
+            WorkflowEngine::new(config.clone(), Arc::new(db_pool.clone()))
+                .await?
+                .with_budget_ledger(cost_optimizer.clone()),
+        );
 
         let saas_auth_service = Arc::new(SaasClientAuthService::new(SaasAuthConfig::default()));
 
-        // Note: BlogWorkflowService would need actual MCP trait implementations
-        // For now, we'll create a placeholder that would be properly implemented
-        // with real MCP orchestrator, content generator, image generator, and quality validator
+        // Note: content generation, image generation and quality validation are
+        // still placeholders; MCP function calls go to the real orchestrator
+        let mcp_orchestrator = Arc::new(HttpMcpOrchestrator::new(&config.mcp_orchestrator)?);
         let blog_workflow_service = Arc::new(create_blog_workflow_service(
-            Arc::new(MockMcpOrchestrator {}),
+            mcp_orchestrator,
             Arc::new(MockContentGenerator {}),
             Arc::new(MockImageGenerator {}),
             Arc::new(MockQualityValidator {}),
+            provider_manager.clone(),
         ));
 
         Ok(Self {
@@ -166,6 +191,8 @@ impl FederationService {
             cost_optimizer,
             saas_auth_service,
             blog_workflow_service,
+            started_at: chrono::Utc::now(),
+            start_time: std::time::Instant::now(),
         })
     }
 
@@ -219,6 +246,14 @@ impl FederationService {
             }
         });
 
+        // Start MCP connection pool maintenance
+        let mcp_proxy = self.mcp_proxy.clone();
+        tokio::spawn(async move {
+            if let Err(e) = mcp_proxy.start_pool_maintenance().await {
+                tracing::error!("MCP connection pool maintenance failed: {}", e);
+            }
+        });
+
         // Start client activity monitoring
         let client_manager = self.client_manager.clone();
         tokio::spawn(async move {
@@ -272,15 +307,14 @@ impl FederationService {
                 }
             },
             "version": env!("CARGO_PKG_VERSION"),
-            "uptime": self.get_uptime().await
+            "started_at": self.started_at,
+            "uptime": self.get_uptime()
         }))
     }
 
     /// Get service uptime in seconds
-    async fn get_uptime(&self) -> u64 {
-        // This would be implemented with a start time tracker
-        // For now, return a placeholder
-        0
+    fn get_uptime(&self) -> u64 {
+        self.start_time.elapsed().as_secs()
     }
 
     /// Get service metrics
@@ -308,37 +342,24 @@ impl FederationService {
 // Helper function to create blog workflow service
 // In a real implementation, this would use proper dependency injection
 fn create_blog_workflow_service(
-    mcp_orchestrator: Arc<MockMcpOrchestrator>,
+    mcp_orchestrator: Arc<dyn blog_workflow::McpOrchestrator + Send + Sync>,
     content_generator: Arc<MockContentGenerator>,
     image_generator: Arc<MockImageGenerator>,
     quality_validator: Arc<MockQualityValidator>,
+    provider_manager: Arc<ProviderManager>,
 ) -> BlogWorkflowService {
     BlogWorkflowService::new(
-        mcp_orchestrator as Arc<dyn blog_workflow::McpOrchestrator + Send + Sync>,
+        mcp_orchestrator,
         content_generator as Arc<dyn blog_workflow::ContentGenerator + Send + Sync>,
         image_generator as Arc<dyn blog_workflow::ImageGenerator + Send + Sync>,
         quality_validator as Arc<dyn blog_workflow::QualityValidator + Send + Sync>,
         blog_workflow::BlogWorkflowConfig::default(),
     )
+    .with_content_moderator(Arc::new(blog_workflow::KeywordModerator::default()))
+    .with_quality_feedback(provider_manager)
 }
 
 // Mock implementations for testing and development
-struct MockMcpOrchestrator;
-#[async_trait::async_trait]
-impl blog_workflow::McpOrchestrator for MockMcpOrchestrator {
-    async fn execute_function(
-        &self,
-        _function_call: &str,
-        _parameters: &serde_json::Value,
-    ) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
-        Ok(serde_json::json!({"status": "mock_response"}))
-    }
-
-    async fn get_available_services(&self) -> Result<Vec<String>, Box<dyn std::error::Error>> {
-        Ok(vec!["mock_service".to_string()])
-    }
-}
-
 struct MockContentGenerator;
 #[async_trait::async_trait]
 impl blog_workflow::ContentGenerator for MockContentGenerator {
@@ -363,6 +384,7 @@ impl blog_workflow::ContentGenerator for MockContentGenerator {
                     avg_syllables_per_word: 1.5,
                 },
             },
+            provider_id: None,
         })
     }
 
@@ -415,8 +437,8 @@ struct MockQualityValidator;
 impl blog_workflow::QualityValidator for MockQualityValidator {
     async fn validate_content(
         &self,
-        _content: &str,
-        _requirements: &blog_workflow::QualityValidationRequirements,
+        content: &str,
+        requirements: &blog_workflow::QualityValidationRequirements,
     ) -> Result<blog_workflow::QualityValidationResult, Box<dyn std::error::Error>> {
         let mut detailed_scores = std::collections::HashMap::new();
         detailed_scores.insert("content_quality".to_string(), 4.2);
@@ -426,13 +448,24 @@ impl blog_workflow::QualityValidator for MockQualityValidator {
         detailed_scores.insert("brand_compliance".to_string(), 4.3);
         detailed_scores.insert("originality".to_string(), 4.4);
 
-        Ok(blog_workflow::QualityValidationResult {
+        let mut result = blog_workflow::QualityValidationResult {
             overall_score: 4.2,
             detailed_scores,
             validation_passed: true,
             issues_found: vec![],
             improvement_suggestions: vec![],
-        })
+        };
+
+        match requirements.brand_profile.as_ref() {
+            Some(profile) => BrandVoiceAssessment::evaluate(content, profile).apply_to(&mut result),
+            None => {
+                result
+                    .detailed_scores
+                    .insert(brand_voice::BRAND_VOICE_SCORE_KEY.to_string(), 5.0);
+            }
+        }
+
+        Ok(result)
     }
 
     async fn validate_image(
@@ -470,6 +503,11 @@ mod tests {
 
         assert_eq!(health["service"], "federation");
         assert_eq!(health["status"], "healthy");
+        assert!(health["uptime"].as_u64().is_some());
+
+        let started_at: chrono::DateTime<chrono::Utc> =
+            serde_json::from_value(health["started_at"].clone()).unwrap();
+        assert_eq!(started_at, service.started_at);
     }
 
     #[tokio::test]
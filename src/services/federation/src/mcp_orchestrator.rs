@@ -0,0 +1,327 @@
+//! HTTP client for the MCP orchestrator service
+//!
+//! This module provides the production `McpOrchestrator` implementation used by
+//! blog workflows. Function calls are POSTed to the mcp-orchestrator service with
+//! per-request timeouts and jittered exponential backoff, and the list of
+//! registered MCP services is cached for a short time.
+
+use crate::blog_workflow::McpOrchestrator;
+use crate::config::{McpOrchestratorConfig, RetryConfig};
+use crate::models::FederationError;
+use crate::telemetry;
+use rand::Rng;
+use reqwest::{Client, Method, StatusCode};
+use serde::Deserialize;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+/// Service name used in error reports
+const SERVICE_NAME: &str = "mcp-orchestrator";
+
+/// `McpOrchestrator` backed by the mcp-orchestrator HTTP API
+#[derive(Debug)]
+pub struct HttpMcpOrchestrator {
+    /// HTTP client with the configured request timeout
+    client: Client,
+    /// Base URL without a trailing slash
+    base_url: String,
+    /// Retry policy for failed calls
+    retry: RetryConfig,
+    /// Lifetime of the cached service list
+    services_cache_ttl: Duration,
+    /// Cached service names and when they were fetched
+    services_cache: RwLock<Option<(Instant, Vec<String>)>>,
+}
+
+/// Response body of `GET /v1/mcps`
+#[derive(Debug, Deserialize)]
+struct McpListResponse {
+    mcps: Vec<McpEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct McpEntry {
+    name: String,
+}
+
+/// Response body of `POST /v1/functions/execute`
+#[derive(Debug, Deserialize)]
+struct FunctionCallResponse {
+    result: serde_json::Value,
+}
+
+impl HttpMcpOrchestrator {
+    /// Create a new orchestrator client
+    pub fn new(config: &McpOrchestratorConfig) -> Result<Self, FederationError> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout))
+            .build()
+            .map_err(|e| FederationError::ConfigurationError {
+                message: format!("Failed to build MCP orchestrator client: {}", e),
+            })?;
+
+        Ok(Self {
+            client,
+            base_url: config.url.trim_end_matches('/').to_string(),
+            retry: config.retry.clone(),
+            services_cache_ttl: Duration::from_secs(config.services_cache_ttl),
+            services_cache: RwLock::new(None),
+        })
+    }
+
+    /// Execute an MCP function through `POST /v1/functions/execute`, returning
+    /// the MCP service's result. `function_call` is `<mcp_service>.<operation>`.
+    pub async fn call_function(
+        &self,
+        function_call: &str,
+        parameters: &serde_json::Value,
+    ) -> Result<serde_json::Value, FederationError> {
+        let body = serde_json::json!({
+            "function": function_call,
+            "parameters": parameters,
+        });
+
+        let response = self
+            .send(Method::POST, "/v1/functions/execute", Some(&body))
+            .await?;
+        let executed: FunctionCallResponse = serde_json::from_value(response)
+            .map_err(|e| external_error(format!("Unexpected function call response: {}", e)))?;
+        Ok(executed.result)
+    }
+
+    /// Names of the registered MCP services, cached for `services_cache_ttl`
+    pub async fn list_services(&self) -> Result<Vec<String>, FederationError> {
+        if let Some((fetched_at, services)) = self.services_cache.read().await.as_ref() {
+            if fetched_at.elapsed() < self.services_cache_ttl {
+                return Ok(services.clone());
+            }
+        }
+
+        let response = self.send(Method::GET, "/v1/mcps", None).await?;
+        let listing: McpListResponse = serde_json::from_value(response)
+            .map_err(|e| external_error(format!("Unexpected MCP listing response: {}", e)))?;
+        let services: Vec<String> = listing.mcps.into_iter().map(|mcp| mcp.name).collect();
+
+        *self.services_cache.write().await = Some((Instant::now(), services.clone()));
+        Ok(services)
+    }
+
+    /// Send a request, retrying connection failures, timeouts and retryable
+    /// status codes with backoff
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&serde_json::Value>,
+    ) -> Result<serde_json::Value, FederationError> {
+        let url = format!("{}{}", self.base_url, path);
+        let max_attempts = self.retry.max_attempts.max(1);
+        let mut attempt = 1;
+
+        loop {
+            let mut request = self.client.request(method.clone(), &url);
+            if let Some(correlation_id) = telemetry::current_correlation_id() {
+                request =
+                    request.header(telemetry::CORRELATION_ID_HEADER, correlation_id.to_string());
+            }
+            if let Some(body) = body {
+                request = request.json(body);
+            }
+
+            let error = match request.send().await {
+                Ok(response) if response.status().is_success() => {
+                    return response
+                        .json()
+                        .await
+                        .map_err(|e| external_error(format!("Invalid JSON from {}: {}", url, e)));
+                }
+                Ok(response) => {
+                    let status = response.status();
+                    let detail = response.text().await.unwrap_or_default();
+                    let error = external_error(format!("{} returned {}: {}", url, status, detail));
+                    if !is_retryable(status) {
+                        return Err(error);
+                    }
+                    error
+                }
+                Err(e) => external_error(format!("Request to {} failed: {}", url, e)),
+            };
+
+            if attempt >= max_attempts {
+                return Err(error);
+            }
+
+            let delay = self.backoff_delay(attempt);
+            warn!(
+                "MCP orchestrator call failed (attempt {}/{}), retrying in {:?}: {}",
+                attempt, max_attempts, delay, error
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Delay before the retry following `attempt`
+    fn backoff_delay(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1) as i32;
+        let delay = (self.retry.base_delay as f64 * self.retry.backoff_multiplier.powi(exponent))
+            .min(self.retry.max_delay as f64) as u64;
+
+        if self.retry.enable_jitter && delay > 0 {
+            let jitter = rand::thread_rng().gen_range(0..=delay / 4);
+            Duration::from_millis(delay - jitter)
+        } else {
+            Duration::from_millis(delay)
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl McpOrchestrator for HttpMcpOrchestrator {
+    async fn execute_function(
+        &self,
+        function_call: &str,
+        parameters: &serde_json::Value,
+    ) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+        debug!("Executing MCP function {}", function_call);
+        self.call_function(function_call, parameters)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
+    }
+
+    async fn get_available_services(&self) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        self.list_services()
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
+    }
+}
+
+fn external_error(message: String) -> FederationError {
+    FederationError::ExternalServiceError {
+        service: SERVICE_NAME.to_string(),
+        message,
+    }
+}
+
+/// Server errors, throttling and request timeouts are worth retrying
+fn is_retryable(status: StatusCode) -> bool {
+    status.is_server_error()
+        || status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn test_config(url: String) -> McpOrchestratorConfig {
+        McpOrchestratorConfig {
+            url,
+            request_timeout: 5,
+            services_cache_ttl: 60,
+            retry: RetryConfig {
+                max_attempts: 3,
+                base_delay: 1,
+                max_delay: 5,
+                backoff_multiplier: 2.0,
+                enable_jitter: false,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_function_retries_server_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/functions/execute"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/functions/execute"))
+            .and(body_json(json!({
+                "function": "text.summarize",
+                "parameters": {"text": "hello"}
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "function": "text.summarize",
+                "mcp_service": "text",
+                "result": {"summary": "hi"},
+                "processing_time_ms": 12
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let orchestrator = HttpMcpOrchestrator::new(&test_config(server.uri())).unwrap();
+        let result = orchestrator
+            .execute_function("text.summarize", &json!({"text": "hello"}))
+            .await
+            .unwrap();
+
+        assert_eq!(result, json!({"summary": "hi"}));
+    }
+
+    #[tokio::test]
+    async fn test_execute_function_does_not_retry_client_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/functions/execute"))
+            .respond_with(ResponseTemplate::new(400).set_body_string("unknown function"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let orchestrator = HttpMcpOrchestrator::new(&test_config(server.uri())).unwrap();
+        let error = orchestrator
+            .call_function("missing", &json!({}))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            error,
+            FederationError::ExternalServiceError { ref service, .. } if service == SERVICE_NAME
+        ));
+        assert!(error.to_string().contains("unknown function"));
+    }
+
+    #[tokio::test]
+    async fn test_available_services_are_cached() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/mcps"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "mcps": [
+                    {"name": "text-processing", "url": "http://text:8803"},
+                    {"name": "image-generation", "url": "http://image:8805"}
+                ],
+                "count": 2
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let orchestrator = HttpMcpOrchestrator::new(&test_config(server.uri())).unwrap();
+        let first = orchestrator.get_available_services().await.unwrap();
+        let second = orchestrator.get_available_services().await.unwrap();
+
+        assert_eq!(first, vec!["text-processing", "image-generation"]);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_backoff_delay_is_capped() {
+        let orchestrator =
+            HttpMcpOrchestrator::new(&test_config("http://localhost:8807/".to_string())).unwrap();
+
+        assert_eq!(orchestrator.base_url, "http://localhost:8807");
+        assert_eq!(orchestrator.backoff_delay(1), Duration::from_millis(1));
+        assert_eq!(orchestrator.backoff_delay(2), Duration::from_millis(2));
+        assert_eq!(orchestrator.backoff_delay(10), Duration::from_millis(5));
+    }
+}
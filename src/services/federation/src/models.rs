@@ -102,6 +102,62 @@ pub struct CostOptimizationConfig {
     pub prefer_cheaper_providers: bool,
     /// Quality vs cost trade-off (0.0 = cheapest, 1.0 = highest quality)
     pub quality_cost_ratio: f64,
+    /// Spend caps for the billing window; defaults to the client tier's caps
+    #[serde(default)]
+    pub budget_caps: Option<BudgetCaps>,
+}
+
+/// Spend caps enforced over a client's billing window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetCaps {
+    /// Spend above which selections are logged but still allowed
+    pub soft_cap: f64,
+    /// Spend above which selections are rejected
+    pub hard_cap: f64,
+    /// Billing window length in days; spend rolls over when it elapses
+    pub window_days: u32,
+}
+
+impl BudgetCaps {
+    /// Default caps for a client tier
+    pub fn for_tier(tier: &ClientTier) -> Self {
+        let (soft_cap, hard_cap) = match tier {
+            ClientTier::Free => (8.0, 10.0),
+            ClientTier::Professional => (400.0, 500.0),
+            ClientTier::Enterprise => (8_000.0, 10_000.0),
+            ClientTier::Custom => (40_000.0, 50_000.0),
+        };
+
+        Self {
+            soft_cap,
+            hard_cap,
+            window_days: 30,
+        }
+    }
+
+    /// Check that the caps are positive, ordered and have a window
+    pub fn validate(&self) -> Result<(), FederationError> {
+        if !(self.soft_cap.is_finite() && self.hard_cap.is_finite())
+            || self.soft_cap < 0.0
+            || self.soft_cap > self.hard_cap
+        {
+            return Err(FederationError::ConfigurationError {
+                message: format!(
+                    "Budget caps must satisfy 0 <= soft cap ({}) <= hard cap ({})",
+                    self.soft_cap, self.hard_cap
+                ),
+            });
+        }
+
+        if self.window_days == 0 {
+            return Err(FederationError::ConfigurationError {
+                message: "Budget window must be at least one day".to_string(),
+            });
+        }
+
+        Ok(())
+    }
 }
 
 /// Schema preferences for compatibility layer
@@ -317,6 +373,9 @@ pub struct Provider {
     pub capabilities: Vec<String>,
     /// Health check endpoint
     pub health_endpoint: Option<String>,
+    /// Region where the provider processes and stores data (e.g., "eu-west-1")
+    #[serde(default)]
+    pub region: Option<String>,
     /// Creation timestamp
     pub created_at: DateTime<Utc>,
     /// Last update timestamp
@@ -613,6 +672,99 @@ pub enum ValidationType {
     Custom(String),
 }
 
+/// Declarative field transform applied to translated data.
+///
+/// `field` is a dotted path from the document root; a `[]` suffix on a segment
+/// descends into every element of that array, e.g. `orders[].placed_at`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FieldTransform {
+    /// Rename the field within its parent object
+    Rename { field: String, to: String },
+    /// Reformat a timestamp
+    DateFormat {
+        field: String,
+        from: DateFormat,
+        to: DateFormat,
+    },
+    /// Multiply a number by `factor`, optionally rounding to `decimals` places
+    NumberScale {
+        field: String,
+        factor: f64,
+        #[serde(default)]
+        decimals: Option<u32>,
+    },
+    /// Change the case of a string
+    StringCase { field: String, case: StringCase },
+    /// Set the field when it is absent or null
+    DefaultIfMissing {
+        field: String,
+        value: serde_json::Value,
+    },
+}
+
+impl FieldTransform {
+    /// Path of the field the transform targets
+    pub fn field(&self) -> &str {
+        match self {
+            Self::Rename { field, .. }
+            | Self::DateFormat { field, .. }
+            | Self::NumberScale { field, .. }
+            | Self::StringCase { field, .. }
+            | Self::DefaultIfMissing { field, .. } => field,
+        }
+    }
+
+    /// Transform name as used in the request payload
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Rename { .. } => "rename",
+            Self::DateFormat { .. } => "date_format",
+            Self::NumberScale { .. } => "number_scale",
+            Self::StringCase { .. } => "string_case",
+            Self::DefaultIfMissing { .. } => "default_if_missing",
+        }
+    }
+}
+
+/// Timestamp representation used by date transforms
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DateFormat {
+    /// ISO-8601 / RFC 3339 string
+    Iso8601,
+    /// Milliseconds since the Unix epoch
+    EpochMillis,
+    /// Seconds since the Unix epoch
+    EpochSeconds,
+    /// chrono `strftime` pattern, interpreted as UTC when it carries no offset
+    Pattern(String),
+}
+
+/// Target case for string transforms
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StringCase {
+    /// UPPER CASE
+    Upper,
+    /// lower case
+    Lower,
+    /// Title Case
+    Title,
+}
+
+/// Failure applying a field transform to one concrete field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldTransformError {
+    /// Concrete path of the field, e.g. `orders[1].placed_at`
+    pub field: String,
+    /// Transform that failed
+    pub transform: String,
+    /// Failure reason
+    pub message: String,
+}
+
 // ================================================================================================
 // Workflow Execution Models
 // ================================================================================================
@@ -761,6 +913,8 @@ pub enum WorkflowStatus {
     Pending,
     /// Workflow is currently running
     Running,
+    /// Workflow is being retried after a failure
+    Retrying,
     /// Workflow completed successfully
     Completed,
     /// Workflow failed
@@ -771,6 +925,8 @@ pub enum WorkflowStatus {
     Paused,
     /// Workflow timed out
     TimedOut,
+    /// Step was skipped because a dependency did not complete
+    Skipped,
 }
 
 /// Workflow execution record
@@ -781,6 +937,9 @@ pub struct WorkflowExecution {
     pub id: Uuid,
     /// Workflow ID
     pub workflow_id: Uuid,
+    /// Correlation ID tagging every span, log line and outbound request of this workflow
+    #[serde(default)]
+    pub correlation_id: Uuid,
     /// Execution status
     pub status: WorkflowStatus,
     /// Start time
@@ -797,6 +956,40 @@ pub struct WorkflowExecution {
     pub total_cost: f64,
     /// Resource usage
     pub resource_usage: ResourceUsage,
+    /// Every run of this execution, the first one included
+    #[serde(default)]
+    pub attempts: Vec<ExecutionAttempt>,
+}
+
+/// Where a retried execution restarts from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryFrom {
+    /// Re-run every step, discarding earlier outputs
+    Beginning,
+    /// Re-run failed and skipped steps, reusing completed step outputs
+    #[default]
+    LastFailedStep,
+}
+
+/// One run of a workflow execution
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionAttempt {
+    /// Attempt number, starting at 1
+    pub attempt: u32,
+    /// Where the attempt restarted from; `None` for the first run
+    pub retry_from: Option<RetryFrom>,
+    /// Outcome of the attempt
+    pub status: WorkflowStatus,
+    /// Start time
+    pub started_at: DateTime<Utc>,
+    /// End time
+    pub ended_at: Option<DateTime<Utc>>,
+    /// Cost incurred by steps that ran in this attempt
+    pub cost: f64,
+    /// Error information if the attempt failed
+    pub error: Option<ExecutionError>,
 }
 
 /// Step execution record
@@ -901,6 +1094,103 @@ pub struct ProviderSelectionRequest {
     pub cost_constraints: Option<CostConstraints>,
     /// Quality requirements
     pub quality_requirements: Option<QualityRequirements>,
+    /// Data residency constraint
+    #[serde(default)]
+    pub residency: Option<DataResidencyConstraint>,
+    /// Skip the routing cache and always run a fresh selection
+    #[serde(default)]
+    pub bypass_routing_cache: bool,
+    /// Rank providers by a weighted cost, latency and quality composite
+    #[serde(default)]
+    pub balanced_weights: Option<BalancedWeights>,
+    /// Preview the selection without reserving budget or recording it
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Weights for balanced provider scoring
+///
+/// Each provider's recent cost, p95 latency and quality score are normalized
+/// across the candidates to 0.0-1.0 (higher is better) and combined using
+/// these weights, which must sum to 1.0.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BalancedWeights {
+    /// Weight of the normalized cost score
+    pub cost_weight: f64,
+    /// Weight of the normalized p95 latency score
+    pub latency_weight: f64,
+    /// Weight of the quality score
+    pub quality_weight: f64,
+}
+
+impl BalancedWeights {
+    /// Tolerance when checking that the weights sum to 1.0
+    pub const SUM_TOLERANCE: f64 = 1e-6;
+
+    /// Check that every weight is non-negative and that they sum to 1.0
+    pub fn validate(&self) -> Result<(), FederationError> {
+        let weights = [self.cost_weight, self.latency_weight, self.quality_weight];
+
+        if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+            return Err(FederationError::ConfigurationError {
+                message: "Balanced weights must be non-negative numbers".to_string(),
+            });
+        }
+
+        let sum: f64 = weights.iter().sum();
+        if (sum - 1.0).abs() > Self::SUM_TOLERANCE {
+            return Err(FederationError::ConfigurationError {
+                message: format!("Balanced weights must sum to 1.0, got {}", sum),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Data residency constraint for provider selection
+///
+/// Region entries match a provider region exactly or as a prefix ending at a
+/// `-` boundary, so `"eu"` allows `"eu-west-1"` but not `"europe-north1"`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DataResidencyConstraint {
+    /// Regions data may be processed in; providers elsewhere are never selected
+    pub allowed_regions: Vec<String>,
+    /// Regions to prefer among compliant providers
+    #[serde(default)]
+    pub preferred_regions: Vec<String>,
+}
+
+impl DataResidencyConstraint {
+    /// Whether a provider in `region` satisfies the constraint. Providers that
+    /// do not declare a region are never compliant.
+    pub fn permits(&self, region: Option<&str>) -> bool {
+        region.map_or(false, |region| {
+            self.allowed_regions
+                .iter()
+                .any(|allowed| region_matches(allowed, region))
+        })
+    }
+
+    /// Whether a provider in `region` is in one of the preferred regions
+    pub fn prefers(&self, region: Option<&str>) -> bool {
+        region.map_or(false, |region| {
+            self.preferred_regions
+                .iter()
+                .any(|preferred| region_matches(preferred, region))
+        })
+    }
+}
+
+fn region_matches(pattern: &str, region: &str) -> bool {
+    let pattern = pattern.trim().to_ascii_lowercase();
+    let region = region.trim().to_ascii_lowercase();
+
+    !pattern.is_empty()
+        && (region == pattern
+            || (region.starts_with(&pattern) && region[pattern.len()..].starts_with('-')))
 }
 
 /// Cost constraints for provider selection
@@ -941,6 +1231,66 @@ pub struct ProviderSelectionResponse {
     pub estimated_cost: f64,
     /// Expected quality metrics
     pub expected_quality: QualityMetrics,
+    /// Score components behind a balanced selection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score_breakdown: Option<ProviderScoreBreakdown>,
+    /// Other candidates that were considered, best scoring or cheapest first
+    #[serde(default)]
+    pub alternatives: Vec<ProviderAlternative>,
+    /// Whether this was a preview that reserved no budget
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// A candidate provider that was not selected
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderAlternative {
+    /// Provider ID
+    pub provider_id: Uuid,
+    /// Provider name
+    pub name: String,
+    /// Estimated cost had it been selected
+    pub estimated_cost: f64,
+    /// Composite score under balanced selection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub composite_score: Option<f64>,
+}
+
+impl ProviderAlternative {
+    /// Describe a candidate by its listed per-request cost
+    pub fn from_provider(provider: &Provider) -> Self {
+        Self {
+            provider_id: provider.id,
+            name: provider.name.clone(),
+            estimated_cost: provider.cost_info.cost_per_request,
+            composite_score: None,
+        }
+    }
+}
+
+/// How a provider scored under balanced selection
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderScoreBreakdown {
+    /// Provider the scores belong to
+    pub provider_id: Uuid,
+    /// Recent average cost per request
+    pub recent_cost: f64,
+    /// 95th percentile latency (milliseconds)
+    pub p95_latency_ms: f64,
+    /// Quality score before weighting (0.0 - 1.0)
+    pub quality: f64,
+    /// Normalized cost score, cheapest candidate scores 1.0
+    pub cost_score: f64,
+    /// Normalized latency score, fastest candidate scores 1.0
+    pub latency_score: f64,
+    /// Normalized quality score
+    pub quality_score: f64,
+    /// Weights applied to the scores
+    pub weights: BalancedWeights,
+    /// Weighted composite of the scores
+    pub composite_score: f64,
 }
 
 /// Schema translation request
@@ -953,8 +1303,15 @@ pub struct SchemaTranslationRequest {
     pub source_version: String,
     /// Target schema version
     pub target_version: String,
+    /// Revision of the provider schema; bump it when the schema changes so
+    /// previously compiled translation plans are not reused
+    #[serde(default)]
+    pub schema_version: Option<String>,
     /// Client ID for custom mappings
     pub client_id: Option<Uuid>,
+    /// Field transforms applied in order to the translated data
+    #[serde(default)]
+    pub transforms: Vec<FieldTransform>,
 }
 
 /// Schema translation response
@@ -967,6 +1324,9 @@ pub struct SchemaTranslationResponse {
     pub translation_metadata: TranslationMetadata,
     /// Any validation warnings
     pub warnings: Vec<String>,
+    /// Fields whose transforms failed; those fields are left unchanged
+    #[serde(default)]
+    pub field_errors: Vec<FieldTransformError>,
 }
 
 /// Translation metadata
@@ -975,6 +1335,8 @@ pub struct SchemaTranslationResponse {
 pub struct TranslationMetadata {
     /// Translation ID used
     pub translation_id: Uuid,
+    /// Hash of the schema pair the translation plan was compiled for
+    pub schema_hash: String,
     /// Fields that were mapped
     pub mapped_fields: Vec<String>,
     /// Fields that were dropped
@@ -1044,6 +1406,22 @@ pub enum FederationError {
     #[error("Validation error: {field} - {message}")]
     ValidationError { field: String, message: String },
 
+    /// Operation is not valid for the workflow's current state
+    #[error("Invalid workflow state: {reason}")]
+    InvalidWorkflowState { reason: String },
+
+    /// No provider satisfies the data residency constraint
+    #[error("Data residency violation: {reason}")]
+    DataResidencyViolation { reason: String },
+
+    /// Spend would exceed the client's hard budget cap
+    #[error("Budget hard cap exceeded for client {client_id}: projected spend {projected_spend:.2} exceeds {hard_cap:.2}")]
+    BudgetExceeded {
+        client_id: Uuid,
+        projected_spend: f64,
+        hard_cap: f64,
+    },
+
     /// Internal server error
     #[error("Internal server error: {message}")]
     InternalError { message: String },
@@ -1060,6 +1438,9 @@ impl FederationError {
             FederationError::ResourceLimitExceeded { .. } => 429,
             FederationError::ValidationError { .. } => 400,
             FederationError::ConfigurationError { .. } => 400,
+            FederationError::InvalidWorkflowState { .. } => 409,
+            FederationError::DataResidencyViolation { .. } => 422,
+            FederationError::BudgetExceeded { .. } => 402,
             _ => 500,
         }
     }
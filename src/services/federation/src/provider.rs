@@ -5,9 +5,11 @@
 //! provider selection based on cost optimization, quality metrics, and availability.
 
 use crate::models::{
-    FederationError, Provider, ProviderConfig, ProviderSelectionRequest, ProviderSelectionResponse,
-    ProviderStatus, ProviderType, QualityMetrics,
+    DataResidencyConstraint, FederationError, Provider, ProviderAlternative, ProviderConfig,
+    ProviderSelectionRequest, ProviderSelectionResponse, ProviderStatus, ProviderType,
+    QualityMetrics,
 };
+use crate::telemetry;
 use crate::utils::{cache::CacheManager, database::DatabaseManager};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
@@ -15,7 +17,7 @@ use dashmap::DashMap;
 use redis::Client as RedisClient;
 use serde_json;
 use sqlx::PgPool;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tokio::time::{interval, Duration};
@@ -39,6 +41,25 @@ pub struct ProviderManager {
     health_monitor: Arc<ProviderHealthMonitor>,
     /// Selection engine for optimal provider selection
     selection_engine: Arc<ProviderSelectionEngine>,
+    /// Rolling output quality reported by downstream consumers, by provider
+    quality_feedback: Arc<DashMap<Uuid, ObservedQuality>>,
+}
+
+/// Number of feedback samples kept per provider for the rolling quality average
+const QUALITY_FEEDBACK_WINDOW: usize = 50;
+
+/// Samples required before observed quality fully replaces the configured score
+const QUALITY_FEEDBACK_WARMUP: usize = 10;
+
+/// Rolling quality metric built from downstream quality feedback
+#[derive(Debug, Clone, Default)]
+pub struct ObservedQuality {
+    /// Most recent normalized scores (0.0-1.0), oldest first
+    samples: VecDeque<f64>,
+    /// Sum of the samples currently in the window
+    sum: f64,
+    /// Last feedback timestamp
+    pub last_updated: Option<DateTime<Utc>>,
 }
 
 /// Provider registry for in-memory caching and fast lookups
@@ -107,6 +128,20 @@ pub struct ProviderHealthState {
     pub error_history: Vec<HealthCheckError>,
 }
 
+impl ProviderHealthState {
+    /// 95th percentile of the recorded response times (nearest rank)
+    pub fn p95_response_time(&self) -> Option<f64> {
+        if self.response_times.is_empty() {
+            return None;
+        }
+
+        let mut sorted = self.response_times.clone();
+        sorted.sort_unstable();
+        let rank = ((sorted.len() as f64) * 0.95).ceil() as usize;
+        Some(sorted[rank.saturating_sub(1)] as f64)
+    }
+}
+
 /// Health check error information
 #[derive(Debug, Clone)]
 pub struct HealthCheckError {
@@ -228,6 +263,7 @@ impl ProviderManager {
             provider_registry,
             health_monitor,
             selection_engine,
+            quality_feedback: Arc::new(DashMap::new()),
         };
 
         // Initialize provider registry from database
@@ -366,6 +402,10 @@ impl ProviderManager {
             provider.health_endpoint = health_endpoint;
         }
 
+        if let Some(region) = updates.region {
+            provider.region = region;
+        }
+
         provider.updated_at = Utc::now();
 
         // Save to database
@@ -405,38 +445,34 @@ impl ProviderManager {
         // Stop health monitoring
         self.health_monitor.remove_provider(provider_id).await?;
 
+        // Drop observed quality history
+        self.quality_feedback.remove(provider_id);
+
         info!("Successfully deleted provider: {}", provider_id);
 
         Ok(())
     }
 
     /// Select optimal provider based on criteria
+    ///
+    /// Dry runs return the same response without recording the selection.
+    #[tracing::instrument(
+        name = "provider_manager.select_provider",
+        skip_all,
+        fields(client_id = %request.client_id, correlation_id = tracing::field::Empty)
+    )]
     pub async fn select_provider(
         &self,
         request: ProviderSelectionRequest,
     ) -> Result<ProviderSelectionResponse, FederationError> {
+        telemetry::record_correlation_id();
         debug!("Selecting provider for client: {}", request.client_id);
 
-        // Get available providers of the requested type
-        let providers = self.get_providers_by_type(&request.service_type).await?;
+        let available_providers = self.get_available_providers(&request).await?;
 
-        // Filter providers based on availability and capabilities
-        let available_providers: Vec<Arc<Provider>> = providers
-            .into_iter()
-            .filter(|p| {
-                // Check status
-                matches!(p.status, ProviderStatus::Active) &&
-                // Check capabilities
-                request.required_capabilities.iter().all(|cap| p.capabilities.contains(cap))
-            })
-            .map(Arc::new)
-            .collect();
-
-        if available_providers.is_empty() {
-            return Err(FederationError::ProviderSelectionFailed {
-                reason: "No available providers match the criteria".to_string(),
-            });
-        }
+        // Residency is a hard constraint, applied before any scoring
+        let available_providers =
+            apply_residency_constraint(available_providers, request.residency.as_ref())?;
 
         // Use selection engine to choose the best provider
         let selected_provider = self
@@ -455,10 +491,28 @@ impl ProviderManager {
         // Get expected quality metrics
         let expected_quality = self.get_expected_quality(&selected_provider.id).await?;
 
+        let mut alternatives = Vec::with_capacity(available_providers.len().saturating_sub(1));
+        for provider in available_providers
+            .iter()
+            .filter(|p| p.id != selected_provider.id)
+        {
+            alternatives.push(ProviderAlternative {
+                estimated_cost: self.calculate_estimated_cost(provider, &request).await?,
+                ..ProviderAlternative::from_provider(provider)
+            });
+        }
+        alternatives.sort_by(|a, b| {
+            a.estimated_cost
+                .partial_cmp(&b.estimated_cost)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
         // Record selection for learning
-        self.selection_engine
-            .record_selection(&selected_provider.id, &request, estimated_cost)
-            .await?;
+        if !request.dry_run {
+            self.selection_engine
+                .record_selection(&selected_provider.id, &request, estimated_cost)
+                .await?;
+        }
 
         let reasoning = format!(
             "Selected {} based on optimal cost-quality ratio: ${:.4} estimated cost, {:.2}% success rate",
@@ -467,19 +521,119 @@ impl ProviderManager {
             expected_quality.success_rate * 100.0
         );
 
-        info!(
-            "Selected provider: {} for client: {}",
-            selected_provider.name, request.client_id
-        );
+        if request.dry_run {
+            info!(
+                "Simulated provider selection: {} for client: {}",
+                selected_provider.name, request.client_id
+            );
+        } else {
+            info!(
+                "Selected provider: {} for client: {}",
+                selected_provider.name, request.client_id
+            );
+        }
 
         Ok(ProviderSelectionResponse {
             provider: (*selected_provider).clone(),
             reasoning,
             estimated_cost,
             expected_quality,
+            score_breakdown: None,
+            alternatives,
+            dry_run: request.dry_run,
         })
     }
 
+    /// Get active providers of the requested type that offer every required
+    /// capability
+    pub async fn get_available_providers(
+        &self,
+        request: &ProviderSelectionRequest,
+    ) -> Result<Vec<Arc<Provider>>, FederationError> {
+        // Get available providers of the requested type
+        let providers = self.get_providers_by_type(&request.service_type).await?;
+
+        // Filter providers based on availability and capabilities
+        let available_providers: Vec<Arc<Provider>> = providers
+            .into_iter()
+            .filter(|p| {
+                // Check status
+                matches!(p.status, ProviderStatus::Active) &&
+                // Check capabilities
+                request.required_capabilities.iter().all(|cap| p.capabilities.contains(cap))
+            })
+            .map(Arc::new)
+            .collect();
+
+        if available_providers.is_empty() {
+            return Err(FederationError::ProviderSelectionFailed {
+                reason: "No available providers match the criteria".to_string(),
+            });
+        }
+
+        Ok(available_providers)
+    }
+
+    /// 95th percentile health check response time for a provider, in
+    /// milliseconds, if any successful checks have been recorded
+    pub fn p95_latency_ms(&self, provider_id: &Uuid) -> Option<f64> {
+        self.health_monitor
+            .health_states
+            .get(provider_id)
+            .and_then(|state| state.p95_response_time())
+    }
+
+    /// Record a downstream quality signal for a provider's output.
+    ///
+    /// `score` is normalized to 0.0-1.0. Returns the provider's new rolling average.
+    pub async fn record_quality_feedback(
+        &self,
+        provider_id: &Uuid,
+        score: f64,
+    ) -> Result<f64, FederationError> {
+        if !score.is_finite() || !(0.0..=1.0).contains(&score) {
+            return Err(FederationError::ValidationError {
+                field: "score".to_string(),
+                message: "Quality score must be between 0.0 and 1.0".to_string(),
+            });
+        }
+
+        if self.get_provider(provider_id).await?.is_none() {
+            return Err(FederationError::ProviderNotFound { id: *provider_id });
+        }
+
+        let mut observed = self.quality_feedback.entry(*provider_id).or_default();
+        observed.record(score);
+        let rolling_average = observed.rolling_average().unwrap_or(score);
+
+        debug!(
+            "Recorded quality feedback {:.3} for provider {} (rolling average {:.3} over {} samples)",
+            score,
+            provider_id,
+            rolling_average,
+            observed.sample_count()
+        );
+
+        Ok(rolling_average)
+    }
+
+    /// Get the observed quality for a provider, if any feedback has been recorded
+    pub fn get_observed_quality(&self, provider_id: &Uuid) -> Option<ObservedQuality> {
+        self.quality_feedback.get(provider_id).map(|q| q.clone())
+    }
+
+    /// Return the providers with their quality score replaced by the effective
+    /// score, so scoring strategies see observed rather than configured quality.
+    pub fn with_observed_quality(&self, providers: &[Arc<Provider>]) -> Vec<Arc<Provider>> {
+        providers
+            .iter()
+            .map(|provider| match self.quality_feedback.get(&provider.id) {
+                Some(observed) => apply_observed_quality(provider, &observed),
+                None => provider.clone(),
+            })
+            .collect()
+    }
+
     /// Start health monitoring background task
     pub async fn start_health_monitoring(&self) -> Result<(), FederationError> {
         info!("Starting provider health monitoring");
@@ -654,6 +808,105 @@ impl ProviderManager {
     }
 }
 
+impl ObservedQuality {
+    fn record(&mut self, score: f64) {
+        self.samples.push_back(score);
+        self.sum += score;
+
+        if self.samples.len() > QUALITY_FEEDBACK_WINDOW {
+            if let Some(oldest) = self.samples.pop_front() {
+                self.sum -= oldest;
+            }
+        }
+
+        self.last_updated = Some(Utc::now());
+    }
+
+    /// Number of samples in the rolling window
+    pub fn sample_count(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Average of the samples in the rolling window
+    pub fn rolling_average(&self) -> Option<f64> {
+        if self.samples.is_empty() {
+            None
+        } else {
+            Some(self.sum / self.samples.len() as f64)
+        }
+    }
+
+    /// Blend the rolling average with the configured score. Observed quality
+    /// gains weight with each sample until it fully takes over after warm-up.
+    pub fn effective_score(&self, configured_score: f64) -> f64 {
+        match self.rolling_average() {
+            Some(average) => {
+                let weight = (self.samples.len() as f64 / QUALITY_FEEDBACK_WARMUP as f64).min(1.0);
+                average * weight + configured_score * (1.0 - weight)
+            }
+            None => configured_score,
+        }
+    }
+}
+
+/// Restrict candidates to providers in allowed regions, narrowing further to
+/// preferred regions when any compliant provider is in one.
+///
+/// Returns `DataResidencyViolation` when no candidate is compliant, so callers
+/// never fall back to a non-compliant provider.
+pub fn apply_residency_constraint(
+    providers: Vec<Arc<Provider>>,
+    residency: Option<&DataResidencyConstraint>,
+) -> Result<Vec<Arc<Provider>>, FederationError> {
+    let residency = match residency {
+        Some(residency) => residency,
+        None => return Ok(providers),
+    };
+
+    if residency.allowed_regions.is_empty() {
+        return Err(FederationError::ValidationError {
+            field: "residency.allowedRegions".to_string(),
+            message: "At least one allowed region is required".to_string(),
+        });
+    }
+
+    let compliant: Vec<Arc<Provider>> = providers
+        .into_iter()
+        .filter(|p| residency.permits(p.region.as_deref()))
+        .collect();
+
+    if compliant.is_empty() {
+        return Err(FederationError::DataResidencyViolation {
+            reason: format!(
+                "No available provider is located in the allowed regions: {}",
+                residency.allowed_regions.join(", ")
+            ),
+        });
+    }
+
+    let preferred: Vec<Arc<Provider>> = compliant
+        .iter()
+        .filter(|p| residency.prefers(p.region.as_deref()))
+        .cloned()
+        .collect();
+
+    Ok(if preferred.is_empty() {
+        compliant
+    } else {
+        preferred
+    })
+}
+
+fn apply_observed_quality(provider: &Arc<Provider>, observed: &ObservedQuality) -> Arc<Provider> {
+    let mut adjusted = (**provider).clone();
+    adjusted.quality_metrics.quality_score =
+        observed.effective_score(provider.quality_metrics.quality_score);
+    if let Some(last_updated) = observed.last_updated {
+        adjusted.quality_metrics.last_updated = last_updated;
+    }
+    Arc::new(adjusted)
+}
+
 impl ProviderRegistry {
     async fn new() -> Result<Self, FederationError> {
         Ok(Self {
@@ -1031,6 +1284,7 @@ pub struct ProviderUpdateRequest {
     pub status: Option<ProviderStatus>,
     pub capabilities: Option<Vec<String>>,
     pub health_endpoint: Option<Option<String>>,
+    pub region: Option<Option<String>>,
 }
 
 #[cfg(test)]
@@ -1048,6 +1302,27 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_health_state_p95_response_time() {
+        let mut state = ProviderHealthState {
+            provider_id: Uuid::new_v4(),
+            status: ProviderStatus::Active,
+            last_success: None,
+            last_failure: None,
+            consecutive_failures: 0,
+            consecutive_successes: 0,
+            response_times: Vec::new(),
+            error_history: Vec::new(),
+        };
+        assert_eq!(state.p95_response_time(), None);
+
+        state.response_times = vec![120, 90, 100, 2000, 110, 95, 105, 130, 98, 102];
+        assert_eq!(state.p95_response_time(), Some(2000.0));
+
+        state.response_times = vec![80];
+        assert_eq!(state.p95_response_time(), Some(80.0));
+    }
+
     #[test]
     fn test_cost_optimized_strategy() {
         let strategy = CostOptimizedStrategy;
@@ -1119,6 +1394,163 @@ mod tests {
         assert_eq!(result4.name, "Provider A"); // Back to first
     }
 
+    #[test]
+    fn test_observed_quality_rolling_window() {
+        let mut observed = ObservedQuality::default();
+        assert_eq!(observed.rolling_average(), None);
+        assert_eq!(observed.effective_score(0.9), 0.9);
+
+        for _ in 0..QUALITY_FEEDBACK_WINDOW {
+            observed.record(0.2);
+        }
+        for _ in 0..QUALITY_FEEDBACK_WINDOW {
+            observed.record(0.6);
+        }
+
+        // Older samples fall out of the window
+        assert_eq!(observed.sample_count(), QUALITY_FEEDBACK_WINDOW);
+        assert!((observed.rolling_average().unwrap() - 0.6).abs() < 1e-9);
+        assert!((observed.effective_score(0.9) - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_observed_quality_blends_during_warmup() {
+        let mut observed = ObservedQuality::default();
+        for _ in 0..QUALITY_FEEDBACK_WARMUP / 2 {
+            observed.record(0.5);
+        }
+
+        // Half way through warm-up: equal weight to observed and configured
+        assert!((observed.effective_score(0.9) - 0.7).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_observed_quality_changes_balanced_selection() {
+        let strategy = BalancedStrategy;
+        let providers = vec![
+            Arc::new(create_test_provider_with_quality(
+                "Provider A",
+                1000.0,
+                0.95,
+            )),
+            Arc::new(create_test_provider_with_quality(
+                "Provider B",
+                1000.0,
+                0.90,
+            )),
+        ];
+        let request = create_test_selection_request();
+
+        let selected = strategy
+            .select_provider(&providers, &request)
+            .unwrap()
+            .unwrap();
+        assert_eq!(selected.name, "Provider A");
+
+        let mut poor_quality = ObservedQuality::default();
+        for _ in 0..QUALITY_FEEDBACK_WARMUP {
+            poor_quality.record(0.3);
+        }
+        let adjusted = vec![
+            apply_observed_quality(&providers[0], &poor_quality),
+            providers[1].clone(),
+        ];
+
+        let selected = strategy
+            .select_provider(&adjusted, &request)
+            .unwrap()
+            .unwrap();
+        assert_eq!(selected.name, "Provider B");
+    }
+
+    #[test]
+    fn test_residency_constraint_excludes_other_regions() {
+        let providers = vec![
+            Arc::new(create_test_provider_in_region(
+                "US Cheap",
+                0.10,
+                Some("us-east-1"),
+            )),
+            Arc::new(create_test_provider_in_region(
+                "EU",
+                0.50,
+                Some("eu-west-1"),
+            )),
+            Arc::new(create_test_provider_in_region("Unknown", 0.05, None)),
+        ];
+        let residency = DataResidencyConstraint {
+            allowed_regions: vec!["eu".to_string()],
+            preferred_regions: vec![],
+        };
+
+        let compliant = apply_residency_constraint(providers, Some(&residency)).unwrap();
+        assert_eq!(compliant.len(), 1);
+
+        // Even a cost-only strategy cannot pick the cheaper non-compliant providers
+        let selected = CostOptimizedStrategy
+            .select_provider(&compliant, &create_test_selection_request())
+            .unwrap()
+            .unwrap();
+        assert_eq!(selected.name, "EU");
+    }
+
+    #[test]
+    fn test_residency_constraint_without_compliant_provider() {
+        let providers = vec![Arc::new(create_test_provider_in_region(
+            "US",
+            0.10,
+            Some("us-east-1"),
+        ))];
+        let residency = DataResidencyConstraint {
+            allowed_regions: vec!["eu".to_string()],
+            preferred_regions: vec![],
+        };
+
+        let result = apply_residency_constraint(providers, Some(&residency));
+        assert!(matches!(
+            result,
+            Err(FederationError::DataResidencyViolation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_residency_preferred_regions() {
+        let providers = vec![
+            Arc::new(create_test_provider_in_region(
+                "Ireland",
+                0.10,
+                Some("eu-west-1"),
+            )),
+            Arc::new(create_test_provider_in_region(
+                "Frankfurt",
+                0.50,
+                Some("eu-central-1"),
+            )),
+        ];
+        let residency = DataResidencyConstraint {
+            allowed_regions: vec!["eu".to_string()],
+            preferred_regions: vec!["eu-central-1".to_string()],
+        };
+
+        let candidates = apply_residency_constraint(providers, Some(&residency)).unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].name, "Frankfurt");
+
+        // Prefix matching stops at region boundaries
+        assert!(!residency.permits(Some("europe-north1")));
+        assert!(!residency.permits(None));
+    }
+
+    fn create_test_provider_in_region(
+        name: &str,
+        cost_per_request: f64,
+        region: Option<&str>,
+    ) -> Provider {
+        let mut provider = create_test_provider(name, cost_per_request);
+        provider.region = region.map(str::to_string);
+        provider
+    }
+
     fn create_test_provider(name: &str, cost_per_request: f64) -> Provider {
         Provider {
             id: Uuid::new_v4(),
@@ -1154,6 +1586,7 @@ mod tests {
             status: ProviderStatus::Active,
             capabilities: vec!["test".to_string()],
             health_endpoint: None,
+            region: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
@@ -1176,6 +1609,10 @@ mod tests {
             required_capabilities: vec!["test".to_string()],
             cost_constraints: None,
             quality_requirements: None,
+            residency: None,
+            bypass_routing_cache: false,
+            balanced_weights: None,
+            dry_run: false,
         }
     }
 }
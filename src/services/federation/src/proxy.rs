@@ -4,8 +4,9 @@
 //! enabling seamless integration with client MCP servers, request proxying, connection management,
 //! and protocol translation between different MCP server implementations.
 
-use crate::config::ProxyConfig;
+use crate::config::{ProxyConfig, SaturationPolicy};
 use crate::models::FederationError;
+use crate::telemetry;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
@@ -13,8 +14,9 @@ use reqwest::Client;
 use serde_json;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::{Mutex, RwLock};
-use tracing::{debug, info};
+use std::time::Duration;
+use tokio::sync::{Mutex, OwnedSemaphorePermit, RwLock, Semaphore};
+use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 /// MCP proxy for handling client MCP server integration
@@ -39,6 +41,8 @@ pub struct McpProxy {
 pub struct ConnectionPool {
     /// Active connections by server ID
     connections: Arc<DashMap<Uuid, Arc<ServerConnection>>>,
+    /// Servers evicted for failing a health check, refused until one passes
+    evicted: Arc<DashMap<Uuid, EvictedTarget>>,
     /// Connection configuration
     config: ProxyConfig,
     /// Connection statistics
@@ -76,6 +80,44 @@ pub struct ServerConnection {
     pub last_activity: Arc<Mutex<DateTime<Utc>>>,
     /// Connection metrics
     pub metrics: Arc<Mutex<ConnectionMetrics>>,
+    /// Maximum concurrent connections to the server
+    pub max_connections: usize,
+    /// Free connection slots; closed when the server is evicted from the pool
+    slots: Arc<Semaphore>,
+}
+
+impl ServerConnection {
+    /// Connections currently in use
+    pub fn in_use(&self) -> usize {
+        self.max_connections
+            .saturating_sub(self.slots.available_permits())
+    }
+}
+
+/// A server kept out of the pool after failing a health check
+#[derive(Debug, Clone)]
+struct EvictedTarget {
+    /// URL to health check
+    url: String,
+    /// Consecutive failed health checks
+    failures: u32,
+    /// When the server is next health checked
+    retry_at: DateTime<Utc>,
+}
+
+/// A connection to a server, returned to the pool when dropped
+#[derive(Debug)]
+pub struct PooledConnection {
+    connection: Arc<ServerConnection>,
+    _slot: OwnedSemaphorePermit,
+}
+
+impl std::ops::Deref for PooledConnection {
+    type Target = ServerConnection;
+
+    fn deref(&self) -> &ServerConnection {
+        &self.connection
+    }
 }
 
 /// Connection status enumeration
@@ -184,6 +226,12 @@ pub struct ConnectionPoolStats {
     pub connection_failures: u64,
     /// Pool utilization
     pub pool_utilization: f64,
+    /// Requests turned away because their target's pool was saturated
+    pub rejected_requests: u64,
+    /// Targets evicted after failing a health check or sitting idle
+    pub evicted_targets: u64,
+    /// Targets refused until they pass a health check
+    pub unhealthy_targets: u64,
 }
 
 impl McpProxy {
@@ -192,6 +240,8 @@ impl McpProxy {
         let http_client = Arc::new(
             Client::builder()
                 .timeout(std::time::Duration::from_secs(config.request_timeout))
+                .pool_max_idle_per_host(config.target_pool.max_connections_per_target as usize)
+                .pool_idle_timeout(Duration::from_secs(config.target_pool.idle_timeout))
                 .build()
                 .map_err(|e| FederationError::InternalError {
                     message: format!("Failed to create HTTP client: {}", e),
@@ -228,8 +278,8 @@ impl McpProxy {
 
         let start_time = Utc::now();
 
-        // Get server connection
-        let connection = self.connection_pool.get_connection(server_id).await?;
+        // Take a connection from the server's pool, waiting or failing if it is saturated
+        let connection = self.connection_pool.acquire(server_id).await?;
 
         // Route the request
         let target_url = format!("{}{}", connection.url, path);
@@ -242,9 +292,20 @@ impl McpProxy {
         };
 
         // Make the request
-        let response = self
+        let response = match self
             .make_request(&target_url, method, headers, translated_body)
-            .await?;
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                let duration = (Utc::now() - start_time).num_milliseconds() as u64;
+                self.update_stats(false, duration).await;
+                self.connection_pool
+                    .update_connection_metrics(server_id, false)
+                    .await?;
+                return Err(e);
+            }
+        };
 
         // Update statistics
         let duration = (Utc::now() - start_time).num_milliseconds() as u64;
@@ -291,16 +352,89 @@ impl McpProxy {
     /// Get proxy metrics
     pub async fn metrics(&self) -> Result<serde_json::Value, FederationError> {
         let stats = self.stats.read().await;
+        let pool_stats = self.connection_pool.get_stats().await?;
 
         Ok(serde_json::json!({
             "proxy_requests_total": stats.total_requests,
             "proxy_requests_successful": stats.successful_requests,
             "proxy_requests_failed": stats.failed_requests,
             "proxy_avg_response_time": stats.avg_response_time,
-            "proxy_active_connections": stats.active_connections
+            "proxy_active_connections": pool_stats.active_connections,
+            "proxy_pool_utilization": pool_stats.pool_utilization,
+            "proxy_pool_rejected_requests": pool_stats.rejected_requests,
+            "proxy_pool_evicted_targets": pool_stats.evicted_targets,
+            "proxy_pool_unhealthy_targets": pool_stats.unhealthy_targets,
+            "proxy_pool_targets": self.connection_pool.target_utilization()
         }))
     }
 
+    /// Periodically evict idle targets and targets that fail health checks
+    pub async fn start_pool_maintenance(&self) -> Result<(), FederationError> {
+        info!("Starting MCP connection pool maintenance");
+
+        let proxy = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(
+                proxy.config.target_pool.health_check_interval.max(1),
+            ));
+
+            loop {
+                interval.tick().await;
+
+                proxy.connection_pool.evict_idle().await;
+                if let Err(e) = proxy.check_target_health().await {
+                    error!("MCP target health check cycle failed: {}", e);
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Health check every pooled target, evicting those that fail, and
+    /// readmit evicted targets whose backoff has elapsed once they pass.
+    /// Returns the number of targets evicted.
+    pub async fn check_target_health(&self) -> Result<usize, FederationError> {
+        let targets: Vec<Arc<ServerConnection>> = self
+            .connection_pool
+            .connections
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect();
+
+        let mut evicted = 0;
+        for target in targets {
+            if !self.target_is_healthy(&target.url).await
+                && self
+                    .connection_pool
+                    .evict_unhealthy(&target.server_id, "failed health check")
+                    .await
+            {
+                evicted += 1;
+            }
+        }
+
+        for (server_id, url) in self.connection_pool.due_for_recheck() {
+            if self.target_is_healthy(&url).await {
+                self.connection_pool.readmit(&server_id);
+            } else {
+                self.connection_pool.record_failed_check(server_id, url);
+            }
+        }
+
+        Ok(evicted)
+    }
+
+    async fn target_is_healthy(&self, url: &str) -> bool {
+        self.http_client
+            .get(format!("{}/health", url))
+            .timeout(Duration::from_secs(self.config.connection_timeout))
+            .send()
+            .await
+            .map(|response| response.status().is_success())
+            .unwrap_or(false)
+    }
+
     /// Stop the proxy
     pub async fn stop(&self) -> Result<(), FederationError> {
         info!("Stopping MCP proxy");
@@ -330,10 +464,20 @@ impl McpProxy {
             }
         };
 
-        // Add headers
+        // Add headers; a running workflow's correlation ID replaces any forwarded one
+        let correlation_id = telemetry::current_correlation_id();
         for (key, value) in headers {
+            if correlation_id.is_some()
+                && key.eq_ignore_ascii_case(telemetry::CORRELATION_ID_HEADER)
+            {
+                continue;
+            }
             request_builder = request_builder.header(key, value);
         }
+        if let Some(correlation_id) = correlation_id {
+            request_builder = request_builder
+                .header(telemetry::CORRELATION_ID_HEADER, correlation_id.to_string());
+        }
 
         // Add body if present
         if let Some(body) = body {
@@ -389,32 +533,206 @@ impl ConnectionPool {
     async fn new(config: ProxyConfig) -> Result<Self, FederationError> {
         Ok(Self {
             connections: Arc::new(DashMap::new()),
+            evicted: Arc::new(DashMap::new()),
             config,
             stats: Arc::new(RwLock::new(ConnectionPoolStats::default())),
         })
     }
 
+    /// A server's pooled connection, created on first use. Servers evicted
+    /// for failing a health check are refused until a later check passes.
     async fn get_connection(
         &self,
         server_id: &Uuid,
     ) -> Result<Arc<ServerConnection>, FederationError> {
+        if let Some(target) = self.evicted.get(server_id) {
+            return Err(FederationError::ExternalServiceError {
+                service: "mcp_server".to_string(),
+                message: format!(
+                    "MCP server {} is unhealthy; next health check at {}",
+                    server_id, target.retry_at
+                ),
+            });
+        }
+
         if let Some(connection) = self.connections.get(server_id) {
             Ok(connection.clone())
         } else {
             // Create new connection (stub implementation)
+            let max_connections =
+                self.config.target_pool.max_connections_per_target.max(1) as usize;
             let connection = Arc::new(ServerConnection {
                 server_id: *server_id,
                 url: format!("http://localhost:8080/{}", server_id), // Mock URL
                 status: Arc::new(Mutex::new(ConnectionStatus::Active)),
                 last_activity: Arc::new(Mutex::new(Utc::now())),
                 metrics: Arc::new(Mutex::new(ConnectionMetrics::default())),
+                max_connections,
+                slots: Arc::new(Semaphore::new(max_connections)),
             });
 
-            self.connections.insert(*server_id, connection.clone());
+            let connection = self
+                .connections
+                .entry(*server_id)
+                .or_insert(connection)
+                .clone();
+            self.stats.write().await.total_connections += 1;
             Ok(connection)
         }
     }
 
+    /// Take one of a server's connections. A saturated pool queues the caller
+    /// for up to `queue_timeout` or rejects it outright, depending on the
+    /// configured saturation policy, so one slow server cannot use up
+    /// connections other servers need.
+    async fn acquire(&self, server_id: &Uuid) -> Result<PooledConnection, FederationError> {
+        let connection = self.get_connection(server_id).await?;
+        let pool = &self.config.target_pool;
+
+        let slot = match pool.saturation_policy {
+            SaturationPolicy::Reject => connection.slots.clone().try_acquire_owned().ok(),
+            SaturationPolicy::Queue => tokio::time::timeout(
+                Duration::from_secs(pool.queue_timeout),
+                connection.slots.clone().acquire_owned(),
+            )
+            .await
+            .ok()
+            .and_then(|slot| slot.ok()),
+        };
+
+        match slot {
+            Some(slot) => {
+                *connection.last_activity.lock().await = Utc::now();
+                Ok(PooledConnection {
+                    connection,
+                    _slot: slot,
+                })
+            }
+            None => {
+                self.stats.write().await.rejected_requests += 1;
+                warn!(
+                    "Connection pool for MCP server {} is saturated ({} connections)",
+                    server_id, connection.max_connections
+                );
+                Err(FederationError::ResourceLimitExceeded {
+                    limit_type: format!("mcp_connections:{}", server_id),
+                })
+            }
+        }
+    }
+
+    /// Drop a server's connections. Requests queued for it fail straight away;
+    /// those already in flight finish normally. Returns whether it was pooled.
+    async fn evict(&self, server_id: &Uuid, reason: &str) -> bool {
+        let Some((_, connection)) = self.connections.remove(server_id) else {
+            return false;
+        };
+
+        connection.slots.close();
+        *connection.status.lock().await = ConnectionStatus::Broken;
+        self.stats.write().await.evicted_targets += 1;
+        warn!(
+            "Evicted MCP server {} from connection pool: {}",
+            server_id, reason
+        );
+        true
+    }
+
+    /// Evict a server that failed a health check and refuse it until a later
+    /// check passes
+    async fn evict_unhealthy(&self, server_id: &Uuid, reason: &str) -> bool {
+        let Some(url) = self
+            .connections
+            .get(server_id)
+            .map(|connection| connection.url.clone())
+        else {
+            return false;
+        };
+
+        self.record_failed_check(*server_id, url);
+        self.evict(server_id, reason).await
+    }
+
+    /// Push an evicted server's next health check back, doubling the backoff
+    /// with each consecutive failure
+    fn record_failed_check(&self, server_id: Uuid, url: String) {
+        let mut target = self.evicted.entry(server_id).or_insert(EvictedTarget {
+            url,
+            failures: 0,
+            retry_at: Utc::now(),
+        });
+        target.failures += 1;
+        target.retry_at = Utc::now() + self.eviction_backoff(target.failures);
+    }
+
+    fn eviction_backoff(&self, failures: u32) -> chrono::Duration {
+        let pool = &self.config.target_pool;
+        let backoff = pool
+            .eviction_backoff
+            .saturating_mul(1u64 << failures.saturating_sub(1).min(32))
+            .min(pool.max_eviction_backoff);
+        chrono::Duration::seconds(backoff as i64)
+    }
+
+    /// Evicted servers whose backoff has elapsed, with their URLs
+    fn due_for_recheck(&self) -> Vec<(Uuid, String)> {
+        let now = Utc::now();
+        self.evicted
+            .iter()
+            .filter(|entry| entry.retry_at <= now)
+            .map(|entry| (*entry.key(), entry.url.clone()))
+            .collect()
+    }
+
+    /// Let a server that passed its health check back into the pool
+    fn readmit(&self, server_id: &Uuid) {
+        if self.evicted.remove(server_id).is_some() {
+            info!(
+                "MCP server {} passed its health check and rejoined the pool",
+                server_id
+            );
+        }
+    }
+
+    /// Evict servers with no connection in use since `idle_timeout` ago
+    async fn evict_idle(&self) -> usize {
+        let cutoff =
+            Utc::now() - chrono::Duration::seconds(self.config.target_pool.idle_timeout as i64);
+
+        let mut idle = Vec::new();
+        for entry in self.connections.iter() {
+            let connection = entry.value();
+            if connection.in_use() == 0 && *connection.last_activity.lock().await <= cutoff {
+                idle.push(connection.server_id);
+            }
+        }
+
+        let mut evicted = 0;
+        for server_id in idle {
+            if self.evict(&server_id, "idle timeout").await {
+                evicted += 1;
+            }
+        }
+        evicted
+    }
+
+    /// Connections in use and capacity for each pooled server
+    fn target_utilization(&self) -> Vec<serde_json::Value> {
+        self.connections
+            .iter()
+            .map(|entry| {
+                let connection = entry.value();
+                let in_use = connection.in_use();
+                serde_json::json!({
+                    "server_id": connection.server_id,
+                    "in_use": in_use,
+                    "max_connections": connection.max_connections,
+                    "utilization": in_use as f64 / connection.max_connections as f64
+                })
+            })
+            .collect()
+    }
+
     async fn update_connection_metrics(
         &self,
         server_id: &Uuid,
@@ -439,11 +757,28 @@ impl ConnectionPool {
     }
 
     async fn get_stats(&self) -> Result<ConnectionPoolStats, FederationError> {
-        Ok(self.stats.read().await.clone())
+        let mut stats = self.stats.read().await.clone();
+
+        let (in_use, capacity) = self.connections.iter().fold((0, 0), |(used, cap), entry| {
+            (used + entry.in_use(), cap + entry.max_connections)
+        });
+        stats.active_connections = in_use as u64;
+        stats.idle_connections = (capacity - in_use) as u64;
+        stats.pool_utilization = if capacity > 0 {
+            in_use as f64 / capacity as f64
+        } else {
+            0.0
+        };
+        stats.unhealthy_targets = self.evicted.len() as u64;
+
+        Ok(stats)
     }
 
     async fn cleanup(&self) -> Result<(), FederationError> {
         info!("Cleaning up connection pool");
+        for entry in self.connections.iter() {
+            entry.slots.close();
+        }
         self.connections.clear();
         Ok(())
     }
@@ -492,6 +827,7 @@ pub struct ProxyResponse {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::TargetPoolConfig;
 
     #[tokio::test]
     async fn test_mcp_proxy_creation() {
@@ -512,6 +848,153 @@ mod tests {
         assert_eq!(pool.connections.len(), 1);
     }
 
+    fn pool_config(max_connections: u32, policy: SaturationPolicy) -> ProxyConfig {
+        ProxyConfig {
+            target_pool: TargetPoolConfig {
+                max_connections_per_target: max_connections,
+                saturation_policy: policy,
+                queue_timeout: 1,
+                ..TargetPoolConfig::default()
+            },
+            ..ProxyConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_saturated_target_is_rejected_without_starving_others() {
+        let pool = ConnectionPool::new(pool_config(2, SaturationPolicy::Reject))
+            .await
+            .unwrap();
+        let busy = Uuid::new_v4();
+        let other = Uuid::new_v4();
+
+        let first = pool.acquire(&busy).await.unwrap();
+        let _second = pool.acquire(&busy).await.unwrap();
+        assert!(matches!(
+            pool.acquire(&busy).await,
+            Err(FederationError::ResourceLimitExceeded { .. })
+        ));
+
+        // Other targets have their own connections
+        let _unaffected = pool.acquire(&other).await.unwrap();
+
+        let stats = pool.get_stats().await.unwrap();
+        assert_eq!(stats.active_connections, 3);
+        assert_eq!(stats.rejected_requests, 1);
+        assert_eq!(stats.pool_utilization, 0.75);
+
+        drop(first);
+        assert!(pool.acquire(&busy).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_queued_request_gets_the_next_free_connection() {
+        let pool = Arc::new(
+            ConnectionPool::new(pool_config(1, SaturationPolicy::Queue))
+                .await
+                .unwrap(),
+        );
+        let server_id = Uuid::new_v4();
+
+        let held = pool.acquire(&server_id).await.unwrap();
+        let waiter = {
+            let pool = pool.clone();
+            tokio::spawn(async move { pool.acquire(&server_id).await.map(|_| ()) })
+        };
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        drop(held);
+        assert!(waiter.await.unwrap().is_ok());
+
+        // Nobody releases this one, so the queued request times out
+        let _held = pool.acquire(&server_id).await.unwrap();
+        assert!(pool.acquire(&server_id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_evicted_and_idle_targets_leave_the_pool() {
+        let mut config = pool_config(1, SaturationPolicy::Queue);
+        config.target_pool.idle_timeout = 0;
+        let pool = Arc::new(ConnectionPool::new(config).await.unwrap());
+        let unhealthy = Uuid::new_v4();
+        let idle = Uuid::new_v4();
+        let busy = Uuid::new_v4();
+
+        // A request queued on an unhealthy target fails as soon as it is evicted
+        let held = pool.acquire(&unhealthy).await.unwrap();
+        let waiter = {
+            let pool = pool.clone();
+            tokio::spawn(async move { pool.acquire(&unhealthy).await.map(|_| ()) })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(pool.evict(&unhealthy, "failed health check").await);
+        assert!(waiter.await.unwrap().is_err());
+        assert!(matches!(
+            *held.status.lock().await,
+            ConnectionStatus::Broken
+        ));
+
+        pool.get_connection(&idle).await.unwrap();
+        let _busy = pool.acquire(&busy).await.unwrap();
+        assert_eq!(pool.evict_idle().await, 1);
+        assert!(!pool.connections.contains_key(&idle));
+        assert!(pool.connections.contains_key(&busy));
+
+        assert_eq!(pool.get_stats().await.unwrap().evicted_targets, 2);
+    }
+
+    #[tokio::test]
+    async fn test_unhealthy_target_is_refused_until_a_check_passes() {
+        let mut config = pool_config(1, SaturationPolicy::Reject);
+        config.target_pool.eviction_backoff = 0;
+        let pool = ConnectionPool::new(config).await.unwrap();
+        let server_id = Uuid::new_v4();
+
+        pool.get_connection(&server_id).await.unwrap();
+        assert!(
+            pool.evict_unhealthy(&server_id, "failed health check")
+                .await
+        );
+
+        // Eviction leaves a marker, so the next request can't recreate the target
+        assert!(matches!(
+            pool.acquire(&server_id).await,
+            Err(FederationError::ExternalServiceError { .. })
+        ));
+        assert!(!pool.connections.contains_key(&server_id));
+        assert_eq!(pool.get_stats().await.unwrap().unhealthy_targets, 1);
+
+        // A failed recheck keeps it out
+        let due = pool.due_for_recheck();
+        assert_eq!(due.len(), 1);
+        let (id, url) = due.into_iter().next().unwrap();
+        pool.record_failed_check(id, url);
+        assert_eq!(pool.evicted.get(&server_id).unwrap().failures, 2);
+        assert!(pool.acquire(&server_id).await.is_err());
+
+        pool.readmit(&server_id);
+        assert!(pool.acquire(&server_id).await.is_ok());
+        assert_eq!(pool.get_stats().await.unwrap().unhealthy_targets, 0);
+    }
+
+    #[tokio::test]
+    async fn test_eviction_backoff_doubles_up_to_the_cap() {
+        let mut config = ProxyConfig::default();
+        config.target_pool.eviction_backoff = 10;
+        config.target_pool.max_eviction_backoff = 25;
+        let pool = ConnectionPool::new(config).await.unwrap();
+
+        assert_eq!(pool.eviction_backoff(1).num_seconds(), 10);
+        assert_eq!(pool.eviction_backoff(2).num_seconds(), 20);
+        assert_eq!(pool.eviction_backoff(3).num_seconds(), 25);
+        assert_eq!(pool.eviction_backoff(u32::MAX).num_seconds(), 25);
+
+        // Backed off targets are not rechecked early
+        let server_id = Uuid::new_v4();
+        pool.record_failed_check(server_id, "http://mcp.invalid".to_string());
+        assert!(pool.due_for_recheck().is_empty());
+    }
+
     #[tokio::test]
     async fn test_protocol_translator() {
         let translator = ProtocolTranslator::new().await.unwrap();
@@ -550,6 +1033,7 @@ impl Default for ProxyConfig {
                 timeout: 60,
                 half_open_max_calls: 3,
             },
+            target_pool: crate::config::TargetPoolConfig::default(),
         }
     }
 }
@@ -0,0 +1,565 @@
+//! Routing Decision Cache for the Federation Service
+//!
+//! Repeated, near-identical provider selection requests reuse the previous
+//! decision instead of re-running an optimization strategy. Requests are
+//! embedded as normalized feature vectors and matched by cosine similarity
+//! within the same client and service type. A cached decision is only served
+//! after re-validating that its provider is still a healthy, in-budget candidate.
+
+use crate::config::RoutingCacheConfig;
+use crate::models::{Provider, ProviderSelectionRequest, ProviderStatus, ProviderType};
+use chrono::{DateTime, Duration, Utc};
+use dashmap::DashMap;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tracing::debug;
+use uuid::Uuid;
+
+/// Turns a selection request into a fixed-length embedding
+pub trait RequestEmbedder: std::fmt::Debug + Send + Sync {
+    /// Embed a request; vectors from one embedder must share a dimension
+    fn embed(&self, request: &ProviderSelectionRequest) -> Vec<f32>;
+}
+
+/// Feature-hashing embedder over the normalized request
+///
+/// Capabilities are lowercased and deduplicated, numeric constraints are
+/// bucketed on a log scale, and each feature is hashed into a signed slot.
+#[derive(Debug, Clone)]
+pub struct HashingEmbedder {
+    dimensions: usize,
+}
+
+impl HashingEmbedder {
+    pub fn new(dimensions: usize) -> Self {
+        Self {
+            dimensions: dimensions.max(1),
+        }
+    }
+}
+
+impl Default for HashingEmbedder {
+    fn default() -> Self {
+        Self::new(256)
+    }
+}
+
+impl RequestEmbedder for HashingEmbedder {
+    fn embed(&self, request: &ProviderSelectionRequest) -> Vec<f32> {
+        let mut vector = vec![0.0f32; self.dimensions];
+
+        for feature in request_features(request) {
+            let hash = fnv1a(feature.as_bytes());
+            let slot = (hash % self.dimensions as u64) as usize;
+            let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+            vector[slot] += sign;
+        }
+
+        let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            vector.iter_mut().for_each(|v| *v /= norm);
+        }
+        vector
+    }
+}
+
+/// Normalized features describing what a request asks for
+fn request_features(request: &ProviderSelectionRequest) -> Vec<String> {
+    let mut features = vec![format!("type:{:?}", request.service_type)];
+
+    let mut capabilities: Vec<String> = request
+        .required_capabilities
+        .iter()
+        .map(|c| c.trim().to_lowercase())
+        .filter(|c| !c.is_empty())
+        .collect();
+    capabilities.sort();
+    capabilities.dedup();
+    features.extend(capabilities.into_iter().map(|c| format!("cap:{}", c)));
+
+    if let Some(cost) = &request.cost_constraints {
+        if let Some(max) = cost.max_cost_per_request {
+            features.push(format!("cost:max_per_request:{}", log_bucket(max)));
+        }
+        if let Some(max) = cost.max_total_cost {
+            features.push(format!("cost:max_total:{}", log_bucket(max)));
+        }
+        if cost.prefer_cheaper {
+            features.push("cost:prefer_cheaper".to_string());
+        }
+    }
+
+    if let Some(quality) = &request.quality_requirements {
+        if let Some(rate) = quality.min_success_rate {
+            features.push(format!("quality:success_rate:{}", (rate * 20.0).round()));
+        }
+        if let Some(time) = quality.max_response_time {
+            features.push(format!("quality:response_time:{}", log_bucket(time)));
+        }
+        if let Some(availability) = quality.min_availability {
+            features.push(format!(
+                "quality:availability:{}",
+                (availability * 20.0).round()
+            ));
+        }
+    }
+
+    if let Some(residency) = &request.residency {
+        features.extend(
+            residency
+                .allowed_regions
+                .iter()
+                .map(|r| format!("region:allowed:{}", r.to_lowercase())),
+        );
+        features.extend(
+            residency
+                .preferred_regions
+                .iter()
+                .map(|r| format!("region:preferred:{}", r.to_lowercase())),
+        );
+    }
+
+    features
+}
+
+/// Quarter-octave bucket so nearby values share a feature
+fn log_bucket(value: f64) -> i64 {
+    if value <= 0.0 {
+        return i64::MIN;
+    }
+    (value.log2() * 4.0).round() as i64
+}
+
+/// FNV-1a, used instead of `DefaultHasher` so embeddings are stable across builds
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f64 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|v| v * v).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    (dot / (norm_a * norm_b)) as f64
+}
+
+/// A previously made routing decision
+#[derive(Debug, Clone)]
+struct CachedRoute {
+    embedding: Vec<f32>,
+    provider_id: Uuid,
+    strategy: String,
+    cached_at: DateTime<Utc>,
+}
+
+/// Result of re-validating a cached decision against current providers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevalidationOutcome {
+    /// Provider is still a healthy, in-budget candidate
+    Valid,
+    /// Provider is no longer among the candidates
+    ProviderMissing,
+    /// Provider is no longer active
+    ProviderUnhealthy,
+    /// Provider now costs more than the request allows
+    OverBudget,
+}
+
+/// Result of a routing cache lookup
+#[derive(Debug, Clone)]
+pub enum RoutingCacheLookup {
+    /// A similar request was routed before and the decision is still valid
+    Hit {
+        provider: Arc<Provider>,
+        strategy: String,
+        similarity: f64,
+    },
+    /// No reusable decision; the embedding can be passed to `insert`
+    Miss { embedding: Vec<f32> },
+    /// Caching is disabled or the request opted out
+    Bypassed,
+}
+
+#[derive(Debug, Default)]
+struct RoutingCacheCounters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    bypassed: AtomicU64,
+    revalidated: AtomicU64,
+    provider_missing: AtomicU64,
+    provider_unhealthy: AtomicU64,
+    over_budget: AtomicU64,
+}
+
+/// Routing cache statistics
+#[derive(Debug, Clone, Serialize)]
+pub struct RoutingCacheStats {
+    pub enabled: bool,
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub bypassed: u64,
+    /// Hits divided by non-bypassed lookups
+    pub hit_rate: f64,
+    pub revalidation: RevalidationStats,
+}
+
+/// Counts of re-validation outcomes for similar cached decisions
+#[derive(Debug, Clone, Serialize)]
+pub struct RevalidationStats {
+    pub valid: u64,
+    pub provider_missing: u64,
+    pub provider_unhealthy: u64,
+    pub over_budget: u64,
+}
+
+/// Similarity-keyed cache of provider routing decisions
+#[derive(Debug)]
+pub struct RoutingCache {
+    config: RoutingCacheConfig,
+    embedder: Arc<dyn RequestEmbedder>,
+    entries: DashMap<(Uuid, ProviderType), Vec<CachedRoute>>,
+    counters: RoutingCacheCounters,
+}
+
+impl RoutingCache {
+    /// Create a cache using the default hashing embedder
+    pub fn new(config: RoutingCacheConfig) -> Self {
+        Self::with_embedder(config, Arc::new(HashingEmbedder::default()))
+    }
+
+    /// Create a cache with a custom request embedder
+    pub fn with_embedder(config: RoutingCacheConfig, embedder: Arc<dyn RequestEmbedder>) -> Self {
+        Self {
+            config,
+            embedder,
+            entries: DashMap::new(),
+            counters: RoutingCacheCounters::default(),
+        }
+    }
+
+    /// Find a reusable decision for `request` among the current `candidates`
+    pub fn lookup(
+        &self,
+        request: &ProviderSelectionRequest,
+        candidates: &[Arc<Provider>],
+    ) -> RoutingCacheLookup {
+        if !self.config.enabled || request.bypass_routing_cache {
+            self.counters.bypassed.fetch_add(1, Ordering::Relaxed);
+            return RoutingCacheLookup::Bypassed;
+        }
+
+        let embedding = self.embedder.embed(request);
+        let key = (request.client_id, request.service_type.clone());
+        let now = Utc::now();
+        let ttl = Duration::seconds(self.config.ttl_seconds as i64);
+
+        let best = self.entries.get_mut(&key).and_then(|mut routes| {
+            routes.retain(|route| now - route.cached_at < ttl);
+            routes
+                .iter()
+                .enumerate()
+                .map(|(index, route)| (index, cosine_similarity(&embedding, &route.embedding)))
+                .filter(|(_, similarity)| *similarity >= self.config.similarity_threshold)
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(index, similarity)| (index, similarity, routes[index].clone()))
+        });
+
+        let Some((index, similarity, route)) = best else {
+            self.counters.misses.fetch_add(1, Ordering::Relaxed);
+            return RoutingCacheLookup::Miss { embedding };
+        };
+
+        let provider = candidates.iter().find(|p| p.id == route.provider_id);
+        let outcome = revalidate(provider.map(Arc::as_ref), request);
+        self.record_revalidation(outcome);
+
+        match (outcome, provider) {
+            (RevalidationOutcome::Valid, Some(provider)) => {
+                self.counters.hits.fetch_add(1, Ordering::Relaxed);
+                debug!(
+                    "Routing cache hit for client {} (similarity {:.3})",
+                    request.client_id, similarity
+                );
+                RoutingCacheLookup::Hit {
+                    provider: provider.clone(),
+                    strategy: route.strategy,
+                    similarity,
+                }
+            }
+            _ => {
+                debug!(
+                    "Evicting cached route to provider {}: {:?}",
+                    route.provider_id, outcome
+                );
+                if let Some(mut routes) = self.entries.get_mut(&key) {
+                    if routes.get(index).map(|r| r.cached_at) == Some(route.cached_at) {
+                        routes.remove(index);
+                    }
+                }
+                self.counters.misses.fetch_add(1, Ordering::Relaxed);
+                RoutingCacheLookup::Miss { embedding }
+            }
+        }
+    }
+
+    /// Remember the decision made for a request that missed the cache
+    pub fn insert(
+        &self,
+        request: &ProviderSelectionRequest,
+        embedding: Vec<f32>,
+        provider_id: Uuid,
+        strategy: &str,
+    ) {
+        if !self.config.enabled || self.config.max_entries_per_client == 0 {
+            return;
+        }
+
+        let mut routes = self
+            .entries
+            .entry((request.client_id, request.service_type.clone()))
+            .or_default();
+
+        routes.push(CachedRoute {
+            embedding,
+            provider_id,
+            strategy: strategy.to_string(),
+            cached_at: Utc::now(),
+        });
+
+        // Routes are appended in time order, so the oldest are at the front
+        let overflow = routes
+            .len()
+            .saturating_sub(self.config.max_entries_per_client);
+        routes.drain(..overflow);
+    }
+
+    /// Current cache statistics
+    pub fn stats(&self) -> RoutingCacheStats {
+        let hits = self.counters.hits.load(Ordering::Relaxed);
+        let misses = self.counters.misses.load(Ordering::Relaxed);
+        let lookups = hits + misses;
+
+        RoutingCacheStats {
+            enabled: self.config.enabled,
+            entries: self.entries.iter().map(|routes| routes.len()).sum(),
+            hits,
+            misses,
+            bypassed: self.counters.bypassed.load(Ordering::Relaxed),
+            hit_rate: if lookups == 0 {
+                0.0
+            } else {
+                hits as f64 / lookups as f64
+            },
+            revalidation: RevalidationStats {
+                valid: self.counters.revalidated.load(Ordering::Relaxed),
+                provider_missing: self.counters.provider_missing.load(Ordering::Relaxed),
+                provider_unhealthy: self.counters.provider_unhealthy.load(Ordering::Relaxed),
+                over_budget: self.counters.over_budget.load(Ordering::Relaxed),
+            },
+        }
+    }
+
+    fn record_revalidation(&self, outcome: RevalidationOutcome) {
+        let counter = match outcome {
+            RevalidationOutcome::Valid => &self.counters.revalidated,
+            RevalidationOutcome::ProviderMissing => &self.counters.provider_missing,
+            RevalidationOutcome::ProviderUnhealthy => &self.counters.provider_unhealthy,
+            RevalidationOutcome::OverBudget => &self.counters.over_budget,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Check that a cached provider may still serve `request`
+fn revalidate(
+    provider: Option<&Provider>,
+    request: &ProviderSelectionRequest,
+) -> RevalidationOutcome {
+    let Some(provider) = provider else {
+        return RevalidationOutcome::ProviderMissing;
+    };
+
+    if !matches!(provider.status, ProviderStatus::Active) {
+        return RevalidationOutcome::ProviderUnhealthy;
+    }
+
+    let max_cost = request
+        .cost_constraints
+        .as_ref()
+        .and_then(|c| c.max_cost_per_request);
+    if max_cost.is_some_and(|max| provider.cost_info.cost_per_request > max) {
+        return RevalidationOutcome::OverBudget;
+    }
+
+    RevalidationOutcome::Valid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        AuthMethod, CostConstraints, CostInfo, ProviderConfig, QualityMetrics, RateLimits,
+    };
+    use std::collections::HashMap;
+
+    fn provider(cost_per_request: f64) -> Arc<Provider> {
+        Arc::new(Provider {
+            id: Uuid::new_v4(),
+            name: "cached".to_string(),
+            provider_type: ProviderType::Llm,
+            config: ProviderConfig {
+                endpoint: "http://example.com".to_string(),
+                auth_method: AuthMethod::None,
+                timeout: 30000,
+                rate_limits: RateLimits {
+                    requests_per_second: None,
+                    requests_per_minute: None,
+                    requests_per_hour: None,
+                    concurrent_requests: None,
+                },
+                headers: HashMap::new(),
+            },
+            cost_info: CostInfo {
+                cost_per_request,
+                cost_per_token: None,
+                cost_per_gb: None,
+                cost_per_compute_hour: None,
+                minimum_cost: 0.0,
+                currency: "USD".to_string(),
+            },
+            quality_metrics: QualityMetrics {
+                avg_response_time: 100.0,
+                success_rate: 0.99,
+                availability: 0.99,
+                quality_score: 0.95,
+                last_updated: Utc::now(),
+            },
+            status: ProviderStatus::Active,
+            capabilities: vec!["chat".to_string()],
+            health_endpoint: None,
+            region: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        })
+    }
+
+    fn request(client_id: Uuid, capabilities: &[&str]) -> ProviderSelectionRequest {
+        ProviderSelectionRequest {
+            client_id,
+            service_type: ProviderType::Llm,
+            required_capabilities: capabilities.iter().map(|c| c.to_string()).collect(),
+            cost_constraints: None,
+            quality_requirements: None,
+            residency: None,
+            bypass_routing_cache: false,
+            balanced_weights: None,
+            dry_run: false,
+        }
+    }
+
+    fn cache_with(cached: &ProviderSelectionRequest, provider: &Provider) -> RoutingCache {
+        let cache = RoutingCache::new(RoutingCacheConfig::default());
+        let RoutingCacheLookup::Miss { embedding } = cache.lookup(cached, &[]) else {
+            panic!("empty cache should miss");
+        };
+        cache.insert(cached, embedding, provider.id, "balanced_optimizer");
+        cache
+    }
+
+    #[test]
+    fn test_normalized_requests_hit_cache() {
+        let client_id = Uuid::new_v4();
+        let selected = provider(0.01);
+        let cache = cache_with(&request(client_id, &["chat", "Streaming"]), &selected);
+
+        let similar = request(client_id, &[" streaming", "CHAT", "chat"]);
+        match cache.lookup(&similar, &[selected.clone()]) {
+            RoutingCacheLookup::Hit {
+                provider, strategy, ..
+            } => {
+                assert_eq!(provider.id, selected.id);
+                assert_eq!(strategy, "balanced_optimizer");
+            }
+            other => panic!("expected hit, got {:?}", other),
+        }
+
+        let different = request(client_id, &["embeddings"]);
+        assert!(matches!(
+            cache.lookup(&different, &[selected.clone()]),
+            RoutingCacheLookup::Miss { .. }
+        ));
+
+        let other_client = request(Uuid::new_v4(), &["chat", "streaming"]);
+        assert!(matches!(
+            cache.lookup(&other_client, &[selected]),
+            RoutingCacheLookup::Miss { .. }
+        ));
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 3);
+        assert_eq!(stats.hit_rate, 0.25);
+    }
+
+    #[test]
+    fn test_revalidation_evicts_stale_routes() {
+        let client_id = Uuid::new_v4();
+        let selected = provider(0.05);
+        let mut cached = request(client_id, &["chat"]);
+        cached.cost_constraints = Some(CostConstraints {
+            max_cost_per_request: Some(0.10),
+            max_total_cost: None,
+            prefer_cheaper: false,
+        });
+
+        let mut unhealthy = (*selected).clone();
+        unhealthy.status = ProviderStatus::Degraded;
+        let cache = cache_with(&cached, &selected);
+        assert!(matches!(
+            cache.lookup(&cached, &[Arc::new(unhealthy)]),
+            RoutingCacheLookup::Miss { .. }
+        ));
+        assert_eq!(cache.stats().revalidation.provider_unhealthy, 1);
+        assert_eq!(cache.stats().entries, 0);
+
+        let mut repriced = (*selected).clone();
+        repriced.cost_info.cost_per_request = 0.20;
+        let cache = cache_with(&cached, &selected);
+        assert!(matches!(
+            cache.lookup(&cached, &[Arc::new(repriced)]),
+            RoutingCacheLookup::Miss { .. }
+        ));
+        assert_eq!(cache.stats().revalidation.over_budget, 1);
+
+        let cache = cache_with(&cached, &selected);
+        assert!(matches!(
+            cache.lookup(&cached, &[]),
+            RoutingCacheLookup::Miss { .. }
+        ));
+        assert_eq!(cache.stats().revalidation.provider_missing, 1);
+    }
+
+    #[test]
+    fn test_bypass_flag_skips_cache() {
+        let client_id = Uuid::new_v4();
+        let selected = provider(0.01);
+        let cache = cache_with(&request(client_id, &["chat"]), &selected);
+
+        let mut bypass = request(client_id, &["chat"]);
+        bypass.bypass_routing_cache = true;
+        assert!(matches!(
+            cache.lookup(&bypass, &[selected]),
+            RoutingCacheLookup::Bypassed
+        ));
+        assert_eq!(cache.stats().bypassed, 1);
+    }
+}
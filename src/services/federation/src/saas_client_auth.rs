@@ -95,6 +95,28 @@ pub struct BrandProfile {
     pub guidelines_url: Option<String>,
     /// Content templates
     pub content_templates: Vec<ContentTemplate>,
+    /// Expected tone of voice
+    #[serde(default)]
+    pub tone: Option<BrandTone>,
+    /// Phrases that must never appear in published content
+    #[serde(default)]
+    pub banned_phrases: Vec<String>,
+    /// Preferred terminology, keyed by the discouraged term
+    #[serde(default)]
+    pub preferred_terms: HashMap<String, String>,
+    /// Target Flesch-Kincaid grade level
+    #[serde(default)]
+    pub target_reading_level: Option<f32>,
+}
+
+/// Tone of voice a brand writes in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BrandTone {
+    /// No contractions or exclamations
+    Formal,
+    /// Plain language without legalistic stock phrases
+    Conversational,
 }
 
 /// Content type enumeration
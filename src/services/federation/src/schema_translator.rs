@@ -5,22 +5,33 @@
 //! between different client schema versions and provider formats.
 
 use crate::models::{
-    FederationError, SchemaTranslation, SchemaTranslationRequest, SchemaTranslationResponse,
-    TranslationMetadata,
+    DateFormat, FederationError, FieldTransform, FieldTransformError, SchemaTranslation,
+    SchemaTranslationRequest, SchemaTranslationResponse, StringCase, TranslationMetadata,
 };
+use crate::telemetry;
 use crate::utils::{cache::CacheManager, database::DatabaseManager};
 use anyhow::Result;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Utc};
 use dashmap::DashMap;
+use redis::aio::ConnectionManager;
 use redis::Client as RedisClient;
+use serde::{Deserialize, Serialize};
 use serde_json;
 use sqlx::PgPool;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use tracing::{debug, info};
+use tokio::sync::{OnceCell, RwLock};
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+/// How long a compiled translation plan stays in Redis
+const PLAN_CACHE_TTL_SECS: u64 = 3600;
+
+/// Connect retries for the plan cache. The cache is optional, so a lookup
+/// fails fast and compiles the plan rather than waiting on Redis.
+const PLAN_CACHE_CONNECT_RETRIES: usize = 1;
+
 /// Schema translator for handling data transformation and compatibility
 #[derive(Debug, Clone)]
 pub struct SchemaTranslationService {
@@ -41,7 +52,6 @@ pub struct SchemaTranslationService {
 }
 
 /// Core translation engine
-#[derive(Debug)]
 pub struct TranslationEngine {
     /// Available translators by schema version pair
     translators: Arc<DashMap<String, Box<dyn VersionTranslator + Send + Sync>>>,
@@ -49,6 +59,32 @@ pub struct TranslationEngine {
     translation_history: Arc<DashMap<String, Vec<TranslationRecord>>>,
     /// Performance metrics
     performance_metrics: Arc<RwLock<TranslationPerformanceMetrics>>,
+    /// Redis client backing the compiled plan cache
+    redis_client: Arc<RedisClient>,
+    /// Multiplexed plan cache connection, opened on first use and shared by
+    /// every lookup
+    plan_cache_connection: OnceCell<ConnectionManager>,
+    /// Plans served from the cache
+    plan_cache_hits: AtomicU64,
+    /// Plans that had to be compiled
+    plan_cache_misses: AtomicU64,
+}
+
+/// Compiled translation plan for a source/target schema pair
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranslationPlan {
+    /// Stable hash of the schema pair and schema version
+    pub schema_hash: String,
+    /// Source schema version
+    pub source_version: String,
+    /// Target schema version
+    pub target_version: String,
+    /// Provider schema revision the plan was compiled against
+    pub schema_version: Option<String>,
+    /// Key of the translator that executes the plan
+    pub translator: String,
+    /// Compilation timestamp
+    pub compiled_at: DateTime<Utc>,
 }
 
 /// Translation statistics
@@ -100,6 +136,17 @@ pub struct TranslationPerformanceMetrics {
     pub cache_hit_rates: HashMap<String, f64>,
 }
 
+/// Outcome of applying field transforms to a document
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransformReport {
+    /// Concrete paths of fields that were transformed
+    pub mapped_fields: Vec<String>,
+    /// Concrete paths of fields that received a default value
+    pub defaulted_fields: Vec<String>,
+    /// Fields whose transform failed
+    pub errors: Vec<FieldTransformError>,
+}
+
 /// Schema translator trait for version-specific translations
 pub trait VersionTranslator: std::fmt::Debug {
     /// Translate data from source to target schema
@@ -125,7 +172,7 @@ impl SchemaTranslationService {
 
         let cache_manager = Arc::new(CacheManager::new(redis_client.clone()).await?);
         let db_manager = Arc::new(DatabaseManager::new(db_pool.clone()).await?);
-        let translation_engine = Arc::new(TranslationEngine::new().await?);
+        let translation_engine = Arc::new(TranslationEngine::new(redis_client.clone()).await?);
 
         Ok(Self {
             db_pool,
@@ -139,10 +186,20 @@ impl SchemaTranslationService {
     }
 
     /// Translate schema data
+    #[tracing::instrument(
+        name = "schema_translator.translate_schema",
+        skip_all,
+        fields(
+            source_version = %request.source_version,
+            target_version = %request.target_version,
+            correlation_id = tracing::field::Empty
+        )
+    )]
     pub async fn translate_schema(
         &self,
         request: SchemaTranslationRequest,
     ) -> Result<SchemaTranslationResponse, FederationError> {
+        telemetry::record_correlation_id();
         let start_time = Utc::now();
 
         debug!(
@@ -157,15 +214,25 @@ impl SchemaTranslationService {
             return Ok(cached_result);
         }
 
-        // Perform translation
-        let translated_data = self
+        // Resolve the compiled plan, then perform translation
+        let plan = self
             .translation_engine
-            .translate(
-                &request.source_data,
+            .plan(
                 &request.source_version,
                 &request.target_version,
+                request.schema_version.as_deref(),
             )
             .await?;
+        let mut translated_data = self
+            .translation_engine
+            .translate(&plan, &request.source_data)?;
+        let report = TranslationEngine::apply_transforms(&mut translated_data, &request.transforms);
+        if !report.errors.is_empty() {
+            warn!(
+                "{} field transform(s) failed during schema translation",
+                report.errors.len()
+            );
+        }
 
         let end_time = Utc::now();
         let duration_ms = (end_time - start_time).num_milliseconds() as u64;
@@ -173,9 +240,10 @@ impl SchemaTranslationService {
         // Generate metadata
         let metadata = TranslationMetadata {
             translation_id: Uuid::new_v4(),
-            mapped_fields: vec![], // This would be populated by the actual translator
+            schema_hash: plan.schema_hash,
+            mapped_fields: report.mapped_fields,
             dropped_fields: vec![],
-            defaulted_fields: vec![],
+            defaulted_fields: report.defaulted_fields,
             duration_ms,
         };
 
@@ -183,6 +251,7 @@ impl SchemaTranslationService {
             translated_data,
             translation_metadata: metadata,
             warnings: vec![],
+            field_errors: report.errors,
         };
 
         // Cache the result
@@ -217,6 +286,12 @@ impl SchemaTranslationService {
         Ok(vec![])
     }
 
+    /// Drop the cached translation plan for a schema hash, returning whether
+    /// one was cached
+    pub async fn invalidate_plan(&self, schema_hash: &str) -> Result<bool, FederationError> {
+        self.translation_engine.invalidate(schema_hash).await
+    }
+
     /// Get service health information
     pub async fn health(&self) -> Result<serde_json::Value, FederationError> {
         let stats = self.stats.read().await;
@@ -243,6 +318,7 @@ impl SchemaTranslationService {
     /// Get service metrics
     pub async fn metrics(&self) -> Result<serde_json::Value, FederationError> {
         let stats = self.stats.read().await;
+        let (plan_hits, plan_misses) = self.translation_engine.plan_cache_stats();
 
         Ok(serde_json::json!({
             "translations_total": stats.total_translations,
@@ -250,6 +326,8 @@ impl SchemaTranslationService {
             "translations_failed": stats.failed_translations,
             "avg_translation_time": stats.avg_translation_time,
             "cache_hit_rate": stats.cache_hit_rate,
+            "plan_cache_hits": plan_hits,
+            "plan_cache_misses": plan_misses,
             "cache_size": self.translation_cache.len(),
             "translators_loaded": self.translation_engine.translators.len()
         }))
@@ -264,6 +342,11 @@ impl SchemaTranslationService {
         hasher.update(request.source_version.as_bytes());
         hasher.update(request.target_version.as_bytes());
         hasher.update(request.source_data.to_string().as_bytes());
+        hasher.update(
+            serde_json::to_string(&request.transforms)
+                .unwrap_or_default()
+                .as_bytes(),
+        );
 
         if let Some(client_id) = request.client_id {
             hasher.update(client_id.to_string().as_bytes());
@@ -314,6 +397,11 @@ impl SchemaTranslationService {
         stats.avg_translation_time =
             (total_time + duration_ms as f64) / stats.total_translations as f64;
 
+        let (plan_hits, plan_misses) = self.translation_engine.plan_cache_stats();
+        if plan_hits + plan_misses > 0 {
+            stats.cache_hit_rate = plan_hits as f64 / (plan_hits + plan_misses) as f64;
+        }
+
         stats.last_updated = Utc::now();
     }
 
@@ -343,8 +431,21 @@ impl SchemaTranslationService {
     }
 }
 
+impl std::fmt::Debug for TranslationEngine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TranslationEngine")
+            .field("translators", &self.translators)
+            .field("translation_history", &self.translation_history)
+            .field("performance_metrics", &self.performance_metrics)
+            .field("redis_client", &self.redis_client)
+            .field("plan_cache_hits", &self.plan_cache_hits)
+            .field("plan_cache_misses", &self.plan_cache_misses)
+            .finish_non_exhaustive()
+    }
+}
+
 impl TranslationEngine {
-    async fn new() -> Result<Self, FederationError> {
+    async fn new(redis_client: Arc<RedisClient>) -> Result<Self, FederationError> {
         let translators = Arc::new(DashMap::new());
 
         // Initialize default translators
@@ -357,30 +458,478 @@ impl TranslationEngine {
             translators,
             translation_history: Arc::new(DashMap::new()),
             performance_metrics: Arc::new(RwLock::new(TranslationPerformanceMetrics::default())),
+            redis_client,
+            plan_cache_connection: OnceCell::new(),
+            plan_cache_hits: AtomicU64::new(0),
+            plan_cache_misses: AtomicU64::new(0),
         })
     }
 
-    async fn translate(
+    /// Stable hash of a source/target schema pair at a given schema version
+    pub fn schema_hash(
+        source_version: &str,
+        target_version: &str,
+        schema_version: Option<&str>,
+    ) -> String {
+        use sha2::{Digest, Sha256};
+
+        let mut hasher = Sha256::new();
+        // Length-prefix each part so ("a", "bc") and ("ab", "c") never collide
+        for part in [source_version, target_version] {
+            hasher.update((part.len() as u64).to_be_bytes());
+            hasher.update(part.as_bytes());
+        }
+        match schema_version {
+            Some(version) => {
+                hasher.update([1u8]);
+                hasher.update((version.len() as u64).to_be_bytes());
+                hasher.update(version.as_bytes());
+            }
+            None => hasher.update([0u8]),
+        }
+
+        hex::encode(hasher.finalize())
+    }
+
+    /// Resolve the translation plan for a schema pair, compiling and caching
+    /// it on a miss
+    pub async fn plan(
         &self,
-        data: &serde_json::Value,
         source_version: &str,
         target_version: &str,
+        schema_version: Option<&str>,
+    ) -> Result<TranslationPlan, FederationError> {
+        let schema_hash = Self::schema_hash(source_version, target_version, schema_version);
+
+        if let Some(plan) = self.cached_plan(&schema_hash).await {
+            self.plan_cache_hits.fetch_add(1, Ordering::Relaxed);
+            metrics::counter!("federation_schema_plan_cache_hits_total").increment(1);
+            debug!("Translation plan cache hit for {}", schema_hash);
+            return Ok(plan);
+        }
+
+        self.plan_cache_misses.fetch_add(1, Ordering::Relaxed);
+        metrics::counter!("federation_schema_plan_cache_misses_total").increment(1);
+        debug!("Translation plan cache miss for {}", schema_hash);
+
+        let plan =
+            self.compile_plan(schema_hash, source_version, target_version, schema_version)?;
+        self.store_plan(&plan).await;
+        Ok(plan)
+    }
+
+    /// Drop a cached plan, e.g. after a provider updates its schema
+    pub async fn invalidate(&self, schema_hash: &str) -> Result<bool, FederationError> {
+        let mut conn = self.connection().await?;
+        let removed: u64 = redis::cmd("DEL")
+            .arg(Self::plan_key(schema_hash))
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| FederationError::CacheError {
+                message: format!("Failed to invalidate translation plan: {}", e),
+            })?;
+
+        info!("Invalidated translation plan {}", schema_hash);
+        Ok(removed > 0)
+    }
+
+    /// Apply field transforms in order. A transform that fails on a field is
+    /// reported and leaves that field unchanged; later transforms still run.
+    pub fn apply_transforms(
+        data: &mut serde_json::Value,
+        transforms: &[FieldTransform],
+    ) -> TransformReport {
+        let mut report = TransformReport::default();
+
+        for transform in transforms {
+            let segments: Vec<&str> = transform.field().split('.').collect();
+            if segments.iter().any(|segment| segment.is_empty()) {
+                report.error(transform.field(), transform, "invalid field path");
+                continue;
+            }
+            apply_at_path(data, &segments, "", transform, &mut report);
+        }
+
+        report
+    }
+
+    /// Plan cache hit and miss counts
+    pub fn plan_cache_stats(&self) -> (u64, u64) {
+        (
+            self.plan_cache_hits.load(Ordering::Relaxed),
+            self.plan_cache_misses.load(Ordering::Relaxed),
+        )
+    }
+
+    fn translate(
+        &self,
+        plan: &TranslationPlan,
+        data: &serde_json::Value,
     ) -> Result<serde_json::Value, FederationError> {
+        match self.translators.get(&plan.translator) {
+            Some(translator) => {
+                translator.translate(data, &plan.source_version, &plan.target_version)
+            }
+            None => Err(FederationError::SchemaTranslationFailed {
+                reason: format!(
+                    "Translator {} for plan {} is not loaded",
+                    plan.translator, plan.schema_hash
+                ),
+            }),
+        }
+    }
+
+    fn compile_plan(
+        &self,
+        schema_hash: String,
+        source_version: &str,
+        target_version: &str,
+        schema_version: Option<&str>,
+    ) -> Result<TranslationPlan, FederationError> {
         let translator_key = format!("{}->{}", source_version, target_version);
 
-        if let Some(translator) = self.translators.get(&translator_key) {
-            translator.translate(data, source_version, target_version)
-        } else {
-            Err(FederationError::SchemaTranslationFailed {
+        if !self.translators.contains_key(&translator_key) {
+            return Err(FederationError::SchemaTranslationFailed {
                 reason: format!(
                     "No translator available for {} -> {}",
                     source_version, target_version
                 ),
+            });
+        }
+
+        Ok(TranslationPlan {
+            schema_hash,
+            source_version: source_version.to_string(),
+            target_version: target_version.to_string(),
+            schema_version: schema_version.map(str::to_string),
+            translator: translator_key,
+            compiled_at: Utc::now(),
+        })
+    }
+
+    fn plan_key(schema_hash: &str) -> String {
+        format!("federation:schema_plan:{}", schema_hash)
+    }
+
+    /// Shared plan cache connection. A failed connect is retried on the next
+    /// call; once open, the manager reconnects on its own.
+    async fn connection(&self) -> Result<ConnectionManager, FederationError> {
+        self.plan_cache_connection
+            .get_or_try_init(|| {
+                ConnectionManager::new_with_backoff(
+                    RedisClient::clone(&self.redis_client),
+                    2,
+                    100,
+                    PLAN_CACHE_CONNECT_RETRIES,
+                )
+            })
+            .await
+            .cloned()
+            .map_err(|e| FederationError::CacheError {
+                message: format!("Failed to connect to Redis: {}", e),
             })
+    }
+
+    /// Cached plan for a hash; cache failures are treated as misses
+    async fn cached_plan(&self, schema_hash: &str) -> Option<TranslationPlan> {
+        let result: Result<Option<String>, FederationError> = async {
+            let mut conn = self.connection().await?;
+            redis::cmd("GET")
+                .arg(Self::plan_key(schema_hash))
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| FederationError::CacheError {
+                    message: format!("Failed to read translation plan: {}", e),
+                })
+        }
+        .await;
+
+        match result {
+            Ok(Some(raw)) => match serde_json::from_str::<TranslationPlan>(&raw) {
+                Ok(plan) if plan.schema_hash == schema_hash => Some(plan),
+                Ok(_) => None,
+                Err(e) => {
+                    warn!(
+                        "Discarding unreadable translation plan {}: {}",
+                        schema_hash, e
+                    );
+                    None
+                }
+            },
+            Ok(None) => None,
+            Err(e) => {
+                warn!("Translation plan cache unavailable: {}", e);
+                None
+            }
+        }
+    }
+
+    async fn store_plan(&self, plan: &TranslationPlan) {
+        let result: Result<(), FederationError> = async {
+            let payload = serde_json::to_string(plan).map_err(|e| FederationError::CacheError {
+                message: format!("Failed to serialize translation plan: {}", e),
+            })?;
+            let mut conn = self.connection().await?;
+            redis::cmd("SET")
+                .arg(Self::plan_key(&plan.schema_hash))
+                .arg(payload)
+                .arg("EX")
+                .arg(PLAN_CACHE_TTL_SECS)
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| FederationError::CacheError {
+                    message: format!("Failed to cache translation plan: {}", e),
+                })
+        }
+        .await;
+
+        if let Err(e) = result {
+            warn!(
+                "Failed to cache translation plan {}: {}",
+                plan.schema_hash, e
+            );
+        }
+    }
+}
+
+impl TransformReport {
+    fn error(&mut self, field: &str, transform: &FieldTransform, message: impl Into<String>) {
+        self.errors.push(FieldTransformError {
+            field: field.to_string(),
+            transform: transform.name().to_string(),
+            message: message.into(),
+        });
+    }
+}
+
+fn join_path(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+/// Walk `segments` from `value`, fanning out over `[]` segments, and apply the
+/// transform to every field the path resolves to. Absent branches are skipped.
+fn apply_at_path(
+    value: &mut serde_json::Value,
+    segments: &[&str],
+    path: &str,
+    transform: &FieldTransform,
+    report: &mut TransformReport,
+) {
+    let Some((segment, rest)) = segments.split_first() else {
+        return;
+    };
+    let (key, each) = match segment.strip_suffix("[]") {
+        Some(key) => (key, true),
+        None => (*segment, false),
+    };
+    let field_path = join_path(path, key);
+
+    let child = if key.is_empty() {
+        value
+    } else {
+        let Some(object) = value.as_object_mut() else {
+            let at = if path.is_empty() { "$" } else { path };
+            report.error(at, transform, "expected an object");
+            return;
+        };
+
+        if rest.is_empty() && !each {
+            apply_to_field(object, key, &field_path, transform, report);
+            return;
+        }
+
+        match object.get_mut(key) {
+            Some(child) if !child.is_null() => child,
+            _ => return,
+        }
+    };
+
+    if !each {
+        apply_at_path(child, rest, &field_path, transform, report);
+        return;
+    }
+
+    let Some(items) = child.as_array_mut() else {
+        report.error(&field_path, transform, "expected an array");
+        return;
+    };
+    for (index, item) in items.iter_mut().enumerate() {
+        let item_path = format!("{}[{}]", field_path, index);
+        if rest.is_empty() {
+            apply_to_value(item, &item_path, transform, report);
+        } else {
+            apply_at_path(item, rest, &item_path, transform, report);
         }
     }
 }
 
+fn apply_to_field(
+    object: &mut serde_json::Map<String, serde_json::Value>,
+    key: &str,
+    path: &str,
+    transform: &FieldTransform,
+    report: &mut TransformReport,
+) {
+    match transform {
+        FieldTransform::Rename { to, .. } => {
+            if to == key || !object.contains_key(key) {
+                return;
+            }
+            if object.contains_key(to) {
+                report.error(path, transform, format!("field `{}` already exists", to));
+                return;
+            }
+            if let Some(value) = object.remove(key) {
+                object.insert(to.clone(), value);
+                report.mapped_fields.push(path.to_string());
+            }
+        }
+        FieldTransform::DefaultIfMissing { value, .. } => {
+            let present = matches!(object.get(key), Some(existing) if !existing.is_null());
+            if !present {
+                object.insert(key.to_string(), value.clone());
+                report.defaulted_fields.push(path.to_string());
+            }
+        }
+        _ => {
+            if let Some(value) = object.get_mut(key).filter(|value| !value.is_null()) {
+                apply_to_value(value, path, transform, report);
+            }
+        }
+    }
+}
+
+fn apply_to_value(
+    value: &mut serde_json::Value,
+    path: &str,
+    transform: &FieldTransform,
+    report: &mut TransformReport,
+) {
+    let result = match transform {
+        FieldTransform::DateFormat { from, to, .. } => {
+            parse_timestamp(value, from).and_then(|timestamp| format_timestamp(timestamp, to))
+        }
+        FieldTransform::NumberScale {
+            factor, decimals, ..
+        } => scale_number(value, *factor, *decimals),
+        FieldTransform::StringCase { case, .. } => change_case(value, *case),
+        FieldTransform::Rename { .. } | FieldTransform::DefaultIfMissing { .. } => {
+            Err("only applies to object fields".to_string())
+        }
+    };
+
+    match result {
+        Ok(transformed) => {
+            *value = transformed;
+            report.mapped_fields.push(path.to_string());
+        }
+        Err(message) => report.error(path, transform, message),
+    }
+}
+
+fn parse_timestamp(
+    value: &serde_json::Value,
+    format: &DateFormat,
+) -> Result<DateTime<Utc>, String> {
+    match format {
+        DateFormat::Iso8601 => {
+            let raw = value.as_str().ok_or("expected an ISO-8601 string")?;
+            DateTime::parse_from_rfc3339(raw)
+                .map(|timestamp| timestamp.with_timezone(&Utc))
+                .map_err(|e| format!("invalid ISO-8601 timestamp `{}`: {}", raw, e))
+        }
+        DateFormat::EpochMillis => value
+            .as_i64()
+            .and_then(|millis| Utc.timestamp_millis_opt(millis).single())
+            .ok_or_else(|| "expected epoch milliseconds".to_string()),
+        DateFormat::EpochSeconds => value
+            .as_i64()
+            .and_then(|secs| Utc.timestamp_opt(secs, 0).single())
+            .ok_or_else(|| "expected epoch seconds".to_string()),
+        DateFormat::Pattern(pattern) => {
+            let raw = value.as_str().ok_or("expected a date string")?;
+            DateTime::parse_from_str(raw, pattern)
+                .map(|timestamp| timestamp.with_timezone(&Utc))
+                .or_else(|_| {
+                    NaiveDateTime::parse_from_str(raw, pattern)
+                        .map(|naive| Utc.from_utc_datetime(&naive))
+                })
+                .or_else(|_| {
+                    NaiveDate::parse_from_str(raw, pattern)
+                        .map(|date| Utc.from_utc_datetime(&date.and_time(Default::default())))
+                })
+                .map_err(|e| format!("`{}` does not match `{}`: {}", raw, pattern, e))
+        }
+    }
+}
+
+fn format_timestamp(
+    timestamp: DateTime<Utc>,
+    format: &DateFormat,
+) -> Result<serde_json::Value, String> {
+    Ok(match format {
+        DateFormat::Iso8601 => {
+            serde_json::Value::from(timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+        }
+        DateFormat::EpochMillis => serde_json::Value::from(timestamp.timestamp_millis()),
+        DateFormat::EpochSeconds => serde_json::Value::from(timestamp.timestamp()),
+        DateFormat::Pattern(pattern) => {
+            use std::fmt::Write;
+
+            let mut formatted = String::new();
+            write!(formatted, "{}", timestamp.format(pattern))
+                .map_err(|_| format!("invalid date pattern `{}`", pattern))?;
+            serde_json::Value::from(formatted)
+        }
+    })
+}
+
+fn scale_number(
+    value: &serde_json::Value,
+    factor: f64,
+    decimals: Option<u32>,
+) -> Result<serde_json::Value, String> {
+    let number = value.as_f64().ok_or("expected a number")?;
+    let mut scaled = number * factor;
+    if let Some(decimals) = decimals {
+        let precision = 10f64.powi(decimals as i32);
+        scaled = (scaled * precision).round() / precision;
+    }
+
+    if decimals == Some(0) && scaled.abs() < i64::MAX as f64 {
+        return Ok(serde_json::Value::from(scaled as i64));
+    }
+    serde_json::Number::from_f64(scaled)
+        .map(serde_json::Value::Number)
+        .ok_or_else(|| format!("scaled value {} is not a finite number", scaled))
+}
+
+fn change_case(value: &serde_json::Value, case: StringCase) -> Result<serde_json::Value, String> {
+    let text = value.as_str().ok_or("expected a string")?;
+    let changed = match case {
+        StringCase::Upper => text.to_uppercase(),
+        StringCase::Lower => text.to_lowercase(),
+        StringCase::Title => {
+            let mut titled = String::with_capacity(text.len());
+            let mut word_start = true;
+            for ch in text.chars() {
+                if word_start {
+                    titled.extend(ch.to_uppercase());
+                } else {
+                    titled.extend(ch.to_lowercase());
+                }
+                word_start = ch.is_whitespace();
+            }
+            titled
+        }
+    };
+    Ok(serde_json::Value::from(changed))
+}
+
 // Example translator implementation
 #[derive(Debug)]
 struct V1ToV2Translator;
@@ -429,9 +978,185 @@ mod tests {
         assert_eq!(versions[0], ("v1.0".to_string(), "v2.0".to_string()));
     }
 
+    async fn test_engine() -> TranslationEngine {
+        let redis_client = Arc::new(RedisClient::open("redis://127.0.0.1/").unwrap());
+        TranslationEngine::new(redis_client).await.unwrap()
+    }
+
     #[tokio::test]
     async fn test_translation_engine_creation() {
-        let engine = TranslationEngine::new().await.unwrap();
+        let engine = test_engine().await;
         assert!(engine.translators.len() > 0);
+        assert_eq!(engine.plan_cache_stats(), (0, 0));
+    }
+
+    #[test]
+    fn test_schema_hash_is_stable() {
+        let first = TranslationEngine::schema_hash("v1.0", "v2.0", Some("3"));
+        let second = TranslationEngine::schema_hash("v1.0", "v2.0", Some("3"));
+
+        assert_eq!(first, second);
+        assert_eq!(first.len(), 64);
+    }
+
+    #[test]
+    fn test_schema_hash_tracks_schema_version() {
+        let unversioned = TranslationEngine::schema_hash("v1.0", "v2.0", None);
+        let v3 = TranslationEngine::schema_hash("v1.0", "v2.0", Some("3"));
+        let v4 = TranslationEngine::schema_hash("v1.0", "v2.0", Some("4"));
+
+        assert_ne!(unversioned, v3);
+        assert_ne!(v3, v4);
+        assert_ne!(
+            TranslationEngine::schema_hash("v1.0", "v2.0", Some("")),
+            unversioned
+        );
+        assert_ne!(
+            TranslationEngine::schema_hash("v1.0", "v2.0", None),
+            TranslationEngine::schema_hash("v2.0", "v1.0", None)
+        );
+        assert_ne!(
+            TranslationEngine::schema_hash("a", "bc", None),
+            TranslationEngine::schema_hash("ab", "c", None)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_compile_plan() {
+        let engine = test_engine().await;
+        let hash = TranslationEngine::schema_hash("v1.0", "v2.0", Some("3"));
+
+        let plan = engine
+            .compile_plan(hash.clone(), "v1.0", "v2.0", Some("3"))
+            .unwrap();
+        assert_eq!(plan.schema_hash, hash);
+        assert_eq!(plan.translator, "v1.0->v2.0");
+        assert_eq!(plan.schema_version.as_deref(), Some("3"));
+
+        let data = json!({"test": "value"});
+        assert_eq!(engine.translate(&plan, &data).unwrap(), data);
+
+        let unknown = TranslationEngine::schema_hash("v2.0", "v9.0", None);
+        assert!(engine.compile_plan(unknown, "v2.0", "v9.0", None).is_err());
+    }
+
+    #[test]
+    fn test_transforms_on_nested_object() {
+        let mut data = json!({
+            "customer": {
+                "full_name": "ada lovelace",
+                "profile": {"signed_up": "2024-03-01T12:30:00Z", "tier": null}
+            }
+        });
+        let transforms: Vec<FieldTransform> = serde_json::from_value(json!([
+            {"type": "rename", "field": "customer.full_name", "to": "name"},
+            {"type": "string_case", "field": "customer.name", "case": "title"},
+            {
+                "type": "date_format",
+                "field": "customer.profile.signed_up",
+                "from": "iso8601",
+                "to": "epoch_millis"
+            },
+            {"type": "default_if_missing", "field": "customer.profile.tier", "value": "free"},
+            {"type": "number_scale", "field": "customer.profile.tier", "factor": 2.0}
+        ]))
+        .unwrap();
+
+        let report = TranslationEngine::apply_transforms(&mut data, &transforms);
+
+        assert_eq!(
+            data,
+            json!({
+                "customer": {
+                    "name": "Ada Lovelace",
+                    "profile": {"signed_up": 1709296200000i64, "tier": "free"}
+                }
+            })
+        );
+        assert_eq!(
+            report.mapped_fields,
+            vec![
+                "customer.full_name",
+                "customer.name",
+                "customer.profile.signed_up"
+            ]
+        );
+        assert_eq!(report.defaulted_fields, vec!["customer.profile.tier"]);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].field, "customer.profile.tier");
+        assert_eq!(report.errors[0].transform, "number_scale");
+    }
+
+    #[test]
+    fn test_transforms_on_array_of_objects() {
+        let mut data = json!({
+            "orders": [
+                {"placed_at": "2024-01-15", "total_cents": 1999, "status": "shipped"},
+                {"placed_at": "not a date", "total_cents": 250},
+                {"placed_at": "2024-02-01", "total_cents": "oops", "status": "PENDING"}
+            ]
+        });
+        let transforms = vec![
+            FieldTransform::DateFormat {
+                field: "orders[].placed_at".to_string(),
+                from: DateFormat::Pattern("%Y-%m-%d".to_string()),
+                to: DateFormat::EpochSeconds,
+            },
+            FieldTransform::NumberScale {
+                field: "orders[].total_cents".to_string(),
+                factor: 0.01,
+                decimals: Some(2),
+            },
+            FieldTransform::Rename {
+                field: "orders[].total_cents".to_string(),
+                to: "total".to_string(),
+            },
+            FieldTransform::StringCase {
+                field: "orders[].status".to_string(),
+                case: StringCase::Upper,
+            },
+        ];
+
+        let report = TranslationEngine::apply_transforms(&mut data, &transforms);
+
+        assert_eq!(
+            data,
+            json!({
+                "orders": [
+                    {"placed_at": 1705276800, "total": 19.99, "status": "SHIPPED"},
+                    {"placed_at": "not a date", "total": 2.5},
+                    {"placed_at": 1706745600, "total": "oops", "status": "PENDING"}
+                ]
+            })
+        );
+
+        let failed: Vec<(&str, &str)> = report
+            .errors
+            .iter()
+            .map(|error| (error.field.as_str(), error.transform.as_str()))
+            .collect();
+        assert_eq!(
+            failed,
+            vec![
+                ("orders[1].placed_at", "date_format"),
+                ("orders[2].total_cents", "number_scale")
+            ]
+        );
+        assert!(report.defaulted_fields.is_empty());
+    }
+
+    #[test]
+    fn test_rename_onto_existing_field_is_reported() {
+        let mut data = json!({"id": 1, "legacy_id": 2});
+        let transforms = vec![FieldTransform::Rename {
+            field: "legacy_id".to_string(),
+            to: "id".to_string(),
+        }];
+
+        let report = TranslationEngine::apply_transforms(&mut data, &transforms);
+
+        assert_eq!(data, json!({"id": 1, "legacy_id": 2}));
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].field, "legacy_id");
     }
 }
@@ -208,6 +208,10 @@ async fn create_router(state: ServerState, config: &Config) -> Result<Router, Fe
             "/providers/select",
             post(handlers::providers::select_provider),
         )
+        .route(
+            "/providers/:id/quality-feedback",
+            post(handlers::providers::record_quality_feedback),
+        )
         // Schema translation endpoints
         .route(
             "/schema/translate",
@@ -221,6 +225,10 @@ async fn create_router(state: ServerState, config: &Config) -> Result<Router, Fe
             "/schema/translations/:id",
             get(handlers::schema::get_translation),
         )
+        .route(
+            "/schema/plans/:hash",
+            delete(handlers::schema::invalidate_translation_plan),
+        )
         // Workflow execution endpoints
         .route("/workflows", post(handlers::workflows::create_workflow))
         .route("/workflows", get(handlers::workflows::list_workflows))
@@ -242,6 +250,18 @@ async fn create_router(state: ServerState, config: &Config) -> Result<Router, Fe
             "/workflows/:id/cancel",
             post(handlers::workflows::cancel_workflow),
         )
+        .route(
+            "/workflow-executions/:id",
+            get(handlers::workflows::get_workflow_execution),
+        )
+        .route(
+            "/workflow-executions/:id/cancel",
+            post(handlers::workflows::cancel_workflow_execution),
+        )
+        .route(
+            "/workflow-executions/:id/retry",
+            post(handlers::workflows::retry_workflow_execution),
+        )
         // MCP proxy endpoints
         .route(
             "/proxy/mcp/:server_id/*path",
@@ -266,6 +286,10 @@ async fn create_router(state: ServerState, config: &Config) -> Result<Router, Fe
             "/cost/reports/:client_id",
             get(handlers::cost::get_client_cost_report),
         )
+        .route(
+            "/cost/budgets/:id/rollover",
+            post(handlers::cost::rollover_budget_window),
+        )
         // Authentication endpoints
         .route("/auth/login", post(handlers::auth::login))
         .route("/auth/refresh", post(handlers::auth::refresh_token))
@@ -9,7 +9,66 @@ use crate::models::FederationError;
 use anyhow::Result;
 use serde_json;
 use std::collections::HashMap;
+use std::future::Future;
+use tracing::Instrument;
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
+use uuid::Uuid;
+
+/// Header carrying the workflow correlation ID on outbound provider and MCP requests
+pub const CORRELATION_ID_HEADER: &str = "x-correlation-id";
+
+tokio::task_local! {
+    /// Correlation ID of the workflow the current task is working on
+    static CORRELATION_ID: Uuid;
+}
+
+/// Generate a correlation ID for a new workflow
+pub fn new_correlation_id() -> Uuid {
+    Uuid::new_v4()
+}
+
+/// Correlation ID of the workflow the current task is running, if any
+pub fn current_correlation_id() -> Option<Uuid> {
+    CORRELATION_ID.try_with(|id| *id).ok()
+}
+
+/// Correlation ID sent by the caller, or a fresh one when it sent none
+pub fn correlation_id_from_headers(headers: &axum::http::HeaderMap) -> Uuid {
+    headers
+        .get(CORRELATION_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| Uuid::parse_str(value).ok())
+        .unwrap_or_else(new_correlation_id)
+}
+
+/// Span covering one run of a workflow
+pub fn workflow_span(correlation_id: Uuid, workflow_id: Uuid) -> tracing::Span {
+    tracing::info_span!("workflow", %correlation_id, %workflow_id)
+}
+
+/// Run `future` inside the workflow's span, with its correlation ID available
+/// to [`current_correlation_id`] for everything it calls
+pub async fn with_correlation_id<F: Future>(
+    correlation_id: Uuid,
+    workflow_id: Uuid,
+    future: F,
+) -> F::Output {
+    CORRELATION_ID
+        .scope(
+            correlation_id,
+            future.instrument(workflow_span(correlation_id, workflow_id)),
+        )
+        .await
+}
+
+/// Record the current workflow's correlation ID on the current span. Component
+/// spans declare `correlation_id = tracing::field::Empty` and call this on entry,
+/// so their events can be matched to a workflow even outside its span.
+pub fn record_correlation_id() {
+    if let Some(correlation_id) = current_correlation_id() {
+        tracing::Span::current().record("correlation_id", tracing::field::display(correlation_id));
+    }
+}
 
 /// Initialize telemetry system
 pub fn init_tracing(config: &TelemetryConfig) -> Result<(), FederationError> {
@@ -90,4 +149,33 @@ mod tests {
         let manager = TelemetryManager::new(config).await.unwrap();
         assert!(manager.config.logging.level.len() > 0);
     }
+
+    #[tokio::test]
+    async fn test_correlation_id_scoped_to_workflow() {
+        assert_eq!(current_correlation_id(), None);
+
+        let correlation_id = new_correlation_id();
+        let seen = with_correlation_id(correlation_id, Uuid::new_v4(), async {
+            tokio::task::yield_now().await;
+            current_correlation_id()
+        })
+        .await;
+
+        assert_eq!(seen, Some(correlation_id));
+        assert_eq!(current_correlation_id(), None);
+    }
+
+    #[test]
+    fn test_correlation_id_from_headers() {
+        let correlation_id = new_correlation_id();
+        let mut headers = axum::http::HeaderMap::new();
+        headers.insert(
+            CORRELATION_ID_HEADER,
+            correlation_id.to_string().parse().unwrap(),
+        );
+        assert_eq!(correlation_id_from_headers(&headers), correlation_id);
+
+        headers.insert(CORRELATION_ID_HEADER, "not-a-uuid".parse().unwrap());
+        assert_ne!(correlation_id_from_headers(&headers), correlation_id);
+    }
 }
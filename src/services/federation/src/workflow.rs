@@ -5,15 +5,23 @@
 //! management across multiple providers and clients.
 
 use crate::config::Config;
-use crate::models::{FederatedWorkflow, FederationError, WorkflowExecution, WorkflowStatus};
+use crate::cost_optimizer::CostOptimizer;
+use crate::models::{
+    ExecutionAttempt, ExecutionError, FederatedWorkflow, FederationError, RetryFrom, StepExecution,
+    WorkflowExecution, WorkflowStatus, WorkflowStep,
+};
+use crate::telemetry;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
 use serde_json;
 use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
-use tracing::{debug, error, info};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn, Instrument};
 use uuid::Uuid;
 
 /// Workflow engine for federated workflow execution
@@ -29,10 +37,92 @@ pub struct WorkflowEngine {
     workflow_executor: Arc<WorkflowExecutor>,
     /// Active workflows
     active_workflows: Arc<DashMap<Uuid, Arc<RwLock<WorkflowExecution>>>>,
+    /// Workflow definitions by workflow ID
+    workflow_definitions: Arc<DashMap<Uuid, FederatedWorkflow>>,
+    /// Workflow ID for each execution ID
+    execution_index: Arc<DashMap<Uuid, Uuid>>,
+    /// Durable per-step result storage
+    step_store: Arc<StepResultStore>,
+    /// Cancellation and deadline of each running execution, by execution ID
+    running: Arc<DashMap<Uuid, ExecutionControl>>,
+    /// Budget held for steps while they run
+    budget_ledger: Option<Arc<dyn BudgetLedger>>,
     /// Workflow statistics
     stats: Arc<RwLock<WorkflowStats>>,
 }
 
+/// Holds client budget for steps while they run
+#[async_trait::async_trait]
+pub trait BudgetLedger: std::fmt::Debug + Send + Sync {
+    /// Hold `amount` of the client's budget, failing if it would exceed a hard cap
+    async fn reserve(&self, client_id: &Uuid, amount: f64) -> Result<(), FederationError>;
+
+    /// Fail if holding `amount` would exceed a hard cap, without holding it
+    async fn check(&self, client_id: &Uuid, amount: f64) -> Result<(), FederationError>;
+
+    /// Return held budget to the client
+    async fn release(&self, client_id: &Uuid, amount: f64) -> Result<(), FederationError>;
+}
+
+#[async_trait::async_trait]
+impl BudgetLedger for CostOptimizer {
+    async fn reserve(&self, client_id: &Uuid, amount: f64) -> Result<(), FederationError> {
+        self.reserve_budget(client_id, amount).await
+    }
+
+    async fn check(&self, client_id: &Uuid, amount: f64) -> Result<(), FederationError> {
+        self.enforce_budget(client_id, amount).await
+    }
+
+    async fn release(&self, client_id: &Uuid, amount: f64) -> Result<(), FederationError> {
+        self.release_budget(client_id, amount).await
+    }
+}
+
+/// Cancellation and deadline shared by the steps of one execution run
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionControl {
+    cancellation: CancellationToken,
+    deadline: Option<tokio::time::Instant>,
+}
+
+/// Why a run stopped before all of its steps finished
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Interruption {
+    Cancelled,
+    TimedOut,
+}
+
+/// Durable storage of per-step results, so retries can reuse completed steps
+#[derive(Debug)]
+pub struct StepResultStore {
+    /// Database connection pool
+    db_pool: Arc<PgPool>,
+}
+
+/// Runs a single workflow step against its provider
+#[async_trait::async_trait]
+pub trait StepRunner: std::fmt::Debug + Send + Sync {
+    /// Execute `step` with the outputs of its dependencies as inputs
+    async fn run_step(
+        &self,
+        workflow: &FederatedWorkflow,
+        step: &WorkflowStep,
+        inputs: &HashMap<String, serde_json::Value>,
+    ) -> Result<StepOutput, FederationError>;
+}
+
+/// Output of a successfully executed step
+#[derive(Debug, Clone)]
+pub struct StepOutput {
+    /// Step result
+    pub result: serde_json::Value,
+    /// Cost incurred by the step
+    pub cost: f64,
+    /// Provider that executed the step
+    pub provider_id: Option<Uuid>,
+}
+
 /// Mock Temporal client for demo purposes
 #[derive(Debug)]
 pub struct TemporalClient {
@@ -88,6 +178,7 @@ impl WorkflowEngine {
     pub async fn new(config: Arc<Config>, db_pool: Arc<PgPool>) -> Result<Self, FederationError> {
         let temporal_client = Arc::new(TemporalClient::new(config.clone()).await?);
         let workflow_executor = Arc::new(WorkflowExecutor::new(config.clone()).await?);
+        let step_store = Arc::new(StepResultStore::new(db_pool.clone()).await?);
 
         Ok(Self {
             config,
@@ -95,10 +186,21 @@ impl WorkflowEngine {
             temporal_client,
             workflow_executor,
             active_workflows: Arc::new(DashMap::new()),
+            workflow_definitions: Arc::new(DashMap::new()),
+            execution_index: Arc::new(DashMap::new()),
+            step_store,
+            running: Arc::new(DashMap::new()),
+            budget_ledger: None,
             stats: Arc::new(RwLock::new(WorkflowStats::default())),
         })
     }
 
+    /// Reserve each step's `cost_budget` from the client's budget while it runs
+    pub fn with_budget_ledger(mut self, ledger: Arc<dyn BudgetLedger>) -> Self {
+        self.budget_ledger = Some(ledger);
+        self
+    }
+
     /// Create a new workflow
     pub async fn create_workflow(
         &self,
@@ -119,6 +221,7 @@ impl WorkflowEngine {
         let execution = WorkflowExecution {
             id: Uuid::new_v4(),
             workflow_id: workflow.id,
+            correlation_id: telemetry::new_correlation_id(),
             status: WorkflowStatus::Pending,
             started_at: Utc::now(),
             ended_at: None,
@@ -133,12 +236,20 @@ impl WorkflowEngine {
                 disk_io: 0,
                 api_calls: 0,
             },
+            attempts: vec![],
         };
 
+        let correlation_id = execution.correlation_id;
+        self.execution_index.insert(execution.id, workflow.id);
+        self.workflow_definitions
+            .insert(workflow.id, workflow.clone());
         self.active_workflows
             .insert(workflow.id, Arc::new(RwLock::new(execution)));
 
-        info!("Workflow created successfully: {}", workflow.id);
+        info!(
+            %correlation_id,
+            "Workflow created successfully: {}", workflow.id
+        );
         Ok(workflow)
     }
 
@@ -149,40 +260,116 @@ impl WorkflowEngine {
     ) -> Result<WorkflowExecution, FederationError> {
         info!("Executing workflow: {}", workflow_id);
 
-        let execution = self.active_workflows.get(workflow_id).ok_or_else(|| {
-            FederationError::WorkflowExecutionFailed {
+        let execution = self
+            .active_workflows
+            .get(workflow_id)
+            .map(|e| e.clone())
+            .ok_or_else(|| FederationError::WorkflowExecutionFailed {
                 reason: format!("Workflow not found: {}", workflow_id),
-            }
-        })?;
+            })?;
+        let workflow = self.get_workflow_definition(workflow_id)?;
 
         let mut execution_guard = execution.write().await;
         execution_guard.status = WorkflowStatus::Running;
         execution_guard.started_at = Utc::now();
+        execution_guard.ended_at = None;
+        execution_guard.error = None;
+        execution_guard.step_executions.clear();
+        execution_guard.total_cost = 0.0;
+        execution_guard.attempts.clear();
 
-        // Execute workflow using Temporal (stub implementation)
-        let result = self.workflow_executor.execute_workflow(workflow_id).await;
+        let correlation_id = execution_guard.correlation_id;
+        telemetry::with_correlation_id(
+            correlation_id,
+            workflow.id,
+            self.run_execution(&workflow, &mut execution_guard, &[], None),
+        )
+        .await?;
 
-        match result {
-            Ok(_) => {
-                execution_guard.status = WorkflowStatus::Completed;
-                execution_guard.ended_at = Some(Utc::now());
-                self.update_stats(true, 1000).await; // Mock 1 second execution time
-                info!("Workflow completed successfully: {}", workflow_id);
-            }
-            Err(e) => {
-                execution_guard.status = WorkflowStatus::Failed;
-                execution_guard.ended_at = Some(Utc::now());
-                execution_guard.error = Some(crate::models::ExecutionError {
-                    code: "EXECUTION_FAILED".to_string(),
-                    message: e.to_string(),
-                    details: None,
-                    stack_trace: None,
-                    occurred_at: Utc::now(),
-                });
-                self.update_stats(false, 1000).await;
-                error!("Workflow execution failed: {} - {}", workflow_id, e);
+        Ok(execution_guard.clone())
+    }
+
+    /// Get an execution with its per-step status
+    pub async fn get_execution(
+        &self,
+        execution_id: &Uuid,
+    ) -> Result<WorkflowExecution, FederationError> {
+        let workflow_id = self.workflow_id_for_execution(execution_id)?;
+        let execution = self
+            .active_workflows
+            .get(&workflow_id)
+            .map(|e| e.clone())
+            .ok_or_else(|| FederationError::WorkflowExecutionFailed {
+                reason: format!("Execution not found: {}", execution_id),
+            })?;
+
+        let execution_guard = execution.read().await;
+        Ok(execution_guard.clone())
+    }
+
+    /// Re-run only the failed and skipped steps of an execution, together with
+    /// anything that depends on them. Completed step outputs are reused, so
+    /// their cost is not incurred again.
+    pub async fn retry_failed_steps(
+        &self,
+        execution_id: &Uuid,
+    ) -> Result<WorkflowExecution, FederationError> {
+        self.retry_workflow(execution_id, RetryFrom::LastFailedStep)
+            .await
+    }
+
+    /// Retry a failed execution, either from the beginning or from the steps
+    /// that did not complete. Each retry is recorded in the execution's attempt
+    /// history, and the workflow's `retry_policy.max_attempts` caps how many
+    /// attempts an execution gets in total.
+    pub async fn retry_workflow(
+        &self,
+        execution_id: &Uuid,
+        from: RetryFrom,
+    ) -> Result<WorkflowExecution, FederationError> {
+        info!("Retrying execution {} from {:?}", execution_id, from);
+
+        let workflow_id = self.workflow_id_for_execution(execution_id)?;
+        let execution = self
+            .active_workflows
+            .get(&workflow_id)
+            .map(|e| e.clone())
+            .ok_or_else(|| FederationError::WorkflowExecutionFailed {
+                reason: format!("Execution not found: {}", execution_id),
+            })?;
+        let workflow = self.get_workflow_definition(&workflow_id)?;
+
+        let mut execution_guard = execution.write().await;
+        ensure_retryable(&execution_guard.status)?;
+        ensure_attempts_remaining(
+            execution_guard.attempts.len(),
+            workflow.config.retry_policy.max_attempts,
+        )?;
+
+        let previous = match from {
+            RetryFrom::Beginning => Vec::new(),
+            RetryFrom::LastFailedStep => {
+                // Stored results are authoritative; fall back to in-memory state if none were persisted
+                let stored = self.step_store.load_steps(execution_id).await?;
+                if stored.is_empty() {
+                    execution_guard.step_executions.clone()
+                } else {
+                    stored
+                }
             }
-        }
+        };
+
+        execution_guard.status = WorkflowStatus::Retrying;
+        execution_guard.ended_at = None;
+        execution_guard.error = None;
+
+        let correlation_id = execution_guard.correlation_id;
+        telemetry::with_correlation_id(
+            correlation_id,
+            workflow.id,
+            self.run_execution(&workflow, &mut execution_guard, &previous, Some(from)),
+        )
+        .await?;
 
         Ok(execution_guard.clone())
     }
@@ -202,21 +389,59 @@ impl WorkflowEngine {
         Ok(execution_guard.status.clone())
     }
 
-    /// Cancel a workflow
+    /// Cancel a workflow's execution
     pub async fn cancel_workflow(&self, workflow_id: &Uuid) -> Result<(), FederationError> {
-        info!("Cancelling workflow: {}", workflow_id);
-
-        let execution = self.active_workflows.get(workflow_id).ok_or_else(|| {
-            FederationError::WorkflowExecutionFailed {
+        let execution_id = self
+            .execution_index
+            .iter()
+            .find(|entry| entry.value() == workflow_id)
+            .map(|entry| *entry.key())
+            .ok_or_else(|| FederationError::WorkflowExecutionFailed {
                 reason: format!("Workflow not found: {}", workflow_id),
-            }
-        })?;
+            })?;
+
+        self.cancel(&execution_id).await
+    }
+
+    /// Cancel an execution. Running steps are interrupted straight away and
+    /// their budget reservations released; this returns once the execution has
+    /// reached the `Cancelled` state.
+    pub async fn cancel(&self, execution_id: &Uuid) -> Result<(), FederationError> {
+        info!("Cancelling execution: {}", execution_id);
+
+        let workflow_id = self.workflow_id_for_execution(execution_id)?;
+        let execution = self
+            .active_workflows
+            .get(&workflow_id)
+            .map(|e| e.clone())
+            .ok_or_else(|| FederationError::WorkflowExecutionFailed {
+                reason: format!("Execution not found: {}", execution_id),
+            })?;
+
+        // Signal the run before waiting for it to hand back the execution
+        if let Some(control) = self.running.get(execution_id) {
+            control.cancellation.cancel();
+        }
 
         let mut execution_guard = execution.write().await;
-        execution_guard.status = WorkflowStatus::Cancelled;
-        execution_guard.ended_at = Some(Utc::now());
+        match execution_guard.status {
+            WorkflowStatus::Cancelled => {}
+            WorkflowStatus::Completed | WorkflowStatus::Failed | WorkflowStatus::TimedOut => {
+                return Err(FederationError::InvalidWorkflowState {
+                    reason: format!(
+                        "Cannot cancel an execution with status {:?}",
+                        execution_guard.status
+                    ),
+                });
+            }
+            _ => {
+                execution_guard.status = WorkflowStatus::Cancelled;
+                execution_guard.ended_at = Some(Utc::now());
+                execution_guard.error = Some(Interruption::Cancelled.error());
+            }
+        }
 
-        info!("Workflow cancelled: {}", workflow_id);
+        info!("Execution cancelled: {}", execution_id);
         Ok(())
     }
 
@@ -295,9 +520,142 @@ impl WorkflowEngine {
             });
         }
 
+        order_steps(workflow)?;
+
+        Ok(())
+    }
+
+    fn get_workflow_definition(
+        &self,
+        workflow_id: &Uuid,
+    ) -> Result<FederatedWorkflow, FederationError> {
+        self.workflow_definitions
+            .get(workflow_id)
+            .map(|w| w.clone())
+            .ok_or_else(|| FederationError::WorkflowExecutionFailed {
+                reason: format!("Workflow not found: {}", workflow_id),
+            })
+    }
+
+    fn workflow_id_for_execution(&self, execution_id: &Uuid) -> Result<Uuid, FederationError> {
+        self.execution_index
+            .get(execution_id)
+            .map(|id| *id)
+            .ok_or_else(|| FederationError::WorkflowExecutionFailed {
+                reason: format!("Execution not found: {}", execution_id),
+            })
+    }
+
+    async fn run_execution(
+        &self,
+        workflow: &FederatedWorkflow,
+        execution: &mut WorkflowExecution,
+        previous: &[StepExecution],
+        retry_from: Option<RetryFrom>,
+    ) -> Result<(), FederationError> {
+        let start = std::time::Instant::now();
+        let attempt_started_at = Utc::now();
+
+        let control = ExecutionControl::with_timeout(self.execution_timeout(workflow));
+        self.running.insert(execution.id, control.clone());
+        let steps = run_workflow_steps(
+            workflow,
+            previous,
+            self.workflow_executor.as_ref(),
+            Some((self.step_store.as_ref(), execution.id)),
+            self.budget_ledger.as_deref(),
+            &control,
+        )
+        .await;
+        self.running.remove(&execution.id);
+        let steps = steps?;
+
+        // Previously completed steps were already paid for, so the total only grows
+        // by the cost of steps that actually ran in this attempt
+        let reused: HashSet<&str> = previous
+            .iter()
+            .filter(|s| matches!(s.status, WorkflowStatus::Completed))
+            .map(|s| s.step_id.as_str())
+            .collect();
+        let new_cost: f64 = steps
+            .iter()
+            .filter(|s| !reused.contains(s.step_id.as_str()))
+            .map(|s| s.cost)
+            .sum();
+        execution.total_cost += new_cost;
+
+        let unfinished: Vec<&str> = steps
+            .iter()
+            .filter(|s| !matches!(s.status, WorkflowStatus::Completed))
+            .map(|s| s.step_id.as_str())
+            .collect();
+        let duration_ms = start.elapsed().as_millis() as u64;
+
+        let interruption = steps.iter().find_map(|s| match s.status {
+            WorkflowStatus::Cancelled => Some(Interruption::Cancelled),
+            WorkflowStatus::TimedOut => Some(Interruption::TimedOut),
+            _ => None,
+        });
+
+        execution.ended_at = Some(Utc::now());
+        if let Some(interruption) = interruption {
+            execution.status = interruption.status();
+            execution.error = Some(interruption.error());
+            if interruption == Interruption::TimedOut {
+                self.update_stats(false, duration_ms).await;
+            }
+            warn!(
+                "Workflow execution {:?}: {} - unfinished steps: {}",
+                interruption,
+                workflow.id,
+                unfinished.join(", ")
+            );
+        } else if unfinished.is_empty() {
+            execution.status = WorkflowStatus::Completed;
+            execution.result = steps.last().and_then(|s| s.result.clone());
+            self.update_stats(true, duration_ms).await;
+            info!("Workflow completed successfully: {}", workflow.id);
+        } else {
+            let message = format!("Steps did not complete: {}", unfinished.join(", "));
+            execution.status = WorkflowStatus::Failed;
+            execution.error = Some(ExecutionError {
+                code: "EXECUTION_FAILED".to_string(),
+                message: message.clone(),
+                details: Some(serde_json::json!({ "unfinished_steps": unfinished })),
+                stack_trace: None,
+                occurred_at: Utc::now(),
+            });
+            self.update_stats(false, duration_ms).await;
+            error!("Workflow execution failed: {} - {}", workflow.id, message);
+        }
+        execution.step_executions = steps;
+        execution.attempts.push(ExecutionAttempt {
+            attempt: execution.attempts.len() as u32 + 1,
+            retry_from,
+            status: execution.status.clone(),
+            started_at: attempt_started_at,
+            ended_at: execution.ended_at,
+            cost: new_cost,
+            error: execution.error.clone(),
+        });
+
+        self.workflow_executor
+            .record_execution(workflow, duration_ms, execution)
+            .await;
+
         Ok(())
     }
 
+    /// The workflow's own timeout, or the configured default when it sets none
+    fn execution_timeout(&self, workflow: &FederatedWorkflow) -> Duration {
+        let seconds = if workflow.config.timeout > 0 {
+            workflow.config.timeout
+        } else {
+            self.config.temporal.workflow_defaults.timeout
+        };
+        Duration::from_secs(seconds)
+    }
+
     async fn update_stats(&self, success: bool, duration_ms: u64) {
         let mut stats = self.stats.write().await;
 
@@ -332,35 +690,430 @@ impl WorkflowExecutor {
         })
     }
 
-    async fn execute_workflow(&self, workflow_id: &Uuid) -> Result<(), FederationError> {
-        debug!("Executing workflow: {}", workflow_id);
-
-        // Mock execution - in real implementation this would:
-        // 1. Load workflow definition
-        // 2. Execute each step with appropriate providers
-        // 3. Handle error recovery and retries
-        // 4. Track progress and resource usage
-
-        // Simulate some work
-        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-
-        // Record execution
+    async fn record_execution(
+        &self,
+        workflow: &FederatedWorkflow,
+        duration_ms: u64,
+        execution: &WorkflowExecution,
+    ) {
         let record = WorkflowExecutionRecord {
             timestamp: Utc::now(),
-            workflow_id: *workflow_id,
-            client_id: Uuid::new_v4(), // This would come from the workflow
-            duration_ms: 100,
-            status: WorkflowStatus::Completed,
-            error: None,
+            workflow_id: workflow.id,
+            client_id: workflow.client_id,
+            duration_ms,
+            status: execution.status.clone(),
+            error: execution.error.as_ref().map(|e| e.message.clone()),
         };
 
         self.execution_history
-            .entry(*workflow_id)
+            .entry(workflow.id)
             .or_insert_with(Vec::new)
             .push(record);
+    }
+}
+
+#[async_trait::async_trait]
+impl StepRunner for WorkflowExecutor {
+    async fn run_step(
+        &self,
+        workflow: &FederatedWorkflow,
+        step: &WorkflowStep,
+        inputs: &HashMap<String, serde_json::Value>,
+    ) -> Result<StepOutput, FederationError> {
+        debug!("Executing step {} of workflow {}", step.id, workflow.id);
+
+        // Mock execution - in real implementation this would dispatch the step
+        // to its provider through Temporal and report the metered cost
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+        Ok(StepOutput {
+            result: serde_json::json!({
+                "step_id": step.id,
+                "inputs": inputs,
+            }),
+            cost: 0.0,
+            provider_id: step.provider_id,
+        })
+    }
+}
+
+impl StepResultStore {
+    async fn new(db_pool: Arc<PgPool>) -> Result<Self, FederationError> {
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS federation_workflow_step_results (
+                execution_id UUID NOT NULL,
+                workflow_id UUID NOT NULL,
+                step_id TEXT NOT NULL,
+                step_execution JSONB NOT NULL,
+                updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+                PRIMARY KEY (execution_id, step_id)
+            )
+            "#,
+        )
+        .execute(db_pool.as_ref())
+        .await
+        .map_err(|e| FederationError::DatabaseError {
+            message: format!("Failed to create step result table: {}", e),
+        })?;
+
+        Ok(Self { db_pool })
+    }
+
+    /// Persist the latest result of a step, replacing any earlier attempt
+    pub async fn save_step(
+        &self,
+        execution_id: &Uuid,
+        workflow_id: &Uuid,
+        step: &StepExecution,
+    ) -> Result<(), FederationError> {
+        let step_json = serde_json::to_value(step).map_err(|e| FederationError::InternalError {
+            message: format!("Failed to serialize step {}: {}", step.step_id, e),
+        })?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO federation_workflow_step_results
+                (execution_id, workflow_id, step_id, step_execution, updated_at)
+            VALUES ($1, $2, $3, $4, NOW())
+            ON CONFLICT (execution_id, step_id)
+            DO UPDATE SET step_execution = EXCLUDED.step_execution, updated_at = NOW()
+            "#,
+        )
+        .bind(execution_id)
+        .bind(workflow_id)
+        .bind(&step.step_id)
+        .bind(step_json)
+        .execute(self.db_pool.as_ref())
+        .await
+        .map_err(|e| FederationError::DatabaseError {
+            message: format!("Failed to save step {}: {}", step.step_id, e),
+        })?;
 
         Ok(())
     }
+
+    /// Load the latest result of every step recorded for an execution
+    pub async fn load_steps(
+        &self,
+        execution_id: &Uuid,
+    ) -> Result<Vec<StepExecution>, FederationError> {
+        let rows: Vec<(serde_json::Value,)> = sqlx::query_as(
+            "SELECT step_execution FROM federation_workflow_step_results WHERE execution_id = $1",
+        )
+        .bind(execution_id)
+        .fetch_all(self.db_pool.as_ref())
+        .await
+        .map_err(|e| FederationError::DatabaseError {
+            message: format!("Failed to load steps for execution {}: {}", execution_id, e),
+        })?;
+
+        rows.into_iter()
+            .map(|(value,)| {
+                serde_json::from_value(value).map_err(|e| FederationError::InternalError {
+                    message: format!("Corrupt step result for execution {}: {}", execution_id, e),
+                })
+            })
+            .collect()
+    }
+}
+
+impl ExecutionControl {
+    /// Control for a run that times out after `timeout`
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self {
+            cancellation: CancellationToken::new(),
+            deadline: Some(tokio::time::Instant::now() + timeout),
+        }
+    }
+
+    /// Interrupt the run's steps
+    pub fn cancel(&self) {
+        self.cancellation.cancel();
+    }
+
+    /// Whether the run has already been cancelled or passed its deadline
+    fn interruption(&self) -> Option<Interruption> {
+        if self.cancellation.is_cancelled() {
+            Some(Interruption::Cancelled)
+        } else if self
+            .deadline
+            .is_some_and(|deadline| tokio::time::Instant::now() >= deadline)
+        {
+            Some(Interruption::TimedOut)
+        } else {
+            None
+        }
+    }
+
+    /// Resolves when the run is cancelled or reaches its deadline
+    async fn interrupted(&self) -> Interruption {
+        let deadline = async {
+            match self.deadline {
+                Some(deadline) => tokio::time::sleep_until(deadline).await,
+                None => std::future::pending().await,
+            }
+        };
+
+        tokio::select! {
+            _ = self.cancellation.cancelled() => Interruption::Cancelled,
+            _ = deadline => Interruption::TimedOut,
+        }
+    }
+}
+
+impl Interruption {
+    fn status(self) -> WorkflowStatus {
+        match self {
+            Interruption::Cancelled => WorkflowStatus::Cancelled,
+            Interruption::TimedOut => WorkflowStatus::TimedOut,
+        }
+    }
+
+    fn error(self) -> ExecutionError {
+        match self {
+            Interruption::Cancelled => {
+                step_error("CANCELLED", "Execution was cancelled".to_string())
+            }
+            Interruption::TimedOut => step_error(
+                "TIMED_OUT",
+                "Execution did not finish before its deadline".to_string(),
+            ),
+        }
+    }
+}
+
+// Step scheduling
+
+/// Only failed or timed-out executions can be retried; completed executions
+/// have nothing to redo and cancelled or in-flight ones must not be restarted.
+fn ensure_retryable(status: &WorkflowStatus) -> Result<(), FederationError> {
+    match status {
+        WorkflowStatus::Failed | WorkflowStatus::TimedOut => Ok(()),
+        WorkflowStatus::Completed => Err(FederationError::InvalidWorkflowState {
+            reason: "Execution already completed successfully".to_string(),
+        }),
+        other => Err(FederationError::InvalidWorkflowState {
+            reason: format!("Cannot retry an execution with status {:?}", other),
+        }),
+    }
+}
+
+/// Executions get at most `max_attempts` runs, the first one included, so a
+/// persistently failing workflow cannot be retried forever
+fn ensure_attempts_remaining(attempts: usize, max_attempts: u32) -> Result<(), FederationError> {
+    if attempts >= max_attempts as usize {
+        return Err(FederationError::InvalidWorkflowState {
+            reason: format!(
+                "Execution has used all {} attempts allowed by its retry policy",
+                max_attempts
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Order steps so every step comes after its dependencies
+fn order_steps(workflow: &FederatedWorkflow) -> Result<Vec<&WorkflowStep>, FederationError> {
+    let known: HashSet<&str> = workflow.steps.iter().map(|s| s.id.as_str()).collect();
+    for step in &workflow.steps {
+        if let Some(missing) = step
+            .dependencies
+            .iter()
+            .find(|d| !known.contains(d.as_str()))
+        {
+            return Err(FederationError::ValidationError {
+                field: "steps".to_string(),
+                message: format!("Step {} depends on unknown step {}", step.id, missing),
+            });
+        }
+    }
+
+    let mut ordered: Vec<&WorkflowStep> = Vec::with_capacity(workflow.steps.len());
+    let mut placed: HashSet<&str> = HashSet::new();
+
+    while ordered.len() < workflow.steps.len() {
+        let ready: Vec<&WorkflowStep> = workflow
+            .steps
+            .iter()
+            .filter(|s| !placed.contains(s.id.as_str()))
+            .filter(|s| s.dependencies.iter().all(|d| placed.contains(d.as_str())))
+            .collect();
+
+        if ready.is_empty() {
+            return Err(FederationError::ValidationError {
+                field: "steps".to_string(),
+                message: "Workflow step dependencies contain a cycle".to_string(),
+            });
+        }
+
+        for step in ready {
+            placed.insert(step.id.as_str());
+            ordered.push(step);
+        }
+    }
+
+    Ok(ordered)
+}
+
+fn step_error(code: &str, message: String) -> ExecutionError {
+    ExecutionError {
+        code: code.to_string(),
+        message,
+        details: None,
+        stack_trace: None,
+        occurred_at: Utc::now(),
+    }
+}
+
+/// Run a workflow's steps in dependency order.
+///
+/// Steps that completed in `previous` are reused as-is; everything else runs,
+/// unless a dependency did not complete (the step is `Skipped`) or the
+/// workflow's cost budget is already spent. Each result is persisted to
+/// `store` as soon as it is known.
+///
+/// A step's `cost_budget` is reserved from `budget` while it runs and released
+/// once it finishes, less what it cost. Cancelling `control` or reaching its
+/// deadline interrupts the running step and marks it, and every step not yet
+/// started, `Cancelled` or `TimedOut`.
+async fn run_workflow_steps(
+    workflow: &FederatedWorkflow,
+    previous: &[StepExecution],
+    runner: &dyn StepRunner,
+    store: Option<(&StepResultStore, Uuid)>,
+    budget: Option<&dyn BudgetLedger>,
+    control: &ExecutionControl,
+) -> Result<Vec<StepExecution>, FederationError> {
+    let previous: HashMap<&str, &StepExecution> =
+        previous.iter().map(|s| (s.step_id.as_str(), s)).collect();
+    let mut spent: f64 = previous.values().map(|s| s.cost).sum();
+    let mut results: Vec<StepExecution> = Vec::with_capacity(workflow.steps.len());
+    let mut outputs: HashMap<&str, serde_json::Value> = HashMap::new();
+
+    for step in order_steps(workflow)? {
+        let prior = previous.get(step.id.as_str()).copied();
+
+        if let Some(done) = prior.filter(|p| matches!(p.status, WorkflowStatus::Completed)) {
+            debug!(
+                "Reusing completed step {} of workflow {}",
+                step.id, workflow.id
+            );
+            outputs.insert(step.id.as_str(), done.result.clone().unwrap_or_default());
+            results.push(done.clone());
+            continue;
+        }
+
+        let started_at = Utc::now();
+        let retry_attempts = prior.map_or(0, |p| p.retry_attempts + 1);
+        let blocked_by: Vec<&str> = step
+            .dependencies
+            .iter()
+            .map(String::as_str)
+            .filter(|d| !outputs.contains_key(d))
+            .collect();
+
+        let mut execution = StepExecution {
+            step_id: step.id.clone(),
+            status: WorkflowStatus::Skipped,
+            provider_id: step.provider_id,
+            started_at,
+            ended_at: Some(started_at),
+            result: None,
+            error: None,
+            cost: 0.0,
+            retry_attempts,
+        };
+
+        if let Some(interruption) = control.interruption() {
+            execution.status = interruption.status();
+            execution.error = Some(interruption.error());
+        } else if !blocked_by.is_empty() {
+            execution.error = Some(step_error(
+                "DEPENDENCY_NOT_COMPLETED",
+                format!("Dependencies did not complete: {}", blocked_by.join(", ")),
+            ));
+        } else if workflow
+            .config
+            .cost_budget
+            .map_or(false, |budget| spent >= budget)
+        {
+            execution.status = WorkflowStatus::Failed;
+            execution.error = Some(step_error(
+                "BUDGET_EXCEEDED",
+                format!("Workflow cost budget exhausted after spending {:.4}", spent),
+            ));
+        } else {
+            let inputs: HashMap<String, serde_json::Value> = step
+                .dependencies
+                .iter()
+                .filter_map(|d| outputs.get(d.as_str()).map(|v| (d.clone(), v.clone())))
+                .collect();
+
+            let reserved = match (budget, step.config.cost_budget) {
+                (Some(budget), Some(amount)) if amount > 0.0 => budget
+                    .reserve(&workflow.client_id, amount)
+                    .await
+                    .map(|()| amount),
+                _ => Ok(0.0),
+            };
+
+            let run = runner
+                .run_step(workflow, step, &inputs)
+                .instrument(tracing::info_span!("workflow_step", step_id = %step.id));
+
+            // Dropping the step future abandons the in-flight provider call
+            let (reserved, outcome) = match reserved {
+                Ok(reserved) => (
+                    reserved,
+                    tokio::select! {
+                        result = run => Ok(result),
+                        interruption = control.interrupted() => Err(interruption),
+                    },
+                ),
+                Err(e) => (0.0, Ok(Err(e))),
+            };
+
+            match outcome {
+                Ok(Ok(output)) => {
+                    spent += output.cost;
+                    execution.status = WorkflowStatus::Completed;
+                    execution.provider_id = output.provider_id.or(step.provider_id);
+                    execution.cost = output.cost;
+                    outputs.insert(step.id.as_str(), output.result.clone());
+                    execution.result = Some(output.result);
+                }
+                Ok(Err(e)) => {
+                    execution.status = WorkflowStatus::Failed;
+                    execution.error = Some(step_error("STEP_FAILED", e.to_string()));
+                }
+                Err(interruption) => {
+                    execution.status = interruption.status();
+                    execution.error = Some(interruption.error());
+                }
+            }
+            execution.ended_at = Some(Utc::now());
+
+            if let Some(budget) = budget.filter(|_| reserved > 0.0) {
+                let unspent = reserved - execution.cost;
+                if let Err(e) = budget.release(&workflow.client_id, unspent).await {
+                    warn!(
+                        "Failed to release {:.4} of budget held for step {}: {}",
+                        unspent, step.id, e
+                    );
+                }
+            }
+        }
+
+        if let Some((store, execution_id)) = store {
+            store
+                .save_step(&execution_id, &workflow.id, &execution)
+                .await?;
+        }
+        results.push(execution);
+    }
+
+    Ok(results)
 }
 
 #[cfg(test)]
@@ -397,6 +1150,294 @@ mod tests {
         assert_eq!(stats.failed_workflows, 0);
     }
 
+    /// Runner that fails or hangs on the listed steps and counts every invocation
+    #[derive(Debug, Default)]
+    struct ScriptedRunner {
+        failing: std::sync::Mutex<HashSet<String>>,
+        hanging: std::sync::Mutex<HashSet<String>>,
+        calls: std::sync::Mutex<Vec<String>>,
+        correlation_ids: std::sync::Mutex<Vec<Option<Uuid>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl StepRunner for ScriptedRunner {
+        async fn run_step(
+            &self,
+            _workflow: &FederatedWorkflow,
+            step: &WorkflowStep,
+            inputs: &HashMap<String, serde_json::Value>,
+        ) -> Result<StepOutput, FederationError> {
+            self.calls.lock().unwrap().push(step.id.clone());
+            self.correlation_ids
+                .lock()
+                .unwrap()
+                .push(telemetry::current_correlation_id());
+
+            if self.failing.lock().unwrap().contains(&step.id) {
+                return Err(FederationError::ExternalServiceError {
+                    service: "provider".to_string(),
+                    message: format!("{} failed", step.id),
+                });
+            }
+            if self.hanging.lock().unwrap().contains(&step.id) {
+                std::future::pending::<()>().await;
+            }
+
+            Ok(StepOutput {
+                result: serde_json::json!({ "step": step.id, "inputs": inputs.len() }),
+                cost: 1.0,
+                provider_id: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_reruns_only_failed_and_dependent_steps() {
+        // extract -> transform -> load, with audit independent of the chain
+        let workflow = create_multi_step_workflow(&[
+            ("extract", &[]),
+            ("transform", &["extract"]),
+            ("load", &["transform"]),
+            ("audit", &[]),
+        ]);
+        let runner = ScriptedRunner::default();
+        runner
+            .failing
+            .lock()
+            .unwrap()
+            .insert("transform".to_string());
+
+        let first = run_workflow_steps(
+            &workflow,
+            &[],
+            &runner,
+            None,
+            None,
+            &ExecutionControl::default(),
+        )
+        .await
+        .unwrap();
+        let status = |steps: &[StepExecution], id: &str| {
+            steps
+                .iter()
+                .find(|s| s.step_id == id)
+                .map(|s| s.status.clone())
+                .unwrap()
+        };
+        assert!(matches!(
+            status(&first, "extract"),
+            WorkflowStatus::Completed
+        ));
+        assert!(matches!(
+            status(&first, "transform"),
+            WorkflowStatus::Failed
+        ));
+        assert!(matches!(status(&first, "load"), WorkflowStatus::Skipped));
+        assert!(matches!(status(&first, "audit"), WorkflowStatus::Completed));
+
+        runner.failing.lock().unwrap().clear();
+        runner.calls.lock().unwrap().clear();
+
+        let retried = run_workflow_steps(
+            &workflow,
+            &first,
+            &runner,
+            None,
+            None,
+            &ExecutionControl::default(),
+        )
+        .await
+        .unwrap();
+        let calls = runner.calls.lock().unwrap().clone();
+        assert_eq!(calls, vec!["transform".to_string(), "load".to_string()]);
+        assert!(retried
+            .iter()
+            .all(|s| matches!(s.status, WorkflowStatus::Completed)));
+
+        let transform = retried.iter().find(|s| s.step_id == "transform").unwrap();
+        assert_eq!(transform.retry_attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_steps_run_with_workflow_correlation_id() {
+        let workflow = create_multi_step_workflow(&[("first", &[]), ("second", &["first"])]);
+        let runner = ScriptedRunner::default();
+        let correlation_id = telemetry::new_correlation_id();
+
+        telemetry::with_correlation_id(
+            correlation_id,
+            workflow.id,
+            run_workflow_steps(
+                &workflow,
+                &[],
+                &runner,
+                None,
+                None,
+                &ExecutionControl::default(),
+            ),
+        )
+        .await
+        .unwrap();
+
+        let seen = runner.correlation_ids.lock().unwrap().clone();
+        assert_eq!(seen, vec![Some(correlation_id); 2]);
+    }
+
+    #[tokio::test]
+    async fn test_budget_exhaustion_fails_remaining_steps() {
+        let mut workflow = create_multi_step_workflow(&[("first", &[]), ("second", &["first"])]);
+        workflow.config.cost_budget = Some(1.0);
+        let runner = ScriptedRunner::default();
+
+        let steps = run_workflow_steps(
+            &workflow,
+            &[],
+            &runner,
+            None,
+            None,
+            &ExecutionControl::default(),
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(steps[0].status, WorkflowStatus::Completed));
+        assert!(matches!(steps[1].status, WorkflowStatus::Failed));
+        assert_eq!(steps[1].error.as_ref().unwrap().code, "BUDGET_EXCEEDED");
+    }
+
+    #[test]
+    fn test_retry_guard_rejects_non_failed_executions() {
+        assert!(ensure_retryable(&WorkflowStatus::Failed).is_ok());
+        assert!(ensure_retryable(&WorkflowStatus::TimedOut).is_ok());
+        assert!(matches!(
+            ensure_retryable(&WorkflowStatus::Completed),
+            Err(FederationError::InvalidWorkflowState { .. })
+        ));
+        assert!(ensure_retryable(&WorkflowStatus::Cancelled).is_err());
+        assert!(ensure_retryable(&WorkflowStatus::Running).is_err());
+        assert!(ensure_retryable(&WorkflowStatus::Retrying).is_err());
+    }
+
+    #[test]
+    fn test_retry_cap_counts_the_first_attempt() {
+        assert!(ensure_attempts_remaining(1, 3).is_ok());
+        assert!(ensure_attempts_remaining(2, 3).is_ok());
+        assert!(matches!(
+            ensure_attempts_remaining(3, 3),
+            Err(FederationError::InvalidWorkflowState { .. })
+        ));
+        assert!(ensure_attempts_remaining(0, 0).is_err());
+    }
+
+    /// Ledger that tracks how much budget is currently held
+    #[derive(Debug, Default)]
+    struct RecordingLedger {
+        held: std::sync::Mutex<f64>,
+    }
+
+    #[async_trait::async_trait]
+    impl BudgetLedger for RecordingLedger {
+        async fn reserve(&self, _client_id: &Uuid, amount: f64) -> Result<(), FederationError> {
+            *self.held.lock().unwrap() += amount;
+            Ok(())
+        }
+
+        async fn check(&self, _client_id: &Uuid, _amount: f64) -> Result<(), FederationError> {
+            Ok(())
+        }
+
+        async fn release(&self, _client_id: &Uuid, amount: f64) -> Result<(), FederationError> {
+            *self.held.lock().unwrap() -= amount;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deadline_times_out_hung_step_and_releases_its_budget() {
+        let mut workflow = create_multi_step_workflow(&[
+            ("fetch", &[]),
+            ("summarize", &["fetch"]),
+            ("notify", &[]),
+        ]);
+        for step in &mut workflow.steps {
+            step.config.cost_budget = Some(5.0);
+        }
+        let runner = ScriptedRunner::default();
+        runner.hanging.lock().unwrap().insert("fetch".to_string());
+        let ledger = RecordingLedger::default();
+        let control = ExecutionControl::with_timeout(Duration::from_millis(50));
+
+        let steps = tokio::time::timeout(
+            Duration::from_secs(5),
+            run_workflow_steps(&workflow, &[], &runner, None, Some(&ledger), &control),
+        )
+        .await
+        .expect("deadline should interrupt the hung step")
+        .unwrap();
+
+        let fetch = steps.iter().find(|s| s.step_id == "fetch").unwrap();
+        assert!(matches!(fetch.status, WorkflowStatus::TimedOut));
+        assert_eq!(fetch.error.as_ref().unwrap().code, "TIMED_OUT");
+        assert!(steps
+            .iter()
+            .all(|s| matches!(s.status, WorkflowStatus::TimedOut)));
+        // Steps after the deadline never start, so only the hung step was attempted
+        assert_eq!(*runner.calls.lock().unwrap(), vec!["fetch".to_string()]);
+        assert_eq!(*ledger.held.lock().unwrap(), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_interrupts_running_step_promptly() {
+        let mut workflow = create_multi_step_workflow(&[("draft", &[]), ("publish", &["draft"])]);
+        workflow.steps[0].config.cost_budget = Some(2.0);
+        let runner = ScriptedRunner::default();
+        runner.hanging.lock().unwrap().insert("publish".to_string());
+        let ledger = RecordingLedger::default();
+        let control = ExecutionControl::with_timeout(Duration::from_secs(3600));
+
+        let canceller = control.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            canceller.cancel();
+        });
+
+        let steps = tokio::time::timeout(
+            Duration::from_secs(5),
+            run_workflow_steps(&workflow, &[], &runner, None, Some(&ledger), &control),
+        )
+        .await
+        .expect("cancellation should interrupt the running step")
+        .unwrap();
+
+        assert!(matches!(steps[0].status, WorkflowStatus::Completed));
+        assert!(matches!(steps[1].status, WorkflowStatus::Cancelled));
+        assert_eq!(steps[1].error.as_ref().unwrap().code, "CANCELLED");
+        // The completed step keeps its actual cost; the rest of its reservation is returned
+        assert_eq!(*ledger.held.lock().unwrap(), 1.0);
+    }
+
+    #[test]
+    fn test_order_steps_rejects_cycles() {
+        let workflow = create_multi_step_workflow(&[("a", &["b"]), ("b", &["a"])]);
+        assert!(order_steps(&workflow).is_err());
+    }
+
+    fn create_multi_step_workflow(steps: &[(&str, &[&str])]) -> FederatedWorkflow {
+        let mut workflow = create_test_workflow();
+        let template = workflow.steps[0].clone();
+        workflow.config.cost_budget = None;
+        workflow.steps = steps
+            .iter()
+            .map(|(id, dependencies)| WorkflowStep {
+                id: id.to_string(),
+                name: id.to_string(),
+                dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+                ..template.clone()
+            })
+            .collect();
+        workflow
+    }
+
     // Mock function for testing
     fn create_test_pool() -> PgPool {
         // This would be a proper test database pool in real tests
@@ -3,8 +3,10 @@
 //! This module provides comprehensive configuration structures for all supported
 //! third-party integrations including Zapier, Slack, and GitHub.
 
+use crate::webhook::WebhookConfig;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::net::IpAddr;
 use url::Url;
 
 /// Main configuration structure for the Integration Service
@@ -28,6 +30,12 @@ pub struct IntegrationConfig {
     pub observability: ObservabilityConfig,
     /// Rate limiting configuration
     pub rate_limiting: RateLimitingConfig,
+    /// OAuth token refresh configuration
+    #[serde(default)]
+    pub token_refresh: TokenRefreshConfig,
+    /// Webhook deduplication and delivery configuration
+    #[serde(default)]
+    pub webhook: WebhookConfig,
 }
 
 /// Server configuration
@@ -119,6 +127,22 @@ pub struct SlackConfig {
     pub bot_scopes: Vec<String>,
     /// User scopes required for the application
     pub user_scopes: Vec<String>,
+    /// Slash command handling
+    #[serde(default)]
+    pub commands: SlashCommandConfig,
+}
+
+/// Slack slash command configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlashCommandConfig {
+    /// Intent parser that plans `/aicore run` requests; slash commands are
+    /// disabled without it
+    pub intent_parser_url: Option<String>,
+    /// Seconds to wait for the intent parser before telling the user it timed out
+    pub processing_timeout_seconds: u64,
+    /// Seconds a proposed workflow stays approvable (Slack accepts
+    /// `response_url` replies for 30 minutes)
+    pub proposal_ttl_seconds: u64,
 }
 
 /// GitHub integration configuration
@@ -148,6 +172,9 @@ pub struct GitHubConfig {
     pub default_permissions: Vec<String>,
     /// Events to subscribe to
     pub webhook_events: Vec<String>,
+    /// QA agent that runs pull request checks (e.g. http://qa-agent:8080)
+    #[serde(default)]
+    pub qa_agent_url: Option<String>,
 }
 
 /// Security configuration
@@ -167,8 +194,20 @@ pub struct SecurityConfig {
     pub hmac_key: Option<String>,
     /// Enable HTTPS redirect
     pub force_https: bool,
-    /// Trusted proxy IPs for rate limiting
-    pub trusted_proxies: Vec<String>,
+    /// Proxies whose `X-Forwarded-For` is used to identify clients for rate limiting
+    #[serde(default)]
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+/// OAuth token refresh configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenRefreshConfig {
+    /// Run the background refresh task (tokens are still refreshed on use)
+    pub enabled: bool,
+    /// Refresh access tokens this many seconds before they expire
+    pub refresh_margin_seconds: u64,
+    /// Seconds between background checks for expiring tokens
+    pub check_interval_seconds: u64,
 }
 
 /// Observability configuration
@@ -239,6 +278,8 @@ impl Default for IntegrationConfig {
             security: SecurityConfig::default(),
             observability: ObservabilityConfig::default(),
             rate_limiting: RateLimitingConfig::default(),
+            token_refresh: TokenRefreshConfig::default(),
+            webhook: WebhookConfig::default(),
         }
     }
 }
@@ -315,6 +356,17 @@ impl Default for SlackConfig {
                 "users:read".to_string(),
             ],
             user_scopes: vec!["identity.basic".to_string()],
+            commands: SlashCommandConfig::default(),
+        }
+    }
+}
+
+impl Default for SlashCommandConfig {
+    fn default() -> Self {
+        Self {
+            intent_parser_url: None,
+            processing_timeout_seconds: 20,
+            proposal_ttl_seconds: 1800, // 30 minutes
         }
     }
 }
@@ -345,6 +397,7 @@ impl Default for GitHubConfig {
                 "release".to_string(),
                 "workflow_run".to_string(),
             ],
+            qa_agent_url: None,
         }
     }
 }
@@ -364,6 +417,16 @@ impl Default for SecurityConfig {
     }
 }
 
+impl Default for TokenRefreshConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            refresh_margin_seconds: 300, // 5 minutes
+            check_interval_seconds: 60,
+        }
+    }
+}
+
 impl Default for ObservabilityConfig {
     fn default() -> Self {
         Self {
@@ -429,6 +492,8 @@ impl IntegrationConfig {
             .set_default("slack.socket_mode", false)?
             .set_default("slack.webhook_path", "/webhooks/slack")?
             .set_default("slack.oauth_callback_path", "/oauth/slack/callback")?
+            .set_default("slack.commands.processing_timeout_seconds", 20)?
+            .set_default("slack.commands.proposal_ttl_seconds", 1800)?
             .set_default("github.enabled", false)?
             .set_default("github.api_base_url", "https://api.github.com")?
             .set_default("github.webhook_path", "/webhooks/github")?
@@ -449,6 +514,9 @@ impl IntegrationConfig {
             .set_default("rate_limiting.enabled", true)?
             .set_default("rate_limiting.requests_per_second", 100)?
             .set_default("rate_limiting.burst_size", 200)?
+            .set_default("token_refresh.enabled", true)?
+            .set_default("token_refresh.refresh_margin_seconds", 300)?
+            .set_default("token_refresh.check_interval_seconds", 60)?
             .add_source(config::Environment::with_prefix("INTEGRATION").separator("_"));
 
         // Load from optional config file
@@ -3,15 +3,26 @@
 //! This module provides HTTP endpoint handlers for webhook processing, health checks,
 //! metrics, and OAuth flows for all supported integrations.
 
+use crate::error::IntegrationError;
+use crate::integrations::slack::SlackIntegration;
+use crate::integrations::slack_commands::{BlockActions, SlashCommand};
 use crate::models::{
     HealthCheckResponse, HealthStatus, IntegrationHealth, SystemHealth, WebhookPayload,
     WebhookResponse,
 };
+use crate::security::SecurityUtils;
 use crate::service::AppState;
+use crate::webhook::router::EventDispatcher;
+use crate::webhook::{DeliveryOutcome, EventPriority, WebhookEvent};
+use ai_core_shared::rate_limit::{
+    per_second_quota,
+    response::{apply_headers, client_key, too_many_requests},
+};
 use axum::{
-    extract::{ConnectInfo, Path, Query, State},
+    extract::{ConnectInfo, Path, Query, Request, State},
     http::{HeaderMap, StatusCode},
-    response::{IntoResponse, Json},
+    middleware::{self, Next},
+    response::{IntoResponse, Json, Response},
     routing::{get, post},
     Router,
 };
@@ -19,7 +30,11 @@ use bytes::Bytes;
 use chrono::Utc;
 use serde::Deserialize;
 use serde_json::{json, Value};
-use std::{collections::HashMap, net::SocketAddr, sync::Arc};
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
@@ -34,6 +49,11 @@ pub fn create_routes(state: Arc<AppState>) -> Router {
         // Webhook endpoints
         .route("/webhooks/zapier", post(zapier_webhook_handler))
         .route("/webhooks/slack", post(slack_webhook_handler))
+        .route("/webhooks/slack/commands", post(slack_command_handler))
+        .route(
+            "/webhooks/slack/interactions",
+            post(slack_interaction_handler),
+        )
         .route("/webhooks/github", post(github_webhook_handler))
         .route("/webhooks/:integration", post(generic_webhook_handler))
         // OAuth endpoints
@@ -47,15 +67,89 @@ pub fn create_routes(state: Arc<AppState>) -> Router {
         )
         .route("/api/v1/events", get(list_events))
         .route("/api/v1/events/:event_id", get(get_event))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit_middleware,
+        ))
         .with_state(state)
 }
 
+/// Apply the configured request limits and attach rate-limit headers
+async fn rate_limit_middleware(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let limits = &state.config.rate_limiting;
+    let path = request.uri().path().to_string();
+
+    // Probes and metric scrapes are never throttled
+    if !limits.enabled || path.starts_with("/health") || path == "/metrics" {
+        return next.run(request).await;
+    }
+
+    let client = client_identifier(&request, &state.config.security.trusted_proxies);
+
+    // Webhook routes use their integration's own limit when one is configured
+    let integration_limit = path
+        .strip_prefix("/webhooks/")
+        .and_then(|rest| rest.split('/').next())
+        .and_then(|name| limits.per_integration_limits.get_key_value(name));
+
+    let (key, requests_per_second, burst_size) = match integration_limit {
+        Some((name, limit)) if limit.per_ip_enabled => (
+            format!("integration:{}:{}", name, client),
+            limit.requests_per_second,
+            limit.burst_size,
+        ),
+        Some((name, limit)) => (
+            format!("integration:{}", name),
+            limit.requests_per_second,
+            limit.burst_size,
+        ),
+        None => (
+            format!("client:{}", client),
+            limits.requests_per_second,
+            limits.burst_size,
+        ),
+    };
+
+    let (limit, window) = per_second_quota(requests_per_second, burst_size);
+    let decision = state.rate_limiter.check(&key, limit, window);
+
+    if !decision.allowed {
+        warn!("Rate limit exceeded for {} on {}", key, path);
+        return too_many_requests(
+            &decision,
+            format!(
+                "Rate limit exceeded. Try again in {} seconds",
+                decision.retry_after_seconds().unwrap_or(1)
+            ),
+        );
+    }
+
+    let mut response = next.run(request).await;
+    apply_headers(response.headers_mut(), &decision);
+    response
+}
+
+/// Identify the caller by peer address, or by forwarded address behind a trusted proxy
+fn client_identifier(request: &Request, trusted_proxies: &[IpAddr]) -> String {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+
+    client_key(request.headers(), peer, trusted_proxies)
+}
+
 /// Health check endpoint
 async fn health_check(State(state): State<Arc<AppState>>) -> impl IntoResponse {
     debug!("Health check requested");
 
     let mut integration_healths = HashMap::new();
     let mut overall_status = HealthStatus::Healthy;
+    let credential_health = state.oauth_tokens.credential_health().await;
 
     // Check each integration
     for (name, integration) in &state.integrations {
@@ -69,13 +163,15 @@ async fn health_check(State(state): State<Arc<AppState>>) -> impl IntoResponse {
                 if status == HealthStatus::Unhealthy && overall_status == HealthStatus::Healthy {
                     overall_status = HealthStatus::Degraded;
                 }
+                let error = (credential_health.get(name) == Some(&false))
+                    .then(|| "OAuth re-authorization required".to_string());
                 integration_healths.insert(
                     name.clone(),
                     IntegrationHealth {
                         status,
                         last_check: Utc::now(),
                         response_time_ms: None,
-                        error: None,
+                        error,
                     },
                 );
             }
@@ -162,13 +258,17 @@ async fn metrics_handler(State(state): State<Arc<AppState>>) -> impl IntoRespons
     let metrics = state.metrics.lock().await;
 
     // TODO: Generate Prometheus metrics format
-    let metrics_text = format!(
+    let mut metrics_text = format!(
         "# HELP integration_requests_total Total number of integration requests\n# TYPE integration_requests_total counter\nintegration_requests_total {}\n\n# HELP integration_requests_successful_total Total number of successful integration requests\n# TYPE integration_requests_successful_total counter\nintegration_requests_successful_total {}\n\n# HELP integration_requests_failed_total Total number of failed integration requests\n# TYPE integration_requests_failed_total counter\nintegration_requests_failed_total {}\n\n# HELP integration_response_time_seconds Average response time in seconds\n# TYPE integration_response_time_seconds gauge\nintegration_response_time_seconds {}\n",
         metrics.total_requests,
         metrics.successful_requests,
         metrics.failed_requests,
         metrics.avg_response_time_ms / 1000.0
     );
+    metrics_text.push_str(&format!(
+        "\n# HELP integration_webhooks_deduplicated_total Webhook deliveries answered from an idempotency record\n# TYPE integration_webhooks_deduplicated_total counter\nintegration_webhooks_deduplicated_total {}\n",
+        state.webhooks.deduplicated_count().await
+    ));
 
     (
         StatusCode::OK,
@@ -197,6 +297,95 @@ async fn slack_webhook_handler(
     process_webhook(state, "slack", addr, headers, body).await
 }
 
+/// Slack slash command handler
+///
+/// Slack gives up on a slash command after 3 seconds, so this only verifies,
+/// parses and acknowledges it; results follow through the `response_url`.
+async fn slack_command_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let Some(router) = state.slack_commands.clone() else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "Slack slash commands are not enabled" })),
+        )
+            .into_response();
+    };
+
+    if let Err(e) = verify_slack_request(&state, &headers, &body).await {
+        warn!(error = %e, "Slack slash command verification failed");
+        return (e.status_code(), Json(json!({ "error": e.to_string() }))).into_response();
+    }
+
+    match SlashCommand::from_form(&body) {
+        Ok(command) => (StatusCode::OK, Json(router.handle_command(command))).into_response(),
+        Err(e) => (e.status_code(), Json(json!({ "error": e.to_string() }))).into_response(),
+    }
+}
+
+/// Slack interactive component handler (workflow Approve/Cancel buttons)
+async fn slack_interaction_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let Some(router) = state.slack_commands.clone() else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "Slack slash commands are not enabled" })),
+        )
+            .into_response();
+    };
+
+    if let Err(e) = verify_slack_request(&state, &headers, &body).await {
+        warn!(error = %e, "Slack interaction verification failed");
+        return (e.status_code(), Json(json!({ "error": e.to_string() }))).into_response();
+    }
+
+    match BlockActions::from_form(&body) {
+        Ok(interaction) => {
+            router.handle_interaction(interaction);
+            StatusCode::OK.into_response()
+        }
+        Err(e) => (e.status_code(), Json(json!({ "error": e.to_string() }))).into_response(),
+    }
+}
+
+/// Check a Slack request's signature with the configured signing secret
+///
+/// Fails closed: without a signing secret no command or interaction is accepted.
+async fn verify_slack_request(
+    state: &AppState,
+    headers: &HeaderMap,
+    body: &Bytes,
+) -> Result<(), IntegrationError> {
+    let signing_secret = state
+        .config
+        .slack
+        .signing_secret
+        .as_deref()
+        .filter(|secret| !secret.is_empty())
+        .ok_or_else(|| {
+            IntegrationError::signature_verification("slack", "Signing secret not configured")
+        })?;
+
+    let header_map: HashMap<String, String> = headers
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
+        .collect();
+
+    match SecurityUtils::verify_slack_signature(body, &header_map, signing_secret) {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(IntegrationError::signature_verification(
+            "slack",
+            "Invalid signature",
+        )),
+        Err(e) => Err(e),
+    }
+}
+
 /// GitHub webhook handler
 async fn github_webhook_handler(
     State(state): State<Arc<AppState>>,
@@ -262,8 +451,16 @@ async fn process_webhook(
         .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
         .collect();
 
-    // Validate webhook signature
-    if let Err(e) = integration.validate_webhook(&body, &header_map).await {
+    // Validate webhook signature before any event routing
+    let validation = match integration.validate_webhook(&body, &header_map).await {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(IntegrationError::signature_verification(
+            integration_name,
+            "Invalid signature",
+        )),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = validation {
         error!(
             request_id = %request_id,
             integration = integration_name,
@@ -310,6 +507,17 @@ async fn process_webhook(
         }
     };
 
+    // Answer Slack's URL verification handshake without routing it as an event
+    if integration_name == "slack" {
+        if let Some(challenge) = SlackIntegration::url_verification_challenge(&json_data) {
+            info!(
+                request_id = %request_id,
+                "Responding to Slack URL verification challenge"
+            );
+            return (StatusCode::OK, Json(json!({ "challenge": challenge }))).into_response();
+        }
+    }
+
     // Create webhook payload
     let webhook_payload = WebhookPayload {
         id: Uuid::parse_str(&request_id).unwrap_or_else(|_| Uuid::new_v4()),
@@ -325,9 +533,58 @@ async fn process_webhook(
             .map(|s| s.to_string()),
     };
 
-    // Process the webhook
-    match integration.process_webhook(webhook_payload).await {
-        Ok(event) => {
+    // Process the webhook unless it repeats a recent delivery
+    let delivery_id = webhook_payload.id;
+    let dispatcher = state.event_dispatcher.clone();
+    let outcome = state
+        .webhooks
+        .handle_webhook_with(webhook_payload, move |payload| async move {
+            let event = integration.process_webhook(payload.clone()).await?;
+            if let Some(dispatcher) = dispatcher {
+                spawn_dispatch(dispatcher, payload);
+            }
+            Ok(event)
+        })
+        .await;
+
+    match outcome {
+        Ok(DeliveryOutcome::Duplicate { event_id, result }) => {
+            info!(
+                request_id = %request_id,
+                integration = integration_name,
+                original_delivery_id = %event_id,
+                "Duplicate webhook delivery, skipping processing"
+            );
+
+            let mut metrics = state.metrics.lock().await;
+            metrics.total_requests += 1;
+            metrics.successful_requests += 1;
+
+            // Answer with the original result so retries see the same response
+            let data = match result {
+                Some(event) => json!({
+                    "delivery_id": event_id,
+                    "event_id": event.id,
+                    "status": event.status.to_string(),
+                    "duplicate": true
+                }),
+                None => json!({
+                    "delivery_id": event_id,
+                    "duplicate": true
+                }),
+            };
+
+            (
+                StatusCode::OK,
+                Json(WebhookResponse::success_with_data(
+                    request_id,
+                    "Duplicate webhook delivery ignored".to_string(),
+                    data,
+                )),
+            )
+                .into_response()
+        }
+        Ok(DeliveryOutcome::Processed(event)) => {
             let processing_time = start_time.elapsed();
 
             info!(
@@ -353,6 +610,7 @@ async fn process_webhook(
                     request_id,
                     "Webhook processed successfully".to_string(),
                     json!({
+                        "delivery_id": delivery_id,
                         "event_id": event.id,
                         "status": event.status.to_string(),
                         "processing_time_ms": processing_time.as_millis()
@@ -386,6 +644,23 @@ async fn process_webhook(
     }
 }
 
+/// Deliver a processed webhook downstream without holding up the response
+///
+/// The dispatcher retries transient failures and dead-letters the event once
+/// its attempts are exhausted.
+fn spawn_dispatch(dispatcher: Arc<EventDispatcher>, payload: WebhookPayload) {
+    tokio::spawn(async move {
+        let mut event = WebhookEvent::new(payload, EventPriority::Normal);
+        if let Err(e) = dispatcher.dispatch(&mut event).await {
+            warn!(
+                event_id = %event.id,
+                error = %e,
+                "Webhook event was not delivered downstream"
+            );
+        }
+    });
+}
+
 /// Extract event type from payload based on integration
 fn extract_event_type(payload: &Value, integration: &str) -> String {
     match integration {
@@ -574,6 +849,21 @@ mod tests {
             metrics: Arc::new(tokio::sync::Mutex::new(
                 crate::metrics::IntegrationMetrics::new(),
             )),
+            rate_limiter: Arc::new(ai_core_shared::rate_limit::SlidingWindowLimiter::new()),
+            oauth_tokens: Arc::new(crate::oauth::OAuthTokenManager::new(
+                reqwest::Client::new(),
+                std::time::Duration::from_secs(300),
+            )),
+            slack_commands: None,
+            webhooks: Arc::new(crate::webhook::WebhookHandler::new(
+                crate::webhook::WebhookConfig::default(),
+                Arc::new(crate::webhook::router::StaticEventRouter::new(
+                    HashMap::new(),
+                    Vec::new(),
+                )),
+                Arc::new(crate::webhook::storage::MemoryEventStorage::new()),
+            )),
+            event_dispatcher: None,
         })
     }
 
@@ -587,6 +877,64 @@ mod tests {
         assert_eq!(response.status_code(), 200);
     }
 
+    #[tokio::test]
+    async fn test_rate_limit_headers_and_429() {
+        let mut state = create_test_state().await;
+        {
+            let state = Arc::get_mut(&mut state).unwrap();
+            state.config.rate_limiting.requests_per_second = 1;
+            state.config.rate_limiting.burst_size = 1;
+        }
+        let app = create_routes(state);
+        let server = TestServer::new(app).unwrap();
+
+        let response = server.get("/api/v1/integrations").await;
+        assert_eq!(response.status_code(), 200);
+        assert_eq!(response.header("x-ratelimit-limit"), "1");
+        assert_eq!(response.header("x-ratelimit-remaining"), "0");
+
+        let response = server.get("/api/v1/integrations").await;
+        assert_eq!(response.status_code(), 429);
+        assert_eq!(response.header("retry-after"), "1");
+
+        let body: Value = response.json();
+        assert_eq!(body["error"]["code"], "RATE_LIMIT_EXCEEDED");
+
+        // Health checks are exempt
+        let response = server.get("/health/live").await;
+        assert_eq!(response.status_code(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_untrusted_forwarded_for_does_not_reset_limit() {
+        use axum::http::{HeaderName, HeaderValue};
+
+        let mut state = create_test_state().await;
+        {
+            let state = Arc::get_mut(&mut state).unwrap();
+            state.config.rate_limiting.requests_per_second = 1;
+            state.config.rate_limiting.burst_size = 1;
+        }
+        let app = create_routes(state);
+        let server = TestServer::new(app).unwrap();
+        let forwarded_for = HeaderName::from_static("x-forwarded-for");
+
+        let response = server
+            .get("/api/v1/integrations")
+            .add_header(
+                forwarded_for.clone(),
+                HeaderValue::from_static("198.51.100.1"),
+            )
+            .await;
+        assert_eq!(response.status_code(), 200);
+
+        let response = server
+            .get("/api/v1/integrations")
+            .add_header(forwarded_for, HeaderValue::from_static("198.51.100.2"))
+            .await;
+        assert_eq!(response.status_code(), 429);
+    }
+
     #[tokio::test]
     async fn test_liveness_check() {
         let state = create_test_state().await;
@@ -626,6 +974,19 @@ mod tests {
         assert_eq!(body["integration"], "zapier");
     }
 
+    #[tokio::test]
+    async fn test_slack_commands_disabled_without_intent_parser() {
+        let state = create_test_state().await;
+        let app = create_routes(state);
+        let server = TestServer::new(app).unwrap();
+
+        let response = server
+            .post("/webhooks/slack/commands")
+            .text("command=%2Faicore&text=run+publish+the+launch+post")
+            .await;
+        assert_eq!(response.status_code(), 404);
+    }
+
     #[test]
     fn test_extract_event_type() {
         // Test Zapier event type extraction
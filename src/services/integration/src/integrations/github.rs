@@ -13,19 +13,38 @@ use crate::models::{
     EventMetadata, EventPayload, EventStatus, GitHubEvent, GitHubOrganization, GitHubRepository,
     GitHubUser, IntegrationEvent, IntegrationType, WebhookPayload,
 };
+use crate::oauth::{OAuthClient, OAuthToken, OAuthTokenManager};
+use crate::webhook::WebhookError;
 use async_trait::async_trait;
 use chrono::Utc;
+use hmac::{Hmac, Mac};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sha1::Sha1;
+use sha2::Sha256;
 use std::collections::HashMap;
+use std::sync::Arc;
+use subtle::ConstantTimeEq;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+/// Header carrying the HMAC-SHA256 signature of the payload
+pub const GITHUB_SIGNATURE_256_HEADER: &str = "x-hub-signature-256";
+/// Legacy header carrying the HMAC-SHA1 signature of the payload
+pub const GITHUB_SIGNATURE_HEADER: &str = "x-hub-signature";
+/// Endpoint issuing and refreshing user access tokens
+const GITHUB_OAUTH_TOKEN_URL: &str = "https://github.com/login/oauth/access_token";
+/// Pull request actions that change the code under review
+const QA_PULL_REQUEST_ACTIONS: [&str; 4] =
+    ["opened", "synchronize", "reopened", "ready_for_review"];
+
 /// GitHub integration implementation
 pub struct GitHubIntegration {
     config: GitHubConfig,
     http_client: Client,
+    /// User OAuth credentials from the authorization flow
+    tokens: Option<Arc<OAuthTokenManager>>,
 }
 
 /// GitHub configuration (simplified version for integration module)
@@ -43,6 +62,7 @@ pub struct GitHubConfig {
     pub oauth_callback_path: String,
     pub default_permissions: Vec<String>,
     pub webhook_events: Vec<String>,
+    pub qa_agent_url: Option<String>,
 }
 
 /// Raw GitHub webhook payload structure
@@ -118,6 +138,10 @@ pub struct GitHubOAuthResponse {
     pub access_token: String,
     pub token_type: String,
     pub scope: Option<String>,
+    /// Present when the app has expiring user tokens enabled
+    pub refresh_token: Option<String>,
+    /// Access token lifetime in seconds, when the app has expiring user tokens
+    pub expires_in: Option<i64>,
 }
 
 impl GitHubIntegration {
@@ -156,6 +180,7 @@ impl GitHubIntegration {
             oauth_callback_path: config.oauth_callback_path.clone(),
             default_permissions: config.default_permissions.clone(),
             webhook_events: config.webhook_events.clone(),
+            qa_agent_url: config.qa_agent_url.clone(),
         };
 
         let http_client = Client::builder()
@@ -169,9 +194,89 @@ impl GitHubIntegration {
         Ok(Self {
             config: github_config,
             http_client,
+            tokens: None,
         })
     }
 
+    /// Keep user OAuth credentials in `tokens`, refreshing expiring user
+    /// tokens when a client ID and secret are configured
+    pub fn with_token_manager(mut self, tokens: Arc<OAuthTokenManager>) -> Self {
+        if let (Some(client_id), Some(client_secret)) =
+            (&self.config.client_id, &self.config.client_secret)
+        {
+            tokens.register_client(
+                "github",
+                OAuthClient {
+                    token_url: GITHUB_OAUTH_TOKEN_URL.to_string(),
+                    client_id: client_id.clone(),
+                    client_secret: client_secret.clone(),
+                },
+            );
+        }
+
+        self.tokens = Some(tokens);
+        self
+    }
+
+    /// Verify a GitHub webhook signature header against the payload
+    ///
+    /// Accepts `sha256=<hex>` (preferred) and the legacy `sha1=<hex>` format and
+    /// compares digests in constant time.
+    pub fn verify_github_signature(
+        &self,
+        payload: &[u8],
+        signature_header: &str,
+    ) -> IntegrationResult<()> {
+        let secret = self.config.webhook_secret.as_ref().ok_or_else(|| {
+            IntegrationError::configuration("GitHub webhook secret not configured")
+        })?;
+
+        let signature_header = signature_header.trim();
+        if signature_header.is_empty() {
+            return Err(
+                WebhookError::MissingSignature(GITHUB_SIGNATURE_256_HEADER.to_string()).into(),
+            );
+        }
+
+        let (algorithm, signature) = signature_header.split_once('=').ok_or_else(|| {
+            WebhookError::MalformedSignature("expected '<algorithm>=<hex digest>'".to_string())
+        })?;
+
+        let provided = hex::decode(signature).map_err(|_| {
+            WebhookError::MalformedSignature("signature digest is not valid hex".to_string())
+        })?;
+
+        let computed = match algorithm {
+            "sha256" => {
+                let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+                    .map_err(|e| IntegrationError::internal(format!("HMAC error: {}", e)))?;
+                mac.update(payload);
+                mac.finalize().into_bytes().to_vec()
+            }
+            "sha1" => {
+                let mut mac = Hmac::<Sha1>::new_from_slice(secret.as_bytes())
+                    .map_err(|e| IntegrationError::internal(format!("HMAC error: {}", e)))?;
+                mac.update(payload);
+                mac.finalize().into_bytes().to_vec()
+            }
+            other => {
+                return Err(WebhookError::MalformedSignature(format!(
+                    "unsupported signature algorithm '{}'",
+                    other
+                ))
+                .into());
+            }
+        };
+
+        // `ct_eq` on slices of different lengths is false without leaking where they differ
+        if bool::from(computed.ct_eq(&provided)) {
+            Ok(())
+        } else {
+            warn!("GitHub webhook signature mismatch ({})", algorithm);
+            Err(WebhookError::SignatureMismatch.into())
+        }
+    }
+
     /// Parse GitHub webhook payload
     fn parse_payload(&self, payload: WebhookPayload) -> IntegrationResult<GitHubEvent> {
         debug!("Parsing GitHub webhook payload");
@@ -290,6 +395,15 @@ impl GitHubIntegration {
             "Processing GitHub event"
         );
 
+        // Pull requests get a QA run, reported back as a check run by the qa-agent
+        if let (Some(qa_agent_url), EventPayload::GitHub(github_event)) =
+            (&self.config.qa_agent_url, &event.payload)
+        {
+            if let Some(trigger) = Self::pull_request_qa_trigger(&event.event_type, github_event) {
+                self.request_pull_request_qa(qa_agent_url, &trigger).await?;
+            }
+        }
+
         // TODO: Integrate with workflow engine
         // This would typically involve:
         // 1. Looking up workflow templates triggered by this event type
@@ -303,6 +417,58 @@ impl GitHubIntegration {
         Ok(())
     }
 
+    /// Pull request commit to run QA against, for pull request events that
+    /// change the code under review
+    pub fn pull_request_qa_trigger(event_type: &str, event: &GitHubEvent) -> Option<Value> {
+        let action = event.action.as_deref()?;
+        if event_type != "pull_request" || !QA_PULL_REQUEST_ACTIONS.contains(&action) {
+            return None;
+        }
+
+        let pull_request = event.event_data.get("pull_request")?;
+        let number = pull_request.get("number")?.as_u64()?;
+        let head_sha = pull_request.get("head")?.get("sha")?.as_str()?;
+        let (owner, repo) = event.repository.full_name.split_once('/')?;
+
+        Some(serde_json::json!({
+            "owner": owner,
+            "repo": repo,
+            "number": number,
+            "head_sha": head_sha
+        }))
+    }
+
+    /// Ask the qa-agent to run its QA workflow for a pull request
+    async fn request_pull_request_qa(
+        &self,
+        qa_agent_url: &str,
+        trigger: &Value,
+    ) -> IntegrationResult<()> {
+        let response = self
+            .http_client
+            .post(format!(
+                "{}/api/qa/pull-requests",
+                qa_agent_url.trim_end_matches('/')
+            ))
+            .json(trigger)
+            .send()
+            .await
+            .map_err(|e| IntegrationError::external_api("qa-agent", 0, e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(IntegrationError::external_api(
+                "qa-agent",
+                status.as_u16(),
+                body,
+            ));
+        }
+
+        info!(trigger = %trigger, "Requested pull request QA run");
+        Ok(())
+    }
+
     /// Generate JWT token for GitHub App authentication
     fn generate_jwt_token(&self) -> IntegrationResult<String> {
         // TODO: Implement JWT token generation for GitHub App
@@ -453,8 +619,13 @@ impl GitHubIntegration {
             .await
     }
 
-    /// Exchange OAuth code for access token
-    pub async fn exchange_oauth_code(&self, code: &str) -> IntegrationResult<GitHubOAuthResponse> {
+    /// Exchange the OAuth code GitHub sends when an app is installed for an
+    /// access token, stored against the installation
+    pub async fn exchange_oauth_code(
+        &self,
+        code: &str,
+        installation_id: u64,
+    ) -> IntegrationResult<GitHubOAuthResponse> {
         let client_id =
             self.config.client_id.as_ref().ok_or_else(|| {
                 IntegrationError::configuration("GitHub client ID not configured")
@@ -472,7 +643,7 @@ impl GitHubIntegration {
 
         let response = self
             .http_client
-            .post("https://github.com/login/oauth/access_token")
+            .post(GITHUB_OAUTH_TOKEN_URL)
             .header("Accept", "application/json")
             .json(&body)
             .send()
@@ -484,6 +655,21 @@ impl GitHubIntegration {
             .await
             .map_err(|e| IntegrationError::external_api("github", 0, e.to_string()))?;
 
+        if let Some(tokens) = &self.tokens {
+            tokens
+                .store(
+                    "github",
+                    &installation_id.to_string(),
+                    OAuthToken::issued_now(
+                        oauth_response.access_token.clone(),
+                        oauth_response.refresh_token.clone(),
+                        oauth_response.expires_in,
+                        oauth_response.scope.clone(),
+                    ),
+                )
+                .await?;
+        }
+
         Ok(oauth_response)
     }
 }
@@ -564,17 +750,27 @@ impl Integration for GitHubIntegration {
             return Ok(false);
         }
 
-        // Use security module for GitHub signature verification
-        if let Some(ref webhook_secret) = self.config.webhook_secret {
-            crate::security::SecurityUtils::verify_github_signature(
-                payload,
-                headers,
-                webhook_secret,
-            )
-        } else {
+        if self.config.webhook_secret.is_none() {
             warn!("GitHub webhook secret not configured, skipping signature verification");
-            Ok(true)
+            return Ok(true);
         }
+
+        // Prefer the SHA-256 signature and fall back to the legacy SHA-1 header
+        let signature_header = headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(GITHUB_SIGNATURE_256_HEADER))
+            .or_else(|| {
+                headers
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(GITHUB_SIGNATURE_HEADER))
+            })
+            .map(|(_, value)| value.as_str())
+            .ok_or_else(|| {
+                WebhookError::MissingSignature(GITHUB_SIGNATURE_256_HEADER.to_string())
+            })?;
+
+        self.verify_github_signature(payload, signature_header)?;
+        Ok(true)
     }
 
     async fn health_check(&self) -> IntegrationResult<bool> {
@@ -590,6 +786,12 @@ impl Integration for GitHubIntegration {
             return Ok(false);
         }
 
+        if let Some(tokens) = &self.tokens {
+            if tokens.needs_reauthorization("github").await {
+                return Ok(false);
+            }
+        }
+
         // TODO: Add actual API health check
         // This could involve generating a JWT token and making a test API call
         // For now, we'll just check configuration
@@ -643,6 +845,7 @@ mod tests {
             oauth_callback_path: "/oauth/github/callback".to_string(),
             default_permissions: vec!["contents".to_string(), "issues".to_string()],
             webhook_events: vec!["push".to_string(), "pull_request".to_string()],
+            qa_agent_url: None,
         }
     }
 
@@ -764,6 +967,106 @@ mod tests {
         assert!(event.error_message.is_none());
     }
 
+    #[test]
+    fn test_pull_request_qa_trigger() {
+        let config = create_test_config();
+        let integration = GitHubIntegration::new(&config).unwrap();
+        let mut payload = create_test_payload("pull_request");
+        payload.data["pull_request"] = json!({
+            "number": 42,
+            "head": { "sha": "6dcb09b5b57875f334f61aebed695e2e4193db5e" }
+        });
+        let mut event = integration.parse_payload(payload).unwrap();
+
+        assert_eq!(
+            GitHubIntegration::pull_request_qa_trigger("pull_request", &event),
+            Some(json!({
+                "owner": "test-org",
+                "repo": "test-repo",
+                "number": 42,
+                "head_sha": "6dcb09b5b57875f334f61aebed695e2e4193db5e"
+            }))
+        );
+
+        // Closing a pull request or other events do not run QA
+        assert!(GitHubIntegration::pull_request_qa_trigger("issues", &event).is_none());
+        event.action = Some("closed".to_string());
+        assert!(GitHubIntegration::pull_request_qa_trigger("pull_request", &event).is_none());
+    }
+
+    fn sign(payload: &[u8], algorithm: &str) -> String {
+        let secret = b"test-webhook-secret";
+        let digest = match algorithm {
+            "sha1" => {
+                let mut mac = Hmac::<Sha1>::new_from_slice(secret).unwrap();
+                mac.update(payload);
+                mac.finalize().into_bytes().to_vec()
+            }
+            _ => {
+                let mut mac = Hmac::<Sha256>::new_from_slice(secret).unwrap();
+                mac.update(payload);
+                mac.finalize().into_bytes().to_vec()
+            }
+        };
+        format!("{}={}", algorithm, hex::encode(digest))
+    }
+
+    #[test]
+    fn test_verify_github_signature() {
+        let integration = GitHubIntegration::new(&create_test_config()).unwrap();
+        let payload = br#"{"action":"opened"}"#;
+
+        assert!(integration
+            .verify_github_signature(payload, &sign(payload, "sha256"))
+            .is_ok());
+        assert!(integration
+            .verify_github_signature(payload, &sign(payload, "sha1"))
+            .is_ok());
+
+        let tampered = integration.verify_github_signature(b"{}", &sign(payload, "sha256"));
+        assert!(matches!(
+            tampered,
+            Err(IntegrationError::SignatureVerification { .. })
+        ));
+
+        for header in ["", "sha256", "sha256=not-hex", "md5=abcd"] {
+            let result = integration.verify_github_signature(payload, header);
+            assert!(matches!(
+                result,
+                Err(IntegrationError::SignatureVerification { .. })
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validate_webhook_prefers_sha256() {
+        let integration = GitHubIntegration::new(&create_test_config()).unwrap();
+        let payload = br#"{"action":"opened"}"#;
+
+        // A valid legacy signature does not rescue an invalid SHA-256 one
+        let mut headers = HashMap::new();
+        headers.insert(
+            GITHUB_SIGNATURE_256_HEADER.to_string(),
+            sign(b"other", "sha256"),
+        );
+        headers.insert(GITHUB_SIGNATURE_HEADER.to_string(), sign(payload, "sha1"));
+        assert!(integration
+            .validate_webhook(payload, &headers)
+            .await
+            .is_err());
+
+        headers.remove(GITHUB_SIGNATURE_256_HEADER);
+        assert!(integration
+            .validate_webhook(payload, &headers)
+            .await
+            .unwrap());
+
+        assert!(integration
+            .validate_webhook(payload, &HashMap::new())
+            .await
+            .is_err());
+    }
+
     #[test]
     fn test_event_metadata_creation() {
         let config = create_test_config();
@@ -13,12 +13,14 @@ use crate::models::{
     EventMetadata, EventPayload, EventStatus, IntegrationEvent, IntegrationType, SlackEvent,
     WebhookPayload,
 };
+use crate::oauth::{OAuthClient, OAuthToken, OAuthTokenManager};
 use async_trait::async_trait;
 use chrono::Utc;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
@@ -26,6 +28,9 @@ use uuid::Uuid;
 pub struct SlackIntegration {
     config: SlackConfig,
     http_client: Client,
+    /// OAuth credentials from the install flow, used instead of the configured
+    /// bot token once stored
+    tokens: Option<Arc<OAuthTokenManager>>,
 }
 
 /// Slack configuration (simplified version for integration module)
@@ -105,6 +110,10 @@ pub struct SlackOAuthResponse {
     pub access_token: Option<String>,
     pub token_type: Option<String>,
     pub scope: Option<String>,
+    /// Present when token rotation is enabled for the app
+    pub refresh_token: Option<String>,
+    /// Access token lifetime in seconds, when token rotation is enabled
+    pub expires_in: Option<i64>,
     pub bot_user_id: Option<String>,
     pub app_id: Option<String>,
     pub team: Option<SlackTeamInfo>,
@@ -164,9 +173,43 @@ impl SlackIntegration {
         Ok(Self {
             config: slack_config,
             http_client,
+            tokens: None,
         })
     }
 
+    /// Keep OAuth credentials in `tokens`, refreshing them through Slack's
+    /// token rotation when a client ID and secret are configured
+    pub fn with_token_manager(mut self, tokens: Arc<OAuthTokenManager>) -> Self {
+        if let (Some(client_id), Some(client_secret)) =
+            (&self.config.client_id, &self.config.client_secret)
+        {
+            tokens.register_client(
+                "slack",
+                OAuthClient {
+                    token_url: format!("{}/oauth.v2.access", self.config.api_base_url),
+                    client_id: client_id.clone(),
+                    client_secret: client_secret.clone(),
+                },
+            );
+        }
+
+        self.tokens = Some(tokens);
+        self
+    }
+
+    /// Extract the challenge token from a Slack `url_verification` request
+    ///
+    /// Slack expects the token echoed back immediately when the Events API
+    /// request URL is configured, so these requests are answered directly
+    /// instead of being routed as events.
+    pub fn url_verification_challenge(data: &Value) -> Option<&str> {
+        if data.get("type").and_then(|t| t.as_str()) != Some("url_verification") {
+            return None;
+        }
+
+        data.get("challenge").and_then(|c| c.as_str())
+    }
+
     /// Parse Slack webhook payload
     fn parse_payload(&self, payload: WebhookPayload) -> IntegrationResult<SlackEvent> {
         debug!("Parsing Slack webhook payload");
@@ -285,18 +328,24 @@ impl SlackIntegration {
         Ok(())
     }
 
-    /// Make API calls to Slack
+    /// Make API calls to Slack on behalf of a workspace
     async fn api_request(
         &self,
+        team_id: &str,
         endpoint: &str,
         method: reqwest::Method,
         body: Option<Value>,
     ) -> IntegrationResult<SlackApiResponse> {
-        let bot_token = self
-            .config
-            .bot_token
-            .as_ref()
-            .ok_or_else(|| IntegrationError::configuration("Slack bot token not configured"))?;
+        // OAuth credentials that need re-authorization fail here, before any call is made
+        let bot_token =
+            match &self.tokens {
+                Some(tokens) if tokens.has_credentials("slack", team_id) => {
+                    tokens.access_token("slack", team_id).await?
+                }
+                _ => self.config.bot_token.clone().ok_or_else(|| {
+                    IntegrationError::configuration("Slack bot token not configured")
+                })?,
+            };
 
         let url = format!("{}/{}", self.config.api_base_url, endpoint);
 
@@ -335,30 +384,48 @@ impl SlackIntegration {
         Ok(response_json)
     }
 
-    /// Send a message to a Slack channel
-    pub async fn send_message(&self, channel: &str, text: &str) -> IntegrationResult<Value> {
+    /// Send a message to a channel in a workspace
+    pub async fn send_message(
+        &self,
+        team_id: &str,
+        channel: &str,
+        text: &str,
+    ) -> IntegrationResult<Value> {
         let body = serde_json::json!({
             "channel": channel,
             "text": text
         });
 
         let response = self
-            .api_request("chat.postMessage", reqwest::Method::POST, Some(body))
+            .api_request(
+                team_id,
+                "chat.postMessage",
+                reqwest::Method::POST,
+                Some(body),
+            )
             .await?;
         Ok(serde_json::to_value(response.data).unwrap_or(Value::Null))
     }
 
     /// Get channel information
-    pub async fn get_channel_info(&self, channel_id: &str) -> IntegrationResult<Value> {
+    pub async fn get_channel_info(
+        &self,
+        team_id: &str,
+        channel_id: &str,
+    ) -> IntegrationResult<Value> {
         let url = format!("conversations.info?channel={}", channel_id);
-        let response = self.api_request(&url, reqwest::Method::GET, None).await?;
+        let response = self
+            .api_request(team_id, &url, reqwest::Method::GET, None)
+            .await?;
         Ok(serde_json::to_value(response.data).unwrap_or(Value::Null))
     }
 
     /// Get user information
-    pub async fn get_user_info(&self, user_id: &str) -> IntegrationResult<Value> {
+    pub async fn get_user_info(&self, team_id: &str, user_id: &str) -> IntegrationResult<Value> {
         let url = format!("users.info?user={}", user_id);
-        let response = self.api_request(&url, reqwest::Method::GET, None).await?;
+        let response = self
+            .api_request(team_id, &url, reqwest::Method::GET, None)
+            .await?;
         Ok(serde_json::to_value(response.data).unwrap_or(Value::Null))
     }
 
@@ -407,6 +474,24 @@ impl SlackIntegration {
             return Err(IntegrationError::oauth("slack", error_msg));
         }
 
+        if let (Some(tokens), Some(access_token)) = (&self.tokens, &oauth_response.access_token) {
+            let team = oauth_response.team.as_ref().ok_or_else(|| {
+                IntegrationError::oauth("slack", "OAuth response did not name the workspace")
+            })?;
+            tokens
+                .store(
+                    "slack",
+                    &team.id,
+                    OAuthToken::issued_now(
+                        access_token.clone(),
+                        oauth_response.refresh_token.clone(),
+                        oauth_response.expires_in,
+                        oauth_response.scope.clone(),
+                    ),
+                )
+                .await?;
+        }
+
         Ok(oauth_response)
     }
 }
@@ -509,6 +594,12 @@ impl Integration for SlackIntegration {
             return Ok(false);
         }
 
+        if let Some(tokens) = &self.tokens {
+            if tokens.needs_reauthorization("slack").await {
+                return Ok(false);
+            }
+        }
+
         // TODO: Add actual API health check
         // This could involve making a simple API call to auth.test
         // For now, we'll just check configuration
@@ -555,6 +646,7 @@ mod tests {
             oauth_callback_path: "/oauth/slack/callback".to_string(),
             bot_scopes: vec!["chat:write".to_string()],
             user_scopes: vec!["identity.basic".to_string()],
+            commands: Default::default(),
         }
     }
 
@@ -646,6 +738,25 @@ mod tests {
         assert_eq!(slack_event.text, Some("test-challenge-12345".to_string()));
     }
 
+    #[test]
+    fn test_url_verification_challenge() {
+        let data = json!({
+            "type": "url_verification",
+            "challenge": "test-challenge-12345",
+            "token": "deprecated"
+        });
+        assert_eq!(
+            SlackIntegration::url_verification_challenge(&data),
+            Some("test-challenge-12345")
+        );
+
+        let data = json!({"type": "event_callback", "challenge": "ignored"});
+        assert_eq!(SlackIntegration::url_verification_challenge(&data), None);
+
+        let data = json!({"type": "url_verification"});
+        assert_eq!(SlackIntegration::url_verification_challenge(&data), None);
+    }
+
     #[tokio::test]
     async fn test_health_check() {
         let config = create_test_config();
@@ -0,0 +1,859 @@
+//! Slack slash commands with interactive workflow approval
+//!
+//! `/aicore run <request>` is acknowledged straight away, well inside Slack's
+//! 3-second window, and the request is planned by the intent parser as a dry
+//! run in the background. The proposed workflow is posted to the command's
+//! `response_url` with Approve and Cancel buttons; approving submits the same
+//! request to the intent parser for execution.
+
+use crate::error::{IntegrationError, IntegrationResult};
+use dashmap::DashMap;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Action ID of the button that approves a proposed workflow
+pub const APPROVE_ACTION_ID: &str = "approve_workflow";
+/// Action ID of the button that cancels a proposed workflow
+pub const CANCEL_ACTION_ID: &str = "cancel_workflow";
+
+/// Slash command invocation, as Slack posts it form-encoded
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SlashCommand {
+    pub command: String,
+    pub text: String,
+    pub team_id: String,
+    pub channel_id: String,
+    pub user_id: String,
+    pub user_name: String,
+    pub response_url: String,
+    pub trigger_id: String,
+}
+
+impl SlashCommand {
+    /// Parse the `application/x-www-form-urlencoded` body of a slash command
+    pub fn from_form(body: &[u8]) -> IntegrationResult<Self> {
+        let mut command = Self::default();
+
+        for (key, value) in form_urlencoded::parse(body) {
+            let field = match key.as_ref() {
+                "command" => &mut command.command,
+                "text" => &mut command.text,
+                "team_id" => &mut command.team_id,
+                "channel_id" => &mut command.channel_id,
+                "user_id" => &mut command.user_id,
+                "user_name" => &mut command.user_name,
+                "response_url" => &mut command.response_url,
+                "trigger_id" => &mut command.trigger_id,
+                _ => continue,
+            };
+            *field = value.into_owned();
+        }
+
+        if command.command.is_empty() || command.user_id.is_empty() {
+            return Err(IntegrationError::invalid_payload(
+                "slack",
+                "Slash command is missing command or user_id",
+            ));
+        }
+
+        if command.response_url.is_empty() {
+            return Err(IntegrationError::invalid_payload(
+                "slack",
+                "Slash command is missing response_url",
+            ));
+        }
+
+        Ok(command)
+    }
+}
+
+/// What a slash command asks for
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommandAction {
+    /// Plan `request` and propose it for approval
+    Run {
+        request: String,
+        response_type: ResponseType,
+    },
+    /// Show usage
+    Help,
+}
+
+impl CommandAction {
+    /// Parse the text after the command name, e.g. `run --channel publish the launch post`
+    pub fn parse(text: &str) -> Self {
+        let text = text.trim();
+        let (subcommand, rest) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+
+        if !subcommand.eq_ignore_ascii_case("run") {
+            return Self::Help;
+        }
+
+        let rest = rest.trim();
+        let (response_type, request) = match rest.strip_prefix("--channel") {
+            Some(request) if request.is_empty() || request.starts_with(char::is_whitespace) => {
+                (ResponseType::InChannel, request.trim())
+            }
+            _ => (ResponseType::Ephemeral, rest),
+        };
+
+        if request.is_empty() {
+            return Self::Help;
+        }
+
+        Self::Run {
+            request: request.to_string(),
+            response_type,
+        }
+    }
+}
+
+/// Who sees a slash command response
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseType {
+    /// Only the user who ran the command
+    Ephemeral,
+    /// Everyone in the channel
+    InChannel,
+}
+
+/// Message returned to Slack, either directly or through a `response_url`
+#[derive(Debug, Clone, Serialize)]
+pub struct SlashCommandResponse {
+    pub response_type: ResponseType,
+    pub text: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub blocks: Vec<Value>,
+    /// Whether a `response_url` reply replaces the message that held the buttons
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replace_original: Option<bool>,
+}
+
+impl SlashCommandResponse {
+    /// Text only the invoking user sees
+    pub fn ephemeral<S: Into<String>>(text: S) -> Self {
+        Self::text(ResponseType::Ephemeral, text)
+    }
+
+    /// Plain text response with the given visibility
+    pub fn text<S: Into<String>>(response_type: ResponseType, text: S) -> Self {
+        Self {
+            response_type,
+            text: text.into(),
+            blocks: Vec::new(),
+            replace_original: None,
+        }
+    }
+
+    fn replacing_original(mut self, replace: bool) -> Self {
+        self.replace_original = Some(replace);
+        self
+    }
+}
+
+/// `block_actions` interaction sent when a user clicks a button
+#[derive(Debug, Clone, Deserialize)]
+pub struct BlockActions {
+    #[serde(rename = "type")]
+    pub interaction_type: String,
+    pub user: InteractionUser,
+    pub response_url: Option<String>,
+    #[serde(default)]
+    pub actions: Vec<BlockAction>,
+}
+
+/// User who triggered an interaction
+#[derive(Debug, Clone, Deserialize)]
+pub struct InteractionUser {
+    pub id: String,
+}
+
+/// Button click within a `block_actions` interaction
+#[derive(Debug, Clone, Deserialize)]
+pub struct BlockAction {
+    pub action_id: String,
+    pub value: Option<String>,
+}
+
+impl BlockActions {
+    /// Parse the form-encoded body of an interaction request, which carries
+    /// the interaction as JSON in its `payload` field
+    pub fn from_form(body: &[u8]) -> IntegrationResult<Self> {
+        let payload = form_urlencoded::parse(body)
+            .find(|(key, _)| key == "payload")
+            .map(|(_, value)| value.into_owned())
+            .ok_or_else(|| {
+                IntegrationError::invalid_payload("slack", "Interaction is missing payload")
+            })?;
+
+        serde_json::from_str(&payload).map_err(|e| {
+            IntegrationError::invalid_payload("slack", format!("JSON parsing error: {}", e))
+        })
+    }
+}
+
+/// Workflow proposal waiting for its requester to approve or cancel it
+#[derive(Debug, Clone)]
+struct PendingProposal {
+    command: SlashCommand,
+    request: String,
+    response_type: ResponseType,
+    proposed_at: Instant,
+}
+
+/// Routes slash commands to the intent parser and tracks proposals awaiting approval
+pub struct SlashCommandRouter {
+    http_client: Client,
+    intent_parser_url: String,
+    /// How long to wait for the intent parser before reporting a timeout
+    processing_timeout: Duration,
+    /// How long a proposal can be approved; Slack's `response_url` stops
+    /// accepting replies after 30 minutes
+    proposal_ttl: Duration,
+    proposals: DashMap<Uuid, PendingProposal>,
+}
+
+impl SlashCommandRouter {
+    pub fn new(
+        http_client: Client,
+        intent_parser_url: impl Into<String>,
+        processing_timeout: Duration,
+        proposal_ttl: Duration,
+    ) -> Self {
+        Self {
+            http_client,
+            intent_parser_url: intent_parser_url.into().trim_end_matches('/').to_string(),
+            processing_timeout,
+            proposal_ttl,
+            proposals: DashMap::new(),
+        }
+    }
+
+    /// Proposals still waiting on their requester
+    pub fn pending_proposals(&self) -> usize {
+        self.proposals.len()
+    }
+
+    /// Answer a slash command without waiting on the intent parser
+    ///
+    /// `run` requests are planned in the background and the proposal is
+    /// posted to the command's `response_url`.
+    pub fn handle_command(self: &Arc<Self>, command: SlashCommand) -> SlashCommandResponse {
+        self.prune_expired();
+
+        match CommandAction::parse(&command.text) {
+            CommandAction::Help => SlashCommandResponse::ephemeral(usage(&command.command)),
+            CommandAction::Run {
+                request,
+                response_type,
+            } => {
+                info!(
+                    team_id = %command.team_id,
+                    user_id = %command.user_id,
+                    "Planning workflow for Slack slash command"
+                );
+
+                let acknowledgement = SlashCommandResponse::text(
+                    response_type,
+                    format!("Planning a workflow for: {}", request),
+                );
+
+                let router = self.clone();
+                tokio::spawn(async move {
+                    router.propose(command, request, response_type).await;
+                });
+
+                acknowledgement
+            }
+        }
+    }
+
+    /// Act on an Approve or Cancel click
+    ///
+    /// Slack only waits for the interaction request to be acknowledged, so
+    /// the outcome is posted to the interaction's `response_url`.
+    pub fn handle_interaction(self: &Arc<Self>, interaction: BlockActions) {
+        let router = self.clone();
+        tokio::spawn(async move {
+            let (Some(action), Some(response_url)) = (
+                interaction.actions.first(),
+                interaction.response_url.as_deref(),
+            ) else {
+                debug!("Ignoring Slack interaction without an action or response URL");
+                return;
+            };
+
+            let message = router.resolve_action(&interaction.user.id, action).await;
+            router.respond(response_url, &message).await;
+        });
+    }
+
+    /// Plan `request` as a dry run and post the proposal to the command's `response_url`
+    async fn propose(&self, command: SlashCommand, request: String, response_type: ResponseType) {
+        let message = match self.parse_intent(&command, &request, true).await {
+            Ok(dry_run) if is_approvable(&dry_run) => {
+                let proposal_id = Uuid::new_v4();
+                let message = proposal_message(proposal_id, &request, &dry_run, response_type);
+                self.proposals.insert(
+                    proposal_id,
+                    PendingProposal {
+                        command: command.clone(),
+                        request,
+                        response_type,
+                        proposed_at: Instant::now(),
+                    },
+                );
+                message
+            }
+            Ok(dry_run) => proposal_message(Uuid::nil(), &request, &dry_run, response_type),
+            Err(IntegrationError::Timeout { seconds }) => SlashCommandResponse::ephemeral(format!(
+                "Planning took longer than {} seconds, so nothing was started. Try again or simplify the request.",
+                seconds
+            )),
+            Err(e) => {
+                SlashCommandResponse::ephemeral(format!("Couldn't plan that request: {}", e))
+            }
+        };
+
+        self.respond(&command.response_url, &message).await;
+    }
+
+    /// Approve or cancel the proposal a button belongs to
+    async fn resolve_action(&self, user_id: &str, action: &BlockAction) -> SlashCommandResponse {
+        let Some(proposal_id) = action
+            .value
+            .as_deref()
+            .and_then(|value| Uuid::parse_str(value).ok())
+        else {
+            return SlashCommandResponse::ephemeral("Unknown workflow proposal.")
+                .replacing_original(false);
+        };
+
+        // Only the requester decides; anyone else gets a private note and the
+        // buttons stay in place
+        match self.proposals.get(&proposal_id) {
+            Some(proposal) if proposal.command.user_id != user_id => {
+                return SlashCommandResponse::ephemeral(format!(
+                    "Only <@{}> can approve or cancel this workflow.",
+                    proposal.command.user_id
+                ))
+                .replacing_original(false);
+            }
+            _ => {}
+        }
+
+        let proposal = match self.proposals.remove(&proposal_id) {
+            Some((_, proposal)) if proposal.proposed_at.elapsed() <= self.proposal_ttl => proposal,
+            _ => {
+                return SlashCommandResponse::ephemeral(
+                    "This proposal has expired. Run the command again to get a new one.",
+                )
+                .replacing_original(true);
+            }
+        };
+
+        match action.action_id.as_str() {
+            APPROVE_ACTION_ID => self.execute(proposal).await.replacing_original(true),
+            CANCEL_ACTION_ID => SlashCommandResponse::text(
+                proposal.response_type,
+                format!(
+                    "<@{}> cancelled the workflow for: {}",
+                    proposal.command.user_id, proposal.request
+                ),
+            )
+            .replacing_original(true),
+            other => {
+                warn!(action_id = other, "Unknown Slack workflow action");
+                SlashCommandResponse::ephemeral("Unknown workflow action.")
+                    .replacing_original(false)
+            }
+        }
+    }
+
+    /// Submit an approved proposal for execution
+    async fn execute(&self, proposal: PendingProposal) -> SlashCommandResponse {
+        match self
+            .parse_intent(&proposal.command, &proposal.request, false)
+            .await
+        {
+            Ok(parsed) => execution_message(&proposal, &parsed),
+            // The parser may still finish and start the workflow, so don't invite a blind retry
+            Err(IntegrationError::Timeout { seconds }) => SlashCommandResponse::ephemeral(format!(
+                "The workflow didn't confirm within {} seconds and may still start. Check before running the command again.",
+                seconds
+            )),
+            Err(e) => SlashCommandResponse::ephemeral(format!(
+                "Couldn't start the workflow: {}",
+                e
+            )),
+        }
+    }
+
+    /// Send a request to the intent parser, giving up after the processing timeout
+    async fn parse_intent(
+        &self,
+        command: &SlashCommand,
+        request: &str,
+        validate_only: bool,
+    ) -> IntegrationResult<Value> {
+        let body = json!({
+            "user_id": slack_user_uuid(&command.team_id, &command.user_id),
+            "text": request,
+            "context": {
+                "source": "slack",
+                "team_id": command.team_id,
+                "channel_id": command.channel_id,
+                "slack_user_id": command.user_id
+            },
+            "validate_only": validate_only
+        });
+
+        let call = async {
+            let response = self
+                .http_client
+                .post(format!("{}/v1/parse", self.intent_parser_url))
+                .json(&body)
+                .send()
+                .await
+                .map_err(|e| IntegrationError::external_api("intent-parser", 0, e.to_string()))?;
+
+            let status = response.status();
+            if !status.is_success() {
+                let message = response.text().await.unwrap_or_default();
+                return Err(IntegrationError::external_api(
+                    "intent-parser",
+                    status.as_u16(),
+                    message,
+                ));
+            }
+
+            response.json::<Value>().await.map_err(|e| {
+                IntegrationError::external_api("intent-parser", status.as_u16(), e.to_string())
+            })
+        };
+
+        tokio::time::timeout(self.processing_timeout, call)
+            .await
+            .map_err(|_| IntegrationError::timeout(self.processing_timeout.as_secs()))?
+    }
+
+    /// Post a delayed response; failures are only logged since Slack has
+    /// nowhere else to show them
+    async fn respond(&self, response_url: &str, message: &SlashCommandResponse) {
+        let result = self
+            .http_client
+            .post(response_url)
+            .json(message)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+
+        if let Err(e) = result {
+            warn!(error = %e, "Failed to post slash command response to Slack");
+        }
+    }
+
+    /// Drop proposals that can no longer be approved
+    fn prune_expired(&self) {
+        self.proposals
+            .retain(|_, proposal| proposal.proposed_at.elapsed() <= self.proposal_ttl);
+    }
+}
+
+/// Stable intent parser user ID for a Slack user, so their history and
+/// preferences carry across commands
+pub fn slack_user_uuid(team_id: &str, user_id: &str) -> Uuid {
+    let digest = Sha256::digest(format!("slack:{}:{}", team_id, user_id).as_bytes());
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    Uuid::from_bytes(bytes)
+}
+
+fn usage(command: &str) -> String {
+    format!(
+        "Usage:\n• `{command} run <request>` plans a workflow and asks you to approve it\n• `{command} run --channel <request>` does the same, visible to the whole channel\n• `{command} help` shows this message"
+    )
+}
+
+/// Whether a dry run produced a plan that can be approved as-is
+fn is_approvable(dry_run: &Value) -> bool {
+    dry_run.get("needs_clarification").and_then(Value::as_bool) != Some(true)
+        && dry_run
+            .pointer("/validation/valid")
+            .and_then(Value::as_bool)
+            != Some(false)
+}
+
+/// Display name of a serialized `WorkflowType`
+fn workflow_type_name(intent: &Value) -> String {
+    match intent.get("workflow_type") {
+        Some(Value::String(name)) => name.clone(),
+        Some(Value::Object(custom)) => custom
+            .values()
+            .next()
+            .and_then(Value::as_str)
+            .unwrap_or("Custom")
+            .to_string(),
+        _ => "Workflow".to_string(),
+    }
+}
+
+/// Message presenting a dry-run plan, with Approve and Cancel buttons when
+/// the plan can run as-is
+fn proposal_message(
+    proposal_id: Uuid,
+    request: &str,
+    dry_run: &Value,
+    response_type: ResponseType,
+) -> SlashCommandResponse {
+    if dry_run.get("needs_clarification").and_then(Value::as_bool) == Some(true) {
+        let questions: Vec<String> = dry_run["questions"]
+            .as_array()
+            .map(|questions| {
+                questions
+                    .iter()
+                    .filter_map(|q| q.get("question").and_then(Value::as_str))
+                    .map(|q| format!("• {}", q))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        return SlashCommandResponse::ephemeral(format!(
+            "I'm not sure what to run for: {}\n{}\nRephrase the request and run the command again.",
+            request,
+            questions.join("\n")
+        ));
+    }
+
+    let intent = dry_run.get("intent").unwrap_or(dry_run);
+    let steps = intent["steps"].as_array().map_or(0, Vec::len);
+    let estimated_cost = intent["estimated_cost"].as_f64().unwrap_or(0.0);
+    let summary = format!(
+        "*Proposed workflow:* {}\n>{}\n{} steps · estimated cost ${:.2}",
+        workflow_type_name(intent),
+        request,
+        steps,
+        estimated_cost
+    );
+
+    if !is_approvable(dry_run) {
+        let warnings: Vec<String> = dry_run
+            .pointer("/validation/warnings")
+            .and_then(Value::as_array)
+            .map(|warnings| {
+                warnings
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(|w| format!("• {}", w))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        return SlashCommandResponse::ephemeral(format!(
+            "{}\nThis workflow can't run as requested:\n{}",
+            summary,
+            warnings.join("\n")
+        ));
+    }
+
+    let button = |action_id: &str, label: &str, style: &str| {
+        json!({
+            "type": "button",
+            "action_id": action_id,
+            "style": style,
+            "text": { "type": "plain_text", "text": label },
+            "value": proposal_id.to_string()
+        })
+    };
+
+    SlashCommandResponse {
+        response_type,
+        text: summary.clone(),
+        blocks: vec![
+            json!({
+                "type": "section",
+                "text": { "type": "mrkdwn", "text": summary }
+            }),
+            json!({
+                "type": "actions",
+                "block_id": "workflow_proposal",
+                "elements": [
+                    button(APPROVE_ACTION_ID, "Approve", "primary"),
+                    button(CANCEL_ACTION_ID, "Cancel", "danger")
+                ]
+            }),
+        ],
+        replace_original: None,
+    }
+}
+
+/// Message reporting the intent parser's answer to an approved proposal
+fn execution_message(proposal: &PendingProposal, parsed: &Value) -> SlashCommandResponse {
+    match parsed.get("workflow_id").and_then(Value::as_str) {
+        Some(workflow_id) => SlashCommandResponse::text(
+            proposal.response_type,
+            format!(
+                "<@{}> approved the {} workflow `{}` for: {}",
+                proposal.command.user_id,
+                workflow_type_name(parsed),
+                workflow_id,
+                proposal.request
+            ),
+        ),
+        None => SlashCommandResponse::ephemeral(format!(
+            "The request needs clarification before it can run: {}\nRephrase it and run the command again.",
+            proposal.request
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn command(response_url: &str, text: &str) -> SlashCommand {
+        SlashCommand {
+            command: "/aicore".to_string(),
+            text: text.to_string(),
+            team_id: "T12345678".to_string(),
+            channel_id: "C12345678".to_string(),
+            user_id: "U12345678".to_string(),
+            user_name: "test-user".to_string(),
+            response_url: response_url.to_string(),
+            trigger_id: "13345224609.738474920.8088930838d88f008e0".to_string(),
+        }
+    }
+
+    fn router(intent_parser_url: &str, processing_timeout: Duration) -> SlashCommandRouter {
+        SlashCommandRouter::new(
+            Client::new(),
+            intent_parser_url,
+            processing_timeout,
+            Duration::from_secs(1800),
+        )
+    }
+
+    #[test]
+    fn test_slash_command_form_parsing() {
+        let body = b"token=deprecated&team_id=T12345678&channel_id=C12345678&user_id=U12345678\
+&user_name=test-user&command=%2Faicore&text=run+publish+the+launch+post\
+&response_url=https%3A%2F%2Fhooks.slack.com%2Fcommands%2F1234%2F5678&trigger_id=1.2.3";
+
+        let command = SlashCommand::from_form(body).unwrap();
+        assert_eq!(command.command, "/aicore");
+        assert_eq!(command.text, "run publish the launch post");
+        assert_eq!(
+            command.response_url,
+            "https://hooks.slack.com/commands/1234/5678"
+        );
+        assert_eq!(command.user_id, "U12345678");
+
+        assert!(SlashCommand::from_form(b"command=%2Faicore&user_id=U1").is_err());
+    }
+
+    #[test]
+    fn test_command_action_parsing() {
+        assert_eq!(
+            CommandAction::parse("run publish the launch post"),
+            CommandAction::Run {
+                request: "publish the launch post".to_string(),
+                response_type: ResponseType::Ephemeral,
+            }
+        );
+        assert_eq!(
+            CommandAction::parse("run --channel publish the launch post"),
+            CommandAction::Run {
+                request: "publish the launch post".to_string(),
+                response_type: ResponseType::InChannel,
+            }
+        );
+        assert_eq!(CommandAction::parse("run"), CommandAction::Help);
+        assert_eq!(CommandAction::parse("run --channel"), CommandAction::Help);
+        assert_eq!(CommandAction::parse("status"), CommandAction::Help);
+        assert_eq!(CommandAction::parse(""), CommandAction::Help);
+    }
+
+    #[test]
+    fn test_interaction_form_parsing() {
+        let payload = json!({
+            "type": "block_actions",
+            "user": { "id": "U12345678" },
+            "response_url": "https://hooks.slack.com/actions/1234/5678",
+            "actions": [{ "action_id": APPROVE_ACTION_ID, "value": Uuid::nil().to_string() }]
+        });
+        let body: String = form_urlencoded::Serializer::new(String::new())
+            .append_pair("payload", &payload.to_string())
+            .finish();
+
+        let interaction = BlockActions::from_form(body.as_bytes()).unwrap();
+        assert_eq!(interaction.interaction_type, "block_actions");
+        assert_eq!(interaction.user.id, "U12345678");
+        assert_eq!(interaction.actions[0].action_id, APPROVE_ACTION_ID);
+
+        assert!(BlockActions::from_form(b"token=missing-payload").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_proposal_is_approved_by_requester() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/parse"))
+            .and(body_partial_json(json!({ "validate_only": true })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "validate_only": true,
+                "intent": {
+                    "workflow_type": "ContentCreation",
+                    "steps": [{}, {}],
+                    "estimated_cost": 0.42
+                },
+                "validation": { "valid": true, "warnings": [] }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/parse"))
+            .and(body_partial_json(json!({ "validate_only": false })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "workflow_id": "6a2f41a3-c54c-fce8-32d2-0324e1c32e22",
+                "workflow_type": "ContentCreation"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/response"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let router = router(&server.uri(), Duration::from_secs(5));
+        let response_url = format!("{}/response", server.uri());
+        router
+            .propose(
+                command(&response_url, ""),
+                "publish the launch post".to_string(),
+                ResponseType::InChannel,
+            )
+            .await;
+
+        let requests = server.received_requests().await.unwrap();
+        let proposal: Value = requests.last().unwrap().body_json().unwrap();
+        assert_eq!(proposal["response_type"], "in_channel");
+        let buttons = proposal["blocks"][1]["elements"].as_array().unwrap();
+        assert_eq!(buttons[0]["action_id"], APPROVE_ACTION_ID);
+        assert_eq!(buttons[1]["action_id"], CANCEL_ACTION_ID);
+        assert_eq!(router.pending_proposals(), 1);
+
+        let approve = BlockAction {
+            action_id: APPROVE_ACTION_ID.to_string(),
+            value: buttons[0]["value"].as_str().map(String::from),
+        };
+
+        // Someone else clicking leaves the proposal in place
+        let response = router.resolve_action("U87654321", &approve).await;
+        assert_eq!(response.response_type, ResponseType::Ephemeral);
+        assert_eq!(response.replace_original, Some(false));
+        assert_eq!(router.pending_proposals(), 1);
+
+        let response = router.resolve_action("U12345678", &approve).await;
+        assert_eq!(response.response_type, ResponseType::InChannel);
+        assert_eq!(response.replace_original, Some(true));
+        assert!(response
+            .text
+            .contains("6a2f41a3-c54c-fce8-32d2-0324e1c32e22"));
+        assert_eq!(router.pending_proposals(), 0);
+
+        // A second click finds nothing left to approve
+        let response = router.resolve_action("U12345678", &approve).await;
+        assert!(response.text.contains("expired"));
+    }
+
+    #[tokio::test]
+    async fn test_slow_intent_parser_reports_timeout() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/parse"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_delay(Duration::from_secs(2))
+                    .set_body_json(json!({})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/response"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let router = router(&server.uri(), Duration::from_millis(100));
+        let response_url = format!("{}/response", server.uri());
+        router
+            .propose(
+                command(&response_url, ""),
+                "publish the launch post".to_string(),
+                ResponseType::Ephemeral,
+            )
+            .await;
+
+        let requests = server.received_requests().await.unwrap();
+        let reply: Value = requests
+            .iter()
+            .find(|request| request.url.path() == "/response")
+            .unwrap()
+            .body_json()
+            .unwrap();
+        assert_eq!(reply["response_type"], "ephemeral");
+        assert!(reply["text"].as_str().unwrap().contains("took longer"));
+        assert_eq!(router.pending_proposals(), 0);
+    }
+
+    #[test]
+    fn test_clarification_and_invalid_plans_are_not_approvable() {
+        let clarification = json!({
+            "needs_clarification": true,
+            "questions": [{ "id": "channel", "question": "Which channel should it post to?" }]
+        });
+        assert!(!is_approvable(&clarification));
+        let message = proposal_message(
+            Uuid::nil(),
+            "post it",
+            &clarification,
+            ResponseType::InChannel,
+        );
+        assert_eq!(message.response_type, ResponseType::Ephemeral);
+        assert!(message.blocks.is_empty());
+        assert!(message.text.contains("Which channel should it post to?"));
+
+        let invalid = json!({
+            "intent": { "workflow_type": { "Custom": "Newsletter" } },
+            "validation": { "valid": false, "warnings": ["Missing Mailchimp integration"] }
+        });
+        assert!(!is_approvable(&invalid));
+        let message = proposal_message(Uuid::nil(), "send it", &invalid, ResponseType::Ephemeral);
+        assert!(message.blocks.is_empty());
+        assert!(message.text.contains("Newsletter"));
+        assert!(message.text.contains("Missing Mailchimp integration"));
+    }
+
+    #[test]
+    fn test_slack_user_uuid_is_stable_per_user() {
+        assert_eq!(
+            slack_user_uuid("T12345678", "U12345678"),
+            slack_user_uuid("T12345678", "U12345678")
+        );
+        assert_ne!(
+            slack_user_uuid("T12345678", "U12345678"),
+            slack_user_uuid("T87654321", "U12345678")
+        );
+    }
+}
@@ -43,6 +43,7 @@ pub mod handlers;
 pub mod integrations;
 pub mod metrics;
 pub mod models;
+pub mod oauth;
 pub mod security;
 pub mod service;
 pub mod utils;
@@ -54,10 +55,16 @@ pub use error::{IntegrationError, IntegrationResult};
 pub use models::{
     EventMetadata, GitHubEvent, IntegrationEvent, SlackEvent, WebhookPayload, ZapierEvent,
 };
+pub use oauth::{
+    CredentialStatus, CredentialStore, MemoryCredentialStore, OAuthToken, OAuthTokenManager,
+    PostgresCredentialStore,
+};
 pub use service::IntegrationService;
+pub use webhook::storage::{MemoryEventStorage, PostgresEventStorage};
 pub use webhook::{
-    EventPriority, EventRouter, EventStorage, WebhookConfig, WebhookError, WebhookEvent,
-    WebhookEventStatus, WebhookHandler, WebhookProcessor, WebhookResult, WebhookStats,
+    DeliveryOutcome, DeliveryRetryPolicy, EventDelivery, EventPriority, EventRouter, EventStorage,
+    IdempotencyRecord, WebhookConfig, WebhookError, WebhookEvent, WebhookEventStatus,
+    WebhookHandler, WebhookProcessor, WebhookResult, WebhookStats,
 };
 
 /// Version information for the integration service
@@ -89,6 +96,18 @@ impl HealthStatus {
         self.integrations.insert(name.to_string(), healthy);
         self
     }
+
+    /// Mark integrations whose OAuth credentials are unusable as unhealthy,
+    /// see [`OAuthTokenManager::credential_health`]
+    pub fn with_credential_health(
+        mut self,
+        credentials: std::collections::HashMap<String, bool>,
+    ) -> Self {
+        for (name, usable) in credentials {
+            *self.integrations.entry(name).or_insert(true) &= usable;
+        }
+        self
+    }
 }
 
 #[cfg(test)]
@@ -112,4 +131,18 @@ mod tests {
         assert_eq!(health.integrations.get("zapier"), Some(&true));
         assert_eq!(health.integrations.get("slack"), Some(&false));
     }
+
+    #[test]
+    fn test_health_status_with_credential_health() {
+        let credentials = [("slack".to_string(), false), ("github".to_string(), true)]
+            .into_iter()
+            .collect();
+        let health = HealthStatus::healthy()
+            .with_integration_status("slack", true)
+            .with_integration_status("github", true)
+            .with_credential_health(credentials);
+
+        assert_eq!(health.integrations.get("slack"), Some(&false));
+        assert_eq!(health.integrations.get("github"), Some(&true));
+    }
 }
@@ -114,6 +114,11 @@ impl SecurityUtils {
             .copied()
             .collect::<Vec<u8>>();
 
+        // Slack signatures are versioned as "v0=<hex digest>"
+        let signature = signature.strip_prefix("v0=").ok_or_else(|| {
+            IntegrationError::signature_verification("slack", "Unsupported signature version")
+        })?;
+
         // Verify signature
         Self::verify_hmac_sha256(&sig_basestring, signature, signing_secret)
     }
@@ -462,5 +467,12 @@ mod tests {
         headers.insert("x-slack-request-timestamp".to_string(), old_timestamp);
         let result = SecurityUtils::verify_slack_signature(payload, &headers, signing_secret);
         assert!(result.is_err());
+
+        // Unversioned signatures are rejected
+        let timestamp = chrono::Utc::now().timestamp().to_string();
+        headers.insert("x-slack-request-timestamp".to_string(), timestamp);
+        headers.insert("x-slack-signature".to_string(), "deadbeef".to_string());
+        let result = SecurityUtils::verify_slack_signature(payload, &headers, signing_secret);
+        assert!(result.is_err());
     }
 }
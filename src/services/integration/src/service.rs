@@ -6,8 +6,14 @@
 use crate::config::IntegrationConfig;
 use crate::error::{IntegrationError, IntegrationResult};
 use crate::handlers::create_routes;
+use crate::integrations::slack_commands::SlashCommandRouter;
 use crate::integrations::{Integration, IntegrationFactory};
 use crate::metrics::IntegrationMetrics;
+use crate::oauth::{OAuthTokenManager, PostgresCredentialStore};
+use crate::webhook::router::{EventDispatcher, HttpEventDelivery, StaticEventRouter};
+use crate::webhook::storage::{MemoryEventStorage, PostgresEventStorage};
+use crate::webhook::{EventStorage, WebhookHandler};
+use ai_core_shared::rate_limit::SlidingWindowLimiter;
 use axum::serve;
 use std::collections::HashMap;
 use std::net::SocketAddr;
@@ -47,6 +53,17 @@ pub struct AppState {
     pub integrations: HashMap<String, Box<dyn Integration>>,
     /// Metrics collector
     pub metrics: Arc<tokio::sync::Mutex<IntegrationMetrics>>,
+    /// Sliding-window request limiter
+    pub rate_limiter: Arc<SlidingWindowLimiter>,
+    /// OAuth credentials for Slack and GitHub connections
+    pub oauth_tokens: Arc<OAuthTokenManager>,
+    /// Slack slash command routing, when an intent parser is configured
+    pub slack_commands: Option<Arc<SlashCommandRouter>>,
+    /// Webhook handling, deduplicating retried deliveries
+    pub webhooks: Arc<WebhookHandler>,
+    /// Downstream delivery of processed webhooks, when a delivery URL is
+    /// configured
+    pub event_dispatcher: Option<Arc<EventDispatcher>>,
 }
 
 /// Custom request ID generator
@@ -115,8 +132,66 @@ impl IntegrationService {
             None
         };
 
-        // Initialize integrations
-        let integrations = Self::initialize_integrations(&config)?;
+        // Initialize OAuth token storage and integrations
+        let oauth_tokens =
+            Self::create_token_manager(&config, http_client.clone(), db_pool.as_ref()).await;
+        let integrations = Self::initialize_integrations(&config, &oauth_tokens)?;
+
+        // Slash commands are planned by the intent parser, so they need its URL,
+        // and can approve workflows, so they need a signing secret to verify requests
+        let has_signing_secret = config
+            .slack
+            .signing_secret
+            .as_ref()
+            .is_some_and(|secret| !secret.is_empty());
+        if config.slack.commands.intent_parser_url.is_some() && !has_signing_secret {
+            warn!("Slack slash commands disabled: no signing secret is configured");
+        }
+        let slack_commands = config
+            .slack
+            .commands
+            .intent_parser_url
+            .as_ref()
+            .filter(|_| has_signing_secret && integrations.contains_key("slack"))
+            .map(|intent_parser_url| {
+                Arc::new(SlashCommandRouter::new(
+                    http_client.clone(),
+                    intent_parser_url.clone(),
+                    std::time::Duration::from_secs(
+                        config.slack.commands.processing_timeout_seconds,
+                    ),
+                    std::time::Duration::from_secs(config.slack.commands.proposal_ttl_seconds),
+                ))
+            });
+
+        // Deduplicate webhook deliveries against the event store, routing
+        // each integration's events to its own processor
+        let event_storage = Self::create_event_storage(db_pool.as_ref()).await;
+        let event_router = Arc::new(StaticEventRouter::new(
+            integrations
+                .keys()
+                .map(|name| (name.clone(), vec![name.clone()]))
+                .collect(),
+            Vec::new(),
+        ));
+        let event_dispatcher = config.webhook.delivery_url.as_ref().map(|url| {
+            info!("Delivering processed webhooks to {}", url);
+            Arc::new(EventDispatcher::new(
+                event_router.clone(),
+                Arc::new(HttpEventDelivery::new(
+                    http_client.clone(),
+                    url.clone(),
+                    std::time::Duration::from_secs(config.webhook.processing_timeout),
+                )),
+                event_storage.clone(),
+                config.webhook.delivery_retry.clone(),
+            ))
+        });
+        let webhooks = Arc::new(WebhookHandler::new(
+            config.webhook.clone(),
+            event_router,
+            event_storage,
+        ));
 
         // Initialize metrics
         let metrics = Arc::new(tokio::sync::Mutex::new(IntegrationMetrics::new()));
@@ -129,6 +204,11 @@ impl IntegrationService {
             db_pool,
             integrations,
             metrics,
+            rate_limiter: Arc::new(SlidingWindowLimiter::new()),
+            oauth_tokens,
+            slack_commands,
+            webhooks,
+            event_dispatcher,
         });
 
         // Create server address
@@ -164,6 +244,15 @@ impl IntegrationService {
                 CustomMakeRequestId::default(),
             ));
 
+        // Refresh OAuth tokens ahead of expiry
+        if self.config.token_refresh.enabled {
+            self.app_state
+                .oauth_tokens
+                .start_refresh_task(std::time::Duration::from_secs(
+                    self.config.token_refresh.check_interval_seconds.max(1),
+                ));
+        }
+
         // Create routes
         let app = create_routes(self.app_state.clone()).layer(middleware);
 
@@ -193,6 +282,7 @@ impl IntegrationService {
     /// Initialize all enabled integrations
     fn initialize_integrations(
         config: &IntegrationConfig,
+        oauth_tokens: &Arc<OAuthTokenManager>,
     ) -> IntegrationResult<HashMap<String, Box<dyn Integration>>> {
         let mut integrations = HashMap::new();
 
@@ -205,7 +295,7 @@ impl IntegrationService {
 
         // Initialize Slack integration
         if config.slack.enabled {
-            match IntegrationFactory::create_slack(&config.slack) {
+            match IntegrationFactory::create_slack(&config.slack, oauth_tokens.clone()) {
                 Ok(slack) => {
                     integrations.insert("slack".to_string(), slack);
                     info!("Slack integration initialized");
@@ -221,7 +311,7 @@ impl IntegrationService {
 
         // Initialize GitHub integration
         if config.github.enabled {
-            match IntegrationFactory::create_github(&config.github) {
+            match IntegrationFactory::create_github(&config.github, oauth_tokens.clone()) {
                 Ok(github) => {
                     integrations.insert("github".to_string(), github);
                     info!("GitHub integration initialized");
@@ -289,6 +379,58 @@ impl IntegrationService {
         Ok(pool)
     }
 
+    /// Create the OAuth token manager, persisting credentials to PostgreSQL
+    /// when a database is configured
+    async fn create_token_manager(
+        config: &IntegrationConfig,
+        http_client: reqwest::Client,
+        db_pool: Option<&sqlx::PgPool>,
+    ) -> Arc<OAuthTokenManager> {
+        let manager = OAuthTokenManager::new(
+            http_client,
+            std::time::Duration::from_secs(config.token_refresh.refresh_margin_seconds),
+        );
+        let Some(pool) = db_pool else {
+            warn!("No database configured, OAuth credentials are kept in memory");
+            return Arc::new(manager);
+        };
+
+        let store = PostgresCredentialStore::new(pool.clone());
+        if let Err(e) = store.run_migrations().await {
+            warn!(
+                "Failed to prepare OAuth credential storage, keeping credentials in memory: {}",
+                e
+            );
+            return Arc::new(manager);
+        }
+
+        let manager = manager.with_store(Arc::new(store));
+        if let Err(e) = manager.load().await {
+            warn!("Failed to load stored OAuth credentials: {}", e);
+        }
+        Arc::new(manager)
+    }
+
+    /// Create webhook event storage, in PostgreSQL when a database is configured
+    async fn create_event_storage(db_pool: Option<&sqlx::PgPool>) -> Arc<dyn EventStorage> {
+        let Some(pool) = db_pool else {
+            warn!("No database configured, webhook idempotency keys are kept in memory");
+            return Arc::new(MemoryEventStorage::new());
+        };
+
+        let storage = PostgresEventStorage::new(pool.clone());
+        match storage.run_migrations().await {
+            Ok(()) => Arc::new(storage),
+            Err(e) => {
+                warn!(
+                    "Failed to prepare webhook event storage, keeping events in memory: {}",
+                    e
+                );
+                Arc::new(MemoryEventStorage::new())
+            }
+        }
+    }
+
     /// Wait for shutdown signal
     async fn shutdown_signal() {
         let ctrl_c = async {
@@ -343,7 +485,11 @@ mod tests {
     #[test]
     fn test_initialize_integrations() {
         let config = IntegrationConfig::default();
-        let result = IntegrationService::initialize_integrations(&config);
+        let oauth_tokens = Arc::new(OAuthTokenManager::new(
+            reqwest::Client::new(),
+            std::time::Duration::from_secs(300),
+        ));
+        let result = IntegrationService::initialize_integrations(&config, &oauth_tokens);
 
         assert!(result.is_ok());
         let integrations = result.unwrap();
@@ -14,18 +14,26 @@ pub mod processor;
 pub mod queue;
 pub mod retry;
 pub mod router;
+pub mod storage;
 pub mod validator;
 
 use crate::error::{IntegrationError, IntegrationResult};
 use crate::models::{IntegrationEvent, WebhookPayload};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
 use tokio::sync::RwLock;
+use tracing::{debug, warn};
 use uuid::Uuid;
 
+/// Header Zapier sets to identify retried deliveries of the same webhook
+pub const ZAPIER_IDEMPOTENCY_HEADER: &str = "x-zapier-idempotency-key";
+/// Body field accepted as an idempotency key when no header is present
+pub const IDEMPOTENCY_KEY_FIELD: &str = "idempotency_key";
+
 /// Webhook event processing status
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum WebhookEventStatus {
@@ -43,6 +51,43 @@ pub enum WebhookEventStatus {
     Cancelled,
 }
 
+impl WebhookEventStatus {
+    /// Stable identifier used by storage backends
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Received => "received",
+            Self::Processing => "processing",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::DeadLettered => "dead_lettered",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    /// Parse a status from its storage identifier
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "received" => Some(Self::Received),
+            "processing" => Some(Self::Processing),
+            "completed" => Some(Self::Completed),
+            "failed" => Some(Self::Failed),
+            "dead_lettered" => Some(Self::DeadLettered),
+            "cancelled" => Some(Self::Cancelled),
+            _ => None,
+        }
+    }
+
+    /// Check if the event is still queued or being processed
+    pub fn is_active(&self) -> bool {
+        matches!(self, Self::Received | Self::Processing)
+    }
+
+    /// Check if the event ended in failure
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::Failed | Self::DeadLettered)
+    }
+}
+
 /// Webhook event priority levels
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum EventPriority {
@@ -147,6 +192,63 @@ impl WebhookEvent {
     }
 }
 
+/// Record of a webhook accepted under an idempotency key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdempotencyRecord {
+    /// Idempotency key, namespaced by integration
+    pub key: String,
+    /// Event created for the first delivery
+    pub event_id: Uuid,
+    /// When the key was first seen
+    pub created_at: DateTime<Utc>,
+    /// When the key stops deduplicating deliveries
+    pub expires_at: DateTime<Utc>,
+    /// Result of the first delivery, once it has been processed
+    #[serde(default)]
+    pub result: Option<serde_json::Value>,
+}
+
+impl IdempotencyRecord {
+    /// Create a record for an accepted event that expires after `ttl`
+    pub fn new(key: String, event_id: Uuid, ttl: chrono::Duration) -> Self {
+        let now = Utc::now();
+        Self {
+            key,
+            event_id,
+            created_at: now,
+            expires_at: now + ttl,
+            result: None,
+        }
+    }
+
+    /// Check if the record no longer deduplicates deliveries
+    pub fn is_expired(&self) -> bool {
+        Utc::now() >= self.expires_at
+    }
+}
+
+/// Extract the idempotency key from a webhook payload
+///
+/// The `X-Zapier-Idempotency-Key` header takes precedence over an
+/// `idempotency_key` body field. Keys are namespaced by integration.
+pub fn extract_idempotency_key(payload: &WebhookPayload) -> Option<String> {
+    let key = payload
+        .headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(ZAPIER_IDEMPOTENCY_HEADER))
+        .map(|(_, value)| value.trim().to_string())
+        .or_else(|| {
+            payload
+                .data
+                .get(IDEMPOTENCY_KEY_FIELD)
+                .and_then(|v| v.as_str())
+                .map(|v| v.trim().to_string())
+        })
+        .filter(|key| !key.is_empty())?;
+
+    Some(format!("{}:{}", payload.integration, key))
+}
+
 /// Webhook processing configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebhookConfig {
@@ -170,6 +272,20 @@ pub struct WebhookConfig {
     pub enable_compression: bool,
     /// Webhook signature validation timeout
     pub signature_validation_timeout: u64,
+    /// How long idempotency keys deduplicate deliveries, in seconds
+    #[serde(default = "default_idempotency_ttl_secs")]
+    pub idempotency_ttl_secs: u64,
+    /// Retry policy for delivering routed events downstream
+    #[serde(default)]
+    pub delivery_retry: DeliveryRetryPolicy,
+    /// Endpoint processed events are delivered to, typically the workflow
+    /// engine; events are not delivered downstream when unset
+    #[serde(default)]
+    pub delivery_url: Option<String>,
+}
+
+fn default_idempotency_ttl_secs() -> u64 {
+    24 * 60 * 60
 }
 
 impl Default for WebhookConfig {
@@ -185,7 +301,62 @@ impl Default for WebhookConfig {
             processing_timeout: 30,
             enable_compression: true,
             signature_validation_timeout: 5,
+            idempotency_ttl_secs: default_idempotency_ttl_secs(),
+            delivery_retry: DeliveryRetryPolicy::default(),
+            delivery_url: None,
+        }
+    }
+}
+
+/// Retry policy for downstream delivery of routed events
+///
+/// Retries use exponential backoff from `base_delay_ms`, capped at
+/// `max_delay_ms`. With `jitter` enabled the actual delay is drawn uniformly
+/// between zero and that cap ("full jitter").
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryRetryPolicy {
+    /// Total delivery attempts, including the first
+    pub max_attempts: u32,
+    /// Backoff base delay in milliseconds
+    pub base_delay_ms: u64,
+    /// Maximum backoff delay in milliseconds
+    pub max_delay_ms: u64,
+    /// Apply full jitter to the backoff delay
+    pub jitter: bool,
+}
+
+impl Default for DeliveryRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay_ms: 200,
+            max_delay_ms: 30_000,
+            jitter: true,
+        }
+    }
+}
+
+impl DeliveryRetryPolicy {
+    /// Backoff cap for the retry following failed attempt `attempt` (1-based)
+    pub fn backoff_ceiling(&self, attempt: u32) -> std::time::Duration {
+        let exponent = attempt.saturating_sub(1).min(32);
+        let delay = self
+            .base_delay_ms
+            .saturating_mul(1u64 << exponent)
+            .min(self.max_delay_ms);
+        std::time::Duration::from_millis(delay)
+    }
+
+    /// Delay to wait before retrying after failed attempt `attempt` (1-based)
+    pub fn delay_for(&self, attempt: u32) -> std::time::Duration {
+        let ceiling = self.backoff_ceiling(attempt);
+        if !self.jitter || ceiling.is_zero() {
+            return ceiling;
         }
+
+        use rand::Rng;
+        let millis = rand::thread_rng().gen_range(0..=ceiling.as_millis() as u64);
+        std::time::Duration::from_millis(millis)
     }
 }
 
@@ -208,6 +379,9 @@ pub struct WebhookStats {
     pub queue_depth: u64,
     /// Last processing timestamp
     pub last_processed_at: Option<DateTime<Utc>>,
+    /// Deliveries answered from an idempotency record instead of being routed
+    #[serde(default)]
+    pub total_deduplicated: u64,
 }
 
 /// Trait for webhook event processing
@@ -238,29 +412,87 @@ pub trait EventRouter: Send + Sync {
     fn get_routing_config(&self) -> HashMap<String, Vec<String>>;
 }
 
-/// Trait for webhook event storage
+/// Trait for delivering routed events to downstream consumers such as the
+/// workflow engine
+#[async_trait]
+pub trait EventDelivery: Send + Sync {
+    /// Deliver an event to the processors it was routed to
+    async fn deliver(&self, event: &WebhookEvent, processors: &[String]) -> IntegrationResult<()>;
+}
+
+/// Trait for webhook event storage backends
+///
+/// See [`storage::MemoryEventStorage`] and [`storage::PostgresEventStorage`].
 #[async_trait]
 pub trait EventStorage: Send + Sync {
-    /// Store a webhook event
-    async fn store_event(&self, event: &WebhookEvent) -> IntegrationResult<()>;
+    /// Store a webhook event, replacing any stored event with the same ID
+    async fn store(&self, event: &WebhookEvent) -> IntegrationResult<()>;
 
     /// Retrieve an event by ID
-    async fn get_event(&self, id: Uuid) -> IntegrationResult<Option<WebhookEvent>>;
-
-    /// Update event status
-    async fn update_event(&self, event: &WebhookEvent) -> IntegrationResult<()>;
-
-    /// Get events ready for retry
-    async fn get_retry_events(&self, limit: usize) -> IntegrationResult<Vec<WebhookEvent>>;
-
-    /// Get dead letter queue events
+    async fn get(&self, id: Uuid) -> IntegrationResult<Option<WebhookEvent>>;
+
+    /// Set an event's status and error, returning `false` if the event is
+    /// not stored
+    async fn update_status(
+        &self,
+        id: Uuid,
+        status: WebhookEventStatus,
+        error: Option<String>,
+    ) -> IntegrationResult<bool>;
+
+    /// List events with the given status, least recently updated first
+    async fn list_by_status(
+        &self,
+        status: WebhookEventStatus,
+        limit: usize,
+    ) -> IntegrationResult<Vec<WebhookEvent>>;
+
+    /// Delete events last updated before `cutoff` and expired idempotency
+    /// records, returning the number of events removed
+    ///
+    /// Events that are still queued or processing are kept regardless of age.
+    async fn purge_older_than(&self, cutoff: DateTime<Utc>) -> IntegrationResult<u64>;
+
+    /// Get dead letter queue events that have not been replayed
     async fn get_dead_letter_events(&self, limit: usize) -> IntegrationResult<Vec<WebhookEvent>>;
 
-    /// Clean up old events
-    async fn cleanup_old_events(&self, retention_hours: u64) -> IntegrationResult<u64>;
+    /// Move an event that could not be delivered to the dead letter store
+    async fn store_dead_letter(&self, event: &WebhookEvent) -> IntegrationResult<()>;
 
     /// Get processing statistics
     async fn get_stats(&self) -> IntegrationResult<WebhookStats>;
+
+    /// Store an idempotency record unless an unexpired one exists for the
+    /// same key, in which case the existing record is returned
+    async fn claim_idempotency_key(
+        &self,
+        record: &IdempotencyRecord,
+    ) -> IntegrationResult<Option<IdempotencyRecord>>;
+
+    /// Remove an idempotency record so the key can be delivered again
+    async fn release_idempotency_key(&self, key: &str) -> IntegrationResult<()>;
+
+    /// Cache the result of the first delivery on its idempotency record
+    async fn complete_idempotency_key(
+        &self,
+        key: &str,
+        result: &serde_json::Value,
+    ) -> IntegrationResult<()>;
+}
+
+/// Outcome of handling a webhook delivery inline
+#[derive(Debug)]
+pub enum DeliveryOutcome<T> {
+    /// The delivery was new and was processed
+    Processed(T),
+    /// The delivery repeats an idempotency key seen within the TTL
+    Duplicate {
+        /// Event ID of the original delivery
+        event_id: Uuid,
+        /// Result of the original delivery, or `None` if it is still being
+        /// processed
+        result: Option<T>,
+    },
 }
 
 /// Main webhook handling system
@@ -284,7 +516,10 @@ impl WebhookHandler {
         storage: Arc<dyn EventStorage>,
     ) -> Self {
         let collector = Arc::new(collector::WebhookCollector::new(config.clone()));
-        let processor = Arc::new(processor::EventProcessor::new(config.clone()));
+        let processor = Arc::new(processor::EventProcessor::new(
+            config.clone(),
+            Arc::clone(&storage),
+        ));
         let retry_manager = Arc::new(retry::RetryManager::new(config.clone()));
         let dead_letter_queue = Arc::new(queue::DeadLetterQueue::new(config.clone()));
 
@@ -308,13 +543,30 @@ impl WebhookHandler {
     }
 
     /// Handle incoming webhook
+    ///
+    /// Deliveries carrying an idempotency key seen within
+    /// `idempotency_ttl_secs` return the original event ID without being
+    /// routed again.
     pub async fn handle_webhook(&self, payload: WebhookPayload) -> IntegrationResult<Uuid> {
+        let idempotency_key = extract_idempotency_key(&payload);
+
         // Create webhook event
         let event = WebhookEvent::new(payload, EventPriority::Normal);
         let event_id = event.id;
 
+        if let Some(original) = self
+            .claim_delivery(idempotency_key.as_deref(), event_id)
+            .await?
+        {
+            return Ok(original.event_id);
+        }
+
         // Collect event for processing
-        self.collector.collect(event).await?;
+        if let Err(e) = self.collector.collect(event).await {
+            // Let the producer's retry through since this delivery was not accepted
+            self.release_delivery(idempotency_key.as_deref()).await;
+            return Err(e);
+        }
 
         // Update statistics
         let mut stats = self.stats.write().await;
@@ -324,6 +576,119 @@ impl WebhookHandler {
         Ok(event_id)
     }
 
+    /// Handle incoming webhook inline with `process` instead of queuing it
+    ///
+    /// Deduplicates like [`handle_webhook`](Self::handle_webhook), recording
+    /// the payload ID as the event ID. The result of a processed delivery is
+    /// cached on its idempotency record and returned for duplicates. A
+    /// delivery that fails to process releases its idempotency key so the
+    /// producer's retry is processed.
+    pub async fn handle_webhook_with<F, Fut, T>(
+        &self,
+        payload: WebhookPayload,
+        process: F,
+    ) -> IntegrationResult<DeliveryOutcome<T>>
+    where
+        F: FnOnce(WebhookPayload) -> Fut,
+        Fut: Future<Output = IntegrationResult<T>>,
+        T: Serialize + DeserializeOwned,
+    {
+        let idempotency_key = extract_idempotency_key(&payload);
+
+        if let Some(original) = self
+            .claim_delivery(idempotency_key.as_deref(), payload.id)
+            .await?
+        {
+            let result = original.result.map(serde_json::from_value).transpose()?;
+            return Ok(DeliveryOutcome::Duplicate {
+                event_id: original.event_id,
+                result,
+            });
+        }
+
+        match process(payload).await {
+            Ok(result) => {
+                if let Some(key) = idempotency_key.as_deref() {
+                    self.complete_delivery(key, &result).await;
+                }
+                self.stats.write().await.total_received += 1;
+                Ok(DeliveryOutcome::Processed(result))
+            }
+            Err(e) => {
+                self.release_delivery(idempotency_key.as_deref()).await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Claim an idempotency key for `event_id`, returning the original
+    /// record if the key was already claimed within the TTL
+    async fn claim_delivery(
+        &self,
+        idempotency_key: Option<&str>,
+        event_id: Uuid,
+    ) -> IntegrationResult<Option<IdempotencyRecord>> {
+        let Some(key) = idempotency_key else {
+            return Ok(None);
+        };
+
+        let record = IdempotencyRecord::new(
+            key.to_string(),
+            event_id,
+            chrono::Duration::seconds(self.config.idempotency_ttl_secs as i64),
+        );
+
+        let Some(existing) = self.storage.claim_idempotency_key(&record).await? else {
+            return Ok(None);
+        };
+
+        debug!(
+            idempotency_key = %key,
+            event_id = %existing.event_id,
+            "Duplicate webhook delivery, returning original event"
+        );
+
+        let mut stats = self.stats.write().await;
+        stats.total_deduplicated += 1;
+
+        Ok(Some(existing))
+    }
+
+    /// Cache the result of a processed delivery for later duplicates
+    async fn complete_delivery<T: Serialize>(&self, idempotency_key: &str, result: &T) {
+        let cached = match serde_json::to_value(result) {
+            Ok(value) => {
+                self.storage
+                    .complete_idempotency_key(idempotency_key, &value)
+                    .await
+            }
+            Err(e) => Err(e.into()),
+        };
+
+        if let Err(e) = cached {
+            warn!(
+                idempotency_key = %idempotency_key,
+                error = %e,
+                "Failed to cache webhook delivery result"
+            );
+        }
+    }
+
+    /// Release an idempotency key for a delivery that was not accepted
+    async fn release_delivery(&self, idempotency_key: Option<&str>) {
+        let Some(key) = idempotency_key else {
+            return;
+        };
+
+        if let Err(e) = self.storage.release_idempotency_key(key).await {
+            warn!(
+                idempotency_key = %key,
+                error = %e,
+                "Failed to release idempotency key"
+            );
+        }
+    }
+
     /// Process webhook events from queue
     pub async fn process_events(&self) -> IntegrationResult<()> {
         self.processor.process_batch().await
@@ -339,16 +704,23 @@ impl WebhookHandler {
         self.dead_letter_queue.process_queue().await
     }
 
+    /// Number of deliveries answered from an idempotency record
+    pub async fn deduplicated_count(&self) -> u64 {
+        self.stats.read().await.total_deduplicated
+    }
+
     /// Get webhook processing statistics
     pub async fn get_stats(&self) -> IntegrationResult<WebhookStats> {
-        self.storage.get_stats().await
+        let mut stats = self.storage.get_stats().await?;
+        stats.total_deduplicated = self.stats.read().await.total_deduplicated;
+        Ok(stats)
     }
 
     /// Clean up old processed events
     pub async fn cleanup_events(&self) -> IntegrationResult<u64> {
-        self.storage
-            .cleanup_old_events(self.config.dead_letter_retention_hours)
-            .await
+        let cutoff =
+            Utc::now() - chrono::Duration::hours(self.config.dead_letter_retention_hours as i64);
+        self.storage.purge_older_than(cutoff).await
     }
 
     /// Start webhook processing background tasks
@@ -381,12 +753,17 @@ impl WebhookHandler {
 
     /// Get event by ID
     pub async fn get_event(&self, id: Uuid) -> IntegrationResult<Option<WebhookEvent>> {
-        self.storage.get_event(id).await
+        self.storage.get(id).await
+    }
+
+    /// Get events whose delivery failed, for inspection and manual replay
+    pub async fn get_failed_events(&self, limit: usize) -> IntegrationResult<Vec<WebhookEvent>> {
+        self.storage.get_dead_letter_events(limit).await
     }
 
     /// Replay a dead lettered event
     pub async fn replay_event(&self, id: Uuid) -> IntegrationResult<()> {
-        if let Some(mut event) = self.storage.get_event(id).await? {
+        if let Some(mut event) = self.storage.get(id).await? {
             // Reset event for replay
             event.status = WebhookEventStatus::Received;
             event.attempt_count = 0;
@@ -395,7 +772,7 @@ impl WebhookHandler {
             event.updated_at = Utc::now();
 
             // Store updated event
-            self.storage.update_event(&event).await?;
+            self.storage.store(&event).await?;
 
             // Re-queue for processing
             self.collector.collect(event).await?;
@@ -411,6 +788,15 @@ pub enum WebhookError {
     #[error("Webhook validation failed: {0}")]
     ValidationFailed(String),
 
+    #[error("Missing webhook signature header: {0}")]
+    MissingSignature(String),
+
+    #[error("Malformed webhook signature: {0}")]
+    MalformedSignature(String),
+
+    #[error("Webhook signature does not match payload")]
+    SignatureMismatch,
+
     #[error("Event processing failed: {0}")]
     ProcessingFailed(String),
 
@@ -443,6 +829,12 @@ impl From<WebhookError> for IntegrationError {
                 integration: "webhook".to_string(),
                 reason: msg,
             },
+            WebhookError::MissingSignature(_)
+            | WebhookError::MalformedSignature(_)
+            | WebhookError::SignatureMismatch => IntegrationError::SignatureVerification {
+                integration: "webhook".to_string(),
+                reason: err.to_string(),
+            },
             WebhookError::ProcessingFailed(msg) => {
                 IntegrationError::WebhookProcessing { message: msg }
             }
@@ -518,6 +910,146 @@ mod tests {
         assert!(config.enable_compression);
     }
 
+    #[test]
+    fn test_extract_idempotency_key() {
+        let mut payload = create_test_payload();
+        payload.integration = "zapier".to_string();
+        assert_eq!(extract_idempotency_key(&payload), None);
+
+        payload.data = json!({"idempotency_key": "body-key"});
+        assert_eq!(
+            extract_idempotency_key(&payload).as_deref(),
+            Some("zapier:body-key")
+        );
+
+        // Header wins over the body field
+        payload.headers.insert(
+            "X-Zapier-Idempotency-Key".to_string(),
+            "header-key".to_string(),
+        );
+        assert_eq!(
+            extract_idempotency_key(&payload).as_deref(),
+            Some("zapier:header-key")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_webhook_returns_original_event() {
+        let router = Arc::new(router::StaticEventRouter::new(
+            HashMap::new(),
+            vec!["default".to_string()],
+        ));
+        let storage = Arc::new(storage::MemoryEventStorage::new());
+        let handler = WebhookHandler::new(WebhookConfig::default(), router, storage);
+        handler.collector.start().await.unwrap();
+
+        let mut payload = create_test_payload();
+        payload
+            .headers
+            .insert(ZAPIER_IDEMPOTENCY_HEADER.to_string(), "zap-1".to_string());
+
+        let first = handler.handle_webhook(payload.clone()).await.unwrap();
+        let second = handler.handle_webhook(payload).await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(handler.get_stats().await.unwrap().total_deduplicated, 1);
+
+        // A different key is routed as a new event
+        let mut other = create_test_payload();
+        other
+            .headers
+            .insert(ZAPIER_IDEMPOTENCY_HEADER.to_string(), "zap-2".to_string());
+        assert_ne!(handler.handle_webhook(other).await.unwrap(), first);
+    }
+
+    #[tokio::test]
+    async fn test_inline_duplicate_is_not_processed_again() {
+        let router = Arc::new(router::StaticEventRouter::new(
+            HashMap::new(),
+            vec!["default".to_string()],
+        ));
+        let storage = Arc::new(storage::MemoryEventStorage::new());
+        let handler = WebhookHandler::new(WebhookConfig::default(), router, storage);
+        let processed = std::sync::atomic::AtomicU32::new(0);
+        let counter = &processed;
+        let process = move |_payload: WebhookPayload| async move {
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok("done".to_string())
+        };
+
+        let mut payload = create_test_payload();
+        payload
+            .headers
+            .insert(ZAPIER_IDEMPOTENCY_HEADER.to_string(), "zap-1".to_string());
+
+        let first = handler
+            .handle_webhook_with(payload.clone(), process)
+            .await
+            .unwrap();
+        assert!(matches!(first, DeliveryOutcome::Processed(ref result) if result == "done"));
+
+        let mut retry = payload.clone();
+        retry.id = Uuid::new_v4();
+        let second = handler.handle_webhook_with(retry, process).await.unwrap();
+        assert!(matches!(
+            second,
+            DeliveryOutcome::Duplicate { event_id, result: Some(ref result) }
+                if event_id == payload.id && result == "done"
+        ));
+        assert_eq!(processed.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(handler.deduplicated_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_inline_failure_releases_idempotency_key() {
+        let router = Arc::new(router::StaticEventRouter::new(
+            HashMap::new(),
+            vec!["default".to_string()],
+        ));
+        let storage = Arc::new(storage::MemoryEventStorage::new());
+        let handler = WebhookHandler::new(WebhookConfig::default(), router, storage);
+
+        let mut payload = create_test_payload();
+        payload
+            .headers
+            .insert(ZAPIER_IDEMPOTENCY_HEADER.to_string(), "zap-1".to_string());
+
+        let failed = handler
+            .handle_webhook_with(payload.clone(), |_| async {
+                Err::<(), _>(IntegrationError::webhook_processing("downstream down"))
+            })
+            .await;
+        assert!(failed.is_err());
+
+        let retried = handler
+            .handle_webhook_with(payload, |_| async { Ok(()) })
+            .await
+            .unwrap();
+        assert!(matches!(retried, DeliveryOutcome::Processed(())));
+    }
+
+    #[test]
+    fn test_delivery_retry_backoff() {
+        let policy = DeliveryRetryPolicy {
+            max_attempts: 5,
+            base_delay_ms: 100,
+            max_delay_ms: 1_000,
+            jitter: false,
+        };
+
+        assert_eq!(policy.delay_for(1).as_millis(), 100);
+        assert_eq!(policy.delay_for(2).as_millis(), 200);
+        assert_eq!(policy.delay_for(4).as_millis(), 800);
+        assert_eq!(policy.delay_for(10).as_millis(), 1_000);
+
+        let jittered = DeliveryRetryPolicy {
+            jitter: true,
+            ..policy
+        };
+        for attempt in 1..10 {
+            assert!(jittered.delay_for(attempt) <= jittered.backoff_ceiling(attempt));
+        }
+    }
+
     #[test]
     fn test_event_priority_ordering() {
         assert!(EventPriority::Critical > EventPriority::High);
@@ -4,7 +4,9 @@
 //! configurable concurrency limits, timeout handling, and error recovery. It
 //! coordinates with registered processors to handle different event types.
 
-use super::{WebhookConfig, WebhookEvent, WebhookProcessor, WebhookResult};
+use super::{
+    EventStorage, WebhookConfig, WebhookEvent, WebhookEventStatus, WebhookProcessor, WebhookResult,
+};
 use crate::error::{IntegrationError, IntegrationResult};
 // use crate::models::IntegrationEvent;
 use async_trait::async_trait;
@@ -219,6 +221,7 @@ pub struct EventProcessor {
     processors: Arc<RwLock<HashMap<String, Arc<dyn WebhookProcessor>>>>,
     circuit_breakers: Arc<DashMap<String, Arc<CircuitBreaker>>>,
     event_provider: Option<Arc<dyn EventProvider>>,
+    storage: Arc<dyn EventStorage>,
     stats: Arc<RwLock<ProcessingStats>>,
     concurrency_limiter: Arc<Semaphore>,
     running: Arc<AtomicBool>,
@@ -230,8 +233,8 @@ pub struct EventProcessor {
 }
 
 impl EventProcessor {
-    /// Create a new event processor
-    pub fn new(config: WebhookConfig) -> Self {
+    /// Create a new event processor that records status transitions in `storage`
+    pub fn new(config: WebhookConfig, storage: Arc<dyn EventStorage>) -> Self {
         let processor_config = ProcessorConfig::default();
         let concurrency_limiter = Arc::new(Semaphore::new(processor_config.max_concurrent));
 
@@ -243,6 +246,7 @@ impl EventProcessor {
             processors: Arc::new(RwLock::new(HashMap::new())),
             circuit_breakers: Arc::new(DashMap::new()),
             event_provider: None,
+            storage,
             stats: Arc::new(RwLock::new(ProcessingStats::default())),
             concurrency_limiter,
             running: Arc::new(AtomicBool::new(false)),
@@ -383,6 +387,7 @@ impl EventProcessor {
 
         // Mark event as processing
         event.mark_processing();
+        self.storage.store(&event).await?;
 
         // Create processing task
         let task = ProcessingTask {
@@ -431,6 +436,7 @@ impl EventProcessor {
             processors: Arc::clone(&self.processors),
             circuit_breakers: Arc::clone(&self.circuit_breakers),
             event_provider: self.event_provider.as_ref().map(Arc::clone),
+            storage: Arc::clone(&self.storage),
             stats: Arc::clone(&self.stats),
             concurrency_limiter: Arc::clone(&self.concurrency_limiter),
             running: Arc::clone(&self.running),
@@ -546,7 +552,7 @@ impl EventProcessor {
             .await;
 
         // Handle result
-        match result {
+        let (status, error) = match result {
             Ok(Ok(_integration_event)) => {
                 debug!(
                     event_id = %event_id,
@@ -559,6 +565,8 @@ impl EventProcessor {
                 if let Some(cb) = self.circuit_breakers.get(&processor_name) {
                     cb.on_success();
                 }
+
+                (WebhookEventStatus::Completed, None)
             }
             Ok(Err(e)) => {
                 error!(
@@ -572,6 +580,8 @@ impl EventProcessor {
                 if let Some(cb) = self.circuit_breakers.get(&processor_name) {
                     cb.on_failure();
                 }
+
+                (WebhookEventStatus::Failed, Some(e.to_string()))
             }
             Err(_) => {
                 error!(
@@ -584,7 +594,16 @@ impl EventProcessor {
                 if let Some(cb) = self.circuit_breakers.get(&processor_name) {
                     cb.on_failure();
                 }
+
+                (
+                    WebhookEventStatus::Failed,
+                    Some("Processing timeout exceeded".to_string()),
+                )
             }
+        };
+
+        if let Err(e) = self.storage.update_status(event_id, status, error).await {
+            warn!(event_id = %event_id, error = %e, "Failed to record event status");
         }
 
         // Update current processing count
@@ -712,6 +731,7 @@ mod tests {
         EventMetadata, EventPayload, EventStatus, IntegrationEvent, IntegrationType,
         WebhookPayload, ZapierEvent,
     };
+    use crate::webhook::storage::MemoryEventStorage;
     use chrono::Utc;
     use serde_json::json;
     use std::collections::HashMap;
@@ -839,7 +859,7 @@ mod tests {
     #[tokio::test]
     async fn test_processor_registration() {
         let config = WebhookConfig::default();
-        let processor = EventProcessor::new(config);
+        let processor = EventProcessor::new(config, Arc::new(MemoryEventStorage::new()));
 
         let mock_processor = Arc::new(MockProcessor::new("test", false, Duration::from_millis(0)));
         processor
@@ -853,7 +873,7 @@ mod tests {
     #[tokio::test]
     async fn test_processor_lifecycle() {
         let config = WebhookConfig::default();
-        let processor = EventProcessor::new(config);
+        let processor = EventProcessor::new(config, Arc::new(MemoryEventStorage::new()));
 
         // Start processor
         processor.start().await.unwrap();
@@ -867,7 +887,8 @@ mod tests {
     #[tokio::test]
     async fn test_event_processing_success() {
         let config = WebhookConfig::default();
-        let processor = EventProcessor::new(config);
+        let storage = Arc::new(MemoryEventStorage::new());
+        let processor = EventProcessor::new(config, storage.clone());
 
         let mock_processor = Arc::new(MockProcessor::new("test", false, Duration::from_millis(0)));
         processor
@@ -877,6 +898,7 @@ mod tests {
 
         // Process an event
         let event = create_test_event();
+        let event_id = event.id;
         processor.process_event(event).await.unwrap();
 
         // Give some time for async processing
@@ -885,6 +907,11 @@ mod tests {
         // Check that processor was called
         assert_eq!(mock_processor.get_process_count(), 1);
 
+        // The status transition is persisted
+        let stored = storage.get(event_id).await.unwrap().unwrap();
+        assert_eq!(stored.status, WebhookEventStatus::Completed);
+        assert_eq!(stored.attempt_count, 1);
+
         let stats = processor.get_stats().await;
         assert!(stats.total_processed > 0);
 
@@ -894,7 +921,7 @@ mod tests {
     #[tokio::test]
     async fn test_circuit_breaker_integration() {
         let config = WebhookConfig::default();
-        let processor = EventProcessor::new(config);
+        let processor = EventProcessor::new(config, Arc::new(MemoryEventStorage::new()));
 
         // Register failing processor
         let mock_processor = Arc::new(MockProcessor::new(
@@ -925,7 +952,7 @@ mod tests {
     #[tokio::test]
     async fn test_processing_stats_tracking() {
         let config = WebhookConfig::default();
-        let processor = EventProcessor::new(config);
+        let processor = EventProcessor::new(config, Arc::new(MemoryEventStorage::new()));
 
         let mock_processor = Arc::new(MockProcessor::new(
             "stats_test",
@@ -4,15 +4,19 @@
 //! processors based on configurable rules, patterns, and load balancing strategies.
 //! It supports dynamic routing configuration and real-time routing decisions.
 
-use super::{EventRouter, WebhookEvent};
+use super::{
+    DeliveryRetryPolicy, EventDelivery, EventRouter, EventStorage, WebhookEvent, WebhookEventStatus,
+};
 use crate::error::{IntegrationError, IntegrationResult};
 use async_trait::async_trait;
+use chrono::Utc;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use tracing::{debug, warn};
+use std::time::Duration;
+use tracing::{debug, error, warn};
 
 /// Routing rule types
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -631,10 +635,154 @@ impl EventRouter for StaticEventRouter {
     }
 }
 
+/// Routes events and delivers them downstream, retrying transient failures
+///
+/// Retryable delivery errors are retried according to the
+/// [`DeliveryRetryPolicy`]. Events that exhaust their attempts, or fail with a
+/// non-retryable error, are marked `Failed` with the last error and moved to
+/// the dead letter store for manual replay.
+pub struct EventDispatcher {
+    router: Arc<dyn EventRouter>,
+    delivery: Arc<dyn EventDelivery>,
+    storage: Arc<dyn EventStorage>,
+    policy: DeliveryRetryPolicy,
+}
+
+impl EventDispatcher {
+    /// Create a new event dispatcher
+    pub fn new(
+        router: Arc<dyn EventRouter>,
+        delivery: Arc<dyn EventDelivery>,
+        storage: Arc<dyn EventStorage>,
+        policy: DeliveryRetryPolicy,
+    ) -> Self {
+        Self {
+            router,
+            delivery,
+            storage,
+            policy,
+        }
+    }
+
+    /// Route an event and deliver it to its processors
+    pub async fn dispatch(&self, event: &mut WebhookEvent) -> IntegrationResult<()> {
+        let processors = self.router.route_event(event).await?;
+        let max_attempts = self.policy.max_attempts.max(1);
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+            event.mark_processing();
+
+            let error = match self.delivery.deliver(event, &processors).await {
+                Ok(()) => {
+                    event.mark_completed();
+                    debug!(event_id = %event.id, attempt, "Event delivered");
+                    return Ok(());
+                }
+                Err(e) => e,
+            };
+
+            if !error.is_retryable() || attempt >= max_attempts {
+                self.dead_letter(event, &error).await?;
+                return Err(error);
+            }
+
+            let delay = self.policy.delay_for(attempt);
+            warn!(
+                event_id = %event.id,
+                attempt,
+                max_attempts,
+                delay_ms = delay.as_millis() as u64,
+                error = %error,
+                "Event delivery failed, retrying"
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Mark an event as failed and move it to the dead letter store
+    async fn dead_letter(
+        &self,
+        event: &mut WebhookEvent,
+        error: &IntegrationError,
+    ) -> IntegrationResult<()> {
+        event.status = WebhookEventStatus::Failed;
+        event.error = Some(error.to_string());
+        event.next_retry_at = None;
+        event.updated_at = Utc::now();
+
+        error!(
+            event_id = %event.id,
+            attempts = event.attempt_count,
+            error = %error,
+            "Event delivery failed permanently, moving to dead letter store"
+        );
+
+        self.storage.store_dead_letter(event).await
+    }
+}
+
+/// Delivers events by POSTing them, with their routed processors, to a
+/// downstream HTTP endpoint
+///
+/// Connection failures, timeouts, `429` and `5xx` responses are retryable;
+/// other error responses are not.
+pub struct HttpEventDelivery {
+    client: reqwest::Client,
+    url: String,
+    timeout: Duration,
+}
+
+impl HttpEventDelivery {
+    /// Create a delivery to `url`, giving each attempt `timeout` to complete
+    pub fn new(client: reqwest::Client, url: impl Into<String>, timeout: Duration) -> Self {
+        Self {
+            client,
+            url: url.into(),
+            timeout,
+        }
+    }
+}
+
+#[async_trait]
+impl EventDelivery for HttpEventDelivery {
+    async fn deliver(&self, event: &WebhookEvent, processors: &[String]) -> IntegrationResult<()> {
+        let response = self
+            .client
+            .post(&self.url)
+            .timeout(self.timeout)
+            .json(&serde_json::json!({
+                "event": event,
+                "processors": processors,
+            }))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            warn!(event_id = %event.id, "Event delivery rate limited downstream");
+            return Err(IntegrationError::service_unavailable("event_delivery"));
+        }
+
+        Err(IntegrationError::external_api(
+            "event_delivery",
+            status.as_u16(),
+            body,
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::models::WebhookPayload;
+    use crate::webhook::storage::MemoryEventStorage;
     use chrono::Utc;
     use serde_json::json;
     use std::collections::HashMap;
@@ -660,6 +808,193 @@ mod tests {
         WebhookEvent::new(payload, super::super::EventPriority::High)
     }
 
+    /// Delivery that fails a fixed number of times before succeeding
+    struct FlakyDelivery {
+        failures_remaining: parking_lot::Mutex<u32>,
+        attempts: AtomicU64,
+        error: fn() -> IntegrationError,
+    }
+
+    impl FlakyDelivery {
+        fn new(failures: u32, error: fn() -> IntegrationError) -> Self {
+            Self {
+                failures_remaining: parking_lot::Mutex::new(failures),
+                attempts: AtomicU64::new(0),
+                error,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl EventDelivery for FlakyDelivery {
+        async fn deliver(
+            &self,
+            _event: &WebhookEvent,
+            _processors: &[String],
+        ) -> IntegrationResult<()> {
+            self.attempts.fetch_add(1, Ordering::Relaxed);
+            let mut remaining = self.failures_remaining.lock();
+            if *remaining > 0 {
+                *remaining -= 1;
+                return Err((self.error)());
+            }
+            Ok(())
+        }
+    }
+
+    fn fast_policy(max_attempts: u32) -> DeliveryRetryPolicy {
+        DeliveryRetryPolicy {
+            max_attempts,
+            base_delay_ms: 1,
+            max_delay_ms: 5,
+            jitter: true,
+        }
+    }
+
+    fn static_router() -> Arc<dyn EventRouter> {
+        Arc::new(StaticEventRouter::new(
+            HashMap::new(),
+            vec!["workflow-engine".to_string()],
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_dispatcher_retries_transient_failures() {
+        let delivery = Arc::new(FlakyDelivery::new(2, || {
+            IntegrationError::service_unavailable("workflow-engine")
+        }));
+        let storage = Arc::new(MemoryEventStorage::new());
+        let dispatcher = EventDispatcher::new(
+            static_router(),
+            delivery.clone(),
+            storage.clone(),
+            fast_policy(3),
+        );
+
+        let mut event = create_test_event("zapier", "zap.trigger");
+        dispatcher.dispatch(&mut event).await.unwrap();
+
+        assert_eq!(delivery.attempts.load(Ordering::Relaxed), 3);
+        assert_eq!(event.status, WebhookEventStatus::Completed);
+        assert!(storage.get_dead_letter_events(10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_dispatcher_dead_letters_exhausted_events() {
+        let delivery = Arc::new(FlakyDelivery::new(10, || {
+            IntegrationError::service_unavailable("workflow-engine")
+        }));
+        let storage = Arc::new(MemoryEventStorage::new());
+        let dispatcher = EventDispatcher::new(
+            static_router(),
+            delivery.clone(),
+            storage.clone(),
+            fast_policy(3),
+        );
+
+        let mut event = create_test_event("zapier", "zap.trigger");
+        assert!(dispatcher.dispatch(&mut event).await.is_err());
+
+        assert_eq!(delivery.attempts.load(Ordering::Relaxed), 3);
+        let dead_letters = storage.get_dead_letter_events(10).await.unwrap();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].id, event.id);
+        assert_eq!(dead_letters[0].status, WebhookEventStatus::Failed);
+        assert!(dead_letters[0]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("workflow-engine"));
+    }
+
+    #[tokio::test]
+    async fn test_http_delivery_retries_server_errors() {
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/events"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/events"))
+            .and(body_partial_json(
+                serde_json::json!({"processors": ["workflow-engine"]}),
+            ))
+            .respond_with(ResponseTemplate::new(202))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let delivery = Arc::new(HttpEventDelivery::new(
+            reqwest::Client::new(),
+            format!("{}/events", server.uri()),
+            Duration::from_secs(5),
+        ));
+        let storage = Arc::new(MemoryEventStorage::new());
+        let dispatcher =
+            EventDispatcher::new(static_router(), delivery, storage.clone(), fast_policy(5));
+
+        let mut event = create_test_event("zapier", "zap.trigger");
+        dispatcher.dispatch(&mut event).await.unwrap();
+        assert_eq!(event.status, WebhookEventStatus::Completed);
+        assert!(storage.get_dead_letter_events(10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_http_delivery_dead_letters_client_errors() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(400).set_body_string("unknown template"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let delivery = Arc::new(HttpEventDelivery::new(
+            reqwest::Client::new(),
+            server.uri(),
+            Duration::from_secs(5),
+        ));
+        let storage = Arc::new(MemoryEventStorage::new());
+        let dispatcher =
+            EventDispatcher::new(static_router(), delivery, storage.clone(), fast_policy(5));
+
+        let mut event = create_test_event("zapier", "zap.trigger");
+        assert!(dispatcher.dispatch(&mut event).await.is_err());
+        let dead_letters = storage.get_dead_letter_events(10).await.unwrap();
+        assert_eq!(dead_letters.len(), 1);
+        assert!(dead_letters[0]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("unknown template"));
+    }
+
+    #[tokio::test]
+    async fn test_dispatcher_does_not_retry_permanent_failures() {
+        let delivery = Arc::new(FlakyDelivery::new(10, || {
+            IntegrationError::configuration("no workflow template")
+        }));
+        let storage = Arc::new(MemoryEventStorage::new());
+        let dispatcher = EventDispatcher::new(
+            static_router(),
+            delivery.clone(),
+            storage.clone(),
+            fast_policy(5),
+        );
+
+        let mut event = create_test_event("zapier", "zap.trigger");
+        assert!(dispatcher.dispatch(&mut event).await.is_err());
+        assert_eq!(delivery.attempts.load(Ordering::Relaxed), 1);
+        assert_eq!(storage.get_dead_letter_events(10).await.unwrap().len(), 1);
+    }
+
     #[test]
     fn test_routing_condition_evaluation() {
         let event = create_test_event("zapier", "zap.trigger");
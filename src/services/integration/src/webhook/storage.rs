@@ -0,0 +1,720 @@
+//! # Event Storage Backends
+//!
+//! Implementations of [`EventStorage`] for persisting webhook events, their
+//! status transitions, dead letters and idempotency records:
+//! - [`MemoryEventStorage`] keeps everything in process and is intended for
+//!   tests and single-instance development setups
+//! - [`PostgresEventStorage`] persists to PostgreSQL via sqlx
+
+use super::{EventStorage, IdempotencyRecord, WebhookEvent, WebhookEventStatus, WebhookStats};
+use crate::error::{IntegrationError, IntegrationResult};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use sqlx::{PgPool, Row};
+use tracing::info;
+use uuid::Uuid;
+
+/// Build processing statistics from per-status counts
+fn stats_from_counts(
+    counts: impl IntoIterator<Item = (WebhookEventStatus, u64)>,
+    dead_letters: u64,
+    last_processed_at: Option<DateTime<Utc>>,
+) -> WebhookStats {
+    let mut stats = WebhookStats {
+        total_dead_lettered: dead_letters,
+        last_processed_at,
+        ..Default::default()
+    };
+
+    for (status, count) in counts {
+        stats.total_received += count;
+        match status {
+            WebhookEventStatus::Received => stats.queue_depth += count,
+            WebhookEventStatus::Processing => stats.currently_processing += count,
+            WebhookEventStatus::Completed => stats.total_processed += count,
+            WebhookEventStatus::Failed => stats.total_failed += count,
+            WebhookEventStatus::DeadLettered | WebhookEventStatus::Cancelled => {}
+        }
+    }
+
+    stats
+}
+
+/// In-memory event storage backed by [`DashMap`]
+#[derive(Default)]
+pub struct MemoryEventStorage {
+    events: DashMap<Uuid, WebhookEvent>,
+    dead_letters: DashMap<Uuid, DateTime<Utc>>,
+    idempotency: DashMap<String, IdempotencyRecord>,
+}
+
+impl MemoryEventStorage {
+    /// Create an empty storage
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of stored events
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Check if no events are stored
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+#[async_trait]
+impl EventStorage for MemoryEventStorage {
+    async fn store(&self, event: &WebhookEvent) -> IntegrationResult<()> {
+        self.events.insert(event.id, event.clone());
+        Ok(())
+    }
+
+    async fn get(&self, id: Uuid) -> IntegrationResult<Option<WebhookEvent>> {
+        Ok(self.events.get(&id).map(|event| event.clone()))
+    }
+
+    async fn update_status(
+        &self,
+        id: Uuid,
+        status: WebhookEventStatus,
+        error: Option<String>,
+    ) -> IntegrationResult<bool> {
+        match self.events.get_mut(&id) {
+            Some(mut event) => {
+                event.status = status;
+                event.error = error;
+                event.updated_at = Utc::now();
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn list_by_status(
+        &self,
+        status: WebhookEventStatus,
+        limit: usize,
+    ) -> IntegrationResult<Vec<WebhookEvent>> {
+        let mut events: Vec<_> = self
+            .events
+            .iter()
+            .filter(|event| event.status == status)
+            .map(|event| event.clone())
+            .collect();
+
+        events.sort_by_key(|event| event.updated_at);
+        events.truncate(limit);
+        Ok(events)
+    }
+
+    async fn purge_older_than(&self, cutoff: DateTime<Utc>) -> IntegrationResult<u64> {
+        let before = self.events.len();
+        self.events
+            .retain(|_, event| event.status.is_active() || event.updated_at >= cutoff);
+        let purged = before.saturating_sub(self.events.len());
+
+        self.dead_letters
+            .retain(|id, _| self.events.contains_key(id));
+        self.idempotency.retain(|_, record| !record.is_expired());
+
+        Ok(purged as u64)
+    }
+
+    async fn get_dead_letter_events(&self, limit: usize) -> IntegrationResult<Vec<WebhookEvent>> {
+        let mut entries: Vec<_> = self
+            .dead_letters
+            .iter()
+            .filter_map(|entry| {
+                let event = self.events.get(entry.key())?;
+                event
+                    .status
+                    .is_failure()
+                    .then(|| (*entry.value(), event.clone()))
+            })
+            .collect();
+
+        entries.sort_by_key(|(dead_lettered_at, _)| *dead_lettered_at);
+        Ok(entries
+            .into_iter()
+            .take(limit)
+            .map(|(_, event)| event)
+            .collect())
+    }
+
+    async fn store_dead_letter(&self, event: &WebhookEvent) -> IntegrationResult<()> {
+        self.events.insert(event.id, event.clone());
+        self.dead_letters.insert(event.id, Utc::now());
+        Ok(())
+    }
+
+    async fn get_stats(&self) -> IntegrationResult<WebhookStats> {
+        let mut counts: Vec<(WebhookEventStatus, u64)> = Vec::new();
+        let mut last_processed_at: Option<DateTime<Utc>> = None;
+
+        for event in self.events.iter() {
+            match counts
+                .iter_mut()
+                .find(|(status, _)| *status == event.status)
+            {
+                Some((_, count)) => *count += 1,
+                None => counts.push((event.status.clone(), 1)),
+            }
+            if event.status == WebhookEventStatus::Completed {
+                last_processed_at = last_processed_at.max(Some(event.updated_at));
+            }
+        }
+
+        Ok(stats_from_counts(
+            counts,
+            self.dead_letters.len() as u64,
+            last_processed_at,
+        ))
+    }
+
+    async fn claim_idempotency_key(
+        &self,
+        record: &IdempotencyRecord,
+    ) -> IntegrationResult<Option<IdempotencyRecord>> {
+        match self.idempotency.entry(record.key.clone()) {
+            Entry::Occupied(existing) if !existing.get().is_expired() => {
+                Ok(Some(existing.get().clone()))
+            }
+            Entry::Occupied(mut expired) => {
+                expired.insert(record.clone());
+                Ok(None)
+            }
+            Entry::Vacant(slot) => {
+                slot.insert(record.clone());
+                Ok(None)
+            }
+        }
+    }
+
+    async fn release_idempotency_key(&self, key: &str) -> IntegrationResult<()> {
+        self.idempotency.remove(key);
+        Ok(())
+    }
+
+    async fn complete_idempotency_key(
+        &self,
+        key: &str,
+        result: &serde_json::Value,
+    ) -> IntegrationResult<()> {
+        if let Some(mut record) = self.idempotency.get_mut(key) {
+            record.result = Some(result.clone());
+        }
+        Ok(())
+    }
+}
+
+/// Schema for the PostgreSQL event storage, applied one statement at a time
+const POSTGRES_SCHEMA: &[&str] = &[
+    r#"
+    CREATE TABLE IF NOT EXISTS webhook_events (
+        id UUID PRIMARY KEY,
+        integration VARCHAR(100) NOT NULL,
+        status VARCHAR(20) NOT NULL,
+        event JSONB NOT NULL,
+        created_at TIMESTAMPTZ NOT NULL,
+        updated_at TIMESTAMPTZ NOT NULL,
+        dead_lettered_at TIMESTAMPTZ
+    )
+    "#,
+    "CREATE INDEX IF NOT EXISTS webhook_events_status_idx ON webhook_events (status, updated_at)",
+    "CREATE INDEX IF NOT EXISTS webhook_events_dead_lettered_idx ON webhook_events (dead_lettered_at) WHERE dead_lettered_at IS NOT NULL",
+    r#"
+    CREATE TABLE IF NOT EXISTS webhook_idempotency_keys (
+        key TEXT PRIMARY KEY,
+        event_id UUID NOT NULL,
+        created_at TIMESTAMPTZ NOT NULL,
+        expires_at TIMESTAMPTZ NOT NULL
+    )
+    "#,
+    "ALTER TABLE webhook_idempotency_keys ADD COLUMN IF NOT EXISTS result JSONB",
+    "CREATE INDEX IF NOT EXISTS webhook_idempotency_keys_expires_idx ON webhook_idempotency_keys (expires_at)",
+];
+
+/// PostgreSQL event storage
+///
+/// The full event is stored as JSONB alongside indexed `status` and
+/// `updated_at` columns, which are kept in sync on every write.
+#[derive(Clone)]
+pub struct PostgresEventStorage {
+    pool: PgPool,
+}
+
+impl PostgresEventStorage {
+    /// Create a storage using an existing connection pool
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Create the storage tables and indexes if they do not exist
+    pub async fn run_migrations(&self) -> IntegrationResult<()> {
+        info!("Running webhook event storage migrations");
+
+        for statement in POSTGRES_SCHEMA {
+            sqlx::query(*statement).execute(&self.pool).await?;
+        }
+
+        Ok(())
+    }
+
+    fn decode_event(row: &sqlx::postgres::PgRow) -> IntegrationResult<WebhookEvent> {
+        let event: serde_json::Value = row.try_get("event")?;
+        Ok(serde_json::from_value(event)?)
+    }
+
+    async fn upsert(
+        &self,
+        event: &WebhookEvent,
+        dead_lettered_at: Option<DateTime<Utc>>,
+    ) -> IntegrationResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO webhook_events
+                (id, integration, status, event, created_at, updated_at, dead_lettered_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (id) DO UPDATE SET
+                status = EXCLUDED.status,
+                event = EXCLUDED.event,
+                updated_at = EXCLUDED.updated_at,
+                dead_lettered_at = COALESCE(EXCLUDED.dead_lettered_at, webhook_events.dead_lettered_at)
+            "#,
+        )
+        .bind(event.id)
+        .bind(&event.payload.integration)
+        .bind(event.status.as_str())
+        .bind(serde_json::to_value(event)?)
+        .bind(event.created_at)
+        .bind(event.updated_at)
+        .bind(dead_lettered_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EventStorage for PostgresEventStorage {
+    async fn store(&self, event: &WebhookEvent) -> IntegrationResult<()> {
+        self.upsert(event, None).await
+    }
+
+    async fn get(&self, id: Uuid) -> IntegrationResult<Option<WebhookEvent>> {
+        let row = sqlx::query("SELECT event FROM webhook_events WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.as_ref().map(Self::decode_event).transpose()
+    }
+
+    async fn update_status(
+        &self,
+        id: Uuid,
+        status: WebhookEventStatus,
+        error: Option<String>,
+    ) -> IntegrationResult<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        let row = sqlx::query("SELECT event FROM webhook_events WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        let Some(row) = row else {
+            return Ok(false);
+        };
+
+        let mut event = Self::decode_event(&row)?;
+        event.status = status;
+        event.error = error;
+        event.updated_at = Utc::now();
+
+        sqlx::query(
+            "UPDATE webhook_events SET status = $2, event = $3, updated_at = $4 WHERE id = $1",
+        )
+        .bind(id)
+        .bind(event.status.as_str())
+        .bind(serde_json::to_value(&event)?)
+        .bind(event.updated_at)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn list_by_status(
+        &self,
+        status: WebhookEventStatus,
+        limit: usize,
+    ) -> IntegrationResult<Vec<WebhookEvent>> {
+        let rows = sqlx::query(
+            "SELECT event FROM webhook_events WHERE status = $1 ORDER BY updated_at ASC LIMIT $2",
+        )
+        .bind(status.as_str())
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(Self::decode_event).collect()
+    }
+
+    async fn purge_older_than(&self, cutoff: DateTime<Utc>) -> IntegrationResult<u64> {
+        let result = sqlx::query(
+            "DELETE FROM webhook_events WHERE updated_at < $1 AND status NOT IN ($2, $3)",
+        )
+        .bind(cutoff)
+        .bind(WebhookEventStatus::Received.as_str())
+        .bind(WebhookEventStatus::Processing.as_str())
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query("DELETE FROM webhook_idempotency_keys WHERE expires_at <= NOW()")
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn get_dead_letter_events(&self, limit: usize) -> IntegrationResult<Vec<WebhookEvent>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT event FROM webhook_events
+            WHERE dead_lettered_at IS NOT NULL AND status IN ($1, $2)
+            ORDER BY dead_lettered_at ASC
+            LIMIT $3
+            "#,
+        )
+        .bind(WebhookEventStatus::Failed.as_str())
+        .bind(WebhookEventStatus::DeadLettered.as_str())
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(Self::decode_event).collect()
+    }
+
+    async fn store_dead_letter(&self, event: &WebhookEvent) -> IntegrationResult<()> {
+        self.upsert(event, Some(Utc::now())).await
+    }
+
+    async fn get_stats(&self) -> IntegrationResult<WebhookStats> {
+        let rows =
+            sqlx::query("SELECT status, COUNT(*) AS count FROM webhook_events GROUP BY status")
+                .fetch_all(&self.pool)
+                .await?;
+
+        let mut counts = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let status: String = row.try_get("status")?;
+            let count: i64 = row.try_get("count")?;
+            let status = WebhookEventStatus::parse(&status).ok_or_else(|| {
+                IntegrationError::internal(format!("Unknown webhook event status: {}", status))
+            })?;
+            counts.push((status, count as u64));
+        }
+
+        let summary = sqlx::query(
+            r#"
+            SELECT
+                COUNT(*) FILTER (WHERE dead_lettered_at IS NOT NULL) AS dead_letters,
+                MAX(updated_at) FILTER (WHERE status = $1) AS last_processed_at
+            FROM webhook_events
+            "#,
+        )
+        .bind(WebhookEventStatus::Completed.as_str())
+        .fetch_one(&self.pool)
+        .await?;
+
+        let dead_letters: i64 = summary.try_get("dead_letters")?;
+        let last_processed_at: Option<DateTime<Utc>> = summary.try_get("last_processed_at")?;
+
+        Ok(stats_from_counts(
+            counts,
+            dead_letters as u64,
+            last_processed_at,
+        ))
+    }
+
+    async fn claim_idempotency_key(
+        &self,
+        record: &IdempotencyRecord,
+    ) -> IntegrationResult<Option<IdempotencyRecord>> {
+        // Insert, or take over an expired record for the same key
+        let claimed = sqlx::query(
+            r#"
+            INSERT INTO webhook_idempotency_keys (key, event_id, created_at, expires_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (key) DO UPDATE SET
+                event_id = EXCLUDED.event_id,
+                created_at = EXCLUDED.created_at,
+                expires_at = EXCLUDED.expires_at,
+                result = NULL
+            WHERE webhook_idempotency_keys.expires_at <= NOW()
+            RETURNING key
+            "#,
+        )
+        .bind(&record.key)
+        .bind(record.event_id)
+        .bind(record.created_at)
+        .bind(record.expires_at)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        if claimed.is_some() {
+            return Ok(None);
+        }
+
+        let row = sqlx::query(
+            "SELECT key, event_id, created_at, expires_at, result FROM webhook_idempotency_keys WHERE key = $1",
+        )
+        .bind(&record.key)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|row| {
+            Ok(IdempotencyRecord {
+                key: row.try_get("key")?,
+                event_id: row.try_get("event_id")?,
+                created_at: row.try_get("created_at")?,
+                expires_at: row.try_get("expires_at")?,
+                result: row.try_get("result")?,
+            })
+        })
+        .transpose()
+    }
+
+    async fn release_idempotency_key(&self, key: &str) -> IntegrationResult<()> {
+        sqlx::query("DELETE FROM webhook_idempotency_keys WHERE key = $1")
+            .bind(key)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn complete_idempotency_key(
+        &self,
+        key: &str,
+        result: &serde_json::Value,
+    ) -> IntegrationResult<()> {
+        sqlx::query("UPDATE webhook_idempotency_keys SET result = $2 WHERE key = $1")
+            .bind(key)
+            .bind(result)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::WebhookPayload;
+    use crate::webhook::EventPriority;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn create_test_event() -> WebhookEvent {
+        let payload = WebhookPayload {
+            id: Uuid::new_v4(),
+            integration: "test".to_string(),
+            event_type: "test.event".to_string(),
+            timestamp: Utc::now(),
+            data: json!({"test": "data"}),
+            headers: HashMap::new(),
+            source_ip: None,
+            user_agent: None,
+        };
+        WebhookEvent::new(payload, EventPriority::Normal)
+    }
+
+    #[tokio::test]
+    async fn test_memory_storage_status_transitions() {
+        let storage = MemoryEventStorage::new();
+        let event = create_test_event();
+        storage.store(&event).await.unwrap();
+
+        assert!(storage
+            .update_status(event.id, WebhookEventStatus::Processing, None)
+            .await
+            .unwrap());
+        assert_eq!(
+            storage
+                .list_by_status(WebhookEventStatus::Processing, 10)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        storage
+            .update_status(
+                event.id,
+                WebhookEventStatus::Failed,
+                Some("downstream unavailable".to_string()),
+            )
+            .await
+            .unwrap();
+        let failed = storage.get(event.id).await.unwrap().unwrap();
+        assert_eq!(failed.status, WebhookEventStatus::Failed);
+        assert_eq!(failed.error.as_deref(), Some("downstream unavailable"));
+        assert!(storage
+            .list_by_status(WebhookEventStatus::Processing, 10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        storage
+            .update_status(event.id, WebhookEventStatus::Completed, None)
+            .await
+            .unwrap();
+        let completed = storage.get(event.id).await.unwrap().unwrap();
+        assert_eq!(completed.status, WebhookEventStatus::Completed);
+        assert!(completed.error.is_none());
+
+        let stats = storage.get_stats().await.unwrap();
+        assert_eq!(stats.total_received, 1);
+        assert_eq!(stats.total_processed, 1);
+        assert_eq!(stats.last_processed_at, Some(completed.updated_at));
+
+        // Unknown events are reported rather than created
+        assert!(!storage
+            .update_status(Uuid::new_v4(), WebhookEventStatus::Completed, None)
+            .await
+            .unwrap());
+        assert_eq!(storage.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_memory_storage_list_by_status_is_oldest_first() {
+        let storage = MemoryEventStorage::new();
+        let now = Utc::now();
+
+        let mut ids = Vec::new();
+        for minutes_ago in [5, 15, 10] {
+            let mut event = create_test_event();
+            event.status = WebhookEventStatus::Failed;
+            event.updated_at = now - chrono::Duration::minutes(minutes_ago);
+            ids.push((minutes_ago, event.id));
+            storage.store(&event).await.unwrap();
+        }
+        storage.store(&create_test_event()).await.unwrap();
+
+        let failed = storage
+            .list_by_status(WebhookEventStatus::Failed, 2)
+            .await
+            .unwrap();
+        let expected: Vec<_> = [15, 10]
+            .iter()
+            .map(|m| ids.iter().find(|(ago, _)| ago == m).unwrap().1)
+            .collect();
+        assert_eq!(
+            failed.iter().map(|event| event.id).collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[tokio::test]
+    async fn test_memory_storage_purge_older_than() {
+        let storage = MemoryEventStorage::new();
+        let now = Utc::now();
+        let cutoff = now - chrono::Duration::hours(1);
+
+        let mut old_completed = create_test_event();
+        old_completed.status = WebhookEventStatus::Completed;
+        old_completed.updated_at = now - chrono::Duration::hours(2);
+
+        let mut old_dead_letter = create_test_event();
+        old_dead_letter.status = WebhookEventStatus::Failed;
+        old_dead_letter.updated_at = now - chrono::Duration::hours(3);
+
+        let mut old_received = create_test_event();
+        old_received.updated_at = now - chrono::Duration::hours(2);
+
+        let mut recent_completed = create_test_event();
+        recent_completed.status = WebhookEventStatus::Completed;
+
+        storage.store(&old_completed).await.unwrap();
+        storage.store_dead_letter(&old_dead_letter).await.unwrap();
+        storage.store(&old_received).await.unwrap();
+        storage.store(&recent_completed).await.unwrap();
+
+        let expired = IdempotencyRecord::new(
+            "test:expired".to_string(),
+            old_completed.id,
+            chrono::Duration::seconds(-1),
+        );
+        storage.claim_idempotency_key(&expired).await.unwrap();
+
+        assert_eq!(storage.purge_older_than(cutoff).await.unwrap(), 2);
+        assert!(storage.get(old_completed.id).await.unwrap().is_none());
+        assert!(storage.get(old_dead_letter.id).await.unwrap().is_none());
+        assert!(storage.get_dead_letter_events(10).await.unwrap().is_empty());
+
+        // Queued events survive regardless of age
+        assert!(storage.get(old_received.id).await.unwrap().is_some());
+        assert!(storage.get(recent_completed.id).await.unwrap().is_some());
+        assert!(storage.idempotency.is_empty());
+
+        // Nothing left to purge
+        assert_eq!(storage.purge_older_than(cutoff).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_memory_storage_dead_letters_exclude_replayed_events() {
+        let storage = MemoryEventStorage::new();
+        let mut event = create_test_event();
+        event.status = WebhookEventStatus::Failed;
+        storage.store_dead_letter(&event).await.unwrap();
+
+        assert_eq!(storage.get_dead_letter_events(10).await.unwrap().len(), 1);
+        assert_eq!(storage.get_stats().await.unwrap().total_dead_lettered, 1);
+
+        storage
+            .update_status(event.id, WebhookEventStatus::Received, None)
+            .await
+            .unwrap();
+        assert!(storage.get_dead_letter_events(10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_memory_storage_idempotency_claims() {
+        let storage = MemoryEventStorage::new();
+        let first = IdempotencyRecord::new(
+            "zapier:abc".to_string(),
+            Uuid::new_v4(),
+            chrono::Duration::hours(1),
+        );
+        assert!(storage
+            .claim_idempotency_key(&first)
+            .await
+            .unwrap()
+            .is_none());
+
+        let retry = IdempotencyRecord::new(
+            "zapier:abc".to_string(),
+            Uuid::new_v4(),
+            chrono::Duration::hours(1),
+        );
+        let existing = storage.claim_idempotency_key(&retry).await.unwrap();
+        assert_eq!(existing.map(|record| record.event_id), Some(first.event_id));
+
+        storage.release_idempotency_key("zapier:abc").await.unwrap();
+        assert!(storage
+            .claim_idempotency_key(&retry)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,190 @@
+//! Redis cache of parsed intents
+//!
+//! Identical requests are served from Redis instead of re-invoking the LLM. Keys
+//! hash the normalized request text, the request constraints and the parts of
+//! the user context that shape the prompt.
+
+use crate::config::RedisConfig;
+use crate::error::Result;
+use crate::types::*;
+use redis::{aio::ConnectionManager, AsyncCommands};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tracing::{debug, info};
+use uuid::Uuid;
+
+/// Bump when prompts or parsing change so stale entries are never served
+const CACHE_KEY_PREFIX: &str = "intent_parser:parsed:v1";
+
+#[derive(Clone)]
+pub struct IntentCache {
+    connection: ConnectionManager,
+    ttl_seconds: u64,
+}
+
+/// Everything that influences a parse result, serialized in a stable order
+#[derive(Serialize)]
+struct CacheKeyMaterial<'a> {
+    text: String,
+    request_context: Option<&'a serde_json::Value>,
+    federation_context: Option<&'a FederationContext>,
+    preferred_providers: Option<&'a Vec<String>>,
+    budget_limit: Option<f64>,
+    time_limit_seconds: Option<i64>,
+    quality_threshold: Option<f32>,
+    clarification: Option<&'a Clarification>,
+    subscription_tier: &'a SubscriptionTier,
+    /// (platform, integration type, credentials valid), sorted
+    integrations: Vec<(&'a str, &'a str, bool)>,
+    preferred_provider_overrides: BTreeMap<&'a str, &'a str>,
+    cost_sensitivity: f32,
+    speed_preference: f32,
+    default_timezone: &'a str,
+    language: &'a str,
+    content_preferences: &'a ContentPreferences,
+}
+
+impl IntentCache {
+    pub async fn new(config: &RedisConfig, ttl_seconds: u64) -> Result<Self> {
+        let client = redis::Client::open(config.url.as_str())?;
+        let connection = tokio::time::timeout(
+            Duration::from_secs(config.connection_timeout_seconds),
+            ConnectionManager::new(client),
+        )
+        .await??;
+
+        info!("Intent cache connected with TTL of {}s", ttl_seconds);
+        Ok(Self {
+            connection,
+            ttl_seconds,
+        })
+    }
+
+    /// Cache key for a request parsed under the given user context
+    pub fn cache_key(request: &ParseIntentRequest, context: &UserContext) -> String {
+        let mut integrations: Vec<(&str, &str, bool)> = context
+            .integrations
+            .iter()
+            .map(|i| {
+                (
+                    i.platform.as_str(),
+                    i.integration_type.as_str(),
+                    i.credentials_valid,
+                )
+            })
+            .collect();
+        integrations.sort();
+
+        let preferences = &context.preferences;
+        let material = CacheKeyMaterial {
+            text: normalize_text(&request.text),
+            request_context: request.context.as_ref(),
+            federation_context: request.federation_context.as_ref(),
+            preferred_providers: request.preferred_providers.as_ref(),
+            budget_limit: request.budget_limit,
+            time_limit_seconds: request.time_limit.map(|limit| limit.num_seconds()),
+            quality_threshold: request.quality_threshold,
+            clarification: request.clarification.as_ref(),
+            subscription_tier: &context.subscription_tier,
+            integrations,
+            preferred_provider_overrides: preferences
+                .preferred_providers
+                .iter()
+                .map(|(domain, provider)| (domain.as_str(), provider.as_str()))
+                .collect(),
+            cost_sensitivity: preferences.cost_sensitivity,
+            speed_preference: preferences.speed_preference,
+            default_timezone: &preferences.default_timezone,
+            language: &preferences.language,
+            content_preferences: &preferences.content_preferences,
+        };
+
+        // Serializing plain structs, tuples and BTreeMaps cannot fail
+        let encoded = serde_json::to_vec(&material).unwrap_or_default();
+        format!("{}:{:x}", CACHE_KEY_PREFIX, Sha256::digest(&encoded))
+    }
+
+    /// Look up a cached parse, flagged as a cache hit with a fresh workflow ID
+    pub async fn get(&self, key: &str) -> Result<Option<ParsedIntent>> {
+        let mut connection = self.connection.clone();
+        let cached: Option<String> = connection.get(key).await?;
+
+        let Some(cached) = cached else {
+            debug!("Intent cache miss: {}", key);
+            return Ok(None);
+        };
+
+        let mut intent: ParsedIntent = serde_json::from_str(&cached)?;
+        intent.workflow_id = Uuid::new_v4();
+        intent.metadata.cache_hit = true;
+        intent.metadata.cached_at = Some(intent.metadata.created_at);
+        intent.metadata.created_at = chrono::Utc::now();
+
+        debug!("Intent cache hit: {}", key);
+        Ok(Some(intent))
+    }
+
+    pub async fn put(&self, key: &str, intent: &ParsedIntent) -> Result<()> {
+        let payload = serde_json::to_string(intent)?;
+        let mut connection = self.connection.clone();
+        connection
+            .set_ex::<_, _, ()>(key, payload, self.ttl_seconds)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Lowercase, trim and collapse runs of whitespace to a single space
+pub fn normalize_text(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(text: &str) -> ParseIntentRequest {
+        serde_json::from_value(serde_json::json!({
+            "user_id": Uuid::nil(),
+            "text": text,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_normalize_text() {
+        assert_eq!(
+            normalize_text("  Create a\tBlog   post\nabout AI "),
+            "create a blog post about ai"
+        );
+    }
+
+    #[test]
+    fn test_cache_key_ignores_formatting() {
+        let context = UserContext::default();
+        assert_eq!(
+            IntentCache::cache_key(&request("Create a blog post"), &context),
+            IntentCache::cache_key(&request("  create a   BLOG post "), &context)
+        );
+    }
+
+    #[test]
+    fn test_cache_key_depends_on_user_context() {
+        let free = UserContext::default();
+        let enterprise = UserContext {
+            subscription_tier: SubscriptionTier::Enterprise,
+            ..UserContext::default()
+        };
+
+        let request = request("Create a blog post");
+        assert_ne!(
+            IntentCache::cache_key(&request, &free),
+            IntentCache::cache_key(&request, &enterprise)
+        );
+    }
+}
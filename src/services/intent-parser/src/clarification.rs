@@ -0,0 +1,487 @@
+//! Clarification for low-confidence parses
+//!
+//! When a parse falls below the confidence threshold the parser returns the most
+//! likely workflows and questions about the entities it could not resolve,
+//! instead of handing a possibly misread intent on for execution.
+
+use crate::types::*;
+use std::collections::HashMap;
+
+/// Keywords that suggest each workflow type, matched as word prefixes
+const WORKFLOW_KEYWORDS: &[(&str, &[&str])] = &[
+    (
+        "content_creation",
+        &[
+            "blog", "article", "post", "content", "video", "image", "write", "copy",
+        ],
+    ),
+    (
+        "marketing_campaign",
+        &[
+            "campaign",
+            "marketing",
+            "ads",
+            "advertis",
+            "promot",
+            "audience",
+            "lead",
+        ],
+    ),
+    (
+        "scheduled_publishing",
+        &[
+            "schedul", "publish", "every", "daily", "weekly", "monthly", "calendar",
+        ],
+    ),
+    (
+        "ecommerce_operation",
+        &[
+            "shop",
+            "store",
+            "order",
+            "inventory",
+            "product",
+            "price",
+            "ecommerce",
+        ],
+    ),
+    (
+        "business_intelligence",
+        &["report", "dashboard", "insight", "kpi", "forecast"],
+    ),
+    (
+        "communication",
+        &["email", "message", "notif", "slack", "reply", "newsletter"],
+    ),
+    (
+        "client_integration",
+        &["integrat", "sync", "connect", "api", "crm", "webhook"],
+    ),
+    (
+        "analytics",
+        &[
+            "analytic",
+            "metric",
+            "traffic",
+            "track",
+            "conversion",
+            "performance",
+        ],
+    ),
+];
+
+/// Rank the workflows the request text could mean, best first. The parsed
+/// workflow type is always a candidate, scored at least at the parse confidence.
+pub fn rank_candidates(
+    text: &str,
+    intent: &ParsedIntent,
+    max_candidates: usize,
+) -> Vec<WorkflowCandidate> {
+    let lowercase = text.to_lowercase();
+    let words: Vec<&str> = lowercase
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    let mut candidates = vec![WorkflowCandidate {
+        workflow_type: intent.workflow_type.clone(),
+        score: intent.confidence_score,
+    }];
+
+    for (name, keywords) in WORKFLOW_KEYWORDS {
+        let hits = words
+            .iter()
+            .filter(|word| keywords.iter().any(|keyword| word.starts_with(keyword)))
+            .count();
+        if hits == 0 {
+            continue;
+        }
+
+        // 1 hit scores 0.5, 2 hits 0.67, 3 hits 0.75, ...
+        let score = hits as f32 / (hits as f32 + 1.0);
+        let workflow_type = workflow_type_from_name(name);
+        match candidates
+            .iter_mut()
+            .find(|candidate| candidate.workflow_type == workflow_type)
+        {
+            Some(candidate) => candidate.score = candidate.score.max(score),
+            None => candidates.push(WorkflowCandidate {
+                workflow_type,
+                score,
+            }),
+        }
+    }
+
+    // Stable sort keeps the parsed workflow ahead of equally scored keyword matches
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+    candidates.truncate(max_candidates.max(1));
+    candidates
+}
+
+/// Questions about what the parse left ambiguous: which candidate workflow was
+/// meant, and required parameters the request did not supply.
+pub fn clarifying_questions(
+    intent: &ParsedIntent,
+    candidates: &[WorkflowCandidate],
+    functions: &HashMap<String, FunctionInfo>,
+) -> Vec<ClarifyingQuestion> {
+    let mut questions = Vec::new();
+
+    if candidates.len() > 1 {
+        let options: Vec<String> = candidates
+            .iter()
+            .map(|candidate| workflow_type_name(&candidate.workflow_type))
+            .collect();
+        questions.push(ClarifyingQuestion {
+            id: "workflow_type".to_string(),
+            question: format!(
+                "Which of these did you mean: {}?",
+                options
+                    .iter()
+                    .map(|option| option.replace('_', " "))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            options,
+        });
+    }
+
+    if intent.functions.is_empty() {
+        questions.push(ClarifyingQuestion {
+            id: "goal".to_string(),
+            question: "What should this workflow produce, and where should the result go?"
+                .to_string(),
+            options: Vec::new(),
+        });
+    }
+
+    for function in &intent.functions {
+        let Some(info) = functions.get(&function.name) else {
+            continue;
+        };
+
+        for parameter in &info.supported_parameters {
+            let supplied = function
+                .parameters
+                .get(&parameter.name)
+                .is_some_and(|value| !value.is_null());
+            if !parameter.required || parameter.default_value.is_some() || supplied {
+                continue;
+            }
+
+            questions.push(ClarifyingQuestion {
+                id: format!("{}.{}", function.name, parameter.name),
+                question: format!(
+                    "What {} should \"{}\" use? {}",
+                    parameter.name.replace('_', " "),
+                    info.name,
+                    parameter.description
+                ),
+                options: parameter_options(parameter),
+            });
+        }
+    }
+
+    questions
+}
+
+/// Apply a caller's disambiguation: force the chosen workflow type and fill
+/// answered `function.parameter` questions into the function parameters.
+pub fn apply_clarification(intent: &mut ParsedIntent, clarification: &Clarification) {
+    if let Some(workflow_type) = &clarification.workflow_type {
+        intent.workflow_type = workflow_type.clone();
+    }
+
+    for (id, answer) in &clarification.answers {
+        let Some((function_name, parameter)) = id.split_once('.') else {
+            continue;
+        };
+        if !is_answered(answer) {
+            continue;
+        }
+
+        for function in intent
+            .functions
+            .iter_mut()
+            .filter(|function| function.name == function_name)
+        {
+            if let Some(parameters) = function.parameters.as_object_mut() {
+                parameters.insert(parameter.to_string(), answer.clone());
+            }
+        }
+    }
+
+    if let Ok(value) = serde_json::to_value(clarification) {
+        intent
+            .metadata
+            .context_variables
+            .insert("clarification".to_string(), value);
+    }
+}
+
+/// Questions the clarification leaves without a non-empty answer. The workflow
+/// question is also answered by picking `workflow_type`.
+pub fn unanswered<'a>(
+    clarification: &Clarification,
+    questions: &'a [ClarifyingQuestion],
+) -> Vec<&'a ClarifyingQuestion> {
+    questions
+        .iter()
+        .filter(|question| {
+            let picked_workflow =
+                question.id == "workflow_type" && clarification.workflow_type.is_some();
+            let answered = clarification
+                .answers
+                .get(&question.id)
+                .is_some_and(is_answered);
+            !picked_workflow && !answered
+        })
+        .collect()
+}
+
+/// Whether a clarification says anything at all
+pub fn is_empty(clarification: &Clarification) -> bool {
+    clarification.workflow_type.is_none() && !clarification.answers.values().any(is_answered)
+}
+
+fn is_answered(answer: &serde_json::Value) -> bool {
+    match answer {
+        serde_json::Value::Null => false,
+        serde_json::Value::String(text) => !text.trim().is_empty(),
+        serde_json::Value::Array(items) => !items.is_empty(),
+        serde_json::Value::Object(fields) => !fields.is_empty(),
+        _ => true,
+    }
+}
+
+/// The snake_case name a workflow type serializes as
+pub fn workflow_type_name(workflow_type: &WorkflowType) -> String {
+    match workflow_type {
+        WorkflowType::Custom(name) => name.clone(),
+        other => serde_json::to_value(other)
+            .ok()
+            .and_then(|value| value.as_str().map(str::to_string))
+            .unwrap_or_default(),
+    }
+}
+
+fn workflow_type_from_name(name: &str) -> WorkflowType {
+    serde_json::from_value(serde_json::Value::String(name.to_string()))
+        .unwrap_or_else(|_| WorkflowType::Custom(name.to_string()))
+}
+
+/// Options from a "Must be one of: a, b, c" validation rule
+fn parameter_options(parameter: &ParameterInfo) -> Vec<String> {
+    parameter
+        .validation_rules
+        .iter()
+        .find_map(|rule| rule.strip_prefix("Must be one of: "))
+        .map(|options| options.split(", ").map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn intent(workflow_type: WorkflowType, confidence_score: f32) -> ParsedIntent {
+        ParsedIntent {
+            workflow_id: Uuid::new_v4(),
+            workflow_type,
+            functions: vec![],
+            dependencies: vec![],
+            estimated_duration: chrono::Duration::minutes(30),
+            estimated_cost: 1.0,
+            confidence_score,
+            steps: vec![],
+            required_integrations: vec![],
+            scheduling_requirements: None,
+            provider_preferences: vec![],
+            metadata: IntentMetadata {
+                created_at: chrono::Utc::now(),
+                complexity_score: 0.2,
+                language: "en".to_string(),
+                domain_scores: HashMap::new(),
+                user_preferences: None,
+                context_variables: HashMap::new(),
+                cache_hit: false,
+                cached_at: None,
+            },
+            cost_preview: None,
+        }
+    }
+
+    fn function_call(name: &str, parameters: serde_json::Value) -> FunctionCall {
+        FunctionCall {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            description: String::new(),
+            parameters,
+            provider: "default".to_string(),
+            estimated_cost: 1.0,
+            estimated_duration: chrono::Duration::minutes(5),
+            confidence_score: 0.4,
+            required_permissions: vec![],
+            mcp_server: None,
+        }
+    }
+
+    #[test]
+    fn test_rank_candidates_orders_by_score() {
+        let intent = intent(WorkflowType::Custom("parsed_from_text".to_string()), 0.3);
+        let candidates = rank_candidates(
+            "Promote our new product with an ad campaign and a blog",
+            &intent,
+            3,
+        );
+
+        assert_eq!(candidates.len(), 3);
+        assert_eq!(candidates[0].workflow_type, WorkflowType::MarketingCampaign);
+        assert!(candidates
+            .windows(2)
+            .all(|pair| pair[0].score >= pair[1].score));
+        assert!(!candidates
+            .iter()
+            .any(|c| c.workflow_type == WorkflowType::Custom("parsed_from_text".to_string())));
+    }
+
+    #[test]
+    fn test_rank_candidates_keeps_parsed_type() {
+        let intent = intent(WorkflowType::Analytics, 0.4);
+        let candidates = rank_candidates("do the thing", &intent, 3);
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].workflow_type, WorkflowType::Analytics);
+        assert_eq!(candidates[0].score, 0.4);
+    }
+
+    #[test]
+    fn test_questions_cover_candidates_and_missing_parameters() {
+        let mut intent = intent(WorkflowType::MarketingCampaign, 0.4);
+        intent.functions = vec![function_call(
+            "setup_marketing_campaign",
+            serde_json::json!({}),
+        )];
+        let candidates = vec![
+            WorkflowCandidate {
+                workflow_type: WorkflowType::MarketingCampaign,
+                score: 0.5,
+            },
+            WorkflowCandidate {
+                workflow_type: WorkflowType::ContentCreation,
+                score: 0.4,
+            },
+        ];
+        let functions = HashMap::from([(
+            "setup_marketing_campaign".to_string(),
+            FunctionInfo {
+                id: "setup_marketing_campaign".to_string(),
+                name: "Setup Marketing Campaign".to_string(),
+                description: String::new(),
+                domain: "marketing".to_string(),
+                cost_range: CostRange {
+                    min_cost: 2.0,
+                    max_cost: 50.0,
+                    average_cost: 15.0,
+                    currency: "USD".to_string(),
+                },
+                estimated_duration: chrono::Duration::hours(2),
+                complexity_score: 0.8,
+                popularity_score: 0.8,
+                success_rate: 0.92,
+                required_permissions: vec![],
+                supported_parameters: vec![ParameterInfo {
+                    name: "campaign_type".to_string(),
+                    parameter_type: "enum".to_string(),
+                    required: true,
+                    description: "Type of marketing campaign".to_string(),
+                    default_value: None,
+                    validation_rules: vec![
+                        "Must be one of: product_launch, brand_awareness".to_string()
+                    ],
+                }],
+            },
+        )]);
+
+        let questions = clarifying_questions(&intent, &candidates, &functions);
+
+        assert_eq!(questions.len(), 2);
+        assert_eq!(questions[0].id, "workflow_type");
+        assert_eq!(
+            questions[0].options,
+            vec!["marketing_campaign", "content_creation"]
+        );
+        assert_eq!(questions[1].id, "setup_marketing_campaign.campaign_type");
+        assert_eq!(
+            questions[1].options,
+            vec!["product_launch", "brand_awareness"]
+        );
+    }
+
+    #[test]
+    fn test_apply_clarification() {
+        let mut intent = intent(WorkflowType::Custom("parsed_from_text".to_string()), 0.3);
+        intent.functions = vec![function_call(
+            "setup_marketing_campaign",
+            serde_json::json!({}),
+        )];
+        let clarification: Clarification = serde_json::from_value(serde_json::json!({
+            "workflow_type": "marketing_campaign",
+            "answers": { "setup_marketing_campaign.campaign_type": "product_launch" },
+        }))
+        .unwrap();
+
+        apply_clarification(&mut intent, &clarification);
+
+        assert_eq!(intent.workflow_type, WorkflowType::MarketingCampaign);
+        assert_eq!(
+            intent.functions[0].parameters["campaign_type"],
+            "product_launch"
+        );
+        assert!(intent
+            .metadata
+            .context_variables
+            .contains_key("clarification"));
+    }
+
+    #[test]
+    fn test_empty_answers_leave_questions_open() {
+        let question = |id: &str| ClarifyingQuestion {
+            id: id.to_string(),
+            question: String::new(),
+            options: vec![],
+        };
+        let questions = vec![
+            question("workflow_type"),
+            question("setup_marketing_campaign.campaign_type"),
+        ];
+
+        let empty: Clarification = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert!(is_empty(&empty));
+        assert_eq!(unanswered(&empty, &questions).len(), 2);
+
+        let blank: Clarification = serde_json::from_value(serde_json::json!({
+            "answers": { "setup_marketing_campaign.campaign_type": " " },
+        }))
+        .unwrap();
+        assert!(is_empty(&blank));
+        assert_eq!(unanswered(&blank, &questions).len(), 2);
+
+        let partial: Clarification = serde_json::from_value(serde_json::json!({
+            "workflow_type": "marketing_campaign",
+        }))
+        .unwrap();
+        assert!(!is_empty(&partial));
+        let open = unanswered(&partial, &questions);
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].id, "setup_marketing_campaign.campaign_type");
+
+        let complete: Clarification = serde_json::from_value(serde_json::json!({
+            "workflow_type": "marketing_campaign",
+            "answers": { "setup_marketing_campaign.campaign_type": "product_launch" },
+        }))
+        .unwrap();
+        assert!(unanswered(&complete, &questions).is_empty());
+    }
+}
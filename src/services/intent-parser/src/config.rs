@@ -15,6 +15,10 @@ pub struct Config {
     pub max_concurrent_requests: usize,
     pub request_timeout_seconds: u64,
     pub cache_ttl_seconds: u64,
+    /// Parses below this confidence return clarification questions instead
+    pub clarification_confidence_threshold: f32,
+    /// Candidate workflows offered in a clarification response
+    pub clarification_max_candidates: usize,
     pub metrics: MetricsConfig,
 }
 
@@ -30,6 +34,12 @@ pub struct LLMConfig {
     pub max_retries: u32,
     pub fallback_provider: Option<String>,
     pub fallback_model: Option<String>,
+    /// Providers tried in order after the primary, from `LLM_FALLBACK_CHAIN`
+    pub fallback_chain: Vec<LLMConfig>,
+    /// Consecutive failures before a provider is skipped
+    pub circuit_breaker_threshold: u32,
+    /// How long a tripped provider is skipped before it is tried again
+    pub circuit_breaker_reset_seconds: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -106,6 +116,24 @@ impl Config {
                 .map_err(|e| {
                     AppError::ConfigurationError(format!("Invalid cache_ttl_seconds: {}", e))
                 })?,
+            clarification_confidence_threshold: env::var("CLARIFICATION_CONFIDENCE_THRESHOLD")
+                .unwrap_or_else(|_| "0.5".to_string())
+                .parse()
+                .map_err(|e| {
+                    AppError::ConfigurationError(format!(
+                        "Invalid clarification_confidence_threshold: {}",
+                        e
+                    ))
+                })?,
+            clarification_max_candidates: env::var("CLARIFICATION_MAX_CANDIDATES")
+                .unwrap_or_else(|_| "3".to_string())
+                .parse()
+                .map_err(|e| {
+                    AppError::ConfigurationError(format!(
+                        "Invalid clarification_max_candidates: {}",
+                        e
+                    ))
+                })?,
             metrics: MetricsConfig::from_env()?,
         })
     }
@@ -158,6 +186,19 @@ impl Config {
             )));
         }
 
+        if !(0.0..=1.0).contains(&self.clarification_confidence_threshold) {
+            return Err(AppError::ConfigurationError(format!(
+                "Invalid clarification_confidence_threshold: {} (must be 0.0-1.0)",
+                self.clarification_confidence_threshold
+            )));
+        }
+
+        if self.clarification_max_candidates == 0 {
+            return Err(AppError::ConfigurationError(
+                "clarification_max_candidates must be greater than 0".to_string(),
+            ));
+        }
+
         Ok(())
     }
 }
@@ -166,28 +207,9 @@ impl LLMConfig {
     pub fn from_env() -> Result<Self> {
         let provider = env::var("LLM_PROVIDER").unwrap_or_else(|_| "openai".to_string());
 
-        let (default_api_url, default_model) = match provider.as_str() {
-            "openai" => (
-                "https://api.openai.com/v1/chat/completions",
-                "gpt-4-1106-preview",
-            ),
-            "anthropic" => (
-                "https://api.anthropic.com/v1/messages",
-                "claude-3-5-sonnet-20241022",
-            ),
-            "ollama" => ("http://localhost:11434/api/chat", "llama2"),
-            "azure" => ("", "gpt-4"), // URL will be set from AZURE_OPENAI_ENDPOINT
-            "gemini" => (
-                "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.0-flash:generateContent",
-                "gemini-2.0-flash",
-            ),
-            _ => {
-                return Err(AppError::ConfigurationError(format!(
-                    "Unsupported LLM provider: {}",
-                    provider
-                )))
-            }
-        };
+        let (default_api_url, default_model) = provider_defaults(&provider).ok_or_else(|| {
+            AppError::ConfigurationError(format!("Unsupported LLM provider: {}", provider))
+        })?;
 
         let api_key = env::var("LLM_API_KEY").map_err(|_| {
             AppError::ConfigurationError("LLM_API_KEY environment variable is required".to_string())
@@ -214,7 +236,7 @@ impl LLMConfig {
             env::var("LLM_API_URL").unwrap_or_else(|_| default_api_url.to_string())
         };
 
-        Ok(LLMConfig {
+        let mut config = LLMConfig {
             provider,
             api_key,
             api_url,
@@ -245,7 +267,101 @@ impl LLMConfig {
                 })?,
             fallback_provider: env::var("LLM_FALLBACK_PROVIDER").ok(),
             fallback_model: env::var("LLM_FALLBACK_MODEL").ok(),
-        })
+            fallback_chain: Vec::new(),
+            circuit_breaker_threshold: env::var("LLM_CIRCUIT_BREAKER_THRESHOLD")
+                .unwrap_or_else(|_| "3".to_string())
+                .parse()
+                .map_err(|e| {
+                    AppError::ConfigurationError(format!(
+                        "Invalid LLM_CIRCUIT_BREAKER_THRESHOLD: {}",
+                        e
+                    ))
+                })?,
+            circuit_breaker_reset_seconds: env::var("LLM_CIRCUIT_BREAKER_RESET_SECONDS")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .map_err(|e| {
+                    AppError::ConfigurationError(format!(
+                        "Invalid LLM_CIRCUIT_BREAKER_RESET_SECONDS: {}",
+                        e
+                    ))
+                })?,
+        };
+
+        if let Ok(chain) = env::var("LLM_FALLBACK_CHAIN") {
+            config.fallback_chain = config.parse_fallback_chain(&chain)?;
+        }
+
+        Ok(config)
+    }
+
+    /// Parse a comma-separated `provider[:model]` list into fallback configs.
+    ///
+    /// Each entry reads `LLM_<PROVIDER>_API_KEY`, `LLM_<PROVIDER>_API_URL` and
+    /// `LLM_<PROVIDER>_TIMEOUT_SECONDS`, falling back to the primary's settings.
+    fn parse_fallback_chain(&self, chain: &str) -> Result<Vec<LLMConfig>> {
+        chain
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let (provider, model) = match entry.split_once(':') {
+                    Some((provider, model)) => (provider.trim(), Some(model.trim())),
+                    None => (entry, None),
+                };
+                let (default_api_url, default_model) =
+                    provider_defaults(provider).ok_or_else(|| {
+                        AppError::ConfigurationError(format!(
+                            "Unsupported LLM provider in LLM_FALLBACK_CHAIN: {}",
+                            provider
+                        ))
+                    })?;
+                let prefix = format!("LLM_{}", provider.to_uppercase());
+
+                Ok(LLMConfig {
+                    provider: provider.to_string(),
+                    api_key: env::var(format!("{}_API_KEY", prefix))
+                        .unwrap_or_else(|_| self.api_key.clone()),
+                    api_url: env::var(format!("{}_API_URL", prefix))
+                        .unwrap_or_else(|_| default_api_url.to_string()),
+                    model: model.unwrap_or(default_model).to_string(),
+                    timeout_seconds: match env::var(format!("{}_TIMEOUT_SECONDS", prefix)) {
+                        Ok(value) => value.parse().map_err(|e| {
+                            AppError::ConfigurationError(format!(
+                                "Invalid {}_TIMEOUT_SECONDS: {}",
+                                prefix, e
+                            ))
+                        })?,
+                        Err(_) => self.timeout_seconds,
+                    },
+                    fallback_provider: None,
+                    fallback_model: None,
+                    fallback_chain: Vec::new(),
+                    ..self.clone()
+                })
+            })
+            .collect()
+    }
+
+    /// Providers to try in order: the primary, the fallback chain, then the
+    /// legacy `LLM_FALLBACK_PROVIDER` if it is not already in the chain
+    pub fn provider_chain(&self) -> Vec<LLMConfig> {
+        let mut chain = vec![LLMConfig {
+            fallback_chain: Vec::new(),
+            ..self.clone()
+        }];
+        chain.extend(self.fallback_chain.iter().cloned());
+
+        if let Some(fallback) = self.get_fallback_config() {
+            let already_chained = chain
+                .iter()
+                .any(|c| c.provider == fallback.provider && c.model == fallback.model);
+            if !already_chained {
+                chain.push(fallback);
+            }
+        }
+
+        chain
     }
 
     pub fn validate(&self) -> Result<()> {
@@ -288,6 +404,21 @@ impl LLMConfig {
             )));
         }
 
+        if self.circuit_breaker_threshold == 0 {
+            return Err(AppError::ConfigurationError(
+                "LLM circuit breaker threshold must be greater than 0".to_string(),
+            ));
+        }
+
+        for fallback in &self.fallback_chain {
+            fallback.validate().map_err(|e| {
+                AppError::ConfigurationError(format!(
+                    "Invalid fallback provider {}: {}",
+                    fallback.provider, e
+                ))
+            })?;
+        }
+
         Ok(())
     }
 
@@ -314,6 +445,9 @@ impl LLMConfig {
                 max_retries: 2, // Reduced retries for fallback
                 fallback_provider: None,
                 fallback_model: None,
+                fallback_chain: Vec::new(),
+                circuit_breaker_threshold: self.circuit_breaker_threshold,
+                circuit_breaker_reset_seconds: self.circuit_breaker_reset_seconds,
             })
         } else {
             None
@@ -321,6 +455,27 @@ impl LLMConfig {
     }
 }
 
+/// Default API URL and model for a supported provider
+fn provider_defaults(provider: &str) -> Option<(&'static str, &'static str)> {
+    match provider {
+        "openai" => Some((
+            "https://api.openai.com/v1/chat/completions",
+            "gpt-4-1106-preview",
+        )),
+        "anthropic" => Some((
+            "https://api.anthropic.com/v1/messages",
+            "claude-3-5-sonnet-20241022",
+        )),
+        "ollama" => Some(("http://localhost:11434/api/chat", "llama2")),
+        "azure" => Some(("", "gpt-4")), // URL will be set from AZURE_OPENAI_ENDPOINT
+        "gemini" => Some((
+            "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.0-flash:generateContent",
+            "gemini-2.0-flash",
+        )),
+        _ => None,
+    }
+}
+
 impl DatabaseConfig {
     pub fn from_env() -> Result<Self> {
         let database_url = env::var("DATABASE_URL").map_err(|_| {
@@ -511,6 +666,8 @@ impl Default for Config {
             max_concurrent_requests: 100,
             request_timeout_seconds: 300,
             cache_ttl_seconds: 3600,
+            clarification_confidence_threshold: 0.5,
+            clarification_max_candidates: 3,
             metrics: MetricsConfig::default(),
         }
     }
@@ -529,6 +686,9 @@ impl Default for LLMConfig {
             max_retries: 3,
             fallback_provider: None,
             fallback_model: None,
+            fallback_chain: Vec::new(),
+            circuit_breaker_threshold: 3,
+            circuit_breaker_reset_seconds: 60,
         }
     }
 }
@@ -0,0 +1,314 @@
+//! Cost preview for parsed plans
+//!
+//! Prices each function call with the provider the request prefers and sums the
+//! results into a cost range and duration, so users see what a workflow will
+//! cost, and whether it fits their budget, before anything executes.
+
+use crate::types::*;
+use std::collections::HashMap;
+
+/// Spread applied to functions the registry has no cost range for
+const UNKNOWN_MIN_FACTOR: f64 = 0.75;
+const UNKNOWN_MAX_FACTOR: f64 = 1.5;
+
+const CURRENCY: &str = "USD";
+
+/// Estimate what `intent` will cost when executed for `request`
+pub fn estimate(
+    intent: &ParsedIntent,
+    request: &ParseIntentRequest,
+    functions: &HashMap<String, FunctionInfo>,
+) -> CostPreview {
+    let estimates: Vec<FunctionCostEstimate> = intent
+        .functions
+        .iter()
+        .map(|function| estimate_function(function, request, functions.get(&function.name)))
+        .collect();
+
+    let (cost_range, estimated_duration) = if estimates.is_empty() {
+        // Nothing itemized; fall back to the plan-level estimate
+        (
+            cost_range(
+                intent.estimated_cost * UNKNOWN_MIN_FACTOR,
+                intent.estimated_cost,
+                intent.estimated_cost * UNKNOWN_MAX_FACTOR,
+            ),
+            intent.estimated_duration,
+        )
+    } else {
+        (
+            cost_range(
+                estimates.iter().map(|e| e.cost_range.min_cost).sum(),
+                estimates.iter().map(|e| e.cost_range.average_cost).sum(),
+                estimates.iter().map(|e| e.cost_range.max_cost).sum(),
+            ),
+            estimates.iter().fold(chrono::Duration::zero(), |total, e| {
+                total + e.estimated_duration
+            }),
+        )
+    };
+
+    let budget_status = request
+        .budget_limit
+        .map(|budget_limit| budget_status(&cost_range, budget_limit));
+
+    CostPreview {
+        cost_range,
+        estimated_duration,
+        functions: estimates,
+        budget_limit: request.budget_limit,
+        budget_status,
+    }
+}
+
+fn estimate_function(
+    function: &FunctionCall,
+    request: &ParseIntentRequest,
+    info: Option<&FunctionInfo>,
+) -> FunctionCostEstimate {
+    let base_cost = function.estimated_cost;
+
+    // Scale the registry's typical spread to this call's point estimate
+    let (min_factor, max_factor) = match info {
+        Some(info) if info.cost_range.average_cost > 0.0 => (
+            info.cost_range.min_cost / info.cost_range.average_cost,
+            info.cost_range.max_cost / info.cost_range.average_cost,
+        ),
+        _ => (UNKNOWN_MIN_FACTOR, UNKNOWN_MAX_FACTOR),
+    };
+
+    let (provider, provider_cost) = pricing_provider(function, request, info);
+
+    FunctionCostEstimate {
+        function_id: function.id,
+        function_name: function.name.clone(),
+        provider,
+        cost_range: cost_range(
+            base_cost * min_factor + provider_cost,
+            base_cost + provider_cost,
+            base_cost * max_factor + provider_cost,
+        ),
+        estimated_duration: function.estimated_duration,
+    }
+}
+
+/// The provider a function is priced with and its per-request charge: the first
+/// preferred provider that is available and capable, then the function's own
+/// provider. Providers without known pricing add nothing.
+fn pricing_provider(
+    function: &FunctionCall,
+    request: &ParseIntentRequest,
+    info: Option<&FunctionInfo>,
+) -> (String, f64) {
+    let available = request
+        .federation_context
+        .as_ref()
+        .map(|federation| federation.available_providers.as_slice())
+        .unwrap_or_default();
+    let preferred = request.preferred_providers.as_deref().unwrap_or_default();
+
+    let find = |name: &str| {
+        available.iter().find(|provider| {
+            (provider.provider_id.eq_ignore_ascii_case(name)
+                || provider.provider_name.eq_ignore_ascii_case(name))
+                && is_capable(provider, function, info)
+        })
+    };
+
+    if let Some(provider) = preferred.iter().find_map(|name| find(name)) {
+        return (
+            provider.provider_id.clone(),
+            provider.cost_per_request.unwrap_or(0.0),
+        );
+    }
+
+    if let Some(provider) = find(&function.provider) {
+        return (
+            provider.provider_id.clone(),
+            provider.cost_per_request.unwrap_or(0.0),
+        );
+    }
+
+    let name = preferred
+        .first()
+        .cloned()
+        .unwrap_or_else(|| function.provider.clone());
+    (name, 0.0)
+}
+
+/// Providers that list no capabilities are assumed to handle anything
+fn is_capable(
+    provider: &ProviderInfo,
+    function: &FunctionCall,
+    info: Option<&FunctionInfo>,
+) -> bool {
+    provider.capabilities.is_empty()
+        || provider.capabilities.iter().any(|capability| {
+            capability == &function.name || info.is_some_and(|info| capability == &info.domain)
+        })
+}
+
+fn budget_status(cost_range: &CostRange, budget_limit: f64) -> BudgetStatus {
+    if cost_range.average_cost > budget_limit {
+        BudgetStatus::Exceeds
+    } else if cost_range.max_cost > budget_limit {
+        BudgetStatus::MayExceed
+    } else {
+        BudgetStatus::WithinBudget
+    }
+}
+
+fn cost_range(min_cost: f64, average_cost: f64, max_cost: f64) -> CostRange {
+    CostRange {
+        min_cost,
+        max_cost,
+        average_cost,
+        currency: CURRENCY.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn request(value: serde_json::Value) -> ParseIntentRequest {
+        let mut request = serde_json::json!({
+            "user_id": Uuid::nil(),
+            "text": "Launch a campaign",
+        });
+        request
+            .as_object_mut()
+            .unwrap()
+            .extend(value.as_object().unwrap().clone());
+        serde_json::from_value(request).unwrap()
+    }
+
+    fn function_call(name: &str, estimated_cost: f64, minutes: i64) -> FunctionCall {
+        FunctionCall {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            description: String::new(),
+            parameters: serde_json::json!({}),
+            provider: "default".to_string(),
+            estimated_cost,
+            estimated_duration: chrono::Duration::minutes(minutes),
+            confidence_score: 0.85,
+            required_permissions: vec![],
+            mcp_server: None,
+        }
+    }
+
+    fn intent(functions: Vec<FunctionCall>) -> ParsedIntent {
+        ParsedIntent {
+            workflow_id: Uuid::new_v4(),
+            workflow_type: WorkflowType::MarketingCampaign,
+            functions,
+            dependencies: vec![],
+            estimated_duration: chrono::Duration::minutes(30),
+            estimated_cost: 1.0,
+            confidence_score: 0.85,
+            steps: vec![],
+            required_integrations: vec![],
+            scheduling_requirements: None,
+            provider_preferences: vec![],
+            metadata: IntentMetadata {
+                created_at: chrono::Utc::now(),
+                complexity_score: 0.5,
+                language: "en".to_string(),
+                domain_scores: HashMap::new(),
+                user_preferences: None,
+                context_variables: HashMap::new(),
+                cache_hit: false,
+                cached_at: None,
+            },
+            cost_preview: None,
+        }
+    }
+
+    fn registry() -> HashMap<String, FunctionInfo> {
+        HashMap::from([(
+            "setup_marketing_campaign".to_string(),
+            FunctionInfo {
+                id: "setup_marketing_campaign".to_string(),
+                name: "Setup Marketing Campaign".to_string(),
+                description: String::new(),
+                domain: "marketing".to_string(),
+                cost_range: cost_range(2.0, 10.0, 20.0),
+                estimated_duration: chrono::Duration::hours(2),
+                complexity_score: 0.8,
+                popularity_score: 0.8,
+                success_rate: 0.92,
+                required_permissions: vec![],
+                supported_parameters: vec![],
+            },
+        )])
+    }
+
+    #[test]
+    fn test_estimate_sums_functions() {
+        let intent = intent(vec![
+            function_call("setup_marketing_campaign", 10.0, 60),
+            function_call("unregistered_function", 4.0, 30),
+        ]);
+        let preview = estimate(&intent, &request(serde_json::json!({})), &registry());
+
+        assert_eq!(preview.functions.len(), 2);
+        assert!((preview.cost_range.min_cost - 5.0).abs() < 1e-9);
+        assert!((preview.cost_range.average_cost - 14.0).abs() < 1e-9);
+        assert!((preview.cost_range.max_cost - 26.0).abs() < 1e-9);
+        assert_eq!(preview.estimated_duration, chrono::Duration::minutes(90));
+        assert!(preview.budget_status.is_none());
+    }
+
+    #[test]
+    fn test_estimate_prices_preferred_provider() {
+        let intent = intent(vec![function_call("setup_marketing_campaign", 10.0, 60)]);
+        let request = request(serde_json::json!({
+            "preferred_providers": ["unknown", "premium-ads"],
+            "federation_context": {
+                "client_id": null,
+                "available_providers": [
+                    {
+                        "provider_id": "budget-ads",
+                        "provider_name": "Budget Ads",
+                        "capabilities": ["marketing"],
+                        "cost_per_request": 0.5,
+                    },
+                    {
+                        "provider_id": "premium-ads",
+                        "provider_name": "Premium Ads",
+                        "capabilities": ["marketing"],
+                        "cost_per_request": 3.0,
+                    },
+                ],
+                "cost_constraints": null,
+                "quality_requirements": null,
+            },
+        }));
+
+        let preview = estimate(&intent, &request, &registry());
+
+        assert_eq!(preview.functions[0].provider, "premium-ads");
+        assert!((preview.cost_range.average_cost - 13.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_estimate_flags_budget() {
+        let intent = intent(vec![function_call("setup_marketing_campaign", 10.0, 60)]);
+        let registry = registry();
+
+        let status = |budget_limit: f64| {
+            estimate(
+                &intent,
+                &request(serde_json::json!({ "budget_limit": budget_limit })),
+                &registry,
+            )
+            .budget_status
+        };
+
+        assert_eq!(status(25.0), Some(BudgetStatus::WithinBudget));
+        assert_eq!(status(15.0), Some(BudgetStatus::MayExceed));
+        assert_eq!(status(5.0), Some(BudgetStatus::Exceeds));
+    }
+}
@@ -4,7 +4,10 @@
 //! requests into structured automation workflows.
 
 pub mod blog_intent;
+pub mod cache;
+pub mod clarification;
 pub mod config;
+pub mod cost_preview;
 pub mod error;
 pub mod llm;
 pub mod parser;
@@ -75,12 +78,34 @@ mod tests {
             budget_limit: None,
             time_limit: None,
             quality_threshold: None,
+            validate_only: false,
+            bypass_cache: false,
+            clarification: None,
         };
 
         assert!(!request.text.is_empty());
         assert!(request.text.len() < 10000);
     }
 
+    #[test]
+    fn test_parse_intent_request_validate_only_flag() {
+        let user_id = Uuid::new_v4();
+        let request: ParseIntentRequest = serde_json::from_value(serde_json::json!({
+            "user_id": user_id,
+            "text": "Create a blog post about AI",
+        }))
+        .unwrap();
+        assert!(!request.validate_only);
+
+        let request: ParseIntentRequest = serde_json::from_value(serde_json::json!({
+            "user_id": user_id,
+            "text": "Create a blog post about AI",
+            "validate_only": true,
+        }))
+        .unwrap();
+        assert!(request.validate_only);
+    }
+
     #[test]
     fn test_function_call_creation() {
         let function_call = FunctionCall {
@@ -182,6 +207,8 @@ mod tests {
             domain_scores: std::collections::HashMap::new(),
             user_preferences: None,
             context_variables: std::collections::HashMap::new(),
+            cache_hit: false,
+            cached_at: None,
         };
 
         assert_eq!(metadata.complexity_score, 0.5);
@@ -4,7 +4,8 @@ use crate::types::*;
 use reqwest::{Client, RequestBuilder};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tracing::{debug, error, info, warn};
 
 #[derive(Clone)]
@@ -12,6 +13,9 @@ pub struct LLMClient {
     client: Client,
     config: LLMConfig,
     provider: LLMProvider,
+    circuit_breaker: Arc<CircuitBreaker>,
+    /// Providers tried in order when this one fails, rate-limits or times out
+    fallbacks: Vec<LLMClient>,
 }
 
 #[derive(Clone)]
@@ -89,9 +93,117 @@ pub struct LLMUsage {
     pub total_tokens: u32,
 }
 
+/// Response from the fallback chain along with the provider that produced it
+#[derive(Debug)]
+pub struct ProviderResponse {
+    pub response: LLMResponse,
+    pub provider: String,
+    pub model: String,
+    /// Providers that failed before this one succeeded
+    pub failures: Vec<ProviderFailure>,
+}
+
+/// Why a provider in the fallback chain did not produce a response
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderFailure {
+    pub provider: String,
+    pub model: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CircuitState {
+    Closed { consecutive_failures: u32 },
+    Open { retry_at: Instant },
+    HalfOpen,
+}
+
+/// Skips a provider for a while after repeated failures
+#[derive(Debug)]
+struct CircuitBreaker {
+    state: Mutex<CircuitState>,
+    threshold: u32,
+    reset_timeout: Duration,
+}
+
+impl CircuitBreaker {
+    fn new(threshold: u32, reset_timeout: Duration) -> Self {
+        Self {
+            state: Mutex::new(CircuitState::Closed {
+                consecutive_failures: 0,
+            }),
+            threshold,
+            reset_timeout,
+        }
+    }
+
+    /// Whether a request may be sent; an expired open circuit lets a single trial through
+    fn allow_request(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        match *state {
+            CircuitState::Closed { .. } => true,
+            CircuitState::Open { retry_at } if Instant::now() >= retry_at => {
+                *state = CircuitState::HalfOpen;
+                true
+            }
+            CircuitState::Open { .. } | CircuitState::HalfOpen => false,
+        }
+    }
+
+    fn record_success(&self) {
+        *self.state.lock().unwrap() = CircuitState::Closed {
+            consecutive_failures: 0,
+        };
+    }
+
+    fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+        let consecutive_failures = match *state {
+            CircuitState::Closed {
+                consecutive_failures,
+            } => consecutive_failures + 1,
+            CircuitState::Open { .. } | CircuitState::HalfOpen => self.threshold,
+        };
+
+        *state = if consecutive_failures >= self.threshold {
+            CircuitState::Open {
+                retry_at: Instant::now() + self.reset_timeout,
+            }
+        } else {
+            CircuitState::Closed {
+                consecutive_failures,
+            }
+        };
+    }
+}
+
 impl LLMClient {
     pub async fn new(config: &Config) -> Result<Self> {
-        let provider = match config.llm.provider.as_str() {
+        let mut clients = config
+            .llm
+            .provider_chain()
+            .iter()
+            .map(Self::for_provider)
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut primary = clients.remove(0);
+        if !clients.is_empty() {
+            info!(
+                "LLM fallback chain: {}",
+                std::iter::once(&primary)
+                    .chain(&clients)
+                    .map(|c| c.provider_label())
+                    .collect::<Vec<_>>()
+                    .join(" -> ")
+            );
+        }
+        primary.fallbacks = clients;
+
+        Ok(primary)
+    }
+
+    fn for_provider(config: &crate::config::LLMConfig) -> Result<Self> {
+        let provider = match config.provider.as_str() {
             "openai" => LLMProvider::OpenAI,
             "anthropic" => LLMProvider::Anthropic,
             "ollama" => LLMProvider::Ollama,
@@ -100,13 +212,13 @@ impl LLMClient {
             _ => {
                 return Err(AppError::ConfigurationError(format!(
                     "Unsupported LLM provider: {}",
-                    config.llm.provider
+                    config.provider
                 )))
             }
         };
 
         let client = Client::builder()
-            .timeout(Duration::from_secs(config.llm.timeout_seconds))
+            .timeout(Duration::from_secs(config.timeout_seconds))
             .build()
             .map_err(|e| {
                 AppError::InternalServerError(format!("Failed to create HTTP client: {}", e))
@@ -115,19 +227,28 @@ impl LLMClient {
         Ok(Self {
             client,
             config: LLMConfig {
-                provider: config.llm.provider.clone(),
-                api_key: config.llm.api_key.clone(),
-                api_url: config.llm.api_url.clone(),
-                model: config.llm.model.clone(),
-                max_tokens: config.llm.max_tokens,
-                temperature: config.llm.temperature,
-                timeout_seconds: config.llm.timeout_seconds,
-                max_retries: config.llm.max_retries,
+                provider: config.provider.clone(),
+                api_key: config.api_key.clone(),
+                api_url: config.api_url.clone(),
+                model: config.model.clone(),
+                max_tokens: config.max_tokens,
+                temperature: config.temperature,
+                timeout_seconds: config.timeout_seconds,
+                max_retries: config.max_retries,
             },
             provider,
+            circuit_breaker: Arc::new(CircuitBreaker::new(
+                config.circuit_breaker_threshold,
+                Duration::from_secs(config.circuit_breaker_reset_seconds),
+            )),
+            fallbacks: Vec::new(),
         })
     }
 
+    fn provider_label(&self) -> String {
+        format!("{} ({})", self.config.provider, self.config.model)
+    }
+
     pub async fn parse_intent_with_context(
         &self,
         request: &ParseIntentRequest,
@@ -156,8 +277,18 @@ impl LLMClient {
             max_tokens: self.config.max_tokens,
         };
 
-        let response = self.send_request(llm_request).await?;
-        self.parse_llm_response(response, request).await
+        // The same prompt, including budget and quality limits, goes to every provider
+        let outcome = self.send_request(llm_request).await?;
+        let mut intent = self.parse_llm_response(outcome.response, request).await?;
+
+        let context_variables = &mut intent.metadata.context_variables;
+        context_variables.insert("llm_provider".to_string(), json!(outcome.provider));
+        context_variables.insert("llm_model".to_string(), json!(outcome.model));
+        if !outcome.failures.is_empty() {
+            context_variables.insert("llm_provider_failures".to_string(), json!(outcome.failures));
+        }
+
+        Ok(intent)
     }
 
     pub async fn validate_parsed_intent(&self, intent: &ParsedIntent) -> Result<ValidationResult> {
@@ -185,8 +316,8 @@ impl LLMClient {
             max_tokens: 2000,
         };
 
-        let response = self.send_request(llm_request).await?;
-        self.parse_validation_response(response).await
+        let outcome = self.send_request(llm_request).await?;
+        self.parse_validation_response(outcome.response).await
     }
 
     pub async fn health_check(&self) -> Result<()> {
@@ -215,14 +346,87 @@ impl LLMClient {
         }
     }
 
-    async fn send_request(&self, request: LLMRequest) -> Result<LLMResponse> {
+    /// Send the request to each provider in the chain until one succeeds
+    async fn send_request(&self, request: LLMRequest) -> Result<ProviderResponse> {
+        let mut failures = Vec::new();
+
+        for client in std::iter::once(self).chain(&self.fallbacks) {
+            if !client.circuit_breaker.allow_request() {
+                warn!(
+                    "Skipping LLM provider {}: circuit open",
+                    client.provider_label()
+                );
+                failures.push(client.failure("circuit breaker open".to_string()));
+                continue;
+            }
+
+            match client.send_with_retries(&request).await {
+                Ok(response) => {
+                    client.circuit_breaker.record_success();
+                    if !failures.is_empty() {
+                        info!(
+                            "LLM request succeeded on fallback provider {} after {} failures",
+                            client.provider_label(),
+                            failures.len()
+                        );
+                    }
+                    return Ok(ProviderResponse {
+                        response,
+                        provider: client.config.provider.clone(),
+                        model: client.config.model.clone(),
+                        failures,
+                    });
+                }
+                Err(e) => {
+                    client.circuit_breaker.record_failure();
+                    warn!("LLM provider {} failed: {}", client.provider_label(), e);
+                    failures.push(client.failure(e.to_string()));
+                }
+            }
+        }
+
+        Err(AppError::LlmError(format!(
+            "All {} LLM providers failed: {}",
+            failures.len(),
+            failures
+                .iter()
+                .map(|f| format!("{} ({}): {}", f.provider, f.model, f.error))
+                .collect::<Vec<_>>()
+                .join("; ")
+        )))
+    }
+
+    fn failure(&self, error: String) -> ProviderFailure {
+        ProviderFailure {
+            provider: self.config.provider.clone(),
+            model: self.config.model.clone(),
+            error,
+        }
+    }
+
+    async fn send_with_retries(&self, request: &LLMRequest) -> Result<LLMResponse> {
         let mut attempts = 0;
         let max_retries = self.config.max_retries;
+        let timeout = Duration::from_secs(self.config.timeout_seconds);
 
         while attempts <= max_retries {
-            match self.send_request_once(&request).await {
+            let result = match tokio::time::timeout(timeout, self.send_request_once(request)).await
+            {
+                Ok(result) => result,
+                Err(_) => Err(AppError::TimeoutError(format!(
+                    "LLM request timed out after {}s",
+                    timeout.as_secs()
+                ))),
+            };
+
+            match result {
                 Ok(response) => return Ok(response),
-                Err(e) if attempts < max_retries => {
+                // Rate limits and timeouts move straight on to the next provider
+                Err(e)
+                    if attempts < max_retries
+                        && e.is_retryable()
+                        && !matches!(e, AppError::TimeoutError(_)) =>
+                {
                     attempts += 1;
                     let delay = Duration::from_millis(1000 * (2_u64.pow(attempts - 1)));
                     warn!(
@@ -253,10 +457,20 @@ impl LLMClient {
         };
 
         let response = req_builder.send().await.map_err(|e| {
-            AppError::ExternalServiceError(format!("LLM API request failed: {}", e))
+            if e.is_timeout() {
+                AppError::TimeoutError(format!("LLM API request timed out: {}", e))
+            } else {
+                AppError::ExternalServiceError(format!("LLM API request failed: {}", e))
+            }
         })?;
 
         let status = response.status();
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(AppError::RateLimitExceeded(format!(
+                "LLM provider {} rate limited the request",
+                self.config.provider
+            )));
+        }
         if !status.is_success() {
             let error_text = response
                 .text()
@@ -272,6 +486,12 @@ impl LLMClient {
             AppError::ExternalServiceError(format!("Failed to parse LLM response: {}", e))
         })?;
 
+        if llm_response.choices.is_empty() {
+            return Err(AppError::ExternalServiceError(
+                "No choices in LLM response".to_string(),
+            ));
+        }
+
         Ok(llm_response)
     }
 
@@ -480,6 +700,18 @@ Response format: Use function calling with the registered functions."#.to_string
             prompt.push_str(&format!("Minimum Quality Threshold: {:.1}/1.0\n", quality));
         }
 
+        if let Some(clarification) = &request.clarification {
+            if let Some(workflow_type) = &clarification.workflow_type {
+                prompt.push_str(&format!(
+                    "Clarified Workflow Type: {}\n",
+                    crate::clarification::workflow_type_name(workflow_type)
+                ));
+            }
+            for (question, answer) in &clarification.answers {
+                prompt.push_str(&format!("Clarification ({}): {}\n", question, answer));
+            }
+        }
+
         if let Some(federation) = &request.federation_context {
             if let Some(client_id) = &federation.client_id {
                 prompt.push_str(&format!("Client ID: {}\n", client_id));
@@ -799,7 +1031,10 @@ Provide a structured response with validation results, warnings, and suggestions
                 domain_scores: std::collections::HashMap::new(),
                 user_preferences: None,
                 context_variables: std::collections::HashMap::new(),
+                cache_hit: false,
+                cached_at: None,
             },
+            cost_preview: None,
         })
     }
 
@@ -833,7 +1068,10 @@ Provide a structured response with validation results, warnings, and suggestions
                 domain_scores: std::collections::HashMap::new(),
                 user_preferences: None,
                 context_variables: std::collections::HashMap::new(),
+                cache_hit: false,
+                cached_at: None,
             },
+            cost_preview: None,
         })
     }
 
@@ -989,3 +1227,152 @@ Provide a structured response with validation results, warnings, and suggestions
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn test_config(api_url: &str, fallbacks: Vec<crate::config::LLMConfig>) -> Config {
+        let mut config = Config::default();
+        config.llm = crate::config::LLMConfig {
+            api_key: "test-key".to_string(),
+            api_url: api_url.to_string(),
+            max_retries: 0,
+            fallback_chain: fallbacks,
+            ..config.llm
+        };
+        config
+    }
+
+    fn fallback_config(provider: &str, model: &str, api_url: &str) -> crate::config::LLMConfig {
+        crate::config::LLMConfig {
+            provider: provider.to_string(),
+            model: model.to_string(),
+            api_key: "test-key".to_string(),
+            api_url: api_url.to_string(),
+            max_retries: 0,
+            ..crate::config::LLMConfig::default()
+        }
+    }
+
+    fn test_request() -> LLMRequest {
+        LLMRequest {
+            messages: vec![LLMMessage {
+                role: "user".to_string(),
+                content: "Create a blog post".to_string(),
+            }],
+            functions: None,
+            function_call: None,
+            temperature: 0.0,
+            max_tokens: 10,
+        }
+    }
+
+    fn ok_response() -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(json!({
+            "choices": [{
+                "message": {"role": "assistant", "content": "OK"},
+                "finish_reason": "stop"
+            }]
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_when_primary_is_rate_limited() {
+        let primary = MockServer::start().await;
+        let fallback = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(429))
+            .mount(&primary)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ok_response())
+            .mount(&fallback)
+            .await;
+
+        let config = test_config(
+            &primary.uri(),
+            vec![fallback_config("ollama", "llama2", &fallback.uri())],
+        );
+        let client = LLMClient::new(&config).await.unwrap();
+
+        let outcome = client.send_request(test_request()).await.unwrap();
+        assert_eq!(outcome.provider, "ollama");
+        assert_eq!(outcome.model, "llama2");
+        assert_eq!(outcome.failures.len(), 1);
+        assert_eq!(outcome.failures[0].provider, "openai");
+        assert!(outcome.failures[0].error.contains("rate limited"));
+    }
+
+    #[tokio::test]
+    async fn test_aggregates_failures_when_all_providers_fail() {
+        let primary = MockServer::start().await;
+        let fallback = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500).set_body_string("boom"))
+            .mount(&primary)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(429))
+            .mount(&fallback)
+            .await;
+
+        let config = test_config(
+            &primary.uri(),
+            vec![fallback_config("ollama", "llama2", &fallback.uri())],
+        );
+        let client = LLMClient::new(&config).await.unwrap();
+
+        let error = client.send_request(test_request()).await.unwrap_err();
+        let message = error.to_string();
+        assert!(matches!(error, AppError::LlmError(_)));
+        assert!(message.contains("openai (gpt-4-1106-preview)"));
+        assert!(message.contains("boom"));
+        assert!(message.contains("ollama (llama2)"));
+    }
+
+    #[tokio::test]
+    async fn test_open_circuit_skips_provider() {
+        let primary = MockServer::start().await;
+        let fallback = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(1)
+            .mount(&primary)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ok_response())
+            .mount(&fallback)
+            .await;
+
+        let mut config = test_config(
+            &primary.uri(),
+            vec![fallback_config("ollama", "llama2", &fallback.uri())],
+        );
+        config.llm.circuit_breaker_threshold = 1;
+        let client = LLMClient::new(&config).await.unwrap();
+
+        client.send_request(test_request()).await.unwrap();
+        let outcome = client.send_request(test_request()).await.unwrap();
+        assert_eq!(outcome.provider, "ollama");
+        assert_eq!(outcome.failures[0].error, "circuit breaker open");
+    }
+
+    #[test]
+    fn test_circuit_breaker_half_open_trial() {
+        let breaker = CircuitBreaker::new(2, Duration::ZERO);
+
+        breaker.record_failure();
+        assert!(breaker.allow_request());
+        breaker.record_failure();
+
+        // Reset timeout has elapsed, so exactly one trial request is allowed
+        assert!(breaker.allow_request());
+        assert!(!breaker.allow_request());
+
+        breaker.record_success();
+        assert!(breaker.allow_request());
+    }
+}
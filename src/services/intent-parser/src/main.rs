@@ -14,12 +14,16 @@ use tower_http::{cors::CorsLayer, trace::TraceLayer};
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
+mod cache;
+mod clarification;
 mod config;
+mod cost_preview;
 mod error;
 mod llm;
 mod parser;
 mod types;
 
+use cache::IntentCache;
 use config::Config;
 use error::{AppError, Result};
 use llm::LLMClient;
@@ -63,8 +67,18 @@ async fn main() -> Result<()> {
     let llm_client = Arc::new(LLMClient::new(&config).await?);
     info!("LLM client initialized");
 
-    // Initialize intent parser
-    let intent_parser = Arc::new(IntentParser::new(llm_client.clone()));
+    // Initialize intent parser, caching parses in Redis when it is reachable
+    let mut intent_parser = IntentParser::new(llm_client.clone()).with_clarification(
+        config.clarification_confidence_threshold,
+        config.clarification_max_candidates,
+    );
+    if config.cache_ttl_seconds > 0 {
+        match IntentCache::new(&config.redis, config.cache_ttl_seconds).await {
+            Ok(cache) => intent_parser = intent_parser.with_cache(cache),
+            Err(e) => warn!("Intent cache unavailable, parsing without cache: {}", e),
+        }
+    }
+    let intent_parser = Arc::new(intent_parser);
     info!("Intent parser initialized");
 
     // Initialize health status
@@ -171,14 +185,34 @@ async fn parse_intent(
     State(state): State<AppState>,
     headers: HeaderMap,
     Json(request): Json<ParseIntentRequest>,
-) -> Result<Json<ParsedIntent>> {
+) -> Result<Json<ParseIntentResponse>> {
     info!("Parsing intent for user: {}", request.user_id);
 
     // Extract user context from headers if available
     let user_context = extract_user_context(&headers)?;
 
-    // Parse the intent
-    let parsed_intent = state
+    // Dry run: report validation results and stop before any workflow is triggered
+    if request.validate_only {
+        let (intent, validation) = state
+            .intent_parser
+            .validate_request_only(&request, user_context)
+            .await
+            .map_err(|e| {
+                error!("Failed to validate intent: {:?}", e);
+                AppError::InternalServerError(format!("Intent validation failed: {}", e))
+            })?;
+
+        return Ok(Json(ParseIntentResponse::ValidateOnly(
+            ValidateOnlyResponse {
+                validate_only: true,
+                intent,
+                validation: validation.into(),
+            },
+        )));
+    }
+
+    // Parse the intent; low-confidence parses come back as clarification requests
+    let outcome = state
         .intent_parser
         .parse_request(&request, user_context)
         .await
@@ -187,13 +221,21 @@ async fn parse_intent(
             AppError::InternalServerError(format!("Intent parsing failed: {}", e))
         })?;
 
-    info!(
-        "Successfully parsed intent for user {}: {} functions generated",
-        request.user_id,
-        parsed_intent.functions.len()
-    );
+    match &outcome {
+        ParseOutcome::Parsed(parsed_intent) => info!(
+            "Successfully parsed intent for user {}: {} functions generated",
+            request.user_id,
+            parsed_intent.functions.len()
+        ),
+        ParseOutcome::NeedsClarification(clarification) => info!(
+            "Intent for user {} needs clarification: {} candidates, {} questions",
+            request.user_id,
+            clarification.candidates.len(),
+            clarification.questions.len()
+        ),
+    }
 
-    Ok(Json(parsed_intent))
+    Ok(Json(outcome.into()))
 }
 
 // Parse multiple intents in batch
@@ -218,16 +260,43 @@ async fn parse_batch_intents(
 
     // Process each request
     for (index, parse_request) in request.requests.into_iter().enumerate() {
-        match state
-            .intent_parser
-            .parse_request(&parse_request, user_context.clone())
-            .await
-        {
-            Ok(parsed_intent) => {
+        let outcome = if parse_request.validate_only {
+            state
+                .intent_parser
+                .validate_request_only(&parse_request, user_context.clone())
+                .await
+                .map(|(intent, validation)| {
+                    (
+                        ParseOutcome::Parsed(Box::new(intent)),
+                        Some(validation.into()),
+                    )
+                })
+        } else {
+            state
+                .intent_parser
+                .parse_request(&parse_request, user_context.clone())
+                .await
+                .map(|outcome| (outcome, None))
+        };
+
+        match outcome {
+            Ok((ParseOutcome::Parsed(parsed_intent), validation)) => {
+                results.push(BatchParseResult {
+                    index,
+                    success: true,
+                    intent: Some(*parsed_intent),
+                    validation,
+                    clarification: None,
+                    error: None,
+                });
+            }
+            Ok((ParseOutcome::NeedsClarification(clarification), _)) => {
                 results.push(BatchParseResult {
                     index,
                     success: true,
-                    intent: Some(parsed_intent),
+                    intent: None,
+                    validation: None,
+                    clarification: Some(clarification),
                     error: None,
                 });
             }
@@ -238,6 +307,8 @@ async fn parse_batch_intents(
                     index,
                     success: false,
                     intent: None,
+                    validation: None,
+                    clarification: None,
                     error: Some(error.to_string()),
                 });
             }
@@ -271,14 +342,7 @@ async fn validate_intent(
 
     let validation_result = state.intent_parser.validate_intent(&intent).await?;
 
-    Ok(Json(ValidationResponse {
-        valid: validation_result.is_valid,
-        confidence_score: validation_result.confidence_score,
-        warnings: validation_result.warnings,
-        suggestions: validation_result.suggestions,
-        estimated_execution_time: validation_result.estimated_execution_time,
-        estimated_cost: validation_result.estimated_cost,
-    }))
+    Ok(Json(validation_result.into()))
 }
 
 // Get available capabilities and functions
@@ -1,3 +1,6 @@
+use crate::cache::IntentCache;
+use crate::clarification;
+use crate::cost_preview;
 use crate::error::{ErrorContext, ParseIntentError, Result};
 use crate::llm::LLMClient;
 use crate::types::*;
@@ -12,6 +15,10 @@ pub struct IntentParser {
     function_registry: Arc<FunctionRegistry>,
     user_context_cache: Arc<tokio::sync::RwLock<HashMap<Uuid, UserContext>>>,
     validation_cache: Arc<tokio::sync::RwLock<HashMap<String, ValidationResult>>>,
+    intent_cache: Option<IntentCache>,
+    /// Parses below this confidence are returned for clarification, not executed
+    confidence_threshold: f32,
+    max_clarification_candidates: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -34,16 +41,136 @@ impl IntentParser {
             function_registry,
             user_context_cache,
             validation_cache,
+            intent_cache: None,
+            confidence_threshold: 0.5,
+            max_clarification_candidates: 3,
         }
     }
 
+    /// Serve repeated requests from the parse cache instead of the LLM
+    pub fn with_cache(mut self, intent_cache: IntentCache) -> Self {
+        self.intent_cache = Some(intent_cache);
+        self
+    }
+
+    /// Confidence below which `parse_request` asks for clarification, and how
+    /// many candidate workflows it offers
+    pub fn with_clarification(mut self, confidence_threshold: f32, max_candidates: usize) -> Self {
+        self.confidence_threshold = confidence_threshold;
+        self.max_clarification_candidates = max_candidates;
+        self
+    }
+
     pub async fn parse_request(
         &self,
         request: &ParseIntentRequest,
         user_context: Option<UserContext>,
-    ) -> Result<ParsedIntent> {
+    ) -> Result<ParseOutcome> {
         info!("Parsing intent request for user: {}", request.user_id);
 
+        let (parsed_intent, context) = self.parse_without_learning(request, user_context).await?;
+
+        // A clarified request is the caller's confirmed intent, but only once it
+        // answers every question the parse raises
+        let threshold = self.effective_confidence_threshold(request);
+        if parsed_intent.confidence_score < threshold {
+            let mut response = self.build_clarification(request, &parsed_intent, threshold);
+            let confirmed = match &request.clarification {
+                // Ask again only what is still open
+                Some(answers) if !clarification::is_empty(answers) => {
+                    response.questions = clarification::unanswered(answers, &response.questions)
+                        .into_iter()
+                        .cloned()
+                        .collect();
+                    response.questions.is_empty()
+                }
+                _ => false,
+            };
+
+            if !confirmed {
+                info!(
+                    "Intent confidence {:.2} below threshold {:.2}, requesting clarification",
+                    parsed_intent.confidence_score, threshold
+                );
+                return Ok(ParseOutcome::NeedsClarification(response));
+            }
+        }
+
+        // Update user context with learning
+        self.update_user_learning(&context, request, &parsed_intent)
+            .await?;
+
+        info!(
+            "Successfully parsed intent: {} functions, {:.2} confidence",
+            parsed_intent.functions.len(),
+            parsed_intent.confidence_score
+        );
+
+        Ok(ParseOutcome::Parsed(Box::new(parsed_intent)))
+    }
+
+    /// The configured threshold, raised to the request's `quality_threshold`
+    fn effective_confidence_threshold(&self, request: &ParseIntentRequest) -> f32 {
+        request
+            .quality_threshold
+            .unwrap_or(0.0)
+            .max(self.confidence_threshold)
+    }
+
+    fn build_clarification(
+        &self,
+        request: &ParseIntentRequest,
+        intent: &ParsedIntent,
+        threshold: f32,
+    ) -> ClarificationResponse {
+        let candidates = clarification::rank_candidates(
+            &request.text,
+            intent,
+            self.max_clarification_candidates,
+        );
+        let questions = clarification::clarifying_questions(
+            intent,
+            &candidates,
+            &self.function_registry.functions,
+        );
+
+        ClarificationResponse {
+            needs_clarification: true,
+            confidence_score: intent.confidence_score,
+            confidence_threshold: threshold,
+            candidates,
+            questions,
+        }
+    }
+
+    /// Dry run for `validate_only` requests: parses the intent and computes its
+    /// `ValidationResult` (cost, permissions, parameters) without updating user
+    /// learning, so nothing downstream observes the request.
+    pub async fn validate_request_only(
+        &self,
+        request: &ParseIntentRequest,
+        user_context: Option<UserContext>,
+    ) -> Result<(ParsedIntent, ValidationResult)> {
+        info!("Validating intent request for user: {}", request.user_id);
+
+        let (parsed_intent, _) = self.parse_without_learning(request, user_context).await?;
+        let validation = self.validate_intent(&parsed_intent).await?;
+
+        info!(
+            "Validated intent without execution: valid={}, {} warnings, {} missing permissions",
+            validation.is_valid,
+            validation.warnings.len(),
+            validation.missing_permissions.len()
+        );
+
+        Ok((parsed_intent, validation))
+    }
+
+    async fn parse_without_learning(
+        &self,
+        request: &ParseIntentRequest,
+        user_context: Option<UserContext>,
+    ) -> Result<(ParsedIntent, UserContext)> {
         // Get or use provided user context
         let context = match user_context {
             Some(ctx) => ctx,
@@ -59,6 +186,27 @@ impl IntentParser {
         // Pre-process the request text
         let _processed_text = self.preprocess_text(&request.text)?;
 
+        // A bypassing request neither reads nor refreshes the cached entry
+        let cache_key = match &self.intent_cache {
+            Some(_) if request.bypass_cache => {
+                debug!("Bypassing intent cache for user: {}", request.user_id);
+                None
+            }
+            Some(_) => Some(IntentCache::cache_key(request, &context)),
+            None => None,
+        };
+
+        if let (Some(cache), Some(key)) = (&self.intent_cache, &cache_key) {
+            match cache.get(key).await {
+                Ok(Some(cached_intent)) => {
+                    info!("Serving cached intent for user: {}", request.user_id);
+                    return Ok((cached_intent, context));
+                }
+                Ok(None) => {}
+                Err(e) => warn!("Intent cache lookup failed: {}", e),
+            }
+        }
+
         // Extract intent using LLM
         let mut parsed_intent = self
             .llm_client
@@ -74,17 +222,30 @@ impl IntentParser {
         self.optimize_intent(&mut parsed_intent, request, &context)
             .await?;
 
-        // Update user context with learning
-        self.update_user_learning(&context, request, &parsed_intent)
-            .await?;
+        if let Some(clarification) = &request.clarification {
+            clarification::apply_clarification(&mut parsed_intent, clarification);
+        }
 
-        info!(
-            "Successfully parsed intent: {} functions, {:.2} confidence",
-            parsed_intent.functions.len(),
-            parsed_intent.confidence_score
-        );
+        // Price the final plan so callers can review it before executing
+        let preview =
+            cost_preview::estimate(&parsed_intent, request, &self.function_registry.functions);
+        if preview.budget_status == Some(BudgetStatus::Exceeds) {
+            warn!(
+                "Estimated cost ({:.2}) exceeds budget limit ({:.2}) for user: {}",
+                preview.cost_range.average_cost,
+                preview.budget_limit.unwrap_or_default(),
+                request.user_id
+            );
+        }
+        parsed_intent.cost_preview = Some(preview);
+
+        if let (Some(cache), Some(key)) = (&self.intent_cache, &cache_key) {
+            if let Err(e) = cache.put(key, &parsed_intent).await {
+                warn!("Failed to cache parsed intent: {}", e);
+            }
+        }
 
-        Ok(parsed_intent)
+        Ok((parsed_intent, context))
     }
 
     pub async fn validate_intent(&self, intent: &ParsedIntent) -> Result<ValidationResult> {
@@ -1,6 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use uuid::Uuid;
 
 // Request types
@@ -14,6 +14,28 @@ pub struct ParseIntentRequest {
     pub budget_limit: Option<f64>,
     pub time_limit: Option<chrono::Duration>,
     pub quality_threshold: Option<f32>,
+    /// Parse and validate without recording user learning or handing the
+    /// intent on for execution.
+    #[serde(default)]
+    pub validate_only: bool,
+    /// Skip the parse cache, e.g. when A/B testing prompt changes. The fresh
+    /// result is neither served from nor written to the cache.
+    #[serde(default)]
+    pub bypass_cache: bool,
+    /// Answers to a previous `needs_clarification` response. The confidence gate
+    /// is lifted once every question the parse raises has a non-empty answer.
+    #[serde(default)]
+    pub clarification: Option<Clarification>,
+}
+
+/// Disambiguation for a request the parser could not confidently interpret
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Clarification {
+    /// The workflow the caller meant, picked from the returned candidates
+    pub workflow_type: Option<WorkflowType>,
+    /// Answers keyed by `ClarifyingQuestion::id`
+    #[serde(default)]
+    pub answers: BTreeMap<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -71,6 +93,43 @@ pub struct ParsedIntent {
     pub scheduling_requirements: Option<SchedulingRequirements>,
     pub provider_preferences: Vec<ProviderPreference>,
     pub metadata: IntentMetadata,
+    /// What the plan is expected to cost before anything executes
+    #[serde(default)]
+    pub cost_preview: Option<CostPreview>,
+}
+
+/// Cost and duration estimate for a parsed plan, priced with the providers the
+/// request prefers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostPreview {
+    pub cost_range: CostRange,
+    /// Sum of the function durations, assuming they run one after another
+    pub estimated_duration: chrono::Duration,
+    pub functions: Vec<FunctionCostEstimate>,
+    pub budget_limit: Option<f64>,
+    /// Set when the request has a `budget_limit`
+    pub budget_status: Option<BudgetStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionCostEstimate {
+    pub function_id: Uuid,
+    pub function_name: String,
+    /// Provider the estimate was priced with
+    pub provider: String,
+    pub cost_range: CostRange,
+    pub estimated_duration: chrono::Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetStatus {
+    /// Even the most expensive outcome fits the budget
+    WithinBudget,
+    /// The expected cost fits but the upper estimate does not
+    MayExceed,
+    /// The expected cost is over budget
+    Exceeds,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -87,6 +146,8 @@ pub struct BatchParseResult {
     pub index: usize,
     pub success: bool,
     pub intent: Option<ParsedIntent>,
+    pub validation: Option<ValidationResponse>,
+    pub clarification: Option<ClarificationResponse>,
     pub error: Option<String>,
 }
 
@@ -98,6 +159,86 @@ pub struct ValidationResponse {
     pub suggestions: Vec<String>,
     pub estimated_execution_time: chrono::Duration,
     pub estimated_cost: f64,
+    pub missing_permissions: Vec<String>,
+    pub invalid_parameters: Vec<String>,
+}
+
+impl From<ValidationResult> for ValidationResponse {
+    fn from(result: ValidationResult) -> Self {
+        Self {
+            valid: result.is_valid,
+            confidence_score: result.confidence_score,
+            warnings: result.warnings,
+            suggestions: result.suggestions,
+            estimated_execution_time: result.estimated_execution_time,
+            estimated_cost: result.estimated_cost,
+            missing_permissions: result.missing_permissions,
+            invalid_parameters: result.invalid_parameters,
+        }
+    }
+}
+
+/// Result of a `validate_only` parse: the intent that would be executed and
+/// its validation report. No workflow is started for it.
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidateOnlyResponse {
+    pub validate_only: bool,
+    pub intent: ParsedIntent,
+    pub validation: ValidationResponse,
+}
+
+/// Returned instead of a parsed intent when confidence is below the threshold,
+/// so a misread request never starts a workflow. Callers re-submit with a
+/// `Clarification` built from the candidates and questions.
+#[derive(Debug, Clone, Serialize)]
+pub struct ClarificationResponse {
+    pub needs_clarification: bool,
+    pub confidence_score: f32,
+    pub confidence_threshold: f32,
+    /// Most likely workflows, best first
+    pub candidates: Vec<WorkflowCandidate>,
+    pub questions: Vec<ClarifyingQuestion>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkflowCandidate {
+    pub workflow_type: WorkflowType,
+    pub score: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ClarifyingQuestion {
+    /// Key for the answer in `Clarification::answers`
+    pub id: String,
+    pub question: String,
+    /// Accepted answers, empty when the answer is free-form
+    pub options: Vec<String>,
+}
+
+/// What `IntentParser::parse_request` produced for a request
+#[derive(Debug, Clone)]
+pub enum ParseOutcome {
+    Parsed(Box<ParsedIntent>),
+    NeedsClarification(ClarificationResponse),
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum ParseIntentResponse {
+    Parsed(ParsedIntent),
+    ValidateOnly(ValidateOnlyResponse),
+    NeedsClarification(ClarificationResponse),
+}
+
+impl From<ParseOutcome> for ParseIntentResponse {
+    fn from(outcome: ParseOutcome) -> Self {
+        match outcome {
+            ParseOutcome::Parsed(intent) => Self::Parsed(*intent),
+            ParseOutcome::NeedsClarification(clarification) => {
+                Self::NeedsClarification(clarification)
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -118,7 +259,7 @@ pub struct FunctionListResponse {
 }
 
 // Core types
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum WorkflowType {
     ContentCreation,
@@ -258,6 +399,12 @@ pub struct IntentMetadata {
     pub domain_scores: HashMap<String, f32>,
     pub user_preferences: Option<UserPreferences>,
     pub context_variables: HashMap<String, serde_json::Value>,
+    /// Served from the parse cache without an LLM call, so no parse cost was incurred
+    #[serde(default)]
+    pub cache_hit: bool,
+    /// When the cached parse was originally produced
+    #[serde(default)]
+    pub cached_at: Option<DateTime<Utc>>,
 }
 
 // User context types
@@ -375,7 +522,7 @@ pub struct FunctionInfo {
     pub supported_parameters: Vec<ParameterInfo>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CostRange {
     pub min_cost: f64,
     pub max_cost: f64,
@@ -96,6 +96,8 @@ impl McpClient {
             retry_backoff_multiplier: 2.0,
             enable_logging: true,
             max_message_size: 1024 * 1024, // 1MB
+            max_stream_chunks: 1000,
+            max_stream_bytes: 16 * 1024 * 1024, // 16MB
         };
 
         let protocol = Arc::new(McpProtocol::new(protocol_config));
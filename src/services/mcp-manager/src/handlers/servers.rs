@@ -5,8 +5,8 @@
 
 use crate::{
     models::{
-        ListServersRequest, ListServersResponse, RegisterServerRequest, RegisterServerResponse,
-        ServerInfo, ServerStatus, UpdateServerRequest,
+        HeartbeatResponse, ListServersRequest, ListServersResponse, RegisterServerRequest,
+        RegisterServerResponse, ServerInfo, ServerStatus, UpdateServerRequest,
     },
     registry::{ServerFilter, ServerSort, SortField, SortOrder},
     server::AppState,
@@ -72,6 +72,7 @@ pub async fn register_server(
     server.metadata = request.metadata.unwrap_or_default();
     server.tags = request.tags.unwrap_or_default();
     server.owner = request.owner;
+    server.heartbeat_ttl_seconds = request.heartbeat_ttl_seconds;
 
     // Register server
     match state.registry().register(server.clone()).await {
@@ -341,6 +342,35 @@ pub async fn update_server_status(
     }
 }
 
+/// Record a server heartbeat
+///
+/// Keeps a server registered with a heartbeat TTL alive. Servers that had been
+/// marked unhealthy for missing their heartbeat are marked running again.
+pub async fn heartbeat(
+    State(state): State<AppState>,
+    Path(server_id): Path<Uuid>,
+) -> Result<Json<HeartbeatResponse>, StatusCode> {
+    let status = match state.registry().heartbeat(&server_id).await {
+        Ok(status) => status,
+        Err(_) => {
+            warn!(server_id = %server_id, "Server not found for heartbeat");
+            return Err(StatusCode::NOT_FOUND);
+        }
+    };
+
+    let next_heartbeat_due = state
+        .registry()
+        .get(&server_id)
+        .await
+        .and_then(|server| server.next_heartbeat_due());
+
+    Ok(Json(HeartbeatResponse {
+        server_id,
+        status,
+        next_heartbeat_due,
+    }))
+}
+
 /// Deregister a server
 ///
 /// Removes a server from the registry and stops all associated services.
@@ -585,6 +615,7 @@ mod tests {
             metadata: None,
             tags: Some(vec!["test".to_string()]),
             owner: Some("test-user".to_string()),
+            heartbeat_ttl_seconds: Some(30),
         }
     }
 
@@ -597,6 +628,11 @@ mod tests {
         let mut invalid_request = request.clone();
         invalid_request.name = "".to_string();
         assert!(invalid_request.validate().is_err());
+
+        // Test with invalid heartbeat TTL
+        let mut invalid_request = request.clone();
+        invalid_request.heartbeat_ttl_seconds = Some(0);
+        assert!(invalid_request.validate().is_err());
     }
 
     #[test]
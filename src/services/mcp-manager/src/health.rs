@@ -378,6 +378,12 @@ impl HealthMonitor {
                 // If we have enough consecutive successes, mark as healthy
                 if state.consecutive_successes >= self.config.success_threshold {
                     state.status = HealthStatus::Healthy;
+
+                    // A successful probe doesn't replace a missed heartbeat;
+                    // only the server's next heartbeat brings it back
+                    if server.heartbeat_expired(health_check.timestamp) {
+                        return Ok(server.status);
+                    }
                     return Ok(ServerStatus::Running);
                 }
             }
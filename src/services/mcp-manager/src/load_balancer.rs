@@ -239,7 +239,7 @@ impl LoadBalancer {
     /// Select a server for handling a request
     pub async fn select_server(&self, context: &RequestContext) -> Result<ServerSelection> {
         // Get available servers
-        let mut servers = if self.config.health_aware {
+        let servers = if self.config.health_aware {
             self.registry.get_available_servers().await
         } else {
             self.registry.list().await
@@ -251,6 +251,50 @@ impl LoadBalancer {
             ));
         }
 
+        self.select_from(servers, context).await
+    }
+
+    /// Select a healthy server advertising `capability` for handling a request.
+    /// Incapable servers are never selected, even when no capable server is
+    /// available.
+    pub async fn select_for_capability(
+        &self,
+        capability: &str,
+        context: &RequestContext,
+    ) -> Result<ServerSelection> {
+        let servers: Vec<ServerInfo> = self
+            .registry
+            .get_healthy_servers()
+            .await
+            .into_iter()
+            .filter(|server| server.capabilities.supports(capability))
+            .collect();
+
+        if servers.is_empty() {
+            return Err(McpError::ServerManagement(format!(
+                "No healthy server advertises capability '{}'",
+                capability
+            )));
+        }
+
+        debug!(
+            capability = %capability,
+            candidates = servers.len(),
+            "Selecting server by capability"
+        );
+
+        let mut selection = self.select_from(servers, context).await?;
+        selection.reason = format!("{} (capability: {})", selection.reason, capability);
+        Ok(selection)
+    }
+
+    /// Apply circuit breakers, capacity limits, sticky sessions and the
+    /// load balancing strategy to a pool of candidate servers
+    async fn select_from(
+        &self,
+        mut servers: Vec<ServerInfo>,
+        context: &RequestContext,
+    ) -> Result<ServerSelection> {
         // Filter out servers with open circuit breakers
         if self.config.circuit_breaker_enabled {
             servers = self.filter_circuit_breaker_servers(servers).await;
@@ -860,7 +904,7 @@ impl Default for CircuitBreakerConfig {
 mod tests {
     use super::*;
     use crate::{
-        models::{ServerCapabilities, ServerConfig},
+        models::{ServerCapabilities, ServerConfig, ServerStatus, ToolInfo},
         registry::{RegistryConfig, ServerRegistry},
     };
 
@@ -978,6 +1022,105 @@ mod tests {
         assert_eq!(selected, Some(servers[1].id)); // Server with fewer connections
     }
 
+    fn tool(name: &str) -> ToolInfo {
+        ToolInfo {
+            name: name.to_string(),
+            description: "Test tool".to_string(),
+            schema: serde_json::json!({}),
+            tags: Vec::new(),
+        }
+    }
+
+    async fn register_server(
+        registry: &ServerRegistry,
+        name: &str,
+        tools: &[&str],
+        status: ServerStatus,
+    ) -> Uuid {
+        let mut server = create_test_server(name, None);
+        server.capabilities.tools = tools.iter().map(|name| tool(name)).collect();
+
+        let server_id = registry.register(server).await.unwrap();
+        registry.update_status(&server_id, status).await.unwrap();
+        server_id
+    }
+
+    fn test_context() -> RequestContext {
+        RequestContext {
+            request_id: "test".to_string(),
+            client_ip: None,
+            session_id: None,
+            priority: 1,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_select_for_capability_without_capable_server() {
+        let registry = Arc::new(ServerRegistry::new(RegistryConfig::default()));
+        register_server(
+            &registry,
+            "text-server",
+            &["generate_text"],
+            ServerStatus::Running,
+        )
+        .await;
+        register_server(
+            &registry,
+            "image-server",
+            &["generate_images"],
+            ServerStatus::Unhealthy,
+        )
+        .await;
+
+        let lb = LoadBalancer::new(registry, LoadBalancerConfig::default());
+        let result = lb
+            .select_for_capability("generate_images", &test_context())
+            .await;
+
+        assert!(matches!(result, Err(McpError::ServerManagement(_))));
+    }
+
+    #[tokio::test]
+    async fn test_select_for_capability_balances_capable_servers() {
+        let registry = Arc::new(ServerRegistry::new(RegistryConfig::default()));
+        let first = register_server(
+            &registry,
+            "image-server-1",
+            &["generate_images"],
+            ServerStatus::Running,
+        )
+        .await;
+        let second = register_server(
+            &registry,
+            "image-server-2",
+            &["generate_text", "generate_images"],
+            ServerStatus::Running,
+        )
+        .await;
+        register_server(
+            &registry,
+            "text-server",
+            &["generate_text"],
+            ServerStatus::Running,
+        )
+        .await;
+
+        let lb = LoadBalancer::new(registry, LoadBalancerConfig::default());
+        let mut selected = Vec::new();
+        for _ in 0..4 {
+            let selection = lb
+                .select_for_capability("generate_images", &test_context())
+                .await
+                .unwrap();
+            selected.push(selection.server.id);
+        }
+
+        assert!(selected.iter().all(|id| *id == first || *id == second));
+        assert!(selected.contains(&first));
+        assert!(selected.contains(&second));
+    }
+
     #[tokio::test]
     async fn test_circuit_breaker() {
         let config = CircuitBreakerConfig::default();
@@ -57,6 +57,14 @@ pub struct ServerInfo {
 
     /// Server owner/creator
     pub owner: Option<String>,
+
+    /// Heartbeat TTL in seconds; servers that miss it are marked unhealthy
+    #[serde(default)]
+    pub heartbeat_ttl_seconds: Option<u64>,
+
+    /// Last heartbeat timestamp
+    #[serde(default)]
+    pub last_heartbeat: Option<DateTime<Utc>>,
 }
 
 /// Server configuration
@@ -184,6 +192,14 @@ pub struct ServerCapabilities {
     pub content_types: Vec<String>,
 }
 
+impl ServerCapabilities {
+    /// Check if a capability is advertised, either as a tool name or a feature
+    pub fn supports(&self, capability: &str) -> bool {
+        self.tools.iter().any(|tool| tool.name == capability)
+            || self.features.iter().any(|feature| feature == capability)
+    }
+}
+
 /// Tool information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolInfo {
@@ -320,7 +336,8 @@ pub struct McpMessage {
     /// Message ID
     pub id: Option<String>,
 
-    /// Message method
+    /// Message method (empty for responses)
+    #[serde(default)]
     pub method: String,
 
     /// Message parameters
@@ -383,6 +400,25 @@ pub struct RegisterServerRequest {
 
     /// Server owner
     pub owner: Option<String>,
+
+    /// Heartbeat TTL in seconds; when set the server must send heartbeats
+    /// within this interval to stay registered
+    #[serde(default)]
+    #[validate(range(min = 1, max = 86400))]
+    pub heartbeat_ttl_seconds: Option<u64>,
+}
+
+/// Server heartbeat response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeartbeatResponse {
+    /// Server ID
+    pub server_id: Uuid,
+
+    /// Server status after the heartbeat
+    pub status: ServerStatus,
+
+    /// When the next heartbeat is due, if the server has a heartbeat TTL
+    pub next_heartbeat_due: Option<DateTime<Utc>>,
 }
 
 /// Server registration response
@@ -715,6 +751,8 @@ impl ServerInfo {
             last_health_check: None,
             tags: Vec::new(),
             owner: None,
+            heartbeat_ttl_seconds: None,
+            last_heartbeat: None,
         }
     }
 
@@ -744,6 +782,24 @@ impl ServerInfo {
         self.last_health_check = Some(Utc::now());
         self.updated_at = Utc::now();
     }
+
+    /// Record a heartbeat from the server
+    pub fn record_heartbeat(&mut self) {
+        self.last_heartbeat = Some(Utc::now());
+        self.updated_at = Utc::now();
+    }
+
+    /// When the next heartbeat is due, if the server has a heartbeat TTL.
+    /// Registration counts as the first heartbeat.
+    pub fn next_heartbeat_due(&self) -> Option<DateTime<Utc>> {
+        let ttl = chrono::Duration::seconds(self.heartbeat_ttl_seconds? as i64);
+        Some(self.last_heartbeat.unwrap_or(self.created_at) + ttl)
+    }
+
+    /// Check if the server has missed its heartbeat TTL
+    pub fn heartbeat_expired(&self, now: DateTime<Utc>) -> bool {
+        self.next_heartbeat_due().is_some_and(|due| now > due)
+    }
 }
 
 impl HealthCheck {
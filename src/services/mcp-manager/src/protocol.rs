@@ -3,12 +3,17 @@
 //! This module handles Model Context Protocol (MCP) communication, including
 //! message serialization/deserialization, protocol validation, and client/server
 //! communication patterns.
+//!
+//! Incoming messages are size-checked before they are parsed and structurally
+//! validated before they are deserialized, so malformed or oversized payloads
+//! are rejected with `McpError::Protocol` instead of being buffered or panicking.
 
 use crate::{
-    models::{McpError, ServerInfo},
+    models::{McpError, McpMessage, ServerInfo},
     McpError as ServiceError, Result,
 };
 use chrono::{DateTime, Utc};
+use dashmap::DashMap;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -35,6 +40,9 @@ pub struct McpProtocol {
 
     /// Message ID counter
     message_id_counter: std::sync::Arc<std::sync::atomic::AtomicU64>,
+
+    /// Requests awaiting a response, by request ID, with their method
+    outstanding_requests: std::sync::Arc<DashMap<String, String>>,
 }
 
 /// Protocol configuration
@@ -54,6 +62,38 @@ pub struct ProtocolConfig {
 
     /// Maximum message size in bytes
     pub max_message_size: usize,
+
+    /// Maximum number of chunks in a streamed response
+    pub max_stream_chunks: usize,
+
+    /// Maximum total size of a streamed response in bytes
+    pub max_stream_bytes: usize,
+}
+
+/// Type of a validated MCP message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageKind {
+    /// Request expecting a response
+    Request,
+
+    /// Request without an ID; no response expected
+    Notification,
+
+    /// Result or error answering a request
+    Response,
+}
+
+/// Chunks of a streamed response to one request. Each chunk must be a
+/// response carrying the request's ID, and the chunk count and total size are
+/// capped by the protocol configuration.
+#[derive(Debug)]
+pub struct ResponseStream {
+    request_id: String,
+    max_message_size: usize,
+    max_chunks: usize,
+    max_total_bytes: usize,
+    total_bytes: usize,
+    chunks: Vec<McpResponse>,
 }
 
 /// MCP request types
@@ -155,6 +195,7 @@ impl McpProtocol {
             client,
             config,
             message_id_counter: std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            outstanding_requests: std::sync::Arc::new(DashMap::new()),
         }
     }
 
@@ -405,45 +446,67 @@ impl McpProtocol {
             .ok_or_else(|| ServiceError::Protocol("Prompt get response missing result".to_string()))
     }
 
-    /// Validate an MCP message
-    pub fn validate_message(&self, message: &Value) -> Result<()> {
-        // Check if it's a valid JSON-RPC message
-        if !message.is_object() {
-            return Err(ServiceError::Protocol(
-                "Message must be an object".to_string(),
-            ));
-        }
+    /// Validate an MCP message, returning its type
+    pub fn validate_message(&self, message: &Value) -> Result<MessageKind> {
+        let message_size = serde_json::to_vec(message)
+            .map_err(ServiceError::Serialization)?
+            .len();
+        check_message_size(message_size, self.config.max_message_size)?;
 
-        let obj = message.as_object().unwrap();
+        validate_structure(message)
+    }
 
-        // Check JSON-RPC version
-        if let Some(jsonrpc) = obj.get("jsonrpc") {
-            if jsonrpc.as_str() != Some(JSONRPC_VERSION) {
-                return Err(ServiceError::Protocol(
-                    "Invalid JSON-RPC version".to_string(),
-                ));
-            }
-        } else {
-            return Err(ServiceError::Protocol(
-                "Missing JSON-RPC version".to_string(),
-            ));
-        }
+    /// Parse and validate a raw MCP message. The size limit is checked before
+    /// parsing, so oversized payloads are never expanded into a JSON tree.
+    pub fn parse_message(&self, raw: &[u8]) -> Result<McpMessage> {
+        let (_, message) = parse_message_bytes(raw, self.config.max_message_size)?;
 
-        // Check message size
-        let message_size = serde_json::to_string(message)
-            .map_err(|e| ServiceError::Serialization(e))?
-            .len();
+        serde_json::from_value(message)
+            .map_err(|e| ServiceError::Protocol(format!("Malformed MCP message: {}", e)))
+    }
 
-        if message_size > self.config.max_message_size {
+    /// Track a request as awaiting a response
+    pub fn track_request(&self, request: &McpRequest) {
+        self.outstanding_requests
+            .insert(request.id.clone(), request.method.clone());
+    }
+
+    /// Stop tracking a request, returning whether it was outstanding
+    pub fn complete_request(&self, request_id: &str) -> bool {
+        self.outstanding_requests.remove(request_id).is_some()
+    }
+
+    /// Check that a response answers an outstanding request
+    pub fn correlate_response(&self, response: &McpResponse) -> Result<()> {
+        if !self.outstanding_requests.contains_key(&response.id) {
             return Err(ServiceError::Protocol(format!(
-                "Message size {} exceeds maximum {}",
-                message_size, self.config.max_message_size
+                "Response ID {} does not match any outstanding request",
+                response.id
             )));
         }
 
         Ok(())
     }
 
+    /// Start collecting a streamed response to an outstanding request
+    pub fn response_stream(&self, request_id: &str) -> Result<ResponseStream> {
+        if !self.outstanding_requests.contains_key(request_id) {
+            return Err(ServiceError::Protocol(format!(
+                "Cannot stream a response to unknown request {}",
+                request_id
+            )));
+        }
+
+        Ok(ResponseStream {
+            request_id: request_id.to_string(),
+            max_message_size: self.config.max_message_size,
+            max_chunks: self.config.max_stream_chunks,
+            max_total_bytes: self.config.max_stream_bytes,
+            total_bytes: 0,
+            chunks: Vec::new(),
+        })
+    }
+
     // Private helper methods
 
     fn create_request(&self, method: &str, params: Option<Value>) -> McpRequest {
@@ -502,6 +565,17 @@ impl McpProtocol {
         &self,
         server: &ServerInfo,
         request: &McpRequest,
+    ) -> Result<CommunicationResult> {
+        self.track_request(request);
+        let result = self.exchange(server, request).await;
+        self.complete_request(&request.id);
+        result
+    }
+
+    async fn exchange(
+        &self,
+        server: &ServerInfo,
+        request: &McpRequest,
     ) -> Result<CommunicationResult> {
         let start_time = std::time::Instant::now();
         let url = &server.config.endpoint;
@@ -522,7 +596,7 @@ impl McpProtocol {
         self.validate_message(&request_value)?;
 
         // Send request
-        let response = timeout(
+        let mut response = timeout(
             Duration::from_secs(self.config.timeout_seconds),
             self.client.post(url).json(request).send(),
         )
@@ -539,17 +613,36 @@ impl McpProtocol {
             )));
         }
 
-        // Parse response
-        let response_text = response.text().await.map_err(|e| ServiceError::Http(e))?;
+        // Read the body, giving up as soon as it exceeds the size limit
+        if let Some(length) = response.content_length() {
+            check_message_size(length as usize, self.config.max_message_size)?;
+        }
 
-        let response_value: Value =
-            serde_json::from_str(&response_text).map_err(|e| ServiceError::Serialization(e))?;
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(ServiceError::Http)? {
+            check_message_size(body.len() + chunk.len(), self.config.max_message_size)?;
+            body.extend_from_slice(&chunk);
+        }
 
-        // Validate response
-        self.validate_message(&response_value)?;
+        // Parse and validate response
+        let (kind, response_value) = parse_message_bytes(&body, self.config.max_message_size)?;
+        if kind != MessageKind::Response {
+            return Err(ServiceError::Protocol(format!(
+                "Expected a response to request {}, got a {:?}",
+                request.id, kind
+            )));
+        }
+
+        let mcp_response: McpResponse = serde_json::from_value(response_value)
+            .map_err(|e| ServiceError::Protocol(format!("Malformed MCP response: {}", e)))?;
 
-        let mcp_response: McpResponse =
-            serde_json::from_value(response_value).map_err(|e| ServiceError::Serialization(e))?;
+        self.correlate_response(&mcp_response)?;
+        if mcp_response.id != request.id {
+            return Err(ServiceError::Protocol(format!(
+                "Response ID {} does not match request ID {}",
+                mcp_response.id, request.id
+            )));
+        }
 
         // Check for protocol errors
         if let Some(error) = &mcp_response.error {
@@ -584,10 +677,155 @@ impl Default for ProtocolConfig {
             retry_backoff_multiplier: 2.0,
             enable_logging: true,
             max_message_size: 1024 * 1024, // 1MB
+            max_stream_chunks: 1000,
+            max_stream_bytes: 16 * 1024 * 1024, // 16MB
         }
     }
 }
 
+impl ResponseStream {
+    /// Add a raw chunk to the stream, rejecting it if it is malformed, answers
+    /// a different request or would exceed the stream limits
+    pub fn push(&mut self, raw: &[u8]) -> Result<()> {
+        if self.chunks.len() >= self.max_chunks {
+            return Err(ServiceError::Protocol(format!(
+                "Streamed response to {} exceeds maximum of {} chunks",
+                self.request_id, self.max_chunks
+            )));
+        }
+
+        if self.total_bytes + raw.len() > self.max_total_bytes {
+            return Err(ServiceError::Protocol(format!(
+                "Streamed response to {} exceeds maximum size {}",
+                self.request_id, self.max_total_bytes
+            )));
+        }
+
+        let (kind, value) = parse_message_bytes(raw, self.max_message_size)?;
+        if kind != MessageKind::Response {
+            return Err(ServiceError::Protocol(format!(
+                "Stream chunk for request {} is a {:?}, not a response",
+                self.request_id, kind
+            )));
+        }
+
+        let chunk: McpResponse = serde_json::from_value(value)
+            .map_err(|e| ServiceError::Protocol(format!("Malformed MCP response: {}", e)))?;
+        if chunk.id != self.request_id {
+            return Err(ServiceError::Protocol(format!(
+                "Stream chunk ID {} does not match request ID {}",
+                chunk.id, self.request_id
+            )));
+        }
+
+        self.total_bytes += raw.len();
+        self.chunks.push(chunk);
+        Ok(())
+    }
+
+    /// Chunks received so far
+    pub fn chunks(&self) -> &[McpResponse] {
+        &self.chunks
+    }
+
+    /// Total size of the chunks received so far in bytes
+    pub fn total_bytes(&self) -> usize {
+        self.total_bytes
+    }
+
+    /// Finish the stream, returning its chunks
+    pub fn into_chunks(self) -> Vec<McpResponse> {
+        self.chunks
+    }
+}
+
+fn check_message_size(size: usize, max_size: usize) -> Result<()> {
+    if size > max_size {
+        return Err(ServiceError::Protocol(format!(
+            "Message size {} exceeds maximum {}",
+            size, max_size
+        )));
+    }
+
+    Ok(())
+}
+
+fn parse_message_bytes(raw: &[u8], max_size: usize) -> Result<(MessageKind, Value)> {
+    check_message_size(raw.len(), max_size)?;
+
+    let message: Value = serde_json::from_slice(raw)
+        .map_err(|e| ServiceError::Protocol(format!("Malformed MCP message: {}", e)))?;
+    let kind = validate_structure(&message)?;
+
+    Ok((kind, message))
+}
+
+/// Check a message is a well-formed JSON-RPC 2.0 request, notification or
+/// response. Null fields count as absent, as serialized `Option`s are null.
+fn validate_structure(message: &Value) -> Result<MessageKind> {
+    let protocol_error = |message: &str| Err(ServiceError::Protocol(message.to_string()));
+
+    let Some(obj) = message.as_object() else {
+        return protocol_error("Message must be an object");
+    };
+
+    match obj.get("jsonrpc") {
+        Some(jsonrpc) if jsonrpc.as_str() == Some(JSONRPC_VERSION) => {}
+        Some(_) => return protocol_error("Invalid JSON-RPC version"),
+        None => return protocol_error("Missing JSON-RPC version"),
+    }
+
+    let field = |name: &str| obj.get(name).filter(|value| !value.is_null());
+    let method = field("method").filter(|method| method.as_str() != Some(""));
+    let result = field("result");
+    let error = field("error");
+
+    let has_id = match field("id") {
+        None => false,
+        Some(Value::String(id)) if !id.is_empty() => true,
+        Some(_) => return protocol_error("Message ID must be a non-empty string"),
+    };
+
+    match (method, result.is_some() || error.is_some()) {
+        (Some(method), false) => {
+            if !method.is_string() {
+                return protocol_error("Method must be a string");
+            }
+            if field("params").is_some_and(|params| !params.is_object() && !params.is_array()) {
+                return protocol_error("Params must be an object or an array");
+            }
+
+            Ok(if has_id {
+                MessageKind::Request
+            } else {
+                MessageKind::Notification
+            })
+        }
+        (None, true) => {
+            if !has_id {
+                return protocol_error("Response is missing its ID");
+            }
+            if result.is_some() && error.is_some() {
+                return protocol_error("Response cannot carry both a result and an error");
+            }
+            if let Some(error) = error {
+                let valid_code = error
+                    .get("code")
+                    .and_then(Value::as_i64)
+                    .is_some_and(|code| i32::try_from(code).is_ok());
+                let valid_message = error.get("message").is_some_and(Value::is_string);
+                if !valid_code || !valid_message {
+                    return protocol_error("Error must have an integer code and a string message");
+                }
+            }
+
+            Ok(MessageKind::Response)
+        }
+        (Some(_), true) => protocol_error("Message cannot be both a request and a response"),
+        (None, false) => protocol_error("Unknown message type: expected a method, result or error"),
+    }
+}
+
 impl McpRequest {
     /// Create a new MCP request
     pub fn new(method: &str, params: Option<Value>) -> Self {
@@ -721,6 +959,195 @@ mod tests {
         assert!(protocol.validate_message(&invalid_message).is_err());
     }
 
+    #[test]
+    fn test_message_kinds() {
+        let protocol = McpProtocol::new(ProtocolConfig::default());
+
+        let request = serde_json::to_value(McpRequest::new(methods::PING, None)).unwrap();
+        assert_eq!(
+            protocol.validate_message(&request).unwrap(),
+            MessageKind::Request
+        );
+
+        let notification =
+            serde_json::to_value(McpNotification::new(methods::INITIALIZED, None)).unwrap();
+        assert_eq!(
+            protocol.validate_message(&notification).unwrap(),
+            MessageKind::Notification
+        );
+
+        let response = serde_json::to_value(McpResponse::error(
+            "test-id".to_string(),
+            McpError {
+                code: error_codes::METHOD_NOT_FOUND,
+                message: "Unknown method".to_string(),
+                data: None,
+            },
+        ))
+        .unwrap();
+        assert_eq!(
+            protocol.validate_message(&response).unwrap(),
+            MessageKind::Response
+        );
+
+        let message = protocol
+            .parse_message(br#"{"jsonrpc":"2.0","id":"test-id","result":{"ok":true}}"#)
+            .unwrap();
+        assert!(message.is_response());
+    }
+
+    #[test]
+    fn test_malformed_messages_are_rejected() {
+        let protocol = McpProtocol::new(ProtocolConfig::default());
+
+        let malformed = [
+            serde_json::json!({ "jsonrpc": "2.0", "id": "test" }),
+            serde_json::json!({ "jsonrpc": "1.0", "id": "test", "method": "ping" }),
+            serde_json::json!({ "jsonrpc": "2.0", "id": 7, "method": "ping" }),
+            serde_json::json!({ "jsonrpc": "2.0", "id": "test", "method": 42 }),
+            serde_json::json!({ "jsonrpc": "2.0", "id": "test", "method": "ping", "params": 1 }),
+            serde_json::json!({ "jsonrpc": "2.0", "result": {} }),
+            serde_json::json!({ "jsonrpc": "2.0", "id": "test", "method": "ping", "result": {} }),
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": "test",
+                "result": {},
+                "error": { "code": -32603, "message": "boom" },
+            }),
+            serde_json::json!({ "jsonrpc": "2.0", "id": "test", "error": { "message": "boom" } }),
+            serde_json::json!(["jsonrpc", "2.0"]),
+        ];
+
+        for message in malformed {
+            assert!(
+                matches!(
+                    protocol.validate_message(&message),
+                    Err(ServiceError::Protocol(_))
+                ),
+                "accepted {}",
+                message
+            );
+        }
+    }
+
+    #[test]
+    fn test_truncated_payloads_are_rejected() {
+        let protocol = McpProtocol::new(ProtocolConfig::default());
+        let payloads = [
+            serde_json::to_vec(&McpRequest::new(
+                methods::CALL_TOOL,
+                Some(serde_json::json!({ "name": "generate_images", "arguments": { "n": 2 } })),
+            ))
+            .unwrap(),
+            serde_json::to_vec(&McpResponse::success(
+                "test-id".to_string(),
+                serde_json::json!({ "content": [{ "type": "text", "text": "done" }] }),
+            ))
+            .unwrap(),
+        ];
+
+        for payload in payloads {
+            assert!(protocol.parse_message(&payload).is_ok());
+            for length in 0..payload.len() {
+                assert!(
+                    protocol.parse_message(&payload[..length]).is_err(),
+                    "accepted truncated payload {:?}",
+                    String::from_utf8_lossy(&payload[..length])
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_oversized_and_garbage_payloads_are_rejected() {
+        let protocol = McpProtocol::new(ProtocolConfig {
+            max_message_size: 256,
+            ..ProtocolConfig::default()
+        });
+
+        let oversized = serde_json::to_vec(&McpRequest::new(
+            methods::CALL_TOOL,
+            Some(serde_json::json!({ "prompt": "x".repeat(1024) })),
+        ))
+        .unwrap();
+        let error = protocol.parse_message(&oversized).unwrap_err();
+        assert!(error.to_string().contains("exceeds maximum"));
+
+        let nested = "[".repeat(200);
+        assert!(protocol.parse_message(nested.as_bytes()).is_err());
+
+        // Pseudo-random bytes must be rejected without panicking
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        for length in [1, 7, 64, 255, 256, 257, 4096] {
+            let garbage: Vec<u8> = (0..length)
+                .map(|_| {
+                    seed ^= seed << 13;
+                    seed ^= seed >> 7;
+                    seed ^= seed << 17;
+                    seed as u8
+                })
+                .collect();
+            assert!(protocol.parse_message(&garbage).is_err());
+        }
+    }
+
+    #[test]
+    fn test_response_correlation() {
+        let protocol = McpProtocol::new(ProtocolConfig::default());
+        let request = protocol.create_request(methods::PING, None);
+        protocol.track_request(&request);
+
+        let response = McpResponse::success(request.id.clone(), serde_json::json!({}));
+        assert!(protocol.correlate_response(&response).is_ok());
+
+        let stray = McpResponse::success("unknown-id".to_string(), serde_json::json!({}));
+        assert!(protocol.correlate_response(&stray).is_err());
+
+        assert!(protocol.complete_request(&request.id));
+        assert!(protocol.correlate_response(&response).is_err());
+    }
+
+    #[test]
+    fn test_response_stream_limits() {
+        let protocol = McpProtocol::new(ProtocolConfig {
+            max_stream_chunks: 3,
+            max_stream_bytes: 1024,
+            ..ProtocolConfig::default()
+        });
+        let request = protocol.create_request(methods::CALL_TOOL, None);
+        assert!(protocol.response_stream(&request.id).is_err());
+        protocol.track_request(&request);
+
+        let chunk = |id: &str, text: &str| {
+            serde_json::to_vec(&McpResponse::success(
+                id.to_string(),
+                serde_json::json!({ "text": text }),
+            ))
+            .unwrap()
+        };
+
+        // Chunk count limit
+        let mut stream = protocol.response_stream(&request.id).unwrap();
+        for _ in 0..3 {
+            stream.push(&chunk(&request.id, "part")).unwrap();
+        }
+        assert!(stream.push(&chunk(&request.id, "part")).is_err());
+        assert_eq!(stream.chunks().len(), 3);
+
+        // Total size limit
+        let mut stream = protocol.response_stream(&request.id).unwrap();
+        stream.push(&chunk(&request.id, &"x".repeat(600))).unwrap();
+        assert!(stream.push(&chunk(&request.id, &"x".repeat(600))).is_err());
+        assert_eq!(stream.chunks().len(), 1);
+
+        // Chunks must answer the streamed request and be complete
+        let mut stream = protocol.response_stream(&request.id).unwrap();
+        assert!(stream.push(&chunk("other-id", "part")).is_err());
+        let valid = chunk(&request.id, "part");
+        assert!(stream.push(&valid[..valid.len() - 1]).is_err());
+        assert!(stream.into_chunks().is_empty());
+    }
+
     #[test]
     fn test_response_creation() {
         let response =
@@ -2,16 +2,24 @@
 //!
 //! This module provides the core registry functionality for managing MCP server instances.
 //! It handles server registration, deregistration, lookup, and lifecycle management.
+//!
+//! Servers registered with a heartbeat TTL must send periodic heartbeats. A
+//! background reaper marks servers that miss their TTL as unhealthy and removes
+//! them once they stay silent for several TTLs. This works independently of the
+//! active probes run by the health monitor.
 
 use crate::{
     models::{ServerInfo, ServerStatus},
     McpError, Result,
 };
+use chrono::{DateTime, Utc};
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
@@ -24,6 +32,9 @@ pub struct ServerRegistry {
     /// Server indices for efficient lookups
     indices: Arc<RwLock<RegistryIndices>>,
 
+    /// Servers marked unhealthy because their heartbeat expired, with the expiry time
+    expired_heartbeats: DashMap<Uuid, DateTime<Utc>>,
+
     /// Registry configuration
     config: RegistryConfig,
 }
@@ -61,6 +72,29 @@ pub struct RegistryConfig {
 
     /// Maximum time before considering a server stale (in seconds)
     pub stale_timeout_seconds: u64,
+
+    /// Interval between heartbeat expiry checks in seconds
+    pub heartbeat_check_interval_seconds: u64,
+
+    /// Number of heartbeat TTLs a server may stay silent before it is removed
+    pub heartbeat_removal_multiplier: u32,
+}
+
+/// Servers affected by a heartbeat expiry check
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeartbeatExpiry {
+    /// Servers marked unhealthy for missing their heartbeat
+    pub expired: Vec<Uuid>,
+
+    /// Servers removed after missing heartbeats for too long
+    pub removed: Vec<Uuid>,
+}
+
+impl HeartbeatExpiry {
+    /// Check if no server was affected
+    pub fn is_empty(&self) -> bool {
+        self.expired.is_empty() && self.removed.is_empty()
+    }
 }
 
 /// Server filter criteria
@@ -116,6 +150,7 @@ impl ServerRegistry {
         Self {
             servers: DashMap::new(),
             indices: Arc::new(RwLock::new(RegistryIndices::default())),
+            expired_heartbeats: DashMap::new(),
             config,
         }
     }
@@ -159,6 +194,8 @@ impl ServerRegistry {
     /// Deregister a server
     pub async fn deregister(&self, server_id: &Uuid) -> Result<()> {
         if let Some((_, server)) = self.servers.remove(server_id) {
+            self.expired_heartbeats.remove(server_id);
+
             // Update indices
             self.update_indices_on_remove(&server).await;
 
@@ -274,6 +311,71 @@ impl ServerRegistry {
         }
     }
 
+    /// Record a heartbeat from a server, returning its status afterwards.
+    /// Starting servers and servers whose heartbeat had expired are marked
+    /// running again.
+    pub async fn heartbeat(&self, server_id: &Uuid) -> Result<ServerStatus> {
+        let (old_status, new_status) = {
+            let mut entry = self.servers.get_mut(server_id).ok_or_else(|| {
+                McpError::ServerManagement(format!("Server with ID {} not found", server_id))
+            })?;
+
+            entry.record_heartbeat();
+
+            let old_status = entry.status;
+            let revived = self.expired_heartbeats.remove(server_id).is_some()
+                && old_status == ServerStatus::Unhealthy;
+            if revived || matches!(old_status, ServerStatus::Starting | ServerStatus::Unknown) {
+                entry.update_status(ServerStatus::Running);
+            }
+
+            (old_status, entry.status)
+        };
+
+        if old_status != new_status {
+            self.update_status_index(server_id, old_status, new_status)
+                .await;
+
+            info!(
+                server_id = %server_id,
+                old_status = ?old_status,
+                new_status = ?new_status,
+                "Server status changed on heartbeat"
+            );
+        }
+
+        debug!(server_id = %server_id, "Server heartbeat received");
+
+        Ok(new_status)
+    }
+
+    /// Mark servers that missed their heartbeat TTL as unhealthy and remove
+    /// servers that stayed silent for `heartbeat_removal_multiplier` TTLs
+    pub async fn expire_heartbeats(&self) -> HeartbeatExpiry {
+        self.expire_heartbeats_at(Utc::now()).await
+    }
+
+    /// Spawn the background task that periodically expires missed heartbeats
+    pub fn start_heartbeat_reaper(self: Arc<Self>) -> JoinHandle<()> {
+        let period = Duration::from_secs(self.config.heartbeat_check_interval_seconds.max(1));
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+
+                let expiry = self.expire_heartbeats().await;
+                if !expiry.is_empty() {
+                    info!(
+                        expired = expiry.expired.len(),
+                        removed = expiry.removed.len(),
+                        "Expired servers with missed heartbeats"
+                    );
+                }
+            }
+        })
+    }
+
     /// List all servers
     pub async fn list(&self) -> Vec<ServerInfo> {
         self.servers.iter().map(|entry| entry.clone()).collect()
@@ -453,6 +555,79 @@ impl ServerRegistry {
 
     // Private helper methods
 
+    async fn expire_heartbeats_at(&self, now: DateTime<Utc>) -> HeartbeatExpiry {
+        let mut expiry = HeartbeatExpiry::default();
+
+        let overdue: Vec<(Uuid, ServerStatus, DateTime<Utc>, u64)> = self
+            .servers
+            .iter()
+            .filter(|entry| entry.heartbeat_expired(now))
+            .filter(|entry| !matches!(entry.status, ServerStatus::Stopping | ServerStatus::Stopped))
+            .filter_map(|entry| {
+                Some((
+                    entry.id,
+                    entry.status,
+                    entry.last_heartbeat.unwrap_or(entry.created_at),
+                    entry.heartbeat_ttl_seconds?,
+                ))
+            })
+            .collect();
+
+        for (server_id, status, last_heartbeat, ttl_seconds) in overdue {
+            let silence_limit = chrono::Duration::seconds(
+                ttl_seconds as i64 * self.config.heartbeat_removal_multiplier.max(1) as i64,
+            );
+
+            if now - last_heartbeat > silence_limit {
+                match self.deregister(&server_id).await {
+                    Ok(()) => {
+                        warn!(
+                            server_id = %server_id,
+                            last_heartbeat = %last_heartbeat,
+                            "Removed server after missed heartbeats"
+                        );
+                        expiry.removed.push(server_id);
+                    }
+                    Err(e) => {
+                        warn!(
+                            server_id = %server_id,
+                            error = %e,
+                            "Failed to remove server with missed heartbeats"
+                        );
+                    }
+                }
+                continue;
+            }
+
+            if self.expired_heartbeats.insert(server_id, now).is_some() {
+                continue;
+            }
+
+            if status != ServerStatus::Unhealthy {
+                if let Err(e) = self
+                    .update_status(&server_id, ServerStatus::Unhealthy)
+                    .await
+                {
+                    warn!(
+                        server_id = %server_id,
+                        error = %e,
+                        "Failed to mark server with missed heartbeat unhealthy"
+                    );
+                    continue;
+                }
+            }
+
+            warn!(
+                server_id = %server_id,
+                last_heartbeat = %last_heartbeat,
+                "Server missed its heartbeat"
+            );
+            expiry.expired.push(server_id);
+        }
+
+        expiry
+    }
+
     async fn update_indices_on_insert(&self, server: &ServerInfo) {
         let mut indices = self.indices.write().await;
 
@@ -752,6 +927,8 @@ impl Default for RegistryConfig {
             auto_cleanup: true,
             cleanup_interval_seconds: 300, // 5 minutes
             stale_timeout_seconds: 3600,   // 1 hour
+            heartbeat_check_interval_seconds: 10,
+            heartbeat_removal_multiplier: 3,
         }
     }
 }
@@ -891,6 +1068,75 @@ mod tests {
         assert_eq!(results[0].name, "server1");
     }
 
+    #[tokio::test]
+    async fn test_missed_heartbeat_expires_server() {
+        let registry = ServerRegistry::new(RegistryConfig::default());
+        let mut server = create_test_server("heartbeat-server", "test");
+        server.heartbeat_ttl_seconds = Some(30);
+
+        let server_id = registry.register(server).await.unwrap();
+        assert_eq!(
+            registry.heartbeat(&server_id).await.unwrap(),
+            ServerStatus::Running
+        );
+
+        // Within the TTL nothing happens
+        let now = Utc::now();
+        assert!(registry.expire_heartbeats_at(now).await.is_empty());
+
+        // A missed heartbeat marks the server unhealthy, once
+        let missed = now + chrono::Duration::seconds(45);
+        let expiry = registry.expire_heartbeats_at(missed).await;
+        assert_eq!(expiry.expired, vec![server_id]);
+        assert!(expiry.removed.is_empty());
+        assert_eq!(
+            registry.get(&server_id).await.unwrap().status,
+            ServerStatus::Unhealthy
+        );
+        assert_eq!(registry.count_by_status(ServerStatus::Unhealthy).await, 1);
+        assert!(registry.expire_heartbeats_at(missed).await.is_empty());
+
+        // Staying silent for three TTLs removes it
+        let silent = now + chrono::Duration::seconds(120);
+        let expiry = registry.expire_heartbeats_at(silent).await;
+        assert_eq!(expiry.removed, vec![server_id]);
+        assert!(!registry.exists(&server_id).await);
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_revives_expired_server() {
+        let registry = ServerRegistry::new(RegistryConfig::default());
+        let mut server = create_test_server("heartbeat-server", "test");
+        server.heartbeat_ttl_seconds = Some(30);
+        let server_id = registry.register(server).await.unwrap();
+
+        let missed = Utc::now() + chrono::Duration::seconds(45);
+        registry.expire_heartbeats_at(missed).await;
+        assert_eq!(
+            registry.get(&server_id).await.unwrap().status,
+            ServerStatus::Unhealthy
+        );
+
+        assert_eq!(
+            registry.heartbeat(&server_id).await.unwrap(),
+            ServerStatus::Running
+        );
+        assert_eq!(registry.count_by_status(ServerStatus::Running).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_servers_without_ttl_never_expire() {
+        let registry = ServerRegistry::new(RegistryConfig::default());
+        let server_id = registry
+            .register(create_test_server("no-heartbeat", "test"))
+            .await
+            .unwrap();
+
+        let later = Utc::now() + chrono::Duration::days(30);
+        assert!(registry.expire_heartbeats_at(later).await.is_empty());
+        assert!(registry.exists(&server_id).await);
+    }
+
     #[tokio::test]
     async fn test_server_statistics() {
         let registry = ServerRegistry::new(RegistryConfig::default());
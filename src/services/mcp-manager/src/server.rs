@@ -81,6 +81,8 @@ impl McpManagerServer {
             auto_cleanup: true,
             cleanup_interval_seconds: 300,
             stale_timeout_seconds: 3600,
+            heartbeat_check_interval_seconds: 10,
+            heartbeat_removal_multiplier: 3,
         };
         let registry = Arc::new(ServerRegistry::new(registry_config));
 
@@ -238,6 +240,7 @@ impl McpManagerServer {
                 "/servers/:id/status",
                 put(handlers::servers::update_server_status),
             )
+            .route("/servers/:id/heartbeat", post(handlers::servers::heartbeat))
             .route(
                 "/servers/:id/health",
                 get(handlers::health::get_server_health),
@@ -316,6 +319,9 @@ impl McpManagerServer {
             });
         }
 
+        // Heartbeat expiry runs even when active health probing is disabled
+        Arc::clone(&self.registry).start_heartbeat_reaper();
+
         // Start registry cleanup task
         let registry = Arc::clone(&self.registry);
         let cleanup_interval = Duration::from_secs(300); // 5 minutes
@@ -0,0 +1,283 @@
+//! User-supplied workflow graphs
+//!
+//! The `custom_dag` workflow type takes its steps from the request instead of a
+//! built-in template. Steps refer to each other by name; the graph is checked
+//! for duplicate names, missing dependencies, unregistered MCP services and
+//! cycles before any step runs.
+
+use crate::{McpRegistry, WorkflowStep};
+use serde::Deserialize;
+use std::collections::HashMap;
+use thiserror::Error;
+use uuid::Uuid;
+
+/// Workflow type whose steps are supplied by the request
+pub const CUSTOM_DAG_WORKFLOW: &str = "custom_dag";
+
+/// One step of a user-supplied workflow graph
+#[derive(Debug, Clone, Deserialize)]
+pub struct StepDefinition {
+    pub name: String,
+    pub mcp_service: String,
+    pub endpoint: String,
+    #[serde(default)]
+    pub parameters: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub depends_on: Vec<String>, // step names
+    pub timeout_seconds: Option<u64>,
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum DagError {
+    #[error("A custom_dag workflow needs at least one step")]
+    Empty,
+
+    #[error("Step name '{0}' is used more than once")]
+    DuplicateStep(String),
+
+    #[error("Step '{step}' depends on unknown step '{dependency}'")]
+    UnknownDependency { step: String, dependency: String },
+
+    #[error("Step '{step}' uses unregistered MCP service '{service}'")]
+    UnknownService { step: String, service: String },
+
+    #[error("Workflow graph has a cycle: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
+}
+
+/// Validate a user-supplied graph and turn it into workflow steps
+pub fn build_steps(
+    definitions: &[StepDefinition],
+    registry: &McpRegistry,
+) -> Result<Vec<WorkflowStep>, DagError> {
+    if definitions.is_empty() {
+        return Err(DagError::Empty);
+    }
+
+    let mut ids = HashMap::with_capacity(definitions.len());
+    for definition in definitions {
+        if ids
+            .insert(definition.name.as_str(), Uuid::new_v4())
+            .is_some()
+        {
+            return Err(DagError::DuplicateStep(definition.name.clone()));
+        }
+    }
+
+    for definition in definitions {
+        if let Some(dependency) = definition
+            .depends_on
+            .iter()
+            .find(|dependency| !ids.contains_key(dependency.as_str()))
+        {
+            return Err(DagError::UnknownDependency {
+                step: definition.name.clone(),
+                dependency: dependency.clone(),
+            });
+        }
+
+        if !registry.services.contains_key(&definition.mcp_service) {
+            return Err(DagError::UnknownService {
+                step: definition.name.clone(),
+                service: definition.mcp_service.clone(),
+            });
+        }
+    }
+
+    if let Some(cycle) = find_cycle(definitions) {
+        return Err(DagError::Cycle(cycle));
+    }
+
+    Ok(definitions
+        .iter()
+        .map(|definition| WorkflowStep {
+            step_id: ids[definition.name.as_str()],
+            step_name: definition.name.clone(),
+            mcp_service: definition.mcp_service.clone(),
+            endpoint: definition.endpoint.clone(),
+            parameters: definition.parameters.clone(),
+            depends_on: definition
+                .depends_on
+                .iter()
+                .map(|dependency| ids[dependency.as_str()])
+                .collect(),
+            timeout_seconds: definition.timeout_seconds,
+            status: "pending".to_string(),
+            result: None,
+            error: None,
+            processing_time_ms: None,
+            started_at: None,
+            completed_at: None,
+        })
+        .collect())
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Visit {
+    New,
+    InProgress,
+    Done,
+}
+
+/// Find a dependency cycle, returned as the step names along it with the
+/// first step repeated at the end. All dependencies must exist.
+fn find_cycle(definitions: &[StepDefinition]) -> Option<Vec<String>> {
+    let index: HashMap<&str, usize> = definitions
+        .iter()
+        .enumerate()
+        .map(|(position, definition)| (definition.name.as_str(), position))
+        .collect();
+    let mut visits = vec![Visit::New; definitions.len()];
+
+    for start in 0..definitions.len() {
+        if visits[start] != Visit::New {
+            continue;
+        }
+
+        // Iterative depth-first search; each frame is a step and its next dependency
+        let mut path: Vec<(usize, usize)> = vec![(start, 0)];
+        visits[start] = Visit::InProgress;
+
+        while let Some((step, next)) = path.last_mut() {
+            let step = *step;
+            let Some(dependency) = definitions[step].depends_on.get(*next) else {
+                visits[step] = Visit::Done;
+                path.pop();
+                continue;
+            };
+            *next += 1;
+
+            let dependency = index[dependency.as_str()];
+            match visits[dependency] {
+                Visit::New => {
+                    visits[dependency] = Visit::InProgress;
+                    path.push((dependency, 0));
+                }
+                Visit::InProgress => {
+                    let cycle_start = path
+                        .iter()
+                        .position(|(position, _)| *position == dependency)
+                        .unwrap_or(0);
+                    let mut cycle: Vec<String> = path[cycle_start..]
+                        .iter()
+                        .map(|(position, _)| definitions[*position].name.clone())
+                        .collect();
+                    cycle.push(definitions[dependency].name.clone());
+                    return Some(cycle);
+                }
+                Visit::Done => {}
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::McpService;
+    use chrono::Utc;
+    use dashmap::DashMap;
+
+    fn registry() -> McpRegistry {
+        let services = DashMap::new();
+        for name in ["demo-content-mcp", "image-generation-mcp"] {
+            services.insert(
+                name.to_string(),
+                McpService {
+                    name: name.to_string(),
+                    url: "http://localhost:8804".to_string(),
+                    capabilities: vec![],
+                    status: "active".to_string(),
+                    last_health_check: Utc::now(),
+                },
+            );
+        }
+        McpRegistry { services }
+    }
+
+    fn definition(name: &str, depends_on: &[&str]) -> StepDefinition {
+        StepDefinition {
+            name: name.to_string(),
+            mcp_service: "demo-content-mcp".to_string(),
+            endpoint: "/v1/content/generate".to_string(),
+            parameters: HashMap::new(),
+            depends_on: depends_on.iter().map(|name| name.to_string()).collect(),
+            timeout_seconds: None,
+        }
+    }
+
+    #[test]
+    fn test_build_steps_maps_dependencies() {
+        let definitions = vec![
+            definition("draft", &[]),
+            definition("illustrate", &["draft"]),
+            definition("publish", &["draft", "illustrate"]),
+        ];
+
+        let steps = build_steps(&definitions, &registry()).unwrap();
+
+        assert_eq!(steps.len(), 3);
+        assert!(steps.iter().all(|step| step.status == "pending"));
+        assert_eq!(steps[1].depends_on, vec![steps[0].step_id]);
+        assert_eq!(
+            steps[2].depends_on,
+            vec![steps[0].step_id, steps[1].step_id]
+        );
+    }
+
+    #[test]
+    fn test_cycle_is_reported() {
+        let definitions = vec![
+            definition("draft", &[]),
+            definition("review", &["draft", "revise"]),
+            definition("revise", &["edit"]),
+            definition("edit", &["review"]),
+        ];
+
+        assert_eq!(
+            build_steps(&definitions, &registry()).unwrap_err(),
+            DagError::Cycle(vec![
+                "review".to_string(),
+                "revise".to_string(),
+                "edit".to_string(),
+                "review".to_string(),
+            ])
+        );
+
+        let error = build_steps(&[definition("loop", &["loop"])], &registry()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Workflow graph has a cycle: loop -> loop"
+        );
+    }
+
+    #[test]
+    fn test_invalid_graphs_are_rejected() {
+        let registry = registry();
+
+        assert_eq!(build_steps(&[], &registry).unwrap_err(), DagError::Empty);
+        assert_eq!(
+            build_steps(&[definition("a", &[]), definition("a", &[])], &registry).unwrap_err(),
+            DagError::DuplicateStep("a".to_string())
+        );
+        assert_eq!(
+            build_steps(&[definition("a", &["missing"])], &registry).unwrap_err(),
+            DagError::UnknownDependency {
+                step: "a".to_string(),
+                dependency: "missing".to_string(),
+            }
+        );
+
+        let mut unregistered = definition("a", &[]);
+        unregistered.mcp_service = "video-mcp".to_string();
+        assert_eq!(
+            build_steps(&[unregistered], &registry).unwrap_err(),
+            DagError::UnknownService {
+                step: "a".to_string(),
+                service: "video-mcp".to_string(),
+            }
+        );
+    }
+}
@@ -20,11 +20,18 @@ use tower_http::{cors::CorsLayer, trace::TraceLayer};
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
+mod dag;
+mod persistence;
+mod template;
+
+use persistence::{PostgresWorkflowStorage, RecoveryPolicy, WorkflowPersistence};
+
 #[derive(Clone)]
 pub struct AppState {
     pub service_name: String,
     pub mcp_registry: Arc<McpRegistry>,
     pub workflow_store: Arc<WorkflowStore>,
+    pub persistence: Arc<WorkflowPersistence>,
 }
 
 #[derive(Clone)]
@@ -49,17 +56,70 @@ pub struct McpService {
 // Request/Response types
 #[derive(Debug, Deserialize)]
 pub struct WorkflowRequest {
-    pub workflow_type: String, // "blog_post_campaign", "content_analysis", "creative_pipeline"
+    pub workflow_type: String, // "blog_post_campaign", "content_analysis", "creative_pipeline", "custom_dag"
+    #[serde(default)]
     pub parameters: HashMap<String, serde_json::Value>,
     pub options: Option<WorkflowOptions>,
+    #[serde(default)]
+    pub steps: Option<Vec<dag::StepDefinition>>, // custom_dag only
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkflowOptions {
     pub timeout_seconds: Option<u64>,
     pub parallel_execution: Option<bool>,
     pub failure_strategy: Option<String>, // "fail_fast", "continue", "retry"
     pub notification_webhook: Option<String>,
+    pub max_retries: Option<u32>, // retry strategy only, defaults to 3
+    pub retry_backoff_ms: Option<u64>, // doubled after every attempt, defaults to 500
+}
+
+/// How a workflow reacts to a failed step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureStrategy {
+    /// Cancel all remaining steps and fail the workflow
+    FailFast,
+    /// Skip the dependents of the failed step but keep running independent steps
+    Continue,
+    /// Re-attempt the failed step with backoff, then skip its dependents
+    Retry { max_retries: u32, backoff: Duration },
+}
+
+const DEFAULT_MAX_RETRIES: u32 = 3;
+const DEFAULT_RETRY_BACKOFF_MS: u64 = 500;
+
+impl FailureStrategy {
+    pub fn from_options(options: Option<&WorkflowOptions>) -> Self {
+        let Some(options) = options else {
+            return FailureStrategy::FailFast;
+        };
+
+        match options.failure_strategy.as_deref() {
+            Some("continue") => FailureStrategy::Continue,
+            Some("retry") => FailureStrategy::Retry {
+                max_retries: options.max_retries.unwrap_or(DEFAULT_MAX_RETRIES),
+                backoff: Duration::from_millis(
+                    options.retry_backoff_ms.unwrap_or(DEFAULT_RETRY_BACKOFF_MS),
+                ),
+            },
+            Some("fail_fast") | None => FailureStrategy::FailFast,
+            Some(other) => {
+                warn!("Unknown failure strategy '{}', using fail_fast", other);
+                FailureStrategy::FailFast
+            }
+        }
+    }
+}
+
+/// What the executor should do next for a workflow
+#[derive(Debug)]
+enum Schedule {
+    /// Execute these steps
+    Ready(Vec<WorkflowStep>),
+    /// Steps are still running
+    Waiting,
+    /// No step is left to run
+    Finished { failed: bool },
 }
 
 #[derive(Debug, Serialize)]
@@ -72,6 +132,7 @@ pub struct WorkflowResponse {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub processing_time_ms: Option<u64>,
+    pub notification: Option<WebhookDelivery>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -82,7 +143,9 @@ pub struct WorkflowStep {
     pub endpoint: String,
     pub parameters: HashMap<String, serde_json::Value>,
     pub depends_on: Vec<Uuid>,
-    pub status: String, // "pending", "running", "completed", "failed", "skipped"
+    #[serde(default)]
+    pub timeout_seconds: Option<u64>, // falls back to the workflow's timeout_seconds
+    pub status: String, // "pending", "running", "completed", "failed", "skipped", "cancelled"
     pub result: Option<serde_json::Value>,
     pub error: Option<String>,
     pub processing_time_ms: Option<u64>,
@@ -90,7 +153,7 @@ pub struct WorkflowStep {
     pub completed_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkflowExecution {
     pub id: Uuid,
     pub workflow_type: String,
@@ -100,8 +163,28 @@ pub struct WorkflowExecution {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub options: Option<WorkflowOptions>,
+    pub notification: Option<WebhookDelivery>,
+}
+
+/// Outcome of posting a workflow summary to its notification webhook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub url: String,
+    pub status: String, // "delivered", "failed"
+    pub attempts: u32,
+    pub response_status: Option<u16>,
+    pub error: Option<String>,
+    pub attempted_at: DateTime<Utc>,
 }
 
+const DEFAULT_STEP_TIMEOUT_SECONDS: u64 = 30;
+const WEBHOOK_MAX_ATTEMPTS: u32 = 3;
+const WEBHOOK_RETRY_BACKOFF_MS: u64 = 1000;
+const WEBHOOK_TIMEOUT_SECONDS: u64 = 10;
+const TERMINAL_PERSIST_ATTEMPTS: u32 = 3;
+const TERMINAL_PERSIST_BACKOFF_MS: u64 = 200;
+const DEFAULT_WORKFLOW_RETENTION_SECONDS: u64 = 3600; // finished workflows kept in memory
+
 #[derive(Debug, Serialize)]
 pub struct HealthStatus {
     pub status: String,
@@ -119,6 +202,17 @@ pub struct McpRegistrationRequest {
     pub capabilities: Vec<String>,
 }
 
+/// A single MCP call outside any workflow. `function` is
+/// `<mcp_service>.<operation>`, where the operation's dot-separated segments
+/// name the service's `/v1/...` endpoint, e.g. `demo-content-mcp.content.generate`.
+#[derive(Debug, Deserialize)]
+pub struct FunctionCallRequest {
+    pub function: String,
+    #[serde(default)]
+    pub parameters: HashMap<String, serde_json::Value>,
+    pub timeout_seconds: Option<u64>,
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize tracing
@@ -142,12 +236,48 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Register default MCP services
     register_default_mcps(&mcp_registry).await;
 
+    let persistence = match std::env::var("DATABASE_URL") {
+        Ok(database_url) => {
+            let pool = sqlx::postgres::PgPoolOptions::new()
+                .max_connections(10)
+                .connect(&database_url)
+                .await?;
+            let storage = PostgresWorkflowStorage::new(pool);
+            storage.run_migrations().await?;
+            info!("Persisting workflows to PostgreSQL");
+            WorkflowPersistence::new(Arc::new(storage))
+        }
+        Err(_) => {
+            warn!("DATABASE_URL is not set, workflows will not survive a restart");
+            WorkflowPersistence::in_memory()
+        }
+    };
+
     let state = AppState {
         service_name: "mcp-orchestrator".to_string(),
         mcp_registry: mcp_registry.clone(),
         workflow_store: workflow_store.clone(),
+        persistence: Arc::new(persistence),
     };
 
+    // Pick up workflows interrupted by the last shutdown
+    let recovery_policy = RecoveryPolicy::parse(
+        std::env::var("ORCHESTRATOR_RECOVERY_POLICY")
+            .ok()
+            .as_deref(),
+    );
+    recover_workflows(&state, recovery_policy).await?;
+
+    // Drop finished workflows from memory; get_workflow reads them back from storage
+    let retention = std::env::var("ORCHESTRATOR_WORKFLOW_RETENTION_SECONDS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_WORKFLOW_RETENTION_SECONDS);
+    let eviction_state = state.clone();
+    tokio::spawn(async move {
+        eviction_loop(eviction_state, Duration::from_secs(retention)).await;
+    });
+
     // Start background health check task
     let health_check_registry = mcp_registry.clone();
     tokio::spawn(async move {
@@ -171,6 +301,7 @@ fn create_router(state: AppState) -> Router {
         .route("/v1/workflows/:workflow_id/cancel", post(cancel_workflow))
         .route("/v1/mcps/register", post(register_mcp))
         .route("/v1/mcps", get(list_mcps))
+        .route("/v1/functions/execute", post(execute_function))
         .route("/v1/capabilities", get(get_capabilities))
         .layer(TraceLayer::new_for_http())
         .layer(CorsLayer::permissive())
@@ -197,6 +328,7 @@ async fn health_check(State(state): State<AppState>) -> impl IntoResponse {
             "content_analysis".to_string(),
             "creative_pipeline".to_string(),
             "social_media_automation".to_string(),
+            dag::CUSTOM_DAG_WORKFLOW.to_string(),
         ],
     })
 }
@@ -204,7 +336,7 @@ async fn health_check(State(state): State<AppState>) -> impl IntoResponse {
 async fn create_workflow(
     State(state): State<AppState>,
     Json(request): Json<WorkflowRequest>,
-) -> Result<impl IntoResponse, StatusCode> {
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
     let workflow_id = Uuid::new_v4();
 
     info!(
@@ -212,12 +344,30 @@ async fn create_workflow(
         request.workflow_type, workflow_id
     );
 
-    // Generate workflow steps based on type
-    let steps = match generate_workflow_steps(&request.workflow_type, &request.parameters) {
-        Ok(steps) => steps,
-        Err(e) => {
-            error!("Failed to generate workflow steps: {}", e);
-            return Err(StatusCode::BAD_REQUEST);
+    // Generate workflow steps based on type, or take them from the request for custom graphs
+    let steps = if request.workflow_type == dag::CUSTOM_DAG_WORKFLOW {
+        let definitions = request.steps.as_deref().unwrap_or_default();
+        match dag::build_steps(definitions, &state.mcp_registry) {
+            Ok(steps) => steps,
+            Err(e) => {
+                error!("Invalid custom workflow graph: {}", e);
+                let mut body = serde_json::json!({ "error": e.to_string() });
+                if let dag::DagError::Cycle(cycle) = &e {
+                    body["cycle"] = serde_json::json!(cycle);
+                }
+                return Err((StatusCode::BAD_REQUEST, Json(body)));
+            }
+        }
+    } else {
+        match generate_workflow_steps(&request.workflow_type, &request.parameters) {
+            Ok(steps) => steps,
+            Err(e) => {
+                error!("Failed to generate workflow steps: {}", e);
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({ "error": e.to_string() })),
+                ));
+            }
         }
     };
 
@@ -230,9 +380,11 @@ async fn create_workflow(
         created_at: Utc::now(),
         updated_at: Utc::now(),
         options: request.options,
+        notification: None,
     };
 
     // Store workflow
+    state.persistence.save(&workflow);
     state.workflow_store.workflows.insert(workflow_id, workflow);
 
     // Start workflow execution in background
@@ -250,6 +402,7 @@ async fn create_workflow(
         created_at: Utc::now(),
         updated_at: Utc::now(),
         processing_time_ms: None,
+        notification: None,
     };
 
     Ok(Json(response))
@@ -259,23 +412,37 @@ async fn get_workflow(
     State(state): State<AppState>,
     Path(workflow_id): Path<Uuid>,
 ) -> Result<impl IntoResponse, StatusCode> {
-    match state.workflow_store.workflows.get(&workflow_id) {
-        Some(workflow) => {
-            let workflow_data = workflow.value();
-            let response = WorkflowResponse {
-                workflow_id: workflow_data.id,
-                workflow_type: workflow_data.workflow_type.clone(),
-                status: workflow_data.status.clone(),
-                steps: workflow_data.steps.clone(),
-                results: workflow_data.results.clone(),
-                created_at: workflow_data.created_at,
-                updated_at: workflow_data.updated_at,
-                processing_time_ms: None,
-            };
-            Ok(Json(response))
-        }
-        None => Err(StatusCode::NOT_FOUND),
-    }
+    let cached = state
+        .workflow_store
+        .workflows
+        .get(&workflow_id)
+        .map(|workflow| workflow.clone());
+
+    // Finished workflows are evicted from memory after a while
+    let workflow_data = match cached {
+        Some(workflow) => workflow,
+        None => match state.persistence.load(workflow_id).await {
+            Ok(Some(workflow)) => workflow,
+            Ok(None) => return Err(StatusCode::NOT_FOUND),
+            Err(e) => {
+                error!("Failed to load workflow {}: {}", workflow_id, e);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        },
+    };
+
+    let response = WorkflowResponse {
+        workflow_id: workflow_data.id,
+        workflow_type: workflow_data.workflow_type,
+        status: workflow_data.status,
+        steps: workflow_data.steps,
+        results: workflow_data.results,
+        created_at: workflow_data.created_at,
+        updated_at: workflow_data.updated_at,
+        processing_time_ms: None,
+        notification: workflow_data.notification,
+    };
+    Ok(Json(response))
 }
 
 async fn cancel_workflow(
@@ -286,6 +453,7 @@ async fn cancel_workflow(
         Some(mut workflow) => {
             workflow.status = "cancelled".to_string();
             workflow.updated_at = Utc::now();
+            state.persistence.save(&workflow);
             info!("Workflow {} cancelled", workflow_id);
             Ok(Json(serde_json::json!({"status": "cancelled"})))
         }
@@ -332,6 +500,85 @@ async fn list_mcps(State(state): State<AppState>) -> impl IntoResponse {
     }))
 }
 
+/// Run one MCP function call synchronously and return its result
+async fn execute_function(
+    State(state): State<AppState>,
+    Json(request): Json<FunctionCallRequest>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let Some((mcp_service, endpoint)) = parse_function(&request.function) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({
+                "error": format!(
+                    "Function must be <mcp_service>.<operation>, got {:?}",
+                    request.function
+                )
+            })),
+        );
+    };
+    if !state.mcp_registry.services.contains_key(mcp_service) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({
+                "error": format!("MCP service {} not found", mcp_service)
+            })),
+        );
+    }
+
+    let timeout = Duration::from_secs(
+        request
+            .timeout_seconds
+            .unwrap_or(DEFAULT_STEP_TIMEOUT_SECONDS),
+    );
+    let client = reqwest::Client::new();
+    let outcome = tokio::time::timeout(
+        timeout,
+        call_mcp(
+            &client,
+            &state,
+            mcp_service,
+            &endpoint,
+            &request.parameters,
+            timeout,
+        ),
+    )
+    .await;
+
+    match outcome {
+        Ok(Ok((result, processing_time_ms))) => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "function": request.function,
+                "mcp_service": mcp_service,
+                "result": result,
+                "processing_time_ms": processing_time_ms,
+            })),
+        ),
+        Ok(Err(e)) => {
+            warn!("Function {} failed: {}", request.function, e);
+            (
+                StatusCode::BAD_GATEWAY,
+                Json(serde_json::json!({ "error": e })),
+            )
+        }
+        Err(_) => (
+            StatusCode::GATEWAY_TIMEOUT,
+            Json(serde_json::json!({
+                "error": format!("Function timed out after {}s", timeout.as_secs())
+            })),
+        ),
+    }
+}
+
+/// Split `<mcp_service>.<operation>` into the service and its endpoint path
+fn parse_function(function: &str) -> Option<(&str, String)> {
+    let (mcp_service, operation) = function.split_once('.')?;
+    if mcp_service.is_empty() || operation.split('.').any(str::is_empty) {
+        return None;
+    }
+    Some((mcp_service, format!("/v1/{}", operation.replace('.', "/"))))
+}
+
 async fn get_capabilities(State(_state): State<AppState>) -> impl IntoResponse {
     Json(serde_json::json!({
         "service": "mcp-orchestrator",
@@ -354,6 +601,12 @@ async fn get_capabilities(State(_state): State<AppState>) -> impl IntoResponse {
                 "description": "Generate creative content with images and variations",
                 "required_parameters": ["concept"],
                 "optional_parameters": ["style", "iterations"]
+            },
+            {
+                "type": "custom_dag",
+                "description": "Run a user-supplied graph of MCP steps linked by depends_on",
+                "required_parameters": ["steps"],
+                "optional_parameters": []
             }
         ],
         "features": [
@@ -441,6 +694,7 @@ fn generate_workflow_steps(
                         params
                     },
                     depends_on: vec![],
+                    timeout_seconds: None,
                     status: "pending".to_string(),
                     result: None,
                     error: None,
@@ -456,10 +710,14 @@ fn generate_workflow_steps(
                     parameters: {
                         let mut params = HashMap::new();
                         params.insert("analysis_type".to_string(), serde_json::json!("keywords"));
-                        params.insert("text".to_string(), serde_json::json!("{{step1.content}}"));
+                        params.insert(
+                            "text".to_string(),
+                            serde_json::json!("{{generate_blog_post.content}}"),
+                        );
                         params
                     },
                     depends_on: vec![step1_id],
+                    timeout_seconds: None,
                     status: "pending".to_string(),
                     result: None,
                     error: None,
@@ -487,6 +745,7 @@ fn generate_workflow_steps(
                         params
                     },
                     depends_on: vec![step1_id],
+                    timeout_seconds: None,
                     status: "pending".to_string(),
                     result: None,
                     error: None,
@@ -510,6 +769,7 @@ fn generate_workflow_steps(
                         params
                     },
                     depends_on: vec![step1_id, step2_id],
+                    timeout_seconds: None,
                     status: "pending".to_string(),
                     result: None,
                     error: None,
@@ -542,6 +802,7 @@ fn generate_workflow_steps(
                         params
                     },
                     depends_on: vec![],
+                    timeout_seconds: None,
                     status: "pending".to_string(),
                     result: None,
                     error: None,
@@ -561,6 +822,7 @@ fn generate_workflow_steps(
                         params
                     },
                     depends_on: vec![],
+                    timeout_seconds: None,
                     status: "pending".to_string(),
                     result: None,
                     error: None,
@@ -583,6 +845,7 @@ fn generate_workflow_steps(
                         params
                     },
                     depends_on: vec![],
+                    timeout_seconds: None,
                     status: "pending".to_string(),
                     result: None,
                     error: None,
@@ -609,61 +872,61 @@ async fn execute_workflow(state: AppState, workflow_id: Uuid) {
 
     workflow.status = "running".to_string();
     workflow.updated_at = Utc::now();
+    state.persistence.save(&workflow);
+    let strategy = FailureStrategy::from_options(workflow.options.as_ref());
+    let default_timeout = Duration::from_secs(
+        workflow
+            .options
+            .as_ref()
+            .and_then(|options| options.timeout_seconds)
+            .unwrap_or(DEFAULT_STEP_TIMEOUT_SECONDS),
+    );
     drop(workflow); // Release the lock
 
     // Execute steps based on dependencies
     let client = reqwest::Client::new();
 
     loop {
-        let mut pending_steps = Vec::new();
-        let mut ready_steps = Vec::new();
-
-        // Check workflow status
-        if let Some(workflow) = state.workflow_store.workflows.get(&workflow_id) {
-            if workflow.status == "cancelled" {
-                info!("Workflow {} was cancelled", workflow_id);
+        let schedule = match state.workflow_store.workflows.get_mut(&workflow_id) {
+            Some(mut workflow) if workflow.status != "cancelled" => {
+                let schedule = schedule_steps(&mut workflow.steps, strategy);
+                workflow.updated_at = Utc::now();
+                state.persistence.save(&workflow);
+                Some(schedule)
+            }
+            Some(_) => None,
+            None => {
+                error!("Workflow {} not found", workflow_id);
                 return;
             }
+        };
 
-            // Find steps that are ready to execute
-            for step in &workflow.steps {
-                match step.status.as_str() {
-                    "pending" => {
-                        // Check if all dependencies are completed
-                        let dependencies_completed = step.depends_on.iter().all(|dep_id| {
-                            workflow
-                                .steps
-                                .iter()
-                                .any(|s| s.step_id == *dep_id && s.status == "completed")
-                        });
-
-                        if dependencies_completed {
-                            ready_steps.push(step.clone());
-                        } else {
-                            pending_steps.push(step.clone());
-                        }
-                    }
-                    "running" => pending_steps.push(step.clone()),
-                    _ => {} // completed, failed, skipped
-                }
+        let Some(schedule) = schedule else {
+            info!("Workflow {} was cancelled", workflow_id);
+            persist_terminal_state(&state, workflow_id).await;
+            notify_webhook(&state, &client, workflow_id).await;
+            return;
+        };
+
+        let ready_steps = match schedule {
+            Schedule::Ready(steps) => steps,
+            Schedule::Waiting => {
+                // Wait for running steps to complete
+                tokio::time::sleep(Duration::from_millis(500)).await;
+                continue;
             }
-        }
-
-        if ready_steps.is_empty() && pending_steps.is_empty() {
-            // All steps are completed
-            if let Some(mut workflow) = state.workflow_store.workflows.get_mut(&workflow_id) {
-                workflow.status = "completed".to_string();
-                workflow.updated_at = Utc::now();
+            Schedule::Finished { failed } => {
+                let status = if failed { "failed" } else { "completed" };
+                if let Some(mut workflow) = state.workflow_store.workflows.get_mut(&workflow_id) {
+                    workflow.status = status.to_string();
+                    workflow.updated_at = Utc::now();
+                }
+                info!("Workflow {} {}", workflow_id, status);
+                persist_terminal_state(&state, workflow_id).await;
+                notify_webhook(&state, &client, workflow_id).await;
+                break;
             }
-            info!("Workflow {} completed", workflow_id);
-            break;
-        }
-
-        if ready_steps.is_empty() {
-            // Wait for running steps to complete
-            tokio::time::sleep(Duration::from_millis(500)).await;
-            continue;
-        }
+        };
 
         // Execute ready steps in parallel
         let execution_futures = ready_steps.into_iter().map(|step| {
@@ -671,18 +934,113 @@ async fn execute_workflow(state: AppState, workflow_id: Uuid) {
             let state = state.clone();
             let workflow_id = workflow_id;
 
-            async move { execute_step(&client, &state, workflow_id, step).await }
+            async move {
+                execute_step(
+                    &client,
+                    &state,
+                    workflow_id,
+                    step,
+                    strategy,
+                    default_timeout,
+                )
+                .await
+            }
         });
 
         join_all(execution_futures).await;
     }
 }
 
+/// Settle steps that can no longer run and collect the steps ready to execute.
+/// Under fail_fast a failed step cancels every pending step; otherwise pending
+/// steps whose prerequisites failed, were skipped or don't exist are skipped.
+fn schedule_steps(steps: &mut [WorkflowStep], strategy: FailureStrategy) -> Schedule {
+    let first_failure = steps
+        .iter()
+        .find(|step| step.status == "failed")
+        .map(|step| step.step_name.clone());
+
+    if let (FailureStrategy::FailFast, Some(failed_step)) = (strategy, &first_failure) {
+        for step in steps.iter_mut().filter(|step| step.status == "pending") {
+            step.status = "cancelled".to_string();
+            step.error = Some(format!("Cancelled after step {} failed", failed_step));
+            step.completed_at = Some(Utc::now());
+        }
+    }
+
+    // Skipping a step can block its own dependents, so repeat until settled
+    loop {
+        let blocked: Vec<(usize, String)> = steps
+            .iter()
+            .enumerate()
+            .filter(|(_, step)| step.status == "pending")
+            .filter_map(|(index, step)| {
+                step.depends_on.iter().find_map(|dep_id| {
+                    match steps.iter().find(|s| s.step_id == *dep_id) {
+                        Some(dep)
+                            if matches!(
+                                dep.status.as_str(),
+                                "failed" | "skipped" | "cancelled"
+                            ) =>
+                        {
+                            Some((
+                                index,
+                                format!("prerequisite {} {}", dep.step_name, dep.status),
+                            ))
+                        }
+                        Some(_) => None,
+                        None => Some((index, format!("prerequisite {} does not exist", dep_id))),
+                    }
+                })
+            })
+            .collect();
+
+        if blocked.is_empty() {
+            break;
+        }
+
+        for (index, reason) in blocked {
+            let step = &mut steps[index];
+            step.status = "skipped".to_string();
+            step.error = Some(format!("Skipped because {}", reason));
+            step.completed_at = Some(Utc::now());
+        }
+    }
+
+    let ready: Vec<WorkflowStep> = steps
+        .iter()
+        .filter(|step| step.status == "pending")
+        .filter(|step| {
+            step.depends_on.iter().all(|dep_id| {
+                steps
+                    .iter()
+                    .any(|s| s.step_id == *dep_id && s.status == "completed")
+            })
+        })
+        .cloned()
+        .collect();
+
+    if !ready.is_empty() {
+        Schedule::Ready(ready)
+    } else if steps
+        .iter()
+        .any(|step| matches!(step.status.as_str(), "pending" | "running"))
+    {
+        Schedule::Waiting
+    } else {
+        Schedule::Finished {
+            failed: first_failure.is_some(),
+        }
+    }
+}
+
 async fn execute_step(
     client: &reqwest::Client,
     state: &AppState,
     workflow_id: Uuid,
     step: WorkflowStep,
+    strategy: FailureStrategy,
+    default_timeout: Duration,
 ) {
     info!(
         "Executing step: {} for workflow: {}",
@@ -700,140 +1058,141 @@ async fn execute_step(
             workflow_step.started_at = Some(Utc::now());
         }
         workflow.updated_at = Utc::now();
+        state.persistence.save(&workflow);
     }
 
-    let start_time = std::time::Instant::now();
-
-    // Get MCP service URL
-    let service_url = match state.mcp_registry.services.get(&step.mcp_service) {
-        Some(service) => service.url.clone(),
-        None => {
-            error!("MCP service {} not found", step.mcp_service);
-            update_step_failure(
-                state,
-                workflow_id,
-                step.step_id,
-                "MCP service not found".to_string(),
-            )
-            .await;
+    // Replace template variables in parameters; an unresolved reference won't
+    // resolve on a retry either, so it fails the step straight away
+    let parameters = match resolve_step_parameters(state, workflow_id, &step.parameters) {
+        Ok(parameters) => parameters,
+        Err(e) => {
+            error!("Step {} failed: {}", step.step_name, e);
+            update_step_failure(state, workflow_id, step.step_id, e).await;
             return;
         }
     };
 
-    // Replace template variables in parameters
-    let resolved_parameters = resolve_step_parameters(state, workflow_id, &step.parameters).await;
-
-    // Execute HTTP request to MCP service
-    let full_url = format!("{}{}", service_url, step.endpoint);
+    let (max_retries, backoff) = match strategy {
+        FailureStrategy::Retry {
+            max_retries,
+            backoff,
+        } => (max_retries, backoff),
+        _ => (0, Duration::ZERO),
+    };
+    let step_timeout = step
+        .timeout_seconds
+        .map(Duration::from_secs)
+        .unwrap_or(default_timeout);
 
-    match client
-        .post(&full_url)
-        .json(&resolved_parameters)
-        .timeout(Duration::from_secs(30))
-        .send()
+    let mut attempt = 0;
+    loop {
+        // Dropping the attempt on expiry also cancels its HTTP request
+        let outcome = tokio::time::timeout(
+            step_timeout,
+            call_mcp(
+                client,
+                state,
+                &step.mcp_service,
+                &step.endpoint,
+                &parameters,
+                step_timeout,
+            ),
+        )
         .await
-    {
-        Ok(response) => {
-            if response.status().is_success() {
-                match response.json::<serde_json::Value>().await {
-                    Ok(result) => {
-                        let processing_time = start_time.elapsed().as_millis() as u64;
-                        info!(
-                            "Step {} completed successfully in {}ms",
-                            step.step_name, processing_time
-                        );
-                        update_step_success(
-                            state,
-                            workflow_id,
-                            step.step_id,
-                            result,
-                            processing_time,
-                        )
-                        .await;
-                    }
-                    Err(e) => {
-                        error!(
-                            "Failed to parse response for step {}: {}",
-                            step.step_name, e
-                        );
-                        update_step_failure(
-                            state,
-                            workflow_id,
-                            step.step_id,
-                            format!("Response parsing error: {}", e),
-                        )
-                        .await;
-                    }
-                }
-            } else {
-                error!(
-                    "HTTP error for step {}: {}",
+        .unwrap_or_else(|_| {
+            Err(format!(
+                "Step timed out after {}s",
+                step_timeout.as_secs_f64()
+            ))
+        });
+
+        match outcome {
+            Ok((result, processing_time)) => {
+                info!(
+                    "Step {} completed successfully in {}ms",
+                    step.step_name, processing_time
+                );
+                update_step_success(state, workflow_id, step.step_id, result, processing_time)
+                    .await;
+                return;
+            }
+            Err(e) if attempt < max_retries => {
+                let delay = backoff.saturating_mul(2u32.saturating_pow(attempt));
+                attempt += 1;
+                warn!(
+                    "Step {} failed (attempt {} of {}), retrying in {:?}: {}",
                     step.step_name,
-                    response.status()
+                    attempt,
+                    max_retries + 1,
+                    delay,
+                    e
                 );
-                update_step_failure(
-                    state,
-                    workflow_id,
-                    step.step_id,
-                    format!("HTTP error: {}", response.status()),
-                )
-                .await;
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => {
+                error!("Step {} failed: {}", step.step_name, e);
+                update_step_failure(state, workflow_id, step.step_id, e).await;
+                return;
             }
-        }
-        Err(e) => {
-            error!("Request failed for step {}: {}", step.step_name, e);
-            update_step_failure(
-                state,
-                workflow_id,
-                step.step_id,
-                format!("Request error: {}", e),
-            )
-            .await;
         }
     }
 }
 
-async fn resolve_step_parameters(
+/// Call an endpoint of a registered MCP service once, returning its result
+/// and processing time
+async fn call_mcp(
+    client: &reqwest::Client,
     state: &AppState,
-    workflow_id: Uuid,
+    mcp_service: &str,
+    endpoint: &str,
     parameters: &HashMap<String, serde_json::Value>,
-) -> HashMap<String, serde_json::Value> {
-    let mut resolved = HashMap::new();
-
-    for (key, value) in parameters {
-        if let Some(value_str) = value.as_str() {
-            if value_str.contains("{{") && value_str.contains("}}") {
-                // This is a template - resolve it
-                let resolved_value = resolve_template_value(state, workflow_id, value_str).await;
-                resolved.insert(key.clone(), serde_json::json!(resolved_value));
-            } else {
-                resolved.insert(key.clone(), value.clone());
-            }
-        } else {
-            resolved.insert(key.clone(), value.clone());
-        }
+    timeout: Duration,
+) -> Result<(serde_json::Value, u64), String> {
+    let start_time = std::time::Instant::now();
+
+    // Get MCP service URL
+    let service_url = match state.mcp_registry.services.get(mcp_service) {
+        Some(service) => service.url.clone(),
+        None => return Err(format!("MCP service {} not found", mcp_service)),
+    };
+
+    // Execute HTTP request to MCP service
+    let full_url = format!("{}{}", service_url, endpoint);
+
+    let response = client
+        .post(&full_url)
+        .json(parameters)
+        .timeout(timeout)
+        .send()
+        .await
+        .map_err(|e| format!("Request error: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("HTTP error: {}", response.status()));
     }
 
-    resolved
+    let result = response
+        .json::<serde_json::Value>()
+        .await
+        .map_err(|e| format!("Response parsing error: {}", e))?;
+
+    Ok((result, start_time.elapsed().as_millis() as u64))
 }
 
-async fn resolve_template_value(state: &AppState, workflow_id: Uuid, template: &str) -> String {
-    // Simple template resolution for {{step1.content}} patterns
-    if let Some(workflow) = state.workflow_store.workflows.get(&workflow_id) {
-        // This is a simplified template resolver
-        // In production, you'd want a more sophisticated template engine
-        if template.contains("{{step1.content}}") {
-            if let Some(step1) = workflow.steps.first() {
-                if let Some(result) = &step1.result {
-                    if let Some(content) = result.get("content") {
-                        return content.as_str().unwrap_or(template).to_string();
-                    }
-                }
-            }
-        }
-    }
+/// Resolve `{{step_name.path}}` templates against the workflow's completed steps
+fn resolve_step_parameters(
+    state: &AppState,
+    workflow_id: Uuid,
+    parameters: &HashMap<String, serde_json::Value>,
+) -> Result<HashMap<String, serde_json::Value>, String> {
+    let steps = state
+        .workflow_store
+        .workflows
+        .get(&workflow_id)
+        .map(|workflow| workflow.steps.clone())
+        .ok_or_else(|| format!("Workflow {} not found", workflow_id))?;
 
-    template.to_string()
+    template::resolve_parameters(parameters, &steps)
 }
 
 async fn update_step_success(
@@ -851,6 +1210,7 @@ async fn update_step_success(
             step.completed_at = Some(Utc::now());
         }
         workflow.updated_at = Utc::now();
+        state.persistence.save(&workflow);
     }
 }
 
@@ -862,9 +1222,294 @@ async fn update_step_failure(state: &AppState, workflow_id: Uuid, step_id: Uuid,
             step.completed_at = Some(Utc::now());
         }
         workflow.updated_at = Utc::now();
+        state.persistence.save(&workflow);
     }
 }
 
+/// Write a workflow's terminal state straight to storage, retrying briefly, so
+/// it is durable before anyone is notified about it
+async fn persist_terminal_state(state: &AppState, workflow_id: Uuid) {
+    let Some(workflow) = state
+        .workflow_store
+        .workflows
+        .get(&workflow_id)
+        .map(|workflow| workflow.clone())
+    else {
+        return;
+    };
+
+    let mut attempt = 1;
+    loop {
+        match state.persistence.save_now(&workflow).await {
+            Ok(()) => return,
+            Err(e) if attempt < TERMINAL_PERSIST_ATTEMPTS => {
+                warn!(
+                    "Failed to persist workflow {} (attempt {} of {}): {}",
+                    workflow_id, attempt, TERMINAL_PERSIST_ATTEMPTS, e
+                );
+                tokio::time::sleep(Duration::from_millis(
+                    TERMINAL_PERSIST_BACKOFF_MS * u64::from(attempt),
+                ))
+                .await;
+                attempt += 1;
+            }
+            Err(e) => {
+                error!(
+                    "Failed to persist final state of workflow {}: {}",
+                    workflow_id, e
+                );
+                return;
+            }
+        }
+    }
+}
+
+/// POST the workflow summary to its notification webhook, if one is set, and
+/// record the delivery outcome on the workflow
+async fn notify_webhook(state: &AppState, client: &reqwest::Client, workflow_id: Uuid) {
+    let (url, summary) = match state.workflow_store.workflows.get(&workflow_id) {
+        Some(workflow) => match workflow
+            .options
+            .as_ref()
+            .and_then(|options| options.notification_webhook.clone())
+        {
+            Some(url) => (url, workflow_summary(&workflow)),
+            None => return,
+        },
+        None => return,
+    };
+
+    let delivery = deliver_webhook(
+        client,
+        &url,
+        &summary,
+        WEBHOOK_MAX_ATTEMPTS,
+        Duration::from_millis(WEBHOOK_RETRY_BACKOFF_MS),
+    )
+    .await;
+
+    if delivery.status == "delivered" {
+        info!("Workflow {} notification delivered to {}", workflow_id, url);
+    } else {
+        warn!(
+            "Workflow {} notification to {} failed after {} attempts: {}",
+            workflow_id,
+            url,
+            delivery.attempts,
+            delivery.error.as_deref().unwrap_or("unknown error")
+        );
+    }
+
+    if let Some(mut workflow) = state.workflow_store.workflows.get_mut(&workflow_id) {
+        workflow.notification = Some(delivery);
+        workflow.updated_at = Utc::now();
+        state.persistence.save(&workflow);
+    }
+}
+
+fn workflow_summary(workflow: &WorkflowExecution) -> serde_json::Value {
+    let steps: Vec<serde_json::Value> = workflow
+        .steps
+        .iter()
+        .map(|step| {
+            serde_json::json!({
+                "step_id": step.step_id,
+                "step_name": step.step_name,
+                "status": step.status,
+                "error": step.error,
+                "processing_time_ms": step.processing_time_ms,
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "workflow_id": workflow.id,
+        "workflow_type": workflow.workflow_type,
+        "status": workflow.status,
+        "steps": steps,
+        "created_at": workflow.created_at,
+        "updated_at": workflow.updated_at,
+    })
+}
+
+/// POST a payload, retrying failed deliveries with exponential backoff
+async fn deliver_webhook(
+    client: &reqwest::Client,
+    url: &str,
+    payload: &serde_json::Value,
+    max_attempts: u32,
+    backoff: Duration,
+) -> WebhookDelivery {
+    let mut delivery = WebhookDelivery {
+        url: url.to_string(),
+        status: "failed".to_string(),
+        attempts: 0,
+        response_status: None,
+        error: None,
+        attempted_at: Utc::now(),
+    };
+
+    while delivery.attempts < max_attempts {
+        if delivery.attempts > 0 {
+            tokio::time::sleep(backoff.saturating_mul(2u32.saturating_pow(delivery.attempts - 1)))
+                .await;
+        }
+
+        delivery.attempts += 1;
+        delivery.attempted_at = Utc::now();
+
+        match client
+            .post(url)
+            .json(payload)
+            .timeout(Duration::from_secs(WEBHOOK_TIMEOUT_SECONDS))
+            .send()
+            .await
+        {
+            Ok(response) if response.status().is_success() => {
+                delivery.status = "delivered".to_string();
+                delivery.response_status = Some(response.status().as_u16());
+                delivery.error = None;
+                break;
+            }
+            Ok(response) => {
+                delivery.response_status = Some(response.status().as_u16());
+                delivery.error = Some(format!("HTTP error: {}", response.status()));
+            }
+            Err(e) => {
+                delivery.response_status = None;
+                delivery.error = Some(format!("Request error: {}", e));
+            }
+        }
+    }
+
+    delivery
+}
+
+/// Reload workflows that were queued or running when the service stopped and
+/// resume or fail them according to the recovery policy
+async fn recover_workflows(
+    state: &AppState,
+    policy: RecoveryPolicy,
+) -> Result<usize, persistence::PersistenceError> {
+    let workflows = state.persistence.load_incomplete().await?;
+    if workflows.is_empty() {
+        return Ok(0);
+    }
+
+    info!(
+        "Recovering {} interrupted workflows ({:?})",
+        workflows.len(),
+        policy
+    );
+
+    let client = reqwest::Client::new();
+    let recovered = workflows.len();
+    for mut workflow in workflows {
+        let workflow_id = workflow.id;
+        let now = Utc::now();
+
+        match policy {
+            RecoveryPolicy::Resume => {
+                // Steps cut off mid-call have no result and run again
+                for step in workflow
+                    .steps
+                    .iter_mut()
+                    .filter(|step| step.status == "running")
+                {
+                    step.status = "pending".to_string();
+                    step.started_at = None;
+                }
+                workflow.status = "queued".to_string();
+                workflow.updated_at = now;
+                state.persistence.save(&workflow);
+                state.workflow_store.workflows.insert(workflow_id, workflow);
+
+                let orchestrator_state = state.clone();
+                tokio::spawn(async move {
+                    execute_workflow(orchestrator_state, workflow_id).await;
+                });
+            }
+            RecoveryPolicy::Fail => {
+                for step in workflow
+                    .steps
+                    .iter_mut()
+                    .filter(|step| matches!(step.status.as_str(), "pending" | "running"))
+                {
+                    step.status = "failed".to_string();
+                    step.error = Some("Interrupted by orchestrator restart".to_string());
+                    step.completed_at = Some(now);
+                }
+                workflow.status = "failed".to_string();
+                workflow.updated_at = now;
+                state.workflow_store.workflows.insert(workflow_id, workflow);
+
+                persist_terminal_state(state, workflow_id).await;
+
+                // Notify in the background so webhook retries don't hold up startup
+                let notify_state = state.clone();
+                let client = client.clone();
+                tokio::spawn(async move {
+                    notify_webhook(&notify_state, &client, workflow_id).await;
+                });
+            }
+        }
+    }
+
+    Ok(recovered)
+}
+
+/// Periodically drop finished workflows older than the retention period from memory
+async fn eviction_loop(state: AppState, retention: Duration) {
+    loop {
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        evict_finished_workflows(&state, retention).await;
+    }
+}
+
+/// Write each expired finished workflow to storage and evict it once the write
+/// succeeds. Workflows that fail to persist stay in memory for the next pass.
+async fn evict_finished_workflows(state: &AppState, retention: Duration) -> usize {
+    let cutoff = Utc::now() - chrono::Duration::from_std(retention).unwrap_or_default();
+    let expired: Vec<WorkflowExecution> = state
+        .workflow_store
+        .workflows
+        .iter()
+        .filter(|workflow| {
+            !matches!(workflow.status.as_str(), "queued" | "running")
+                && workflow.updated_at < cutoff
+        })
+        .map(|workflow| workflow.clone())
+        .collect();
+
+    let mut evicted = 0;
+    for workflow in expired {
+        if let Err(e) = state.persistence.save_now(&workflow).await {
+            warn!(
+                "Keeping workflow {} in memory, failed to persist it: {}",
+                workflow.id, e
+            );
+            continue;
+        }
+
+        // A workflow updated since the snapshot was taken waits for the next pass
+        if state
+            .workflow_store
+            .workflows
+            .remove_if(&workflow.id, |_, current| {
+                current.updated_at == workflow.updated_at
+            })
+            .is_some()
+        {
+            evicted += 1;
+        }
+    }
+
+    if evicted > 0 {
+        info!("Evicted {} finished workflows from memory", evicted);
+    }
+    evicted
+}
+
 async fn health_check_loop(registry: Arc<McpRegistry>) {
     let client = reqwest::Client::new();
 
@@ -892,3 +1537,611 @@ async fn health_check_loop(registry: Arc<McpRegistry>) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use persistence::{MemoryWorkflowStorage, PersistenceResult, WorkflowStorage};
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+    use std::sync::Mutex;
+
+    struct MockMcp {
+        url: String,
+        flaky_calls: Arc<AtomicU32>,
+        webhooks: Arc<Mutex<Vec<serde_json::Value>>>,
+    }
+
+    /// Serve a mock MCP service: `/ok` succeeds, `/fail` always fails, `/flaky`
+    /// fails its first two calls and `/slow` takes five seconds. `/v1/echo`
+    /// returns the request body. `/hook` records
+    /// webhook payloads, `/hook-flaky` fails its first call and `/hook-down` always fails.
+    async fn mock_mcp() -> MockMcp {
+        let flaky_calls = Arc::new(AtomicU32::new(0));
+        let calls = flaky_calls.clone();
+        let webhooks = Arc::new(Mutex::new(Vec::new()));
+        let received = webhooks.clone();
+        let hook_calls = Arc::new(AtomicU32::new(0));
+
+        let app = Router::new()
+            .route(
+                "/ok",
+                post(|| async { Json(serde_json::json!({ "content": "ok" })) }),
+            )
+            .route(
+                "/fail",
+                post(|| async { StatusCode::INTERNAL_SERVER_ERROR }),
+            )
+            .route(
+                "/flaky",
+                post(move || {
+                    let calls = calls.clone();
+                    async move {
+                        if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                            Err(StatusCode::SERVICE_UNAVAILABLE)
+                        } else {
+                            Ok(Json(serde_json::json!({ "content": "ok" })))
+                        }
+                    }
+                }),
+            )
+            .route(
+                "/v1/echo",
+                post(|Json(body): Json<serde_json::Value>| async move { Json(body) }),
+            )
+            .route(
+                "/slow",
+                post(|| async {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    Json(serde_json::json!({ "content": "late" }))
+                }),
+            )
+            .route(
+                "/hook",
+                post(move |Json(payload): Json<serde_json::Value>| {
+                    let received = received.clone();
+                    async move {
+                        received.lock().unwrap().push(payload);
+                        StatusCode::OK
+                    }
+                }),
+            )
+            .route(
+                "/hook-flaky",
+                post(move || {
+                    let calls = hook_calls.clone();
+                    async move {
+                        if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                            StatusCode::BAD_GATEWAY
+                        } else {
+                            StatusCode::OK
+                        }
+                    }
+                }),
+            )
+            .route(
+                "/hook-down",
+                post(|| async { StatusCode::INTERNAL_SERVER_ERROR }),
+            );
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        MockMcp {
+            url,
+            flaky_calls,
+            webhooks,
+        }
+    }
+
+    fn step(name: &str, endpoint: &str, depends_on: &[&WorkflowStep]) -> WorkflowStep {
+        WorkflowStep {
+            step_id: Uuid::new_v4(),
+            step_name: name.to_string(),
+            mcp_service: "mock-mcp".to_string(),
+            endpoint: endpoint.to_string(),
+            parameters: HashMap::new(),
+            depends_on: depends_on.iter().map(|step| step.step_id).collect(),
+            timeout_seconds: None,
+            status: "pending".to_string(),
+            result: None,
+            error: None,
+            processing_time_ms: None,
+            started_at: None,
+            completed_at: None,
+        }
+    }
+
+    fn options(failure_strategy: &str, max_retries: Option<u32>) -> WorkflowOptions {
+        WorkflowOptions {
+            timeout_seconds: None,
+            parallel_execution: None,
+            failure_strategy: Some(failure_strategy.to_string()),
+            notification_webhook: None,
+            max_retries,
+            retry_backoff_ms: Some(1),
+        }
+    }
+
+    /// Orchestrator state with in-memory persistence and the mock registered as "mock-mcp"
+    fn test_state(mock: &MockMcp) -> AppState {
+        let state = AppState {
+            service_name: "mcp-orchestrator".to_string(),
+            mcp_registry: Arc::new(McpRegistry {
+                services: DashMap::new(),
+            }),
+            workflow_store: Arc::new(WorkflowStore {
+                workflows: DashMap::new(),
+            }),
+            persistence: Arc::new(WorkflowPersistence::in_memory()),
+        };
+        state.mcp_registry.services.insert(
+            "mock-mcp".to_string(),
+            McpService {
+                name: "mock-mcp".to_string(),
+                url: mock.url.clone(),
+                capabilities: vec![],
+                status: "active".to_string(),
+                last_health_check: Utc::now(),
+            },
+        );
+        state
+    }
+
+    fn workflow(steps: Vec<WorkflowStep>, options: WorkflowOptions) -> WorkflowExecution {
+        WorkflowExecution {
+            id: Uuid::new_v4(),
+            workflow_type: "test".to_string(),
+            status: "queued".to_string(),
+            steps,
+            results: HashMap::new(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            options: Some(options),
+            notification: None,
+        }
+    }
+
+    /// Run a workflow against a mock MCP service until it finishes
+    async fn execute(
+        mock: &MockMcp,
+        steps: Vec<WorkflowStep>,
+        options: WorkflowOptions,
+    ) -> WorkflowExecution {
+        let state = test_state(mock);
+        let workflow = workflow(steps, options);
+        let workflow_id = workflow.id;
+        state.workflow_store.workflows.insert(workflow_id, workflow);
+
+        tokio::time::timeout(
+            Duration::from_secs(10),
+            execute_workflow(state.clone(), workflow_id),
+        )
+        .await
+        .expect("workflow did not finish");
+
+        let workflow = state.workflow_store.workflows.get(&workflow_id).unwrap();
+        workflow.clone()
+    }
+
+    /// Run a workflow to completion, returning its status and step statuses by name
+    async fn run(
+        steps: Vec<WorkflowStep>,
+        options: WorkflowOptions,
+    ) -> (String, HashMap<String, String>, Arc<AtomicU32>) {
+        let mock = mock_mcp().await;
+        let workflow = execute(&mock, steps, options).await;
+
+        let statuses = workflow
+            .steps
+            .iter()
+            .map(|step| (step.step_name.clone(), step.status.clone()))
+            .collect();
+        (workflow.status, statuses, mock.flaky_calls)
+    }
+
+    #[tokio::test]
+    async fn test_fail_fast_cancels_remaining_steps() {
+        // a (fails) and b run first; c waits on b
+        let a = step("a", "/fail", &[]);
+        let b = step("b", "/ok", &[]);
+        let c = step("c", "/ok", &[&b]);
+
+        let (status, steps, _) = run(vec![a, b, c], options("fail_fast", None)).await;
+
+        assert_eq!(status, "failed");
+        assert_eq!(steps["a"], "failed");
+        assert_eq!(steps["b"], "completed");
+        assert_eq!(steps["c"], "cancelled");
+    }
+
+    #[tokio::test]
+    async fn test_continue_skips_dependents_only() {
+        // d and its dependent e hang off the failing step a
+        let a = step("a", "/fail", &[]);
+        let b = step("b", "/ok", &[]);
+        let c = step("c", "/ok", &[&b]);
+        let d = step("d", "/ok", &[&a]);
+        let e = step("e", "/ok", &[&c, &d]);
+
+        let (status, steps, _) = run(vec![a, b, c, d, e], options("continue", None)).await;
+
+        assert_eq!(status, "failed");
+        assert_eq!(steps["a"], "failed");
+        assert_eq!(steps["b"], "completed");
+        assert_eq!(steps["c"], "completed");
+        assert_eq!(steps["d"], "skipped");
+        assert_eq!(steps["e"], "skipped");
+    }
+
+    #[tokio::test]
+    async fn test_retry_recovers_flaky_step() {
+        let a = step("a", "/flaky", &[]);
+        let b = step("b", "/ok", &[&a]);
+
+        let (status, steps, flaky_calls) = run(vec![a, b], options("retry", Some(3))).await;
+
+        assert_eq!(status, "completed");
+        assert_eq!(steps["a"], "completed");
+        assert_eq!(steps["b"], "completed");
+        assert_eq!(flaky_calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_gives_up_after_max_retries() {
+        let a = step("a", "/flaky", &[]);
+        let b = step("b", "/ok", &[&a]);
+        let c = step("c", "/ok", &[]);
+
+        let (status, steps, flaky_calls) = run(vec![a, b, c], options("retry", Some(1))).await;
+
+        assert_eq!(status, "failed");
+        assert_eq!(steps["a"], "failed");
+        assert_eq!(steps["b"], "skipped");
+        assert_eq!(steps["c"], "completed");
+        assert_eq!(flaky_calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_step_timeout_fails_step() {
+        // a has its own timeout; c falls back to the workflow timeout
+        let mut a = step("a", "/slow", &[]);
+        a.timeout_seconds = Some(1);
+        let b = step("b", "/ok", &[&a]);
+        let c = step("c", "/slow", &[]);
+        let mut options = options("continue", None);
+        options.timeout_seconds = Some(1);
+
+        let started = std::time::Instant::now();
+        let workflow = execute(&mock_mcp().await, vec![a, b, c], options).await;
+
+        assert!(started.elapsed() < Duration::from_secs(4));
+        assert_eq!(workflow.status, "failed");
+        for step in &workflow.steps {
+            match step.step_name.as_str() {
+                "b" => assert_eq!(step.status, "skipped"),
+                _ => {
+                    assert_eq!(step.status, "failed");
+                    assert!(step.error.as_deref().unwrap().contains("timed out"));
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_webhook_fires_on_terminal_state() {
+        let mock = mock_mcp().await;
+        let mut options = options("fail_fast", None);
+        options.notification_webhook = Some(format!("{}/hook", mock.url));
+
+        let workflow = execute(&mock, vec![step("a", "/ok", &[])], options).await;
+
+        let delivery = workflow.notification.unwrap();
+        assert_eq!(delivery.status, "delivered");
+        assert_eq!(delivery.attempts, 1);
+        assert_eq!(delivery.response_status, Some(200));
+
+        let webhooks = mock.webhooks.lock().unwrap();
+        assert_eq!(webhooks.len(), 1);
+        assert_eq!(webhooks[0]["workflow_id"], workflow.id.to_string());
+        assert_eq!(webhooks[0]["status"], "completed");
+        assert_eq!(webhooks[0]["steps"][0]["status"], "completed");
+    }
+
+    #[tokio::test]
+    async fn test_webhook_delivery_retries() {
+        let mock = mock_mcp().await;
+        let client = reqwest::Client::new();
+        let payload = serde_json::json!({ "status": "completed" });
+        let backoff = Duration::from_millis(1);
+
+        let delivery = deliver_webhook(
+            &client,
+            &format!("{}/hook-flaky", mock.url),
+            &payload,
+            3,
+            backoff,
+        )
+        .await;
+        assert_eq!(delivery.status, "delivered");
+        assert_eq!(delivery.attempts, 2);
+
+        let delivery = deliver_webhook(
+            &client,
+            &format!("{}/hook-down", mock.url),
+            &payload,
+            3,
+            backoff,
+        )
+        .await;
+        assert_eq!(delivery.status, "failed");
+        assert_eq!(delivery.attempts, 3);
+        assert_eq!(delivery.response_status, Some(500));
+        assert!(delivery.error.is_some());
+    }
+
+    #[test]
+    fn test_failure_strategy_from_options() {
+        assert_eq!(
+            FailureStrategy::from_options(None),
+            FailureStrategy::FailFast
+        );
+        assert_eq!(
+            FailureStrategy::from_options(Some(&options("continue", None))),
+            FailureStrategy::Continue
+        );
+        assert_eq!(
+            FailureStrategy::from_options(Some(&options("retry", None))),
+            FailureStrategy::Retry {
+                max_retries: DEFAULT_MAX_RETRIES,
+                backoff: Duration::from_millis(1),
+            }
+        );
+        assert_eq!(
+            FailureStrategy::from_options(Some(&options("unknown", None))),
+            FailureStrategy::FailFast
+        );
+    }
+
+    #[tokio::test]
+    async fn test_finished_workflow_is_persisted_and_served_after_eviction() {
+        let mock = mock_mcp().await;
+        let state = test_state(&mock);
+        let workflow = workflow(vec![step("a", "/ok", &[])], options("fail_fast", None));
+        let workflow_id = workflow.id;
+        state.workflow_store.workflows.insert(workflow_id, workflow);
+
+        execute_workflow(state.clone(), workflow_id).await;
+
+        // The terminal state is written directly, not left to the batch writer
+        let stored = state.persistence.load(workflow_id).await.unwrap().unwrap();
+        assert_eq!(stored.status, "completed");
+        assert_eq!(stored.steps[0].status, "completed");
+
+        assert_eq!(evict_finished_workflows(&state, Duration::ZERO).await, 1);
+        let response = get_workflow(State(state.clone()), Path(workflow_id))
+            .await
+            .unwrap()
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let missing = get_workflow(State(state), Path(Uuid::new_v4())).await;
+        assert_eq!(missing.err(), Some(StatusCode::NOT_FOUND));
+    }
+
+    /// Storage whose writes fail while `down` is set
+    #[derive(Default)]
+    struct FlakyStorage {
+        inner: MemoryWorkflowStorage,
+        down: AtomicBool,
+    }
+
+    #[async_trait::async_trait]
+    impl WorkflowStorage for FlakyStorage {
+        async fn save_batch(&self, workflows: &[WorkflowExecution]) -> PersistenceResult<()> {
+            if self.down.load(Ordering::SeqCst) {
+                return Err(sqlx::Error::PoolTimedOut.into());
+            }
+            self.inner.save_batch(workflows).await
+        }
+
+        async fn load(&self, id: Uuid) -> PersistenceResult<Option<WorkflowExecution>> {
+            self.inner.load(id).await
+        }
+
+        async fn load_incomplete(&self) -> PersistenceResult<Vec<WorkflowExecution>> {
+            self.inner.load_incomplete().await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_finished_workflow_stays_in_memory_until_persisted() {
+        let mock = mock_mcp().await;
+        let storage = Arc::new(FlakyStorage::default());
+        let state = AppState {
+            persistence: Arc::new(WorkflowPersistence::new(storage.clone())),
+            ..test_state(&mock)
+        };
+        let mut finished = workflow(vec![step("a", "/ok", &[])], options("fail_fast", None));
+        finished.status = "completed".to_string();
+        let workflow_id = finished.id;
+        state.workflow_store.workflows.insert(workflow_id, finished);
+
+        storage.down.store(true, Ordering::SeqCst);
+        assert_eq!(evict_finished_workflows(&state, Duration::ZERO).await, 0);
+        assert!(state.workflow_store.workflows.contains_key(&workflow_id));
+
+        storage.down.store(false, Ordering::SeqCst);
+        assert_eq!(evict_finished_workflows(&state, Duration::ZERO).await, 1);
+        assert!(!state.workflow_store.workflows.contains_key(&workflow_id));
+        let stored = state.persistence.load(workflow_id).await.unwrap().unwrap();
+        assert_eq!(stored.status, "completed");
+    }
+
+    #[tokio::test]
+    async fn test_execute_function_calls_mcp_endpoint() {
+        let mock = mock_mcp().await;
+        let state = test_state(&mock);
+        let call = |function: &str| FunctionCallRequest {
+            function: function.to_string(),
+            parameters: HashMap::from([("topic".to_string(), serde_json::json!("rust"))]),
+            timeout_seconds: Some(1),
+        };
+
+        let (status, Json(body)) =
+            execute_function(State(state.clone()), Json(call("mock-mcp.echo"))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["mcp_service"], "mock-mcp");
+        assert_eq!(body["result"], serde_json::json!({ "topic": "rust" }));
+
+        let (status, _) = execute_function(State(state.clone()), Json(call("mock-mcp"))).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = execute_function(State(state.clone()), Json(call("other.echo"))).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _) = execute_function(State(state), Json(call("mock-mcp.missing"))).await;
+        assert_eq!(status, StatusCode::BAD_GATEWAY);
+
+        assert_eq!(
+            parse_function("demo-content-mcp.content.generate"),
+            Some(("demo-content-mcp", "/v1/content/generate".to_string()))
+        );
+        assert_eq!(parse_function("demo-content-mcp.content..generate"), None);
+    }
+
+    /// A workflow whose first step finished and whose second step was cut off
+    fn interrupted_workflow() -> WorkflowExecution {
+        let mut first = step("a", "/ok", &[]);
+        first.status = "completed".to_string();
+        first.result = Some(serde_json::json!({ "ok": true }));
+        let mut second = step("b", "/ok", &[&first]);
+        second.status = "running".to_string();
+        second.started_at = Some(Utc::now());
+
+        let mut workflow = workflow(vec![first, second], options("fail_fast", None));
+        workflow.status = "running".to_string();
+        workflow
+    }
+
+    #[tokio::test]
+    async fn test_recovery_fails_interrupted_workflows() {
+        let mock = mock_mcp().await;
+        let state = test_state(&mock);
+        let interrupted = interrupted_workflow();
+        state.persistence.save_now(&interrupted).await.unwrap();
+
+        let recovered = recover_workflows(&state, RecoveryPolicy::Fail)
+            .await
+            .unwrap();
+
+        assert_eq!(recovered, 1);
+        let stored = state
+            .persistence
+            .load(interrupted.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.status, "failed");
+        assert_eq!(stored.steps[0].status, "completed");
+        assert_eq!(stored.steps[1].status, "failed");
+        assert!(state
+            .persistence
+            .load_incomplete()
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_recovery_does_not_wait_for_webhooks() {
+        let mock = mock_mcp().await;
+        let state = test_state(&mock);
+        let mut interrupted = interrupted_workflow();
+        interrupted.options.as_mut().unwrap().notification_webhook =
+            Some(format!("{}/hook-down", mock.url));
+        state.persistence.save_now(&interrupted).await.unwrap();
+
+        // Delivery to a failing endpoint retries with backoff for seconds
+        let recovered = tokio::time::timeout(
+            Duration::from_millis(500),
+            recover_workflows(&state, RecoveryPolicy::Fail),
+        )
+        .await
+        .expect("recovery waited for webhook delivery")
+        .unwrap();
+        assert_eq!(recovered, 1);
+    }
+
+    #[tokio::test]
+    async fn test_recovery_resumes_interrupted_workflows() {
+        let mock = mock_mcp().await;
+        let state = test_state(&mock);
+        let interrupted = interrupted_workflow();
+        state.persistence.save_now(&interrupted).await.unwrap();
+
+        recover_workflows(&state, RecoveryPolicy::Resume)
+            .await
+            .unwrap();
+
+        tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                let status = state
+                    .workflow_store
+                    .workflows
+                    .get(&interrupted.id)
+                    .map(|workflow| workflow.status.clone());
+                if status.as_deref() == Some("completed") {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("resumed workflow did not finish");
+
+        let stored = state
+            .persistence
+            .load(interrupted.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.status, "completed");
+        assert_eq!(stored.steps[1].status, "completed");
+    }
+    #[tokio::test]
+    async fn test_custom_dag_cycle_is_bad_request() {
+        let state = AppState {
+            service_name: "mcp-orchestrator".to_string(),
+            mcp_registry: Arc::new(McpRegistry {
+                services: DashMap::new(),
+            }),
+            workflow_store: Arc::new(WorkflowStore {
+                workflows: DashMap::new(),
+            }),
+            persistence: Arc::new(WorkflowPersistence::in_memory()),
+        };
+        register_default_mcps(&state.mcp_registry).await;
+
+        let request: WorkflowRequest = serde_json::from_value(serde_json::json!({
+            "workflow_type": "custom_dag",
+            "steps": [
+                { "name": "draft", "mcp_service": "demo-content-mcp", "endpoint": "/v1/content/generate", "depends_on": ["review"] },
+                { "name": "review", "mcp_service": "text-processing-mcp", "endpoint": "/v1/text/analyze", "depends_on": ["draft"] },
+            ],
+        }))
+        .unwrap();
+
+        let Err((status, Json(body))) = create_workflow(State(state.clone()), Json(request)).await
+        else {
+            panic!("cyclic graph was accepted");
+        };
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body["cycle"],
+            serde_json::json!(["draft", "review", "draft"])
+        );
+        assert!(state.workflow_store.workflows.is_empty());
+    }
+}
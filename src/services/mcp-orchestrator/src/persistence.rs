@@ -0,0 +1,428 @@
+//! Workflow persistence
+//!
+//! Workflow snapshots are written to a [`WorkflowStorage`] so history and
+//! in-flight executions survive restarts. Step updates go through a background
+//! writer that coalesces snapshots per workflow and flushes them in batches;
+//! terminal transitions are written synchronously with
+//! [`WorkflowPersistence::save_now`]. Every write carries the snapshot's
+//! `updated_at`, and storages ignore snapshots older than the one they hold, so
+//! a late batch can never roll back a terminal state.
+
+use crate::WorkflowExecution;
+use async_trait::async_trait;
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use sqlx::{PgPool, Row};
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use thiserror::Error;
+use tokio::sync::{mpsc, oneshot};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Flush once this many workflows have pending snapshots
+const WRITE_BATCH_SIZE: usize = 50;
+/// Flush pending snapshots at least this often
+const WRITE_FLUSH_INTERVAL_MS: u64 = 250;
+
+#[derive(Debug, Error)]
+pub enum PersistenceError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Persistence writer has stopped")]
+    WriterStopped,
+}
+
+pub type PersistenceResult<T> = Result<T, PersistenceError>;
+
+/// What to do with workflows that were queued or running when the service stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecoveryPolicy {
+    /// Re-queue the workflow; interrupted steps run again
+    Resume,
+    /// Mark the workflow and its unfinished steps as failed
+    Fail,
+}
+
+impl RecoveryPolicy {
+    pub fn parse(value: Option<&str>) -> Self {
+        match value {
+            Some("resume") => RecoveryPolicy::Resume,
+            Some("fail") | None => RecoveryPolicy::Fail,
+            Some(other) => {
+                warn!("Unknown recovery policy '{}', using fail", other);
+                RecoveryPolicy::Fail
+            }
+        }
+    }
+}
+
+/// Backend that stores workflow snapshots
+#[async_trait]
+pub trait WorkflowStorage: Send + Sync {
+    /// Store snapshots, skipping any older than the stored version
+    async fn save_batch(&self, workflows: &[WorkflowExecution]) -> PersistenceResult<()>;
+
+    /// Load a workflow by ID
+    async fn load(&self, id: Uuid) -> PersistenceResult<Option<WorkflowExecution>>;
+
+    /// Load workflows that were queued or running, oldest first
+    async fn load_incomplete(&self) -> PersistenceResult<Vec<WorkflowExecution>>;
+}
+
+fn is_incomplete(status: &str) -> bool {
+    matches!(status, "queued" | "running")
+}
+
+/// In-memory storage, for tests and deployments without a database
+#[derive(Default)]
+pub struct MemoryWorkflowStorage {
+    workflows: DashMap<Uuid, WorkflowExecution>,
+}
+
+impl MemoryWorkflowStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl WorkflowStorage for MemoryWorkflowStorage {
+    async fn save_batch(&self, workflows: &[WorkflowExecution]) -> PersistenceResult<()> {
+        for workflow in workflows {
+            match self.workflows.entry(workflow.id) {
+                Entry::Occupied(mut stored) => {
+                    if stored.get().updated_at <= workflow.updated_at {
+                        stored.insert(workflow.clone());
+                    }
+                }
+                Entry::Vacant(slot) => {
+                    slot.insert(workflow.clone());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn load(&self, id: Uuid) -> PersistenceResult<Option<WorkflowExecution>> {
+        Ok(self.workflows.get(&id).map(|workflow| workflow.clone()))
+    }
+
+    async fn load_incomplete(&self) -> PersistenceResult<Vec<WorkflowExecution>> {
+        let mut workflows: Vec<_> = self
+            .workflows
+            .iter()
+            .filter(|workflow| is_incomplete(&workflow.status))
+            .map(|workflow| workflow.clone())
+            .collect();
+        workflows.sort_by_key(|workflow| workflow.created_at);
+        Ok(workflows)
+    }
+}
+
+/// Schema for the PostgreSQL workflow storage, applied one statement at a time
+const POSTGRES_SCHEMA: &[&str] = &[
+    r#"
+    CREATE TABLE IF NOT EXISTS orchestrator_workflows (
+        id UUID PRIMARY KEY,
+        workflow_type VARCHAR(100) NOT NULL,
+        status VARCHAR(20) NOT NULL,
+        execution JSONB NOT NULL,
+        created_at TIMESTAMPTZ NOT NULL,
+        updated_at TIMESTAMPTZ NOT NULL
+    )
+    "#,
+    "CREATE INDEX IF NOT EXISTS orchestrator_workflows_status_idx ON orchestrator_workflows (status, created_at)",
+];
+
+/// PostgreSQL workflow storage
+///
+/// The full execution, steps included, is stored as JSONB next to indexed
+/// `status` and timestamp columns.
+#[derive(Clone)]
+pub struct PostgresWorkflowStorage {
+    pool: PgPool,
+}
+
+impl PostgresWorkflowStorage {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Create the storage table and indexes if they do not exist
+    pub async fn run_migrations(&self) -> PersistenceResult<()> {
+        info!("Running workflow storage migrations");
+
+        for statement in POSTGRES_SCHEMA {
+            sqlx::query(statement).execute(&self.pool).await?;
+        }
+
+        Ok(())
+    }
+
+    fn decode(row: &sqlx::postgres::PgRow) -> PersistenceResult<WorkflowExecution> {
+        let execution: serde_json::Value = row.try_get("execution")?;
+        Ok(serde_json::from_value(execution)?)
+    }
+}
+
+#[async_trait]
+impl WorkflowStorage for PostgresWorkflowStorage {
+    async fn save_batch(&self, workflows: &[WorkflowExecution]) -> PersistenceResult<()> {
+        let mut transaction = self.pool.begin().await?;
+
+        for workflow in workflows {
+            sqlx::query(
+                r#"
+                INSERT INTO orchestrator_workflows
+                    (id, workflow_type, status, execution, created_at, updated_at)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                ON CONFLICT (id) DO UPDATE SET
+                    status = EXCLUDED.status,
+                    execution = EXCLUDED.execution,
+                    updated_at = EXCLUDED.updated_at
+                WHERE orchestrator_workflows.updated_at <= EXCLUDED.updated_at
+                "#,
+            )
+            .bind(workflow.id)
+            .bind(&workflow.workflow_type)
+            .bind(&workflow.status)
+            .bind(serde_json::to_value(workflow)?)
+            .bind(workflow.created_at)
+            .bind(workflow.updated_at)
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    async fn load(&self, id: Uuid) -> PersistenceResult<Option<WorkflowExecution>> {
+        let row = sqlx::query("SELECT execution FROM orchestrator_workflows WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.as_ref().map(Self::decode).transpose()
+    }
+
+    async fn load_incomplete(&self) -> PersistenceResult<Vec<WorkflowExecution>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT execution FROM orchestrator_workflows
+            WHERE status IN ('queued', 'running')
+            ORDER BY created_at
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(Self::decode).collect()
+    }
+}
+
+enum WriterCommand {
+    Save(Box<WorkflowExecution>),
+    Flush(oneshot::Sender<PersistenceResult<()>>),
+}
+
+/// Handle used by the orchestrator to persist workflow state
+pub struct WorkflowPersistence {
+    storage: Arc<dyn WorkflowStorage>,
+    writer: mpsc::UnboundedSender<WriterCommand>,
+}
+
+impl WorkflowPersistence {
+    /// Wrap a storage and start its background writer
+    pub fn new(storage: Arc<dyn WorkflowStorage>) -> Self {
+        let (writer, commands) = mpsc::unbounded_channel();
+        tokio::spawn(run_writer(
+            storage.clone(),
+            commands,
+            WRITE_BATCH_SIZE,
+            Duration::from_millis(WRITE_FLUSH_INTERVAL_MS),
+        ));
+
+        Self { storage, writer }
+    }
+
+    /// Persistence backed by process memory only
+    pub fn in_memory() -> Self {
+        Self::new(Arc::new(MemoryWorkflowStorage::new()))
+    }
+
+    /// Queue a snapshot for the next batch without waiting for the write
+    pub fn save(&self, workflow: &WorkflowExecution) {
+        if self
+            .writer
+            .send(WriterCommand::Save(Box::new(workflow.clone())))
+            .is_err()
+        {
+            error!(
+                "Persistence writer has stopped, dropping update for workflow {}",
+                workflow.id
+            );
+        }
+    }
+
+    /// Write a snapshot and wait until the storage has accepted it
+    pub async fn save_now(&self, workflow: &WorkflowExecution) -> PersistenceResult<()> {
+        self.storage
+            .save_batch(std::slice::from_ref(workflow))
+            .await
+    }
+
+    /// Wait until every queued snapshot has been written
+    pub async fn flush(&self) -> PersistenceResult<()> {
+        let (done, result) = oneshot::channel();
+        self.writer
+            .send(WriterCommand::Flush(done))
+            .map_err(|_| PersistenceError::WriterStopped)?;
+        result.await.map_err(|_| PersistenceError::WriterStopped)?
+    }
+
+    pub async fn load(&self, id: Uuid) -> PersistenceResult<Option<WorkflowExecution>> {
+        self.storage.load(id).await
+    }
+
+    pub async fn load_incomplete(&self) -> PersistenceResult<Vec<WorkflowExecution>> {
+        self.storage.load_incomplete().await
+    }
+}
+
+/// Collect snapshots, keeping only the newest per workflow, and write them in
+/// batches. A failed batch stays pending and is retried on the next flush.
+async fn run_writer(
+    storage: Arc<dyn WorkflowStorage>,
+    mut commands: mpsc::UnboundedReceiver<WriterCommand>,
+    batch_size: usize,
+    flush_interval: Duration,
+) {
+    let mut pending: HashMap<Uuid, WorkflowExecution> = HashMap::new();
+    let mut interval = tokio::time::interval(flush_interval);
+
+    loop {
+        tokio::select! {
+            command = commands.recv() => match command {
+                Some(WriterCommand::Save(workflow)) => {
+                    match pending.get(&workflow.id) {
+                        Some(queued) if queued.updated_at > workflow.updated_at => {}
+                        _ => {
+                            pending.insert(workflow.id, *workflow);
+                        }
+                    }
+                    if pending.len() >= batch_size {
+                        let _ = write_pending(storage.as_ref(), &mut pending).await;
+                    }
+                }
+                Some(WriterCommand::Flush(done)) => {
+                    let _ = done.send(write_pending(storage.as_ref(), &mut pending).await);
+                }
+                None => {
+                    let _ = write_pending(storage.as_ref(), &mut pending).await;
+                    return;
+                }
+            },
+            _ = interval.tick() => {
+                let _ = write_pending(storage.as_ref(), &mut pending).await;
+            }
+        }
+    }
+}
+
+async fn write_pending(
+    storage: &dyn WorkflowStorage,
+    pending: &mut HashMap<Uuid, WorkflowExecution>,
+) -> PersistenceResult<()> {
+    if pending.is_empty() {
+        return Ok(());
+    }
+
+    let batch: Vec<WorkflowExecution> = pending.values().cloned().collect();
+    match storage.save_batch(&batch).await {
+        Ok(()) => {
+            pending.clear();
+            Ok(())
+        }
+        Err(e) => {
+            warn!(
+                "Failed to persist {} workflow updates, will retry: {}",
+                batch.len(),
+                e
+            );
+            Err(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn workflow(status: &str) -> WorkflowExecution {
+        WorkflowExecution {
+            id: Uuid::new_v4(),
+            workflow_type: "test".to_string(),
+            status: status.to_string(),
+            steps: vec![],
+            results: HashMap::new(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            options: None,
+            notification: None,
+        }
+    }
+
+    fn advance(workflow: &WorkflowExecution, status: &str) -> WorkflowExecution {
+        let mut next = workflow.clone();
+        next.status = status.to_string();
+        next.updated_at = workflow.updated_at + chrono::Duration::milliseconds(1);
+        next
+    }
+
+    #[tokio::test]
+    async fn test_batched_saves_are_written_on_flush() {
+        let storage = Arc::new(MemoryWorkflowStorage::new());
+        let persistence = WorkflowPersistence::new(storage.clone());
+        let queued = workflow("queued");
+        let running = advance(&queued, "running");
+
+        persistence.save(&running);
+        persistence.save(&queued); // older snapshot arriving late
+        persistence.flush().await.unwrap();
+
+        let stored = storage.load(queued.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, "running");
+        assert_eq!(storage.load_incomplete().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_stale_snapshot_does_not_overwrite_terminal_state() {
+        let persistence = WorkflowPersistence::in_memory();
+        let running = workflow("running");
+        let completed = advance(&running, "completed");
+
+        persistence.save(&running);
+        persistence.save_now(&completed).await.unwrap();
+        persistence.flush().await.unwrap();
+
+        let stored = persistence.load(running.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, "completed");
+        assert!(persistence.load_incomplete().await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_recovery_policy_parse() {
+        assert_eq!(RecoveryPolicy::parse(None), RecoveryPolicy::Fail);
+        assert_eq!(
+            RecoveryPolicy::parse(Some("resume")),
+            RecoveryPolicy::Resume
+        );
+        assert_eq!(RecoveryPolicy::parse(Some("other")), RecoveryPolicy::Fail);
+    }
+}
@@ -0,0 +1,279 @@
+//! Step parameter templates
+//!
+//! Parameters may reference the results of completed steps with
+//! `{{<step_name>.<path>}}`, where the path walks object fields and array
+//! indices, e.g. `{{analyze.keywords[0].word}}`. A parameter that is exactly one
+//! template takes the referenced JSON value as-is; templates embedded in longer
+//! strings are interpolated as text. References that cannot be resolved are
+//! errors, never passed through.
+
+use crate::WorkflowStep;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// One segment of a template path
+#[derive(Debug, PartialEq)]
+enum Segment<'a> {
+    Field(&'a str),
+    Index(usize),
+}
+
+/// Resolve every template in a step's parameters against completed steps
+pub fn resolve_parameters(
+    parameters: &HashMap<String, Value>,
+    steps: &[WorkflowStep],
+) -> Result<HashMap<String, Value>, String> {
+    parameters
+        .iter()
+        .map(|(key, value)| {
+            resolve_value(value, steps)
+                .map(|value| (key.clone(), value))
+                .map_err(|e| format!("Parameter '{}': {}", key, e))
+        })
+        .collect()
+}
+
+/// Resolve templates in a value, recursing into arrays and objects
+pub fn resolve_value(value: &Value, steps: &[WorkflowStep]) -> Result<Value, String> {
+    match value {
+        Value::String(text) => render(text, steps),
+        Value::Array(items) => items
+            .iter()
+            .map(|item| resolve_value(item, steps))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+        Value::Object(fields) => fields
+            .iter()
+            .map(|(key, value)| resolve_value(value, steps).map(|value| (key.clone(), value)))
+            .collect::<Result<serde_json::Map<_, _>, _>>()
+            .map(Value::Object),
+        other => Ok(other.clone()),
+    }
+}
+
+/// Render a string containing zero or more `{{...}}` references
+fn render(text: &str, steps: &[WorkflowStep]) -> Result<Value, String> {
+    let trimmed = text.trim();
+    if let Some(expression) = trimmed
+        .strip_prefix("{{")
+        .and_then(|rest| rest.strip_suffix("}}"))
+        .filter(|expression| !expression.contains("{{") && !expression.contains("}}"))
+    {
+        return lookup(expression.trim(), steps).cloned();
+    }
+
+    let mut rendered = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find("}}")
+            .ok_or_else(|| format!("Unclosed template in '{}'", text))?;
+
+        match lookup(after[..end].trim(), steps)? {
+            Value::String(value) => rendered.push_str(value),
+            value => rendered.push_str(&value.to_string()),
+        }
+        rest = &after[end + 2..];
+    }
+    rendered.push_str(rest);
+
+    Ok(Value::String(rendered))
+}
+
+/// Find the value an expression such as `analyze.keywords[0].word` refers to
+fn lookup<'a>(expression: &str, steps: &'a [WorkflowStep]) -> Result<&'a Value, String> {
+    let name_end = expression.find(['.', '[']).unwrap_or(expression.len());
+    let (step_name, path) = expression.split_at(name_end);
+    if step_name.is_empty() {
+        return Err(format!(
+            "Template '{{{{{}}}}}' has no step name",
+            expression
+        ));
+    }
+
+    let step = steps
+        .iter()
+        .find(|step| step.step_name == step_name)
+        .ok_or_else(|| format!("Template references unknown step '{}'", step_name))?;
+    let result = match (&step.status[..], &step.result) {
+        ("completed", Some(result)) => result,
+        _ => {
+            return Err(format!(
+                "Template references step '{}', which has not completed (status: {})",
+                step_name, step.status
+            ))
+        }
+    };
+
+    let mut current = result;
+    for segment in parse_path(path)? {
+        current = match segment {
+            Segment::Field(field) => current.get(field),
+            Segment::Index(index) => current.get(index),
+        }
+        .ok_or_else(|| {
+            format!(
+                "Step '{}' result has no value at '{}'",
+                step_name,
+                expression[name_end..].trim_start_matches('.')
+            )
+        })?;
+    }
+
+    Ok(current)
+}
+
+fn parse_path(path: &str) -> Result<Vec<Segment<'_>>, String> {
+    let mut segments = Vec::new();
+    let mut rest = path;
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('.') {
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            if end == 0 {
+                return Err(format!("Empty field name in path '{}'", path));
+            }
+            segments.push(Segment::Field(&after[..end]));
+            rest = &after[end..];
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let end = after
+                .find(']')
+                .ok_or_else(|| format!("Unclosed index in path '{}'", path))?;
+            let index = after[..end]
+                .trim()
+                .parse()
+                .map_err(|_| format!("Invalid index '{}' in path '{}'", &after[..end], path))?;
+            segments.push(Segment::Index(index));
+            rest = &after[end + 1..];
+        } else {
+            return Err(format!("Invalid path '{}'", path));
+        }
+    }
+
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn step(name: &str, status: &str, result: Option<Value>) -> WorkflowStep {
+        WorkflowStep {
+            step_id: Uuid::new_v4(),
+            step_name: name.to_string(),
+            mcp_service: "test-mcp".to_string(),
+            endpoint: "/".to_string(),
+            parameters: HashMap::new(),
+            depends_on: vec![],
+            timeout_seconds: None,
+            status: status.to_string(),
+            result,
+            error: None,
+            processing_time_ms: None,
+            started_at: None,
+            completed_at: None,
+        }
+    }
+
+    fn steps() -> Vec<WorkflowStep> {
+        vec![
+            step(
+                "generate",
+                "completed",
+                Some(serde_json::json!({ "content": "Rust is fast", "word_count": 3 })),
+            ),
+            step(
+                "analyze",
+                "completed",
+                Some(serde_json::json!({
+                    "keywords": [
+                        { "word": "rust", "score": 0.9 },
+                        { "word": "fast", "score": 0.7 },
+                    ],
+                })),
+            ),
+            step("publish", "pending", None),
+        ]
+    }
+
+    #[test]
+    fn test_resolves_nested_paths_and_indices() {
+        let steps = steps();
+
+        assert_eq!(
+            resolve_value(&serde_json::json!("{{analyze.keywords[0].word}}"), &steps).unwrap(),
+            "rust"
+        );
+        assert_eq!(
+            resolve_value(&serde_json::json!("{{ analyze.keywords[1] }}"), &steps).unwrap(),
+            serde_json::json!({ "word": "fast", "score": 0.7 })
+        );
+        assert_eq!(
+            resolve_value(&serde_json::json!("{{generate.word_count}}"), &steps).unwrap(),
+            3
+        );
+        assert_eq!(
+            resolve_value(&serde_json::json!("{{generate}}"), &steps).unwrap()["content"],
+            "Rust is fast"
+        );
+    }
+
+    #[test]
+    fn test_interpolates_embedded_templates() {
+        let steps = steps();
+        let value = serde_json::json!({
+            "prompt": "Illustrate '{{generate.content}}' ({{generate.word_count}} words)",
+            "tags": ["{{analyze.keywords[0].word}}", "static"],
+        });
+
+        let resolved = resolve_value(&value, &steps).unwrap();
+
+        assert_eq!(resolved["prompt"], "Illustrate 'Rust is fast' (3 words)");
+        assert_eq!(resolved["tags"], serde_json::json!(["rust", "static"]));
+    }
+
+    #[test]
+    fn test_unresolved_references_are_errors() {
+        let steps = steps();
+        let unresolved = [
+            "{{missing.content}}",
+            "{{publish.url}}",
+            "{{generate.title}}",
+            "{{analyze.keywords[5].word}}",
+            "{{analyze.keywords[x]}}",
+            "{{analyze.keywords[0}}",
+            "{{.content}}",
+            "Prefix {{generate.content",
+        ];
+
+        for template in unresolved {
+            assert!(
+                resolve_value(&serde_json::json!(template), &steps).is_err(),
+                "resolved {}",
+                template
+            );
+        }
+
+        let parameters =
+            HashMap::from([("text".to_string(), serde_json::json!("{{generate.title}}"))]);
+        let error = resolve_parameters(&parameters, &steps).unwrap_err();
+        assert!(error.contains("'text'"));
+        assert!(error.contains("title"));
+    }
+
+    #[test]
+    fn test_plain_values_pass_through() {
+        let parameters = HashMap::from([
+            ("topic".to_string(), serde_json::json!("Rust")),
+            ("count".to_string(), serde_json::json!(2)),
+        ]);
+
+        assert_eq!(
+            resolve_parameters(&parameters, &steps()).unwrap(),
+            parameters
+        );
+    }
+}
@@ -14,12 +14,14 @@ use async_trait::async_trait;
 pub mod email;
 pub mod push;
 pub mod sms;
+pub mod sns;
 pub mod webhook;
 pub mod websocket;
 
 pub use email::EmailChannel;
 pub use push::PushChannel;
 pub use sms::SmsChannel;
+pub use sns::SnsChannel;
 pub use webhook::WebhookChannel;
 pub use websocket::WebSocketChannel;
 
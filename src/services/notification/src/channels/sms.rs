@@ -1,7 +1,7 @@
 //! SMS notification channel implementation using Twilio or AWS SNS
 
-use crate::channels::{ChannelInfo, NotificationChannel as NotificationChannelTrait};
-use crate::config::SmsConfig;
+use crate::channels::{ChannelInfo, NotificationChannel as NotificationChannelTrait, SnsChannel};
+use crate::config::{SmsConfig, SmsProvider};
 use crate::error::{NotificationError, Result};
 use ai_core_shared::types::NotificationResponse;
 use async_trait::async_trait;
@@ -11,6 +11,7 @@ use tracing::{info, warn};
 #[derive(Clone)]
 pub struct SmsChannel {
     config: SmsConfig,
+    sns: Option<SnsChannel>,
 }
 
 impl SmsChannel {
@@ -23,13 +24,24 @@ impl SmsChannel {
             return Err(NotificationError::config("SMS channel is disabled"));
         }
 
+        let sns = match config.provider {
+            SmsProvider::AwsSns => Some(SnsChannel::new(config).await?),
+            SmsProvider::Twilio => None,
+        };
+
         info!("SMS channel initialized successfully");
 
         Ok(Self {
             config: config.clone(),
+            sns,
         })
     }
 
+    /// Get the AWS SNS channel when SNS is the configured provider
+    pub fn sns_channel(&self) -> Option<&SnsChannel> {
+        self.sns.as_ref()
+    }
+
     /// Send SMS via Twilio
     async fn send_via_twilio(
         &self,
@@ -48,24 +60,6 @@ impl SmsChannel {
         Ok(())
     }
 
-    /// Send SMS via AWS SNS
-    async fn send_via_aws_sns(
-        &self,
-        notification: &NotificationResponse,
-        phone: &str,
-    ) -> Result<()> {
-        // Stub implementation - in production this would use AWS SNS SDK
-        info!(
-            "Sending SMS via AWS SNS to {} for notification {}",
-            phone, notification.id
-        );
-
-        // Simulate API call
-        tokio::time::sleep(tokio::time::Duration::from_millis(150)).await;
-
-        Ok(())
-    }
-
     /// Get recipient phone number from user ID
     async fn get_recipient_phone(&self, recipient_id: &str) -> Result<String> {
         // In a real implementation, this would query the database to get the user's phone
@@ -87,18 +81,15 @@ impl NotificationChannelTrait for SmsChannel {
     async fn send_notification(&self, notification: &NotificationResponse) -> Result<()> {
         info!("Sending SMS notification: {}", notification.id);
 
+        // AWS SNS resolves and validates the recipient number itself
+        if let Some(ref sns) = self.sns {
+            return sns.send_notification(notification).await;
+        }
+
         // Get recipient phone number
         let recipient_phone = self.get_recipient_phone(&notification.recipient_id).await?;
 
-        // Send SMS based on configured provider
-        match self.config.provider {
-            crate::config::SmsProvider::Twilio => {
-                self.send_via_twilio(notification, &recipient_phone).await
-            }
-            crate::config::SmsProvider::AwsSns => {
-                self.send_via_aws_sns(notification, &recipient_phone).await
-            }
-        }
+        self.send_via_twilio(notification, &recipient_phone).await
     }
 
     async fn health_check(&self) -> Result<bool> {
@@ -122,7 +113,7 @@ impl NotificationChannelTrait for SmsChannel {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{SmsConfig, SmsProvider};
+    use crate::config::{AwsSnsConfig, SmsConfig, SmsProvider};
     use ai_core_shared::types::*;
     use chrono::Utc;
 
@@ -196,4 +187,16 @@ mod tests {
         assert!(info.enabled);
         assert!(info.supports_retry);
     }
+
+    #[cfg(not(feature = "sms-aws"))]
+    #[tokio::test]
+    async fn test_aws_sns_requires_feature() {
+        let config = SmsConfig {
+            provider: SmsProvider::AwsSns,
+            aws_sns: Some(AwsSnsConfig::default()),
+            ..create_test_config()
+        };
+
+        assert!(SmsChannel::new(&config).await.is_err());
+    }
 }
@@ -0,0 +1,581 @@
+//! SMS notification channel implementation using AWS SNS
+//!
+//! Messages are published directly to phone numbers (no topic) with the
+//! configured sender ID attached as the `AWS.SNS.SMS.SenderID` attribute.
+//! Throttling and transient SNS failures are reported as retryable errors,
+//! while invalid numbers, opted-out recipients and credential problems are
+//! permanent.
+
+use crate::channels::{ChannelInfo, NotificationChannel as NotificationChannelTrait};
+use crate::config::{AwsSnsConfig, SmsConfig};
+use crate::error::{NotificationError, Result};
+use ai_core_shared::types::{
+    DeliveryAttempt, DeliveryStatus, NotificationChannel, NotificationPreferences,
+    NotificationResponse,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use governor::{DefaultDirectRateLimiter, Quota, RateLimiter};
+use std::{fmt, num::NonZeroU32, sync::Arc};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// SNS error codes that indicate the request may succeed if retried later
+const THROTTLING_CODES: &[&str] = &["Throttling", "ThrottlingException", "KMSThrottling"];
+const TRANSIENT_CODES: &[&str] = &[
+    "InternalError",
+    "InternalFailure",
+    "ServiceUnavailable",
+    "RequestTimeout",
+];
+
+/// SNS error codes caused by the caller's credentials
+const AUTH_CODES: &[&str] = &[
+    "AuthorizationError",
+    "InvalidClientTokenId",
+    "SignatureDoesNotMatch",
+    "AccessDenied",
+];
+
+/// A single SMS publish request
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnsSmsRequest {
+    pub phone_number: String,
+    pub message: String,
+    pub sender_id: Option<String>,
+}
+
+/// Error returned by an SNS publish call
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnsError {
+    /// SNS error code, `None` when the request never reached SNS
+    pub code: Option<String>,
+    pub message: String,
+}
+
+impl SnsError {
+    /// Create an error carrying an SNS error code
+    pub fn service<S1: Into<String>, S2: Into<String>>(code: S1, message: S2) -> Self {
+        Self {
+            code: Some(code.into()),
+            message: message.into(),
+        }
+    }
+
+    /// Create an error for a request that failed before SNS answered
+    pub fn transport<S: Into<String>>(message: S) -> Self {
+        Self {
+            code: None,
+            message: message.into(),
+        }
+    }
+
+    /// Whether SNS rejected the request because of throttling
+    pub fn is_throttling(&self) -> bool {
+        self.code
+            .as_deref()
+            .is_some_and(|code| THROTTLING_CODES.contains(&code))
+    }
+
+    /// Whether the same request may succeed if retried later
+    pub fn is_retryable(&self) -> bool {
+        match self.code.as_deref() {
+            None => true,
+            Some(code) => THROTTLING_CODES.contains(&code) || TRANSIENT_CODES.contains(&code),
+        }
+    }
+}
+
+impl fmt::Display for SnsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.code {
+            Some(ref code) => write!(f, "{}: {}", code, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl From<SnsError> for NotificationError {
+    fn from(err: SnsError) -> Self {
+        let code = err.code.as_deref().unwrap_or_default();
+        if err.is_throttling() {
+            NotificationError::rate_limit(format!("AWS SNS throttled the request: {}", err))
+        } else if err.is_retryable() {
+            NotificationError::sms(format!("AWS SNS request failed: {}", err))
+        } else if AUTH_CODES.contains(&code) {
+            NotificationError::auth(format!("AWS SNS rejected the credentials: {}", err))
+        } else if code.starts_with("InvalidParameter") {
+            NotificationError::validation("phone_number", err.to_string())
+        } else {
+            NotificationError::business_logic(format!("AWS SNS rejected the message: {}", err))
+        }
+    }
+}
+
+/// Minimal SNS client used by the SNS channel, mockable in tests
+#[async_trait]
+pub trait SnsClient: Send + Sync {
+    /// Publish an SMS and return the SNS message ID
+    async fn publish_sms(&self, request: SnsSmsRequest) -> std::result::Result<String, SnsError>;
+}
+
+/// SNS client backed by the AWS SDK
+#[cfg(feature = "sms-aws")]
+pub struct AwsSnsClient {
+    client: aws_sdk_sns::Client,
+}
+
+#[cfg(feature = "sms-aws")]
+impl AwsSnsClient {
+    /// Create a client for the configured region and credentials
+    pub async fn new(config: &AwsSnsConfig, timeout_seconds: u64) -> Self {
+        let credentials = aws_sdk_sns::config::Credentials::new(
+            config.access_key_id.clone(),
+            config.secret_access_key.clone(),
+            None,
+            None,
+            "notification-service",
+        );
+        let timeouts = aws_config::timeout::TimeoutConfig::builder()
+            .operation_timeout(std::time::Duration::from_secs(timeout_seconds))
+            .build();
+
+        let shared_config = aws_config::defaults(aws_config::BehaviorVersion::latest())
+            .region(aws_config::Region::new(config.region.clone()))
+            .credentials_provider(credentials)
+            .timeout_config(timeouts)
+            .load()
+            .await;
+
+        Self {
+            client: aws_sdk_sns::Client::new(&shared_config),
+        }
+    }
+}
+
+#[cfg(feature = "sms-aws")]
+#[async_trait]
+impl SnsClient for AwsSnsClient {
+    async fn publish_sms(&self, request: SnsSmsRequest) -> std::result::Result<String, SnsError> {
+        use aws_sdk_sns::error::{ProvideErrorMetadata, SdkError};
+        use aws_sdk_sns::types::MessageAttributeValue;
+
+        let mut publish = self
+            .client
+            .publish()
+            .phone_number(request.phone_number)
+            .message(request.message);
+
+        if let Some(sender_id) = request.sender_id {
+            let attribute = MessageAttributeValue::builder()
+                .data_type("String")
+                .string_value(sender_id)
+                .build()
+                .map_err(|e| SnsError::transport(e.to_string()))?;
+            publish = publish.message_attributes("AWS.SNS.SMS.SenderID", attribute);
+        }
+
+        match publish.send().await {
+            Ok(output) => Ok(output.message_id().unwrap_or_default().to_string()),
+            Err(SdkError::ServiceError(err)) => {
+                let err = err.into_err();
+                Err(SnsError::service(
+                    err.code().unwrap_or("Unknown"),
+                    err.message().unwrap_or_default(),
+                ))
+            }
+            Err(err) => Err(SnsError::transport(err.to_string())),
+        }
+    }
+}
+
+/// SMS channel for sending notifications through AWS SNS
+#[derive(Clone)]
+pub struct SnsChannel {
+    config: AwsSnsConfig,
+    client: Arc<dyn SnsClient>,
+    rate_limiter: Arc<DefaultDirectRateLimiter>,
+    rate_limit_per_minute: u32,
+}
+
+impl SnsChannel {
+    /// Create a new SNS channel using the AWS SDK client
+    pub async fn new(config: &SmsConfig) -> Result<Self> {
+        info!("Initializing AWS SNS channel");
+
+        let client = Self::default_client(config).await?;
+        let channel = Self::with_client(config, client)?;
+
+        info!(
+            "AWS SNS channel initialized successfully in {}",
+            channel.config.region
+        );
+
+        Ok(channel)
+    }
+
+    #[cfg(feature = "sms-aws")]
+    async fn default_client(config: &SmsConfig) -> Result<Arc<dyn SnsClient>> {
+        let aws_config = config
+            .aws_sns
+            .as_ref()
+            .ok_or_else(|| NotificationError::config("AWS SNS configuration is missing"))?;
+
+        Ok(Arc::new(
+            AwsSnsClient::new(aws_config, config.timeout_seconds).await,
+        ))
+    }
+
+    #[cfg(not(feature = "sms-aws"))]
+    async fn default_client(_config: &SmsConfig) -> Result<Arc<dyn SnsClient>> {
+        Err(NotificationError::config(
+            "AWS SNS support requires the sms-aws feature",
+        ))
+    }
+
+    /// Create a new SNS channel with a custom client
+    pub fn with_client(config: &SmsConfig, client: Arc<dyn SnsClient>) -> Result<Self> {
+        if !config.enabled {
+            return Err(NotificationError::config("SMS channel is disabled"));
+        }
+
+        let aws_config = config
+            .aws_sns
+            .clone()
+            .ok_or_else(|| NotificationError::config("AWS SNS configuration is missing"))?;
+
+        let quota = Quota::per_minute(
+            NonZeroU32::new(config.rate_limit_per_minute).unwrap_or(NonZeroU32::MIN),
+        );
+
+        Ok(Self {
+            config: aws_config,
+            client,
+            rate_limiter: Arc::new(RateLimiter::direct(quota)),
+            rate_limit_per_minute: config.rate_limit_per_minute,
+        })
+    }
+
+    /// Deliver a notification and record the outcome as a delivery attempt
+    ///
+    /// Recipients whose preferences disable SMS are skipped without calling SNS.
+    pub async fn deliver(
+        &self,
+        notification: &NotificationResponse,
+        preferences: Option<&NotificationPreferences>,
+    ) -> DeliveryAttempt {
+        let mut attempt = DeliveryAttempt {
+            id: Uuid::new_v4().to_string(),
+            channel: NotificationChannel::Sms,
+            attempted_at: Utc::now(),
+            status: DeliveryStatus::Success,
+            response: None,
+            error: None,
+            retry_count: 0,
+            next_retry_at: None,
+        };
+
+        if preferences.is_some_and(|p| !p.sms_notifications) {
+            info!(
+                "Recipient {} opted out of SMS, skipping notification {}",
+                notification.recipient_id, notification.id
+            );
+            attempt.status = DeliveryStatus::Skipped;
+            attempt.response = Some("Recipient opted out of SMS notifications".to_string());
+            return attempt;
+        }
+
+        match self.publish(notification).await {
+            Ok(message_id) => attempt.response = Some(message_id),
+            Err(e) => {
+                attempt.status = DeliveryStatus::Failed;
+                attempt.error = Some(e.to_string());
+                if e.is_retryable() {
+                    attempt.next_retry_at = Some(Utc::now() + chrono::Duration::seconds(60));
+                }
+            }
+        }
+
+        attempt
+    }
+
+    /// Publish a notification as an SMS and return the SNS message ID
+    pub async fn publish(&self, notification: &NotificationResponse) -> Result<String> {
+        if self.rate_limiter.check().is_err() {
+            return Err(NotificationError::rate_limit(
+                "Rate limit exceeded for sms channel",
+            ));
+        }
+
+        let phone_number = Self::recipient_phone(notification)?;
+        let request = SnsSmsRequest {
+            phone_number,
+            message: Self::format_message(notification),
+            sender_id: self.config.sender_id.clone(),
+        };
+
+        match self.client.publish_sms(request).await {
+            Ok(message_id) => {
+                info!(
+                    "Sent SMS via AWS SNS for notification {} (message ID {})",
+                    notification.id, message_id
+                );
+                Ok(message_id)
+            }
+            Err(e) => {
+                warn!(
+                    "AWS SNS failed to send notification {}: {}",
+                    notification.id, e
+                );
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Resolve the E.164 phone number for a notification
+    ///
+    /// A `phone_number` metadata entry takes precedence over the recipient ID.
+    fn recipient_phone(notification: &NotificationResponse) -> Result<String> {
+        let phone = notification
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get("phone_number"))
+            .and_then(|v| v.as_str())
+            .unwrap_or(&notification.recipient_id);
+
+        let digits = phone.strip_prefix('+').unwrap_or_default();
+        if digits.is_empty() || digits.len() > 15 || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(NotificationError::validation(
+                "phone_number",
+                format!("'{}' is not an E.164 phone number", phone),
+            ));
+        }
+
+        Ok(phone.to_string())
+    }
+
+    fn format_message(notification: &NotificationResponse) -> String {
+        if notification.title.is_empty() {
+            notification.content.clone()
+        } else {
+            format!("{}: {}", notification.title, notification.content)
+        }
+    }
+}
+
+#[async_trait]
+impl NotificationChannelTrait for SnsChannel {
+    async fn send_notification(&self, notification: &NotificationResponse) -> Result<()> {
+        self.publish(notification).await.map(|_| ())
+    }
+
+    async fn health_check(&self) -> Result<bool> {
+        Ok(!self.config.region.is_empty())
+    }
+
+    fn get_channel_info(&self) -> ChannelInfo {
+        ChannelInfo {
+            name: "SMS".to_string(),
+            description: format!("SMS notifications via AWS SNS ({})", self.config.region),
+            enabled: true,
+            rate_limit_per_minute: Some(self.rate_limit_per_minute),
+            supports_retry: true,
+            supports_scheduling: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SmsProvider;
+    use ai_core_shared::types::*;
+    use parking_lot::Mutex;
+
+    /// SNS client that replays canned responses and records requests
+    struct MockSnsClient {
+        responses: Mutex<Vec<std::result::Result<String, SnsError>>>,
+        requests: Mutex<Vec<SnsSmsRequest>>,
+    }
+
+    impl MockSnsClient {
+        fn new(responses: Vec<std::result::Result<String, SnsError>>) -> Arc<Self> {
+            Arc::new(Self {
+                responses: Mutex::new(responses),
+                requests: Mutex::new(Vec::new()),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl SnsClient for MockSnsClient {
+        async fn publish_sms(
+            &self,
+            request: SnsSmsRequest,
+        ) -> std::result::Result<String, SnsError> {
+            self.requests.lock().push(request);
+            self.responses.lock().remove(0)
+        }
+    }
+
+    fn create_test_config(rate_limit_per_minute: u32) -> SmsConfig {
+        SmsConfig {
+            enabled: true,
+            provider: SmsProvider::AwsSns,
+            twilio: None,
+            aws_sns: Some(AwsSnsConfig {
+                region: "eu-west-1".to_string(),
+                access_key_id: "test-key".to_string(),
+                secret_access_key: "test-secret".to_string(),
+                sender_id: Some("AICore".to_string()),
+            }),
+            timeout_seconds: 30,
+            rate_limit_per_minute,
+        }
+    }
+
+    fn create_test_notification() -> NotificationResponse {
+        NotificationResponse {
+            id: "test-123".to_string(),
+            recipient_id: "+14155550100".to_string(),
+            notification_type: NotificationType::WorkflowCompleted,
+            title: "Workflow done".to_string(),
+            content: "Your workflow finished".to_string(),
+            channels: vec![NotificationChannel::Sms],
+            priority: NotificationPriority::Normal,
+            status: NotificationStatus::Pending,
+            delivery_attempts: vec![],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            scheduled_at: None,
+            delivered_at: None,
+            expires_at: None,
+            metadata: None,
+        }
+    }
+
+    fn create_test_preferences(sms_notifications: bool) -> NotificationPreferences {
+        NotificationPreferences {
+            email_notifications: true,
+            sms_notifications,
+            push_notifications: true,
+            webhook_notifications: false,
+            websocket_notifications: false,
+            webhook_url: None,
+            notification_frequency: NotificationFrequency::RealTime,
+            quiet_hours: None,
+            channels: vec![NotificationChannel::Sms],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deliver_records_message_id() {
+        let client = MockSnsClient::new(vec![Ok("msg-42".to_string())]);
+        let channel = SnsChannel::with_client(&create_test_config(60), client.clone()).unwrap();
+
+        let preferences = create_test_preferences(true);
+        let attempt = channel
+            .deliver(&create_test_notification(), Some(&preferences))
+            .await;
+
+        assert_eq!(attempt.status, DeliveryStatus::Success);
+        assert_eq!(attempt.channel, NotificationChannel::Sms);
+        assert_eq!(attempt.response.as_deref(), Some("msg-42"));
+
+        let requests = client.requests.lock();
+        assert_eq!(
+            requests[0],
+            SnsSmsRequest {
+                phone_number: "+14155550100".to_string(),
+                message: "Workflow done: Your workflow finished".to_string(),
+                sender_id: Some("AICore".to_string()),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deliver_classifies_failures() {
+        let client = MockSnsClient::new(vec![
+            Err(SnsError::service("Throttling", "Rate exceeded")),
+            Err(SnsError::service(
+                "InvalidParameter",
+                "Invalid parameter: PhoneNumber",
+            )),
+            Err(SnsError::transport("connection reset")),
+        ]);
+        let channel = SnsChannel::with_client(&create_test_config(60), client).unwrap();
+        let notification = create_test_notification();
+
+        let throttled = channel.deliver(&notification, None).await;
+        assert_eq!(throttled.status, DeliveryStatus::Failed);
+        assert!(throttled.next_retry_at.is_some());
+
+        let invalid = channel.deliver(&notification, None).await;
+        assert_eq!(invalid.status, DeliveryStatus::Failed);
+        assert!(invalid.next_retry_at.is_none());
+
+        let transport = channel.deliver(&notification, None).await;
+        assert!(transport.next_retry_at.is_some());
+    }
+
+    #[test]
+    fn test_sns_error_mapping() {
+        let throttled: NotificationError = SnsError::service("Throttling", "slow down").into();
+        assert!(matches!(throttled, NotificationError::RateLimit { .. }));
+        assert!(throttled.is_retryable());
+
+        let internal: NotificationError = SnsError::service("InternalError", "oops").into();
+        assert!(internal.is_retryable());
+
+        let auth: NotificationError = SnsError::service("AuthorizationError", "denied").into();
+        assert!(!auth.is_retryable());
+
+        let opted_out: NotificationError = SnsError::service("OptedOut", "opted out").into();
+        assert!(!opted_out.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_deliver_respects_opt_out() {
+        let client = MockSnsClient::new(vec![]);
+        let channel = SnsChannel::with_client(&create_test_config(60), client.clone()).unwrap();
+
+        let preferences = create_test_preferences(false);
+        let attempt = channel
+            .deliver(&create_test_notification(), Some(&preferences))
+            .await;
+
+        assert_eq!(attempt.status, DeliveryStatus::Skipped);
+        assert!(client.requests.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_is_retryable() {
+        let client = MockSnsClient::new(vec![Ok("msg-1".to_string())]);
+        let channel = SnsChannel::with_client(&create_test_config(1), client.clone()).unwrap();
+        let notification = create_test_notification();
+
+        assert!(channel.publish(&notification).await.is_ok());
+
+        let err = channel.publish(&notification).await.unwrap_err();
+        assert!(matches!(err, NotificationError::RateLimit { .. }));
+        assert!(err.is_retryable());
+        assert_eq!(client.requests.lock().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_non_e164_recipient() {
+        let client = MockSnsClient::new(vec![]);
+        let channel = SnsChannel::with_client(&create_test_config(60), client.clone()).unwrap();
+        let mut notification = create_test_notification();
+        notification.recipient_id = "user-123".to_string();
+
+        let err = channel.publish(&notification).await.unwrap_err();
+        assert!(matches!(err, NotificationError::Validation { .. }));
+
+        notification.metadata = Some(serde_json::json!({ "phone_number": "+442071838750" }));
+        let client = MockSnsClient::new(vec![Ok("msg-2".to_string())]);
+        let channel = SnsChannel::with_client(&create_test_config(60), client.clone()).unwrap();
+        assert_eq!(channel.publish(&notification).await.unwrap(), "msg-2");
+        assert_eq!(client.requests.lock()[0].phone_number, "+442071838750");
+    }
+}
@@ -2,214 +2,89 @@
 
 use crate::channels::{ChannelInfo, NotificationChannel as NotificationChannelTrait};
 use crate::config::WebSocketConfig;
-use crate::error::{NotificationError, Result};
+use crate::error::Result;
+use crate::websocket::WebSocketManager;
 use ai_core_shared::{
     api::NotificationWebSocketMessage,
     types::{NotificationResponse, WebSocketMessageType},
 };
 use async_trait::async_trait;
-use axum::extract::ws::{Message, WebSocket};
-use dashmap::DashMap;
-use futures::{SinkExt, StreamExt};
+use axum::extract::ws::WebSocket;
 use serde_json::json;
 use std::sync::Arc;
-use tokio::sync::mpsc;
-use tracing::{error, info, warn};
-use uuid::Uuid;
+use tracing::{error, info};
 
 /// WebSocket channel for sending real-time notifications
+///
+/// Delivery goes through a [`WebSocketManager`], which tracks every connection per
+/// user and buffers messages for clients that reconnect.
 #[derive(Clone)]
 pub struct WebSocketChannel {
     config: WebSocketConfig,
-    connections: Arc<DashMap<String, WebSocketConnection>>,
-    broadcast_tx: Arc<tokio::sync::broadcast::Sender<NotificationWebSocketMessage>>,
-}
-
-/// Represents an active WebSocket connection
-#[derive(Debug)]
-struct WebSocketConnection {
-    user_id: String,
-    connection_id: String,
-    tx: mpsc::UnboundedSender<Message>,
-    connected_at: chrono::DateTime<chrono::Utc>,
+    manager: Arc<WebSocketManager>,
 }
 
 impl WebSocketChannel {
-    /// Create a new WebSocket channel with the given configuration
+    /// Create a new WebSocket channel with its own connection manager
     pub async fn new(config: &WebSocketConfig) -> Result<Self> {
-        info!("Initializing WebSocket channel");
-
-        let (broadcast_tx, _) = tokio::sync::broadcast::channel(1000);
+        let manager = WebSocketManager::with_config(config, None).await?;
+        Ok(Self::with_manager(config, Arc::new(manager)))
+    }
 
+    /// Create a WebSocket channel that delivers through an existing connection manager
+    pub fn with_manager(config: &WebSocketConfig, manager: Arc<WebSocketManager>) -> Self {
         info!("WebSocket channel initialized successfully");
 
-        Ok(Self {
+        Self {
             config: config.clone(),
-            connections: Arc::new(DashMap::new()),
-            broadcast_tx: Arc::new(broadcast_tx),
-        })
+            manager,
+        }
+    }
+
+    /// Connection manager used for delivery, shared with the WebSocket routes
+    pub fn manager(&self) -> Arc<WebSocketManager> {
+        self.manager.clone()
     }
 
     /// Add a new WebSocket connection
     pub async fn add_connection(&self, user_id: String, websocket: WebSocket) -> Result<()> {
-        let connection_id = Uuid::new_v4().to_string();
-        let (mut ws_tx, mut ws_rx) = websocket.split();
-        let (tx, mut rx) = mpsc::unbounded_channel::<Message>();
-
-        // Store connection
-        let connection = WebSocketConnection {
-            user_id: user_id.clone(),
-            connection_id: connection_id.clone(),
-            tx: tx.clone(),
-            connected_at: chrono::Utc::now(),
-        };
-
-        self.connections.insert(connection_id.clone(), connection);
-
-        // Handle outgoing messages
-        let connections_clone = self.connections.clone();
-        let connection_id_clone = connection_id.clone();
-        tokio::spawn(async move {
-            while let Some(message) = rx.recv().await {
-                if ws_tx.send(message).await.is_err() {
-                    break;
-                }
-            }
-            connections_clone.remove(&connection_id_clone);
-        });
-
-        // Handle incoming messages (ping/pong, etc.)
-        let tx_clone = tx.clone();
-        let connections_clone = self.connections.clone();
-        let connection_id_clone = connection_id.clone();
+        let manager = self.manager.clone();
         tokio::spawn(async move {
-            while let Some(msg) = ws_rx.next().await {
-                match msg {
-                    Ok(Message::Close(_)) => {
-                        break;
-                    }
-                    Ok(Message::Pong(_)) => {
-                        // Handle pong response
-                    }
-                    Ok(Message::Ping(data)) => {
-                        if tx_clone.send(Message::Pong(data)).is_err() {
-                            break;
-                        }
-                    }
-                    Ok(Message::Text(text)) => {
-                        // Handle incoming text messages (could be acknowledgments, etc.)
-                        info!("Received WebSocket message: {}", text);
-                    }
-                    Ok(Message::Binary(_)) => {
-                        // Handle binary messages if needed
-                    }
-                    Err(e) => {
-                        error!("WebSocket error: {}", e);
-                        break;
-                    }
-                }
+            if let Err(e) = manager
+                .handle_connection(user_id.clone(), websocket, None)
+                .await
+            {
+                error!("WebSocket connection error for user {}: {}", user_id, e);
             }
-            connections_clone.remove(&connection_id_clone);
         });
 
-        // Send welcome message
-        let welcome_message = NotificationWebSocketMessage {
-            message_type: WebSocketMessageType::ConnectionStatus,
-            data: json!({
-                "status": "connected",
-                "connection_id": connection_id
-            }),
-            timestamp: chrono::Utc::now(),
-        };
-
-        if let Ok(message_text) = serde_json::to_string(&welcome_message) {
-            let _ = tx.send(Message::Text(message_text));
-        }
-
-        info!(
-            "WebSocket connection established for user {} with connection {}",
-            user_id, connection_id
-        );
         Ok(())
     }
 
-    /// Send message to specific user's connections
+    /// Send message to all of a user's connections, buffering it for replay
     pub async fn send_to_user(
         &self,
         user_id: &str,
         message: &NotificationWebSocketMessage,
     ) -> Result<()> {
-        let message_text = serde_json::to_string(message)
-            .map_err(|e| NotificationError::serialization(e.to_string()))?;
-
-        let mut sent_count = 0;
-        for connection in self.connections.iter() {
-            if connection.user_id == user_id {
-                if connection
-                    .tx
-                    .send(Message::Text(message_text.clone()))
-                    .is_ok()
-                {
-                    sent_count += 1;
-                }
-            }
-        }
-
-        if sent_count == 0 {
-            warn!("No active WebSocket connections found for user {}", user_id);
-        } else {
-            info!(
-                "Sent WebSocket message to {} connections for user {}",
-                sent_count, user_id
-            );
-        }
-
+        self.manager.send_to_user(user_id, message).await?;
         Ok(())
     }
 
     /// Broadcast message to all connected users
     pub async fn broadcast(&self, message: &NotificationWebSocketMessage) -> Result<()> {
-        let _ = self.broadcast_tx.send(message.clone());
+        self.manager.broadcast(message).await?;
         Ok(())
     }
 
     /// Get connection statistics
     pub fn get_connection_stats(&self) -> serde_json::Value {
-        let total_connections = self.connections.len();
-        let mut user_connections: std::collections::HashMap<String, usize> =
-            std::collections::HashMap::new();
-
-        for connection in self.connections.iter() {
-            *user_connections
-                .entry(connection.user_id.clone())
-                .or_insert(0) += 1;
-        }
-
-        json!({
-            "total_connections": total_connections,
-            "unique_users": user_connections.len(),
-            "user_connections": user_connections
-        })
+        self.manager.get_stats()
     }
 
     /// Clean up inactive connections (called periodically)
     pub async fn cleanup_connections(&self) -> Result<()> {
-        let mut to_remove = Vec::new();
-        let cutoff_time = chrono::Utc::now() - chrono::Duration::hours(1);
-
-        for entry in self.connections.iter() {
-            if entry.connected_at < cutoff_time {
-                // Test if connection is still alive by sending ping
-                if entry.tx.send(Message::Ping(vec![])).is_err() {
-                    to_remove.push(entry.connection_id.clone());
-                }
-            }
-        }
-
-        for connection_id in to_remove {
-            self.connections.remove(&connection_id);
-        }
-
+        self.manager.cleanup_stale_connections().await;
         Ok(())
     }
 }
@@ -263,6 +138,8 @@ mod tests {
             pong_timeout_seconds: 10,
             message_buffer_size: 1024,
             max_message_size: 64 * 1024,
+            replay_window_seconds: 60,
+            replay_buffer_size: 10,
         }
     }
 
@@ -46,6 +46,10 @@ pub struct NotificationConfig {
     /// Scheduling configuration
     pub scheduler: SchedulerConfig,
 
+    /// Digest configuration for daily and weekly notification frequencies
+    #[serde(default)]
+    pub digest: DigestConfig,
+
     /// Metrics configuration
     pub metrics: MetricsConfig,
 }
@@ -131,6 +135,9 @@ pub struct AwsSnsConfig {
     pub region: String,
     pub access_key_id: String,
     pub secret_access_key: String,
+    /// Alphanumeric sender ID shown to recipients where supported
+    #[serde(default)]
+    pub sender_id: Option<String>,
 }
 
 /// Push notification configuration
@@ -177,6 +184,20 @@ pub struct WebSocketConfig {
     pub pong_timeout_seconds: u64,
     pub message_buffer_size: usize,
     pub max_message_size: usize,
+    /// How long sent messages stay available for replay to reconnecting clients
+    #[serde(default = "default_replay_window_seconds")]
+    pub replay_window_seconds: u64,
+    /// Maximum number of messages buffered per user for replay
+    #[serde(default = "default_replay_buffer_size")]
+    pub replay_buffer_size: usize,
+}
+
+fn default_replay_window_seconds() -> u64 {
+    120
+}
+
+fn default_replay_buffer_size() -> usize {
+    100
 }
 
 /// Template configuration
@@ -237,6 +258,21 @@ pub struct SchedulerConfig {
     pub retention_days: u32,
 }
 
+/// Digest configuration
+///
+/// Send times are UTC wall-clock times in `HH:MM` format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestConfig {
+    pub enabled: bool,
+    pub daily_send_time: String,
+    pub weekly_send_time: String,
+    pub weekly_send_day: chrono::Weekday,
+    /// Maximum notifications listed in a digest, the rest are summarized as "and N more"
+    pub max_items: usize,
+    /// Template used to render digests, the built-in digest template when unset
+    pub template_id: Option<String>,
+}
+
 /// Metrics configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetricsConfig {
@@ -262,6 +298,7 @@ impl Default for NotificationConfig {
             retry: RetryConfig::default(),
             rate_limit: RateLimitConfig::default(),
             scheduler: SchedulerConfig::default(),
+            digest: DigestConfig::default(),
             metrics: MetricsConfig::default(),
         }
     }
@@ -370,6 +407,7 @@ impl Default for AwsSnsConfig {
             region: std::env::var("AWS_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
             access_key_id: std::env::var("AWS_ACCESS_KEY_ID").unwrap_or_default(),
             secret_access_key: std::env::var("AWS_SECRET_ACCESS_KEY").unwrap_or_default(),
+            sender_id: std::env::var("AWS_SNS_SENDER_ID").ok(),
         }
     }
 }
@@ -429,6 +467,8 @@ impl Default for WebSocketConfig {
             pong_timeout_seconds: 10,
             message_buffer_size: 1024,
             max_message_size: 64 * 1024, // 64KB
+            replay_window_seconds: default_replay_window_seconds(),
+            replay_buffer_size: default_replay_buffer_size(),
         }
     }
 }
@@ -546,6 +586,19 @@ impl Default for SchedulerConfig {
     }
 }
 
+impl Default for DigestConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            daily_send_time: "09:00".to_string(),
+            weekly_send_time: "09:00".to_string(),
+            weekly_send_day: chrono::Weekday::Mon,
+            max_items: 20,
+            template_id: None,
+        }
+    }
+}
+
 impl Default for MetricsConfig {
     fn default() -> Self {
         Self {
@@ -623,6 +676,17 @@ impl NotificationConfig {
                                     .to_string(),
                             );
                         }
+                        if aws.region.is_empty() {
+                            return Err("AWS region is required for AWS SNS".to_string());
+                        }
+                        if let Some(ref sender_id) = aws.sender_id {
+                            let valid = (1..=11).contains(&sender_id.len())
+                                && sender_id.chars().all(|c| c.is_ascii_alphanumeric())
+                                && sender_id.chars().any(|c| c.is_ascii_alphabetic());
+                            if !valid {
+                                return Err("AWS SNS sender ID must be 1-11 alphanumeric characters with at least one letter".to_string());
+                            }
+                        }
                     } else {
                         return Err(
                             "AWS SNS configuration is required when SMS provider is AWS SNS"
@@ -649,6 +713,21 @@ impl NotificationConfig {
             return Err("Backoff multiplier must be greater than 1.0".to_string());
         }
 
+        if self.websocket.enabled && self.websocket.ping_interval_seconds == 0 {
+            return Err("WebSocket ping interval must be greater than 0".to_string());
+        }
+
+        if self.digest.enabled {
+            for send_time in [&self.digest.daily_send_time, &self.digest.weekly_send_time] {
+                if chrono::NaiveTime::parse_from_str(send_time, "%H:%M").is_err() {
+                    return Err(format!("Invalid digest send time '{}'", send_time));
+                }
+            }
+            if self.digest.max_items == 0 {
+                return Err("Digest max items must be greater than 0".to_string());
+            }
+        }
+
         Ok(())
     }
 
@@ -704,6 +783,20 @@ mod tests {
         assert!(invalid_config.validate().is_err());
     }
 
+    #[test]
+    fn test_digest_validation() {
+        let mut config = NotificationConfig::default();
+        config.digest.daily_send_time = "25:00".to_string();
+        assert!(config.validate().is_err());
+
+        config.digest.daily_send_time = "18:30".to_string();
+        config.digest.max_items = 0;
+        assert!(config.validate().is_err());
+
+        config.digest.enabled = false;
+        assert!(config.validate().is_ok());
+    }
+
     #[test]
     fn test_timeout_getter() {
         let config = NotificationConfig::default();
@@ -0,0 +1,491 @@
+//! Notification digest module
+//!
+//! Recipients with a daily or weekly `NotificationFrequency` receive a single
+//! digest instead of individual notifications. This module provides:
+//! - Per-recipient digest queues keyed by frequency
+//! - Queue entries the manager persists so queued notifications survive a restart
+//! - Send time calculation for each frequency
+//! - Digest template context with "and N more" overflow
+//! - Digest statistics
+
+use crate::config::DigestConfig;
+use crate::error::{NotificationError, Result};
+use ai_core_shared::types::{
+    NotificationChannel, NotificationFrequency, NotificationResponse, NotificationStatus,
+    NotificationType,
+};
+
+use chrono::{DateTime, Datelike, NaiveTime, Utc};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Digest cadence for a recipient
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestFrequency {
+    Daily,
+    Weekly,
+}
+
+impl DigestFrequency {
+    /// Digest cadence for a notification frequency, `None` for non-digest frequencies
+    pub fn from_preference(frequency: &NotificationFrequency) -> Option<Self> {
+        match frequency {
+            NotificationFrequency::Daily => Some(DigestFrequency::Daily),
+            NotificationFrequency::Weekly => Some(DigestFrequency::Weekly),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DigestFrequency::Daily => "daily",
+            DigestFrequency::Weekly => "weekly",
+        }
+    }
+}
+
+/// Statistics for a sent digest
+#[derive(Debug, Clone, Serialize)]
+pub struct DigestStats {
+    pub digest_id: String,
+    pub recipient_id: String,
+    pub frequency: DigestFrequency,
+    /// Notifications collapsed into the digest
+    pub collapsed: usize,
+    /// Notifications listed individually in the digest
+    pub listed: usize,
+    /// Notifications summarized as "and N more"
+    pub overflow: usize,
+}
+
+/// A queued notification as persisted until its digest is sent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedDigestEntry {
+    pub frequency: DigestFrequency,
+    pub due_at: DateTime<Utc>,
+    pub notification: NotificationResponse,
+}
+
+/// Whether a digest with this delivery status should be sent again. Digests
+/// that reached any channel are not, so no recipient gets one twice.
+pub fn needs_resend(status: &NotificationStatus) -> bool {
+    !matches!(
+        status,
+        NotificationStatus::Delivered | NotificationStatus::PartiallyDelivered
+    )
+}
+
+/// Notifications queued for one recipient's digest
+#[derive(Debug, Clone)]
+pub struct DigestBatch {
+    pub recipient_id: String,
+    pub frequency: DigestFrequency,
+    pub due_at: DateTime<Utc>,
+    pub notifications: Vec<NotificationResponse>,
+}
+
+impl DigestBatch {
+    /// Build the template context, listing at most `max_items` notifications
+    ///
+    /// The most recent notifications are listed first.
+    pub fn context(&self, max_items: usize) -> serde_json::Value {
+        let items: Vec<serde_json::Value> = self
+            .notifications
+            .iter()
+            .rev()
+            .take(max_items)
+            .map(|n| {
+                serde_json::json!({
+                    "id": n.id,
+                    "title": n.title,
+                    "content": n.content,
+                    "notification_type": n.notification_type.to_string(),
+                    "created_at": n.created_at.to_rfc3339(),
+                })
+            })
+            .collect();
+
+        serde_json::json!({
+            "frequency": self.frequency.as_str(),
+            "total": self.notifications.len(),
+            "overflow_count": self.notifications.len().saturating_sub(items.len()),
+            "items": items,
+        })
+    }
+
+    /// Channels used by any collapsed notification, in first-seen order
+    pub fn channels(&self) -> Vec<NotificationChannel> {
+        let mut channels = Vec::new();
+        for channel in self.notifications.iter().flat_map(|n| &n.channels) {
+            if !channels.contains(channel) {
+                channels.push(channel.clone());
+            }
+        }
+        channels
+    }
+
+    /// Notification type shared by all collapsed notifications, `Custom` when mixed
+    pub fn notification_type(&self) -> NotificationType {
+        match self.notifications.split_first() {
+            Some((first, rest))
+                if rest
+                    .iter()
+                    .all(|n| n.notification_type == first.notification_type) =>
+            {
+                first.notification_type.clone()
+            }
+            _ => NotificationType::Custom,
+        }
+    }
+
+    /// Statistics for a digest built from this batch
+    pub fn stats(&self, digest_id: &str, max_items: usize) -> DigestStats {
+        let collapsed = self.notifications.len();
+        let listed = collapsed.min(max_items);
+        DigestStats {
+            digest_id: digest_id.to_string(),
+            recipient_id: self.recipient_id.clone(),
+            frequency: self.frequency,
+            collapsed,
+            listed,
+            overflow: collapsed - listed,
+        }
+    }
+}
+
+/// In-memory queue of notifications waiting for their recipient's digest
+#[derive(Clone)]
+pub struct DigestQueue {
+    daily_send_time: NaiveTime,
+    weekly_send_time: NaiveTime,
+    weekly_send_day: chrono::Weekday,
+    pending: Arc<DashMap<(String, DigestFrequency), DigestBatch>>,
+}
+
+impl DigestQueue {
+    /// Create a new digest queue
+    pub fn new(config: &DigestConfig) -> Result<Self> {
+        let parse_time = |value: &str| {
+            NaiveTime::parse_from_str(value, "%H:%M").map_err(|e| {
+                NotificationError::config(format!("Invalid digest send time '{}': {}", value, e))
+            })
+        };
+
+        Ok(Self {
+            daily_send_time: parse_time(&config.daily_send_time)?,
+            weekly_send_time: parse_time(&config.weekly_send_time)?,
+            weekly_send_day: config.weekly_send_day,
+            pending: Arc::new(DashMap::new()),
+        })
+    }
+
+    /// Next digest send time strictly after `after`
+    pub fn next_send_time(
+        &self,
+        frequency: DigestFrequency,
+        after: DateTime<Utc>,
+    ) -> DateTime<Utc> {
+        let (send_time, days_ahead, period) = match frequency {
+            DigestFrequency::Daily => (self.daily_send_time, 0, 1),
+            DigestFrequency::Weekly => {
+                let target = self.weekly_send_day.num_days_from_monday();
+                let today = after.weekday().num_days_from_monday();
+                (self.weekly_send_time, (target + 7 - today) % 7, 7)
+            }
+        };
+
+        let candidate = (after.date_naive() + chrono::Duration::days(i64::from(days_ahead)))
+            .and_time(send_time)
+            .and_utc();
+        if candidate > after {
+            candidate
+        } else {
+            candidate + chrono::Duration::days(period)
+        }
+    }
+
+    /// When the recipient's pending digest goes out, or the next send time if none is pending
+    pub fn due_at(
+        &self,
+        recipient_id: &str,
+        frequency: DigestFrequency,
+        now: DateTime<Utc>,
+    ) -> DateTime<Utc> {
+        self.pending
+            .get(&(recipient_id.to_string(), frequency))
+            .map(|batch| batch.due_at)
+            .unwrap_or_else(|| self.next_send_time(frequency, now))
+    }
+
+    /// Queue a notification for the recipient's next digest
+    pub fn enqueue(
+        &self,
+        notification: NotificationResponse,
+        frequency: DigestFrequency,
+        now: DateTime<Utc>,
+    ) -> DateTime<Utc> {
+        let key = (notification.recipient_id.clone(), frequency);
+        let mut batch = self.pending.entry(key).or_insert_with(|| DigestBatch {
+            recipient_id: notification.recipient_id.clone(),
+            frequency,
+            due_at: self.next_send_time(frequency, now),
+            notifications: Vec::new(),
+        });
+        batch.notifications.push(notification);
+        batch.due_at
+    }
+
+    /// Restore a persisted entry after a restart. A batch restored from
+    /// several entries goes out at the earliest of their send times.
+    pub fn restore(&self, entry: QueuedDigestEntry) {
+        let key = (entry.notification.recipient_id.clone(), entry.frequency);
+        let mut batch = self.pending.entry(key).or_insert_with(|| DigestBatch {
+            recipient_id: entry.notification.recipient_id.clone(),
+            frequency: entry.frequency,
+            due_at: entry.due_at,
+            notifications: Vec::new(),
+        });
+        batch.due_at = batch.due_at.min(entry.due_at);
+        if !batch
+            .notifications
+            .iter()
+            .any(|n| n.id == entry.notification.id)
+        {
+            batch.notifications.push(entry.notification);
+        }
+    }
+
+    /// Put a batch back after a failed send, merging anything queued since
+    pub fn requeue(&self, mut batch: DigestBatch, now: DateTime<Utc>) {
+        let key = (batch.recipient_id.clone(), batch.frequency);
+        match self.pending.entry(key) {
+            dashmap::mapref::entry::Entry::Occupied(mut entry) => {
+                batch
+                    .notifications
+                    .append(&mut entry.get_mut().notifications);
+                entry.get_mut().notifications = batch.notifications;
+            }
+            dashmap::mapref::entry::Entry::Vacant(entry) => {
+                batch.due_at = self.next_send_time(batch.frequency, now);
+                entry.insert(batch);
+            }
+        }
+    }
+
+    /// Remove and return all batches due at or before `now`
+    pub fn take_due(&self, now: DateTime<Utc>) -> Vec<DigestBatch> {
+        let due: Vec<_> = self
+            .pending
+            .iter()
+            .filter(|batch| batch.due_at <= now)
+            .map(|batch| batch.key().clone())
+            .collect();
+
+        due.into_iter()
+            .filter_map(|key| self.pending.remove(&key).map(|(_, batch)| batch))
+            .collect()
+    }
+
+    /// Number of notifications waiting for a digest
+    pub fn pending_count(&self) -> usize {
+        self.pending
+            .iter()
+            .map(|batch| batch.notifications.len())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ai_core_shared::types::*;
+    use chrono::TimeZone;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    fn create_test_queue() -> DigestQueue {
+        let config = DigestConfig {
+            daily_send_time: "09:00".to_string(),
+            weekly_send_time: "17:30".to_string(),
+            weekly_send_day: chrono::Weekday::Fri,
+            ..DigestConfig::default()
+        };
+        DigestQueue::new(&config).unwrap()
+    }
+
+    fn create_test_notification(
+        id: &str,
+        notification_type: NotificationType,
+    ) -> NotificationResponse {
+        let now = Utc::now();
+        NotificationResponse {
+            id: id.to_string(),
+            recipient_id: "user123".to_string(),
+            notification_type,
+            title: format!("Title {}", id),
+            content: format!("Content {}", id),
+            channels: vec![NotificationChannel::Email],
+            priority: NotificationPriority::Normal,
+            status: NotificationStatus::Queued,
+            delivery_attempts: vec![],
+            created_at: now,
+            updated_at: now,
+            scheduled_at: None,
+            delivered_at: None,
+            expires_at: None,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_next_send_time() {
+        let queue = create_test_queue();
+
+        // 2024-06-05 is a Wednesday
+        assert_eq!(
+            queue.next_send_time(DigestFrequency::Daily, utc(2024, 6, 5, 8, 0)),
+            utc(2024, 6, 5, 9, 0)
+        );
+        assert_eq!(
+            queue.next_send_time(DigestFrequency::Daily, utc(2024, 6, 5, 9, 0)),
+            utc(2024, 6, 6, 9, 0)
+        );
+        assert_eq!(
+            queue.next_send_time(DigestFrequency::Weekly, utc(2024, 6, 5, 8, 0)),
+            utc(2024, 6, 7, 17, 30)
+        );
+        assert_eq!(
+            queue.next_send_time(DigestFrequency::Weekly, utc(2024, 6, 7, 18, 0)),
+            utc(2024, 6, 14, 17, 30)
+        );
+    }
+
+    #[test]
+    fn test_enqueue_and_take_due() {
+        let queue = create_test_queue();
+        let now = utc(2024, 6, 5, 8, 0);
+
+        let due = queue.enqueue(
+            create_test_notification("1", NotificationType::WorkflowCompleted),
+            DigestFrequency::Daily,
+            now,
+        );
+        queue.enqueue(
+            create_test_notification("2", NotificationType::WorkflowCompleted),
+            DigestFrequency::Daily,
+            now + chrono::Duration::minutes(30),
+        );
+        queue.enqueue(
+            create_test_notification("3", NotificationType::WorkflowFailed),
+            DigestFrequency::Weekly,
+            now,
+        );
+
+        assert_eq!(due, utc(2024, 6, 5, 9, 0));
+        assert_eq!(queue.due_at("user123", DigestFrequency::Daily, now), due);
+        assert_eq!(queue.pending_count(), 3);
+        assert!(queue.take_due(now).is_empty());
+
+        let batches = queue.take_due(due);
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].frequency, DigestFrequency::Daily);
+        assert_eq!(batches[0].notifications.len(), 2);
+        assert_eq!(
+            batches[0].notification_type(),
+            NotificationType::WorkflowCompleted
+        );
+        assert_eq!(queue.pending_count(), 1);
+    }
+
+    #[test]
+    fn test_digest_overflow() {
+        let queue = create_test_queue();
+        let now = utc(2024, 6, 5, 8, 0);
+        for i in 0..5 {
+            let notification_type = if i % 2 == 0 {
+                NotificationType::WorkflowCompleted
+            } else {
+                NotificationType::WorkflowFailed
+            };
+            queue.enqueue(
+                create_test_notification(&i.to_string(), notification_type),
+                DigestFrequency::Daily,
+                now,
+            );
+        }
+
+        let batch = queue.take_due(utc(2024, 6, 5, 9, 0)).remove(0);
+        let context = batch.context(3);
+        assert_eq!(context["total"], 5);
+        assert_eq!(context["overflow_count"], 2);
+        assert_eq!(context["items"].as_array().unwrap().len(), 3);
+        assert_eq!(context["items"][0]["title"], "Title 4");
+
+        let stats = batch.stats("digest-1", 3);
+        assert_eq!(stats.collapsed, 5);
+        assert_eq!(stats.listed, 3);
+        assert_eq!(stats.overflow, 2);
+        assert_eq!(batch.notification_type(), NotificationType::Custom);
+        assert_eq!(batch.channels(), vec![NotificationChannel::Email]);
+    }
+
+    #[test]
+    fn test_requeue_merges_new_notifications() {
+        let queue = create_test_queue();
+        let now = utc(2024, 6, 5, 8, 0);
+        queue.enqueue(
+            create_test_notification("1", NotificationType::WorkflowCompleted),
+            DigestFrequency::Daily,
+            now,
+        );
+
+        let batch = queue.take_due(utc(2024, 6, 5, 9, 0)).remove(0);
+        queue.enqueue(
+            create_test_notification("2", NotificationType::WorkflowCompleted),
+            DigestFrequency::Daily,
+            utc(2024, 6, 5, 9, 1),
+        );
+        queue.requeue(batch, utc(2024, 6, 5, 9, 1));
+
+        let batch = queue.take_due(utc(2024, 6, 6, 9, 0)).remove(0);
+        let ids: Vec<_> = batch.notifications.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "2"]);
+    }
+
+    #[test]
+    fn test_restore_rebuilds_batches_from_persisted_entries() {
+        let queue = create_test_queue();
+        let entry = |id: &str, due_at| QueuedDigestEntry {
+            frequency: DigestFrequency::Daily,
+            due_at,
+            notification: create_test_notification(id, NotificationType::WorkflowCompleted),
+        };
+
+        queue.restore(entry("2", utc(2024, 6, 6, 9, 0)));
+        queue.restore(entry("1", utc(2024, 6, 5, 9, 0)));
+        // Restoring the same entry twice does not duplicate it
+        queue.restore(entry("1", utc(2024, 6, 5, 9, 0)));
+
+        assert_eq!(queue.pending_count(), 2);
+        let batches = queue.take_due(utc(2024, 6, 5, 9, 0));
+        assert_eq!(batches.len(), 1);
+        let ids: Vec<_> = batches[0]
+            .notifications
+            .iter()
+            .map(|n| n.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["2", "1"]);
+    }
+
+    #[test]
+    fn test_only_undelivered_digests_are_resent() {
+        assert!(needs_resend(&NotificationStatus::Failed));
+        assert!(needs_resend(&NotificationStatus::Processing));
+        assert!(!needs_resend(&NotificationStatus::Delivered));
+        assert!(!needs_resend(&NotificationStatus::PartiallyDelivered));
+    }
+}
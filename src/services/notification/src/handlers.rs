@@ -266,6 +266,13 @@ pub mod templates_handler {
         pub data: Option<serde_json::Value>,
     }
 
+    #[derive(Deserialize)]
+    pub struct PreviewTemplateRequest {
+        pub sample_data: serde_json::Value,
+        #[serde(default)]
+        pub strict: bool,
+    }
+
     /// Create a new notification template
     pub async fn create_template(
         State(manager): State<Arc<NotificationManager>>,
@@ -382,6 +389,26 @@ pub mod templates_handler {
             None => Err(NotificationError::not_found("template")),
         }
     }
+
+    /// Preview a template with sample data, reporting missing and unused variables
+    pub async fn preview_template(
+        State(manager): State<Arc<NotificationManager>>,
+        Path(id): Path<String>,
+        Json(request): Json<PreviewTemplateRequest>,
+    ) -> Result<impl IntoResponse> {
+        info!("Previewing template: {}", id);
+
+        match manager
+            .render_preview(&id, &request.sample_data, request.strict)
+            .await
+        {
+            Ok(preview) => Ok(Json(preview)),
+            Err(e) => {
+                error!("Failed to preview template {}: {}", id, e);
+                Err(e)
+            }
+        }
+    }
 }
 
 pub mod subscriptions_handler {
@@ -499,6 +526,12 @@ pub mod websocket_handler {
     use super::*;
     use axum::extract::ws::WebSocket;
 
+    #[derive(Deserialize)]
+    pub struct ReconnectQuery {
+        /// Last message ID the client received; later buffered messages are replayed
+        pub last_message_id: Option<u64>,
+    }
+
     /// Handle WebSocket connections
     pub async fn websocket_handler(
         ws: WebSocketUpgrade,
@@ -507,29 +540,36 @@ pub mod websocket_handler {
         info!("New WebSocket connection");
 
         ws.on_upgrade(move |socket| {
-            handle_websocket_connection(socket, ws_manager, "anonymous".to_string())
+            handle_websocket_connection(socket, ws_manager, "anonymous".to_string(), None)
         })
     }
 
     /// Handle user-specific WebSocket connections
     pub async fn user_websocket_handler(
         Path(user_id): Path<String>,
+        Query(query): Query<ReconnectQuery>,
         ws: WebSocketUpgrade,
         State(ws_manager): State<Arc<WebSocketManager>>,
     ) -> Response {
         info!("New WebSocket connection for user: {}", user_id);
 
-        ws.on_upgrade(move |socket| handle_websocket_connection(socket, ws_manager, user_id))
+        ws.on_upgrade(move |socket| {
+            handle_websocket_connection(socket, ws_manager, user_id, query.last_message_id)
+        })
     }
 
     async fn handle_websocket_connection(
         socket: WebSocket,
         ws_manager: Arc<WebSocketManager>,
         user_id: String,
+        last_message_id: Option<u64>,
     ) {
         info!("Handling WebSocket connection for user: {}", user_id);
 
-        if let Err(e) = ws_manager.handle_connection(user_id.clone(), socket).await {
+        if let Err(e) = ws_manager
+            .handle_connection(user_id.clone(), socket, last_message_id)
+            .await
+        {
             error!("WebSocket connection error for user {}: {}", user_id, e);
         }
 
@@ -17,8 +17,9 @@
 //! - **Delivery tracking**: Comprehensive tracking of delivery attempts and status
 //! - **Retry mechanisms**: Configurable retry logic with exponential backoff
 //! - **Rate limiting**: Per-channel and per-user rate limiting
-//! - **Real-time updates**: WebSocket connections for instant notifications
+//! - **Real-time updates**: WebSocket fan-out to every open tab, with replay on reconnect
 //! - **Subscription management**: User preference management and opt-out support
+//! - **Digests**: Daily and weekly digests for users who prefer batched notifications
 //! - **Analytics**: Delivery statistics and performance metrics
 //!
 //! ## Usage
@@ -57,6 +58,7 @@ use std::sync::Arc;
 
 pub mod channels;
 pub mod config;
+pub mod digest;
 pub mod error;
 pub mod handlers;
 pub mod manager;
@@ -66,11 +66,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         },
     )?);
 
-    // Initialize WebSocket manager
-    let websocket_manager = Arc::new(WebSocketManager::new().await.map_err(|e| {
-        error!("Failed to initialize WebSocket manager: {}", e);
-        e
-    })?);
+    // Share the notification manager's WebSocket registry so deliveries reach routed connections
+    let websocket_manager = notification_manager.websocket_manager();
 
     // Start scheduler if enabled
     if config.scheduler.enabled {
@@ -113,7 +110,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     info!("WebSocket endpoint: ws://{}/ws", addr);
 
     // Start background tasks
-    let cleanup_task = start_cleanup_task(websocket_manager.clone(), cancellation_token.clone());
+    let cleanup_task = start_cleanup_task(
+        websocket_manager.clone(),
+        config.websocket.ping_interval_seconds,
+        cancellation_token.clone(),
+    );
 
     // Start server with graceful shutdown
     let server_task = tokio::spawn({
@@ -268,10 +269,13 @@ async fn load_config(
 /// Start background cleanup tasks
 fn start_cleanup_task(
     websocket_manager: Arc<WebSocketManager>,
+    ping_interval_seconds: u64,
     cancellation_token: CancellationToken,
 ) -> tokio::task::JoinHandle<()> {
     tokio::spawn(async move {
-        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(300)); // 5 minutes
+        // Reaping relies on pongs, so ping at the configured interval
+        let mut interval =
+            tokio::time::interval(tokio::time::Duration::from_secs(ping_interval_seconds));
 
         loop {
             tokio::select! {
@@ -11,14 +11,18 @@ use crate::channels::{
     EmailChannel, NotificationChannel, PushChannel, SmsChannel, WebSocketChannel, WebhookChannel,
 };
 use crate::config::NotificationConfig;
+use crate::digest::{
+    needs_resend, DigestBatch, DigestFrequency, DigestQueue, DigestStats, QueuedDigestEntry,
+};
 use crate::error::{NotificationError, Result};
 use crate::metrics::NotificationMetrics;
 use crate::scheduler::NotificationScheduler;
-use crate::templates::TemplateManager;
+use crate::templates::{RenderedPreview, TemplateManager};
+use crate::websocket::WebSocketManager;
 
 use ai_core_shared::types::*;
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeZone, Utc};
 use dashmap::DashMap;
 use mongodb::{
     bson::{doc, DateTime as BsonDateTime},
@@ -58,6 +62,7 @@ pub struct NotificationManager {
     // Core components
     template_manager: TemplateManager,
     scheduler: Option<NotificationScheduler>,
+    digest_queue: DigestQueue,
     metrics: NotificationMetrics,
 
     // Active connections and state
@@ -113,15 +118,20 @@ impl NotificationManager {
             None
         };
 
+        // Initialize metrics
+        let metrics = NotificationMetrics::new(&config.metrics)?;
+
         let webhook_channel = WebhookChannel::new(&config.webhook).await?;
-        let websocket_channel = WebSocketChannel::new(&config.websocket).await?;
+
+        // The WebSocket routes register connections with the same manager the channel delivers through
+        let websocket_manager =
+            WebSocketManager::with_config(&config.websocket, Some(metrics.clone())).await?;
+        let websocket_channel =
+            WebSocketChannel::with_manager(&config.websocket, Arc::new(websocket_manager));
 
         // Initialize template manager
         let template_manager = TemplateManager::new(&config.template).await?;
 
-        // Initialize metrics
-        let metrics = NotificationMetrics::new(&config.metrics)?;
-
         // Initialize scheduler
         let scheduler = if config.scheduler.enabled {
             Some(NotificationScheduler::new(&config.scheduler).await?)
@@ -129,6 +139,8 @@ impl NotificationManager {
             None
         };
 
+        let digest_queue = DigestQueue::new(&config.digest)?;
+
         info!("Notification manager initialized successfully");
 
         Ok(Self {
@@ -143,6 +155,7 @@ impl NotificationManager {
             websocket_channel,
             template_manager,
             scheduler,
+            digest_queue,
             metrics,
             active_connections: Arc::new(DashMap::new()),
             notification_counter: AtomicU64::new(0),
@@ -173,7 +186,7 @@ impl NotificationManager {
 
         // Filter channels based on user preferences and quiet hours
         let filtered_channels = if let Some(prefs) = &user_preferences {
-            self.filter_channels_by_preferences(&request.channels, prefs, &now)?
+            self.filter_channels_by_preferences(&request.channels, prefs)?
         } else {
             request.channels.clone()
         };
@@ -192,6 +205,44 @@ impl NotificationManager {
             (request.title.clone(), request.content.clone())
         };
 
+        // Recipients with a daily or weekly frequency get non-urgent notifications in a digest
+        let digest_frequency = match user_preferences {
+            Some(ref prefs)
+                if self.config.digest.enabled
+                    && self.scheduler.is_some()
+                    && request.scheduled_at.is_none()
+                    && request.priority != NotificationPriority::Urgent =>
+            {
+                DigestFrequency::from_preference(&prefs.notification_frequency)
+            }
+            _ => None,
+        };
+
+        // Defer non-urgent notifications that would land in the recipient's quiet hours
+        let mut scheduled_at = request.scheduled_at;
+        if digest_frequency.is_none() && request.priority != NotificationPriority::Urgent {
+            if let Some(quiet_hours) = user_preferences
+                .as_ref()
+                .and_then(|p| p.quiet_hours.as_ref())
+            {
+                let send_at = scheduled_at.map_or(now, |at| at.max(now));
+                if let Some(quiet_end) = quiet_hours_end(send_at, quiet_hours) {
+                    if self.scheduler.is_some() {
+                        info!(
+                            "Deferring notification {} until quiet hours end at {}",
+                            notification_id, quiet_end
+                        );
+                        scheduled_at = Some(quiet_end);
+                    } else {
+                        warn!(
+                            "Scheduler disabled, notification {} will be sent during quiet hours",
+                            notification_id
+                        );
+                    }
+                }
+            }
+        }
+
         // Create notification record
         let mut notification = NotificationResponse {
             id: notification_id.clone(),
@@ -205,17 +256,40 @@ impl NotificationManager {
             delivery_attempts: Vec::new(),
             created_at: now,
             updated_at: now,
-            scheduled_at: request.scheduled_at,
+            scheduled_at,
             delivered_at: None,
             expires_at: request.expires_at,
             metadata: request.metadata,
         };
 
+        if let Some(frequency) = digest_frequency {
+            let due_at = self
+                .digest_queue
+                .due_at(&notification.recipient_id, frequency, now);
+            notification.scheduled_at = Some(due_at);
+            self.store_notification(&notification).await?;
+            self.persist_digest_entry(&QueuedDigestEntry {
+                frequency,
+                due_at,
+                notification: notification.clone(),
+            })
+            .await?;
+            self.digest_queue
+                .enqueue(notification.clone(), frequency, now);
+
+            info!(
+                "Queued notification {} for {} digest",
+                notification_id,
+                frequency.as_str()
+            );
+            return Ok(notification);
+        }
+
         // Store notification in database
         self.store_notification(&notification).await?;
 
         // If scheduled for future, add to scheduler
-        if let Some(scheduled_at) = request.scheduled_at {
+        if let Some(scheduled_at) = scheduled_at {
             if scheduled_at > now {
                 if let Some(ref scheduler) = self.scheduler {
                     scheduler.schedule_notification(&notification).await?;
@@ -225,7 +299,8 @@ impl NotificationManager {
         }
 
         // Send immediately
-        self.process_notification(&mut notification).await?;
+        self.process_notification(&mut notification, user_preferences.as_ref())
+            .await?;
 
         // Update notification status
         self.update_notification_status(&notification).await?;
@@ -451,6 +526,18 @@ impl NotificationManager {
         self.template_manager.delete_template(id).await
     }
 
+    /// Render a template preview with sample data
+    pub async fn render_preview(
+        &self,
+        template_id: &str,
+        sample_data: &serde_json::Value,
+        strict: bool,
+    ) -> Result<RenderedPreview> {
+        self.template_manager
+            .render_preview(template_id, sample_data, strict)
+            .await
+    }
+
     // Subscription management methods
 
     /// Create a notification subscription
@@ -634,12 +721,73 @@ impl NotificationManager {
     /// Start the background scheduler
     pub async fn start_scheduler(&self) -> Result<()> {
         if let Some(ref scheduler) = self.scheduler {
-            scheduler.start().await
+            if scheduler.is_running().await {
+                return Ok(());
+            }
+
+            scheduler.start().await?;
+            if self.config.digest.enabled {
+                match self.restore_digests().await {
+                    Ok(restored) => info!("Restored {} queued digest notifications", restored),
+                    Err(e) => error!("Failed to restore queued digest notifications: {}", e),
+                }
+                scheduler
+                    .add_background_task(self.spawn_digest_task())
+                    .await;
+            }
+            Ok(())
         } else {
             Err(NotificationError::config("Scheduler is not enabled"))
         }
     }
 
+    /// Send every digest that is due, returning stats for each digest sent
+    pub async fn flush_digests(&self, now: DateTime<Utc>) -> Vec<DigestStats> {
+        let mut sent = Vec::new();
+
+        for batch in self.digest_queue.take_due(now) {
+            match self.send_digest(&batch).await {
+                Ok(stats) => {
+                    info!(
+                        "Sent {} digest {} to {} ({} notifications collapsed)",
+                        stats.frequency.as_str(),
+                        stats.digest_id,
+                        stats.recipient_id,
+                        stats.collapsed
+                    );
+                    if let Err(e) = self.remove_digest_entries(&batch).await {
+                        error!(
+                            "Sent digest {} but failed to dequeue its notifications: {}",
+                            stats.digest_id, e
+                        );
+                    }
+                    sent.push(stats);
+                }
+                Err(e) => {
+                    error!(
+                        "Failed to send {} digest to {}: {}",
+                        batch.frequency.as_str(),
+                        batch.recipient_id,
+                        e
+                    );
+                    self.digest_queue.requeue(batch, now);
+                }
+            }
+        }
+
+        sent
+    }
+
+    /// Number of notifications waiting for a digest
+    pub fn pending_digest_notifications(&self) -> usize {
+        self.digest_queue.pending_count()
+    }
+
+    /// WebSocket connection manager used for real-time delivery
+    pub fn websocket_manager(&self) -> Arc<WebSocketManager> {
+        self.websocket_channel.manager()
+    }
+
     /// Stop the background scheduler
     pub async fn stop_scheduler(&self) -> Result<()> {
         if let Some(ref scheduler) = self.scheduler {
@@ -933,17 +1081,7 @@ impl NotificationManager {
         &self,
         channels: &[ai_core_shared::types::NotificationChannel],
         preferences: &NotificationPreferences,
-        now: &chrono::DateTime<chrono::Utc>,
     ) -> Result<Vec<ai_core_shared::types::NotificationChannel>> {
-        // Check quiet hours
-        if let Some(ref quiet_hours) = preferences.quiet_hours {
-            if self.is_in_quiet_hours(now, quiet_hours)? {
-                // Only allow critical notifications during quiet hours
-                return Ok(vec![ai_core_shared::types::NotificationChannel::Push]);
-                // Or filter by priority
-            }
-        }
-
         // Check channel preferences
         let mut filtered = Vec::new();
         for channel in channels {
@@ -969,14 +1107,160 @@ impl NotificationManager {
         Ok(filtered)
     }
 
-    fn is_in_quiet_hours(
-        &self,
-        _now: &chrono::DateTime<chrono::Utc>,
-        _quiet_hours: &ai_core_shared::types::QuietHours,
-    ) -> Result<bool> {
-        // This is a simplified implementation
-        // In a real system, you'd want proper timezone handling
-        Ok(false) // For now, never in quiet hours
+    fn spawn_digest_task(&self) -> tokio::task::JoinHandle<()> {
+        let manager = self.clone();
+        let check_interval = Duration::from_secs(self.config.scheduler.check_interval_seconds);
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(check_interval);
+            loop {
+                interval.tick().await;
+                manager.flush_digests(Utc::now()).await;
+            }
+        })
+    }
+
+    /// Persist a queued notification so a restart does not lose it
+    async fn persist_digest_entry(&self, entry: &QueuedDigestEntry) -> Result<()> {
+        if let Some(ref mongo) = self.mongo {
+            let collection: Collection<QueuedDigestEntry> = mongo.collection("digest_queue");
+            match collection.insert_one(entry, None).await {
+                Ok(_) => Ok(()),
+                Err(e) => Err(NotificationError::database(e.to_string())),
+            }
+        } else {
+            warn!("No MongoDB connection, digest queue not persisted");
+            Ok(())
+        }
+    }
+
+    /// Drop a sent batch's notifications from the persisted queue
+    async fn remove_digest_entries(&self, batch: &DigestBatch) -> Result<()> {
+        if let Some(ref mongo) = self.mongo {
+            let collection: Collection<QueuedDigestEntry> = mongo.collection("digest_queue");
+            let ids: Vec<&str> = batch.notifications.iter().map(|n| n.id.as_str()).collect();
+            match collection
+                .delete_many(doc! { "notification.id": { "$in": ids } }, None)
+                .await
+            {
+                Ok(_) => Ok(()),
+                Err(e) => Err(NotificationError::database(e.to_string())),
+            }
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Load the persisted queue into the in-memory digest queue, returning
+    /// the number of notifications restored
+    async fn restore_digests(&self) -> Result<usize> {
+        let Some(ref mongo) = self.mongo else {
+            return Ok(0);
+        };
+
+        let collection: Collection<QueuedDigestEntry> = mongo.collection("digest_queue");
+        let mut cursor = collection
+            .find(doc! {}, None)
+            .await
+            .map_err(|e| NotificationError::database(e.to_string()))?;
+
+        let mut restored = 0;
+        while cursor
+            .advance()
+            .await
+            .map_err(|e| NotificationError::database(e.to_string()))?
+        {
+            let entry = cursor
+                .deserialize_current()
+                .map_err(|e| NotificationError::database(e.to_string()))?;
+            self.digest_queue.restore(entry);
+            restored += 1;
+        }
+
+        Ok(restored)
+    }
+
+    /// Deliver a digest. Fails only when the digest reached no channel, in
+    /// which case the batch is queued again; once delivered, failing to
+    /// record the outcome is logged rather than causing a second digest.
+    async fn send_digest(&self, batch: &DigestBatch) -> Result<DigestStats> {
+        let now = Utc::now();
+        let max_items = self.config.digest.max_items;
+        let digest_id = Uuid::new_v4().to_string();
+        let stats = batch.stats(&digest_id, max_items);
+
+        let (title, content) = self
+            .template_manager
+            .render_digest(
+                self.config.digest.template_id.as_deref(),
+                &batch.context(max_items),
+            )
+            .await?;
+
+        let mut digest = NotificationResponse {
+            id: digest_id,
+            recipient_id: batch.recipient_id.clone(),
+            notification_type: batch.notification_type(),
+            title,
+            content,
+            channels: batch.channels(),
+            priority: NotificationPriority::Normal,
+            status: NotificationStatus::Queued,
+            delivery_attempts: Vec::new(),
+            created_at: now,
+            updated_at: now,
+            scheduled_at: Some(batch.due_at),
+            delivered_at: None,
+            expires_at: None,
+            metadata: Some(serde_json::json!({
+                "digest": {
+                    "frequency": stats.frequency,
+                    "collapsed": stats.collapsed,
+                    "listed": stats.listed,
+                    "overflow": stats.overflow,
+                    "notification_ids": batch
+                        .notifications
+                        .iter()
+                        .map(|n| n.id.as_str())
+                        .collect::<Vec<_>>(),
+                }
+            })),
+        };
+
+        self.store_notification(&digest).await?;
+
+        // Channels were already filtered by preferences when the notifications were queued
+        self.process_notification(&mut digest, None).await?;
+        if let Err(e) = self.update_notification_status(&digest).await {
+            warn!("Failed to record status of digest {}: {}", digest.id, e);
+        }
+
+        if needs_resend(&digest.status) {
+            return Err(NotificationError::external_service(
+                "digest",
+                format!("digest {} was not delivered on any channel", digest.id),
+            ));
+        }
+
+        self.metrics.record_notification_sent(&digest).await;
+        self.metrics
+            .record_digest(stats.frequency.as_str(), stats.collapsed);
+
+        // Collapsed notifications share the outcome of their digest
+        for notification in &batch.notifications {
+            let mut notification = notification.clone();
+            notification.status = digest.status.clone();
+            notification.delivered_at = digest.delivered_at;
+            notification.updated_at = digest.updated_at;
+            if let Err(e) = self.update_notification_status(&notification).await {
+                warn!(
+                    "Failed to record digest {} outcome for notification {}: {}",
+                    digest.id, notification.id, e
+                );
+            }
+        }
+
+        Ok(stats)
     }
 
     async fn render_notification_content(
@@ -1003,7 +1287,11 @@ impl NotificationManager {
         }
     }
 
-    async fn process_notification(&self, notification: &mut NotificationResponse) -> Result<()> {
+    async fn process_notification(
+        &self,
+        notification: &mut NotificationResponse,
+        preferences: Option<&NotificationPreferences>,
+    ) -> Result<()> {
         notification.status = NotificationStatus::Processing;
         notification.updated_at = Utc::now();
 
@@ -1012,6 +1300,19 @@ impl NotificationManager {
 
         // Process each channel
         for channel in &notification.channels {
+            // AWS SNS records its own attempt so the SNS message ID is kept
+            if let (ai_core_shared::types::NotificationChannel::Sms, Some(sns_channel)) = (
+                channel,
+                self.sms_channel.as_ref().and_then(|c| c.sns_channel()),
+            ) {
+                let attempt = sns_channel.deliver(notification, preferences).await;
+                if attempt.status == DeliveryStatus::Success {
+                    successful_channels += 1;
+                }
+                notification.delivery_attempts.push(attempt);
+                continue;
+            }
+
             let attempt_id = Uuid::new_v4().to_string();
             let attempt_start = Utc::now();
 
@@ -1170,6 +1471,7 @@ impl Clone for NotificationManager {
             websocket_channel: self.websocket_channel.clone(),
             template_manager: self.template_manager.clone(),
             scheduler: self.scheduler.clone(),
+            digest_queue: self.digest_queue.clone(),
             metrics: self.metrics.clone(),
             active_connections: self.active_connections.clone(),
             notification_counter: AtomicU64::new(self.notification_counter.load(Ordering::Relaxed)),
@@ -1188,10 +1490,160 @@ fn channel_to_string(channel: &ai_core_shared::types::NotificationChannel) -> St
     }
 }
 
+/// Longest DST gap searched when quiet hours end on a skipped local time
+const MAX_DST_GAP_MINUTES: i64 = 180;
+
+/// Return when the recipient's quiet hours end if `at` falls inside them
+///
+/// Start and end are wall-clock times in the recipient's timezone, so windows
+/// that cross midnight (e.g. 22:00-07:00) and DST changes are handled in local
+/// time. An empty or unknown timezone falls back to UTC. If the end time is
+/// skipped by a DST change, quiet hours end at the first valid local time after it.
+fn quiet_hours_end(
+    at: DateTime<Utc>,
+    quiet_hours: &ai_core_shared::types::QuietHours,
+) -> Option<DateTime<Utc>> {
+    let timezone = if quiet_hours.timezone.is_empty() {
+        chrono_tz::UTC
+    } else {
+        quiet_hours.timezone.parse().unwrap_or_else(|_| {
+            warn!(
+                "Unknown quiet hours timezone '{}', falling back to UTC",
+                quiet_hours.timezone
+            );
+            chrono_tz::UTC
+        })
+    };
+
+    let parse_time = |value: &str| chrono::NaiveTime::parse_from_str(value, "%H:%M").ok();
+    let (Some(start), Some(end)) = (
+        parse_time(&quiet_hours.start_time),
+        parse_time(&quiet_hours.end_time),
+    ) else {
+        warn!(
+            "Invalid quiet hours {}-{}, ignoring them",
+            quiet_hours.start_time, quiet_hours.end_time
+        );
+        return None;
+    };
+
+    let local = at.with_timezone(&timezone);
+    let time = local.time();
+    let today = local.date_naive();
+
+    let end_date = if start < end {
+        if time < start || time >= end {
+            return None;
+        }
+        today
+    } else if start > end {
+        if time >= start {
+            today.succ_opt()?
+        } else if time < end {
+            today
+        } else {
+            return None;
+        }
+    } else {
+        // Identical start and end times describe an empty window
+        return None;
+    };
+
+    let end_local = end_date.and_time(end);
+    (0..=MAX_DST_GAP_MINUTES).find_map(|minutes| {
+        timezone
+            .from_local_datetime(&(end_local + chrono::Duration::minutes(minutes)))
+            .earliest()
+            .map(|end| end.with_timezone(&Utc))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn quiet_hours(start_time: &str, end_time: &str, timezone: &str) -> QuietHours {
+        QuietHours {
+            start_time: start_time.to_string(),
+            end_time: end_time.to_string(),
+            timezone: timezone.to_string(),
+        }
+    }
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn test_quiet_hours_same_day_window() {
+        let hours = quiet_hours("12:00", "14:00", "");
+
+        assert_eq!(
+            quiet_hours_end(utc(2024, 6, 1, 12, 30), &hours),
+            Some(utc(2024, 6, 1, 14, 0))
+        );
+        assert_eq!(quiet_hours_end(utc(2024, 6, 1, 14, 0), &hours), None);
+        assert_eq!(quiet_hours_end(utc(2024, 6, 1, 11, 59), &hours), None);
+    }
+
+    #[test]
+    fn test_quiet_hours_cross_midnight_in_timezone() {
+        // New York is UTC-4 in June
+        let hours = quiet_hours("22:00", "07:00", "America/New_York");
+
+        // 23:30 local on June 1st ends at 07:00 local on June 2nd
+        assert_eq!(
+            quiet_hours_end(utc(2024, 6, 2, 3, 30), &hours),
+            Some(utc(2024, 6, 2, 11, 0))
+        );
+        // 05:00 local ends the same morning
+        assert_eq!(
+            quiet_hours_end(utc(2024, 6, 2, 9, 0), &hours),
+            Some(utc(2024, 6, 2, 11, 0))
+        );
+        // 12:00 local is outside quiet hours
+        assert_eq!(quiet_hours_end(utc(2024, 6, 2, 16, 0), &hours), None);
+    }
+
+    #[test]
+    fn test_quiet_hours_across_dst_transitions() {
+        // Spring forward: 02:00 EST jumps to 03:00 EDT on 2024-03-10
+        let hours = quiet_hours("22:00", "07:00", "America/New_York");
+        assert_eq!(
+            quiet_hours_end(utc(2024, 3, 10, 4, 0), &hours),
+            Some(utc(2024, 3, 10, 11, 0))
+        );
+
+        // End time inside the skipped hour ends quiet hours at 03:00 EDT
+        let hours = quiet_hours("23:00", "02:30", "America/New_York");
+        assert_eq!(
+            quiet_hours_end(utc(2024, 3, 10, 5, 0), &hours),
+            Some(utc(2024, 3, 10, 7, 0))
+        );
+
+        // Fall back: 01:30 happens twice on 2024-11-03, the first one wins
+        let hours = quiet_hours("23:00", "01:30", "America/New_York");
+        assert_eq!(
+            quiet_hours_end(utc(2024, 11, 3, 4, 0), &hours),
+            Some(utc(2024, 11, 3, 5, 30))
+        );
+    }
+
+    #[test]
+    fn test_quiet_hours_fallbacks() {
+        let unknown = quiet_hours("22:00", "07:00", "Mars/Olympus_Mons");
+        assert_eq!(
+            quiet_hours_end(utc(2024, 6, 1, 23, 0), &unknown),
+            Some(utc(2024, 6, 2, 7, 0))
+        );
+
+        let invalid = quiet_hours("late", "07:00", "UTC");
+        assert_eq!(quiet_hours_end(utc(2024, 6, 1, 23, 0), &invalid), None);
+
+        let empty = quiet_hours("07:00", "07:00", "UTC");
+        assert_eq!(quiet_hours_end(utc(2024, 6, 1, 7, 0), &empty), None);
+    }
+
     #[tokio::test]
     async fn test_notification_manager_creation() {
         let config = NotificationConfig::default();
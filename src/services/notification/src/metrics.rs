@@ -13,7 +13,7 @@ use ai_core_shared::types::{
     ChannelStats, NotificationChannel, NotificationResponse, NotificationStats,
 };
 
-use prometheus::{HistogramVec, IntCounterVec, IntGaugeVec, Registry};
+use prometheus::{HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Registry};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
@@ -34,11 +34,13 @@ pub struct NotificationMetrics {
 
     // Gauges
     active_connections: IntGaugeVec,
+    websocket_connected_users: IntGauge,
     queue_size: IntGaugeVec,
 
     // Histograms
     delivery_duration: HistogramVec,
     template_render_duration: HistogramVec,
+    digest_size: HistogramVec,
 
     // Channel-specific metrics
     channel_stats: Arc<RwLock<HashMap<NotificationChannel, ChannelMetrics>>>,
@@ -133,6 +135,20 @@ impl NotificationMetrics {
             NotificationError::internal(format!("Failed to create active_connections gauge: {}", e))
         })?;
 
+        let websocket_connected_users = IntGauge::with_opts(
+            prometheus::Opts::new(
+                "websocket_connected_users",
+                "Number of users with at least one open WebSocket connection",
+            )
+            .namespace(&config.namespace),
+        )
+        .map_err(|e| {
+            NotificationError::internal(format!(
+                "Failed to create websocket_connected_users gauge: {}",
+                e
+            ))
+        })?;
+
         let queue_size = IntGaugeVec::new(
             prometheus::Opts::new(
                 "notification_queue_size",
@@ -178,6 +194,19 @@ impl NotificationMetrics {
             ))
         })?;
 
+        let digest_size = HistogramVec::new(
+            prometheus::HistogramOpts::new(
+                "notification_digest_size",
+                "Number of notifications collapsed into each digest",
+            )
+            .namespace(&config.namespace)
+            .buckets(vec![1.0, 2.0, 5.0, 10.0, 20.0, 50.0, 100.0, 250.0]),
+            &["frequency"],
+        )
+        .map_err(|e| {
+            NotificationError::internal(format!("Failed to create digest_size histogram: {}", e))
+        })?;
+
         // Register metrics
         registry
             .register(Box::new(notifications_total.clone()))
@@ -213,6 +242,14 @@ impl NotificationMetrics {
             .map_err(|e| {
                 NotificationError::internal(format!("Failed to register active_connections: {}", e))
             })?;
+        registry
+            .register(Box::new(websocket_connected_users.clone()))
+            .map_err(|e| {
+                NotificationError::internal(format!(
+                    "Failed to register websocket_connected_users: {}",
+                    e
+                ))
+            })?;
         registry
             .register(Box::new(queue_size.clone()))
             .map_err(|e| {
@@ -231,6 +268,11 @@ impl NotificationMetrics {
                     e
                 ))
             })?;
+        registry
+            .register(Box::new(digest_size.clone()))
+            .map_err(|e| {
+                NotificationError::internal(format!("Failed to register digest_size: {}", e))
+            })?;
 
         info!("Notification metrics initialized successfully");
 
@@ -242,9 +284,11 @@ impl NotificationMetrics {
             notifications_delivered,
             notifications_failed,
             active_connections,
+            websocket_connected_users,
             queue_size,
             delivery_duration,
             template_render_duration,
+            digest_size,
             channel_stats: Arc::new(RwLock::new(HashMap::new())),
         })
     }
@@ -333,6 +377,11 @@ impl NotificationMetrics {
             .set(count);
     }
 
+    /// Record the number of users with open WebSocket connections
+    pub fn record_websocket_users(&self, count: i64) {
+        self.websocket_connected_users.set(count);
+    }
+
     /// Record notification queue size
     pub fn record_queue_size(&self, priority: &str, size: i64) {
         self.queue_size.with_label_values(&[priority]).set(size);
@@ -346,6 +395,13 @@ impl NotificationMetrics {
             .observe(duration);
     }
 
+    /// Record how many notifications were collapsed into a digest
+    pub fn record_digest(&self, frequency: &str, collapsed: usize) {
+        self.digest_size
+            .with_label_values(&[frequency])
+            .observe(collapsed as f64);
+    }
+
     /// Get aggregated notification statistics
     pub async fn get_notification_stats(&self) -> Result<NotificationStats> {
         let stats = self.channel_stats.read().await;
@@ -649,6 +705,17 @@ mod tests {
         assert!(health_metrics.is_object());
     }
 
+    #[test]
+    fn test_websocket_users_metric() {
+        let config = create_test_config();
+        let metrics = NotificationMetrics::new(&config).unwrap();
+
+        metrics.record_websocket_users(3);
+
+        let exported = metrics.export_metrics().unwrap();
+        assert!(exported.contains("websocket_connected_users 3"));
+    }
+
     #[tokio::test]
     async fn test_queue_size_metric() {
         let config = create_test_config();
@@ -661,6 +728,17 @@ mod tests {
         assert!(health_metrics.is_object());
     }
 
+    #[test]
+    fn test_digest_size_metric() {
+        let config = create_test_config();
+        let metrics = NotificationMetrics::new(&config).unwrap();
+
+        metrics.record_digest("daily", 7);
+
+        let exported = metrics.export_metrics().unwrap();
+        assert!(exported.contains("notification_digest_size"));
+    }
+
     #[test]
     fn test_template_render_duration() {
         let config = create_test_config();
@@ -99,6 +99,10 @@ fn create_api_router(notification_manager: Arc<NotificationManager>) -> Router {
             "/api/v1/templates/:id/render",
             post(templates_handler::render_template),
         )
+        .route(
+            "/api/v1/templates/:id/preview",
+            post(templates_handler::preview_template),
+        )
         // Subscription endpoints
         .route(
             "/api/v1/subscriptions",
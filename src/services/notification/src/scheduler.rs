@@ -143,6 +143,16 @@ impl NotificationScheduler {
         Ok(())
     }
 
+    /// Check whether the scheduler background tasks are running
+    pub async fn is_running(&self) -> bool {
+        *self.is_running.read().await
+    }
+
+    /// Track a background task so it is stopped together with the scheduler
+    pub async fn add_background_task(&self, task: tokio::task::JoinHandle<()>) {
+        self.task_handles.write().await.push(task);
+    }
+
     /// Schedule a notification for future delivery
     pub async fn schedule_notification(&self, notification: &NotificationResponse) -> Result<()> {
         let scheduled_at = notification.scheduled_at.ok_or_else(|| {
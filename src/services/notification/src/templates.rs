@@ -5,6 +5,7 @@
 //! - Template rendering with Handlebars
 //! - Template caching
 //! - Variable validation
+//! - Template previews with missing/unused variable reports
 //! - Multi-language support
 
 use crate::config::TemplateConfig;
@@ -18,12 +19,39 @@ use mongodb::{
     options::FindOptions,
     Collection, Database,
 };
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{info, warn};
 use uuid::Uuid;
 
+const DIGEST_SUBJECT_TEMPLATE: &str = "digest_subject";
+const DIGEST_CONTENT_TEMPLATE: &str = "digest_content";
+const DEFAULT_DIGEST_SUBJECT: &str = "Your {{frequency}} digest: {{total}} notifications";
+const DEFAULT_DIGEST_CONTENT: &str = "{{#each items}}- {{title}}: {{content}}\n{{/each}}\
+{{#if overflow_count}}...and {{overflow_count}} more{{/if}}";
+
+/// Result of rendering a template against sample data
+#[derive(Debug, Clone, Serialize)]
+pub struct RenderedPreview {
+    pub template_id: String,
+    pub channels: Vec<ChannelPreview>,
+    /// Variables referenced by the template but absent from the sample data
+    pub missing_variables: Vec<String>,
+    /// Sample data fields the template never references
+    pub unused_variables: Vec<String>,
+}
+
+/// Rendered template output for a single channel
+#[derive(Debug, Clone, Serialize)]
+pub struct ChannelPreview {
+    pub channel: NotificationChannel,
+    /// Rendered subject, `None` for channels without one (SMS)
+    pub subject: Option<String>,
+    pub content: String,
+}
+
 /// Template manager for handling notification templates
 #[derive(Clone)]
 pub struct TemplateManager {
@@ -47,6 +75,16 @@ impl TemplateManager {
         // Register built-in helpers
         Self::register_helpers(&mut handlebars)?;
 
+        // Register the built-in digest template
+        handlebars
+            .register_template_string(DIGEST_SUBJECT_TEMPLATE, DEFAULT_DIGEST_SUBJECT)
+            .and_then(|_| {
+                handlebars.register_template_string(DIGEST_CONTENT_TEMPLATE, DEFAULT_DIGEST_CONTENT)
+            })
+            .map_err(|e| {
+                NotificationError::template(format!("Failed to register digest template: {}", e))
+            })?;
+
         let manager = Self {
             config: config.clone(),
             handlebars: Arc::new(RwLock::new(handlebars)),
@@ -307,6 +345,117 @@ impl TemplateManager {
         Ok((subject, content))
     }
 
+    /// Render a digest, using the built-in digest template when `template_id` is unset
+    pub async fn render_digest(
+        &self,
+        template_id: Option<&str>,
+        context: &serde_json::Value,
+    ) -> Result<(String, String)> {
+        if let Some(template_id) = template_id {
+            return self
+                .render_notification(template_id, &Some(context.clone()))
+                .await;
+        }
+
+        let handlebars = self.handlebars.read().await;
+        let subject = handlebars
+            .render(DIGEST_SUBJECT_TEMPLATE, context)
+            .map_err(|e| NotificationError::template(format!("Subject render error: {}", e)))?;
+        let content = handlebars
+            .render(DIGEST_CONTENT_TEMPLATE, context)
+            .map_err(|e| NotificationError::template(format!("Content render error: {}", e)))?;
+
+        Ok((subject, content))
+    }
+
+    /// Render a template with sample data and report variable problems
+    ///
+    /// Missing variables render as empty strings unless `strict` is set, in
+    /// which case they are reported as a validation error.
+    pub async fn render_preview(
+        &self,
+        template_id: &str,
+        sample_data: &serde_json::Value,
+        strict: bool,
+    ) -> Result<RenderedPreview> {
+        let template = self
+            .get_template(template_id)
+            .await?
+            .ok_or_else(|| NotificationError::not_found("template"))?;
+
+        let supplied: BTreeSet<&str> = sample_data
+            .as_object()
+            .map(|data| data.keys().map(String::as_str).collect())
+            .unwrap_or_default();
+
+        let handlebars = self.handlebars.read().await;
+
+        let mut referenced = BTreeSet::new();
+        for source in [&template.subject_template, &template.content_template] {
+            collect_template_variables(
+                source,
+                &|name: &str| handlebars.has_helper(name),
+                &mut referenced,
+            );
+        }
+        let required = template
+            .variables
+            .iter()
+            .filter(|v| v.is_required)
+            .map(|v| v.name.clone());
+
+        let missing_variables: Vec<String> = referenced
+            .iter()
+            .cloned()
+            .chain(required)
+            .filter(|name| !supplied.contains(name.as_str()))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let unused_variables: Vec<String> = supplied
+            .iter()
+            .filter(|name| !referenced.contains(**name))
+            .map(|name| name.to_string())
+            .collect();
+
+        if strict && !missing_variables.is_empty() {
+            return Err(NotificationError::validation(
+                "sample_data",
+                format!(
+                    "Missing template variables: {}",
+                    missing_variables.join(", ")
+                ),
+            ));
+        }
+
+        let subject = handlebars
+            .render(&format!("subject_{}", template_id), sample_data)
+            .map_err(|e| NotificationError::template(format!("Subject render error: {}", e)))?;
+        let content = handlebars
+            .render(&format!("content_{}", template_id), sample_data)
+            .map_err(|e| NotificationError::template(format!("Content render error: {}", e)))?;
+
+        let channels = template
+            .channels
+            .iter()
+            .map(|channel| ChannelPreview {
+                channel: channel.clone(),
+                subject: match channel {
+                    NotificationChannel::Sms => None,
+                    _ => Some(subject.clone()),
+                },
+                content: content.clone(),
+            })
+            .collect();
+
+        Ok(RenderedPreview {
+            template_id: template.id,
+            channels,
+            missing_variables,
+            unused_variables,
+        })
+    }
+
     /// Validate template variables against provided data
     pub fn validate_template_data(
         &self,
@@ -521,6 +670,154 @@ impl TemplateManager {
     }
 }
 
+/// Collect the root data fields referenced by a Handlebars template
+///
+/// Only fields resolved against the root context are collected: paths inside
+/// `#each`/`#with` (or sections on a variable) are relative to the block
+/// context and are skipped unless they use `@root`.
+fn collect_template_variables(
+    source: &str,
+    is_helper: &dyn Fn(&str) -> bool,
+    variables: &mut BTreeSet<String>,
+) {
+    // One entry per open block, true when the block changes the context
+    let mut blocks: Vec<bool> = Vec::new();
+    let mut rest = source;
+
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        let expression = after[..end]
+            .trim_start_matches(['{', '~'])
+            .trim_end_matches('~')
+            .trim();
+        rest = &after[end + 2..];
+
+        let (kind, body) = match expression.chars().next() {
+            Some(c @ ('#' | '^' | '/' | '!' | '>')) => (Some(c), expression[1..].trim()),
+            _ => (None, expression),
+        };
+        if matches!(kind, Some('!' | '>')) || body == "else" || body.is_empty() {
+            continue;
+        }
+        // `{{else if cond}}` continues the enclosing block with a new condition
+        let body = body.strip_prefix("else ").map_or(body, str::trim);
+        if kind == Some('/') {
+            blocks.pop();
+            continue;
+        }
+
+        let in_scope = !blocks.contains(&true);
+        let tokens = tokenize_expression(body);
+        let Some(&(head, _)) = tokens.first() else {
+            if matches!(kind, Some('#' | '^')) {
+                blocks.push(false);
+            }
+            continue;
+        };
+        let helper = tokens.len() > 1 || is_helper(head);
+
+        for (i, (token, is_subexpression_head)) in tokens.iter().enumerate() {
+            if (i == 0 && helper) || *is_subexpression_head {
+                continue;
+            }
+            if let Some(name) = root_variable(token, in_scope) {
+                variables.insert(name.to_string());
+            }
+        }
+
+        match kind {
+            Some('#') => blocks.push(!helper || matches!(head, "each" | "with")),
+            Some('^') => blocks.push(false),
+            _ => {}
+        }
+    }
+}
+
+/// Split an expression into path tokens, dropping literals
+///
+/// Each token is flagged when it names the helper of a subexpression.
+fn tokenize_expression(expression: &str) -> Vec<(&str, bool)> {
+    let mut tokens = Vec::new();
+    let mut subexpression_head = false;
+    let mut chars = expression.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        match c {
+            '(' => subexpression_head = true,
+            ')' | ' ' | '\t' | '\n' | '\r' => {}
+            '"' | '\'' => {
+                skip_quoted(&mut chars, c);
+            }
+            _ => {
+                let mut end = start + c.len_utf8();
+                while let Some(&(i, next)) = chars.peek() {
+                    if next.is_whitespace() || next == '(' || next == ')' {
+                        break;
+                    }
+                    chars.next();
+                    end = match next {
+                        // Quoted hash values may contain whitespace
+                        '"' | '\'' => skip_quoted(&mut chars, next).unwrap_or(expression.len()),
+                        _ => i + next.len_utf8(),
+                    };
+                }
+                let token = &expression[start..end];
+                // Hash arguments (key=value) reference their value
+                let token = token.split_once('=').map_or(token, |(_, value)| value);
+                if !token.is_empty() {
+                    tokens.push((token, subexpression_head));
+                }
+                subexpression_head = false;
+            }
+        }
+    }
+
+    // Literal values never reference data, but keep helper names in place
+    tokens
+        .into_iter()
+        .enumerate()
+        .filter(|(i, (token, head))| *i == 0 || *head || !is_literal(token))
+        .map(|(_, token)| token)
+        .collect()
+}
+
+/// Consume a quoted string, returning the byte offset just past the closing quote
+fn skip_quoted(
+    chars: &mut std::iter::Peekable<std::str::CharIndices<'_>>,
+    quote: char,
+) -> Option<usize> {
+    chars
+        .find(|&(_, c)| c == quote)
+        .map(|(i, c)| i + c.len_utf8())
+}
+
+fn is_literal(token: &str) -> bool {
+    matches!(token, "true" | "false" | "null" | "undefined")
+        || token.starts_with(['"', '\''])
+        || token.parse::<f64>().is_ok()
+}
+
+/// Resolve the root field of a path, if it refers to the root context
+fn root_variable(path: &str, in_scope: bool) -> Option<&str> {
+    let path = match path.strip_prefix("@root.") {
+        Some(root_path) => root_path,
+        None if in_scope && !path.starts_with(['@', '.']) && !is_literal(path) => {
+            path.strip_prefix("this.").unwrap_or(path)
+        }
+        None => return None,
+    };
+
+    let name = path.split(['.', '/', '[']).next().unwrap_or_default();
+    if name.is_empty() || name == "this" {
+        None
+    } else {
+        Some(name)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -600,6 +897,96 @@ mod tests {
         assert_eq!(content, "Hello John, your workflow is ready!");
     }
 
+    #[test]
+    fn test_collect_template_variables() {
+        let mut variables = BTreeSet::new();
+        collect_template_variables(
+            "Hi {{name}}, {{#if account.premium}}VIP{{else if trial}}trial{{/if}} \
+             {{date completed_at '%Y-%m-%d %H:%M'}} {{{raw_html}}} {{! ignored }}\
+             {{#each items}}{{title}} {{@root.currency}}{{/each}} {{uppercase (lookup labels 'a')}} {{@index}}",
+            &|name: &str| matches!(name, "if" | "each" | "date" | "uppercase" | "lookup"),
+            &mut variables,
+        );
+
+        let expected: BTreeSet<String> = [
+            "account",
+            "completed_at",
+            "currency",
+            "items",
+            "labels",
+            "name",
+            "raw_html",
+            "trial",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+        assert_eq!(variables, expected);
+    }
+
+    #[tokio::test]
+    async fn test_render_preview() {
+        let config = create_test_config();
+        let manager = TemplateManager::new(&config).await.unwrap();
+
+        let request = CreateTemplateRequest {
+            name: "Preview Template".to_string(),
+            description: None,
+            notification_type: NotificationType::WorkflowCompleted,
+            channels: vec![NotificationChannel::Email, NotificationChannel::Sms],
+            subject_template: "Workflow {{workflow_name}}".to_string(),
+            content_template: "Hello {{user_name}}, {{workflow_name}} finished".to_string(),
+            variables: vec![],
+        };
+        let template = manager.create_template(request).await.unwrap();
+
+        let data = serde_json::json!({
+            "workflow_name": "nightly-etl",
+            "usr_name": "Ada"
+        });
+
+        let preview = manager
+            .render_preview(&template.id, &data, false)
+            .await
+            .unwrap();
+        assert_eq!(preview.missing_variables, vec!["user_name".to_string()]);
+        assert_eq!(preview.unused_variables, vec!["usr_name".to_string()]);
+        assert_eq!(preview.channels.len(), 2);
+        assert_eq!(
+            preview.channels[0].subject.as_deref(),
+            Some("Workflow nightly-etl")
+        );
+        assert_eq!(preview.channels[0].content, "Hello , nightly-etl finished");
+        assert_eq!(preview.channels[1].channel, NotificationChannel::Sms);
+        assert!(preview.channels[1].subject.is_none());
+
+        let result = manager.render_preview(&template.id, &data, true).await;
+        assert!(matches!(result, Err(NotificationError::Validation { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_render_default_digest() {
+        let config = create_test_config();
+        let manager = TemplateManager::new(&config).await.unwrap();
+
+        let context = serde_json::json!({
+            "frequency": "daily",
+            "total": 3,
+            "overflow_count": 1,
+            "items": [
+                { "title": "Build", "content": "passed" },
+                { "title": "Deploy", "content": "finished" }
+            ]
+        });
+
+        let (subject, content) = manager.render_digest(None, &context).await.unwrap();
+        assert_eq!(subject, "Your daily digest: 3 notifications");
+        assert_eq!(
+            content,
+            "- Build: passed\n- Deploy: finished\n...and 1 more"
+        );
+    }
+
     #[tokio::test]
     async fn test_template_syntax_validation() {
         let config = create_test_config();
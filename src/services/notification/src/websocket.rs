@@ -6,16 +6,23 @@
 //! - Real-time notification broadcasting
 //! - Connection pooling and cleanup
 //! - Message routing and delivery
+//! - Replay of recently sent messages to reconnecting clients
 
+use crate::config::WebSocketConfig;
 use crate::error::{NotificationError, Result};
+use crate::metrics::NotificationMetrics;
 use ai_core_shared::{api::NotificationWebSocketMessage, types::WebSocketMessageType};
 
 use axum::extract::ws::{Message, WebSocket};
 use dashmap::DashMap;
 use futures::{SinkExt, StreamExt};
+use serde::Serialize;
 use serde_json::json;
+use std::collections::VecDeque;
+use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 use tokio::sync::{broadcast, mpsc};
+use tokio_util::sync::CancellationToken;
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
@@ -30,6 +37,15 @@ pub struct WebSocketManager {
     broadcast_tx: Arc<broadcast::Sender<NotificationWebSocketMessage>>,
     /// Message statistics
     stats: Arc<WebSocketStats>,
+    /// Recently sent messages per user, replayed to reconnecting clients
+    replay_buffers: Arc<DashMap<String, ReplayBuffer>>,
+    /// Source of increasing message IDs used as replay cursors
+    next_message_id: Arc<AtomicU64>,
+    replay_window: chrono::Duration,
+    replay_buffer_size: usize,
+    /// Connections silent for longer than this are reaped
+    stale_after: chrono::Duration,
+    metrics: Option<NotificationMetrics>,
 }
 
 /// Represents an active WebSocket connection
@@ -40,6 +56,31 @@ struct WebSocketConnection {
     sender: mpsc::UnboundedSender<Message>,
     connected_at: chrono::DateTime<chrono::Utc>,
     last_ping: chrono::DateTime<chrono::Utc>,
+    /// Cancelled when the connection is reaped so its handler shuts down
+    shutdown: CancellationToken,
+}
+
+/// Messages kept for a user so a reconnecting client can catch up
+#[derive(Debug, Default)]
+struct ReplayBuffer {
+    messages: VecDeque<BufferedMessage>,
+    /// Highest message ID dropped from the buffer, used to detect replay gaps
+    evicted_through: u64,
+}
+
+#[derive(Debug)]
+struct BufferedMessage {
+    message_id: u64,
+    text: String,
+    buffered_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Wire format for user messages, tagged with the ID clients send back as `last_message_id`
+#[derive(Serialize)]
+struct SequencedMessage<'a> {
+    message_id: u64,
+    #[serde(flatten)]
+    message: &'a NotificationWebSocketMessage,
 }
 
 /// WebSocket connection statistics
@@ -52,17 +93,34 @@ struct WebSocketStats {
 }
 
 impl WebSocketManager {
-    /// Create a new WebSocket manager
+    /// Create a new WebSocket manager with the default configuration
     pub async fn new() -> Result<Self> {
+        Self::with_config(&WebSocketConfig::default(), None).await
+    }
+
+    /// Create a new WebSocket manager, optionally reporting connection gauges to `metrics`
+    pub async fn with_config(
+        config: &WebSocketConfig,
+        metrics: Option<NotificationMetrics>,
+    ) -> Result<Self> {
         info!("Initializing WebSocket manager");
 
         let (broadcast_tx, _) = broadcast::channel(1000);
 
+        // Allow one missed pong before treating a connection as dead
+        let stale_after_seconds = 2 * config.ping_interval_seconds + config.pong_timeout_seconds;
+
         let manager = Self {
             connections: Arc::new(DashMap::new()),
             user_connections: Arc::new(DashMap::new()),
             broadcast_tx: Arc::new(broadcast_tx),
             stats: Arc::new(WebSocketStats::default()),
+            replay_buffers: Arc::new(DashMap::new()),
+            next_message_id: Arc::new(AtomicU64::new(0)),
+            replay_window: chrono::Duration::seconds(config.replay_window_seconds as i64),
+            replay_buffer_size: config.replay_buffer_size,
+            stale_after: chrono::Duration::seconds(stale_after_seconds as i64),
+            metrics,
         };
 
         info!("WebSocket manager initialized successfully");
@@ -70,7 +128,15 @@ impl WebSocketManager {
     }
 
     /// Handle a new WebSocket connection
-    pub async fn handle_connection(&self, user_id: String, websocket: WebSocket) -> Result<()> {
+    ///
+    /// When `last_message_id` is given, buffered messages sent after it are
+    /// replayed before any new messages are delivered.
+    pub async fn handle_connection(
+        &self,
+        user_id: String,
+        websocket: WebSocket,
+        last_message_id: Option<u64>,
+    ) -> Result<()> {
         let connection_id = Uuid::new_v4().to_string();
         info!(
             "Handling new WebSocket connection: {} for user: {}",
@@ -79,6 +145,7 @@ impl WebSocketManager {
 
         let (mut ws_sender, mut ws_receiver) = websocket.split();
         let (msg_tx, mut msg_rx) = mpsc::unbounded_channel::<Message>();
+        let shutdown = CancellationToken::new();
 
         // Create connection record
         let connection = WebSocketConnection {
@@ -87,49 +154,27 @@ impl WebSocketManager {
             sender: msg_tx.clone(),
             connected_at: chrono::Utc::now(),
             last_ping: chrono::Utc::now(),
+            shutdown: shutdown.clone(),
         };
 
-        // Store connection
-        self.connections.insert(connection_id.clone(), connection);
-
-        // Update user connections mapping
-        self.user_connections
-            .entry(user_id.clone())
-            .or_insert_with(Vec::new)
-            .push(connection_id.clone());
+        // Store connection, queueing the welcome message and any replay
+        let replayed = self.register_connection(connection, last_message_id);
+        if replayed > 0 {
+            info!(
+                "Replayed {} buffered messages to connection: {}",
+                replayed, connection_id
+            );
+        }
 
         // Update stats
         self.stats
             .total_connections
             .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
 
-        // Send welcome message
-        let welcome_msg = NotificationWebSocketMessage {
-            message_type: WebSocketMessageType::ConnectionStatus,
-            data: json!({
-                "status": "connected",
-                "connection_id": &connection_id,
-                "timestamp": chrono::Utc::now()
-            }),
-            timestamp: chrono::Utc::now(),
-        };
-
-        if let Ok(msg_text) = serde_json::to_string(&welcome_msg) {
-            let _ = msg_tx.send(Message::Text(msg_text));
-        }
-
-        // Clone references for tasks
-        let connections = self.connections.clone();
-        let user_connections = self.user_connections.clone();
-        let stats = self.stats.clone();
-        let connection_id_clone = connection_id.clone();
-        let user_id_clone = user_id.clone();
-
         // Handle outgoing messages
-        let outgoing_task = {
+        let mut outgoing_task = {
             let connection_id = connection_id.clone();
-            let connections = connections.clone();
-            let stats = stats.clone();
+            let stats = self.stats.clone();
 
             tokio::spawn(async move {
                 while let Some(message) = msg_rx.recv().await {
@@ -148,8 +193,6 @@ impl WebSocketManager {
                         .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
                 }
 
-                // Clean up connection on task completion
-                connections.remove(&connection_id);
                 info!(
                     "Outgoing message task completed for connection: {}",
                     connection_id
@@ -158,26 +201,25 @@ impl WebSocketManager {
         };
 
         // Handle incoming messages
-        let incoming_task = {
+        let mut incoming_task = {
             let connection_id = connection_id.clone();
-            let connections = connections.clone();
-            let user_connections = user_connections.clone();
-            let stats = stats.clone();
+            let connections = self.connections.clone();
+            let stats = self.stats.clone();
             let msg_tx = msg_tx.clone();
 
             tokio::spawn(async move {
                 while let Some(msg_result) = ws_receiver.next().await {
+                    // Any frame from the client shows the connection is still alive
+                    if let Some(mut conn) = connections.get_mut(&connection_id) {
+                        conn.last_ping = chrono::Utc::now();
+                    }
+
                     match msg_result {
                         Ok(Message::Close(_)) => {
                             info!("WebSocket connection closed by client: {}", connection_id);
                             break;
                         }
-                        Ok(Message::Pong(_)) => {
-                            // Update last ping time
-                            if let Some(mut conn) = connections.get_mut(&connection_id) {
-                                conn.last_ping = chrono::Utc::now();
-                            }
-                        }
+                        Ok(Message::Pong(_)) => {}
                         Ok(Message::Ping(data)) => {
                             if msg_tx.send(Message::Pong(data)).is_err() {
                                 break;
@@ -215,14 +257,6 @@ impl WebSocketManager {
                     }
                 }
 
-                // Clean up on task completion
-                Self::cleanup_connection(
-                    &connections,
-                    &user_connections,
-                    &connection_id_clone,
-                    &user_id_clone,
-                )
-                .await;
                 info!(
                     "Incoming message task completed for connection: {}",
                     connection_id
@@ -230,11 +264,18 @@ impl WebSocketManager {
             })
         };
 
-        // Wait for either task to complete
+        // Wait for either task to complete or for the connection to be reaped
         tokio::select! {
-            _ = outgoing_task => {},
-            _ = incoming_task => {},
+            _ = &mut outgoing_task => {},
+            _ = &mut incoming_task => {},
+            _ = shutdown.cancelled() => {
+                info!("Closing reaped WebSocket connection: {}", connection_id);
+            }
         }
+        outgoing_task.abort();
+        incoming_task.abort();
+
+        self.cleanup_connection(&connection_id, &user_id);
 
         info!(
             "WebSocket connection handler completed for user: {}",
@@ -249,28 +290,53 @@ impl WebSocketManager {
         user_id: &str,
         message: &NotificationWebSocketMessage,
     ) -> Result<usize> {
-        let message_text = serde_json::to_string(message)
+        let (sent_count, dead_connections) = {
+            // Hold the user's buffer while sending so a reconnect cannot interleave its replay
+            let mut buffer = self.replay_buffers.entry(user_id.to_string()).or_default();
+
+            let message_id = self
+                .next_message_id
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+                + 1;
+            let message_text = serde_json::to_string(&SequencedMessage {
+                message_id,
+                message,
+            })
             .map_err(|e| NotificationError::serialization(e.to_string()))?;
 
-        let mut sent_count = 0;
-
-        if let Some(connection_ids) = self.user_connections.get(user_id) {
-            for connection_id in connection_ids.iter() {
-                if let Some(connection) = self.connections.get(connection_id) {
-                    if connection
-                        .sender
-                        .send(Message::Text(message_text.clone()))
-                        .is_ok()
+            let now = chrono::Utc::now();
+            buffer.messages.push_back(BufferedMessage {
+                message_id,
+                text: message_text.clone(),
+                buffered_at: now,
+            });
+            self.prune_replay_buffer(&mut buffer, now);
+
+            let mut sent_count = 0;
+            let mut dead_connections = Vec::new();
+            for connection_id in self.get_user_connections(user_id) {
+                match self.connections.get(&connection_id) {
+                    Some(connection)
+                        if connection
+                            .sender
+                            .send(Message::Text(message_text.clone()))
+                            .is_ok() =>
                     {
                         sent_count += 1;
                     }
+                    _ => dead_connections.push(connection_id),
                 }
             }
+            (sent_count, dead_connections)
+        };
+
+        for connection_id in dead_connections {
+            self.cleanup_connection(&connection_id, user_id);
         }
 
         if sent_count == 0 {
             warn!(
-                "No active WebSocket connections found for user: {}",
+                "No active WebSocket connections found for user: {}, message buffered for replay",
                 user_id
             );
         } else {
@@ -341,9 +407,15 @@ impl WebSocketManager {
             "messages_sent": self.stats.messages_sent.load(std::sync::atomic::Ordering::Relaxed),
             "messages_received": self.stats.messages_received.load(std::sync::atomic::Ordering::Relaxed),
             "connection_errors": self.stats.connection_errors.load(std::sync::atomic::Ordering::Relaxed),
+            "buffered_messages": self.replay_buffers.iter().map(|buffer| buffer.messages.len()).sum::<usize>(),
         })
     }
 
+    /// Number of users with at least one open connection
+    pub fn connected_user_count(&self) -> usize {
+        self.user_connections.len()
+    }
+
     /// Get connections for a specific user
     pub fn get_user_connections(&self, user_id: &str) -> Vec<String> {
         self.user_connections
@@ -357,28 +429,29 @@ impl WebSocketManager {
         self.user_connections.contains_key(user_id)
     }
 
-    /// Clean up stale connections
+    /// Reap connections that stopped answering pings or whose handler has exited,
+    /// and drop replay messages older than the replay window
     pub async fn cleanup_stale_connections(&self) -> usize {
-        let cutoff_time = chrono::Utc::now() - chrono::Duration::minutes(30);
+        let now = chrono::Utc::now();
+        let cutoff_time = now - self.stale_after;
         let mut to_remove = Vec::new();
 
         for entry in self.connections.iter() {
-            if entry.last_ping < cutoff_time {
+            if entry.last_ping < cutoff_time || entry.sender.is_closed() {
                 to_remove.push((entry.connection_id.clone(), entry.user_id.clone()));
             }
         }
 
         let removed_count = to_remove.len();
         for (connection_id, user_id) in to_remove {
-            Self::cleanup_connection(
-                &self.connections,
-                &self.user_connections,
-                &connection_id,
-                &user_id,
-            )
-            .await;
+            self.cleanup_connection(&connection_id, &user_id);
         }
 
+        self.replay_buffers.retain(|_, buffer| {
+            self.prune_replay_buffer(buffer, now);
+            !buffer.messages.is_empty()
+        });
+
         if removed_count > 0 {
             info!("Cleaned up {} stale WebSocket connections", removed_count);
         }
@@ -438,6 +511,87 @@ impl WebSocketManager {
 
     // Private helper methods
 
+    /// Register a connection, queueing the welcome message and any messages
+    /// buffered after `last_message_id`. Returns the number of replayed messages.
+    fn register_connection(
+        &self,
+        connection: WebSocketConnection,
+        last_message_id: Option<u64>,
+    ) -> usize {
+        let connection_id = connection.connection_id.clone();
+        let user_id = connection.user_id.clone();
+        let sender = connection.sender.clone();
+        let now = chrono::Utc::now();
+
+        // Holding the buffer keeps new messages out until this connection is registered,
+        // so nothing is missed or delivered ahead of the replay
+        let mut buffer = self.replay_buffers.entry(user_id.clone()).or_default();
+        self.prune_replay_buffer(&mut buffer, now);
+
+        let replay: Vec<&BufferedMessage> = match last_message_id {
+            Some(cursor) => buffer
+                .messages
+                .iter()
+                .filter(|message| message.message_id > cursor)
+                .collect(),
+            None => Vec::new(),
+        };
+        let replayed = replay.len();
+        // Unknown when no cursor was given; false when messages after the cursor were already evicted
+        let replay_complete = last_message_id.map(|cursor| cursor >= buffer.evicted_through);
+
+        let welcome_msg = NotificationWebSocketMessage {
+            message_type: WebSocketMessageType::ConnectionStatus,
+            data: json!({
+                "status": "connected",
+                "connection_id": &connection_id,
+                "timestamp": now,
+                "last_message_id": buffer.messages.back().map(|message| message.message_id),
+                "replayed": replayed,
+                "replay_complete": replay_complete
+            }),
+            timestamp: now,
+        };
+
+        if let Ok(msg_text) = serde_json::to_string(&welcome_msg) {
+            let _ = sender.send(Message::Text(msg_text));
+        }
+        for message in replay {
+            let _ = sender.send(Message::Text(message.text.clone()));
+        }
+
+        self.connections.insert(connection_id.clone(), connection);
+        self.user_connections
+            .entry(user_id)
+            .or_default()
+            .push(connection_id);
+        drop(buffer);
+
+        self.record_connection_metrics();
+        replayed
+    }
+
+    /// Drop buffered messages that are outside the replay window or over capacity
+    fn prune_replay_buffer(&self, buffer: &mut ReplayBuffer, now: chrono::DateTime<chrono::Utc>) {
+        let cutoff_time = now - self.replay_window;
+
+        while let Some(oldest) = buffer.messages.front() {
+            if buffer.messages.len() <= self.replay_buffer_size && oldest.buffered_at >= cutoff_time
+            {
+                break;
+            }
+            buffer.evicted_through = oldest.message_id;
+            buffer.messages.pop_front();
+        }
+    }
+
+    fn record_connection_metrics(&self) {
+        if let Some(ref metrics) = self.metrics {
+            metrics.record_websocket_connections(self.connections.len() as i64);
+            metrics.record_websocket_users(self.user_connections.len() as i64);
+        }
+    }
+
     async fn handle_incoming_message(connection_id: &str, message: &str) -> Result<()> {
         // Parse and handle incoming messages
         if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(message) {
@@ -468,23 +622,20 @@ impl WebSocketManager {
         Ok(())
     }
 
-    async fn cleanup_connection(
-        connections: &Arc<DashMap<String, WebSocketConnection>>,
-        user_connections: &Arc<DashMap<String, Vec<String>>>,
-        connection_id: &str,
-        user_id: &str,
-    ) {
-        // Remove connection
-        connections.remove(connection_id);
+    fn cleanup_connection(&self, connection_id: &str, user_id: &str) {
+        // Remove connection and stop its handler if it is still running
+        if let Some((_, connection)) = self.connections.remove(connection_id) {
+            connection.shutdown.cancel();
+        }
 
         // Update user connections mapping
-        if let Some(mut user_conns) = user_connections.get_mut(user_id) {
-            user_conns.retain(|id| id != connection_id);
-            if user_conns.is_empty() {
-                drop(user_conns);
-                user_connections.remove(user_id);
-            }
-        }
+        self.user_connections
+            .remove_if_mut(user_id, |_, user_conns| {
+                user_conns.retain(|id| id != connection_id);
+                user_conns.is_empty()
+            });
+
+        self.record_connection_metrics();
 
         info!(
             "Cleaned up WebSocket connection: {} for user: {}",
@@ -496,8 +647,38 @@ impl WebSocketManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::MetricsConfig;
     use chrono::Utc;
 
+    fn test_connection(user_id: &str) -> (WebSocketConnection, mpsc::UnboundedReceiver<Message>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let connection = WebSocketConnection {
+            connection_id: Uuid::new_v4().to_string(),
+            user_id: user_id.to_string(),
+            sender,
+            connected_at: Utc::now(),
+            last_ping: Utc::now(),
+            shutdown: CancellationToken::new(),
+        };
+        (connection, receiver)
+    }
+
+    fn test_message(sequence: u64) -> NotificationWebSocketMessage {
+        NotificationWebSocketMessage {
+            message_type: WebSocketMessageType::Notification,
+            data: json!({"sequence": sequence}),
+            timestamp: Utc::now(),
+        }
+    }
+
+    fn received(receiver: &mut mpsc::UnboundedReceiver<Message>) -> Vec<serde_json::Value> {
+        let mut messages = Vec::new();
+        while let Ok(Message::Text(text)) = receiver.try_recv() {
+            messages.push(serde_json::from_str(&text).unwrap());
+        }
+        messages
+    }
+
     #[tokio::test]
     async fn test_websocket_manager_creation() {
         let manager = WebSocketManager::new().await;
@@ -582,6 +763,113 @@ mod tests {
         assert_eq!(result.unwrap(), 0);
     }
 
+    #[tokio::test]
+    async fn test_send_to_user_fans_out_to_all_connections() {
+        let manager = WebSocketManager::new().await.unwrap();
+        let (first, mut first_rx) = test_connection("user123");
+        let (second, mut second_rx) = test_connection("user123");
+        manager.register_connection(first, None);
+        manager.register_connection(second, None);
+
+        let sent = manager
+            .send_to_user("user123", &test_message(1))
+            .await
+            .unwrap();
+        assert_eq!(sent, 2);
+
+        for receiver in [&mut first_rx, &mut second_rx] {
+            let messages = received(receiver);
+            assert_eq!(messages.len(), 2);
+            assert_eq!(messages[0]["message_type"], "connection_status");
+            assert_eq!(messages[1]["data"]["sequence"], 1);
+            assert!(messages[1]["message_id"].is_u64());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_replays_messages_after_cursor() {
+        let manager = WebSocketManager::new().await.unwrap();
+        let (connection, mut receiver) = test_connection("user123");
+        manager.register_connection(connection, None);
+
+        manager
+            .send_to_user("user123", &test_message(1))
+            .await
+            .unwrap();
+        let cursor = received(&mut receiver)[1]["message_id"].as_u64().unwrap();
+
+        // Messages sent while the client is away are buffered
+        drop(receiver);
+        manager
+            .send_to_user("user123", &test_message(2))
+            .await
+            .unwrap();
+        manager
+            .send_to_user("user123", &test_message(3))
+            .await
+            .unwrap();
+        assert!(!manager.has_active_connections("user123"));
+
+        let (connection, mut receiver) = test_connection("user123");
+        let replayed = manager.register_connection(connection, Some(cursor));
+        assert_eq!(replayed, 2);
+
+        let messages = received(&mut receiver);
+        assert_eq!(messages[0]["data"]["replayed"], 2);
+        assert_eq!(messages[0]["data"]["replay_complete"], true);
+        assert_eq!(messages[1]["data"]["sequence"], 2);
+        assert_eq!(messages[2]["data"]["sequence"], 3);
+    }
+
+    #[tokio::test]
+    async fn test_replay_reports_evicted_messages() {
+        let config = WebSocketConfig {
+            replay_buffer_size: 2,
+            ..WebSocketConfig::default()
+        };
+        let manager = WebSocketManager::with_config(&config, None).await.unwrap();
+
+        for sequence in 1..=3 {
+            manager
+                .send_to_user("user123", &test_message(sequence))
+                .await
+                .unwrap();
+        }
+
+        let (connection, mut receiver) = test_connection("user123");
+        assert_eq!(manager.register_connection(connection, Some(0)), 2);
+
+        let messages = received(&mut receiver);
+        assert_eq!(messages[0]["data"]["replay_complete"], false);
+        assert_eq!(messages[1]["data"]["sequence"], 2);
+        assert_eq!(messages[2]["data"]["sequence"], 3);
+    }
+
+    #[tokio::test]
+    async fn test_stale_connections_are_reaped() {
+        let metrics = NotificationMetrics::new(&MetricsConfig::default()).unwrap();
+        let manager =
+            WebSocketManager::with_config(&WebSocketConfig::default(), Some(metrics.clone()))
+                .await
+                .unwrap();
+
+        let (mut stale, _stale_rx) = test_connection("user123");
+        stale.last_ping = Utc::now() - chrono::Duration::minutes(5);
+        let shutdown = stale.shutdown.clone();
+        let (live, _live_rx) = test_connection("user456");
+        manager.register_connection(stale, None);
+        manager.register_connection(live, None);
+        assert_eq!(manager.connected_user_count(), 2);
+
+        assert_eq!(manager.cleanup_stale_connections().await, 1);
+        assert!(shutdown.is_cancelled());
+        assert!(!manager.has_active_connections("user123"));
+        assert!(manager.has_active_connections("user456"));
+
+        let exported = metrics.export_metrics().unwrap();
+        assert!(exported.contains("notification_service_websocket_connected_users 1"));
+    }
+
     #[tokio::test]
     async fn test_handle_incoming_message() {
         let result = WebSocketManager::handle_incoming_message(
@@ -29,6 +29,10 @@ pub struct ServiceDiscoveryConfig {
     /// Service mesh configuration
     pub service_mesh: ServiceMeshConfig,
 
+    /// DNS SRV responder configuration
+    #[serde(default)]
+    pub dns: DnsConfig,
+
     /// Database configuration
     pub database: DatabaseConfig,
 
@@ -128,6 +132,15 @@ pub struct RegistrationConfig {
 
     /// Retry interval in seconds
     pub retry_interval: u32,
+
+    /// Seconds a deregistered instance keeps draining in-flight requests before
+    /// it is removed; 0 removes it immediately
+    #[serde(default = "default_drain_grace_period")]
+    pub drain_grace_period: u32,
+}
+
+fn default_drain_grace_period() -> u32 {
+    30
 }
 
 /// Service discovery configuration
@@ -144,6 +157,33 @@ pub struct DiscoveryConfig {
 
     /// Enable caching
     pub enable_caching: bool,
+
+    /// Registry change notifications
+    #[serde(default)]
+    pub watch: WatchConfig,
+}
+
+/// Registry watch configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchConfig {
+    /// Events buffered per subscriber before a slow subscriber starts lagging
+    pub buffer_size: usize,
+
+    /// Redis pub/sub channel, appended to the Redis key prefix
+    pub channel: String,
+
+    /// Interval between keep-alive comments on watch streams in seconds
+    pub keep_alive_seconds: u32,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self {
+            buffer_size: 256,
+            channel: "service_changes".to_string(),
+            keep_alive_seconds: 15,
+        }
+    }
 }
 
 /// Health check configuration
@@ -166,6 +206,50 @@ pub struct HealthCheckConfig {
 
     /// Health check types configuration
     pub types: HealthCheckTypesConfig,
+
+    /// Outlier detection based on observed request failures
+    #[serde(default)]
+    pub outlier_detection: OutlierDetectionConfig,
+}
+
+/// Outlier detection configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutlierDetectionConfig {
+    /// Enable outlier ejection
+    pub enabled: bool,
+
+    /// Failure percentage within the window that triggers ejection
+    pub failure_threshold_percent: f64,
+
+    /// Minimum requests in the window before an instance can be ejected
+    pub min_requests: u32,
+
+    /// Sliding window for request outcomes in seconds
+    pub window_seconds: u32,
+
+    /// Ejection duration for the first ejection in seconds, doubled on each repeat
+    pub base_ejection_seconds: u32,
+
+    /// Maximum ejection duration in seconds
+    pub max_ejection_seconds: u32,
+
+    /// Concurrent probe requests allowed while half-open, and successes required
+    /// for reinstatement
+    pub half_open_max_requests: u32,
+}
+
+impl Default for OutlierDetectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            failure_threshold_percent: 50.0,
+            min_requests: 10,
+            window_seconds: 30,
+            base_ejection_seconds: 30,
+            max_ejection_seconds: 300,
+            half_open_max_requests: 3,
+        }
+    }
 }
 
 /// Health check types configuration
@@ -179,6 +263,10 @@ pub struct HealthCheckTypesConfig {
 
     /// gRPC health check configuration
     pub grpc: GrpcHealthCheckConfig,
+
+    /// Script health check configuration
+    #[serde(default)]
+    pub script: ScriptHealthCheckConfig,
 }
 
 /// HTTP health check configuration
@@ -211,7 +299,69 @@ pub struct GrpcHealthCheckConfig {
     pub enabled: bool,
 
     /// Default service name for health checks
+    #[serde(default = "default_grpc_service")]
     pub service_name: String,
+
+    /// Deadline of the health Check call in seconds
+    #[serde(default = "default_grpc_timeout")]
+    pub timeout: u32,
+}
+
+/// Script health check configuration
+///
+/// Scripts run arbitrary commands on the discovery host, so only commands on
+/// the allowlist are executed, with a cleared environment and a hard timeout.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptHealthCheckConfig {
+    /// Enable script health checks
+    pub enabled: bool,
+
+    /// Scripts that may be run; a check must match an entry's full argv
+    pub allowed_scripts: Vec<AllowedScript>,
+
+    /// Seconds a script may run before it is killed
+    pub timeout: u32,
+
+    /// Environment passed to scripts, which inherit nothing else
+    pub environment: HashMap<String, String>,
+
+    /// Bytes of stdout and stderr kept in the check result
+    pub max_output_bytes: usize,
+}
+
+impl Default for ScriptHealthCheckConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            allowed_scripts: Vec::new(),
+            timeout: crate::health_check::DEFAULT_TIMEOUT,
+            environment: HashMap::from([("PATH".to_string(), "/usr/bin:/bin".to_string())]),
+            max_output_bytes: 4096,
+        }
+    }
+}
+
+/// A script health check the operator permits, fixed to its exact arguments
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllowedScript {
+    /// Absolute path of the command
+    pub command: String,
+
+    /// Arguments, which a registration must repeat exactly
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// Directory the script runs in; a registration may only name this one
+    #[serde(default)]
+    pub working_dir: Option<String>,
+}
+
+fn default_grpc_service() -> String {
+    crate::health_check::DEFAULT_GRPC_SERVICE.to_string()
+}
+
+fn default_grpc_timeout() -> u32 {
+    crate::health_check::DEFAULT_TIMEOUT
 }
 
 /// Load balancer configuration
@@ -279,10 +429,52 @@ pub struct ConsistentHashConfig {
     /// Number of virtual nodes
     pub virtual_nodes: u32,
 
+    /// Per-service virtual node counts; more nodes smooth the key
+    /// distribution at the cost of a larger ring
+    #[serde(default)]
+    pub service_virtual_nodes: HashMap<String, u32>,
+
     /// Hash function to use
     pub hash_function: String,
 }
 
+impl ConsistentHashConfig {
+    /// Virtual nodes per instance on the ring of `service_name`
+    pub fn virtual_nodes_for(&self, service_name: &str) -> u32 {
+        self.service_virtual_nodes
+            .get(service_name)
+            .copied()
+            .unwrap_or(self.virtual_nodes)
+    }
+}
+
+/// DNS SRV responder configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DnsConfig {
+    /// Answer DNS SRV queries for registered services
+    pub enabled: bool,
+
+    /// Address to listen on, for both UDP and TCP
+    pub bind_address: String,
+
+    /// Zone served, e.g. `discovery` for `_http._tcp.myservice.discovery.`
+    pub zone: String,
+
+    /// TTL of returned records in seconds
+    pub ttl: u32,
+}
+
+impl Default for DnsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_address: "0.0.0.0:8053".to_string(),
+            zone: "discovery".to_string(),
+            ttl: 30,
+        }
+    }
+}
+
 /// Circuit breaker configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CircuitBreakerConfig {
@@ -894,6 +1086,45 @@ impl ServiceDiscoveryConfig {
             }
         }
 
+        if self.registry.discovery.watch.buffer_size == 0 {
+            return Err(anyhow::anyhow!("Watch buffer size must be greater than 0"));
+        }
+
+        let outlier_detection = &self.registry.health_checks.outlier_detection;
+        if outlier_detection.enabled {
+            if !(outlier_detection.failure_threshold_percent > 0.0
+                && outlier_detection.failure_threshold_percent <= 100.0)
+            {
+                return Err(anyhow::anyhow!(
+                    "Outlier detection failure threshold must be between 0 and 100 percent"
+                ));
+            }
+
+            if outlier_detection.min_requests == 0 || outlier_detection.half_open_max_requests == 0
+            {
+                return Err(anyhow::anyhow!(
+                    "Outlier detection request counts must be greater than 0"
+                ));
+            }
+
+            if outlier_detection.base_ejection_seconds > outlier_detection.max_ejection_seconds {
+                return Err(anyhow::anyhow!(
+                    "Outlier detection base ejection time must not exceed the maximum"
+                ));
+            }
+        }
+
+        if self.dns.enabled {
+            if self.dns.zone.trim_matches('.').is_empty() {
+                return Err(anyhow::anyhow!("DNS zone cannot be empty"));
+            }
+
+            self.dns
+                .bind_address
+                .parse::<std::net::SocketAddr>()
+                .map_err(|e| anyhow::anyhow!("Invalid DNS bind address: {}", e))?;
+        }
+
         // Validate circuit breaker configuration
         if self.circuit_breaker.enabled {
             if self.circuit_breaker.defaults.failure_threshold == 0 {
@@ -908,6 +1139,7 @@ impl ServiceDiscoveryConfig {
             "round_robin",
             "least_connections",
             "weighted_round_robin",
+            "weighted_least_connections",
             "consistent_hash",
             "random",
             "ip_hash",
@@ -921,6 +1153,18 @@ impl ServiceDiscoveryConfig {
             ));
         }
 
+        let consistent_hash = &self.load_balancer.strategies.consistent_hash;
+        if consistent_hash.virtual_nodes == 0
+            || consistent_hash
+                .service_virtual_nodes
+                .values()
+                .any(|&n| n == 0)
+        {
+            return Err(anyhow::anyhow!(
+                "Consistent hash virtual nodes must be greater than 0"
+            ));
+        }
+
         Ok(())
     }
 }
@@ -944,12 +1188,14 @@ impl Default for ServiceDiscoveryConfig {
                     grace_period: 15,
                     max_retries: 3,
                     retry_interval: 5,
+                    drain_grace_period: default_drain_grace_period(),
                 },
                 discovery: DiscoveryConfig {
                     cache_ttl: 60,
                     refresh_interval: 30,
                     batch_size: 100,
                     enable_caching: true,
+                    watch: WatchConfig::default(),
                 },
                 health_checks: HealthCheckConfig {
                     enabled: true,
@@ -969,9 +1215,12 @@ impl Default for ServiceDiscoveryConfig {
                         },
                         grpc: GrpcHealthCheckConfig {
                             enabled: true,
-                            service_name: "health".to_string(),
+                            service_name: default_grpc_service(),
+                            timeout: default_grpc_timeout(),
                         },
+                        script: ScriptHealthCheckConfig::default(),
                     },
+                    outlier_detection: OutlierDetectionConfig::default(),
                 },
             },
             load_balancer: LoadBalancerConfig {
@@ -989,6 +1238,7 @@ impl Default for ServiceDiscoveryConfig {
                     consistent_hash: ConsistentHashConfig {
                         enabled: true,
                         virtual_nodes: 150,
+                        service_virtual_nodes: HashMap::new(),
                         hash_function: "sha256".to_string(),
                     },
                     random: StrategyConfig { enabled: true },
@@ -1005,6 +1255,7 @@ impl Default for ServiceDiscoveryConfig {
                 },
                 services: HashMap::new(),
             },
+            dns: DnsConfig::default(),
             service_mesh: ServiceMeshConfig {
                 enabled: true,
                 backend: "native".to_string(),
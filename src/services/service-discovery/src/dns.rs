@@ -0,0 +1,986 @@
+//! DNS SRV Responder Module
+//!
+//! Answers DNS SRV queries such as `_http._tcp.user-service.discovery.` over UDP
+//! and TCP with records built from the healthy registrations in the service
+//! registry, so clients that cannot call the REST API can still discover services.
+//!
+//! Only the subset of RFC 1035 / RFC 2782 needed for single-question SRV lookups
+//! is implemented. SRV weight comes from the registration weight and priority from
+//! the `srv_priority` metadata key (default 0). Instances registered with an IP
+//! address get a synthetic target name `<instance-id>.<service>.<zone>` with a
+//! matching A/AAAA additional record, and the target also answers A/AAAA queries
+//! of its own. UDP responses that do not fit in 512 bytes are truncated with TC
+//! set, and clients retry over TCP on the same address.
+
+use crate::config::DnsConfig;
+use crate::models::{ServiceProtocol, ServiceRegistration, ServiceStatus};
+use crate::registry::ServiceRegistry;
+
+use anyhow::{Context, Result};
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpListener, UdpSocket};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// Maximum size of a DNS message over UDP without EDNS
+const MAX_UDP_MESSAGE_SIZE: usize = 512;
+
+/// Maximum size of a DNS message over TCP, bounded by its 2-byte length prefix
+const MAX_TCP_MESSAGE_SIZE: usize = u16::MAX as usize;
+
+/// How long an idle TCP connection is kept open for further queries
+const TCP_IDLE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Metadata key holding the SRV priority of a registration
+pub const SRV_PRIORITY_METADATA_KEY: &str = "srv_priority";
+
+const TYPE_A: u16 = 1;
+const TYPE_AAAA: u16 = 28;
+const TYPE_SRV: u16 = 33;
+const TYPE_ANY: u16 = 255;
+const CLASS_IN: u16 = 1;
+
+/// DNS response codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResponseCode {
+    NoError = 0,
+    FormatError = 1,
+    ServerFailure = 2,
+    NameError = 3,
+    NotImplemented = 4,
+    Refused = 5,
+}
+
+/// The single question of a DNS query
+#[derive(Debug, Clone)]
+struct DnsQuestion {
+    id: u16,
+    flags: u16,
+    /// Lowercased name without the trailing dot
+    name: String,
+    qtype: u16,
+    qclass: u16,
+    /// Question section as received, echoed back in the response
+    raw: Vec<u8>,
+}
+
+/// Service lookup parsed from an SRV owner name
+#[derive(Debug, Clone, PartialEq)]
+struct SrvQuery {
+    service: String,
+    protocol: ServiceProtocol,
+}
+
+/// Address lookup parsed from a synthetic SRV target name
+#[derive(Debug, Clone, PartialEq)]
+struct TargetQuery {
+    service: String,
+    instance: Uuid,
+}
+
+/// SRV record built from a registration
+#[derive(Debug, Clone, PartialEq)]
+struct SrvRecord {
+    priority: u16,
+    weight: u16,
+    port: u16,
+    target: String,
+    /// Address record for synthetic targets
+    address: Option<IpAddr>,
+}
+
+/// UDP and TCP DNS responder backed by the service registry
+pub struct DnsResponder {
+    config: DnsConfig,
+    registry: Arc<dyn ServiceRegistry>,
+}
+
+impl DnsResponder {
+    /// Create a new DNS responder
+    pub fn new(config: DnsConfig, registry: Arc<dyn ServiceRegistry>) -> Self {
+        Self { config, registry }
+    }
+
+    /// Serve DNS queries over UDP and TCP until either listener fails
+    pub async fn serve(self: Arc<Self>) -> Result<()> {
+        let socket = UdpSocket::bind(&self.config.bind_address)
+            .await
+            .with_context(|| format!("Failed to bind DNS socket {}", self.config.bind_address))?;
+        let listener = TcpListener::bind(&self.config.bind_address)
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to bind DNS TCP listener {}",
+                    self.config.bind_address
+                )
+            })?;
+        info!(
+            "DNS SRV responder listening on {} (UDP and TCP) for zone {}",
+            self.config.bind_address, self.config.zone
+        );
+
+        tokio::try_join!(
+            Arc::clone(&self).serve_udp(socket),
+            self.serve_tcp(listener)
+        )?;
+        Ok(())
+    }
+
+    async fn serve_udp(self: Arc<Self>, socket: UdpSocket) -> Result<()> {
+        let mut buffer = [0u8; MAX_UDP_MESSAGE_SIZE];
+        loop {
+            let (len, peer) = match socket.recv_from(&mut buffer).await {
+                Ok(received) => received,
+                Err(e) => {
+                    warn!("Failed to receive DNS query: {}", e);
+                    continue;
+                }
+            };
+
+            if let Some(response) = self.handle_packet(&buffer[..len]).await {
+                if let Err(e) = socket.send_to(&response, peer).await {
+                    warn!("Failed to send DNS response to {}: {}", peer, e);
+                }
+            }
+        }
+    }
+
+    async fn serve_tcp(self: Arc<Self>, listener: TcpListener) -> Result<()> {
+        loop {
+            let (stream, peer) = match listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    warn!("Failed to accept DNS TCP connection: {}", e);
+                    continue;
+                }
+            };
+
+            let responder = Arc::clone(&self);
+            tokio::spawn(async move {
+                if let Err(e) = responder.serve_connection(stream).await {
+                    debug!("DNS TCP connection from {} closed: {}", peer, e);
+                }
+            });
+        }
+    }
+
+    /// Answer length-prefixed queries on a TCP connection until the client
+    /// closes it or stays idle
+    async fn serve_connection<S>(&self, mut stream: S) -> std::io::Result<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        loop {
+            let mut prefix = [0u8; 2];
+            match tokio::time::timeout(TCP_IDLE_TIMEOUT, stream.read_exact(&mut prefix)).await {
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(()),
+                Ok(Err(e)) => return Err(e),
+                Err(_) => return Ok(()),
+            }
+
+            let mut packet = vec![0u8; u16::from_be_bytes(prefix) as usize];
+            tokio::time::timeout(TCP_IDLE_TIMEOUT, stream.read_exact(&mut packet))
+                .await
+                .map_err(|_| std::io::Error::from(std::io::ErrorKind::TimedOut))??;
+
+            let Some(response) = self.respond(&packet, MAX_TCP_MESSAGE_SIZE).await else {
+                return Ok(());
+            };
+            stream
+                .write_all(&(response.len() as u16).to_be_bytes())
+                .await?;
+            stream.write_all(&response).await?;
+        }
+    }
+
+    /// Build the UDP response to a DNS query packet, if it deserves one
+    pub async fn handle_packet(&self, packet: &[u8]) -> Option<Vec<u8>> {
+        self.respond(packet, MAX_UDP_MESSAGE_SIZE).await
+    }
+
+    /// Build the response to a DNS query, truncated to `max_size` bytes
+    async fn respond(&self, packet: &[u8], max_size: usize) -> Option<Vec<u8>> {
+        let question = match parse_question(packet) {
+            Ok(question) => question,
+            Err(Some(id)) => return Some(error_response(id, ResponseCode::FormatError)),
+            Err(None) => return None,
+        };
+
+        // Only standard queries are supported
+        if (question.flags >> 11) & 0x0f != 0 {
+            return Some(empty_response(&question, ResponseCode::NotImplemented));
+        }
+
+        if let Some(target) = self.parse_target_name(&question.name) {
+            return Some(self.answer_target(&question, &target, max_size).await);
+        }
+
+        let query = match self.parse_srv_name(&question.name) {
+            Ok(query) => query,
+            Err(code) => return Some(empty_response(&question, code)),
+        };
+
+        let registrations = match self.registry.get_services_by_name(&query.service).await {
+            Ok(registrations) => registrations,
+            Err(e) => {
+                warn!("DNS lookup for {} failed: {}", query.service, e);
+                return Some(empty_response(&question, ResponseCode::ServerFailure));
+            }
+        };
+
+        if registrations.is_empty() {
+            return Some(empty_response(&question, ResponseCode::NameError));
+        }
+
+        let records =
+            if question.qclass == CLASS_IN && matches!(question.qtype, TYPE_SRV | TYPE_ANY) {
+                self.srv_records(&query, &registrations)
+            } else {
+                Vec::new()
+            };
+
+        debug!(
+            "Answering DNS query {} with {} SRV records",
+            question.name,
+            records.len()
+        );
+
+        let ttl = self.config.ttl;
+        let answers: Vec<Vec<u8>> = records
+            .iter()
+            .map(|record| srv_answer(record, ttl))
+            .collect();
+        let additionals: Vec<Vec<u8>> = records
+            .iter()
+            .filter_map(|record| address_record(record, ttl))
+            .collect();
+        Some(build_response(
+            &question,
+            ResponseCode::NoError,
+            &answers,
+            &additionals,
+            max_size,
+        ))
+    }
+
+    /// Answer an A/AAAA query for a synthetic SRV target
+    async fn answer_target(
+        &self,
+        question: &DnsQuestion,
+        target: &TargetQuery,
+        max_size: usize,
+    ) -> Vec<u8> {
+        let registrations = match self.registry.get_services_by_name(&target.service).await {
+            Ok(registrations) => registrations,
+            Err(e) => {
+                warn!("DNS lookup for {} failed: {}", target.service, e);
+                return empty_response(question, ResponseCode::ServerFailure);
+            }
+        };
+
+        // Targets resolve for as long as the instance is registered, so clients
+        // holding a recent SRV answer can still connect
+        let address = registrations
+            .iter()
+            .find(|registration| registration.id == target.instance)
+            .and_then(|registration| registration.address.parse::<IpAddr>().ok());
+        let Some(address) = address else {
+            return empty_response(question, ResponseCode::NameError);
+        };
+
+        let (record_type, _) = address_rdata(address);
+        let answers = if question.qclass == CLASS_IN
+            && (question.qtype == record_type || question.qtype == TYPE_ANY)
+        {
+            vec![address_answer(address, self.config.ttl)]
+        } else {
+            Vec::new()
+        };
+
+        debug!(
+            "Answering DNS query {} with {} address records",
+            question.name,
+            answers.len()
+        );
+        build_response(question, ResponseCode::NoError, &answers, &[], max_size)
+    }
+
+    /// Parse a synthetic target name `<instance-id>.<service>.<zone>`
+    fn parse_target_name(&self, name: &str) -> Option<TargetQuery> {
+        let zone = self.config.zone.trim_matches('.').to_lowercase();
+        let relative = name.strip_suffix(zone.as_str())?.strip_suffix('.')?;
+
+        let (instance, service) = relative.split_once('.')?;
+        if instance.len() != 32 || service.is_empty() || service.contains('.') {
+            return None;
+        }
+
+        Some(TargetQuery {
+            service: service.to_string(),
+            instance: Uuid::try_parse(instance).ok()?,
+        })
+    }
+
+    /// Parse `_<protocol>._<transport>.<service>.<zone>`
+    fn parse_srv_name(&self, name: &str) -> std::result::Result<SrvQuery, ResponseCode> {
+        let zone = self.config.zone.trim_matches('.').to_lowercase();
+        let relative = name
+            .strip_suffix(zone.as_str())
+            .and_then(|rest| rest.strip_suffix('.'))
+            .ok_or(ResponseCode::Refused)?;
+
+        let labels: Vec<&str> = relative.split('.').collect();
+        let (protocol, transport, service) = match labels.as_slice() {
+            [protocol, transport, service] => (*protocol, *transport, *service),
+            _ => return Err(ResponseCode::NameError),
+        };
+
+        let protocol = match protocol {
+            "_http" => ServiceProtocol::Http,
+            "_https" => ServiceProtocol::Https,
+            "_grpc" => ServiceProtocol::Grpc,
+            "_tcp" => ServiceProtocol::Tcp,
+            "_udp" => ServiceProtocol::Udp,
+            _ => return Err(ResponseCode::NameError),
+        };
+
+        let transport_matches = match transport {
+            "_udp" => protocol == ServiceProtocol::Udp,
+            "_tcp" => protocol != ServiceProtocol::Udp,
+            _ => false,
+        };
+        if !transport_matches || service.is_empty() {
+            return Err(ResponseCode::NameError);
+        }
+
+        Ok(SrvQuery {
+            service: service.to_string(),
+            protocol,
+        })
+    }
+
+    /// SRV records for the healthy registrations matching a query
+    fn srv_records(
+        &self,
+        query: &SrvQuery,
+        registrations: &[ServiceRegistration],
+    ) -> Vec<SrvRecord> {
+        let zone = self.config.zone.trim_matches('.');
+
+        registrations
+            .iter()
+            .filter(|registration| {
+                registration.status == ServiceStatus::Healthy
+                    && registration.protocol == query.protocol
+            })
+            .map(|registration| {
+                let priority = registration
+                    .metadata
+                    .get(SRV_PRIORITY_METADATA_KEY)
+                    .and_then(|priority| priority.parse().ok())
+                    .unwrap_or(0);
+
+                let (target, address) = match registration.address.parse::<IpAddr>() {
+                    Ok(ip) => (
+                        format!("{}.{}.{}", registration.id.simple(), query.service, zone),
+                        Some(ip),
+                    ),
+                    Err(_) => (registration.address.trim_end_matches('.').to_string(), None),
+                };
+
+                SrvRecord {
+                    priority,
+                    weight: registration.weight.min(u16::MAX as u32) as u16,
+                    port: registration.port,
+                    target,
+                    address,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Parse the header and single question of a query
+///
+/// Errors carry the query id when a format error response can be sent.
+fn parse_question(packet: &[u8]) -> std::result::Result<DnsQuestion, Option<u16>> {
+    if packet.len() < 12 {
+        return Err(None);
+    }
+
+    let id = u16::from_be_bytes([packet[0], packet[1]]);
+    let flags = u16::from_be_bytes([packet[2], packet[3]]);
+    let question_count = u16::from_be_bytes([packet[4], packet[5]]);
+
+    // Never answer responses
+    if flags & 0x8000 != 0 {
+        return Err(None);
+    }
+    if question_count != 1 {
+        return Err(Some(id));
+    }
+
+    let mut offset = 12;
+    let mut labels = Vec::new();
+    loop {
+        let len = *packet.get(offset).ok_or(Some(id))? as usize;
+        offset += 1;
+        if len == 0 {
+            break;
+        }
+        // Compression pointers and extended labels are not valid in a lone question
+        if len > 63 {
+            return Err(Some(id));
+        }
+        let label = packet.get(offset..offset + len).ok_or(Some(id))?;
+        labels.push(String::from_utf8_lossy(label).to_lowercase());
+        offset += len;
+    }
+
+    let fixed = packet.get(offset..offset + 4).ok_or(Some(id))?;
+    let qtype = u16::from_be_bytes([fixed[0], fixed[1]]);
+    let qclass = u16::from_be_bytes([fixed[2], fixed[3]]);
+    offset += 4;
+
+    Ok(DnsQuestion {
+        id,
+        flags,
+        name: labels.join("."),
+        qtype,
+        qclass,
+        raw: packet[12..offset].to_vec(),
+    })
+}
+
+/// Header-only response for queries that could not be parsed
+fn error_response(id: u16, code: ResponseCode) -> Vec<u8> {
+    let mut response = Vec::with_capacity(12);
+    response.extend_from_slice(&id.to_be_bytes());
+    response.extend_from_slice(&(0x8000 | code as u16).to_be_bytes());
+    response.extend_from_slice(&[0; 8]);
+    response
+}
+
+/// Response carrying only the question, for errors and empty answers
+fn empty_response(question: &DnsQuestion, code: ResponseCode) -> Vec<u8> {
+    build_response(question, code, &[], &[], MAX_UDP_MESSAGE_SIZE)
+}
+
+/// Build a response from encoded records, truncating answers that do not fit
+/// in `max_size` bytes
+fn build_response(
+    question: &DnsQuestion,
+    code: ResponseCode,
+    answers: &[Vec<u8>],
+    additionals: &[Vec<u8>],
+    max_size: usize,
+) -> Vec<u8> {
+    let mut answer_section = Vec::new();
+    let mut answer_count: u16 = 0;
+    let mut truncated = false;
+    let mut size = 12 + question.raw.len();
+
+    for answer in answers {
+        if size + answer.len() > max_size {
+            truncated = true;
+            break;
+        }
+        size += answer.len();
+        answer_section.extend_from_slice(answer);
+        answer_count += 1;
+    }
+
+    // Additional records are optional, include those that fit
+    let mut additional_section = Vec::new();
+    let mut additional_count: u16 = 0;
+    if !truncated {
+        for additional in additionals {
+            if size + additional.len() > max_size {
+                break;
+            }
+            size += additional.len();
+            additional_section.extend_from_slice(additional);
+            additional_count += 1;
+        }
+    }
+
+    // QR and AA set; opcode and RD copied from the query
+    let mut flags = 0x8000 | 0x0400 | (question.flags & 0x7900) | code as u16;
+    if truncated {
+        flags |= 0x0200;
+    }
+
+    let mut response = Vec::with_capacity(size);
+    response.extend_from_slice(&question.id.to_be_bytes());
+    response.extend_from_slice(&flags.to_be_bytes());
+    response.extend_from_slice(&1u16.to_be_bytes());
+    response.extend_from_slice(&answer_count.to_be_bytes());
+    response.extend_from_slice(&0u16.to_be_bytes());
+    response.extend_from_slice(&additional_count.to_be_bytes());
+    response.extend_from_slice(&question.raw);
+    response.extend(answer_section);
+    response.extend(additional_section);
+    response
+}
+
+/// SRV answer whose owner name points at the question name
+fn srv_answer(record: &SrvRecord, ttl: u32) -> Vec<u8> {
+    let mut rdata = Vec::new();
+    rdata.extend_from_slice(&record.priority.to_be_bytes());
+    rdata.extend_from_slice(&record.weight.to_be_bytes());
+    rdata.extend_from_slice(&record.port.to_be_bytes());
+    encode_name(&record.target, &mut rdata);
+
+    let mut answer = vec![0xc0, 0x0c];
+    push_record_header(&mut answer, TYPE_SRV, ttl, rdata.len());
+    answer.extend(rdata);
+    answer
+}
+
+/// A or AAAA record for a synthetic SRV target
+fn address_record(record: &SrvRecord, ttl: u32) -> Option<Vec<u8>> {
+    let (record_type, rdata) = address_rdata(record.address?);
+
+    let mut additional = Vec::new();
+    encode_name(&record.target, &mut additional);
+    push_record_header(&mut additional, record_type, ttl, rdata.len());
+    additional.extend(rdata);
+    Some(additional)
+}
+
+/// A or AAAA answer whose owner name points at the question name
+fn address_answer(address: IpAddr, ttl: u32) -> Vec<u8> {
+    let (record_type, rdata) = address_rdata(address);
+
+    let mut answer = vec![0xc0, 0x0c];
+    push_record_header(&mut answer, record_type, ttl, rdata.len());
+    answer.extend(rdata);
+    answer
+}
+
+fn address_rdata(address: IpAddr) -> (u16, Vec<u8>) {
+    match address {
+        IpAddr::V4(ip) => (TYPE_A, ip.octets().to_vec()),
+        IpAddr::V6(ip) => (TYPE_AAAA, ip.octets().to_vec()),
+    }
+}
+
+fn push_record_header(buffer: &mut Vec<u8>, record_type: u16, ttl: u32, rdata_len: usize) {
+    buffer.extend_from_slice(&record_type.to_be_bytes());
+    buffer.extend_from_slice(&CLASS_IN.to_be_bytes());
+    buffer.extend_from_slice(&ttl.to_be_bytes());
+    buffer.extend_from_slice(&(rdata_len as u16).to_be_bytes());
+}
+
+/// Encode a domain name as uncompressed labels
+fn encode_name(name: &str, buffer: &mut Vec<u8>) {
+    for label in name.split('.').filter(|label| !label.is_empty()) {
+        let label = &label.as_bytes()[..label.len().min(63)];
+        buffer.push(label.len() as u8);
+        buffer.extend_from_slice(label);
+    }
+    buffer.push(0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn responder() -> DnsResponder {
+        responder_with(Vec::new())
+    }
+
+    fn responder_with(registrations: Vec<ServiceRegistration>) -> DnsResponder {
+        DnsResponder {
+            config: DnsConfig {
+                enabled: true,
+                zone: "discovery.".to_string(),
+                ttl: 45,
+                ..Default::default()
+            },
+            registry: Arc::new(StaticRegistry(registrations)),
+        }
+    }
+
+    fn registration(address: &str, port: u16, status: ServiceStatus) -> ServiceRegistration {
+        ServiceRegistration {
+            id: Uuid::new_v4(),
+            name: "user-service".to_string(),
+            namespace: crate::models::DEFAULT_NAMESPACE.to_string(),
+            version: "1.0.0".to_string(),
+            address: address.to_string(),
+            port,
+            protocol: ServiceProtocol::Http,
+            health_check: None,
+            metadata: HashMap::new(),
+            weight: 200,
+            status,
+            registered_at: Utc::now(),
+            last_heartbeat: None,
+            ttl: 30,
+            dependencies: Vec::new(),
+            circuit_breaker: None,
+        }
+    }
+
+    fn query_packet(name: &str, qtype: u16) -> Vec<u8> {
+        let mut packet = vec![0x12, 0x34, 0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 0];
+        encode_name(name, &mut packet);
+        packet.extend_from_slice(&qtype.to_be_bytes());
+        packet.extend_from_slice(&CLASS_IN.to_be_bytes());
+        packet
+    }
+
+    fn rcode(response: &[u8]) -> u16 {
+        u16::from_be_bytes([response[2], response[3]]) & 0x000f
+    }
+
+    fn count(response: &[u8], index: usize) -> u16 {
+        u16::from_be_bytes([response[4 + index * 2], response[5 + index * 2]])
+    }
+
+    /// Registry stand-in answering lookups from a fixed set of registrations
+    struct StaticRegistry(Vec<ServiceRegistration>);
+
+    #[async_trait::async_trait]
+    impl ServiceRegistry for StaticRegistry {
+        async fn register_service(
+            &self,
+            _request: crate::models::RegisterServiceRequest,
+        ) -> Result<Uuid> {
+            unimplemented!()
+        }
+
+        async fn deregister_service(&self, _service_id: Uuid) -> Result<()> {
+            unimplemented!()
+        }
+
+        async fn deregister_by(&self, _filter: crate::models::ServiceFilter) -> Result<usize> {
+            unimplemented!()
+        }
+
+        async fn update_service(
+            &self,
+            _service_id: Uuid,
+            _request: crate::models::UpdateServiceRequest,
+        ) -> Result<()> {
+            unimplemented!()
+        }
+
+        async fn heartbeat(&self, _service_id: Uuid, _status: Option<ServiceStatus>) -> Result<()> {
+            unimplemented!()
+        }
+
+        async fn discover_services(
+            &self,
+            _query: crate::models::ServiceDiscoveryQuery,
+        ) -> Result<crate::models::ServiceDiscoveryResponse> {
+            unimplemented!()
+        }
+
+        async fn get_service(&self, _service_id: Uuid) -> Result<Option<ServiceRegistration>> {
+            unimplemented!()
+        }
+
+        async fn get_services_by_name(
+            &self,
+            service_name: &str,
+        ) -> Result<Vec<ServiceRegistration>> {
+            Ok(self
+                .0
+                .iter()
+                .filter(|registration| registration.name == service_name)
+                .cloned()
+                .collect())
+        }
+
+        async fn get_service_statistics(
+            &self,
+            _service_id: Uuid,
+        ) -> Result<crate::models::ServiceStatistics> {
+            unimplemented!()
+        }
+
+        async fn record_health_check(
+            &self,
+            _result: crate::models::HealthCheckResult,
+        ) -> Result<()> {
+            unimplemented!()
+        }
+
+        async fn get_health_status(
+            &self,
+            _service_id: Uuid,
+        ) -> Result<crate::models::HealthStatus> {
+            unimplemented!()
+        }
+
+        fn watch(&self, _service_name: &str) -> crate::watch::ServiceChangeStream {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    fn test_parse_srv_name() {
+        let responder = responder();
+
+        assert_eq!(
+            responder.parse_srv_name("_http._tcp.user-service.discovery"),
+            Ok(SrvQuery {
+                service: "user-service".to_string(),
+                protocol: ServiceProtocol::Http,
+            })
+        );
+        assert_eq!(
+            responder.parse_srv_name("_http._tcp.user-service.example.com"),
+            Err(ResponseCode::Refused)
+        );
+        assert_eq!(
+            responder.parse_srv_name("_http._udp.user-service.discovery"),
+            Err(ResponseCode::NameError)
+        );
+        assert_eq!(
+            responder.parse_srv_name("user-service.discovery"),
+            Err(ResponseCode::NameError)
+        );
+    }
+
+    #[test]
+    fn test_srv_records_only_include_healthy_instances() {
+        let responder = responder();
+        let query = responder
+            .parse_srv_name("_http._tcp.user-service.discovery")
+            .unwrap();
+
+        let mut primary = registration("10.0.0.1", 8080, ServiceStatus::Healthy);
+        primary
+            .metadata
+            .insert(SRV_PRIORITY_METADATA_KEY.to_string(), "5".to_string());
+        let hostname = registration("users-2.internal.", 8081, ServiceStatus::Healthy);
+        let unhealthy = registration("10.0.0.3", 8082, ServiceStatus::Unhealthy);
+        let mut grpc = registration("10.0.0.4", 9090, ServiceStatus::Healthy);
+        grpc.protocol = ServiceProtocol::Grpc;
+
+        let records = responder.srv_records(&query, &[primary.clone(), hostname, unhealthy, grpc]);
+
+        assert_eq!(
+            records,
+            vec![
+                SrvRecord {
+                    priority: 5,
+                    weight: 200,
+                    port: 8080,
+                    target: format!("{}.user-service.discovery", primary.id.simple()),
+                    address: Some("10.0.0.1".parse().unwrap()),
+                },
+                SrvRecord {
+                    priority: 0,
+                    weight: 200,
+                    port: 8081,
+                    target: "users-2.internal".to_string(),
+                    address: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_response_encodes_srv_and_address_records() {
+        let packet = query_packet("_http._tcp.user-service.discovery", TYPE_SRV);
+        let question = parse_question(&packet).unwrap();
+        let record = SrvRecord {
+            priority: 1,
+            weight: 100,
+            port: 8080,
+            target: "a.user-service.discovery".to_string(),
+            address: Some("10.0.0.1".parse().unwrap()),
+        };
+
+        let response = build_response(
+            &question,
+            ResponseCode::NoError,
+            &[srv_answer(&record, 45)],
+            &[address_record(&record, 45).unwrap()],
+            MAX_UDP_MESSAGE_SIZE,
+        );
+
+        assert_eq!(&response[0..2], &[0x12, 0x34]);
+        assert_eq!(rcode(&response), 0);
+        assert_eq!(count(&response, 0), 1);
+        assert_eq!(count(&response, 1), 1);
+        assert_eq!(count(&response, 3), 1);
+        assert_eq!(&response[12..packet.len()], &packet[12..]);
+
+        let answer = &response[packet.len()..];
+        assert_eq!(&answer[0..2], &[0xc0, 0x0c]);
+        assert_eq!(u16::from_be_bytes([answer[2], answer[3]]), TYPE_SRV);
+        assert_eq!(
+            u32::from_be_bytes([answer[6], answer[7], answer[8], answer[9]]),
+            45
+        );
+        assert_eq!(&answer[12..18], &[0, 1, 0, 100, 0x1f, 0x90]);
+        assert!(response.ends_with(&[10, 0, 0, 1]));
+    }
+
+    #[test]
+    fn test_build_response_truncates_to_udp_limit() {
+        let packet = query_packet("_http._tcp.user-service.discovery", TYPE_SRV);
+        let question = parse_question(&packet).unwrap();
+        let answers: Vec<Vec<u8>> = (0..40)
+            .map(|i| {
+                let record = SrvRecord {
+                    priority: 0,
+                    weight: 1,
+                    port: 8000 + i,
+                    target: format!("instance-{}.user-service.discovery", i),
+                    address: None,
+                };
+                srv_answer(&record, 30)
+            })
+            .collect();
+
+        let response = build_response(
+            &question,
+            ResponseCode::NoError,
+            &answers,
+            &[],
+            MAX_UDP_MESSAGE_SIZE,
+        );
+
+        assert!(response.len() <= MAX_UDP_MESSAGE_SIZE);
+        assert_ne!(u16::from_be_bytes([response[2], response[3]]) & 0x0200, 0);
+        assert!(count(&response, 1) < 40);
+    }
+
+    #[tokio::test]
+    async fn test_handle_packet_error_codes() {
+        let responder = responder();
+
+        let unknown = responder
+            .handle_packet(&query_packet("_http._tcp.missing.discovery", TYPE_SRV))
+            .await
+            .unwrap();
+        assert_eq!(rcode(&unknown), ResponseCode::NameError as u16);
+
+        let outside = responder
+            .handle_packet(&query_packet("_http._tcp.missing.example.com", TYPE_SRV))
+            .await
+            .unwrap();
+        assert_eq!(rcode(&outside), ResponseCode::Refused as u16);
+
+        let truncated_question = [0x12, 0x34, 0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 0, 5];
+        let malformed = responder.handle_packet(&truncated_question).await.unwrap();
+        assert_eq!(rcode(&malformed), ResponseCode::FormatError as u16);
+
+        assert!(responder.handle_packet(&[0x12]).await.is_none());
+    }
+
+    #[test]
+    fn test_parse_target_name() {
+        let responder = responder();
+        let instance = Uuid::new_v4();
+
+        assert_eq!(
+            responder.parse_target_name(&format!("{}.user-service.discovery", instance.simple())),
+            Some(TargetQuery {
+                service: "user-service".to_string(),
+                instance,
+            })
+        );
+        assert_eq!(
+            responder.parse_target_name(&format!("{}.user-service.example.com", instance.simple())),
+            None
+        );
+        assert_eq!(
+            responder.parse_target_name("instance-1.user-service.discovery"),
+            None
+        );
+        assert_eq!(
+            responder.parse_target_name("_http._tcp.user-service.discovery"),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_synthetic_targets_answer_address_queries() {
+        let v4 = registration("10.0.0.1", 8080, ServiceStatus::Healthy);
+        let v6 = registration("fd00::1", 8081, ServiceStatus::Healthy);
+        let responder = responder_with(vec![v4.clone(), v6.clone()]);
+        let v4_target = format!("{}.user-service.discovery", v4.id.simple());
+        let v6_target = format!("{}.user-service.discovery", v6.id.simple());
+
+        let a = responder
+            .handle_packet(&query_packet(&v4_target, TYPE_A))
+            .await
+            .unwrap();
+        assert_eq!(rcode(&a), 0);
+        assert_eq!(count(&a, 1), 1);
+        assert!(a.ends_with(&[0, 4, 10, 0, 0, 1]));
+
+        let aaaa = responder
+            .handle_packet(&query_packet(&v6_target, TYPE_AAAA))
+            .await
+            .unwrap();
+        assert_eq!(count(&aaaa, 1), 1);
+        let expected: IpAddr = "fd00::1".parse().unwrap();
+        assert!(aaaa.ends_with(&address_rdata(expected).1));
+
+        // The name exists but holds no record of the other family
+        let mismatched = responder
+            .handle_packet(&query_packet(&v4_target, TYPE_AAAA))
+            .await
+            .unwrap();
+        assert_eq!(rcode(&mismatched), 0);
+        assert_eq!(count(&mismatched, 1), 0);
+
+        let unknown = responder
+            .handle_packet(&query_packet(
+                &format!("{}.user-service.discovery", Uuid::new_v4().simple()),
+                TYPE_A,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(rcode(&unknown), ResponseCode::NameError as u16);
+    }
+
+    #[tokio::test]
+    async fn test_tcp_answers_are_not_truncated() {
+        let registrations: Vec<ServiceRegistration> = (0..40)
+            .map(|i| registration(&format!("10.0.1.{}", i), 8000 + i, ServiceStatus::Healthy))
+            .collect();
+        let responder = responder_with(registrations);
+        let query = query_packet("_http._tcp.user-service.discovery", TYPE_SRV);
+
+        let udp = responder.handle_packet(&query).await.unwrap();
+        assert_ne!(u16::from_be_bytes([udp[2], udp[3]]) & 0x0200, 0);
+
+        let (mut client, server) = tokio::io::duplex(MAX_TCP_MESSAGE_SIZE);
+        let connection = tokio::spawn(async move { responder.serve_connection(server).await });
+
+        client
+            .write_all(&(query.len() as u16).to_be_bytes())
+            .await
+            .unwrap();
+        client.write_all(&query).await.unwrap();
+
+        let mut prefix = [0u8; 2];
+        client.read_exact(&mut prefix).await.unwrap();
+        let mut response = vec![0u8; u16::from_be_bytes(prefix) as usize];
+        client.read_exact(&mut response).await.unwrap();
+
+        assert!(response.len() > MAX_UDP_MESSAGE_SIZE);
+        assert_eq!(u16::from_be_bytes([response[2], response[3]]) & 0x0200, 0);
+        assert_eq!(count(&response, 1), 40);
+        assert_eq!(count(&response, 3), 40);
+
+        drop(client);
+        assert!(connection.await.unwrap().is_ok());
+    }
+}
@@ -0,0 +1,452 @@
+//! gRPC Health Checking Module
+//!
+//! Client for the standard gRPC Health Checking Protocol
+//! (`grpc.health.v1.Health/Check`) over plaintext HTTP/2. The request and
+//! response messages each carry a single field, so they are encoded by hand
+//! instead of through generated protobuf code.
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use http::HeaderMap;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tokio::net::TcpStream;
+
+/// Path of the health Check RPC
+const CHECK_PATH: &str = "/grpc.health.v1.Health/Check";
+
+/// gRPC status code of a call whose deadline passed
+const DEADLINE_EXCEEDED: u32 = 4;
+
+/// Largest value the `grpc-timeout` header accepts
+const MAX_TIMEOUT_VALUE: u128 = 99_999_999;
+
+/// Serving status reported by a gRPC health server
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServingStatus {
+    Unknown,
+    Serving,
+    NotServing,
+    ServiceUnknown,
+}
+
+impl ServingStatus {
+    fn from_proto(value: u64) -> Self {
+        match value {
+            1 => ServingStatus::Serving,
+            2 => ServingStatus::NotServing,
+            3 => ServingStatus::ServiceUnknown,
+            _ => ServingStatus::Unknown,
+        }
+    }
+}
+
+/// Reasons a health check returned no serving status
+#[derive(Debug, Error)]
+pub enum GrpcHealthError {
+    #[error("gRPC connection failed: {0}")]
+    Connect(String),
+
+    #[error("gRPC health check deadline exceeded")]
+    DeadlineExceeded,
+
+    #[error("gRPC health check failed with status {code}: {message}")]
+    Status { code: u32, message: String },
+
+    #[error("gRPC protocol error: {0}")]
+    Protocol(String),
+}
+
+impl From<h2::Error> for GrpcHealthError {
+    fn from(error: h2::Error) -> Self {
+        GrpcHealthError::Protocol(error.to_string())
+    }
+}
+
+/// Call `grpc.health.v1.Health/Check` for `service` on the server at `address`
+///
+/// The deadline bounds the whole check. Not connecting within it is a
+/// connection failure; the time left after connecting is sent to the server
+/// as the call deadline and also bounds the call locally.
+pub async fn check(
+    address: &str,
+    service: &str,
+    deadline: Duration,
+) -> Result<ServingStatus, GrpcHealthError> {
+    let started = Instant::now();
+    let client = match tokio::time::timeout(deadline, connect(address)).await {
+        Ok(client) => client?,
+        Err(_) => {
+            return Err(GrpcHealthError::Connect(format!(
+                "timed out after {:?}",
+                deadline
+            )))
+        }
+    };
+
+    let remaining = deadline.saturating_sub(started.elapsed());
+    match tokio::time::timeout(remaining, call(client, address, service, remaining)).await {
+        Ok(result) => result,
+        Err(_) => Err(GrpcHealthError::DeadlineExceeded),
+    }
+}
+
+async fn connect(address: &str) -> Result<h2::client::SendRequest<Bytes>, GrpcHealthError> {
+    let stream = TcpStream::connect(address)
+        .await
+        .map_err(|e| GrpcHealthError::Connect(e.to_string()))?;
+    let (client, connection) = h2::client::handshake(stream)
+        .await
+        .map_err(|e| GrpcHealthError::Connect(e.to_string()))?;
+
+    // The connection closes once the client handle and its streams are dropped
+    tokio::spawn(async move {
+        let _ = connection.await;
+    });
+
+    Ok(client)
+}
+
+async fn call(
+    client: h2::client::SendRequest<Bytes>,
+    address: &str,
+    service: &str,
+    deadline: Duration,
+) -> Result<ServingStatus, GrpcHealthError> {
+    let request = http::Request::post(format!("http://{}{}", address, CHECK_PATH))
+        .header("content-type", "application/grpc")
+        .header("te", "trailers")
+        .header(
+            "grpc-timeout",
+            format!("{}m", deadline.as_millis().min(MAX_TIMEOUT_VALUE)),
+        )
+        .body(())
+        .map_err(|e| GrpcHealthError::Protocol(e.to_string()))?;
+
+    let mut client = client.ready().await?;
+    let (response, mut send_stream) = client.send_request(request, false)?;
+    send_stream.send_data(encode_request(service), true)?;
+
+    let response = response.await?;
+    if response.status() != http::StatusCode::OK {
+        return Err(GrpcHealthError::Protocol(format!(
+            "unexpected HTTP status {}",
+            response.status()
+        )));
+    }
+
+    // Trailers-only responses carry the gRPC status in the headers
+    let (parts, mut body) = response.into_parts();
+    check_grpc_status(&parts.headers)?;
+
+    let mut message = BytesMut::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk?;
+        let _ = body.flow_control().release_capacity(chunk.len());
+        message.extend_from_slice(&chunk);
+    }
+
+    if let Some(trailers) = body.trailers().await? {
+        check_grpc_status(&trailers)?;
+    }
+
+    decode_response(message.freeze())
+}
+
+/// Fail on a non-OK `grpc-status`, if the headers carry one
+fn check_grpc_status(headers: &HeaderMap) -> Result<(), GrpcHealthError> {
+    let Some(status) = headers.get("grpc-status") else {
+        return Ok(());
+    };
+
+    let code = status
+        .to_str()
+        .ok()
+        .and_then(|status| status.parse::<u32>().ok())
+        .ok_or_else(|| GrpcHealthError::Protocol("invalid grpc-status".to_string()))?;
+
+    match code {
+        0 => Ok(()),
+        DEADLINE_EXCEEDED => Err(GrpcHealthError::DeadlineExceeded),
+        _ => Err(GrpcHealthError::Status {
+            code,
+            message: headers
+                .get("grpc-message")
+                .and_then(|message| message.to_str().ok())
+                .unwrap_or_default()
+                .to_string(),
+        }),
+    }
+}
+
+/// Length-prefixed `HealthCheckRequest { string service = 1; }`
+fn encode_request(service: &str) -> Bytes {
+    let mut message = BytesMut::new();
+    if !service.is_empty() {
+        message.put_u8(0x0a);
+        put_varint(&mut message, service.len() as u64);
+        message.put_slice(service.as_bytes());
+    }
+
+    frame(message.freeze())
+}
+
+/// Prefix a message with the uncompressed gRPC frame header
+fn frame(message: Bytes) -> Bytes {
+    let mut framed = BytesMut::with_capacity(5 + message.len());
+    framed.put_u8(0);
+    framed.put_u32(message.len() as u32);
+    framed.put_slice(&message);
+    framed.freeze()
+}
+
+/// Unframe a `HealthCheckResponse { ServingStatus status = 1; }`
+fn decode_response(mut framed: Bytes) -> Result<ServingStatus, GrpcHealthError> {
+    let malformed = || GrpcHealthError::Protocol("malformed health check response".to_string());
+
+    if framed.len() < 5 {
+        return Err(malformed());
+    }
+    if framed.get_u8() != 0 {
+        return Err(GrpcHealthError::Protocol(
+            "compressed responses are not supported".to_string(),
+        ));
+    }
+    let length = framed.get_u32() as usize;
+    if framed.len() < length {
+        return Err(malformed());
+    }
+    let mut message = framed.split_to(length);
+
+    // Fields other than `status` are skipped; a missing status means UNKNOWN
+    let mut status = 0;
+    while message.has_remaining() {
+        let key = get_varint(&mut message).ok_or_else(malformed)?;
+        match (key >> 3, key & 0x7) {
+            (1, 0) => status = get_varint(&mut message).ok_or_else(malformed)?,
+            (_, 0) => {
+                get_varint(&mut message).ok_or_else(malformed)?;
+            }
+            (_, wire_type) => {
+                let skip = match wire_type {
+                    1 => 8,
+                    2 => get_varint(&mut message).ok_or_else(malformed)? as usize,
+                    5 => 4,
+                    _ => return Err(malformed()),
+                };
+                if message.remaining() < skip {
+                    return Err(malformed());
+                }
+                message.advance(skip);
+            }
+        }
+    }
+
+    Ok(ServingStatus::from_proto(status))
+}
+
+fn put_varint(buf: &mut BytesMut, mut value: u64) {
+    while value >= 0x80 {
+        buf.put_u8((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.put_u8(value as u8);
+}
+
+fn get_varint(buf: &mut Bytes) -> Option<u64> {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        if !buf.has_remaining() {
+            return None;
+        }
+        let byte = buf.get_u8();
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::net::SocketAddr;
+    use tokio::net::{TcpListener, TcpSocket};
+
+    /// Reply a mock health server gives for a service
+    #[derive(Clone, Copy)]
+    enum Reply {
+        Status(u64),
+        Slow,
+    }
+
+    /// Serve `grpc.health.v1.Health/Check`, answering NOT_FOUND for unknown services
+    async fn spawn_health_server(replies: HashMap<&'static str, Reply>) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let replies = replies.clone();
+                tokio::spawn(async move {
+                    let mut connection = h2::server::handshake(socket).await.unwrap();
+                    while let Some(Ok((request, respond))) = connection.accept().await {
+                        tokio::spawn(answer(request, respond, replies.clone()));
+                    }
+                });
+            }
+        });
+
+        address
+    }
+
+    async fn answer(
+        request: http::Request<h2::RecvStream>,
+        mut respond: h2::server::SendResponse<Bytes>,
+        replies: HashMap<&'static str, Reply>,
+    ) {
+        assert_eq!(request.uri().path(), CHECK_PATH);
+        assert!(request.headers().contains_key("grpc-timeout"));
+
+        let mut body = request.into_body();
+        let mut framed = BytesMut::new();
+        while let Some(chunk) = body.data().await {
+            framed.extend_from_slice(&chunk.unwrap());
+        }
+        let mut message = framed.freeze().slice(5..);
+        let service = if message.has_remaining() {
+            assert_eq!(message.get_u8(), 0x0a);
+            let length = get_varint(&mut message).unwrap() as usize;
+            String::from_utf8(message.split_to(length).to_vec()).unwrap()
+        } else {
+            String::new()
+        };
+
+        let status = match replies.get(service.as_str()) {
+            Some(Reply::Status(status)) => *status,
+            Some(Reply::Slow) => {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                1
+            }
+            None => {
+                let response = http::Response::builder()
+                    .header("content-type", "application/grpc")
+                    .header("grpc-status", "5")
+                    .header("grpc-message", "unknown service")
+                    .body(())
+                    .unwrap();
+                respond.send_response(response, true).unwrap();
+                return;
+            }
+        };
+
+        let response = http::Response::builder()
+            .header("content-type", "application/grpc")
+            .body(())
+            .unwrap();
+        let mut send_stream = respond.send_response(response, false).unwrap();
+
+        let mut message = BytesMut::new();
+        message.put_u8(0x08);
+        put_varint(&mut message, status);
+        send_stream
+            .send_data(frame(message.freeze()), false)
+            .unwrap();
+
+        let mut trailers = HeaderMap::new();
+        trailers.insert("grpc-status", "0".parse().unwrap());
+        send_stream.send_trailers(trailers).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_check_maps_serving_statuses() {
+        let address = spawn_health_server(HashMap::from([
+            ("health", Reply::Status(1)),
+            ("draining", Reply::Status(2)),
+            ("", Reply::Status(1)),
+        ]))
+        .await
+        .to_string();
+        let deadline = Duration::from_secs(2);
+
+        assert_eq!(
+            check(&address, "health", deadline).await.unwrap(),
+            ServingStatus::Serving
+        );
+        assert_eq!(
+            check(&address, "draining", deadline).await.unwrap(),
+            ServingStatus::NotServing
+        );
+        assert_eq!(
+            check(&address, "", deadline).await.unwrap(),
+            ServingStatus::Serving
+        );
+
+        match check(&address, "missing", deadline).await {
+            Err(GrpcHealthError::Status { code, message }) => {
+                assert_eq!(code, 5);
+                assert_eq!(message, "unknown service");
+            }
+            other => panic!("expected NOT_FOUND status, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_distinguishes_deadline_from_connection_failure() {
+        let address = spawn_health_server(HashMap::from([("slow", Reply::Slow)]))
+            .await
+            .to_string();
+        assert!(matches!(
+            check(&address, "slow", Duration::from_millis(100)).await,
+            Err(GrpcHealthError::DeadlineExceeded)
+        ));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let closed = listener.local_addr().unwrap().to_string();
+        drop(listener);
+        assert!(matches!(
+            check(&closed, "health", Duration::from_secs(1)).await,
+            Err(GrpcHealthError::Connect(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_check_reports_hung_connect_as_connection_failure() {
+        // Nothing accepts on this listener, so once its backlog is full new
+        // connects hang instead of completing
+        let socket = TcpSocket::new_v4().unwrap();
+        socket.bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        let listener = socket.listen(1).unwrap();
+        let address = listener.local_addr().unwrap();
+        let mut backlog = Vec::new();
+        for _ in 0..8 {
+            if let Ok(Ok(stream)) =
+                tokio::time::timeout(Duration::from_millis(50), TcpStream::connect(address)).await
+            {
+                backlog.push(stream);
+            }
+        }
+
+        assert!(matches!(
+            check(&address.to_string(), "health", Duration::from_millis(200)).await,
+            Err(GrpcHealthError::Connect(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_skips_unknown_fields() {
+        let mut message = BytesMut::new();
+        // Field 2, length-delimited, followed by status NOT_SERVING
+        message.put_slice(&[0x12, 0x02, 0xff, 0xff, 0x08, 0x02]);
+        assert_eq!(
+            decode_response(frame(message.freeze())).unwrap(),
+            ServingStatus::NotServing
+        );
+        assert_eq!(
+            decode_response(frame(Bytes::new())).unwrap(),
+            ServingStatus::Unknown
+        );
+        assert!(decode_response(Bytes::from_static(&[0, 0, 0, 0, 9, 0x08])).is_err());
+    }
+}
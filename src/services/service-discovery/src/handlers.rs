@@ -8,21 +8,26 @@ use crate::health::{HealthMonitor, HealthMonitorImpl};
 use crate::load_balancer::{LoadBalancer, LoadBalancerImpl};
 use crate::models::{
     HealthCheckResult, HeartbeatRequest, RegisterServiceRequest, ServiceDiscoveryQuery,
-    ServiceDiscoveryResponse, ServiceInstance, ServiceRegistration, ServiceStatistics,
-    UpdateServiceRequest,
+    ServiceDiscoveryResponse, ServiceFilter, ServiceInstance, ServiceRegistration,
+    ServiceStatistics, UpdateServiceRequest,
 };
 use crate::registry::{ServiceRegistry, ServiceRegistryImpl};
 
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
-    response::Json,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Json,
+    },
     routing::{delete, get, post, put},
     Router,
 };
+use futures::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
@@ -71,6 +76,13 @@ pub struct ServiceRegistrationResponse {
     pub message: String,
 }
 
+/// Bulk deregistration response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkDeregistrationResponse {
+    pub removed: usize,
+    pub message: String,
+}
+
 /// Health check response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HealthResponse {
@@ -96,6 +108,7 @@ pub fn create_router(state: AppState) -> Router {
     Router::new()
         // Service management routes
         .route("/api/v1/services", post(register_service))
+        .route("/api/v1/services/deregister", post(deregister_services))
         .route("/api/v1/services/:id", get(get_service))
         .route("/api/v1/services/:id", put(update_service))
         .route("/api/v1/services/:id", delete(deregister_service))
@@ -106,6 +119,7 @@ pub fn create_router(state: AppState) -> Router {
             "/api/v1/services/:name/instances",
             get(get_service_instances),
         )
+        .route("/api/v1/services/:name/watch", get(watch_service))
         // Health check routes
         .route("/api/v1/services/:id/health", get(get_service_health))
         .route("/api/v1/services/:id/health", post(check_service_health))
@@ -143,6 +157,13 @@ pub async fn register_service(
 ) -> Result<Json<ApiResponse<ServiceRegistrationResponse>>, StatusCode> {
     debug!("Registering service: {}", request.name);
 
+    if let Some(health_check) = &request.health_check {
+        if let Err(e) = state.health_monitor.validate_health_check(health_check) {
+            warn!("Rejected health check for service {}: {}", request.name, e);
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+
     match state.registry.register_service(request.clone()).await {
         Ok(service_id) => {
             info!(
@@ -236,6 +257,39 @@ pub async fn deregister_service(
     }
 }
 
+/// Deregister every service instance matching a filter
+pub async fn deregister_services(
+    State(state): State<AppState>,
+    Json(filter): Json<ServiceFilter>,
+) -> Result<Json<ApiResponse<BulkDeregistrationResponse>>, StatusCode> {
+    debug!("Deregistering services matching: {:?}", filter);
+
+    if filter.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    // Remove from health monitoring first
+    for service_id in state.registry.matching_service_ids(&filter) {
+        if let Err(e) = state.health_monitor.remove_service(service_id).await {
+            warn!("Failed to remove service from health monitoring: {}", e);
+        }
+    }
+
+    match state.registry.deregister_by(filter).await {
+        Ok(removed) => {
+            info!("Successfully deregistered {} services", removed);
+            Ok(Json(ApiResponse::success(BulkDeregistrationResponse {
+                removed,
+                message: format!("Deregistered {} services", removed),
+            })))
+        }
+        Err(e) => {
+            error!("Failed to deregister services: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
 /// Process service heartbeat
 pub async fn service_heartbeat(
     State(state): State<AppState>,
@@ -298,6 +352,7 @@ pub async fn get_service_instances(
                 .map(|s| ServiceInstance {
                     id: s.id,
                     name: s.name,
+                    namespace: s.namespace,
                     version: s.version,
                     address: s.address,
                     port: s.port,
@@ -321,6 +376,23 @@ pub async fn get_service_instances(
     }
 }
 
+/// Stream registry changes for a service as server-sent events
+pub async fn watch_service(
+    State(state): State<AppState>,
+    Path(service_name): Path<String>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    debug!("Watching service: {}", service_name);
+
+    let events = state
+        .registry
+        .watch(&service_name)
+        .map(|change| Event::default().event(change.kind()).json_data(&change));
+    let keep_alive =
+        Duration::from_secs(state.config.registry.discovery.watch.keep_alive_seconds as u64);
+
+    Sse::new(events).keep_alive(KeepAlive::new().interval(keep_alive))
+}
+
 /// Get service health status
 pub async fn get_service_health(
     State(state): State<AppState>,
@@ -2,13 +2,16 @@
 //!
 //! Provides active health checking capabilities for registered services.
 //! Supports HTTP, TCP, gRPC, and script-based health checks with configurable
-//! intervals, timeouts, and failure thresholds.
+//! intervals, timeouts, and failure thresholds. Instances ejected by outlier
+//! detection are marked unhealthy regardless of their health check results.
 
-use crate::config::ServiceDiscoveryConfig;
+use crate::config::{AllowedScript, ScriptHealthCheckConfig, ServiceDiscoveryConfig};
+use crate::grpc_health::{self, GrpcHealthError, ServingStatus};
 use crate::models::{
-    HealthCheckConfig, HealthCheckResult, HealthCheckTypeConfig, HealthStatus,
-    ServiceRegistration, ServiceStatus,
+    HealthCheckConfig, HealthCheckResult, HealthCheckTypeConfig, HealthStatus, ServiceRegistration,
+    ServiceStatus,
 };
+use crate::outlier::{OutlierDetector, OutlierEvent};
 use crate::registry::ServiceRegistry;
 
 use anyhow::Result;
@@ -18,6 +21,7 @@ use dashmap::DashMap;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::process::Stdio;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::net::TcpStream;
@@ -61,6 +65,10 @@ pub struct HealthMonitoringStats {
     /// Number of unhealthy services
     pub unhealthy_services: u64,
 
+    /// Number of instances currently ejected by outlier detection
+    #[serde(default)]
+    pub ejected_services: u64,
+
     /// Total health checks performed
     pub total_health_checks: u64,
 
@@ -174,6 +182,9 @@ pub struct HealthMonitorImpl {
 
     /// Running state
     is_running: Arc<RwLock<bool>>,
+
+    /// Outlier detector fed with request outcomes by the load balancer
+    outlier_detector: Arc<OutlierDetector>,
 }
 
 impl HealthMonitorImpl {
@@ -185,6 +196,9 @@ impl HealthMonitorImpl {
             ))
             .build()
             .expect("Failed to create HTTP client");
+        let outlier_detector = Arc::new(OutlierDetector::new(
+            config.registry.health_checks.outlier_detection.clone(),
+        ));
 
         Self {
             config,
@@ -198,13 +212,89 @@ impl HealthMonitorImpl {
                 total_services: 0,
                 healthy_services: 0,
                 unhealthy_services: 0,
+                ejected_services: 0,
                 total_health_checks: 0,
                 avg_response_time_ms: 0.0,
                 error_rate: 0.0,
                 last_updated: Utc::now(),
             })),
             is_running: Arc::new(RwLock::new(false)),
+            outlier_detector,
+        }
+    }
+
+    /// Reject script health checks whose command line the configuration does not permit
+    pub fn validate_health_check(&self, health_check: &HealthCheckConfig) -> Result<()> {
+        match &health_check.config {
+            HealthCheckTypeConfig::Script {
+                command,
+                args,
+                working_dir,
+            } => ensure_script_allowed(
+                &self.config.registry.health_checks.types.script,
+                command,
+                args,
+                working_dir.as_deref(),
+            )
+            .map(|_| ()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Outlier detector to share with the load balancer
+    pub fn outlier_detector(&self) -> Arc<OutlierDetector> {
+        Arc::clone(&self.outlier_detector)
+    }
+
+    /// Apply outlier ejections and re-admissions to the registry
+    async fn apply_outlier_events(&self) {
+        for event in self.outlier_detector.poll_events() {
+            let (service_id, status) = match event {
+                OutlierEvent::Ejected {
+                    instance_id,
+                    duration,
+                } => {
+                    warn!(
+                        "Outlier detection ejected service {} for {:?}",
+                        instance_id, duration
+                    );
+                    (instance_id, ServiceStatus::Unhealthy)
+                }
+                OutlierEvent::HalfOpened { instance_id } => {
+                    info!(
+                        "Re-admitting service {} for probe traffic after ejection",
+                        instance_id
+                    );
+                    (instance_id, ServiceStatus::Healthy)
+                }
+                OutlierEvent::Reinstated { instance_id } => {
+                    info!("Service {} fully reinstated after ejection", instance_id);
+                    continue;
+                }
+            };
+
+            if let Err(e) = self
+                .registry
+                .update_service(
+                    service_id,
+                    crate::models::UpdateServiceRequest {
+                        status: Some(status.clone()),
+                        weight: None,
+                        metadata: None,
+                        health_check: None,
+                        circuit_breaker: None,
+                    },
+                )
+                .await
+            {
+                error!(
+                    "Failed to update service {} status to {:?} after outlier detection: {}",
+                    service_id, status, e
+                );
+            }
         }
+
+        self.stats.write().await.ejected_services = self.outlier_detector.ejected_count() as u64;
     }
 
     /// Main monitoring loop
@@ -218,6 +308,8 @@ impl HealthMonitorImpl {
                 break;
             }
 
+            self.apply_outlier_events().await;
+
             // Check which services need health checks
             let services_to_check: Vec<Uuid> = self
                 .schedulers
@@ -322,7 +414,10 @@ impl HealthMonitorImpl {
             } else {
                 warn!("Marked service {} as unhealthy", service_id);
             }
-        } else if scheduler.should_mark_healthy() && service.status != ServiceStatus::Healthy {
+        } else if scheduler.should_mark_healthy()
+            && service.status != ServiceStatus::Healthy
+            && !self.outlier_detector.is_ejected(service_id)
+        {
             if let Err(e) = self
                 .registry
                 .update_service(
@@ -362,10 +457,11 @@ impl HealthMonitorImpl {
     ) -> HealthCheckResult {
         let start_time = Instant::now();
         let check_timeout = Duration::from_secs(config.timeout as u64);
+        let mut details = HashMap::new();
 
         let (status, error_message, response_time_ms) = match timeout(
             check_timeout,
-            self.perform_check_by_type(service, &config.config),
+            self.perform_check_by_type(service, &config.config, check_timeout, &mut details),
         )
         .await
         {
@@ -387,15 +483,17 @@ impl HealthMonitorImpl {
             response_time_ms,
             error_message,
             timestamp: Utc::now(),
-            details: HashMap::new(),
+            details,
         }
     }
 
-    /// Perform health check based on check type
+    /// Perform health check based on check type, adding check-specific details
     async fn perform_check_by_type(
         &self,
         service: &ServiceRegistration,
         config: &HealthCheckTypeConfig,
+        check_timeout: Duration,
+        details: &mut HashMap<String, String>,
     ) -> Result<(HealthStatus, Option<String>)> {
         match config {
             HealthCheckTypeConfig::Http {
@@ -417,15 +515,22 @@ impl HealthMonitorImpl {
             }
             HealthCheckTypeConfig::Tcp {} => self.perform_tcp_check(service).await,
             HealthCheckTypeConfig::Grpc { service_name } => {
-                self.perform_grpc_check(service, service_name).await
+                self.perform_grpc_check(service, service_name.as_deref(), check_timeout)
+                    .await
             }
             HealthCheckTypeConfig::Script {
                 command,
                 args,
                 working_dir,
             } => {
-                self.perform_script_check(command, args, working_dir.as_deref())
-                    .await
+                run_script_check(
+                    &self.config.registry.health_checks.types.script,
+                    command,
+                    args,
+                    working_dir.as_deref(),
+                    details,
+                )
+                .await
             }
         }
     }
@@ -510,55 +615,34 @@ impl HealthMonitorImpl {
         }
     }
 
-    /// Perform gRPC health check
+    /// Perform gRPC health check with `grpc.health.v1.Health/Check`
+    ///
+    /// The call deadline is the shorter of the service's check timeout and the
+    /// configured gRPC timeout.
     async fn perform_grpc_check(
         &self,
         service: &ServiceRegistration,
-        _service_name: &str,
+        service_name: Option<&str>,
+        check_timeout: Duration,
     ) -> Result<(HealthStatus, Option<String>)> {
-        // This is a simplified implementation
-        // In a real implementation, you would use a gRPC client to call the health service
-
-        // For now, just try to establish a TCP connection to the gRPC port
+        let grpc_config = &self.config.registry.health_checks.types.grpc;
+        let service_name = service_name.unwrap_or(&grpc_config.service_name);
         let address = format!("{}:{}", service.address, service.port);
+        let deadline = check_timeout.min(Duration::from_secs(grpc_config.timeout as u64));
 
-        match TcpStream::connect(&address).await {
-            Ok(_) => {
-                // In a real implementation, you would send a gRPC health check request here
-                // For example: grpc_health_v1::health_client::HealthClient::check()
-                Ok((HealthStatus::Healthy, None))
-            }
-            Err(e) => Ok((
+        match grpc_health::check(&address, service_name, deadline).await {
+            Ok(ServingStatus::Serving) => Ok((HealthStatus::Healthy, None)),
+            Ok(status) => Ok((
                 HealthStatus::Unhealthy,
-                Some(format!("gRPC connection failed: {}", e)),
+                Some(format!(
+                    "gRPC service '{}' reported {:?}",
+                    service_name, status
+                )),
             )),
-        }
-    }
-
-    /// Perform script-based health check
-    async fn perform_script_check(
-        &self,
-        command: &str,
-        args: &[String],
-        working_dir: Option<&str>,
-    ) -> Result<(HealthStatus, Option<String>)> {
-        let mut cmd = Command::new(command);
-        cmd.args(args);
-
-        if let Some(dir) = working_dir {
-            cmd.current_dir(dir);
-        }
-
-        let output = cmd.output().await?;
-
-        if output.status.success() {
-            Ok((HealthStatus::Healthy, None))
-        } else {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            Ok((
-                HealthStatus::Unhealthy,
-                Some(format!("Script failed: {}", stderr)),
-            ))
+            Err(e @ GrpcHealthError::DeadlineExceeded) => {
+                Ok((HealthStatus::Timeout, Some(e.to_string())))
+            }
+            Err(e) => Ok((HealthStatus::Unhealthy, Some(e.to_string()))),
         }
     }
 
@@ -648,6 +732,8 @@ impl HealthMonitor for HealthMonitorImpl {
 
     async fn monitor_service(&self, service: ServiceRegistration) -> Result<()> {
         if let Some(health_config) = &service.health_check {
+            self.validate_health_check(health_config)?;
+
             let scheduler = HealthCheckScheduler::new(service.id, health_config.clone());
 
             self.schedulers.insert(service.id, RwLock::new(scheduler));
@@ -668,6 +754,7 @@ impl HealthMonitor for HealthMonitorImpl {
     async fn remove_service(&self, service_id: Uuid) -> Result<()> {
         self.schedulers.remove(&service_id);
         self.monitored_services.remove(&service_id);
+        self.outlier_detector.remove(service_id);
 
         debug!("Removed service {} from health monitoring", service_id);
         Ok(())
@@ -701,6 +788,122 @@ impl HealthMonitor for HealthMonitorImpl {
     }
 }
 
+/// Find the allowlist entry for a script check's full command line
+///
+/// The command and arguments must match an entry exactly, and a working
+/// directory, if given, must be the entry's.
+fn ensure_script_allowed<'a>(
+    config: &'a ScriptHealthCheckConfig,
+    command: &str,
+    args: &[String],
+    working_dir: Option<&str>,
+) -> Result<&'a AllowedScript> {
+    if !config.enabled {
+        return Err(anyhow::anyhow!("Script health checks are disabled"));
+    }
+
+    config
+        .allowed_scripts
+        .iter()
+        .find(|allowed| {
+            allowed.command == command
+                && allowed.args == args
+                && (working_dir.is_none() || allowed.working_dir.as_deref() == working_dir)
+        })
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Health check script {} {:?} is not on the allowlist",
+                command,
+                args
+            )
+        })
+}
+
+/// Run an allowlisted health check script with a cleared environment
+///
+/// Exit code 0 is healthy. The exit code, duration and truncated output are
+/// added to `details`; a script still running at the timeout is killed.
+async fn run_script_check(
+    config: &ScriptHealthCheckConfig,
+    command: &str,
+    args: &[String],
+    working_dir: Option<&str>,
+    details: &mut HashMap<String, String>,
+) -> Result<(HealthStatus, Option<String>)> {
+    // Run exactly what the operator allowlisted
+    let script = ensure_script_allowed(config, command, args, working_dir)?;
+
+    let mut cmd = Command::new(&script.command);
+    cmd.args(&script.args)
+        .env_clear()
+        .envs(&config.environment)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    if let Some(dir) = &script.working_dir {
+        cmd.current_dir(dir);
+    }
+
+    let started = Instant::now();
+    let child = cmd.spawn()?;
+    let script_timeout = Duration::from_secs(config.timeout as u64);
+    let output = timeout(script_timeout, child.wait_with_output()).await;
+    details.insert(
+        "duration_ms".to_string(),
+        started.elapsed().as_millis().to_string(),
+    );
+
+    // Dropping the timed-out future kills the script
+    let Ok(output) = output else {
+        return Ok((
+            HealthStatus::Timeout,
+            Some(format!(
+                "Script timed out after {}s and was killed",
+                config.timeout
+            )),
+        ));
+    };
+    let output = output?;
+
+    let stdout = truncate_output(&output.stdout, config.max_output_bytes);
+    let stderr = truncate_output(&output.stderr, config.max_output_bytes);
+    details.insert("stdout".to_string(), stdout);
+    details.insert("stderr".to_string(), stderr.clone());
+
+    match output.status.code() {
+        Some(0) => {
+            details.insert("exit_code".to_string(), "0".to_string());
+            Ok((HealthStatus::Healthy, None))
+        }
+        Some(code) => {
+            details.insert("exit_code".to_string(), code.to_string());
+            Ok((
+                HealthStatus::Unhealthy,
+                Some(format!("Script exited with code {}: {}", code, stderr)),
+            ))
+        }
+        None => Ok((
+            HealthStatus::Unhealthy,
+            Some("Script was terminated by a signal".to_string()),
+        )),
+    }
+}
+
+/// Lossy UTF-8 of at most `max_bytes` bytes of script output
+fn truncate_output(output: &[u8], max_bytes: usize) -> String {
+    if output.len() <= max_bytes {
+        return String::from_utf8_lossy(output).into_owned();
+    }
+
+    format!(
+        "{}... [{} bytes truncated]",
+        String::from_utf8_lossy(&output[..max_bytes]),
+        output.len() - max_bytes
+    )
+}
+
 impl Clone for HealthMonitorImpl {
     fn clone(&self) -> Self {
         Self {
@@ -713,6 +916,7 @@ impl Clone for HealthMonitorImpl {
             task_handles: Arc::clone(&self.task_handles),
             stats: Arc::clone(&self.stats),
             is_running: Arc::clone(&self.is_running),
+            outlier_detector: Arc::clone(&self.outlier_detector),
         }
     }
 }
@@ -782,12 +986,140 @@ mod tests {
         assert!(scheduler.should_mark_healthy());
     }
 
+    /// Fails with a fixed exit code and enough stderr to be truncated
+    const FAILING_SCRIPT: &str = "echo 'health check failed: dependency unreachable' >&2; exit 2";
+
+    fn allowed(command: &str, args: &[&str], working_dir: Option<&str>) -> AllowedScript {
+        AllowedScript {
+            command: command.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            working_dir: working_dir.map(str::to_string),
+        }
+    }
+
+    fn script_config(timeout: u32) -> ScriptHealthCheckConfig {
+        ScriptHealthCheckConfig {
+            enabled: true,
+            allowed_scripts: vec![
+                allowed("/usr/bin/env", &[], None),
+                allowed("/bin/sh", &["-c", FAILING_SCRIPT], None),
+                allowed("/bin/sleep", &["10"], None),
+                allowed("/bin/pwd", &[], Some("/tmp")),
+            ],
+            timeout,
+            max_output_bytes: 64,
+            ..Default::default()
+        }
+    }
+
+    async fn run_script(
+        config: &ScriptHealthCheckConfig,
+        command: &str,
+        args: &[&str],
+        working_dir: Option<&str>,
+    ) -> (
+        Result<(HealthStatus, Option<String>)>,
+        HashMap<String, String>,
+    ) {
+        let mut details = HashMap::new();
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        let result = run_script_check(config, command, &args, working_dir, &mut details).await;
+        (result, details)
+    }
+
+    #[tokio::test]
+    async fn test_script_check_success_runs_with_restricted_environment() {
+        std::env::set_var("SERVICE_DISCOVERY_SECRET", "leaked");
+        let (result, details) = run_script(&script_config(5), "/usr/bin/env", &[], None).await;
+
+        let (status, error) = result.unwrap();
+        assert_eq!(status, HealthStatus::Healthy);
+        assert_eq!(error, None);
+        assert_eq!(details["exit_code"], "0");
+        assert_eq!(details["stdout"], "PATH=/usr/bin:/bin\n");
+        assert!(details.contains_key("duration_ms"));
+    }
+
+    #[tokio::test]
+    async fn test_script_check_nonzero_exit_is_unhealthy() {
+        let config = ScriptHealthCheckConfig {
+            max_output_bytes: 16,
+            ..script_config(5)
+        };
+        let (result, details) = run_script(&config, "/bin/sh", &["-c", FAILING_SCRIPT], None).await;
+
+        let (status, error) = result.unwrap();
+        assert_eq!(status, HealthStatus::Unhealthy);
+        assert!(error.unwrap().starts_with("Script exited with code 2"));
+        assert_eq!(details["exit_code"], "2");
+        let (kept, truncated) = details["stderr"].split_once("... [").unwrap();
+        assert_eq!(kept.len(), 16);
+        assert!(truncated.ends_with(" bytes truncated]"));
+    }
+
+    #[tokio::test]
+    async fn test_script_check_timeout_kills_script() {
+        let started = Instant::now();
+        let (result, details) = run_script(&script_config(1), "/bin/sleep", &["10"], None).await;
+
+        let (status, error) = result.unwrap();
+        assert_eq!(status, HealthStatus::Timeout);
+        assert!(error.unwrap().contains("timed out"));
+        assert!(!details.contains_key("exit_code"));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_script_check_runs_in_allowlisted_working_dir() {
+        let config = script_config(5);
+        let (result, details) = run_script(&config, "/bin/pwd", &[], None).await;
+        assert_eq!(result.unwrap().0, HealthStatus::Healthy);
+        assert_eq!(details["stdout"], "/tmp\n");
+
+        let (result, _) = run_script(&config, "/bin/pwd", &[], Some("/tmp")).await;
+        assert_eq!(result.unwrap().0, HealthStatus::Healthy);
+    }
+
+    #[tokio::test]
+    async fn test_script_check_rejects_command_lines_off_the_allowlist() {
+        let config = script_config(5);
+        for (command, args, working_dir) in [
+            ("/bin/bash", vec![], None),
+            // Registrations cannot change the arguments or the directory
+            ("/bin/sleep", vec!["1"], None),
+            ("/bin/sh", vec!["-c", "exit 0"], None),
+            ("/usr/bin/env", vec!["SECRET=1", "/bin/pwd"], None),
+            ("/bin/pwd", vec![], Some("/")),
+        ] {
+            let (result, details) = run_script(&config, command, &args, working_dir).await;
+            assert!(
+                result
+                    .unwrap_err()
+                    .to_string()
+                    .contains("not on the allowlist"),
+                "{} {:?} should be rejected",
+                command,
+                args
+            );
+            assert!(details.is_empty());
+        }
+
+        let disabled = ScriptHealthCheckConfig {
+            enabled: false,
+            ..script_config(5)
+        };
+        let (result, details) = run_script(&disabled, "/usr/bin/env", &[], None).await;
+        assert!(result.unwrap_err().to_string().contains("disabled"));
+        assert!(details.is_empty());
+    }
+
     #[test]
     fn test_health_monitoring_stats() {
         let stats = HealthMonitoringStats {
             total_services: 10,
             healthy_services: 8,
             unhealthy_services: 2,
+            ejected_services: 1,
             total_health_checks: 1000,
             avg_response_time_ms: 150.5,
             error_rate: 0.1,
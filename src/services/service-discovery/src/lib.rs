@@ -52,23 +52,31 @@
 use thiserror::Error;
 
 pub mod config;
+pub mod dns;
+pub mod grpc_health;
 pub mod handlers;
 pub mod health;
 pub mod load_balancer;
 pub mod models;
+pub mod outlier;
 pub mod registry;
+pub mod watch;
 
 // Re-export commonly used types
 pub use config::{Args, ServiceDiscoveryConfig};
+pub use dns::DnsResponder;
 pub use health::{HealthMonitor, HealthMonitorImpl, HealthMonitoringStats};
 pub use load_balancer::{LoadBalancer, LoadBalancerImpl};
+pub use outlier::{OutlierDetector, OutlierEvent, OutlierStatus};
 pub use models::{
     CircuitBreakerConfig, HealthCheckConfig, HealthCheckResult, HealthCheckType,
     HealthCheckTypeConfig, HealthStatus, LoadBalancerStats, LoadBalancingStrategy,
-    RegisterServiceRequest, ServiceDiscoveryQuery, ServiceDiscoveryResponse, ServiceInstance,
-    ServiceRegistration, ServiceStatistics, ServiceStatus, UpdateServiceRequest,
+    RegisterServiceRequest, ServiceDiscoveryQuery, ServiceDiscoveryResponse, ServiceFilter,
+    ServiceInstance, ServiceRegistration, ServiceStatistics, ServiceStatus, UpdateServiceRequest,
+    DEFAULT_NAMESPACE,
 };
 pub use registry::{ServiceRegistry, ServiceRegistryImpl};
+pub use watch::{ServiceChangeEvent, ServiceChangeStream, ServiceWatchHub};
 
 /// Service Discovery library errors
 #[derive(Error, Debug)]
@@ -273,9 +281,11 @@ pub mod prelude {
         models::{
             HealthCheckConfig, HealthCheckResult, HealthStatus, LoadBalancingStrategy,
             RegisterServiceRequest, ServiceDiscoveryQuery, ServiceDiscoveryResponse,
-            ServiceInstance, ServiceRegistration, ServiceStatus, UpdateServiceRequest,
+            ServiceFilter, ServiceInstance, ServiceRegistration, ServiceStatus,
+            UpdateServiceRequest,
         },
         registry::{ServiceRegistry, ServiceRegistryImpl},
+        watch::{ServiceChangeEvent, ServiceChangeStream},
         utils, Result, ServiceDiscoveryError,
     };
 }
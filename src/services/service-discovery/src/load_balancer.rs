@@ -1,15 +1,17 @@
 //! Load Balancer Module
 //!
 //! Provides various load balancing strategies for distributing requests across service instances.
-//! Supports round-robin, least connections, weighted round-robin, consistent hash, random, and IP hash strategies.
+//! Supports round-robin, least connections, weighted round-robin, weighted least connections,
+//! consistent hash, random, and IP hash strategies.
 
 use crate::config::ServiceDiscoveryConfig;
 use crate::models::{LoadBalancerStats, LoadBalancingStrategy, ResponseTimeStats, ServiceInstance};
+use crate::outlier::OutlierDetector;
 
 use anyhow::Result;
 use async_trait::async_trait;
 use dashmap::DashMap;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::hash::{Hash, Hasher};
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
@@ -153,6 +155,48 @@ impl ConnectionInfo {
     }
 }
 
+/// Active connection slot on a selected instance
+///
+/// The instance's active connection count is decremented when the guard is dropped.
+/// Callers that use guards should report request outcomes with [`ConnectionGuard::record`]
+/// rather than [`LoadBalancer::record_request`], which also decrements the count.
+pub struct ConnectionGuard {
+    instance: ServiceInstance,
+    connection_info: Arc<DashMap<Uuid, ConnectionInfo>>,
+    outlier_detector: Option<Arc<OutlierDetector>>,
+    started_at: Instant,
+}
+
+impl ConnectionGuard {
+    /// The selected service instance
+    pub fn instance(&self) -> &ServiceInstance {
+        &self.instance
+    }
+
+    /// Record the request outcome and release the connection
+    pub async fn record(self, success: bool) {
+        let response_time = self.started_at.elapsed();
+        if let Some(info) = self.connection_info.get(&self.instance.id) {
+            info.record_request(response_time, success).await;
+        }
+        if let Some(detector) = &self.outlier_detector {
+            detector.record_outcome(self.instance.id, success);
+        }
+    }
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        if let Some(info) = self.connection_info.get(&self.instance.id) {
+            let _ = info.active_connections.fetch_update(
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+                |connections| Some(connections.saturating_sub(1)),
+            );
+        }
+    }
+}
+
 /// Round-robin counter for services
 #[derive(Debug)]
 struct RoundRobinCounter {
@@ -229,58 +273,95 @@ impl WeightedRoundRobinState {
     }
 }
 
+/// Size of the `u64` hash space, 2^64
+const HASH_SPACE: f64 = 18_446_744_073_709_551_616.0;
+
 /// Consistent hash ring for consistent hashing
 #[derive(Debug)]
 struct ConsistentHashRing {
     /// Virtual nodes on the ring
-    ring: RwLock<std::collections::BTreeMap<u64, Uuid>>,
+    ring: RwLock<RingState>,
 
     /// Number of virtual nodes per instance
     virtual_nodes: usize,
 }
 
+/// Ring contents, rebuilt only when the instance set changes
+#[derive(Debug, Default)]
+struct RingState {
+    /// Virtual node hashes mapped to their instance
+    nodes: BTreeMap<u64, Uuid>,
+
+    /// Sorted IDs of the instances on the ring
+    members: Vec<Uuid>,
+
+    /// Share of the hash space owned by each instance
+    distribution: HashMap<Uuid, f64>,
+
+    /// Share of the hash space that changed owner on the last rebuild
+    remapped_fraction: Option<f64>,
+}
+
 impl ConsistentHashRing {
     fn new(virtual_nodes: usize) -> Self {
         Self {
-            ring: RwLock::new(std::collections::BTreeMap::new()),
+            ring: RwLock::new(RingState::default()),
             virtual_nodes,
         }
     }
 
-    /// Update the hash ring with new instances
-    async fn update(&self, instances: &[ServiceInstance]) {
+    /// Update the hash ring with new instances, returning the share of keys
+    /// that remapped if a populated ring changed
+    async fn update(&self, instances: &[ServiceInstance]) -> Option<f64> {
+        let mut members: Vec<Uuid> = instances.iter().map(|instance| instance.id).collect();
+        members.sort_unstable();
+        members.dedup();
+
         let mut ring = self.ring.write().await;
-        ring.clear();
+        if ring.members == members {
+            return None;
+        }
 
-        for instance in instances {
+        let mut nodes = BTreeMap::new();
+        for instance_id in &members {
             for i in 0..self.virtual_nodes {
-                let key = format!("{}:{}", instance.id, i);
+                let key = format!("{}:{}", instance_id, i);
                 let hash = self.hash(&key);
-                ring.insert(hash, instance.id);
+                nodes.insert(hash, *instance_id);
             }
         }
+
+        let remapped_fraction = if ring.nodes.is_empty() {
+            None
+        } else {
+            Some(remapped_fraction(&ring.nodes, &nodes))
+        };
+
+        ring.distribution = ring_distribution(&nodes);
+        ring.nodes = nodes;
+        ring.members = members;
+        if remapped_fraction.is_some() {
+            ring.remapped_fraction = remapped_fraction;
+        }
+
+        remapped_fraction
     }
 
     /// Find the instance responsible for a given key
     async fn find(&self, key: &str, instances: &[ServiceInstance]) -> Option<ServiceInstance> {
         let ring = self.ring.read().await;
-        if ring.is_empty() {
-            return None;
-        }
-
-        let hash = self.hash(key);
-
-        // Find the first node clockwise from the hash
-        let instance_id = ring
-            .range(hash..)
-            .next()
-            .or_else(|| ring.iter().next())
-            .map(|(_, id)| *id)?;
+        let instance_id = ring_owner(&ring.nodes, self.hash(key))?;
 
         // Find the instance with this ID
         instances.iter().find(|i| i.id == instance_id).cloned()
     }
 
+    /// Current share of keys per instance and the share remapped by the last change
+    async fn distribution(&self) -> (HashMap<Uuid, f64>, Option<f64>) {
+        let ring = self.ring.read().await;
+        (ring.distribution.clone(), ring.remapped_fraction)
+    }
+
     /// Hash function for consistent hashing
     fn hash(&self, key: &str) -> u64 {
         let mut hasher = std::collections::hash_map::DefaultHasher::new();
@@ -289,6 +370,65 @@ impl ConsistentHashRing {
     }
 }
 
+/// Instance owning `hash`: the first node clockwise from it
+fn ring_owner(nodes: &BTreeMap<u64, Uuid>, hash: u64) -> Option<Uuid> {
+    nodes
+        .range(hash..)
+        .next()
+        .or_else(|| nodes.iter().next())
+        .map(|(_, id)| *id)
+}
+
+/// Length of the arc `(previous, hash]`, wrapping around the ring
+fn arc_length(previous: u64, hash: u64) -> f64 {
+    match hash.wrapping_sub(previous) {
+        // A single point owns the whole ring
+        0 => HASH_SPACE,
+        length => length as f64,
+    }
+}
+
+/// Share of the hash space each instance owns
+fn ring_distribution(nodes: &BTreeMap<u64, Uuid>) -> HashMap<Uuid, f64> {
+    let mut distribution = HashMap::new();
+    let Some((&last, _)) = nodes.iter().next_back() else {
+        return distribution;
+    };
+
+    let mut previous = last;
+    for (&hash, instance_id) in nodes {
+        *distribution.entry(*instance_id).or_insert(0.0) += arc_length(previous, hash) / HASH_SPACE;
+        previous = hash;
+    }
+
+    distribution
+}
+
+/// Share of the hash space whose owner differs between two rings
+///
+/// Between consecutive node hashes of either ring both owners are constant,
+/// so comparing owners at each boundary measures the remapped arcs exactly.
+fn remapped_fraction(before: &BTreeMap<u64, Uuid>, after: &BTreeMap<u64, Uuid>) -> f64 {
+    let mut boundaries: Vec<u64> = before.keys().chain(after.keys()).copied().collect();
+    boundaries.sort_unstable();
+    boundaries.dedup();
+
+    let Some(&last) = boundaries.last() else {
+        return 0.0;
+    };
+
+    let mut remapped = 0.0;
+    let mut previous = last;
+    for &hash in &boundaries {
+        if ring_owner(before, hash) != ring_owner(after, hash) {
+            remapped += arc_length(previous, hash);
+        }
+        previous = hash;
+    }
+
+    remapped / HASH_SPACE
+}
+
 /// Load balancer implementation
 pub struct LoadBalancerImpl {
     /// Configuration
@@ -304,10 +444,16 @@ pub struct LoadBalancerImpl {
     weighted_rr_state: Arc<DashMap<String, WeightedRoundRobinState>>,
 
     /// Consistent hash rings per service
-    hash_rings: Arc<DashMap<String, ConsistentHashRing>>,
+    hash_rings: Arc<DashMap<String, Arc<ConsistentHashRing>>>,
 
     /// Service statistics
     service_stats: Arc<DashMap<String, RwLock<LoadBalancerStats>>>,
+
+    /// Last seen weight per service instance
+    instance_weights: Arc<DashMap<Uuid, u32>>,
+
+    /// Outlier detector fed with request outcomes
+    outlier_detector: Option<Arc<OutlierDetector>>,
 }
 
 impl LoadBalancerImpl {
@@ -320,9 +466,52 @@ impl LoadBalancerImpl {
             weighted_rr_state: Arc::new(DashMap::new()),
             hash_rings: Arc::new(DashMap::new()),
             service_stats: Arc::new(DashMap::new()),
+            instance_weights: Arc::new(DashMap::new()),
+            outlier_detector: None,
+        }
+    }
+
+    /// Report request outcomes to an outlier detector and skip instances it ejects
+    pub fn with_outlier_detector(mut self, outlier_detector: Arc<OutlierDetector>) -> Self {
+        self.outlier_detector = Some(outlier_detector);
+        self
+    }
+
+    /// Whether outlier detection lets an instance receive traffic
+    fn admits(&self, instance_id: Uuid) -> bool {
+        match &self.outlier_detector {
+            Some(detector) => detector.is_available(instance_id),
+            None => true,
         }
     }
 
+    /// Select an instance and hold a connection slot on it until the guard is dropped
+    pub async fn acquire_instance(
+        &self,
+        service_name: &str,
+        instances: &[ServiceInstance],
+        strategy: LoadBalancingStrategy,
+        client_key: Option<&str>,
+    ) -> Result<Option<ConnectionGuard>> {
+        let selected = self
+            .select_instance(service_name, instances, strategy, client_key)
+            .await?;
+
+        Ok(selected.map(|instance| ConnectionGuard {
+            instance,
+            connection_info: Arc::clone(&self.connection_info),
+            outlier_detector: self.outlier_detector.clone(),
+            started_at: Instant::now(),
+        }))
+    }
+
+    fn active_connections(&self, instance_id: Uuid) -> u64 {
+        self.connection_info
+            .get(&instance_id)
+            .map(|info| info.active_connections.load(Ordering::Relaxed))
+            .unwrap_or(0)
+    }
+
     /// Select instance using round-robin strategy
     fn select_round_robin(
         &self,
@@ -367,6 +556,39 @@ impl LoadBalancerImpl {
         Some(selected.clone())
     }
 
+    /// Select instance using weighted least connections strategy
+    ///
+    /// Picks the instance with the lowest active connections per unit of weight,
+    /// preferring the higher weight on ties.
+    fn select_weighted_least_connections(
+        &self,
+        instances: &[ServiceInstance],
+    ) -> Option<ServiceInstance> {
+        let mut selected: Option<(&ServiceInstance, u64)> = None;
+
+        for instance in instances {
+            let connections = self.active_connections(instance.id);
+            let weight = u64::from(instance.weight.max(1));
+
+            let better = match selected {
+                None => true,
+                Some((best, best_connections)) => {
+                    let best_weight = u64::from(best.weight.max(1));
+                    // Compare connections / weight without floating point
+                    let load = connections * best_weight;
+                    let best_load = best_connections * weight;
+                    load < best_load || (load == best_load && weight > best_weight)
+                }
+            };
+
+            if better {
+                selected = Some((instance, connections));
+            }
+        }
+
+        selected.map(|(instance, _)| instance.clone())
+    }
+
     /// Select instance using weighted round-robin strategy
     async fn select_weighted_round_robin(
         &self,
@@ -401,15 +623,24 @@ impl LoadBalancerImpl {
             .load_balancer
             .strategies
             .consistent_hash
-            .virtual_nodes as usize;
+            .virtual_nodes_for(service_name) as usize;
 
+        // Clone the ring out so no map shard stays locked across awaits
         let ring = self
             .hash_rings
             .entry(service_name.to_string())
-            .or_insert_with(|| ConsistentHashRing::new(virtual_nodes));
+            .or_insert_with(|| Arc::new(ConsistentHashRing::new(virtual_nodes)))
+            .clone();
 
         // Update ring with current instances
-        ring.update(instances).await;
+        if let Some(remapped) = ring.update(instances).await {
+            info!(
+                "Consistent hash ring for {} rebalanced across {} instances, {:.1}% of keys remapped",
+                service_name,
+                instances.len(),
+                remapped * 100.0
+            );
+        }
 
         // Find instance for key
         ring.find(key, instances).await
@@ -458,8 +689,11 @@ impl LoadBalancerImpl {
                     service_name: service_name.to_string(),
                     total_requests: 0,
                     active_connections: HashMap::new(),
+                    weighted_loads: HashMap::new(),
                     response_times: HashMap::new(),
                     error_rates: HashMap::new(),
+                    ring_distribution: HashMap::new(),
+                    ring_remapped_fraction: None,
                     last_updated: chrono::Utc::now(),
                 })
             });
@@ -468,14 +702,14 @@ impl LoadBalancerImpl {
 
         // Update active connections
         stats.active_connections.clear();
+        stats.weighted_loads.clear();
         for instance in instances {
-            if let Some(info) = self.connection_info.get(&instance.id) {
-                let connections = info.active_connections.load(Ordering::Relaxed);
-                stats
-                    .active_connections
-                    .insert(instance.id, connections as u32);
+            self.instance_weights.insert(instance.id, instance.weight);
+            if self.connection_info.contains_key(&instance.id) {
+                stats.active_connections.insert(instance.id, 0);
             }
         }
+        self.refresh_connection_stats(&mut stats);
 
         // Update response times and error rates
         stats.response_times.clear();
@@ -488,6 +722,17 @@ impl LoadBalancerImpl {
             }
         }
 
+        // Update consistent hash ring distribution
+        let ring = self
+            .hash_rings
+            .get(service_name)
+            .map(|ring| Arc::clone(&ring));
+        if let Some(ring) = ring {
+            let (distribution, remapped_fraction) = ring.distribution().await;
+            stats.ring_distribution = distribution;
+            stats.ring_remapped_fraction = remapped_fraction;
+        }
+
         // Update total requests
         stats.total_requests = self
             .connection_info
@@ -499,6 +744,26 @@ impl LoadBalancerImpl {
 
         Ok(())
     }
+
+    /// Refresh connection counts, which change as guards are released
+    fn refresh_connection_stats(&self, stats: &mut LoadBalancerStats) {
+        let instance_ids: Vec<Uuid> = stats.active_connections.keys().copied().collect();
+        for instance_id in instance_ids {
+            let connections = self.active_connections(instance_id);
+            let weight = self
+                .instance_weights
+                .get(&instance_id)
+                .map(|weight| (*weight).max(1))
+                .unwrap_or(1);
+
+            stats
+                .active_connections
+                .insert(instance_id, connections as u32);
+            stats
+                .weighted_loads
+                .insert(instance_id, connections as f64 / weight as f64);
+        }
+    }
 }
 
 #[async_trait]
@@ -514,10 +779,12 @@ impl LoadBalancer for LoadBalancerImpl {
             return Ok(None);
         }
 
-        // Filter healthy instances only
+        // Filter healthy instances not ejected by outlier detection; draining
+        // instances only finish requests already in flight
         let healthy_instances: Vec<_> = instances
             .iter()
             .filter(|instance| matches!(instance.status, crate::models::ServiceStatus::Healthy))
+            .filter(|instance| self.admits(instance.id))
             .cloned()
             .collect();
 
@@ -540,6 +807,9 @@ impl LoadBalancer for LoadBalancerImpl {
                 self.select_weighted_round_robin(service_name, &healthy_instances)
                     .await
             }
+            LoadBalancingStrategy::WeightedLeastConnections => {
+                self.select_weighted_least_connections(&healthy_instances)
+            }
             LoadBalancingStrategy::ConsistentHash => {
                 if let Some(key) = client_key {
                     self.select_consistent_hash(service_name, &healthy_instances, key)
@@ -569,6 +839,10 @@ impl LoadBalancer for LoadBalancerImpl {
                 .entry(instance.id)
                 .or_insert_with(ConnectionInfo::new);
             info.active_connections.fetch_add(1, Ordering::Relaxed);
+
+            if let Some(detector) = &self.outlier_detector {
+                detector.on_selected(instance.id);
+            }
         }
 
         // Update service statistics
@@ -601,6 +875,10 @@ impl LoadBalancer for LoadBalancerImpl {
         // Record request statistics
         info.record_request(response_time, success).await;
 
+        if let Some(detector) = &self.outlier_detector {
+            detector.record_outcome(service_id, success);
+        }
+
         debug!(
             "Recorded request for service {}: {}ms, success: {}",
             service_id,
@@ -617,8 +895,9 @@ impl LoadBalancer for LoadBalancerImpl {
             .get(service_name)
             .ok_or_else(|| anyhow::anyhow!("No statistics found for service: {}", service_name))?;
 
-        let stats = stats_entry.read().await;
-        Ok(stats.clone())
+        let mut stats = stats_entry.read().await.clone();
+        self.refresh_connection_stats(&mut stats);
+        Ok(stats)
     }
 
     async fn reset_stats(&self, service_name: &str) -> Result<()> {
@@ -661,6 +940,7 @@ fn percentile(sorted_samples: &[u64], percentile: f64) -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::lb_defaults;
     use crate::models::{ServiceProtocol, ServiceStatus};
     use std::collections::HashMap;
 
@@ -669,6 +949,7 @@ mod tests {
             ServiceInstance {
                 id: Uuid::new_v4(),
                 name: "test-service".to_string(),
+                namespace: crate::models::DEFAULT_NAMESPACE.to_string(),
                 version: "1.0.0".to_string(),
                 address: "127.0.0.1".to_string(),
                 port: 8080,
@@ -681,6 +962,7 @@ mod tests {
             ServiceInstance {
                 id: Uuid::new_v4(),
                 name: "test-service".to_string(),
+                namespace: crate::models::DEFAULT_NAMESPACE.to_string(),
                 version: "1.0.0".to_string(),
                 address: "127.0.0.1".to_string(),
                 port: 8081,
@@ -744,6 +1026,219 @@ mod tests {
         assert_ne!(result1.as_ref().unwrap().id, result2.as_ref().unwrap().id);
     }
 
+    #[tokio::test]
+    async fn test_weighted_least_connections_follows_weights() {
+        let lb = LoadBalancerImpl::new(Arc::new(ServiceDiscoveryConfig::default()));
+        let instances = create_test_instances();
+
+        // Hold every connection open: 30 selections split 1:2 by weight
+        let mut guards = Vec::new();
+        for _ in 0..30 {
+            let guard = lb
+                .acquire_instance(
+                    "test-service",
+                    &instances,
+                    LoadBalancingStrategy::WeightedLeastConnections,
+                    None,
+                )
+                .await
+                .unwrap()
+                .unwrap();
+            guards.push(guard);
+        }
+
+        let stats = lb.get_stats("test-service").await.unwrap();
+        assert_eq!(stats.active_connections[&instances[0].id], 10);
+        assert_eq!(stats.active_connections[&instances[1].id], 20);
+        assert_eq!(stats.weighted_loads[&instances[0].id], 0.1);
+        assert_eq!(stats.weighted_loads[&instances[1].id], 0.1);
+
+        drop(guards);
+        let stats = lb.get_stats("test-service").await.unwrap();
+        assert_eq!(stats.active_connections[&instances[0].id], 0);
+        assert_eq!(stats.active_connections[&instances[1].id], 0);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_weighted_least_connections_under_concurrent_load() {
+        let lb = Arc::new(LoadBalancerImpl::new(Arc::new(
+            ServiceDiscoveryConfig::default(),
+        )));
+        let mut instances = create_test_instances();
+        instances[1].weight = 300;
+        let heavy_id = instances[1].id;
+
+        let mut tasks = Vec::new();
+        for _ in 0..50 {
+            let lb = Arc::clone(&lb);
+            let instances = instances.clone();
+            tasks.push(tokio::spawn(async move {
+                let mut heavy_selections = 0;
+                for _ in 0..20 {
+                    let guard = lb
+                        .acquire_instance(
+                            "test-service",
+                            &instances,
+                            LoadBalancingStrategy::WeightedLeastConnections,
+                            None,
+                        )
+                        .await
+                        .unwrap()
+                        .unwrap();
+                    if guard.instance().id == heavy_id {
+                        heavy_selections += 1;
+                    }
+                    tokio::time::sleep(Duration::from_millis(2)).await;
+                    guard.record(true).await;
+                }
+                heavy_selections
+            }));
+        }
+
+        let mut heavy_selections = 0;
+        for task in tasks {
+            heavy_selections += task.await.unwrap();
+        }
+
+        // Weight 300 vs 100 should receive about 75% of 1000 requests
+        let heavy_share = heavy_selections as f64 / 1000.0;
+        assert!(
+            (0.65..=0.85).contains(&heavy_share),
+            "heavy instance share was {}",
+            heavy_share
+        );
+
+        let stats = lb.get_stats("test-service").await.unwrap();
+        assert!(stats.active_connections.values().all(|&count| count == 0));
+    }
+
+    #[tokio::test]
+    async fn test_outlier_ejected_instances_are_skipped() {
+        let config = Arc::new(ServiceDiscoveryConfig::default());
+        let detector = Arc::new(OutlierDetector::new(
+            config.registry.health_checks.outlier_detection.clone(),
+        ));
+        let lb = LoadBalancerImpl::new(config).with_outlier_detector(Arc::clone(&detector));
+        let instances = create_test_instances();
+
+        for _ in 0..10 {
+            detector.record_outcome(instances[0].id, false);
+        }
+
+        for _ in 0..4 {
+            let selected = lb
+                .select_instance(
+                    "test-service",
+                    &instances,
+                    LoadBalancingStrategy::RoundRobin,
+                    None,
+                )
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(selected.id, instances[1].id);
+        }
+    }
+
+    fn hash_test_instances(count: usize) -> Vec<ServiceInstance> {
+        let template = create_test_instances().remove(0);
+        (0..count)
+            .map(|_| ServiceInstance {
+                id: Uuid::new_v4(),
+                ..template.clone()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_adding_instance_remaps_proportional_share_of_keys() {
+        let ring = ConsistentHashRing::new(lb_defaults::DEFAULT_VIRTUAL_NODES as usize);
+        let instances = hash_test_instances(10);
+        let (existing, _) = instances.split_at(9);
+        let added_id = instances[9].id;
+
+        assert_eq!(ring.update(existing).await, None);
+        let keys: Vec<String> = (0..10_000).map(|i| format!("client-{}", i)).collect();
+        let mut before = Vec::new();
+        for key in &keys {
+            before.push(ring.find(key, existing).await.unwrap().id);
+        }
+
+        // Adding a 10th instance should move about 1/10 of the keyspace
+        let remapped = ring.update(&instances).await.unwrap();
+        assert!(
+            (0.07..=0.13).contains(&remapped),
+            "remapped fraction was {}",
+            remapped
+        );
+        assert_eq!(ring.update(&instances).await, None);
+
+        // Sampled keys agree, and every moved key moved to the new instance
+        let mut moved = 0;
+        for (key, previous_id) in keys.iter().zip(&before) {
+            let owner = ring.find(key, &instances).await.unwrap().id;
+            if owner != *previous_id {
+                assert_eq!(owner, added_id);
+                moved += 1;
+            }
+        }
+        let sampled = moved as f64 / keys.len() as f64;
+        assert!(
+            (sampled - remapped).abs() < 0.02,
+            "sampled {} vs computed {}",
+            sampled,
+            remapped
+        );
+
+        let (distribution, last_remapped) = ring.distribution().await;
+        assert_eq!(last_remapped, Some(remapped));
+        assert!((distribution[&added_id] - remapped).abs() < 1e-9);
+        assert!((distribution.values().sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_virtual_nodes_are_tunable_per_service() {
+        let mut config = ServiceDiscoveryConfig::default();
+        config
+            .load_balancer
+            .strategies
+            .consistent_hash
+            .service_virtual_nodes
+            .insert("session-service".to_string(), 500);
+        let lb = LoadBalancerImpl::new(Arc::new(config));
+        let instances = create_test_instances();
+
+        for service_name in ["session-service", "test-service"] {
+            lb.select_instance(
+                service_name,
+                &instances,
+                LoadBalancingStrategy::ConsistentHash,
+                Some("client-1"),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        }
+
+        let ring_size = |service_name: &str| {
+            lb.hash_rings
+                .get(service_name)
+                .unwrap()
+                .ring
+                .try_read()
+                .unwrap()
+                .nodes
+                .len()
+        };
+        assert_eq!(ring_size("session-service"), 1000);
+        assert_eq!(ring_size("test-service"), 300);
+
+        let stats = lb.get_stats("session-service").await.unwrap();
+        assert_eq!(stats.ring_distribution.len(), 2);
+        assert!((stats.ring_distribution.values().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert_eq!(stats.ring_remapped_fraction, None);
+    }
+
     #[tokio::test]
     async fn test_connection_info_stats() {
         let info = ConnectionInfo::new();
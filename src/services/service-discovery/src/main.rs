@@ -7,6 +7,7 @@ use anyhow::{Context, Result};
 use clap::Parser;
 use service_discovery::{
     config::{Args, ServiceDiscoveryConfig},
+    dns::DnsResponder,
     handlers::{create_router, AppState},
     health::{HealthMonitor, HealthMonitorImpl},
     load_balancer::{LoadBalancer, LoadBalancerImpl},
@@ -197,7 +198,10 @@ async fn main() -> Result<()> {
     ));
 
     info!("Initializing load balancer...");
-    let load_balancer = Arc::new(LoadBalancerImpl::new(Arc::clone(&config)));
+    let load_balancer = Arc::new(
+        LoadBalancerImpl::new(Arc::clone(&config))
+            .with_outlier_detector(health_monitor.outlier_detector()),
+    );
 
     // Start health monitoring if enabled
     if config.registry.health_checks.enabled {
@@ -208,6 +212,19 @@ async fn main() -> Result<()> {
             .context("Failed to start health monitoring")?;
     }
 
+    // Start the DNS SRV responder if enabled
+    if config.dns.enabled {
+        let dns_responder = Arc::new(DnsResponder::new(
+            config.dns.clone(),
+            Arc::clone(&registry) as Arc<dyn ServiceRegistry>,
+        ));
+        tokio::spawn(async move {
+            if let Err(e) = dns_responder.serve().await {
+                warn!("DNS SRV responder stopped: {:#}", e);
+            }
+        });
+    }
+
     // Create application state
     let app_state = service_discovery::handlers::AppState {
         config: Arc::clone(&config),
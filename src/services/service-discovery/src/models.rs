@@ -9,6 +9,13 @@ use std::collections::HashMap;
 use uuid::Uuid;
 use validator::Validate;
 
+/// Namespace of instances registered without one
+pub const DEFAULT_NAMESPACE: &str = "default";
+
+fn default_namespace() -> String {
+    DEFAULT_NAMESPACE.to_string()
+}
+
 /// Service registration information
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct ServiceRegistration {
@@ -19,6 +26,11 @@ pub struct ServiceRegistration {
     #[validate(length(min = 1, max = 100))]
     pub name: String,
 
+    /// Namespace grouping related instances (e.g., per environment)
+    #[serde(default = "default_namespace")]
+    #[validate(length(min = 1, max = 100))]
+    pub namespace: String,
+
     /// Service version using semantic versioning
     #[validate(length(min = 1, max = 50))]
     pub version: String,
@@ -91,6 +103,8 @@ pub enum ServiceStatus {
     Expired,
     /// Service is in maintenance mode
     Maintenance,
+    /// Service is deregistering; in-flight requests finish but no new ones are routed to it
+    Draining,
 }
 
 /// Health check configuration
@@ -142,7 +156,10 @@ pub enum HealthCheckTypeConfig {
     },
     Tcp,
     Grpc {
-        service_name: String,
+        /// Service queried with `grpc.health.v1.Health/Check`; the configured
+        /// default is used when unset
+        #[serde(default)]
+        service_name: Option<String>,
     },
     Script {
         command: String,
@@ -213,6 +230,10 @@ pub struct ServiceDiscoveryQuery {
     #[validate(length(min = 1, max = 100))]
     pub service_name: String,
 
+    /// Only return instances in this namespace
+    #[validate(length(min = 1, max = 100))]
+    pub namespace: Option<String>,
+
     /// Version constraint
     pub version: Option<String>,
 
@@ -237,6 +258,7 @@ pub enum LoadBalancingStrategy {
     RoundRobin,
     LeastConnections,
     WeightedRoundRobin,
+    WeightedLeastConnections,
     ConsistentHash,
     Random,
     IpHash,
@@ -270,6 +292,10 @@ pub struct ServiceInstance {
     /// Service name
     pub name: String,
 
+    /// Service namespace
+    #[serde(default = "default_namespace")]
+    pub namespace: String,
+
     /// Service version
     pub version: String,
 
@@ -339,12 +365,27 @@ pub struct LoadBalancerStats {
     /// Active connections per instance
     pub active_connections: HashMap<Uuid, u32>,
 
+    /// Active connections divided by instance weight, as used by
+    /// weighted-least-connections
+    #[serde(default)]
+    pub weighted_loads: HashMap<Uuid, f64>,
+
     /// Response time percentiles per instance
     pub response_times: HashMap<Uuid, ResponseTimeStats>,
 
     /// Error rate per instance
     pub error_rates: HashMap<Uuid, f64>,
 
+    /// Estimated share of consistent-hash keys owned by each instance,
+    /// from the arcs of the hash ring it covers
+    #[serde(default)]
+    pub ring_distribution: HashMap<Uuid, f64>,
+
+    /// Share of consistent-hash keys that moved to another instance when
+    /// the instance set last changed
+    #[serde(default)]
+    pub ring_remapped_fraction: Option<f64>,
+
     /// Last updated timestamp
     pub last_updated: DateTime<Utc>,
 }
@@ -522,6 +563,9 @@ pub struct RegisterServiceRequest {
     #[validate(length(min = 1, max = 100))]
     pub name: String,
 
+    #[validate(length(min = 1, max = 100))]
+    pub namespace: Option<String>,
+
     #[validate(length(min = 1, max = 50))]
     pub version: String,
 
@@ -548,6 +592,53 @@ pub struct RegisterServiceRequest {
     pub circuit_breaker: Option<CircuitBreakerConfig>,
 }
 
+/// Selects registered instances for bulk operations such as deregistration
+///
+/// Every criterion that is set must match; labels must all be present in the
+/// instance's metadata with the same values.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ServiceFilter {
+    /// Service name
+    pub service_name: Option<String>,
+
+    /// Exact version tag
+    pub version: Option<String>,
+
+    /// Namespace
+    pub namespace: Option<String>,
+
+    /// Metadata labels
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+}
+
+impl ServiceFilter {
+    /// Whether the filter sets no criteria and so would match every instance
+    pub fn is_empty(&self) -> bool {
+        self.service_name.is_none()
+            && self.version.is_none()
+            && self.namespace.is_none()
+            && self.labels.is_empty()
+    }
+
+    /// Whether `service` meets every criterion of the filter
+    pub fn matches(&self, service: &ServiceRegistration) -> bool {
+        self.service_name.iter().all(|name| *name == service.name)
+            && self
+                .version
+                .iter()
+                .all(|version| *version == service.version)
+            && self
+                .namespace
+                .iter()
+                .all(|namespace| *namespace == service.namespace)
+            && self
+                .labels
+                .iter()
+                .all(|(key, value)| service.metadata.get(key) == Some(value))
+    }
+}
+
 /// Service heartbeat request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HeartbeatRequest {
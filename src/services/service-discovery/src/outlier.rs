@@ -0,0 +1,429 @@
+//! Outlier Detection Module
+//!
+//! Tracks request outcomes per service instance, as reported by the load balancer,
+//! and ejects instances whose failure rate exceeds a threshold even when their
+//! health endpoint still passes. Ejections back off exponentially on repeats, and
+//! ejected instances return through a half-open state that only admits a limited
+//! number of probe requests before full reinstatement.
+
+use crate::config::OutlierDetectionConfig;
+
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Outlier state of a service instance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutlierStatus {
+    /// Receiving normal traffic
+    Active,
+    /// Ejected from load balancing
+    Ejected,
+    /// Receiving limited probe traffic after an ejection
+    HalfOpen,
+}
+
+/// State change that the health monitor must apply to the registry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutlierEvent {
+    /// Instance was ejected for the given duration
+    Ejected {
+        instance_id: Uuid,
+        duration: Duration,
+    },
+    /// Ejection expired and the instance accepts probe traffic
+    HalfOpened { instance_id: Uuid },
+    /// Probes succeeded and the instance receives full traffic again
+    Reinstated { instance_id: Uuid },
+}
+
+#[derive(Debug, Clone, Copy)]
+enum State {
+    Active,
+    Ejected {
+        until: Instant,
+    },
+    HalfOpen {
+        probes_in_flight: u32,
+        probes_succeeded: u32,
+    },
+}
+
+/// Outlier tracking for a single instance
+#[derive(Debug)]
+struct InstanceOutliers {
+    state: State,
+
+    /// Recent request outcomes, `true` for success
+    outcomes: VecDeque<(Instant, bool)>,
+
+    /// Consecutive ejections, used for exponential backoff
+    ejection_count: u32,
+
+    /// When the instance was last reinstated
+    reinstated_at: Option<Instant>,
+}
+
+impl InstanceOutliers {
+    fn new() -> Self {
+        Self {
+            state: State::Active,
+            outcomes: VecDeque::new(),
+            ejection_count: 0,
+            reinstated_at: None,
+        }
+    }
+}
+
+/// Outlier detector shared by the load balancer and the health monitor
+#[derive(Debug)]
+pub struct OutlierDetector {
+    config: OutlierDetectionConfig,
+    instances: DashMap<Uuid, InstanceOutliers>,
+    events: Mutex<Vec<OutlierEvent>>,
+}
+
+impl OutlierDetector {
+    /// Create a new outlier detector
+    pub fn new(config: OutlierDetectionConfig) -> Self {
+        Self {
+            config,
+            instances: DashMap::new(),
+            events: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Record the outcome of a request sent to an instance
+    pub fn record_outcome(&self, instance_id: Uuid, success: bool) {
+        self.record_outcome_at(instance_id, success, Instant::now());
+    }
+
+    /// Whether the load balancer may send a request to an instance
+    pub fn is_available(&self, instance_id: Uuid) -> bool {
+        if !self.config.enabled {
+            return true;
+        }
+
+        match self.instances.get(&instance_id).map(|entry| entry.state) {
+            None | Some(State::Active) => true,
+            Some(State::Ejected { .. }) => false,
+            Some(State::HalfOpen {
+                probes_in_flight, ..
+            }) => probes_in_flight < self.config.half_open_max_requests,
+        }
+    }
+
+    /// Note that the load balancer selected an instance
+    pub fn on_selected(&self, instance_id: Uuid) {
+        if let Some(mut entry) = self.instances.get_mut(&instance_id) {
+            if let State::HalfOpen {
+                probes_in_flight, ..
+            } = &mut entry.state
+            {
+                *probes_in_flight += 1;
+            }
+        }
+    }
+
+    /// Whether an instance is currently ejected
+    pub fn is_ejected(&self, instance_id: Uuid) -> bool {
+        matches!(
+            self.instances.get(&instance_id).map(|entry| entry.state),
+            Some(State::Ejected { .. })
+        )
+    }
+
+    /// Current outlier status of an instance
+    pub fn status(&self, instance_id: Uuid) -> OutlierStatus {
+        match self.instances.get(&instance_id).map(|entry| entry.state) {
+            None | Some(State::Active) => OutlierStatus::Active,
+            Some(State::Ejected { .. }) => OutlierStatus::Ejected,
+            Some(State::HalfOpen { .. }) => OutlierStatus::HalfOpen,
+        }
+    }
+
+    /// Number of currently ejected instances
+    pub fn ejected_count(&self) -> usize {
+        self.instances
+            .iter()
+            .filter(|entry| matches!(entry.state, State::Ejected { .. }))
+            .count()
+    }
+
+    /// Stop tracking an instance
+    pub fn remove(&self, instance_id: Uuid) {
+        self.instances.remove(&instance_id);
+    }
+
+    /// Move expired ejections to half-open and drain pending events
+    pub fn poll_events(&self) -> Vec<OutlierEvent> {
+        self.poll_events_at(Instant::now())
+    }
+
+    fn record_outcome_at(&self, instance_id: Uuid, success: bool, now: Instant) {
+        if !self.config.enabled {
+            return;
+        }
+
+        let mut entry = self
+            .instances
+            .entry(instance_id)
+            .or_insert_with(InstanceOutliers::new);
+        let instance = entry.value_mut();
+
+        match &mut instance.state {
+            State::Active => {
+                instance.outcomes.push_back((now, success));
+                let window = Duration::from_secs(self.config.window_seconds as u64);
+                while let Some((timestamp, _)) = instance.outcomes.front() {
+                    if now.duration_since(*timestamp) > window {
+                        instance.outcomes.pop_front();
+                    } else {
+                        break;
+                    }
+                }
+
+                let total = instance.outcomes.len();
+                let failures = instance.outcomes.iter().filter(|(_, ok)| !ok).count();
+                let failure_percent = failures as f64 * 100.0 / total as f64;
+
+                if total >= self.config.min_requests as usize
+                    && failure_percent >= self.config.failure_threshold_percent
+                {
+                    let duration = self.eject(instance_id, instance, now);
+                    warn!(
+                        "Ejecting instance {} for {:?}: {:.1}% of {} recent requests failed",
+                        instance_id, duration, failure_percent, total
+                    );
+                }
+            }
+            State::Ejected { .. } => {}
+            State::HalfOpen {
+                probes_in_flight,
+                probes_succeeded,
+            } => {
+                *probes_in_flight = probes_in_flight.saturating_sub(1);
+
+                if !success {
+                    let duration = self.eject(instance_id, instance, now);
+                    warn!(
+                        "Probe request to half-open instance {} failed, ejecting for {:?}",
+                        instance_id, duration
+                    );
+                } else {
+                    *probes_succeeded += 1;
+                    if *probes_succeeded >= self.config.half_open_max_requests {
+                        instance.state = State::Active;
+                        instance.outcomes.clear();
+                        instance.reinstated_at = Some(now);
+                        self.push_event(OutlierEvent::Reinstated { instance_id });
+                        info!(
+                            "Reinstated instance {} after successful probes",
+                            instance_id
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Eject an instance, returning the ejection duration
+    fn eject(&self, instance_id: Uuid, instance: &mut InstanceOutliers, now: Instant) -> Duration {
+        let max_ejection = Duration::from_secs(self.config.max_ejection_seconds as u64);
+
+        // Repeat offenders back off exponentially; a long stable period resets the count
+        let repeat = match instance.state {
+            State::HalfOpen { .. } => true,
+            _ => matches!(
+                instance.reinstated_at,
+                Some(reinstated_at) if now.duration_since(reinstated_at) < max_ejection
+            ),
+        };
+        instance.ejection_count = if repeat {
+            instance.ejection_count + 1
+        } else {
+            1
+        };
+
+        let duration = self.ejection_duration(instance.ejection_count);
+        instance.state = State::Ejected {
+            until: now + duration,
+        };
+        instance.outcomes.clear();
+        self.push_event(OutlierEvent::Ejected {
+            instance_id,
+            duration,
+        });
+
+        duration
+    }
+
+    /// Ejection duration for the nth consecutive ejection
+    fn ejection_duration(&self, ejection_count: u32) -> Duration {
+        let multiplier = 2u64.saturating_pow(ejection_count.saturating_sub(1));
+        let seconds = (self.config.base_ejection_seconds as u64)
+            .saturating_mul(multiplier)
+            .min(self.config.max_ejection_seconds as u64);
+        Duration::from_secs(seconds)
+    }
+
+    fn poll_events_at(&self, now: Instant) -> Vec<OutlierEvent> {
+        for mut entry in self.instances.iter_mut() {
+            if let State::Ejected { until } = entry.state {
+                if now >= until {
+                    entry.state = State::HalfOpen {
+                        probes_in_flight: 0,
+                        probes_succeeded: 0,
+                    };
+                    let instance_id = *entry.key();
+                    self.push_event(OutlierEvent::HalfOpened { instance_id });
+                }
+            }
+        }
+
+        std::mem::take(&mut *self.events.lock().unwrap())
+    }
+
+    fn push_event(&self, event: OutlierEvent) {
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> OutlierDetectionConfig {
+        OutlierDetectionConfig {
+            enabled: true,
+            failure_threshold_percent: 50.0,
+            min_requests: 4,
+            window_seconds: 10,
+            base_ejection_seconds: 30,
+            max_ejection_seconds: 100,
+            half_open_max_requests: 2,
+        }
+    }
+
+    fn record_failures(detector: &OutlierDetector, id: Uuid, count: usize, now: Instant) {
+        for _ in 0..count {
+            detector.record_outcome_at(id, false, now);
+        }
+    }
+
+    #[test]
+    fn test_ejects_after_failure_threshold() {
+        let detector = OutlierDetector::new(test_config());
+        let id = Uuid::new_v4();
+        let now = Instant::now();
+
+        detector.record_outcome_at(id, true, now);
+        detector.record_outcome_at(id, true, now);
+        record_failures(&detector, id, 1, now);
+        assert!(detector.is_available(id));
+
+        // 2 of 4 requests failed
+        record_failures(&detector, id, 1, now);
+        assert!(!detector.is_available(id));
+        assert_eq!(detector.status(id), OutlierStatus::Ejected);
+        assert_eq!(
+            detector.poll_events_at(now),
+            vec![OutlierEvent::Ejected {
+                instance_id: id,
+                duration: Duration::from_secs(30),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_old_outcomes_leave_the_window() {
+        let detector = OutlierDetector::new(test_config());
+        let id = Uuid::new_v4();
+        let start = Instant::now();
+
+        record_failures(&detector, id, 3, start);
+        let later = start + Duration::from_secs(11);
+        for _ in 0..3 {
+            detector.record_outcome_at(id, true, later);
+        }
+        record_failures(&detector, id, 1, later);
+
+        assert_eq!(detector.status(id), OutlierStatus::Active);
+    }
+
+    #[test]
+    fn test_half_open_limits_probes_and_reinstates() {
+        let detector = OutlierDetector::new(test_config());
+        let id = Uuid::new_v4();
+        let now = Instant::now();
+
+        record_failures(&detector, id, 4, now);
+        let expired = now + Duration::from_secs(30);
+        let events = detector.poll_events_at(expired);
+        assert_eq!(
+            events.last(),
+            Some(&OutlierEvent::HalfOpened { instance_id: id })
+        );
+
+        // Only two probes may be in flight
+        assert!(detector.is_available(id));
+        detector.on_selected(id);
+        detector.on_selected(id);
+        assert!(!detector.is_available(id));
+
+        detector.record_outcome_at(id, true, expired);
+        assert_eq!(detector.status(id), OutlierStatus::HalfOpen);
+        assert!(detector.is_available(id));
+
+        detector.record_outcome_at(id, true, expired);
+        assert_eq!(detector.status(id), OutlierStatus::Active);
+        assert_eq!(
+            detector.poll_events_at(expired),
+            vec![OutlierEvent::Reinstated { instance_id: id }]
+        );
+    }
+
+    #[test]
+    fn test_repeated_ejections_back_off_exponentially() {
+        let detector = OutlierDetector::new(test_config());
+        let id = Uuid::new_v4();
+        let mut now = Instant::now();
+        let mut durations = Vec::new();
+
+        record_failures(&detector, id, 4, now);
+        for _ in 0..3 {
+            let events = detector.poll_events_at(now);
+            durations.extend(events.into_iter().filter_map(|event| match event {
+                OutlierEvent::Ejected { duration, .. } => Some(duration.as_secs()),
+                _ => None,
+            }));
+
+            // Let the ejection expire, then fail the first probe
+            now += Duration::from_secs(durations.last().copied().unwrap());
+            detector.poll_events_at(now);
+            detector.on_selected(id);
+            detector.record_outcome_at(id, false, now);
+        }
+
+        assert_eq!(durations, vec![30, 60, 100]);
+    }
+
+    #[test]
+    fn test_disabled_detector_never_ejects() {
+        let detector = OutlierDetector::new(OutlierDetectionConfig {
+            enabled: false,
+            ..test_config()
+        });
+        let id = Uuid::new_v4();
+
+        record_failures(&detector, id, 10, Instant::now());
+
+        assert!(detector.is_available(id));
+        assert!(detector.poll_events().is_empty());
+    }
+}
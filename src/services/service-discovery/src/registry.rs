@@ -6,14 +6,16 @@
 use crate::config::ServiceDiscoveryConfig;
 use crate::models::{
     HealthCheckResult, HealthStatus, LoadBalancingStrategy, RegisterServiceRequest,
-    ServiceDiscoveryQuery, ServiceDiscoveryResponse, ServiceInstance, ServiceRegistration,
-    ServiceStatistics, ServiceStatus, UpdateServiceRequest,
+    ServiceDiscoveryQuery, ServiceDiscoveryResponse, ServiceFilter, ServiceInstance,
+    ServiceRegistration, ServiceStatistics, ServiceStatus, UpdateServiceRequest, DEFAULT_NAMESPACE,
 };
+use crate::watch::{ServiceChangeEvent, ServiceChangeStream, ServiceWatchHub};
 
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use chrono::{Duration, Utc};
 use dashmap::DashMap;
+use parking_lot::RwLock;
 
 use sqlx::{Pool, Postgres};
 
@@ -36,6 +38,9 @@ pub trait ServiceRegistry: Send + Sync {
     /// Deregister a service
     async fn deregister_service(&self, service_id: Uuid) -> Result<()>;
 
+    /// Deregister every instance matching the filter, returning how many were removed
+    async fn deregister_by(&self, filter: ServiceFilter) -> Result<usize>;
+
     /// Update service information
     async fn update_service(&self, service_id: Uuid, request: UpdateServiceRequest) -> Result<()>;
 
@@ -60,6 +65,9 @@ pub trait ServiceRegistry: Send + Sync {
     /// Health check operations
     async fn record_health_check(&self, result: HealthCheckResult) -> Result<()>;
     async fn get_health_status(&self, service_id: Uuid) -> Result<HealthStatus>;
+
+    /// Watch registrations, deregistrations and status changes for a service name
+    fn watch(&self, service_name: &str) -> ServiceChangeStream;
 }
 
 /// PostgreSQL and Redis-backed service registry implementation
@@ -79,11 +87,18 @@ pub struct ServiceRegistryImpl {
     /// Service name to IDs mapping cache
     name_cache: Arc<DashMap<String, Vec<Uuid>>>,
 
+    /// Held for reading while discovery walks the caches and for writing while
+    /// instances are added or removed, so a query never sees a partial change
+    membership: Arc<RwLock<()>>,
+
     /// Health check results cache
     health_cache: Arc<DashMap<Uuid, HealthCheckResult>>,
 
     /// Service statistics cache
     stats_cache: Arc<DashMap<Uuid, ServiceStatistics>>,
+
+    /// Change notifications for watchers
+    watch_hub: Arc<ServiceWatchHub>,
 }
 
 impl ServiceRegistryImpl {
@@ -93,14 +108,22 @@ impl ServiceRegistryImpl {
         redis_pool: deadpool_redis::Pool,
         config: Arc<ServiceDiscoveryConfig>,
     ) -> Self {
+        let watch_config = &config.registry.discovery.watch;
+        let watch_hub = ServiceWatchHub::new(watch_config.buffer_size).with_redis(
+            redis_pool.clone(),
+            format!("{}{}", config.database.redis.prefix, watch_config.channel),
+        );
+
         Self {
             db_pool,
             redis_pool,
             config,
             service_cache: Arc::new(DashMap::new()),
             name_cache: Arc::new(DashMap::new()),
+            membership: Arc::new(RwLock::new(())),
             health_cache: Arc::new(DashMap::new()),
             stats_cache: Arc::new(DashMap::new()),
+            watch_hub: Arc::new(watch_hub),
         }
     }
 
@@ -115,6 +138,14 @@ impl ServiceRegistryImpl {
         // Start cleanup tasks
         self.start_cleanup_tasks().await?;
 
+        // Keep the caches in step with changes made on other discovery nodes
+        let registry = self.clone();
+        self.watch_hub
+            .start_relay(&self.config.database.redis.url, move |event| {
+                registry.apply_remote_change(event)
+            })
+            .context("Failed to start service change relay")?;
+
         info!("Service registry initialized successfully");
         Ok(())
     }
@@ -127,6 +158,7 @@ impl ServiceRegistryImpl {
             CREATE TABLE IF NOT EXISTS services (
                 id UUID PRIMARY KEY,
                 name VARCHAR(100) NOT NULL,
+                namespace VARCHAR(100) NOT NULL DEFAULT 'default',
                 version VARCHAR(50) NOT NULL,
                 address VARCHAR(255) NOT NULL,
                 port INTEGER NOT NULL CHECK (port > 0 AND port <= 65535),
@@ -145,8 +177,11 @@ impl ServiceRegistryImpl {
                 updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
             )
             "#,
+            "ALTER TABLE services ADD COLUMN IF NOT EXISTS namespace VARCHAR(100) NOT NULL DEFAULT 'default'",
+            "ALTER TABLE services ADD COLUMN IF NOT EXISTS draining_since TIMESTAMPTZ",
             // Indexes for services table
             "CREATE INDEX IF NOT EXISTS idx_services_name ON services(name)",
+            "CREATE INDEX IF NOT EXISTS idx_services_namespace_name ON services(namespace, name)",
             "CREATE INDEX IF NOT EXISTS idx_services_status ON services(status)",
             "CREATE INDEX IF NOT EXISTS idx_services_expires_at ON services(expires_at)",
             "CREATE INDEX IF NOT EXISTS idx_services_name_status ON services(name, status)",
@@ -272,6 +307,7 @@ impl ServiceRegistryImpl {
             "stopping" => ServiceStatus::Stopping,
             "expired" => ServiceStatus::Expired,
             "maintenance" => ServiceStatus::Maintenance,
+            "draining" => ServiceStatus::Draining,
             _ => ServiceStatus::Unhealthy,
         };
 
@@ -302,6 +338,9 @@ impl ServiceRegistryImpl {
                 if let Err(e) = cleanup_registry.cleanup_expired_services().await {
                     error!("Failed to cleanup expired services: {}", e);
                 }
+                if let Err(e) = cleanup_registry.cleanup_stale_drains().await {
+                    error!("Failed to cleanup stale drains: {}", e);
+                }
             }
         });
 
@@ -342,6 +381,37 @@ impl ServiceRegistryImpl {
         Ok(())
     }
 
+    /// Remove instances left draining past the grace period, e.g. when the
+    /// node that started the drain restarted before its timer fired
+    async fn cleanup_stale_drains(&self) -> Result<()> {
+        let service_ids: Vec<Uuid> = sqlx::query_scalar(
+            "DELETE FROM services WHERE status = 'draining' \
+             AND COALESCE(draining_since, updated_at) <= NOW() - make_interval(secs => $1) \
+             RETURNING id",
+        )
+        .bind(self.config.registry.registration.drain_grace_period as f64)
+        .fetch_all(&self.db_pool)
+        .await
+        .context("Failed to cleanup stale drains")?;
+
+        if service_ids.is_empty() {
+            return Ok(());
+        }
+
+        for service in self.uncache_registrations(&service_ids) {
+            self.watch_hub.publish(ServiceChangeEvent::Removed {
+                service_id: service.id,
+                service_name: service.name,
+            });
+        }
+
+        info!(
+            "Removed {} services left draining past the grace period",
+            service_ids.len()
+        );
+        Ok(())
+    }
+
     /// Refresh in-memory cache from database
     async fn refresh_cache(&self) -> Result<()> {
         // Clear caches
@@ -377,6 +447,151 @@ impl ServiceRegistryImpl {
         Ok(())
     }
 
+    /// Add a new registration to the caches and notify watchers
+    fn cache_registration(&self, registration: ServiceRegistration) {
+        self.insert_into_caches(registration.clone());
+
+        self.watch_hub.publish(ServiceChangeEvent::Added {
+            service: registration,
+        });
+    }
+
+    fn insert_into_caches(&self, registration: ServiceRegistration) {
+        let _membership = self.membership.write();
+        let service_id = registration.id;
+        let service_name = registration.name.clone();
+        if self
+            .service_cache
+            .insert(service_id, registration)
+            .is_none()
+        {
+            self.name_cache
+                .entry(service_name)
+                .or_default()
+                .push(service_id);
+        }
+    }
+
+    /// Mirror a change made on another discovery node in the local caches
+    fn apply_remote_change(&self, event: &ServiceChangeEvent) {
+        match event {
+            ServiceChangeEvent::Added { service } => self.insert_into_caches(service.clone()),
+            ServiceChangeEvent::Removed { service_id, .. } => {
+                self.uncache_registrations(&[*service_id]);
+            }
+            ServiceChangeEvent::StatusChanged {
+                service_id,
+                current,
+                ..
+            } => {
+                if let Some(mut service) = self.service_cache.get_mut(service_id) {
+                    service.status = current.clone();
+                }
+            }
+            ServiceChangeEvent::Lagged { .. } => {}
+        }
+    }
+
+    /// IDs of the cached instances matching `filter`
+    pub fn matching_service_ids(&self, filter: &ServiceFilter) -> Vec<Uuid> {
+        let _membership = self.membership.read();
+        self.service_cache
+            .iter()
+            .filter(|entry| filter.matches(entry.value()))
+            .map(|entry| *entry.key())
+            .collect()
+    }
+
+    /// Remove instances from the caches in one step, returning the removed registrations
+    fn uncache_registrations(&self, service_ids: &[Uuid]) -> Vec<ServiceRegistration> {
+        let removed: Vec<ServiceRegistration> = {
+            let _membership = self.membership.write();
+            service_ids
+                .iter()
+                .filter_map(|service_id| self.service_cache.remove(service_id))
+                .map(|(_, service)| {
+                    if let Some(mut name_entry) = self.name_cache.get_mut(&service.name) {
+                        name_entry.retain(|&id| id != service.id);
+                    }
+                    service
+                })
+                .collect()
+        };
+
+        for service in &removed {
+            self.health_cache.remove(&service.id);
+            self.stats_cache.remove(&service.id);
+        }
+
+        removed
+    }
+
+    /// Stop routing new requests to instances and remove them once the drain
+    /// grace period has passed. Instances already draining keep their original
+    /// deadline; returns the instances whose drain started here.
+    fn begin_draining(&self, service_ids: &[Uuid]) -> Vec<Uuid> {
+        let mut started = Vec::new();
+        for &service_id in service_ids {
+            let status_change = match self.service_cache.get_mut(&service_id) {
+                Some(service) if service.status == ServiceStatus::Draining => continue,
+                Some(mut service) => {
+                    let event = ServiceChangeEvent::StatusChanged {
+                        service_id,
+                        service_name: service.name.clone(),
+                        previous: service.status.clone(),
+                        current: ServiceStatus::Draining,
+                    };
+                    service.status = ServiceStatus::Draining;
+                    Some(event)
+                }
+                // Not cached here, but the database row still needs removing
+                None => None,
+            };
+            started.push(service_id);
+
+            // Publish after the cache entry guard is released
+            if let Some(event) = status_change {
+                self.watch_hub.publish(event);
+            }
+        }
+
+        if started.is_empty() {
+            return started;
+        }
+
+        let registry = self.clone();
+        let service_ids = started.clone();
+        let grace_period = std::time::Duration::from_secs(
+            self.config.registry.registration.drain_grace_period as u64,
+        );
+        tokio::spawn(async move {
+            tokio::time::sleep(grace_period).await;
+            registry.finish_draining(&service_ids).await;
+        });
+        started
+    }
+
+    /// Remove drained instances from rotation, then from the database
+    async fn finish_draining(&self, service_ids: &[Uuid]) {
+        // Leave the caches first so a slow database cannot extend the drain
+        for service in self.uncache_registrations(service_ids) {
+            self.watch_hub.publish(ServiceChangeEvent::Removed {
+                service_id: service.id,
+                service_name: service.name,
+            });
+        }
+
+        if let Err(e) = sqlx::query("DELETE FROM services WHERE id = ANY($1)")
+            .bind(service_ids)
+            .execute(&self.db_pool)
+            .await
+        {
+            error!("Failed to remove drained services from database: {}", e);
+        }
+
+        info!("Removed {} drained services", service_ids.len());
+    }
+
     /// Apply load balancing strategy to service list
     fn apply_load_balancing_strategy(
         &self,
@@ -395,6 +610,11 @@ impl ServiceRegistryImpl {
                 services.sort_by_key(|s| s.weight);
                 services
             }
+            LoadBalancingStrategy::WeightedLeastConnections => {
+                // Connection counts live in the load balancer; prefer higher weights here
+                services.sort_by_key(|s| std::cmp::Reverse(s.weight));
+                services
+            }
             LoadBalancingStrategy::WeightedRoundRobin => {
                 // For weighted round-robin, higher weight services should appear more often
                 let mut weighted_services = Vec::new();
@@ -457,6 +677,7 @@ impl ServiceRegistryImpl {
         ServiceInstance {
             id: service.id,
             name: service.name.clone(),
+            namespace: service.namespace.clone(),
             version: service.version.clone(),
             address: service.address.clone(),
             port: service.port,
@@ -503,6 +724,9 @@ impl ServiceRegistry for ServiceRegistryImpl {
         let service_registration = ServiceRegistration {
             id: service_id,
             name: request.name.clone(),
+            namespace: request
+                .namespace
+                .unwrap_or_else(|| DEFAULT_NAMESPACE.to_string()),
             version: request.version,
             address: request.address,
             port: request.port,
@@ -518,14 +742,7 @@ impl ServiceRegistry for ServiceRegistryImpl {
             circuit_breaker: request.circuit_breaker,
         };
 
-        // Update caches
-        self.service_cache.insert(service_id, service_registration);
-
-        let mut name_entry = self
-            .name_cache
-            .entry(request.name.clone())
-            .or_insert_with(Vec::new);
-        name_entry.push(service_id);
+        self.cache_registration(service_registration);
 
         // Initialize statistics
         // TODO: Replace with actual SQLX query
@@ -542,6 +759,26 @@ impl ServiceRegistry for ServiceRegistryImpl {
     }
 
     async fn deregister_service(&self, service_id: Uuid) -> Result<()> {
+        if self.config.registry.registration.drain_grace_period > 0 {
+            let result = sqlx::query(
+                "UPDATE services SET status = 'draining', \
+                 draining_since = COALESCE(draining_since, NOW()), updated_at = NOW() \
+                 WHERE id = $1",
+            )
+            .bind(service_id)
+            .execute(&self.db_pool)
+            .await
+            .context("Failed to mark service as draining")?;
+
+            if result.rows_affected() == 0 {
+                return Err(anyhow::anyhow!("Service not found: {}", service_id));
+            }
+
+            self.begin_draining(&[service_id]);
+            info!("Draining service {} before deregistration", service_id);
+            return Ok(());
+        }
+
         // Remove from database
         // TODO: Replace with actual SQLX query
         let result = sqlx::query("DELETE FROM services WHERE id = $1")
@@ -555,19 +792,71 @@ impl ServiceRegistry for ServiceRegistryImpl {
         }
 
         // Remove from caches
-        if let Some((_, service)) = self.service_cache.remove(&service_id) {
-            if let Some(mut name_entry) = self.name_cache.get_mut(&service.name) {
-                name_entry.retain(|&id| id != service_id);
-            }
+        for service in self.uncache_registrations(&[service_id]) {
+            self.watch_hub.publish(ServiceChangeEvent::Removed {
+                service_id,
+                service_name: service.name,
+            });
         }
 
-        self.health_cache.remove(&service_id);
-        self.stats_cache.remove(&service_id);
-
         info!("Deregistered service {}", service_id);
         Ok(())
     }
 
+    async fn deregister_by(&self, filter: ServiceFilter) -> Result<usize> {
+        if filter.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Refusing to deregister every service: the filter sets no criteria"
+            ));
+        }
+
+        let service_ids = self.matching_service_ids(&filter);
+        if service_ids.is_empty() {
+            return Ok(0);
+        }
+
+        if self.config.registry.registration.drain_grace_period > 0 {
+            sqlx::query(
+                "UPDATE services SET status = 'draining', \
+                 draining_since = COALESCE(draining_since, NOW()), updated_at = NOW() \
+                 WHERE id = ANY($1)",
+            )
+            .bind(&service_ids)
+            .execute(&self.db_pool)
+            .await
+            .context("Failed to mark services as draining")?;
+
+            self.begin_draining(&service_ids);
+            info!(
+                "Draining {} services matching {:?} before deregistration",
+                service_ids.len(),
+                filter
+            );
+            return Ok(service_ids.len());
+        }
+
+        sqlx::query("DELETE FROM services WHERE id = ANY($1)")
+            .bind(&service_ids)
+            .execute(&self.db_pool)
+            .await
+            .context("Failed to deregister services from database")?;
+
+        let removed = self.uncache_registrations(&service_ids);
+        for service in &removed {
+            self.watch_hub.publish(ServiceChangeEvent::Removed {
+                service_id: service.id,
+                service_name: service.name.clone(),
+            });
+        }
+
+        info!(
+            "Deregistered {} services matching {:?}",
+            removed.len(),
+            filter
+        );
+        Ok(removed.len())
+    }
+
     async fn update_service(&self, service_id: Uuid, request: UpdateServiceRequest) -> Result<()> {
         // Build dynamic update query
         let mut query_parts = Vec::new();
@@ -609,9 +898,26 @@ impl ServiceRegistry for ServiceRegistryImpl {
             .context("Failed to update service")?;
 
         // Update cache
+        let mut status_change = None;
         if let Some(mut service) = self.service_cache.get_mut(&service_id) {
             if let Some(status) = request.status {
-                service.status = status;
+                // Health checks and heartbeats must not put a draining instance back in rotation
+                if service.status == ServiceStatus::Draining {
+                    debug!(
+                        "Ignoring status {:?} for draining service {}",
+                        status, service_id
+                    );
+                } else {
+                    if service.status != status {
+                        status_change = Some(ServiceChangeEvent::StatusChanged {
+                            service_id,
+                            service_name: service.name.clone(),
+                            previous: service.status.clone(),
+                            current: status.clone(),
+                        });
+                    }
+                    service.status = status;
+                }
             }
             if let Some(weight) = request.weight {
                 service.weight = weight;
@@ -627,6 +933,11 @@ impl ServiceRegistry for ServiceRegistryImpl {
             }
         }
 
+        // Publish after the cache entry guard is released
+        if let Some(event) = status_change {
+            self.watch_hub.publish(event);
+        }
+
         debug!("Updated service {}", service_id);
         Ok(())
     }
@@ -659,6 +970,7 @@ impl ServiceRegistry for ServiceRegistryImpl {
         let mut matching_services = Vec::new();
 
         // Get services by name from cache
+        let membership = self.membership.read();
         if let Some(service_ids) = self.name_cache.get(&query.service_name) {
             for &service_id in service_ids.iter() {
                 if let Some(service) = self.service_cache.get(&service_id) {
@@ -667,6 +979,12 @@ impl ServiceRegistry for ServiceRegistryImpl {
                         continue;
                     }
 
+                    if let Some(ref namespace) = query.namespace {
+                        if service.namespace != *namespace {
+                            continue;
+                        }
+                    }
+
                     // Version constraint check (simplified)
                     if let Some(ref version_constraint) = query.version {
                         if service.version != *version_constraint {
@@ -691,6 +1009,7 @@ impl ServiceRegistry for ServiceRegistryImpl {
                 }
             }
         }
+        drop(membership);
 
         // Apply load balancing strategy
         let strategy = query
@@ -851,6 +1170,10 @@ impl ServiceRegistry for ServiceRegistryImpl {
             Ok(HealthStatus::Unknown)
         }
     }
+
+    fn watch(&self, service_name: &str) -> ServiceChangeStream {
+        self.watch_hub.subscribe(service_name)
+    }
 }
 
 impl Clone for ServiceRegistryImpl {
@@ -861,8 +1184,10 @@ impl Clone for ServiceRegistryImpl {
             config: Arc::clone(&self.config),
             service_cache: Arc::clone(&self.service_cache),
             name_cache: Arc::clone(&self.name_cache),
+            membership: Arc::clone(&self.membership),
             health_cache: Arc::clone(&self.health_cache),
             stats_cache: Arc::clone(&self.stats_cache),
+            watch_hub: Arc::clone(&self.watch_hub),
         }
     }
 }
@@ -882,4 +1207,231 @@ mod tests {
     fn test_service_conversion() {
         // Test conversion between different service representations
     }
+
+    /// Registry whose pools connect on first use, for tests that stay in the caches
+    fn lazy_registry() -> ServiceRegistryImpl {
+        lazy_registry_with(ServiceDiscoveryConfig::default())
+    }
+
+    fn lazy_registry_with(config: ServiceDiscoveryConfig) -> ServiceRegistryImpl {
+        let config = Arc::new(config);
+        let db_pool = sqlx::postgres::PgPoolOptions::new()
+            .connect_lazy(&config.database.postgres.url)
+            .unwrap();
+        let redis_pool = deadpool_redis::Config::from_url(&config.database.redis.url)
+            .create_pool(Some(deadpool_redis::Runtime::Tokio1))
+            .unwrap();
+
+        ServiceRegistryImpl::new(db_pool, redis_pool, config)
+    }
+
+    #[tokio::test]
+    async fn test_registration_emits_single_added_event() {
+        use futures::StreamExt;
+
+        let registry = lazy_registry();
+        let mut changes = registry.watch("user-service");
+
+        let service_id = Uuid::new_v4();
+        registry.cache_registration(ServiceRegistration {
+            id: service_id,
+            name: "user-service".to_string(),
+            namespace: DEFAULT_NAMESPACE.to_string(),
+            version: "1.0.0".to_string(),
+            address: "10.0.0.1".to_string(),
+            port: 8080,
+            protocol: ServiceProtocol::Http,
+            health_check: None,
+            metadata: std::collections::HashMap::new(),
+            weight: 100,
+            status: ServiceStatus::Healthy,
+            registered_at: Utc::now(),
+            last_heartbeat: None,
+            ttl: 30,
+            dependencies: Vec::new(),
+            circuit_breaker: None,
+        });
+
+        match changes.next().await {
+            Some(ServiceChangeEvent::Added { service }) => assert_eq!(service.id, service_id),
+            other => panic!("expected Added event, got {:?}", other),
+        }
+
+        let next = tokio::time::timeout(std::time::Duration::from_millis(50), changes.next()).await;
+        assert!(next.is_err(), "unexpected second event: {:?}", next);
+    }
+
+    #[tokio::test]
+    async fn test_filter_matches_only_its_namespace() {
+        let registry = lazy_registry();
+        let registration = |namespace: &str| ServiceRegistration {
+            id: Uuid::new_v4(),
+            name: "user-service".to_string(),
+            namespace: namespace.to_string(),
+            version: "1.0.0".to_string(),
+            address: "10.0.0.1".to_string(),
+            port: 8080,
+            protocol: ServiceProtocol::Http,
+            health_check: None,
+            metadata: std::collections::HashMap::new(),
+            weight: 100,
+            status: ServiceStatus::Healthy,
+            registered_at: Utc::now(),
+            last_heartbeat: None,
+            ttl: 30,
+            dependencies: Vec::new(),
+            circuit_breaker: None,
+        };
+
+        let staging = registration("staging");
+        let production = registration("production");
+        registry.cache_registration(staging.clone());
+        registry.cache_registration(production.clone());
+
+        let filter = ServiceFilter {
+            service_name: Some("user-service".to_string()),
+            namespace: Some("staging".to_string()),
+            ..Default::default()
+        };
+        let matching = registry.matching_service_ids(&filter);
+        assert_eq!(matching, vec![staging.id]);
+
+        let removed = registry.uncache_registrations(&matching);
+        assert_eq!(removed.len(), 1);
+        assert!(registry.service_cache.get(&staging.id).is_none());
+        assert!(registry.service_cache.get(&production.id).is_some());
+        assert!(ServiceFilter::default().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_draining_instance_gets_no_new_requests_until_removed() {
+        use crate::load_balancer::{LoadBalancer, LoadBalancerImpl};
+        use futures::StreamExt;
+
+        let mut config = ServiceDiscoveryConfig::default();
+        config.registry.registration.drain_grace_period = 1;
+        let registry = lazy_registry_with(config.clone());
+        let load_balancer = LoadBalancerImpl::new(Arc::new(config));
+
+        let registration = |address: &str| ServiceRegistration {
+            id: Uuid::new_v4(),
+            name: "user-service".to_string(),
+            namespace: DEFAULT_NAMESPACE.to_string(),
+            version: "1.0.0".to_string(),
+            address: address.to_string(),
+            port: 8080,
+            protocol: ServiceProtocol::Http,
+            health_check: None,
+            metadata: std::collections::HashMap::new(),
+            weight: 100,
+            status: ServiceStatus::Healthy,
+            registered_at: Utc::now(),
+            last_heartbeat: None,
+            ttl: 30,
+            dependencies: Vec::new(),
+            circuit_breaker: None,
+        };
+        let draining = registration("10.0.0.1");
+        let serving = registration("10.0.0.2");
+        registry.cache_registration(draining.clone());
+        registry.cache_registration(serving.clone());
+
+        let mut changes = registry.watch("user-service");
+        assert_eq!(registry.begin_draining(&[draining.id]), vec![draining.id]);
+        match changes.next().await {
+            Some(ServiceChangeEvent::StatusChanged { current, .. }) => {
+                assert_eq!(current, ServiceStatus::Draining)
+            }
+            other => panic!("expected StatusChanged event, got {:?}", other),
+        }
+
+        // Deregistering again keeps the original deadline
+        assert!(registry.begin_draining(&[draining.id]).is_empty());
+
+        let instances: Vec<ServiceInstance> = [draining.id, serving.id]
+            .iter()
+            .map(|id| {
+                registry.service_registration_to_instance(&registry.service_cache.get(id).unwrap())
+            })
+            .collect();
+        assert_eq!(instances[0].status, ServiceStatus::Draining);
+        for _ in 0..10 {
+            let selected = load_balancer
+                .select_instance(
+                    "user-service",
+                    &instances,
+                    LoadBalancingStrategy::RoundRobin,
+                    None,
+                )
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(selected.id, serving.id);
+        }
+
+        // Still registered during the grace period, removed after it
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        assert!(registry.service_cache.contains_key(&draining.id));
+
+        match tokio::time::timeout(std::time::Duration::from_secs(2), changes.next()).await {
+            Ok(Some(ServiceChangeEvent::Removed { service_id, .. })) => {
+                assert_eq!(service_id, draining.id)
+            }
+            other => panic!("expected Removed event, got {:?}", other),
+        }
+        assert!(!registry.service_cache.contains_key(&draining.id));
+        assert!(registry.service_cache.contains_key(&serving.id));
+    }
+
+    #[tokio::test]
+    async fn test_remote_changes_update_local_caches() {
+        let registry = lazy_registry();
+        let service = ServiceRegistration {
+            id: Uuid::new_v4(),
+            name: "user-service".to_string(),
+            namespace: DEFAULT_NAMESPACE.to_string(),
+            version: "1.0.0".to_string(),
+            address: "10.0.0.1".to_string(),
+            port: 8080,
+            protocol: ServiceProtocol::Http,
+            health_check: None,
+            metadata: std::collections::HashMap::new(),
+            weight: 100,
+            status: ServiceStatus::Healthy,
+            registered_at: Utc::now(),
+            last_heartbeat: None,
+            ttl: 30,
+            dependencies: Vec::new(),
+            circuit_breaker: None,
+        };
+        let added = ServiceChangeEvent::Added {
+            service: service.clone(),
+        };
+
+        // A replayed Added event must not list the instance twice
+        registry.apply_remote_change(&added);
+        registry.apply_remote_change(&added);
+        assert_eq!(
+            registry.name_cache.get("user-service").unwrap().clone(),
+            vec![service.id]
+        );
+
+        registry.apply_remote_change(&ServiceChangeEvent::StatusChanged {
+            service_id: service.id,
+            service_name: service.name.clone(),
+            previous: ServiceStatus::Healthy,
+            current: ServiceStatus::Draining,
+        });
+        assert_eq!(
+            registry.service_cache.get(&service.id).unwrap().status,
+            ServiceStatus::Draining
+        );
+
+        registry.apply_remote_change(&ServiceChangeEvent::Removed {
+            service_id: service.id,
+            service_name: service.name.clone(),
+        });
+        assert!(!registry.service_cache.contains_key(&service.id));
+        assert!(registry.name_cache.get("user-service").unwrap().is_empty());
+    }
 }
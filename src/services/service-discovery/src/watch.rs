@@ -0,0 +1,441 @@
+//! Service Watch Module
+//!
+//! Push notifications for service registry changes. Events are fanned out to local
+//! subscribers through a bounded broadcast channel and relayed to the other discovery
+//! nodes over Redis pub/sub, so a watcher connected to any node sees every change.
+//! Outgoing events are published by a single task in the order they happened, and
+//! events from other nodes are applied to the local registry before watchers see them.
+//!
+//! Publishing never waits on subscribers: a subscriber that falls more than the
+//! configured buffer behind skips the missed events and receives a
+//! [`ServiceChangeEvent::Lagged`] marker telling it to re-read the registry.
+
+use crate::models::{ServiceRegistration, ServiceStatus};
+
+use anyhow::{Context, Result};
+use futures::{Stream, StreamExt};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tracing::{debug, error, warn};
+use uuid::Uuid;
+
+/// Delay before reconnecting the Redis relay after a failure
+const RELAY_RECONNECT_DELAY: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Events waiting to be published to Redis before new ones are dropped
+const RELAY_QUEUE_CAPACITY: usize = 1024;
+
+/// Change to the instances registered under a service name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServiceChangeEvent {
+    /// A new instance was registered
+    Added { service: ServiceRegistration },
+
+    /// An instance was deregistered
+    Removed {
+        service_id: Uuid,
+        service_name: String,
+    },
+
+    /// An instance changed health status
+    StatusChanged {
+        service_id: Uuid,
+        service_name: String,
+        previous: ServiceStatus,
+        current: ServiceStatus,
+    },
+
+    /// The subscriber fell behind and missed events; its view must be refreshed
+    Lagged { service_name: String, skipped: u64 },
+}
+
+impl ServiceChangeEvent {
+    /// Name of the service the event belongs to
+    pub fn service_name(&self) -> &str {
+        match self {
+            Self::Added { service } => &service.name,
+            Self::Removed { service_name, .. }
+            | Self::StatusChanged { service_name, .. }
+            | Self::Lagged { service_name, .. } => service_name,
+        }
+    }
+
+    /// Event kind, used as the SSE event name
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Added { .. } => "added",
+            Self::Removed { .. } => "removed",
+            Self::StatusChanged { .. } => "status_changed",
+            Self::Lagged { .. } => "lagged",
+        }
+    }
+}
+
+/// Stream of changes for a single service name
+pub type ServiceChangeStream = Pin<Box<dyn Stream<Item = ServiceChangeEvent> + Send>>;
+
+/// Event as published on the Redis channel
+#[derive(Debug, Serialize, Deserialize)]
+struct ChangeEnvelope {
+    /// Node that published the event
+    origin: Uuid,
+    event: ServiceChangeEvent,
+}
+
+/// Redis pub/sub relay settings
+struct RedisRelay {
+    pool: deadpool_redis::Pool,
+    channel: String,
+    /// Serialized events, published in order by one task
+    outgoing: mpsc::Sender<String>,
+    /// Taken by the publishing task when the relay starts
+    outgoing_rx: Mutex<Option<mpsc::Receiver<String>>>,
+}
+
+/// Fan-out hub for registry change events
+pub struct ServiceWatchHub {
+    /// Identifies events published by this node on the shared channel
+    node_id: Uuid,
+    sender: broadcast::Sender<ServiceChangeEvent>,
+    relay: Option<RedisRelay>,
+}
+
+impl ServiceWatchHub {
+    /// Create a hub that only notifies local subscribers
+    pub fn new(buffer_size: usize) -> Self {
+        let (sender, _) = broadcast::channel(buffer_size);
+        Self {
+            node_id: Uuid::new_v4(),
+            sender,
+            relay: None,
+        }
+    }
+
+    /// Also publish events to other discovery nodes over Redis
+    pub fn with_redis(mut self, pool: deadpool_redis::Pool, channel: String) -> Self {
+        let (outgoing, outgoing_rx) = mpsc::channel(RELAY_QUEUE_CAPACITY);
+        self.relay = Some(RedisRelay {
+            pool,
+            channel,
+            outgoing,
+            outgoing_rx: Mutex::new(Some(outgoing_rx)),
+        });
+        self
+    }
+
+    /// Publish an event to local subscribers and other nodes
+    pub fn publish(&self, event: ServiceChangeEvent) {
+        if let Some(relay) = &self.relay {
+            let envelope = ChangeEnvelope {
+                origin: self.node_id,
+                event: event.clone(),
+            };
+            match serde_json::to_string(&envelope) {
+                Ok(payload) => match relay.outgoing.try_send(payload) {
+                    Ok(()) => {}
+                    Err(TrySendError::Full(_)) => {
+                        warn!("Service change relay queue is full, dropping event")
+                    }
+                    Err(TrySendError::Closed(_)) => {
+                        warn!("Service change relay has stopped, dropping event")
+                    }
+                },
+                Err(e) => error!("Failed to serialize service change event: {}", e),
+            }
+        }
+
+        self.deliver(event);
+    }
+
+    /// Subscribe to changes for a service name
+    pub fn subscribe(&self, service_name: &str) -> ServiceChangeStream {
+        let service_name = service_name.to_string();
+        let receiver = self.sender.subscribe();
+
+        futures::stream::unfold(
+            (receiver, service_name),
+            |(mut receiver, service_name)| async move {
+                loop {
+                    match receiver.recv().await {
+                        Ok(event) if event.service_name() == service_name => {
+                            return Some((event, (receiver, service_name)));
+                        }
+                        Ok(_) => continue,
+                        Err(RecvError::Lagged(skipped)) => {
+                            warn!(
+                                "Watcher for {} lagged behind by {} events",
+                                service_name, skipped
+                            );
+                            let event = ServiceChangeEvent::Lagged {
+                                service_name: service_name.clone(),
+                                skipped,
+                            };
+                            return Some((event, (receiver, service_name)));
+                        }
+                        Err(RecvError::Closed) => return None,
+                    }
+                }
+            },
+        )
+        .boxed()
+    }
+
+    /// Number of active local subscribers
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+
+    /// Publish queued events to other nodes and forward events published by other
+    /// nodes to local subscribers, passing each to `apply` first
+    pub fn start_relay<F>(self: &Arc<Self>, redis_url: &str, apply: F) -> Result<()>
+    where
+        F: Fn(&ServiceChangeEvent) + Send + Sync + 'static,
+    {
+        let Some(relay) = &self.relay else {
+            return Ok(());
+        };
+
+        let client = redis::Client::open(redis_url).context("Invalid Redis URL")?;
+        let Some(mut outgoing) = relay.outgoing_rx.lock().take() else {
+            anyhow::bail!("Service change relay already started");
+        };
+
+        let pool = relay.pool.clone();
+        let channel = relay.channel.clone();
+        tokio::spawn(async move {
+            while let Some(payload) = outgoing.recv().await {
+                if let Err(e) = publish_to_redis(&pool, &channel, payload).await {
+                    warn!("Failed to relay service change event: {}", e);
+                }
+            }
+        });
+
+        let channel = relay.channel.clone();
+        let hub = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = hub.relay_messages(&client, &channel, &apply).await {
+                    warn!("Service change relay disconnected: {}", e);
+                }
+                tokio::time::sleep(RELAY_RECONNECT_DELAY).await;
+            }
+        });
+
+        debug!("Service change relay started on channel {}", relay.channel);
+        Ok(())
+    }
+
+    /// Receive events from the Redis channel until the connection drops
+    async fn relay_messages(
+        &self,
+        client: &redis::Client,
+        channel: &str,
+        apply: &(dyn Fn(&ServiceChangeEvent) + Send + Sync),
+    ) -> Result<()> {
+        let mut pubsub = client
+            .get_async_connection()
+            .await
+            .context("Failed to connect to Redis")?
+            .into_pubsub();
+        pubsub
+            .subscribe(channel)
+            .await
+            .context("Failed to subscribe to service change channel")?;
+
+        let mut messages = pubsub.on_message();
+        while let Some(message) = messages.next().await {
+            match message.get_payload::<String>() {
+                Ok(payload) => self.handle_remote(&payload, apply),
+                Err(e) => warn!("Invalid service change payload: {}", e),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply and deliver an event received from the Redis channel
+    fn handle_remote(&self, payload: &str, apply: &(dyn Fn(&ServiceChangeEvent) + Send + Sync)) {
+        match serde_json::from_str::<ChangeEnvelope>(payload) {
+            // Events from this node were already applied and delivered when published
+            Ok(envelope) if envelope.origin == self.node_id => {}
+            Ok(envelope) => {
+                apply(&envelope.event);
+                self.deliver(envelope.event);
+            }
+            Err(e) => warn!("Failed to decode service change event: {}", e),
+        }
+    }
+
+    fn deliver(&self, event: ServiceChangeEvent) {
+        // Sending only fails when nobody is subscribed
+        let _ = self.sender.send(event);
+    }
+}
+
+async fn publish_to_redis(
+    pool: &deadpool_redis::Pool,
+    channel: &str,
+    payload: String,
+) -> Result<()> {
+    let mut conn = pool
+        .get()
+        .await
+        .context("Failed to get Redis connection from pool")?;
+
+    redis::cmd("PUBLISH")
+        .arg(channel)
+        .arg(payload)
+        .query_async::<_, i64>(&mut *conn)
+        .await
+        .context("Failed to publish service change event")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ServiceProtocol;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    fn registration(name: &str) -> ServiceRegistration {
+        ServiceRegistration {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            namespace: crate::models::DEFAULT_NAMESPACE.to_string(),
+            version: "1.0.0".to_string(),
+            address: "10.0.0.1".to_string(),
+            port: 8080,
+            protocol: ServiceProtocol::Http,
+            health_check: None,
+            metadata: HashMap::new(),
+            weight: 100,
+            status: ServiceStatus::Healthy,
+            registered_at: Utc::now(),
+            last_heartbeat: None,
+            ttl: 30,
+            dependencies: Vec::new(),
+            circuit_breaker: None,
+        }
+    }
+
+    async fn assert_no_event(stream: &mut ServiceChangeStream) {
+        let next = tokio::time::timeout(Duration::from_millis(50), stream.next()).await;
+        assert!(next.is_err(), "unexpected event: {:?}", next);
+    }
+
+    #[tokio::test]
+    async fn test_subscriber_only_receives_its_service() {
+        let hub = ServiceWatchHub::new(16);
+        let mut stream = hub.subscribe("user-service");
+
+        let service = registration("user-service");
+        hub.publish(ServiceChangeEvent::Added {
+            service: registration("billing-service"),
+        });
+        hub.publish(ServiceChangeEvent::Removed {
+            service_id: service.id,
+            service_name: service.name.clone(),
+        });
+
+        assert!(matches!(
+            stream.next().await,
+            Some(ServiceChangeEvent::Removed { service_id, .. }) if service_id == service.id
+        ));
+        assert_no_event(&mut stream).await;
+    }
+
+    #[tokio::test]
+    async fn test_slow_subscriber_lags_without_blocking_publisher() {
+        let hub = ServiceWatchHub::new(4);
+        let mut stream = hub.subscribe("user-service");
+
+        for _ in 0..10 {
+            hub.publish(ServiceChangeEvent::Added {
+                service: registration("user-service"),
+            });
+        }
+
+        assert!(matches!(
+            stream.next().await,
+            Some(ServiceChangeEvent::Lagged { skipped: 6, .. })
+        ));
+        for _ in 0..4 {
+            assert!(matches!(
+                stream.next().await,
+                Some(ServiceChangeEvent::Added { .. })
+            ));
+        }
+        assert_no_event(&mut stream).await;
+    }
+
+    #[tokio::test]
+    async fn test_relay_queues_events_in_publish_order() {
+        let pool = deadpool_redis::Config::from_url("redis://127.0.0.1:6379")
+            .create_pool(Some(deadpool_redis::Runtime::Tokio1))
+            .unwrap();
+        let hub = ServiceWatchHub::new(16).with_redis(pool, "changes".to_string());
+
+        let services: Vec<ServiceRegistration> =
+            (0..5).map(|_| registration("user-service")).collect();
+        for service in &services {
+            hub.publish(ServiceChangeEvent::Added {
+                service: service.clone(),
+            });
+        }
+
+        let relay = hub.relay.as_ref().unwrap();
+        let mut outgoing = relay.outgoing_rx.lock().take().unwrap();
+        for service in &services {
+            let envelope: ChangeEnvelope =
+                serde_json::from_str(&outgoing.recv().await.unwrap()).unwrap();
+            assert_eq!(envelope.origin, hub.node_id);
+            assert!(matches!(
+                envelope.event,
+                ServiceChangeEvent::Added { service: queued } if queued.id == service.id
+            ));
+        }
+        assert!(outgoing.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_remote_events_skip_own_origin() {
+        let hub = ServiceWatchHub::new(16);
+        let mut stream = hub.subscribe("user-service");
+
+        let service_id = Uuid::new_v4();
+        let event = ServiceChangeEvent::StatusChanged {
+            service_id,
+            service_name: "user-service".to_string(),
+            previous: ServiceStatus::Healthy,
+            current: ServiceStatus::Unhealthy,
+        };
+        let own = ChangeEnvelope {
+            origin: hub.node_id,
+            event: event.clone(),
+        };
+        let remote = ChangeEnvelope {
+            origin: Uuid::new_v4(),
+            event,
+        };
+
+        let applied = Mutex::new(Vec::new());
+        let apply = |event: &ServiceChangeEvent| applied.lock().push(event.kind());
+        hub.handle_remote(&serde_json::to_string(&own).unwrap(), &apply);
+        hub.handle_remote(&serde_json::to_string(&remote).unwrap(), &apply);
+        assert_eq!(*applied.lock(), vec!["status_changed"]);
+
+        assert!(matches!(
+            stream.next().await,
+            Some(ServiceChangeEvent::StatusChanged { service_id: id, .. }) if id == service_id
+        ));
+        assert_no_event(&mut stream).await;
+    }
+}
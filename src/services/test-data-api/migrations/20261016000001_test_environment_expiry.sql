@@ -0,0 +1,11 @@
+-- Auto-expiry of test environments past their TTL
+ALTER TYPE environment_status ADD VALUE IF NOT EXISTS 'expiring';
+ALTER TYPE environment_status ADD VALUE IF NOT EXISTS 'expired';
+
+ALTER TABLE test_environments
+    -- Pinned environments are never auto-expired
+    ADD COLUMN IF NOT EXISTS pinned BOOLEAN NOT NULL DEFAULT FALSE,
+    -- When the grace period before teardown started
+    ADD COLUMN IF NOT EXISTS expiring_since TIMESTAMPTZ,
+    -- Status restored if the environment is pulled out of its grace period
+    ADD COLUMN IF NOT EXISTS status_before_expiry environment_status;
@@ -0,0 +1,15 @@
+-- Entities produced by generation jobs that have no dedicated table
+CREATE TABLE IF NOT EXISTS generated_records (
+    id UUID PRIMARY KEY,
+    -- Generation job that produced the record
+    generation_id UUID NOT NULL,
+    -- Data type the record was generated as, e.g. 'workflows'
+    data_type VARCHAR(50) NOT NULL,
+    test_environment VARCHAR(255) NOT NULL,
+    -- Generated payload; references to parent entities (e.g. owner_id) live here
+    data JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_generated_records_generation_id ON generated_records(generation_id);
+CREATE INDEX IF NOT EXISTS idx_generated_records_environment_type ON generated_records(test_environment, data_type);
@@ -12,6 +12,17 @@ use uuid::Uuid;
 
 use crate::models::UserRole;
 
+// ============================================================================
+// Test Data API Scopes
+// ============================================================================
+
+/// List and read test data, environments, and job status.
+pub const SCOPE_READ: &str = "test-data:read";
+/// Create test users and environments and start data generation.
+pub const SCOPE_WRITE: &str = "test-data:write";
+/// Destructive operations: deletions, environment resets, and cleanups.
+pub const SCOPE_ADMIN: &str = "test-data:admin";
+
 // ============================================================================
 // Authentication Service - JWT Token Management
 // ============================================================================
@@ -203,6 +214,38 @@ impl AuthService {
         Ok(false)
     }
 
+    /// Checks a `test-data:*` scope. Scopes are hierarchical: `admin` implies
+    /// `write`, which implies `read`. Granted scopes come from the token's
+    /// permissions plus those of its role.
+    pub fn has_scope(&self, claims: &Claims, required_scope: &str) -> bool {
+        if matches!(claims.role, UserRole::Admin) {
+            return true;
+        }
+
+        let implied_by: &[&str] = match required_scope {
+            SCOPE_READ => &[SCOPE_READ, SCOPE_WRITE, SCOPE_ADMIN],
+            SCOPE_WRITE => &[SCOPE_WRITE, SCOPE_ADMIN],
+            SCOPE_ADMIN => &[SCOPE_ADMIN],
+            other => return claims.permissions.iter().any(|p| p == other || p == "*"),
+        };
+
+        let role_permissions = self.get_role_permissions(&claims.role);
+        claims
+            .permissions
+            .iter()
+            .chain(role_permissions.iter())
+            .any(|permission| permission == "*" || implied_by.contains(&permission.as_str()))
+    }
+
+    /// Scopes granted to `claims`, for reporting in authorization errors.
+    pub fn granted_scopes(&self, claims: &Claims) -> Vec<String> {
+        [SCOPE_READ, SCOPE_WRITE, SCOPE_ADMIN]
+            .into_iter()
+            .filter(|scope| self.has_scope(claims, scope))
+            .map(String::from)
+            .collect()
+    }
+
     pub async fn check_environment_access(&self, claims: &Claims, environment: &str) -> Result<bool> {
         debug!("Checking environment access '{}' for user: {}", environment, claims.username);
 
@@ -350,6 +393,7 @@ impl AuthService {
                 "user:delete".to_string(),
                 "environment:create".to_string(),
                 "environment:reset".to_string(),
+                SCOPE_ADMIN.to_string(),
             ],
             UserRole::Developer => vec![
                 "read".to_string(),
@@ -358,6 +402,7 @@ impl AuthService {
                 "test:create".to_string(),
                 "test:execute".to_string(),
                 "data:generate".to_string(),
+                SCOPE_WRITE.to_string(),
             ],
             UserRole::Tester => vec![
                 "read".to_string(),
@@ -365,13 +410,16 @@ impl AuthService {
                 "test:create".to_string(),
                 "test:execute".to_string(),
                 "test:report".to_string(),
+                SCOPE_WRITE.to_string(),
             ],
             UserRole::User => vec![
                 "read".to_string(),
                 "workflow:execute".to_string(),
+                SCOPE_READ.to_string(),
             ],
             UserRole::Viewer => vec![
                 "read".to_string(),
+                SCOPE_READ.to_string(),
             ],
             UserRole::Guest => vec![
                 "limited_read".to_string(),
@@ -485,6 +533,31 @@ pub fn require_permission(required_permission: &str) -> impl Fn(&Claims) -> bool
     }
 }
 
+/// Scope an authenticated request needs, based on its method and route.
+/// Returns `None` for routes that only require a valid token.
+pub fn required_scope(method: &axum::http::Method, path: &str) -> Option<&'static str> {
+    use axum::http::Method;
+
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    match (method, segments.as_slice()) {
+        (&Method::DELETE, ["api", "test-users", _]) => Some(SCOPE_ADMIN),
+        (&Method::POST, ["api", "environments", _, "reset"]) => Some(SCOPE_ADMIN),
+        // Unpinning lets the environment be auto-expired and its data removed
+        (&Method::DELETE, ["api", "environments", _, "pin"]) => Some(SCOPE_ADMIN),
+        (&Method::POST, ["api", "cleanup"]) => Some(SCOPE_ADMIN),
+        (&Method::DELETE, ["api", "cleanup", _]) => Some(SCOPE_ADMIN),
+        (&Method::POST, ["api", "test-users"]) => Some(SCOPE_WRITE),
+        (&Method::POST, ["api", "environments"]) => Some(SCOPE_WRITE),
+        (&Method::PUT, ["api", "environments", _, "pin"]) => Some(SCOPE_WRITE),
+        (&Method::POST, ["api", "generate-data"]) => Some(SCOPE_WRITE),
+        (&Method::DELETE, ["api", "generate-data", _]) => Some(SCOPE_WRITE),
+        (&Method::GET, ["api", ..]) => Some(SCOPE_READ),
+        (_, ["api", ..]) => Some(SCOPE_ADMIN),
+        _ => None,
+    }
+}
+
 pub fn require_role(required_role: UserRole) -> impl Fn(&Claims) -> bool {
     move |claims: &Claims| {
         matches!(claims.role, UserRole::Admin) || claims.role == required_role
@@ -509,6 +582,9 @@ pub enum AuthError {
     #[error("Permission denied")]
     PermissionDenied,
 
+    #[error("Missing required scope: {0}")]
+    InsufficientScope(String),
+
     #[error("Environment access denied")]
     EnvironmentAccessDenied,
 
@@ -594,4 +670,54 @@ mod tests {
         assert!(auth_service.check_permission(&admin_claims, "delete").await.unwrap());
         assert!(auth_service.check_environment_access(&admin_claims, "any_env").await.unwrap());
     }
+
+    fn claims_with(role: UserRole, permissions: &[&str]) -> Claims {
+        Claims {
+            sub: Uuid::new_v4().to_string(),
+            username: "scoped_user".to_string(),
+            email: "scoped@example.com".to_string(),
+            role,
+            permissions: permissions.iter().map(|p| p.to_string()).collect(),
+            environment: "test".to_string(),
+            exp: (Utc::now() + Duration::hours(1)).timestamp(),
+            iat: Utc::now().timestamp(),
+            jti: Uuid::new_v4().to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scope_hierarchy() {
+        let auth_service = AuthService::new("test_secret_key".to_string()).await.unwrap();
+
+        let reader = claims_with(UserRole::Guest, &[SCOPE_READ]);
+        assert!(auth_service.has_scope(&reader, SCOPE_READ));
+        assert!(!auth_service.has_scope(&reader, SCOPE_WRITE));
+        assert!(!auth_service.has_scope(&reader, SCOPE_ADMIN));
+
+        let operator = claims_with(UserRole::Guest, &[SCOPE_ADMIN]);
+        assert!(auth_service.has_scope(&operator, SCOPE_READ));
+        assert!(auth_service.has_scope(&operator, SCOPE_ADMIN));
+
+        // Role permissions grant scopes too: testers may write but not clean up
+        let tester = claims_with(UserRole::Tester, &["read"]);
+        assert!(auth_service.has_scope(&tester, SCOPE_WRITE));
+        assert!(!auth_service.has_scope(&tester, SCOPE_ADMIN));
+        assert_eq!(auth_service.granted_scopes(&tester), vec![SCOPE_READ, SCOPE_WRITE]);
+    }
+
+    #[test]
+    fn test_required_scope_per_route() {
+        use axum::http::Method;
+
+        assert_eq!(required_scope(&Method::GET, "/api/environments"), Some(SCOPE_READ));
+        assert_eq!(required_scope(&Method::POST, "/api/generate-data"), Some(SCOPE_WRITE));
+        assert_eq!(required_scope(&Method::DELETE, "/api/generate-data/abc"), Some(SCOPE_WRITE));
+        assert_eq!(required_scope(&Method::POST, "/api/cleanup"), Some(SCOPE_ADMIN));
+        assert_eq!(required_scope(&Method::DELETE, "/api/cleanup/abc"), Some(SCOPE_ADMIN));
+        assert_eq!(required_scope(&Method::POST, "/api/environments/abc/reset"), Some(SCOPE_ADMIN));
+        assert_eq!(required_scope(&Method::PUT, "/api/environments/abc/pin"), Some(SCOPE_WRITE));
+        assert_eq!(required_scope(&Method::DELETE, "/api/environments/abc/pin"), Some(SCOPE_ADMIN));
+        assert_eq!(required_scope(&Method::DELETE, "/api/test-users/abc"), Some(SCOPE_ADMIN));
+        assert_eq!(required_scope(&Method::GET, "/health"), None);
+    }
 }
@@ -5,7 +5,13 @@
 use anyhow::{anyhow, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
@@ -35,17 +41,138 @@ struct CleanupJob {
     items_cleaned: i32,
     total_items: i32,
     backup_created: bool,
+    started_at: Option<DateTime<Utc>>,
+    tables_processed: i32,
+    total_tables: i32,
+    current_table: Option<String>,
+    /// Items cleaned by phases that already finished; the running phase reports on top of it
+    phase_base_items: i32,
+    cancel_requested: Arc<AtomicBool>,
+}
+
+impl CleanupJob {
+    fn is_cancel_requested(&self) -> bool {
+        self.cancel_requested.load(Ordering::SeqCst)
+    }
+
+    fn is_finished(&self) -> bool {
+        matches!(
+            self.status,
+            CleanupStatus::Completed | CleanupStatus::Failed | CleanupStatus::Cancelled
+        )
+    }
+
+    fn to_response(&self) -> CleanupResponse {
+        let estimated_remaining_seconds = match (self.started_at, self.is_finished()) {
+            (_, true) => Some(0),
+            (Some(started_at), false) if self.progress > 0 => {
+                let elapsed = (Utc::now() - started_at).num_seconds().max(0);
+                Some(elapsed * (100 - self.progress.min(100)) as i64 / self.progress as i64)
+            }
+            _ => None,
+        };
+
+        CleanupResponse {
+            cleanup_id: self.id,
+            status: format!("{:?}", self.status).to_lowercase(),
+            items_to_cleanup: self.total_items,
+            estimated_duration_seconds: estimated_remaining_seconds.map(|s| s as i32).unwrap_or(300),
+            progress_url: format!("/api/cleanup/{}/status", self.id),
+            progress: CleanupProgress {
+                percent_complete: self.progress,
+                tables_processed: self.tables_processed,
+                total_tables: self.total_tables,
+                current_table: self.current_table.clone(),
+                items_cleaned: self.items_cleaned,
+                estimated_remaining_seconds,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 enum CleanupStatus {
     Pending,
     Running,
+    Cancelling,
     Completed,
     Failed,
     Cancelled,
 }
 
+/// A unit of cleanup work against one backing table or store. Cancellation is
+/// checked between phases and between items within a phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CleanupPhase {
+    Users,
+    Workflows,
+    RedisCache,
+    MongoData,
+    ClickHouseData,
+    Artifacts,
+    Environments,
+}
+
+impl CleanupPhase {
+    /// Phases for a cleanup type, ordered so dependents are removed before what they reference.
+    fn for_cleanup_type(cleanup_type: &CleanupType) -> Vec<CleanupPhase> {
+        match cleanup_type {
+            CleanupType::Users => vec![CleanupPhase::Users],
+            CleanupType::Workflows => vec![CleanupPhase::Workflows],
+            CleanupType::TestData => vec![
+                CleanupPhase::RedisCache,
+                CleanupPhase::MongoData,
+                CleanupPhase::ClickHouseData,
+            ],
+            CleanupType::Environments => vec![CleanupPhase::Environments],
+            CleanupType::Artifacts => vec![CleanupPhase::Artifacts],
+            CleanupType::All => vec![
+                CleanupPhase::Users,
+                CleanupPhase::Workflows,
+                CleanupPhase::RedisCache,
+                CleanupPhase::MongoData,
+                CleanupPhase::ClickHouseData,
+                CleanupPhase::Artifacts,
+                CleanupPhase::Environments,
+            ],
+        }
+    }
+
+    fn table_name(&self) -> &'static str {
+        match self {
+            CleanupPhase::Users => "test_users",
+            CleanupPhase::Workflows => "test_workflows",
+            CleanupPhase::RedisCache => "redis_cache",
+            CleanupPhase::MongoData => "mongodb_documents",
+            CleanupPhase::ClickHouseData => "clickhouse_events",
+            CleanupPhase::Artifacts => "test_artifacts",
+            CleanupPhase::Environments => "test_environments",
+        }
+    }
+}
+
+/// Outcome of one pass of TTL-based environment expiry
+#[derive(Debug, Clone, Default)]
+pub struct ExpirySweep {
+    /// Environments that passed their TTL and entered the grace period
+    pub expiring: Vec<Uuid>,
+    /// Environments pulled back out of the grace period, e.g. because they were pinned
+    pub restored: Vec<Uuid>,
+    /// Environments torn down after their grace period
+    pub expired: Vec<Uuid>,
+    /// Environments whose teardown failed; they stay `Expiring` and are retried
+    pub failed: Vec<Uuid>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CleanupError {
+    #[error("Cleanup job not found")]
+    NotFound,
+
+    #[error("Cleanup operation already {0}")]
+    AlreadyFinished(String),
+}
+
 #[derive(Debug, Clone)]
 struct CleanupPolicy {
     name: String,
@@ -129,6 +256,12 @@ impl CleanupService {
             items_cleaned: 0,
             total_items: estimated_items,
             backup_created: false,
+            started_at: None,
+            tables_processed: 0,
+            total_tables: CleanupPhase::for_cleanup_type(&request.cleanup_type).len() as i32,
+            current_table: None,
+            phase_base_items: 0,
+            cancel_requested: Arc::new(AtomicBool::new(false)),
         };
 
         // Store job
@@ -154,6 +287,10 @@ impl CleanupService {
             items_to_cleanup: estimated_items,
             estimated_duration_seconds: estimated_duration,
             progress_url: format!("/api/cleanup/{}/status", cleanup_id),
+            progress: CleanupProgress {
+                total_tables: CleanupPhase::for_cleanup_type(&request.cleanup_type).len() as i32,
+                ..CleanupProgress::default()
+            },
         })
     }
 
@@ -162,13 +299,25 @@ impl CleanupService {
         let job = jobs.get(&cleanup_id)
             .ok_or_else(|| anyhow!("Cleanup job not found"))?;
 
-        Ok(CleanupResponse {
-            cleanup_id,
-            status: format!("{:?}", job.status).to_lowercase(),
-            items_to_cleanup: job.total_items,
-            estimated_duration_seconds: if job.completed_at.is_some() { 0 } else { 300 },
-            progress_url: format!("/api/cleanup/{}/status", cleanup_id),
-        })
+        Ok(job.to_response())
+    }
+
+    /// Requests cancellation of a pending or running cleanup. Deletions that
+    /// already happened stay committed; the job stops before the next item and
+    /// ends up `cancelled` with the number of items it managed to clean.
+    pub async fn cancel_cleanup(&self, cleanup_id: Uuid) -> std::result::Result<CleanupResponse, CleanupError> {
+        let mut jobs = self.cleanup_jobs.write().await;
+        let job = jobs.get_mut(&cleanup_id).ok_or(CleanupError::NotFound)?;
+
+        if job.is_finished() {
+            return Err(CleanupError::AlreadyFinished(format!("{:?}", job.status).to_lowercase()));
+        }
+
+        info!("Cancellation requested for cleanup operation: {}", cleanup_id);
+        job.cancel_requested.store(true, Ordering::SeqCst);
+        job.status = CleanupStatus::Cancelling;
+
+        Ok(job.to_response())
     }
 
     pub async fn reset_environment(&self, environment_id: Uuid) -> Result<()> {
@@ -221,6 +370,97 @@ impl CleanupService {
         Ok(())
     }
 
+    /// Expires environments that have outlived their TTL. An environment's own
+    /// `expires_at` wins over `default_ttl`. Past the TTL it turns `Expiring`;
+    /// once `grace_period` has passed since then it is torn down and ends up
+    /// `Expired`. Pinned environments and environments with `auto_cleanup`
+    /// off are never expired.
+    pub async fn expire_environments(
+        &self,
+        default_ttl: chrono::Duration,
+        grace_period: chrono::Duration,
+    ) -> Result<ExpirySweep> {
+        let mut sweep = ExpirySweep::default();
+        let now = Utc::now();
+
+        for environment in self.database.get_test_environments().await? {
+            match environment.expiry_action(now, default_ttl, grace_period) {
+                ExpiryAction::None => {}
+                ExpiryAction::MarkExpiring => {
+                    info!(
+                        "Test environment {} ({}) passed its TTL, tearing down after {} minutes",
+                        environment.name, environment.id, grace_period.num_minutes()
+                    );
+                    self.database.mark_test_environment_expiring(environment.id).await?;
+                    sweep.expiring.push(environment.id);
+                }
+                ExpiryAction::Restore => {
+                    info!("Test environment {} ({}) no longer expiring", environment.name, environment.id);
+                    self.database.restore_test_environment(environment.id).await?;
+                    sweep.restored.push(environment.id);
+                }
+                ExpiryAction::TearDown => match self.teardown_environment(&environment).await {
+                    Ok(()) => sweep.expired.push(environment.id),
+                    Err(e) => {
+                        error!("Failed to tear down expired environment {}: {}", environment.id, e);
+                        // Back to Expiring so the next sweep retries
+                        self.database
+                            .update_test_environment_status(environment.id, EnvironmentStatus::Expiring)
+                            .await?;
+                        sweep.failed.push(environment.id);
+                    }
+                },
+            }
+        }
+
+        Ok(sweep)
+    }
+
+    /// Removes an expired environment's test users and generated data, then marks it `Expired`
+    async fn teardown_environment(&self, environment: &TestEnvironment) -> Result<()> {
+        info!("Tearing down expired test environment: {} ({})", environment.name, environment.id);
+
+        self.database
+            .update_test_environment_status(environment.id, EnvironmentStatus::Destroying)
+            .await?;
+
+        self.stop_environment_processes(environment).await?;
+
+        // Test users reference their environment by name
+        let mut users_removed = 0;
+        loop {
+            let users = self.database.get_test_users(&environment.name, 100).await?;
+            if users.is_empty() {
+                break;
+            }
+
+            let mut batch_removed = 0;
+            for user in users {
+                if self.database.delete_test_user(user.id).await? {
+                    batch_removed += 1;
+                }
+            }
+
+            if batch_removed == 0 {
+                break;
+            }
+            users_removed += batch_removed;
+        }
+
+        self.clear_environment_databases(environment).await?;
+        self.clear_environment_caches(environment).await?;
+
+        self.database
+            .update_test_environment_status(environment.id, EnvironmentStatus::Expired)
+            .await?;
+
+        info!(
+            "Expired test environment {} torn down ({} test users removed)",
+            environment.id, users_removed
+        );
+        Ok(())
+    }
+
     // ========================================================================
     // Cleanup Execution Implementation
     // ========================================================================
@@ -228,13 +468,21 @@ impl CleanupService {
     async fn execute_cleanup(&self, cleanup_id: Uuid) -> Result<()> {
         info!("Executing cleanup operation: {}", cleanup_id);
 
-        // Update status to running
-        self.update_cleanup_status(cleanup_id, CleanupStatus::Running, 0).await;
-
         let job = {
-            let jobs = self.cleanup_jobs.read().await;
-            jobs.get(&cleanup_id).cloned()
-                .ok_or_else(|| anyhow!("Cleanup job not found"))?
+            let mut jobs = self.cleanup_jobs.write().await;
+            let job = jobs.get_mut(&cleanup_id)
+                .ok_or_else(|| anyhow!("Cleanup job not found"))?;
+
+            // Cancelled before the worker picked it up
+            if job.is_cancel_requested() {
+                drop(jobs);
+                self.mark_cleanup_cancelled(cleanup_id, 0).await;
+                return Ok(());
+            }
+
+            job.status = CleanupStatus::Running;
+            job.started_at = Some(Utc::now());
+            job.clone()
         };
 
         // Create backup if requested
@@ -243,15 +491,34 @@ impl CleanupService {
             self.mark_backup_created(cleanup_id).await;
         }
 
-        // Execute cleanup based on type
-        let cleaned_count = match job.request.cleanup_type {
-            CleanupType::Users => self.cleanup_users(&job).await?,
-            CleanupType::Workflows => self.cleanup_workflows(&job).await?,
-            CleanupType::TestData => self.cleanup_test_data(&job).await?,
-            CleanupType::Environments => self.cleanup_environments(&job).await?,
-            CleanupType::Artifacts => self.cleanup_artifacts(&job).await?,
-            CleanupType::All => self.cleanup_all(&job).await?,
-        };
+        // Execute cleanup phase by phase so progress and cancellation are per table
+        let mut cleaned_count = 0;
+
+        for phase in CleanupPhase::for_cleanup_type(&job.request.cleanup_type) {
+            if job.is_cancel_requested() {
+                break;
+            }
+
+            self.begin_cleanup_phase(cleanup_id, phase, cleaned_count).await;
+
+            cleaned_count += match phase {
+                CleanupPhase::Users => self.cleanup_users(&job).await?,
+                CleanupPhase::Workflows => self.cleanup_workflows(&job).await?,
+                CleanupPhase::RedisCache => self.cleanup_redis_cache(&job).await?,
+                CleanupPhase::MongoData => self.cleanup_mongodb_data(&job).await?,
+                CleanupPhase::ClickHouseData => self.cleanup_clickhouse_data(&job).await?,
+                CleanupPhase::Artifacts => self.cleanup_artifacts(&job).await?,
+                CleanupPhase::Environments => self.cleanup_environments(&job).await?,
+            };
+
+            self.finish_cleanup_phase(cleanup_id, cleaned_count).await;
+        }
+
+        if job.is_cancel_requested() {
+            self.mark_cleanup_cancelled(cleanup_id, cleaned_count).await;
+            info!("Cleanup operation cancelled: {} ({} items cleaned before cancellation)", cleanup_id, cleaned_count);
+            return Ok(());
+        }
 
         // Mark as completed
         self.mark_cleanup_completed(cleanup_id, cleaned_count).await;
@@ -276,6 +543,10 @@ impl CleanupService {
 
                 let mut batch_cleaned = 0;
                 for user in users {
+                    if job.is_cancel_requested() {
+                        return Ok(cleaned_count);
+                    }
+
                     // Check if user should be cleaned up
                     if self.should_cleanup_user(&user, job).await? {
                         if let Ok(deleted) = self.database.delete_test_user(user.id).await {
@@ -320,6 +591,10 @@ impl CleanupService {
 
         // Simulate workflow cleanup
         for i in 0..50 {
+            if job.is_cancel_requested() {
+                break;
+            }
+
             // Check if should be cleaned up based on age, status, etc.
             cleaned_count += 1;
 
@@ -334,23 +609,6 @@ impl CleanupService {
         Ok(cleaned_count)
     }
 
-    async fn cleanup_test_data(&self, job: &CleanupJob) -> Result<i32> {
-        debug!("Cleaning up general test data");
-
-        let mut cleaned_count = 0;
-
-        // Clean up cached data from Redis
-        cleaned_count += self.cleanup_redis_cache(job).await?;
-
-        // Clean up MongoDB test documents
-        cleaned_count += self.cleanup_mongodb_data(job).await?;
-
-        // Clean up ClickHouse analytics data
-        cleaned_count += self.cleanup_clickhouse_data(job).await?;
-
-        Ok(cleaned_count)
-    }
-
     async fn cleanup_environments(&self, job: &CleanupJob) -> Result<i32> {
         debug!("Cleaning up test environments");
 
@@ -358,6 +616,10 @@ impl CleanupService {
         let environments = self.database.get_test_environments().await?;
 
         for environment in environments {
+            if job.is_cancel_requested() {
+                break;
+            }
+
             if self.should_cleanup_environment(&environment, job).await? {
                 // In a real implementation, this would properly destroy the environment
                 debug!("Would destroy environment: {} ({})", environment.name, environment.id);
@@ -392,6 +654,10 @@ impl CleanupService {
 
             // Simulate artifact cleanup
             for i in 0..20 {
+                if job.is_cancel_requested() {
+                    return Ok(cleaned_count);
+                }
+
                 cleaned_count += 1;
 
                 if i % 5 == 0 {
@@ -409,21 +675,6 @@ impl CleanupService {
         Ok(cleaned_count)
     }
 
-    async fn cleanup_all(&self, job: &CleanupJob) -> Result<i32> {
-        debug!("Performing comprehensive cleanup");
-
-        let mut total_cleaned = 0;
-
-        // Clean up in order of dependency
-        total_cleaned += self.cleanup_users(job).await?;
-        total_cleaned += self.cleanup_workflows(job).await?;
-        total_cleaned += self.cleanup_test_data(job).await?;
-        total_cleaned += self.cleanup_artifacts(job).await?;
-        total_cleaned += self.cleanup_environments(job).await?;
-
-        Ok(total_cleaned)
-    }
-
     async fn execute_environment_reset(&self, environment: &TestEnvironment, request: &CleanupRequest) -> Result<()> {
         debug!("Executing environment reset: {}", environment.name);
 
@@ -516,7 +767,7 @@ impl CleanupService {
 
         for environment in environments {
             if let Some(expires_at) = environment.expires_at {
-                if expires_at <= *cutoff_time && environment.auto_cleanup {
+                if expires_at <= *cutoff_time && environment.auto_cleanup && !environment.pinned {
                     // In real implementation, would destroy the environment
                     debug!("Would cleanup expired environment: {}", environment.name);
                     cleaned_count += 1;
@@ -568,6 +819,10 @@ impl CleanupService {
             return Ok(true);
         }
 
+        if environment.pinned {
+            return Ok(false);
+        }
+
         if environment.should_cleanup() {
             return Ok(true);
         }
@@ -779,21 +1034,43 @@ impl CleanupService {
         Ok(())
     }
 
-    async fn update_cleanup_status(&self, cleanup_id: Uuid, status: CleanupStatus, progress: u32) {
+    /// Records progress reported by the running phase. `progress` and
+    /// `items_cleaned` are relative to that phase and folded into the job totals.
+    async fn update_cleanup_progress(&self, cleanup_id: Uuid, progress: u32, items_cleaned: i32) {
         if let Ok(mut jobs) = self.cleanup_jobs.try_write() {
             if let Some(job) = jobs.get_mut(&cleanup_id) {
-                job.status = status;
-                job.progress = progress;
+                let total_tables = job.total_tables.max(1) as u32;
+                let phase_progress = progress.min(100);
+                job.progress = std::cmp::min(
+                    (job.tables_processed as u32 * 100 + phase_progress) / total_tables,
+                    99,
+                );
+                job.items_cleaned = job.phase_base_items + items_cleaned;
             }
         }
     }
 
-    async fn update_cleanup_progress(&self, cleanup_id: Uuid, progress: u32, items_cleaned: i32) {
-        if let Ok(mut jobs) = self.cleanup_jobs.try_write() {
-            if let Some(job) = jobs.get_mut(&cleanup_id) {
-                job.progress = progress;
-                job.items_cleaned = items_cleaned;
-            }
+    async fn begin_cleanup_phase(&self, cleanup_id: Uuid, phase: CleanupPhase, items_cleaned: i32) {
+        let mut jobs = self.cleanup_jobs.write().await;
+        if let Some(job) = jobs.get_mut(&cleanup_id) {
+            debug!("Cleanup {} processing table {}", cleanup_id, phase.table_name());
+            job.current_table = Some(phase.table_name().to_string());
+            job.phase_base_items = items_cleaned;
+            job.items_cleaned = items_cleaned;
+        }
+    }
+
+    async fn finish_cleanup_phase(&self, cleanup_id: Uuid, items_cleaned: i32) {
+        let mut jobs = self.cleanup_jobs.write().await;
+        if let Some(job) = jobs.get_mut(&cleanup_id) {
+            job.tables_processed += 1;
+            job.current_table = None;
+            job.phase_base_items = items_cleaned;
+            job.items_cleaned = items_cleaned;
+            job.progress = std::cmp::min(
+                (job.tables_processed * 100 / job.total_tables.max(1)) as u32,
+                99,
+            );
         }
     }
 
@@ -816,6 +1093,16 @@ impl CleanupService {
         }
     }
 
+    async fn mark_cleanup_cancelled(&self, cleanup_id: Uuid, items_cleaned: i32) {
+        let mut jobs = self.cleanup_jobs.write().await;
+        if let Some(job) = jobs.get_mut(&cleanup_id) {
+            job.status = CleanupStatus::Cancelled;
+            job.items_cleaned = items_cleaned;
+            job.current_table = None;
+            job.completed_at = Some(Utc::now());
+        }
+    }
+
     async fn mark_cleanup_failed(&self, cleanup_id: Uuid, error_message: String) {
         if let Ok(mut jobs) = self.cleanup_jobs.try_write() {
             if let Some(job) = jobs.get_mut(&cleanup_id) {
@@ -840,3 +1127,195 @@ impl Clone for CleanupService {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_job(cleanup_type: CleanupType) -> CleanupJob {
+        let phases = CleanupPhase::for_cleanup_type(&cleanup_type);
+        CleanupJob {
+            id: Uuid::new_v4(),
+            request: CleanupRequest {
+                environment_ids: vec![Uuid::new_v4()],
+                cleanup_type,
+                force: false,
+                backup_before_cleanup: false,
+            },
+            status: CleanupStatus::Running,
+            progress: 0,
+            created_at: Utc::now(),
+            completed_at: None,
+            error_message: None,
+            items_cleaned: 0,
+            total_items: 100,
+            backup_created: false,
+            started_at: Some(Utc::now() - chrono::Duration::seconds(60)),
+            tables_processed: 0,
+            total_tables: phases.len() as i32,
+            current_table: None,
+            phase_base_items: 0,
+            cancel_requested: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    #[test]
+    fn test_all_cleanup_removes_users_before_environments() {
+        let phases = CleanupPhase::for_cleanup_type(&CleanupType::All);
+        let users = phases.iter().position(|p| *p == CleanupPhase::Users).unwrap();
+        let environments = phases.iter().position(|p| *p == CleanupPhase::Environments).unwrap();
+
+        assert_eq!(phases.len(), 7);
+        assert!(users < environments);
+    }
+
+    #[test]
+    fn test_progress_response_estimates_remaining_time() {
+        let mut job = test_job(CleanupType::All);
+        job.progress = 50;
+        job.tables_processed = 3;
+        job.items_cleaned = 42;
+
+        let response = job.to_response();
+        assert_eq!(response.progress.tables_processed, 3);
+        assert_eq!(response.progress.total_tables, 7);
+        assert_eq!(response.progress.items_cleaned, 42);
+
+        let remaining = response.progress.estimated_remaining_seconds.unwrap();
+        assert!((55..=65).contains(&remaining));
+    }
+
+    fn test_environment(created_hours_ago: i64) -> TestEnvironment {
+        let created_at = Utc::now() - chrono::Duration::hours(created_hours_ago);
+        TestEnvironment {
+            id: Uuid::new_v4(),
+            name: "expiry-test".to_string(),
+            description: None,
+            environment_type: EnvironmentType::Testing,
+            configuration: EnvironmentConfig {
+                base_url: "http://localhost".to_string(),
+                api_endpoints: HashMap::new(),
+                authentication: AuthenticationConfig {
+                    jwt_secret: "secret".to_string(),
+                    token_expiry_hours: 1,
+                    refresh_token_expiry_days: 1,
+                    multi_factor_enabled: false,
+                    oauth_providers: vec![],
+                },
+                feature_flags: HashMap::new(),
+                resource_limits: ResourceLimits {
+                    cpu_limit: "1".to_string(),
+                    memory_limit: "1Gi".to_string(),
+                    disk_limit: "1Gi".to_string(),
+                    network_bandwidth_limit: "1Gbps".to_string(),
+                    concurrent_users: 10,
+                    api_rate_limit: 100,
+                },
+                monitoring: MonitoringConfig {
+                    metrics_enabled: false,
+                    logging_level: "info".to_string(),
+                    trace_sampling_rate: 0.0,
+                    alert_endpoints: vec![],
+                    dashboard_urls: vec![],
+                },
+            },
+            database_configs: HashMap::new(),
+            service_configs: HashMap::new(),
+            status: EnvironmentStatus::Ready,
+            created_by: Uuid::new_v4(),
+            created_at,
+            updated_at: created_at,
+            expires_at: None,
+            auto_cleanup: true,
+            pinned: false,
+            expiring_since: None,
+            status_before_expiry: None,
+        }
+    }
+
+    #[test]
+    fn test_environment_expiry_uses_default_ttl_then_grace_period() {
+        let ttl = chrono::Duration::hours(72);
+        let grace = chrono::Duration::hours(1);
+        let now = Utc::now();
+
+        assert_eq!(test_environment(10).expiry_action(now, ttl, grace), ExpiryAction::None);
+
+        let mut environment = test_environment(72);
+        assert_eq!(environment.expiry_action(now, ttl, grace), ExpiryAction::MarkExpiring);
+
+        // Within the grace period nothing happens, after it the environment is torn down
+        environment.status = EnvironmentStatus::Expiring;
+        environment.expiring_since = Some(now);
+        assert_eq!(environment.expiry_action(now, ttl, grace), ExpiryAction::None);
+        assert_eq!(
+            environment.expiry_action(now + chrono::Duration::minutes(61), ttl, grace),
+            ExpiryAction::TearDown
+        );
+
+        environment.status = EnvironmentStatus::Expired;
+        assert_eq!(environment.expiry_action(now, ttl, grace), ExpiryAction::None);
+    }
+
+    #[test]
+    fn test_environment_expiry_honours_override_and_pin() {
+        let ttl = chrono::Duration::hours(72);
+        let grace = chrono::Duration::hours(1);
+        let now = Utc::now();
+
+        // A per-environment expiry replaces the default TTL either way
+        let mut short_lived = test_environment(2);
+        short_lived.expires_at = Some(now - chrono::Duration::hours(1));
+        assert_eq!(short_lived.expiry_action(now, ttl, grace), ExpiryAction::MarkExpiring);
+
+        let mut long_lived = test_environment(100);
+        long_lived.expires_at = Some(now + chrono::Duration::hours(1));
+        assert_eq!(long_lived.expiry_action(now, ttl, grace), ExpiryAction::None);
+
+        let mut pinned = test_environment(100);
+        pinned.pinned = true;
+        assert_eq!(pinned.expiry_action(now, ttl, grace), ExpiryAction::None);
+
+        // Pinning during the grace period restores the environment
+        pinned.status = EnvironmentStatus::Expiring;
+        assert_eq!(pinned.expiry_action(now, ttl, grace), ExpiryAction::Restore);
+
+        let mut kept = test_environment(100);
+        kept.auto_cleanup = false;
+        assert_eq!(kept.expiry_action(now, ttl, grace), ExpiryAction::None);
+
+        kept.status = EnvironmentStatus::Expiring;
+        assert_eq!(kept.expiry_action(now, ttl, grace), ExpiryAction::Restore);
+    }
+
+    #[test]
+    fn test_environment_grace_period_runs_from_when_expiry_started() {
+        let ttl = chrono::Duration::hours(72);
+        let grace = chrono::Duration::hours(1);
+        let now = Utc::now();
+
+        // Long past its TTL, e.g. after the sweeper was down, but only just marked
+        let mut environment = test_environment(200);
+        environment.status = EnvironmentStatus::Expiring;
+        environment.expiring_since = Some(now - chrono::Duration::minutes(5));
+        assert_eq!(environment.expiry_action(now, ttl, grace), ExpiryAction::None);
+        assert_eq!(
+            environment.expiry_action(now + chrono::Duration::minutes(56), ttl, grace),
+            ExpiryAction::TearDown
+        );
+    }
+
+    #[test]
+    fn test_cancelled_job_reports_cleaned_items() {
+        let mut job = test_job(CleanupType::Users);
+        job.cancel_requested.store(true, Ordering::SeqCst);
+        job.status = CleanupStatus::Cancelled;
+        job.items_cleaned = 17;
+
+        let response = job.to_response();
+        assert!(job.is_finished());
+        assert_eq!(response.status, "cancelled");
+        assert_eq!(response.progress.items_cleaned, 17);
+        assert_eq!(response.progress.estimated_remaining_seconds, Some(0));
+    }
+}
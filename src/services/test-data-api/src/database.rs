@@ -13,7 +13,7 @@ use mongodb::{
 use redis::{aio::ConnectionManager, AsyncCommands, Client as RedisClient};
 use serde::{Deserialize, Serialize};
 use sqlx::{
-    postgres::{PgConnectOptions, PgPool, PgPoolOptions, PgRow},
+    postgres::{PgConnectOptions, PgExecutor, PgPool, PgPoolOptions, PgRow},
     ConnectOptions, FromRow, Row as SqlxRow,
 };
 use std::{collections::HashMap, str::FromStr, sync::Arc, time::Duration};
@@ -141,6 +141,37 @@ impl DatabaseManager {
         Ok(postgres_deleted)
     }
 
+    // ========================================================================
+    // Generated Data
+    // ========================================================================
+
+    /// Inserts generated entities in order inside one PostgreSQL transaction,
+    /// so parents land before their children and a failure part-way leaves
+    /// nothing behind. Users are copied to the cache, metadata and analytics
+    /// stores only once the transaction has committed.
+    pub async fn insert_generated_entities(&self, generation_id: Uuid, entities: &[GeneratedEntity]) -> Result<()> {
+        debug!("Inserting {} generated entities for generation {}", entities.len(), generation_id);
+
+        self.postgres.insert_generated_entities(generation_id, entities).await?;
+
+        for entity in entities {
+            if let GeneratedEntity::User(user) = entity {
+                if let Err(e) = self.redis.cache_test_user(user).await {
+                    warn!("Failed to cache generated user {}: {}", user.id, e);
+                }
+                if let Err(e) = self.mongodb.store_user_metadata(user).await {
+                    warn!("Failed to store metadata for generated user {}: {}", user.id, e);
+                }
+                if let Err(e) = self.clickhouse.log_user_creation(user).await {
+                    warn!("Failed to log creation of generated user {}: {}", user.id, e);
+                }
+            }
+        }
+
+        info!("Committed {} generated entities for generation {}", entities.len(), generation_id);
+        Ok(())
+    }
+
     // ========================================================================
     // Test Environment Management
     // ========================================================================
@@ -170,6 +201,9 @@ impl DatabaseManager {
             updated_at: now,
             expires_at,
             auto_cleanup: request.auto_cleanup,
+            pinned: request.pinned,
+            expiring_since: None,
+            status_before_expiry: None,
         };
 
         // Store in PostgreSQL
@@ -185,6 +219,21 @@ impl DatabaseManager {
         Ok(environment)
     }
 
+    pub async fn get_test_environment(&self, environment_id: Uuid) -> Result<Option<TestEnvironment>> {
+        debug!("Fetching test environment: {}", environment_id);
+        self.postgres.get_test_environment(environment_id).await
+    }
+
+    pub async fn get_test_users_page(
+        &self,
+        environment: &str,
+        after: Option<(DateTime<Utc>, Uuid)>,
+        limit: i64,
+    ) -> Result<Vec<TestUser>> {
+        // Paged reads bypass the Redis cache, which has no stable ordering
+        self.postgres.get_test_users_page(environment, after, limit).await
+    }
+
     pub async fn get_test_environments(&self) -> Result<Vec<TestEnvironment>> {
         debug!("Fetching all test environments");
 
@@ -194,11 +243,69 @@ impl DatabaseManager {
         Ok(environments)
     }
 
+    pub async fn update_test_environment_status(
+        &self,
+        environment_id: Uuid,
+        status: EnvironmentStatus,
+    ) -> Result<Option<TestEnvironment>> {
+        debug!("Setting test environment {} status to {}", environment_id, status);
+
+        let environment = self.postgres.update_test_environment_status(environment_id, &status).await?;
+        if let Some(environment) = &environment {
+            self.redis.cache_environment(environment).await?;
+        }
+
+        Ok(environment)
+    }
+
+    pub async fn mark_test_environment_expiring(
+        &self,
+        environment_id: Uuid,
+    ) -> Result<Option<TestEnvironment>> {
+        debug!("Starting grace period of test environment {}", environment_id);
+
+        let environment = self.postgres.mark_test_environment_expiring(environment_id).await?;
+        if let Some(environment) = &environment {
+            self.redis.cache_environment(environment).await?;
+        }
+
+        Ok(environment)
+    }
+
+    pub async fn restore_test_environment(
+        &self,
+        environment_id: Uuid,
+    ) -> Result<Option<TestEnvironment>> {
+        debug!("Ending grace period of test environment {}", environment_id);
+
+        let environment = self.postgres.restore_test_environment(environment_id).await?;
+        if let Some(environment) = &environment {
+            self.redis.cache_environment(environment).await?;
+        }
+
+        Ok(environment)
+    }
+
+    pub async fn set_test_environment_pinned(
+        &self,
+        environment_id: Uuid,
+        pinned: bool,
+    ) -> Result<Option<TestEnvironment>> {
+        debug!("Setting test environment {} pinned: {}", environment_id, pinned);
+
+        let environment = self.postgres.set_test_environment_pinned(environment_id, pinned).await?;
+        if let Some(environment) = &environment {
+            self.redis.cache_environment(environment).await?;
+        }
+
+        Ok(environment)
+    }
+
     // ========================================================================
     // Utility Methods
     // ========================================================================
 
-    fn hash_password(&self, password: &str) -> Result<String> {
+    pub fn hash_password(&self, password: &str) -> Result<String> {
         use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
         use argon2::password_hash::{rand_core::OsRng, SaltString};
 
@@ -261,6 +368,10 @@ impl PostgresManager {
     }
 
     pub async fn create_test_user(&self, user: &TestUser) -> Result<()> {
+        Self::insert_test_user(&self.pool, user).await
+    }
+
+    async fn insert_test_user<'c>(executor: impl PgExecutor<'c>, user: &TestUser) -> Result<()> {
         let query = r#"
             INSERT INTO test_users (
                 id, username, email, password_hash, first_name, last_name,
@@ -285,7 +396,61 @@ impl PostgresManager {
             .bind(user.last_login_at)
             .bind(&user.test_environment)
             .bind(user.cleanup_after)
-            .execute(&self.pool)
+            .execute(executor)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Inserts entities in the given order in one transaction; nothing is kept if any insert fails
+    pub async fn insert_generated_entities(&self, generation_id: Uuid, entities: &[GeneratedEntity]) -> Result<()> {
+        let mut transaction = self.pool.begin().await?;
+
+        for entity in entities {
+            match entity {
+                GeneratedEntity::User(user) => {
+                    Self::insert_test_user(&mut *transaction, user).await?;
+                }
+                GeneratedEntity::Record { id, data_type, test_environment, data } => {
+                    Self::insert_generated_record(
+                        &mut *transaction,
+                        *id,
+                        generation_id,
+                        &data_type.to_string(),
+                        test_environment,
+                        data,
+                    )
+                    .await?;
+                }
+            }
+        }
+
+        transaction.commit().await?;
+        Ok(())
+    }
+
+    async fn insert_generated_record<'c>(
+        executor: impl PgExecutor<'c>,
+        id: Uuid,
+        generation_id: Uuid,
+        data_type: &str,
+        test_environment: &str,
+        data: &serde_json::Value,
+    ) -> Result<()> {
+        let query = r#"
+            INSERT INTO generated_records (
+                id, generation_id, data_type, test_environment, data, created_at
+            ) VALUES ($1, $2, $3, $4, $5, $6)
+        "#;
+
+        sqlx::query(query)
+            .bind(id)
+            .bind(generation_id)
+            .bind(data_type)
+            .bind(test_environment)
+            .bind(data)
+            .bind(Utc::now())
+            .execute(executor)
             .await?;
 
         Ok(())
@@ -308,6 +473,50 @@ impl PostgresManager {
         Ok(users)
     }
 
+    /// Keyset-paginated read ordered by `(created_at, id)` so pages stay stable
+    /// while rows are inserted concurrently.
+    pub async fn get_test_users_page(
+        &self,
+        environment: &str,
+        after: Option<(DateTime<Utc>, Uuid)>,
+        limit: i64,
+    ) -> Result<Vec<TestUser>> {
+        let users = match after {
+            Some((created_at, id)) => {
+                let query = r#"
+                    SELECT * FROM test_users
+                    WHERE test_environment = $1 AND (created_at, id) > ($2, $3)
+                    ORDER BY created_at, id
+                    LIMIT $4
+                "#;
+
+                sqlx::query_as::<_, TestUser>(query)
+                    .bind(environment)
+                    .bind(created_at)
+                    .bind(id)
+                    .bind(limit)
+                    .fetch_all(&self.pool)
+                    .await?
+            }
+            None => {
+                let query = r#"
+                    SELECT * FROM test_users
+                    WHERE test_environment = $1
+                    ORDER BY created_at, id
+                    LIMIT $2
+                "#;
+
+                sqlx::query_as::<_, TestUser>(query)
+                    .bind(environment)
+                    .bind(limit)
+                    .fetch_all(&self.pool)
+                    .await?
+            }
+        };
+
+        Ok(users)
+    }
+
     pub async fn delete_test_user(&self, user_id: Uuid) -> Result<bool> {
         let result = sqlx::query("DELETE FROM test_users WHERE id = $1")
             .bind(user_id)
@@ -322,8 +531,8 @@ impl PostgresManager {
             INSERT INTO test_environments (
                 id, name, description, environment_type, configuration,
                 database_configs, service_configs, status, created_by,
-                created_at, updated_at, expires_at, auto_cleanup
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+                created_at, updated_at, expires_at, auto_cleanup, pinned
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
         "#;
 
         sqlx::query(query)
@@ -340,21 +549,114 @@ impl PostgresManager {
             .bind(env.updated_at)
             .bind(env.expires_at)
             .bind(env.auto_cleanup)
+            .bind(env.pinned)
             .execute(&self.pool)
             .await?;
 
         Ok(())
     }
 
+    pub async fn get_test_environment(&self, environment_id: Uuid) -> Result<Option<TestEnvironment>> {
+        let environment = sqlx::query_as::<_, TestEnvironment>("SELECT * FROM test_environments WHERE id = $1")
+            .bind(environment_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(environment)
+    }
+
+    /// Environments that have not been torn down, newest first
     pub async fn get_test_environments(&self) -> Result<Vec<TestEnvironment>> {
-        let query = "SELECT * FROM test_environments ORDER BY created_at DESC";
+        let query = "SELECT * FROM test_environments WHERE status <> $1 ORDER BY created_at DESC";
         let environments = sqlx::query_as::<_, TestEnvironment>(query)
+            .bind(EnvironmentStatus::Expired)
             .fetch_all(&self.pool)
             .await?;
 
         Ok(environments)
     }
 
+    pub async fn update_test_environment_status(
+        &self,
+        environment_id: Uuid,
+        status: &EnvironmentStatus,
+    ) -> Result<Option<TestEnvironment>> {
+        let environment = sqlx::query_as::<_, TestEnvironment>(
+            "UPDATE test_environments SET status = $2, updated_at = NOW() WHERE id = $1 RETURNING *",
+        )
+            .bind(environment_id)
+            .bind(status)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(environment)
+    }
+
+    /// Moves an environment to `Expiring`, recording when its grace period
+    /// started and the status it had before
+    pub async fn mark_test_environment_expiring(
+        &self,
+        environment_id: Uuid,
+    ) -> Result<Option<TestEnvironment>> {
+        let environment = sqlx::query_as::<_, TestEnvironment>(
+            r#"
+            UPDATE test_environments
+            SET status_before_expiry = status, status = $2, expiring_since = NOW(), updated_at = NOW()
+            WHERE id = $1 AND status NOT IN ($2, $3, $4)
+            RETURNING *
+            "#,
+        )
+            .bind(environment_id)
+            .bind(EnvironmentStatus::Expiring)
+            .bind(EnvironmentStatus::Destroying)
+            .bind(EnvironmentStatus::Expired)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(environment)
+    }
+
+    /// Takes an environment out of its grace period, back to the status it
+    /// had before it started expiring
+    pub async fn restore_test_environment(
+        &self,
+        environment_id: Uuid,
+    ) -> Result<Option<TestEnvironment>> {
+        let environment = sqlx::query_as::<_, TestEnvironment>(
+            r#"
+            UPDATE test_environments
+            SET status = COALESCE(status_before_expiry, $3), status_before_expiry = NULL,
+                expiring_since = NULL, updated_at = NOW()
+            WHERE id = $1 AND status = $2
+            RETURNING *
+            "#,
+        )
+            .bind(environment_id)
+            .bind(EnvironmentStatus::Expiring)
+            .bind(EnvironmentStatus::Ready)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(environment)
+    }
+
+    pub async fn set_test_environment_pinned(
+        &self,
+        environment_id: Uuid,
+        pinned: bool,
+    ) -> Result<Option<TestEnvironment>> {
+        let environment = sqlx::query_as::<_, TestEnvironment>(
+            "UPDATE test_environments SET pinned = $2, updated_at = NOW() WHERE id = $1 AND status <> $3 RETURNING *",
+        )
+            .bind(environment_id)
+            .bind(pinned)
+            .bind(EnvironmentStatus::Expired)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(environment)
+    }
+
     pub async fn health_check(&self) -> ConnectionHealth {
         let start = std::time::Instant::now();
 
@@ -684,6 +986,89 @@ impl std::fmt::Display for EnvironmentStatus {
             EnvironmentStatus::Maintenance => write!(f, "maintenance"),
             EnvironmentStatus::Error => write!(f, "error"),
             EnvironmentStatus::Destroying => write!(f, "destroying"),
+            EnvironmentStatus::Expiring => write!(f, "expiring"),
+            EnvironmentStatus::Expired => write!(f, "expired"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn postgres() -> PostgresManager {
+        let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
+        PostgresManager::new(&url).await.unwrap()
+    }
+
+    fn test_user() -> TestUser {
+        let id = Uuid::new_v4();
+        TestUser {
+            id,
+            username: format!("user_{}", id.simple()),
+            email: format!("{}@example.com", id.simple()),
+            password_hash: "secret-hash".to_string(),
+            test_environment: "transaction-test".to_string(),
+            ..TestUser::default()
+        }
+    }
+
+    fn owned_record(id: Uuid, owner: &TestUser) -> GeneratedEntity {
+        GeneratedEntity::Record {
+            id,
+            data_type: DataType::Workflows,
+            test_environment: owner.test_environment.clone(),
+            data: serde_json::json!({ "owner_id": owner.id }),
         }
     }
+
+    async fn user_exists(postgres: &PostgresManager, id: Uuid) -> bool {
+        sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM test_users WHERE id = $1)")
+            .bind(id)
+            .fetch_one(&postgres.pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL at TEST_DATABASE_URL
+    async fn test_generated_entities_are_committed_together() {
+        let postgres = postgres().await;
+        let generation_id = Uuid::new_v4();
+        let user = test_user();
+        let record_id = Uuid::new_v4();
+
+        let entities = vec![GeneratedEntity::User(user.clone()), owned_record(record_id, &user)];
+        postgres.insert_generated_entities(generation_id, &entities).await.unwrap();
+
+        assert!(user_exists(&postgres, user.id).await);
+
+        let data: serde_json::Value =
+            sqlx::query_scalar("SELECT data FROM generated_records WHERE id = $1 AND generation_id = $2")
+                .bind(record_id)
+                .bind(generation_id)
+                .fetch_one(&postgres.pool)
+                .await
+                .unwrap();
+        assert_eq!(data["owner_id"], serde_json::json!(user.id));
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires PostgreSQL at TEST_DATABASE_URL
+    async fn test_failed_insert_rolls_back_the_whole_graph() {
+        let postgres = postgres().await;
+        let existing = test_user();
+        let record_id = Uuid::new_v4();
+        postgres
+            .insert_generated_entities(Uuid::new_v4(), &[owned_record(record_id, &existing)])
+            .await
+            .unwrap();
+
+        // The child reuses an existing record id, so its insert fails after the user's succeeded
+        let user = test_user();
+        let entities = vec![GeneratedEntity::User(user.clone()), owned_record(record_id, &user)];
+        assert!(postgres.insert_generated_entities(Uuid::new_v4(), &entities).await.is_err());
+
+        assert!(!user_exists(&postgres, user.id).await);
+    }
 }
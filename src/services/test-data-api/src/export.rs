@@ -0,0 +1,312 @@
+// AI-CORE Test Data Export Module
+// Streams test environment data as NDJSON or CSV with bounded memory
+// Backend Agent Implementation - T2.2
+
+use anyhow::{anyhow, Result};
+use axum::body::Bytes;
+use chrono::{DateTime, Utc};
+use futures_util::stream::{self, Stream};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::database::DatabaseManager;
+use crate::models::*;
+
+/// Number of rows fetched from the database per chunk of the export stream.
+const EXPORT_PAGE_SIZE: i64 = 500;
+
+const REDACTED: &str = "***";
+
+// ============================================================================
+// Export Request Types
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Ndjson,
+    Csv,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Ndjson => "application/x-ndjson",
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+        }
+    }
+
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Ndjson => "ndjson",
+            ExportFormat::Csv => "csv",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportEntity {
+    Environment,
+    Users,
+}
+
+impl std::fmt::Display for ExportEntity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportEntity::Environment => write!(f, "environment"),
+            ExportEntity::Users => write!(f, "users"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+    pub entity_type: Option<ExportEntity>,
+}
+
+impl ExportQuery {
+    pub fn validate(&self) -> Result<()> {
+        // CSV columns differ per entity, so a single CSV stream can only hold one entity type
+        if self.format == ExportFormat::Csv && self.entity_type.is_none() {
+            return Err(anyhow!("CSV exports require an entity_type filter"));
+        }
+
+        Ok(())
+    }
+
+    fn includes(&self, entity: ExportEntity) -> bool {
+        self.entity_type.map_or(true, |filter| filter == entity)
+    }
+}
+
+// ============================================================================
+// Streaming Export
+// ============================================================================
+
+enum ExportStage {
+    Environment,
+    Users {
+        after: Option<(DateTime<Utc>, Uuid)>,
+        first_page: bool,
+    },
+    Done,
+}
+
+struct ExportState {
+    database: Arc<DatabaseManager>,
+    environment: TestEnvironment,
+    query: ExportQuery,
+    stage: ExportStage,
+}
+
+/// Streams every entity belonging to `environment` in the requested format.
+/// Users are read page by page with a keyset cursor so only one page is held
+/// in memory at a time, regardless of environment size.
+pub fn export_environment(
+    database: Arc<DatabaseManager>,
+    environment: TestEnvironment,
+    query: ExportQuery,
+) -> impl Stream<Item = Result<Bytes>> {
+    let state = ExportState {
+        database,
+        environment,
+        query,
+        stage: ExportStage::Environment,
+    };
+
+    stream::try_unfold(state, |mut state| async move {
+        loop {
+            match state.stage {
+                ExportStage::Environment => {
+                    state.stage = ExportStage::Users { after: None, first_page: true };
+
+                    if state.query.includes(ExportEntity::Environment) {
+                        let chunk = encode_environment(&state.environment, state.query.format)?;
+                        return Ok(Some((chunk, state)));
+                    }
+                }
+                ExportStage::Users { after, first_page } => {
+                    if !state.query.includes(ExportEntity::Users) {
+                        state.stage = ExportStage::Done;
+                        continue;
+                    }
+
+                    let users = state
+                        .database
+                        .get_test_users_page(&state.environment.name, after, EXPORT_PAGE_SIZE)
+                        .await?;
+
+                    debug!("Exporting {} users for environment {}", users.len(), state.environment.id);
+
+                    state.stage = match users.last() {
+                        Some(last) if users.len() as i64 == EXPORT_PAGE_SIZE => ExportStage::Users {
+                            after: Some((last.created_at, last.id)),
+                            first_page: false,
+                        },
+                        _ => ExportStage::Done,
+                    };
+
+                    if users.is_empty() && !(first_page && state.query.format == ExportFormat::Csv) {
+                        continue;
+                    }
+
+                    let chunk = encode_users(&users, state.query.format, first_page)?;
+                    return Ok(Some((chunk, state)));
+                }
+                ExportStage::Done => return Ok(None),
+            }
+        }
+    })
+}
+
+// ============================================================================
+// Encoding Helpers
+// ============================================================================
+
+fn encode_environment(environment: &TestEnvironment, format: ExportFormat) -> Result<Bytes> {
+    match format {
+        ExportFormat::Ndjson => {
+            let mut data = serde_json::to_value(environment)?;
+            redact_environment_secrets(&mut data);
+            ndjson_line(ExportEntity::Environment, data)
+        }
+        ExportFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            writer.write_record([
+                "id", "name", "environment_type", "status", "created_by",
+                "created_at", "expires_at", "auto_cleanup",
+            ])?;
+            writer.write_record([
+                environment.id.to_string(),
+                environment.name.clone(),
+                environment.environment_type.to_string(),
+                environment.status.to_string(),
+                environment.created_by.to_string(),
+                environment.created_at.to_rfc3339(),
+                environment.expires_at.map(|t| t.to_rfc3339()).unwrap_or_default(),
+                environment.auto_cleanup.to_string(),
+            ])?;
+            Ok(Bytes::from(writer.into_inner()?))
+        }
+    }
+}
+
+fn encode_users(users: &[TestUser], format: ExportFormat, include_header: bool) -> Result<Bytes> {
+    match format {
+        ExportFormat::Ndjson => {
+            let mut buffer = Vec::new();
+            for user in users {
+                let mut data = serde_json::to_value(user)?;
+                if let Some(object) = data.as_object_mut() {
+                    object.remove("password_hash");
+                }
+                buffer.extend_from_slice(&ndjson_line(ExportEntity::Users, data)?);
+            }
+            Ok(Bytes::from(buffer))
+        }
+        ExportFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            if include_header {
+                writer.write_record([
+                    "id", "username", "email", "first_name", "last_name", "role",
+                    "permissions", "is_active", "created_at", "test_environment", "cleanup_after",
+                ])?;
+            }
+            for user in users {
+                writer.write_record([
+                    user.id.to_string(),
+                    user.username.clone(),
+                    user.email.clone(),
+                    user.first_name.clone().unwrap_or_default(),
+                    user.last_name.clone().unwrap_or_default(),
+                    user.role.to_string(),
+                    user.permissions.join(";"),
+                    user.is_active.to_string(),
+                    user.created_at.to_rfc3339(),
+                    user.test_environment.clone(),
+                    user.cleanup_after.map(|t| t.to_rfc3339()).unwrap_or_default(),
+                ])?;
+            }
+            Ok(Bytes::from(writer.into_inner()?))
+        }
+    }
+}
+
+fn ndjson_line(entity: ExportEntity, data: serde_json::Value) -> Result<Bytes> {
+    let mut line = serde_json::to_vec(&serde_json::json!({
+        "entity_type": entity.to_string(),
+        "data": data,
+    }))?;
+    line.push(b'\n');
+    Ok(Bytes::from(line))
+}
+
+/// Exported environments leave this service, so credentials are masked.
+fn redact_environment_secrets(data: &mut serde_json::Value) {
+    if let Some(secret) = data.pointer_mut("/configuration/authentication/jwt_secret") {
+        *secret = serde_json::json!(REDACTED);
+    }
+
+    if let Some(configs) = data.get_mut("database_configs").and_then(|v| v.as_object_mut()) {
+        for config in configs.values_mut() {
+            if let Some(password) = config.get_mut("password") {
+                *password = serde_json::json!(REDACTED);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_user(username: &str) -> TestUser {
+        TestUser {
+            username: username.to_string(),
+            email: format!("{}@example.com", username),
+            password_hash: "secret-hash".to_string(),
+            permissions: vec!["read".to_string(), "test".to_string()],
+            ..TestUser::default()
+        }
+    }
+
+    #[test]
+    fn test_csv_export_requires_entity_type() {
+        let query = ExportQuery { format: ExportFormat::Csv, entity_type: None };
+        assert!(query.validate().is_err());
+
+        let query = ExportQuery { format: ExportFormat::Csv, entity_type: Some(ExportEntity::Users) };
+        assert!(query.validate().is_ok());
+    }
+
+    #[test]
+    fn test_ndjson_users_omit_password_hash() {
+        let chunk = encode_users(&[test_user("alice"), test_user("bob")], ExportFormat::Ndjson, true).unwrap();
+        let text = String::from_utf8(chunk.to_vec()).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert!(!text.contains("secret-hash"));
+
+        let first: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(first["entity_type"], "users");
+        assert_eq!(first["data"]["username"], "alice");
+    }
+
+    #[test]
+    fn test_csv_header_only_on_first_page() {
+        let first = encode_users(&[test_user("alice")], ExportFormat::Csv, true).unwrap();
+        let next = encode_users(&[test_user("bob")], ExportFormat::Csv, false).unwrap();
+
+        assert!(String::from_utf8_lossy(&first).starts_with("id,username,email"));
+        assert!(!String::from_utf8_lossy(&next).contains("username"));
+        assert_eq!(String::from_utf8_lossy(&next).lines().count(), 1);
+        assert!(String::from_utf8_lossy(&next).contains("read;test"));
+    }
+}
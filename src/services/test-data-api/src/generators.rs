@@ -13,7 +13,13 @@ use faker_rand::en_us::{
 };
 use rand::{prelude::*, thread_rng, Rng};
 use serde_json::Value;
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
@@ -21,12 +27,17 @@ use uuid::Uuid;
 use crate::database::DatabaseManager;
 use crate::models::*;
 
+/// Password given to every generated test user
+const GENERATED_USER_PASSWORD: &str = "GeneratedPassword123!";
+
 // ============================================================================
 // Test Data Generator - AI-Enhanced Data Generation Service
 // ============================================================================
 
 pub struct DataGenerator {
     database: Arc<DatabaseManager>,
+    /// Records built and inserted per batch; progress and cancellation are per batch
+    batch_size: usize,
     generation_jobs: Arc<RwLock<HashMap<Uuid, GenerationJob>>>,
     templates: Arc<RwLock<HashMap<String, DataTemplate>>>,
 }
@@ -42,17 +53,76 @@ struct GenerationJob {
     error_message: Option<String>,
     generated_count: i32,
     output_urls: Vec<String>,
+    entity_counts: HashMap<String, i32>,
+    /// Seed for this job's RNG; reported back so a run can be reproduced
+    seed: u64,
+    started_at: Option<DateTime<Utc>>,
+    cancel_requested: Arc<AtomicBool>,
+}
+
+impl GenerationJob {
+    fn is_cancel_requested(&self) -> bool {
+        self.cancel_requested.load(Ordering::SeqCst)
+    }
+
+    fn is_finished(&self) -> bool {
+        matches!(
+            self.status,
+            GenerationStatus::Completed | GenerationStatus::Failed | GenerationStatus::Cancelled
+        )
+    }
+
+    fn to_response(&self) -> DataGenerationResponse {
+        let estimated_remaining_seconds = match (self.started_at, self.is_finished()) {
+            (_, true) => Some(0),
+            (Some(started_at), false) if self.progress > 0 => {
+                let elapsed = (Utc::now() - started_at).num_seconds().max(0);
+                Some(elapsed * (100 - self.progress.min(100)) as i64 / self.progress as i64)
+            }
+            _ => None,
+        };
+
+        let estimated_completion_time = match (self.completed_at, estimated_remaining_seconds) {
+            (Some(completed_at), _) => completed_at,
+            (None, Some(remaining)) => Utc::now() + chrono::Duration::seconds(remaining),
+            (None, None) => self.created_at + chrono::Duration::seconds(300),
+        };
+
+        DataGenerationResponse {
+            generation_id: self.id,
+            status: format!("{:?}", self.status).to_lowercase(),
+            estimated_completion_time,
+            progress_url: format!("/api/generate-data/{}/status", self.id),
+            generated_count: self.generated_count,
+            total_count: self.request.data_generation.count,
+            data_urls: self.output_urls.clone(),
+            entity_counts: self.entity_counts.clone(),
+            seed: Some(self.seed),
+            progress: self.progress,
+            estimated_remaining_seconds,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 enum GenerationStatus {
     Pending,
     Running,
+    Cancelling,
     Completed,
     Failed,
     Cancelled,
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum GenerationError {
+    #[error("Generation job not found")]
+    NotFound,
+
+    #[error("Data generation already {0}")]
+    AlreadyFinished(String),
+}
+
 #[derive(Debug, Clone)]
 struct DataTemplate {
     name: String,
@@ -81,11 +151,12 @@ enum GeneratorType {
 }
 
 impl DataGenerator {
-    pub async fn new(database: Arc<DatabaseManager>) -> Result<Self> {
+    pub async fn new(database: Arc<DatabaseManager>, batch_size: usize) -> Result<Self> {
         info!("Initializing DataGenerator with AI-enhanced capabilities");
 
         let generator = Self {
             database,
+            batch_size: batch_size.max(1),
             generation_jobs: Arc::new(RwLock::new(HashMap::new())),
             templates: Arc::new(RwLock::new(HashMap::new())),
         };
@@ -101,9 +172,14 @@ impl DataGenerator {
     // Public API Methods
     // ========================================================================
 
+    /// Starts a generation job. With `seed` set, the same request always yields
+    /// the same records (ids, names, emails and field values; timestamps stay
+    /// relative to when the job runs). Without a seed a random one is picked,
+    /// so output stays random but the response still reports the seed used.
     pub async fn generate_data(&self, request: GenerateDataRequest) -> Result<DataGenerationResponse> {
         let generation_id = Uuid::new_v4();
         let now = Utc::now();
+        let seed = request.data_generation.seed.unwrap_or_else(rand::random);
 
         debug!("Starting data generation: {} - {:?}", generation_id, request.data_generation.data_type);
 
@@ -121,6 +197,10 @@ impl DataGenerator {
             error_message: None,
             generated_count: 0,
             output_urls: Vec::new(),
+            entity_counts: HashMap::new(),
+            seed,
+            started_at: None,
+            cancel_requested: Arc::new(AtomicBool::new(false)),
         };
 
         // Store job
@@ -150,6 +230,10 @@ impl DataGenerator {
             generated_count: 0,
             total_count: request.data_generation.count,
             data_urls: Vec::new(),
+            entity_counts: HashMap::new(),
+            seed: Some(seed),
+            progress: 0,
+            estimated_remaining_seconds: None,
         })
     }
 
@@ -158,15 +242,27 @@ impl DataGenerator {
         let job = jobs.get(&generation_id)
             .ok_or_else(|| anyhow!("Generation job not found"))?;
 
-        Ok(DataGenerationResponse {
-            generation_id,
-            status: format!("{:?}", job.status).to_lowercase(),
-            estimated_completion_time: job.completed_at.unwrap_or(job.created_at + chrono::Duration::seconds(300)),
-            progress_url: format!("/api/generate-data/{}/status", generation_id),
-            generated_count: job.generated_count,
-            total_count: job.request.data_generation.count,
-            data_urls: job.output_urls.clone(),
-        })
+        Ok(job.to_response())
+    }
+
+    /// Requests cancellation of a pending or running generation. Batches that
+    /// were already inserted stay; the job stops at the next batch boundary and
+    /// ends up `cancelled` with the number of records it managed to insert.
+    /// Related-data jobs insert everything at the end, so cancelling one
+    /// before that point leaves nothing behind.
+    pub async fn cancel_generation(&self, generation_id: Uuid) -> std::result::Result<DataGenerationResponse, GenerationError> {
+        let mut jobs = self.generation_jobs.write().await;
+        let job = jobs.get_mut(&generation_id).ok_or(GenerationError::NotFound)?;
+
+        if job.is_finished() {
+            return Err(GenerationError::AlreadyFinished(format!("{:?}", job.status).to_lowercase()));
+        }
+
+        info!("Cancellation requested for data generation: {}", generation_id);
+        job.cancel_requested.store(true, Ordering::SeqCst);
+        job.status = GenerationStatus::Cancelling;
+
+        Ok(job.to_response())
     }
 
     // ========================================================================
@@ -176,26 +272,36 @@ impl DataGenerator {
     async fn execute_generation(&self, generation_id: Uuid) -> Result<()> {
         info!("Executing data generation: {}", generation_id);
 
-        // Update status to running
-        self.update_job_status(generation_id, GenerationStatus::Running, 0).await;
-
         let job = {
-            let jobs = self.generation_jobs.read().await;
-            jobs.get(&generation_id).cloned()
-                .ok_or_else(|| anyhow!("Generation job not found"))?
+            let mut jobs = self.generation_jobs.write().await;
+            let job = jobs.get_mut(&generation_id)
+                .ok_or_else(|| anyhow!("Generation job not found"))?;
+
+            // Cancelled before the worker picked it up
+            if job.is_cancel_requested() {
+                drop(jobs);
+                self.mark_generation_cancelled(generation_id).await;
+                return Ok(());
+            }
+
+            job.status = GenerationStatus::Running;
+            job.started_at = Some(Utc::now());
+            job.clone()
         };
 
-        match job.request.data_generation.data_type {
-            DataType::Users => self.generate_users(&job).await?,
-            DataType::Workflows => self.generate_workflows(&job).await?,
-            DataType::TestCases => self.generate_test_cases(&job).await?,
-            DataType::Organizations => self.generate_organizations(&job).await?,
-            DataType::Projects => self.generate_projects(&job).await?,
-            DataType::Documents => self.generate_documents(&job).await?,
-            DataType::Events => self.generate_events(&job).await?,
-            DataType::Metrics => self.generate_metrics(&job).await?,
-            DataType::Logs => self.generate_logs(&job).await?,
-            DataType::Custom(ref custom_type) => self.generate_custom_data(&job, custom_type).await?,
+        // Each job owns its RNG, so concurrent jobs never share random state
+        let mut rng = StdRng::seed_from_u64(job.seed);
+
+        if !job.request.data_generation.relationships.is_empty() {
+            self.generate_related_data(&job, &mut rng).await?;
+        } else {
+            self.generate_batches(&job, &mut rng).await?;
+        }
+
+        if job.is_cancel_requested() {
+            self.mark_generation_cancelled(generation_id).await;
+            info!("Data generation cancelled: {}", generation_id);
+            return Ok(());
         }
 
         // Mark as completed
@@ -205,60 +311,188 @@ impl DataGenerator {
         Ok(())
     }
 
-    async fn generate_users(&self, job: &GenerationJob) -> Result<()> {
-        debug!("Generating {} test users", job.request.data_generation.count);
-
-        let batch_size = 100;
-        let total_count = job.request.data_generation.count;
+    /// Generates `count` records of the requested type, building and inserting
+    /// `batch_size` records at a time. Progress is reported after every batch
+    /// and cancellation is checked before starting the next one.
+    async fn generate_batches(&self, job: &GenerationJob, rng: &mut StdRng) -> Result<()> {
+        let request = &job.request.data_generation;
+        let environment = &job.request.target_environment;
+        let total_count = request.count.max(0) as usize;
+        let password_hash = self.database.hash_password(GENERATED_USER_PASSWORD)?;
         let mut generated_count = 0;
 
-        for batch_start in (0..total_count).step_by(batch_size) {
-            let batch_end = std::cmp::min(batch_start + batch_size as i32, total_count);
-            let batch_count = batch_end - batch_start;
+        debug!("Generating {} {} records in batches of {}", total_count, request.data_type, self.batch_size);
 
-            let mut batch_users = Vec::with_capacity(batch_count as usize);
+        while generated_count < total_count {
+            if job.is_cancel_requested() {
+                info!(
+                    "Stopping data generation {} after {} of {} records",
+                    job.id, generated_count, total_count
+                );
+                break;
+            }
 
-            for i in 0..batch_count {
-                let user = self.generate_test_user(&job.request.target_environment).await?;
-                batch_users.push(user);
-                generated_count += 1;
+            let batch_count = std::cmp::min(self.batch_size, total_count - generated_count);
+            let mut batch = Vec::with_capacity(batch_count);
+            self.build_entities(
+                &request.data_type,
+                vec![HashMap::new(); batch_count],
+                environment,
+                &password_hash,
+                &mut batch,
+                rng,
+            )
+            .await?;
 
-                // Update progress
-                if i % 10 == 0 {
-                    let progress = ((generated_count as f32 / total_count as f32) * 100.0) as u32;
-                    self.update_job_progress(job.id, progress, generated_count).await;
-                }
-            }
+            self.database.insert_generated_entities(job.id, &batch).await?;
+            generated_count += batch_count;
 
-            // Store batch in database
-            for user in batch_users {
-                let create_request = CreateTestUserRequest {
-                    username: user.username,
-                    email: user.email,
-                    password: "GeneratedPassword123!".to_string(),
-                    first_name: user.first_name,
-                    last_name: user.last_name,
-                    role: user.role,
-                    permissions: user.permissions,
-                    metadata: Some(user.metadata),
-                    test_environment: user.test_environment,
-                    ttl_hours: Some(72), // 3 days default
-                };
-
-                self.database.create_test_user(create_request).await?;
-            }
+            let progress = (generated_count * 100 / total_count) as u32;
+            self.update_job_progress(job.id, progress, generated_count as i32).await;
 
             // Small delay to prevent overwhelming the database
             tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
         }
 
+        let mut entity_counts = HashMap::new();
+        entity_counts.insert(request.data_type.to_string(), generated_count as i32);
+        self.update_entity_counts(job.id, entity_counts).await;
+        Ok(())
+    }
+
+    // ========================================================================
+    // Related Data Generation
+    // ========================================================================
+
+    /// Generates the root entity type followed by every related child type,
+    /// wiring each child's foreign key fields to already-generated parents.
+    /// The whole graph is built first and then inserted parents-first in a
+    /// single transaction, so a failure leaves no partial graph behind.
+    async fn generate_related_data(&self, job: &GenerationJob, rng: &mut StdRng) -> Result<()> {
+        let request = &job.request.data_generation;
+        let environment = &job.request.target_environment;
+        let plan = plan_relationship_order(&request.data_type, &request.relationships)?;
+
+        debug!(
+            "Generating related data rooted at {} across {} child entity types",
+            request.data_type, plan.len()
+        );
+
+        // Building the graph is the first 90%; the insert accounts for the rest
+        let total_steps = plan.len() as u32 + 1;
+        let password_hash = self.database.hash_password(GENERATED_USER_PASSWORD)?;
+        let mut entities = Vec::new();
+        let mut generated_ids: HashMap<DataType, Vec<Uuid>> = HashMap::new();
+        let mut entity_counts: HashMap<String, i32> = HashMap::new();
+
+        let root_links = vec![HashMap::new(); request.count as usize];
+        let root_ids = self
+            .build_entities(&request.data_type, root_links, environment, &password_hash, &mut entities, rng)
+            .await?;
+        entity_counts.insert(request.data_type.to_string(), root_ids.len() as i32);
+        generated_ids.insert(request.data_type.clone(), root_ids);
+        self.update_job_progress(job.id, 90 / total_steps, 0).await;
+
+        for (step, (child_type, parents)) in plan.iter().enumerate() {
+            // Nothing has been inserted yet, so cancelling here leaves no records
+            if job.is_cancel_requested() {
+                return Ok(());
+            }
+
+            let links = build_foreign_key_links(parents, &generated_ids, rng)?;
+
+            let child_ids = self
+                .build_entities(child_type, links, environment, &password_hash, &mut entities, rng)
+                .await?;
+            *entity_counts.entry(child_type.to_string()).or_insert(0) += child_ids.len() as i32;
+            generated_ids.insert(child_type.clone(), child_ids);
+
+            let progress = ((step as u32 + 2) * 90) / total_steps;
+            self.update_job_progress(job.id, progress, 0).await;
+        }
+
+        if job.is_cancel_requested() {
+            return Ok(());
+        }
+
+        self.database.insert_generated_entities(job.id, &entities).await?;
+
+        self.update_job_progress(job.id, 100, entities.len() as i32).await;
+        self.update_entity_counts(job.id, entity_counts).await;
         Ok(())
     }
 
-    async fn generate_workflows(&self, job: &GenerationJob) -> Result<()> {
-        debug!("Generating {} test workflows", job.request.data_generation.count);
+    /// Builds one entity of `data_type` per link map, applying the foreign key
+    /// values from that map, and appends them to `entities` for insertion.
+    /// Returns the ids of the built entities in order.
+    async fn build_entities(
+        &self,
+        data_type: &DataType,
+        links: Vec<HashMap<String, Uuid>>,
+        environment: &str,
+        password_hash: &str,
+        entities: &mut Vec<GeneratedEntity>,
+        rng: &mut StdRng,
+    ) -> Result<Vec<Uuid>> {
+        let mut ids = Vec::with_capacity(links.len());
+
+        for foreign_keys in links {
+            if *data_type == DataType::Users {
+                let mut user = self.generate_test_user(environment, rng).await?;
+                user.password_hash = password_hash.to_string();
+                for (field, parent_id) in &foreign_keys {
+                    user.metadata[field.as_str()] = serde_json::json!(parent_id);
+                }
+
+                ids.push(user.id);
+                entities.push(GeneratedEntity::User(user));
+                continue;
+            }
+
+            let mut record = self.build_entity_record(data_type, environment, rng);
+
+            for (field, parent_id) in &foreign_keys {
+                record[field.as_str()] = serde_json::json!(parent_id);
+            }
+
+            let id = record["id"]
+                .as_str()
+                .and_then(|id| Uuid::parse_str(id).ok())
+                .ok_or_else(|| anyhow!("Generated {} record has no id", data_type))?;
+
+            debug!("Generated {} record: {} ({:?})", data_type, id, foreign_keys);
+            ids.push(id);
+            entities.push(GeneratedEntity::Record {
+                id,
+                data_type: data_type.clone(),
+                test_environment: environment.to_string(),
+                data: record,
+            });
+        }
+
+        Ok(ids)
+    }
+
+    fn build_entity_record(&self, data_type: &DataType, environment: &str, rng: &mut StdRng) -> Value {
+        match data_type {
+            DataType::Users | DataType::Custom(_) => self.build_custom_record(&data_type.to_string(), environment, rng),
+            DataType::Workflows => self.build_workflow_record(environment, rng),
+            DataType::TestCases => serde_json::to_value(self.build_test_case(rng)).unwrap_or(Value::Null),
+            DataType::Organizations => self.build_organization_record(environment, rng),
+            DataType::Projects => self.build_project_record(environment, rng),
+            DataType::Documents => self.build_document_record(environment, rng),
+            DataType::Events => self.build_event_record(environment, rng),
+            DataType::Metrics => self.build_metric_record(environment, rng),
+            DataType::Logs => self.build_log_record(environment, rng),
+        }
+    }
+
+    // ========================================================================
+    // Record Builders
+    // ========================================================================
 
-        let workflow_templates = vec![
+    fn build_workflow_record(&self, environment: &str, rng: &mut StdRng) -> Value {
+        let workflow_templates = [
             ("Data Processing Pipeline", "Automated data ingestion and processing"),
             ("User Onboarding Flow", "Complete user registration and verification"),
             ("Invoice Generation", "Automated invoice creation and delivery"),
@@ -271,399 +505,292 @@ impl DataGenerator {
             ("Financial Reporting", "Automated financial data aggregation"),
         ];
 
-        let mut rng = thread_rng();
+        let template = workflow_templates.choose(rng).unwrap();
 
-        for i in 0..job.request.data_generation.count {
-            let template = workflow_templates.choose(&mut rng).unwrap();
-            let workflow_id = Uuid::new_v4();
-
-            let workflow_definition = serde_json::json!({
-                "version": "1.0",
-                "triggers": [
-                    {
-                        "type": "manual",
-                        "name": "Start Process"
-                    }
-                ],
-                "steps": self.generate_workflow_steps(&mut rng),
-                "variables": self.generate_workflow_variables(&mut rng),
-                "error_handling": {
-                    "retry_attempts": rng.gen_range(1..4),
-                    "timeout_minutes": rng.gen_range(5..60),
-                    "fallback_action": "notify_admin"
+        let workflow_definition = serde_json::json!({
+            "version": "1.0",
+            "triggers": [
+                {
+                    "type": "manual",
+                    "name": "Start Process"
                 }
-            });
-
-            let input_schema = serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "priority": {"type": "string", "enum": ["low", "medium", "high"]},
-                    "department": {"type": "string"},
-                    "requester_id": {"type": "string"},
-                    "data": {"type": "object"}
-                },
-                "required": ["priority", "department", "requester_id"]
-            });
-
-            let output_schema = serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "status": {"type": "string", "enum": ["completed", "failed", "cancelled"]},
-                    "result": {"type": "object"},
-                    "execution_time_ms": {"type": "integer"},
-                    "error_message": {"type": "string"}
-                },
-                "required": ["status", "execution_time_ms"]
-            });
+            ],
+            "steps": self.generate_workflow_steps(rng),
+            "variables": self.generate_workflow_variables(rng),
+            "error_handling": {
+                "retry_attempts": rng.gen_range(1..4),
+                "timeout_minutes": rng.gen_range(5..60),
+                "fallback_action": "notify_admin"
+            }
+        });
 
-            // Store workflow (in a real implementation, you'd have a workflows table)
-            debug!("Generated workflow: {} - {}", template.0, workflow_id);
+        let input_schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "priority": {"type": "string", "enum": ["low", "medium", "high"]},
+                "department": {"type": "string"},
+                "requester_id": {"type": "string"},
+                "data": {"type": "object"}
+            },
+            "required": ["priority", "department", "requester_id"]
+        });
 
-            // Update progress
-            if i % 10 == 0 {
-                let progress = ((i as f32 / job.request.data_generation.count as f32) * 100.0) as u32;
-                self.update_job_progress(job.id, progress, i).await;
-            }
-        }
+        let output_schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "status": {"type": "string", "enum": ["completed", "failed", "cancelled"]},
+                "result": {"type": "object"},
+                "execution_time_ms": {"type": "integer"},
+                "error_message": {"type": "string"}
+            },
+            "required": ["status", "execution_time_ms"]
+        });
 
-        Ok(())
+        serde_json::json!({
+            "id": random_uuid(rng),
+            "name": template.0,
+            "description": template.1,
+            "workflow_definition": workflow_definition,
+            "input_schema": input_schema,
+            "output_schema": output_schema,
+            "test_environment": environment
+        })
     }
 
-    async fn generate_test_cases(&self, job: &GenerationJob) -> Result<()> {
-        debug!("Generating {} test cases", job.request.data_generation.count);
-
-        let test_categories = vec![
+    fn build_test_case(&self, rng: &mut StdRng) -> TestCase {
+        let test_categories = [
             "Authentication", "Authorization", "Data Validation", "API Integration",
             "User Interface", "Performance", "Security", "Error Handling",
             "Business Logic", "Workflow Execution", "Data Processing", "Reporting",
         ];
 
-        let assertion_types = vec![
+        let assertion_types = [
             AssertionType::Equals, AssertionType::NotEquals, AssertionType::Contains,
             AssertionType::GreaterThan, AssertionType::LessThan, AssertionType::IsNotNull,
         ];
 
-        let mut rng = thread_rng();
-
-        for i in 0..job.request.data_generation.count {
-            let category = test_categories.choose(&mut rng).unwrap();
-            let test_case_id = Uuid::new_v4();
-
-            let test_case = TestCase {
-                id: test_case_id,
-                name: format!("{} Test Case {}", category, rng.gen_range(1000..9999)),
-                description: Some(format!("Automated test case for {} functionality", category)),
-                input_data: self.generate_test_input_data(&mut rng),
-                expected_output: self.generate_expected_output(&mut rng),
-                assertions: self.generate_test_assertions(&assertion_types, &mut rng),
-                setup_steps: vec![
-                    "Initialize test environment".to_string(),
-                    "Prepare test data".to_string(),
-                    "Configure system settings".to_string(),
-                ],
-                cleanup_steps: vec![
-                    "Clean up test data".to_string(),
-                    "Reset system state".to_string(),
-                    "Archive test results".to_string(),
-                ],
-                timeout_seconds: rng.gen_range(30..300),
-                retry_count: rng.gen_range(0..3),
-            };
-
-            debug!("Generated test case: {} - {}", test_case.name, test_case_id);
-
-            // Update progress
-            if i % 10 == 0 {
-                let progress = ((i as f32 / job.request.data_generation.count as f32) * 100.0) as u32;
-                self.update_job_progress(job.id, progress, i).await;
-            }
+        let category = test_categories.choose(rng).unwrap();
+
+        TestCase {
+            id: random_uuid(rng),
+            name: format!("{} Test Case {}", category, rng.gen_range(1000..9999)),
+            description: Some(format!("Automated test case for {} functionality", category)),
+            input_data: self.generate_test_input_data(rng),
+            expected_output: self.generate_expected_output(rng),
+            assertions: self.generate_test_assertions(&assertion_types, rng),
+            setup_steps: vec![
+                "Initialize test environment".to_string(),
+                "Prepare test data".to_string(),
+                "Configure system settings".to_string(),
+            ],
+            cleanup_steps: vec![
+                "Clean up test data".to_string(),
+                "Reset system state".to_string(),
+                "Archive test results".to_string(),
+            ],
+            timeout_seconds: rng.gen_range(30..300),
+            retry_count: rng.gen_range(0..3),
         }
-
-        Ok(())
     }
 
-    async fn generate_organizations(&self, job: &GenerationJob) -> Result<()> {
-        debug!("Generating {} organizations", job.request.data_generation.count);
-
-        let industry_types = vec![
+    fn build_organization_record(&self, environment: &str, rng: &mut StdRng) -> Value {
+        let industry_types = [
             "Technology", "Healthcare", "Finance", "Manufacturing", "Retail",
             "Education", "Government", "Non-profit", "Consulting", "Media",
         ];
 
-        let company_sizes = vec!["Startup", "Small", "Medium", "Large", "Enterprise"];
-
-        let mut rng = thread_rng();
-
-        for i in 0..job.request.data_generation.count {
-            let org_id = Uuid::new_v4();
-            let company_name: String = CompanyName.fake(&mut rng);
-            let industry = industry_types.choose(&mut rng).unwrap();
-            let size = company_sizes.choose(&mut rng).unwrap();
-
-            let organization = serde_json::json!({
-                "id": org_id,
-                "name": company_name,
-                "industry": industry,
-                "size": size,
-                "employees": rng.gen_range(10..10000),
-                "founded_year": rng.gen_range(1950..2024),
-                "headquarters": {
-                    "city": CityName.fake::<String>(&mut rng),
-                    "state": StateName.fake::<String>(&mut rng),
-                    "country": "USA"
-                },
-                "contact": {
-                    "email": format!("info@{}.com", company_name.to_lowercase().replace(" ", "")),
-                    "phone": PhoneNumber.fake::<String>(&mut rng)
-                },
-                "metadata": {
-                    "test_organization": true,
-                    "generated_at": Utc::now(),
-                    "generator_version": "1.0"
-                }
-            });
+        let company_sizes = ["Startup", "Small", "Medium", "Large", "Enterprise"];
 
-            debug!("Generated organization: {} - {}", company_name, org_id);
+        let company_name: String = CompanyName.fake(rng);
+        let industry = industry_types.choose(rng).unwrap();
+        let size = company_sizes.choose(rng).unwrap();
 
-            // Update progress
-            if i % 10 == 0 {
-                let progress = ((i as f32 / job.request.data_generation.count as f32) * 100.0) as u32;
-                self.update_job_progress(job.id, progress, i).await;
+        serde_json::json!({
+            "id": random_uuid(rng),
+            "name": company_name,
+            "industry": industry,
+            "size": size,
+            "employees": rng.gen_range(10..10000),
+            "founded_year": rng.gen_range(1950..2024),
+            "headquarters": {
+                "city": CityName.fake::<String>(rng),
+                "state": StateName.fake::<String>(rng),
+                "country": "USA"
+            },
+            "contact": {
+                "email": format!("info@{}.com", company_name.to_lowercase().replace(" ", "")),
+                "phone": PhoneNumber.fake::<String>(rng)
+            },
+            "metadata": {
+                "test_organization": true,
+                "environment": environment,
+                "generated_at": Utc::now(),
+                "generator_version": "1.0"
             }
-        }
-
-        Ok(())
+        })
     }
 
-    async fn generate_projects(&self, job: &GenerationJob) -> Result<()> {
-        debug!("Generating {} projects", job.request.data_generation.count);
-
-        let project_types = vec![
+    fn build_project_record(&self, environment: &str, rng: &mut StdRng) -> Value {
+        let project_types = [
             "Web Application", "Mobile App", "API Service", "Data Pipeline",
             "Machine Learning", "DevOps Infrastructure", "Security Audit",
             "Database Migration", "System Integration", "Performance Optimization",
         ];
 
-        let project_statuses = vec!["Planning", "In Progress", "Testing", "Deployment", "Completed", "On Hold"];
-
-        let mut rng = thread_rng();
-
-        for i in 0..job.request.data_generation.count {
-            let project_id = Uuid::new_v4();
-            let project_type = project_types.choose(&mut rng).unwrap();
-            let status = project_statuses.choose(&mut rng).unwrap();
-
-            let project = serde_json::json!({
-                "id": project_id,
-                "name": format!("{} Project {}", project_type, rng.gen_range(1000..9999)),
-                "description": format!("Test project for {} development and testing", project_type),
-                "type": project_type,
-                "status": status,
-                "priority": ["Low", "Medium", "High", "Critical"].choose(&mut rng).unwrap(),
-                "budget": rng.gen_range(10000.0..1000000.0),
-                "timeline": {
-                    "start_date": Utc::now() - chrono::Duration::days(rng.gen_range(1..365)),
-                    "end_date": Utc::now() + chrono::Duration::days(rng.gen_range(30..365)),
-                    "estimated_hours": rng.gen_range(100..5000)
-                },
-                "team": {
-                    "lead_id": Uuid::new_v4(),
-                    "member_count": rng.gen_range(3..15),
-                    "skills_required": ["Development", "Testing", "Design", "DevOps"]
-                },
-                "metadata": {
-                    "test_project": true,
-                    "environment": job.request.target_environment,
-                    "generated_at": Utc::now()
-                }
-            });
+        let project_statuses = ["Planning", "In Progress", "Testing", "Deployment", "Completed", "On Hold"];
 
-            debug!("Generated project: {} - {}", project["name"], project_id);
+        let project_type = project_types.choose(rng).unwrap();
+        let status = project_statuses.choose(rng).unwrap();
 
-            // Update progress
-            if i % 10 == 0 {
-                let progress = ((i as f32 / job.request.data_generation.count as f32) * 100.0) as u32;
-                self.update_job_progress(job.id, progress, i).await;
+        serde_json::json!({
+            "id": random_uuid(rng),
+            "name": format!("{} Project {}", project_type, rng.gen_range(1000..9999)),
+            "description": format!("Test project for {} development and testing", project_type),
+            "type": project_type,
+            "status": status,
+            "priority": ["Low", "Medium", "High", "Critical"].choose(rng).unwrap(),
+            "budget": rng.gen_range(10000.0..1000000.0),
+            "timeline": {
+                "start_date": Utc::now() - chrono::Duration::days(rng.gen_range(1..365)),
+                "end_date": Utc::now() + chrono::Duration::days(rng.gen_range(30..365)),
+                "estimated_hours": rng.gen_range(100..5000)
+            },
+            "team": {
+                "lead_id": random_uuid(rng),
+                "member_count": rng.gen_range(3..15),
+                "skills_required": ["Development", "Testing", "Design", "DevOps"]
+            },
+            "metadata": {
+                "test_project": true,
+                "environment": environment,
+                "generated_at": Utc::now()
             }
-        }
-
-        Ok(())
+        })
     }
 
-    async fn generate_documents(&self, job: &GenerationJob) -> Result<()> {
-        debug!("Generating {} documents", job.request.data_generation.count);
-
-        let document_types = vec![
+    fn build_document_record(&self, environment: &str, rng: &mut StdRng) -> Value {
+        let document_types = [
             "User Manual", "API Documentation", "Test Plan", "Requirements Specification",
             "Design Document", "Meeting Notes", "Project Report", "Technical Specification",
             "User Guide", "Installation Instructions", "Troubleshooting Guide", "FAQ",
         ];
 
-        let mut rng = thread_rng();
-
-        for i in 0..job.request.data_generation.count {
-            let doc_id = Uuid::new_v4();
-            let doc_type = document_types.choose(&mut rng).unwrap();
-
-            let document = serde_json::json!({
-                "id": doc_id,
-                "title": format!("{} v{}.{}", doc_type, rng.gen_range(1..5), rng.gen_range(0..10)),
-                "type": doc_type,
-                "content": format!("This is a generated {} for testing purposes. It contains sample content that would typically be found in this type of document.", doc_type),
-                "author": {
-                    "name": format!("{} {}", FirstName.fake::<String>(&mut rng), LastName.fake::<String>(&mut rng)),
-                    "email": Email.fake::<String>(&mut rng)
-                },
-                "version": format!("{}.{}.{}", rng.gen_range(1..5), rng.gen_range(0..10), rng.gen_range(0..100)),
-                "status": ["Draft", "Review", "Approved", "Published", "Archived"].choose(&mut rng).unwrap(),
-                "tags": ["test", "generated", "documentation"],
-                "created_at": Utc::now() - chrono::Duration::days(rng.gen_range(1..365)),
-                "updated_at": Utc::now() - chrono::Duration::days(rng.gen_range(0..30)),
-                "word_count": rng.gen_range(500..5000),
-                "metadata": {
-                    "test_document": true,
-                    "environment": job.request.target_environment
-                }
-            });
-
-            debug!("Generated document: {} - {}", document["title"], doc_id);
+        let doc_type = document_types.choose(rng).unwrap();
 
-            // Update progress
-            if i % 10 == 0 {
-                let progress = ((i as f32 / job.request.data_generation.count as f32) * 100.0) as u32;
-                self.update_job_progress(job.id, progress, i).await;
+        serde_json::json!({
+            "id": random_uuid(rng),
+            "title": format!("{} v{}.{}", doc_type, rng.gen_range(1..5), rng.gen_range(0..10)),
+            "type": doc_type,
+            "content": format!("This is a generated {} for testing purposes. It contains sample content that would typically be found in this type of document.", doc_type),
+            "author": {
+                "name": format!("{} {}", FirstName.fake::<String>(rng), LastName.fake::<String>(rng)),
+                "email": Email.fake::<String>(rng)
+            },
+            "version": format!("{}.{}.{}", rng.gen_range(1..5), rng.gen_range(0..10), rng.gen_range(0..100)),
+            "status": ["Draft", "Review", "Approved", "Published", "Archived"].choose(rng).unwrap(),
+            "tags": ["test", "generated", "documentation"],
+            "created_at": Utc::now() - chrono::Duration::days(rng.gen_range(1..365)),
+            "updated_at": Utc::now() - chrono::Duration::days(rng.gen_range(0..30)),
+            "word_count": rng.gen_range(500..5000),
+            "metadata": {
+                "test_document": true,
+                "environment": environment
             }
-        }
-
-        Ok(())
+        })
     }
 
-    async fn generate_events(&self, job: &GenerationJob) -> Result<()> {
-        debug!("Generating {} events", job.request.data_generation.count);
-
-        let event_types = vec![
+    fn build_event_record(&self, environment: &str, rng: &mut StdRng) -> Value {
+        let event_types = [
             "user.login", "user.logout", "user.created", "user.updated", "user.deleted",
             "workflow.started", "workflow.completed", "workflow.failed",
             "api.request", "api.error", "system.startup", "system.shutdown",
             "data.imported", "data.exported", "backup.created", "backup.restored",
         ];
 
-        let severity_levels = vec!["info", "warning", "error", "critical"];
-
-        let mut rng = thread_rng();
-
-        for i in 0..job.request.data_generation.count {
-            let event_id = Uuid::new_v4();
-            let event_type = event_types.choose(&mut rng).unwrap();
-            let severity = severity_levels.choose(&mut rng).unwrap();
-
-            let event = serde_json::json!({
-                "id": event_id,
-                "type": event_type,
-                "severity": severity,
-                "timestamp": Utc::now() - chrono::Duration::seconds(rng.gen_range(0..86400)), // Last 24 hours
-                "source": format!("service-{}", rng.gen_range(1..10)),
-                "user_id": if event_type.starts_with("user.") { Some(Uuid::new_v4()) } else { None },
-                "session_id": Uuid::new_v4(),
-                "ip_address": format!("{}.{}.{}.{}",
-                    rng.gen_range(1..255), rng.gen_range(1..255),
-                    rng.gen_range(1..255), rng.gen_range(1..255)),
-                "user_agent": "Mozilla/5.0 (TestBot/1.0)",
-                "details": {
-                    "message": format!("Generated test event for {}", event_type),
-                    "duration_ms": rng.gen_range(1..5000),
-                    "status_code": if event_type.starts_with("api.") { Some(rng.gen_range(200..500)) } else { None },
-                    "error_code": if severity == "error" || severity == "critical" {
-                        Some(format!("ERR_{}", rng.gen_range(1000..9999)))
-                    } else { None }
-                },
-                "metadata": {
-                    "test_event": true,
-                    "environment": job.request.target_environment,
-                    "correlation_id": Uuid::new_v4()
-                }
-            });
+        let severity_levels = ["info", "warning", "error", "critical"];
 
-            debug!("Generated event: {} - {}", event_type, event_id);
+        let event_type = event_types.choose(rng).unwrap();
+        let severity = severity_levels.choose(rng).unwrap();
 
-            // Update progress
-            if i % 10 == 0 {
-                let progress = ((i as f32 / job.request.data_generation.count as f32) * 100.0) as u32;
-                self.update_job_progress(job.id, progress, i).await;
+        serde_json::json!({
+            "id": random_uuid(rng),
+            "type": event_type,
+            "severity": severity,
+            "timestamp": Utc::now() - chrono::Duration::seconds(rng.gen_range(0..86400)), // Last 24 hours
+            "source": format!("service-{}", rng.gen_range(1..10)),
+            "user_id": if event_type.starts_with("user.") { Some(random_uuid(rng)) } else { None },
+            "session_id": random_uuid(rng),
+            "ip_address": format!("{}.{}.{}.{}",
+                rng.gen_range(1..255), rng.gen_range(1..255),
+                rng.gen_range(1..255), rng.gen_range(1..255)),
+            "user_agent": "Mozilla/5.0 (TestBot/1.0)",
+            "details": {
+                "message": format!("Generated test event for {}", event_type),
+                "duration_ms": rng.gen_range(1..5000),
+                "status_code": if event_type.starts_with("api.") { Some(rng.gen_range(200..500)) } else { None },
+                "error_code": if *severity == "error" || *severity == "critical" {
+                    Some(format!("ERR_{}", rng.gen_range(1000..9999)))
+                } else { None }
+            },
+            "metadata": {
+                "test_event": true,
+                "environment": environment,
+                "correlation_id": random_uuid(rng)
             }
-        }
-
-        Ok(())
+        })
     }
 
-    async fn generate_metrics(&self, job: &GenerationJob) -> Result<()> {
-        debug!("Generating {} metrics", job.request.data_generation.count);
-
-        let metric_names = vec![
+    fn build_metric_record(&self, environment: &str, rng: &mut StdRng) -> Value {
+        let metric_names = [
             "cpu_usage_percent", "memory_usage_percent", "disk_usage_percent",
             "network_bytes_in", "network_bytes_out", "response_time_ms",
             "requests_per_second", "error_rate_percent", "active_connections",
             "queue_length", "cache_hit_rate", "database_connections",
         ];
 
-        let mut rng = thread_rng();
-
-        for i in 0..job.request.data_generation.count {
-            let metric_name = metric_names.choose(&mut rng).unwrap();
-            let timestamp = Utc::now() - chrono::Duration::seconds(rng.gen_range(0..3600)); // Last hour
-
-            let value = match metric_name.as_ref() {
-                "cpu_usage_percent" | "memory_usage_percent" | "disk_usage_percent" => rng.gen_range(0.0..100.0),
-                "network_bytes_in" | "network_bytes_out" => rng.gen_range(1000.0..1000000.0),
-                "response_time_ms" => rng.gen_range(10.0..2000.0),
-                "requests_per_second" => rng.gen_range(1.0..1000.0),
-                "error_rate_percent" => rng.gen_range(0.0..10.0),
-                "active_connections" => rng.gen_range(1.0..500.0),
-                "queue_length" => rng.gen_range(0.0..100.0),
-                "cache_hit_rate" => rng.gen_range(70.0..99.0),
-                "database_connections" => rng.gen_range(1.0..50.0),
-                _ => rng.gen_range(0.0..1000.0),
-            };
-
-            let metric = serde_json::json!({
-                "name": metric_name,
-                "value": value,
-                "timestamp": timestamp,
-                "unit": self.get_metric_unit(metric_name),
-                "tags": {
-                    "service": format!("service-{}", rng.gen_range(1..5)),
-                    "environment": job.request.target_environment,
-                    "host": format!("host-{}", rng.gen_range(1..10)),
-                    "region": ["us-east-1", "us-west-2", "eu-west-1"].choose(&mut rng).unwrap()
-                },
-                "metadata": {
-                    "test_metric": true,
-                    "generator_version": "1.0"
-                }
-            });
-
-            debug!("Generated metric: {} = {} at {}", metric_name, value, timestamp);
+        let metric_name = metric_names.choose(rng).unwrap();
+        let timestamp = Utc::now() - chrono::Duration::seconds(rng.gen_range(0..3600)); // Last hour
+
+        let value = match *metric_name {
+            "cpu_usage_percent" | "memory_usage_percent" | "disk_usage_percent" => rng.gen_range(0.0..100.0),
+            "network_bytes_in" | "network_bytes_out" => rng.gen_range(1000.0..1000000.0),
+            "response_time_ms" => rng.gen_range(10.0..2000.0),
+            "requests_per_second" => rng.gen_range(1.0..1000.0),
+            "error_rate_percent" => rng.gen_range(0.0..10.0),
+            "active_connections" => rng.gen_range(1.0..500.0),
+            "queue_length" => rng.gen_range(0.0..100.0),
+            "cache_hit_rate" => rng.gen_range(70.0..99.0),
+            "database_connections" => rng.gen_range(1.0..50.0),
+            _ => rng.gen_range(0.0..1000.0),
+        };
 
-            // Update progress
-            if i % 10 == 0 {
-                let progress = ((i as f32 / job.request.data_generation.count as f32) * 100.0) as u32;
-                self.update_job_progress(job.id, progress, i).await;
+        serde_json::json!({
+            "id": random_uuid(rng),
+            "name": metric_name,
+            "value": value,
+            "timestamp": timestamp,
+            "unit": self.get_metric_unit(metric_name),
+            "tags": {
+                "service": format!("service-{}", rng.gen_range(1..5)),
+                "environment": environment,
+                "host": format!("host-{}", rng.gen_range(1..10)),
+                "region": ["us-east-1", "us-west-2", "eu-west-1"].choose(rng).unwrap()
+            },
+            "metadata": {
+                "test_metric": true,
+                "generator_version": "1.0"
             }
-        }
-
-        Ok(())
+        })
     }
 
-    async fn generate_logs(&self, job: &GenerationJob) -> Result<()> {
-        debug!("Generating {} log entries", job.request.data_generation.count);
-
-        let log_levels = vec!["DEBUG", "INFO", "WARN", "ERROR", "FATAL"];
-        let services = vec![
+    fn build_log_record(&self, environment: &str, rng: &mut StdRng) -> Value {
+        let log_levels = ["DEBUG", "INFO", "WARN", "ERROR", "FATAL"];
+        let services = [
             "api-gateway", "user-service", "auth-service", "workflow-engine",
             "data-processor", "notification-service", "file-storage", "analytics",
         ];
 
-        let log_messages = vec![
+        let log_messages = [
             "User authentication successful",
             "Processing workflow step",
             "Database connection established",
@@ -676,85 +803,55 @@ impl DataGenerator {
             "Metric collection complete",
         ];
 
-        let mut rng = thread_rng();
-
-        for i in 0..job.request.data_generation.count {
-            let level = log_levels.choose(&mut rng).unwrap();
-            let service = services.choose(&mut rng).unwrap();
-            let message = log_messages.choose(&mut rng).unwrap();
-            let timestamp = Utc::now() - chrono::Duration::seconds(rng.gen_range(0..7200)); // Last 2 hours
-
-            let log_entry = serde_json::json!({
-                "timestamp": timestamp,
-                "level": level,
-                "service": service,
-                "message": message,
-                "request_id": Uuid::new_v4(),
-                "user_id": if rng.gen_bool(0.7) { Some(Uuid::new_v4()) } else { None },
-                "session_id": if rng.gen_bool(0.8) { Some(Uuid::new_v4()) } else { None },
-                "duration_ms": rng.gen_range(1..1000),
-                "details": {
-                    "method": ["GET", "POST", "PUT", "DELETE"].choose(&mut rng).unwrap(),
-                    "path": format!("/api/v1/{}", ["users", "workflows", "data", "health"].choose(&mut rng).unwrap()),
-                    "status_code": if level == "ERROR" { rng.gen_range(400..500) } else { rng.gen_range(200..300) },
-                    "response_size": rng.gen_range(100..10000)
-                },
-                "metadata": {
-                    "test_log": true,
-                    "environment": job.request.target_environment,
-                    "host": format!("host-{}", rng.gen_range(1..5)),
-                    "version": "1.0.0"
-                }
-            });
-
-            debug!("Generated log: {} - {} - {}", level, service, message);
+        let level = log_levels.choose(rng).unwrap();
+        let service = services.choose(rng).unwrap();
+        let message = log_messages.choose(rng).unwrap();
+        let timestamp = Utc::now() - chrono::Duration::seconds(rng.gen_range(0..7200)); // Last 2 hours
 
-            // Update progress
-            if i % 10 == 0 {
-                let progress = ((i as f32 / job.request.data_generation.count as f32) * 100.0) as u32;
-                self.update_job_progress(job.id, progress, i).await;
+        serde_json::json!({
+            "id": random_uuid(rng),
+            "timestamp": timestamp,
+            "level": level,
+            "service": service,
+            "message": message,
+            "request_id": random_uuid(rng),
+            "user_id": if rng.gen_bool(0.7) { Some(random_uuid(rng)) } else { None },
+            "session_id": if rng.gen_bool(0.8) { Some(random_uuid(rng)) } else { None },
+            "duration_ms": rng.gen_range(1..1000),
+            "details": {
+                "method": ["GET", "POST", "PUT", "DELETE"].choose(rng).unwrap(),
+                "path": format!("/api/v1/{}", ["users", "workflows", "data", "health"].choose(rng).unwrap()),
+                "status_code": if *level == "ERROR" { rng.gen_range(400..500) } else { rng.gen_range(200..300) },
+                "response_size": rng.gen_range(100..10000)
+            },
+            "metadata": {
+                "test_log": true,
+                "environment": environment,
+                "host": format!("host-{}", rng.gen_range(1..5)),
+                "version": "1.0.0"
             }
-        }
-
-        Ok(())
+        })
     }
 
-    async fn generate_custom_data(&self, job: &GenerationJob, custom_type: &str) -> Result<()> {
-        debug!("Generating {} custom data items of type: {}", job.request.data_generation.count, custom_type);
-
-        // This would be extended based on custom requirements
-        for i in 0..job.request.data_generation.count {
-            let custom_data = serde_json::json!({
-                "id": Uuid::new_v4(),
-                "type": custom_type,
-                "data": {
-                    "generated": true,
-                    "timestamp": Utc::now(),
-                    "environment": job.request.target_environment
-                }
-            });
-
-            debug!("Generated custom data: {} - {}", custom_type, custom_data["id"]);
-
-            // Update progress
-            if i % 10 == 0 {
-                let progress = ((i as f32 / job.request.data_generation.count as f32) * 100.0) as u32;
-                self.update_job_progress(job.id, progress, i).await;
+    fn build_custom_record(&self, custom_type: &str, environment: &str, rng: &mut StdRng) -> Value {
+        serde_json::json!({
+            "id": random_uuid(rng),
+            "type": custom_type,
+            "data": {
+                "generated": true,
+                "timestamp": Utc::now(),
+                "environment": environment
             }
-        }
-
-        Ok(())
+        })
     }
 
     // ========================================================================
     // Helper Methods
     // ========================================================================
 
-    async fn generate_test_user(&self, environment: &str) -> Result<TestUser> {
-        let mut rng = thread_rng();
-
-        let first_name: String = FirstName.fake(&mut rng);
-        let last_name: String = LastName.fake(&mut rng);
+    async fn generate_test_user(&self, environment: &str, rng: &mut StdRng) -> Result<TestUser> {
+        let first_name: String = FirstName.fake(rng);
+        let last_name: String = LastName.fake(rng);
         let username = format!("{}_{}", first_name.to_lowercase(), rng.gen_range(1000..9999));
         let email = format!("{}@test-{}.com", username, environment);
 
@@ -763,7 +860,7 @@ impl DataGenerator {
             UserRole::Tester, UserRole::Manager
         ];
 
-        let permissions = match roles.choose(&mut rng).unwrap() {
+        let permissions = match roles.choose(rng).unwrap() {
             UserRole::Admin => vec!["*".to_string()],
             UserRole::Manager => vec!["read".to_string(), "write".to_string(), "manage".to_string()],
             UserRole::Developer => vec!["read".to_string(), "write".to_string(), "deploy".to_string()],
@@ -774,13 +871,13 @@ impl DataGenerator {
         };
 
         Ok(TestUser {
-            id: Uuid::new_v4(),
+            id: random_uuid(rng),
             username,
             email,
             password_hash: "hashed_password".to_string(),
             first_name: Some(first_name),
             last_name: Some(last_name),
-            role: roles.choose(&mut rng).unwrap().clone(),
+            role: roles.choose(rng).unwrap().clone(),
             permissions,
             metadata: serde_json::json!({
                 "generated": true,
@@ -796,7 +893,7 @@ impl DataGenerator {
         })
     }
 
-    fn generate_workflow_steps(&self, rng: &mut ThreadRng) -> Vec<Value> {
+    fn generate_workflow_steps(&self, rng: &mut StdRng) -> Vec<Value> {
         let step_count = rng.gen_range(3..8);
         let mut steps = Vec::new();
 
@@ -816,7 +913,7 @@ impl DataGenerator {
         steps
     }
 
-    fn generate_workflow_variables(&self, rng: &mut ThreadRng) -> HashMap<String, Value> {
+    fn generate_workflow_variables(&self, rng: &mut StdRng) -> HashMap<String, Value> {
         let mut variables = HashMap::new();
 
         variables.insert("priority".to_string(), serde_json::json!("medium"));
@@ -827,7 +924,7 @@ impl DataGenerator {
         variables
     }
 
-    fn generate_test_input_data(&self, rng: &mut ThreadRng) -> Value {
+    fn generate_test_input_data(&self, rng: &mut StdRng) -> Value {
         serde_json::json!({
             "test_parameter_1": format!("value_{}", rng.gen_range(1000..9999)),
             "test_parameter_2": rng.gen_range(1..100),
@@ -839,7 +936,7 @@ impl DataGenerator {
         })
     }
 
-    fn generate_expected_output(&self, rng: &mut ThreadRng) -> Value {
+    fn generate_expected_output(&self, rng: &mut StdRng) -> Value {
         serde_json::json!({
             "status": "success",
             "result_code": rng.gen_range(200..300),
@@ -852,7 +949,7 @@ impl DataGenerator {
         })
     }
 
-    fn generate_test_assertions(&self, assertion_types: &[AssertionType], rng: &mut ThreadRng) -> Vec<TestAssertion> {
+    fn generate_test_assertions(&self, assertion_types: &[AssertionType], rng: &mut StdRng) -> Vec<TestAssertion> {
         let count = rng.gen_range(2..6);
         let mut assertions = Vec::new();
 
@@ -907,6 +1004,30 @@ impl DataGenerator {
             return Err(anyhow!("Target environment cannot be empty"));
         }
 
+        for relationship in &request.data_generation.relationships {
+            let children = &relationship.children_per_parent;
+            if children.min < 0 || children.max < children.min || children.max > 1000 {
+                return Err(anyhow!(
+                    "Invalid children_per_parent for {} -> {}: expected 0 <= min <= max <= 1000",
+                    relationship.parent_type, relationship.child_type
+                ));
+            }
+
+            if relationship.foreign_key_field.is_empty() {
+                return Err(anyhow!(
+                    "Relationship {} -> {} must name a foreign_key_field",
+                    relationship.parent_type, relationship.child_type
+                ));
+            }
+        }
+
+        if !request.data_generation.relationships.is_empty() {
+            plan_relationship_order(
+                &request.data_generation.data_type,
+                &request.data_generation.relationships,
+            )?;
+        }
+
         Ok(())
     }
 
@@ -936,43 +1057,163 @@ impl DataGenerator {
         Ok(())
     }
 
-    async fn update_job_status(&self, job_id: Uuid, status: GenerationStatus, progress: u32) {
-        if let Ok(mut jobs) = self.generation_jobs.try_write() {
-            if let Some(job) = jobs.get_mut(&job_id) {
-                job.status = status;
-                job.progress = progress;
-            }
+    async fn update_job_progress(&self, job_id: Uuid, progress: u32, generated_count: i32) {
+        let mut jobs = self.generation_jobs.write().await;
+        if let Some(job) = jobs.get_mut(&job_id) {
+            job.progress = progress;
+            job.generated_count = generated_count;
         }
     }
 
-    async fn update_job_progress(&self, job_id: Uuid, progress: u32, generated_count: i32) {
-        if let Ok(mut jobs) = self.generation_jobs.try_write() {
-            if let Some(job) = jobs.get_mut(&job_id) {
-                job.progress = progress;
-                job.generated_count = generated_count;
-            }
+    async fn update_entity_counts(&self, job_id: Uuid, entity_counts: HashMap<String, i32>) {
+        let mut jobs = self.generation_jobs.write().await;
+        if let Some(job) = jobs.get_mut(&job_id) {
+            job.entity_counts = entity_counts;
         }
     }
 
     async fn mark_generation_completed(&self, job_id: Uuid) {
-        if let Ok(mut jobs) = self.generation_jobs.try_write() {
-            if let Some(job) = jobs.get_mut(&job_id) {
-                job.status = GenerationStatus::Completed;
-                job.progress = 100;
-                job.completed_at = Some(Utc::now());
-            }
+        let mut jobs = self.generation_jobs.write().await;
+        if let Some(job) = jobs.get_mut(&job_id) {
+            job.status = GenerationStatus::Completed;
+            job.progress = 100;
+            job.completed_at = Some(Utc::now());
+        }
+    }
+
+    async fn mark_generation_cancelled(&self, job_id: Uuid) {
+        let mut jobs = self.generation_jobs.write().await;
+        if let Some(job) = jobs.get_mut(&job_id) {
+            job.status = GenerationStatus::Cancelled;
+            job.completed_at = Some(Utc::now());
         }
     }
 
     async fn mark_generation_failed(&self, job_id: Uuid, error_message: String) {
-        if let Ok(mut jobs) = self.generation_jobs.try_write() {
-            if let Some(job) = jobs.get_mut(&job_id) {
-                job.status = GenerationStatus::Failed;
-                job.error_message = Some(error_message);
-                job.completed_at = Some(Utc::now());
+        let mut jobs = self.generation_jobs.write().await;
+        if let Some(job) = jobs.get_mut(&job_id) {
+            job.status = GenerationStatus::Failed;
+            job.error_message = Some(error_message);
+            job.completed_at = Some(Utc::now());
+        }
+    }
+}
+
+// ============================================================================
+// Relationship Planning
+// ============================================================================
+
+/// Orders the child entity types of a relationship graph so every parent type is
+/// generated before any child that references it. Each entry carries the
+/// relationships that point at that child; the first one drives how many
+/// children are created, any others only contribute foreign keys.
+fn plan_relationship_order(
+    root: &DataType,
+    relationships: &[DataRelationship],
+) -> Result<Vec<(DataType, Vec<DataRelationship>)>> {
+    let mut generated = vec![root.clone()];
+    let mut pending: Vec<DataType> = Vec::new();
+
+    for relationship in relationships {
+        if relationship.child_type == *root {
+            return Err(anyhow!(
+                "Relationship {} -> {} points back at the root entity type",
+                relationship.parent_type, relationship.child_type
+            ));
+        }
+
+        if !pending.contains(&relationship.child_type) {
+            pending.push(relationship.child_type.clone());
+        }
+    }
+
+    let mut plan = Vec::with_capacity(pending.len());
+
+    while !pending.is_empty() {
+        let ready = pending.iter().position(|child| {
+            relationships
+                .iter()
+                .filter(|relationship| relationship.child_type == *child)
+                .all(|relationship| generated.contains(&relationship.parent_type))
+        });
+
+        let Some(index) = ready else {
+            let unresolved: Vec<String> = pending.iter().map(|t| t.to_string()).collect();
+            return Err(anyhow!(
+                "Relationship templates contain a cycle or an unreachable parent for: {}",
+                unresolved.join(", ")
+            ));
+        };
+
+        let child = pending.remove(index);
+        let parents = relationships
+            .iter()
+            .filter(|relationship| relationship.child_type == child)
+            .cloned()
+            .collect();
+
+        generated.push(child.clone());
+        plan.push((child, parents));
+    }
+
+    Ok(plan)
+}
+
+/// A version 4 UUID drawn from `rng`, so seeded runs reproduce record ids
+fn random_uuid(rng: &mut impl Rng) -> Uuid {
+    uuid::Builder::from_random_bytes(rng.gen()).into_uuid()
+}
+
+/// Builds one foreign key map per child record to generate. The first
+/// relationship fans out `children_per_parent` children for each parent id;
+/// secondary relationships attach a randomly chosen existing parent.
+fn build_foreign_key_links(
+    parents: &[DataRelationship],
+    generated_ids: &HashMap<DataType, Vec<Uuid>>,
+    rng: &mut impl Rng,
+) -> Result<Vec<HashMap<String, Uuid>>> {
+    let (primary, secondary) = parents
+        .split_first()
+        .ok_or_else(|| anyhow!("Child entity type has no parent relationship"))?;
+
+    let primary_ids = generated_ids
+        .get(&primary.parent_type)
+        .ok_or_else(|| anyhow!("Parent {} has not been generated", primary.parent_type))?;
+
+    let mut links = Vec::new();
+
+    for parent_id in primary_ids {
+        let children = &primary.children_per_parent;
+        let mut count = rng.gen_range(children.min..=children.max);
+        if matches!(primary.cardinality, Cardinality::Required) {
+            count = count.max(1);
+        }
+
+        for _ in 0..count {
+            let mut foreign_keys = HashMap::new();
+            foreign_keys.insert(primary.foreign_key_field.clone(), *parent_id);
+
+            for relationship in secondary {
+                let candidates = generated_ids.get(&relationship.parent_type).map(Vec::as_slice).unwrap_or(&[]);
+                match candidates.choose(rng) {
+                    Some(candidate) => {
+                        foreign_keys.insert(relationship.foreign_key_field.clone(), *candidate);
+                    }
+                    None if matches!(relationship.cardinality, Cardinality::Required) => {
+                        return Err(anyhow!(
+                            "Required parent {} has no generated records for {}",
+                            relationship.parent_type, relationship.foreign_key_field
+                        ));
+                    }
+                    None => {}
+                }
             }
+
+            links.push(foreign_keys);
         }
     }
+
+    Ok(links)
 }
 
 // ============================================================================
@@ -983,8 +1224,152 @@ impl Clone for DataGenerator {
     fn clone(&self) -> Self {
         Self {
             database: self.database.clone(),
+            batch_size: self.batch_size,
             generation_jobs: self.generation_jobs.clone(),
             templates: self.templates.clone(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_job(count: i32) -> GenerationJob {
+        GenerationJob {
+            id: Uuid::new_v4(),
+            request: GenerateDataRequest {
+                data_generation: DataGenerationRequest {
+                    data_type: DataType::Users,
+                    count,
+                    template: None,
+                    constraints: None,
+                    relationships: Vec::new(),
+                    output_format: OutputFormat::Json,
+                    seed: None,
+                },
+                target_environment: "test".to_string(),
+                cleanup_strategy: CleanupStrategy::Manual,
+                notification_webhook: None,
+            },
+            status: GenerationStatus::Running,
+            progress: 0,
+            created_at: Utc::now(),
+            completed_at: None,
+            error_message: None,
+            generated_count: 0,
+            output_urls: Vec::new(),
+            entity_counts: HashMap::new(),
+            seed: 7,
+            started_at: Some(Utc::now() - chrono::Duration::seconds(30)),
+            cancel_requested: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    #[test]
+    fn test_progress_response_estimates_remaining_time() {
+        let mut job = test_job(10_000);
+        job.progress = 25;
+        job.generated_count = 2_500;
+
+        let response = job.to_response();
+        assert_eq!(response.progress, 25);
+        assert_eq!(response.generated_count, 2_500);
+        assert_eq!(response.total_count, 10_000);
+
+        let remaining = response.estimated_remaining_seconds.unwrap();
+        assert!((85..=95).contains(&remaining));
+        assert!(response.estimated_completion_time > Utc::now());
+    }
+
+    #[test]
+    fn test_cancelled_job_reports_generated_records() {
+        let mut job = test_job(10_000);
+        job.cancel_requested.store(true, Ordering::SeqCst);
+        job.status = GenerationStatus::Cancelled;
+        job.completed_at = Some(Utc::now());
+        job.progress = 30;
+        job.generated_count = 3_000;
+
+        let response = job.to_response();
+        assert!(job.is_finished());
+        assert_eq!(response.status, "cancelled");
+        assert_eq!(response.generated_count, 3_000);
+        assert_eq!(response.estimated_remaining_seconds, Some(0));
+    }
+
+    fn relationship(parent: DataType, child: DataType, field: &str, min: i32, max: i32) -> DataRelationship {
+        DataRelationship {
+            parent_type: parent,
+            child_type: child,
+            relationship_type: RelationshipType::OneToMany,
+            cardinality: Cardinality::Optional,
+            foreign_key_field: field.to_string(),
+            children_per_parent: ChildrenPerParent { min, max },
+        }
+    }
+
+    #[test]
+    fn test_plan_orders_parents_before_children() {
+        let relationships = vec![
+            relationship(DataType::Workflows, DataType::Events, "workflow_id", 1, 1),
+            relationship(DataType::Users, DataType::Workflows, "owner_id", 1, 1),
+            relationship(DataType::Users, DataType::Events, "user_id", 1, 1),
+        ];
+
+        let plan = plan_relationship_order(&DataType::Users, &relationships).unwrap();
+        let order: Vec<DataType> = plan.iter().map(|(child, _)| child.clone()).collect();
+
+        assert_eq!(order, vec![DataType::Workflows, DataType::Events]);
+        assert_eq!(plan[1].1.len(), 2);
+    }
+
+    #[test]
+    fn test_plan_rejects_cycles() {
+        let relationships = vec![
+            relationship(DataType::Users, DataType::Workflows, "owner_id", 1, 1),
+            relationship(DataType::Events, DataType::Projects, "event_id", 1, 1),
+            relationship(DataType::Projects, DataType::Events, "project_id", 1, 1),
+        ];
+
+        assert!(plan_relationship_order(&DataType::Users, &relationships).is_err());
+    }
+
+    #[test]
+    fn test_foreign_key_links_reference_generated_parents() {
+        let users: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+        let mut generated_ids = HashMap::new();
+        generated_ids.insert(DataType::Users, users.clone());
+
+        let parents = vec![relationship(DataType::Users, DataType::Workflows, "owner_id", 2, 2)];
+        let links = build_foreign_key_links(&parents, &generated_ids, &mut thread_rng()).unwrap();
+
+        assert_eq!(links.len(), 6);
+        assert!(links.iter().all(|link| users.contains(&link["owner_id"])));
+    }
+
+    #[test]
+    fn test_same_seed_reproduces_ids_and_links() {
+        let users: Vec<Uuid> = (0..5).map(|_| Uuid::new_v4()).collect();
+        let projects: Vec<Uuid> = (0..5).map(|_| Uuid::new_v4()).collect();
+        let mut generated_ids = HashMap::new();
+        generated_ids.insert(DataType::Users, users);
+        generated_ids.insert(DataType::Projects, projects);
+
+        let parents = vec![
+            relationship(DataType::Users, DataType::Workflows, "owner_id", 0, 4),
+            relationship(DataType::Projects, DataType::Workflows, "project_id", 1, 1),
+        ];
+
+        let run = |seed: u64| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let ids: Vec<Uuid> = (0..3).map(|_| random_uuid(&mut rng)).collect();
+            let links = build_foreign_key_links(&parents, &generated_ids, &mut rng).unwrap();
+            (ids, links)
+        };
+
+        assert_eq!(run(42), run(42));
+        assert_ne!(run(42).0, run(43).0);
+        assert!(run(42).0.iter().all(|id| id.get_version_num() == 4));
+    }
+}
@@ -3,18 +3,25 @@
 // Complete HTTP API server with multi-database support and comprehensive endpoints
 
 use axum::{
-    extract::{Path, Query, State},
-    http::{HeaderMap, StatusCode},
-    middleware::from_fn,
-    response::{IntoResponse, Json},
+    body::Body,
+    extract::{ConnectInfo, MatchedPath, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::{from_fn, from_fn_with_state},
+    response::{IntoResponse, Json, Response},
     routing::{delete, get, post, put},
     Router,
 };
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{PgPool, Pool, Postgres};
+use ai_core_shared::rate_limit::{
+    per_second_quota,
+    response::{apply_headers, client_key, too_many_requests},
+    SlidingWindowLimiter,
+};
 use std::{
     collections::HashMap,
+    net::{IpAddr, SocketAddr},
     sync::Arc,
     time::Duration,
 };
@@ -36,14 +43,16 @@ mod cleanup;
 mod auth;
 mod health;
 mod metrics;
+mod export;
 
 use models::*;
 use database::DatabaseManager;
-use generators::DataGenerator;
-use cleanup::CleanupService;
+use generators::{DataGenerator, GenerationError};
+use cleanup::{CleanupError, CleanupService};
 use auth::AuthService;
 use health::HealthService;
 use metrics::MetricsService;
+use export::ExportQuery;
 
 // ============================================================================
 // Application State and Configuration
@@ -57,6 +66,7 @@ pub struct AppState {
     pub auth_service: Arc<AuthService>,
     pub health_service: Arc<HealthService>,
     pub metrics_service: Arc<MetricsService>,
+    pub rate_limiter: Arc<SlidingWindowLimiter>,
     pub config: Arc<AppConfig>,
 }
 
@@ -73,9 +83,17 @@ pub struct AppConfig {
     pub request_timeout_seconds: u64,
     pub max_request_size: usize,
     pub rate_limit_per_second: u64,
+    /// Proxies whose `X-Forwarded-For` is used to identify clients for rate limiting
+    pub trusted_proxies: Vec<IpAddr>,
     pub cleanup_interval_hours: u64,
     pub data_generation_batch_size: usize,
     pub environment_ttl_hours: u64,
+    /// How often environments are checked against their TTL
+    pub environment_expiry_check_minutes: u64,
+    /// How long an environment stays `Expiring` before it is torn down
+    pub environment_expiry_grace_minutes: u64,
+    /// Lets requests under `/api/test-data` through without a token. Only for local testing.
+    pub allow_unauthenticated_test_data: bool,
 }
 
 impl Default for AppConfig {
@@ -92,10 +110,38 @@ impl Default for AppConfig {
             request_timeout_seconds: 30,
             max_request_size: 16 * 1024 * 1024, // 16MB
             rate_limit_per_second: 100,
+            trusted_proxies: Vec::new(),
             cleanup_interval_hours: 24,
             data_generation_batch_size: 1000,
             environment_ttl_hours: 72,
+            environment_expiry_check_minutes: 5,
+            environment_expiry_grace_minutes: 60,
+            allow_unauthenticated_test_data: false,
+        }
+    }
+}
+
+impl AppConfig {
+    /// Default configuration with overrides from the process environment
+    pub fn from_env() -> Self {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    /// Default configuration with overrides looked up through `var`
+    ///
+    /// `ALLOW_UNAUTHENTICATED_TEST_DATA` accepts `true`/`1` and `false`/`0`.
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        let mut config = Self::default();
+
+        if let Some(value) = var("ALLOW_UNAUTHENTICATED_TEST_DATA") {
+            match value.trim().to_ascii_lowercase().as_str() {
+                "true" | "1" => config.allow_unauthenticated_test_data = true,
+                "false" | "0" => config.allow_unauthenticated_test_data = false,
+                other => warn!("Ignoring invalid ALLOW_UNAUTHENTICATED_TEST_DATA value '{}'", other),
+            }
         }
+
+        config
     }
 }
 
@@ -132,6 +178,29 @@ async fn auth_middleware(
                 let token = &auth_str[7..];
                 match state.auth_service.validate_token(token).await {
                     Ok(user_claims) => {
+                        let scope = auth::required_scope(request.method(), request.uri().path());
+                        if let Some(scope) = scope {
+                            if !state.auth_service.has_scope(&user_claims, scope) {
+                                warn!(
+                                    "User {} lacks scope '{}' for {} {}",
+                                    user_claims.username, scope, request.method(), request.uri().path()
+                                );
+                                return (StatusCode::FORBIDDEN, Json(ApiError {
+                                    error_code: "INSUFFICIENT_SCOPE".to_string(),
+                                    message: format!("This operation requires the '{}' scope", scope),
+                                    details: Some(serde_json::json!({
+                                        "required_scope": scope,
+                                        "granted_scopes": state.auth_service.granted_scopes(&user_claims),
+                                    })),
+                                    timestamp: Utc::now(),
+                                    request_id: Uuid::new_v4().to_string(),
+                                    suggestions: vec![
+                                        "Request a token that includes the required scope".to_string(),
+                                    ],
+                                })).into_response();
+                            }
+                        }
+
                         request.extensions_mut().insert(user_claims);
                         return next.run(request).await;
                     }
@@ -155,9 +224,13 @@ async fn auth_middleware(
         }
     }
 
-    // For testing endpoints, allow access without authentication
+    // Health and metrics are always public; the test-data bypass must be enabled explicitly
     let path = request.uri().path();
-    if path.starts_with("/health") || path.starts_with("/metrics") || path.starts_with("/api/test-data") {
+    if path.starts_with("/health") || path.starts_with("/metrics") {
+        return next.run(request).await;
+    }
+
+    if state.config.allow_unauthenticated_test_data && path.starts_with("/api/test-data") {
         return next.run(request).await;
     }
 
@@ -174,6 +247,64 @@ async fn auth_middleware(
     })).into_response()
 }
 
+async fn metrics_middleware(
+    State(state): State<AppState>,
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> Response {
+    // Label by route template so ids in paths don't create a series per resource
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let method = request.method().to_string();
+    let start = std::time::Instant::now();
+
+    let response = next.run(request).await;
+
+    if let Err(e) = state
+        .metrics_service
+        .observe_latency(&method, &route, response.status().as_u16(), start.elapsed())
+        .await
+    {
+        warn!("Failed to record request metrics for {} {}: {}", method, route, e);
+    }
+
+    response
+}
+
+async fn rate_limit_middleware(
+    State(state): State<AppState>,
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> Response {
+    // Probes and scrapes must keep working while a client is being throttled
+    let path = request.uri().path();
+    if path.starts_with("/health") || path.starts_with("/metrics") {
+        return next.run(request).await;
+    }
+
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let client = client_key(request.headers(), peer, &state.config.trusted_proxies);
+
+    let rate = u32::try_from(state.config.rate_limit_per_second).unwrap_or(u32::MAX);
+    let (limit, window) = per_second_quota(rate, rate);
+    let decision = state.rate_limiter.check(&client, limit, window);
+
+    if !decision.allowed {
+        warn!("Rate limit exceeded for client {} on {}", client, path);
+        return too_many_requests(&decision, "Too many requests, please slow down");
+    }
+
+    let mut response = next.run(request).await;
+    apply_headers(response.headers_mut(), &decision);
+    response
+}
+
 // ============================================================================
 // Test User Management Endpoints
 // ============================================================================
@@ -383,6 +514,60 @@ async fn get_test_environments(
     }
 }
 
+async fn pin_test_environment(
+    State(state): State<AppState>,
+    Path(environment_id): Path<Uuid>,
+) -> Result<Json<TestEnvironment>, (StatusCode, Json<ApiError>)> {
+    set_environment_pinned(state, environment_id, true).await
+}
+
+async fn unpin_test_environment(
+    State(state): State<AppState>,
+    Path(environment_id): Path<Uuid>,
+) -> Result<Json<TestEnvironment>, (StatusCode, Json<ApiError>)> {
+    set_environment_pinned(state, environment_id, false).await
+}
+
+async fn set_environment_pinned(
+    state: AppState,
+    environment_id: Uuid,
+    pinned: bool,
+) -> Result<Json<TestEnvironment>, (StatusCode, Json<ApiError>)> {
+    debug!("Setting test environment {} pinned: {}", environment_id, pinned);
+
+    match state.database.set_test_environment_pinned(environment_id, pinned).await {
+        Ok(Some(environment)) => {
+            info!("Test environment {} {}", environment_id, if pinned { "pinned" } else { "unpinned" });
+            Ok(Json(environment))
+        }
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiError {
+                error_code: "ENVIRONMENT_NOT_FOUND".to_string(),
+                message: "Test environment not found".to_string(),
+                details: Some(serde_json::json!({"environment_id": environment_id})),
+                timestamp: Utc::now(),
+                request_id: Uuid::new_v4().to_string(),
+                suggestions: vec!["Verify the environment ID is correct and it has not expired".to_string()],
+            }),
+        )),
+        Err(e) => {
+            error!("Failed to update test environment pin: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    error_code: "ENVIRONMENT_UPDATE_FAILED".to_string(),
+                    message: "Failed to update test environment".to_string(),
+                    details: Some(serde_json::json!({"error": e.to_string()})),
+                    timestamp: Utc::now(),
+                    request_id: Uuid::new_v4().to_string(),
+                    suggestions: vec!["Check database connectivity".to_string()],
+                }),
+            ))
+        }
+    }
+}
+
 async fn reset_test_environment(
     State(state): State<AppState>,
     Path(environment_id): Path<Uuid>,
@@ -412,6 +597,84 @@ async fn reset_test_environment(
     }
 }
 
+async fn export_test_environment(
+    State(state): State<AppState>,
+    Path(environment_id): Path<Uuid>,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, (StatusCode, Json<ApiError>)> {
+    debug!("Exporting test environment: {} ({:?})", environment_id, query.format);
+
+    if let Err(e) = query.validate() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiError {
+                error_code: "INVALID_EXPORT_REQUEST".to_string(),
+                message: e.to_string(),
+                details: Some(serde_json::json!({"format": query.format, "entity_type": query.entity_type})),
+                timestamp: Utc::now(),
+                request_id: Uuid::new_v4().to_string(),
+                suggestions: vec!["Add entity_type=users or entity_type=environment to CSV exports".to_string()],
+            }),
+        ));
+    }
+
+    let environment = match state.database.get_test_environment(environment_id).await {
+        Ok(Some(environment)) => environment,
+        Ok(None) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(ApiError {
+                    error_code: "ENVIRONMENT_NOT_FOUND".to_string(),
+                    message: "Test environment not found".to_string(),
+                    details: Some(serde_json::json!({"environment_id": environment_id})),
+                    timestamp: Utc::now(),
+                    request_id: Uuid::new_v4().to_string(),
+                    suggestions: vec!["Verify the environment ID is correct".to_string()],
+                }),
+            ));
+        }
+        Err(e) => {
+            error!("Failed to load test environment for export: {}", e);
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    error_code: "EXPORT_FAILED".to_string(),
+                    message: "Failed to export test environment".to_string(),
+                    details: Some(serde_json::json!({"error": e.to_string()})),
+                    timestamp: Utc::now(),
+                    request_id: Uuid::new_v4().to_string(),
+                    suggestions: vec!["Check database connectivity".to_string()],
+                }),
+            ));
+        }
+    };
+
+    info!("Streaming export of test environment: {} ({})", environment.name, environment.id);
+    state.metrics_service.increment_counter("environment_exports_started").await;
+
+    let format = query.format;
+    let filename = format!("environment-{}.{}", environment_id, format.file_extension());
+    let stream = export::export_environment(state.database.clone(), environment, query);
+
+    Response::builder()
+        .header(header::CONTENT_TYPE, format.content_type())
+        .header(header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", filename))
+        .body(Body::from_stream(stream))
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiError {
+                    error_code: "EXPORT_FAILED".to_string(),
+                    message: "Failed to build export response".to_string(),
+                    details: Some(serde_json::json!({"error": e.to_string()})),
+                    timestamp: Utc::now(),
+                    request_id: Uuid::new_v4().to_string(),
+                    suggestions: vec![],
+                }),
+            )
+        })
+}
+
 // ============================================================================
 // Data Generation Endpoints
 // ============================================================================
@@ -470,6 +733,43 @@ async fn get_generation_status(
     }
 }
 
+async fn cancel_generation(
+    State(state): State<AppState>,
+    Path(generation_id): Path<Uuid>,
+) -> Result<Json<DataGenerationResponse>, (StatusCode, Json<ApiError>)> {
+    debug!("Cancelling data generation: {}", generation_id);
+
+    match state.data_generator.cancel_generation(generation_id).await {
+        Ok(response) => {
+            info!("Cancellation requested for data generation: {}", generation_id);
+            state.metrics_service.increment_counter("data_generation_cancelled").await;
+            Ok(Json(response))
+        }
+        Err(GenerationError::NotFound) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiError {
+                error_code: "GENERATION_NOT_FOUND".to_string(),
+                message: "Data generation not found".to_string(),
+                details: Some(serde_json::json!({"generation_id": generation_id})),
+                timestamp: Utc::now(),
+                request_id: Uuid::new_v4().to_string(),
+                suggestions: vec!["Verify the generation ID is correct".to_string()],
+            }),
+        )),
+        Err(e @ GenerationError::AlreadyFinished(_)) => Err((
+            StatusCode::CONFLICT,
+            Json(ApiError {
+                error_code: "GENERATION_ALREADY_FINISHED".to_string(),
+                message: e.to_string(),
+                details: Some(serde_json::json!({"generation_id": generation_id})),
+                timestamp: Utc::now(),
+                request_id: Uuid::new_v4().to_string(),
+                suggestions: vec!["Check the generation status for the final result".to_string()],
+            }),
+        )),
+    }
+}
+
 // ============================================================================
 // Cleanup Endpoints
 // ============================================================================
@@ -528,6 +828,43 @@ async fn get_cleanup_status(
     }
 }
 
+async fn cancel_cleanup(
+    State(state): State<AppState>,
+    Path(cleanup_id): Path<Uuid>,
+) -> Result<Json<CleanupResponse>, (StatusCode, Json<ApiError>)> {
+    debug!("Cancelling cleanup: {}", cleanup_id);
+
+    match state.cleanup_service.cancel_cleanup(cleanup_id).await {
+        Ok(response) => {
+            info!("Cancellation requested for cleanup: {}", cleanup_id);
+            state.metrics_service.increment_counter("cleanup_operations_cancelled").await;
+            Ok(Json(response))
+        }
+        Err(CleanupError::NotFound) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiError {
+                error_code: "CLEANUP_NOT_FOUND".to_string(),
+                message: "Cleanup operation not found".to_string(),
+                details: Some(serde_json::json!({"cleanup_id": cleanup_id})),
+                timestamp: Utc::now(),
+                request_id: Uuid::new_v4().to_string(),
+                suggestions: vec!["Verify the cleanup ID is correct".to_string()],
+            }),
+        )),
+        Err(e @ CleanupError::AlreadyFinished(_)) => Err((
+            StatusCode::CONFLICT,
+            Json(ApiError {
+                error_code: "CLEANUP_ALREADY_FINISHED".to_string(),
+                message: e.to_string(),
+                details: Some(serde_json::json!({"cleanup_id": cleanup_id})),
+                timestamp: Utc::now(),
+                request_id: Uuid::new_v4().to_string(),
+                suggestions: vec!["Check the cleanup status for the final result".to_string()],
+            }),
+        )),
+    }
+}
+
 // ============================================================================
 // Health and Metrics Endpoints
 // ============================================================================
@@ -591,20 +928,28 @@ async fn create_router(state: AppState) -> Router {
         .route("/api/environments", post(create_test_environment))
         .route("/api/environments", get(get_test_environments))
         .route("/api/environments/:id/reset", post(reset_test_environment))
+        .route("/api/environments/:id/pin", put(pin_test_environment))
+        .route("/api/environments/:id/pin", delete(unpin_test_environment))
+        .route("/api/environments/:id/export", get(export_test_environment))
 
         // Data Generation Routes
         .route("/api/generate-data", post(generate_test_data))
         .route("/api/generate-data/:id/status", get(get_generation_status))
+        .route("/api/generate-data/:id", delete(cancel_generation))
 
         // Cleanup Routes
         .route("/api/cleanup", post(cleanup_test_data))
         .route("/api/cleanup/:id/status", get(get_cleanup_status))
+        .route("/api/cleanup/:id", delete(cancel_cleanup))
 
         // Health and Metrics Routes
         .route("/health", get(health_check))
         .route("/health/detailed", get(health_check))
         .route("/metrics", get(get_metrics))
 
+        // Per-route request metrics (route_layer so MatchedPath is available)
+        .route_layer(from_fn_with_state(state.clone(), metrics_middleware))
+
         // Add middleware layers
         .layer(
             ServiceBuilder::new()
@@ -615,6 +960,7 @@ async fn create_router(state: AppState) -> Router {
                         .on_request(DefaultOnRequest::new().level(tracing::Level::INFO))
                         .on_response(DefaultOnResponse::new().level(tracing::Level::INFO)),
                 )
+                .layer(from_fn_with_state(state.clone(), rate_limit_middleware))
                 .layer(from_fn(auth_middleware))
                 .layer(CorsLayer::permissive()),
         )
@@ -632,7 +978,7 @@ async fn initialize_services(config: AppConfig) -> anyhow::Result<AppState> {
     let database = Arc::new(DatabaseManager::new(&config).await?);
 
     // Initialize other services
-    let data_generator = Arc::new(DataGenerator::new(database.clone()).await?);
+    let data_generator = Arc::new(DataGenerator::new(database.clone(), config.data_generation_batch_size).await?);
     let cleanup_service = Arc::new(CleanupService::new(database.clone()).await?);
     let auth_service = Arc::new(AuthService::new(config.jwt_secret.clone()).await?);
     let health_service = Arc::new(HealthService::new(database.clone()).await?);
@@ -647,6 +993,7 @@ async fn initialize_services(config: AppConfig) -> anyhow::Result<AppState> {
         auth_service,
         health_service,
         metrics_service,
+        rate_limiter: Arc::new(SlidingWindowLimiter::new()),
         config: Arc::new(config),
     })
 }
@@ -671,6 +1018,29 @@ async fn start_background_tasks(state: AppState) {
         }
     });
 
+    // Expire test environments that have outlived their TTL
+    let expiry_interval = Duration::from_secs(state.config.environment_expiry_check_minutes * 60);
+    let default_ttl = chrono::Duration::hours(state.config.environment_ttl_hours as i64);
+    let grace_period = chrono::Duration::minutes(state.config.environment_expiry_grace_minutes as i64);
+    let cleanup_service = state.cleanup_service.clone();
+    let metrics_service = state.metrics_service.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(expiry_interval);
+        loop {
+            interval.tick().await;
+
+            match cleanup_service.expire_environments(default_ttl, grace_period).await {
+                Ok(sweep) => {
+                    metrics_service.add_to_counter("test_environments_expiring", sweep.expiring.len() as u64).await;
+                    metrics_service.add_to_counter("test_environments_restored", sweep.restored.len() as u64).await;
+                    metrics_service.add_to_counter("test_environments_expired", sweep.expired.len() as u64).await;
+                    metrics_service.add_to_counter("test_environment_teardowns_failed", sweep.failed.len() as u64).await;
+                }
+                Err(e) => error!("Environment expiry sweep failed: {}", e),
+            }
+        }
+    });
+
     // Start metrics collection task
     let metrics_service = state.metrics_service.clone();
     tokio::spawn(async move {
@@ -696,7 +1066,10 @@ async fn main() -> anyhow::Result<()> {
     info!("Starting AI-CORE Test Data Management API Server");
 
     // Load configuration
-    let config = AppConfig::default(); // In production, load from environment/config files
+    let config = AppConfig::from_env();
+    if config.allow_unauthenticated_test_data {
+        warn!("Unauthenticated access to /api/test-data is enabled");
+    }
 
     // Initialize services
     let state = initialize_services(config.clone()).await?;
@@ -723,15 +1096,20 @@ async fn main() -> anyhow::Result<()> {
     info!("  POST /api/environments - Create test environment");
     info!("  GET  /api/environments - List test environments");
     info!("  POST /api/environments/:id/reset - Reset environment");
+    info!("  PUT  /api/environments/:id/pin - Exempt environment from auto-expiry");
+    info!("  DELETE /api/environments/:id/pin - Allow environment to auto-expire");
+    info!("  GET  /api/environments/:id/export - Stream environment data (ndjson/csv)");
     info!("  POST /api/generate-data - Generate test data");
     info!("  GET  /api/generate-data/:id/status - Get generation status");
+    info!("  DELETE /api/generate-data/:id - Cancel data generation");
     info!("  POST /api/cleanup - Start cleanup operation");
     info!("  GET  /api/cleanup/:id/status - Get cleanup status");
+    info!("  DELETE /api/cleanup/:id - Cancel cleanup operation");
     info!("  GET  /health - Health check");
     info!("  GET  /metrics - Prometheus metrics");
 
     // Start serving requests with graceful shutdown
-    axum::serve(listener, app)
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
         .with_graceful_shutdown(shutdown_signal())
         .await?;
 
@@ -807,4 +1185,40 @@ mod tests {
 
         assert!(user_request.validate().is_ok());
     }
+
+    #[test]
+    fn test_config_reads_unauthenticated_test_data_flag() {
+        let config = AppConfig::from_vars(|_| None);
+        assert!(!config.allow_unauthenticated_test_data);
+
+        let config = AppConfig::from_vars(|name| {
+            (name == "ALLOW_UNAUTHENTICATED_TEST_DATA").then(|| "true".to_string())
+        });
+        assert!(config.allow_unauthenticated_test_data);
+
+        let config = AppConfig::from_vars(|_| Some("yes please".to_string()));
+        assert!(!config.allow_unauthenticated_test_data);
+    }
+
+    async fn test_server(allow_unauthenticated: bool) -> TestServer {
+        let mut config = AppConfig::from_vars(|name| {
+            (name == "ALLOW_UNAUTHENTICATED_TEST_DATA").then(|| allow_unauthenticated.to_string())
+        });
+        config.database_url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
+
+        let state = initialize_services(config).await.unwrap();
+        TestServer::new(create_router(state).await).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_router_gates_unauthenticated_test_data() {
+        let server = test_server(false).await;
+        let response = server.get("/api/test-data/users").await;
+        assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+
+        // With the flag set the request gets past auth and reaches routing
+        let server = test_server(true).await;
+        let response = server.get("/api/test-data/users").await;
+        assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+    }
 }
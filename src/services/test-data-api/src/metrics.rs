@@ -26,14 +26,32 @@ pub struct MetricsService {
     histograms: Arc<RwLock<HashMap<String, Histogram>>>,
     timers: Arc<RwLock<HashMap<String, Timer>>>,
     custom_metrics: Arc<Mutex<HashMap<String, CustomMetric>>>,
+    http_routes: Arc<RwLock<HashMap<RouteKey, RouteMetrics>>>,
     start_time: Instant,
     system_start_time: SystemTime,
 }
 
+/// Latency buckets for per-route request histograms, in seconds.
+const HTTP_LATENCY_BUCKETS_SECONDS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct RouteKey {
+    method: String,
+    route: String,
+}
+
+#[derive(Debug)]
+struct RouteMetrics {
+    latency_seconds: Histogram,
+    status_codes: RwLock<HashMap<u16, AtomicU64>>,
+}
+
 #[derive(Debug, Clone)]
 struct Histogram {
     buckets: Vec<(f64, AtomicU64)>, // (upper_bound, count)
-    sum: AtomicU64,
+    sum: AtomicU64, // f64 bits, in the recorded unit
     count: AtomicU64,
 }
 
@@ -70,7 +88,7 @@ pub struct MetricsSnapshot {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HistogramSnapshot {
     pub buckets: Vec<(f64, u64)>,
-    pub sum: u64,
+    pub sum: f64,
     pub count: u64,
     pub average: f64,
 }
@@ -107,6 +125,7 @@ impl MetricsService {
             histograms: Arc::new(RwLock::new(HashMap::new())),
             timers: Arc::new(RwLock::new(HashMap::new())),
             custom_metrics: Arc::new(Mutex::new(HashMap::new())),
+            http_routes: Arc::new(RwLock::new(HashMap::new())),
             start_time: Instant::now(),
             system_start_time: SystemTime::now(),
         };
@@ -281,6 +300,94 @@ impl MetricsService {
         }
     }
 
+    // ========================================================================
+    // HTTP Request Metrics
+    // ========================================================================
+
+    /// Records one handled request. `route` must be the matched route template
+    /// (e.g. `/api/test-users/:id`), not the raw path, to keep label cardinality bounded.
+    pub async fn observe_latency(
+        &self,
+        method: &str,
+        route: &str,
+        status: u16,
+        duration: Duration,
+    ) -> Result<()> {
+        let key = RouteKey {
+            method: method.to_string(),
+            route: route.to_string(),
+        };
+        let seconds = duration.as_secs_f64();
+
+        {
+            let routes = self.http_routes.read().unwrap();
+            if let Some(metrics) = routes.get(&key) {
+                metrics.record(status, seconds);
+            } else {
+                drop(routes);
+                let mut routes = self.http_routes.write().unwrap();
+                routes.entry(key).or_insert_with(RouteMetrics::new).record(status, seconds);
+            }
+        }
+
+        self.increment_counter("requests_total").await?;
+        if status >= 400 {
+            self.increment_counter("requests_error").await?;
+        } else {
+            self.increment_counter("requests_success").await?;
+        }
+        self.record_histogram("http_request_duration_ms", seconds * 1000.0).await?;
+
+        Ok(())
+    }
+
+    fn render_http_route_metrics(&self, output: &mut String) {
+        let routes = self.http_routes.read().unwrap();
+        let mut keys: Vec<&RouteKey> = routes.keys().collect();
+        keys.sort();
+
+        output.push_str("# HELP test_data_api_http_requests_total HTTP requests by route and status code\n");
+        output.push_str("# TYPE test_data_api_http_requests_total counter\n");
+        for key in &keys {
+            let status_codes = routes[*key].status_codes.read().unwrap();
+            let mut codes: Vec<(&u16, &AtomicU64)> = status_codes.iter().collect();
+            codes.sort_by_key(|(code, _)| **code);
+
+            for (code, count) in codes {
+                output.push_str(&format!(
+                    "test_data_api_http_requests_total{{method=\"{}\",route=\"{}\",status=\"{}\"}} {}\n",
+                    key.method, key.route, code, count.load(Ordering::Relaxed)
+                ));
+            }
+        }
+
+        output.push_str("# HELP test_data_api_http_request_duration_seconds HTTP request latency by route\n");
+        output.push_str("# TYPE test_data_api_http_request_duration_seconds histogram\n");
+        for key in &keys {
+            let snapshot = routes[*key].latency_seconds.snapshot();
+            let labels = format!("method=\"{}\",route=\"{}\"", key.method, key.route);
+
+            for (upper_bound, count) in &snapshot.buckets {
+                output.push_str(&format!(
+                    "test_data_api_http_request_duration_seconds_bucket{{{},le=\"{}\"}} {}\n",
+                    labels, upper_bound, count
+                ));
+            }
+            output.push_str(&format!(
+                "test_data_api_http_request_duration_seconds_bucket{{{},le=\"+Inf\"}} {}\n",
+                labels, snapshot.count
+            ));
+            output.push_str(&format!(
+                "test_data_api_http_request_duration_seconds_sum{{{}}} {}\n",
+                labels, snapshot.sum
+            ));
+            output.push_str(&format!(
+                "test_data_api_http_request_duration_seconds_count{{{}}} {}\n",
+                labels, snapshot.count
+            ));
+        }
+    }
+
     // ========================================================================
     // Custom Metrics Operations
     // ========================================================================
@@ -474,6 +581,9 @@ impl MetricsService {
             output.push_str(&format!("{}_count {}\n", metric_name, timer.count));
         }
 
+        // Add per-route HTTP metrics
+        self.render_http_route_metrics(&mut output);
+
         // Add system metrics
         output.push_str(&format!("test_data_api_memory_usage_bytes {}\n",
             snapshot.system_metrics.memory_usage_mb * 1024.0 * 1024.0));
@@ -617,7 +727,9 @@ impl Histogram {
     fn record(&self, value: f64) {
         // Update count and sum
         self.count.fetch_add(1, Ordering::Relaxed);
-        self.sum.fetch_add((value * 1000.0) as u64, Ordering::Relaxed); // Store as microseconds
+        let _ = self.sum.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+            Some((f64::from_bits(bits) + value).to_bits())
+        });
 
         // Update buckets
         for (bound, counter) in &self.buckets {
@@ -629,14 +741,14 @@ impl Histogram {
 
     fn snapshot(&self) -> HistogramSnapshot {
         let count = self.count.load(Ordering::Relaxed);
-        let sum = self.sum.load(Ordering::Relaxed);
+        let sum = f64::from_bits(self.sum.load(Ordering::Relaxed));
 
         let buckets = self.buckets.iter()
             .map(|(bound, counter)| (*bound, counter.load(Ordering::Relaxed)))
             .collect();
 
         let average = if count > 0 {
-            (sum as f64 / 1000.0) / count as f64
+            sum / count as f64
         } else {
             0.0
         };
@@ -650,6 +762,35 @@ impl Histogram {
     }
 }
 
+// ============================================================================
+// Route Metrics Implementation
+// ============================================================================
+
+impl RouteMetrics {
+    fn new() -> Self {
+        Self {
+            latency_seconds: Histogram::new_with_buckets(HTTP_LATENCY_BUCKETS_SECONDS.to_vec()),
+            status_codes: RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn record(&self, status: u16, seconds: f64) {
+        self.latency_seconds.record(seconds);
+
+        let status_codes = self.status_codes.read().unwrap();
+        if let Some(counter) = status_codes.get(&status) {
+            counter.fetch_add(1, Ordering::Relaxed);
+        } else {
+            drop(status_codes);
+            let mut status_codes = self.status_codes.write().unwrap();
+            status_codes
+                .entry(status)
+                .or_insert_with(|| AtomicU64::new(0))
+                .fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
 // ============================================================================
 // Timer Implementation
 // ============================================================================
@@ -715,6 +856,7 @@ impl Clone for MetricsService {
             histograms: self.histograms.clone(),
             timers: self.timers.clone(),
             custom_metrics: self.custom_metrics.clone(),
+            http_routes: self.http_routes.clone(),
             start_time: self.start_time,
             system_start_time: self.system_start_time,
         }
@@ -838,4 +980,40 @@ mod tests {
         assert!(prometheus_output.contains("test_data_api_test_requests"));
         assert!(prometheus_output.contains("test_data_api_test_connections"));
     }
+
+    #[tokio::test]
+    async fn test_http_route_metrics_export() {
+        let service = MetricsService::new().await.unwrap();
+
+        service.observe_latency("GET", "/api/test-users", 200, Duration::from_millis(3)).await.unwrap();
+        service.observe_latency("GET", "/api/test-users", 200, Duration::from_millis(40)).await.unwrap();
+        service.observe_latency("GET", "/api/test-users", 500, Duration::from_millis(700)).await.unwrap();
+
+        let output = service.get_prometheus_metrics().await.unwrap();
+        assert!(output.contains(
+            "test_data_api_http_requests_total{method=\"GET\",route=\"/api/test-users\",status=\"200\"} 2"
+        ));
+        assert!(output.contains(
+            "test_data_api_http_requests_total{method=\"GET\",route=\"/api/test-users\",status=\"500\"} 1"
+        ));
+        assert!(output.contains(
+            "test_data_api_http_request_duration_seconds_bucket{method=\"GET\",route=\"/api/test-users\",le=\"0.05\"} 2"
+        ));
+        assert!(output.contains(
+            "test_data_api_http_request_duration_seconds_bucket{method=\"GET\",route=\"/api/test-users\",le=\"+Inf\"} 3"
+        ));
+        assert_eq!(service.get_counter("requests_error").await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sub_millisecond_latency_sum() {
+        let service = MetricsService::new().await.unwrap();
+
+        service.observe_latency("GET", "/health", 200, Duration::from_micros(250)).await.unwrap();
+
+        let output = service.get_prometheus_metrics().await.unwrap();
+        assert!(output.contains(
+            "test_data_api_http_request_duration_seconds_sum{method=\"GET\",route=\"/health\"} 0.00025"
+        ));
+    }
 }
@@ -149,6 +149,18 @@ pub struct TestEnvironment {
     pub updated_at: DateTime<Utc>,
     pub expires_at: Option<DateTime<Utc>>,
     pub auto_cleanup: bool,
+    /// Pinned environments are never auto-expired
+    #[serde(default)]
+    #[sqlx(default)]
+    pub pinned: bool,
+    /// When the environment passed its TTL and its grace period started
+    #[serde(default)]
+    #[sqlx(default)]
+    pub expiring_since: Option<DateTime<Utc>>,
+    /// Status to go back to if the environment is pulled out of its grace period
+    #[serde(default)]
+    #[sqlx(default)]
+    pub status_before_expiry: Option<EnvironmentStatus>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
@@ -226,7 +238,7 @@ pub struct MonitoringConfig {
     pub dashboard_urls: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "environment_status", rename_all = "lowercase")]
 pub enum EnvironmentStatus {
     Provisioning,
@@ -235,6 +247,23 @@ pub enum EnvironmentStatus {
     Maintenance,
     Error,
     Destroying,
+    /// Past its TTL and waiting out the grace period before teardown
+    Expiring,
+    /// Torn down after expiring; hidden from environment listings
+    Expired,
+}
+
+/// What the expiry sweep should do with an environment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpiryAction {
+    None,
+    /// The TTL has passed: start the grace period
+    MarkExpiring,
+    /// The grace period has passed: tear the environment down
+    TearDown,
+    /// Pinned, given a later expiry or opted out of auto-cleanup while
+    /// expiring: bring it back to its previous status
+    Restore,
 }
 
 // ============================================================================
@@ -249,10 +278,12 @@ pub struct DataGenerationRequest {
     pub constraints: Option<DataConstraints>,
     pub relationships: Vec<DataRelationship>,
     pub output_format: OutputFormat,
+    /// Fixes the generator's RNG so the same seed reproduces the same records;
+    /// omit it for random output
     pub seed: Option<u64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum DataType {
     Users,
     Workflows,
@@ -310,6 +341,21 @@ pub struct DataRelationship {
     pub relationship_type: RelationshipType,
     pub cardinality: Cardinality,
     pub foreign_key_field: String,
+    #[serde(default)]
+    pub children_per_parent: ChildrenPerParent,
+}
+
+/// How many child records to generate for every parent record of a relationship.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChildrenPerParent {
+    pub min: i32,
+    pub max: i32,
+}
+
+impl Default for ChildrenPerParent {
+    fn default() -> Self {
+        Self { min: 1, max: 1 }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -336,6 +382,19 @@ pub enum OutputFormat {
     Xml,
 }
 
+/// A generated record, in the order it is inserted. Foreign key fields from
+/// related-data generation live in the user's metadata or the record's data.
+#[derive(Debug, Clone)]
+pub enum GeneratedEntity {
+    User(TestUser),
+    Record {
+        id: Uuid,
+        data_type: DataType,
+        test_environment: String,
+        data: serde_json::Value,
+    },
+}
+
 // ============================================================================
 // Test Execution Models
 // ============================================================================
@@ -445,6 +504,8 @@ pub struct CreateEnvironmentRequest {
     pub service_configs: HashMap<String, ServiceConfig>,
     pub expires_after_hours: Option<i32>,
     pub auto_cleanup: bool,
+    #[serde(default)]
+    pub pinned: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -481,6 +542,16 @@ pub struct DataGenerationResponse {
     pub generated_count: i32,
     pub total_count: i32,
     pub data_urls: Vec<String>,
+    #[serde(default)]
+    pub entity_counts: HashMap<String, i32>,
+    /// Seed the job ran with, whether requested or picked at random
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Percentage of the job's records that have been inserted
+    #[serde(default)]
+    pub progress: u32,
+    #[serde(default)]
+    pub estimated_remaining_seconds: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -508,6 +579,18 @@ pub struct CleanupResponse {
     pub items_to_cleanup: i32,
     pub estimated_duration_seconds: i32,
     pub progress_url: String,
+    #[serde(default)]
+    pub progress: CleanupProgress,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CleanupProgress {
+    pub percent_complete: u32,
+    pub tables_processed: i32,
+    pub total_tables: i32,
+    pub current_table: Option<String>,
+    pub items_cleaned: i32,
+    pub estimated_remaining_seconds: Option<i64>,
 }
 
 // ============================================================================
@@ -631,6 +714,39 @@ impl TestEnvironment {
     pub fn should_cleanup(&self) -> bool {
         self.auto_cleanup && (self.is_expired() || self.status == EnvironmentStatus::Error)
     }
+
+    /// When the environment's TTL runs out: its own `expires_at` if set,
+    /// otherwise `default_ttl` after creation
+    pub fn expiry_deadline(&self, default_ttl: chrono::Duration) -> DateTime<Utc> {
+        self.expires_at.unwrap_or(self.created_at + default_ttl)
+    }
+
+    /// What the expiry sweep should do at `now`. The grace period runs from
+    /// when the environment was marked `Expiring`. Pinned environments and
+    /// environments with `auto_cleanup` off are never expired.
+    pub fn expiry_action(
+        &self,
+        now: DateTime<Utc>,
+        default_ttl: chrono::Duration,
+        grace_period: chrono::Duration,
+    ) -> ExpiryAction {
+        let deadline = self.expiry_deadline(default_ttl);
+        let exempt = self.pinned || !self.auto_cleanup;
+
+        match self.status {
+            EnvironmentStatus::Expiring if exempt || now < deadline => ExpiryAction::Restore,
+            EnvironmentStatus::Expiring
+                if now >= self.expiring_since.unwrap_or(deadline) + grace_period =>
+            {
+                ExpiryAction::TearDown
+            }
+            EnvironmentStatus::Expiring => ExpiryAction::None,
+            EnvironmentStatus::Destroying | EnvironmentStatus::Expired => ExpiryAction::None,
+            _ if exempt => ExpiryAction::None,
+            _ if now >= deadline => ExpiryAction::MarkExpiring,
+            _ => ExpiryAction::None,
+        }
+    }
 }
 
 impl TestExecution {
@@ -673,6 +789,23 @@ impl std::fmt::Display for UserRole {
     }
 }
 
+impl std::fmt::Display for DataType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataType::Users => write!(f, "users"),
+            DataType::Workflows => write!(f, "workflows"),
+            DataType::TestCases => write!(f, "test_cases"),
+            DataType::Organizations => write!(f, "organizations"),
+            DataType::Projects => write!(f, "projects"),
+            DataType::Documents => write!(f, "documents"),
+            DataType::Events => write!(f, "events"),
+            DataType::Metrics => write!(f, "metrics"),
+            DataType::Logs => write!(f, "logs"),
+            DataType::Custom(name) => write!(f, "{}", name),
+        }
+    }
+}
+
 impl std::fmt::Display for ExecutionStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -0,0 +1,380 @@
+//! Cache of Gemini analysis results
+//!
+//! Repeated analysis of the same text is served from the cache instead of
+//! calling Gemini again. Keys hash the exact text, the analysis type and the
+//! options that shape the prompt. Entries live in Redis when
+//! `TEXT_PROCESSING_CACHE_REDIS_URL` is set and in process memory otherwise,
+//! and expire after `TEXT_PROCESSING_CACHE_TTL_SECONDS`. Local fallback results
+//! are never cached, so the next request tries Gemini again.
+
+use crate::{AnalysisResults, TextAnalysisRequest, FULL_ANALYSIS};
+use chrono::{DateTime, Utc};
+use redis::{aio::ConnectionManager, AsyncCommands};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::env;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// Bump when prompts or parsing change so stale entries are never served
+const CACHE_KEY_PREFIX: &str = "text_processing:analysis:v1";
+
+/// A stored Gemini result
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CachedAnalysis {
+    pub results: AnalysisResults,
+    pub ai_model: String,
+    pub cached_at: DateTime<Utc>,
+}
+
+/// Everything that influences an analysis result, serialized in a stable order.
+/// Options only count for the analysis types whose prompt uses them, which for
+/// a full analysis is all of them.
+#[derive(Serialize)]
+struct CacheKeyMaterial<'a> {
+    text: &'a str,
+    analysis_type: &'a str,
+    language: Option<&'a str>,
+    max_keywords: Option<usize>,
+    sentiment_detail: Option<bool>,
+    summary_length: Option<&'a str>,
+}
+
+enum CacheBackend {
+    /// Entries with their expiry, bounded to `max_entries`
+    Memory {
+        entries: Mutex<HashMap<String, (Instant, String)>>,
+        max_entries: usize,
+    },
+    Redis(ConnectionManager),
+}
+
+/// Hit and miss counts since startup, reported by the health endpoint
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheStats {
+    pub backend: &'static str,
+    pub ttl_seconds: u64,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+#[derive(Clone)]
+pub struct AnalysisCache {
+    backend: Arc<CacheBackend>,
+    ttl: Duration,
+    hits: Arc<AtomicU64>,
+    misses: Arc<AtomicU64>,
+}
+
+impl AnalysisCache {
+    pub fn in_memory(ttl: Duration, max_entries: usize) -> Self {
+        Self::with_backend(
+            CacheBackend::Memory {
+                entries: Mutex::new(HashMap::new()),
+                max_entries: max_entries.max(1),
+            },
+            ttl,
+        )
+    }
+
+    pub async fn redis(redis_url: &str, ttl: Duration) -> Result<Self, redis::RedisError> {
+        let client = redis::Client::open(redis_url)?;
+        let connection = ConnectionManager::new(client).await?;
+        Ok(Self::with_backend(CacheBackend::Redis(connection), ttl))
+    }
+
+    fn with_backend(backend: CacheBackend, ttl: Duration) -> Self {
+        Self {
+            backend: Arc::new(backend),
+            ttl,
+            hits: Arc::new(AtomicU64::new(0)),
+            misses: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Build the cache from the environment, falling back to memory when Redis
+    /// is not configured or cannot be reached
+    pub async fn from_env() -> Self {
+        let read = |name: &str, default: u64| {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        };
+        let ttl = Duration::from_secs(read("TEXT_PROCESSING_CACHE_TTL_SECONDS", 3600));
+        let max_entries = read("TEXT_PROCESSING_CACHE_MAX_ENTRIES", 10_000) as usize;
+
+        if let Ok(redis_url) = env::var("TEXT_PROCESSING_CACHE_REDIS_URL") {
+            match Self::redis(&redis_url, ttl).await {
+                Ok(cache) => {
+                    info!("Analysis cache using Redis with TTL of {}s", ttl.as_secs());
+                    return cache;
+                }
+                Err(e) => warn!(
+                    "Failed to connect analysis cache to Redis ({}), using in-memory cache",
+                    e
+                ),
+            }
+        }
+
+        info!(
+            "Analysis cache using memory with TTL of {}s and up to {} entries",
+            ttl.as_secs(),
+            max_entries
+        );
+        Self::in_memory(ttl, max_entries)
+    }
+
+    /// Cache key for a request
+    pub fn cache_key(request: &TextAnalysisRequest) -> String {
+        let options = request.options.as_ref();
+        let analysis_type = request.analysis_type.as_str();
+        let uses =
+            |option_type: &str| analysis_type == option_type || analysis_type == FULL_ANALYSIS;
+
+        let material = CacheKeyMaterial {
+            text: &request.text,
+            analysis_type,
+            language: request.language.as_deref(),
+            max_keywords: uses("keywords")
+                .then(|| options.and_then(|o| o.max_keywords).unwrap_or(10)),
+            sentiment_detail: uses("sentiment")
+                .then(|| options.and_then(|o| o.sentiment_detail).unwrap_or(false)),
+            summary_length: uses("summary").then(|| {
+                options
+                    .and_then(|o| o.summary_length.as_deref())
+                    .unwrap_or("medium")
+            }),
+        };
+
+        // Serializing plain structs cannot fail
+        let encoded = serde_json::to_vec(&material).unwrap_or_default();
+        format!("{}:{:x}", CACHE_KEY_PREFIX, Sha256::digest(&encoded))
+    }
+
+    /// Look up a cached result. Backend errors are logged and count as misses.
+    pub async fn get(&self, key: &str) -> Option<CachedAnalysis> {
+        let cached = match self.backend.as_ref() {
+            CacheBackend::Memory { entries, .. } => {
+                let mut entries = entries.lock().unwrap_or_else(|e| e.into_inner());
+                match entries.get(key) {
+                    Some((expires_at, _)) if *expires_at <= Instant::now() => {
+                        entries.remove(key);
+                        None
+                    }
+                    Some((_, payload)) => Some(payload.clone()),
+                    None => None,
+                }
+            }
+            CacheBackend::Redis(connection) => {
+                let mut connection = connection.clone();
+                connection
+                    .get::<_, Option<String>>(key)
+                    .await
+                    .unwrap_or_else(|e| {
+                        warn!("Analysis cache lookup failed: {}", e);
+                        None
+                    })
+            }
+        };
+
+        let analysis = cached.and_then(|payload| {
+            serde_json::from_str::<CachedAnalysis>(&payload)
+                .map_err(|e| warn!("Discarding unreadable analysis cache entry: {}", e))
+                .ok()
+        });
+
+        match analysis {
+            Some(analysis) => {
+                debug!("Analysis cache hit: {}", key);
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(analysis)
+            }
+            None => {
+                debug!("Analysis cache miss: {}", key);
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Store a result. Failures are logged; the response is still served.
+    pub async fn put(&self, key: &str, analysis: &CachedAnalysis) {
+        let payload = match serde_json::to_string(analysis) {
+            Ok(payload) => payload,
+            Err(e) => {
+                warn!("Failed to serialize analysis for caching: {}", e);
+                return;
+            }
+        };
+
+        match self.backend.as_ref() {
+            CacheBackend::Memory {
+                entries,
+                max_entries,
+            } => {
+                let now = Instant::now();
+                let mut entries = entries.lock().unwrap_or_else(|e| e.into_inner());
+                if entries.len() >= *max_entries && !entries.contains_key(key) {
+                    entries.retain(|_, (expires_at, _)| *expires_at > now);
+                }
+                if entries.len() >= *max_entries && !entries.contains_key(key) {
+                    // Still full of live entries: drop the one closest to expiry
+                    if let Some(oldest) = entries
+                        .iter()
+                        .min_by_key(|(_, (expires_at, _))| *expires_at)
+                        .map(|(key, _)| key.clone())
+                    {
+                        entries.remove(&oldest);
+                    }
+                }
+                entries.insert(key.to_string(), (now + self.ttl, payload));
+            }
+            CacheBackend::Redis(connection) => {
+                let mut connection = connection.clone();
+                if let Err(e) = connection
+                    .set_ex::<_, _, ()>(key, payload, self.ttl.as_secs().max(1))
+                    .await
+                {
+                    warn!("Failed to store analysis in cache: {}", e);
+                }
+            }
+        }
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            backend: match self.backend.as_ref() {
+                CacheBackend::Memory { .. } => "memory",
+                CacheBackend::Redis(_) => "redis",
+            },
+            ttl_seconds: self.ttl.as_secs(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AnalysisOptions;
+
+    fn request(
+        text: &str,
+        analysis_type: &str,
+        options: Option<AnalysisOptions>,
+    ) -> TextAnalysisRequest {
+        TextAnalysisRequest {
+            text: text.to_string(),
+            analysis_type: analysis_type.to_string(),
+            language: None,
+            options,
+        }
+    }
+
+    fn options(max_keywords: Option<usize>, summary_length: Option<&str>) -> AnalysisOptions {
+        AnalysisOptions {
+            max_keywords,
+            summary_length: summary_length.map(str::to_string),
+            sentiment_detail: None,
+            readability_metrics: None,
+            moderate_input: None,
+        }
+    }
+
+    fn analysis() -> CachedAnalysis {
+        CachedAnalysis {
+            results: AnalysisResults {
+                keywords: None,
+                sentiment: Some(crate::fallback::sentiment("Great work", false)),
+                readability: None,
+                grammar: None,
+                summary: None,
+            },
+            ai_model: "gemini-1.5-flash".to_string(),
+            cached_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_cache_key_depends_on_text_type_and_relevant_options() {
+        let key = AnalysisCache::cache_key;
+        let text = "Rust is fast";
+
+        assert_eq!(
+            key(&request(text, "keywords", None)),
+            key(&request(text, "keywords", None))
+        );
+        assert_ne!(
+            key(&request(text, "keywords", None)),
+            key(&request(text, "sentiment", None))
+        );
+        assert_ne!(
+            key(&request(text, "keywords", None)),
+            key(&request("Rust is fast!", "keywords", None))
+        );
+
+        // Defaults match explicit values; options for other analysis types are ignored
+        assert_eq!(
+            key(&request(text, "keywords", None)),
+            key(&request(
+                text,
+                "keywords",
+                Some(options(Some(10), Some("long")))
+            ))
+        );
+        assert_ne!(
+            key(&request(text, "keywords", None)),
+            key(&request(text, "keywords", Some(options(Some(5), None))))
+        );
+        assert_ne!(
+            key(&request(text, "summary", None)),
+            key(&request(
+                text,
+                "summary",
+                Some(options(None, Some("short")))
+            ))
+        );
+        assert_ne!(
+            key(&request(text, "full", None)),
+            key(&request(text, "full", Some(options(Some(5), None))))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_expires_and_counts_hits() {
+        let cache = AnalysisCache::in_memory(Duration::from_millis(50), 10);
+
+        assert!(cache.get("a").await.is_none());
+        cache.put("a", &analysis()).await;
+
+        let hit = cache.get("a").await.unwrap();
+        assert_eq!(hit.ai_model, "gemini-1.5-flash");
+        assert!(hit.results.sentiment.is_some());
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(cache.get("a").await.is_none());
+
+        let stats = cache.stats();
+        assert_eq!(stats.backend, "memory");
+        assert_eq!((stats.hits, stats.misses), (1, 2));
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_is_bounded() {
+        let cache = AnalysisCache::in_memory(Duration::from_secs(60), 2);
+
+        for key in ["a", "b", "c"] {
+            cache.put(key, &analysis()).await;
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+
+        assert!(cache.get("a").await.is_none());
+        assert!(cache.get("b").await.is_some());
+        assert!(cache.get("c").await.is_some());
+    }
+}
@@ -0,0 +1,861 @@
+//! Local text analysis
+//!
+//! Used when Gemini is unavailable or returns output that cannot be parsed.
+//! Every analyser runs offline and is deterministic: term-frequency keywords
+//! with stopword removal, lexicon-based sentiment, Flesch-Kincaid readability,
+//! rule-based grammar checks and an extractive summary.
+
+use crate::{
+    EmotionalTone, GrammarAnalysis, GrammarIssue, KeyPhrase, Keyword, KeywordAnalysis,
+    ReadabilityAnalysis, SentenceSentiment, SentimentAnalysis, SummaryAnalysis, TextStats,
+};
+use std::collections::HashMap;
+
+/// Reported as `ai_model` for results produced here rather than by Gemini
+pub const FALLBACK_MODEL: &str = "local-fallback";
+
+const STOPWORDS: &[&str] = &[
+    "a", "about", "above", "after", "again", "against", "all", "also", "am", "an", "and", "any",
+    "are", "as", "at", "be", "because", "been", "before", "being", "below", "between", "both",
+    "but", "by", "can", "could", "did", "do", "does", "doing", "down", "during", "each", "even",
+    "few", "for", "from", "further", "had", "has", "have", "having", "he", "her", "here", "hers",
+    "him", "his", "how", "however", "i", "if", "in", "into", "is", "it", "its", "itself", "just",
+    "like", "made", "make", "many", "may", "me", "might", "more", "most", "much", "must", "my",
+    "no", "nor", "not", "now", "of", "off", "on", "once", "one", "only", "or", "other", "our",
+    "ours", "out", "over", "own", "same", "she", "should", "so", "some", "such", "than", "that",
+    "the", "their", "theirs", "them", "then", "there", "these", "they", "this", "those", "through",
+    "to", "too", "under", "until", "up", "us", "very", "was", "we", "well", "were", "what", "when",
+    "where", "which", "while", "who", "whom", "why", "will", "with", "would", "yet", "you", "your",
+    "yours",
+];
+
+/// Sentiment lexicon: word, valence from -3 to 3, and the emotion it signals
+const SENTIMENT_LEXICON: &[(&str, i32, &str)] = &[
+    ("amazing", 3, "joy"),
+    ("awesome", 3, "joy"),
+    ("brilliant", 3, "joy"),
+    ("delighted", 3, "joy"),
+    ("excellent", 3, "joy"),
+    ("fantastic", 3, "joy"),
+    ("love", 3, "joy"),
+    ("outstanding", 3, "joy"),
+    ("wonderful", 3, "joy"),
+    ("enjoy", 2, "joy"),
+    ("excited", 2, "anticipation"),
+    ("glad", 2, "joy"),
+    ("great", 2, "joy"),
+    ("happy", 2, "joy"),
+    ("impressive", 2, "joy"),
+    ("pleased", 2, "joy"),
+    ("recommend", 2, "trust"),
+    ("reliable", 2, "trust"),
+    ("success", 2, "joy"),
+    ("successful", 2, "joy"),
+    ("beautiful", 2, "joy"),
+    ("fast", 1, "joy"),
+    ("good", 1, "joy"),
+    ("helpful", 1, "trust"),
+    ("hope", 1, "anticipation"),
+    ("improved", 1, "joy"),
+    ("nice", 1, "joy"),
+    ("safe", 1, "trust"),
+    ("secure", 1, "trust"),
+    ("trust", 1, "trust"),
+    ("useful", 1, "trust"),
+    ("awful", -3, "disgust"),
+    ("disaster", -3, "sadness"),
+    ("hate", -3, "anger"),
+    ("horrible", -3, "disgust"),
+    ("terrible", -3, "disgust"),
+    ("worst", -3, "anger"),
+    ("angry", -2, "anger"),
+    ("annoying", -2, "anger"),
+    ("bad", -2, "sadness"),
+    ("broken", -2, "anger"),
+    ("disappointed", -2, "sadness"),
+    ("disappointing", -2, "sadness"),
+    ("fail", -2, "sadness"),
+    ("failed", -2, "sadness"),
+    ("failure", -2, "sadness"),
+    ("frustrated", -2, "anger"),
+    ("frustrating", -2, "anger"),
+    ("sad", -2, "sadness"),
+    ("scared", -2, "fear"),
+    ("useless", -2, "anger"),
+    ("afraid", -2, "fear"),
+    ("worried", -2, "fear"),
+    ("bug", -1, "anger"),
+    ("confusing", -1, "fear"),
+    ("difficult", -1, "sadness"),
+    ("expensive", -1, "sadness"),
+    ("poor", -1, "sadness"),
+    ("problem", -1, "fear"),
+    ("risk", -1, "fear"),
+    ("slow", -1, "sadness"),
+    ("unfortunately", -1, "sadness"),
+];
+
+const NEGATIONS: &[&str] = &["not", "no", "never", "none", "nobody", "nothing", "neither"];
+
+const INTENSIFIERS: &[&str] = &[
+    "very",
+    "really",
+    "extremely",
+    "incredibly",
+    "highly",
+    "so",
+    "truly",
+];
+
+/// Words a negation or intensifier reaches past itself
+const MODIFIER_SCOPE: usize = 3;
+
+/// Lowercased words with surrounding punctuation removed
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '\''))
+        .map(|word| word.trim_matches('\'').to_lowercase())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+fn is_stopword(word: &str) -> bool {
+    STOPWORDS.binary_search(&word).is_ok()
+}
+
+/// Words that can carry meaning: not stopwords, numbers or single letters
+fn content_words(text: &str) -> Vec<String> {
+    words(text)
+        .into_iter()
+        .filter(|word| {
+            word.chars().count() > 2 && !is_stopword(word) && !word.chars().all(|c| c.is_numeric())
+        })
+        .collect()
+}
+
+/// Sentences including their terminal punctuation, in document order
+fn sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        if matches!(c, '.' | '!' | '?') {
+            let mut end = index + c.len_utf8();
+            while let Some(&(next_index, next)) = chars.peek() {
+                if !matches!(next, '.' | '!' | '?') {
+                    break;
+                }
+                end = next_index + next.len_utf8();
+                chars.next();
+            }
+            sentences.push(&text[start..end]);
+            start = end;
+        }
+    }
+    sentences.push(&text[start..]);
+
+    sentences
+        .into_iter()
+        .map(str::trim)
+        .filter(|sentence| sentence.chars().any(char::is_alphanumeric))
+        .collect()
+}
+
+/// Term frequency of every content word, normalised so the most frequent is 1.0
+fn term_frequencies(text: &str) -> HashMap<String, f32> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for word in content_words(text) {
+        *counts.entry(word).or_insert(0) += 1;
+    }
+
+    let max = counts.values().copied().max().unwrap_or(1) as f32;
+    counts
+        .into_iter()
+        .map(|(word, count)| (word, count as f32 / max))
+        .collect()
+}
+
+// ============================================================================
+// Keywords
+// ============================================================================
+
+/// Keywords ranked by term frequency after stopword removal. Ties keep the
+/// order in which the words first appear; repeated adjacent keyword pairs are
+/// reported as phrases.
+pub fn keywords(text: &str, max_keywords: usize) -> KeywordAnalysis {
+    let tokens = content_words(text);
+
+    let mut counts: HashMap<&str, (usize, usize)> = HashMap::new();
+    for (position, word) in tokens.iter().enumerate() {
+        counts.entry(word).or_insert((0, position)).0 += 1;
+    }
+    let max_count = counts.values().map(|(count, _)| *count).max().unwrap_or(1) as f32;
+
+    let mut ranked: Vec<(&str, usize, usize)> = counts
+        .iter()
+        .map(|(word, (count, first))| (*word, *count, *first))
+        .collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.2.cmp(&b.2)));
+
+    let keywords: Vec<Keyword> = ranked
+        .iter()
+        .take(max_keywords)
+        .map(|(word, count, _)| Keyword {
+            word: word.to_string(),
+            frequency: *count,
+            relevance_score: *count as f32 / max_count,
+            category: None,
+        })
+        .collect();
+
+    // Adjacent in the original text, so only pairs with no word between them count
+    let all_words = words(text);
+    let mut phrase_counts: HashMap<String, (usize, usize, f32)> = HashMap::new();
+    for (position, pair) in all_words.windows(2).enumerate() {
+        let (Some((first, _)), Some((second, _))) =
+            (counts.get(pair[0].as_str()), counts.get(pair[1].as_str()))
+        else {
+            continue;
+        };
+        let importance = (*first + *second) as f32 / (2.0 * max_count);
+        phrase_counts
+            .entry(format!("{} {}", pair[0], pair[1]))
+            .or_insert((0, position, importance))
+            .0 += 1;
+    }
+
+    let mut phrases: Vec<(String, usize, usize, f32)> = phrase_counts
+        .into_iter()
+        .filter(|(_, (count, _, _))| *count > 1)
+        .map(|(phrase, (count, first, importance))| (phrase, count, first, importance))
+        .collect();
+    phrases.sort_by(|a, b| b.1.cmp(&a.1).then(a.2.cmp(&b.2)));
+
+    KeywordAnalysis {
+        topics: keywords.iter().take(3).map(|k| k.word.clone()).collect(),
+        keywords,
+        phrases: phrases
+            .into_iter()
+            .take(max_keywords)
+            .map(|(phrase, frequency, _, importance_score)| KeyPhrase {
+                phrase,
+                frequency,
+                importance_score,
+            })
+            .collect(),
+        // More text gives the frequencies more to go on
+        confidence_score: (0.3 + tokens.len() as f32 / 100.0).min(0.7),
+    }
+}
+
+// ============================================================================
+// Sentiment
+// ============================================================================
+
+/// Lexicon score of one run of words. Negations flip and intensifiers amplify
+/// the next few sentiment words. Returns the total valence and, per emotion,
+/// the absolute valence that contributed to it.
+fn score_words(words: &[String]) -> (f32, HashMap<&'static str, f32>) {
+    let mut total = 0.0;
+    let mut emotions: HashMap<&'static str, f32> = HashMap::new();
+    let mut negated_until = 0;
+    let mut intensified_until = 0;
+
+    for (position, word) in words.iter().enumerate() {
+        if NEGATIONS.contains(&word.as_str()) || word.ends_with("n't") {
+            negated_until = position + MODIFIER_SCOPE + 1;
+            continue;
+        }
+        if INTENSIFIERS.contains(&word.as_str()) {
+            intensified_until = position + 2;
+            continue;
+        }
+
+        let Some((_, valence, emotion)) =
+            SENTIMENT_LEXICON.iter().find(|(entry, _, _)| entry == word)
+        else {
+            continue;
+        };
+
+        let mut score = *valence as f32;
+        if position < intensified_until {
+            score *= 1.5;
+        }
+        if position < negated_until {
+            // "not good" is mildly negative rather than the mirror of "good"
+            score *= -0.5;
+            negated_until = 0;
+        }
+
+        total += score;
+        if score.signum() == (*valence as f32).signum() {
+            *emotions.entry(emotion).or_insert(0.0) += score.abs();
+        }
+    }
+
+    (total, emotions)
+}
+
+/// Squash a raw lexicon total into -1.0..=1.0
+fn normalize(total: f32) -> f32 {
+    total / (total * total + 15.0).sqrt()
+}
+
+fn label(compound: f32) -> &'static str {
+    if compound >= 0.05 {
+        "positive"
+    } else if compound <= -0.05 {
+        "negative"
+    } else {
+        "neutral"
+    }
+}
+
+fn confidence(compound: f32) -> f32 {
+    0.5 + 0.45 * compound.abs()
+}
+
+/// Lexicon-based sentiment with negation and intensifier handling. With
+/// `per_sentence` each sentence is scored on its own as well.
+pub fn sentiment(text: &str, per_sentence: bool) -> SentimentAnalysis {
+    let (total, emotions) = score_words(&words(text));
+    let compound = normalize(total);
+    let overall_confidence = confidence(compound);
+
+    let emotion_total: f32 = emotions.values().sum();
+    let mut emotional_tone: Vec<EmotionalTone> = emotions
+        .into_iter()
+        .map(|(emotion, weight)| EmotionalTone {
+            emotion: emotion.to_string(),
+            intensity: weight / emotion_total,
+        })
+        .collect();
+    emotional_tone.sort_by(|a, b| {
+        b.intensity
+            .total_cmp(&a.intensity)
+            .then(a.emotion.cmp(&b.emotion))
+    });
+    if emotional_tone.is_empty() {
+        emotional_tone.push(EmotionalTone {
+            emotion: "neutral".to_string(),
+            intensity: overall_confidence,
+        });
+    }
+
+    let sentiment_by_sentence = per_sentence.then(|| {
+        sentences(text)
+            .into_iter()
+            .map(|sentence| {
+                let compound = normalize(score_words(&words(sentence)).0);
+                SentenceSentiment {
+                    sentence: sentence.to_string(),
+                    sentiment: label(compound).to_string(),
+                    confidence: confidence(compound),
+                }
+            })
+            .collect()
+    });
+
+    SentimentAnalysis {
+        overall_sentiment: label(compound).to_string(),
+        confidence_score: overall_confidence,
+        emotional_tone,
+        sentiment_by_sentence,
+    }
+}
+
+// ============================================================================
+// Readability
+// ============================================================================
+
+/// Estimated syllables: vowel groups, less a silent trailing "e", at least one
+fn syllables(word: &str) -> usize {
+    let word = word.to_lowercase();
+    let mut count = 0;
+    let mut previous_vowel = false;
+
+    for c in word.chars() {
+        let vowel = matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y');
+        if vowel && !previous_vowel {
+            count += 1;
+        }
+        previous_vowel = vowel;
+    }
+
+    if count > 1 && word.ends_with('e') && !word.ends_with("le") && !word.ends_with("ee") {
+        count -= 1;
+    }
+
+    count.max(1)
+}
+
+/// Flesch-Kincaid grade and reading ease, using the sentence count already
+/// computed for the response. Words of three or more syllables count as
+/// difficult.
+pub fn readability(text: &str, stats: &TextStats) -> ReadabilityAnalysis {
+    let tokens: Vec<String> = words(text)
+        .into_iter()
+        .filter(|word| word.chars().any(char::is_alphabetic))
+        .collect();
+
+    if tokens.is_empty() {
+        return ReadabilityAnalysis {
+            reading_level: "Elementary".to_string(),
+            complexity_score: 0.0,
+            avg_sentence_length: 0.0,
+            difficult_words_percentage: 0.0,
+            suggestions: vec!["Provide more text for a readability assessment".to_string()],
+            flesch_kincaid_grade: Some(0.0),
+            flesch_reading_ease: Some(100.0),
+        };
+    }
+
+    let syllable_counts: Vec<usize> = tokens.iter().map(|word| syllables(word)).collect();
+    let words_per_sentence = stats.word_count as f32 / stats.sentence_count.max(1) as f32;
+    let syllables_per_word = syllable_counts.iter().sum::<usize>() as f32 / tokens.len() as f32;
+    let difficult_words = syllable_counts.iter().filter(|&&count| count >= 3).count();
+    let difficult_words_percentage = difficult_words as f32 * 100.0 / tokens.len() as f32;
+
+    let grade = (0.39 * words_per_sentence + 11.8 * syllables_per_word - 15.59).max(0.0);
+    let ease = (206.835 - 1.015 * words_per_sentence - 84.6 * syllables_per_word).clamp(0.0, 100.0);
+
+    let reading_level = match grade {
+        g if g < 6.0 => "Elementary",
+        g if g < 9.0 => "Middle School",
+        g if g < 13.0 => "High School",
+        g if g < 17.0 => "College",
+        _ => "Graduate",
+    };
+
+    let mut suggestions = Vec::new();
+    if words_per_sentence > 20.0 {
+        suggestions.push(format!(
+            "Sentences average {:.0} words; split long sentences to stay under 20",
+            words_per_sentence
+        ));
+    }
+    if difficult_words_percentage > 15.0 {
+        suggestions.push(format!(
+            "{:.0}% of words have three or more syllables; prefer shorter alternatives",
+            difficult_words_percentage
+        ));
+    }
+    if suggestions.is_empty() {
+        suggestions.push("Readability is suitable for a general audience".to_string());
+    }
+
+    ReadabilityAnalysis {
+        reading_level: reading_level.to_string(),
+        complexity_score: 1.0 - ease / 100.0,
+        avg_sentence_length: words_per_sentence,
+        difficult_words_percentage,
+        suggestions,
+        flesch_kincaid_grade: Some(grade),
+        flesch_reading_ease: Some(ease),
+    }
+}
+
+// ============================================================================
+// Grammar
+// ============================================================================
+
+fn issue(
+    issue_type: &str,
+    description: String,
+    position: usize,
+    severity: &str,
+    suggestion: String,
+) -> GrammarIssue {
+    GrammarIssue {
+        issue_type: issue_type.to_string(),
+        description,
+        position,
+        severity: severity.to_string(),
+        suggestion,
+    }
+}
+
+/// Rule-based checks for spacing, repeated words, capitalisation and missing
+/// terminal punctuation. Positions are byte offsets into the text; the
+/// corrected text applies every fix and keeps line breaks.
+pub fn grammar(text: &str) -> GrammarAnalysis {
+    let mut issues = Vec::new();
+    let mut corrected = String::with_capacity(text.len());
+    let mut previous: Option<&str> = None;
+    let mut sentence_start = true;
+    let mut offset = 0;
+
+    for token in text.split_inclusive(char::is_whitespace) {
+        let position = offset;
+        offset += token.len();
+        let word = token.trim_end();
+
+        if word.is_empty() {
+            // Extra whitespace; a run that contains a line break is kept as-is
+            if token.contains('\n') {
+                corrected.push('\n');
+            } else if token == " " && corrected.ends_with(' ') {
+                issues.push(issue(
+                    "spacing",
+                    "Multiple consecutive spaces found".to_string(),
+                    position,
+                    "low",
+                    "Use single spaces between words".to_string(),
+                ));
+            }
+            continue;
+        }
+
+        if word
+            .chars()
+            .all(|c| matches!(c, ',' | '.' | ';' | ':' | '!' | '?'))
+            && previous.is_some()
+        {
+            issues.push(issue(
+                "spacing",
+                format!("Space before '{}'", word),
+                position,
+                "low",
+                format!("Attach '{}' to the preceding word", word),
+            ));
+            let trimmed = corrected.trim_end().len();
+            corrected.truncate(trimmed);
+            corrected.push_str(word);
+            corrected.push(' ');
+            sentence_start = word.ends_with(['.', '!', '?']);
+            continue;
+        }
+
+        let bare = |w: &str| {
+            w.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        };
+        if let Some(previous) = previous {
+            if !previous.ends_with(|c: char| c.is_ascii_punctuation())
+                && !bare(word).is_empty()
+                && bare(previous) == bare(word)
+            {
+                issues.push(issue(
+                    "repeated_word",
+                    format!("The word '{}' is repeated", bare(word)),
+                    position,
+                    "medium",
+                    format!("Remove the second '{}'", bare(word)),
+                ));
+                // Keep any trailing punctuation of the dropped word
+                let trailing = &word[word
+                    .trim_end_matches(|c: char| c.is_ascii_punctuation())
+                    .len()..];
+                let trimmed = corrected.trim_end().len();
+                corrected.truncate(trimmed);
+                corrected.push_str(trailing);
+                corrected.push(' ');
+                sentence_start = word.ends_with(['.', '!', '?']);
+                continue;
+            }
+        }
+
+        let mut fixed = word.to_string();
+        let first = word.chars().next().unwrap_or(' ');
+        if sentence_start && first.is_lowercase() {
+            issues.push(issue(
+                "capitalization",
+                format!("Sentence starts with lowercase '{}'", word),
+                position,
+                "medium",
+                "Capitalize the first word of each sentence".to_string(),
+            ));
+            fixed = first.to_uppercase().chain(word.chars().skip(1)).collect();
+        } else if word == "i" || word.starts_with("i'") {
+            issues.push(issue(
+                "capitalization",
+                "The pronoun 'I' should be capitalized".to_string(),
+                position,
+                "medium",
+                "Write 'I' in uppercase".to_string(),
+            ));
+            fixed = format!("I{}", &word[1..]);
+        }
+
+        corrected.push_str(&fixed);
+        corrected.push(if token.ends_with('\n') { '\n' } else { ' ' });
+        sentence_start = word.ends_with(['.', '!', '?']);
+        previous = Some(word);
+    }
+
+    let mut corrected = corrected.trim_end().to_string();
+    if !corrected.is_empty() && !corrected.ends_with(['.', '!', '?', '"', ')']) {
+        issues.push(issue(
+            "punctuation",
+            "Text does not end with terminal punctuation".to_string(),
+            text.trim_end().len(),
+            "low",
+            "End the final sentence with a period".to_string(),
+        ));
+        corrected.push('.');
+    }
+
+    let word_count = text.split_whitespace().count().max(1) as f32;
+    let grammar_score = (1.0 - issues.len() as f32 * 5.0 / word_count).clamp(0.0, 1.0);
+
+    let mut suggestions: Vec<String> = Vec::new();
+    for issue in &issues {
+        let suggestion = match issue.issue_type.as_str() {
+            "spacing" => "Normalize spacing around words and punctuation",
+            "repeated_word" => "Proofread for accidentally repeated words",
+            "capitalization" => "Check capitalization of sentence starts and 'I'",
+            _ => "End every sentence with terminal punctuation",
+        };
+        if !suggestions.iter().any(|s| s == suggestion) {
+            suggestions.push(suggestion.to_string());
+        }
+    }
+    if suggestions.is_empty() {
+        suggestions.push("No issues found by local checks".to_string());
+    }
+
+    GrammarAnalysis {
+        grammar_score,
+        issues_found: issues,
+        suggestions,
+        corrected_text: Some(corrected),
+    }
+}
+
+// ============================================================================
+// Summary
+// ============================================================================
+
+/// Extractive summary: the first and last sentences plus the highest-scoring
+/// sentences in between, kept in document order. Sentences score by the mean
+/// term frequency of their content words. Key points are the top-scoring
+/// sentences overall.
+pub fn summary(text: &str, summary_type: &str) -> SummaryAnalysis {
+    let sentences = sentences(text);
+    let target = match summary_type {
+        "short" => 2,
+        "long" => 5,
+        _ => 3,
+    };
+
+    let frequencies = term_frequencies(text);
+    let scores: Vec<f32> = sentences
+        .iter()
+        .map(|sentence| {
+            let words = content_words(sentence);
+            if words.is_empty() {
+                return 0.0;
+            }
+            words
+                .iter()
+                .map(|word| frequencies.get(word).copied().unwrap_or(0.0))
+                .sum::<f32>()
+                / words.len() as f32
+        })
+        .collect();
+
+    // Rank by score, earlier sentences first on ties
+    let mut ranked: Vec<usize> = (0..sentences.len()).collect();
+    ranked.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]).then(a.cmp(&b)));
+
+    let mut selected: Vec<usize> = if sentences.len() <= target {
+        (0..sentences.len()).collect()
+    } else {
+        let last = sentences.len() - 1;
+        let mut selected = vec![0, last];
+        selected.extend(
+            ranked
+                .iter()
+                .filter(|&&index| index != 0 && index != last)
+                .take(target - 2),
+        );
+        selected
+    };
+    selected.sort_unstable();
+
+    let summary = if selected.is_empty() {
+        "Text summary not available".to_string()
+    } else {
+        selected
+            .iter()
+            .map(|&index| sentences[index])
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    let mut key_points: Vec<usize> = ranked.into_iter().take(3).collect();
+    key_points.sort_unstable();
+
+    let original_length = text.chars().count();
+    let summary_length = summary.chars().count();
+    let compression_ratio = if original_length > 0 {
+        summary_length as f32 / original_length as f32
+    } else {
+        0.0
+    };
+
+    SummaryAnalysis {
+        summary,
+        key_points: key_points
+            .into_iter()
+            .map(|index| sentences[index].to_string())
+            .collect(),
+        summary_type: summary_type.to_string(),
+        compression_ratio,
+        original_length,
+        summary_length,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate_text_stats;
+
+    const ARTICLE: &str = "Rust makes systems programming safe. The Rust compiler checks memory \
+        safety at compile time. Teams adopting Rust report fewer memory bugs. Some developers find \
+        the borrow checker difficult at first. Memory safety without garbage collection is the \
+        main reason teams choose Rust.";
+
+    #[test]
+    fn test_stopwords_are_sorted() {
+        assert!(STOPWORDS.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_keywords_rank_by_frequency_without_stopwords() {
+        let analysis = keywords(ARTICLE, 3);
+        let words: Vec<&str> = analysis.keywords.iter().map(|k| k.word.as_str()).collect();
+
+        assert_eq!(words, vec!["rust", "memory", "safety"]);
+        assert_eq!(analysis.keywords[0].frequency, 4);
+        assert_eq!(analysis.keywords[0].relevance_score, 1.0);
+        assert_eq!(analysis.topics, words);
+        assert_eq!(analysis.phrases[0].phrase, "memory safety");
+        assert_eq!(analysis.phrases[0].frequency, 2);
+        assert!(analysis.keywords.iter().all(|k| !is_stopword(&k.word)));
+
+        // Same input, same output
+        let again = keywords(ARTICLE, 3);
+        assert_eq!(
+            serde_json::to_value(&analysis).unwrap(),
+            serde_json::to_value(&again).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_sentiment_handles_negation_and_intensifiers() {
+        assert_eq!(
+            sentiment("This release is really great and I love it.", false).overall_sentiment,
+            "positive"
+        );
+        assert_eq!(
+            sentiment("The update is terrible and the app feels broken.", false).overall_sentiment,
+            "negative"
+        );
+        assert_eq!(
+            sentiment("The meeting is at noon on Tuesday.", false).overall_sentiment,
+            "neutral"
+        );
+        assert_eq!(
+            sentiment("The new design is not good.", false).overall_sentiment,
+            "negative"
+        );
+
+        let strong = sentiment("The results are very good.", false);
+        let plain = sentiment("The results are good.", false);
+        assert!(strong.confidence_score > plain.confidence_score);
+        assert_eq!(strong.emotional_tone[0].emotion, "joy");
+
+        let detailed = sentiment("Setup was awful. Support was excellent!", true);
+        let by_sentence = detailed.sentiment_by_sentence.unwrap();
+        assert_eq!(by_sentence.len(), 2);
+        assert_eq!(by_sentence[0].sentiment, "negative");
+        assert_eq!(by_sentence[1].sentiment, "positive");
+    }
+
+    #[test]
+    fn test_readability_uses_flesch_kincaid() {
+        assert_eq!(syllables("cat"), 1);
+        assert_eq!(syllables("table"), 2);
+        assert_eq!(syllables("make"), 1);
+        assert_eq!(syllables("readability"), 5);
+
+        let simple = "The cat sat on the mat. The dog ran to the park.";
+        let analysis = readability(simple, &calculate_text_stats(simple));
+        assert_eq!(analysis.reading_level, "Elementary");
+        assert_eq!(analysis.avg_sentence_length, 6.0);
+        assert_eq!(analysis.difficult_words_percentage, 0.0);
+        assert!(analysis.flesch_reading_ease.unwrap() > 90.0);
+
+        let dense = "Comprehensive organizational restructuring necessitates considerable \
+            administrative coordination between interdependent departmental representatives.";
+        let analysis = readability(dense, &calculate_text_stats(dense));
+        assert_eq!(analysis.reading_level, "Graduate");
+        assert!(analysis.complexity_score > 0.9);
+        assert!(analysis.difficult_words_percentage > 50.0);
+        assert!(analysis.flesch_kincaid_grade.unwrap() > 17.0);
+    }
+
+    #[test]
+    fn test_grammar_flags_and_corrects_common_issues() {
+        let analysis = grammar("the report is is ready .  i checked it\nthe numbers look fine");
+        let types: Vec<&str> = analysis
+            .issues_found
+            .iter()
+            .map(|issue| issue.issue_type.as_str())
+            .collect();
+
+        assert_eq!(
+            types,
+            vec![
+                "capitalization",
+                "repeated_word",
+                "spacing",
+                "spacing",
+                "capitalization",
+                "punctuation"
+            ]
+        );
+        assert_eq!(
+            analysis.corrected_text.as_deref(),
+            Some("The report is ready. I checked it\nthe numbers look fine.")
+        );
+        assert!(analysis.grammar_score < 0.5);
+
+        let clean = grammar("The report is ready. I checked it.");
+        assert!(clean.issues_found.is_empty());
+        assert_eq!(clean.grammar_score, 1.0);
+        assert_eq!(
+            clean.corrected_text.as_deref(),
+            Some("The report is ready. I checked it.")
+        );
+    }
+
+    #[test]
+    fn test_summary_keeps_first_last_and_top_sentences() {
+        let analysis = summary(ARTICLE, "medium");
+
+        assert_eq!(
+            analysis.summary,
+            "Rust makes systems programming safe. The Rust compiler checks memory safety at \
+             compile time. Memory safety without garbage collection is the main reason teams \
+             choose Rust."
+        );
+        assert_eq!(analysis.key_points.len(), 3);
+        assert!(analysis.compression_ratio < 1.0);
+
+        let short = summary(ARTICLE, "short");
+        assert_eq!(
+            short.summary,
+            "Rust makes systems programming safe. Memory safety without garbage collection is the \
+             main reason teams choose Rust."
+        );
+
+        assert_eq!(
+            summary("Just one sentence", "long").summary,
+            "Just one sentence"
+        );
+        assert_eq!(summary("", "short").summary, "Text summary not available");
+    }
+}
@@ -0,0 +1,233 @@
+//! Language of the analysed text
+//!
+//! Requests that name no language are detected with whatlang's trigram model.
+//! Only languages in `SUPPORTED_LANGUAGES` are used for analysis; unsupported
+//! or low-confidence detections fall back to English and say so in the
+//! response.
+
+use serde::Serialize;
+use whatlang::Lang;
+
+/// Detections below this whatlang confidence fall back to English
+pub const MIN_DETECTION_CONFIDENCE: f64 = 0.5;
+
+/// A language both detection and the analysis prompts support
+pub struct SupportedLanguage {
+    pub lang: Lang,
+    /// ISO 639-1 code
+    pub code: &'static str,
+    pub name: &'static str,
+}
+
+pub const SUPPORTED_LANGUAGES: [SupportedLanguage; 6] = [
+    SupportedLanguage {
+        lang: Lang::Eng,
+        code: "en",
+        name: "English",
+    },
+    SupportedLanguage {
+        lang: Lang::Spa,
+        code: "es",
+        name: "Spanish",
+    },
+    SupportedLanguage {
+        lang: Lang::Fra,
+        code: "fr",
+        name: "French",
+    },
+    SupportedLanguage {
+        lang: Lang::Deu,
+        code: "de",
+        name: "German",
+    },
+    SupportedLanguage {
+        lang: Lang::Ita,
+        code: "it",
+        name: "Italian",
+    },
+    SupportedLanguage {
+        lang: Lang::Por,
+        code: "pt",
+        name: "Portuguese",
+    },
+];
+
+const DEFAULT_LANGUAGE: &SupportedLanguage = &SUPPORTED_LANGUAGES[0];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LanguageSource {
+    /// Named in the request
+    Requested,
+    Detected,
+    /// English, because detection failed or was not confident
+    Default,
+}
+
+/// Language the text was analysed as
+#[derive(Debug, Clone, Serialize)]
+pub struct TextLanguage {
+    pub code: String,
+    pub name: String,
+    pub source: LanguageSource,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl TextLanguage {
+    fn from_supported(language: &SupportedLanguage, source: LanguageSource) -> Self {
+        Self {
+            code: language.code.to_string(),
+            name: language.name.to_string(),
+            source,
+            confidence: None,
+            note: None,
+        }
+    }
+
+    fn default_with_note(confidence: Option<f64>, note: String) -> Self {
+        Self {
+            confidence,
+            note: Some(note),
+            ..Self::from_supported(DEFAULT_LANGUAGE, LanguageSource::Default)
+        }
+    }
+}
+
+/// Names of the supported languages, for the health and capabilities endpoints
+pub fn supported_language_names() -> Vec<String> {
+    SUPPORTED_LANGUAGES
+        .iter()
+        .map(|language| language.name.to_string())
+        .collect()
+}
+
+/// Find a supported language by ISO 639-1 code, ISO 639-3 code or English name
+fn lookup(language: &str) -> Option<&'static SupportedLanguage> {
+    SUPPORTED_LANGUAGES.iter().find(|supported| {
+        language.eq_ignore_ascii_case(supported.code)
+            || language.eq_ignore_ascii_case(supported.lang.code())
+            || language.eq_ignore_ascii_case(supported.name)
+    })
+}
+
+/// Use the requested language when given, otherwise detect it from the text
+pub fn resolve(requested: Option<&str>, text: &str) -> TextLanguage {
+    match requested
+        .map(str::trim)
+        .filter(|language| !language.is_empty())
+    {
+        Some(language) => match lookup(language) {
+            Some(supported) => TextLanguage::from_supported(supported, LanguageSource::Requested),
+            None => TextLanguage {
+                code: language.to_lowercase(),
+                name: language.to_string(),
+                source: LanguageSource::Requested,
+                confidence: None,
+                note: Some(format!(
+                    "'{}' is not a supported language; analysis quality may vary",
+                    language
+                )),
+            },
+        },
+        None => detect(text),
+    }
+}
+
+fn detect(text: &str) -> TextLanguage {
+    let Some(info) = whatlang::detect(text) else {
+        return TextLanguage::default_with_note(
+            None,
+            "Language could not be detected; assuming English".to_string(),
+        );
+    };
+
+    let confidence = info.confidence();
+    let detected = info.lang();
+
+    if confidence < MIN_DETECTION_CONFIDENCE {
+        return TextLanguage::default_with_note(
+            Some(confidence),
+            format!(
+                "Detected {} with low confidence ({:.2}); assuming English",
+                detected.eng_name(),
+                confidence
+            ),
+        );
+    }
+
+    match SUPPORTED_LANGUAGES
+        .iter()
+        .find(|supported| supported.lang == detected)
+    {
+        Some(supported) => TextLanguage {
+            confidence: Some(confidence),
+            ..TextLanguage::from_supported(supported, LanguageSource::Detected)
+        },
+        None => TextLanguage::default_with_note(
+            Some(confidence),
+            format!(
+                "Detected {}, which is not supported; assuming English",
+                detected.eng_name()
+            ),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requested_language_matches_code_or_name() {
+        for requested in ["es", "spa", "Spanish", " spanish "] {
+            let language = resolve(Some(requested), "This text is in English.");
+            assert_eq!(language.code, "es");
+            assert_eq!(language.source, LanguageSource::Requested);
+            assert!(language.note.is_none());
+        }
+
+        let unknown = resolve(Some("Klingon"), "Qapla'");
+        assert_eq!(unknown.name, "Klingon");
+        assert!(unknown.note.is_some());
+    }
+
+    #[test]
+    fn test_detects_supported_language() {
+        let language = resolve(
+            None,
+            "El equipo de desarrollo terminó la nueva versión del servicio y los usuarios \
+             están muy contentos con las mejoras de rendimiento.",
+        );
+        assert_eq!(language.code, "es");
+        assert_eq!(language.source, LanguageSource::Detected);
+        assert!(language.confidence.unwrap() >= MIN_DETECTION_CONFIDENCE);
+
+        let blank = resolve(
+            Some("  "),
+            "Rust is a fast and reliable language for building services that people depend on.",
+        );
+        assert_eq!(blank.code, "en");
+        assert_eq!(blank.source, LanguageSource::Detected);
+    }
+
+    #[test]
+    fn test_unsupported_or_unclear_text_falls_back_to_english() {
+        let russian = resolve(
+            None,
+            "Команда разработчиков выпустила новую версию сервиса, и пользователи довольны.",
+        );
+        assert_eq!(russian.code, "en");
+        assert_eq!(russian.source, LanguageSource::Default);
+        assert!(russian.note.unwrap().contains("Russian"));
+
+        for unclear in ["123 456", "Great work"] {
+            let language = resolve(None, unclear);
+            assert_eq!(language.code, "en");
+            assert_eq!(language.source, LanguageSource::Default);
+            assert!(language.note.is_some());
+        }
+    }
+}
@@ -2,40 +2,115 @@
 //!
 //! Advanced text analysis and processing service using Google Gemini for intelligent text operations.
 
+use ai_core_shared::moderation::{
+    self, ContentModerator, HttpContentModerator, KeywordModerator, ModerationOutcome,
+    ModerationPolicy,
+};
+use ai_core_shared::rate_limit::{
+    per_second_quota,
+    response::{apply_headers, client_key, too_many_requests},
+    SlidingWindowLimiter,
+};
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::{IntoResponse, Json},
+    extract::{ConnectInfo, Path, Request, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Json, Response},
     routing::{get, post},
     Router,
 };
 use chrono::{DateTime, Utc};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
 use tokio::net::TcpListener;
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 use tracing::{error, info, warn};
 
 use uuid::Uuid;
 
+mod cache;
+mod fallback;
+mod language;
+
+use cache::{AnalysisCache, CacheStats, CachedAnalysis};
+use language::TextLanguage;
+
+/// Reported as `ai_model` for results produced by Gemini
+const GEMINI_MODEL: &str = "gemini-1.5-flash";
+
+/// Gemini API endpoint the client calls by default
+const GEMINI_API_BASE_URL: &str = "https://generativelanguage.googleapis.com";
+
 #[derive(Clone)]
 pub struct AppState {
     pub service_name: String,
     pub gemini_client: GeminiClient,
+    pub rate_limit: RateLimitSettings,
+    pub rate_limiter: Arc<SlidingWindowLimiter>,
+    pub content_moderator: Arc<dyn ContentModerator>,
+    pub moderation_policy: ModerationPolicy,
+    /// Moderate every input unless the request opts out
+    pub moderate_inputs_by_default: bool,
+    pub analysis_cache: AnalysisCache,
+}
+
+/// Per-client request quota, configured from the environment
+#[derive(Debug, Clone)]
+pub struct RateLimitSettings {
+    pub requests_per_second: u32,
+    pub burst_size: u32,
+    /// Proxies whose `X-Forwarded-For` identifies the client
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+impl RateLimitSettings {
+    pub fn from_env() -> Self {
+        let read = |name: &str, default: u32| {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        };
+
+        Self {
+            requests_per_second: read("TEXT_PROCESSING_RATE_LIMIT_RPS", 10),
+            burst_size: read("TEXT_PROCESSING_RATE_LIMIT_BURST", 20),
+            trusted_proxies: env::var("TEXT_PROCESSING_TRUSTED_PROXIES")
+                .map(|proxies| {
+                    proxies
+                        .split(',')
+                        .filter_map(|proxy| proxy.trim().parse().ok())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
 }
 
 #[derive(Clone)]
 pub struct GeminiClient {
     pub api_key: String,
     pub client: reqwest::Client,
+    pub base_url: String,
 }
 
+/// Individual analyses, each backed by its own Gemini prompt
+pub const ANALYSIS_TYPES: [&str; 5] =
+    ["keywords", "sentiment", "readability", "grammar", "summary"];
+
+/// Analysis type that runs every entry of `ANALYSIS_TYPES` in one request
+pub const FULL_ANALYSIS: &str = "full";
+
 // Request/Response types
 #[derive(Debug, Deserialize)]
 pub struct TextAnalysisRequest {
     pub text: String,
-    pub analysis_type: String, // "keywords", "sentiment", "readability", "grammar", "summary"
+    pub analysis_type: String, // "keywords", "sentiment", "readability", "grammar", "summary", "full"
+    /// Detected from the text when omitted
     pub language: Option<String>,
     pub options: Option<AnalysisOptions>,
 }
@@ -46,6 +121,7 @@ pub struct AnalysisOptions {
     pub summary_length: Option<String>, // "short", "medium", "long"
     pub sentiment_detail: Option<bool>,
     pub readability_metrics: Option<bool>,
+    pub moderate_input: Option<bool>,
 }
 
 #[derive(Debug, Serialize)]
@@ -53,10 +129,26 @@ pub struct TextAnalysisResponse {
     pub id: Uuid,
     pub analysis_type: String,
     pub original_text_stats: TextStats,
+    pub language: TextLanguage,
     pub results: AnalysisResults,
     pub processing_time_ms: u64,
     pub ai_model: String,
+    /// Whether each analysis that ran came from Gemini or the local fallback
+    pub analysis_sources: BTreeMap<String, AnalysisSource>,
     pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub moderation: Option<ModerationOutcome>,
+    /// Served from the analysis cache instead of calling Gemini
+    pub cache_hit: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cached_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalysisSource {
+    Ai,
+    Fallback,
 }
 
 #[derive(Debug, Serialize)]
@@ -68,7 +160,7 @@ pub struct TextStats {
     pub avg_words_per_sentence: f32,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct AnalysisResults {
     pub keywords: Option<KeywordAnalysis>,
     pub sentiment: Option<SentimentAnalysis>,
@@ -128,6 +220,10 @@ pub struct ReadabilityAnalysis {
     pub avg_sentence_length: f32,
     pub difficult_words_percentage: f32,
     pub suggestions: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flesch_kincaid_grade: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flesch_reading_ease: Option<f32>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -147,7 +243,7 @@ pub struct GrammarIssue {
     pub suggestion: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct SummaryAnalysis {
     pub summary: String,
     pub key_points: Vec<String>,
@@ -164,6 +260,7 @@ pub struct HealthStatus {
     pub timestamp: DateTime<Utc>,
     pub gemini_available: bool,
     pub supported_languages: Vec<String>,
+    pub cache: CacheStats,
 }
 
 // Gemini API types
@@ -222,13 +319,17 @@ impl GeminiClient {
             .timeout(std::time::Duration::from_secs(30))
             .build()?;
 
-        Ok(Self { api_key, client })
+        Ok(Self {
+            api_key,
+            client,
+            base_url: GEMINI_API_BASE_URL.to_string(),
+        })
     }
 
     pub async fn analyze_text(&self, prompt: &str) -> Result<String, Box<dyn std::error::Error>> {
         let url = format!(
-            "https://generativelanguage.googleapis.com/v1beta/models/gemini-1.5-flash:generateContent?key={}",
-            self.api_key
+            "{}/v1beta/models/{}:generateContent?key={}",
+            self.base_url, GEMINI_MODEL, self.api_key
         );
 
         let request_body = GeminiRequest {
@@ -313,6 +414,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             GeminiClient {
                 api_key: "fallback".to_string(),
                 client: reqwest::Client::new(),
+                base_url: GEMINI_API_BASE_URL.to_string(),
             }
         }
     };
@@ -320,6 +422,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let state = AppState {
         service_name: "text-processing-mcp".to_string(),
         gemini_client,
+        rate_limit: RateLimitSettings::from_env(),
+        rate_limiter: Arc::new(SlidingWindowLimiter::new()),
+        content_moderator: create_content_moderator(),
+        moderation_policy: ModerationPolicy::default(),
+        moderate_inputs_by_default: env::var("TEXT_PROCESSING_MODERATE_INPUTS")
+            .map(|v| v == "true")
+            .unwrap_or(false),
+        analysis_cache: AnalysisCache::from_env().await,
     };
 
     let app = create_router(state);
@@ -327,21 +437,74 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let listener = TcpListener::bind("0.0.0.0:8805").await?;
     info!("Text Processing MCP Server listening on http://0.0.0.0:8805");
 
-    axum::serve(listener, app).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
     Ok(())
 }
 
+/// Use the external classifier when configured, otherwise the local keyword moderator
+fn create_content_moderator() -> Arc<dyn ContentModerator> {
+    match env::var("TEXT_PROCESSING_MODERATION_URL") {
+        Ok(endpoint) => {
+            info!("Using external content moderator at {}", endpoint);
+            Arc::new(HttpContentModerator::new(
+                endpoint,
+                env::var("TEXT_PROCESSING_MODERATION_API_KEY").ok(),
+            ))
+        }
+        Err(_) => Arc::new(KeywordModerator::default()),
+    }
+}
+
 fn create_router(state: AppState) -> Router {
     Router::new()
         .route("/health", get(health_check))
         .route("/v1/analyze", post(analyze_text))
         .route("/v1/analysis/:analysis_id", get(get_analysis))
         .route("/v1/capabilities", get(get_capabilities))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit_middleware,
+        ))
         .layer(TraceLayer::new_for_http())
         .layer(CorsLayer::permissive())
         .with_state(state)
 }
 
+async fn rate_limit_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.uri().path() == "/health" {
+        return next.run(request).await;
+    }
+
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let client = client_key(request.headers(), peer, &state.rate_limit.trusted_proxies);
+
+    let (limit, window) = per_second_quota(
+        state.rate_limit.requests_per_second,
+        state.rate_limit.burst_size,
+    );
+    let decision = state.rate_limiter.check(&client, limit, window);
+
+    if !decision.allowed {
+        warn!("Rate limit exceeded for client {}", client);
+        return too_many_requests(&decision, "Too many text processing requests");
+    }
+
+    let mut response = next.run(request).await;
+    apply_headers(response.headers_mut(), &decision);
+    response
+}
+
 async fn health_check(State(state): State<AppState>) -> impl IntoResponse {
     let gemini_available =
         state.gemini_client.api_key != "fallback" && env::var("GEMINI_API_KEY").is_ok();
@@ -351,21 +514,29 @@ async fn health_check(State(state): State<AppState>) -> impl IntoResponse {
         service: state.service_name,
         timestamp: Utc::now(),
         gemini_available,
-        supported_languages: vec![
-            "English".to_string(),
-            "Spanish".to_string(),
-            "French".to_string(),
-            "German".to_string(),
-            "Italian".to_string(),
-            "Portuguese".to_string(),
-        ],
+        supported_languages: language::supported_language_names(),
+        cache: state.analysis_cache.stats(),
     })
 }
 
+/// Whether the request asked to skip cached results with `Cache-Control: no-cache`
+fn bypasses_cache(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::CACHE_CONTROL)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|directive| {
+            let directive = directive.trim();
+            directive.eq_ignore_ascii_case("no-cache") || directive.eq_ignore_ascii_case("no-store")
+        })
+}
+
 async fn analyze_text(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(request): Json<TextAnalysisRequest>,
-) -> Result<impl IntoResponse, StatusCode> {
+) -> Result<Response, StatusCode> {
     let start_time = std::time::Instant::now();
 
     info!(
@@ -374,18 +545,102 @@ async fn analyze_text(
         request.text.len()
     );
 
+    let moderate_input = request
+        .options
+        .as_ref()
+        .and_then(|o| o.moderate_input)
+        .unwrap_or(state.moderate_inputs_by_default);
+
+    let moderation = if moderate_input {
+        let outcome = moderation::moderate(
+            state.content_moderator.as_ref(),
+            &state.moderation_policy,
+            &request.text,
+        )
+        .await
+        .map_err(|e| {
+            error!(
+                "Content moderation via {} failed: {}",
+                state.content_moderator.name(),
+                e
+            );
+            StatusCode::SERVICE_UNAVAILABLE
+        })?;
+
+        if outcome.is_blocked() {
+            warn!(
+                "Rejected analysis input: {}",
+                outcome.reason.as_deref().unwrap_or_default()
+            );
+            return Ok((
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(serde_json::json!({
+                    "status": "moderation_blocked",
+                    "error": outcome.reason,
+                    "moderation": outcome,
+                })),
+            )
+                .into_response());
+        }
+
+        Some(outcome)
+    } else {
+        None
+    };
+
     // Calculate basic text statistics
     let text_stats = calculate_text_stats(&request.text);
 
-    // Perform AI-powered analysis
-    let analysis_results = match perform_analysis(&state.gemini_client, &request).await {
-        Ok(results) => {
-            info!("Text analysis completed successfully using Gemini API");
-            results
+    let language = language::resolve(request.language.as_deref(), &request.text);
+    match &language.note {
+        Some(note) => info!("Analyzing text as {}: {}", language.name, note),
+        None => info!(
+            "Analyzing text as {} ({:?})",
+            language.name, language.source
+        ),
+    }
+
+    let cache_key = AnalysisCache::cache_key(&request);
+    let bypass_cache = bypasses_cache(&headers);
+    let cached = if bypass_cache {
+        None
+    } else {
+        state.analysis_cache.get(&cache_key).await
+    };
+    let cache_hit = cached.is_some();
+
+    let (analysis_results, ai_model, analysis_sources, cached_at) = match cached {
+        // Only results produced entirely by Gemini are cached
+        Some(cached) => {
+            let sources = requested_analysis_types(&request.analysis_type)
+                .into_iter()
+                .map(|analysis_type| (analysis_type.to_string(), AnalysisSource::Ai))
+                .collect();
+            (
+                cached.results,
+                cached.ai_model,
+                sources,
+                Some(cached.cached_at),
+            )
         }
-        Err(e) => {
-            warn!("Gemini API failed ({}), using fallback analysis", e);
-            perform_fallback_analysis(&request)
+        None => {
+            let (results, sources) =
+                run_requested_analyses(&state.gemini_client, &request, &language.name, &text_stats)
+                    .await;
+            let ai_model = model_for_sources(&sources);
+
+            if sources.values().all(|source| *source == AnalysisSource::Ai) {
+                info!("Text analysis completed successfully using Gemini API");
+                let cached = CachedAnalysis {
+                    results,
+                    ai_model,
+                    cached_at: Utc::now(),
+                };
+                state.analysis_cache.put(&cache_key, &cached).await;
+                (cached.results, cached.ai_model, sources, None)
+            } else {
+                (results, ai_model, sources, None)
+            }
         }
     };
 
@@ -395,20 +650,133 @@ async fn analyze_text(
         id: Uuid::new_v4(),
         analysis_type: request.analysis_type,
         original_text_stats: text_stats,
+        language,
         results: analysis_results,
         processing_time_ms: processing_time,
-        ai_model: "gemini-1.5-flash".to_string(),
+        ai_model,
+        analysis_sources,
         created_at: Utc::now(),
+        moderation,
+        cache_hit,
+        cached_at,
     };
 
-    info!("Text analysis completed in {}ms", processing_time);
+    info!(
+        "Text analysis completed in {}ms{}",
+        processing_time,
+        if cache_hit { " (cache hit)" } else { "" }
+    );
+
+    let cache_status = match (cache_hit, bypass_cache) {
+        (true, _) => "HIT",
+        (false, true) => "BYPASS",
+        (false, false) => "MISS",
+    };
+    let mut response = Json(response).into_response();
+    response
+        .headers_mut()
+        .insert("x-cache", HeaderValue::from_static(cache_status));
+    Ok(response)
+}
 
-    Ok(Json(response))
+/// The individual analyses an analysis type expands to
+fn requested_analysis_types(analysis_type: &str) -> Vec<&str> {
+    if analysis_type == FULL_ANALYSIS {
+        ANALYSIS_TYPES.to_vec()
+    } else {
+        vec![analysis_type]
+    }
+}
+
+/// Model reported for a response, combining both when only some analyses fell back
+fn model_for_sources(sources: &BTreeMap<String, AnalysisSource>) -> String {
+    let used_ai = sources.values().any(|s| *s == AnalysisSource::Ai);
+    let used_fallback = sources.values().any(|s| *s == AnalysisSource::Fallback);
+
+    match (used_ai, used_fallback) {
+        (true, true) => format!("{}+{}", GEMINI_MODEL, fallback::FALLBACK_MODEL),
+        (true, false) => GEMINI_MODEL.to_string(),
+        (false, _) => fallback::FALLBACK_MODEL.to_string(),
+    }
+}
+
+/// Run the requested analysis, or every analysis for `full`. Gemini calls for a
+/// full analysis run concurrently and each one falls back on its own.
+async fn run_requested_analyses(
+    gemini_client: &GeminiClient,
+    request: &TextAnalysisRequest,
+    language: &str,
+    stats: &TextStats,
+) -> (AnalysisResults, BTreeMap<String, AnalysisSource>) {
+    if request.analysis_type != FULL_ANALYSIS {
+        let (results, source) = run_analysis(
+            gemini_client,
+            request,
+            &request.analysis_type,
+            language,
+            stats,
+        )
+        .await;
+        return (
+            results,
+            BTreeMap::from([(request.analysis_type.clone(), source)]),
+        );
+    }
+
+    let (keywords, sentiment, readability, grammar, summary) = tokio::join!(
+        run_analysis(gemini_client, request, "keywords", language, stats),
+        run_analysis(gemini_client, request, "sentiment", language, stats),
+        run_analysis(gemini_client, request, "readability", language, stats),
+        run_analysis(gemini_client, request, "grammar", language, stats),
+        run_analysis(gemini_client, request, "summary", language, stats),
+    );
+
+    let sources = BTreeMap::from([
+        ("keywords".to_string(), keywords.1),
+        ("sentiment".to_string(), sentiment.1),
+        ("readability".to_string(), readability.1),
+        ("grammar".to_string(), grammar.1),
+        ("summary".to_string(), summary.1),
+    ]);
+    let results = AnalysisResults {
+        keywords: keywords.0.keywords,
+        sentiment: sentiment.0.sentiment,
+        readability: readability.0.readability,
+        grammar: grammar.0.grammar,
+        summary: summary.0.summary,
+    };
+
+    (results, sources)
+}
+
+/// Run one analysis with Gemini, using local analysis if the call fails
+async fn run_analysis(
+    gemini_client: &GeminiClient,
+    request: &TextAnalysisRequest,
+    analysis_type: &str,
+    language: &str,
+    stats: &TextStats,
+) -> (AnalysisResults, AnalysisSource) {
+    match perform_analysis(gemini_client, request, analysis_type, language).await {
+        Ok(results) => (results, AnalysisSource::Ai),
+        Err(e) => {
+            warn!(
+                "Gemini API failed for {} analysis ({}), using fallback analysis",
+                analysis_type, e
+            );
+            (
+                perform_fallback_analysis(request, analysis_type, stats),
+                AnalysisSource::Fallback,
+            )
+        }
+    }
 }
 
 async fn perform_analysis(
     gemini_client: &GeminiClient,
     request: &TextAnalysisRequest,
+    analysis_type: &str,
+    language: &str,
 ) -> Result<AnalysisResults, Box<dyn std::error::Error>> {
     let mut results = AnalysisResults {
         keywords: None,
@@ -418,24 +786,32 @@ async fn perform_analysis(
         summary: None,
     };
 
-    match request.analysis_type.as_str() {
+    match analysis_type {
         "keywords" => {
-            results.keywords =
-                Some(analyze_keywords_ai(gemini_client, &request.text, &request.options).await?);
+            results.keywords = Some(
+                analyze_keywords_ai(gemini_client, &request.text, &request.options, language)
+                    .await?,
+            );
         }
         "sentiment" => {
-            results.sentiment =
-                Some(analyze_sentiment_ai(gemini_client, &request.text, &request.options).await?);
+            results.sentiment = Some(
+                analyze_sentiment_ai(gemini_client, &request.text, &request.options, language)
+                    .await?,
+            );
         }
         "readability" => {
-            results.readability = Some(analyze_readability_ai(gemini_client, &request.text).await?);
+            results.readability =
+                Some(analyze_readability_ai(gemini_client, &request.text, language).await?);
         }
         "grammar" => {
-            results.grammar = Some(analyze_grammar_ai(gemini_client, &request.text).await?);
+            results.grammar =
+                Some(analyze_grammar_ai(gemini_client, &request.text, language).await?);
         }
         "summary" => {
-            results.summary =
-                Some(analyze_summary_ai(gemini_client, &request.text, &request.options).await?);
+            results.summary = Some(
+                analyze_summary_ai(gemini_client, &request.text, &request.options, language)
+                    .await?,
+            );
         }
         _ => {
             return Err("Unsupported analysis type".into());
@@ -449,6 +825,7 @@ async fn analyze_keywords_ai(
     gemini_client: &GeminiClient,
     text: &str,
     options: &Option<AnalysisOptions>,
+    language: &str,
 ) -> Result<KeywordAnalysis, Box<dyn std::error::Error>> {
     let max_keywords = options.as_ref().and_then(|o| o.max_keywords).unwrap_or(10);
 
@@ -456,6 +833,8 @@ async fn analyze_keywords_ai(
         "Analyze the following text and extract the {} most important keywords and key phrases.
         Also identify the main topics discussed.
 
+        {}
+
         Text: \"{}\"
 
         Please provide a detailed analysis in this exact JSON format:
@@ -469,25 +848,20 @@ async fn analyze_keywords_ai(
             \"topics\": [\"topic1\", \"topic2\"],
             \"confidence_score\": 0.85
         }}",
-        max_keywords, text
+        max_keywords,
+        language_instruction(language),
+        text
     );
 
     let response = gemini_client.analyze_text(&prompt).await?;
-
-    // Parse JSON response or create fallback
-    match serde_json::from_str::<KeywordAnalysis>(&response) {
-        Ok(analysis) => Ok(analysis),
-        Err(_) => {
-            // Fallback parsing if JSON format is not perfect
-            Ok(create_fallback_keyword_analysis(text, max_keywords))
-        }
-    }
+    parse_gemini_json(&response)
 }
 
 async fn analyze_sentiment_ai(
     gemini_client: &GeminiClient,
     text: &str,
     options: &Option<AnalysisOptions>,
+    language: &str,
 ) -> Result<SentimentAnalysis, Box<dyn std::error::Error>> {
     let detail_level = options
         .as_ref()
@@ -499,6 +873,8 @@ async fn analyze_sentiment_ai(
             "Perform detailed sentiment analysis on the following text. Analyze overall sentiment,
             emotional tones, and sentiment for each sentence.
 
+            {}
+
             Text: \"{}\"
 
             Provide analysis in this JSON format:
@@ -512,12 +888,15 @@ async fn analyze_sentiment_ai(
                     {{\"sentence\": \"...\", \"sentiment\": \"positive\", \"confidence\": 0.8}}
                 ]
             }}",
+            language_instruction(language),
             text
         )
     } else {
         format!(
             "Analyze the sentiment of this text. Determine if it's positive, negative, or neutral.
 
+            {}
+
             Text: \"{}\"
 
             Provide analysis in this JSON format:
@@ -528,25 +907,25 @@ async fn analyze_sentiment_ai(
                     {{\"emotion\": \"joy\", \"intensity\": 0.7}}
                 ]
             }}",
+            language_instruction(language),
             text
         )
     };
 
     let response = gemini_client.analyze_text(&prompt).await?;
-
-    match serde_json::from_str::<SentimentAnalysis>(&response) {
-        Ok(analysis) => Ok(analysis),
-        Err(_) => Ok(create_fallback_sentiment_analysis(text)),
-    }
+    parse_gemini_json(&response)
 }
 
 async fn analyze_readability_ai(
     gemini_client: &GeminiClient,
     text: &str,
+    language: &str,
 ) -> Result<ReadabilityAnalysis, Box<dyn std::error::Error>> {
     let prompt = format!(
         "Analyze the readability of this text. Determine reading level, complexity, and provide suggestions.
 
+        {}
+
         Text: \"{}\"
 
         Provide analysis in this JSON format:
@@ -557,24 +936,24 @@ async fn analyze_readability_ai(
             \"difficult_words_percentage\": 12.3,
             \"suggestions\": [\"suggestion1\", \"suggestion2\"]
         }}",
+        language_instruction(language),
         text
     );
 
     let response = gemini_client.analyze_text(&prompt).await?;
-
-    match serde_json::from_str::<ReadabilityAnalysis>(&response) {
-        Ok(analysis) => Ok(analysis),
-        Err(_) => Ok(create_fallback_readability_analysis(text)),
-    }
+    parse_gemini_json(&response)
 }
 
 async fn analyze_grammar_ai(
     gemini_client: &GeminiClient,
     text: &str,
+    language: &str,
 ) -> Result<GrammarAnalysis, Box<dyn std::error::Error>> {
     let prompt = format!(
         "Analyze the grammar of this text. Identify issues and provide corrections.
 
+        {}
+
         Text: \"{}\"
 
         Provide analysis in this JSON format:
@@ -592,21 +971,19 @@ async fn analyze_grammar_ai(
             \"suggestions\": [\"overall suggestion1\"],
             \"corrected_text\": \"corrected version of the text\"
         }}",
+        language_instruction(language),
         text
     );
 
     let response = gemini_client.analyze_text(&prompt).await?;
-
-    match serde_json::from_str::<GrammarAnalysis>(&response) {
-        Ok(analysis) => Ok(analysis),
-        Err(_) => Ok(create_fallback_grammar_analysis(text)),
-    }
+    parse_gemini_json(&response)
 }
 
 async fn analyze_summary_ai(
     gemini_client: &GeminiClient,
     text: &str,
     options: &Option<AnalysisOptions>,
+    language: &str,
 ) -> Result<SummaryAnalysis, Box<dyn std::error::Error>> {
     let summary_length = options
         .as_ref()
@@ -622,6 +999,8 @@ async fn analyze_summary_ai(
     let prompt = format!(
         "Summarize the following text in {} and identify key points.
 
+        {}
+
         Text: \"{}\"
 
         Provide analysis in this JSON format:
@@ -631,45 +1010,69 @@ async fn analyze_summary_ai(
             \"summary_type\": \"{}\",
             \"compression_ratio\": 0.25
         }}",
-        length_instruction, text, summary_length
+        length_instruction,
+        language_instruction(language),
+        text,
+        summary_length
     );
 
     let response = gemini_client.analyze_text(&prompt).await?;
 
-    match serde_json::from_str::<serde_json::Value>(&response) {
-        Ok(json) => {
-            let summary = json["summary"]
-                .as_str()
-                .unwrap_or("Summary not available")
-                .to_string();
-            let key_points: Vec<String> = json["key_points"]
-                .as_array()
-                .map(|arr| {
-                    arr.iter()
-                        .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                        .collect()
-                })
-                .unwrap_or_else(|| vec!["Key points not available".to_string()]);
+    let json: serde_json::Value = parse_gemini_json(&response)?;
+    let summary = json["summary"]
+        .as_str()
+        .ok_or("Gemini summary has no 'summary' field")?
+        .to_string();
+    let key_points: Vec<String> = json["key_points"]
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_else(|| vec!["Key points not available".to_string()]);
 
-            let original_length = text.chars().count();
-            let summary_len = summary.chars().count();
-            let compression_ratio = if original_length > 0 {
-                summary_len as f32 / original_length as f32
-            } else {
-                0.0
-            };
-
-            Ok(SummaryAnalysis {
-                summary,
-                key_points,
-                summary_type: summary_length.to_string(),
-                compression_ratio,
-                original_length,
-                summary_length: summary_len,
-            })
-        }
-        Err(_) => Ok(create_fallback_summary_analysis(text, summary_length)),
-    }
+    let original_length = text.chars().count();
+    let summary_len = summary.chars().count();
+    let compression_ratio = if original_length > 0 {
+        summary_len as f32 / original_length as f32
+    } else {
+        0.0
+    };
+
+    Ok(SummaryAnalysis {
+        summary,
+        key_points,
+        summary_type: summary_length.to_string(),
+        compression_ratio,
+        original_length,
+        summary_length: summary_len,
+    })
+}
+
+/// Prompt line telling Gemini which language the text is in
+fn language_instruction(language: &str) -> String {
+    format!(
+        "The text is written in {0}. Write descriptions, suggestions, summaries and corrections in {0}, \
+         but keep JSON field names and labels such as sentiment values in English.",
+        language
+    )
+}
+
+/// Parse Gemini's JSON answer, which may be wrapped in a markdown code fence.
+/// Unparseable answers are errors so the caller falls back to local analysis.
+fn parse_gemini_json<T: serde::de::DeserializeOwned>(
+    response: &str,
+) -> Result<T, Box<dyn std::error::Error>> {
+    let trimmed = response.trim();
+    let json = trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|rest| rest.strip_suffix("```"))
+        .unwrap_or(trimmed);
+
+    serde_json::from_str(json.trim())
+        .map_err(|e| format!("Gemini returned unparseable analysis: {}", e).into())
 }
 
 fn calculate_text_stats(text: &str) -> TextStats {
@@ -703,8 +1106,12 @@ fn calculate_text_stats(text: &str) -> TextStats {
     }
 }
 
-// Fallback implementations
-fn perform_fallback_analysis(request: &TextAnalysisRequest) -> AnalysisResults {
+/// Local analysis used when Gemini is unavailable or its answer cannot be parsed
+fn perform_fallback_analysis(
+    request: &TextAnalysisRequest,
+    analysis_type: &str,
+    stats: &TextStats,
+) -> AnalysisResults {
     let mut results = AnalysisResults {
         keywords: None,
         sentiment: None,
@@ -713,26 +1120,28 @@ fn perform_fallback_analysis(request: &TextAnalysisRequest) -> AnalysisResults {
         summary: None,
     };
 
-    match request.analysis_type.as_str() {
+    match analysis_type {
         "keywords" => {
             let max_keywords = request
                 .options
                 .as_ref()
                 .and_then(|o| o.max_keywords)
                 .unwrap_or(10);
-            results.keywords = Some(create_fallback_keyword_analysis(
-                &request.text,
-                max_keywords,
-            ));
+            results.keywords = Some(fallback::keywords(&request.text, max_keywords));
         }
         "sentiment" => {
-            results.sentiment = Some(create_fallback_sentiment_analysis(&request.text));
+            let per_sentence = request
+                .options
+                .as_ref()
+                .and_then(|o| o.sentiment_detail)
+                .unwrap_or(false);
+            results.sentiment = Some(fallback::sentiment(&request.text, per_sentence));
         }
         "readability" => {
-            results.readability = Some(create_fallback_readability_analysis(&request.text));
+            results.readability = Some(fallback::readability(&request.text, stats));
         }
         "grammar" => {
-            results.grammar = Some(create_fallback_grammar_analysis(&request.text));
+            results.grammar = Some(fallback::grammar(&request.text));
         }
         "summary" => {
             let summary_length = request
@@ -740,10 +1149,7 @@ fn perform_fallback_analysis(request: &TextAnalysisRequest) -> AnalysisResults {
                 .as_ref()
                 .and_then(|o| o.summary_length.as_deref())
                 .unwrap_or("medium");
-            results.summary = Some(create_fallback_summary_analysis(
-                &request.text,
-                summary_length,
-            ));
+            results.summary = Some(fallback::summary(&request.text, summary_length));
         }
         _ => {}
     }
@@ -751,164 +1157,6 @@ fn perform_fallback_analysis(request: &TextAnalysisRequest) -> AnalysisResults {
     results
 }
 
-fn create_fallback_keyword_analysis(text: &str, max_keywords: usize) -> KeywordAnalysis {
-    // Simple keyword extraction
-    let words: Vec<&str> = text.split_whitespace().collect();
-    let mut word_counts = std::collections::HashMap::new();
-
-    for word in words {
-        let clean_word = word
-            .to_lowercase()
-            .chars()
-            .filter(|c| c.is_alphabetic())
-            .collect::<String>();
-        if clean_word.len() > 3 {
-            *word_counts.entry(clean_word).or_insert(0) += 1;
-        }
-    }
-
-    let mut keywords: Vec<_> = word_counts
-        .into_iter()
-        .map(|(word, freq)| Keyword {
-            word,
-            frequency: freq,
-            relevance_score: (freq as f32).min(10.0) / 10.0,
-            category: Some("general".to_string()),
-        })
-        .collect();
-
-    keywords.sort_by(|a, b| b.frequency.cmp(&a.frequency));
-    keywords.truncate(max_keywords);
-
-    KeywordAnalysis {
-        keywords,
-        phrases: vec![], // Simplified - no phrase extraction in fallback
-        topics: vec!["general".to_string()],
-        confidence_score: 0.6,
-    }
-}
-
-fn create_fallback_sentiment_analysis(text: &str) -> SentimentAnalysis {
-    // Simple sentiment analysis based on word patterns
-    let positive_words = [
-        "good",
-        "great",
-        "excellent",
-        "amazing",
-        "wonderful",
-        "fantastic",
-    ];
-    let negative_words = ["bad", "terrible", "awful", "horrible", "disappointing"];
-
-    let text_lower = text.to_lowercase();
-    let positive_count = positive_words
-        .iter()
-        .filter(|&&word| text_lower.contains(word))
-        .count();
-    let negative_count = negative_words
-        .iter()
-        .filter(|&&word| text_lower.contains(word))
-        .count();
-
-    let (sentiment, confidence) = if positive_count > negative_count {
-        ("positive", 0.7)
-    } else if negative_count > positive_count {
-        ("negative", 0.7)
-    } else {
-        ("neutral", 0.6)
-    };
-
-    SentimentAnalysis {
-        overall_sentiment: sentiment.to_string(),
-        confidence_score: confidence,
-        emotional_tone: vec![EmotionalTone {
-            emotion: sentiment.to_string(),
-            intensity: confidence,
-        }],
-        sentiment_by_sentence: None,
-    }
-}
-
-fn create_fallback_readability_analysis(text: &str) -> ReadabilityAnalysis {
-    let words = text.split_whitespace().count();
-    let sentences = text.matches(&['.', '!', '?'][..]).count().max(1);
-    let avg_sentence_length = words as f32 / sentences as f32;
-
-    let (reading_level, complexity_score) = if avg_sentence_length < 10.0 {
-        ("Elementary", 0.3)
-    } else if avg_sentence_length < 15.0 {
-        ("Middle School", 0.5)
-    } else if avg_sentence_length < 20.0 {
-        ("High School", 0.7)
-    } else {
-        ("College", 0.9)
-    };
-
-    ReadabilityAnalysis {
-        reading_level: reading_level.to_string(),
-        complexity_score,
-        avg_sentence_length,
-        difficult_words_percentage: 15.0, // Placeholder
-        suggestions: vec![
-            "Consider shorter sentences for better readability".to_string(),
-            "Use simpler vocabulary where possible".to_string(),
-        ],
-    }
-}
-
-fn create_fallback_grammar_analysis(text: &str) -> GrammarAnalysis {
-    // Very basic grammar checks
-    let mut issues = Vec::new();
-
-    // Check for double spaces
-    if text.contains("  ") {
-        issues.push(GrammarIssue {
-            issue_type: "spacing".to_string(),
-            description: "Multiple consecutive spaces found".to_string(),
-            position: text.find("  ").unwrap_or(0),
-            severity: "low".to_string(),
-            suggestion: "Use single spaces between words".to_string(),
-        });
-    }
-
-    let grammar_score = if issues.is_empty() { 0.9 } else { 0.7 };
-
-    GrammarAnalysis {
-        grammar_score,
-        issues_found: issues,
-        suggestions: vec!["Consider using a comprehensive grammar checker".to_string()],
-        corrected_text: Some(text.replace("  ", " ")),
-    }
-}
-
-fn create_fallback_summary_analysis(text: &str, summary_type: &str) -> SummaryAnalysis {
-    let sentences: Vec<&str> = text.split(&['.', '!', '?'][..]).collect();
-    let first_sentence = sentences.first().unwrap_or(&"").trim();
-
-    let summary = if first_sentence.is_empty() {
-        "Text summary not available".to_string()
-    } else {
-        format!("{}.", first_sentence)
-    };
-
-    let original_length = text.chars().count();
-    let summary_length = summary.chars().count();
-    let compression_ratio = if original_length > 0 {
-        summary_length as f32 / original_length as f32
-    } else {
-        0.0
-    };
-
-    SummaryAnalysis {
-        summary,
-        key_points: vec!["Key points analysis requires AI processing".to_string()],
-        summary_type: summary_type.to_string(),
-        compression_ratio,
-        original_length,
-        summary_length,
-    }
-}
-
 async fn get_analysis(
     State(_state): State<AppState>,
     Path(analysis_id): Path<Uuid>,
@@ -924,6 +1172,7 @@ async fn get_analysis(
             paragraph_count: 1,
             avg_words_per_sentence: 10.0,
         },
+        language: language::resolve(Some("en"), ""),
         results: AnalysisResults {
             keywords: None,
             sentiment: Some(SentimentAnalysis {
@@ -940,8 +1189,12 @@ async fn get_analysis(
             summary: None,
         },
         processing_time_ms: 1500,
-        ai_model: "gemini-1.5-flash".to_string(),
+        ai_model: GEMINI_MODEL.to_string(),
+        analysis_sources: BTreeMap::from([("sentiment".to_string(), AnalysisSource::Ai)]),
         created_at: Utc::now(),
+        moderation: None,
+        cache_hit: false,
+        cached_at: None,
     })
 }
 
@@ -949,29 +1202,225 @@ async fn get_capabilities(State(_state): State<AppState>) -> impl IntoResponse {
     Json(serde_json::json!({
         "service": "text-processing-mcp",
         "version": env!("CARGO_PKG_VERSION"),
-        "ai_model": "gemini-1.5-flash",
+        "ai_model": GEMINI_MODEL,
+        "fallback_model": fallback::FALLBACK_MODEL,
         "supported_analysis_types": [
             "keywords",
             "sentiment",
             "readability",
             "grammar",
-            "summary"
-        ],
-        "supported_languages": [
-            "English",
-            "Spanish",
-            "French",
-            "German",
-            "Italian",
-            "Portuguese"
+            "summary",
+            "full"
         ],
+        "supported_languages": language::supported_language_names(),
         "max_text_length": 10000,
         "features": [
             "ai_powered_analysis",
             "fallback_processing",
             "detailed_statistics",
             "multi_language_support",
-            "real_time_processing"
+            "real_time_processing",
+            "input_moderation",
+            "result_caching",
+            "combined_full_analysis",
+            "language_detection"
         ]
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tokio::sync::Barrier;
+
+    /// Stand-in for the Gemini API answering each analysis prompt with valid JSON
+    #[derive(Clone, Default)]
+    struct GeminiStub {
+        /// Requests are held until this many are in flight at once
+        concurrent: Option<Arc<Barrier>>,
+        /// Analysis answered with a server error
+        failing: Option<&'static str>,
+    }
+
+    /// The analysis a prompt asks for, from its first line
+    fn prompt_analysis_type(prompt: &str) -> &'static str {
+        let first_line = prompt.lines().next().unwrap_or_default();
+        if first_line.contains("keywords") {
+            "keywords"
+        } else if first_line.contains("sentiment") {
+            "sentiment"
+        } else if first_line.contains("readability") {
+            "readability"
+        } else if first_line.contains("grammar") {
+            "grammar"
+        } else {
+            "summary"
+        }
+    }
+
+    fn stub_answer(analysis_type: &str) -> serde_json::Value {
+        match analysis_type {
+            "keywords" => serde_json::json!({
+                "keywords": [
+                    {"word": "launch", "frequency": 1, "relevance_score": 0.9, "category": "noun"}
+                ],
+                "phrases": [],
+                "topics": ["product launch"],
+                "confidence_score": 0.9,
+            }),
+            "sentiment" => serde_json::json!({
+                "overall_sentiment": "positive",
+                "confidence_score": 0.9,
+                "emotional_tone": [],
+            }),
+            "readability" => serde_json::json!({
+                "reading_level": "High School",
+                "complexity_score": 0.4,
+                "avg_sentence_length": 5.0,
+                "difficult_words_percentage": 10.0,
+                "suggestions": [],
+            }),
+            "grammar" => serde_json::json!({
+                "grammar_score": 1.0,
+                "issues_found": [],
+                "suggestions": [],
+                "corrected_text": null,
+            }),
+            _ => serde_json::json!({
+                "summary": "The launch went well.",
+                "key_points": ["Customers liked the dashboard"],
+            }),
+        }
+    }
+
+    async fn answer_prompt(
+        State(stub): State<GeminiStub>,
+        Json(body): Json<serde_json::Value>,
+    ) -> Response {
+        if let Some(barrier) = &stub.concurrent {
+            if tokio::time::timeout(Duration::from_secs(5), barrier.wait())
+                .await
+                .is_err()
+            {
+                return StatusCode::SERVICE_UNAVAILABLE.into_response();
+            }
+        }
+
+        let prompt = body["contents"][0]["parts"][0]["text"]
+            .as_str()
+            .unwrap_or_default();
+        let analysis_type = prompt_analysis_type(prompt);
+        if stub.failing == Some(analysis_type) {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+
+        Json(serde_json::json!({
+            "candidates": [{
+                "content": {"parts": [{"text": stub_answer(analysis_type).to_string()}]},
+                "finishReason": "STOP",
+            }],
+        }))
+        .into_response()
+    }
+
+    async fn gemini_client(stub: GeminiStub) -> GeminiClient {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let app = Router::new().fallback(answer_prompt).with_state(stub);
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        GeminiClient {
+            api_key: "test".to_string(),
+            client: reqwest::Client::new(),
+            base_url: format!("http://{}", address),
+        }
+    }
+
+    fn full_request() -> TextAnalysisRequest {
+        TextAnalysisRequest {
+            text: "The launch went well. Customers loved the new dashboard.".to_string(),
+            analysis_type: FULL_ANALYSIS.to_string(),
+            language: None,
+            options: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_full_analysis_runs_every_analysis_concurrently() {
+        // Each stubbed call waits for all five, so sequential calls would time out
+        let client = gemini_client(GeminiStub {
+            concurrent: Some(Arc::new(Barrier::new(ANALYSIS_TYPES.len()))),
+            ..Default::default()
+        })
+        .await;
+        let request = full_request();
+        let stats = calculate_text_stats(&request.text);
+
+        let (results, sources) = run_requested_analyses(&client, &request, "English", &stats).await;
+
+        assert_eq!(
+            sources.keys().map(String::as_str).collect::<Vec<_>>(),
+            ["grammar", "keywords", "readability", "sentiment", "summary"]
+        );
+        assert!(sources.values().all(|source| *source == AnalysisSource::Ai));
+        assert_eq!(model_for_sources(&sources), GEMINI_MODEL);
+        assert_eq!(results.keywords.unwrap().topics, ["product launch"]);
+        assert_eq!(results.sentiment.unwrap().overall_sentiment, "positive");
+        assert!(results.readability.is_some());
+        assert!(results.grammar.is_some());
+        assert_eq!(results.summary.unwrap().summary, "The launch went well.");
+    }
+
+    #[tokio::test]
+    async fn test_full_analysis_falls_back_per_analysis() {
+        let client = gemini_client(GeminiStub {
+            failing: Some("sentiment"),
+            ..Default::default()
+        })
+        .await;
+        let request = full_request();
+        let stats = calculate_text_stats(&request.text);
+
+        let (results, sources) = run_requested_analyses(&client, &request, "English", &stats).await;
+
+        assert_eq!(sources["sentiment"], AnalysisSource::Fallback);
+        assert!(sources
+            .iter()
+            .filter(|(analysis_type, _)| analysis_type.as_str() != "sentiment")
+            .all(|(_, source)| *source == AnalysisSource::Ai));
+        assert_eq!(
+            model_for_sources(&sources),
+            format!("{}+{}", GEMINI_MODEL, fallback::FALLBACK_MODEL)
+        );
+
+        // The failed analysis is still answered, locally
+        assert!(results.sentiment.is_some());
+        assert_eq!(results.keywords.unwrap().topics, ["product launch"]);
+        assert_eq!(results.summary.unwrap().summary, "The launch went well.");
+    }
+
+    #[tokio::test]
+    async fn test_single_analysis_falls_back_alone() {
+        let client = gemini_client(GeminiStub {
+            failing: Some("grammar"),
+            ..Default::default()
+        })
+        .await;
+        let request = TextAnalysisRequest {
+            analysis_type: "grammar".to_string(),
+            ..full_request()
+        };
+        let stats = calculate_text_stats(&request.text);
+
+        let (results, sources) = run_requested_analyses(&client, &request, "English", &stats).await;
+
+        assert_eq!(
+            sources,
+            BTreeMap::from([("grammar".to_string(), AnalysisSource::Fallback)])
+        );
+        assert!(results.grammar.is_some());
+        assert!(results.keywords.is_none());
+        assert_eq!(model_for_sources(&sources), fallback::FALLBACK_MODEL);
+    }
+}
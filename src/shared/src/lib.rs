@@ -1,6 +1,8 @@
 //! Shared types and utilities for the AI-CORE Platform
 
 pub mod config;
+pub mod moderation;
+pub mod rate_limit;
 pub mod types;
 
 // Export config types with different names to avoid conflicts
@@ -0,0 +1,499 @@
+//! Content moderation shared by generation and analysis services
+//!
+//! A [`ContentModerator`] only scores text per category. Whether a score flags
+//! or blocks the content is decided by a [`ModerationPolicy`], so the same
+//! classifier can back services with different tolerance levels.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use thiserror::Error;
+
+/// Categories of disallowed content
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModerationCategory {
+    Hate,
+    Sexual,
+    Violence,
+}
+
+impl ModerationCategory {
+    pub const ALL: [ModerationCategory; 3] = [
+        ModerationCategory::Hate,
+        ModerationCategory::Sexual,
+        ModerationCategory::Violence,
+    ];
+}
+
+impl fmt::Display for ModerationCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModerationCategory::Hate => write!(f, "hate"),
+            ModerationCategory::Sexual => write!(f, "sexual"),
+            ModerationCategory::Violence => write!(f, "violence"),
+        }
+    }
+}
+
+/// Action taken on moderated content, ordered from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModerationAction {
+    Allow,
+    Flag,
+    Block,
+}
+
+/// Score thresholds (0.0-1.0) for a single category
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CategoryThreshold {
+    /// Scores at or above this are flagged for review
+    pub flag_at: f32,
+    /// Scores at or above this block the content
+    pub block_at: f32,
+}
+
+impl CategoryThreshold {
+    fn action_for(&self, score: f32) -> ModerationAction {
+        if score >= self.block_at {
+            ModerationAction::Block
+        } else if score >= self.flag_at {
+            ModerationAction::Flag
+        } else {
+            ModerationAction::Allow
+        }
+    }
+}
+
+/// Which categories are checked and how strictly
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModerationPolicy {
+    /// Whether moderation runs at all
+    pub enabled: bool,
+    /// Thresholds per category; categories without an entry are not enforced
+    pub thresholds: HashMap<ModerationCategory, CategoryThreshold>,
+}
+
+/// Score at which the default policy flags a category
+pub const DEFAULT_FLAG_AT: f32 = 0.5;
+/// Score at which the default policy blocks a category
+pub const DEFAULT_BLOCK_AT: f32 = 0.8;
+
+impl Default for ModerationPolicy {
+    fn default() -> Self {
+        let thresholds = ModerationCategory::ALL
+            .into_iter()
+            .map(|category| {
+                (
+                    category,
+                    CategoryThreshold {
+                        flag_at: DEFAULT_FLAG_AT,
+                        block_at: DEFAULT_BLOCK_AT,
+                    },
+                )
+            })
+            .collect();
+
+        Self {
+            enabled: true,
+            thresholds,
+        }
+    }
+}
+
+impl ModerationPolicy {
+    /// Apply the thresholds to classifier scores
+    pub fn evaluate(&self, scores: &HashMap<ModerationCategory, f32>) -> ModerationOutcome {
+        let scores: BTreeMap<ModerationCategory, f32> = scores
+            .iter()
+            .map(|(k, v)| (*k, v.clamp(0.0, 1.0)))
+            .collect();
+
+        let mut flagged = Vec::new();
+        let mut blocked = Vec::new();
+
+        for (category, score) in &scores {
+            let Some(threshold) = self.thresholds.get(category) else {
+                continue;
+            };
+
+            match threshold.action_for(*score) {
+                ModerationAction::Block => blocked.push(*category),
+                ModerationAction::Flag => flagged.push(*category),
+                ModerationAction::Allow => {}
+            }
+        }
+
+        let action = if !blocked.is_empty() {
+            ModerationAction::Block
+        } else if !flagged.is_empty() {
+            ModerationAction::Flag
+        } else {
+            ModerationAction::Allow
+        };
+
+        let reason = match action {
+            ModerationAction::Allow => None,
+            ModerationAction::Flag => Some(describe("flagged", &flagged, &scores)),
+            ModerationAction::Block => Some(describe("blocked", &blocked, &scores)),
+        };
+
+        ModerationOutcome {
+            action,
+            scores,
+            flagged,
+            blocked,
+            reason,
+        }
+    }
+}
+
+fn describe(
+    verb: &str,
+    categories: &[ModerationCategory],
+    scores: &BTreeMap<ModerationCategory, f32>,
+) -> String {
+    let details: Vec<String> = categories
+        .iter()
+        .map(|c| format!("{} ({:.2})", c, scores.get(c).copied().unwrap_or_default()))
+        .collect();
+    format!("Content {} for {}", verb, details.join(", "))
+}
+
+/// Result of moderating a piece of content
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModerationOutcome {
+    /// Most severe action across all categories
+    pub action: ModerationAction,
+    /// Classifier scores per category
+    pub scores: BTreeMap<ModerationCategory, f32>,
+    /// Categories over their flag threshold but under the block threshold
+    pub flagged: Vec<ModerationCategory>,
+    /// Categories over their block threshold
+    pub blocked: Vec<ModerationCategory>,
+    /// Human-readable explanation when the content was flagged or blocked
+    pub reason: Option<String>,
+}
+
+impl ModerationOutcome {
+    /// Outcome for content that was not moderated
+    pub fn allowed() -> Self {
+        Self {
+            action: ModerationAction::Allow,
+            scores: BTreeMap::new(),
+            flagged: Vec::new(),
+            blocked: Vec::new(),
+            reason: None,
+        }
+    }
+
+    pub fn is_blocked(&self) -> bool {
+        self.action == ModerationAction::Block
+    }
+
+    pub fn is_flagged(&self) -> bool {
+        self.action == ModerationAction::Flag
+    }
+}
+
+/// Moderation errors
+#[derive(Error, Debug)]
+pub enum ModerationError {
+    #[error("Moderation service unavailable: {0}")]
+    Unavailable(String),
+
+    #[error("Invalid moderation response: {0}")]
+    InvalidResponse(String),
+}
+
+/// Classifies content into moderation category scores (0.0-1.0)
+#[async_trait]
+pub trait ContentModerator: Send + Sync {
+    /// Name reported in logs and metrics
+    fn name(&self) -> &str;
+
+    /// Score `content` for each category the moderator understands
+    async fn classify(
+        &self,
+        content: &str,
+    ) -> Result<HashMap<ModerationCategory, f32>, ModerationError>;
+}
+
+/// Classify `content` and apply `policy`; disabled policies allow everything
+pub async fn moderate(
+    moderator: &dyn ContentModerator,
+    policy: &ModerationPolicy,
+    content: &str,
+) -> Result<ModerationOutcome, ModerationError> {
+    if !policy.enabled {
+        return Ok(ModerationOutcome::allowed());
+    }
+
+    let scores = moderator.classify(content).await?;
+    Ok(policy.evaluate(&scores))
+}
+
+/// Highest score the default [`KeywordModerator`] gives a category
+///
+/// Kept under [`DEFAULT_BLOCK_AT`]: a bare term match says nothing about
+/// context ("kill the process", "bomb the exam"), so on its own it flags
+/// content for review but never blocks it.
+pub const KEYWORD_MAX_SCORE: f32 = 0.75;
+
+/// Local term-matching classifier that needs no external service
+///
+/// Each distinct term found adds `score_per_match` to its category, capped at
+/// `max_score`.
+#[derive(Debug, Clone)]
+pub struct KeywordModerator {
+    terms: HashMap<ModerationCategory, Vec<String>>,
+    score_per_match: f32,
+    max_score: f32,
+}
+
+impl KeywordModerator {
+    pub fn new(score_per_match: f32) -> Self {
+        Self {
+            terms: HashMap::new(),
+            score_per_match,
+            max_score: 1.0,
+        }
+    }
+
+    /// Cap every category's score at `max_score`
+    pub fn with_max_score(mut self, max_score: f32) -> Self {
+        self.max_score = max_score.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Replace the term list for a category
+    pub fn with_terms<I, S>(mut self, category: ModerationCategory, terms: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.terms.insert(
+            category,
+            terms.into_iter().map(|t| t.into().to_lowercase()).collect(),
+        );
+        self
+    }
+
+    fn score(&self, content: &str) -> HashMap<ModerationCategory, f32> {
+        let words: Vec<String> = content
+            .split(|c: char| !c.is_alphanumeric() && c != '\'')
+            .filter(|w| !w.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+
+        self.terms
+            .iter()
+            .map(|(category, terms)| {
+                let matches = terms.iter().filter(|t| words.contains(t)).count();
+                (
+                    *category,
+                    (matches as f32 * self.score_per_match).min(self.max_score),
+                )
+            })
+            .collect()
+    }
+}
+
+impl Default for KeywordModerator {
+    fn default() -> Self {
+        Self::new(0.45)
+            .with_max_score(KEYWORD_MAX_SCORE)
+            .with_terms(
+                ModerationCategory::Hate,
+                ["subhuman", "vermin", "exterminate", "genocide"],
+            )
+            .with_terms(
+                ModerationCategory::Sexual,
+                ["porn", "pornographic", "nsfw", "explicit", "nude", "xxx"],
+            )
+            .with_terms(
+                ModerationCategory::Violence,
+                ["kill", "murder", "massacre", "behead", "torture", "bomb"],
+            )
+    }
+}
+
+#[async_trait]
+impl ContentModerator for KeywordModerator {
+    fn name(&self) -> &str {
+        "keyword"
+    }
+
+    async fn classify(
+        &self,
+        content: &str,
+    ) -> Result<HashMap<ModerationCategory, f32>, ModerationError> {
+        Ok(self.score(content))
+    }
+}
+
+#[cfg(feature = "http")]
+pub use http::HttpContentModerator;
+
+#[cfg(feature = "http")]
+mod http {
+    use super::*;
+    use std::time::Duration;
+
+    /// How long a classification request may take before it fails
+    const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+    #[derive(Debug, Deserialize)]
+    struct ClassificationResponse {
+        scores: HashMap<ModerationCategory, f32>,
+    }
+
+    /// Moderator backed by an external classification API
+    ///
+    /// POSTs `{"input": "..."}` and expects `{"scores": {"hate": 0.1, ...}}`.
+    /// Requests fail as unavailable after 10 seconds unless another timeout is
+    /// set with [`with_timeout`](Self::with_timeout).
+    #[derive(Debug, Clone)]
+    pub struct HttpContentModerator {
+        client: reqwest::Client,
+        endpoint: String,
+        api_key: Option<String>,
+        timeout: Duration,
+    }
+
+    impl HttpContentModerator {
+        pub fn new(endpoint: impl Into<String>, api_key: Option<String>) -> Self {
+            Self {
+                client: reqwest::Client::new(),
+                endpoint: endpoint.into(),
+                api_key,
+                timeout: DEFAULT_TIMEOUT,
+            }
+        }
+
+        /// Fail classification requests that take longer than `timeout`
+        pub fn with_timeout(mut self, timeout: Duration) -> Self {
+            self.timeout = timeout;
+            self
+        }
+    }
+
+    #[async_trait]
+    impl ContentModerator for HttpContentModerator {
+        fn name(&self) -> &str {
+            "http"
+        }
+
+        async fn classify(
+            &self,
+            content: &str,
+        ) -> Result<HashMap<ModerationCategory, f32>, ModerationError> {
+            let mut request = self
+                .client
+                .post(&self.endpoint)
+                .timeout(self.timeout)
+                .json(&serde_json::json!({ "input": content }));
+            if let Some(key) = &self.api_key {
+                request = request.bearer_auth(key);
+            }
+
+            let response = request
+                .send()
+                .await
+                .map_err(|e| ModerationError::Unavailable(e.to_string()))?;
+
+            if !response.status().is_success() {
+                return Err(ModerationError::Unavailable(format!(
+                    "classifier returned {}",
+                    response.status()
+                )));
+            }
+
+            response
+                .json::<ClassificationResponse>()
+                .await
+                .map(|body| body.scores)
+                .map_err(|e| ModerationError::InvalidResponse(e.to_string()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_blocks_over_threshold_and_flags_below() {
+        let policy = ModerationPolicy::default();
+        let scores = HashMap::from([
+            (ModerationCategory::Hate, 0.1),
+            (ModerationCategory::Sexual, 0.6),
+            (ModerationCategory::Violence, 0.9),
+        ]);
+
+        let outcome = policy.evaluate(&scores);
+        assert!(outcome.is_blocked());
+        assert_eq!(outcome.blocked, vec![ModerationCategory::Violence]);
+        assert_eq!(outcome.flagged, vec![ModerationCategory::Sexual]);
+        assert_eq!(
+            outcome.reason.as_deref(),
+            Some("Content blocked for violence (0.90)")
+        );
+    }
+
+    #[test]
+    fn test_policy_ignores_categories_without_thresholds() {
+        let mut policy = ModerationPolicy::default();
+        policy.thresholds.remove(&ModerationCategory::Violence);
+
+        let outcome = policy.evaluate(&HashMap::from([(ModerationCategory::Violence, 1.0)]));
+        assert_eq!(outcome.action, ModerationAction::Allow);
+        assert!(outcome.reason.is_none());
+    }
+
+    #[test]
+    fn test_keyword_moderator_scores_distinct_matches() {
+        let moderator = KeywordModerator::default();
+
+        let scores = tokio_test::block_on(moderator.classify("How to bake bread")).unwrap();
+        assert!(scores.values().all(|s| *s == 0.0));
+
+        let scores =
+            tokio_test::block_on(moderator.classify("They will KILL and torture")).unwrap();
+        assert_eq!(scores[&ModerationCategory::Violence], 0.75);
+
+        let uncapped = KeywordModerator::new(0.45)
+            .with_terms(ModerationCategory::Violence, ["kill", "torture"]);
+        let scores =
+            tokio_test::block_on(uncapped.classify("They will KILL and torture, kill!")).unwrap();
+        assert_eq!(scores[&ModerationCategory::Violence], 0.9);
+    }
+
+    #[test]
+    fn test_keyword_moderator_flags_but_never_blocks_by_default() {
+        let outcome = tokio_test::block_on(moderate(
+            &KeywordModerator::default(),
+            &ModerationPolicy::default(),
+            "murder, massacre, torture and a bomb",
+        ))
+        .unwrap();
+
+        assert!(outcome.is_flagged());
+        assert_eq!(outcome.flagged, vec![ModerationCategory::Violence]);
+    }
+
+    #[test]
+    fn test_disabled_policy_skips_classification() {
+        let policy = ModerationPolicy {
+            enabled: false,
+            ..ModerationPolicy::default()
+        };
+
+        let outcome =
+            tokio_test::block_on(moderate(&KeywordModerator::default(), &policy, "murder"))
+                .unwrap();
+        assert_eq!(outcome, ModerationOutcome::allowed());
+    }
+}
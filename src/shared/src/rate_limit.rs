@@ -0,0 +1,527 @@
+//! Sliding-window rate limiting shared by every public API
+//!
+//! Services keep their own middleware, but limiter state, response headers and
+//! the 429 body all come from here so clients see identical behavior everywhere.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Header carrying the request limit for the window
+pub const HEADER_LIMIT: &str = "x-ratelimit-limit";
+/// Header carrying the requests left in the window
+pub const HEADER_REMAINING: &str = "x-ratelimit-remaining";
+/// Header carrying the Unix timestamp at which the current window ends
+pub const HEADER_RESET: &str = "x-ratelimit-reset";
+/// Header carrying the seconds to wait before retrying a rejected request
+pub const HEADER_RETRY_AFTER: &str = "retry-after";
+
+/// Error code used in every rate-limit response body
+pub const RATE_LIMIT_ERROR_CODE: &str = "RATE_LIMIT_EXCEEDED";
+
+/// How often a limiter drops the state of idle keys while handling checks
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Outcome of a rate-limit check, reflecting the limiter state after the check
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitDecision {
+    /// Whether the request may proceed
+    pub allowed: bool,
+    /// Maximum units allowed per window
+    pub limit: u32,
+    /// Units still available in the sliding window
+    pub remaining: u32,
+    /// Time until the current window ends
+    pub reset_after: Duration,
+    /// Time until a rejected request would be admitted
+    pub retry_after: Option<Duration>,
+}
+
+/// JSON body returned with every 429 response
+#[derive(Debug, Clone, Serialize)]
+pub struct RateLimitErrorBody {
+    pub error: RateLimitErrorDetail,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RateLimitErrorDetail {
+    pub code: &'static str,
+    pub message: String,
+    pub limit: u32,
+    pub remaining: u32,
+    pub reset_at: u64,
+    pub retry_after_seconds: u64,
+}
+
+impl RateLimitDecision {
+    /// Decision for a request that is not subject to limiting
+    pub fn unlimited(limit: u32, window: Duration) -> Self {
+        Self {
+            allowed: true,
+            limit,
+            remaining: limit,
+            reset_after: window,
+            retry_after: None,
+        }
+    }
+
+    /// Unix timestamp (seconds) at which the current window ends
+    pub fn reset_at(&self) -> u64 {
+        (SystemTime::now() + self.reset_after)
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    }
+
+    /// Whole seconds to wait before retrying, rounded up and never zero
+    pub fn retry_after_seconds(&self) -> Option<u64> {
+        self.retry_after.map(ceil_seconds)
+    }
+
+    /// Response headers describing this decision. `Retry-After` is only
+    /// included for rejected requests.
+    pub fn headers(&self) -> Vec<(&'static str, String)> {
+        let mut headers = vec![
+            (HEADER_LIMIT, self.limit.to_string()),
+            (HEADER_REMAINING, self.remaining.to_string()),
+            (HEADER_RESET, self.reset_at().to_string()),
+        ];
+
+        if let Some(retry_after) = self.retry_after_seconds() {
+            headers.push((HEADER_RETRY_AFTER, retry_after.to_string()));
+        }
+
+        headers
+    }
+
+    /// Body for a 429 response
+    pub fn error_body(&self, message: impl Into<String>) -> RateLimitErrorBody {
+        RateLimitErrorBody {
+            error: RateLimitErrorDetail {
+                code: RATE_LIMIT_ERROR_CODE,
+                message: message.into(),
+                limit: self.limit,
+                remaining: self.remaining,
+                reset_at: self.reset_at(),
+                retry_after_seconds: self
+                    .retry_after_seconds()
+                    .unwrap_or_else(|| ceil_seconds(self.reset_after)),
+            },
+        }
+    }
+}
+
+/// Translate a requests-per-second rate with a burst allowance into a window:
+/// up to `burst_size` requests per `burst_size / requests_per_second` seconds,
+/// so short bursts are absorbed while the sustained rate stays at
+/// `requests_per_second`.
+pub fn per_second_quota(requests_per_second: u32, burst_size: u32) -> (u32, Duration) {
+    let rate = requests_per_second.max(1);
+    let limit = burst_size.max(rate);
+    (limit, Duration::from_secs_f64(limit as f64 / rate as f64))
+}
+
+/// Address to rate-limit a request by.
+///
+/// `X-Forwarded-For` is only honored when the connecting peer is one of
+/// `trusted_proxies`, since any other client could set it to pick its own key.
+/// The chain is then walked from the nearest hop, and the first address that is
+/// not itself a trusted proxy is the client.
+pub fn client_address(
+    peer: Option<IpAddr>,
+    forwarded_for: Option<&str>,
+    trusted_proxies: &[IpAddr],
+) -> Option<IpAddr> {
+    let mut client = peer?;
+    if !trusted_proxies.contains(&client) {
+        return Some(client);
+    }
+
+    for hop in forwarded_for.unwrap_or_default().rsplit(',') {
+        match hop.trim().parse::<IpAddr>() {
+            Ok(address) => {
+                client = address;
+                if !trusted_proxies.contains(&address) {
+                    break;
+                }
+            }
+            // A malformed hop was written by someone we do not trust
+            Err(_) => break,
+        }
+    }
+
+    Some(client)
+}
+
+/// In-memory sliding-window counter limiter.
+///
+/// Each key keeps counts for the current and previous fixed windows; the
+/// previous window's count is weighted by how much of it still overlaps the
+/// sliding window ending now. Keys idle for more than two windows are dropped
+/// as checks come in, so the map only holds recently active clients.
+#[derive(Debug, Default)]
+pub struct SlidingWindowLimiter {
+    windows: Mutex<KeyedWindows>,
+}
+
+#[derive(Debug, Default)]
+struct KeyedWindows {
+    states: HashMap<String, WindowState>,
+    pruned_at: Option<Instant>,
+}
+
+impl KeyedWindows {
+    fn prune(&mut self, now: Instant) {
+        self.states.retain(|_, state| {
+            now.saturating_duration_since(state.current_start) < state.window * 2
+        });
+        self.pruned_at = Some(now);
+    }
+}
+
+#[derive(Debug, Clone)]
+struct WindowState {
+    window: Duration,
+    current_start: Instant,
+    current: u32,
+    previous: u32,
+}
+
+impl SlidingWindowLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check and, if allowed, record a single request for `key`
+    pub fn check(&self, key: &str, limit: u32, window: Duration) -> RateLimitDecision {
+        self.check_cost(key, 1, limit, window)
+    }
+
+    /// Check and, if allowed, record a request consuming `cost` units
+    pub fn check_cost(
+        &self,
+        key: &str,
+        cost: u32,
+        limit: u32,
+        window: Duration,
+    ) -> RateLimitDecision {
+        self.check_at(key, cost, limit, window, Instant::now())
+    }
+
+    /// Drop keys idle for more than two windows
+    pub fn prune(&self) {
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        windows.prune(Instant::now());
+    }
+
+    fn check_at(
+        &self,
+        key: &str,
+        cost: u32,
+        limit: u32,
+        window: Duration,
+        now: Instant,
+    ) -> RateLimitDecision {
+        let window = window.max(Duration::from_millis(1));
+        let mut windows = self.windows.lock().unwrap_or_else(|e| e.into_inner());
+        let recently_pruned = matches!(
+            windows.pruned_at,
+            Some(at) if now.saturating_duration_since(at) < PRUNE_INTERVAL
+        );
+        if !recently_pruned {
+            windows.prune(now);
+        }
+
+        let state = windows
+            .states
+            .entry(key.to_string())
+            .or_insert_with(|| WindowState {
+                window,
+                current_start: now,
+                current: 0,
+                previous: 0,
+            });
+
+        // A changed window length invalidates the recorded counts
+        if state.window != window {
+            *state = WindowState {
+                window,
+                current_start: now,
+                current: 0,
+                previous: 0,
+            };
+        }
+        state.advance(now);
+
+        let elapsed = now.duration_since(state.current_start);
+        let reset_after = window.saturating_sub(elapsed);
+        let estimated = state.estimated(elapsed);
+
+        if estimated + cost as f64 <= limit as f64 {
+            state.current = state.current.saturating_add(cost);
+            let used = state.estimated(elapsed);
+
+            RateLimitDecision {
+                allowed: true,
+                limit,
+                remaining: (limit as f64 - used).floor().max(0.0) as u32,
+                reset_after,
+                retry_after: None,
+            }
+        } else {
+            RateLimitDecision {
+                allowed: false,
+                limit,
+                remaining: (limit as f64 - estimated).floor().max(0.0) as u32,
+                reset_after,
+                retry_after: Some(state.time_until_admitted(elapsed, cost, limit)),
+            }
+        }
+    }
+}
+
+impl WindowState {
+    /// Roll fixed windows forward so `current_start` is the window containing `now`
+    fn advance(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.current_start);
+        if elapsed < self.window {
+            return;
+        }
+
+        let windows_passed = (elapsed.as_nanos() / self.window.as_nanos()) as u32;
+        self.previous = if windows_passed == 1 { self.current } else { 0 };
+        self.current = 0;
+        self.current_start += self.window * windows_passed;
+    }
+
+    fn overlap(&self, elapsed: Duration) -> f64 {
+        1.0 - (elapsed.as_secs_f64() / self.window.as_secs_f64()).min(1.0)
+    }
+
+    fn estimated(&self, elapsed: Duration) -> f64 {
+        self.previous as f64 * self.overlap(elapsed) + self.current as f64
+    }
+
+    /// How long until a request of `cost` units fits under `limit`, assuming
+    /// no other requests arrive in the meantime
+    fn time_until_admitted(&self, elapsed: Duration, cost: u32, limit: u32) -> Duration {
+        let window = self.window.as_secs_f64();
+        let elapsed = elapsed.as_secs_f64();
+        let budget = limit as f64 - cost as f64;
+
+        if budget < 0.0 {
+            // The request can never fit; ask the client to wait a full window
+            return self.window;
+        }
+
+        let wait = if self.current as f64 <= budget && self.previous > 0 {
+            // Wait for the previous window's weight to decay enough
+            let weight = (budget - self.current as f64) / self.previous as f64;
+            window * (1.0 - weight) - elapsed
+        } else if self.current > 0 {
+            // Wait for this window to roll over and its weight to decay
+            let weight = budget / self.current as f64;
+            (window - elapsed) + window * (1.0 - weight)
+        } else {
+            window - elapsed
+        };
+
+        // Round to whole milliseconds so float error never adds a second on ceil
+        Duration::from_millis((wait.max(0.0) * 1000.0).round() as u64)
+    }
+}
+
+/// Axum helpers so every service renders rate-limit responses identically
+#[cfg(feature = "axum")]
+pub mod response {
+    use super::{client_address, RateLimitDecision};
+    use axum::body::Body;
+    use axum::http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode};
+    use axum::response::Response;
+    use std::net::IpAddr;
+
+    /// Key identifying the client of a request received from `peer`, honoring
+    /// `X-Forwarded-For` only from `trusted_proxies`
+    pub fn client_key(
+        headers: &HeaderMap,
+        peer: Option<IpAddr>,
+        trusted_proxies: &[IpAddr],
+    ) -> String {
+        let forwarded_for = headers
+            .get("x-forwarded-for")
+            .and_then(|value| value.to_str().ok());
+
+        client_address(peer, forwarded_for, trusted_proxies)
+            .map(|address| address.to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+
+    /// Add the rate-limit headers for `decision` to `headers`
+    pub fn apply_headers(headers: &mut HeaderMap, decision: &RateLimitDecision) {
+        for (name, value) in decision.headers() {
+            if let Ok(value) = HeaderValue::from_str(&value) {
+                headers.insert(HeaderName::from_static(name), value);
+            }
+        }
+    }
+
+    /// 429 response carrying the rate-limit headers and the shared JSON body
+    pub fn too_many_requests(decision: &RateLimitDecision, message: impl Into<String>) -> Response {
+        let body = serde_json::to_vec(&decision.error_body(message)).unwrap_or_default();
+
+        let mut response = Response::new(Body::from(body));
+        *response.status_mut() = StatusCode::TOO_MANY_REQUESTS;
+        response.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        apply_headers(response.headers_mut(), decision);
+
+        response
+    }
+}
+
+fn ceil_seconds(duration: Duration) -> u64 {
+    let seconds = duration.as_secs() + u64::from(duration.subsec_nanos() > 0);
+    seconds.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_enforced_within_window() {
+        let limiter = SlidingWindowLimiter::new();
+        let window = Duration::from_secs(60);
+        let start = Instant::now();
+
+        for expected_remaining in (0..3).rev() {
+            let decision = limiter.check_at("client", 1, 3, window, start);
+            assert!(decision.allowed);
+            assert_eq!(decision.remaining, expected_remaining);
+        }
+
+        let rejected = limiter.check_at("client", 1, 3, window, start + Duration::from_secs(10));
+        assert!(!rejected.allowed);
+        assert_eq!(rejected.remaining, 0);
+        assert_eq!(rejected.reset_after, Duration::from_secs(50));
+
+        // The current window must roll over and a third of it slide out: 50s + 20s
+        assert_eq!(rejected.retry_after_seconds(), Some(70));
+
+        // Other keys are unaffected
+        assert!(limiter.check_at("other", 1, 3, window, start).allowed);
+    }
+
+    #[test]
+    fn test_previous_window_weight_decays() {
+        let limiter = SlidingWindowLimiter::new();
+        let window = Duration::from_secs(60);
+        let start = Instant::now();
+
+        for _ in 0..4 {
+            assert!(limiter.check_at("client", 1, 4, window, start).allowed);
+        }
+
+        // 15s into the next window 75% of the previous window still counts: 3 of 4 used
+        let decision = limiter.check_at("client", 1, 4, window, start + Duration::from_secs(75));
+        assert!(decision.allowed);
+        assert_eq!(decision.remaining, 0);
+
+        let rejected = limiter.check_at("client", 1, 4, window, start + Duration::from_secs(75));
+        assert!(!rejected.allowed);
+        // Needs the previous weight to fall to 50%: 30s into the window
+        assert_eq!(rejected.retry_after_seconds(), Some(15));
+    }
+
+    #[test]
+    fn test_idle_keys_are_pruned_while_checking() {
+        let limiter = SlidingWindowLimiter::new();
+        let window = Duration::from_secs(1);
+        let start = Instant::now();
+
+        for client in 0..100 {
+            limiter.check_at(&format!("client-{}", client), 1, 10, window, start);
+        }
+        assert_eq!(limiter.windows.lock().unwrap().states.len(), 100);
+
+        // The next check after the prune interval forgets the idle clients
+        limiter.check_at("active", 1, 10, window, start + PRUNE_INTERVAL);
+        let windows = limiter.windows.lock().unwrap();
+        assert_eq!(windows.states.len(), 1);
+        assert!(windows.states.contains_key("active"));
+    }
+
+    #[test]
+    fn test_forwarded_for_only_honored_from_trusted_proxies() {
+        let proxy: IpAddr = "10.0.0.1".parse().unwrap();
+        let client: IpAddr = "203.0.113.7".parse().unwrap();
+        let spoofer: IpAddr = "198.51.100.9".parse().unwrap();
+
+        // A direct client cannot pick its own key
+        assert_eq!(
+            client_address(Some(spoofer), Some("192.0.2.1"), &[proxy]),
+            Some(spoofer)
+        );
+        assert_eq!(
+            client_address(Some(spoofer), Some("192.0.2.1"), &[]),
+            Some(spoofer)
+        );
+
+        // Through the proxy, the nearest untrusted hop is the client, not a
+        // spoofed entry the client prepended
+        assert_eq!(
+            client_address(Some(proxy), Some("192.0.2.1, 203.0.113.7"), &[proxy]),
+            Some(client)
+        );
+        assert_eq!(
+            client_address(
+                Some(proxy),
+                Some("garbage, 203.0.113.7, 10.0.0.1"),
+                &[proxy]
+            ),
+            Some(client)
+        );
+
+        // A proxy forwarding nothing usable is treated as the client
+        assert_eq!(client_address(Some(proxy), None, &[proxy]), Some(proxy));
+        assert_eq!(client_address(None, Some("192.0.2.1"), &[proxy]), None);
+    }
+
+    #[test]
+    fn test_per_second_quota() {
+        assert_eq!(per_second_quota(100, 200), (200, Duration::from_secs(2)));
+        assert_eq!(per_second_quota(10, 5), (10, Duration::from_secs(1)));
+        assert_eq!(per_second_quota(0, 0), (1, Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_headers_and_error_body() {
+        let decision = RateLimitDecision {
+            allowed: false,
+            limit: 10,
+            remaining: 0,
+            reset_after: Duration::from_secs(30),
+            retry_after: Some(Duration::from_millis(1500)),
+        };
+
+        let headers = decision.headers();
+        assert!(headers.contains(&(HEADER_LIMIT, "10".to_string())));
+        assert!(headers.contains(&(HEADER_REMAINING, "0".to_string())));
+        assert!(headers.contains(&(HEADER_RETRY_AFTER, "2".to_string())));
+        assert!(headers.iter().any(|(name, _)| *name == HEADER_RESET));
+
+        let body = serde_json::to_value(decision.error_body("Too many requests")).unwrap();
+        assert_eq!(body["error"]["code"], RATE_LIMIT_ERROR_CODE);
+        assert_eq!(body["error"]["retry_after_seconds"], 2);
+
+        let allowed = RateLimitDecision::unlimited(10, Duration::from_secs(60));
+        assert!(allowed
+            .headers()
+            .iter()
+            .all(|(name, _)| *name != HEADER_RETRY_AFTER));
+    }
+}